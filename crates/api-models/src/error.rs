@@ -0,0 +1,16 @@
+//! API error response body.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Error response body.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ErrorResponse {
+    /// Error code.
+    pub code: String,
+    /// Error message.
+    pub message: String,
+    /// Additional details.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+}
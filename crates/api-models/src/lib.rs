@@ -0,0 +1,9 @@
+//! Request and response models of the REST API.
+//!
+//! Shared by the API server and its clients; this crate only depends on the
+//! domain types the models carry, so clients do not build the server.
+
+/// Error response body.
+pub mod error;
+/// API request/response models.
+pub mod models;
@@ -0,0 +1,1672 @@
+//! API request and response models.
+
+use clmm_lp_domain::alerts::{AlertLevel, AlertType, RuleCondition};
+use clmm_lp_domain::metrics::sharpe::{Benchmark, SharpeConfig};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+// ============================================================================
+// Position Models
+// ============================================================================
+
+/// Request to open a new position.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OpenPositionRequest {
+    /// Pool address.
+    pub pool_address: String,
+    /// Lower tick of the range.
+    pub tick_lower: i32,
+    /// Upper tick of the range.
+    pub tick_upper: i32,
+    /// Amount of token A to deposit (zero for ranges below the current price).
+    pub amount_a: u64,
+    /// Amount of token B to deposit (zero for ranges above the current price).
+    pub amount_b: u64,
+    /// Slippage tolerance in basis points.
+    #[serde(default = "default_slippage")]
+    pub slippage_tolerance_bps: u16,
+}
+
+fn default_slippage() -> u16 {
+    50
+}
+
+/// Request to rebalance a position.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RebalanceRequest {
+    /// New lower tick.
+    pub new_tick_lower: i32,
+    /// New upper tick.
+    pub new_tick_upper: i32,
+    /// Slippage tolerance in basis points.
+    #[serde(default = "default_slippage")]
+    pub slippage_tolerance_bps: u16,
+}
+
+/// Request to migrate a position to another pool of the same pair.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MigratePositionRequest {
+    /// Pool to move the position to.
+    pub target_pool: String,
+    /// Lower tick in the target pool.
+    pub new_tick_lower: i32,
+    /// Upper tick in the target pool.
+    pub new_tick_upper: i32,
+    /// Projected fee APR of the target pool (0.3 = 30%).
+    #[schema(value_type = String)]
+    pub target_fee_apr: Decimal,
+    /// Projected fee APR of the current pool; defaults to the position's
+    /// own fee APR.
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub current_fee_apr: Option<Decimal>,
+    /// SOL price in USD, to value transaction costs.
+    #[schema(value_type = String)]
+    pub sol_price_usd: Decimal,
+    /// Slippage tolerance in basis points.
+    #[serde(default = "default_slippage")]
+    pub slippage_tolerance_bps: u16,
+}
+
+/// Outcome of a migration request.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MigrationResponse {
+    /// Whether the migration was carried out (or simulated in dry-run mode).
+    pub migrated: bool,
+    /// Whether the projected fee gain pays for the migration.
+    pub profitable: bool,
+    /// Extra fees projected over the horizon in USD.
+    #[schema(value_type = String)]
+    pub projected_gain_usd: Decimal,
+    /// Estimated migration cost in USD.
+    #[schema(value_type = String)]
+    pub estimated_cost_usd: Decimal,
+    /// Minimum projected gain required in USD.
+    #[schema(value_type = String)]
+    pub min_required_gain_usd: Decimal,
+    /// New position address, if one was opened.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_position: Option<String>,
+    /// Human-readable outcome.
+    pub message: String,
+}
+
+/// Position response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PositionResponse {
+    /// Position address.
+    pub address: String,
+    /// Pool address.
+    pub pool_address: String,
+    /// Protocol the position belongs to.
+    pub protocol: PositionProtocol,
+    /// Owner address.
+    pub owner: String,
+    /// Lower tick.
+    pub tick_lower: i32,
+    /// Upper tick.
+    pub tick_upper: i32,
+    /// Liquidity amount.
+    pub liquidity: String,
+    /// Whether position is in range.
+    pub in_range: bool,
+    /// Current value in USD.
+    #[schema(value_type = String)]
+    pub value_usd: Decimal,
+    /// PnL details.
+    pub pnl: PnLResponse,
+    /// Health score details, if computed.
+    pub health: Option<PositionHealthResponse>,
+    /// Position status.
+    pub status: PositionStatus,
+    /// ID of the strategy assigned to the position, if any.
+    pub strategy_id: Option<String>,
+    /// Position tags.
+    pub tags: Vec<String>,
+    /// Whether the position is externally managed and only monitored.
+    pub read_only: bool,
+    /// Since when the position is skipped because its pool state is
+    /// unavailable, if it currently is.
+    #[schema(value_type = Option<String>)]
+    pub stale_since: Option<chrono::DateTime<chrono::Utc>>,
+    /// Created timestamp.
+    #[schema(value_type = Option<String>)]
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Query parameters for listing positions.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, IntoParams)]
+pub struct ListPositionsQuery {
+    /// Only return positions carrying this tag.
+    pub tag: Option<String>,
+    /// Only return positions of this protocol.
+    pub protocol: Option<PositionProtocol>,
+}
+
+/// Request to replace the tags on a position.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SetPositionTagsRequest {
+    /// Tags such as "core" or "experiment-A"; an empty list clears them.
+    pub tags: Vec<String>,
+}
+
+/// Tags of a position.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PositionTagsResponse {
+    /// Position address.
+    pub position_address: String,
+    /// Sorted, unique tags.
+    pub tags: Vec<String>,
+}
+
+/// Request to change whether a position is read-only.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SetPositionReadOnlyRequest {
+    /// True to only monitor the position; false to let the bot manage it.
+    pub read_only: bool,
+}
+
+/// Management mode of a position.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PositionReadOnlyResponse {
+    /// Position address.
+    pub position_address: String,
+    /// Whether the position is only monitored.
+    pub read_only: bool,
+}
+
+/// Request to assign a strategy to a position.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AssignStrategyRequest {
+    /// ID of the strategy whose rules the position follows.
+    pub strategy_id: String,
+    /// Decision parameter overrides, with percentages as fractions
+    /// (e.g. `{"il_rebalance_threshold": "0.08", "range_spec": "2sigma"}`).
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub overrides: serde_json::Value,
+}
+
+/// Strategy assignment of a position.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PositionStrategyResponse {
+    /// Position address.
+    pub position_address: String,
+    /// Assigned strategy ID, or null if the position uses the executor default.
+    pub strategy_id: Option<String>,
+    /// Decision parameter overrides.
+    #[schema(value_type = Object)]
+    pub overrides: serde_json::Value,
+}
+
+/// PnL response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PnLResponse {
+    /// Unrealized PnL in USD.
+    #[schema(value_type = String)]
+    pub unrealized_pnl_usd: Decimal,
+    /// Unrealized PnL percentage.
+    #[schema(value_type = String)]
+    pub unrealized_pnl_pct: Decimal,
+    /// Fees earned (token A).
+    pub fees_earned_a: u64,
+    /// Fees earned (token B).
+    pub fees_earned_b: u64,
+    /// Fees earned in USD.
+    #[schema(value_type = String)]
+    pub fees_earned_usd: Decimal,
+    /// Impermanent loss percentage.
+    #[schema(value_type = String)]
+    pub il_pct: Decimal,
+    /// Net PnL in USD.
+    #[schema(value_type = String)]
+    pub net_pnl_usd: Decimal,
+    /// Net PnL percentage.
+    #[schema(value_type = String)]
+    pub net_pnl_pct: Decimal,
+}
+
+/// Position health score response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PositionHealthResponse {
+    /// Composite score (0-100).
+    #[schema(value_type = String)]
+    pub score: Decimal,
+    /// Time-in-range component (0-100).
+    #[schema(value_type = String)]
+    pub time_in_range: Decimal,
+    /// Fee APR vs pool average component (0-100).
+    #[schema(value_type = String)]
+    pub fee_efficiency: Decimal,
+    /// Impermanent loss component (0-100).
+    #[schema(value_type = String)]
+    pub impermanent_loss: Decimal,
+    /// Distance to range edge component (0-100).
+    #[schema(value_type = String)]
+    pub range_position: Decimal,
+    /// Data freshness component (0-100).
+    #[schema(value_type = String)]
+    pub freshness: Decimal,
+    /// When the score was computed.
+    #[schema(value_type = String)]
+    pub computed_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// CLMM protocol of a position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PositionProtocol {
+    /// Orca Whirlpool.
+    OrcaWhirlpool,
+    /// Raydium CLMM.
+    RaydiumClmm,
+    /// Meteora DLMM.
+    MeteoraDlmm,
+}
+
+/// Position status.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PositionStatus {
+    /// Position is active.
+    Active,
+    /// Position is out of range.
+    OutOfRange,
+    /// Position is closed.
+    Closed,
+    /// Position is pending.
+    Pending,
+}
+
+/// List positions response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ListPositionsResponse {
+    /// List of positions.
+    pub positions: Vec<PositionResponse>,
+    /// Total count.
+    pub total: usize,
+}
+
+// ============================================================================
+// Strategy Models
+// ============================================================================
+
+/// Request to create a strategy.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateStrategyRequest {
+    /// Strategy name.
+    pub name: String,
+    /// Pool address.
+    pub pool_address: String,
+    /// Strategy type.
+    pub strategy_type: StrategyType,
+    /// Strategy parameters.
+    pub parameters: StrategyParameters,
+    /// Whether to auto-execute.
+    #[serde(default)]
+    pub auto_execute: bool,
+    /// Whether to run in dry-run mode.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Strategy type.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StrategyType {
+    /// Static range strategy.
+    StaticRange,
+    /// Periodic rebalancing.
+    Periodic,
+    /// Threshold-based rebalancing.
+    Threshold,
+    /// IL limit strategy.
+    IlLimit,
+}
+
+/// Strategy parameters.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StrategyParameters {
+    /// Tick range width.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tick_width: Option<i32>,
+    /// Rebalance threshold percentage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub rebalance_threshold_pct: Option<Decimal>,
+    /// Maximum IL percentage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub max_il_pct: Option<Decimal>,
+    /// Evaluation interval in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eval_interval_secs: Option<u64>,
+    /// Minimum rebalance interval in hours.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_rebalance_interval_hours: Option<u64>,
+    /// Health score (0-100) below which to rebalance.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub min_health_score: Option<Decimal>,
+    /// Range for new positions (e.g. "90:110", "5%", "2sigma", "500ticks").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range_spec: Option<String>,
+    /// Divergence from the oracle price, in percent, above which actions are skipped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub max_oracle_divergence_pct: Option<Decimal>,
+}
+
+/// Strategy response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StrategyResponse {
+    /// Strategy ID.
+    pub id: String,
+    /// Strategy name.
+    pub name: String,
+    /// Pool address.
+    pub pool_address: String,
+    /// Strategy type.
+    pub strategy_type: StrategyType,
+    /// Strategy parameters.
+    pub parameters: StrategyParameters,
+    /// Whether strategy is running.
+    pub running: bool,
+    /// Whether in dry-run mode.
+    pub dry_run: bool,
+    /// Created timestamp.
+    #[schema(value_type = String)]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Updated timestamp.
+    #[schema(value_type = String)]
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// List strategies response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ListStrategiesResponse {
+    /// List of strategies.
+    pub strategies: Vec<StrategyResponse>,
+    /// Total count.
+    pub total: usize,
+}
+
+/// Strategy performance response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StrategyPerformanceResponse {
+    /// Strategy ID.
+    pub strategy_id: String,
+    /// Total PnL in USD.
+    #[schema(value_type = String)]
+    pub total_pnl_usd: Decimal,
+    /// Total PnL percentage.
+    #[schema(value_type = String)]
+    pub total_pnl_pct: Decimal,
+    /// Total fees earned in USD.
+    #[schema(value_type = String)]
+    pub total_fees_usd: Decimal,
+    /// Total IL percentage.
+    #[schema(value_type = String)]
+    pub total_il_pct: Decimal,
+    /// Number of rebalances.
+    pub rebalance_count: u32,
+    /// Total transaction costs in lamports.
+    pub total_tx_costs_lamports: u64,
+    /// Win rate percentage.
+    #[schema(value_type = String)]
+    pub win_rate_pct: Decimal,
+}
+
+/// An applied runtime configuration change.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConfigChangeResponse {
+    /// When the change was applied.
+    #[schema(value_type = String)]
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// What changed, e.g. "alert rules".
+    pub section: String,
+    /// Where the change came from: "API" or "file <path>".
+    pub source: String,
+    /// Short description of the new values.
+    pub summary: String,
+}
+
+/// Runtime configuration change log.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConfigChangesResponse {
+    /// Changes, oldest first.
+    pub changes: Vec<ConfigChangeResponse>,
+    /// Total count.
+    pub total: usize,
+}
+
+// ============================================================================
+// Pool Models
+// ============================================================================
+
+/// Pool response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PoolResponse {
+    /// Pool address.
+    pub address: String,
+    /// Protocol name.
+    pub protocol: String,
+    /// Token A mint.
+    pub token_mint_a: String,
+    /// Token B mint.
+    pub token_mint_b: String,
+    /// Current tick.
+    pub current_tick: i32,
+    /// Tick spacing.
+    pub tick_spacing: i32,
+    /// Current price.
+    #[schema(value_type = String)]
+    pub price: Decimal,
+    /// Total liquidity.
+    pub liquidity: String,
+    /// Fee rate in basis points.
+    pub fee_rate_bps: u16,
+    /// 24h volume in USD.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub volume_24h_usd: Option<Decimal>,
+    /// TVL in USD.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub tvl_usd: Option<Decimal>,
+    /// APY estimate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub apy_estimate: Option<Decimal>,
+    /// Active reward emissions.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rewards: Vec<PoolRewardResponse>,
+}
+
+/// Pool reward emission.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PoolRewardResponse {
+    /// Reward slot index.
+    pub index: usize,
+    /// Reward token mint.
+    pub mint: String,
+    /// Raw reward tokens emitted per day.
+    #[schema(value_type = String)]
+    pub emissions_per_day: Decimal,
+    /// Reward APR, if the reward price and pool TVL are known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub reward_apr: Option<Decimal>,
+}
+
+/// List pools response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ListPoolsResponse {
+    /// List of pools.
+    pub pools: Vec<PoolResponse>,
+    /// Total count.
+    pub total: usize,
+}
+
+/// Pool state response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PoolStateResponse {
+    /// Pool address.
+    pub address: String,
+    /// Current tick.
+    pub current_tick: i32,
+    /// Sqrt price X64.
+    pub sqrt_price: String,
+    /// Current price.
+    #[schema(value_type = String)]
+    pub price: Decimal,
+    /// Total liquidity.
+    pub liquidity: String,
+    /// Fee growth global A.
+    pub fee_growth_global_a: String,
+    /// Fee growth global B.
+    pub fee_growth_global_b: String,
+    /// Timestamp.
+    #[schema(value_type = String)]
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+/// Token risk screening of a pool.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PoolRiskResponse {
+    /// Pool address.
+    pub pool_address: String,
+    /// Whether the executor would refuse to open positions in this pool.
+    pub blocked: bool,
+    /// Token A screening.
+    pub token_a: TokenRiskResponse,
+    /// Token B screening.
+    pub token_b: TokenRiskResponse,
+}
+
+/// Token risk screening of a mint.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TokenRiskResponse {
+    /// Token mint.
+    pub mint: String,
+    /// Whether the mint is trusted and was not screened.
+    pub trusted: bool,
+    /// Whether any risk blocks opening positions.
+    pub blocked: bool,
+    /// Red flags found.
+    pub risks: Vec<String>,
+    /// Share of supply held by the ten largest holders.
+    #[schema(value_type = Option<String>)]
+    pub top_holders_share: Option<Decimal>,
+}
+
+/// A pool strategies are paused on after an anomaly.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PausedPoolResponse {
+    /// Pool address.
+    pub pool_address: String,
+    /// When the pool was paused.
+    #[schema(value_type = String)]
+    pub paused_at: chrono::DateTime<chrono::Utc>,
+    /// Anomalies that paused it.
+    pub anomalies: Vec<String>,
+}
+
+/// Pools strategies are paused on.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ListPausedPoolsResponse {
+    /// Paused pools, oldest first.
+    pub pools: Vec<PausedPoolResponse>,
+    /// Total count.
+    pub total: usize,
+}
+
+// ============================================================================
+// Analytics Models
+// ============================================================================
+
+/// Portfolio analytics response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PortfolioAnalyticsResponse {
+    /// Total value in USD.
+    #[schema(value_type = String)]
+    pub total_value_usd: Decimal,
+    /// Total PnL in USD.
+    #[schema(value_type = String)]
+    pub total_pnl_usd: Decimal,
+    /// Total PnL percentage.
+    #[schema(value_type = String)]
+    pub total_pnl_pct: Decimal,
+    /// Total fees earned in USD.
+    #[schema(value_type = String)]
+    pub total_fees_usd: Decimal,
+    /// Total IL percentage.
+    #[schema(value_type = String)]
+    pub total_il_pct: Decimal,
+    /// Number of active positions.
+    pub active_positions: u32,
+    /// Number of positions in range.
+    pub positions_in_range: u32,
+    /// Best performing position.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub best_position: Option<String>,
+    /// Worst performing position.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub worst_position: Option<String>,
+    /// Breakdown by position tag, sorted by tag.
+    pub by_tag: Vec<TagAnalyticsResponse>,
+}
+
+/// Portfolio analytics for positions sharing a tag.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TagAnalyticsResponse {
+    /// Tag.
+    pub tag: String,
+    /// Number of positions with the tag.
+    pub positions: u32,
+    /// Number of those positions in range.
+    pub positions_in_range: u32,
+    /// Total value in USD.
+    #[schema(value_type = String)]
+    pub total_value_usd: Decimal,
+    /// Total PnL in USD.
+    #[schema(value_type = String)]
+    pub total_pnl_usd: Decimal,
+    /// Total fees earned in USD.
+    #[schema(value_type = String)]
+    pub total_fees_usd: Decimal,
+    /// Rebalances recorded by the lifecycle tracker.
+    pub total_rebalances: u32,
+}
+
+/// Bucket size for performance history.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PerformancePeriod {
+    /// One bucket per day.
+    #[default]
+    Daily,
+    /// One bucket per ISO week.
+    Weekly,
+    /// One bucket per calendar month.
+    Monthly,
+}
+
+/// Query parameters for performance history.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, IntoParams)]
+pub struct PerformanceHistoryQuery {
+    /// Bucket size (defaults to daily).
+    #[serde(default)]
+    pub period: PerformancePeriod,
+    /// Only include positions carrying this tag.
+    pub tag: Option<String>,
+}
+
+/// Performance over time, bucketed by period.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PerformanceHistoryResponse {
+    /// Bucket size.
+    pub period: PerformancePeriod,
+    /// Buckets, oldest first, with empty periods included.
+    pub buckets: Vec<PerformanceBucketResponse>,
+}
+
+/// Performance for a single period.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PerformanceBucketResponse {
+    /// Start of the period (UTC).
+    #[schema(value_type = String)]
+    pub period_start: chrono::DateTime<chrono::Utc>,
+    /// Fees collected in USD.
+    #[schema(value_type = String)]
+    pub fees_usd: Decimal,
+    /// Rewards collected in USD.
+    #[schema(value_type = String)]
+    pub rewards_usd: Decimal,
+    /// Realized PnL in USD from positions closed in the period.
+    #[schema(value_type = String)]
+    pub realized_pnl_usd: Decimal,
+    /// Rebalances performed.
+    pub rebalances: u32,
+    /// Migrations to another pool performed.
+    pub migrations: u32,
+    /// Transaction costs in lamports.
+    pub tx_costs_lamports: u64,
+    /// Positions opened.
+    pub positions_opened: u32,
+    /// Positions closed.
+    pub positions_closed: u32,
+}
+
+/// Simulation request.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SimulationRequest {
+    /// Pool address.
+    pub pool_address: String,
+    /// Lower tick.
+    pub tick_lower: i32,
+    /// Upper tick.
+    pub tick_upper: i32,
+    /// Initial capital in USD.
+    #[schema(value_type = String)]
+    pub initial_capital_usd: Decimal,
+    /// Start date.
+    #[schema(value_type = String)]
+    pub start_date: chrono::NaiveDate,
+    /// End date.
+    #[schema(value_type = String)]
+    pub end_date: chrono::NaiveDate,
+    /// Strategy type.
+    #[serde(default)]
+    pub strategy_type: Option<StrategyType>,
+}
+
+/// Simulation response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SimulationResponse {
+    /// Simulation ID.
+    pub id: String,
+    /// Pool address.
+    pub pool_address: String,
+    /// Tick range.
+    pub tick_lower: i32,
+    /// Tick range.
+    pub tick_upper: i32,
+    /// Initial capital.
+    #[schema(value_type = String)]
+    pub initial_capital_usd: Decimal,
+    /// Final value.
+    #[schema(value_type = String)]
+    pub final_value_usd: Decimal,
+    /// Total return percentage.
+    #[schema(value_type = String)]
+    pub total_return_pct: Decimal,
+    /// Fee earnings percentage.
+    #[schema(value_type = String)]
+    pub fee_earnings_pct: Decimal,
+    /// IL percentage.
+    #[schema(value_type = String)]
+    pub il_pct: Decimal,
+    /// Sharpe ratio.
+    #[schema(value_type = String)]
+    pub sharpe_ratio: Decimal,
+    /// Max drawdown percentage.
+    #[schema(value_type = String)]
+    pub max_drawdown_pct: Decimal,
+    /// Number of rebalances.
+    pub rebalance_count: u32,
+}
+
+/// Query parameters for comparing the pools of a token pair.
+#[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+pub struct VenueComparisonQuery {
+    /// First token mint.
+    pub token_a: String,
+    /// Second token mint.
+    pub token_b: String,
+    /// Range width around the current price in percent (defaults to 10 = ±10%).
+    pub range_width_pct: Option<f64>,
+    /// Days of stored price history to read volume and volatility from
+    /// (defaults to 30).
+    pub days: Option<u32>,
+}
+
+/// Pools of a token pair compared for a range width.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct VenueComparisonResponse {
+    /// Range width around the current price in percent.
+    pub range_width_pct: f64,
+    /// Compared pools, best first.
+    pub venues: Vec<VenueResponse>,
+    /// Address of the recommended pool, if any could be estimated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recommended: Option<String>,
+}
+
+/// One pool of a token pair.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct VenueResponse {
+    /// Pool address.
+    pub pool_address: String,
+    /// Protocol (orca, raydium).
+    pub protocol: String,
+    /// Fee tier in basis points.
+    pub fee_tier_bps: u16,
+    /// Value of the pool's active liquidity within the range, in token B.
+    #[schema(value_type = String)]
+    pub range_tvl: Decimal,
+    /// Mean daily volume over the history, in token B.
+    #[schema(value_type = String)]
+    pub mean_daily_volume: Decimal,
+    /// Fee APR while the price stays in range.
+    #[schema(value_type = String)]
+    pub fee_apr: Decimal,
+    /// Annualized realized volatility of the pool price.
+    pub volatility: f64,
+    /// Expected fraction of the time in range.
+    pub time_in_range: f64,
+    /// Fee APR weighted by the expected time in range.
+    #[schema(value_type = String)]
+    pub expected_fee_apr: Decimal,
+}
+
+/// Query parameters for comparing saved simulations.
+#[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+pub struct SimulationCompareQuery {
+    /// Comma-separated simulation IDs; the first is the baseline.
+    pub ids: String,
+    /// Number of points on the shared curve axis (defaults to 100).
+    pub points: Option<usize>,
+}
+
+/// Comparison of saved simulations against a baseline.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SimulationComparisonResponse {
+    /// Compared simulation IDs, baseline first.
+    pub simulation_ids: Vec<String>,
+    /// Shared progress axis from 0 (start) to 1 (end).
+    #[schema(value_type = Vec<String>)]
+    pub axis: Vec<Decimal>,
+    /// Per-metric values and diffs.
+    pub metrics: Vec<MetricComparisonResponse>,
+    /// Equity curves normalized to their starting value.
+    pub curves: Vec<NormalizedCurveResponse>,
+}
+
+/// One metric across the compared simulations.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MetricComparisonResponse {
+    /// Metric name.
+    pub metric: String,
+    /// Value per simulation, null where unavailable.
+    #[schema(value_type = Vec<Option<String>>)]
+    pub values: Vec<Option<Decimal>>,
+    /// Difference from the baseline per simulation.
+    #[schema(value_type = Vec<Option<String>>)]
+    pub diffs: Vec<Option<Decimal>>,
+}
+
+/// Equity curve resampled onto the comparison axis.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct NormalizedCurveResponse {
+    /// Simulation ID.
+    pub simulation_id: String,
+    /// Value relative to the start (1.0 = break-even); empty if no curve
+    /// was saved.
+    #[schema(value_type = Vec<String>)]
+    pub values: Vec<Decimal>,
+}
+
+/// Request to replay a position's history under alternative scenarios.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WhatIfRequest {
+    /// Scenarios to compare against the actual history.
+    pub scenarios: Vec<WhatIfScenarioRequest>,
+    /// Capital in USD; required if the position's opening was not recorded.
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub capital_usd: Option<Decimal>,
+    /// Cost per rebalance in USD.
+    #[serde(default = "default_what_if_rebalance_cost")]
+    #[schema(value_type = String)]
+    pub rebalance_cost_usd: Decimal,
+}
+
+fn default_what_if_rebalance_cost() -> Decimal {
+    Decimal::ONE
+}
+
+/// One alternative scenario.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WhatIfScenarioRequest {
+    /// Name shown in the report; defaults to a description of the scenario.
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Factor applied to the width of every range (2 = twice as wide).
+    #[serde(default = "default_width_multiplier")]
+    #[schema(value_type = String)]
+    pub width_multiplier: Decimal,
+    /// How the range is managed.
+    #[serde(default)]
+    pub strategy: WhatIfStrategyKind,
+    /// Hours between rebalances, for the periodic strategy.
+    #[serde(default)]
+    pub rebalance_interval_hours: Option<u64>,
+    /// Price move that triggers a rebalance (0.05 = 5%), for the threshold
+    /// strategy.
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub threshold_pct: Option<Decimal>,
+}
+
+fn default_width_multiplier() -> Decimal {
+    Decimal::ONE
+}
+
+/// Range management of a what-if scenario.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WhatIfStrategyKind {
+    /// Rebalance when the position actually did.
+    #[default]
+    Actual,
+    /// Never rebalance.
+    Static,
+    /// Rebalance at a fixed interval.
+    Periodic,
+    /// Rebalance when price moves past a threshold.
+    Threshold,
+}
+
+/// Counterfactual comparison of a position's history.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WhatIfResponse {
+    /// Position address.
+    pub position_address: String,
+    /// Start of the replayed period.
+    #[schema(value_type = String)]
+    pub start: chrono::DateTime<chrono::Utc>,
+    /// End of the replayed period.
+    #[schema(value_type = String)]
+    pub end: chrono::DateTime<chrono::Utc>,
+    /// Number of price points replayed.
+    pub steps: u64,
+    /// Replay of the ranges the position actually held.
+    pub actual: WhatIfOutcomeResponse,
+    /// Alternative scenarios, in request order.
+    pub scenarios: Vec<WhatIfOutcomeResponse>,
+}
+
+/// Outcome of one replay.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WhatIfOutcomeResponse {
+    /// Scenario name.
+    pub label: String,
+    /// Initial lower price.
+    #[schema(value_type = String)]
+    pub lower_price: Decimal,
+    /// Initial upper price.
+    #[schema(value_type = String)]
+    pub upper_price: Decimal,
+    /// Final value in USD.
+    #[schema(value_type = String)]
+    pub final_value_usd: Decimal,
+    /// Net PnL in USD.
+    #[schema(value_type = String)]
+    pub net_pnl_usd: Decimal,
+    /// Fees earned in USD.
+    #[schema(value_type = String)]
+    pub fees_usd: Decimal,
+    /// Final impermanent loss.
+    #[schema(value_type = String)]
+    pub il_pct: Decimal,
+    /// Fraction of steps in range.
+    #[schema(value_type = String)]
+    pub time_in_range_pct: Decimal,
+    /// Number of rebalances.
+    pub rebalance_count: u32,
+    /// Net PnL minus the actual net PnL.
+    #[schema(value_type = String)]
+    pub pnl_vs_actual_usd: Decimal,
+    /// Fees minus the actual fees.
+    #[schema(value_type = String)]
+    pub fees_vs_actual_usd: Decimal,
+}
+
+/// Range optimization request.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OptimizationRequest {
+    /// Current price of token A in token B.
+    #[schema(value_type = String)]
+    pub current_price: Decimal,
+    /// Annualized volatility (e.g., 0.5 = 50%).
+    pub volatility: f64,
+    /// Optimization objective.
+    #[serde(default)]
+    pub objective: OptimizationObjective,
+    /// Number of Monte Carlo iterations.
+    #[serde(default = "default_optimization_iterations")]
+    pub iterations: usize,
+    /// Capital in USD.
+    #[serde(default = "default_optimization_capital")]
+    #[schema(value_type = String)]
+    pub capital_usd: Decimal,
+    /// Pool fee rate (e.g., 0.003 = 0.3%).
+    #[serde(default = "default_optimization_fee_rate")]
+    #[schema(value_type = String)]
+    pub fee_rate: Decimal,
+    /// Pool tick spacing (defaults to the standard spacing for the fee tier).
+    #[serde(default)]
+    pub tick_spacing: Option<u16>,
+    /// Percentiles of final net PnL whose Monte Carlo paths are kept with
+    /// the result (defaults to 5, 50 and 95; empty keeps none).
+    #[serde(default = "default_path_percentiles")]
+    pub path_percentiles: Vec<u8>,
+    /// Annual risk-free rate for the Sharpe ratio (defaults to 0.05).
+    #[serde(default = "default_risk_free_rate")]
+    #[schema(value_type = String)]
+    pub risk_free_rate: Decimal,
+    /// Benchmark for the Sharpe ratio: `hodl`, `fifty_fifty` or `zero`
+    /// (defaults to `zero`).
+    #[serde(default)]
+    #[schema(value_type = String)]
+    pub benchmark: Benchmark,
+    /// Queue priority when a database backs the job queue; higher runs
+    /// first (defaults to 0).
+    #[serde(default)]
+    pub priority: i32,
+}
+
+impl OptimizationRequest {
+    /// Returns the Sharpe configuration requested.
+    #[must_use]
+    pub fn sharpe_config(&self) -> SharpeConfig {
+        SharpeConfig::new(self.risk_free_rate, self.benchmark)
+    }
+}
+
+fn default_risk_free_rate() -> Decimal {
+    SharpeConfig::default().risk_free_rate
+}
+
+fn default_path_percentiles() -> Vec<u8> {
+    vec![5, 50, 95]
+}
+
+fn default_optimization_iterations() -> usize {
+    100
+}
+
+fn default_optimization_capital() -> Decimal {
+    Decimal::from(1000)
+}
+
+fn default_optimization_fee_rate() -> Decimal {
+    Decimal::new(3, 3)
+}
+
+/// Optimization objective.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OptimizationObjective {
+    /// Maximize net PnL (fees - IL).
+    #[default]
+    Pnl,
+    /// Maximize fees earned.
+    Fees,
+    /// Maximize Sharpe ratio.
+    Sharpe,
+}
+
+impl OptimizationObjective {
+    /// Returns the objective type stored with optimization records.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pnl => "pnl",
+            Self::Fees => "fees",
+            Self::Sharpe => "sharpe",
+        }
+    }
+}
+
+/// Monte Carlo paths kept with an optimization result.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OptimizationPathsResponse {
+    /// Optimization job ID.
+    pub optimization_id: String,
+    /// Kept paths, lowest percentile first.
+    pub paths: Vec<PercentilePathResponse>,
+}
+
+/// A Monte Carlo path at a percentile of final net PnL.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PercentilePathResponse {
+    /// Percentile of the path's final net PnL among all paths (0-100).
+    pub percentile: u8,
+    /// Simulated price at each step.
+    #[schema(value_type = Vec<String>)]
+    pub prices: Vec<Decimal>,
+    /// Net PnL after each step.
+    #[schema(value_type = Vec<String>)]
+    pub pnl: Vec<Decimal>,
+}
+
+/// Simulations that backtested an optimization's recommendation.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OptimizationValidationResponse {
+    /// Optimization ID.
+    pub optimization_id: String,
+    /// Recommended lower price bound.
+    #[schema(value_type = String)]
+    pub recommended_lower: Decimal,
+    /// Recommended upper price bound.
+    #[schema(value_type = String)]
+    pub recommended_upper: Decimal,
+    /// Expected net PnL as a percentage of initial capital.
+    #[schema(value_type = Option<String>)]
+    pub expected_return_pct: Option<Decimal>,
+    /// Expected fees as a percentage of initial capital.
+    #[schema(value_type = Option<String>)]
+    pub expected_fees_pct: Option<Decimal>,
+    /// Validating simulations, oldest first.
+    pub simulations: Vec<ValidationRunResponse>,
+    /// Mean realized minus expected return, in percentage points.
+    #[schema(value_type = Option<String>)]
+    pub mean_return_error_pct: Option<Decimal>,
+}
+
+/// A simulation that backtested a recommendation.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ValidationRunResponse {
+    /// Simulation ID.
+    pub simulation_id: String,
+    /// When the simulation was stored.
+    #[schema(value_type = String)]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Realized net PnL as a percentage of initial capital.
+    #[schema(value_type = Option<String>)]
+    pub return_pct: Option<Decimal>,
+    /// Realized fees as a percentage of initial capital.
+    #[schema(value_type = Option<String>)]
+    pub fees_pct: Option<Decimal>,
+    /// Realized minus expected return, in percentage points.
+    #[schema(value_type = Option<String>)]
+    pub return_error_pct: Option<Decimal>,
+    /// Time in range as percentage.
+    #[schema(value_type = String)]
+    pub time_in_range_pct: Decimal,
+}
+
+/// Query parameters for a pool's range utilization heatmap.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, IntoParams)]
+pub struct RangeUtilizationQuery {
+    /// Days of price history to read (defaults to 30).
+    pub days: Option<u32>,
+    /// Maximum number of buckets (defaults to 50); buckets widen by whole
+    /// tick spacings to fit.
+    pub max_buckets: Option<usize>,
+}
+
+/// Time the price spent per bucket of a pool's tick grid.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RangeUtilizationResponse {
+    /// Pool address.
+    pub pool_address: String,
+    /// Pool tick spacing.
+    pub tick_spacing: i32,
+    /// Width of each bucket in ticks.
+    pub bucket_ticks: i32,
+    /// Start of the lookback window in seconds.
+    pub start: i64,
+    /// End of the lookback window in seconds.
+    pub end: i64,
+    /// Total seconds covered by stored prices.
+    pub total_seconds: u64,
+    /// Buckets from the lowest price up.
+    pub buckets: Vec<UtilizationBucketResponse>,
+}
+
+/// Time spent within one bucket of the tick grid.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UtilizationBucketResponse {
+    /// Lower tick, inclusive.
+    pub tick_lower: i32,
+    /// Upper tick, exclusive.
+    pub tick_upper: i32,
+    /// Price at the lower tick.
+    #[schema(value_type = String)]
+    pub price_lower: Decimal,
+    /// Price at the upper tick.
+    #[schema(value_type = String)]
+    pub price_upper: Decimal,
+    /// Seconds the price spent in the bucket.
+    pub seconds: u64,
+    /// Share of the total time, in percent.
+    #[schema(value_type = String)]
+    pub share_pct: Decimal,
+}
+
+/// Query parameters for a pool's optimization leaderboard.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, IntoParams)]
+pub struct OptimizationLeaderboardQuery {
+    /// Objective to rank by (defaults to pnl).
+    #[serde(default)]
+    pub objective: OptimizationObjective,
+    /// Maximum number of ranked entries (defaults to 10).
+    pub limit: Option<usize>,
+}
+
+/// Stored optimizations of a pool ranked by objective value.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OptimizationLeaderboardResponse {
+    /// Pool address.
+    pub pool_address: String,
+    /// Objective the optimizations maximized.
+    pub objective: OptimizationObjective,
+    /// Best optimizations, highest objective value first.
+    pub entries: Vec<LeaderboardEntryResponse>,
+    /// Recommended ranges over time, oldest first.
+    pub drift: Vec<RecommendationDriftResponse>,
+}
+
+/// A ranked optimization with its parameters.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LeaderboardEntryResponse {
+    /// Rank, starting at 1.
+    pub rank: usize,
+    /// Optimization ID.
+    pub optimization_id: String,
+    /// Value of the objective.
+    #[schema(value_type = String)]
+    pub objective_value: Decimal,
+    /// Recommended lower price bound.
+    #[schema(value_type = String)]
+    pub recommended_lower: Decimal,
+    /// Recommended upper price bound.
+    #[schema(value_type = String)]
+    pub recommended_upper: Decimal,
+    /// Volatility used.
+    #[schema(value_type = String)]
+    pub volatility: Decimal,
+    /// Initial capital used.
+    #[schema(value_type = String)]
+    pub initial_capital: Decimal,
+    /// Expected PnL.
+    #[schema(value_type = String)]
+    pub expected_pnl: Decimal,
+    /// Expected fees.
+    #[schema(value_type = String)]
+    pub expected_fees: Decimal,
+    /// Expected IL.
+    #[schema(value_type = String)]
+    pub expected_il: Decimal,
+    /// Sharpe ratio if calculated.
+    #[schema(value_type = Option<String>)]
+    pub sharpe_ratio: Option<Decimal>,
+    /// Annual risk-free rate the Sharpe ratio was computed with.
+    #[schema(value_type = Option<String>)]
+    pub risk_free_rate: Option<Decimal>,
+    /// Benchmark the Sharpe ratio was computed against.
+    pub benchmark: Option<String>,
+    /// Number of simulations run.
+    pub simulations_run: i32,
+    /// When the optimization was stored.
+    #[schema(value_type = String)]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A recommendation and how it moved from the previous one.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RecommendationDriftResponse {
+    /// Optimization ID.
+    pub optimization_id: String,
+    /// When the optimization was stored.
+    #[schema(value_type = String)]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Volatility used.
+    #[schema(value_type = String)]
+    pub volatility: Decimal,
+    /// Recommended lower price bound.
+    #[schema(value_type = String)]
+    pub recommended_lower: Decimal,
+    /// Recommended upper price bound.
+    #[schema(value_type = String)]
+    pub recommended_upper: Decimal,
+    /// Range midpoint change in percent; null for the first entry.
+    #[schema(value_type = Option<String>)]
+    pub midpoint_change_pct: Option<Decimal>,
+    /// Range width change in percent; null for the first entry.
+    #[schema(value_type = Option<String>)]
+    pub width_change_pct: Option<Decimal>,
+    /// Volatility change; null for the first entry.
+    #[schema(value_type = Option<String>)]
+    pub volatility_change: Option<Decimal>,
+}
+
+/// Query parameters for the capital efficiency of a range.
+#[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+pub struct CapitalEfficiencyQuery {
+    /// Lower bound of the range, in token B per token A.
+    #[param(value_type = String)]
+    pub lower_price: Decimal,
+    /// Upper bound of the range, in token B per token A.
+    #[param(value_type = String)]
+    pub upper_price: Decimal,
+    /// Capital to deploy, valued in token B (defaults to 1000).
+    #[param(value_type = Option<String>)]
+    pub capital: Option<Decimal>,
+}
+
+/// Capital efficiency of a range at the pool's current price and liquidity.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CapitalEfficiencyResponse {
+    /// Pool address.
+    pub pool_address: String,
+    /// Current price, in token B per token A.
+    #[schema(value_type = String)]
+    pub current_price: Decimal,
+    /// Lower bound of the range.
+    #[schema(value_type = String)]
+    pub lower_price: Decimal,
+    /// Upper bound of the range.
+    #[schema(value_type = String)]
+    pub upper_price: Decimal,
+    /// Capital deployed, valued in token B.
+    #[schema(value_type = String)]
+    pub capital: Decimal,
+    /// Liquidity per unit of capital relative to a full-range position.
+    #[schema(value_type = String)]
+    pub efficiency_multiple: Decimal,
+    /// Liquidity the capital provides.
+    pub liquidity: String,
+    /// Pool liquidity currently in range.
+    pub active_liquidity: String,
+    /// Expected share of swap fees at the current liquidity, in percent.
+    #[schema(value_type = String)]
+    pub fee_share_pct: Decimal,
+    /// Lower bound relative to the current price, in percent.
+    #[schema(value_type = String)]
+    pub lower_band_pct: Decimal,
+    /// Upper bound relative to the current price, in percent.
+    #[schema(value_type = String)]
+    pub upper_band_pct: Decimal,
+    /// Whether the current price is inside the range.
+    pub in_range: bool,
+}
+
+/// How stored prices are reduced to the requested number of points.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceDownsampling {
+    /// Keep the stored candles that best preserve the close price line (LTTB).
+    #[default]
+    Lttb,
+    /// Merge consecutive candles into wider OHLC candles.
+    Ohlc,
+}
+
+/// Query parameters for a pool's price history.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, IntoParams)]
+pub struct PriceHistoryQuery {
+    /// Start of the range in Unix seconds (defaults to 90 days before `end`).
+    pub start: Option<i64>,
+    /// End of the range in Unix seconds (defaults to now).
+    pub end: Option<i64>,
+    /// Maximum number of points returned (defaults to 1000).
+    pub target_points: Option<usize>,
+    /// Downsampling method (defaults to lttb).
+    #[serde(default)]
+    pub method: PriceDownsampling,
+}
+
+/// Stored price history of a pool, downsampled for charting.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PriceHistoryResponse {
+    /// Pool address.
+    pub pool_address: String,
+    /// Downsampling method used.
+    pub method: PriceDownsampling,
+    /// Number of stored candles in the range.
+    pub source_points: usize,
+    /// Candles, oldest first.
+    pub points: Vec<PricePointResponse>,
+}
+
+/// A price candle.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PricePointResponse {
+    /// Candle start in Unix seconds.
+    pub timestamp: u64,
+    /// Open price.
+    #[schema(value_type = String)]
+    pub open: Decimal,
+    /// High price.
+    #[schema(value_type = String)]
+    pub high: Decimal,
+    /// Low price.
+    #[schema(value_type = String)]
+    pub low: Decimal,
+    /// Close price.
+    #[schema(value_type = String)]
+    pub close: Decimal,
+    /// Volume.
+    #[schema(value_type = String)]
+    pub volume: Decimal,
+}
+
+/// Optimization job status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OptimizationJobStatus {
+    /// Waiting for a free optimization slot or queue worker.
+    Queued,
+    /// Running.
+    Running,
+    /// Finished with a result.
+    Completed,
+    /// Cancelled before finishing.
+    Cancelled,
+    /// Failed.
+    Failed,
+}
+
+impl OptimizationJobStatus {
+    /// Returns true if the job can no longer change.
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Completed | Self::Cancelled | Self::Failed)
+    }
+}
+
+/// Optimization job status response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OptimizationJobResponse {
+    /// Job ID.
+    pub id: String,
+    /// Job status.
+    pub status: OptimizationJobStatus,
+    /// Completed fraction between 0 and 1.
+    pub progress: f64,
+    /// Monte Carlo paths completed.
+    pub iterations_done: usize,
+    /// Total Monte Carlo paths.
+    pub total_iterations: usize,
+    /// Best objective score so far.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub best_score: Option<Decimal>,
+    /// Recommended lower price (best so far while running).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub lower_price: Option<Decimal>,
+    /// Recommended upper price (best so far while running).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub upper_price: Option<Decimal>,
+    /// Expected PnL of the final recommendation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub expected_pnl: Option<Decimal>,
+    /// Expected fees of the final recommendation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub expected_fees: Option<Decimal>,
+    /// Expected IL of the final recommendation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub expected_il: Option<Decimal>,
+    /// Mean Sharpe ratio of the final recommendation's simulated paths.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub sharpe_ratio: Option<Decimal>,
+    /// Annual risk-free rate the Sharpe ratio was computed with.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub risk_free_rate: Option<Decimal>,
+    /// Benchmark the Sharpe ratio was computed against.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub benchmark: Option<String>,
+    /// Error message if the job failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Created timestamp.
+    #[schema(value_type = String)]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+// ============================================================================
+// Alert Models
+// ============================================================================
+
+/// Request to create or replace an alert rule.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AlertRuleRequest {
+    /// Unique rule name; when replacing, must be empty or match the path.
+    #[serde(default)]
+    pub name: String,
+    /// Condition with its threshold, e.g. `"RangeExit"` or `{"ILExceeds": "0.05"}`.
+    #[schema(value_type = Object)]
+    pub condition: RuleCondition,
+    /// Severity: `Info`, `Warning` or `Critical`.
+    #[schema(value_type = String)]
+    pub severity: AlertLevel,
+    /// Alert type reported; defaults to the one matching the condition.
+    #[serde(default)]
+    #[schema(value_type = Option<Object>)]
+    pub alert_type: Option<AlertType>,
+    /// Message template with placeholders such as `{il_pct}` or `{price}`.
+    #[serde(default)]
+    pub message_template: String,
+    /// Notifier channels to send to (e.g. `webhook`); empty sends to all.
+    #[serde(default)]
+    pub channels: Vec<String>,
+    /// Position tags the rule is limited to; empty applies to all positions.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Cooldown between alerts in seconds.
+    #[serde(default)]
+    pub cooldown_secs: Option<u64>,
+    /// Whether the rule is enabled.
+    #[serde(default = "default_rule_enabled")]
+    pub enabled: bool,
+}
+
+fn default_rule_enabled() -> bool {
+    true
+}
+
+/// Alert rule response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AlertRuleResponse {
+    /// Rule name.
+    pub name: String,
+    /// Condition with its threshold.
+    #[schema(value_type = Object)]
+    pub condition: RuleCondition,
+    /// Severity.
+    #[schema(value_type = String)]
+    pub severity: AlertLevel,
+    /// Alert type reported.
+    #[schema(value_type = Object)]
+    pub alert_type: AlertType,
+    /// Message template.
+    pub message_template: String,
+    /// Notifier channels; empty sends to all.
+    pub channels: Vec<String>,
+    /// Position tags the rule is limited to.
+    pub tags: Vec<String>,
+    /// Cooldown between alerts in seconds.
+    pub cooldown_secs: u64,
+    /// Whether the rule is enabled.
+    pub enabled: bool,
+    /// Created timestamp.
+    #[schema(value_type = String)]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Last update timestamp.
+    #[schema(value_type = String)]
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// List of alert rules.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ListAlertRulesResponse {
+    /// Stored alert rules.
+    pub rules: Vec<AlertRuleResponse>,
+    /// Total count.
+    pub total: usize,
+}
+
+// ============================================================================
+// Health Models
+// ============================================================================
+
+/// Health check response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct HealthResponse {
+    /// Service status.
+    pub status: ServiceStatus,
+    /// Version.
+    pub version: String,
+    /// Uptime in seconds.
+    pub uptime_secs: u64,
+    /// Component health.
+    pub components: ComponentHealth,
+}
+
+/// Service status.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceStatus {
+    /// Service is healthy.
+    Healthy,
+    /// Service is degraded.
+    Degraded,
+    /// Service is unhealthy.
+    Unhealthy,
+}
+
+/// Component health status.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ComponentHealth {
+    /// RPC connection status.
+    pub rpc: bool,
+    /// Database status.
+    pub database: bool,
+    /// Circuit breaker status.
+    pub circuit_breaker: CircuitBreakerStatus,
+    /// Background loops that missed their heartbeat.
+    pub stalled_loops: Vec<String>,
+}
+
+/// Circuit breaker status.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitBreakerStatus {
+    /// Circuit is closed (normal).
+    Closed,
+    /// Circuit is open (blocking).
+    Open,
+    /// Circuit is half-open (testing).
+    HalfOpen,
+}
+
+/// Metrics response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MetricsResponse {
+    /// Request count.
+    pub request_count: u64,
+    /// Error count.
+    pub error_count: u64,
+    /// Average response time in milliseconds.
+    pub avg_response_time_ms: f64,
+    /// Active WebSocket connections.
+    pub active_ws_connections: u32,
+    /// Positions monitored.
+    pub positions_monitored: u32,
+    /// Strategies running.
+    pub strategies_running: u32,
+    /// Account updates dropped for arriving after a newer slot.
+    pub stale_updates_dropped: u64,
+    /// Account updates dropped as repeats of one already applied.
+    pub duplicate_updates_dropped: u64,
+}
+
+// ============================================================================
+// Common Models
+// ============================================================================
+
+/// Success response wrapper.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SuccessResponse<T> {
+    /// Success flag.
+    pub success: bool,
+    /// Response data.
+    pub data: T,
+}
+
+impl<T> SuccessResponse<T> {
+    /// Creates a new success response.
+    pub fn new(data: T) -> Self {
+        Self {
+            success: true,
+            data,
+        }
+    }
+}
+
+/// Message response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MessageResponse {
+    /// Message.
+    pub message: String,
+}
+
+impl MessageResponse {
+    /// Creates a new message response.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
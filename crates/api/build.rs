@@ -0,0 +1,16 @@
+//! Generates the gRPC service from `proto/engine.proto`.
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Use the vendored protoc unless one is configured explicitly
+    if std::env::var_os("PROTOC").is_none() {
+        // SAFETY: build scripts are single-threaded
+        unsafe {
+            std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+        }
+    }
+
+    tonic_prost_build::configure()
+        .build_client(true)
+        .compile_protos(&["proto/engine.proto"], &["proto"])?;
+    Ok(())
+}
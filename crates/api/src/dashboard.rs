@@ -0,0 +1,196 @@
+//! Web dashboard served by the API server.
+//!
+//! With the `dashboard` feature, the built dashboard (`web/dist`, from
+//! `npm run build`) is compiled into the binary and served at `/`, so small
+//! deployments need no separate web server. Paths matching no asset fall
+//! back to `index.html`, letting the app handle its client-side routes.
+
+use axum::body::Body;
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use std::borrow::Cow;
+
+/// Entry point of the dashboard.
+pub const INDEX: &str = "index.html";
+
+/// Cache policy of bundle assets, whose names change with their content.
+const CACHE_IMMUTABLE: &str = "public, max-age=31536000, immutable";
+
+/// Cache policy of the entry point, revalidated on every load so new
+/// deployments are picked up.
+const CACHE_REVALIDATE: &str = "no-cache";
+
+/// Cache policy of other files, such as the favicon.
+const CACHE_SHORT: &str = "public, max-age=3600";
+
+/// Path prefixes owned by the API; misses there are not client routes.
+const API_PREFIXES: [&str; 3] = ["api/", "api-docs/", "docs"];
+
+/// A dashboard file.
+#[derive(Debug, Clone)]
+pub struct DashboardAsset {
+    /// File contents.
+    pub data: Cow<'static, [u8]>,
+    /// MIME type.
+    pub content_type: String,
+    /// Entity tag derived from the contents.
+    pub etag: String,
+}
+
+/// Returns the `Cache-Control` value for an asset path.
+#[must_use]
+pub fn cache_control(path: &str) -> &'static str {
+    if path == INDEX {
+        CACHE_REVALIDATE
+    } else if path.starts_with("assets/") {
+        CACHE_IMMUTABLE
+    } else {
+        CACHE_SHORT
+    }
+}
+
+/// Returns true if a path that matches no asset is a client-side route
+/// and should load the app.
+///
+/// Paths with a file extension and paths under the API are real misses.
+#[must_use]
+pub fn is_client_route(path: &str) -> bool {
+    let has_extension = path
+        .rsplit('/')
+        .next()
+        .is_some_and(|name| name.contains('.'));
+    !has_extension && !API_PREFIXES.iter().any(|prefix| path.starts_with(prefix))
+}
+
+/// Serves the asset at `path`, falling back to the entry point for
+/// client-side routes.
+///
+/// Answers `304 Not Modified` when `headers` carry the asset's entity tag.
+pub fn serve_asset(
+    path: &str,
+    headers: &HeaderMap,
+    lookup: impl Fn(&str) -> Option<DashboardAsset>,
+) -> Response {
+    let path = path.trim_start_matches('/');
+    let path = if path.is_empty() { INDEX } else { path };
+
+    let (path, asset) = match lookup(path) {
+        Some(asset) => (path, asset),
+        None if is_client_route(path) => match lookup(INDEX) {
+            Some(asset) => (INDEX, asset),
+            None => return StatusCode::NOT_FOUND.into_response(),
+        },
+        None => return StatusCode::NOT_FOUND.into_response(),
+    };
+
+    let etag = format!("\"{}\"", asset.etag);
+    let not_modified = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|tags| tags.split(',').any(|tag| tag.trim() == etag));
+
+    let response = Response::builder()
+        .header(header::CACHE_CONTROL, cache_control(path))
+        .header(header::ETAG, etag);
+    let response = if not_modified {
+        response
+            .status(StatusCode::NOT_MODIFIED)
+            .body(Body::empty())
+    } else {
+        response
+            .header(header::CONTENT_TYPE, asset.content_type)
+            .body(Body::from(asset.data))
+    };
+    response.unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())
+}
+
+#[cfg(feature = "dashboard")]
+mod embedded {
+    use super::DashboardAsset;
+    use rust_embed::RustEmbed;
+
+    /// Dashboard build compiled into the binary.
+    #[derive(RustEmbed)]
+    #[folder = "../../web/dist/"]
+    #[allow_missing = true]
+    struct Assets;
+
+    /// Looks up an embedded asset.
+    pub(super) fn lookup(path: &str) -> Option<DashboardAsset> {
+        let file = Assets::get(path)?;
+        Some(DashboardAsset {
+            content_type: file.metadata.mimetype().to_string(),
+            etag: hex_digest(&file.metadata.sha256_hash()),
+            data: file.data,
+        })
+    }
+
+    fn hex_digest(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+}
+
+/// Serves the embedded dashboard; used as the router fallback.
+#[cfg(feature = "dashboard")]
+pub async fn dashboard_handler(uri: axum::http::Uri, headers: HeaderMap) -> Response {
+    serve_asset(uri.path(), &headers, embedded::lookup)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(path: &str) -> Option<DashboardAsset> {
+        let (data, content_type) = match path {
+            INDEX => ("<html></html>", "text/html"),
+            "assets/index-3f9a.js" => ("console.log(1)", "text/javascript"),
+            _ => return None,
+        };
+        Some(DashboardAsset {
+            data: Cow::Borrowed(data.as_bytes()),
+            content_type: content_type.to_string(),
+            etag: format!("{}-etag", path.len()),
+        })
+    }
+
+    fn header(response: &Response, name: header::HeaderName) -> &str {
+        response.headers()[name].to_str().unwrap()
+    }
+
+    #[test]
+    fn test_serves_assets_with_cache_headers() {
+        let response = serve_asset("/assets/index-3f9a.js", &HeaderMap::new(), lookup);
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(header(&response, header::CONTENT_TYPE), "text/javascript");
+        assert_eq!(header(&response, header::CACHE_CONTROL), CACHE_IMMUTABLE);
+
+        let response = serve_asset("/", &HeaderMap::new(), lookup);
+        assert_eq!(header(&response, header::CONTENT_TYPE), "text/html");
+        assert_eq!(header(&response, header::CACHE_CONTROL), CACHE_REVALIDATE);
+    }
+
+    #[test]
+    fn test_falls_back_to_index_for_client_routes() {
+        let response = serve_asset("/positions/abc", &HeaderMap::new(), lookup);
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(header(&response, header::CACHE_CONTROL), CACHE_REVALIDATE);
+
+        // Missing files and API paths are not client routes
+        let missing = serve_asset("/assets/old-1234.js", &HeaderMap::new(), lookup);
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+        let api = serve_asset("/api/v1/unknown", &HeaderMap::new(), lookup);
+        assert_eq!(api.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_not_modified_when_etag_matches() {
+        let first = serve_asset("/", &HeaderMap::new(), lookup);
+        let etag = first.headers()[header::ETAG].clone();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, etag);
+        let second = serve_asset("/", &headers, lookup);
+        assert_eq!(second.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(header(&second, header::CACHE_CONTROL), CACHE_REVALIDATE);
+    }
+}
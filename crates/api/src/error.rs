@@ -5,9 +5,8 @@ use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
 };
-use serde::{Deserialize, Serialize};
+pub use clmm_lp_api_models::error::ErrorResponse;
 use thiserror::Error;
-use utoipa::ToSchema;
 
 /// API error type.
 #[derive(Debug, Error)]
@@ -114,17 +113,5 @@ impl From<anyhow::Error> for ApiError {
     }
 }
 
-/// Error response body.
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
-pub struct ErrorResponse {
-    /// Error code.
-    pub code: String,
-    /// Error message.
-    pub message: String,
-    /// Additional details.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub details: Option<serde_json::Value>,
-}
-
 /// Result type for API handlers.
 pub type ApiResult<T> = Result<T, ApiError>;
@@ -0,0 +1,583 @@
+//! gRPC service for the execution engine.
+//!
+//! Serves the `clmm_lp.engine.v1.EngineService` defined in
+//! `proto/engine.proto` next to the REST API. Position and strategy calls
+//! go through the same handlers as their REST endpoints; decisions are
+//! streamed from the executors' broadcast channel.
+
+use crate::error::ApiError;
+use crate::handlers;
+use crate::models::{ListPositionsQuery, PositionProtocol, PositionResponse};
+use crate::state::{AlertUpdate, AppState};
+use axum::extract::{Path, Query, State};
+use clmm_lp_execution::prelude::{CircuitState, Decision, DecisionRecord};
+use futures::Stream;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashSet;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+use tonic::service::Interceptor;
+use tonic::service::interceptor::InterceptedService;
+use tonic::{Request, Response, Status};
+use tracing::{info, warn};
+
+/// Generated protobuf messages, client and server.
+#[allow(missing_docs, clippy::all)]
+pub mod proto {
+    tonic::include_proto!("clmm_lp.engine.v1");
+}
+
+use proto::engine_service_server::{EngineService, EngineServiceServer};
+
+/// Metadata key carrying the API key.
+pub const API_KEY_METADATA: &str = "x-api-key";
+
+/// Reason recorded when a halt request gives none.
+const DEFAULT_HALT_REASON: &str = "halted over gRPC";
+
+/// Engine service backed by the shared application state.
+#[derive(Clone)]
+pub struct EngineGrpcService {
+    state: AppState,
+}
+
+impl EngineGrpcService {
+    /// Creates a service over `state`.
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    /// Collects the engine-wide and per-executor circuit states.
+    async fn engine_status(&self) -> proto::EngineStatus {
+        let strategies = self.state.strategies.read().await;
+        let executors = self.state.executors.read().await;
+        let mut statuses = Vec::with_capacity(executors.len());
+        for (id, executor) in executors.iter() {
+            let circuit = executor.read().await.circuit_breaker().state().await;
+            statuses.push(proto::StrategyStatus {
+                id: id.clone(),
+                name: strategies
+                    .get(id)
+                    .map(|s| s.name.clone())
+                    .unwrap_or_default(),
+                circuit_state: circuit_state(circuit) as i32,
+            });
+        }
+        statuses.sort_by(|a, b| a.id.cmp(&b.id));
+
+        proto::EngineStatus {
+            dry_run: self.state.dry_run,
+            circuit_state: circuit_state(self.state.circuit_breaker.state().await) as i32,
+            strategies: statuses,
+        }
+    }
+}
+
+/// Stream of decisions returned by `StreamDecisions`.
+type DecisionStream = Pin<Box<dyn Stream<Item = Result<proto::DecisionEvent, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl EngineService for EngineGrpcService {
+    async fn list_positions(
+        &self,
+        request: Request<proto::ListPositionsRequest>,
+    ) -> Result<Response<proto::ListPositionsResponse>, Status> {
+        let request = request.into_inner();
+        let query = ListPositionsQuery {
+            protocol: position_protocol(request.protocol()),
+            tag: request.tag,
+        };
+        let response = handlers::list_positions(State(self.state.clone()), Query(query)).await?;
+
+        Ok(Response::new(proto::ListPositionsResponse {
+            positions: response.0.positions.into_iter().map(position).collect(),
+        }))
+    }
+
+    async fn get_position(
+        &self,
+        request: Request<proto::GetPositionRequest>,
+    ) -> Result<Response<proto::Position>, Status> {
+        let address = request.into_inner().address;
+        let response = handlers::get_position(State(self.state.clone()), Path(address)).await?;
+        Ok(Response::new(position(response.0)))
+    }
+
+    type StreamDecisionsStream = DecisionStream;
+
+    async fn stream_decisions(
+        &self,
+        request: Request<proto::StreamDecisionsRequest>,
+    ) -> Result<Response<Self::StreamDecisionsStream>, Status> {
+        let filter = DecisionFilter::new(&request.into_inner())?;
+        let receiver = self.state.subscribe_decisions();
+
+        let stream =
+            futures::stream::unfold((receiver, filter), |(mut receiver, filter)| async move {
+                loop {
+                    match receiver.recv().await {
+                        Ok(record) if filter.matches(&record) => {
+                            return Some((Ok(decision_event(&record)), (receiver, filter)));
+                        }
+                        Ok(_) => {}
+                        Err(RecvError::Lagged(skipped)) => {
+                            warn!(skipped, "Decision stream lagged");
+                        }
+                        Err(RecvError::Closed) => return None,
+                    }
+                }
+            });
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    async fn start_strategy(
+        &self,
+        request: Request<proto::StrategyRequest>,
+    ) -> Result<Response<proto::ControlResponse>, Status> {
+        if self.state.circuit_breaker.state().await == CircuitState::Open {
+            return Err(Status::failed_precondition(
+                "Engine is halted; resume it before starting strategies",
+            ));
+        }
+        let id = request.into_inner().strategy_id;
+        let response = handlers::start_strategy(State(self.state.clone()), Path(id)).await?;
+        Ok(Response::new(proto::ControlResponse {
+            message: response.0.message,
+        }))
+    }
+
+    async fn stop_strategy(
+        &self,
+        request: Request<proto::StrategyRequest>,
+    ) -> Result<Response<proto::ControlResponse>, Status> {
+        let id = request.into_inner().strategy_id;
+        let response = handlers::stop_strategy(State(self.state.clone()), Path(id)).await?;
+        Ok(Response::new(proto::ControlResponse {
+            message: response.0.message,
+        }))
+    }
+
+    async fn halt(
+        &self,
+        request: Request<proto::HaltRequest>,
+    ) -> Result<Response<proto::EngineStatus>, Status> {
+        let reason = request.into_inner().reason;
+        let reason = if reason.is_empty() {
+            DEFAULT_HALT_REASON.to_string()
+        } else {
+            reason
+        };
+
+        self.state.circuit_breaker.manual_trip(&reason).await;
+        for executor in self.state.executors.read().await.values() {
+            executor
+                .read()
+                .await
+                .circuit_breaker()
+                .manual_trip(&reason)
+                .await;
+        }
+        warn!(reason = %reason, "Engine halted");
+        self.state.broadcast_alert(AlertUpdate {
+            level: "critical".to_string(),
+            message: format!("Engine halted: {reason}"),
+            timestamp: chrono::Utc::now(),
+            position_address: None,
+            tags: Vec::new(),
+        });
+
+        Ok(Response::new(self.engine_status().await))
+    }
+
+    async fn resume(
+        &self,
+        _request: Request<proto::ResumeRequest>,
+    ) -> Result<Response<proto::EngineStatus>, Status> {
+        self.state.circuit_breaker.reset().await;
+        self.state.drawdown_guard.reset().await;
+        for executor in self.state.executors.read().await.values() {
+            executor.read().await.circuit_breaker().reset().await;
+        }
+        info!("Engine resumed");
+        self.state.broadcast_alert(AlertUpdate {
+            level: "info".to_string(),
+            message: "Engine resumed".to_string(),
+            timestamp: chrono::Utc::now(),
+            position_address: None,
+            tags: Vec::new(),
+        });
+
+        Ok(Response::new(self.engine_status().await))
+    }
+
+    async fn get_engine_status(
+        &self,
+        _request: Request<proto::GetEngineStatusRequest>,
+    ) -> Result<Response<proto::EngineStatus>, Status> {
+        Ok(Response::new(self.engine_status().await))
+    }
+}
+
+/// Accepts requests carrying one of the configured API keys in the
+/// `x-api-key` metadata. With no keys configured, every request passes.
+#[derive(Debug, Clone)]
+pub struct ApiKeyInterceptor {
+    api_keys: Arc<HashSet<String>>,
+}
+
+impl ApiKeyInterceptor {
+    /// Creates an interceptor accepting `api_keys`.
+    pub fn new(api_keys: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            api_keys: Arc::new(api_keys.into_iter().collect()),
+        }
+    }
+}
+
+impl Interceptor for ApiKeyInterceptor {
+    fn call(&mut self, request: Request<()>) -> Result<Request<()>, Status> {
+        if self.api_keys.is_empty() {
+            return Ok(request);
+        }
+        match request
+            .metadata()
+            .get(API_KEY_METADATA)
+            .and_then(|key| key.to_str().ok())
+        {
+            Some(key) if self.api_keys.contains(key) => Ok(request),
+            Some(_) => Err(Status::unauthenticated("Invalid API key")),
+            None => Err(Status::unauthenticated("Missing API key")),
+        }
+    }
+}
+
+/// Builds the engine service, guarded by the API keys in the state's config.
+pub fn engine_service(
+    state: AppState,
+) -> InterceptedService<EngineServiceServer<EngineGrpcService>, ApiKeyInterceptor> {
+    let interceptor = ApiKeyInterceptor::new(state.config.api_keys.clone());
+    EngineServiceServer::with_interceptor(EngineGrpcService::new(state), interceptor)
+}
+
+/// Serves the engine service on `addr` until `shutdown` completes.
+pub async fn serve(
+    state: AppState,
+    addr: SocketAddr,
+    shutdown: impl Future<Output = ()>,
+) -> anyhow::Result<()> {
+    info!(address = %addr, "Starting gRPC server");
+    tonic::transport::Server::builder()
+        .add_service(engine_service(state))
+        .serve_with_shutdown(addr, shutdown)
+        .await?;
+    info!("gRPC server stopped");
+    Ok(())
+}
+
+impl From<ApiError> for Status {
+    fn from(err: ApiError) -> Self {
+        let message = err.to_string();
+        match err {
+            ApiError::BadRequest(_) | ApiError::Validation(_) => Status::invalid_argument(message),
+            ApiError::Unauthorized(_) => Status::unauthenticated(message),
+            ApiError::Forbidden(_) => Status::permission_denied(message),
+            ApiError::NotFound(_) => Status::not_found(message),
+            ApiError::Conflict(_) => Status::failed_precondition(message),
+            ApiError::Internal(_) => Status::internal(message),
+            ApiError::ServiceUnavailable(_) => Status::unavailable(message),
+        }
+    }
+}
+
+/// Positions and decision kinds a decision stream is limited to.
+struct DecisionFilter {
+    positions: HashSet<Pubkey>,
+    include_hold: bool,
+}
+
+impl DecisionFilter {
+    /// Parses the filter of a `StreamDecisions` request.
+    fn new(request: &proto::StreamDecisionsRequest) -> Result<Self, Status> {
+        let positions = request
+            .positions
+            .iter()
+            .map(|address| {
+                Pubkey::from_str(address).map_err(|_| {
+                    Status::invalid_argument(format!("Invalid position address: {address}"))
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            positions,
+            include_hold: request.include_hold,
+        })
+    }
+
+    /// Returns true if `record` passes the filter.
+    fn matches(&self, record: &DecisionRecord) -> bool {
+        (self.include_hold || !matches!(record.decision, Decision::Hold))
+            && (self.positions.is_empty() || self.positions.contains(&record.position))
+    }
+}
+
+/// Converts a decision record into its protobuf representation.
+fn decision_event(record: &DecisionRecord) -> proto::DecisionEvent {
+    use proto::decision_event::Decision as Kind;
+
+    let decision = match &record.decision {
+        Decision::Hold => Kind::Hold(proto::Hold {}),
+        Decision::Rebalance {
+            new_tick_lower,
+            new_tick_upper,
+        } => Kind::Rebalance(proto::Rebalance {
+            new_tick_lower: *new_tick_lower,
+            new_tick_upper: *new_tick_upper,
+        }),
+        Decision::Close => Kind::Close(proto::Close {}),
+        Decision::IncreaseLiquidity { amount } => Kind::IncreaseLiquidity(proto::LiquidityChange {
+            amount: amount.to_string(),
+        }),
+        Decision::DecreaseLiquidity { amount } => Kind::DecreaseLiquidity(proto::LiquidityChange {
+            amount: amount.to_string(),
+        }),
+        Decision::CollectFees => Kind::CollectFees(proto::CollectFees {}),
+    };
+
+    proto::DecisionEvent {
+        position: record.position.to_string(),
+        strategy: record.strategy.clone(),
+        reason: record.reason.clone(),
+        price: record.price.to_string(),
+        regime: record.regime.as_ref().map(|r| r.regime.to_string()),
+        timestamp_ms: record.timestamp.timestamp_millis(),
+        description: record.decision.description(),
+        decision: Some(decision),
+    }
+}
+
+/// Converts a REST position response into its protobuf representation.
+fn position(response: PositionResponse) -> proto::Position {
+    proto::Position {
+        address: response.address,
+        pool_address: response.pool_address,
+        protocol: protocol(response.protocol) as i32,
+        owner: response.owner,
+        tick_lower: response.tick_lower,
+        tick_upper: response.tick_upper,
+        liquidity: response.liquidity,
+        in_range: response.in_range,
+        value_usd: response.value_usd.to_string(),
+        net_pnl_usd: response.pnl.net_pnl_usd.to_string(),
+        net_pnl_pct: response.pnl.net_pnl_pct.to_string(),
+        fees_earned_usd: response.pnl.fees_earned_usd.to_string(),
+        il_pct: response.pnl.il_pct.to_string(),
+        health_score: response.health.map(|h| h.score.to_string()),
+        strategy_id: response.strategy_id,
+        tags: response.tags,
+        read_only: response.read_only,
+        stale_since_ms: response.stale_since.map(|t| t.timestamp_millis()),
+    }
+}
+
+fn protocol(protocol: PositionProtocol) -> proto::Protocol {
+    match protocol {
+        PositionProtocol::OrcaWhirlpool => proto::Protocol::OrcaWhirlpool,
+        PositionProtocol::RaydiumClmm => proto::Protocol::RaydiumClmm,
+        PositionProtocol::MeteoraDlmm => proto::Protocol::MeteoraDlmm,
+    }
+}
+
+/// Maps a protocol filter; unspecified means no filter.
+fn position_protocol(protocol: proto::Protocol) -> Option<PositionProtocol> {
+    match protocol {
+        proto::Protocol::Unspecified => None,
+        proto::Protocol::OrcaWhirlpool => Some(PositionProtocol::OrcaWhirlpool),
+        proto::Protocol::RaydiumClmm => Some(PositionProtocol::RaydiumClmm),
+        proto::Protocol::MeteoraDlmm => Some(PositionProtocol::MeteoraDlmm),
+    }
+}
+
+fn circuit_state(state: CircuitState) -> proto::CircuitState {
+    match state {
+        CircuitState::Closed => proto::CircuitState::Closed,
+        CircuitState::Open => proto::CircuitState::Open,
+        CircuitState::HalfOpen => proto::CircuitState::HalfOpen,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::ApiConfig;
+    use clmm_lp_protocols::prelude::RpcConfig;
+    use proto::engine_service_client::EngineServiceClient;
+    use rust_decimal::Decimal;
+    use tonic::transport::server::TcpIncoming;
+
+    fn record(decision: Decision) -> DecisionRecord {
+        DecisionRecord {
+            position: Pubkey::new_unique(),
+            decision,
+            reason: "price left range".to_string(),
+            strategy: Some("core".to_string()),
+            regime: None,
+            price: Decimal::new(10525, 2),
+            deduplicated: false,
+            timestamp: chrono::DateTime::from_timestamp_millis(1_700_000_000_123).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_decision_event_and_filter() {
+        let rebalance = record(Decision::Rebalance {
+            new_tick_lower: -120,
+            new_tick_upper: 240,
+        });
+        let event = decision_event(&rebalance);
+        assert_eq!(event.position, rebalance.position.to_string());
+        assert_eq!(event.price, "105.25");
+        assert_eq!(event.timestamp_ms, 1_700_000_000_123);
+        assert_eq!(event.strategy.as_deref(), Some("core"));
+        assert_eq!(
+            event.decision,
+            Some(proto::decision_event::Decision::Rebalance(
+                proto::Rebalance {
+                    new_tick_lower: -120,
+                    new_tick_upper: 240,
+                }
+            ))
+        );
+
+        let hold = record(Decision::Hold);
+        let all = DecisionFilter::new(&proto::StreamDecisionsRequest::default()).unwrap();
+        assert!(all.matches(&rebalance));
+        assert!(!all.matches(&hold));
+
+        let one = DecisionFilter::new(&proto::StreamDecisionsRequest {
+            positions: vec![hold.position.to_string()],
+            include_hold: true,
+        })
+        .unwrap();
+        assert!(one.matches(&hold));
+        assert!(!one.matches(&rebalance));
+
+        let invalid = DecisionFilter::new(&proto::StreamDecisionsRequest {
+            positions: vec!["not-a-pubkey".to_string()],
+            include_hold: false,
+        });
+        assert_eq!(invalid.err().unwrap().code(), tonic::Code::InvalidArgument);
+    }
+
+    #[test]
+    fn test_api_key_interceptor() {
+        let mut open = ApiKeyInterceptor::new(Vec::new());
+        assert!(open.call(Request::new(())).is_ok());
+
+        let mut guarded = ApiKeyInterceptor::new(vec!["secret".to_string()]);
+        let missing = guarded.call(Request::new(())).unwrap_err();
+        assert_eq!(missing.code(), tonic::Code::Unauthenticated);
+
+        let mut request = Request::new(());
+        request
+            .metadata_mut()
+            .insert(API_KEY_METADATA, "wrong".parse().unwrap());
+        assert!(guarded.call(request).is_err());
+
+        let mut request = Request::new(());
+        request
+            .metadata_mut()
+            .insert(API_KEY_METADATA, "secret".parse().unwrap());
+        assert!(guarded.call(request).is_ok());
+    }
+
+    #[test]
+    fn test_api_errors_map_to_status_codes() {
+        assert_eq!(
+            Status::from(ApiError::not_found("Strategy not found")).code(),
+            tonic::Code::NotFound
+        );
+        assert_eq!(
+            Status::from(ApiError::Conflict("running".to_string())).code(),
+            tonic::Code::FailedPrecondition
+        );
+        assert_eq!(
+            Status::from(ApiError::bad_request("Invalid position address")).code(),
+            tonic::Code::InvalidArgument
+        );
+    }
+
+    #[tokio::test]
+    async fn test_halt_and_decision_stream_over_grpc() {
+        let config = ApiConfig {
+            api_keys: vec!["secret".to_string()],
+            ..ApiConfig::default()
+        };
+        let state = AppState::new(RpcConfig::default(), config);
+        let incoming = TcpIncoming::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+        let addr = incoming.local_addr().unwrap();
+        let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(engine_service(state.clone()))
+                .serve_with_incoming_shutdown(incoming, async {
+                    let _ = stopped.await;
+                }),
+        );
+
+        let channel = tonic::transport::Endpoint::from_shared(format!("http://{addr}"))
+            .unwrap()
+            .connect()
+            .await
+            .unwrap();
+        let mut client =
+            EngineServiceClient::with_interceptor(channel, |mut request: Request<()>| {
+                request
+                    .metadata_mut()
+                    .insert(API_KEY_METADATA, "secret".parse().unwrap());
+                Ok(request)
+            });
+
+        let status = client
+            .halt(proto::HaltRequest::default())
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(status.circuit_state(), proto::CircuitState::Open);
+        let refused = client
+            .start_strategy(proto::StrategyRequest {
+                strategy_id: "missing".to_string(),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(refused.code(), tonic::Code::FailedPrecondition);
+        let status = client
+            .resume(proto::ResumeRequest {})
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(status.circuit_state(), proto::CircuitState::Closed);
+
+        let mut stream = client
+            .stream_decisions(proto::StreamDecisionsRequest::default())
+            .await
+            .unwrap()
+            .into_inner();
+        state.decision_updates.send(record(Decision::Hold)).unwrap();
+        let close = record(Decision::Close);
+        state.decision_updates.send(close.clone()).unwrap();
+        let event = stream.message().await.unwrap().unwrap();
+        assert_eq!(event.position, close.position.to_string());
+        assert_eq!(
+            event.decision,
+            Some(proto::decision_event::Decision::Close(proto::Close {}))
+        );
+
+        let _ = stop.send(());
+        drop(stream);
+        server.await.unwrap().unwrap();
+    }
+}
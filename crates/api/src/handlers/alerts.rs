@@ -0,0 +1,150 @@
+//! Alert rule handlers.
+
+use crate::error::{ApiError, ApiResult};
+use crate::models::{AlertRuleRequest, AlertRuleResponse, ListAlertRulesResponse, MessageResponse};
+use crate::services::AlertRuleService;
+use crate::services::alert_rule_service::rule_from_request;
+use crate::state::AppState;
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use clmm_lp_execution::prelude::{ConfigChange, ConfigSection, ConfigSource};
+use tracing::info;
+
+/// List stored alert rules.
+#[utoipa::path(
+    get,
+    path = "/alerts/rules",
+    tag = "Alerts",
+    responses(
+        (status = 200, description = "List of alert rules", body = ListAlertRulesResponse),
+        (status = 503, description = "No database configured")
+    )
+)]
+pub async fn list_alert_rules(
+    State(state): State<AppState>,
+) -> ApiResult<Json<ListAlertRulesResponse>> {
+    let rules = AlertRuleService::new(state).list().await?;
+    let total = rules.len();
+    Ok(Json(ListAlertRulesResponse { rules, total }))
+}
+
+/// Get a stored alert rule.
+#[utoipa::path(
+    get,
+    path = "/alerts/rules/{name}",
+    tag = "Alerts",
+    params(
+        ("name" = String, Path, description = "Rule name")
+    ),
+    responses(
+        (status = 200, description = "Alert rule", body = AlertRuleResponse),
+        (status = 404, description = "Rule not found")
+    )
+)]
+pub async fn get_alert_rule(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> ApiResult<Json<AlertRuleResponse>> {
+    Ok(Json(AlertRuleService::new(state).get(&name).await?))
+}
+
+/// Create an alert rule and apply it to the running monitor.
+#[utoipa::path(
+    post,
+    path = "/alerts/rules",
+    tag = "Alerts",
+    request_body = AlertRuleRequest,
+    responses(
+        (status = 201, description = "Alert rule created", body = AlertRuleResponse),
+        (status = 409, description = "Rule already exists"),
+        (status = 422, description = "Invalid rule")
+    )
+)]
+pub async fn create_alert_rule(
+    State(state): State<AppState>,
+    Json(request): Json<AlertRuleRequest>,
+) -> ApiResult<Json<AlertRuleResponse>> {
+    let rule = rule_from_request(request)?;
+    let response = AlertRuleService::new(state.clone())
+        .save(rule, false)
+        .await?;
+    record_change(&state, format!("created rule {}", response.name)).await;
+    info!(rule = %response.name, "Alert rule created");
+    Ok(Json(response))
+}
+
+/// Replace an alert rule and apply it to the running monitor.
+#[utoipa::path(
+    put,
+    path = "/alerts/rules/{name}",
+    tag = "Alerts",
+    params(
+        ("name" = String, Path, description = "Rule name")
+    ),
+    request_body = AlertRuleRequest,
+    responses(
+        (status = 200, description = "Alert rule updated", body = AlertRuleResponse),
+        (status = 404, description = "Rule not found"),
+        (status = 422, description = "Invalid rule")
+    )
+)]
+pub async fn update_alert_rule(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Json(mut request): Json<AlertRuleRequest>,
+) -> ApiResult<Json<AlertRuleResponse>> {
+    if request.name.trim().is_empty() {
+        request.name.clone_from(&name);
+    } else if request.name.trim() != name {
+        return Err(ApiError::Validation(
+            "Rule name does not match the path".to_string(),
+        ));
+    }
+    let rule = rule_from_request(request)?;
+    let response = AlertRuleService::new(state.clone())
+        .save(rule, true)
+        .await?;
+    record_change(&state, format!("updated rule {}", name)).await;
+    info!(rule = %name, "Alert rule updated");
+    Ok(Json(response))
+}
+
+/// Delete an alert rule and remove it from the running monitor.
+#[utoipa::path(
+    delete,
+    path = "/alerts/rules/{name}",
+    tag = "Alerts",
+    params(
+        ("name" = String, Path, description = "Rule name")
+    ),
+    responses(
+        (status = 200, description = "Alert rule deleted", body = MessageResponse),
+        (status = 404, description = "Rule not found")
+    )
+)]
+pub async fn delete_alert_rule(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> ApiResult<Json<MessageResponse>> {
+    AlertRuleService::new(state.clone()).delete(&name).await?;
+    record_change(&state, format!("deleted rule {}", name)).await;
+    info!(rule = %name, "Alert rule deleted");
+    Ok(Json(MessageResponse::new(format!(
+        "Alert rule {} deleted",
+        name
+    ))))
+}
+
+/// Records an alert rule change in the config audit log.
+async fn record_change(state: &AppState, summary: String) {
+    state
+        .config_audit
+        .record(ConfigChange::new(
+            ConfigSection::AlertRules,
+            ConfigSource::Api,
+            summary,
+        ))
+        .await;
+}
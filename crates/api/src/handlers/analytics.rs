@@ -1,10 +1,50 @@
 //! Analytics handlers.
 
 use crate::error::{ApiError, ApiResult};
-use crate::models::{PortfolioAnalyticsResponse, SimulationRequest, SimulationResponse};
+use crate::models::{
+    MetricComparisonResponse, NormalizedCurveResponse, OptimizationJobResponse,
+    OptimizationPathsResponse, OptimizationRequest, OptimizationValidationResponse,
+    PerformanceBucketResponse, PerformanceHistoryQuery, PerformanceHistoryResponse,
+    PerformancePeriod, PortfolioAnalyticsResponse, SimulationCompareQuery,
+    SimulationComparisonResponse, SimulationRequest, SimulationResponse, TagAnalyticsResponse,
+    VenueComparisonQuery, VenueComparisonResponse, VenueResponse,
+};
+use crate::services::OptimizationService;
 use crate::state::AppState;
-use axum::{Json, extract::State};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use clmm_lp_data::prelude::{PoolRecord, SavedSimulation, SimulationComparison};
+use clmm_lp_execution::prelude::{BucketPeriod, MonitoredPosition};
+use clmm_lp_optimization::prelude::{PoolMetrics, range_depth, rank_venues, realized_volatility};
+use clmm_lp_protocols::prelude::{RaydiumPoolReader, WhirlpoolReader};
 use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use std::collections::BTreeMap;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Maximum number of simulations in one comparison.
+const MAX_COMPARED_SIMULATIONS: usize = 10;
+
+/// Default number of points on the comparison curve axis.
+const DEFAULT_CURVE_POINTS: usize = 100;
+
+/// Maximum number of points on the comparison curve axis.
+const MAX_CURVE_POINTS: usize = 1000;
+
+/// Default range width of a venue comparison in percent.
+const DEFAULT_VENUE_RANGE_WIDTH_PCT: f64 = 10.0;
+
+/// Default days of history a venue comparison reads.
+const DEFAULT_VENUE_HISTORY_DAYS: u32 = 30;
+
+/// Maximum days of history a venue comparison reads.
+const MAX_VENUE_HISTORY_DAYS: u32 = 365;
+
+/// Holding period venues are compared over in days.
+const VENUE_HORIZON_DAYS: f64 = 30.0;
 
 /// Get portfolio analytics.
 #[utoipa::path(
@@ -74,11 +114,96 @@ pub async fn get_portfolio_analytics(
         positions_in_range: in_range_count,
         best_position,
         worst_position,
+        by_tag: tag_analytics(&state, &positions).await,
     };
 
     Ok(Json(response))
 }
 
+/// Aggregates monitored positions per tag, with rebalance counts from the
+/// lifecycle tracker.
+async fn tag_analytics(
+    state: &AppState,
+    positions: &[MonitoredPosition],
+) -> Vec<TagAnalyticsResponse> {
+    let mut by_tag: BTreeMap<&str, TagAnalyticsResponse> = BTreeMap::new();
+    for position in positions {
+        for tag in &position.tags {
+            let entry = by_tag.entry(tag).or_insert_with(|| TagAnalyticsResponse {
+                tag: tag.clone(),
+                positions: 0,
+                positions_in_range: 0,
+                total_value_usd: Decimal::ZERO,
+                total_pnl_usd: Decimal::ZERO,
+                total_fees_usd: Decimal::ZERO,
+                total_rebalances: 0,
+            });
+            entry.positions += 1;
+            if position.in_range {
+                entry.positions_in_range += 1;
+            }
+            entry.total_value_usd += position.pnl.current_value_usd;
+            entry.total_pnl_usd += position.pnl.net_pnl_usd;
+            entry.total_fees_usd += position.pnl.fees_usd;
+        }
+    }
+
+    let lifecycle_stats = state.lifecycle.get_aggregate_stats_by_tag().await;
+    by_tag
+        .into_values()
+        .map(|mut entry| {
+            entry.total_rebalances = lifecycle_stats
+                .get(&entry.tag)
+                .map_or(0, |stats| stats.total_rebalances);
+            entry
+        })
+        .collect()
+}
+
+/// Get portfolio performance over time.
+#[utoipa::path(
+    get,
+    path = "/analytics/performance",
+    tag = "Analytics",
+    params(PerformanceHistoryQuery),
+    responses(
+        (status = 200, description = "Performance per period", body = PerformanceHistoryResponse)
+    )
+)]
+pub async fn get_performance_history(
+    State(state): State<AppState>,
+    Query(query): Query<PerformanceHistoryQuery>,
+) -> ApiResult<Json<PerformanceHistoryResponse>> {
+    let period = match query.period {
+        PerformancePeriod::Daily => BucketPeriod::Daily,
+        PerformancePeriod::Weekly => BucketPeriod::Weekly,
+        PerformancePeriod::Monthly => BucketPeriod::Monthly,
+    };
+
+    let buckets = state
+        .lifecycle
+        .get_bucketed_stats(period, query.tag.as_deref())
+        .await
+        .into_iter()
+        .map(|b| PerformanceBucketResponse {
+            period_start: b.period_start,
+            fees_usd: b.fees_usd,
+            rewards_usd: b.rewards_usd,
+            realized_pnl_usd: b.realized_pnl_usd,
+            rebalances: b.rebalances,
+            migrations: b.migrations,
+            tx_costs_lamports: b.tx_costs_lamports,
+            positions_opened: b.positions_opened,
+            positions_closed: b.positions_closed,
+        })
+        .collect();
+
+    Ok(Json(PerformanceHistoryResponse {
+        period: query.period,
+        buckets,
+    }))
+}
+
 /// Run a simulation.
 #[utoipa::path(
     post,
@@ -111,7 +236,7 @@ pub async fn run_simulation(
     // For now, return placeholder response
 
     let response = SimulationResponse {
-        id: uuid::Uuid::new_v4().to_string(),
+        id: Uuid::new_v4().to_string(),
         pool_address: request.pool_address,
         tick_lower: request.tick_lower,
         tick_upper: request.tick_upper,
@@ -127,3 +252,369 @@ pub async fn run_simulation(
 
     Ok(Json(response))
 }
+
+/// Start a range optimization job.
+#[utoipa::path(
+    post,
+    path = "/analytics/optimize",
+    tag = "Analytics",
+    request_body = OptimizationRequest,
+    responses(
+        (status = 200, description = "Optimization job queued", body = OptimizationJobResponse),
+        (status = 400, description = "Invalid request")
+    )
+)]
+pub async fn start_optimization(
+    State(state): State<AppState>,
+    Json(request): Json<OptimizationRequest>,
+) -> ApiResult<Json<OptimizationJobResponse>> {
+    let job = OptimizationService::new(state).submit(request).await?;
+    Ok(Json(job))
+}
+
+/// Get the status and progress of an optimization job.
+#[utoipa::path(
+    get,
+    path = "/analytics/optimize/{id}",
+    tag = "Analytics",
+    params(
+        ("id" = String, Path, description = "Optimization job ID")
+    ),
+    responses(
+        (status = 200, description = "Optimization job status", body = OptimizationJobResponse),
+        (status = 404, description = "Job not found")
+    )
+)]
+pub async fn get_optimization_job(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<OptimizationJobResponse>> {
+    let job = OptimizationService::new(state).get(&id).await?;
+    Ok(Json(job))
+}
+
+/// Cancel a queued or running optimization job.
+#[utoipa::path(
+    delete,
+    path = "/analytics/optimize/{id}",
+    tag = "Analytics",
+    params(
+        ("id" = String, Path, description = "Optimization job ID")
+    ),
+    responses(
+        (status = 200, description = "Cancellation requested", body = OptimizationJobResponse),
+        (status = 404, description = "Job not found"),
+        (status = 409, description = "Job already finished")
+    )
+)]
+pub async fn cancel_optimization_job(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<OptimizationJobResponse>> {
+    let job = OptimizationService::new(state).cancel(&id).await?;
+    Ok(Json(job))
+}
+
+/// Get the Monte Carlo paths kept with an optimization result.
+///
+/// The paths at the requested percentiles of final net PnL, with their
+/// prices and PnL after each step, show the spread of outcomes behind a
+/// recommendation.
+#[utoipa::path(
+    get,
+    path = "/analytics/optimize/{id}/paths",
+    tag = "Analytics",
+    params(
+        ("id" = String, Path, description = "Optimization job ID")
+    ),
+    responses(
+        (status = 200, description = "Kept paths, lowest percentile first", body = OptimizationPathsResponse),
+        (status = 404, description = "Job not found"),
+        (status = 409, description = "Job not completed")
+    )
+)]
+pub async fn get_optimization_paths(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<OptimizationPathsResponse>> {
+    let paths = OptimizationService::new(state).paths(&id).await?;
+    Ok(Json(paths))
+}
+
+/// Get the simulations that backtested an optimization's recommendation.
+///
+/// Simulations saved with a source optimization are listed with their
+/// realized returns against the return the optimizer expected, showing how
+/// the recommendation performed out-of-sample.
+#[utoipa::path(
+    get,
+    path = "/analytics/optimize/{id}/simulations",
+    tag = "Analytics",
+    params(
+        ("id" = String, Path, description = "Optimization job ID")
+    ),
+    responses(
+        (status = 200, description = "Validating simulations, oldest first", body = OptimizationValidationResponse),
+        (status = 404, description = "Optimization not found"),
+        (status = 503, description = "No database configured")
+    )
+)]
+pub async fn get_optimization_simulations(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<OptimizationValidationResponse>> {
+    let validation = OptimizationService::new(state).validation(&id).await?;
+    Ok(Json(validation))
+}
+
+/// Compare the stored pools of a token pair across protocols and fee tiers.
+///
+/// Each pool is estimated for a new range of the requested width: its fee
+/// APR against the liquidity already within the range, weighted by the
+/// expected time in range at the pool's realized volatility.
+#[utoipa::path(
+    get,
+    path = "/analytics/venues",
+    tag = "Analytics",
+    params(VenueComparisonQuery),
+    responses(
+        (status = 200, description = "Pools compared best first", body = VenueComparisonResponse),
+        (status = 404, description = "No stored pools for the pair"),
+        (status = 422, description = "Invalid range width or history length"),
+        (status = 503, description = "No database configured")
+    )
+)]
+pub async fn compare_venues(
+    State(state): State<AppState>,
+    Query(query): Query<VenueComparisonQuery>,
+) -> ApiResult<Json<VenueComparisonResponse>> {
+    let database = state.require_database()?;
+
+    let range_width_pct = query
+        .range_width_pct
+        .unwrap_or(DEFAULT_VENUE_RANGE_WIDTH_PCT);
+    if !(range_width_pct > 0.0 && range_width_pct <= 1000.0) {
+        return Err(ApiError::Validation(
+            "Range width must be in (0, 1000] percent".to_string(),
+        ));
+    }
+    let days = query.days.unwrap_or(DEFAULT_VENUE_HISTORY_DAYS);
+    if !(1..=MAX_VENUE_HISTORY_DAYS).contains(&days) {
+        return Err(ApiError::Validation(format!(
+            "Days must be 1-{}",
+            MAX_VENUE_HISTORY_DAYS
+        )));
+    }
+    let range_width = range_width_pct / 100.0;
+
+    // Both protocols order a pair's mints the same way, so every pool
+    // quotes in the same token B
+    let pools = database
+        .pools()
+        .find_by_pair(&query.token_a, &query.token_b)
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+    if pools.is_empty() {
+        return Err(ApiError::not_found("No stored pools for the pair"));
+    }
+
+    let end = chrono::Utc::now().timestamp();
+    let start = end - i64::from(days) * 86_400;
+    let mut observed = Vec::new();
+    for pool in pools {
+        let Some((fee_bps, liquidity, raw_price)) = read_venue_state(&state, &pool).await else {
+            continue;
+        };
+        let candles = database
+            .prices()
+            .find_by_pool_and_range(pool.id, start, end)
+            .await
+            .map_err(|e| ApiError::internal(e.to_string()))?;
+
+        // Stored volume is in token A; value it at each candle's close
+        let mut daily_volumes: BTreeMap<i64, Decimal> = BTreeMap::new();
+        for candle in &candles {
+            *daily_volumes.entry(candle.timestamp / 86_400).or_default() +=
+                candle.volume.unwrap_or_default() * candle.close_price;
+        }
+        let closes: Vec<(u64, Decimal)> = candles
+            .iter()
+            .map(|c| {
+                (
+                    u64::try_from(c.timestamp).unwrap_or_default(),
+                    c.close_price,
+                )
+            })
+            .collect();
+
+        let scale = 10f64.powi(i32::from(pool.decimals_b));
+        let range_tvl = Decimal::from_f64(range_depth(liquidity, raw_price, range_width) / scale)
+            .unwrap_or_default();
+        observed.push((
+            pool,
+            fee_bps,
+            daily_volumes.into_values().collect::<Vec<_>>(),
+            range_tvl,
+            realized_volatility(&closes),
+        ));
+    }
+
+    // Pools of a pair share its price, so any pool's history stands in for
+    // one without enough of its own
+    let pair_volatility = observed
+        .iter()
+        .find_map(|(_, _, _, _, volatility)| *volatility)
+        .unwrap_or(0.0);
+    let venues: Vec<_> = observed
+        .into_iter()
+        .map(|(pool, fee_bps, daily_volumes, range_tvl, volatility)| {
+            let metrics = PoolMetrics::new(
+                Decimal::from(fee_bps) / Decimal::from(10_000),
+                daily_volumes,
+                range_tvl,
+                volatility.unwrap_or(pair_volatility),
+            );
+            ((pool, fee_bps, metrics.clone()), metrics)
+        })
+        .collect();
+
+    let venues: Vec<VenueResponse> = rank_venues(venues, range_width, VENUE_HORIZON_DAYS)
+        .into_iter()
+        .map(|((pool, fee_bps, metrics), estimate)| VenueResponse {
+            pool_address: pool.address,
+            protocol: pool.protocol,
+            fee_tier_bps: fee_bps,
+            range_tvl: metrics.tvl_usd.round_dp(2),
+            mean_daily_volume: metrics.mean_daily_volume().round_dp(2),
+            fee_apr: estimate.fee_apr.round_dp(6),
+            volatility: metrics.volatility,
+            time_in_range: estimate.time_in_range,
+            expected_fee_apr: estimate.expected_fee_apr.round_dp(6),
+        })
+        .collect();
+
+    Ok(Json(VenueComparisonResponse {
+        range_width_pct,
+        recommended: venues
+            .first()
+            .filter(|venue| venue.expected_fee_apr > Decimal::ZERO)
+            .map(|venue| venue.pool_address.clone()),
+        venues,
+    }))
+}
+
+/// Reads a pool's fee tier in basis points, active liquidity and raw price
+/// from chain, or `None` if it cannot be read.
+async fn read_venue_state(state: &AppState, pool: &PoolRecord) -> Option<(u16, u128, f64)> {
+    let result = match pool.protocol.as_str() {
+        "orca" => WhirlpoolReader::new(state.provider.clone())
+            .get_pool_state(&pool.address)
+            .await
+            .map(|s| {
+                let fee_bps = s.fee_bps().round().to_u16().unwrap_or_default();
+                (fee_bps, s.liquidity, s.price)
+            }),
+        "raydium" => RaydiumPoolReader::new(state.provider.clone())
+            .get_pool_state(&pool.address)
+            .await
+            .map(|s| {
+                let fee_bps = u16::try_from(pool.fee_tier).unwrap_or_default();
+                (fee_bps, s.liquidity, s.price)
+            }),
+        other => {
+            warn!(pool = %pool.address, protocol = other, "Unsupported protocol, skipping pool");
+            return None;
+        }
+    };
+    match result {
+        Ok((fee_bps, liquidity, price)) => Some((fee_bps, liquidity, price.to_f64()?)),
+        Err(e) => {
+            warn!(pool = %pool.address, error = %e, "Failed to read pool state, skipping pool");
+            None
+        }
+    }
+}
+
+/// Compare saved simulations against the first one.
+#[utoipa::path(
+    get,
+    path = "/simulations/compare",
+    tag = "Analytics",
+    params(SimulationCompareQuery),
+    responses(
+        (status = 200, description = "Simulation comparison", body = SimulationComparisonResponse),
+        (status = 400, description = "Invalid simulation ID"),
+        (status = 404, description = "Simulation or results not found"),
+        (status = 422, description = "Too few or too many simulations"),
+        (status = 503, description = "No database configured")
+    )
+)]
+pub async fn compare_simulations(
+    State(state): State<AppState>,
+    Query(query): Query<SimulationCompareQuery>,
+) -> ApiResult<Json<SimulationComparisonResponse>> {
+    let database = state.require_database()?;
+
+    let ids = query
+        .ids
+        .split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(|id| {
+            Uuid::parse_str(id)
+                .map_err(|_| ApiError::bad_request(format!("Invalid simulation ID: {}", id)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if !(2..=MAX_COMPARED_SIMULATIONS).contains(&ids.len()) {
+        return Err(ApiError::Validation(format!(
+            "Compare 2-{} simulations",
+            MAX_COMPARED_SIMULATIONS
+        )));
+    }
+    let points = query.points.unwrap_or(DEFAULT_CURVE_POINTS);
+    if !(2..=MAX_CURVE_POINTS).contains(&points) {
+        return Err(ApiError::Validation(format!(
+            "Points must be 2-{}",
+            MAX_CURVE_POINTS
+        )));
+    }
+
+    let repository = database.simulations();
+    let mut simulations = Vec::with_capacity(ids.len());
+    for id in ids {
+        let saved = SavedSimulation::load(&repository, id)
+            .await
+            .map_err(|e| ApiError::internal(e.to_string()))?
+            .ok_or_else(|| ApiError::not_found(format!("Simulation results not found: {}", id)))?;
+        simulations.push(saved);
+    }
+
+    let comparison = SimulationComparison::build(&simulations, points);
+
+    Ok(Json(SimulationComparisonResponse {
+        simulation_ids: comparison
+            .simulation_ids
+            .iter()
+            .map(Uuid::to_string)
+            .collect(),
+        axis: comparison.axis,
+        metrics: comparison
+            .metrics
+            .into_iter()
+            .map(|m| MetricComparisonResponse {
+                metric: m.metric.to_string(),
+                values: m.values,
+                diffs: m.diffs,
+            })
+            .collect(),
+        curves: comparison
+            .curves
+            .into_iter()
+            .map(|c| NormalizedCurveResponse {
+                simulation_id: c.simulation_id.to_string(),
+                values: c.values,
+            })
+            .collect(),
+    }))
+}
@@ -57,7 +57,16 @@ pub async fn health_check(State(state): State<AppState>) -> ApiResult<Json<Healt
     // Check RPC health
     let rpc_healthy = state.provider.get_slot().await.is_ok();
 
-    let status = if rpc_healthy && circuit_state == CircuitState::Closed {
+    let stalled_loops: Vec<String> = state
+        .heartbeats
+        .status()
+        .into_iter()
+        .filter(|beat| beat.stalled)
+        .map(|beat| beat.name)
+        .collect();
+
+    let status = if rpc_healthy && circuit_state == CircuitState::Closed && stalled_loops.is_empty()
+    {
         ServiceStatus::Healthy
     } else if rpc_healthy {
         ServiceStatus::Degraded
@@ -73,6 +82,7 @@ pub async fn health_check(State(state): State<AppState>) -> ApiResult<Json<Healt
             rpc: rpc_healthy,
             database: true, // Placeholder - no DB yet
             circuit_breaker: circuit_status,
+            stalled_loops,
         },
     };
 
@@ -129,6 +139,7 @@ pub async fn readiness(State(state): State<AppState>) -> Result<&'static str, &'
 pub async fn metrics(State(state): State<AppState>) -> ApiResult<Json<MetricsResponse>> {
     let positions = state.monitor.get_positions().await;
     let strategies = state.strategies.read().await;
+    let slot_order = state.slot_orderer.stats();
 
     let response = MetricsResponse {
         request_count: REQUEST_COUNT.load(std::sync::atomic::Ordering::Relaxed),
@@ -137,6 +148,8 @@ pub async fn metrics(State(state): State<AppState>) -> ApiResult<Json<MetricsRes
         active_ws_connections: 0,  // Placeholder
         positions_monitored: positions.len() as u32,
         strategies_running: strategies.values().filter(|s| s.running).count() as u32,
+        stale_updates_dropped: slot_order.stale_dropped,
+        duplicate_updates_dropped: slot_order.duplicates_dropped,
     };
 
     Ok(Json(response))
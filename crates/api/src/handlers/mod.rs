@@ -1,11 +1,13 @@
 //! Request handlers for API endpoints.
 
+pub mod alerts;
 pub mod analytics;
 pub mod health;
 pub mod pools;
 pub mod positions;
 pub mod strategies;
 
+pub use alerts::*;
 pub use analytics::*;
 pub use health::*;
 pub use pools::*;
@@ -1,16 +1,56 @@
 //! Pool handlers.
 
 use crate::error::{ApiError, ApiResult};
-use crate::models::{ListPoolsResponse, PoolResponse, PoolStateResponse};
+use crate::models::{
+    CapitalEfficiencyQuery, CapitalEfficiencyResponse, LeaderboardEntryResponse,
+    ListPausedPoolsResponse, ListPoolsResponse, MessageResponse, OptimizationLeaderboardQuery,
+    OptimizationLeaderboardResponse, PausedPoolResponse, PoolResponse, PoolRewardResponse,
+    PoolRiskResponse, PoolStateResponse, PriceDownsampling, PriceHistoryQuery,
+    PriceHistoryResponse, PricePointResponse, RangeUtilizationQuery, RangeUtilizationResponse,
+    RecommendationDriftResponse, TokenRiskResponse, UtilizationBucketResponse,
+};
 use crate::state::AppState;
 use axum::{
     Json,
-    extract::{Path, State},
+    extract::{Path, Query, State},
+};
+use clmm_lp_data::prelude::{
+    DownsampleMethod, OptimizationLeaderboard, RangeUtilization, downsample_candles,
 };
-use clmm_lp_protocols::prelude::WhirlpoolReader;
+use clmm_lp_domain::prelude::{Price, capital_efficiency};
+use clmm_lp_protocols::prelude::{TokenRiskChecker, TokenRiskReport, WhirlpoolReader};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
 use solana_sdk::pubkey::Pubkey;
 use std::str::FromStr;
 
+/// Default number of ranked optimizations in a leaderboard.
+const DEFAULT_LEADERBOARD_LIMIT: usize = 10;
+
+/// Default capital for capital efficiency, in token B.
+const DEFAULT_EFFICIENCY_CAPITAL: i64 = 1000;
+
+/// Default number of points in a price history.
+const DEFAULT_PRICE_POINTS: usize = 1000;
+
+/// Maximum number of points in a price history.
+const MAX_PRICE_POINTS: usize = 10_000;
+
+/// Default length of a price history in seconds (90 days).
+const DEFAULT_PRICE_RANGE_SECS: i64 = 90 * 86_400;
+
+/// Default days of history a utilization heatmap reads.
+const DEFAULT_UTILIZATION_DAYS: u32 = 30;
+
+/// Maximum days of history a utilization heatmap reads.
+const MAX_UTILIZATION_DAYS: u32 = 365;
+
+/// Default number of buckets in a utilization heatmap.
+const DEFAULT_UTILIZATION_BUCKETS: usize = 50;
+
+/// Maximum number of buckets in a utilization heatmap.
+const MAX_UTILIZATION_BUCKETS: usize = 1000;
+
 /// List available pools.
 #[utoipa::path(
     get,
@@ -56,6 +96,18 @@ pub async fn get_pool(
         .await
         .map_err(|e| ApiError::not_found(format!("Pool not found: {}", e)))?;
 
+    let rewards = pool_state
+        .rewards
+        .iter()
+        .map(|reward| PoolRewardResponse {
+            index: reward.index,
+            mint: reward.mint.to_string(),
+            emissions_per_day: reward.emissions_per_day(),
+            reward_apr: None, // Would need reward price and TVL
+        })
+        .collect();
+    let fee_rate_bps = pool_state.fee_bps().round().to_u16().unwrap_or_default();
+
     let response = PoolResponse {
         address: pool_state.address,
         protocol: "orca_whirlpool".to_string(),
@@ -65,10 +117,11 @@ pub async fn get_pool(
         tick_spacing: pool_state.tick_spacing as i32,
         price: pool_state.price,
         liquidity: pool_state.liquidity.to_string(),
-        fee_rate_bps: pool_state.fee_rate_bps,
+        fee_rate_bps,
         volume_24h_usd: None,
         tvl_usd: None,
         apy_estimate: None,
+        rewards,
     };
 
     Ok(Json(response))
@@ -114,3 +167,455 @@ pub async fn get_pool_state(
 
     Ok(Json(response))
 }
+
+/// Get the capital efficiency of a range at the pool's current price and liquidity.
+#[utoipa::path(
+    get,
+    path = "/pools/{address}/capital-efficiency",
+    tag = "Pools",
+    params(
+        ("address" = String, Path, description = "Pool address"),
+        CapitalEfficiencyQuery
+    ),
+    responses(
+        (status = 200, description = "Capital efficiency of the range", body = CapitalEfficiencyResponse),
+        (status = 404, description = "Pool not found"),
+        (status = 422, description = "Invalid range or capital")
+    )
+)]
+pub async fn get_capital_efficiency(
+    State(state): State<AppState>,
+    Path(address): Path<String>,
+    Query(query): Query<CapitalEfficiencyQuery>,
+) -> ApiResult<Json<CapitalEfficiencyResponse>> {
+    let _pubkey =
+        Pubkey::from_str(&address).map_err(|_| ApiError::bad_request("Invalid pool address"))?;
+
+    let pool = WhirlpoolReader::new(state.provider.clone())
+        .get_pool_state(&address)
+        .await
+        .map_err(|e| ApiError::not_found(format!("Pool not found: {}", e)))?;
+    let decimals_a = state
+        .provider
+        .get_mint_decimals(&pool.token_mint_a)
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+    let decimals_b = state
+        .provider
+        .get_mint_decimals(&pool.token_mint_b)
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    // The pool price and liquidity are in raw token units; prices are scaled
+    // by the difference in decimals, so both mints must be scalable
+    token_unit(decimals_a)?;
+    let capital = query
+        .capital
+        .unwrap_or(Decimal::from(DEFAULT_EFFICIENCY_CAPITAL));
+    let raw_capital = capital
+        .checked_mul(token_unit(decimals_b)?)
+        .ok_or_else(|| ApiError::Validation(format!("Capital {} is too large", capital)))?;
+    let efficiency = capital_efficiency(
+        pool.price,
+        Price::new(query.lower_price).to_raw(decimals_a, decimals_b),
+        Price::new(query.upper_price).to_raw(decimals_a, decimals_b),
+        raw_capital,
+        pool.liquidity,
+    )
+    .map_err(|e| ApiError::Validation(e.to_string()))?;
+
+    Ok(Json(CapitalEfficiencyResponse {
+        pool_address: address,
+        current_price: Price::from_raw(pool.price, decimals_a, decimals_b).value,
+        lower_price: query.lower_price,
+        upper_price: query.upper_price,
+        capital,
+        efficiency_multiple: efficiency.multiplier,
+        liquidity: efficiency.liquidity.to_string(),
+        active_liquidity: pool.liquidity.to_string(),
+        fee_share_pct: efficiency.fee_share * Decimal::ONE_HUNDRED,
+        lower_band_pct: efficiency.lower_band_pct,
+        upper_band_pct: efficiency.upper_band_pct,
+        in_range: efficiency.in_range,
+    }))
+}
+
+/// Returns one whole token in base units, or a validation error for a mint
+/// with more decimals than a token amount can hold.
+fn token_unit(decimals: u8) -> ApiResult<Decimal> {
+    10u64
+        .checked_pow(u32::from(decimals))
+        .map(Decimal::from)
+        .ok_or_else(|| ApiError::Validation(format!("Unsupported token decimals: {}", decimals)))
+}
+
+/// Screen a pool's tokens for mint, freeze and holder concentration risks.
+#[utoipa::path(
+    get,
+    path = "/pools/{address}/risk",
+    tag = "Pools",
+    params(
+        ("address" = String, Path, description = "Pool address")
+    ),
+    responses(
+        (status = 200, description = "Token risk screening", body = PoolRiskResponse),
+        (status = 404, description = "Pool not found")
+    )
+)]
+pub async fn get_pool_risk(
+    State(state): State<AppState>,
+    Path(address): Path<String>,
+) -> ApiResult<Json<PoolRiskResponse>> {
+    let _pubkey =
+        Pubkey::from_str(&address).map_err(|_| ApiError::bad_request("Invalid pool address"))?;
+
+    let report = TokenRiskChecker::new(state.provider.clone())
+        .check_pool(&address)
+        .await
+        .map_err(|e| ApiError::not_found(format!("Pool not found: {}", e)))?;
+
+    Ok(Json(PoolRiskResponse {
+        blocked: report.is_blocked(),
+        pool_address: report.pool,
+        token_a: token_risk_response(report.token_a),
+        token_b: token_risk_response(report.token_b),
+    }))
+}
+
+/// List pools strategies are paused on after a liquidity, volume or fee tier
+/// anomaly.
+#[utoipa::path(
+    get,
+    path = "/pools/anomalies",
+    tag = "Pools",
+    responses(
+        (status = 200, description = "Paused pools", body = ListPausedPoolsResponse)
+    )
+)]
+pub async fn list_paused_pools(
+    State(state): State<AppState>,
+) -> ApiResult<Json<ListPausedPoolsResponse>> {
+    let pools: Vec<PausedPoolResponse> = state
+        .anomaly_detector
+        .paused_pools()
+        .await
+        .into_iter()
+        .map(|paused| PausedPoolResponse {
+            pool_address: paused.pool.to_string(),
+            paused_at: paused.paused_at,
+            anomalies: paused.anomalies.iter().map(ToString::to_string).collect(),
+        })
+        .collect();
+    let total = pools.len();
+    Ok(Json(ListPausedPoolsResponse { pools, total }))
+}
+
+/// Resume strategies on a pool paused after an anomaly.
+#[utoipa::path(
+    post,
+    path = "/pools/{address}/resume",
+    tag = "Pools",
+    params(
+        ("address" = String, Path, description = "Pool address")
+    ),
+    responses(
+        (status = 200, description = "Strategies resumed", body = MessageResponse),
+        (status = 404, description = "Pool is not paused")
+    )
+)]
+pub async fn resume_pool(
+    State(state): State<AppState>,
+    Path(address): Path<String>,
+) -> ApiResult<Json<MessageResponse>> {
+    let pool =
+        Pubkey::from_str(&address).map_err(|_| ApiError::bad_request("Invalid pool address"))?;
+    if !state.anomaly_detector.resume(&pool).await {
+        return Err(ApiError::not_found(format!(
+            "Pool is not paused: {}",
+            address
+        )));
+    }
+    Ok(Json(MessageResponse::new(format!(
+        "Strategies resumed on pool {}",
+        address
+    ))))
+}
+
+/// Converts a mint screening report to its API representation.
+fn token_risk_response(report: TokenRiskReport) -> TokenRiskResponse {
+    TokenRiskResponse {
+        mint: report.mint.to_string(),
+        trusted: report.trusted,
+        blocked: report.is_blocked(),
+        risks: report.risks.iter().map(ToString::to_string).collect(),
+        top_holders_share: report.top_holders_share,
+    }
+}
+
+/// Rank stored optimizations of a pool and show how recommendations drifted.
+#[utoipa::path(
+    get,
+    path = "/pools/{address}/optimizations",
+    tag = "Pools",
+    params(
+        ("address" = String, Path, description = "Pool address"),
+        OptimizationLeaderboardQuery
+    ),
+    responses(
+        (status = 200, description = "Optimization leaderboard", body = OptimizationLeaderboardResponse),
+        (status = 404, description = "Pool not stored"),
+        (status = 503, description = "No database configured")
+    )
+)]
+pub async fn get_optimization_leaderboard(
+    State(state): State<AppState>,
+    Path(address): Path<String>,
+    Query(query): Query<OptimizationLeaderboardQuery>,
+) -> ApiResult<Json<OptimizationLeaderboardResponse>> {
+    let database = state.require_database()?;
+
+    let pool = database
+        .pools()
+        .find_by_address(&address)
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?
+        .ok_or_else(|| ApiError::not_found(format!("Pool not stored: {}", address)))?;
+
+    let objective_type = query.objective.as_str();
+    let records = database
+        .simulations()
+        .find_optimizations_by_pool(pool.id, objective_type)
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    let leaderboard = OptimizationLeaderboard::build(
+        objective_type,
+        &records,
+        query.limit.unwrap_or(DEFAULT_LEADERBOARD_LIMIT),
+    );
+
+    let entries = leaderboard
+        .entries
+        .into_iter()
+        .map(|entry| LeaderboardEntryResponse {
+            rank: entry.rank,
+            optimization_id: entry.record.id.to_string(),
+            objective_value: entry.objective_value,
+            recommended_lower: entry.record.recommended_lower,
+            recommended_upper: entry.record.recommended_upper,
+            volatility: entry.record.volatility,
+            initial_capital: entry.record.initial_capital,
+            expected_pnl: entry.record.expected_pnl,
+            expected_fees: entry.record.expected_fees,
+            expected_il: entry.record.expected_il,
+            sharpe_ratio: entry.record.sharpe_ratio,
+            risk_free_rate: entry.record.risk_free_rate,
+            benchmark: entry.record.sharpe_benchmark,
+            simulations_run: entry.record.simulations_run,
+            created_at: entry.record.created_at,
+        })
+        .collect();
+
+    let drift = leaderboard
+        .drift
+        .into_iter()
+        .map(|d| RecommendationDriftResponse {
+            optimization_id: d.optimization_id.to_string(),
+            created_at: d.created_at,
+            volatility: d.volatility,
+            recommended_lower: d.recommended_lower,
+            recommended_upper: d.recommended_upper,
+            midpoint_change_pct: d.midpoint_change_pct,
+            width_change_pct: d.width_change_pct,
+            volatility_change: d.volatility_change,
+        })
+        .collect();
+
+    Ok(Json(OptimizationLeaderboardResponse {
+        pool_address: address,
+        objective: query.objective,
+        entries,
+        drift,
+    }))
+}
+
+/// Get a pool's stored price history, downsampled to at most `target_points`.
+#[utoipa::path(
+    get,
+    path = "/pools/{address}/prices",
+    tag = "Pools",
+    params(
+        ("address" = String, Path, description = "Pool address"),
+        PriceHistoryQuery
+    ),
+    responses(
+        (status = 200, description = "Price history", body = PriceHistoryResponse),
+        (status = 404, description = "Pool not stored"),
+        (status = 422, description = "Invalid range or point count"),
+        (status = 503, description = "No database configured")
+    )
+)]
+pub async fn get_price_history(
+    State(state): State<AppState>,
+    Path(address): Path<String>,
+    Query(query): Query<PriceHistoryQuery>,
+) -> ApiResult<Json<PriceHistoryResponse>> {
+    let database = state.require_database()?;
+
+    let target = query.target_points.unwrap_or(DEFAULT_PRICE_POINTS);
+    if !(2..=MAX_PRICE_POINTS).contains(&target) {
+        return Err(ApiError::Validation(format!(
+            "Target points must be 2-{}",
+            MAX_PRICE_POINTS
+        )));
+    }
+    let end = query.end.unwrap_or_else(|| chrono::Utc::now().timestamp());
+    let start = query.start.unwrap_or(end - DEFAULT_PRICE_RANGE_SECS);
+    if start >= end {
+        return Err(ApiError::Validation("Start must be before end".to_string()));
+    }
+
+    let pool = database
+        .pools()
+        .find_by_address(&address)
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?
+        .ok_or_else(|| ApiError::not_found(format!("Pool not stored: {}", address)))?;
+    let candles: Vec<_> = database
+        .prices()
+        .find_by_pool_and_range(pool.id, start, end)
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?
+        .iter()
+        .map(|record| record.to_candle())
+        .collect();
+
+    let method = match query.method {
+        PriceDownsampling::Lttb => DownsampleMethod::Lttb,
+        PriceDownsampling::Ohlc => DownsampleMethod::Ohlc,
+    };
+    let points = downsample_candles(&candles, target, method)
+        .into_iter()
+        .map(|c| PricePointResponse {
+            timestamp: c.timestamp,
+            open: c.open,
+            high: c.high,
+            low: c.low,
+            close: c.close,
+            volume: c.volume,
+        })
+        .collect();
+
+    Ok(Json(PriceHistoryResponse {
+        pool_address: address,
+        method: query.method,
+        source_points: candles.len(),
+        points,
+    }))
+}
+
+/// Get the time a pool's price spent in each bucket of its tick grid.
+///
+/// Stored prices over the lookback window are bucketed into the tick grid,
+/// showing where liquidity would have been utilized before choosing bounds.
+#[utoipa::path(
+    get,
+    path = "/pools/{address}/utilization",
+    tag = "Pools",
+    params(
+        ("address" = String, Path, description = "Pool address"),
+        RangeUtilizationQuery
+    ),
+    responses(
+        (status = 200, description = "Time spent per bucket, lowest price first", body = RangeUtilizationResponse),
+        (status = 404, description = "Pool not stored"),
+        (status = 422, description = "Invalid lookback or bucket count"),
+        (status = 503, description = "No database configured")
+    )
+)]
+pub async fn get_range_utilization(
+    State(state): State<AppState>,
+    Path(address): Path<String>,
+    Query(query): Query<RangeUtilizationQuery>,
+) -> ApiResult<Json<RangeUtilizationResponse>> {
+    let database = state.require_database()?;
+
+    let days = query.days.unwrap_or(DEFAULT_UTILIZATION_DAYS);
+    if !(1..=MAX_UTILIZATION_DAYS).contains(&days) {
+        return Err(ApiError::Validation(format!(
+            "Days must be 1-{}",
+            MAX_UTILIZATION_DAYS
+        )));
+    }
+    let max_buckets = query.max_buckets.unwrap_or(DEFAULT_UTILIZATION_BUCKETS);
+    if !(1..=MAX_UTILIZATION_BUCKETS).contains(&max_buckets) {
+        return Err(ApiError::Validation(format!(
+            "Max buckets must be 1-{}",
+            MAX_UTILIZATION_BUCKETS
+        )));
+    }
+
+    let pool = database
+        .pools()
+        .find_by_address(&address)
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?
+        .ok_or_else(|| ApiError::not_found(format!("Pool not stored: {}", address)))?;
+    let end = chrono::Utc::now().timestamp();
+    let start = end - i64::from(days) * 86_400;
+    let points: Vec<(u64, Decimal)> = database
+        .prices()
+        .find_by_pool_and_range(pool.id, start, end)
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?
+        .iter()
+        .map(|record| {
+            let candle = record.to_candle();
+            (candle.timestamp, candle.close)
+        })
+        .collect();
+
+    let utilization = RangeUtilization::build(
+        &points,
+        pool.tick_spacing,
+        max_buckets,
+        u8::try_from(pool.decimals_a).unwrap_or_default(),
+        u8::try_from(pool.decimals_b).unwrap_or_default(),
+    );
+
+    Ok(Json(RangeUtilizationResponse {
+        pool_address: address,
+        tick_spacing: pool.tick_spacing,
+        bucket_ticks: utilization.bucket_ticks,
+        start,
+        end,
+        total_seconds: utilization.total_seconds,
+        buckets: utilization
+            .buckets
+            .into_iter()
+            .map(|b| UtilizationBucketResponse {
+                tick_lower: b.tick_lower,
+                tick_upper: b.tick_upper,
+                price_lower: b.price_lower,
+                price_upper: b.price_upper,
+                seconds: b.seconds,
+                share_pct: b.share_pct,
+            })
+            .collect(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_unit_rejects_unsupported_decimals() {
+        assert_eq!(token_unit(6).unwrap(), Decimal::from(1_000_000));
+        assert_eq!(
+            token_unit(19).unwrap(),
+            Decimal::from(10_000_000_000_000_000_000u64)
+        );
+        assert!(matches!(token_unit(20), Err(ApiError::Validation(_))));
+    }
+}
@@ -2,39 +2,60 @@
 
 use crate::error::{ApiError, ApiResult};
 use crate::models::{
-    ListPositionsResponse, MessageResponse, OpenPositionRequest, PnLResponse, PositionResponse,
-    PositionStatus, RebalanceRequest,
+    AssignStrategyRequest, ListPositionsQuery, ListPositionsResponse, MessageResponse,
+    MigratePositionRequest, MigrationResponse, OpenPositionRequest, PnLResponse,
+    PositionHealthResponse, PositionProtocol, PositionReadOnlyResponse, PositionResponse,
+    PositionStatus, PositionStrategyResponse, PositionTagsResponse, RebalanceRequest,
+    SetPositionReadOnlyRequest, SetPositionTagsRequest, WhatIfRequest, WhatIfResponse,
 };
+use crate::services::ReplayService;
 use crate::state::{AlertUpdate, AppState, PositionUpdate};
 use axum::{
     Json,
-    extract::{Path, State},
+    extract::{Path, Query, State},
 };
-use clmm_lp_execution::prelude::{RebalanceData, RebalanceReason};
-use clmm_lp_protocols::prelude::WhirlpoolReader;
+use clmm_lp_execution::prelude::{
+    DecisionOverrides, HealthScore, MigrationConfig, MigrationExecutor, MigrationParams,
+    PositionStrategy, RebalanceData, RebalanceReason,
+};
+use clmm_lp_protocols::prelude::{Protocol, WhirlpoolReader, quote_deposit};
 use solana_sdk::pubkey::Pubkey;
 use std::str::FromStr;
 use tracing::{info, warn};
 
-/// List all positions.
+/// Maximum length of a position tag.
+const MAX_TAG_LEN: usize = 64;
+
+/// List all positions, optionally filtered by tag.
 #[utoipa::path(
     get,
     path = "/positions",
     tag = "Positions",
+    params(ListPositionsQuery),
     responses(
         (status = 200, description = "List of positions", body = ListPositionsResponse)
     )
 )]
 pub async fn list_positions(
     State(state): State<AppState>,
+    Query(query): Query<ListPositionsQuery>,
 ) -> ApiResult<Json<ListPositionsResponse>> {
-    let positions = state.monitor.get_positions().await;
+    let positions = match &query.tag {
+        Some(tag) => state.monitor.get_positions_by_tag(tag).await,
+        None => state.monitor.get_positions().await,
+    };
 
     let responses: Vec<PositionResponse> = positions
         .iter()
+        .filter(|p| {
+            query
+                .protocol
+                .is_none_or(|protocol| protocol_response(p.protocol) == protocol)
+        })
         .map(|p| PositionResponse {
             address: p.address.to_string(),
             pool_address: p.pool.to_string(),
+            protocol: protocol_response(p.protocol),
             owner: p.on_chain.owner.to_string(),
             tick_lower: p.on_chain.tick_lower,
             tick_upper: p.on_chain.tick_upper,
@@ -51,11 +72,16 @@ pub async fn list_positions(
                 net_pnl_usd: p.pnl.net_pnl_usd,
                 net_pnl_pct: p.pnl.net_pnl_pct,
             },
+            health: p.health.as_ref().map(health_response),
             status: if p.in_range {
                 PositionStatus::Active
             } else {
                 PositionStatus::OutOfRange
             },
+            strategy_id: p.strategy.as_ref().map(|s| s.name.clone()),
+            tags: p.tags.clone(),
+            read_only: p.read_only,
+            stale_since: p.stale_since,
             created_at: None,
         })
         .collect();
@@ -66,6 +92,28 @@ pub async fn list_positions(
     }))
 }
 
+/// Converts a protocol into its API representation.
+fn protocol_response(protocol: Protocol) -> PositionProtocol {
+    match protocol {
+        Protocol::OrcaWhirlpool => PositionProtocol::OrcaWhirlpool,
+        Protocol::RaydiumClmm => PositionProtocol::RaydiumClmm,
+        Protocol::MeteoraDlmm => PositionProtocol::MeteoraDlmm,
+    }
+}
+
+/// Converts a health score into its API representation.
+fn health_response(health: &HealthScore) -> PositionHealthResponse {
+    PositionHealthResponse {
+        score: health.score,
+        time_in_range: health.components.time_in_range,
+        fee_efficiency: health.components.fee_efficiency,
+        impermanent_loss: health.components.impermanent_loss,
+        range_position: health.components.range_position,
+        freshness: health.components.freshness,
+        computed_at: health.computed_at,
+    }
+}
+
 /// Get a specific position.
 #[utoipa::path(
     get,
@@ -95,6 +143,7 @@ pub async fn get_position(
     let response = PositionResponse {
         address: position.address.to_string(),
         pool_address: position.pool.to_string(),
+        protocol: protocol_response(position.protocol),
         owner: position.on_chain.owner.to_string(),
         tick_lower: position.on_chain.tick_lower,
         tick_upper: position.on_chain.tick_upper,
@@ -111,11 +160,16 @@ pub async fn get_position(
             net_pnl_usd: position.pnl.net_pnl_usd,
             net_pnl_pct: position.pnl.net_pnl_pct,
         },
+        health: position.health.as_ref().map(health_response),
         status: if position.in_range {
             PositionStatus::Active
         } else {
             PositionStatus::OutOfRange
         },
+        strategy_id: position.strategy.as_ref().map(|s| s.name.clone()),
+        tags: position.tags.clone(),
+        read_only: position.read_only,
+        stale_since: position.stale_since,
         created_at: None,
     };
 
@@ -168,11 +222,27 @@ pub async fn open_position(
         )));
     }
 
+    // Quote the deposit; single-sided ranges only need one token
+    let quote = quote_deposit(
+        &pool_state,
+        request.tick_lower,
+        request.tick_upper,
+        request.amount_a,
+        request.amount_b,
+        request.slippage_tolerance_bps,
+    )
+    .map_err(|e| ApiError::Validation(e.to_string()))?;
+
     if state.dry_run {
         info!("Dry-run mode: would open position");
         return Ok(Json(MessageResponse::new(format!(
-            "[DRY-RUN] Would open position in pool {} with range [{}, {}]",
-            request.pool_address, request.tick_lower, request.tick_upper
+            "[DRY-RUN] Would open {:?} position in pool {} with range [{}, {}] (max A: {}, max B: {})",
+            quote.side,
+            request.pool_address,
+            request.tick_lower,
+            request.tick_upper,
+            quote.token_max_a,
+            quote.token_max_b
         ))));
     }
 
@@ -193,7 +263,8 @@ pub async fn open_position(
     ),
     responses(
         (status = 200, description = "Position closed", body = MessageResponse),
-        (status = 404, description = "Position not found")
+        (status = 404, description = "Position not found"),
+        (status = 409, description = "Position is read-only")
     )
 )]
 pub async fn close_position(
@@ -212,6 +283,12 @@ pub async fn close_position(
         .find(|p| p.address == pubkey)
         .ok_or_else(|| ApiError::not_found("Position not found"))?;
 
+    if position.read_only {
+        return Err(ApiError::Conflict(
+            "Position is read-only; it is monitored but not managed".to_string(),
+        ));
+    }
+
     if state.dry_run {
         info!("Dry-run mode: would close position");
 
@@ -249,7 +326,8 @@ pub async fn close_position(
     ),
     responses(
         (status = 200, description = "Fees collected", body = MessageResponse),
-        (status = 404, description = "Position not found")
+        (status = 404, description = "Position not found"),
+        (status = 409, description = "Position is read-only")
     )
 )]
 pub async fn collect_fees(
@@ -268,6 +346,12 @@ pub async fn collect_fees(
         .find(|p| p.address == pubkey)
         .ok_or_else(|| ApiError::not_found("Position not found"))?;
 
+    if position.read_only {
+        return Err(ApiError::Conflict(
+            "Position is read-only; it is monitored but not managed".to_string(),
+        ));
+    }
+
     if state.dry_run {
         info!("Dry-run mode: would collect fees");
 
@@ -307,7 +391,8 @@ pub async fn collect_fees(
     request_body = RebalanceRequest,
     responses(
         (status = 200, description = "Position rebalanced", body = MessageResponse),
-        (status = 404, description = "Position not found")
+        (status = 404, description = "Position not found"),
+        (status = 409, description = "Position is read-only")
     )
 )]
 pub async fn rebalance_position(
@@ -340,6 +425,12 @@ pub async fn rebalance_position(
         .find(|p| p.address == pubkey)
         .ok_or_else(|| ApiError::not_found("Position not found"))?;
 
+    if position.read_only {
+        return Err(ApiError::Conflict(
+            "Position is read-only; it is monitored but not managed".to_string(),
+        ));
+    }
+
     // Fetch pool state for validation
     let pool_reader = WhirlpoolReader::new(state.provider.clone());
     let pool_state = pool_reader
@@ -418,6 +509,7 @@ pub async fn rebalance_position(
         message: format!("Rebalance initiated for position {}", address),
         timestamp: chrono::Utc::now(),
         position_address: Some(address.clone()),
+        tags: position.tags.clone(),
     });
 
     // Actual execution requires wallet configuration
@@ -427,6 +519,166 @@ pub async fn rebalance_position(
     )))
 }
 
+/// Migrate a position to another pool of the same pair.
+///
+/// Closes the position, swaps to the new range's token ratio and opens it in
+/// the target pool, if the projected fee APR gain pays for the migration.
+#[utoipa::path(
+    post,
+    path = "/positions/{address}/migrate",
+    tag = "Positions",
+    params(
+        ("address" = String, Path, description = "Position address")
+    ),
+    request_body = MigratePositionRequest,
+    responses(
+        (status = 200, description = "Migration outcome", body = MigrationResponse),
+        (status = 400, description = "Pools hold different pairs"),
+        (status = 404, description = "Position not found"),
+        (status = 409, description = "Position is read-only or the target pool is paused")
+    )
+)]
+pub async fn migrate_position(
+    State(state): State<AppState>,
+    Path(address): Path<String>,
+    Json(request): Json<MigratePositionRequest>,
+) -> ApiResult<Json<MigrationResponse>> {
+    let pubkey = Pubkey::from_str(&address)
+        .map_err(|_| ApiError::bad_request("Invalid position address"))?;
+    let target_pool = Pubkey::from_str(&request.target_pool)
+        .map_err(|_| ApiError::bad_request("Invalid target pool address"))?;
+
+    if request.new_tick_lower >= request.new_tick_upper {
+        return Err(ApiError::Validation(
+            "new_tick_lower must be less than new_tick_upper".to_string(),
+        ));
+    }
+
+    let positions = state.monitor.get_positions().await;
+    let position = positions
+        .iter()
+        .find(|p| p.address == pubkey)
+        .ok_or_else(|| ApiError::not_found("Position not found"))?;
+
+    if position.read_only {
+        return Err(ApiError::Conflict(
+            "Position is read-only; it is monitored but not managed".to_string(),
+        ));
+    }
+    if position.pool == target_pool {
+        return Err(ApiError::bad_request(
+            "Position is already in the target pool",
+        ));
+    }
+    if state.anomaly_detector.is_paused(&target_pool).await {
+        return Err(ApiError::Conflict(
+            "Target pool is paused after an anomaly".to_string(),
+        ));
+    }
+
+    let pool_reader = WhirlpoolReader::new(state.provider.clone());
+    let from = pool_reader
+        .get_pool_state(&position.pool.to_string())
+        .await
+        .map_err(|e| ApiError::Internal(format!("Failed to fetch pool state: {}", e)))?;
+    let to = pool_reader
+        .get_pool_state(&request.target_pool)
+        .await
+        .map_err(|e| ApiError::Internal(format!("Failed to fetch target pool state: {}", e)))?;
+
+    if (from.token_mint_a, from.token_mint_b) != (to.token_mint_a, to.token_mint_b) {
+        return Err(ApiError::bad_request(
+            "Target pool holds a different token pair",
+        ));
+    }
+    let tick_spacing = to.tick_spacing as i32;
+    if request.new_tick_lower % tick_spacing != 0 || request.new_tick_upper % tick_spacing != 0 {
+        return Err(ApiError::Validation(format!(
+            "Tick bounds must be multiples of the target pool's tick spacing ({})",
+            tick_spacing
+        )));
+    }
+
+    let mut executor = MigrationExecutor::new(
+        state.provider.clone(),
+        state.tx_manager.clone(),
+        state.lifecycle.clone(),
+        MigrationConfig {
+            max_slippage_bps: request.slippage_tolerance_bps,
+            ..Default::default()
+        },
+    );
+    executor.set_dry_run(state.dry_run);
+
+    info!(
+        position = %address,
+        from_pool = %position.pool,
+        to_pool = %target_pool,
+        dry_run = state.dry_run,
+        "Migrating position"
+    );
+    let result = executor
+        .execute(MigrationParams {
+            position: pubkey,
+            from_pool: position.pool,
+            to_pool: target_pool,
+            current_tick_lower: position.on_chain.tick_lower,
+            current_tick_upper: position.on_chain.tick_upper,
+            new_tick_lower: request.new_tick_lower,
+            new_tick_upper: request.new_tick_upper,
+            current_liquidity: position.on_chain.liquidity,
+            position_value_usd: position.pnl.current_value_usd,
+            from_fee_apr: request.current_fee_apr.unwrap_or(position.pnl.fee_apr),
+            to_fee_apr: request.target_fee_apr,
+            sol_price_usd: request.sol_price_usd,
+        })
+        .await;
+
+    let message = match (&result.error, state.dry_run) {
+        (Some(error), _) => error.clone(),
+        (None, true) => format!(
+            "[DRY-RUN] Would migrate position {} from pool {} to {}",
+            address, position.pool, target_pool
+        ),
+        (None, false) => format!(
+            "Position {} migrated from pool {} to {}",
+            address, position.pool, target_pool
+        ),
+    };
+
+    if result.success {
+        state.broadcast_position_update(PositionUpdate {
+            update_type: if state.dry_run {
+                "migration_simulated"
+            } else {
+                "migrated"
+            }
+            .to_string(),
+            position_address: address.clone(),
+            timestamp: chrono::Utc::now(),
+            data: serde_json::json!({
+                "from_pool": position.pool.to_string(),
+                "to_pool": target_pool.to_string(),
+                "new_position": result.new_position.map(|p| p.to_string()),
+                "new_range": [request.new_tick_lower, request.new_tick_upper],
+                "dry_run": state.dry_run
+            }),
+        });
+    } else {
+        warn!(position = %address, reason = %message, "Migration not carried out");
+    }
+
+    Ok(Json(MigrationResponse {
+        migrated: result.success,
+        profitable: result.check.is_profitable,
+        projected_gain_usd: result.check.projected_gain_usd,
+        estimated_cost_usd: result.check.estimated_cost_usd,
+        min_required_gain_usd: result.check.min_required_gain_usd,
+        new_position: result.new_position.map(|p| p.to_string()),
+        message,
+    }))
+}
+
 /// Get position PnL details.
 #[utoipa::path(
     get,
@@ -466,3 +718,257 @@ pub async fn get_position_pnl(
 
     Ok(Json(response))
 }
+
+/// Converts a strategy assignment into its API representation.
+fn strategy_response(
+    address: &Pubkey,
+    strategy: Option<&PositionStrategy>,
+) -> PositionStrategyResponse {
+    PositionStrategyResponse {
+        position_address: address.to_string(),
+        strategy_id: strategy.map(|s| s.name.clone()),
+        overrides: strategy
+            .and_then(|s| serde_json::to_value(&s.overrides).ok())
+            .unwrap_or_else(|| serde_json::json!({})),
+    }
+}
+
+/// Get the strategy assigned to a position.
+#[utoipa::path(
+    get,
+    path = "/positions/{address}/strategy",
+    tag = "Positions",
+    params(
+        ("address" = String, Path, description = "Position address")
+    ),
+    responses(
+        (status = 200, description = "Strategy assignment", body = PositionStrategyResponse),
+        (status = 404, description = "Position not found")
+    )
+)]
+pub async fn get_position_strategy(
+    State(state): State<AppState>,
+    Path(address): Path<String>,
+) -> ApiResult<Json<PositionStrategyResponse>> {
+    let pubkey = Pubkey::from_str(&address)
+        .map_err(|_| ApiError::bad_request("Invalid position address"))?;
+
+    let position = state
+        .monitor
+        .get_position(&pubkey)
+        .await
+        .ok_or_else(|| ApiError::not_found("Position not found"))?;
+
+    Ok(Json(strategy_response(&pubkey, position.strategy.as_ref())))
+}
+
+/// Assign a strategy to a position.
+#[utoipa::path(
+    put,
+    path = "/positions/{address}/strategy",
+    tag = "Positions",
+    params(
+        ("address" = String, Path, description = "Position address")
+    ),
+    request_body = AssignStrategyRequest,
+    responses(
+        (status = 200, description = "Strategy assigned", body = PositionStrategyResponse),
+        (status = 404, description = "Position or strategy not found"),
+        (status = 422, description = "Invalid overrides")
+    )
+)]
+pub async fn assign_position_strategy(
+    State(state): State<AppState>,
+    Path(address): Path<String>,
+    Json(request): Json<AssignStrategyRequest>,
+) -> ApiResult<Json<PositionStrategyResponse>> {
+    let pubkey = Pubkey::from_str(&address)
+        .map_err(|_| ApiError::bad_request("Invalid position address"))?;
+
+    if !state
+        .strategies
+        .read()
+        .await
+        .contains_key(&request.strategy_id)
+    {
+        return Err(ApiError::not_found("Strategy not found"));
+    }
+
+    let overrides: DecisionOverrides = if request.overrides.is_null() {
+        DecisionOverrides::default()
+    } else {
+        serde_json::from_value(request.overrides)
+            .map_err(|e| ApiError::Validation(format!("Invalid overrides: {}", e)))?
+    };
+    let strategy = PositionStrategy::new(request.strategy_id).with_overrides(overrides);
+
+    if !state
+        .monitor
+        .set_position_strategy(&pubkey, Some(strategy.clone()))
+        .await
+    {
+        return Err(ApiError::not_found("Position not found"));
+    }
+
+    info!(position = %address, strategy = %strategy.name, "Strategy assigned to position");
+
+    Ok(Json(strategy_response(&pubkey, Some(&strategy))))
+}
+
+/// Remove a position's strategy assignment so it uses the executor default.
+#[utoipa::path(
+    delete,
+    path = "/positions/{address}/strategy",
+    tag = "Positions",
+    params(
+        ("address" = String, Path, description = "Position address")
+    ),
+    responses(
+        (status = 200, description = "Strategy assignment removed", body = MessageResponse),
+        (status = 404, description = "Position not found")
+    )
+)]
+pub async fn clear_position_strategy(
+    State(state): State<AppState>,
+    Path(address): Path<String>,
+) -> ApiResult<Json<MessageResponse>> {
+    let pubkey = Pubkey::from_str(&address)
+        .map_err(|_| ApiError::bad_request("Invalid position address"))?;
+
+    if !state.monitor.set_position_strategy(&pubkey, None).await {
+        return Err(ApiError::not_found("Position not found"));
+    }
+
+    Ok(Json(MessageResponse::new("Strategy assignment removed")))
+}
+
+/// Replace the tags on a position.
+#[utoipa::path(
+    put,
+    path = "/positions/{address}/tags",
+    tag = "Positions",
+    params(
+        ("address" = String, Path, description = "Position address")
+    ),
+    request_body = SetPositionTagsRequest,
+    responses(
+        (status = 200, description = "Tags updated", body = PositionTagsResponse),
+        (status = 404, description = "Position not found"),
+        (status = 422, description = "Invalid tags")
+    )
+)]
+pub async fn set_position_tags(
+    State(state): State<AppState>,
+    Path(address): Path<String>,
+    Json(request): Json<SetPositionTagsRequest>,
+) -> ApiResult<Json<PositionTagsResponse>> {
+    let pubkey = Pubkey::from_str(&address)
+        .map_err(|_| ApiError::bad_request("Invalid position address"))?;
+
+    let mut tags = Vec::with_capacity(request.tags.len());
+    for tag in request.tags {
+        let tag = tag.trim();
+        if tag.is_empty() || tag.len() > MAX_TAG_LEN {
+            return Err(ApiError::Validation(format!(
+                "Tags must be 1-{} characters",
+                MAX_TAG_LEN
+            )));
+        }
+        tags.push(tag.to_string());
+    }
+    tags.sort();
+    tags.dedup();
+
+    if !state.monitor.set_position_tags(&pubkey, tags.clone()).await {
+        return Err(ApiError::not_found("Position not found"));
+    }
+    state.lifecycle.set_tags(pubkey, tags.clone()).await;
+
+    Ok(Json(PositionTagsResponse {
+        position_address: address,
+        tags,
+    }))
+}
+
+/// Mark a position as read-only or managed.
+///
+/// Read-only positions are monitored and alerted on, but the bot never
+/// evaluates, rebalances, closes or exits them. With a database the mode is
+/// saved and restored when the server restarts.
+#[utoipa::path(
+    put,
+    path = "/positions/{address}/read-only",
+    tag = "Positions",
+    params(
+        ("address" = String, Path, description = "Position address")
+    ),
+    request_body = SetPositionReadOnlyRequest,
+    responses(
+        (status = 200, description = "Management mode updated", body = PositionReadOnlyResponse),
+        (status = 404, description = "Position not found")
+    )
+)]
+pub async fn set_position_read_only(
+    State(state): State<AppState>,
+    Path(address): Path<String>,
+    Json(request): Json<SetPositionReadOnlyRequest>,
+) -> ApiResult<Json<PositionReadOnlyResponse>> {
+    let pubkey = Pubkey::from_str(&address)
+        .map_err(|_| ApiError::bad_request("Invalid position address"))?;
+
+    if state.monitor.get_position(&pubkey).await.is_none() {
+        return Err(ApiError::not_found("Position not found"));
+    }
+    // Saved first, so a restart never hands a read-only position back to the bot
+    if let Some(database) = &state.database {
+        database
+            .read_only_positions()
+            .set(&address, request.read_only)
+            .await
+            .map_err(|e| ApiError::internal(e.to_string()))?;
+    }
+    if !state
+        .monitor
+        .set_position_read_only(&pubkey, request.read_only)
+        .await
+    {
+        return Err(ApiError::not_found("Position not found"));
+    }
+
+    Ok(Json(PositionReadOnlyResponse {
+        position_address: address,
+        read_only: request.read_only,
+    }))
+}
+
+/// Replay a position's history under alternative scenarios.
+///
+/// The actual range changes and each scenario are simulated over the pool's
+/// stored price history for the position's lifetime.
+#[utoipa::path(
+    post,
+    path = "/positions/{address}/what-if",
+    tag = "Positions",
+    params(
+        ("address" = String, Path, description = "Position address")
+    ),
+    request_body = WhatIfRequest,
+    responses(
+        (status = 200, description = "Counterfactual comparison", body = WhatIfResponse),
+        (status = 400, description = "Invalid position address"),
+        (status = 404, description = "No range history or pool not in database"),
+        (status = 422, description = "Invalid scenarios or not enough price history"),
+        (status = 503, description = "No database configured")
+    )
+)]
+pub async fn position_what_if(
+    State(state): State<AppState>,
+    Path(address): Path<String>,
+    Json(request): Json<WhatIfRequest>,
+) -> ApiResult<Json<WhatIfResponse>> {
+    let pubkey = Pubkey::from_str(&address)
+        .map_err(|_| ApiError::bad_request("Invalid position address"))?;
+
+    let report = ReplayService::new(state).what_if(&pubkey, request).await?;
+    Ok(Json(report))
+}
@@ -2,17 +2,27 @@
 
 use crate::error::{ApiError, ApiResult};
 use crate::models::{
-    CreateStrategyRequest, ListStrategiesResponse, MessageResponse, StrategyParameters,
-    StrategyPerformanceResponse, StrategyResponse, StrategyType,
+    ConfigChangeResponse, ConfigChangesResponse, CreateStrategyRequest, ListStrategiesResponse,
+    MessageResponse, StrategyParameters, StrategyPerformanceResponse, StrategyResponse,
+    StrategyType,
+};
+use crate::services::strategy_service::{
+    decision_config_from_parameters, strategy_decision_configs, validate_parameters,
+};
+use crate::state::{
+    AlertBroadcastNotifier, AlertUpdate, AppState, LIVE_CANDLE_INTERVAL_SECS, StrategyState,
 };
-use crate::state::{AlertUpdate, AppState, StrategyState};
 use axum::{
     Json,
     extract::{Path, State},
 };
-use clmm_lp_execution::prelude::{DecisionConfig, ExecutorConfig, StrategyExecutor};
+use clmm_lp_execution::prelude::{
+    ConfigChange, ConfigSection, ConfigSource, ExecutorConfig, MultiNotifier, RestartPolicy,
+    StrategyExecutor,
+};
 use rust_decimal::Decimal;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 use tracing::info;
 
@@ -40,6 +50,9 @@ pub async fn list_strategies(
                     max_il_pct: None,
                     eval_interval_secs: None,
                     min_rebalance_interval_hours: None,
+                    min_health_score: None,
+                    range_spec: None,
+                    max_oracle_divergence_pct: None,
                 });
 
             StrategyResponse {
@@ -104,6 +117,9 @@ pub async fn get_strategy(
             max_il_pct: None,
             eval_interval_secs: None,
             min_rebalance_interval_hours: None,
+            min_health_score: None,
+            range_spec: None,
+            max_oracle_divergence_pct: None,
         });
 
     let response = StrategyResponse {
@@ -142,7 +158,8 @@ pub async fn get_strategy(
     request_body = CreateStrategyRequest,
     responses(
         (status = 201, description = "Strategy created", body = StrategyResponse),
-        (status = 400, description = "Invalid request")
+        (status = 400, description = "Invalid request"),
+        (status = 422, description = "Invalid range spec")
     )
 )]
 pub async fn create_strategy(
@@ -159,6 +176,7 @@ pub async fn create_strategy(
         "auto_execute": request.auto_execute,
         "dry_run": request.dry_run,
     });
+    validate_parameters(&config["parameters"])?;
 
     let strategy_state = StrategyState {
         id: id.clone(),
@@ -203,7 +221,9 @@ pub async fn create_strategy(
     request_body = CreateStrategyRequest,
     responses(
         (status = 200, description = "Strategy updated", body = StrategyResponse),
-        (status = 404, description = "Strategy not found")
+        (status = 400, description = "Invalid strategy parameters"),
+        (status = 404, description = "Strategy not found"),
+        (status = 422, description = "Invalid range spec")
     )
 )]
 pub async fn update_strategy(
@@ -226,9 +246,46 @@ pub async fn update_strategy(
         "dry_run": request.dry_run,
     });
 
+    validate_parameters(&config["parameters"])?;
+    let decision_config = decision_config_from_parameters(&config["parameters"]);
+    decision_config
+        .validate()
+        .map_err(|e| ApiError::bad_request(e.to_string()))?;
+
     strategy.name = request.name.clone();
     strategy.config = config;
     strategy.updated_at = now;
+    let running = strategy.running;
+    let created_at = strategy.created_at;
+    drop(strategies);
+
+    // Running executors pick up the new thresholds without a restart
+    for (executor_id, executor) in state.executors.read().await.iter() {
+        let executor = executor.read().await;
+        if executor_id == &id {
+            executor
+                .reload_decision_config(decision_config.clone())
+                .await
+                .map_err(|e| ApiError::internal(e.to_string()))?;
+        }
+        executor
+            .reload_strategy(&id, decision_config.clone())
+            .await
+            .map_err(|e| ApiError::internal(e.to_string()))?;
+    }
+    state
+        .config_audit
+        .record(ConfigChange::new(
+            ConfigSection::Decision {
+                strategy: Some(id.clone()),
+            },
+            ConfigSource::Api,
+            format!(
+                "rebalance at {} IL, close at {} IL",
+                decision_config.il_rebalance_threshold, decision_config.il_close_threshold
+            ),
+        ))
+        .await;
 
     info!(id = %id, "Strategy updated");
 
@@ -238,15 +295,44 @@ pub async fn update_strategy(
         pool_address: request.pool_address,
         strategy_type: request.strategy_type,
         parameters: request.parameters,
-        running: strategy.running,
+        running,
         dry_run: request.dry_run,
-        created_at: strategy.created_at,
+        created_at,
         updated_at: now,
     };
 
     Ok(Json(response))
 }
 
+/// List runtime configuration changes.
+#[utoipa::path(
+    get,
+    path = "/config/changes",
+    tag = "Strategies",
+    responses(
+        (status = 200, description = "Applied configuration changes, oldest first", body = ConfigChangesResponse)
+    )
+)]
+pub async fn list_config_changes(
+    State(state): State<AppState>,
+) -> ApiResult<Json<ConfigChangesResponse>> {
+    let changes: Vec<ConfigChangeResponse> = state
+        .config_audit
+        .changes()
+        .await
+        .into_iter()
+        .map(|change| ConfigChangeResponse {
+            timestamp: change.timestamp,
+            section: change.section.to_string(),
+            source: change.source.to_string(),
+            summary: change.summary,
+        })
+        .collect();
+    let total = changes.len();
+
+    Ok(Json(ConfigChangesResponse { changes, total }))
+}
+
 /// Delete a strategy.
 #[utoipa::path(
     delete,
@@ -327,6 +413,13 @@ pub async fn start_strategy(
         .and_then(|v| v.as_u64())
         .unwrap_or(300);
 
+    let defaults = ExecutorConfig::default();
+    let max_concurrent_executions = strategy_config
+        .get("parameters")
+        .and_then(|p| p.get("max_concurrent_executions"))
+        .and_then(|v| v.as_u64())
+        .map_or(defaults.max_concurrent_executions, |v| v as usize);
+
     // Create executor configuration
     let executor_config = ExecutorConfig {
         eval_interval_secs,
@@ -334,6 +427,8 @@ pub async fn start_strategy(
         require_confirmation: !auto_execute,
         max_slippage_pct: Decimal::new(5, 3), // 0.5%
         dry_run,
+        max_concurrent_executions,
+        ..defaults
     };
 
     // Create strategy executor
@@ -344,31 +439,28 @@ pub async fn start_strategy(
         executor_config,
     );
 
+    executor.set_price_oracle(state.reference_price.clone());
+    executor.set_pool_cache(state.pool_cache.clone());
+    executor.set_candle_builder(state.candles.clone(), LIVE_CANDLE_INTERVAL_SECS);
+    let mut notifier = MultiNotifier::new();
+    notifier.add(AlertBroadcastNotifier::new(&state));
+    executor.set_notifier(notifier);
+    executor.set_decision_sender(state.decision_updates.clone());
+    executor.set_heartbeat(state.register_heartbeat(
+        format!("strategy executor {}", id),
+        Duration::from_secs(eval_interval_secs),
+    ));
+    executor.set_drawdown_guard(state.drawdown_guard.clone());
+    executor.set_anomaly_detector(state.anomaly_detector.clone());
+    executor.set_in_flight_registry(state.in_flight.clone());
+
     // Configure decision engine if parameters provided
     if let Some(params) = strategy_config.get("parameters") {
-        let mut decision_config = DecisionConfig::default();
-
-        if let Some(threshold) = params.get("rebalance_threshold_pct")
-            && let Some(val) = threshold.as_f64()
-        {
-            decision_config.il_rebalance_threshold =
-                Decimal::from_f64_retain(val / 100.0).unwrap_or(Decimal::new(5, 2));
-        }
-
-        if let Some(max_il) = params.get("max_il_pct")
-            && let Some(val) = max_il.as_f64()
-        {
-            decision_config.il_close_threshold =
-                Decimal::from_f64_retain(val / 100.0).unwrap_or(Decimal::new(15, 2));
-        }
-
-        if let Some(min_hours) = params.get("min_rebalance_interval_hours")
-            && let Some(val) = min_hours.as_u64()
-        {
-            decision_config.min_rebalance_interval_hours = val;
-        }
-
-        executor.set_decision_config(decision_config);
+        executor.set_decision_config(decision_config_from_parameters(params));
+    }
+    // Positions may be assigned to any strategy's rules
+    for (strategy_id, config) in strategy_decision_configs(&*state.strategies.read().await) {
+        executor.register_strategy(strategy_id, config);
     }
 
     let executor = Arc::new(RwLock::new(executor));
@@ -384,20 +476,33 @@ pub async fn start_strategy(
     let id_clone = id.clone();
     let alert_sender = state.alert_updates.clone();
 
-    tokio::spawn(async move {
-        info!(strategy_id = %id_clone, "Strategy executor task started");
-
-        let executor_guard = executor_clone.read().await;
-        executor_guard.start().await;
-
-        // Notify when stopped
-        let _ = alert_sender.send(AlertUpdate {
-            level: "info".to_string(),
-            message: format!("Strategy {} stopped", id_clone),
-            timestamp: chrono::Utc::now(),
-            position_address: None,
-        });
-    });
+    let task_name = format!("strategy {}", id_clone);
+    let supervised =
+        state
+            .supervisor
+            .supervise(task_name.clone(), RestartPolicy::OnPanic, move |shutdown| {
+                let executor = executor_clone.clone();
+                async move {
+                    executor.read().await.run_until(shutdown).await;
+                }
+            });
+    state
+        .shutdown
+        .spawn(task_name, async move {
+            info!(strategy_id = %id_clone, "Strategy executor task started");
+
+            supervised.await;
+
+            // Notify when stopped
+            let _ = alert_sender.send(AlertUpdate {
+                level: "info".to_string(),
+                message: format!("Strategy {} stopped", id_clone),
+                timestamp: chrono::Utc::now(),
+                position_address: None,
+                tags: Vec::new(),
+            });
+        })
+        .await;
 
     // Broadcast alert
     state.broadcast_alert(AlertUpdate {
@@ -405,6 +510,7 @@ pub async fn start_strategy(
         message: format!("Strategy {} started", id),
         timestamp: chrono::Utc::now(),
         position_address: None,
+        tags: Vec::new(),
     });
 
     info!(id = %id, dry_run = dry_run, auto_execute = auto_execute, "Strategy started");
@@ -469,6 +575,7 @@ pub async fn stop_strategy(
         message: format!("Strategy {} stopped", id),
         timestamp: chrono::Utc::now(),
         position_address: None,
+        tags: Vec::new(),
     });
 
     info!(id = %id, "Strategy stopped");
@@ -514,3 +621,45 @@ pub async fn get_strategy_performance(
 
     Ok(Json(response))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::ApiConfig;
+    use clmm_lp_protocols::prelude::RpcConfig;
+
+    fn request(range_spec: &str) -> CreateStrategyRequest {
+        serde_json::from_value(serde_json::json!({
+            "name": "wide",
+            "pool_address": "pool",
+            "strategy_type": "threshold",
+            "parameters": { "range_spec": range_spec },
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_malformed_range_spec_is_rejected() {
+        let state = AppState::new(RpcConfig::default(), ApiConfig::default());
+
+        let result = create_strategy(State(state.clone()), Json(request("2 sigmas"))).await;
+        assert!(matches!(result, Err(ApiError::Validation(_))));
+        assert!(state.strategies.read().await.is_empty());
+
+        let Json(created) = create_strategy(State(state.clone()), Json(request("2sigma")))
+            .await
+            .unwrap();
+        let result = update_strategy(
+            State(state.clone()),
+            Path(created.id.clone()),
+            Json(request("90:")),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::Validation(_))));
+        let strategies = state.strategies.read().await;
+        assert_eq!(
+            strategies[&created.id].config["parameters"]["range_spec"],
+            "2sigma"
+        );
+    }
+}
@@ -5,22 +5,29 @@
 //! - Strategy configuration and execution
 //! - Pool information and analytics
 //! - Real-time WebSocket updates
+//! - gRPC service for positions, decision streams and engine control
 //! - OpenAPI documentation with Swagger UI
+//! - Optional embedded web dashboard
 //! - JWT and API key authentication
+//! - Native HTTPS with certificate hot reload
 
 /// Prelude module for convenient imports.
 pub mod prelude;
 
 /// Authentication module.
 pub mod auth;
+/// Embedded web dashboard.
+pub mod dashboard;
 /// Error types.
 pub mod error;
+/// gRPC service for the execution engine.
+pub mod grpc;
 /// Request handlers.
 pub mod handlers;
 /// Middleware components.
 pub mod middleware;
 /// API request/response models.
-pub mod models;
+pub use clmm_lp_api_models::models;
 /// OpenAPI documentation.
 pub mod openapi;
 /// Route definitions.
@@ -31,6 +38,8 @@ pub mod server;
 pub mod services;
 /// Application state.
 pub mod state;
+/// Native HTTPS.
+pub mod tls;
 /// WebSocket handlers.
 pub mod websocket;
 
@@ -38,5 +47,6 @@ pub use auth::{AuthConfig, AuthError, AuthState, Claims, Role};
 pub use error::ApiError;
 pub use openapi::ApiDoc;
 pub use server::{ApiServer, ServerConfig};
-pub use services::{PositionService, StrategyService};
+pub use services::{OptimizationService, PositionService, StrategyService};
 pub use state::AppState;
+pub use tls::TlsConfig;
@@ -1,72 +1,735 @@
 //! CLMM Liquidity Provider API Server.
 //!
-//! This binary starts the REST API server with WebSocket support.
+//! This binary starts the REST API server with WebSocket support, and the
+//! gRPC engine service when `API_GRPC_PORT` is set.
+//!
+//! With `--worker` it binds no port and only runs optimization job workers,
+//! draining the queue the API servers sharing `DATABASE_URL` submit to.
 
-use anyhow::Result;
-use clmm_lp_api::server::{ApiServer, ServerConfig, shutdown_signal};
-use clmm_lp_api::state::ApiConfig;
-use clmm_lp_protocols::prelude::RpcConfig;
+use anyhow::{Context, Result};
+use clmm_lp_api::server::{
+    ApiServer, CorsConfig, SecurityHeadersConfig, ServerConfig, shutdown_signal,
+};
+use clmm_lp_api::services::alert_rule_service::{ALERT_RULE_SYNC_INTERVAL_SECS, sync_alert_rules};
+use clmm_lp_api::services::optimization_service::run_job_worker;
+use clmm_lp_api::state::{
+    AlertBroadcastNotifier, AlertUpdate, ApiConfig, AppState, PNL_UPDATE_INTERVAL_SECS,
+};
+use clmm_lp_api::tls::TlsConfig;
+use clmm_lp_api::websocket::run_pnl_updates;
+use clmm_lp_data::prelude::{Database, PoolRecord};
+use clmm_lp_execution::prelude::{
+    AccountListener, AccountListenerConfig, AdvisorConfig, Alert, AlertLevel, AlertType,
+    ConfigReloader, ConsoleNotifier, DailyReporter, MultiNotifier, OptimizerRecommender,
+    PipeNotifier, RangeAdvisor, ReportConfig, RestartPolicy, StateSource, SubscriptionType,
+    TelegramNotifier,
+};
+use clmm_lp_execution::shutdown::{CancellationToken, ShutdownController};
+use clmm_lp_protocols::prelude::{Cluster, CommitmentLevel, RpcConfig};
+use rust_decimal::Decimal;
+use solana_sdk::pubkey::Pubkey;
 use std::env;
-use tracing::info;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::{info, warn};
 
 #[tokio::main]
 async fn main() -> Result<()> {
     // Initialize logging
     tracing_subscriber::fmt::init();
 
-    info!("Starting CLMM Liquidity Provider API Server");
+    let worker_only = env::args().skip(1).any(|arg| arg == "--worker");
+    if worker_only {
+        info!("Starting CLMM Liquidity Provider job worker");
+    } else {
+        info!("Starting CLMM Liquidity Provider API Server");
+    }
 
     // Load configuration from environment
-    let config = load_config_from_env();
+    let config = load_config_from_env()?;
 
     info!(
         host = %config.host,
         port = config.port,
+        grpc_port = ?config.grpc_port,
+        cluster = %config.rpc_config.cluster,
         "Server configuration loaded"
     );
 
-    // Create and run server
-    let server = ApiServer::new(config);
+    let mut state = AppState::new(config.rpc_config.clone(), config.api_config.clone());
+
+    // Saved simulations are only available with a database
+    if let Ok(database_url) = env::var("DATABASE_URL") {
+        match Database::connect(&database_url).await {
+            Ok(database) => {
+                info!("Connected to database");
+                state.set_database(database);
+            }
+            Err(e) => warn!(error = %e, "Failed to connect to database"),
+        }
+    }
+
+    if worker_only {
+        return run_workers(state, config.api_config.job_workers).await;
+    }
+
+    // Positions marked read-only stay so across restarts; starting without
+    // the marks could let the bot act on them
+    if let Some(database) = &state.database {
+        let records = database
+            .read_only_positions()
+            .find_all()
+            .await
+            .context("Failed to load read-only positions")?;
+        let addresses = records
+            .iter()
+            .map(|record| Pubkey::from_str(&record.position_address))
+            .collect::<Result<Vec<_>, _>>()
+            .context("Invalid read-only position address")?;
+        state.monitor.load_read_only(addresses).await;
+    }
+
+    // Pools onboarded with `clmm-lp-cli pools import` invalidate the pool
+    // cache and update live candles whenever their account changes
+    if let Some(database) = &state.database {
+        match database.pools().find_watched().await {
+            Ok(pools) if !pools.is_empty() => {
+                watch_pools(&state, &config.rpc_config, &pools).await;
+            }
+            Ok(_) => {}
+            Err(e) => warn!(error = %e, "Failed to load watched pools"),
+        }
+    }
+
+    if config.api_config.advisor_enabled {
+        let recommender = OptimizerRecommender::new(config.api_config.advisor_volatility)
+            .with_threads(config.api_config.optimizer_threads);
+        let mut notifier = MultiNotifier::new();
+        notifier.add(ConsoleNotifier);
+        notifier.add(AlertBroadcastNotifier::new(&state));
+        let advisor = RangeAdvisor::new(
+            state.monitor.clone(),
+            state.provider.clone(),
+            Arc::new(recommender),
+            AdvisorConfig::default(),
+        )
+        .with_notifier(notifier)
+        .with_heartbeat(state.register_heartbeat("range advisor scheduler", Duration::ZERO));
+        info!("Starting nightly range advisor");
+        let advisor = Arc::new(advisor);
+        let supervised =
+            state
+                .supervisor
+                .supervise("range advisor", RestartPolicy::Always, move |shutdown| {
+                    advisor.clone().run_daily_until(shutdown)
+                });
+        state.shutdown.spawn("range advisor", supervised).await;
+    }
+
+    if config.api_config.daily_report_enabled {
+        start_daily_report(&state, &config.api_config).await;
+    }
+
+    if config.api_config.desktop_notifications || config.api_config.alert_pipe.is_some() {
+        start_local_alerts(&state, &config.api_config).await;
+    }
+
+    // A sharp drop in portfolio value halts strategy executors
+    let guard = state.drawdown_guard.clone();
+    let monitor = state.monitor.clone();
+    let supervised =
+        state
+            .supervisor
+            .supervise("drawdown guard", RestartPolicy::Always, move |shutdown| {
+                guard.clone().run_until(monitor.clone(), shutdown)
+            });
+    state.shutdown.spawn("drawdown guard", supervised).await;
+
+    // Liquidity, volume or fee tier anomalies pause strategies on the pool
+    let detector = state.anomaly_detector.clone();
+    let monitor = state.monitor.clone();
+    let pool_cache = state.pool_cache.clone();
+    let supervised = state.supervisor.supervise(
+        "pool anomaly detector",
+        RestartPolicy::Always,
+        move |shutdown| {
+            detector
+                .clone()
+                .run_until(monitor.clone(), pool_cache.clone(), shutdown)
+        },
+    );
+    state
+        .shutdown
+        .spawn("pool anomaly detector", supervised)
+        .await;
+
+    // Loops that stop completing cycles raise a critical alert
+    let heartbeats = state.heartbeats.clone();
+    let supervised = state.supervisor.supervise(
+        "heartbeat watchdog",
+        RestartPolicy::Always,
+        move |shutdown| heartbeats.clone().run_until(shutdown),
+    );
+    state.shutdown.spawn("heartbeat watchdog", supervised).await;
+
+    // Position WebSocket clients get PnL attribution as it changes
+    let pnl_state = state.clone();
+    let supervised =
+        state
+            .supervisor
+            .supervise("pnl updates", RestartPolicy::Always, move |shutdown| {
+                run_pnl_updates(
+                    pnl_state.clone(),
+                    Duration::from_secs(PNL_UPDATE_INTERVAL_SECS),
+                    shutdown,
+                )
+            });
+    state.shutdown.spawn("pnl updates", supervised).await;
+
+    // Alert rules follow the runtime config file while the server runs
+    if let Ok(path) = env::var("RUNTIME_CONFIG_PATH") {
+        let reloader = Arc::new(
+            ConfigReloader::new(state.config_audit.clone()).with_monitor(state.monitor.clone()),
+        );
+        let path = std::path::PathBuf::from(path);
+        let supervised =
+            state
+                .supervisor
+                .supervise("config watcher", RestartPolicy::Always, move |shutdown| {
+                    reloader
+                        .clone()
+                        .watch_file(path.clone(), Duration::from_secs(5), shutdown)
+                });
+        state.shutdown.spawn("config watcher", supervised).await;
+    }
+
+    // Alert rules stored with the API or `clmm-lp-cli alerts` are hot-loaded
+    if state.database.is_some() {
+        let rules_state = state.clone();
+        let supervised =
+            state
+                .supervisor
+                .supervise("alert rule sync", RestartPolicy::Always, move |shutdown| {
+                    sync_alert_rules(
+                        rules_state.clone(),
+                        Duration::from_secs(ALERT_RULE_SYNC_INTERVAL_SECS),
+                        shutdown,
+                    )
+                });
+        state.shutdown.spawn("alert rule sync", supervised).await;
+    }
+
+    // Optimization jobs queued in the database run on a pool of workers;
+    // more can run in other processes sharing the database
+    if state.database.is_some() {
+        start_job_workers(&state, config.api_config.job_workers).await;
+    }
+
+    // Lifecycle events only live in memory; keep them across restarts
+    if let Ok(path) = env::var("LIFECYCLE_SNAPSHOT_PATH") {
+        let lifecycle = state.lifecycle.clone();
+        state
+            .shutdown
+            .on_flush("lifecycle snapshot", move || async move {
+                let events = lifecycle.get_all_events().await;
+                tokio::fs::write(&path, serde_json::to_vec_pretty(&events)?).await?;
+                info!(path = %path, events = events.len(), "Saved lifecycle events");
+                Ok(())
+            })
+            .await;
+    }
+
+    // Create and run server; background tasks are stopped once it has drained
+    let shutdown = state.shutdown.clone();
+    let server = ApiServer::with_state(config, state);
     server.run_with_shutdown(shutdown_signal()).await?;
 
+    report_shutdown(&shutdown).await;
+
     Ok(())
 }
 
+/// Runs only job workers until a shutdown signal, for worker processes
+/// scaled separately from the API servers.
+async fn run_workers(state: AppState, workers: usize) -> Result<()> {
+    if state.database.is_none() {
+        anyhow::bail!("Worker mode needs a reachable DATABASE_URL to take jobs from");
+    }
+    if workers == 0 {
+        anyhow::bail!("Worker mode needs API_JOB_WORKERS of at least 1");
+    }
+
+    start_job_workers(&state, workers).await;
+    info!(workers, "Job workers running");
+    shutdown_signal().await;
+
+    report_shutdown(&state.shutdown).await;
+    Ok(())
+}
+
+/// Starts `workers` supervised job workers taking jobs from the database.
+async fn start_job_workers(state: &AppState, workers: usize) {
+    let prefix = env::var("API_WORKER_ID")
+        .or_else(|_| env::var("HOSTNAME"))
+        .unwrap_or_else(|_| "api".to_string());
+    for n in 0..workers {
+        let worker_id = format!("{}-{}-{}", prefix, std::process::id(), n);
+        let name = format!("job worker {}", n);
+        let worker_state = state.clone();
+        let supervised =
+            state
+                .supervisor
+                .supervise(&name, RestartPolicy::Always, move |shutdown| {
+                    run_job_worker(worker_state.clone(), worker_id.clone(), shutdown)
+                });
+        state.shutdown.spawn(&name, supervised).await;
+    }
+}
+
+/// Stops background tasks, warning if any had to be aborted.
+async fn report_shutdown(shutdown: &ShutdownController) {
+    let report = shutdown.shutdown().await;
+    if !report.is_clean() {
+        warn!(
+            aborted_tasks = ?report.aborted_tasks,
+            abandoned_transactions = report.abandoned_transactions,
+            failed_flushes = ?report.failed_flushes,
+            "Shutdown was not clean"
+        );
+    }
+}
+
+/// Starts the daily report, sent to the console and Telegram if configured,
+/// counting the alerts published to the alerts WebSocket.
+async fn start_daily_report(state: &AppState, api_config: &ApiConfig) {
+    let mut notifier = MultiNotifier::new();
+    notifier.add(ConsoleNotifier);
+    if let (Some(token), Some(chat_id)) =
+        (&api_config.telegram_bot_token, &api_config.telegram_chat_id)
+    {
+        notifier.add(TelegramNotifier::new(token, chat_id));
+    }
+    let config = ReportConfig {
+        output_dir: api_config.daily_report_dir.clone().map(Into::into),
+        ..Default::default()
+    };
+    let reporter = DailyReporter::new(state.lifecycle.clone(), state.monitor.clone(), config)
+        .with_notifier(notifier)
+        .with_heartbeat(state.register_heartbeat("daily report scheduler", Duration::ZERO));
+    info!("Starting daily report");
+    let reporter = Arc::new(reporter);
+
+    let counter = reporter.clone();
+    let alert_state = state.clone();
+    let supervised = state.supervisor.supervise(
+        "daily report alert counter",
+        RestartPolicy::Always,
+        move |shutdown| count_alerts(counter.clone(), alert_state.subscribe_alerts(), shutdown),
+    );
+    state
+        .shutdown
+        .spawn("daily report alert counter", supervised)
+        .await;
+
+    let supervised =
+        state
+            .supervisor
+            .supervise("daily report", RestartPolicy::Always, move |shutdown| {
+                reporter.clone().run_daily_until(shutdown)
+            });
+    state.shutdown.spawn("daily report", supervised).await;
+}
+
+/// Starts forwarding published alerts to desktop notifications and the
+/// alert pipe, for operators running the server on their workstation.
+async fn start_local_alerts(state: &AppState, api_config: &ApiConfig) {
+    let mut notifier = MultiNotifier::new();
+    if api_config.desktop_notifications {
+        #[cfg(feature = "desktop-notifications")]
+        notifier.add(clmm_lp_execution::prelude::DesktopNotifier::new());
+        #[cfg(not(feature = "desktop-notifications"))]
+        warn!("Desktop notifications need the desktop-notifications feature; skipping");
+    }
+    if let Some(path) = &api_config.alert_pipe {
+        notifier.add(PipeNotifier::new(path));
+    }
+    info!("Starting local alert notifications");
+    let notifier = Arc::new(notifier);
+
+    let alert_state = state.clone();
+    let supervised =
+        state
+            .supervisor
+            .supervise("local alerts", RestartPolicy::Always, move |shutdown| {
+                forward_alerts(notifier.clone(), alert_state.subscribe_alerts(), shutdown)
+            });
+    state.shutdown.spawn("local alerts", supervised).await;
+}
+
+/// Sends published alerts to `notifier` until `shutdown` is cancelled.
+async fn forward_alerts(
+    notifier: Arc<MultiNotifier>,
+    mut alerts: broadcast::Receiver<AlertUpdate>,
+    shutdown: CancellationToken,
+) {
+    loop {
+        let update = tokio::select! {
+            () = shutdown.cancelled() => break,
+            update = alerts.recv() => update,
+        };
+        match update {
+            Ok(update) => {
+                let level = match update.level.as_str() {
+                    "critical" => AlertLevel::Critical,
+                    "warning" => AlertLevel::Warning,
+                    _ => AlertLevel::Info,
+                };
+                let mut alert = Alert::new(
+                    level,
+                    AlertType::Custom("Alert".to_string()),
+                    update.message,
+                );
+                alert.position = update.position_address;
+                alert.tags = update.tags;
+                alert.timestamp = update.timestamp;
+                notifier.notify_all(&alert).await;
+            }
+            Err(broadcast::error::RecvError::Lagged(missed)) => {
+                warn!(missed, "Local alert notifications missed alerts");
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
+
+/// Counts published alerts towards the daily report until `shutdown` is
+/// cancelled.
+async fn count_alerts(
+    reporter: Arc<DailyReporter>,
+    mut alerts: broadcast::Receiver<AlertUpdate>,
+    shutdown: CancellationToken,
+) {
+    loop {
+        let alert = tokio::select! {
+            () = shutdown.cancelled() => break,
+            alert = alerts.recv() => alert,
+        };
+        match alert {
+            Ok(alert) => {
+                let level = match alert.level.as_str() {
+                    "critical" => AlertLevel::Critical,
+                    "warning" => AlertLevel::Warning,
+                    _ => AlertLevel::Info,
+                };
+                reporter.record_alert(level, alert.timestamp);
+            }
+            Err(broadcast::error::RecvError::Lagged(missed)) => {
+                warn!(missed, "Daily report missed alerts");
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
+
+/// Subscribes watched pools in a state source feeding the pool cache, live
+/// candles and the reconciler.
+///
+/// Pools are streamed from Geyser when `API_GEYSER_ENDPOINT` is set, and
+/// from WebSocket subscriptions otherwise.
+async fn watch_pools(state: &AppState, rpc_config: &RpcConfig, pools: &[PoolRecord]) {
+    let mut source = state_source(state, rpc_config);
+    let Some(mut updates) = source.take_receiver() else {
+        return;
+    };
+
+    // Drain updates before subscribing, which delivers snapshots
+    let pool_cache = state.pool_cache.clone();
+    let candles = state.candles.clone();
+    let reconciler = state.reconciler.clone();
+    state
+        .shutdown
+        .spawn("pool updates", async move {
+            while let Some(update) = updates.recv().await {
+                pool_cache.process_update(&update).await;
+                let now = chrono::Utc::now().timestamp().max(0) as u64;
+                candles.process_update(&update, now).await;
+                reconciler.process_update(update).await;
+            }
+        })
+        .await;
+
+    for pool in pools {
+        match Pubkey::from_str(&pool.address) {
+            Ok(address) => {
+                state
+                    .reconciler
+                    .track_via(source.as_ref(), address, SubscriptionType::Pool)
+                    .await;
+            }
+            Err(e) => warn!(address = %pool.address, error = %e, "Invalid watched pool address"),
+        }
+    }
+    info!(
+        source = source.name(),
+        pools = source.subscription_count().await,
+        "Watching pools"
+    );
+
+    let source: Arc<dyn StateSource> = Arc::from(source);
+    let supervised =
+        state
+            .supervisor
+            .supervise("pool listener", RestartPolicy::Always, move |shutdown| {
+                let source = source.clone();
+                async move { source.run_until(shutdown).await }
+            });
+    state.shutdown.spawn("pool listener", supervised).await;
+
+    // Refetch watched pools whose updates stopped arriving
+    let reconciler = state.reconciler.clone();
+    let supervised =
+        state
+            .supervisor
+            .supervise("reconciler", RestartPolicy::Always, move |shutdown| {
+                let reconciler = reconciler.clone();
+                async move { reconciler.run_until(shutdown).await }
+            });
+    state.shutdown.spawn("reconciler", supervised).await;
+}
+
+/// Creates the source watched pools are streamed from.
+fn state_source(state: &AppState, rpc_config: &RpcConfig) -> Box<dyn StateSource> {
+    if let Some(endpoint) = &state.config.geyser_endpoint {
+        #[cfg(feature = "geyser")]
+        {
+            let mut config = clmm_lp_execution::prelude::GeyserConfig::new(endpoint);
+            config.commitment = rpc_config.commitment;
+            config.x_token = state.config.geyser_x_token.clone();
+            return Box::new(
+                clmm_lp_execution::prelude::GeyserSource::new(config)
+                    .with_slot_orderer(state.slot_orderer.clone())
+                    .with_provider(state.provider.clone()),
+            );
+        }
+        #[cfg(not(feature = "geyser"))]
+        warn!(
+            endpoint = %endpoint,
+            "Geyser ingestion needs the geyser feature; using WebSocket subscriptions"
+        );
+    }
+
+    let config = AccountListenerConfig {
+        commitment: rpc_config.commitment,
+        ..AccountListenerConfig::for_cluster(rpc_config.cluster)
+    };
+    Box::new(
+        AccountListener::new(config)
+            .with_slot_orderer(state.slot_orderer.clone())
+            .with_provider(state.provider.clone()),
+    )
+}
+
 /// Loads server configuration from environment variables.
-fn load_config_from_env() -> ServerConfig {
+///
+/// Fails on an unknown `SOLANA_CLUSTER` or `SOLANA_COMMITMENT` rather than
+/// falling back to a default.
+fn load_config_from_env() -> Result<ServerConfig> {
     let host = env::var("API_HOST").unwrap_or_else(|_| "0.0.0.0".to_string());
     let port = env::var("API_PORT")
         .ok()
         .and_then(|p| p.parse().ok())
         .unwrap_or(8080);
+    let grpc_port = env::var("API_GRPC_PORT").ok().and_then(|p| p.parse().ok());
 
+    let cluster: Cluster = match env::var("SOLANA_CLUSTER") {
+        Ok(name) => name.parse()?,
+        Err(_) => Cluster::default(),
+    };
     let rpc_url = env::var("SOLANA_RPC_URL")
-        .unwrap_or_else(|_| "https://api.mainnet-beta.solana.com".to_string());
+        .ok()
+        .filter(|url| !url.is_empty())
+        .unwrap_or_else(|| cluster.rpc_url().to_string());
+
+    let commitment: CommitmentLevel = match env::var("SOLANA_COMMITMENT") {
+        Ok(level) => level.parse()?,
+        Err(_) => CommitmentLevel::default(),
+    };
 
     let rpc_config = RpcConfig {
         primary_url: rpc_url,
-        ..Default::default()
+        commitment,
+        ..RpcConfig::for_cluster(cluster)
     };
 
+    let (lifecycle_webhook_url, lifecycle_webhook_secret) = load_lifecycle_webhook_from_env()?;
     let api_config = ApiConfig {
-        enable_cors: env::var("API_CORS_ALLOW_ALL")
-            .map(|v| v == "true")
-            .unwrap_or(true),
         rate_limit_per_minute: env::var("API_RATE_LIMIT_RPM")
             .ok()
             .and_then(|v| v.parse().ok())
             .unwrap_or(100),
-        request_timeout_secs: env::var("API_REQUEST_TIMEOUT_SECS")
+        max_concurrent_optimizations: env::var("API_MAX_CONCURRENT_OPTIMIZATIONS")
             .ok()
             .and_then(|v| v.parse().ok())
-            .unwrap_or(30),
+            .unwrap_or(1),
+        optimizer_threads: env::var("API_OPTIMIZER_THREADS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(2),
+        job_workers: env::var("API_JOB_WORKERS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(1),
+        advisor_enabled: env::var("API_ADVISOR_ENABLED")
+            .map(|v| v == "true")
+            .unwrap_or(false),
+        advisor_volatility: env::var("API_ADVISOR_VOLATILITY")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0.5),
+        heartbeat_window_secs: env::var("API_HEARTBEAT_WINDOW_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(600),
+        max_drawdown_pct: env::var("API_MAX_DRAWDOWN_PCT")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(Decimal::new(10, 2)),
+        drawdown_window_hours: env::var("API_DRAWDOWN_WINDOW_HOURS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(24),
+        daily_report_enabled: env::var("API_DAILY_REPORT_ENABLED")
+            .map(|v| v == "true")
+            .unwrap_or(false),
+        daily_report_dir: env::var("API_DAILY_REPORT_DIR").ok(),
+        telegram_bot_token: env::var("API_TELEGRAM_BOT_TOKEN").ok(),
+        telegram_chat_id: env::var("API_TELEGRAM_CHAT_ID").ok(),
+        lifecycle_webhook_url,
+        lifecycle_webhook_secret,
+        desktop_notifications: env::var("API_DESKTOP_NOTIFICATIONS")
+            .map(|v| v == "true")
+            .unwrap_or(false),
+        alert_pipe: env::var("API_ALERT_PIPE").ok(),
+        geyser_endpoint: env::var("API_GEYSER_ENDPOINT")
+            .ok()
+            .filter(|url| !url.is_empty()),
+        geyser_x_token: env::var("API_GEYSER_X_TOKEN").ok(),
+        priority_fee_api_url: env::var("API_PRIORITY_FEE_API_URL")
+            .ok()
+            .filter(|url| !url.is_empty()),
         ..Default::default()
     };
 
-    ServerConfig {
+    Ok(ServerConfig {
         host,
         port,
+        grpc_port,
         rpc_config,
         api_config,
+        serve_dashboard: env::var("API_SERVE_DASHBOARD")
+            .map(|v| v == "true")
+            .unwrap_or(false),
+        cors: load_cors_config_from_env(),
+        security_headers: load_security_headers_from_env(),
+        max_body_bytes: env::var("API_MAX_BODY_BYTES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(1024 * 1024),
+        request_timeout_secs: env::var("API_REQUEST_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(30),
+        tls: load_tls_config_from_env()?,
+    })
+}
+
+/// Loads the lifecycle webhook endpoint and its signing secret from
+/// environment variables; an endpoint without a secret is refused.
+fn load_lifecycle_webhook_from_env() -> Result<(Option<String>, Option<String>)> {
+    let url = env::var("API_LIFECYCLE_WEBHOOK_URL")
+        .ok()
+        .filter(|url| !url.is_empty());
+    let secret = env::var("API_LIFECYCLE_WEBHOOK_SECRET")
+        .ok()
+        .filter(|secret| !secret.is_empty());
+
+    if url.is_some() && secret.is_none() {
+        anyhow::bail!(
+            "API_LIFECYCLE_WEBHOOK_URL requires a non-empty API_LIFECYCLE_WEBHOOK_SECRET"
+        );
+    }
+    Ok((url, secret))
+}
+
+/// Loads the TLS configuration from environment variables; HTTPS is
+/// served when both a certificate and a key are given.
+fn load_tls_config_from_env() -> Result<Option<TlsConfig>> {
+    let cert_path = env::var("API_TLS_CERT_PATH").ok().filter(|p| !p.is_empty());
+    let key_path = env::var("API_TLS_KEY_PATH").ok().filter(|p| !p.is_empty());
+
+    match (cert_path, key_path) {
+        (Some(cert_path), Some(key_path)) => {
+            let mut tls = TlsConfig::new(cert_path, key_path);
+            if let Some(secs) = env::var("API_TLS_RELOAD_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+            {
+                tls = tls.with_reload_interval(secs);
+            }
+            Ok(Some(tls))
+        }
+        (None, None) => Ok(None),
+        _ => anyhow::bail!("API_TLS_CERT_PATH and API_TLS_KEY_PATH must be set together"),
+    }
+}
+
+/// Parses a comma-separated list from an environment variable.
+fn env_list(name: &str) -> Option<Vec<String>> {
+    env::var(name).ok().map(|v| {
+        v.split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(String::from)
+            .collect()
+    })
+}
+
+/// Loads the CORS policy from environment variables.
+fn load_cors_config_from_env() -> CorsConfig {
+    let defaults = CorsConfig::default();
+    // `API_CORS_ALLOW_ALL=false` predates the origin list and disables CORS
+    let cors_disabled = env::var("API_CORS_ALLOW_ALL").is_ok_and(|v| v == "false");
+
+    CorsConfig {
+        allowed_origins: match env_list("API_CORS_ORIGINS") {
+            Some(origins) => origins,
+            None if cors_disabled => Vec::new(),
+            None => defaults.allowed_origins,
+        },
+        allowed_methods: env_list("API_CORS_METHODS").unwrap_or(defaults.allowed_methods),
+        allowed_headers: env_list("API_CORS_HEADERS").unwrap_or(defaults.allowed_headers),
+        max_age_secs: env::var("API_CORS_MAX_AGE_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(defaults.max_age_secs),
+    }
+}
+
+/// Loads the security headers from environment variables.
+fn load_security_headers_from_env() -> SecurityHeadersConfig {
+    let defaults = SecurityHeadersConfig::default();
+
+    SecurityHeadersConfig {
+        // 0 disables HSTS
+        hsts_max_age_secs: match env::var("API_HSTS_MAX_AGE_SECS") {
+            Ok(v) => v.parse().ok().filter(|secs| *secs > 0),
+            Err(_) => defaults.hsts_max_age_secs,
+        },
+        hsts_include_subdomains: env::var("API_HSTS_INCLUDE_SUBDOMAINS")
+            .map(|v| v == "true")
+            .unwrap_or(defaults.hsts_include_subdomains),
+        ..defaults
     }
 }
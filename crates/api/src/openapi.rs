@@ -4,11 +4,25 @@
 
 use crate::handlers;
 use crate::models::{
-    CreateStrategyRequest, HealthResponse, ListPoolsResponse, ListPositionsResponse,
-    ListStrategiesResponse, MessageResponse, MetricsResponse, OpenPositionRequest, PnLResponse,
-    PoolResponse, PoolStateResponse, PortfolioAnalyticsResponse, PositionResponse,
-    RebalanceRequest, SimulationRequest, SimulationResponse, StrategyPerformanceResponse,
-    StrategyResponse,
+    AlertRuleRequest, AlertRuleResponse, AssignStrategyRequest, CapitalEfficiencyResponse,
+    ConfigChangeResponse, ConfigChangesResponse, CreateStrategyRequest, HealthResponse,
+    LeaderboardEntryResponse, ListAlertRulesResponse, ListPausedPoolsResponse, ListPoolsResponse,
+    ListPositionsResponse, ListStrategiesResponse, MessageResponse, MetricComparisonResponse,
+    MetricsResponse, MigratePositionRequest, MigrationResponse, NormalizedCurveResponse,
+    OpenPositionRequest, OptimizationJobResponse, OptimizationJobStatus,
+    OptimizationLeaderboardResponse, OptimizationObjective, OptimizationPathsResponse,
+    OptimizationRequest, OptimizationValidationResponse, PausedPoolResponse,
+    PercentilePathResponse, PerformanceBucketResponse, PerformanceHistoryResponse,
+    PerformancePeriod, PnLResponse, PoolResponse, PoolRewardResponse, PoolRiskResponse,
+    PoolStateResponse, PortfolioAnalyticsResponse, PositionHealthResponse,
+    PositionReadOnlyResponse, PositionResponse, PositionStrategyResponse, PositionTagsResponse,
+    PriceDownsampling, PriceHistoryResponse, PricePointResponse, RangeUtilizationResponse,
+    RebalanceRequest, RecommendationDriftResponse, SetPositionReadOnlyRequest,
+    SetPositionTagsRequest, SimulationComparisonResponse, SimulationRequest, SimulationResponse,
+    StrategyPerformanceResponse, StrategyResponse, TagAnalyticsResponse, TokenRiskResponse,
+    UtilizationBucketResponse, ValidationRunResponse, VenueComparisonResponse, VenueResponse,
+    WhatIfOutcomeResponse, WhatIfRequest, WhatIfResponse, WhatIfScenarioRequest,
+    WhatIfStrategyKind,
 };
 use utoipa::OpenApi;
 
@@ -37,8 +51,9 @@ use utoipa::OpenApi;
         (name = "Health", description = "Health check and metrics endpoints"),
         (name = "Positions", description = "LP position management"),
         (name = "Strategies", description = "Automated strategy management"),
+        (name = "Alerts", description = "Alert rule configuration"),
         (name = "Pools", description = "Pool information and state"),
-        (name = "Analytics", description = "Portfolio analytics and simulations")
+        (name = "Analytics", description = "Portfolio analytics, simulations and optimizations")
     ),
     paths(
         // Health endpoints
@@ -53,7 +68,14 @@ use utoipa::OpenApi;
         handlers::close_position,
         handlers::collect_fees,
         handlers::rebalance_position,
+        handlers::migrate_position,
         handlers::get_position_pnl,
+        handlers::get_position_strategy,
+        handlers::assign_position_strategy,
+        handlers::clear_position_strategy,
+        handlers::set_position_tags,
+        handlers::set_position_read_only,
+        handlers::position_what_if,
         // Strategy endpoints
         handlers::list_strategies,
         handlers::get_strategy,
@@ -63,13 +85,35 @@ use utoipa::OpenApi;
         handlers::start_strategy,
         handlers::stop_strategy,
         handlers::get_strategy_performance,
+        handlers::list_config_changes,
+        // Alert endpoints
+        handlers::list_alert_rules,
+        handlers::get_alert_rule,
+        handlers::create_alert_rule,
+        handlers::update_alert_rule,
+        handlers::delete_alert_rule,
         // Pool endpoints
         handlers::list_pools,
         handlers::get_pool,
         handlers::get_pool_state,
+        handlers::get_pool_risk,
+        handlers::list_paused_pools,
+        handlers::resume_pool,
+        handlers::get_capital_efficiency,
+        handlers::get_optimization_leaderboard,
+        handlers::get_price_history,
+        handlers::get_range_utilization,
         // Analytics endpoints
         handlers::get_portfolio_analytics,
+        handlers::get_performance_history,
         handlers::run_simulation,
+        handlers::start_optimization,
+        handlers::get_optimization_job,
+        handlers::cancel_optimization_job,
+        handlers::get_optimization_paths,
+        handlers::get_optimization_simulations,
+        handlers::compare_simulations,
+        handlers::compare_venues,
     ),
     components(
         schemas(
@@ -80,22 +124,73 @@ use utoipa::OpenApi;
             ListPositionsResponse,
             PositionResponse,
             PnLResponse,
+            PositionHealthResponse,
             OpenPositionRequest,
             RebalanceRequest,
+            MigratePositionRequest,
+            MigrationResponse,
+            AssignStrategyRequest,
+            PositionStrategyResponse,
+            SetPositionTagsRequest,
+            PositionTagsResponse,
+            SetPositionReadOnlyRequest,
+            PositionReadOnlyResponse,
+            WhatIfRequest,
+            WhatIfScenarioRequest,
+            WhatIfStrategyKind,
+            WhatIfResponse,
+            WhatIfOutcomeResponse,
             MessageResponse,
             // Strategies
             ListStrategiesResponse,
             StrategyResponse,
             StrategyPerformanceResponse,
             CreateStrategyRequest,
+            ConfigChangeResponse,
+            ConfigChangesResponse,
+            // Alerts
+            AlertRuleRequest,
+            AlertRuleResponse,
+            ListAlertRulesResponse,
             // Pools
             ListPoolsResponse,
             PoolResponse,
+            PoolRewardResponse,
             PoolStateResponse,
+            PoolRiskResponse,
+            TokenRiskResponse,
+            PausedPoolResponse,
+            ListPausedPoolsResponse,
+            OptimizationLeaderboardResponse,
+            LeaderboardEntryResponse,
+            RecommendationDriftResponse,
+            CapitalEfficiencyResponse,
+            PriceDownsampling,
+            PriceHistoryResponse,
+            PricePointResponse,
+            RangeUtilizationResponse,
+            UtilizationBucketResponse,
             // Analytics
             PortfolioAnalyticsResponse,
+            TagAnalyticsResponse,
+            PerformancePeriod,
+            PerformanceHistoryResponse,
+            PerformanceBucketResponse,
             SimulationRequest,
             SimulationResponse,
+            SimulationComparisonResponse,
+            VenueComparisonResponse,
+            VenueResponse,
+            MetricComparisonResponse,
+            NormalizedCurveResponse,
+            OptimizationRequest,
+            OptimizationObjective,
+            OptimizationJobStatus,
+            OptimizationJobResponse,
+            OptimizationPathsResponse,
+            PercentilePathResponse,
+            OptimizationValidationResponse,
+            ValidationRunResponse,
         )
     ),
     modifiers(&SecurityAddon)
@@ -15,17 +15,25 @@ pub use crate::error::{ApiError, ApiResult, ErrorResponse};
 pub use crate::models::{
     CircuitBreakerStatus, ComponentHealth, CreateStrategyRequest, HealthResponse,
     ListPoolsResponse, ListPositionsResponse, ListStrategiesResponse, MessageResponse,
-    MetricsResponse, OpenPositionRequest, PnLResponse, PoolResponse, PoolStateResponse,
-    PortfolioAnalyticsResponse, PositionResponse, PositionStatus, RebalanceRequest, ServiceStatus,
-    SimulationRequest, SimulationResponse, StrategyParameters, StrategyPerformanceResponse,
-    StrategyResponse, StrategyType, SuccessResponse,
+    MetricsResponse, OpenPositionRequest, OptimizationJobResponse, OptimizationJobStatus,
+    OptimizationObjective, OptimizationRequest, PnLResponse, PoolResponse, PoolRewardResponse,
+    PoolStateResponse, PortfolioAnalyticsResponse, PositionHealthResponse, PositionResponse,
+    PositionStatus, RebalanceRequest, ServiceStatus, SimulationRequest, SimulationResponse,
+    StrategyParameters, StrategyPerformanceResponse, StrategyResponse, StrategyType,
+    SuccessResponse,
 };
 
 // Server
-pub use crate::server::{ApiServer, ServerConfig, shutdown_signal};
+pub use crate::server::{
+    ApiServer, CorsConfig, SecurityHeadersConfig, ServerConfig, shutdown_signal,
+};
+pub use crate::tls::TlsConfig;
 
 // State
-pub use crate::state::{AlertUpdate, ApiConfig, AppState, PositionUpdate, StrategyState};
+pub use crate::state::{
+    AlertUpdate, ApiConfig, AppState, OptimizationJob, OptimizationJobState, PositionUpdate,
+    StrategyState,
+};
 
 // Middleware
 pub use crate::middleware::RateLimiter;
@@ -34,7 +42,7 @@ pub use crate::middleware::RateLimiter;
 pub use crate::routes::{create_router, create_versioned_router};
 
 // Services
-pub use crate::services::{PositionService, StrategyService};
+pub use crate::services::{OptimizationService, PositionService, StrategyService};
 
 // Authentication
 pub use crate::auth::{AuthConfig, AuthError, AuthState, Claims, Role};
@@ -26,7 +26,29 @@ pub fn create_router(state: AppState) -> Router {
             "/positions/{address}/rebalance",
             post(handlers::rebalance_position),
         )
+        .route(
+            "/positions/{address}/migrate",
+            post(handlers::migrate_position),
+        )
         .route("/positions/{address}/pnl", get(handlers::get_position_pnl))
+        .route(
+            "/positions/{address}/strategy",
+            get(handlers::get_position_strategy)
+                .put(handlers::assign_position_strategy)
+                .delete(handlers::clear_position_strategy),
+        )
+        .route(
+            "/positions/{address}/tags",
+            put(handlers::set_position_tags),
+        )
+        .route(
+            "/positions/{address}/read-only",
+            put(handlers::set_position_read_only),
+        )
+        .route(
+            "/positions/{address}/what-if",
+            post(handlers::position_what_if),
+        )
         // Strategy routes
         .route("/strategies", get(handlers::list_strategies))
         .route("/strategies", post(handlers::create_strategy))
@@ -39,16 +61,68 @@ pub fn create_router(state: AppState) -> Router {
             "/strategies/{id}/performance",
             get(handlers::get_strategy_performance),
         )
+        .route("/config/changes", get(handlers::list_config_changes))
+        // Alert rule routes
+        .route(
+            "/alerts/rules",
+            get(handlers::list_alert_rules).post(handlers::create_alert_rule),
+        )
+        .route(
+            "/alerts/rules/{name}",
+            get(handlers::get_alert_rule)
+                .put(handlers::update_alert_rule)
+                .delete(handlers::delete_alert_rule),
+        )
         // Pool routes
         .route("/pools", get(handlers::list_pools))
+        .route("/pools/anomalies", get(handlers::list_paused_pools))
         .route("/pools/{address}", get(handlers::get_pool))
         .route("/pools/{address}/state", get(handlers::get_pool_state))
+        .route("/pools/{address}/risk", get(handlers::get_pool_risk))
+        .route("/pools/{address}/resume", post(handlers::resume_pool))
+        .route(
+            "/pools/{address}/capital-efficiency",
+            get(handlers::get_capital_efficiency),
+        )
+        .route("/pools/{address}/prices", get(handlers::get_price_history))
+        .route(
+            "/pools/{address}/utilization",
+            get(handlers::get_range_utilization),
+        )
+        .route(
+            "/pools/{address}/optimizations",
+            get(handlers::get_optimization_leaderboard),
+        )
         // Analytics routes
         .route(
             "/analytics/portfolio",
             get(handlers::get_portfolio_analytics),
         )
+        .route(
+            "/analytics/performance",
+            get(handlers::get_performance_history),
+        )
         .route("/analytics/simulate", post(handlers::run_simulation))
+        .route("/analytics/venues", get(handlers::compare_venues))
+        .route("/analytics/optimize", post(handlers::start_optimization))
+        .route(
+            "/analytics/optimize/{id}",
+            get(handlers::get_optimization_job),
+        )
+        .route(
+            "/analytics/optimize/{id}",
+            delete(handlers::cancel_optimization_job),
+        )
+        .route(
+            "/analytics/optimize/{id}/paths",
+            get(handlers::get_optimization_paths),
+        )
+        .route(
+            "/analytics/optimize/{id}/simulations",
+            get(handlers::get_optimization_simulations),
+        )
+        // Simulation routes
+        .route("/simulations/compare", get(handlers::compare_simulations))
         // WebSocket routes
         .route("/ws/positions", get(websocket::positions_ws))
         .route("/ws/alerts", get(websocket::alerts_ws))
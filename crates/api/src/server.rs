@@ -1,26 +1,37 @@
 //! Server configuration and startup.
 
+use crate::grpc;
 use crate::handlers::health::init_start_time;
 use crate::middleware::{RateLimiter, request_logging};
 use crate::openapi::ApiDoc;
 use crate::routes::create_versioned_router;
 use crate::state::{ApiConfig, AppState};
+use crate::tls::{self, TlsConfig};
+use axum::extract::DefaultBodyLimit;
+use axum::http::{HeaderName, HeaderValue, Method, header};
 use axum::{Router, middleware};
 use clmm_lp_protocols::prelude::RpcConfig;
+use futures::FutureExt;
 use std::collections::HashSet;
+use std::future::Future;
 use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::net::TcpListener;
 use tower_http::{
-    cors::{Any, CorsLayer},
+    cors::{AllowHeaders, AllowMethods, AllowOrigin, CorsLayer},
+    limit::RequestBodyLimitLayer,
+    set_header::SetResponseHeaderLayer,
     timeout::TimeoutLayer,
     trace::TraceLayer,
 };
-use tracing::info;
+use tracing::{info, warn};
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
+/// Default request timeout in seconds.
+pub const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 30;
+
 /// Server configuration.
 #[derive(Debug, Clone)]
 pub struct ServerConfig {
@@ -28,10 +39,26 @@ pub struct ServerConfig {
     pub host: String,
     /// Port to bind to.
     pub port: u16,
+    /// Port to serve the gRPC engine service on, if enabled.
+    pub grpc_port: Option<u16>,
     /// RPC configuration.
     pub rpc_config: RpcConfig,
     /// API configuration.
     pub api_config: ApiConfig,
+    /// Whether to serve the embedded web dashboard at `/`; needs the
+    /// `dashboard` feature.
+    pub serve_dashboard: bool,
+    /// Cross-origin request policy for browser clients.
+    pub cors: CorsConfig,
+    /// Security headers added to every response.
+    pub security_headers: SecurityHeadersConfig,
+    /// Largest request body accepted, in bytes; larger requests get
+    /// `413 Payload Too Large`.
+    pub max_body_bytes: usize,
+    /// Request timeout in seconds.
+    pub request_timeout_secs: u64,
+    /// Serves HTTPS with this certificate instead of plain HTTP.
+    pub tls: Option<TlsConfig>,
 }
 
 impl Default for ServerConfig {
@@ -39,12 +66,193 @@ impl Default for ServerConfig {
         Self {
             host: "0.0.0.0".to_string(),
             port: 8080,
+            grpc_port: None,
             rpc_config: RpcConfig::default(),
             api_config: ApiConfig::default(),
+            serve_dashboard: false,
+            cors: CorsConfig::default(),
+            security_headers: SecurityHeadersConfig::default(),
+            max_body_bytes: 1024 * 1024,
+            request_timeout_secs: DEFAULT_REQUEST_TIMEOUT_SECS,
+            tls: None,
+        }
+    }
+}
+
+/// Cross-origin request policy.
+#[derive(Debug, Clone)]
+pub struct CorsConfig {
+    /// Origins allowed to call the API, e.g. `https://app.example.com`;
+    /// `*` allows any origin and an empty list disables CORS.
+    pub allowed_origins: Vec<String>,
+    /// Methods allowed in cross-origin requests; `*` allows any.
+    pub allowed_methods: Vec<String>,
+    /// Request headers allowed in cross-origin requests; `*` allows any.
+    pub allowed_headers: Vec<String>,
+    /// How long browsers may cache preflight responses, in seconds.
+    pub max_age_secs: u64,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            allowed_origins: vec!["*".to_string()],
+            allowed_methods: ["GET", "POST", "PUT", "PATCH", "DELETE"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            allowed_headers: vec!["*".to_string()],
+            max_age_secs: 3600,
+        }
+    }
+}
+
+impl ServerConfig {
+    /// Returns the CORS layer, honoring the deprecated
+    /// `ApiConfig::enable_cors` switch.
+    #[allow(deprecated)]
+    fn cors_layer(&self) -> Option<CorsLayer> {
+        if !self.api_config.enable_cors {
+            return None;
+        }
+        self.cors.layer()
+    }
+
+    /// Returns the request timeout, falling back to the deprecated
+    /// `ApiConfig::request_timeout_secs` when it is the only one changed.
+    #[allow(deprecated)]
+    fn request_timeout(&self) -> Duration {
+        let secs = if self.request_timeout_secs == DEFAULT_REQUEST_TIMEOUT_SECS {
+            self.api_config.request_timeout_secs
+        } else {
+            self.request_timeout_secs
+        };
+        Duration::from_secs(secs)
+    }
+}
+
+impl CorsConfig {
+    /// Builds the CORS layer, or `None` if no origin is allowed.
+    ///
+    /// Entries that are not valid origins, methods or header names are
+    /// skipped with a warning.
+    #[must_use]
+    pub fn layer(&self) -> Option<CorsLayer> {
+        if self.allowed_origins.is_empty() {
+            return None;
         }
+
+        let origins = if is_wildcard(&self.allowed_origins) {
+            AllowOrigin::any()
+        } else {
+            AllowOrigin::list(parse_entries::<HeaderValue>(
+                &self.allowed_origins,
+                "origin",
+            ))
+        };
+        let methods = if is_wildcard(&self.allowed_methods) {
+            AllowMethods::any()
+        } else {
+            AllowMethods::list(parse_entries::<Method>(&self.allowed_methods, "method"))
+        };
+        let headers = if is_wildcard(&self.allowed_headers) {
+            AllowHeaders::any()
+        } else {
+            AllowHeaders::list(parse_entries::<HeaderName>(&self.allowed_headers, "header"))
+        };
+
+        Some(
+            CorsLayer::new()
+                .allow_origin(origins)
+                .allow_methods(methods)
+                .allow_headers(headers)
+                .max_age(Duration::from_secs(self.max_age_secs)),
+        )
     }
 }
 
+/// Security headers added to every response.
+#[derive(Debug, Clone)]
+pub struct SecurityHeadersConfig {
+    /// `max-age` of the `Strict-Transport-Security` header in seconds;
+    /// `None` omits it. Browsers only honor it over HTTPS.
+    pub hsts_max_age_secs: Option<u64>,
+    /// Whether HSTS also covers subdomains.
+    pub hsts_include_subdomains: bool,
+    /// Whether to send `X-Content-Type-Options: nosniff`.
+    pub no_sniff: bool,
+    /// Value of the `X-Frame-Options` header; `None` omits it.
+    pub frame_options: Option<String>,
+    /// Value of the `Referrer-Policy` header; `None` omits it.
+    pub referrer_policy: Option<String>,
+}
+
+impl Default for SecurityHeadersConfig {
+    fn default() -> Self {
+        Self {
+            hsts_max_age_secs: Some(31_536_000),
+            hsts_include_subdomains: false,
+            no_sniff: true,
+            frame_options: Some("DENY".to_string()),
+            referrer_policy: Some("no-referrer".to_string()),
+        }
+    }
+}
+
+impl SecurityHeadersConfig {
+    /// Returns the headers to add to responses.
+    #[must_use]
+    pub fn headers(&self) -> Vec<(HeaderName, HeaderValue)> {
+        let mut headers = Vec::new();
+        if let Some(max_age) = self.hsts_max_age_secs {
+            let mut value = format!("max-age={}", max_age);
+            if self.hsts_include_subdomains {
+                value.push_str("; includeSubDomains");
+            }
+            headers.push((header::STRICT_TRANSPORT_SECURITY, value));
+        }
+        if self.no_sniff {
+            headers.push((header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()));
+        }
+        if let Some(value) = &self.frame_options {
+            headers.push((header::X_FRAME_OPTIONS, value.clone()));
+        }
+        if let Some(value) = &self.referrer_policy {
+            headers.push((header::REFERRER_POLICY, value.clone()));
+        }
+
+        headers
+            .into_iter()
+            .filter_map(|(name, value)| match HeaderValue::from_str(&value) {
+                Ok(value) => Some((name, value)),
+                Err(_) => {
+                    warn!(header = %name, value = %value, "Skipping invalid security header");
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+/// Returns true if `entries` allow anything.
+fn is_wildcard(entries: &[String]) -> bool {
+    entries.iter().any(|entry| entry.trim() == "*")
+}
+
+/// Parses configured entries, skipping invalid ones.
+fn parse_entries<T: std::str::FromStr>(entries: &[String], kind: &str) -> Vec<T> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let parsed = entry.trim().parse().ok();
+            if parsed.is_none() {
+                warn!(kind = kind, entry = %entry, "Skipping invalid CORS entry");
+            }
+            parsed
+        })
+        .collect()
+}
+
 /// API server.
 pub struct ApiServer {
     /// Server configuration.
@@ -83,24 +291,39 @@ impl ApiServer {
         router =
             router.merge(SwaggerUi::new("/docs").url("/api-docs/openapi.json", ApiDoc::openapi()));
 
+        // Paths no route claims load the dashboard
+        if self.config.serve_dashboard {
+            #[cfg(feature = "dashboard")]
+            {
+                router = router.fallback(crate::dashboard::dashboard_handler);
+            }
+            #[cfg(not(feature = "dashboard"))]
+            tracing::warn!("Serving the dashboard needs the dashboard feature");
+        }
+
         // Add middleware
         router = router.layer(middleware::from_fn(request_logging));
 
-        // Add CORS if enabled
-        if self.config.api_config.enable_cors {
-            let cors = CorsLayer::new()
-                .allow_origin(Any)
-                .allow_methods(Any)
-                .allow_headers(Any);
-            router = router.layer(cors);
-        }
+        // Reject large bodies before they are read; the limit replaces the
+        // extractors' own default
+        router = router
+            .layer(RequestBodyLimitLayer::new(self.config.max_body_bytes))
+            .layer(DefaultBodyLimit::disable());
 
         // Add timeout
         #[allow(deprecated)]
         {
-            router = router.layer(TimeoutLayer::new(Duration::from_secs(
-                self.config.api_config.request_timeout_secs,
-            )));
+            router = router.layer(TimeoutLayer::new(self.config.request_timeout()));
+        }
+
+        // Add CORS if any origin is allowed
+        if let Some(cors) = self.config.cors_layer() {
+            router = router.layer(cors);
+        }
+
+        // Security headers also cover preflight and error responses
+        for (name, value) in self.config.security_headers.headers() {
+            router = router.layer(SetResponseHeaderLayer::if_not_present(name, value));
         }
 
         // Add tracing
@@ -117,10 +340,10 @@ impl ApiServer {
 
         let router = self.build_router();
 
-        info!(address = %addr, "Starting API server");
+        info!(address = %addr, tls = self.config.tls.is_some(), "Starting API server");
 
-        let listener = TcpListener::bind(addr).await?;
-        axum::serve(listener, router).await?;
+        let rest = self.serve_rest(addr, router, std::future::pending());
+        tokio::try_join!(rest, self.serve_grpc(std::future::pending()))?;
 
         Ok(())
     }
@@ -136,17 +359,53 @@ impl ApiServer {
 
         let router = self.build_router();
 
-        info!(address = %addr, "Starting API server with graceful shutdown");
+        info!(
+            address = %addr,
+            tls = self.config.tls.is_some(),
+            "Starting API server with graceful shutdown"
+        );
+
+        // Both servers drain on the same signal
+        let shutdown_signal = shutdown_signal.shared();
+        let rest_shutdown = shutdown_signal.clone();
+        let rest = async {
+            self.serve_rest(addr, router, rest_shutdown).await?;
+            info!("API server stopped");
+            Ok(())
+        };
+        tokio::try_join!(rest, self.serve_grpc(shutdown_signal))?;
+
+        Ok(())
+    }
+
+    /// Serves the REST API until `shutdown` completes, over HTTPS if TLS is
+    /// configured.
+    async fn serve_rest(
+        &self,
+        addr: SocketAddr,
+        router: Router,
+        shutdown: impl Future<Output = ()> + Send + 'static,
+    ) -> anyhow::Result<()> {
+        if let Some(tls_config) = &self.config.tls {
+            return tls::serve(addr, router, tls_config, shutdown).await;
+        }
 
         let listener = TcpListener::bind(addr).await?;
         axum::serve(listener, router)
-            .with_graceful_shutdown(shutdown_signal)
+            .with_graceful_shutdown(shutdown)
             .await?;
-
-        info!("API server stopped");
-
         Ok(())
     }
+
+    /// Serves the gRPC engine service until `shutdown` completes, if a gRPC
+    /// port is configured.
+    async fn serve_grpc(&self, shutdown: impl Future<Output = ()>) -> anyhow::Result<()> {
+        let Some(port) = self.config.grpc_port else {
+            return Ok(());
+        };
+        let addr: SocketAddr = format!("{}:{}", self.config.host, port).parse()?;
+        grpc::serve(self.state.clone(), addr, shutdown).await
+    }
 }
 
 /// Creates a shutdown signal that listens for Ctrl+C.
@@ -156,3 +415,192 @@ pub async fn shutdown_signal() {
         .expect("Failed to install Ctrl+C handler");
     info!("Shutdown signal received");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use tower::ServiceExt;
+
+    #[test]
+    fn test_security_headers() {
+        let config = SecurityHeadersConfig {
+            hsts_include_subdomains: true,
+            referrer_policy: None,
+            ..Default::default()
+        };
+        let headers = config.headers();
+
+        assert_eq!(headers.len(), 3);
+        assert!(headers.contains(&(
+            header::STRICT_TRANSPORT_SECURITY,
+            HeaderValue::from_static("max-age=31536000; includeSubDomains")
+        )));
+        assert!(headers.contains(&(
+            header::X_CONTENT_TYPE_OPTIONS,
+            HeaderValue::from_static("nosniff")
+        )));
+        assert!(headers.contains(&(header::X_FRAME_OPTIONS, HeaderValue::from_static("DENY"))));
+
+        let disabled = SecurityHeadersConfig {
+            hsts_max_age_secs: None,
+            no_sniff: false,
+            frame_options: None,
+            referrer_policy: None,
+            ..Default::default()
+        };
+        assert!(disabled.headers().is_empty());
+    }
+
+    /// Router with the middleware of `config` over a fresh state.
+    fn router(config: ServerConfig) -> Router {
+        let state = AppState::new(config.rpc_config.clone(), config.api_config.clone());
+        ApiServer::with_state(config, state).build_router()
+    }
+
+    fn preflight(origin: &str) -> Request<Body> {
+        Request::builder()
+            .method(Method::OPTIONS)
+            .uri("/api/v1/health")
+            .header(header::ORIGIN, origin)
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, "GET")
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    fn restricted_cors() -> ServerConfig {
+        ServerConfig {
+            cors: CorsConfig {
+                allowed_origins: vec!["https://app.example.com".to_string(), "\n".to_string()],
+                allowed_methods: vec!["GET".to_string()],
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_preflight_from_allowed_origin() {
+        let response = router(restricted_cors())
+            .oneshot(preflight("https://app.example.com"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            response.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN),
+            Some(&HeaderValue::from_static("https://app.example.com"))
+        );
+        assert_eq!(
+            response.headers().get(header::ACCESS_CONTROL_ALLOW_METHODS),
+            Some(&HeaderValue::from_static("GET"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_preflight_from_rejected_origin() {
+        let response = router(restricted_cors())
+            .oneshot(preflight("https://evil.example.com"))
+            .await
+            .unwrap();
+        assert!(
+            response
+                .headers()
+                .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+                .is_none()
+        );
+
+        // The deprecated switch still turns CORS off
+        #[allow(deprecated)]
+        let config = ServerConfig {
+            api_config: ApiConfig {
+                enable_cors: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let response = router(config)
+            .oneshot(preflight("https://app.example.com"))
+            .await
+            .unwrap();
+        assert!(
+            response
+                .headers()
+                .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_responses_carry_security_headers() {
+        let response = router(ServerConfig::default())
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/health/live")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let headers = response.headers();
+
+        assert_eq!(
+            headers.get(header::STRICT_TRANSPORT_SECURITY),
+            Some(&HeaderValue::from_static("max-age=31536000"))
+        );
+        assert_eq!(
+            headers.get(header::X_CONTENT_TYPE_OPTIONS),
+            Some(&HeaderValue::from_static("nosniff"))
+        );
+        assert_eq!(
+            headers.get(header::X_FRAME_OPTIONS),
+            Some(&HeaderValue::from_static("DENY"))
+        );
+        assert_eq!(
+            headers.get(header::REFERRER_POLICY),
+            Some(&HeaderValue::from_static("no-referrer"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_oversized_body_is_rejected() {
+        let config = ServerConfig {
+            max_body_bytes: 1024,
+            ..Default::default()
+        };
+        let body = vec![b' '; 2048];
+        let response = router(config)
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/api/v1/positions")
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .header(header::CONTENT_LENGTH, body.len())
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_request_timeout_applies_by_default() {
+        let legacy = ServerConfig {
+            api_config: ApiConfig {
+                request_timeout_secs: 5,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(legacy.request_timeout(), Duration::from_secs(5));
+
+        let explicit = ServerConfig {
+            request_timeout_secs: 10,
+            ..legacy
+        };
+        assert_eq!(explicit.request_timeout(), Duration::from_secs(10));
+    }
+}
@@ -0,0 +1,301 @@
+//! Alert rule service for rules stored in the database.
+//!
+//! Stored rules replace the position monitor's rules whenever they change,
+//! whether through the API or `clmm-lp-cli alerts`.
+
+use crate::error::ApiError;
+use crate::models::{AlertRuleRequest, AlertRuleResponse};
+use crate::state::AppState;
+use clmm_lp_data::prelude::AlertRuleRecord;
+use clmm_lp_execution::prelude::AlertRule;
+use clmm_lp_execution::shutdown::CancellationToken;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Maximum length of a rule name.
+pub const MAX_RULE_NAME_LEN: usize = 64;
+
+/// How often stored rules are checked for changes made outside the API.
+pub const ALERT_RULE_SYNC_INTERVAL_SECS: u64 = 10;
+
+/// Service for creating, updating and hot-loading alert rules.
+pub struct AlertRuleService {
+    /// Application state.
+    state: AppState,
+}
+
+impl AlertRuleService {
+    /// Creates a new alert rule service.
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    /// Lists the stored rules.
+    pub async fn list(&self) -> Result<Vec<AlertRuleResponse>, ApiError> {
+        let records = self
+            .state
+            .require_database()?
+            .alert_rules()
+            .find_all()
+            .await
+            .map_err(|e| ApiError::internal(e.to_string()))?;
+        records.iter().map(rule_response).collect()
+    }
+
+    /// Gets a stored rule by name.
+    pub async fn get(&self, name: &str) -> Result<AlertRuleResponse, ApiError> {
+        let record = self
+            .state
+            .require_database()?
+            .alert_rules()
+            .find_by_name(name)
+            .await
+            .map_err(|e| ApiError::internal(e.to_string()))?
+            .ok_or_else(|| ApiError::not_found(format!("Alert rule not found: {}", name)))?;
+        rule_response(&record)
+    }
+
+    /// Stores a rule and reloads the monitor's rules.
+    ///
+    /// With `replace` false, fails if a rule with the same name exists;
+    /// otherwise fails if it does not.
+    pub async fn save(
+        &self,
+        rule: AlertRule,
+        replace: bool,
+    ) -> Result<AlertRuleResponse, ApiError> {
+        let repository = self.state.require_database()?.alert_rules();
+        let exists = repository
+            .find_by_name(&rule.name)
+            .await
+            .map_err(|e| ApiError::internal(e.to_string()))?
+            .is_some();
+        if exists && !replace {
+            return Err(ApiError::Conflict(format!(
+                "Alert rule already exists: {}",
+                rule.name
+            )));
+        }
+        if !exists && replace {
+            return Err(ApiError::not_found(format!(
+                "Alert rule not found: {}",
+                rule.name
+            )));
+        }
+
+        let definition =
+            serde_json::to_value(&rule).map_err(|e| ApiError::internal(e.to_string()))?;
+        let record = repository
+            .upsert(&rule.name, definition)
+            .await
+            .map_err(|e| ApiError::internal(e.to_string()))?;
+        self.reload().await?;
+        rule_response(&record)
+    }
+
+    /// Deletes a stored rule and reloads the monitor's rules.
+    pub async fn delete(&self, name: &str) -> Result<(), ApiError> {
+        let deleted = self
+            .state
+            .require_database()?
+            .alert_rules()
+            .delete(name)
+            .await
+            .map_err(|e| ApiError::internal(e.to_string()))?;
+        if !deleted {
+            return Err(ApiError::not_found(format!(
+                "Alert rule not found: {}",
+                name
+            )));
+        }
+        self.reload().await?;
+        Ok(())
+    }
+
+    /// Loads the stored rules.
+    pub async fn load(&self) -> Result<Vec<AlertRule>, ApiError> {
+        let records = self
+            .state
+            .require_database()?
+            .alert_rules()
+            .find_all()
+            .await
+            .map_err(|e| ApiError::internal(e.to_string()))?;
+        records.iter().map(rule_from_record).collect()
+    }
+
+    /// Replaces the monitor's rules with the stored rules.
+    pub async fn reload(&self) -> Result<usize, ApiError> {
+        let rules = self.load().await?;
+        let count = rules.len();
+        self.state.monitor.set_alert_rules(rules).await?;
+        info!(rules = count, "Alert rules reloaded");
+        Ok(count)
+    }
+}
+
+/// Builds a rule from a create or replace request.
+///
+/// # Errors
+/// Returns a validation error for an empty or overlong name, or an empty
+/// channel or tag.
+pub fn rule_from_request(request: AlertRuleRequest) -> Result<AlertRule, ApiError> {
+    let name = request.name.trim().to_string();
+    if name.is_empty() || name.len() > MAX_RULE_NAME_LEN {
+        return Err(ApiError::Validation(format!(
+            "Rule name must be 1-{} characters",
+            MAX_RULE_NAME_LEN
+        )));
+    }
+    let channels = trimmed(request.channels, "Channel")?;
+    let tags = trimmed(request.tags, "Tag")?;
+
+    let alert_type = request
+        .alert_type
+        .unwrap_or_else(|| request.condition.default_alert_type());
+    let mut rule = AlertRule::new(name, request.condition, request.severity, alert_type)
+        .with_message(request.message_template)
+        .with_channels(channels)
+        .with_tags(tags);
+    if let Some(secs) = request.cooldown_secs {
+        rule = rule.with_cooldown(secs);
+    }
+    if !request.enabled {
+        rule = rule.disabled();
+    }
+    Ok(rule)
+}
+
+/// Trims each value, rejecting empty ones.
+fn trimmed(values: Vec<String>, what: &str) -> Result<Vec<String>, ApiError> {
+    values
+        .into_iter()
+        .map(|value| {
+            let value = value.trim();
+            if value.is_empty() {
+                Err(ApiError::Validation(format!("{} must not be empty", what)))
+            } else {
+                Ok(value.to_string())
+            }
+        })
+        .collect()
+}
+
+/// Parses the rule stored in a record.
+fn rule_from_record(record: &AlertRuleRecord) -> Result<AlertRule, ApiError> {
+    serde_json::from_value(record.definition.clone()).map_err(|e| {
+        ApiError::internal(format!("Invalid stored alert rule {}: {}", record.name, e))
+    })
+}
+
+/// Converts a record to a response.
+fn rule_response(record: &AlertRuleRecord) -> Result<AlertRuleResponse, ApiError> {
+    let rule = rule_from_record(record)?;
+    Ok(AlertRuleResponse {
+        name: rule.name,
+        condition: rule.condition,
+        severity: rule.level,
+        alert_type: rule.alert_type,
+        message_template: rule.message_template,
+        channels: rule.channels,
+        tags: rule.tags,
+        cooldown_secs: rule.cooldown_secs,
+        enabled: rule.enabled,
+        created_at: record.created_at,
+        updated_at: record.updated_at,
+    })
+}
+
+/// Applies stored rules to the monitor whenever they change until
+/// `shutdown` is cancelled, so rules edited with the CLI are hot-loaded.
+///
+/// An empty table leaves the monitor's configured rules in place until a
+/// rule has been stored.
+pub async fn sync_alert_rules(state: AppState, interval: Duration, shutdown: CancellationToken) {
+    let service = AlertRuleService::new(state.clone());
+    let mut applied = serde_json::json!([]);
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        tokio::select! {
+            () = shutdown.cancelled() => break,
+            _ = ticker.tick() => {}
+        }
+        let rules = match service.load().await {
+            Ok(rules) => rules,
+            Err(e) => {
+                warn!(error = %e, "Failed to load alert rules");
+                continue;
+            }
+        };
+        let snapshot = serde_json::to_value(&rules).unwrap_or_default();
+        if snapshot == applied {
+            continue;
+        }
+        let count = rules.len();
+        match state.monitor.set_alert_rules(rules).await {
+            Ok(()) => {
+                info!(rules = count, "Stored alert rules applied");
+                applied = snapshot;
+            }
+            Err(e) => warn!(error = %e, "Stored alert rules are invalid"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clmm_lp_execution::prelude::{AlertLevel, AlertType, RuleCondition};
+
+    fn request(json: serde_json::Value) -> AlertRuleRequest {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_rule_from_request() {
+        let rule = rule_from_request(request(serde_json::json!({
+            "name": " il-high ",
+            "condition": {"ILExceeds": "0.05"},
+            "severity": "Critical",
+            "channels": ["webhook"],
+        })))
+        .unwrap();
+
+        assert_eq!(rule.name, "il-high");
+        assert!(
+            matches!(rule.condition, RuleCondition::ILExceeds(t) if t == rust_decimal::Decimal::new(5, 2))
+        );
+        assert_eq!(rule.level, AlertLevel::Critical);
+        assert_eq!(rule.alert_type, AlertType::ILThreshold);
+        assert_eq!(rule.channels, vec!["webhook".to_string()]);
+        assert_eq!(rule.cooldown_secs, 300);
+        assert!(rule.enabled);
+
+        let stored = AlertRuleRecord {
+            name: rule.name.clone(),
+            definition: serde_json::to_value(&rule).unwrap(),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        };
+        let response = rule_response(&stored).unwrap();
+        assert_eq!(response.severity, AlertLevel::Critical);
+        assert_eq!(response.channels, rule.channels);
+
+        let disabled = rule_from_request(request(serde_json::json!({
+            "name": "exit",
+            "condition": "RangeExit",
+            "severity": "Warning",
+            "cooldown_secs": 0,
+            "enabled": false,
+        })))
+        .unwrap();
+        assert!(!disabled.enabled);
+        assert_eq!(disabled.cooldown_secs, 0);
+
+        let unnamed = request(serde_json::json!({
+            "condition": "RangeExit",
+            "severity": "Warning",
+        }));
+        assert!(rule_from_request(unnamed).is_err());
+    }
+}
@@ -3,8 +3,14 @@
 //! This module provides services that bridge API handlers with
 //! the execution layer.
 
+pub mod alert_rule_service;
+pub mod optimization_service;
 pub mod position_service;
+pub mod replay_service;
 pub mod strategy_service;
 
+pub use alert_rule_service::AlertRuleService;
+pub use optimization_service::OptimizationService;
 pub use position_service::PositionService;
+pub use replay_service::ReplayService;
 pub use strategy_service::StrategyService;
@@ -0,0 +1,982 @@
+//! Optimization service for running range optimizations as background jobs.
+//!
+//! Without a database, jobs run in the API process as soon as an
+//! optimization slot is free. With one, jobs go through the persistent job
+//! queue and are run by queue workers, which may live in other processes;
+//! queued and running jobs then survive restarts.
+
+use crate::error::ApiError;
+use crate::models::{
+    OptimizationJobResponse, OptimizationJobStatus, OptimizationObjective,
+    OptimizationPathsResponse, OptimizationRequest, OptimizationValidationResponse,
+    PercentilePathResponse, ValidationRunResponse,
+};
+use crate::state::{AppState, OptimizationJob};
+use clmm_lp_data::prelude::{Database, JobRecord, OptimizationRecord, OptimizationValidation};
+use clmm_lp_domain::entities::position::Position;
+use clmm_lp_domain::value_objects::OptimizationResult;
+use clmm_lp_domain::value_objects::amount::Amount;
+use clmm_lp_execution::shutdown::CancellationToken;
+use clmm_lp_optimization::prelude::{
+    CacheLookup, MaximizeFees, MaximizeNetPnL, MaximizeSharpeRatio, OptimizationCache,
+    OptimizationError, OptimizationKey, OptimizationProgress, PoolConstraints, RangeOptimizer,
+};
+use clmm_lp_simulation::prelude::{ConstantVolume, PercentilePath};
+use primitive_types::U256;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinError;
+use tracing::{error, info, warn};
+
+/// Maximum number of Monte Carlo paths kept per optimization.
+const MAX_PATH_PERCENTILES: usize = 10;
+
+/// Submitted prices kept per parameter set as the data window results are
+/// cached by.
+const OPTIMIZATION_WINDOW: usize = 30;
+
+/// Kind of optimization jobs in the job queue.
+pub const OPTIMIZATION_JOB_KIND: &str = "optimization";
+
+/// Times a queued job is claimed before it fails.
+pub const JOB_MAX_ATTEMPTS: i32 = 3;
+
+/// How often idle queue workers look for queued jobs.
+pub const JOB_POLL_INTERVAL_SECS: u64 = 2;
+
+/// How often a queue worker reports that a job is still running.
+pub const JOB_HEARTBEAT_INTERVAL_SECS: u64 = 10;
+
+/// Running jobs without a heartbeat for this long are requeued.
+pub const JOB_STALE_AFTER_SECS: i64 = 60;
+
+/// What the optimizer produced for a job.
+type JobOutcome = Result<(OptimizationResult, Vec<PercentilePath>), OptimizationError>;
+
+/// Service for optimization jobs.
+pub struct OptimizationService {
+    /// Application state.
+    state: AppState,
+}
+
+impl OptimizationService {
+    /// Creates a new optimization service.
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    /// Queues an optimization job and returns its initial status.
+    pub async fn submit(
+        &self,
+        request: OptimizationRequest,
+    ) -> Result<OptimizationJobResponse, ApiError> {
+        if request.current_price <= Decimal::ZERO {
+            return Err(ApiError::Validation(
+                "current_price must be positive".to_string(),
+            ));
+        }
+        if !(request.volatility > 0.0 && request.volatility.is_finite()) {
+            return Err(ApiError::Validation(
+                "volatility must be positive".to_string(),
+            ));
+        }
+        if request.iterations == 0 {
+            return Err(ApiError::Validation(
+                "iterations must be at least 1".to_string(),
+            ));
+        }
+        if request.path_percentiles.len() > MAX_PATH_PERCENTILES
+            || request.path_percentiles.iter().any(|p| *p > 100)
+        {
+            return Err(ApiError::Validation(format!(
+                "path_percentiles must hold at most {} values between 0 and 100",
+                MAX_PATH_PERCENTILES
+            )));
+        }
+        if request.risk_free_rate.abs() >= Decimal::ONE {
+            return Err(ApiError::Validation(
+                "risk_free_rate must be between -1 and 1".to_string(),
+            ));
+        }
+
+        if let Some(database) = &self.state.database {
+            let payload =
+                serde_json::to_value(&request).map_err(|e| ApiError::internal(e.to_string()))?;
+            let record = database
+                .jobs()
+                .enqueue(
+                    uuid::Uuid::new_v4(),
+                    OPTIMIZATION_JOB_KIND,
+                    payload,
+                    request.priority,
+                    JOB_MAX_ATTEMPTS,
+                )
+                .await
+                .map_err(|e| ApiError::internal(e.to_string()))?;
+            info!(
+                job_id = %record.id,
+                objective = ?request.objective,
+                priority = request.priority,
+                "Queued optimization job"
+            );
+            return Ok(queued_job_response(&record, None));
+        }
+
+        let job = Arc::new(OptimizationJob::new(uuid::Uuid::new_v4().to_string()));
+        self.state
+            .optimization_jobs
+            .write()
+            .await
+            .insert(job.id.clone(), job.clone());
+
+        info!(job_id = %job.id, objective = ?request.objective, "Queued optimization job");
+
+        let slots = self.state.optimization_slots.clone();
+        let threads = self.state.config.optimizer_threads;
+        let cache = self.state.optimization_cache.clone();
+        let worker = job.clone();
+        tokio::spawn(async move {
+            let Ok(_permit) = slots.acquire_owned().await else {
+                return;
+            };
+            if worker.cancellation.is_cancelled() {
+                worker.update(|s| s.status = OptimizationJobStatus::Cancelled);
+                return;
+            }
+            worker.update(|s| s.status = OptimizationJobStatus::Running);
+
+            let runner = worker.clone();
+            let outcome = tokio::task::spawn_blocking(move || {
+                run_optimization(&request, threads, &cache, &runner)
+            })
+            .await;
+
+            record_outcome(&worker, outcome);
+            info!(job_id = %worker.id, status = ?worker.snapshot().status, "Optimization job finished");
+        });
+
+        Ok(job_response(&job))
+    }
+
+    /// Runs a job claimed from the queue by `worker_id`, reporting
+    /// heartbeats until it finishes.
+    ///
+    /// The job stops when cancelled through the queue. On `shutdown` it is
+    /// put back in the queue for another worker.
+    pub async fn run_claimed(
+        &self,
+        record: JobRecord,
+        worker_id: &str,
+        shutdown: &CancellationToken,
+    ) {
+        let Some(database) = self.state.database.clone() else {
+            return;
+        };
+        let repository = database.jobs();
+
+        let request: OptimizationRequest = match serde_json::from_value(record.payload.clone()) {
+            Ok(request) => request,
+            Err(e) => {
+                let message = format!("Invalid job payload: {}", e);
+                if let Err(e) = repository
+                    .mark_failed(record.id, worker_id, &message, false)
+                    .await
+                {
+                    warn!(job_id = %record.id, error = %e, "Failed to record job failure");
+                }
+                return;
+            }
+        };
+
+        let mut job = OptimizationJob::new(record.id.to_string());
+        job.created_at = record.created_at;
+        job.update(|s| s.status = OptimizationJobStatus::Running);
+        let job = Arc::new(job);
+        self.state
+            .optimization_jobs
+            .write()
+            .await
+            .insert(job.id.clone(), job.clone());
+        info!(
+            job_id = %job.id,
+            worker_id = worker_id,
+            attempt = record.attempts,
+            "Running queued optimization job"
+        );
+
+        let runner = job.clone();
+        let threads = self.state.config.optimizer_threads;
+        let cache = self.state.optimization_cache.clone();
+        let job_request = request.clone();
+        let optimization = tokio::task::spawn_blocking(move || {
+            run_optimization(&job_request, threads, &cache, &runner)
+        });
+        tokio::pin!(optimization);
+
+        let mut heartbeat = tokio::time::interval(Duration::from_secs(JOB_HEARTBEAT_INTERVAL_SECS));
+        let mut released = false;
+        let outcome = loop {
+            tokio::select! {
+                outcome = &mut optimization => break outcome,
+                () = shutdown.cancelled(), if !released => {
+                    released = true;
+                    job.cancellation.cancel();
+                }
+                _ = heartbeat.tick() => match repository.heartbeat(record.id, worker_id).await {
+                    Ok(Some(status)) if status == "cancelled" => job.cancellation.cancel(),
+                    Ok(Some(_)) => {}
+                    Ok(None) => {
+                        warn!(job_id = %job.id, "Queued job was taken over by another worker");
+                        job.cancellation.cancel();
+                    }
+                    Err(e) => warn!(job_id = %job.id, error = %e, "Failed to record job heartbeat"),
+                },
+            }
+        };
+        record_outcome(&job, outcome);
+
+        let mut state = job.snapshot();
+        let result = match state.status {
+            // Only the worker still owning the job stores its result
+            OptimizationJobStatus::Completed => {
+                match repository.mark_completed(record.id, worker_id).await {
+                    Ok(true) => {
+                        persist_optimization(&database, &job, &request).await;
+                        Ok(())
+                    }
+                    Ok(false) => {
+                        warn!(
+                            job_id = %job.id,
+                            "Queued job was cancelled or taken over before completing, discarding result"
+                        );
+                        job.update(|s| s.status = OptimizationJobStatus::Cancelled);
+                        state = job.snapshot();
+                        Ok(())
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+            OptimizationJobStatus::Cancelled if released => {
+                repository.release(record.id, worker_id).await
+            }
+            OptimizationJobStatus::Failed => {
+                let message = state.error.unwrap_or_default();
+                repository
+                    .mark_failed(record.id, worker_id, &message, true)
+                    .await
+            }
+            // Cancelled through the queue, which already holds the status
+            _ => Ok(()),
+        };
+        if let Err(e) = result {
+            error!(job_id = %job.id, error = %e, "Failed to record job outcome");
+        }
+        info!(job_id = %job.id, status = ?state.status, "Queued optimization job finished");
+
+        // The queue is authoritative for jobs that may run again or were
+        // cancelled elsewhere
+        if state.status != OptimizationJobStatus::Completed {
+            self.state.optimization_jobs.write().await.remove(&job.id);
+        }
+    }
+
+    /// Gets the status of a job.
+    ///
+    /// Jobs run by this process are served from memory with their progress;
+    /// others are read from the job queue.
+    pub async fn get(&self, job_id: &str) -> Result<OptimizationJobResponse, ApiError> {
+        if let Some(job) = self.state.optimization_jobs.read().await.get(job_id) {
+            return Ok(job_response(job));
+        }
+
+        let record = self.find_queued(job_id).await?;
+        let result = if record.status == "completed" {
+            self.state
+                .require_database()?
+                .simulations()
+                .find_optimization_by_id(record.id)
+                .await
+                .map_err(|e| ApiError::internal(e.to_string()))?
+        } else {
+            None
+        };
+        Ok(queued_job_response(&record, result.as_ref()))
+    }
+
+    /// Finds a job in the job queue.
+    async fn find_queued(&self, job_id: &str) -> Result<JobRecord, ApiError> {
+        let not_found = || ApiError::not_found("Optimization job not found");
+        let Some(database) = &self.state.database else {
+            return Err(not_found());
+        };
+        let id = uuid::Uuid::parse_str(job_id).map_err(|_| not_found())?;
+        database
+            .jobs()
+            .find_by_id(id)
+            .await
+            .map_err(|e| ApiError::internal(e.to_string()))?
+            .filter(|record| record.kind == OPTIMIZATION_JOB_KIND)
+            .ok_or_else(not_found)
+    }
+
+    /// Gets the Monte Carlo paths kept with a job's result.
+    ///
+    /// Jobs still in memory are served directly; older ones are read from
+    /// the database.
+    pub async fn paths(&self, job_id: &str) -> Result<OptimizationPathsResponse, ApiError> {
+        let job = self
+            .state
+            .optimization_jobs
+            .read()
+            .await
+            .get(job_id)
+            .cloned();
+        if let Some(job) = job {
+            let state = job.snapshot();
+            if state.status != OptimizationJobStatus::Completed {
+                return Err(ApiError::Conflict(
+                    "Optimization job has not completed".to_string(),
+                ));
+            }
+            return Ok(OptimizationPathsResponse {
+                optimization_id: job.id.clone(),
+                paths: state.paths.into_iter().map(path_response).collect(),
+            });
+        }
+
+        let id = uuid::Uuid::parse_str(job_id)
+            .map_err(|_| ApiError::not_found("Optimization job not found"))?;
+        let repository = self.state.require_database()?.simulations();
+        repository
+            .find_optimization_by_id(id)
+            .await
+            .map_err(|e| ApiError::internal(e.to_string()))?
+            .ok_or_else(|| ApiError::not_found("Optimization job not found"))?;
+        let points = repository
+            .find_optimization_paths(id)
+            .await
+            .map_err(|e| ApiError::internal(e.to_string()))?;
+
+        let mut paths: Vec<PercentilePathResponse> = Vec::new();
+        for point in points {
+            let percentile = u8::try_from(point.percentile).unwrap_or_default();
+            if paths.last().is_none_or(|p| p.percentile != percentile) {
+                paths.push(PercentilePathResponse {
+                    percentile,
+                    prices: Vec::new(),
+                    pnl: Vec::new(),
+                });
+            }
+            if let Some(path) = paths.last_mut() {
+                path.prices.push(point.price);
+                path.pnl.push(point.pnl);
+            }
+        }
+
+        Ok(OptimizationPathsResponse {
+            optimization_id: job_id.to_string(),
+            paths,
+        })
+    }
+
+    /// Gets the simulations that backtested a job's recommendation, with
+    /// their realized returns against the expected return.
+    pub async fn validation(
+        &self,
+        job_id: &str,
+    ) -> Result<OptimizationValidationResponse, ApiError> {
+        let id = uuid::Uuid::parse_str(job_id)
+            .map_err(|_| ApiError::not_found("Optimization job not found"))?;
+        let repository = self.state.require_database()?.simulations();
+        let validation = OptimizationValidation::load(&repository, id)
+            .await
+            .map_err(|e| ApiError::internal(e.to_string()))?
+            .ok_or_else(|| ApiError::not_found("Optimization job not found"))?;
+
+        Ok(OptimizationValidationResponse {
+            optimization_id: job_id.to_string(),
+            recommended_lower: validation.optimization.recommended_lower,
+            recommended_upper: validation.optimization.recommended_upper,
+            expected_return_pct: validation.expected_return_pct,
+            expected_fees_pct: validation.expected_fees_pct,
+            simulations: validation
+                .runs
+                .into_iter()
+                .map(|run| ValidationRunResponse {
+                    simulation_id: run.simulation_id.to_string(),
+                    created_at: run.created_at,
+                    return_pct: run.return_pct,
+                    fees_pct: run.fees_pct,
+                    return_error_pct: run.return_error_pct,
+                    time_in_range_pct: run.time_in_range_pct,
+                })
+                .collect(),
+            mean_return_error_pct: validation.mean_return_error_pct,
+        })
+    }
+
+    /// Requests cancellation of a queued or running job.
+    pub async fn cancel(&self, job_id: &str) -> Result<OptimizationJobResponse, ApiError> {
+        let job = self
+            .state
+            .optimization_jobs
+            .read()
+            .await
+            .get(job_id)
+            .cloned();
+        let Some(job) = job else {
+            // Jobs in the queue stop at their worker's next heartbeat
+            let record = self.find_queued(job_id).await?;
+            let cancelled = self
+                .state
+                .require_database()?
+                .jobs()
+                .cancel(record.id)
+                .await
+                .map_err(|e| ApiError::internal(e.to_string()))?
+                .ok_or_else(|| {
+                    ApiError::Conflict("Optimization job has already finished".to_string())
+                })?;
+            warn!(job_id = %job_id, "Cancelling optimization job");
+            return Ok(queued_job_response(&cancelled, None));
+        };
+
+        if job.snapshot().status.is_finished() {
+            return Err(ApiError::Conflict(
+                "Optimization job has already finished".to_string(),
+            ));
+        }
+
+        warn!(job_id = %job_id, "Cancelling optimization job");
+        job.cancellation.cancel();
+        if let (Some(database), Ok(id)) = (&self.state.database, uuid::Uuid::parse_str(job_id))
+            && let Err(e) = database.jobs().cancel(id).await
+        {
+            warn!(job_id = %job_id, error = %e, "Failed to cancel queued job");
+        }
+        // Queued jobs never start; running jobs stop at the next path
+        job.update(|s| {
+            if s.status == OptimizationJobStatus::Queued {
+                s.status = OptimizationJobStatus::Cancelled;
+            }
+        });
+
+        Ok(job_response(&job))
+    }
+}
+
+/// Runs a job queue worker until `shutdown` is cancelled, claiming queued
+/// optimization jobs one at a time.
+///
+/// Running jobs whose worker stopped responding, e.g. because its process
+/// crashed, are put back in the queue while polling.
+pub async fn run_job_worker(state: AppState, worker_id: String, shutdown: CancellationToken) {
+    let Some(database) = state.database.clone() else {
+        return;
+    };
+    let repository = database.jobs();
+    let service = OptimizationService::new(state);
+    info!(worker_id = %worker_id, "Started job queue worker");
+
+    let mut ticker = tokio::time::interval(Duration::from_secs(JOB_POLL_INTERVAL_SECS));
+    loop {
+        tokio::select! {
+            () = shutdown.cancelled() => break,
+            _ = ticker.tick() => {}
+        }
+
+        let cutoff = chrono::Utc::now() - chrono::Duration::seconds(JOB_STALE_AFTER_SECS);
+        match repository.requeue_stale(cutoff).await {
+            Ok(0) => {}
+            Ok(jobs) => warn!(jobs = jobs, "Requeued jobs whose worker stopped responding"),
+            Err(e) => {
+                warn!(error = %e, "Failed to poll the job queue");
+                continue;
+            }
+        }
+
+        while !shutdown.is_cancelled() {
+            match repository.claim(&worker_id, &[OPTIMIZATION_JOB_KIND]).await {
+                Ok(Some(record)) => service.run_claimed(record, &worker_id, &shutdown).await,
+                Ok(None) => break,
+                Err(e) => {
+                    warn!(error = %e, "Failed to claim a queued job");
+                    break;
+                }
+            }
+        }
+    }
+    info!(worker_id = %worker_id, "Job queue worker stopped");
+}
+
+/// Records what the optimizer produced in the job state.
+///
+/// A run that finished after its job was cancelled counts as cancelled, so
+/// its result is not persisted.
+fn record_outcome(job: &OptimizationJob, outcome: Result<JobOutcome, JoinError>) {
+    let cancelled = job.cancellation.is_cancelled();
+    job.update(|s| match outcome {
+        Ok(Ok(_)) if cancelled => {
+            s.status = OptimizationJobStatus::Cancelled;
+        }
+        Ok(Ok((result, paths))) => {
+            s.status = OptimizationJobStatus::Completed;
+            s.result = Some(result);
+            s.paths = paths;
+        }
+        Ok(Err(OptimizationError::Cancelled)) => {
+            s.status = OptimizationJobStatus::Cancelled;
+        }
+        Ok(Err(e)) => {
+            s.status = OptimizationJobStatus::Failed;
+            s.error = Some(e.to_string());
+        }
+        Err(e) => {
+            s.status = OptimizationJobStatus::Failed;
+            s.error = Some(e.to_string());
+        }
+    });
+}
+
+/// Saves a completed job's result and kept paths, keyed by the job ID.
+///
+/// Failures are logged; the result stays available in memory.
+async fn persist_optimization(
+    database: &Database,
+    job: &OptimizationJob,
+    request: &OptimizationRequest,
+) {
+    let state = job.snapshot();
+    let Some(result) = state.result else {
+        return;
+    };
+    let Ok(id) = uuid::Uuid::parse_str(&job.id) else {
+        return;
+    };
+
+    let repository = database.simulations();
+    let saved = repository
+        .save_optimization(
+            id,
+            None,
+            request.objective.as_str(),
+            job.created_at.timestamp(),
+            chrono::Utc::now().timestamp(),
+            request.capital_usd,
+            Decimal::from_f64(request.volatility).unwrap_or_default(),
+            result.recommended_range.lower_price.value,
+            result.recommended_range.upper_price.value,
+            result.expected_pnl,
+            result.expected_fees,
+            result.expected_il,
+            result.sharpe_ratio,
+            result.sharpe_config,
+            i32::try_from(request.iterations).unwrap_or(i32::MAX),
+        )
+        .await;
+    if let Err(e) = saved {
+        warn!(job_id = %job.id, error = %e, "Failed to save optimization result");
+        return;
+    }
+
+    let paths: Vec<(i16, Vec<(Decimal, Decimal)>)> = state
+        .paths
+        .iter()
+        .map(|path| {
+            let points = path.prices.iter().copied().zip(path.pnl.iter().copied());
+            (i16::from(path.percentile), points.collect())
+        })
+        .collect();
+    if let Err(e) = repository.save_optimization_paths(id, &paths).await {
+        warn!(job_id = %job.id, error = %e, "Failed to save optimization paths");
+    }
+}
+
+/// Runs the optimizer for a job, reporting progress into the job state,
+/// then simulates the recommended range again to keep its percentile paths.
+fn run_optimization(
+    request: &OptimizationRequest,
+    threads: usize,
+    cache: &Mutex<OptimizationCache>,
+    job: &Arc<OptimizationJob>,
+) -> JobOutcome {
+    let pool_constraints = match request.tick_spacing {
+        Some(spacing) => PoolConstraints::new(spacing),
+        None => PoolConstraints::for_fee_tier(request.fee_rate),
+    };
+    let progress_job = job.clone();
+    let optimizer = RangeOptimizer::new(request.iterations, 30, 1.0 / 365.0)
+        .with_pool_constraints(pool_constraints)
+        .with_threads(threads)
+        .with_cancellation(job.cancellation.clone())
+        .with_sharpe(request.sharpe_config())
+        .with_progress(Arc::new(move |progress: &OptimizationProgress| {
+            progress_job.update(|s| s.progress = progress.clone());
+        }));
+
+    let position = Position::new(
+        "optimization",
+        "api",
+        Amount::zero(9),
+        Amount::zero(6),
+        0,
+        0,
+    );
+    let volume = ConstantVolume::from_amount(Amount::new(U256::from(1_000_000_000_000u64), 6));
+    let pool_liquidity = request.capital_usd.to_u128().unwrap_or(1000) * 1000;
+
+    // Results are cached by the prices submitted with the same parameters;
+    // a moved price warm-starts from the previous result
+    let params = (
+        request.objective.as_str(),
+        request.iterations,
+        request.volatility.to_bits(),
+        request.capital_usd,
+        request.fee_rate,
+        request.tick_spacing,
+        request.risk_free_rate,
+        request.benchmark,
+    );
+    let (key, window, lookup) = {
+        let mut cache = cache
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let window = cache.observe(
+            &format!("{:?}", params),
+            request.current_price,
+            OPTIMIZATION_WINDOW,
+        );
+        let key = OptimizationKey::new("api", &window, request.objective.as_str(), &params);
+        let lookup = cache.lookup(&key, &window);
+        (key, window, lookup)
+    };
+    let optimizer = match &lookup {
+        CacheLookup::WarmStart(previous) => optimizer.with_warm_start(previous.recommended_range),
+        _ => optimizer,
+    };
+
+    let optimized = match lookup {
+        CacheLookup::Hit(result) => Ok(result),
+        _ => match request.objective {
+            OptimizationObjective::Pnl => optimizer.try_optimize(
+                position.clone(),
+                request.current_price,
+                request.volatility,
+                0.0,
+                volume.clone(),
+                pool_liquidity,
+                request.fee_rate,
+                MaximizeNetPnL,
+            ),
+            OptimizationObjective::Fees => optimizer.try_optimize(
+                position.clone(),
+                request.current_price,
+                request.volatility,
+                0.0,
+                volume.clone(),
+                pool_liquidity,
+                request.fee_rate,
+                MaximizeFees,
+            ),
+            OptimizationObjective::Sharpe => optimizer.try_optimize(
+                position.clone(),
+                request.current_price,
+                request.volatility,
+                0.0,
+                volume.clone(),
+                pool_liquidity,
+                request.fee_rate,
+                MaximizeSharpeRatio::new(request.risk_free_rate),
+            ),
+        }
+        .inspect(|result| {
+            // A cancelled run may stop early; keep it out of the cache
+            if !job.cancellation.is_cancelled() {
+                cache
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .insert(key, &window, result.clone());
+            }
+        }),
+    };
+    let result = optimized?;
+
+    if job.cancellation.is_cancelled() {
+        return Err(OptimizationError::Cancelled);
+    }
+    let paths = optimizer.percentile_paths(
+        position,
+        &result.recommended_range,
+        request.current_price,
+        request.volatility,
+        0.0,
+        volume,
+        pool_liquidity,
+        request.fee_rate,
+        &request.path_percentiles,
+    );
+
+    Ok((result, paths))
+}
+
+/// Converts a kept path to its API response.
+fn path_response(path: PercentilePath) -> PercentilePathResponse {
+    PercentilePathResponse {
+        percentile: path.percentile,
+        prices: path.prices,
+        pnl: path.pnl,
+    }
+}
+
+/// Builds the API response for a job.
+fn job_response(job: &OptimizationJob) -> OptimizationJobResponse {
+    let state = job.snapshot();
+    let range = state
+        .result
+        .as_ref()
+        .map(|r| &r.recommended_range)
+        .or(state.progress.best_range.as_ref());
+    let sharpe_config = state.result.as_ref().and_then(|r| r.sharpe_config);
+
+    OptimizationJobResponse {
+        id: job.id.clone(),
+        status: state.status,
+        progress: if state.status == OptimizationJobStatus::Completed {
+            1.0
+        } else {
+            state.progress.fraction()
+        },
+        iterations_done: state.progress.iterations_done,
+        total_iterations: state.progress.total_iterations,
+        best_score: state.progress.best_score,
+        lower_price: range.map(|r| r.lower_price.value),
+        upper_price: range.map(|r| r.upper_price.value),
+        expected_pnl: state.result.as_ref().map(|r| r.expected_pnl),
+        expected_fees: state.result.as_ref().map(|r| r.expected_fees),
+        expected_il: state.result.as_ref().map(|r| r.expected_il),
+        sharpe_ratio: state.result.as_ref().and_then(|r| r.sharpe_ratio),
+        risk_free_rate: sharpe_config.map(|c| c.risk_free_rate),
+        benchmark: sharpe_config.map(|c| c.benchmark.to_string()),
+        error: state.error,
+        created_at: job.created_at,
+    }
+}
+
+/// Maps a job queue status to the job status.
+fn queued_job_status(status: &str) -> OptimizationJobStatus {
+    match status {
+        "running" => OptimizationJobStatus::Running,
+        "completed" => OptimizationJobStatus::Completed,
+        "cancelled" => OptimizationJobStatus::Cancelled,
+        "failed" => OptimizationJobStatus::Failed,
+        _ => OptimizationJobStatus::Queued,
+    }
+}
+
+/// Builds the API response for a job read from the queue, with its stored
+/// result once completed.
+fn queued_job_response(
+    record: &JobRecord,
+    result: Option<&OptimizationRecord>,
+) -> OptimizationJobResponse {
+    let status = queued_job_status(&record.status);
+    let total_iterations = record
+        .payload
+        .get("iterations")
+        .and_then(serde_json::Value::as_u64)
+        .and_then(|n| usize::try_from(n).ok())
+        .unwrap_or_default();
+
+    OptimizationJobResponse {
+        id: record.id.to_string(),
+        status,
+        progress: if status == OptimizationJobStatus::Completed {
+            1.0
+        } else {
+            0.0
+        },
+        iterations_done: if status == OptimizationJobStatus::Completed {
+            total_iterations
+        } else {
+            0
+        },
+        total_iterations,
+        best_score: None,
+        lower_price: result.map(|r| r.recommended_lower),
+        upper_price: result.map(|r| r.recommended_upper),
+        expected_pnl: result.map(|r| r.expected_pnl),
+        expected_fees: result.map(|r| r.expected_fees),
+        expected_il: result.map(|r| r.expected_il),
+        sharpe_ratio: result.and_then(|r| r.sharpe_ratio),
+        risk_free_rate: result.and_then(|r| r.risk_free_rate),
+        benchmark: result.and_then(|r| r.sharpe_benchmark.clone()),
+        error: record.error.clone(),
+        created_at: record.created_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::ApiConfig;
+    use clmm_lp_domain::metrics::sharpe::Benchmark;
+    use clmm_lp_protocols::prelude::RpcConfig;
+
+    fn request(iterations: usize) -> OptimizationRequest {
+        OptimizationRequest {
+            current_price: Decimal::from(100),
+            volatility: 0.5,
+            objective: OptimizationObjective::Pnl,
+            iterations,
+            capital_usd: Decimal::from(1000),
+            fee_rate: Decimal::new(3, 3),
+            tick_spacing: None,
+            path_percentiles: vec![5, 50, 95],
+            risk_free_rate: Decimal::new(5, 2),
+            benchmark: Benchmark::Zero,
+            priority: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_job_completes_with_result() {
+        let state = AppState::new(RpcConfig::default(), ApiConfig::default());
+        let service = OptimizationService::new(state);
+
+        let submitted = service.submit(request(5)).await.unwrap();
+        let mut status = submitted.clone();
+        for _ in 0..500 {
+            status = service.get(&submitted.id).await.unwrap();
+            if status.status.is_finished() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        assert_eq!(status.status, OptimizationJobStatus::Completed);
+        assert!(status.lower_price.is_some());
+        assert!(status.expected_pnl.is_some());
+        assert!(status.sharpe_ratio.is_some());
+        assert_eq!(status.risk_free_rate, Some(Decimal::new(5, 2)));
+        assert_eq!(status.benchmark.as_deref(), Some("zero"));
+        assert!((status.progress - 1.0).abs() < f64::EPSILON);
+        assert!(service.cancel(&submitted.id).await.is_err());
+
+        let paths = service.paths(&submitted.id).await.unwrap();
+        assert_eq!(
+            paths.paths.iter().map(|p| p.percentile).collect::<Vec<_>>(),
+            vec![5, 50, 95]
+        );
+        assert_eq!(paths.paths[0].prices.len(), paths.paths[0].pnl.len());
+    }
+
+    #[test]
+    fn test_run_finishing_after_cancellation_is_cancelled() {
+        let cache = Mutex::new(OptimizationCache::default());
+        let job = Arc::new(OptimizationJob::new("late".to_string()));
+        let outcome = run_optimization(&request(5), 1, &cache, &job);
+        assert!(outcome.is_ok());
+
+        job.cancellation.cancel();
+        record_outcome(&job, Ok(outcome));
+        let state = job.snapshot();
+        assert_eq!(state.status, OptimizationJobStatus::Cancelled);
+        assert!(state.result.is_none());
+    }
+
+    #[test]
+    fn test_repeated_request_is_served_from_cache() {
+        let cache = Mutex::new(OptimizationCache::default());
+        let first = Arc::new(OptimizationJob::new("first".to_string()));
+        let (result, _) = run_optimization(&request(5), 1, &cache, &first).unwrap();
+        assert!(first.snapshot().progress.iterations_done > 0);
+
+        // Same request: no iterations are run
+        let second = Arc::new(OptimizationJob::new("second".to_string()));
+        let (cached, paths) = run_optimization(&request(5), 1, &cache, &second).unwrap();
+        assert_eq!(second.snapshot().progress.iterations_done, 0);
+        assert_eq!(cached.recommended_range, result.recommended_range);
+        assert_eq!(paths.len(), 3);
+
+        // A moved price warm-starts and runs again
+        let moved = OptimizationRequest {
+            current_price: Decimal::from(101),
+            ..request(5)
+        };
+        let third = Arc::new(OptimizationJob::new("third".to_string()));
+        run_optimization(&moved, 1, &cache, &third).unwrap();
+        assert!(third.snapshot().progress.iterations_done > 0);
+        assert_eq!(cache.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_queued_job_can_be_cancelled() {
+        let state = AppState::new(RpcConfig::default(), ApiConfig::default());
+        // Hold the only slot so the job stays queued
+        let _permit = state.optimization_slots.clone().acquire_owned().await;
+        let service = OptimizationService::new(state);
+
+        let submitted = service.submit(request(5)).await.unwrap();
+        assert_eq!(submitted.status, OptimizationJobStatus::Queued);
+
+        let cancelled = service.cancel(&submitted.id).await.unwrap();
+        assert_eq!(cancelled.status, OptimizationJobStatus::Cancelled);
+        assert!(service.paths(&submitted.id).await.is_err());
+        assert!(service.get("missing").await.is_err());
+    }
+
+    #[test]
+    fn test_queued_job_response_reads_stored_result() {
+        let now = chrono::Utc::now();
+        let mut record = JobRecord {
+            id: uuid::Uuid::new_v4(),
+            kind: OPTIMIZATION_JOB_KIND.to_string(),
+            payload: serde_json::to_value(request(40)).unwrap(),
+            status: "running".to_string(),
+            priority: 0,
+            attempts: 1,
+            max_attempts: JOB_MAX_ATTEMPTS,
+            worker_id: Some("worker-1".to_string()),
+            error: None,
+            heartbeat_at: Some(now),
+            started_at: Some(now),
+            finished_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+
+        let running = queued_job_response(&record, None);
+        assert_eq!(running.status, OptimizationJobStatus::Running);
+        assert_eq!(running.total_iterations, 40);
+        assert_eq!(running.lower_price, None);
+
+        record.status = "completed".to_string();
+        let stored = OptimizationRecord {
+            id: record.id,
+            pool_id: None,
+            objective_type: "pnl".to_string(),
+            start_timestamp: 0,
+            end_timestamp: 0,
+            initial_capital: Decimal::from(1000),
+            volatility: Decimal::new(5, 1),
+            recommended_lower: Decimal::from(90),
+            recommended_upper: Decimal::from(110),
+            expected_pnl: Decimal::from(12),
+            expected_fees: Decimal::from(15),
+            expected_il: Decimal::from(-3),
+            sharpe_ratio: None,
+            risk_free_rate: Some(Decimal::new(5, 2)),
+            sharpe_benchmark: Some("zero".to_string()),
+            simulations_run: 40,
+            created_at: now,
+        };
+        let completed = queued_job_response(&record, Some(&stored));
+        assert_eq!(completed.status, OptimizationJobStatus::Completed);
+        assert!((completed.progress - 1.0).abs() < f64::EPSILON);
+        assert_eq!(completed.iterations_done, 40);
+        assert_eq!(completed.upper_price, Some(Decimal::from(110)));
+        assert_eq!(completed.benchmark.as_deref(), Some("zero"));
+    }
+}
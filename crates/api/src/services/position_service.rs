@@ -4,7 +4,7 @@ use crate::error::ApiError;
 use crate::models::{OpenPositionRequest, RebalanceRequest};
 use crate::state::{AlertUpdate, AppState, PositionUpdate};
 use clmm_lp_execution::prelude::{RebalanceParams, RebalanceReason, StrategyExecutor};
-use clmm_lp_protocols::prelude::WhirlpoolReader;
+use clmm_lp_protocols::prelude::{WhirlpoolReader, quote_deposit};
 use solana_sdk::pubkey::Pubkey;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -141,11 +141,27 @@ impl PositionService {
             )));
         }
 
+        // Quote the deposit; single-sided ranges only need one token
+        let quote = quote_deposit(
+            &pool_state,
+            request.tick_lower,
+            request.tick_upper,
+            request.amount_a,
+            request.amount_b,
+            request.slippage_tolerance_bps,
+        )
+        .map_err(|e| ApiError::Validation(e.to_string()))?;
+
         if self.dry_run {
             info!("Dry-run mode: would open position");
             return Ok(OperationResult::dry_run(format!(
-                "Would open position in pool {} with range [{}, {}]",
-                request.pool_address, request.tick_lower, request.tick_upper
+                "Would open {:?} position in pool {} with range [{}, {}] (max A: {}, max B: {})",
+                quote.side,
+                request.pool_address,
+                request.tick_lower,
+                request.tick_upper,
+                quote.token_max_a,
+                quote.token_max_b
             )));
         }
 
@@ -357,6 +373,7 @@ impl PositionService {
                 message: format!("Rebalance initiated for position {}", address),
                 timestamp: chrono::Utc::now(),
                 position_address: Some(address.to_string()),
+                tags: position.tags.clone(),
             });
 
             info!("Rebalance recorded - actual execution pending wallet configuration");
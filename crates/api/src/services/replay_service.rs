@@ -0,0 +1,430 @@
+//! Replay service for counterfactual "what-if" reports on live positions.
+
+use crate::error::ApiError;
+use crate::models::{
+    WhatIfOutcomeResponse, WhatIfRequest, WhatIfResponse, WhatIfScenarioRequest, WhatIfStrategyKind,
+};
+use crate::state::AppState;
+use chrono::{DateTime, Utc};
+use clmm_lp_data::prelude::{PoolRecord, PriceRecord};
+use clmm_lp_domain::value_objects::price::Price;
+use clmm_lp_domain::value_objects::price_range::PriceRange;
+use clmm_lp_execution::prelude::{EventData, LifecycleEvent};
+use clmm_lp_protocols::prelude::tick_to_price;
+use clmm_lp_simulation::prelude::{
+    DynamicLiquidityModel, HistoricalVolume, ReplayHistory, SimulationClock, SimulationConfig,
+    SimulationSummary, WhatIfScenario, WhatIfStrategy, replay_what_if,
+};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use solana_sdk::pubkey::Pubkey;
+
+/// Maximum number of scenarios in one report.
+const MAX_SCENARIOS: usize = 10;
+
+/// Ranges, liquidity and capital recovered from a position's lifecycle.
+struct PositionHistory {
+    /// Pool address.
+    pool: Pubkey,
+    /// Ticks the position started with.
+    initial_ticks: (i32, i32),
+    /// Rebalances as (time, new ticks).
+    rebalances: Vec<(DateTime<Utc>, (i32, i32))>,
+    /// Position liquidity.
+    liquidity: u128,
+    /// Entry value in USD, if the opening was recorded.
+    entry_value_usd: Option<Decimal>,
+    /// Start of the history.
+    start: DateTime<Utc>,
+    /// End of the history; now for open positions.
+    end: DateTime<Utc>,
+}
+
+impl PositionHistory {
+    /// Recovers the history from lifecycle events, oldest first.
+    fn from_events(events: &[LifecycleEvent]) -> Option<Self> {
+        let first = events.first()?;
+        let mut history = Self {
+            pool: first.pool,
+            initial_ticks: (0, 0),
+            rebalances: Vec::new(),
+            liquidity: 0,
+            entry_value_usd: None,
+            start: first.timestamp,
+            end: Utc::now(),
+        };
+        let mut has_range = false;
+
+        for event in events {
+            match &event.data {
+                EventData::PositionOpened(data) => {
+                    history.initial_ticks = (data.tick_lower, data.tick_upper);
+                    history.liquidity = data.liquidity;
+                    history.entry_value_usd = Some(data.entry_value_usd);
+                    history.start = event.timestamp;
+                    has_range = true;
+                }
+                EventData::Rebalance(data) => {
+                    if !has_range {
+                        // Opened before tracking started: the first
+                        // rebalance tells where it was.
+                        history.initial_ticks = (data.old_tick_lower, data.old_tick_upper);
+                        history.liquidity = data.old_liquidity;
+                        has_range = true;
+                    }
+                    history
+                        .rebalances
+                        .push((event.timestamp, (data.new_tick_lower, data.new_tick_upper)));
+                }
+                EventData::Migration(data) => {
+                    // The position starts life in its pool at the migration
+                    history.initial_ticks = (data.new_tick_lower, data.new_tick_upper);
+                    history.liquidity = data.new_liquidity;
+                    history.start = event.timestamp;
+                    has_range = true;
+                }
+                EventData::LiquidityChange(data) => history.liquidity = data.new_liquidity,
+                EventData::PositionClosed(_) => history.end = event.timestamp,
+                EventData::FeesCollected(_) => {}
+            }
+        }
+
+        has_range.then_some(history)
+    }
+}
+
+/// Service for what-if replays.
+pub struct ReplayService {
+    /// Application state.
+    state: AppState,
+}
+
+impl ReplayService {
+    /// Creates a new replay service.
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    /// Replays a position's history under the requested scenarios, using
+    /// the pool's price history stored in the database.
+    pub async fn what_if(
+        &self,
+        position: &Pubkey,
+        request: WhatIfRequest,
+    ) -> Result<WhatIfResponse, ApiError> {
+        if request.scenarios.is_empty() || request.scenarios.len() > MAX_SCENARIOS {
+            return Err(ApiError::Validation(format!(
+                "Request 1-{} scenarios",
+                MAX_SCENARIOS
+            )));
+        }
+        let database = self.state.require_database()?;
+
+        let events = self.state.lifecycle.get_events(position).await;
+        let history = PositionHistory::from_events(&events).ok_or_else(|| {
+            ApiError::not_found(format!("No range history recorded for {}", position))
+        })?;
+        let capital = request
+            .capital_usd
+            .or(history.entry_value_usd)
+            .filter(|c| *c > Decimal::ZERO)
+            .ok_or_else(|| {
+                ApiError::Validation(
+                    "capital_usd is required when the position's opening was not recorded"
+                        .to_string(),
+                )
+            })?;
+
+        let pool = database
+            .pools()
+            .find_by_address(&history.pool.to_string())
+            .await
+            .map_err(|e| ApiError::internal(e.to_string()))?
+            .ok_or_else(|| {
+                ApiError::not_found(format!("Pool not in database: {}", history.pool))
+            })?;
+        let records = database
+            .prices()
+            .find_by_pool_and_range(pool.id, history.start.timestamp(), history.end.timestamp())
+            .await
+            .map_err(|e| ApiError::internal(e.to_string()))?;
+        if records.len() < 2 {
+            return Err(ApiError::Validation(format!(
+                "Not enough price history for pool {} between {} and {}",
+                history.pool, history.start, history.end
+            )));
+        }
+
+        let step_seconds = (records[1].timestamp - records[0].timestamp).max(1);
+        let scenarios = request
+            .scenarios
+            .iter()
+            .map(|s| to_scenario(s, step_seconds))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let to_range = |(lower, upper): (i32, i32)| -> Result<PriceRange, ApiError> {
+            Ok(PriceRange::new(
+                Price::new(tick_price(lower, &pool)?),
+                Price::new(tick_price(upper, &pool)?),
+            ))
+        };
+        let replay_history = ReplayHistory {
+            initial_range: to_range(history.initial_ticks)?,
+            range_changes: history
+                .rebalances
+                .iter()
+                .map(|(at, ticks)| Ok((step_at(&records, at.timestamp()), to_range(*ticks)?)))
+                .collect::<Result<_, ApiError>>()?,
+        };
+
+        let prices: Vec<Price> = records.iter().map(|r| Price::new(r.close_price)).collect();
+        let volume = HistoricalVolume::new(
+            records
+                .iter()
+                .map(|r| r.volume.unwrap_or_default())
+                .collect(),
+        );
+        let liquidity = DynamicLiquidityModel::from_history(pool_liquidity(&records)?);
+        let config = SimulationConfig::new(capital, replay_history.initial_range)
+            .with_fee_rate(Decimal::from(pool.fee_tier) / Decimal::from(10_000))
+            .with_pool_liquidity(history.liquidity)
+            .with_rebalance_cost(request.rebalance_cost_usd)
+            .with_step_duration(step_seconds as u64)
+            .with_clock(SimulationClock::from_timestamps(
+                records
+                    .iter()
+                    .map(|r| r.timestamp.max(0) as u64)
+                    .collect::<Vec<_>>(),
+            ));
+
+        let report = replay_what_if(
+            &config,
+            &prices,
+            &replay_history,
+            &volume,
+            &liquidity,
+            &scenarios,
+        );
+
+        Ok(WhatIfResponse {
+            position_address: position.to_string(),
+            start: history.start,
+            end: history.end,
+            steps: prices.len() as u64,
+            actual: outcome_response("actual", &report.actual, Decimal::ZERO, Decimal::ZERO),
+            scenarios: report
+                .scenarios
+                .iter()
+                .map(|s| outcome_response(&s.label, &s.summary, s.pnl_vs_actual, s.fees_vs_actual))
+                .collect(),
+        })
+    }
+}
+
+/// Converts a requested scenario, with intervals in steps of `step_seconds`.
+fn to_scenario(
+    request: &WhatIfScenarioRequest,
+    step_seconds: i64,
+) -> Result<WhatIfScenario, ApiError> {
+    if request.width_multiplier <= Decimal::ZERO {
+        return Err(ApiError::Validation(
+            "width_multiplier must be positive".to_string(),
+        ));
+    }
+
+    let strategy = match request.strategy {
+        WhatIfStrategyKind::Actual => WhatIfStrategy::Actual,
+        WhatIfStrategyKind::Static => WhatIfStrategy::Static,
+        WhatIfStrategyKind::Periodic => {
+            let hours = request
+                .rebalance_interval_hours
+                .filter(|h| *h > 0)
+                .ok_or_else(|| {
+                    ApiError::Validation(
+                        "rebalance_interval_hours is required for the periodic strategy"
+                            .to_string(),
+                    )
+                })?;
+            WhatIfStrategy::Periodic {
+                interval: (hours * 3600 / step_seconds as u64).max(1),
+            }
+        }
+        WhatIfStrategyKind::Threshold => {
+            let threshold_pct = request
+                .threshold_pct
+                .filter(|p| *p > Decimal::ZERO)
+                .ok_or_else(|| {
+                    ApiError::Validation(
+                        "threshold_pct is required for the threshold strategy".to_string(),
+                    )
+                })?;
+            WhatIfStrategy::Threshold { threshold_pct }
+        }
+    };
+
+    let label = request.label.clone().unwrap_or_else(|| {
+        format!(
+            "{:?} x{}",
+            request.strategy,
+            request.width_multiplier.normalize()
+        )
+        .to_lowercase()
+    });
+    Ok(WhatIfScenario::new(label, request.width_multiplier).with_strategy(strategy))
+}
+
+/// Converts a tick to a price in whole tokens, matching stored prices.
+///
+/// Fails if the pool's token decimals put the price out of range.
+fn tick_price(tick: i32, pool: &PoolRecord) -> Result<Decimal, ApiError> {
+    let price = tick_to_price(tick);
+    let shift = i32::from(pool.decimals_a) - i32::from(pool.decimals_b);
+    let scaled = 10u64
+        .checked_pow(shift.unsigned_abs())
+        .map(Decimal::from)
+        .and_then(|scale| {
+            if shift >= 0 {
+                price.checked_mul(scale)
+            } else {
+                price.checked_div(scale)
+            }
+        });
+    scaled.ok_or_else(|| {
+        ApiError::Validation(format!(
+            "Price at tick {} of pool {} with {} and {} token decimals is out of range",
+            tick, pool.address, pool.decimals_a, pool.decimals_b
+        ))
+    })
+}
+
+/// Returns the index of the first record at or after `timestamp`.
+fn step_at(records: &[PriceRecord], timestamp: i64) -> u64 {
+    records.partition_point(|r| r.timestamp < timestamp) as u64
+}
+
+/// Returns the pool liquidity at each record, carrying the last known
+/// value over gaps.
+fn pool_liquidity(records: &[PriceRecord]) -> Result<Vec<u128>, ApiError> {
+    let first = records
+        .iter()
+        .find_map(|r| r.liquidity.and_then(|l| l.to_u128()))
+        .ok_or_else(|| ApiError::Validation("Price history has no pool liquidity".to_string()))?;
+
+    let mut last = first;
+    Ok(records
+        .iter()
+        .map(|r| {
+            if let Some(liquidity) = r.liquidity.and_then(|l| l.to_u128()) {
+                last = liquidity;
+            }
+            last
+        })
+        .collect())
+}
+
+/// Converts a replay summary into its API representation.
+fn outcome_response(
+    label: &str,
+    summary: &SimulationSummary,
+    pnl_vs_actual: Decimal,
+    fees_vs_actual: Decimal,
+) -> WhatIfOutcomeResponse {
+    WhatIfOutcomeResponse {
+        label: label.to_string(),
+        lower_price: summary.config.initial_range.lower_price.value,
+        upper_price: summary.config.initial_range.upper_price.value,
+        final_value_usd: summary.final_value,
+        net_pnl_usd: summary.net_pnl,
+        fees_usd: summary.total_fees,
+        il_pct: summary.final_il_pct,
+        time_in_range_pct: summary.time_in_range_pct(),
+        rebalance_count: summary.rebalance_count,
+        pnl_vs_actual_usd: pnl_vs_actual,
+        fees_vs_actual_usd: fees_vs_actual,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clmm_lp_execution::prelude::{
+        LifecycleEventType, PositionOpenedData, RebalanceData, RebalanceReason,
+    };
+
+    fn event(event_type: LifecycleEventType, data: EventData) -> LifecycleEvent {
+        LifecycleEvent::new(event_type, Pubkey::new_unique(), Pubkey::new_unique(), data)
+    }
+
+    fn rebalance(old: (i32, i32), new: (i32, i32)) -> LifecycleEvent {
+        event(
+            LifecycleEventType::Rebalanced,
+            EventData::Rebalance(RebalanceData {
+                old_tick_lower: old.0,
+                old_tick_upper: old.1,
+                new_tick_lower: new.0,
+                new_tick_upper: new.1,
+                old_liquidity: 500,
+                new_liquidity: 500,
+                tx_cost_lamports: 5000,
+                il_at_rebalance: Decimal::ZERO,
+                reason: RebalanceReason::RangeExit,
+            }),
+        )
+    }
+
+    #[test]
+    fn test_history_from_events() {
+        let opened = event(
+            LifecycleEventType::PositionOpened,
+            EventData::PositionOpened(PositionOpenedData {
+                tick_lower: -100,
+                tick_upper: 100,
+                liquidity: 1000,
+                amount_a: 0,
+                amount_b: 0,
+                entry_price: Decimal::ONE,
+                entry_value_usd: Decimal::from(500),
+            }),
+        );
+        let history =
+            PositionHistory::from_events(&[opened, rebalance((-100, 100), (0, 200))]).unwrap();
+        assert_eq!(history.initial_ticks, (-100, 100));
+        assert_eq!(history.liquidity, 1000);
+        assert_eq!(history.entry_value_usd, Some(Decimal::from(500)));
+        assert_eq!(history.rebalances.len(), 1);
+        assert_eq!(history.rebalances[0].1, (0, 200));
+
+        // Without an opening the first rebalance gives the starting range
+        let history = PositionHistory::from_events(&[rebalance((-50, 50), (0, 100))]).unwrap();
+        assert_eq!(history.initial_ticks, (-50, 50));
+        assert_eq!(history.entry_value_usd, None);
+
+        assert!(PositionHistory::from_events(&[]).is_none());
+    }
+
+    #[test]
+    fn test_tick_price_rejects_out_of_range_decimals() {
+        let now = chrono::Utc::now();
+        let mut pool = PoolRecord {
+            id: uuid::Uuid::new_v4(),
+            protocol: "orca".to_string(),
+            address: Pubkey::new_unique().to_string(),
+            token_mint_a: Pubkey::new_unique().to_string(),
+            token_mint_b: Pubkey::new_unique().to_string(),
+            symbol_a: "SOL".to_string(),
+            symbol_b: "USDC".to_string(),
+            decimals_a: 9,
+            decimals_b: 6,
+            fee_tier: 30,
+            tick_spacing: 64,
+            created_at: now,
+            updated_at: now,
+        };
+        assert_eq!(tick_price(0, &pool).unwrap(), Decimal::from(1000));
+
+        pool.decimals_a = 30;
+        assert!(matches!(tick_price(0, &pool), Err(ApiError::Validation(_))));
+        pool.decimals_a = -30;
+        assert!(matches!(tick_price(0, &pool), Err(ApiError::Validation(_))));
+    }
+}
@@ -1,12 +1,19 @@
 //! Strategy service for managing automated strategies.
 
 use crate::error::ApiError;
-use crate::state::{AlertUpdate, AppState};
-use clmm_lp_execution::prelude::{DecisionConfig, ExecutorConfig, StrategyExecutor};
+use crate::state::{
+    AlertBroadcastNotifier, AlertUpdate, AppState, LIVE_CANDLE_INTERVAL_SECS, StrategyState,
+};
+use clmm_lp_domain::value_objects::range_spec::RangeSpec;
+use clmm_lp_execution::prelude::{
+    DecisionConfig, ExecutorConfig, MultiNotifier, RestartPolicy, StrategyExecutor,
+};
 use rust_decimal::Decimal;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
-use tracing::info;
+use tracing::{info, warn};
 
 /// Result of a strategy operation.
 #[derive(Debug, Clone)]
@@ -35,6 +42,85 @@ impl StrategyOperationResult {
     }
 }
 
+/// Builds a decision configuration from a strategy's JSON parameters.
+pub(crate) fn decision_config_from_parameters(params: &serde_json::Value) -> DecisionConfig {
+    let mut decision_config = DecisionConfig::default();
+
+    if let Some(threshold) = params.get("rebalance_threshold_pct")
+        && let Some(val) = threshold.as_f64()
+    {
+        decision_config.il_rebalance_threshold =
+            Decimal::from_f64_retain(val / 100.0).unwrap_or(Decimal::new(5, 2));
+    }
+
+    if let Some(max_il) = params.get("max_il_pct")
+        && let Some(val) = max_il.as_f64()
+    {
+        decision_config.il_close_threshold =
+            Decimal::from_f64_retain(val / 100.0).unwrap_or(Decimal::new(15, 2));
+    }
+
+    if let Some(min_hours) = params.get("min_rebalance_interval_hours")
+        && let Some(val) = min_hours.as_u64()
+    {
+        decision_config.min_rebalance_interval_hours = val;
+    }
+
+    if let Some(min_health) = params.get("min_health_score")
+        && let Some(val) = min_health.as_f64()
+    {
+        decision_config.min_health_score = Decimal::from_f64_retain(val);
+    }
+
+    if let Some(divergence) = params.get("max_oracle_divergence_pct")
+        && let Some(val) = divergence.as_f64()
+    {
+        decision_config.max_oracle_divergence_pct = Decimal::from_f64_retain(val / 100.0);
+    }
+
+    // Specs are validated when strategies are saved; only configs stored
+    // before that can still hold an invalid one
+    match range_spec_from_parameters(params) {
+        Ok(spec) => decision_config.range_spec = spec,
+        Err(e) => warn!(error = %e, "Ignoring invalid range_spec"),
+    }
+
+    decision_config
+}
+
+/// Parses the optional `range_spec` of a strategy's JSON parameters.
+fn range_spec_from_parameters(params: &serde_json::Value) -> Result<Option<RangeSpec>, String> {
+    params
+        .get("range_spec")
+        .and_then(|v| v.as_str())
+        .map(str::parse)
+        .transpose()
+}
+
+/// Checks that a strategy's JSON parameters can be applied as given.
+pub(crate) fn validate_parameters(params: &serde_json::Value) -> Result<(), ApiError> {
+    range_spec_from_parameters(params)
+        .map_err(|e| ApiError::Validation(format!("Invalid range_spec: {}", e)))?;
+    Ok(())
+}
+
+/// Decision configurations of all strategies by ID, for per-position assignments.
+pub(crate) fn strategy_decision_configs(
+    strategies: &HashMap<String, StrategyState>,
+) -> Vec<(String, DecisionConfig)> {
+    strategies
+        .iter()
+        .map(|(id, strategy)| {
+            let config = strategy
+                .config
+                .get("parameters")
+                .map(decision_config_from_parameters)
+                .unwrap_or_default();
+            (id.clone(), config)
+        })
+        .collect()
+}
+
 /// Service for strategy operations.
 pub struct StrategyService {
     /// Application state.
@@ -61,6 +147,7 @@ impl StrategyService {
 
         // Get strategy configuration
         let mut strategies = self.state.strategies.write().await;
+        let named_configs = strategy_decision_configs(&strategies);
         let strategy = strategies
             .get_mut(strategy_id)
             .ok_or_else(|| ApiError::not_found("Strategy not found"))?;
@@ -91,6 +178,14 @@ impl StrategyService {
             .and_then(|v| v.as_u64())
             .unwrap_or(300);
 
+        let defaults = ExecutorConfig::default();
+        let max_concurrent_executions = strategy
+            .config
+            .get("parameters")
+            .and_then(|p| p.get("max_concurrent_executions"))
+            .and_then(|v| v.as_u64())
+            .map_or(defaults.max_concurrent_executions, |v| v as usize);
+
         // Create executor configuration
         let executor_config = ExecutorConfig {
             eval_interval_secs,
@@ -98,42 +193,39 @@ impl StrategyService {
             require_confirmation: !auto_execute,
             max_slippage_pct: Decimal::new(5, 3), // 0.5%
             dry_run,
+            max_concurrent_executions,
+            ..defaults
         };
 
         // Create strategy executor
-        let executor = StrategyExecutor::new(
+        let mut executor = StrategyExecutor::new(
             self.state.provider.clone(),
             self.state.monitor.clone(),
             self.state.tx_manager.clone(),
             executor_config,
         );
 
+        executor.set_price_oracle(self.state.reference_price.clone());
+        executor.set_pool_cache(self.state.pool_cache.clone());
+        executor.set_candle_builder(self.state.candles.clone(), LIVE_CANDLE_INTERVAL_SECS);
+        let mut notifier = MultiNotifier::new();
+        notifier.add(AlertBroadcastNotifier::new(&self.state));
+        executor.set_notifier(notifier);
+        executor.set_decision_sender(self.state.decision_updates.clone());
+        executor.set_heartbeat(self.state.register_heartbeat(
+            format!("strategy executor {}", strategy_id),
+            Duration::from_secs(eval_interval_secs),
+        ));
+        executor.set_drawdown_guard(self.state.drawdown_guard.clone());
+        executor.set_anomaly_detector(self.state.anomaly_detector.clone());
+        executor.set_in_flight_registry(self.state.in_flight.clone());
+
         // Configure decision engine if parameters provided
         if let Some(params) = strategy.config.get("parameters") {
-            let mut decision_config = DecisionConfig::default();
-
-            if let Some(threshold) = params.get("rebalance_threshold_pct")
-                && let Some(val) = threshold.as_f64()
-            {
-                decision_config.il_rebalance_threshold =
-                    Decimal::from_f64_retain(val / 100.0).unwrap_or(Decimal::new(5, 2));
-            }
-
-            if let Some(max_il) = params.get("max_il_pct")
-                && let Some(val) = max_il.as_f64()
-            {
-                decision_config.il_close_threshold =
-                    Decimal::from_f64_retain(val / 100.0).unwrap_or(Decimal::new(15, 2));
-            }
-
-            if let Some(min_hours) = params.get("min_rebalance_interval_hours")
-                && let Some(val) = min_hours.as_u64()
-            {
-                decision_config.min_rebalance_interval_hours = val;
-            }
-
-            // Note: Would need mutable access to set config
-            // executor.set_decision_config(decision_config);
+            executor.set_decision_config(decision_config_from_parameters(params));
+        }
+        for (id, config) in named_configs {
+            executor.register_strategy(id, config);
         }
 
         let executor = Arc::new(RwLock::new(executor));
@@ -149,20 +241,34 @@ impl StrategyService {
         let strategy_id_clone = strategy_id.to_string();
         let alert_sender = self.state.alert_updates.clone();
 
-        tokio::spawn(async move {
-            info!(strategy_id = %strategy_id_clone, "Strategy executor task started");
-
-            let executor_guard = executor_clone.read().await;
-            executor_guard.start().await;
-
-            // Notify when stopped
-            let _ = alert_sender.send(AlertUpdate {
-                level: "info".to_string(),
-                message: format!("Strategy {} stopped", strategy_id_clone),
-                timestamp: chrono::Utc::now(),
-                position_address: None,
-            });
-        });
+        let task_name = format!("strategy {}", strategy_id_clone);
+        let supervised = self.state.supervisor.supervise(
+            task_name.clone(),
+            RestartPolicy::OnPanic,
+            move |shutdown| {
+                let executor = executor_clone.clone();
+                async move {
+                    executor.read().await.run_until(shutdown).await;
+                }
+            },
+        );
+        self.state
+            .shutdown
+            .spawn(task_name, async move {
+                info!(strategy_id = %strategy_id_clone, "Strategy executor task started");
+
+                supervised.await;
+
+                // Notify when stopped
+                let _ = alert_sender.send(AlertUpdate {
+                    level: "info".to_string(),
+                    message: format!("Strategy {} stopped", strategy_id_clone),
+                    timestamp: chrono::Utc::now(),
+                    position_address: None,
+                    tags: Vec::new(),
+                });
+            })
+            .await;
 
         // Update strategy state
         strategy.running = true;
@@ -174,6 +280,7 @@ impl StrategyService {
             message: format!("Strategy {} started", strategy_id),
             timestamp: chrono::Utc::now(),
             position_address: None,
+            tags: Vec::new(),
         });
 
         info!(strategy_id = %strategy_id, "Strategy started successfully");
@@ -222,6 +329,7 @@ impl StrategyService {
             message: format!("Strategy {} stopped", strategy_id),
             timestamp: chrono::Utc::now(),
             position_address: None,
+            tags: Vec::new(),
         });
 
         info!(strategy_id = %strategy_id, "Strategy stopped successfully");
@@ -284,6 +392,7 @@ impl StrategyService {
                     "closed_positions": stats.closed_positions,
                     "total_rebalances": stats.total_rebalances,
                     "total_fees_usd": stats.total_fees_usd.to_string(),
+                    "total_rewards_usd": stats.total_rewards_usd.to_string(),
                     "total_pnl_usd": stats.total_pnl_usd.to_string(),
                     "avg_pnl_pct": stats.avg_pnl_pct.to_string(),
                     "total_tx_costs_lamports": stats.total_tx_costs_lamports
@@ -307,6 +416,7 @@ impl StrategyService {
                     "closed_positions": stats.closed_positions,
                     "total_rebalances": stats.total_rebalances,
                     "total_fees_usd": stats.total_fees_usd.to_string(),
+                    "total_rewards_usd": stats.total_rewards_usd.to_string(),
                     "total_pnl_usd": stats.total_pnl_usd.to_string(),
                     "avg_pnl_pct": stats.avg_pnl_pct.to_string(),
                     "total_tx_costs_lamports": stats.total_tx_costs_lamports
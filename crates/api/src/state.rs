@@ -1,12 +1,37 @@
 //! Application state shared across handlers.
 
+use crate::error::{ApiError, ApiResult};
+use crate::models::OptimizationJobStatus;
+use crate::server::DEFAULT_REQUEST_TIMEOUT_SECS;
+use async_trait::async_trait;
+use clmm_lp_data::prelude::{Database, JupiterProvider};
+use clmm_lp_domain::value_objects::OptimizationResult;
 use clmm_lp_execution::prelude::{
-    CircuitBreaker, LifecycleTracker, PositionMonitor, StrategyExecutor, TransactionManager,
+    Alert, AlertLevel, AnomalyConfig, CallbackConfig, CandleBuilderConfig, CircuitBreaker,
+    ConfigAudit, DecisionRecord, DrawdownConfig, DrawdownGuard, ExecutorConfig, Heartbeat,
+    HeartbeatConfig, HeartbeatMonitor, InFlightRegistry, LifecycleCallbacks, LifecycleTracker,
+    LiveCandleBuilder, MultiNotifier, Notifier, PoolAnomalyDetector, PoolStateCache,
+    PositionMonitor, PriorityFeeConfig, PriorityFeeEstimator, Reconciler, ReconcilerConfig,
+    ReferencePrice, ShutdownController, SlotOrderer, StrategyExecutor, Supervisor,
+    SupervisorConfig, TransactionManager,
 };
-use clmm_lp_protocols::prelude::{RpcConfig, RpcProvider};
+use clmm_lp_optimization::prelude::{CancellationToken, OptimizationCache, OptimizationProgress};
+use clmm_lp_protocols::prelude::{RpcConfig, RpcProvider, WhirlpoolState};
+use clmm_lp_simulation::prelude::PercentilePath;
+use rust_decimal::Decimal;
+use solana_sdk::pubkey::Pubkey;
 use std::collections::HashMap;
-use std::sync::Arc;
-use tokio::sync::{RwLock, broadcast};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{RwLock, Semaphore, broadcast};
+use tracing::error;
+
+/// Interval of the live candles strategy executors read regime and
+/// volatility from.
+pub const LIVE_CANDLE_INTERVAL_SECS: u64 = 300;
+
+/// Interval of PnL attribution updates on the positions WebSocket.
+pub const PNL_UPDATE_INTERVAL_SECS: u64 = 5;
 
 /// Application state shared across all handlers.
 #[derive(Clone)]
@@ -27,31 +52,150 @@ pub struct AppState {
     pub position_updates: broadcast::Sender<PositionUpdate>,
     /// WebSocket broadcast channel for alerts.
     pub alert_updates: broadcast::Sender<AlertUpdate>,
+    /// Broadcast channel for decisions made by strategy executors.
+    pub decision_updates: broadcast::Sender<DecisionRecord>,
     /// API configuration.
     pub config: ApiConfig,
     /// Strategy executors by ID.
     pub executors: Arc<RwLock<HashMap<String, Arc<RwLock<StrategyExecutor>>>>>,
     /// Whether in dry-run mode.
     pub dry_run: bool,
+    /// Range optimization jobs by ID.
+    pub optimization_jobs: Arc<RwLock<HashMap<String, Arc<OptimizationJob>>>>,
+    /// Limits how many optimization jobs run at once.
+    pub optimization_slots: Arc<Semaphore>,
+    /// Database for saved simulations, if configured.
+    pub database: Option<Database>,
+    /// Results of previous optimization jobs.
+    pub optimization_cache: Arc<Mutex<OptimizationCache>>,
+    /// Coordinates shutdown of background tasks.
+    pub shutdown: Arc<ShutdownController>,
+    /// Restarts background tasks that crash.
+    pub supervisor: Arc<Supervisor>,
+    /// Audit log of runtime configuration changes.
+    pub config_audit: Arc<ConfigAudit>,
+    /// Oracle strategy executors check pool prices against.
+    pub reference_price: Arc<JupiterReferencePrice>,
+    /// Pool states shared by all strategy executors.
+    pub pool_cache: Arc<PoolStateCache>,
+    /// Candles built from live updates of watched pools.
+    pub candles: Arc<LiveCandleBuilder>,
+    /// Watchdog for loops that stop completing cycles.
+    pub heartbeats: Arc<HeartbeatMonitor>,
+    /// Halts strategy executors on a portfolio drawdown.
+    pub drawdown_guard: Arc<DrawdownGuard>,
+    /// Pauses strategies on pools with abnormal liquidity, volume or fee
+    /// tier changes.
+    pub anomaly_detector: Arc<PoolAnomalyDetector>,
+    /// Decisions being executed by any strategy executor.
+    pub in_flight: Arc<InFlightRegistry>,
+    /// Orders account updates from listeners by slot, dropping stale ones.
+    pub slot_orderer: Arc<SlotOrderer>,
+    /// Keeps watched and transacted accounts in sync with the chain.
+    pub reconciler: Arc<Reconciler>,
 }
 
 impl AppState {
     /// Creates a new application state.
     pub fn new(rpc_config: RpcConfig, api_config: ApiConfig) -> Self {
+        let commitment = rpc_config.commitment;
+        let shutdown = Arc::new(ShutdownController::default());
         let provider = Arc::new(RpcProvider::new(rpc_config));
         let monitor = Arc::new(PositionMonitor::new(
             provider.clone(),
             clmm_lp_execution::prelude::MonitorConfig::default(),
         ));
-        let tx_manager = Arc::new(TransactionManager::new(
+        let pool_cache = Arc::new(PoolStateCache::new(
+            provider.clone(),
+            Duration::from_secs(ExecutorConfig::default().pool_cache_ttl_secs),
+        ));
+        let reconciler = Arc::new(
+            Reconciler::new(provider.clone(), ReconcilerConfig::default())
+                .with_monitor(monitor.clone()),
+        );
+        let fee_estimator = Arc::new(PriorityFeeEstimator::new(
             provider.clone(),
-            clmm_lp_execution::prelude::TransactionConfig::default(),
+            PriorityFeeConfig {
+                fee_api_url: api_config.priority_fee_api_url.clone(),
+                ..Default::default()
+            },
         ));
+        let tx_manager = Arc::new(
+            TransactionManager::new(
+                provider.clone(),
+                clmm_lp_execution::prelude::TransactionConfig {
+                    commitment,
+                    ..Default::default()
+                },
+            )
+            .with_in_flight(shutdown.in_flight())
+            .with_confirmation_hook(reconciler.clone())
+            .with_confirmation_hook(pool_cache.clone())
+            .with_fee_estimator(fee_estimator),
+        );
         let circuit_breaker = Arc::new(CircuitBreaker::default());
-        let lifecycle = Arc::new(LifecycleTracker::new());
+        let mut lifecycle = LifecycleTracker::new();
+        if let Some(url) = &api_config.lifecycle_webhook_url {
+            match api_config
+                .lifecycle_webhook_secret
+                .as_deref()
+                .filter(|secret| !secret.is_empty())
+            {
+                Some(secret) => {
+                    lifecycle = lifecycle
+                        .with_callbacks(LifecycleCallbacks::new(CallbackConfig::new(url, secret)));
+                }
+                None => error!("Lifecycle webhook has no secret; callbacks are disabled"),
+            }
+        }
+        let lifecycle = Arc::new(lifecycle);
 
+        let optimization_slots = Arc::new(Semaphore::new(
+            api_config.max_concurrent_optimizations.max(1),
+        ));
         let (position_tx, _) = broadcast::channel(1000);
         let (alert_tx, _) = broadcast::channel(1000);
+        let (decision_tx, _) = broadcast::channel(1000);
+
+        let mut notifier = MultiNotifier::new();
+        notifier.add(AlertBroadcastNotifier {
+            sender: alert_tx.clone(),
+        });
+        let supervisor = Arc::new(
+            Supervisor::new(SupervisorConfig::default(), shutdown.token()).with_notifier(notifier),
+        );
+        let mut notifier = MultiNotifier::new();
+        notifier.add(AlertBroadcastNotifier {
+            sender: alert_tx.clone(),
+        });
+        let config_audit = Arc::new(ConfigAudit::new().with_notifier(notifier));
+        let mut notifier = MultiNotifier::new();
+        notifier.add(AlertBroadcastNotifier {
+            sender: alert_tx.clone(),
+        });
+        let heartbeats =
+            Arc::new(HeartbeatMonitor::new(HeartbeatConfig::default()).with_notifier(notifier));
+        let mut notifier = MultiNotifier::new();
+        notifier.add(AlertBroadcastNotifier {
+            sender: alert_tx.clone(),
+        });
+        let drawdown_guard = Arc::new(
+            DrawdownGuard::new(DrawdownConfig {
+                max_drawdown_pct: api_config.max_drawdown_pct,
+                window_hours: api_config.drawdown_window_hours,
+                ..Default::default()
+            })
+            .with_circuit_breaker(circuit_breaker.clone())
+            .with_lifecycle(lifecycle.clone())
+            .with_notifier(notifier),
+        );
+        let mut notifier = MultiNotifier::new();
+        notifier.add(AlertBroadcastNotifier {
+            sender: alert_tx.clone(),
+        });
+        let anomaly_detector =
+            Arc::new(PoolAnomalyDetector::new(AnomalyConfig::default()).with_notifier(notifier));
+        let reference_price = Arc::new(JupiterReferencePrice::new(provider.clone()));
 
         Self {
             provider,
@@ -62,17 +206,55 @@ impl AppState {
             strategies: Arc::new(RwLock::new(HashMap::new())),
             position_updates: position_tx,
             alert_updates: alert_tx,
+            decision_updates: decision_tx,
             config: api_config,
             executors: Arc::new(RwLock::new(HashMap::new())),
             dry_run: true, // Default to dry-run for safety
+            optimization_jobs: Arc::new(RwLock::new(HashMap::new())),
+            optimization_slots,
+            database: None,
+            optimization_cache: Arc::new(Mutex::new(OptimizationCache::default())),
+            shutdown,
+            supervisor,
+            config_audit,
+            reference_price,
+            pool_cache,
+            candles: Arc::new(LiveCandleBuilder::new(CandleBuilderConfig::default())),
+            heartbeats,
+            drawdown_guard,
+            anomaly_detector,
+            in_flight: Arc::new(InFlightRegistry::new()),
+            slot_orderer: Arc::new(SlotOrderer::new()),
+            reconciler,
         }
     }
 
+    /// Registers a loop with the heartbeat watchdog.
+    ///
+    /// The loop is reported stalled after the configured heartbeat window,
+    /// or two of its cycles if that is longer.
+    pub fn register_heartbeat(&self, name: impl Into<String>, cycle: Duration) -> Heartbeat {
+        let window = Duration::from_secs(self.config.heartbeat_window_secs).max(cycle * 2);
+        self.heartbeats.register(name, window)
+    }
+
     /// Sets dry-run mode.
     pub fn set_dry_run(&mut self, dry_run: bool) {
         self.dry_run = dry_run;
     }
 
+    /// Sets the database used for saved simulations.
+    pub fn set_database(&mut self, database: Database) {
+        self.database = Some(database);
+    }
+
+    /// Returns the database, or a 503 error if none is configured.
+    pub fn require_database(&self) -> ApiResult<&Database> {
+        self.database
+            .as_ref()
+            .ok_or_else(|| ApiError::ServiceUnavailable("No database configured".to_string()))
+    }
+
     /// Broadcasts a position update.
     pub fn broadcast_position_update(&self, update: PositionUpdate) {
         let _ = self.position_updates.send(update);
@@ -92,6 +274,11 @@ impl AppState {
     pub fn subscribe_alerts(&self) -> broadcast::Receiver<AlertUpdate> {
         self.alert_updates.subscribe()
     }
+
+    /// Subscribes to strategy executor decisions.
+    pub fn subscribe_decisions(&self) -> broadcast::Receiver<DecisionRecord> {
+        self.decision_updates.subscribe()
+    }
 }
 
 /// API configuration.
@@ -103,23 +290,93 @@ pub struct ApiConfig {
     pub port: u16,
     /// API keys for authentication.
     pub api_keys: Vec<String>,
-    /// Whether to enable CORS.
+    /// Whether to enable CORS; `false` disables it whatever
+    /// [`ServerConfig::cors`](crate::server::ServerConfig::cors) allows.
+    #[deprecated(note = "configure `ServerConfig::cors` instead")]
     pub enable_cors: bool,
-    /// Request timeout in seconds.
+    /// Request timeout in seconds; applies when
+    /// [`ServerConfig::request_timeout_secs`](crate::server::ServerConfig::request_timeout_secs)
+    /// is left at its default.
+    #[deprecated(note = "set `ServerConfig::request_timeout_secs` instead")]
     pub request_timeout_secs: u64,
     /// Rate limit per minute.
     pub rate_limit_per_minute: u32,
+    /// Maximum optimization jobs running at once; others wait queued.
+    pub max_concurrent_optimizations: usize,
+    /// Worker threads per optimization job.
+    pub optimizer_threads: usize,
+    /// Job queue workers run by this process when a database is
+    /// configured; 0 leaves queued jobs to workers in other processes.
+    pub job_workers: usize,
+    /// Whether to run the nightly range advisor.
+    pub advisor_enabled: bool,
+    /// Annualized volatility the range advisor assumes (e.g., 0.5 = 50%).
+    pub advisor_volatility: f64,
+    /// Seconds a watched loop may go without completing a cycle before a
+    /// critical "stalled" alert is sent.
+    pub heartbeat_window_secs: u64,
+    /// Portfolio drawdown from the window's peak that halts strategy
+    /// executors (0.1 = 10%).
+    pub max_drawdown_pct: Decimal,
+    /// Hours over which the drawdown peak is taken.
+    pub drawdown_window_hours: u64,
+    /// Whether to send the daily summary report.
+    pub daily_report_enabled: bool,
+    /// Directory daily reports are also written to as Markdown files.
+    pub daily_report_dir: Option<String>,
+    /// Telegram bot token daily reports are sent with.
+    pub telegram_bot_token: Option<String>,
+    /// Telegram chat daily reports are sent to.
+    pub telegram_chat_id: Option<String>,
+    /// Endpoint signed callbacks are posted to on lifecycle milestones.
+    pub lifecycle_webhook_url: Option<String>,
+    /// Secret lifecycle callbacks are signed with; callbacks stay disabled
+    /// without one.
+    pub lifecycle_webhook_secret: Option<String>,
+    /// Whether to show alerts as desktop notifications; needs the
+    /// `desktop-notifications` feature.
+    pub desktop_notifications: bool,
+    /// Named pipe alerts are written to as JSON lines.
+    pub alert_pipe: Option<String>,
+    /// Yellowstone Geyser gRPC endpoint watched pools are streamed from
+    /// instead of WebSocket subscriptions; needs the `geyser` feature.
+    pub geyser_endpoint: Option<String>,
+    /// Access token for the Geyser endpoint.
+    pub geyser_x_token: Option<String>,
+    /// Helius-style fee API consulted alongside recent RPC fees when
+    /// pricing priority fees.
+    pub priority_fee_api_url: Option<String>,
 }
 
 impl Default for ApiConfig {
+    #[allow(deprecated)]
     fn default() -> Self {
         Self {
             host: "0.0.0.0".to_string(),
             port: 8080,
             api_keys: vec![],
             enable_cors: true,
-            request_timeout_secs: 30,
+            request_timeout_secs: DEFAULT_REQUEST_TIMEOUT_SECS,
             rate_limit_per_minute: 100,
+            max_concurrent_optimizations: 1,
+            optimizer_threads: 2,
+            job_workers: 1,
+            advisor_enabled: false,
+            advisor_volatility: 0.5,
+            heartbeat_window_secs: 600,
+            max_drawdown_pct: Decimal::new(10, 2),
+            drawdown_window_hours: 24,
+            daily_report_enabled: false,
+            daily_report_dir: None,
+            telegram_bot_token: None,
+            telegram_chat_id: None,
+            lifecycle_webhook_url: None,
+            lifecycle_webhook_secret: None,
+            desktop_notifications: false,
+            alert_pipe: None,
+            geyser_endpoint: None,
+            geyser_x_token: None,
+            priority_fee_api_url: None,
         }
     }
 }
@@ -141,6 +398,69 @@ pub struct StrategyState {
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// A submitted range optimization job.
+#[derive(Debug)]
+pub struct OptimizationJob {
+    /// Job ID.
+    pub id: String,
+    /// Cancels the job while queued or running.
+    pub cancellation: CancellationToken,
+    /// Created timestamp.
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Mutable job state, updated from optimizer worker threads.
+    state: Mutex<OptimizationJobState>,
+}
+
+/// Snapshot of an optimization job's state.
+#[derive(Debug, Clone)]
+pub struct OptimizationJobState {
+    /// Job status.
+    pub status: OptimizationJobStatus,
+    /// Latest progress report.
+    pub progress: OptimizationProgress,
+    /// Final result once completed.
+    pub result: Option<OptimizationResult>,
+    /// Monte Carlo paths of the result at the requested percentiles.
+    pub paths: Vec<PercentilePath>,
+    /// Error message if failed.
+    pub error: Option<String>,
+}
+
+impl OptimizationJob {
+    /// Creates a queued job.
+    pub fn new(id: String) -> Self {
+        Self {
+            id,
+            cancellation: CancellationToken::new(),
+            created_at: chrono::Utc::now(),
+            state: Mutex::new(OptimizationJobState {
+                status: OptimizationJobStatus::Queued,
+                progress: OptimizationProgress::default(),
+                result: None,
+                paths: Vec::new(),
+                error: None,
+            }),
+        }
+    }
+
+    /// Returns a copy of the current state.
+    pub fn snapshot(&self) -> OptimizationJobState {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// Updates the job state.
+    pub fn update(&self, f: impl FnOnce(&mut OptimizationJobState)) {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        f(&mut state);
+    }
+}
+
 /// Position update for WebSocket broadcast.
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct PositionUpdate {
@@ -165,4 +485,105 @@ pub struct AlertUpdate {
     pub timestamp: chrono::DateTime<chrono::Utc>,
     /// Related position (if any).
     pub position_address: Option<String>,
+    /// Tags of the related position.
+    pub tags: Vec<String>,
+}
+
+/// Notifier that forwards execution alerts to the alerts WebSocket.
+pub struct AlertBroadcastNotifier {
+    /// Alert broadcast channel.
+    sender: broadcast::Sender<AlertUpdate>,
+}
+
+impl AlertBroadcastNotifier {
+    /// Creates a notifier publishing on the state's alert channel.
+    pub fn new(state: &AppState) -> Self {
+        Self {
+            sender: state.alert_updates.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for AlertBroadcastNotifier {
+    async fn notify(&self, alert: &Alert) -> anyhow::Result<()> {
+        let level = match alert.level {
+            AlertLevel::Info => "info",
+            AlertLevel::Warning => "warning",
+            AlertLevel::Critical => "critical",
+        };
+        // No subscribers is not an error
+        let _ = self.sender.send(AlertUpdate {
+            level: level.to_string(),
+            message: alert.message.clone(),
+            timestamp: alert.timestamp,
+            position_address: alert.position.clone(),
+            tags: alert.tags.clone(),
+        });
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "websocket"
+    }
+}
+
+/// Byte offset of the decimals field in an SPL mint account.
+const MINT_DECIMALS_OFFSET: usize = 44;
+
+/// Reference pool prices from the Jupiter Price API.
+pub struct JupiterReferencePrice {
+    /// Jupiter price client.
+    jupiter: JupiterProvider,
+    /// RPC provider for mint decimals.
+    provider: Arc<RpcProvider>,
+    /// Decimals by mint.
+    decimals: RwLock<HashMap<Pubkey, u8>>,
+}
+
+impl JupiterReferencePrice {
+    /// Creates a reference price source reading mint decimals through `provider`.
+    pub fn new(provider: Arc<RpcProvider>) -> Self {
+        Self {
+            jupiter: JupiterProvider::new(),
+            provider,
+            decimals: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Gets a mint's decimals, caching them.
+    async fn decimals(&self, mint: &Pubkey) -> anyhow::Result<u8> {
+        if let Some(decimals) = self.decimals.read().await.get(mint) {
+            return Ok(*decimals);
+        }
+        let account = self.provider.get_account(mint).await?;
+        let decimals = *account
+            .data
+            .get(MINT_DECIMALS_OFFSET)
+            .ok_or_else(|| anyhow::anyhow!("{} is not a mint account", mint))?;
+        self.decimals.write().await.insert(*mint, decimals);
+        Ok(decimals)
+    }
+}
+
+#[async_trait]
+impl ReferencePrice for JupiterReferencePrice {
+    async fn price(&self, pool: &WhirlpoolState) -> anyhow::Result<Decimal> {
+        let ratio = self
+            .jupiter
+            .get_price_ratio(
+                &pool.token_mint_a.to_string(),
+                &pool.token_mint_b.to_string(),
+            )
+            .await?;
+        // Jupiter prices whole tokens; pool prices are in raw units
+        let decimals_a = self.decimals(&pool.token_mint_a).await?;
+        let decimals_b = self.decimals(&pool.token_mint_b).await?;
+        let scale = |decimals: u8| Decimal::from(10u64.pow(u32::from(decimals)));
+        Ok(ratio * scale(decimals_b) / scale(decimals_a))
+    }
+
+    fn name(&self) -> &str {
+        "jupiter"
+    }
 }
@@ -0,0 +1,201 @@
+//! Native HTTPS for the REST API.
+//!
+//! Deployments without a reverse proxy can terminate TLS in the server
+//! itself. The certificate and key are read from PEM files and watched for
+//! changes, so renewed certificates (e.g. from certbot) are picked up
+//! without a restart; connections already open keep their certificate.
+
+use anyhow::{Context, Result};
+use axum::Router;
+use axum_server::Handle;
+use axum_server::tls_rustls::RustlsConfig;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tracing::{info, warn};
+
+/// Time open connections get to finish after shutdown is signalled.
+const SHUTDOWN_GRACE_SECS: u64 = 30;
+
+/// TLS configuration.
+#[derive(Debug, Clone)]
+pub struct TlsConfig {
+    /// PEM certificate chain, leaf first.
+    pub cert_path: PathBuf,
+    /// PEM private key.
+    pub key_path: PathBuf,
+    /// How often the files are checked for changes, in seconds; 0 disables
+    /// reloading.
+    pub reload_interval_secs: u64,
+}
+
+impl TlsConfig {
+    /// Creates a configuration reloading changed files every 30 seconds.
+    pub fn new(cert_path: impl Into<PathBuf>, key_path: impl Into<PathBuf>) -> Self {
+        Self {
+            cert_path: cert_path.into(),
+            key_path: key_path.into(),
+            reload_interval_secs: 30,
+        }
+    }
+
+    /// Sets how often the files are checked for changes.
+    #[must_use]
+    pub fn with_reload_interval(mut self, secs: u64) -> Self {
+        self.reload_interval_secs = secs;
+        self
+    }
+
+    /// Loads the certificate and key.
+    ///
+    /// # Errors
+    /// Returns an error if the files cannot be read or do not hold a
+    /// matching certificate and key.
+    pub async fn load(&self) -> Result<RustlsConfig> {
+        install_crypto_provider();
+        RustlsConfig::from_pem_file(&self.cert_path, &self.key_path)
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to load TLS certificate {} and key {}",
+                    self.cert_path.display(),
+                    self.key_path.display()
+                )
+            })
+    }
+}
+
+/// Modification times and sizes of the certificate and key files, used to
+/// notice when they are replaced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FileStamp(Vec<Option<(SystemTime, u64)>>);
+
+impl FileStamp {
+    fn read(paths: &[&Path]) -> Self {
+        Self(
+            paths
+                .iter()
+                .map(|path| {
+                    let metadata = std::fs::metadata(path).ok()?;
+                    Some((metadata.modified().ok()?, metadata.len()))
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Serves `router` over HTTPS on `addr` until `shutdown` completes.
+///
+/// # Errors
+/// Returns an error if the certificate cannot be loaded or the server
+/// fails.
+pub async fn serve(
+    addr: SocketAddr,
+    router: Router,
+    config: &TlsConfig,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> Result<()> {
+    let rustls = config.load().await?;
+
+    let handle = Handle::new();
+    let shutdown_handle = handle.clone();
+    tokio::spawn(async move {
+        shutdown.await;
+        shutdown_handle.graceful_shutdown(Some(Duration::from_secs(SHUTDOWN_GRACE_SECS)));
+    });
+
+    let watcher = (config.reload_interval_secs > 0)
+        .then(|| tokio::spawn(watch_certificate(config.clone(), rustls.clone())));
+
+    let result = axum_server::bind_rustls(addr, rustls)
+        .handle(handle)
+        .serve(router.into_make_service())
+        .await;
+
+    if let Some(watcher) = watcher {
+        watcher.abort();
+    }
+    result.context("HTTPS server failed")
+}
+
+/// Reloads the certificate and key whenever their files change.
+///
+/// A failed reload keeps the current certificate and is retried on the next
+/// check, so a certificate replaced before its key is picked up once both
+/// are in place.
+async fn watch_certificate(config: TlsConfig, rustls: RustlsConfig) {
+    let paths = [config.cert_path.as_path(), config.key_path.as_path()];
+    let mut loaded = FileStamp::read(&paths);
+    let mut interval = tokio::time::interval(Duration::from_secs(config.reload_interval_secs));
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+
+        let current = FileStamp::read(&paths);
+        if current == loaded {
+            continue;
+        }
+
+        match rustls
+            .reload_from_pem_file(&config.cert_path, &config.key_path)
+            .await
+        {
+            Ok(()) => {
+                info!(cert = %config.cert_path.display(), "Reloaded TLS certificate");
+                loaded = current;
+            }
+            Err(e) => {
+                warn!(
+                    cert = %config.cert_path.display(),
+                    error = %e,
+                    "Failed to reload TLS certificate, keeping the current one"
+                );
+            }
+        }
+    }
+}
+
+/// Installs the process-wide rustls crypto provider unless one is set.
+fn install_crypto_provider() {
+    if rustls::crypto::CryptoProvider::get_default().is_none() {
+        // Losing the race to another installer is fine
+        let _ = rustls::crypto::ring::default_provider().install_default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_stamp_changes_with_files() {
+        let dir = std::env::temp_dir().join(format!("clmm-lp-tls-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let cert = dir.join("cert.pem");
+        let key = dir.join("key.pem");
+        let paths = [cert.as_path(), key.as_path()];
+
+        let missing = FileStamp::read(&paths);
+        assert_eq!(missing, FileStamp(vec![None, None]));
+
+        std::fs::write(&cert, "cert").unwrap();
+        std::fs::write(&key, "key").unwrap();
+        let written = FileStamp::read(&paths);
+        assert_ne!(written, missing);
+        assert_eq!(FileStamp::read(&paths), written);
+
+        std::fs::write(&cert, "renewed cert").unwrap();
+        assert_ne!(FileStamp::read(&paths), written);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_load_fails_without_files() {
+        let config = TlsConfig::new("/nonexistent/cert.pem", "/nonexistent/key.pem");
+        let err = config.load().await.unwrap_err();
+        assert!(err.to_string().contains("/nonexistent/cert.pem"));
+    }
+}
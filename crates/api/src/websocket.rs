@@ -1,16 +1,25 @@
 //! WebSocket handlers for real-time updates.
 
-use crate::state::AppState;
+use crate::state::{AppState, PositionUpdate};
 use axum::{
     extract::{
-        State,
+        Query, State,
         ws::{Message, WebSocket, WebSocketUpgrade},
     },
     response::Response,
 };
+use clmm_lp_execution::prelude::{MonitoredPosition, PnLAttribution};
+use clmm_lp_execution::shutdown::CancellationToken;
 use futures::{SinkExt, StreamExt};
+use serde::Deserialize;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::time::Duration;
 use tracing::{debug, error, info};
 
+/// Update type of periodic PnL attribution updates.
+pub const PNL_UPDATE_TYPE: &str = "pnl";
+
 /// WebSocket handler for position updates.
 pub async fn positions_ws(ws: WebSocketUpgrade, State(state): State<AppState>) -> Response {
     ws.on_upgrade(|socket| handle_positions_ws(socket, state))
@@ -65,13 +74,24 @@ async fn handle_positions_ws(socket: WebSocket, state: AppState) {
     info!("Position WebSocket client disconnected");
 }
 
+/// Query parameters for the alerts WebSocket.
+#[derive(Debug, Default, Deserialize)]
+pub struct AlertsQuery {
+    /// Only forward alerts for positions carrying this tag.
+    pub tag: Option<String>,
+}
+
 /// WebSocket handler for alert updates.
-pub async fn alerts_ws(ws: WebSocketUpgrade, State(state): State<AppState>) -> Response {
-    ws.on_upgrade(|socket| handle_alerts_ws(socket, state))
+pub async fn alerts_ws(
+    ws: WebSocketUpgrade,
+    State(state): State<AppState>,
+    Query(query): Query<AlertsQuery>,
+) -> Response {
+    ws.on_upgrade(|socket| handle_alerts_ws(socket, state, query.tag))
 }
 
 /// Handles alerts WebSocket connection.
-async fn handle_alerts_ws(socket: WebSocket, state: AppState) {
+async fn handle_alerts_ws(socket: WebSocket, state: AppState, tag: Option<String>) {
     let (mut sender, mut receiver) = socket.split();
 
     // Subscribe to alert updates
@@ -82,6 +102,9 @@ async fn handle_alerts_ws(socket: WebSocket, state: AppState) {
     // Spawn task to forward alerts to client
     let send_task = tokio::spawn(async move {
         while let Ok(alert) = rx.recv().await {
+            if tag.as_ref().is_some_and(|tag| !alert.tags.contains(tag)) {
+                continue;
+            }
             let msg = serde_json::to_string(&alert).unwrap_or_default();
             if sender.send(Message::Text(msg.into())).await.is_err() {
                 break;
@@ -114,3 +137,127 @@ async fn handle_alerts_ws(socket: WebSocket, state: AppState) {
 
     info!("Alerts WebSocket client disconnected");
 }
+
+/// Turns monitored positions into PnL attribution updates, with deltas
+/// against the last update sent for each position.
+#[derive(Debug, Default)]
+pub struct PnLAttributionFeed {
+    /// Last attribution sent per position.
+    last: HashMap<Pubkey, PnLAttribution>,
+}
+
+impl PnLAttributionFeed {
+    /// Creates an empty feed.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns an update for each position whose attribution changed since
+    /// the last call. Positions no longer monitored are forgotten.
+    pub fn updates(&mut self, positions: &[MonitoredPosition]) -> Vec<PositionUpdate> {
+        self.last
+            .retain(|address, _| positions.iter().any(|p| p.address == *address));
+        let now = chrono::Utc::now();
+        positions
+            .iter()
+            .filter_map(|position| {
+                let attribution = position.pnl.attribution();
+                let delta = self.record(position.address, attribution)?;
+                Some(pnl_update(position.address, attribution, delta, now))
+            })
+            .collect()
+    }
+
+    /// Stores `attribution` for `address`. Returns `None` if it is unchanged,
+    /// otherwise the delta against the previous one, which is itself `None`
+    /// for a position seen for the first time.
+    fn record(
+        &mut self,
+        address: Pubkey,
+        attribution: PnLAttribution,
+    ) -> Option<Option<PnLAttribution>> {
+        match self.last.insert(address, attribution) {
+            Some(previous) if previous == attribution => None,
+            previous => Some(previous.map(|previous| attribution - previous)),
+        }
+    }
+}
+
+/// Builds a PnL attribution update.
+fn pnl_update(
+    address: Pubkey,
+    attribution: PnLAttribution,
+    delta: Option<PnLAttribution>,
+    timestamp: chrono::DateTime<chrono::Utc>,
+) -> PositionUpdate {
+    PositionUpdate {
+        update_type: PNL_UPDATE_TYPE.to_string(),
+        position_address: address.to_string(),
+        timestamp,
+        data: serde_json::json!({
+            "net_pnl_usd": attribution.net_pnl_usd(),
+            "attribution": attribution,
+            "net_pnl_delta_usd": delta.map(|d| d.net_pnl_usd()),
+            "delta": delta,
+        }),
+    }
+}
+
+/// Broadcasts PnL attribution updates to position WebSocket clients every
+/// `interval` until `shutdown` is cancelled.
+pub async fn run_pnl_updates(state: AppState, interval: Duration, shutdown: CancellationToken) {
+    let mut feed = PnLAttributionFeed::new();
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        tokio::select! {
+            () = shutdown.cancelled() => break,
+            _ = ticker.tick() => {}
+        }
+        // Nothing to compute while no client is listening
+        if state.position_updates.receiver_count() == 0 {
+            continue;
+        }
+        let positions = state.monitor.get_positions().await;
+        for update in feed.updates(&positions) {
+            state.broadcast_position_update(update);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn test_feed_reports_deltas_only_on_change() {
+        let mut feed = PnLAttributionFeed::new();
+        let address = Pubkey::new_unique();
+        let usd = Decimal::from;
+        let first = PnLAttribution::new(usd(1000), usd(1030), Decimal::new(-2, 2), usd(15));
+
+        assert_eq!(feed.record(address, first), Some(None));
+        assert_eq!(feed.record(address, first), None);
+
+        let second = PnLAttribution::new(usd(1000), usd(1010), Decimal::new(-3, 2), usd(18));
+        let delta = feed.record(address, second).unwrap().unwrap();
+        assert_eq!(delta.fees_usd, usd(3));
+        assert_eq!(delta.il_usd, usd(-10));
+        assert_eq!(delta.price_exposure_usd, usd(-10));
+
+        let update = pnl_update(address, second, Some(delta), chrono::Utc::now());
+        assert_eq!(update.update_type, PNL_UPDATE_TYPE);
+        assert_eq!(update.data["attribution"]["fees_usd"], "18");
+        assert_eq!(update.data["delta"], serde_json::to_value(delta).unwrap());
+        let net_delta: Decimal = update.data["net_pnl_delta_usd"]
+            .as_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(net_delta, usd(-17));
+
+        // Positions that stop being monitored start over
+        assert!(feed.updates(&[]).is_empty());
+        assert_eq!(feed.record(address, second), Some(None));
+    }
+}
@@ -0,0 +1,276 @@
+//! Alert rule command implementation.
+//!
+//! Rules are stored in the database; a running API server hot-loads them
+//! into its position monitor.
+
+use anyhow::{Context, Result, bail};
+use clap::ValueEnum;
+use clmm_lp_data::prelude::*;
+use clmm_lp_execution::prelude::{AlertLevel, AlertRule, RuleCondition};
+use prettytable::{Table, row};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+
+/// Maximum length of a rule name, as stored in the database.
+const MAX_RULE_NAME_LEN: usize = 64;
+
+/// Condition that triggers an alert rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ConditionKind {
+    /// Position leaves its range
+    RangeExit,
+    /// Position re-enters its range
+    RangeEntry,
+    /// Impermanent loss above the threshold (fraction, e.g. 0.05)
+    IlExceeds,
+    /// Net PnL above the threshold (percent)
+    PnlExceeds,
+    /// Net PnL below the threshold (percent)
+    PnlBelow,
+    /// Fees earned above the threshold (USD)
+    FeesExceed,
+    /// Hours since the last rebalance above the threshold
+    HoursSinceRebalance,
+    /// Health score below the threshold (0-100)
+    HealthBelow,
+    /// Price outside the position's break-even band
+    OutsideBreakeven,
+}
+
+/// Severity of the alerts a rule raises.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Severity {
+    /// Informational
+    Info,
+    /// Needs attention
+    Warning,
+    /// Needs action
+    Critical,
+}
+
+impl From<Severity> for AlertLevel {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Info => Self::Info,
+            Severity::Warning => Self::Warning,
+            Severity::Critical => Self::Critical,
+        }
+    }
+}
+
+/// Arguments of `alerts add`.
+#[derive(Debug, Clone)]
+pub struct AddRuleArgs {
+    /// Unique rule name.
+    pub name: String,
+    /// Triggering condition.
+    pub condition: ConditionKind,
+    /// Threshold of the condition, if it takes one.
+    pub threshold: Option<f64>,
+    /// Alert severity.
+    pub severity: Severity,
+    /// Message template.
+    pub message: Option<String>,
+    /// Notifier channels; empty sends to all.
+    pub channels: Vec<String>,
+    /// Position tags the rule is limited to.
+    pub tags: Vec<String>,
+    /// Cooldown between alerts in seconds.
+    pub cooldown_secs: u64,
+    /// Whether the rule starts disabled.
+    pub disabled: bool,
+}
+
+/// Builds the rule described by `args`.
+///
+/// # Errors
+/// Returns an error for an invalid name, or a threshold that is missing,
+/// not needed or not representable.
+pub fn build_rule(args: &AddRuleArgs) -> Result<AlertRule> {
+    let name = args.name.trim();
+    if name.is_empty() || name.len() > MAX_RULE_NAME_LEN {
+        bail!("Rule name must be 1-{} characters", MAX_RULE_NAME_LEN);
+    }
+
+    let condition = build_condition(args.condition, args.threshold)?;
+    let alert_type = condition.default_alert_type();
+    let mut rule = AlertRule::new(name, condition, args.severity.into(), alert_type)
+        .with_channels(cleaned(&args.channels))
+        .with_tags(cleaned(&args.tags))
+        .with_cooldown(args.cooldown_secs);
+    if let Some(message) = &args.message {
+        rule = rule.with_message(message.clone());
+    }
+    if args.disabled {
+        rule = rule.disabled();
+    }
+    Ok(rule)
+}
+
+/// Builds a condition from its kind and threshold.
+fn build_condition(kind: ConditionKind, threshold: Option<f64>) -> Result<RuleCondition> {
+    let decimal = || -> Result<Decimal> {
+        let value = threshold.with_context(|| format!("{:?} requires --threshold", kind))?;
+        Decimal::from_f64(value).with_context(|| format!("Invalid threshold: {}", value))
+    };
+
+    let condition = match kind {
+        ConditionKind::RangeExit => RuleCondition::RangeExit,
+        ConditionKind::RangeEntry => RuleCondition::RangeEntry,
+        ConditionKind::OutsideBreakeven => RuleCondition::OutsideBreakeven,
+        ConditionKind::IlExceeds => RuleCondition::ILExceeds(decimal()?),
+        ConditionKind::PnlExceeds => RuleCondition::PnLExceeds(decimal()?),
+        ConditionKind::PnlBelow => RuleCondition::PnLBelow(decimal()?),
+        ConditionKind::FeesExceed => RuleCondition::FeesExceed(decimal()?),
+        ConditionKind::HealthBelow => RuleCondition::HealthBelow(decimal()?),
+        ConditionKind::HoursSinceRebalance => {
+            let hours = threshold.with_context(|| format!("{:?} requires --threshold", kind))?;
+            if hours < 0.0 || hours.fract() != 0.0 {
+                bail!("Hours since rebalance must be a whole number");
+            }
+            RuleCondition::TimeSinceRebalance(hours as u64)
+        }
+    };
+    if threshold.is_some()
+        && matches!(
+            condition,
+            RuleCondition::RangeExit | RuleCondition::RangeEntry | RuleCondition::OutsideBreakeven
+        )
+    {
+        bail!("{:?} does not take a threshold", kind);
+    }
+    Ok(condition)
+}
+
+/// Trims values and drops empty ones.
+fn cleaned(values: &[String]) -> Vec<String> {
+    values
+        .iter()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .collect()
+}
+
+/// Stores a rule, replacing any rule with the same name.
+///
+/// # Errors
+/// Returns an error if the rule cannot be stored.
+pub async fn run_add(db: &Database, rule: &AlertRule) -> Result<()> {
+    db.alert_rules()
+        .upsert(&rule.name, serde_json::to_value(rule)?)
+        .await?;
+    println!("✅ Saved alert rule {}", rule.name);
+    println!("   Running API servers apply it within a few seconds.");
+    Ok(())
+}
+
+/// Prints the stored rules.
+///
+/// # Errors
+/// Returns an error if the rules cannot be loaded.
+pub async fn run_list(db: &Database) -> Result<()> {
+    let records = db.alert_rules().find_all().await?;
+    if records.is_empty() {
+        println!("No alert rules found.");
+        return Ok(());
+    }
+
+    println!("🔔 Alert Rules:");
+    println!();
+    let mut table = Table::new();
+    table.add_row(row![
+        "Name",
+        "Condition",
+        "Severity",
+        "Channels",
+        "Tags",
+        "Cooldown",
+        "Enabled"
+    ]);
+    for record in records {
+        let rule: AlertRule = serde_json::from_value(record.definition)
+            .with_context(|| format!("Invalid stored alert rule {}", record.name))?;
+        table.add_row(row![
+            rule.name,
+            format!("{:?}", rule.condition),
+            format!("{:?}", rule.level),
+            or_all(&rule.channels),
+            or_all(&rule.tags),
+            format!("{}s", rule.cooldown_secs),
+            if rule.enabled { "yes" } else { "no" }
+        ]);
+    }
+    table.printstd();
+    Ok(())
+}
+
+/// Joins values, showing an empty list as "all".
+fn or_all(values: &[String]) -> String {
+    if values.is_empty() {
+        "all".to_string()
+    } else {
+        values.join(", ")
+    }
+}
+
+/// Removes a stored rule.
+///
+/// # Errors
+/// Returns an error if no rule has the name or it cannot be removed.
+pub async fn run_remove(db: &Database, name: &str) -> Result<()> {
+    if !db.alert_rules().delete(name).await? {
+        bail!("Alert rule not found: {}", name);
+    }
+    println!("✅ Removed alert rule {}", name);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clmm_lp_execution::prelude::AlertType;
+    use rust_decimal_macros::dec;
+
+    fn args(condition: ConditionKind, threshold: Option<f64>) -> AddRuleArgs {
+        AddRuleArgs {
+            name: "il-high".to_string(),
+            condition,
+            threshold,
+            severity: Severity::Critical,
+            message: None,
+            channels: vec!["webhook".to_string(), " ".to_string()],
+            tags: Vec::new(),
+            cooldown_secs: 600,
+            disabled: false,
+        }
+    }
+
+    #[test]
+    fn test_build_rule() {
+        let rule = build_rule(&args(ConditionKind::IlExceeds, Some(0.05))).unwrap();
+        assert!(matches!(rule.condition, RuleCondition::ILExceeds(t) if t == dec!(0.05)));
+        assert_eq!(rule.level, AlertLevel::Critical);
+        assert_eq!(rule.alert_type, AlertType::ILThreshold);
+        assert_eq!(rule.channels, vec!["webhook".to_string()]);
+        assert_eq!(rule.cooldown_secs, 600);
+        assert!(rule.enabled);
+
+        let rule = build_rule(&args(ConditionKind::HoursSinceRebalance, Some(48.0))).unwrap();
+        assert!(matches!(
+            rule.condition,
+            RuleCondition::TimeSinceRebalance(48)
+        ));
+    }
+
+    #[test]
+    fn test_threshold_must_match_condition() {
+        assert!(build_rule(&args(ConditionKind::IlExceeds, None)).is_err());
+        assert!(build_rule(&args(ConditionKind::RangeExit, Some(1.0))).is_err());
+        assert!(build_rule(&args(ConditionKind::HoursSinceRebalance, Some(1.5))).is_err());
+        assert!(build_rule(&args(ConditionKind::RangeExit, None)).is_ok());
+
+        let mut unnamed = args(ConditionKind::RangeExit, None);
+        unnamed.name = "  ".to_string();
+        assert!(build_rule(&unnamed).is_err());
+    }
+}
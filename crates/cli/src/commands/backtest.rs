@@ -4,15 +4,22 @@
 //! using historical price data.
 
 use crate::output::{BacktestReport, print_backtest_report};
-use anyhow::Result;
+use anyhow::{Context, Result, bail};
+use chrono::NaiveDate;
 use clmm_lp_data::prelude::*;
+use clmm_lp_domain::entities::price_candle::PriceCandle;
 use clmm_lp_domain::entities::token::Token;
+use clmm_lp_domain::value_objects::amount::Amount;
 use clmm_lp_domain::value_objects::price::Price;
 use clmm_lp_domain::value_objects::price_range::PriceRange;
 use clmm_lp_simulation::prelude::*;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::FromPrimitive;
 use tracing::info;
+use uuid::Uuid;
+
+/// Seconds between backtest steps; interval options are in hours.
+const STEP_SECS: u64 = 3600;
 
 /// Arguments for the backtest command.
 #[derive(Debug, Clone)]
@@ -158,7 +165,7 @@ fn run_simulation(args: &BacktestArgs, prices: &[Price]) -> Result<BacktestRepor
         .unwrap_or(Decimal::from(100));
 
     // Create simulation config
-    let config = SimulationConfig::new(args.capital, range.clone())
+    let config = SimulationConfig::new(args.capital, range)
         .with_fee_rate(Decimal::from_f64(0.003).unwrap())
         .with_rebalance_cost(args.tx_cost)
         .with_pool_liquidity(1_000_000_000)
@@ -308,6 +315,146 @@ fn calculate_sharpe(pnl_history: &[Decimal]) -> Option<Decimal> {
     Some(sharpe)
 }
 
+/// Loads a saved pool and its cached hourly candles for the days `from`
+/// through `to`, without calling any external API.
+///
+/// `pool` is the pool's ID or on-chain address. Fails, naming the missing
+/// spans, unless the cache covers the whole period.
+pub async fn load_cached_candles(
+    db: &Database,
+    pool: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<(PoolRecord, Vec<PriceCandle>)> {
+    if to < from {
+        bail!("--to {} is before --from {}", to, from);
+    }
+    let record = match Uuid::parse_str(pool) {
+        Ok(id) => db.pools().find_by_id(id).await?,
+        Err(_) => db.pools().find_by_address(pool).await?,
+    }
+    .with_context(|| format!("No saved pool {}; import it with `pools import`", pool))?;
+
+    let start = from.and_time(chrono::NaiveTime::MIN).and_utc().timestamp();
+    let end = to
+        .succ_opt()
+        .context("--to is out of range")?
+        .and_time(chrono::NaiveTime::MIN)
+        .and_utc()
+        .timestamp();
+    let prices = db
+        .prices()
+        .find_by_pool_and_range(record.id, start, end - 1)
+        .await?;
+    if prices.is_empty() {
+        bail!(
+            "No cached prices for pool {} between {} and {}; backfill them with `pools import` and `pools backfill`",
+            record.address,
+            from,
+            to
+        );
+    }
+
+    let timestamps: Vec<u64> = prices
+        .iter()
+        .map(|p| u64::try_from(p.timestamp).unwrap_or_default())
+        .collect();
+    if let Some(step) = timestamps
+        .windows(2)
+        .map(|w| w[1] - w[0])
+        .filter(|&d| d > 0)
+        .min()
+        && step != STEP_SECS
+    {
+        bail!(
+            "Cached prices for pool {} are at {}-minute resolution; backtests need hourly candles",
+            record.address,
+            step / 60
+        );
+    }
+    let missing = missing_ranges(
+        &timestamps,
+        u64::try_from(start)?,
+        u64::try_from(end)?,
+        STEP_SECS,
+    );
+    if !missing.is_empty() {
+        let spans: Vec<String> = missing
+            .iter()
+            .map(|&(gap_start, gap_end)| {
+                format!(
+                    "{} to {}",
+                    format_timestamp(gap_start),
+                    format_timestamp(gap_end)
+                )
+            })
+            .collect();
+        bail!(
+            "Cached prices for pool {} are missing {} span(s): {}",
+            record.address,
+            spans.len(),
+            spans.join(", ")
+        );
+    }
+
+    let token_a = Token::new(
+        &record.token_mint_a,
+        &record.symbol_a,
+        u8::try_from(record.decimals_a)?,
+        &record.symbol_a,
+    );
+    let token_b = Token::new(
+        &record.token_mint_b,
+        &record.symbol_b,
+        u8::try_from(record.decimals_b)?,
+        &record.symbol_b,
+    );
+    let candles = prices
+        .iter()
+        .zip(timestamps)
+        .map(|(price, start_timestamp)| PriceCandle {
+            token_a: token_a.clone(),
+            token_b: token_b.clone(),
+            start_timestamp,
+            duration_seconds: STEP_SECS,
+            open: Price::new(price.open_price),
+            high: Price::new(price.high_price),
+            low: Price::new(price.low_price),
+            close: Price::new(price.close_price),
+            volume_token_a: Amount::from_decimal(
+                price.volume.unwrap_or_default(),
+                token_a.decimals,
+            ),
+        })
+        .collect();
+    Ok((record, candles))
+}
+
+/// Finds the spans of `[start, end)` not covered by candles of `step`
+/// seconds starting at `timestamps`, which must be sorted.
+#[must_use]
+pub fn missing_ranges(timestamps: &[u64], start: u64, end: u64, step: u64) -> Vec<(u64, u64)> {
+    let mut missing = Vec::new();
+    let mut covered_until = start;
+    for &timestamp in timestamps {
+        if timestamp > covered_until {
+            missing.push((covered_until, timestamp));
+        }
+        covered_until = covered_until.max(timestamp + step);
+    }
+    if covered_until < end {
+        missing.push((covered_until, end));
+    }
+    missing
+}
+
+fn format_timestamp(timestamp: u64) -> String {
+    chrono::DateTime::from_timestamp(i64::try_from(timestamp).unwrap_or_default(), 0).map_or_else(
+        || timestamp.to_string(),
+        |t| t.format("%Y-%m-%d %H:%M").to_string(),
+    )
+}
+
 /// Prints backtest report in CSV format.
 fn print_csv_backtest(report: &BacktestReport) {
     println!("metric,value");
@@ -330,3 +477,24 @@ fn print_csv_backtest(report: &BacktestReport) {
         println!("sharpe_ratio,{}", sharpe);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_ranges() {
+        let hour = 3600;
+        // Complete coverage of three hours
+        assert!(missing_ranges(&[0, hour, 2 * hour], 0, 3 * hour, hour).is_empty());
+
+        // Missing head, a gap in the middle and the tail
+        let missing = missing_ranges(&[hour, 2 * hour, 5 * hour], 0, 8 * hour, hour);
+        assert_eq!(
+            missing,
+            vec![(0, hour), (3 * hour, 5 * hour), (6 * hour, 8 * hour)]
+        );
+
+        assert_eq!(missing_ranges(&[], 0, hour, hour), vec![(0, hour)]);
+    }
+}
@@ -3,10 +3,13 @@
 //! This module contains the implementation of all CLI commands,
 //! separated into logical modules for maintainability.
 
+pub mod alerts;
 pub mod analyze;
 pub mod backtest;
 pub mod data;
 pub mod optimize;
+pub mod pools;
+pub mod sweep;
 
 pub use analyze::run_analyze;
 pub use backtest::run_backtest;
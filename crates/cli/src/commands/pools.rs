@@ -0,0 +1,484 @@
+//! Pool onboarding and analysis command implementation.
+//!
+//! Imports a TOML watchlist of pools: each address is resolved on-chain,
+//! stored as a pool record, added to the watchlist the live services keep
+//! in sync, and gets a price history backfill scheduled. Saved pools can
+//! then be analyzed from their cached prices.
+
+use anyhow::{Context, Result, bail};
+use clmm_lp_data::prelude::*;
+use clmm_lp_domain::entities::token::Token;
+use clmm_lp_protocols::prelude::{RaydiumPoolReader, RpcProvider, WhirlpoolReader};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use serde::Deserialize;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Mints with well-known symbols, used when the watchlist omits them.
+const KNOWN_MINTS: [(&str, &str); 3] = [
+    ("So11111111111111111111111111111111111111112", "SOL"),
+    ("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", "USDC"),
+    ("Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB", "USDT"),
+];
+
+/// Protocol of a watchlist pool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WatchlistProtocol {
+    /// Orca Whirlpool.
+    #[default]
+    Orca,
+    /// Raydium CLMM.
+    Raydium,
+}
+
+impl WatchlistProtocol {
+    /// Protocol name as stored with pool records.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Orca => "orca",
+            Self::Raydium => "raydium",
+        }
+    }
+}
+
+/// A pool to onboard.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WatchlistEntry {
+    /// On-chain pool address.
+    pub address: String,
+    /// Pool protocol.
+    #[serde(default)]
+    pub protocol: WatchlistProtocol,
+    /// Token A symbol; derived from the mint if omitted.
+    pub symbol_a: Option<String>,
+    /// Token B symbol; derived from the mint if omitted.
+    pub symbol_b: Option<String>,
+    /// Fee tier in basis points; required for Raydium pools, whose fee is
+    /// not part of the pool account.
+    pub fee_bps: Option<u16>,
+    /// Days of price history to backfill, overriding the command default.
+    pub backfill_days: Option<u64>,
+}
+
+/// A watchlist file.
+///
+/// ```toml
+/// [[pools]]
+/// address = "HJPjoWUrhoZzkNfRpHuieeFk9WcZWjwy6PBjZ81ngndJ"
+/// symbol_a = "SOL"
+/// symbol_b = "USDC"
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct Watchlist {
+    /// Pools to onboard.
+    #[serde(default)]
+    pub pools: Vec<WatchlistEntry>,
+}
+
+impl Watchlist {
+    /// Parses a watchlist from TOML.
+    ///
+    /// # Errors
+    /// Returns an error if the TOML is invalid or lists an address twice.
+    pub fn parse(contents: &str) -> Result<Self> {
+        let watchlist: Self = toml::from_str(contents).context("Invalid watchlist")?;
+        let mut seen = std::collections::HashSet::new();
+        for entry in &watchlist.pools {
+            if !seen.insert(entry.address.as_str()) {
+                bail!("Pool {} is listed twice", entry.address);
+            }
+        }
+        Ok(watchlist)
+    }
+
+    /// Reads a watchlist file.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read or parsed.
+    pub async fn load(path: &Path) -> Result<Self> {
+        let contents = tokio::fs::read_to_string(path)
+            .await
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&contents).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+}
+
+/// Arguments for the import action.
+#[derive(Debug, Clone)]
+pub struct ImportArgs {
+    /// Days of price history to backfill per pool; 0 schedules none.
+    pub backfill_days: u64,
+    /// Backfill candle resolution in minutes.
+    pub resolution_minutes: u64,
+}
+
+/// On-chain configuration of a pool.
+struct ResolvedPool {
+    mint_a: String,
+    mint_b: String,
+    decimals_a: u8,
+    decimals_b: u8,
+    fee_bps: u16,
+    tick_spacing: u16,
+}
+
+/// Imports a watchlist into the database.
+///
+/// Pools that cannot be resolved are reported and skipped; the rest are
+/// still imported.
+pub async fn run_import(
+    db: &Database,
+    provider: Arc<RpcProvider>,
+    watchlist: &Watchlist,
+    args: &ImportArgs,
+) -> Result<()> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let mut imported = 0;
+
+    for entry in &watchlist.pools {
+        info!(address = %entry.address, protocol = entry.protocol.as_str(), "Resolving pool");
+        let resolved = match resolve_pool(provider.clone(), entry).await {
+            Ok(resolved) => resolved,
+            Err(e) => {
+                warn!(address = %entry.address, error = %e, "Failed to resolve pool");
+                println!("❌ {}: {:#}", entry.address, e);
+                continue;
+            }
+        };
+
+        let symbol_a = entry
+            .symbol_a
+            .clone()
+            .unwrap_or_else(|| symbol_for_mint(&resolved.mint_a));
+        let symbol_b = entry
+            .symbol_b
+            .clone()
+            .unwrap_or_else(|| symbol_for_mint(&resolved.mint_b));
+        let id = match db.pools().find_by_address(&entry.address).await? {
+            Some(existing) => existing.id,
+            None => Uuid::new_v4(),
+        };
+        let record = db
+            .pools()
+            .upsert(
+                id,
+                entry.protocol.as_str(),
+                &entry.address,
+                &resolved.mint_a,
+                &resolved.mint_b,
+                &symbol_a,
+                &symbol_b,
+                i16::from(resolved.decimals_a),
+                i16::from(resolved.decimals_b),
+                i32::from(resolved.fee_bps),
+                i32::from(resolved.tick_spacing),
+            )
+            .await?;
+        let newly_watched = db.pools().watch(record.id).await?;
+
+        let days = entry.backfill_days.unwrap_or(args.backfill_days);
+        let backfill = if days > 0 {
+            let start = now.saturating_sub(days * 24 * 3600);
+            db.backfill_jobs()
+                .enqueue(
+                    record.id,
+                    start as i64,
+                    now as i64,
+                    (args.resolution_minutes * 60) as i64,
+                )
+                .await?;
+            format!("backfill of {} days scheduled", days)
+        } else {
+            "no backfill".to_string()
+        };
+
+        println!(
+            "✅ {}/{} {} ({} bps){}, {}",
+            record.symbol_a,
+            record.symbol_b,
+            record.address,
+            record.fee_tier,
+            if newly_watched {
+                ""
+            } else {
+                " already watched"
+            },
+            backfill
+        );
+        imported += 1;
+    }
+
+    println!();
+    println!(
+        "Imported {} of {} pools; the API server subscribes watched pools on startup.",
+        imported,
+        watchlist.pools.len()
+    );
+    Ok(())
+}
+
+/// Runs pending backfill jobs, fetching price history from Birdeye.
+///
+/// Jobs that fail are marked failed with their error and left for review.
+pub async fn run_backfill(db: &Database, provider: &BirdeyeProvider, limit: i64) -> Result<()> {
+    let jobs = db.backfill_jobs().find_pending(limit).await?;
+    if jobs.is_empty() {
+        println!("No pending backfill jobs.");
+        return Ok(());
+    }
+
+    for job in jobs {
+        match run_backfill_job(db, provider, &job).await {
+            Ok(count) => {
+                db.backfill_jobs().mark_done(job.id).await?;
+                println!("✅ Job {}: stored {} candles", job.id, count);
+            }
+            Err(e) => {
+                db.backfill_jobs()
+                    .mark_failed(job.id, &format!("{:#}", e))
+                    .await?;
+                println!("❌ Job {}: {:#}", job.id, e);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Width of the longest utilization bar in characters.
+const UTILIZATION_BAR_WIDTH: usize = 40;
+
+/// Prints the time a saved pool's price spent in each bucket of its tick
+/// grid over the last `days` days of cached prices.
+pub async fn run_utilization(
+    db: &Database,
+    pool: &str,
+    days: u32,
+    max_buckets: usize,
+) -> Result<()> {
+    let record = match Uuid::parse_str(pool) {
+        Ok(id) => db.pools().find_by_id(id).await?,
+        Err(_) => db.pools().find_by_address(pool).await?,
+    }
+    .with_context(|| format!("No saved pool {}; import it with `pools import`", pool))?;
+
+    let end = i64::try_from(SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs())?;
+    let start = end - i64::from(days) * 86_400;
+    let points: Vec<_> = db
+        .prices()
+        .find_by_pool_and_range(record.id, start, end)
+        .await?
+        .iter()
+        .map(|price| {
+            let candle = price.to_candle();
+            (candle.timestamp, candle.close)
+        })
+        .collect();
+    if points.is_empty() {
+        bail!(
+            "No cached prices for pool {} in the last {} days; backfill them with `pools backfill`",
+            record.address,
+            days
+        );
+    }
+
+    let utilization = RangeUtilization::build(
+        &points,
+        record.tick_spacing,
+        max_buckets,
+        u8::try_from(record.decimals_a)?,
+        u8::try_from(record.decimals_b)?,
+    );
+    println!(
+        "🔥 Range utilization of {}/{} ({}) over {} days, {} ticks per bucket",
+        record.symbol_a, record.symbol_b, record.address, days, utilization.bucket_ticks
+    );
+
+    let max_share = utilization
+        .buckets
+        .iter()
+        .map(|b| b.share_pct)
+        .max()
+        .unwrap_or_default();
+    // Highest prices first, as on a price chart
+    for bucket in utilization.buckets.iter().rev() {
+        let filled = if max_share.is_zero() {
+            0
+        } else {
+            (bucket.share_pct / max_share * Decimal::from(UTILIZATION_BAR_WIDTH))
+                .to_usize()
+                .unwrap_or_default()
+        };
+        println!(
+            "{:>12.4} - {:<12.4} {:<width$} {:>5.1}%",
+            bucket.price_lower,
+            bucket.price_upper,
+            "█".repeat(filled),
+            bucket.share_pct,
+            width = UTILIZATION_BAR_WIDTH
+        );
+    }
+    Ok(())
+}
+
+/// Fetches and stores the candles of one backfill job.
+async fn run_backfill_job(
+    db: &Database,
+    provider: &BirdeyeProvider,
+    job: &BackfillJobRecord,
+) -> Result<usize> {
+    let pool = db
+        .pools()
+        .find_by_id(job.pool_id)
+        .await?
+        .context("Pool no longer exists")?;
+    let token_a = Token::new(
+        &pool.token_mint_a,
+        &pool.symbol_a,
+        u8::try_from(pool.decimals_a)?,
+        &pool.symbol_a,
+    );
+    let token_b = Token::new(
+        &pool.token_mint_b,
+        &pool.symbol_b,
+        u8::try_from(pool.decimals_b)?,
+        &pool.symbol_b,
+    );
+
+    let candles = provider
+        .get_price_history(
+            &token_a,
+            &token_b,
+            u64::try_from(job.start_timestamp)?,
+            u64::try_from(job.end_timestamp)?,
+            u64::try_from(job.resolution_secs)?,
+        )
+        .await?;
+
+    for candle in &candles {
+        db.prices()
+            .save(
+                Uuid::new_v4(),
+                Some(pool.id),
+                candle.start_timestamp as i64,
+                candle.open.value,
+                candle.high.value,
+                candle.low.value,
+                candle.close.value,
+                Some(candle.volume_token_a.to_decimal()),
+                None,
+            )
+            .await?;
+    }
+    Ok(candles.len())
+}
+
+/// Reads the configuration of a pool from chain.
+async fn resolve_pool(provider: Arc<RpcProvider>, entry: &WatchlistEntry) -> Result<ResolvedPool> {
+    let (mint_a, mint_b, fee_bps, tick_spacing) = match entry.protocol {
+        WatchlistProtocol::Orca => {
+            let pool = WhirlpoolReader::new(provider.clone())
+                .get_pool_state(&entry.address)
+                .await?;
+            (
+                pool.token_mint_a,
+                pool.token_mint_b,
+                entry
+                    .fee_bps
+                    .or_else(|| pool.fee_bps().round().to_u16())
+                    .context("Pool fee does not fit in basis points")?,
+                pool.tick_spacing,
+            )
+        }
+        WatchlistProtocol::Raydium => {
+            let pool = RaydiumPoolReader::new(provider.clone())
+                .get_pool_state(&entry.address)
+                .await?;
+            let fee_bps = entry
+                .fee_bps
+                .context("fee_bps is required for Raydium pools")?;
+            (
+                pool.token_mint_a,
+                pool.token_mint_b,
+                fee_bps,
+                pool.tick_spacing,
+            )
+        }
+    };
+
+    Ok(ResolvedPool {
+        decimals_a: provider.get_mint_decimals(&mint_a).await?,
+        decimals_b: provider.get_mint_decimals(&mint_b).await?,
+        mint_a: mint_a.to_string(),
+        mint_b: mint_b.to_string(),
+        fee_bps,
+        tick_spacing,
+    })
+}
+
+/// Returns the symbol of a well-known mint, or a shortened mint address.
+fn symbol_for_mint(mint: &str) -> String {
+    KNOWN_MINTS
+        .iter()
+        .find(|(known, _)| *known == mint)
+        .map_or_else(
+            || mint.chars().take(4).collect(),
+            |(_, symbol)| (*symbol).to_string(),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_watchlist() {
+        let watchlist = Watchlist::parse(
+            r#"
+            [[pools]]
+            address = "HJPjoWUrhoZzkNfRpHuieeFk9WcZWjwy6PBjZ81ngndJ"
+            symbol_a = "SOL"
+
+            [[pools]]
+            address = "2QdhepnKRTLjjSqPL1PtKNwqrUkoLee5Gqs8bvZhRdMv"
+            protocol = "raydium"
+            fee_bps = 5
+            backfill_days = 7
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(watchlist.pools.len(), 2);
+        assert_eq!(watchlist.pools[0].protocol, WatchlistProtocol::Orca);
+        assert_eq!(watchlist.pools[0].symbol_a.as_deref(), Some("SOL"));
+        assert_eq!(watchlist.pools[1].protocol, WatchlistProtocol::Raydium);
+        assert_eq!(watchlist.pools[1].fee_bps, Some(5));
+        assert_eq!(watchlist.pools[1].backfill_days, Some(7));
+
+        let duplicated = r#"
+            [[pools]]
+            address = "HJPjoWUrhoZzkNfRpHuieeFk9WcZWjwy6PBjZ81ngndJ"
+            [[pools]]
+            address = "HJPjoWUrhoZzkNfRpHuieeFk9WcZWjwy6PBjZ81ngndJ"
+        "#;
+        assert!(Watchlist::parse(duplicated).is_err());
+        assert!(Watchlist::parse("[[pools]]\nprotocol = \"orca\"").is_err());
+    }
+
+    #[test]
+    fn test_symbol_for_mint() {
+        assert_eq!(
+            symbol_for_mint("So11111111111111111111111111111111111111112"),
+            "SOL"
+        );
+        assert_eq!(
+            symbol_for_mint("JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN"),
+            "JUPy"
+        );
+    }
+}
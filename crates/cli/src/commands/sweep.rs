@@ -0,0 +1,166 @@
+//! Sweep command implementation.
+//!
+//! Backtests a grid of strategy parameters on one price history and ranks
+//! the results, optionally scanning at a coarser resolution first.
+
+use anyhow::{Result, bail};
+use clmm_lp_data::prelude::*;
+use clmm_lp_domain::prelude::*;
+use clmm_lp_simulation::prelude::*;
+use prettytable::{Table, row};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Arguments for the sweep command.
+#[derive(Debug, Clone)]
+pub struct SweepArgs {
+    /// Token A symbol.
+    pub symbol_a: String,
+    /// Token A mint address.
+    pub mint_a: String,
+    /// Days of history to backtest.
+    pub days: u64,
+    /// Candle resolution of the backtest in minutes.
+    pub resolution_minutes: u64,
+    /// Range widths swept (0.1 = 10%).
+    pub widths: Vec<Decimal>,
+    /// Rebalance intervals in hours swept for periodic strategies.
+    pub intervals_hours: Vec<u64>,
+    /// Price moves swept for threshold strategies (0.05 = 5%).
+    pub thresholds: Vec<Decimal>,
+    /// Initial capital in USD.
+    pub capital: Decimal,
+    /// Pool fee rate (0.003 = 0.3%).
+    pub fee_rate: Decimal,
+    /// Transaction cost per rebalance in USD.
+    pub tx_cost: Decimal,
+    /// Resolution of a coarse scan in minutes, enabling two-pass mode.
+    pub coarse_minutes: Option<u64>,
+    /// Candidates re-verified at full resolution in two-pass mode.
+    pub top_n: usize,
+    /// Candidates printed.
+    pub limit: usize,
+}
+
+impl SweepArgs {
+    /// Returns how the grid is backtested.
+    #[must_use]
+    pub fn mode(&self) -> SweepMode {
+        match self.coarse_minutes {
+            Some(minutes) => SweepMode::TwoPass {
+                coarse_step_seconds: minutes * 60,
+                top_n: self.top_n,
+            },
+            None => SweepMode::SinglePass,
+        }
+    }
+}
+
+/// Fetches the price history and runs the sweep.
+pub async fn run_sweep<P: MarketDataProvider + Sync>(provider: &P, args: &SweepArgs) -> Result<()> {
+    let token_a = Token::new(&args.mint_a, &args.symbol_a, 9, &args.symbol_a);
+    let token_b = Token::new(
+        "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+        "USDC",
+        6,
+        "USD Coin",
+    );
+    let step_seconds = args.resolution_minutes.max(1) * 60;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let start_time = now - args.days * 86_400;
+
+    println!(
+        "🔍 Fetching {}-minute history for {}/USDC ({} days)...",
+        args.resolution_minutes, args.symbol_a, args.days
+    );
+    let candles = provider
+        .get_price_history(&token_a, &token_b, start_time, now, step_seconds)
+        .await?;
+    if candles.is_empty() {
+        bail!("No data found for the specified period");
+    }
+
+    // Volume is credited in USD at each candle's close
+    let points: Vec<(u64, Price, Decimal)> = candles
+        .iter()
+        .map(|c| {
+            (
+                c.start_timestamp,
+                c.close,
+                c.volume_token_a.to_decimal() * c.close.value,
+            )
+        })
+        .collect();
+    let series = SweepSeries::new(step_seconds, &points);
+
+    let grid = SweepParams::grid(&args.widths, &args.intervals_hours, &args.thresholds);
+    if grid.is_empty() {
+        bail!("The parameter grid is empty; pass at least one width");
+    }
+    // Same 1% share of the pool as the backtest command
+    let position_liquidity = args.capital.to_u128().unwrap_or(0) * 10;
+    let config = SimulationConfig::new(
+        args.capital,
+        PriceRange::from_center_and_width(points[0].1, args.widths[0]),
+    )
+    .with_fee_rate(args.fee_rate)
+    .with_pool_liquidity(position_liquidity)
+    .with_rebalance_cost(args.tx_cost);
+
+    println!(
+        "🚀 Sweeping {} parameter sets over {} steps ({:?})...",
+        grid.len(),
+        series.len(),
+        args.mode()
+    );
+    let report = SweepEngine::new(config, grid)
+        .with_mode(args.mode())
+        .with_market_liquidity(position_liquidity * 100)
+        .run(&series);
+
+    print_sweep_report(&report, args.limit);
+    Ok(())
+}
+
+/// Prints the best candidates of a sweep.
+fn print_sweep_report(report: &SweepReport, limit: usize) {
+    println!(
+        "📊 {} coarse and {} full-resolution backtests, {} simulated steps",
+        report.coarse_runs, report.fine_runs, report.simulated_steps
+    );
+
+    let mut table = Table::new();
+    table.add_row(row![
+        "Strategy",
+        "Width",
+        "Trigger",
+        "Net PnL",
+        "Fees",
+        "Rebalances",
+        "Verified"
+    ]);
+    for candidate in report.candidates.iter().take(limit) {
+        let (strategy, trigger) = match &candidate.params {
+            SweepParams::Static { .. } => ("static", "-".to_string()),
+            SweepParams::Periodic { interval_hours, .. } => {
+                ("periodic", format!("{}h", interval_hours))
+            }
+            SweepParams::Threshold { threshold_pct, .. } => (
+                "threshold",
+                format!("{:.1}%", threshold_pct * Decimal::from(100)),
+            ),
+        };
+        let summary = &candidate.summary;
+        table.add_row(row![
+            strategy,
+            format!("{:.1}%", candidate.params.width_pct() * Decimal::from(100)),
+            trigger,
+            format!("${:.2}", summary.net_pnl),
+            format!("${:.2}", summary.total_fees),
+            summary.rebalance_count,
+            if candidate.verified { "yes" } else { "coarse" }
+        ]);
+    }
+    table.printstd();
+}
@@ -7,14 +7,23 @@ use anyhow::Result;
 use clap::{Parser, Subcommand, ValueEnum};
 use clmm_lp_data::prelude::*;
 use clmm_lp_domain::prelude::*;
+use clmm_lp_execution::strategy::DecisionOverrides;
 use clmm_lp_optimization::prelude::*;
+use clmm_lp_protocols::prelude::{RpcConfig, RpcProvider};
 use clmm_lp_simulation::prelude::*;
+use commands::alerts::{
+    AddRuleArgs, ConditionKind, Severity, build_rule, run_add, run_list, run_remove,
+};
+use commands::backtest::load_cached_candles;
+use commands::pools::{ImportArgs, Watchlist, run_backfill, run_import, run_utilization};
+use commands::sweep::{SweepArgs, run_sweep};
 use dotenv::dotenv;
-use prettytable::{Table, row};
-use primitive_types::U256;
+use prettytable::{Cell, Row, Table, row};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
 use std::env;
+use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::info;
 use uuid::Uuid;
@@ -38,6 +47,51 @@ enum OptimizationObjectiveArg {
     Sharpe,
 }
 
+impl OptimizationObjectiveArg {
+    /// Objective type as stored with optimization results.
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Pnl => "pnl",
+            Self::Fees => "fees",
+            Self::Sharpe => "sharpe",
+        }
+    }
+}
+
+/// Benchmark Sharpe ratios are measured against.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum BenchmarkArg {
+    /// Holding the tokens the position was opened with
+    Hodl,
+    /// Holding half the capital in each token
+    FiftyFifty,
+    /// Cash, so only the risk-free rate is subtracted
+    Zero,
+}
+
+impl BenchmarkArg {
+    /// Sharpe configuration for this benchmark and a risk-free rate.
+    fn sharpe_config(self, risk_free_rate: f64) -> Result<SharpeConfig> {
+        let rate = Decimal::from_f64(risk_free_rate)
+            .ok_or_else(|| anyhow::anyhow!("Invalid risk-free rate: {}", risk_free_rate))?;
+        let benchmark = match self {
+            Self::Hodl => Benchmark::Hodl,
+            Self::FiftyFifty => Benchmark::FiftyFifty,
+            Self::Zero => Benchmark::Zero,
+        };
+        Ok(SharpeConfig::new(rate, benchmark))
+    }
+}
+
+/// Venue to fetch perp funding rates from.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum FundingVenueArg {
+    /// Drift Protocol (markets like SOL-PERP)
+    Drift,
+    /// Hyperliquid (markets like SOL)
+    Hyperliquid,
+}
+
 /// Rebalancing strategy for backtest.
 #[derive(Debug, Clone, Copy, ValueEnum, Default)]
 enum StrategyArg {
@@ -48,6 +102,8 @@ enum StrategyArg {
     Periodic,
     /// Rebalance when price moves beyond threshold
     Threshold,
+    /// Ladder of sub-ranges, each rebalanced independently
+    Grid,
 }
 
 #[derive(Subcommand)]
@@ -80,13 +136,35 @@ enum Commands {
         #[arg(short, long, default_value_t = 30)]
         days: u64,
 
+        /// Saved pool (UUID or address) to backtest on its cached prices
+        /// instead of fetching from Birdeye; the run is saved with the pool
+        #[arg(long, requires_all = ["from", "to"], conflicts_with = "days")]
+        pool: Option<String>,
+
+        /// First day of cached prices to backtest (YYYY-MM-DD)
+        #[arg(long, requires = "pool")]
+        from: Option<chrono::NaiveDate>,
+
+        /// Last day of cached prices to backtest, inclusive (YYYY-MM-DD)
+        #[arg(long, requires = "pool")]
+        to: Option<chrono::NaiveDate>,
+
         /// Lower price bound
-        #[arg(long)]
-        lower: f64,
+        #[arg(long, required_unless_present_any = ["range", "optimization"], requires = "upper")]
+        lower: Option<f64>,
 
         /// Upper price bound
-        #[arg(long)]
-        upper: f64,
+        #[arg(long, required_unless_present_any = ["range", "optimization"], requires = "lower")]
+        upper: Option<f64>,
+
+        /// Range around the entry price: "90:110", "5%", "2sigma" (daily σ) or "500ticks"
+        #[arg(long, conflicts_with_all = ["lower", "upper"])]
+        range: Option<RangeSpec>,
+
+        /// Stored optimization (UUID) whose recommended range is backtested;
+        /// the saved run is linked to it
+        #[arg(long, requires = "pool", conflicts_with_all = ["lower", "upper", "range"])]
+        optimization: Option<Uuid>,
 
         /// Initial capital in USD
         #[arg(long, default_value_t = 1000.0)]
@@ -104,9 +182,49 @@ enum Commands {
         #[arg(long, default_value_t = 0.05)]
         threshold_pct: f64,
 
+        /// Number of sub-ranges splitting the range (for grid strategy)
+        #[arg(long, default_value_t = 5)]
+        grid_levels: usize,
+
         /// Transaction cost per rebalance in USD
         #[arg(long, default_value_t = 1.0)]
         tx_cost: f64,
+
+        /// Per-step volatility of pool liquidity shocks (0 keeps liquidity constant)
+        #[arg(long, default_value_t = 0.0)]
+        liquidity_shock: f64,
+
+        /// Collect fees every N hours instead of crediting them as earned
+        #[arg(long)]
+        collect_every: Option<u64>,
+
+        /// Transaction cost per fee collection in USD (defaults to --tx-cost)
+        #[arg(long, requires = "collect_every")]
+        collect_cost: Option<f64>,
+
+        /// Compound collected fees back into liquidity
+        #[arg(long, requires = "collect_every")]
+        compound: bool,
+
+        /// Deposit --deposit-amount into the position every N hours
+        #[arg(long, requires = "deposit_amount")]
+        deposit_every: Option<u64>,
+
+        /// Amount in USD added on each scheduled deposit
+        #[arg(long, requires = "deposit_every")]
+        deposit_amount: Option<f64>,
+
+        /// Withdraw collected fees every N hours
+        #[arg(long)]
+        withdraw_fees_every: Option<u64>,
+
+        /// Annual risk-free rate for the Sharpe ratio
+        #[arg(long, default_value_t = 0.05)]
+        risk_free_rate: f64,
+
+        /// Benchmark the Sharpe ratio is measured against
+        #[arg(long, value_enum, default_value_t = BenchmarkArg::Zero)]
+        benchmark: BenchmarkArg,
     },
     /// Optimize price range for LP position
     Optimize {
@@ -130,15 +248,128 @@ enum Commands {
         #[arg(long, value_enum, default_value_t = OptimizationObjectiveArg::Pnl)]
         objective: OptimizationObjectiveArg,
 
+        /// TOML file of custom objectives ([[objectives]] with name and formula)
+        #[arg(long, requires = "custom_objective")]
+        objectives_file: Option<PathBuf>,
+
+        /// Optimize for this objective from --objectives-file instead of --objective
+        #[arg(long, requires = "objectives_file")]
+        custom_objective: Option<String>,
+
         /// Number of Monte Carlo iterations
         #[arg(long, default_value_t = 100)]
         iterations: usize,
+
+        /// Pool fee rate (e.g., 0.003 = 0.3%)
+        #[arg(long, default_value_t = 0.003)]
+        fee_rate: f64,
+
+        /// Pool tick spacing (defaults to the standard spacing for the fee tier)
+        #[arg(long)]
+        tick_spacing: Option<u16>,
+
+        /// Worker threads for simulations (defaults to all cores)
+        #[arg(long)]
+        threads: Option<usize>,
+
+        /// Size the deployment with this multiple of the Kelly fraction (0.5 = half Kelly)
+        #[arg(long)]
+        kelly: Option<f64>,
+
+        /// Maximum fraction of capital to deploy when Kelly sizing
+        #[arg(long, default_value_t = 1.0, requires = "kelly")]
+        max_deployment: f64,
+
+        /// Transaction cost per rebalance in USD, weighed in the explanation
+        #[arg(long, default_value_t = 1.0)]
+        tx_cost: f64,
+
+        /// Lower bound of the previous recommendation to search around and compare against
+        #[arg(long, requires = "previous_upper")]
+        previous_lower: Option<f64>,
+
+        /// Upper bound of the previous recommendation
+        #[arg(long, requires = "previous_lower")]
+        previous_upper: Option<f64>,
+
+        /// Annual risk-free rate for the Sharpe ratio
+        #[arg(long, default_value_t = 0.05)]
+        risk_free_rate: f64,
+
+        /// Benchmark the Sharpe ratio is measured against
+        #[arg(long, value_enum, default_value_t = BenchmarkArg::Zero)]
+        benchmark: BenchmarkArg,
+    },
+    /// Backtest a grid of strategy parameters and rank the results
+    Sweep {
+        /// Token A Symbol (e.g., SOL)
+        #[arg(short, long, default_value = "SOL")]
+        symbol_a: String,
+
+        /// Token A Mint Address
+        #[arg(long, default_value = "So11111111111111111111111111111111111111112")]
+        mint_a: String,
+
+        /// Days of history to backtest
+        #[arg(short, long, default_value_t = 7)]
+        days: u64,
+
+        /// Candle resolution of the backtest in minutes
+        #[arg(short, long, default_value_t = 1)]
+        resolution_minutes: u64,
+
+        /// Range widths to sweep, comma-separated (0.1 = 10%)
+        #[arg(long, value_delimiter = ',', default_value = "0.05,0.1,0.2")]
+        widths: Vec<f64>,
+
+        /// Rebalance intervals in hours to sweep for the periodic strategy
+        #[arg(long, value_delimiter = ',', default_value = "6,24")]
+        intervals: Vec<u64>,
+
+        /// Price moves to sweep for the threshold strategy (0.05 = 5%)
+        #[arg(long, value_delimiter = ',', default_value = "0.02,0.05")]
+        thresholds: Vec<f64>,
+
+        /// Initial capital in USD
+        #[arg(long, default_value_t = 1000.0)]
+        capital: f64,
+
+        /// Pool fee rate (e.g., 0.003 = 0.3%)
+        #[arg(long, default_value_t = 0.003)]
+        fee_rate: f64,
+
+        /// Transaction cost per rebalance in USD
+        #[arg(long, default_value_t = 1.0)]
+        tx_cost: f64,
+
+        /// Scan the grid at this resolution in minutes first, then re-verify
+        /// the best candidates at --resolution-minutes (e.g. 60)
+        #[arg(long)]
+        coarse_minutes: Option<u64>,
+
+        /// Candidates re-verified at full resolution after a coarse scan
+        #[arg(long, default_value_t = 5, requires = "coarse_minutes")]
+        top_n: usize,
+
+        /// Number of candidates to print
+        #[arg(short, long, default_value_t = 10)]
+        limit: usize,
     },
     /// Database management commands
     Db {
         #[command(subcommand)]
         action: DbAction,
     },
+    /// Pool onboarding commands
+    Pools {
+        #[command(subcommand)]
+        action: PoolsAction,
+    },
+    /// Alert rule commands
+    Alerts {
+        #[command(subcommand)]
+        action: AlertsAction,
+    },
     /// Analyze a token pair's historical data
     Analyze {
         /// Token A Symbol (e.g., SOL)
@@ -174,6 +405,170 @@ enum DbAction {
         #[arg(short, long, default_value_t = 10)]
         limit: i64,
     },
+    /// Assign a strategy to a position
+    AssignStrategy {
+        /// Position address
+        #[arg(short, long)]
+        position: String,
+
+        /// Strategy ID
+        #[arg(short, long)]
+        strategy: String,
+
+        /// Parameter override as key=value (repeatable), e.g. range_spec=2sigma
+        #[arg(long = "set", value_name = "KEY=VALUE")]
+        overrides: Vec<String>,
+    },
+    /// Remove a position's strategy assignment
+    ClearStrategy {
+        /// Position address
+        #[arg(short, long)]
+        position: String,
+    },
+    /// List position strategy assignments
+    ListStrategies,
+    /// Replace the tags on a position
+    TagPosition {
+        /// Position address
+        #[arg(short, long)]
+        position: String,
+
+        /// Tags, comma-separated (empty clears them)
+        #[arg(short, long, value_delimiter = ',')]
+        tags: Vec<String>,
+    },
+    /// List tagged positions
+    ListTags {
+        /// Only show positions with this tag
+        #[arg(short, long)]
+        tag: Option<String>,
+    },
+    /// Compare saved simulations against the first one
+    CompareSimulations {
+        /// Simulation IDs, comma-separated; the first is the baseline
+        #[arg(short, long, value_delimiter = ',', required = true)]
+        ids: Vec<Uuid>,
+
+        /// Number of equity curve points to print
+        #[arg(short, long, default_value_t = 11)]
+        points: usize,
+    },
+    /// Rank stored optimizations for a pool
+    Leaderboard {
+        /// Pool address
+        #[arg(short, long)]
+        pool: String,
+
+        /// Objective to rank by
+        #[arg(short, long, value_enum, default_value_t = OptimizationObjectiveArg::Pnl)]
+        objective: OptimizationObjectiveArg,
+
+        /// Maximum number of ranked optimizations
+        #[arg(short, long, default_value_t = 10)]
+        limit: usize,
+    },
+    /// Fetch perp funding rates and store them
+    SyncFunding {
+        /// Venue to fetch from
+        #[arg(short, long, value_enum, default_value_t = FundingVenueArg::Hyperliquid)]
+        venue: FundingVenueArg,
+
+        /// Market name, e.g. SOL on Hyperliquid or SOL-PERP on Drift
+        #[arg(short, long, default_value = "SOL")]
+        market: String,
+
+        /// Days of history to fetch
+        #[arg(short, long, default_value_t = 30)]
+        days: u64,
+    },
+}
+
+/// Alert rule actions.
+#[derive(Subcommand)]
+enum AlertsAction {
+    /// Add an alert rule, replacing any rule with the same name
+    Add {
+        /// Unique rule name
+        name: String,
+
+        /// Condition that triggers the rule
+        #[arg(short, long, value_enum)]
+        condition: ConditionKind,
+
+        /// Threshold of the condition (IL as a fraction, PnL in percent,
+        /// fees in USD, hours, or health score)
+        #[arg(short, long)]
+        threshold: Option<f64>,
+
+        /// Severity of the alerts raised
+        #[arg(short, long, value_enum, default_value = "warning")]
+        severity: Severity,
+
+        /// Message template, e.g. "IL at {il_pct}"
+        #[arg(short, long)]
+        message: Option<String>,
+
+        /// Notifier channel to send to (console, file, webhook, websocket);
+        /// repeat for several, omit for all
+        #[arg(long = "channel")]
+        channels: Vec<String>,
+
+        /// Only apply to positions with this tag; repeat for several
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+
+        /// Cooldown between alerts in seconds
+        #[arg(long, default_value_t = 300)]
+        cooldown: u64,
+
+        /// Store the rule disabled
+        #[arg(long)]
+        disabled: bool,
+    },
+    /// List stored alert rules
+    List,
+    /// Remove an alert rule
+    Remove {
+        /// Rule name
+        name: String,
+    },
+}
+
+/// Pool onboarding actions.
+#[derive(Subcommand)]
+enum PoolsAction {
+    /// Import a TOML watchlist: resolve, store and watch each pool
+    Import {
+        /// Watchlist file with a [[pools]] entry per pool
+        file: PathBuf,
+
+        /// Days of price history to backfill per pool (0 schedules none)
+        #[arg(short, long, default_value_t = 30)]
+        backfill_days: u64,
+
+        /// Backfill candle resolution in minutes
+        #[arg(short, long, default_value_t = 60)]
+        resolution_minutes: u64,
+    },
+    /// Run pending price history backfills
+    Backfill {
+        /// Maximum number of jobs to run
+        #[arg(short, long, default_value_t = 10)]
+        limit: i64,
+    },
+    /// Show how long the price spent in each bucket of a pool's tick grid
+    Utilization {
+        /// Saved pool (UUID or address)
+        pool: String,
+
+        /// Days of cached prices to read
+        #[arg(short, long, default_value_t = 30)]
+        days: u32,
+
+        /// Maximum number of buckets; buckets widen by whole tick spacings
+        #[arg(short, long, default_value_t = 40)]
+        buckets: usize,
+    },
 }
 
 #[tokio::main]
@@ -242,40 +637,75 @@ async fn main() -> Result<()> {
             symbol_a,
             mint_a,
             days,
+            pool,
+            from,
+            to,
             lower,
             upper,
+            range,
+            optimization,
             capital,
             strategy,
             rebalance_interval,
             threshold_pct,
+            grid_levels,
             tx_cost,
+            liquidity_shock,
+            collect_every,
+            collect_cost,
+            compound,
+            deposit_every,
+            deposit_amount,
+            withdraw_fees_every,
+            risk_free_rate,
+            benchmark,
         } => {
-            let api_key = env::var("BIRDEYE_API_KEY")
-                .expect("BIRDEYE_API_KEY must be set in .env or environment");
-
-            println!("📡 Initializing Backtest Engine...");
-            let provider = BirdeyeProvider::new(api_key);
-
-            // Define Tokens
-            let token_a = Token::new(mint_a, symbol_a, 9, symbol_a);
-            let token_b = Token::new(
-                "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
-                "USDC",
-                6,
-                "USD Coin",
-            );
-
-            let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
-            let start_time = now - (days * 24 * 3600);
-
-            println!(
-                "🔍 Fetching historical data for {}/USDC ({} days)...",
-                symbol_a, days
-            );
+            let sharpe = benchmark.sharpe_config(*risk_free_rate)?;
+
+            // A saved pool is backtested on its cached prices only, and the
+            // run is recorded against it
+            let (symbol_a, days, candles, saved_pool) =
+                if let (Some(pool), Some(from), Some(to)) = (pool, from, to) {
+                    let database_url = env::var("DATABASE_URL")
+                        .unwrap_or_else(|_| "postgres://localhost/clmm_lp".to_string());
+                    let db = Database::connect(&database_url).await?;
 
-            let candles = provider
-                .get_price_history(&token_a, &token_b, start_time, now, 3600) // 1h resolution
-                .await?;
+                    println!(
+                        "🗄️  Loading cached prices for pool {} ({} to {})...",
+                        pool, from, to
+                    );
+                    let (record, candles) = load_cached_candles(&db, pool, *from, *to).await?;
+                    let days = u64::try_from((*to - *from).num_days() + 1)?;
+                    (record.symbol_a.clone(), days, candles, Some((db, record)))
+                } else {
+                    let api_key = env::var("BIRDEYE_API_KEY")
+                        .expect("BIRDEYE_API_KEY must be set in .env or environment");
+
+                    println!("📡 Initializing Backtest Engine...");
+                    let provider = BirdeyeProvider::new(api_key);
+
+                    // Define Tokens
+                    let token_a = Token::new(mint_a, symbol_a, 9, symbol_a);
+                    let token_b = Token::new(
+                        "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+                        "USDC",
+                        6,
+                        "USD Coin",
+                    );
+
+                    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+                    let start_time = now - (days * 24 * 3600);
+
+                    println!(
+                        "🔍 Fetching historical data for {}/USDC ({} days)...",
+                        symbol_a, days
+                    );
+
+                    let candles = provider
+                        .get_price_history(&token_a, &token_b, start_time, now, 3600) // 1h resolution
+                        .await?;
+                    (symbol_a.clone(), *days, candles, None)
+                };
 
             if candles.is_empty() {
                 println!("❌ No data found for the specified period.");
@@ -287,24 +717,117 @@ async fn main() -> Result<()> {
             let entry_price = prices.first().cloned().unwrap_or(Price::new(Decimal::ONE));
             let final_price = prices.last().cloned().unwrap_or(entry_price);
 
+            // A linked optimization supplies its recommended range
+            let source_optimization = match (optimization, &saved_pool) {
+                (Some(id), Some((db, record))) => {
+                    let source = db
+                        .simulations()
+                        .find_optimization_by_id(*id)
+                        .await?
+                        .ok_or_else(|| anyhow::anyhow!("Optimization not found: {}", id))?;
+                    if source.pool_id.is_some_and(|pool_id| pool_id != record.id) {
+                        anyhow::bail!(
+                            "Optimization {} was run for a different pool than {}",
+                            id,
+                            record.address
+                        );
+                    }
+                    println!(
+                        "🎯 Backtesting recommendation of optimization {}: {:.4} - {:.4}",
+                        id, source.recommended_lower, source.recommended_upper
+                    );
+                    Some(source)
+                }
+                _ => None,
+            };
+
+            // Resolve the initial range against the entry price
+            let range_spec = match (&source_optimization, range, lower, upper) {
+                (Some(source), _, _, _) => RangeSpec::Absolute {
+                    lower: source.recommended_lower,
+                    upper: source.recommended_upper,
+                },
+                (None, Some(spec), _, _) => spec.clone(),
+                (None, None, Some(lower), Some(upper)) => RangeSpec::Absolute {
+                    lower: Decimal::from_f64(*lower).unwrap(),
+                    upper: Decimal::from_f64(*upper).unwrap(),
+                },
+                _ => anyhow::bail!("Either --range or both --lower and --upper are required"),
+            };
+            let price_values: Vec<f64> = prices
+                .iter()
+                .map(|p| p.value.to_f64().unwrap_or(0.0))
+                .collect();
+            let volatility_daily = calculate_volatility(&price_values) / (365.0_f64).sqrt();
+            let initial_range = range_spec
+                .to_price_range(entry_price.value, Decimal::from_f64(volatility_daily))
+                .map_err(|e| anyhow::anyhow!("Invalid range {}: {}", range_spec, e))?;
+            let lower = initial_range.lower_price.value.to_f64().unwrap_or_default();
+            let upper = initial_range.upper_price.value.to_f64().unwrap_or_default();
+
             // Setup position tracker
-            let initial_range = PriceRange::new(
-                Price::new(Decimal::from_f64(*lower).unwrap()),
-                Price::new(Decimal::from_f64(*upper).unwrap()),
-            );
             let capital_dec = Decimal::from_f64(*capital).unwrap();
             let tx_cost_dec = Decimal::from_f64(*tx_cost).unwrap();
 
+            // Snapshots carry the candles' own timestamps, gaps included
+            let clock = SimulationClock::from_timestamps(
+                candles
+                    .iter()
+                    .map(|c| c.start_timestamp)
+                    .collect::<Vec<_>>(),
+            );
             let mut tracker =
-                PositionTracker::new(capital_dec, entry_price, initial_range, tx_cost_dec);
+                PositionTracker::new(capital_dec, entry_price, initial_range, tx_cost_dec)
+                    .with_clock(clock.clone());
+            // Hourly steps, so the collection interval is in steps too
+            let fee_collection = collect_every.map(|interval| {
+                let cost = collect_cost
+                    .and_then(Decimal::from_f64)
+                    .unwrap_or(tx_cost_dec);
+                let policy = FeeCollection::every(interval, cost);
+                if *compound {
+                    policy.with_compounding()
+                } else {
+                    policy
+                }
+            });
+            if let Some(policy) = &fee_collection {
+                tracker = tracker.with_fee_collection(policy.clone());
+            }
+            let mut cash_flows = CashFlowSchedule::new(3600);
+            if let (Some(interval), Some(amount)) = (deposit_every, deposit_amount) {
+                let amount = Decimal::from_f64(*amount)
+                    .ok_or_else(|| anyhow::anyhow!("Invalid deposit amount: {}", amount))?;
+                cash_flows = cash_flows.deposit_every(*interval, amount);
+            }
+            if let Some(interval) = withdraw_fees_every {
+                cash_flows = cash_flows.withdraw_fees_every(*interval);
+            }
+            let cash_flows = (!cash_flows.is_empty()).then_some(cash_flows);
+            if let Some(schedule) = &cash_flows {
+                tracker = tracker.with_cash_flows(schedule.clone());
+            }
+            tracker = tracker.with_sharpe(sharpe, 3600);
 
             // Setup volume and liquidity models
-            let mut volume_model = ConstantVolume::from_amount(
-                Amount::new(U256::from(1_000_000_000_000u64), 6), // 1M USDC vol per step
-            );
+            // 1M USDC vol per step on average, shaped by the observed hourly seasonality
+            let mut volume_model =
+                fit_volume_seasonality(&candles, 1).with_mean_step_volume(Decimal::from(1_000_000));
             let liquidity_amount = (*capital as u128) * 10;
             let global_liquidity = liquidity_amount * 100; // 1% share
-            let fee_rate = Decimal::from_f64(0.003).unwrap();
+            // Pool liquidity drifts around its starting level, moving our fee share
+            let liquidity_model = DynamicLiquidityModel::mean_reverting(
+                global_liquidity,
+                global_liquidity,
+                0.05,
+                *liquidity_shock,
+                prices.len(),
+            );
+            // A saved pool's own fee tier, in basis points, replaces the default
+            let fee_rate = saved_pool.as_ref().map_or_else(
+                || Decimal::from_f64(0.003).unwrap(),
+                |(_, record)| Decimal::from(record.fee_tier) / Decimal::from(10_000),
+            );
 
             println!(
                 "🚀 Running backtest with {:?} strategy over {} steps...",
@@ -314,17 +837,44 @@ async fn main() -> Result<()> {
 
             // Run simulation with strategy
             let range_width_pct =
-                Decimal::from_f64((*upper - *lower) / ((*upper + *lower) / 2.0)).unwrap();
+                Decimal::from_f64((upper - lower) / ((upper + lower) / 2.0)).unwrap();
 
-            for price in &prices {
+            // The grid splits the requested range into equal sub-ranges
+            let grid_strategy = GridStrategy::new(
+                *grid_levels,
+                range_width_pct / Decimal::from((*grid_levels).max(1)),
+            );
+            let mut grid_tracker = matches!(strategy, StrategyArg::Grid).then(|| {
+                let center = initial_range.midpoint();
+                let grid = GridTracker::new(
+                    capital_dec,
+                    entry_price,
+                    center,
+                    &grid_strategy,
+                    tx_cost_dec,
+                );
+                let grid = match &fee_collection {
+                    Some(policy) => grid.with_fee_collection(policy.clone()),
+                    None => grid,
+                };
+                let grid = match &cash_flows {
+                    Some(schedule) => grid.with_cash_flows(schedule.clone()),
+                    None => grid,
+                };
+                grid.with_sharpe(sharpe, 3600).with_clock(clock.clone())
+            });
+
+            for (step, price) in prices.iter().enumerate() {
                 // Calculate fees for this step
-                let in_range = price.value >= tracker.current_range.lower_price.value
-                    && price.value <= tracker.current_range.upper_price.value;
+                let in_range = match &grid_tracker {
+                    Some(grid) => grid.is_in_range(*price),
+                    None => tracker.current_range.contains(*price),
+                };
 
                 let step_fees = if in_range {
-                    let vol = volume_model.next_volume().to_decimal();
-                    let fee_share =
-                        Decimal::from(liquidity_amount) / Decimal::from(global_liquidity);
+                    let vol = volume_model.get_volume(step);
+                    let fee_share = Decimal::from(liquidity_amount)
+                        / Decimal::from(liquidity_model.get_liquidity(step).max(1));
                     vol * fee_share * fee_rate
                 } else {
                     Decimal::ZERO
@@ -347,24 +897,85 @@ async fn main() -> Result<()> {
                         );
                         tracker.record_step(*price, step_fees, Some(&strat));
                     }
+                    StrategyArg::Grid => {
+                        if let Some(grid) = grid_tracker.as_mut() {
+                            grid.record_step(*price, step_fees, &grid_strategy);
+                        }
+                    }
                 }
             }
 
             // Get summary
-            let summary = tracker.summary();
+            let summary = grid_tracker
+                .as_ref()
+                .map_or_else(|| tracker.summary(), GridTracker::summary);
 
             // Print rich report
             print_backtest_report(
-                symbol_a,
-                *days,
+                &symbol_a,
+                days,
                 *capital,
                 entry_price.value,
                 final_price.value,
-                *lower,
-                *upper,
+                lower,
+                upper,
                 &summary,
                 *strategy,
             );
+
+            if let Some((db, record)) = saved_pool {
+                let simulation_id = Uuid::new_v4();
+                let strategy_type = strategy
+                    .to_possible_value()
+                    .map_or_else(String::new, |v| v.get_name().to_string());
+                db.simulations()
+                    .save_simulation(
+                        simulation_id,
+                        Some(record.id),
+                        &strategy_type,
+                        serde_json::json!({
+                            "rebalance_interval_hours": rebalance_interval,
+                            "threshold_pct": threshold_pct,
+                            "grid_levels": grid_levels,
+                            "range": range_spec.to_string(),
+                        }),
+                        candles.first().map_or(0, |c| c.start_timestamp as i64),
+                        candles.last().map_or(0, |c| c.start_timestamp as i64),
+                        capital_dec,
+                        entry_price.value,
+                        initial_range.lower_price.value,
+                        initial_range.upper_price.value,
+                        fee_rate,
+                        tx_cost_dec,
+                        source_optimization.as_ref().map(|source| source.id),
+                    )
+                    .await?;
+                db.simulations()
+                    .save_result(
+                        Uuid::new_v4(),
+                        simulation_id,
+                        summary.final_value,
+                        summary.final_pnl,
+                        summary.total_fees,
+                        // IL is measured against holding
+                        summary.final_il_pct * summary.hodl_value,
+                        summary.final_il_pct,
+                        summary.time_in_range_pct,
+                        summary.max_drawdown,
+                        i32::try_from(summary.rebalance_count)?,
+                        summary.total_rebalance_cost,
+                        summary.hodl_value,
+                        summary.vs_hodl,
+                        summary.sharpe_ratio,
+                        summary.sharpe_config,
+                        final_price.value,
+                    )
+                    .await?;
+                println!(
+                    "💾 Saved simulation {} for pool {}",
+                    simulation_id, record.address
+                );
+            }
         }
         Commands::Optimize {
             symbol_a,
@@ -372,8 +983,47 @@ async fn main() -> Result<()> {
             days,
             capital,
             objective,
+            objectives_file,
+            custom_objective,
             iterations,
+            fee_rate,
+            tick_spacing,
+            threads,
+            kelly,
+            max_deployment,
+            tx_cost,
+            previous_lower,
+            previous_upper,
+            risk_free_rate,
+            benchmark,
         } => {
+            let sharpe = benchmark.sharpe_config(*risk_free_rate)?;
+            // Compile custom objectives up front so a bad formula fails fast
+            let (objective_label, objective_fn): (String, SharedObjective) =
+                match (objectives_file, custom_objective) {
+                    (Some(path), Some(name)) => {
+                        let registry = load_objective_registry(path)?;
+                        let selected = registry.get(name).ok_or_else(|| {
+                            anyhow::anyhow!(
+                                "Unknown objective '{}'; available: {}",
+                                name,
+                                registry.names().collect::<Vec<_>>().join(", ")
+                            )
+                        })?;
+                        (name.clone(), selected)
+                    }
+                    _ => (
+                        format!("{:?}", objective),
+                        match objective {
+                            OptimizationObjectiveArg::Pnl => Arc::new(MaximizeNetPnL),
+                            OptimizationObjectiveArg::Fees => Arc::new(MaximizeFees),
+                            OptimizationObjectiveArg::Sharpe => {
+                                Arc::new(MaximizeSharpeRatio::new(sharpe.risk_free_rate))
+                            }
+                        },
+                    ),
+                };
+
             let api_key = env::var("BIRDEYE_API_KEY")
                 .expect("BIRDEYE_API_KEY must be set in .env or environment");
 
@@ -419,72 +1069,176 @@ async fn main() -> Result<()> {
             println!("📊 Market Analysis:");
             println!("   Current Price: ${:.4}", current_price);
             println!("   Volatility (annualized): {:.1}%", volatility * 100.0);
+
+            // Setup optimizer constrained to the pool's initializable ticks
+            let fee_rate = Decimal::from_f64(*fee_rate).unwrap_or(Decimal::new(3, 3));
+            let pool_constraints = match tick_spacing {
+                Some(spacing) => PoolConstraints::new(*spacing),
+                None => PoolConstraints::for_fee_tier(fee_rate),
+            }
+            .with_token_decimals(token_a.decimals, token_b.decimals);
+            println!(
+                "   Fee Tier: {}% (tick spacing {})",
+                (fee_rate * Decimal::ONE_HUNDRED).normalize(),
+                pool_constraints.tick_spacing
+            );
             println!();
+            let mut optimizer = RangeOptimizer::new(*iterations, 30, 1.0 / 365.0)
+                .with_pool_constraints(pool_constraints)
+                .with_tx_cost(Decimal::from_f64(*tx_cost).unwrap_or(Decimal::ONE))
+                .with_sharpe(sharpe);
+            if let Some(threads) = threads {
+                optimizer = optimizer.with_threads(*threads);
+            }
+            if let (Some(lower), Some(upper)) = (previous_lower, previous_upper) {
+                let previous = PriceRange::new(
+                    Price::new(Decimal::from_f64(*lower).unwrap_or_default()),
+                    Price::new(Decimal::from_f64(*upper).unwrap_or_default()),
+                );
+                optimizer = optimizer.with_warm_start(previous);
+            }
 
-            // Setup optimizer
-            let optimizer = RangeOptimizer::new(*iterations, 30, 1.0 / 365.0);
-
-            let base_position = Position {
-                id: clmm_lp_domain::entities::position::PositionId(Uuid::new_v4()),
-                pool_address: "opt-pool".to_string(),
-                owner_address: "user".to_string(),
-                liquidity_amount: 0,
-                deposited_amount_a: Amount::new(U256::zero(), 9),
-                deposited_amount_b: Amount::new(U256::zero(), 6),
-                current_amount_a: Amount::new(U256::zero(), 9),
-                current_amount_b: Amount::new(U256::zero(), 6),
-                unclaimed_fees_a: Amount::new(U256::zero(), 9),
-                unclaimed_fees_b: Amount::new(U256::zero(), 6),
-                range: None,
-                opened_at: now,
-                status: PositionStatus::Open,
-            };
+            // Progress line on stderr; Ctrl-C cancels between simulation paths
+            let cancellation = CancellationToken::new();
+            let ctrl_c = cancellation.clone();
+            tokio::spawn(async move {
+                if tokio::signal::ctrl_c().await.is_ok() {
+                    ctrl_c.cancel();
+                }
+            });
+            optimizer = optimizer
+                .with_cancellation(cancellation)
+                .with_progress(Arc::new(|progress: &OptimizationProgress| {
+                    let best = progress
+                        .best_score
+                        .map_or_else(|| "-".to_string(), |s| format!("{:.4}", s));
+                    eprint!(
+                        "\r   Progress: {:>3.0}% ({}/{} ranges, best score {})",
+                        progress.fraction() * 100.0,
+                        progress.candidates_done,
+                        progress.total_candidates,
+                        best
+                    );
+                    if progress.is_complete() {
+                        eprintln!();
+                    }
+                }));
+
+            let base_position =
+                Position::new("opt-pool", "user", Amount::zero(9), Amount::zero(6), 0, now);
 
-            let volume =
-                ConstantVolume::from_amount(Amount::new(U256::from(1_000_000_000_000u64), 6));
+            // Daily steps from now on, keeping the day-of-week volume pattern
+            let volume = fit_volume_seasonality(&candles, 24)
+                .starting_at(now)
+                .with_mean_step_volume(Decimal::from(1_000_000));
+            let (peak, trough) = volume.factor_range();
+            println!(
+                "   Volume Seasonality: peak {:.2}x / trough {:.2}x of mean hourly volume",
+                peak, trough
+            );
             let pool_liquidity = (*capital as u128) * 1000;
-            let fee_rate = Decimal::from_f64(0.003).unwrap();
 
             println!(
-                "🔄 Running optimization with {:?} objective ({} iterations)...",
-                objective, iterations
+                "🔄 Running optimization with {} objective ({} iterations)...",
+                objective_label, iterations
             );
 
-            let result = match objective {
-                OptimizationObjectiveArg::Pnl => optimizer.optimize(
-                    base_position,
-                    current_price_dec,
-                    volatility,
-                    0.0,
-                    volume,
-                    pool_liquidity,
-                    fee_rate,
-                    MaximizeNetPnL,
-                ),
-                OptimizationObjectiveArg::Fees => optimizer.optimize(
-                    base_position,
-                    current_price_dec,
-                    volatility,
-                    0.0,
-                    volume,
-                    pool_liquidity,
-                    fee_rate,
-                    MaximizeFees,
-                ),
-                OptimizationObjectiveArg::Sharpe => optimizer.optimize(
+            let result = optimizer.try_optimize(
+                base_position.clone(),
+                current_price_dec,
+                volatility,
+                0.0,
+                volume.clone(),
+                pool_liquidity,
+                fee_rate,
+                objective_fn,
+            );
+
+            let result = match result {
+                Ok(result) => result,
+                Err(OptimizationError::Cancelled) => {
+                    eprintln!();
+                    println!("⚠️  Optimization cancelled");
+                    return Ok(());
+                }
+                Err(e) => return Err(e.into()),
+            };
+
+            // Print optimization results
+            print_optimization_report(symbol_a, current_price, volatility, *capital, &result);
+
+            // Show how fragile the recommendation is to its inputs
+            println!("🔬 Running sensitivity analysis...");
+            if let Some(report) = optimizer.sensitivity(
+                base_position.clone(),
+                &result.recommended_range,
+                current_price_dec,
+                volatility,
+                0.0,
+                volume.clone(),
+                pool_liquidity,
+                fee_rate,
+            ) {
+                print_sensitivity_report(&report);
+            }
+
+            // Size the deployment from the recommended range's return distribution
+            if let Some(multiplier) = kelly {
+                println!("🎲 Sizing capital with the Kelly criterion...");
+                if let Some(sizing) = optimizer.size_capital(
                     base_position,
+                    &result.recommended_range,
                     current_price_dec,
                     volatility,
                     0.0,
                     volume,
                     pool_liquidity,
                     fee_rate,
-                    MaximizeSharpeRatio::new(Decimal::from_f64(0.05).unwrap()),
-                ),
+                    KellySizing::new(*multiplier, *max_deployment),
+                ) {
+                    print_capital_sizing(*capital, &sizing);
+                }
+            }
+        }
+        Commands::Sweep {
+            symbol_a,
+            mint_a,
+            days,
+            resolution_minutes,
+            widths,
+            intervals,
+            thresholds,
+            capital,
+            fee_rate,
+            tx_cost,
+            coarse_minutes,
+            top_n,
+            limit,
+        } => {
+            let api_key = env::var("BIRDEYE_API_KEY")
+                .expect("BIRDEYE_API_KEY must be set in .env or environment");
+            let decimal = |value: f64| {
+                Decimal::from_f64(value).ok_or_else(|| anyhow::anyhow!("Invalid number: {}", value))
             };
-
-            // Print optimization results
-            print_optimization_report(symbol_a, current_price, volatility, *capital, &result);
+            let args = SweepArgs {
+                symbol_a: symbol_a.clone(),
+                mint_a: mint_a.clone(),
+                days: *days,
+                resolution_minutes: *resolution_minutes,
+                widths: widths.iter().map(|w| decimal(*w)).collect::<Result<_>>()?,
+                intervals_hours: intervals.clone(),
+                thresholds: thresholds
+                    .iter()
+                    .map(|t| decimal(*t))
+                    .collect::<Result<_>>()?,
+                capital: decimal(*capital)?,
+                fee_rate: decimal(*fee_rate)?,
+                tx_cost: decimal(*tx_cost)?,
+                coarse_minutes: *coarse_minutes,
+                top_n: *top_n,
+                limit: *limit,
+            };
+            run_sweep(&BirdeyeProvider::new(api_key), &args).await?;
         }
         Commands::Db { action } => {
             let database_url = env::var("DATABASE_URL")
@@ -557,6 +1311,234 @@ async fn main() -> Result<()> {
                         table.printstd();
                     }
                 }
+                DbAction::AssignStrategy {
+                    position,
+                    strategy,
+                    overrides,
+                } => {
+                    let overrides = parse_overrides(overrides)?;
+                    let db = Database::connect(&database_url).await?;
+                    db.position_strategies()
+                        .upsert(position, strategy, overrides)
+                        .await?;
+                    println!("✅ Assigned strategy {} to position {}", strategy, position);
+                }
+                DbAction::ClearStrategy { position } => {
+                    let db = Database::connect(&database_url).await?;
+                    if db.position_strategies().delete(position).await? {
+                        println!("✅ Cleared strategy for position {}", position);
+                    } else {
+                        println!("No strategy assigned to position {}", position);
+                    }
+                }
+                DbAction::ListStrategies => {
+                    let db = Database::connect(&database_url).await?;
+                    let assignments = db.position_strategies().find_all().await?;
+
+                    if assignments.is_empty() {
+                        println!("No strategy assignments found.");
+                    } else {
+                        println!("🧭 Position Strategies:");
+                        println!();
+                        let mut table = Table::new();
+                        table.add_row(row!["Position", "Strategy", "Overrides", "Updated"]);
+                        for assignment in assignments {
+                            table.add_row(row![
+                                assignment.position_address,
+                                assignment.strategy_id,
+                                assignment.overrides,
+                                assignment.updated_at.format("%Y-%m-%d %H:%M")
+                            ]);
+                        }
+                        table.printstd();
+                    }
+                }
+                DbAction::TagPosition { position, tags } => {
+                    let mut tags: Vec<String> = tags
+                        .iter()
+                        .map(|t| t.trim().to_string())
+                        .filter(|t| !t.is_empty())
+                        .collect();
+                    tags.sort();
+                    tags.dedup();
+
+                    let db = Database::connect(&database_url).await?;
+                    db.position_tags().set_tags(position, &tags).await?;
+                    println!("✅ Tagged position {} with [{}]", position, tags.join(", "));
+                }
+                DbAction::ListTags { tag } => {
+                    let db = Database::connect(&database_url).await?;
+                    let mut tagged: Vec<(String, Vec<String>)> =
+                        db.position_tags().find_all().await?.into_iter().collect();
+                    if let Some(tag) = tag {
+                        tagged.retain(|(_, tags)| tags.contains(tag));
+                    }
+                    tagged.sort();
+
+                    if tagged.is_empty() {
+                        println!("No tagged positions found.");
+                    } else {
+                        println!("🏷️  Position Tags:");
+                        println!();
+                        let mut table = Table::new();
+                        table.add_row(row!["Position", "Tags"]);
+                        for (position, tags) in tagged {
+                            table.add_row(row![position, tags.join(", ")]);
+                        }
+                        table.printstd();
+                    }
+                }
+                DbAction::CompareSimulations { ids, points } => {
+                    if ids.len() < 2 {
+                        anyhow::bail!("Compare at least two simulations");
+                    }
+
+                    let db = Database::connect(&database_url).await?;
+                    let repository = db.simulations();
+                    let mut simulations = Vec::with_capacity(ids.len());
+                    for id in ids {
+                        let saved = SavedSimulation::load(&repository, *id)
+                            .await?
+                            .ok_or_else(|| anyhow::anyhow!("No results for simulation {}", id))?;
+                        simulations.push(saved);
+                    }
+
+                    let comparison = SimulationComparison::build(&simulations, *points);
+                    print_simulation_comparison(&comparison);
+                }
+                DbAction::Leaderboard {
+                    pool,
+                    objective,
+                    limit,
+                } => {
+                    let db = Database::connect(&database_url).await?;
+                    let pool_record = db
+                        .pools()
+                        .find_by_address(pool)
+                        .await?
+                        .ok_or_else(|| anyhow::anyhow!("Pool {} is not stored", pool))?;
+                    let records = db
+                        .simulations()
+                        .find_optimizations_by_pool(pool_record.id, objective.as_str())
+                        .await?;
+
+                    let leaderboard =
+                        OptimizationLeaderboard::build(objective.as_str(), &records, *limit);
+                    print_optimization_leaderboard(&leaderboard);
+                }
+                DbAction::SyncFunding {
+                    venue,
+                    market,
+                    days,
+                } => {
+                    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+                    let start_time = now - (days * 24 * 3600);
+
+                    let rates = match venue {
+                        FundingVenueArg::Drift => {
+                            DriftFundingProvider::new()
+                                .get_funding_history(market, start_time, now)
+                                .await?
+                        }
+                        FundingVenueArg::Hyperliquid => {
+                            HyperliquidFundingProvider::new()
+                                .get_funding_history(market, start_time, now)
+                                .await?
+                        }
+                    };
+
+                    let db = Database::connect(&database_url).await?;
+                    db.funding_rates().save_all(&rates).await?;
+
+                    let curve = FundingCurve::new(rates);
+                    println!(
+                        "✅ Stored {} funding rates for {} ({:?})",
+                        curve.rates().len(),
+                        market,
+                        venue
+                    );
+                    if let Some(annualized) = curve.annualized_rate() {
+                        println!(
+                            "   Mean funding: {:.2}% annualized",
+                            annualized * Decimal::ONE_HUNDRED
+                        );
+                    }
+                }
+            }
+        }
+        Commands::Pools { action } => {
+            let database_url = env::var("DATABASE_URL")
+                .unwrap_or_else(|_| "postgres://localhost/clmm_lp".to_string());
+            let db = Database::connect(&database_url).await?;
+
+            match action {
+                PoolsAction::Import {
+                    file,
+                    backfill_days,
+                    resolution_minutes,
+                } => {
+                    let watchlist = Watchlist::load(file).await?;
+                    let rpc_config = env::var("SOLANA_RPC_URL")
+                        .map(RpcConfig::new)
+                        .unwrap_or_default();
+                    let provider = Arc::new(RpcProvider::new(rpc_config));
+                    println!(
+                        "📥 Importing {} pools from {}...",
+                        watchlist.pools.len(),
+                        file.display()
+                    );
+                    let args = ImportArgs {
+                        backfill_days: *backfill_days,
+                        resolution_minutes: *resolution_minutes,
+                    };
+                    run_import(&db, provider, &watchlist, &args).await?;
+                }
+                PoolsAction::Backfill { limit } => {
+                    let api_key = env::var("BIRDEYE_API_KEY")
+                        .expect("BIRDEYE_API_KEY must be set in .env or environment");
+                    run_backfill(&db, &BirdeyeProvider::new(api_key), *limit).await?;
+                }
+                PoolsAction::Utilization {
+                    pool,
+                    days,
+                    buckets,
+                } => {
+                    run_utilization(&db, pool, *days, (*buckets).max(1)).await?;
+                }
+            }
+        }
+        Commands::Alerts { action } => {
+            let database_url = env::var("DATABASE_URL")
+                .unwrap_or_else(|_| "postgres://localhost/clmm_lp".to_string());
+            let db = Database::connect(&database_url).await?;
+
+            match action {
+                AlertsAction::Add {
+                    name,
+                    condition,
+                    threshold,
+                    severity,
+                    message,
+                    channels,
+                    tags,
+                    cooldown,
+                    disabled,
+                } => {
+                    let rule = build_rule(&AddRuleArgs {
+                        name: name.clone(),
+                        condition: *condition,
+                        threshold: *threshold,
+                        severity: *severity,
+                        message: message.clone(),
+                        channels: channels.clone(),
+                        tags: tags.clone(),
+                        cooldown_secs: *cooldown,
+                        disabled: *disabled,
+                    })?;
+                    run_add(&db, &rule).await?;
+                }
+                AlertsAction::List => run_list(&db).await?,
+                AlertsAction::Remove { name } => run_remove(&db, name).await?,
             }
         }
         Commands::Analyze {
@@ -719,6 +1701,186 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Fits hour-of-week volume seasonality from candles, in quote token terms.
+///
+/// The model starts at the first candle; callers rescale it to their volume level.
+fn fit_volume_seasonality(candles: &[PriceCandle], step_hours: usize) -> SeasonalVolumeModel {
+    let samples: Vec<(u64, Decimal)> = candles
+        .iter()
+        .map(|c| {
+            (
+                c.start_timestamp,
+                c.volume_token_a.to_decimal() * c.close.value,
+            )
+        })
+        .collect();
+    let start = candles.first().map_or(0, |c| c.start_timestamp);
+
+    SeasonalVolumeModel::fit(&samples, step_hours).starting_at(start)
+}
+
+/// Prints metric diffs and normalized equity curves of a comparison.
+/// Builds the objective registry from a TOML file of custom objectives.
+fn load_objective_registry(path: &PathBuf) -> Result<ObjectiveRegistry> {
+    use anyhow::Context;
+
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let config: ObjectiveConfig = toml::from_str(&contents)
+        .with_context(|| format!("Invalid objectives file {}", path.display()))?;
+    Ok(ObjectiveRegistry::from_config(&config)?)
+}
+
+fn print_simulation_comparison(comparison: &SimulationComparison) {
+    let short_ids: Vec<String> = comparison
+        .simulation_ids
+        .iter()
+        .map(|id| id.to_string()[..8].to_string())
+        .collect();
+
+    println!("📊 Simulation Comparison (baseline {}):", short_ids[0]);
+    println!();
+
+    let mut table = Table::new();
+    let mut header = vec![Cell::new("Metric")];
+    header.extend(short_ids.iter().map(|id| Cell::new(id)));
+    table.add_row(Row::new(header));
+    for metric in &comparison.metrics {
+        let mut cells = vec![Cell::new(metric.metric)];
+        for (i, (value, diff)) in metric.values.iter().zip(&metric.diffs).enumerate() {
+            let text = match (value, diff) {
+                (Some(value), Some(diff)) if i > 0 => {
+                    format!("{:.4} ({:+.4})", value, diff)
+                }
+                (Some(value), _) => format!("{:.4}", value),
+                (None, _) => "-".to_string(),
+            };
+            cells.push(Cell::new(&text));
+        }
+        table.add_row(Row::new(cells));
+    }
+    table.printstd();
+
+    if comparison.curves.iter().all(|c| c.values.is_empty()) {
+        println!();
+        println!("No equity curves saved.");
+        return;
+    }
+
+    println!();
+    println!("📈 Normalized Equity (1.0 = start):");
+    println!();
+    let mut table = Table::new();
+    let mut header = vec![Cell::new("Progress")];
+    header.extend(short_ids.iter().map(|id| Cell::new(id)));
+    table.add_row(Row::new(header));
+    for (i, progress) in comparison.axis.iter().enumerate() {
+        let mut cells = vec![Cell::new(&format!(
+            "{:.0}%",
+            progress * Decimal::ONE_HUNDRED
+        ))];
+        for curve in &comparison.curves {
+            let text = curve
+                .values
+                .get(i)
+                .map_or_else(|| "-".to_string(), |v| format!("{:.4}", v));
+            cells.push(Cell::new(&text));
+        }
+        table.add_row(Row::new(cells));
+    }
+    table.printstd();
+}
+
+/// Prints ranked optimizations and the drift of their recommendations.
+fn print_optimization_leaderboard(leaderboard: &OptimizationLeaderboard) {
+    if leaderboard.entries.is_empty() {
+        println!(
+            "No ranked optimizations found for objective '{}'.",
+            leaderboard.objective_type
+        );
+        return;
+    }
+
+    println!(
+        "🏆 Optimization Leaderboard ({}):",
+        leaderboard.objective_type
+    );
+    println!();
+    let mut table = Table::new();
+    table.add_row(row![
+        "#",
+        "ID",
+        "Objective",
+        "Range",
+        "Volatility",
+        "Capital",
+        "Created"
+    ]);
+    for entry in &leaderboard.entries {
+        let record = &entry.record;
+        table.add_row(row![
+            entry.rank,
+            record.id.to_string()[..8].to_string(),
+            format!("{:.4}", entry.objective_value),
+            format!(
+                "${:.2} - ${:.2}",
+                record.recommended_lower, record.recommended_upper
+            ),
+            format!("{:.1}%", record.volatility * Decimal::ONE_HUNDRED),
+            format!("${:.2}", record.initial_capital),
+            record.created_at.format("%Y-%m-%d %H:%M")
+        ]);
+    }
+    table.printstd();
+
+    println!();
+    println!("📉 Recommendation Drift:");
+    println!();
+    let mut table = Table::new();
+    table.add_row(row![
+        "Created",
+        "Range",
+        "Midpoint Δ",
+        "Width Δ",
+        "Volatility Δ"
+    ]);
+    let pct = |v: Option<Decimal>| v.map_or_else(|| "-".to_string(), |v| format!("{:+.2}%", v));
+    for drift in &leaderboard.drift {
+        table.add_row(row![
+            drift.created_at.format("%Y-%m-%d %H:%M"),
+            format!(
+                "${:.2} - ${:.2}",
+                drift.recommended_lower, drift.recommended_upper
+            ),
+            pct(drift.midpoint_change_pct),
+            pct(drift.width_change_pct),
+            pct(drift.volatility_change.map(|v| v * Decimal::ONE_HUNDRED))
+        ]);
+    }
+    table.printstd();
+}
+
+/// Parses `key=value` overrides into a JSON object and validates it.
+///
+/// Values are read as JSON where possible (numbers, booleans) and kept as
+/// strings otherwise, so both `pause_on_trend=true` and `range_spec=2sigma` work.
+fn parse_overrides(pairs: &[String]) -> Result<serde_json::Value> {
+    let mut map = serde_json::Map::new();
+    for pair in pairs {
+        let (key, value) = pair
+            .split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("Invalid override '{}', expected KEY=VALUE", pair))?;
+        let value = serde_json::from_str(value)
+            .unwrap_or_else(|_| serde_json::Value::String(value.to_string()));
+        map.insert(key.trim().to_string(), value);
+    }
+
+    let overrides = serde_json::Value::Object(map);
+    serde_json::from_value::<DecisionOverrides>(overrides.clone())
+        .map_err(|e| anyhow::anyhow!("Invalid overrides: {}", e))?;
+    Ok(overrides)
+}
+
 /// Calculates annualized volatility from price series.
 fn calculate_volatility(prices: &[f64]) -> f64 {
     if prices.len() < 2 {
@@ -767,6 +1929,14 @@ fn print_backtest_report(
     println!();
     println!("📊 BACKTEST RESULTS: {}/USDC", symbol);
     println!("Period: {} days | Strategy: {:?}", days, strategy);
+    let date = |timestamp: Option<u64>| {
+        timestamp
+            .and_then(|t| chrono::DateTime::from_timestamp(t as i64, 0))
+            .map(|d| d.format("%Y-%m-%d %H:%M UTC").to_string())
+    };
+    if let (Some(start), Some(end)) = (date(summary.start_timestamp), date(summary.end_timestamp)) {
+        println!("From {} to {}", start, end);
+    }
     println!();
 
     // Position Configuration Table
@@ -795,6 +1965,30 @@ fn print_backtest_report(
         format!("${:+.2} ({:+.2}%)", summary.final_pnl, return_pct)
     ]);
     perf_table.add_row(row!["Fees Earned", format!("${:.2}", summary.total_fees)]);
+    if !summary.compounded_fees.is_zero() {
+        perf_table.add_row(row![
+            "Fees Compounded",
+            format!("${:.2}", summary.compounded_fees)
+        ]);
+    }
+    if !summary.total_deposited.is_zero() {
+        perf_table.add_row(row![
+            "Deposited",
+            format!("${:.2}", summary.total_deposited)
+        ]);
+    }
+    if !summary.total_withdrawn.is_zero() {
+        perf_table.add_row(row![
+            "Withdrawn",
+            format!("${:.2}", summary.total_withdrawn)
+        ]);
+    }
+    if let Some(mwr) = summary.money_weighted_return {
+        perf_table.add_row(row![
+            "Money-Weighted Return (ann.)",
+            format!("{:+.2}%", mwr * Decimal::from(100))
+        ]);
+    }
     perf_table.add_row(row![
         "Impermanent Loss",
         format!("{:.2}%", summary.final_il_pct * Decimal::from(100))
@@ -814,6 +2008,12 @@ fn print_backtest_report(
         "Max Drawdown",
         format!("{:.2}%", summary.max_drawdown * Decimal::from(100))
     ]);
+    if let (Some(sharpe), Some(config)) = (summary.sharpe_ratio, summary.sharpe_config) {
+        risk_table.add_row(row![
+            format!("Sharpe Ratio ({})", config),
+            format!("{:.2}", sharpe)
+        ]);
+    }
     risk_table.add_row(row![
         "Rebalances",
         format!(
@@ -821,6 +2021,15 @@ fn print_backtest_report(
             summary.rebalance_count, summary.total_rebalance_cost
         )
     ]);
+    if summary.fee_collections > 0 {
+        risk_table.add_row(row![
+            "Fee Collections",
+            format!(
+                "{} (cost: ${:.2})",
+                summary.fee_collections, summary.total_collection_cost
+            )
+        ]);
+    }
     risk_table.printstd();
 
     println!();
@@ -875,6 +2084,22 @@ fn print_optimization_report(
     range_table.add_row(row!["Lower Bound", format!("${:.4}", lower)]);
     range_table.add_row(row!["Upper Bound", format!("${:.4}", upper)]);
     range_table.add_row(row!["Range Width", format!("{}%", width_pct)]);
+    let current_price_dec = Decimal::from_f64(current_price).unwrap_or_default();
+    if let Some(spec) = RangeSpec::percent_from_range(&result.recommended_range, current_price_dec)
+    {
+        range_table.add_row(row!["As Percent", spec.to_string()]);
+    }
+    let volatility_daily = Decimal::from_f64(volatility / (365.0_f64).sqrt()).unwrap_or_default();
+    if let Some(RangeSpec::StdDev(sigmas)) = RangeSpec::std_dev_from_range(
+        &result.recommended_range,
+        current_price_dec,
+        volatility_daily,
+    ) {
+        range_table.add_row(row![
+            "As Daily σ",
+            RangeSpec::StdDev(sigmas.round_dp(2)).to_string()
+        ]);
+    }
     range_table.printstd();
 
     println!();
@@ -890,12 +2115,25 @@ fn print_optimization_report(
         "Expected Fees",
         format!("${:.4}", result.expected_fees)
     ]);
+    perf_table.add_row(row!["Fee Std Dev", format!("${:.4}", result.fee_std_dev)]);
     perf_table.add_row(row!["Expected IL", format!("${:.4}", result.expected_il)]);
     if let Some(sharpe) = result.sharpe_ratio {
-        perf_table.add_row(row!["Sharpe Ratio", format!("{:.2}", sharpe)]);
+        let label = result.sharpe_config.map_or_else(
+            || "Sharpe Ratio".to_string(),
+            |config| format!("Sharpe Ratio ({})", config),
+        );
+        perf_table.add_row(row![label, format!("{:.2}", sharpe)]);
     }
     perf_table.printstd();
 
+    if let Some(explanation) = &result.explanation {
+        println!();
+        println!("🧭 Why this range:");
+        for line in explanation.lines() {
+            println!("   • {}", line);
+        }
+    }
+
     println!();
     println!("💡 Tip: Use these bounds with the backtest command:");
     println!(
@@ -904,3 +2142,77 @@ fn print_optimization_report(
     );
     println!();
 }
+
+/// Prints the Kelly-sized deployment of a recommendation.
+fn print_capital_sizing(capital: f64, sizing: &CapitalSizing) {
+    let mut table = Table::new();
+    table.add_row(row!["CAPITAL SIZING", ""]);
+    table.add_row(row![
+        "Full Kelly",
+        format!("{:.1}%", sizing.full_kelly * 100.0)
+    ]);
+    table.add_row(row![
+        "Deploy",
+        format!(
+            "{:.1}% (${:.2})",
+            sizing.fraction * 100.0,
+            capital * sizing.fraction
+        )
+    ]);
+    table.add_row(row![
+        "Expected Growth",
+        format!(
+            "{:+.4}% per period ({:+.2}% annualized)",
+            sizing.growth_rate * 100.0,
+            sizing.annualized_growth_rate * 100.0
+        )
+    ]);
+    table.add_row(row![
+        "Growth at 100% Deployed",
+        sizing.full_deployment_growth_rate.map_or_else(
+            || "ruin on some paths".to_string(),
+            |g| format!("{:+.4}% per period", g * 100.0)
+        )
+    ]);
+    table.printstd();
+    println!();
+}
+
+/// Prints how the recommendation's expected outcome changes under perturbed inputs.
+fn print_sensitivity_report(report: &SensitivityReport) {
+    let mut table = Table::new();
+    table.add_row(row![
+        "SCENARIO",
+        "Lower",
+        "Upper",
+        "Volatility",
+        "Exp. PnL",
+        "Δ PnL",
+        "Exp. Fees",
+        "Exp. IL"
+    ]);
+    for point in std::iter::once(&report.baseline).chain(&report.points) {
+        table.add_row(row![
+            point.perturbation.to_string(),
+            format!("${:.4}", point.range.lower_price.value),
+            format!("${:.4}", point.range.upper_price.value),
+            format!("{:.1}%", point.volatility * 100.0),
+            format!("${:+.4}", point.expected_pnl),
+            format!("${:+.4}", report.pnl_change(point)),
+            format!("${:.4}", point.expected_fees),
+            format!("${:.4}", point.expected_il)
+        ]);
+    }
+    table.printstd();
+
+    println!();
+    if report.is_fragile(Decimal::new(5, 1)) {
+        println!(
+            "⚠️  Fragile recommendation: a perturbed scenario loses ${:.4} of expected PnL",
+            report.worst_pnl_drop()
+        );
+    } else {
+        println!("✅ Recommendation is robust to the tested perturbations");
+    }
+    println!();
+}
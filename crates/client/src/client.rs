@@ -0,0 +1,729 @@
+//! HTTP client and endpoint methods.
+
+use crate::error::{ClientError, ClientResult};
+use crate::models::*;
+use clmm_lp_api_models::error::ErrorResponse;
+use reqwest::{Method, RequestBuilder, Response, StatusCode, Url};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+use tracing::debug;
+
+/// Path prefix all API routes are served under.
+const API_PREFIX: &str = "api/v1";
+
+/// Credentials sent with every request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Auth {
+    /// API key, sent in the `X-API-Key` header.
+    ApiKey(String),
+    /// JWT, sent as an `Authorization: Bearer` header.
+    Bearer(String),
+}
+
+/// Client configuration.
+#[derive(Debug, Clone)]
+pub struct ClientConfig {
+    /// Server root, e.g. `http://localhost:8080`.
+    pub base_url: Url,
+    /// Credentials, if the server requires them.
+    pub auth: Option<Auth>,
+    /// Timeout for each attempt.
+    pub timeout: Duration,
+    /// Retries after the first attempt for transient failures.
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for each one after.
+    pub retry_backoff: Duration,
+}
+
+impl ClientConfig {
+    /// Creates a configuration for the server at `base_url`.
+    pub fn new(base_url: &str) -> ClientResult<Self> {
+        let base_url = Url::parse(base_url)
+            .map_err(|e| ClientError::InvalidUrl(format!("{base_url}: {e}")))?;
+        if base_url.cannot_be_a_base() {
+            return Err(ClientError::InvalidUrl(base_url.to_string()));
+        }
+        Ok(Self {
+            base_url,
+            auth: None,
+            timeout: Duration::from_secs(30),
+            max_retries: 3,
+            retry_backoff: Duration::from_millis(500),
+        })
+    }
+}
+
+/// Typed client for the REST API.
+///
+/// Reads and other idempotent calls are retried on connection failures,
+/// timeouts, `429` and `5xx` responses. Calls that create or trigger
+/// something are only retried when the connection could not be made, so a
+/// request the server may have acted on is never sent twice.
+#[derive(Debug, Clone)]
+pub struct ClmmClient {
+    config: ClientConfig,
+    http: reqwest::Client,
+}
+
+impl ClmmClient {
+    /// Creates a client for the server at `base_url` with default settings.
+    pub fn new(base_url: &str) -> ClientResult<Self> {
+        Ok(Self::with_config(ClientConfig::new(base_url)?))
+    }
+
+    /// Creates a client from `config`.
+    #[must_use]
+    pub fn with_config(config: ClientConfig) -> Self {
+        Self {
+            config,
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// Authenticates with an API key.
+    #[must_use]
+    pub fn with_api_key(mut self, key: impl Into<String>) -> Self {
+        self.config.auth = Some(Auth::ApiKey(key.into()));
+        self
+    }
+
+    /// Authenticates with a JWT bearer token.
+    #[must_use]
+    pub fn with_bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.config.auth = Some(Auth::Bearer(token.into()));
+        self
+    }
+
+    /// Sets the timeout for each attempt.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.config.timeout = timeout;
+        self
+    }
+
+    /// Sets the retries for transient failures and the initial backoff.
+    #[must_use]
+    pub fn with_retries(mut self, max_retries: u32, backoff: Duration) -> Self {
+        self.config.max_retries = max_retries;
+        self.config.retry_backoff = backoff;
+        self
+    }
+
+    /// Gets the configuration.
+    #[must_use]
+    pub fn config(&self) -> &ClientConfig {
+        &self.config
+    }
+
+    // Health
+
+    /// Gets the health of the service and its components.
+    pub async fn health(&self) -> ClientResult<HealthResponse> {
+        self.get(&["health"]).await
+    }
+
+    /// Returns true if the service is up.
+    pub async fn liveness(&self) -> ClientResult<bool> {
+        self.probe(&["health", "live"]).await
+    }
+
+    /// Returns true if the service is ready to take traffic.
+    pub async fn readiness(&self) -> ClientResult<bool> {
+        self.probe(&["health", "ready"]).await
+    }
+
+    /// Gets service metrics.
+    pub async fn metrics(&self) -> ClientResult<MetricsResponse> {
+        self.get(&["metrics"]).await
+    }
+
+    // Positions
+
+    /// Lists positions, optionally filtered by tag or protocol.
+    pub async fn list_positions(
+        &self,
+        query: &ListPositionsQuery,
+    ) -> ClientResult<ListPositionsResponse> {
+        self.get_with(&["positions"], query).await
+    }
+
+    /// Gets a position.
+    pub async fn get_position(&self, address: &str) -> ClientResult<PositionResponse> {
+        self.get(&["positions", address]).await
+    }
+
+    /// Opens a position.
+    pub async fn open_position(
+        &self,
+        request: &OpenPositionRequest,
+    ) -> ClientResult<MessageResponse> {
+        self.post(&["positions"], request).await
+    }
+
+    /// Closes a position.
+    pub async fn close_position(&self, address: &str) -> ClientResult<MessageResponse> {
+        self.execute(
+            self.request(Method::DELETE, &["positions", address])?,
+            false,
+        )
+        .await
+    }
+
+    /// Collects a position's fees.
+    pub async fn collect_fees(&self, address: &str) -> ClientResult<MessageResponse> {
+        self.execute(
+            self.request(Method::POST, &["positions", address, "collect"])?,
+            false,
+        )
+        .await
+    }
+
+    /// Rebalances a position to a new range.
+    pub async fn rebalance_position(
+        &self,
+        address: &str,
+        request: &RebalanceRequest,
+    ) -> ClientResult<MessageResponse> {
+        self.post(&["positions", address, "rebalance"], request)
+            .await
+    }
+
+    /// Migrates a position to another pool or venue.
+    pub async fn migrate_position(
+        &self,
+        address: &str,
+        request: &MigratePositionRequest,
+    ) -> ClientResult<MigrationResponse> {
+        self.post(&["positions", address, "migrate"], request).await
+    }
+
+    /// Gets a position's PnL.
+    pub async fn get_position_pnl(&self, address: &str) -> ClientResult<PnLResponse> {
+        self.get(&["positions", address, "pnl"]).await
+    }
+
+    /// Gets the strategy assigned to a position.
+    pub async fn get_position_strategy(
+        &self,
+        address: &str,
+    ) -> ClientResult<PositionStrategyResponse> {
+        self.get(&["positions", address, "strategy"]).await
+    }
+
+    /// Assigns a strategy to a position.
+    pub async fn assign_position_strategy(
+        &self,
+        address: &str,
+        request: &AssignStrategyRequest,
+    ) -> ClientResult<PositionStrategyResponse> {
+        self.put(&["positions", address, "strategy"], request).await
+    }
+
+    /// Clears the strategy assigned to a position.
+    pub async fn clear_position_strategy(&self, address: &str) -> ClientResult<MessageResponse> {
+        self.delete(&["positions", address, "strategy"]).await
+    }
+
+    /// Replaces the tags on a position.
+    pub async fn set_position_tags(
+        &self,
+        address: &str,
+        request: &SetPositionTagsRequest,
+    ) -> ClientResult<PositionTagsResponse> {
+        self.put(&["positions", address, "tags"], request).await
+    }
+
+    /// Marks a position read-only or managed.
+    pub async fn set_position_read_only(
+        &self,
+        address: &str,
+        request: &SetPositionReadOnlyRequest,
+    ) -> ClientResult<PositionReadOnlyResponse> {
+        self.put(&["positions", address, "read-only"], request)
+            .await
+    }
+
+    /// Projects a position under hypothetical prices.
+    pub async fn position_what_if(
+        &self,
+        address: &str,
+        request: &WhatIfRequest,
+    ) -> ClientResult<WhatIfResponse> {
+        self.post_idempotent(&["positions", address, "what-if"], request)
+            .await
+    }
+
+    // Strategies
+
+    /// Lists strategies.
+    pub async fn list_strategies(&self) -> ClientResult<ListStrategiesResponse> {
+        self.get(&["strategies"]).await
+    }
+
+    /// Gets a strategy.
+    pub async fn get_strategy(&self, id: &str) -> ClientResult<StrategyResponse> {
+        self.get(&["strategies", id]).await
+    }
+
+    /// Creates a strategy.
+    pub async fn create_strategy(
+        &self,
+        request: &CreateStrategyRequest,
+    ) -> ClientResult<StrategyResponse> {
+        self.post(&["strategies"], request).await
+    }
+
+    /// Replaces a strategy's configuration.
+    pub async fn update_strategy(
+        &self,
+        id: &str,
+        request: &CreateStrategyRequest,
+    ) -> ClientResult<StrategyResponse> {
+        self.put(&["strategies", id], request).await
+    }
+
+    /// Deletes a strategy.
+    pub async fn delete_strategy(&self, id: &str) -> ClientResult<MessageResponse> {
+        self.delete(&["strategies", id]).await
+    }
+
+    /// Starts a strategy.
+    pub async fn start_strategy(&self, id: &str) -> ClientResult<MessageResponse> {
+        self.execute(
+            self.request(Method::POST, &["strategies", id, "start"])?,
+            false,
+        )
+        .await
+    }
+
+    /// Stops a strategy.
+    pub async fn stop_strategy(&self, id: &str) -> ClientResult<MessageResponse> {
+        self.execute(
+            self.request(Method::POST, &["strategies", id, "stop"])?,
+            false,
+        )
+        .await
+    }
+
+    /// Gets a strategy's performance.
+    pub async fn get_strategy_performance(
+        &self,
+        id: &str,
+    ) -> ClientResult<StrategyPerformanceResponse> {
+        self.get(&["strategies", id, "performance"]).await
+    }
+
+    /// Lists recorded configuration changes.
+    pub async fn list_config_changes(&self) -> ClientResult<ConfigChangesResponse> {
+        self.get(&["config", "changes"]).await
+    }
+
+    // Alert rules
+
+    /// Lists alert rules.
+    pub async fn list_alert_rules(&self) -> ClientResult<ListAlertRulesResponse> {
+        self.get(&["alerts", "rules"]).await
+    }
+
+    /// Gets an alert rule.
+    pub async fn get_alert_rule(&self, name: &str) -> ClientResult<AlertRuleResponse> {
+        self.get(&["alerts", "rules", name]).await
+    }
+
+    /// Creates an alert rule.
+    pub async fn create_alert_rule(
+        &self,
+        request: &AlertRuleRequest,
+    ) -> ClientResult<AlertRuleResponse> {
+        self.post(&["alerts", "rules"], request).await
+    }
+
+    /// Replaces an alert rule.
+    pub async fn update_alert_rule(
+        &self,
+        name: &str,
+        request: &AlertRuleRequest,
+    ) -> ClientResult<AlertRuleResponse> {
+        self.put(&["alerts", "rules", name], request).await
+    }
+
+    /// Deletes an alert rule.
+    pub async fn delete_alert_rule(&self, name: &str) -> ClientResult<MessageResponse> {
+        self.delete(&["alerts", "rules", name]).await
+    }
+
+    // Pools
+
+    /// Lists pools.
+    pub async fn list_pools(&self) -> ClientResult<ListPoolsResponse> {
+        self.get(&["pools"]).await
+    }
+
+    /// Lists pools paused by anomaly detection.
+    pub async fn list_paused_pools(&self) -> ClientResult<ListPausedPoolsResponse> {
+        self.get(&["pools", "anomalies"]).await
+    }
+
+    /// Gets a pool.
+    pub async fn get_pool(&self, address: &str) -> ClientResult<PoolResponse> {
+        self.get(&["pools", address]).await
+    }
+
+    /// Gets a pool's current on-chain state.
+    pub async fn get_pool_state(&self, address: &str) -> ClientResult<PoolStateResponse> {
+        self.get(&["pools", address, "state"]).await
+    }
+
+    /// Gets a pool's risk assessment.
+    pub async fn get_pool_risk(&self, address: &str) -> ClientResult<PoolRiskResponse> {
+        self.get(&["pools", address, "risk"]).await
+    }
+
+    /// Resumes a pool paused by anomaly detection.
+    pub async fn resume_pool(&self, address: &str) -> ClientResult<MessageResponse> {
+        self.execute(
+            self.request(Method::POST, &["pools", address, "resume"])?,
+            false,
+        )
+        .await
+    }
+
+    /// Gets capital efficiency across range widths for a pool.
+    pub async fn get_capital_efficiency(
+        &self,
+        address: &str,
+        query: &CapitalEfficiencyQuery,
+    ) -> ClientResult<CapitalEfficiencyResponse> {
+        self.get_with(&["pools", address, "capital-efficiency"], query)
+            .await
+    }
+
+    /// Gets a pool's price history.
+    pub async fn get_price_history(
+        &self,
+        address: &str,
+        query: &PriceHistoryQuery,
+    ) -> ClientResult<PriceHistoryResponse> {
+        self.get_with(&["pools", address, "prices"], query).await
+    }
+
+    /// Gets the time a pool's price spent in each bucket of its tick grid.
+    pub async fn get_range_utilization(
+        &self,
+        address: &str,
+        query: &RangeUtilizationQuery,
+    ) -> ClientResult<RangeUtilizationResponse> {
+        self.get_with(&["pools", address, "utilization"], query)
+            .await
+    }
+
+    /// Gets the best optimization results recorded for a pool.
+    pub async fn get_optimization_leaderboard(
+        &self,
+        address: &str,
+        query: &OptimizationLeaderboardQuery,
+    ) -> ClientResult<OptimizationLeaderboardResponse> {
+        self.get_with(&["pools", address, "optimizations"], query)
+            .await
+    }
+
+    // Analytics
+
+    /// Gets portfolio analytics.
+    pub async fn get_portfolio_analytics(&self) -> ClientResult<PortfolioAnalyticsResponse> {
+        self.get(&["analytics", "portfolio"]).await
+    }
+
+    /// Gets portfolio performance history.
+    pub async fn get_performance_history(
+        &self,
+        query: &PerformanceHistoryQuery,
+    ) -> ClientResult<PerformanceHistoryResponse> {
+        self.get_with(&["analytics", "performance"], query).await
+    }
+
+    /// Runs a simulation.
+    pub async fn run_simulation(
+        &self,
+        request: &SimulationRequest,
+    ) -> ClientResult<SimulationResponse> {
+        self.post_idempotent(&["analytics", "simulate"], request)
+            .await
+    }
+
+    /// Compares a position's economics across venues.
+    pub async fn compare_venues(
+        &self,
+        query: &VenueComparisonQuery,
+    ) -> ClientResult<VenueComparisonResponse> {
+        self.get_with(&["analytics", "venues"], query).await
+    }
+
+    /// Starts an optimization job.
+    pub async fn start_optimization(
+        &self,
+        request: &OptimizationRequest,
+    ) -> ClientResult<OptimizationJobResponse> {
+        self.post(&["analytics", "optimize"], request).await
+    }
+
+    /// Gets an optimization job.
+    pub async fn get_optimization_job(&self, id: &str) -> ClientResult<OptimizationJobResponse> {
+        self.get(&["analytics", "optimize", id]).await
+    }
+
+    /// Cancels an optimization job.
+    pub async fn cancel_optimization_job(&self, id: &str) -> ClientResult<OptimizationJobResponse> {
+        self.delete(&["analytics", "optimize", id]).await
+    }
+
+    /// Gets the price paths an optimization job evaluated.
+    pub async fn get_optimization_paths(
+        &self,
+        id: &str,
+    ) -> ClientResult<OptimizationPathsResponse> {
+        self.get(&["analytics", "optimize", id, "paths"]).await
+    }
+
+    /// Gets the simulations that backtested an optimization's recommendation.
+    pub async fn get_optimization_simulations(
+        &self,
+        id: &str,
+    ) -> ClientResult<OptimizationValidationResponse> {
+        self.get(&["analytics", "optimize", id, "simulations"])
+            .await
+    }
+
+    /// Compares recorded simulations.
+    pub async fn compare_simulations(
+        &self,
+        query: &SimulationCompareQuery,
+    ) -> ClientResult<SimulationComparisonResponse> {
+        self.get_with(&["simulations", "compare"], query).await
+    }
+
+    // Transport
+
+    async fn get<T: DeserializeOwned>(&self, path: &[&str]) -> ClientResult<T> {
+        self.execute(self.request(Method::GET, path)?, true).await
+    }
+
+    async fn get_with<T: DeserializeOwned, Q: Serialize + ?Sized>(
+        &self,
+        path: &[&str],
+        query: &Q,
+    ) -> ClientResult<T> {
+        self.execute(self.request(Method::GET, path)?.query(query), true)
+            .await
+    }
+
+    async fn post<T: DeserializeOwned, B: Serialize + ?Sized>(
+        &self,
+        path: &[&str],
+        body: &B,
+    ) -> ClientResult<T> {
+        self.execute(self.request(Method::POST, path)?.json(body), false)
+            .await
+    }
+
+    /// Posts a request that only computes a result, so is safe to repeat.
+    async fn post_idempotent<T: DeserializeOwned, B: Serialize + ?Sized>(
+        &self,
+        path: &[&str],
+        body: &B,
+    ) -> ClientResult<T> {
+        self.execute(self.request(Method::POST, path)?.json(body), true)
+            .await
+    }
+
+    async fn put<T: DeserializeOwned, B: Serialize + ?Sized>(
+        &self,
+        path: &[&str],
+        body: &B,
+    ) -> ClientResult<T> {
+        self.execute(self.request(Method::PUT, path)?.json(body), true)
+            .await
+    }
+
+    async fn delete<T: DeserializeOwned>(&self, path: &[&str]) -> ClientResult<T> {
+        self.execute(self.request(Method::DELETE, path)?, true)
+            .await
+    }
+
+    /// Calls a health probe, which answers with plain text.
+    async fn probe(&self, path: &[&str]) -> ClientResult<bool> {
+        match self.send(self.request(Method::GET, path)?, true).await {
+            Ok(_) => Ok(true),
+            Err(ClientError::Api { status: 503, .. }) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Builds a request to the API route made of `path` segments, each
+    /// percent-encoded, with credentials attached.
+    fn request(&self, method: Method, path: &[&str]) -> ClientResult<RequestBuilder> {
+        let mut url = self.config.base_url.clone();
+        url.path_segments_mut()
+            .map_err(|()| ClientError::InvalidUrl(self.config.base_url.to_string()))?
+            .pop_if_empty()
+            .extend(API_PREFIX.split('/'))
+            .extend(path);
+
+        let request = self.http.request(method, url).timeout(self.config.timeout);
+        Ok(match &self.config.auth {
+            Some(Auth::ApiKey(key)) => request.header("X-API-Key", key),
+            Some(Auth::Bearer(token)) => request.bearer_auth(token),
+            None => request,
+        })
+    }
+
+    async fn execute<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+        idempotent: bool,
+    ) -> ClientResult<T> {
+        let response = self.send(request, idempotent).await?;
+        let body = response.bytes().await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// Sends `request`, retrying transient failures, and turns error
+    /// statuses into [`ClientError::Api`].
+    async fn send(&self, request: RequestBuilder, idempotent: bool) -> ClientResult<Response> {
+        let mut backoff = self.config.retry_backoff;
+        let mut attempt = 0;
+        loop {
+            let result = request
+                .try_clone()
+                .expect("request bodies are buffered")
+                .send()
+                .await;
+            let retry = match &result {
+                Ok(response) => idempotent && is_transient(response.status()),
+                Err(e) => e.is_connect() || (idempotent && e.is_timeout()),
+            };
+            if retry && attempt < self.config.max_retries {
+                attempt += 1;
+                debug!(attempt, ?backoff, "Retrying API request");
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+                continue;
+            }
+
+            let response = result?;
+            let status = response.status();
+            if status.is_success() {
+                return Ok(response);
+            }
+            return Err(api_error(status, &response.bytes().await?));
+        }
+    }
+}
+
+fn is_transient(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+fn api_error(status: StatusCode, body: &[u8]) -> ClientError {
+    match serde_json::from_slice::<ErrorResponse>(body) {
+        Ok(error) => ClientError::Api {
+            status: status.as_u16(),
+            code: error.code,
+            message: error.message,
+            details: error.details,
+        },
+        // Routes outside the handlers (unknown paths, probes, extractor
+        // rejections) answer with plain text
+        Err(_) => ClientError::Api {
+            status: status.as_u16(),
+            code: status
+                .canonical_reason()
+                .unwrap_or("UNKNOWN")
+                .to_uppercase()
+                .replace(' ', "_"),
+            message: String::from_utf8_lossy(body).into_owned(),
+            details: None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::Router;
+    use axum::http::StatusCode as AxumStatus;
+    use axum::routing::get;
+    use clmm_lp_api::AppState;
+    use clmm_lp_api::routes::create_versioned_router;
+    use clmm_lp_api::state::ApiConfig;
+    use clmm_lp_protocols::prelude::RpcConfig;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    async fn serve(router: Router) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        url
+    }
+
+    #[tokio::test]
+    async fn test_calls_api_and_decodes_errors() {
+        let state = AppState::new(RpcConfig::default(), ApiConfig::default());
+        let url = serve(create_versioned_router(state)).await;
+        let client = ClmmClient::new(&url).unwrap().with_api_key("key");
+
+        assert!(client.liveness().await.unwrap());
+        let strategies = client.list_strategies().await.unwrap();
+        assert_eq!(strategies.total, 0);
+
+        // Path segments are encoded rather than spliced into the route
+        let error = client.get_strategy("missing/strategy").await.unwrap_err();
+        assert!(error.is_not_found());
+        let ClientError::Api { code, message, .. } = error else {
+            panic!("expected an API error");
+        };
+        assert_eq!(code, "NOT_FOUND");
+        assert_eq!(message, "Not found: Strategy not found");
+    }
+
+    #[tokio::test]
+    async fn test_retries_transient_failures_on_reads_only() {
+        let hits = Arc::new(AtomicU32::new(0));
+        let counter = hits.clone();
+        let handler = move || {
+            let counter = counter.clone();
+            async move {
+                if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                    (
+                        AxumStatus::SERVICE_UNAVAILABLE,
+                        r#"{"strategies":[],"total":0}"#,
+                    )
+                } else {
+                    (AxumStatus::OK, r#"{"strategies":[],"total":0}"#)
+                }
+            }
+        };
+        let router = Router::new().route("/api/v1/strategies", get(handler.clone()).post(handler));
+        let client = ClmmClient::new(&serve(router).await)
+            .unwrap()
+            .with_retries(2, Duration::from_millis(1));
+
+        client.list_strategies().await.unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+        // A create the server may have acted on is not repeated
+        hits.store(0, Ordering::SeqCst);
+        let request: CreateStrategyRequest = serde_json::from_value(serde_json::json!({
+            "name": "s",
+            "pool_address": "pool",
+            "strategy_type": "static_range",
+            "parameters": {},
+        }))
+        .unwrap();
+        let error = client.create_strategy(&request).await.unwrap_err();
+        assert_eq!(error.status(), Some(503));
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+}
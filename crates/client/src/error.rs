@@ -0,0 +1,54 @@
+//! Client error types.
+
+use thiserror::Error;
+
+/// Client error type.
+#[derive(Debug, Error)]
+pub enum ClientError {
+    /// The API answered with an error status.
+    #[error("API error {status} ({code}): {message}")]
+    Api {
+        /// HTTP status code.
+        status: u16,
+        /// Error code from the response body, or the status reason if the
+        /// body was not an API error.
+        code: String,
+        /// Error message.
+        message: String,
+        /// Additional details.
+        details: Option<serde_json::Value>,
+    },
+
+    /// The request could not be sent or its response read.
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    /// The response body did not match the expected model.
+    #[error("Decode error: {0}")]
+    Decode(#[from] serde_json::Error),
+
+    /// The base URL or a path built from it is invalid.
+    #[error("Invalid URL: {0}")]
+    InvalidUrl(String),
+}
+
+impl ClientError {
+    /// Returns the HTTP status of an API error.
+    #[must_use]
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Self::Api { status, .. } => Some(*status),
+            Self::Http(e) => e.status().map(|s| s.as_u16()),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the API reported the resource as not found.
+    #[must_use]
+    pub fn is_not_found(&self) -> bool {
+        self.status() == Some(404)
+    }
+}
+
+/// Result type for client calls.
+pub type ClientResult<T> = Result<T, ClientError>;
@@ -0,0 +1,34 @@
+//! Typed client for the CLMM LP REST API.
+//!
+//! This crate lets Rust consumers call the API without hand-rolling HTTP:
+//! - One method per endpoint, taking and returning the API's own models
+//! - API key or bearer token authentication
+//! - Retries with exponential backoff for transient failures
+//! - API error bodies decoded into [`ClientError::Api`]
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use clmm_lp_client::prelude::*;
+//!
+//! # async fn run() -> Result<(), ClientError> {
+//! let client = ClmmClient::new("http://localhost:8080")?.with_api_key("my-key");
+//! let strategies = client.list_strategies().await?;
+//! println!("{} strategies", strategies.total);
+//! # Ok(())
+//! # }
+//! ```
+
+/// Prelude module for convenient imports.
+pub mod prelude;
+
+/// HTTP client and endpoint methods.
+pub mod client;
+/// Error types.
+pub mod error;
+
+/// API request and response models, shared with the server.
+pub use clmm_lp_api_models::models;
+
+pub use client::{Auth, ClientConfig, ClmmClient};
+pub use error::{ClientError, ClientResult};
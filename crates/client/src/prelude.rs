@@ -0,0 +1,18 @@
+//! Prelude module for convenient imports.
+//!
+//! This module re-exports the most commonly used types from the crate.
+//!
+//! # Example
+//!
+//! ```rust
+//! use clmm_lp_client::prelude::*;
+//! ```
+
+// Client
+pub use crate::client::{Auth, ClientConfig, ClmmClient};
+
+// Error types
+pub use crate::error::{ClientError, ClientResult};
+
+// Models
+pub use crate::models::*;
@@ -0,0 +1,19 @@
+-- Migration: 003_add_position_strategies
+-- Adds per-position strategy assignments
+
+-- Position strategies table: strategy and parameter overrides per position
+CREATE TABLE IF NOT EXISTS position_strategies (
+    position_address VARCHAR(64) PRIMARY KEY,
+    strategy_id VARCHAR(100) NOT NULL,
+    overrides JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Index for strategy lookups
+CREATE INDEX IF NOT EXISTS idx_position_strategies_strategy ON position_strategies(strategy_id);
+
+-- Insert migration record
+INSERT INTO schema_migrations (version, name)
+VALUES (3, '003_add_position_strategies')
+ON CONFLICT (version) DO NOTHING;
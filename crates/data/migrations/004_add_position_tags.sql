@@ -0,0 +1,18 @@
+-- Migration: 004_add_position_tags
+-- Adds free-form tags for grouping positions
+
+-- Position tags table: one row per (position, tag)
+CREATE TABLE IF NOT EXISTS position_tags (
+    position_address VARCHAR(64) NOT NULL,
+    tag VARCHAR(64) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (position_address, tag)
+);
+
+-- Index for tag lookups
+CREATE INDEX IF NOT EXISTS idx_position_tags_tag ON position_tags(tag);
+
+-- Insert migration record
+INSERT INTO schema_migrations (version, name)
+VALUES (4, '004_add_position_tags')
+ON CONFLICT (version) DO NOTHING;
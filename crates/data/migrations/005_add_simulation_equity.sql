@@ -0,0 +1,16 @@
+-- Migration: 005_add_simulation_equity
+-- Stores equity curves so saved simulations can be compared over time
+
+-- Simulation equity table: portfolio value per simulation step
+CREATE TABLE IF NOT EXISTS simulation_equity (
+    simulation_id UUID NOT NULL REFERENCES simulations(id) ON DELETE CASCADE,
+    step INTEGER NOT NULL,
+    timestamp BIGINT NOT NULL,  -- Unix timestamp in seconds
+    value DECIMAL(20, 8) NOT NULL,
+    PRIMARY KEY (simulation_id, step)
+);
+
+-- Insert migration record
+INSERT INTO schema_migrations (version, name)
+VALUES (5, '005_add_simulation_equity')
+ON CONFLICT (version) DO NOTHING;
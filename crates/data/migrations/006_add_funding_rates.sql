@@ -0,0 +1,18 @@
+-- Migration: 006_add_funding_rates
+-- Stores perp funding and borrow rates for hedging simulations and strategies
+
+-- Funding rates table: hourly rates per venue and market
+CREATE TABLE IF NOT EXISTS funding_rates (
+    venue VARCHAR(20) NOT NULL,  -- 'drift', 'hyperliquid'
+    kind VARCHAR(20) NOT NULL,   -- 'funding', 'borrow'
+    market VARCHAR(50) NOT NULL,
+    timestamp BIGINT NOT NULL,  -- Unix timestamp in seconds
+    hourly_rate DECIMAL(20, 12) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (venue, kind, market, timestamp)
+);
+
+-- Insert migration record
+INSERT INTO schema_migrations (version, name)
+VALUES (6, '006_add_funding_rates')
+ON CONFLICT (version) DO NOTHING;
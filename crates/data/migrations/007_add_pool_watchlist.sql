@@ -0,0 +1,29 @@
+-- Migration: 007_add_pool_watchlist
+-- Tracks onboarded pools and the market data backfills scheduled for them
+
+-- Watchlist table: pools kept in sync by the live services
+CREATE TABLE IF NOT EXISTS pool_watchlist (
+    pool_id UUID PRIMARY KEY REFERENCES pools(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Backfill jobs table: price history ranges waiting to be fetched
+CREATE TABLE IF NOT EXISTS backfill_jobs (
+    id UUID PRIMARY KEY,
+    pool_id UUID NOT NULL REFERENCES pools(id) ON DELETE CASCADE,
+    start_timestamp BIGINT NOT NULL,  -- Unix timestamp in seconds
+    end_timestamp BIGINT NOT NULL,
+    resolution_secs BIGINT NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',  -- 'pending', 'done', 'failed'
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Index for picking up pending jobs
+CREATE INDEX IF NOT EXISTS idx_backfill_jobs_status ON backfill_jobs(status, created_at);
+
+-- Insert migration record
+INSERT INTO schema_migrations (version, name)
+VALUES (7, '007_add_pool_watchlist')
+ON CONFLICT (version) DO NOTHING;
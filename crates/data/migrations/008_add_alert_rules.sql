@@ -0,0 +1,15 @@
+-- Migration: 008_add_alert_rules
+-- Stores alert rules managed through the API and CLI
+
+-- Alert rules table: the serialized rule (condition, severity, channels, ...) by name
+CREATE TABLE IF NOT EXISTS alert_rules (
+    name VARCHAR(64) PRIMARY KEY,
+    definition JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Insert migration record
+INSERT INTO schema_migrations (version, name)
+VALUES (8, '008_add_alert_rules')
+ON CONFLICT (version) DO NOTHING;
@@ -0,0 +1,17 @@
+-- Migration: 009_add_optimization_paths
+-- Stores percentile Monte Carlo paths so an optimization's spread of outcomes can be charted
+
+-- Optimization paths table: price and net PnL per step of each kept percentile path
+CREATE TABLE IF NOT EXISTS optimization_paths (
+    optimization_id UUID NOT NULL REFERENCES optimization_results(id) ON DELETE CASCADE,
+    percentile SMALLINT NOT NULL,
+    step INTEGER NOT NULL,
+    price DECIMAL(20, 8) NOT NULL,
+    pnl DECIMAL(20, 8) NOT NULL,
+    PRIMARY KEY (optimization_id, percentile, step)
+);
+
+-- Insert migration record
+INSERT INTO schema_migrations (version, name)
+VALUES (9, '009_add_optimization_paths')
+ON CONFLICT (version) DO NOTHING;
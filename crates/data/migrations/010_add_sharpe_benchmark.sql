@@ -0,0 +1,14 @@
+-- Migration: 010_add_sharpe_benchmark
+-- Records the risk-free rate and benchmark Sharpe ratios were computed with,
+-- so ratios measured against different references are not compared
+
+ALTER TABLE optimization_results ADD COLUMN IF NOT EXISTS risk_free_rate DECIMAL(10, 6);
+ALTER TABLE optimization_results ADD COLUMN IF NOT EXISTS sharpe_benchmark VARCHAR(16);
+
+ALTER TABLE simulation_results ADD COLUMN IF NOT EXISTS risk_free_rate DECIMAL(10, 6);
+ALTER TABLE simulation_results ADD COLUMN IF NOT EXISTS sharpe_benchmark VARCHAR(16);
+
+-- Insert migration record
+INSERT INTO schema_migrations (version, name)
+VALUES (10, '010_add_sharpe_benchmark')
+ON CONFLICT (version) DO NOTHING;
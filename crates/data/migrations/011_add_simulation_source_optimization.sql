@@ -0,0 +1,14 @@
+-- Migration: 011_add_simulation_source_optimization
+-- Links simulations to the optimization whose recommendation they backtested,
+-- so recommendations can be measured out-of-sample
+
+ALTER TABLE simulations ADD COLUMN IF NOT EXISTS source_optimization_id UUID
+    REFERENCES optimization_results(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_simulations_source_optimization
+    ON simulations(source_optimization_id);
+
+-- Insert migration record
+INSERT INTO schema_migrations (version, name)
+VALUES (11, '011_add_simulation_source_optimization')
+ON CONFLICT (version) DO NOTHING;
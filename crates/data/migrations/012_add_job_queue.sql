@@ -0,0 +1,33 @@
+-- Migration: 012_add_job_queue
+-- Persistent queue of API-submitted jobs, claimed by workers that may run
+-- in other processes, so queued and running jobs survive API restarts
+
+CREATE TABLE IF NOT EXISTS job_queue (
+    id UUID PRIMARY KEY,
+    kind VARCHAR(50) NOT NULL,  -- e.g. 'optimization'
+    payload JSONB NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'queued',  -- 'queued', 'running', 'completed', 'failed', 'cancelled'
+    priority INTEGER NOT NULL DEFAULT 0,  -- Higher runs first
+    attempts INTEGER NOT NULL DEFAULT 0,
+    max_attempts INTEGER NOT NULL DEFAULT 3,
+    worker_id VARCHAR(100),
+    error TEXT,
+    heartbeat_at TIMESTAMPTZ,
+    started_at TIMESTAMPTZ,
+    finished_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Index for claiming the next queued job
+CREATE INDEX IF NOT EXISTS idx_job_queue_claim
+    ON job_queue(kind, status, priority DESC, created_at);
+
+-- Index for finding running jobs whose worker stopped
+CREATE INDEX IF NOT EXISTS idx_job_queue_heartbeat
+    ON job_queue(status, heartbeat_at);
+
+-- Insert migration record
+INSERT INTO schema_migrations (version, name)
+VALUES (12, '012_add_job_queue')
+ON CONFLICT (version) DO NOTHING;
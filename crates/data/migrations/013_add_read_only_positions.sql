@@ -0,0 +1,13 @@
+-- Migration: 013_add_read_only_positions
+-- Keeps positions marked read-only across restarts
+
+-- Read-only positions table: one row per position the bot must not act on
+CREATE TABLE IF NOT EXISTS read_only_positions (
+    position_address VARCHAR(64) PRIMARY KEY,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Insert migration record
+INSERT INTO schema_migrations (version, name)
+VALUES (13, '013_add_read_only_positions')
+ON CONFLICT (version) DO NOTHING;
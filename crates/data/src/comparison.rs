@@ -0,0 +1,320 @@
+//! Side-by-side comparison of saved simulation results.
+//!
+//! Saved simulations can cover different periods with different step counts,
+//! so equity curves are resampled onto a shared progress axis (0 = start of
+//! the simulation, 1 = end) and normalized to their starting value.
+
+use crate::repositories::{
+    EquityPointRecord, SimulationRecord, SimulationRepository, SimulationResultRecord,
+};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+/// Decimal places kept on resampled curve values.
+const CURVE_DECIMALS: u32 = 8;
+
+/// A saved simulation with its results and equity curve.
+#[derive(Debug, Clone)]
+pub struct SavedSimulation {
+    /// Simulation configuration.
+    pub simulation: SimulationRecord,
+    /// Summary results.
+    pub result: SimulationResultRecord,
+    /// Equity curve ordered by step; empty if none was stored.
+    pub equity: Vec<EquityPointRecord>,
+}
+
+impl SavedSimulation {
+    /// Loads a simulation with its results and equity curve.
+    ///
+    /// Returns `None` if the simulation does not exist or has no results.
+    ///
+    /// # Errors
+    /// Returns an error if a query fails.
+    pub async fn load(
+        repository: &SimulationRepository,
+        id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let Some(simulation) = repository.find_simulation_by_id(id).await? else {
+            return Ok(None);
+        };
+        let Some(result) = repository.find_result_by_simulation(id).await? else {
+            return Ok(None);
+        };
+        let equity = repository.find_equity_curve(id).await?;
+        Ok(Some(Self {
+            simulation,
+            result,
+            equity,
+        }))
+    }
+
+    /// Returns the final PnL as a percentage of initial capital.
+    #[must_use]
+    pub fn return_pct(&self) -> Option<Decimal> {
+        if self.simulation.initial_capital.is_zero() {
+            return None;
+        }
+        Some(self.result.final_pnl / self.simulation.initial_capital * Decimal::ONE_HUNDRED)
+    }
+}
+
+/// Extracts a metric from a saved simulation.
+type MetricFn = fn(&SavedSimulation) -> Option<Decimal>;
+
+/// Metrics compared across simulations, in output order.
+const METRICS: &[(&str, MetricFn)] = &[
+    ("final_value", |s| Some(s.result.final_value)),
+    ("final_pnl", |s| Some(s.result.final_pnl)),
+    ("return_pct", SavedSimulation::return_pct),
+    ("total_fees", |s| Some(s.result.total_fees)),
+    ("total_il", |s| Some(s.result.total_il)),
+    ("final_il_pct", |s| Some(s.result.final_il_pct)),
+    ("time_in_range_pct", |s| Some(s.result.time_in_range_pct)),
+    ("max_drawdown", |s| Some(s.result.max_drawdown)),
+    ("rebalance_count", |s| {
+        Some(Decimal::from(s.result.rebalance_count))
+    }),
+    ("total_rebalance_cost", |s| {
+        Some(s.result.total_rebalance_cost)
+    }),
+    ("vs_hodl", |s| Some(s.result.vs_hodl)),
+    ("sharpe_ratio", |s| s.result.sharpe_ratio),
+];
+
+/// One metric across all compared simulations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricComparison {
+    /// Metric name.
+    pub metric: &'static str,
+    /// Value per simulation, in comparison order.
+    pub values: Vec<Option<Decimal>>,
+    /// Difference from the baseline (first) simulation.
+    pub diffs: Vec<Option<Decimal>>,
+}
+
+/// Equity curve resampled onto the comparison axis.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NormalizedCurve {
+    /// Simulation the curve belongs to.
+    pub simulation_id: Uuid,
+    /// Value relative to the starting value (1.0 = break-even), one per
+    /// axis point; empty if no usable curve was stored.
+    pub values: Vec<Decimal>,
+}
+
+/// Comparison of several saved simulations against the first one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationComparison {
+    /// Compared simulations; the first is the baseline.
+    pub simulation_ids: Vec<Uuid>,
+    /// Shared progress axis from 0 to 1.
+    pub axis: Vec<Decimal>,
+    /// Per-metric values and diffs.
+    pub metrics: Vec<MetricComparison>,
+    /// Normalized equity curves.
+    pub curves: Vec<NormalizedCurve>,
+}
+
+impl SimulationComparison {
+    /// Compares simulations, resampling equity curves to `points` points.
+    ///
+    /// At least two points are always used so the axis covers start and end.
+    #[must_use]
+    pub fn build(simulations: &[SavedSimulation], points: usize) -> Self {
+        let points = points.max(2);
+        let last = Decimal::from(points - 1);
+        let axis: Vec<Decimal> = (0..points).map(|i| Decimal::from(i) / last).collect();
+
+        let metrics = METRICS
+            .iter()
+            .map(|(metric, extract)| {
+                let values: Vec<Option<Decimal>> = simulations.iter().map(extract).collect();
+                let baseline = values.first().copied().flatten();
+                let diffs = values.iter().map(|v| Some((*v)? - baseline?)).collect();
+                MetricComparison {
+                    metric,
+                    values,
+                    diffs,
+                }
+            })
+            .collect();
+
+        let curves = simulations
+            .iter()
+            .map(|s| NormalizedCurve {
+                simulation_id: s.simulation.id,
+                values: resample(&s.equity, &axis),
+            })
+            .collect();
+
+        Self {
+            simulation_ids: simulations.iter().map(|s| s.simulation.id).collect(),
+            axis,
+            metrics,
+            curves,
+        }
+    }
+
+    /// Returns the comparison of a metric by name.
+    #[must_use]
+    pub fn metric(&self, name: &str) -> Option<&MetricComparison> {
+        self.metrics.iter().find(|m| m.metric == name)
+    }
+}
+
+/// Resamples a curve onto `axis` by linear interpolation over its time span,
+/// dividing by the first value.
+fn resample(points: &[EquityPointRecord], axis: &[Decimal]) -> Vec<Decimal> {
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        return Vec::new();
+    };
+    if first.value.is_zero() {
+        return Vec::new();
+    }
+
+    let start = Decimal::from(first.timestamp);
+    let span = Decimal::from(last.timestamp - first.timestamp);
+    let mut segment = 0;
+
+    axis.iter()
+        .map(|progress| {
+            let t = start + span * progress;
+            while segment + 2 < points.len() && Decimal::from(points[segment + 1].timestamp) < t {
+                segment += 1;
+            }
+
+            let value = match points.get(segment + 1) {
+                None => first.value,
+                Some(b) => {
+                    let a = &points[segment];
+                    let dt = Decimal::from(b.timestamp - a.timestamp);
+                    if dt.is_zero() {
+                        b.value
+                    } else {
+                        a.value + (b.value - a.value) * (t - Decimal::from(a.timestamp)) / dt
+                    }
+                }
+            };
+            (value / first.value).round_dp(CURVE_DECIMALS)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn saved(capital: Decimal, pnl: Decimal, curve: &[(i64, Decimal)]) -> SavedSimulation {
+        let id = Uuid::new_v4();
+        SavedSimulation {
+            simulation: SimulationRecord {
+                id,
+                pool_id: None,
+                strategy_type: "static".to_string(),
+                strategy_config: serde_json::Value::Null,
+                start_timestamp: 0,
+                end_timestamp: 100,
+                initial_capital: capital,
+                entry_price: dec!(100),
+                lower_price: dec!(90),
+                upper_price: dec!(110),
+                fee_rate: dec!(0.003),
+                tx_cost: dec!(1),
+                source_optimization_id: None,
+                created_at: chrono::Utc::now(),
+            },
+            result: SimulationResultRecord {
+                id: Uuid::new_v4(),
+                simulation_id: id,
+                final_value: capital + pnl,
+                final_pnl: pnl,
+                total_fees: dec!(10),
+                total_il: dec!(-2),
+                final_il_pct: dec!(-0.5),
+                time_in_range_pct: dec!(80),
+                max_drawdown: dec!(5),
+                rebalance_count: 2,
+                total_rebalance_cost: dec!(2),
+                hodl_value: capital,
+                vs_hodl: pnl,
+                sharpe_ratio: None,
+                risk_free_rate: None,
+                sharpe_benchmark: None,
+                final_price: dec!(100),
+                created_at: chrono::Utc::now(),
+            },
+            equity: curve
+                .iter()
+                .enumerate()
+                .map(|(step, (timestamp, value))| EquityPointRecord {
+                    simulation_id: id,
+                    step: step as i32,
+                    timestamp: *timestamp,
+                    value: *value,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_metric_diffs_against_baseline() {
+        let a = saved(dec!(1000), dec!(50), &[]);
+        let b = saved(dec!(2000), dec!(40), &[]);
+        let comparison = SimulationComparison::build(&[a, b], 5);
+
+        let ret = comparison.metric("return_pct").unwrap();
+        assert_eq!(ret.values, vec![Some(dec!(5)), Some(dec!(2))]);
+        assert_eq!(ret.diffs, vec![Some(dec!(0)), Some(dec!(-3))]);
+
+        let pnl = comparison.metric("final_pnl").unwrap();
+        assert_eq!(pnl.diffs[1], Some(dec!(-10)));
+
+        // Missing values have no diff
+        let sharpe = comparison.metric("sharpe_ratio").unwrap();
+        assert_eq!(sharpe.diffs, vec![None, None]);
+    }
+
+    #[test]
+    fn test_curves_share_axis() {
+        // Different periods and step counts
+        let a = saved(
+            dec!(1000),
+            dec!(100),
+            &[(0, dec!(1000)), (50, dec!(1050)), (100, dec!(1100))],
+        );
+        let b = saved(
+            dec!(500),
+            dec!(-50),
+            &[(1000, dec!(500)), (1400, dec!(450))],
+        );
+        let comparison = SimulationComparison::build(&[a, b], 5);
+
+        assert_eq!(
+            comparison.axis,
+            vec![dec!(0), dec!(0.25), dec!(0.5), dec!(0.75), dec!(1)]
+        );
+        assert_eq!(
+            comparison.curves[0].values,
+            vec![dec!(1), dec!(1.025), dec!(1.05), dec!(1.075), dec!(1.1)]
+        );
+        assert_eq!(
+            comparison.curves[1].values,
+            vec![dec!(1), dec!(0.975), dec!(0.95), dec!(0.925), dec!(0.9)]
+        );
+    }
+
+    #[test]
+    fn test_missing_curve_is_empty() {
+        let a = saved(dec!(1000), dec!(0), &[]);
+        let b = saved(dec!(1000), dec!(0), &[(0, dec!(0)), (10, dec!(5))]);
+        let c = saved(dec!(1000), dec!(0), &[(7, dec!(1000))]);
+        let comparison = SimulationComparison::build(&[a, b, c], 1);
+
+        assert_eq!(comparison.axis.len(), 2);
+        assert!(comparison.curves[0].values.is_empty());
+        assert!(comparison.curves[1].values.is_empty());
+        assert_eq!(comparison.curves[2].values, vec![dec!(1), dec!(1)]);
+    }
+}
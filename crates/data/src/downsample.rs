@@ -0,0 +1,181 @@
+//! Downsampling of long price series for charting.
+//!
+//! Two methods reduce a series to a target number of points:
+//! - Largest-Triangle-Three-Buckets (LTTB) keeps the original candles that
+//!   best preserve the visual shape of the close price line.
+//! - OHLC bucketing merges consecutive candles into wider ones, keeping the
+//!   extremes of every bucket.
+
+use crate::timeseries::OhlcvCandle;
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+
+/// How to reduce a series to fewer points.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DownsampleMethod {
+    /// Keep the candles that best preserve the shape of the close prices.
+    #[default]
+    Lttb,
+    /// Merge consecutive candles into wider OHLC candles.
+    Ohlc,
+}
+
+/// Reduces candles, ordered oldest first, to at most `target` points.
+///
+/// Series already within the target are returned unchanged.
+#[must_use]
+pub fn downsample_candles(
+    candles: &[OhlcvCandle],
+    target: usize,
+    method: DownsampleMethod,
+) -> Vec<OhlcvCandle> {
+    if candles.len() <= target {
+        return candles.to_vec();
+    }
+    match method {
+        DownsampleMethod::Lttb => {
+            let points: Vec<(f64, f64)> = candles
+                .iter()
+                .map(|c| (c.timestamp as f64, c.close.to_f64().unwrap_or_default()))
+                .collect();
+            lttb_indices(&points, target)
+                .into_iter()
+                .map(|i| candles[i].clone())
+                .collect()
+        }
+        DownsampleMethod::Ohlc => bucket_candles(candles, target),
+    }
+}
+
+/// Selects the indices of at most `target` points with LTTB.
+///
+/// The first and last points are always kept; every other bucket
+/// contributes the point forming the largest triangle with the previously
+/// selected point and the average of the next bucket.
+#[must_use]
+pub fn lttb_indices(points: &[(f64, f64)], target: usize) -> Vec<usize> {
+    let n = points.len();
+    if target >= n {
+        return (0..n).collect();
+    }
+    if target < 3 {
+        // Too few points for buckets; keep the endpoints, the last one first
+        return vec![0, n - 1].split_off(2 - target);
+    }
+
+    let bucket_size = (n - 2) as f64 / (target - 2) as f64;
+    let mut selected = Vec::with_capacity(target);
+    selected.push(0);
+    let mut previous = 0;
+
+    for bucket in 0..target - 2 {
+        let start = (bucket as f64 * bucket_size) as usize + 1;
+        let end = (((bucket + 1) as f64 * bucket_size) as usize + 1).min(n - 1);
+
+        // Average of the next bucket, or the last point for the final bucket
+        let next_start = end;
+        let next_end = (((bucket + 2) as f64 * bucket_size) as usize + 1).min(n);
+        let next = &points[next_start..next_end.max(next_start + 1)];
+        let (avg_x, avg_y) = next.iter().fold((0.0, 0.0), |(x, y), p| (x + p.0, y + p.1));
+        let (avg_x, avg_y) = (avg_x / next.len() as f64, avg_y / next.len() as f64);
+
+        let (px, py) = points[previous];
+        let best = (start..end.max(start + 1))
+            .max_by(|&a, &b| {
+                let area = |i: usize| {
+                    let (x, y) = points[i];
+                    ((px - avg_x) * (y - py) - (px - x) * (avg_y - py)).abs()
+                };
+                area(a).total_cmp(&area(b))
+            })
+            .unwrap_or(start);
+        selected.push(best);
+        previous = best;
+    }
+
+    selected.push(n - 1);
+    selected
+}
+
+/// Merges consecutive candles into at most `target` wider candles.
+fn bucket_candles(candles: &[OhlcvCandle], target: usize) -> Vec<OhlcvCandle> {
+    let size = candles.len().div_ceil(target.max(1));
+    candles
+        .chunks(size)
+        .filter_map(|chunk| {
+            let (first, last) = (chunk.first()?, chunk.last()?);
+            Some(OhlcvCandle::new(
+                first.timestamp,
+                first.open,
+                chunk.iter().map(|c| c.high).max()?,
+                chunk.iter().map(|c| c.low).min()?,
+                last.close,
+                chunk.iter().map(|c| c.volume).sum(),
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    fn candles(closes: &[i64]) -> Vec<OhlcvCandle> {
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, close)| {
+                let close = Decimal::from(*close);
+                OhlcvCandle::new(
+                    i as u64 * 60,
+                    close,
+                    close + Decimal::ONE,
+                    close - Decimal::ONE,
+                    close,
+                    Decimal::TEN,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_lttb_keeps_endpoints_and_spikes() {
+        let mut closes = vec![100; 1000];
+        closes[500] = 200;
+        let series = candles(&closes);
+
+        let sampled = downsample_candles(&series, 50, DownsampleMethod::Lttb);
+        assert_eq!(sampled.len(), 50);
+        assert_eq!(sampled[0].timestamp, 0);
+        assert_eq!(sampled[49].timestamp, 999 * 60);
+        assert!(sampled.iter().any(|c| c.close == Decimal::from(200)));
+        assert!(sampled.windows(2).all(|w| w[0].timestamp < w[1].timestamp));
+
+        assert_eq!(
+            lttb_indices(&[(0.0, 1.0), (1.0, 2.0), (2.0, 3.0)], 2),
+            vec![0, 2]
+        );
+    }
+
+    #[test]
+    fn test_ohlc_buckets_keep_extremes() {
+        let series = candles(&[10, 12, 8, 11, 15, 9, 10]);
+
+        let sampled = downsample_candles(&series, 3, DownsampleMethod::Ohlc);
+        assert_eq!(sampled.len(), 3);
+        assert_eq!(sampled[0].open, Decimal::from(10));
+        assert_eq!(sampled[0].high, Decimal::from(13));
+        assert_eq!(sampled[0].low, Decimal::from(7));
+        assert_eq!(sampled[0].close, Decimal::from(8));
+        assert_eq!(sampled[0].volume, Decimal::from(30));
+        assert_eq!(sampled[2].timestamp, 6 * 60);
+
+        // Short series are returned as they are
+        assert_eq!(
+            downsample_candles(&series, 10, DownsampleMethod::Ohlc),
+            series
+        );
+    }
+}
@@ -0,0 +1,188 @@
+//! Perpetual funding and borrow rate series.
+//!
+//! Rates from every venue are normalized to a fraction per hour, so curves
+//! from different venues can be compared and fed to hedging simulations.
+
+use rust_decimal::Decimal;
+use std::fmt;
+use std::str::FromStr;
+
+/// Hours in a (non-leap) year, used to annualize hourly rates.
+const HOURS_PER_YEAR: u32 = 8760;
+
+/// Venue a rate was observed on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateVenue {
+    /// Drift Protocol (Solana).
+    Drift,
+    /// Hyperliquid.
+    Hyperliquid,
+}
+
+impl RateVenue {
+    /// Returns the identifier stored in the database.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Drift => "drift",
+            Self::Hyperliquid => "hyperliquid",
+        }
+    }
+}
+
+impl fmt::Display for RateVenue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RateVenue {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "drift" => Ok(Self::Drift),
+            "hyperliquid" => Ok(Self::Hyperliquid),
+            _ => Err(anyhow::anyhow!("Unknown rate venue: {}", s)),
+        }
+    }
+}
+
+/// What a rate is paid for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateKind {
+    /// Perpetual funding; positive rates are paid by longs to shorts.
+    Funding,
+    /// Interest on borrowed assets.
+    Borrow,
+}
+
+impl RateKind {
+    /// Returns the identifier stored in the database.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Funding => "funding",
+            Self::Borrow => "borrow",
+        }
+    }
+}
+
+impl FromStr for RateKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "funding" => Ok(Self::Funding),
+            "borrow" => Ok(Self::Borrow),
+            _ => Err(anyhow::anyhow!("Unknown rate kind: {}", s)),
+        }
+    }
+}
+
+/// A rate observation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FundingRate {
+    /// Venue the rate was observed on.
+    pub venue: RateVenue,
+    /// Kind of rate.
+    pub kind: RateKind,
+    /// Market name, e.g. "SOL-PERP" or "SOL".
+    pub market: String,
+    /// Timestamp in seconds since epoch.
+    pub timestamp: u64,
+    /// Rate as a fraction per hour.
+    pub hourly_rate: Decimal,
+}
+
+/// Rates of one market ordered by time, usable as a step function.
+#[derive(Debug, Clone, Default)]
+pub struct FundingCurve {
+    /// Observations, oldest first.
+    rates: Vec<FundingRate>,
+}
+
+impl FundingCurve {
+    /// Creates a curve from observations in any order.
+    #[must_use]
+    pub fn new(mut rates: Vec<FundingRate>) -> Self {
+        rates.sort_by_key(|r| r.timestamp);
+        Self { rates }
+    }
+
+    /// Returns the observations, oldest first.
+    #[must_use]
+    pub fn rates(&self) -> &[FundingRate] {
+        &self.rates
+    }
+
+    /// Returns true if the curve has no observations.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rates.is_empty()
+    }
+
+    /// Returns the hourly rate in effect at `timestamp`: the latest
+    /// observation at or before it, or the first one for earlier times.
+    #[must_use]
+    pub fn rate_at(&self, timestamp: u64) -> Option<Decimal> {
+        let index = self.rates.partition_point(|r| r.timestamp <= timestamp);
+        self.rates
+            .get(index.saturating_sub(1))
+            .map(|r| r.hourly_rate)
+    }
+
+    /// Returns the mean hourly rate.
+    #[must_use]
+    pub fn mean_hourly_rate(&self) -> Option<Decimal> {
+        if self.rates.is_empty() {
+            return None;
+        }
+        let sum: Decimal = self.rates.iter().map(|r| r.hourly_rate).sum();
+        Some(sum / Decimal::from(self.rates.len()))
+    }
+
+    /// Returns the mean rate annualized without compounding.
+    #[must_use]
+    pub fn annualized_rate(&self) -> Option<Decimal> {
+        self.mean_hourly_rate()
+            .map(|rate| rate * Decimal::from(HOURS_PER_YEAR))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn rate(timestamp: u64, hourly_rate: Decimal) -> FundingRate {
+        FundingRate {
+            venue: RateVenue::Hyperliquid,
+            kind: RateKind::Funding,
+            market: "SOL".to_string(),
+            timestamp,
+            hourly_rate,
+        }
+    }
+
+    #[test]
+    fn test_funding_curve() {
+        let curve = FundingCurve::new(vec![rate(7200, dec!(0.0003)), rate(3600, dec!(0.0001))]);
+
+        assert_eq!(curve.rate_at(0), Some(dec!(0.0001)));
+        assert_eq!(curve.rate_at(3600), Some(dec!(0.0001)));
+        assert_eq!(curve.rate_at(7199), Some(dec!(0.0001)));
+        assert_eq!(curve.rate_at(10_000), Some(dec!(0.0003)));
+        assert_eq!(curve.mean_hourly_rate(), Some(dec!(0.0002)));
+        assert_eq!(curve.annualized_rate(), Some(dec!(1.752)));
+        assert!(FundingCurve::default().rate_at(0).is_none());
+    }
+
+    #[test]
+    fn test_venue_round_trip() {
+        for venue in [RateVenue::Drift, RateVenue::Hyperliquid] {
+            assert_eq!(venue.as_str().parse::<RateVenue>().unwrap(), venue);
+        }
+        assert!("binance".parse::<RateVenue>().is_err());
+    }
+}
@@ -0,0 +1,210 @@
+//! Ranking of stored optimizations for a pool.
+//!
+//! Optimizations of one pool and objective are ranked by the value they
+//! maximized, and the recommended ranges are replayed in time order to show
+//! how recommendations drifted as market conditions changed.
+
+use crate::repositories::OptimizationRecord;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+/// A ranked optimization.
+#[derive(Debug, Clone)]
+pub struct LeaderboardEntry {
+    /// Rank, starting at 1.
+    pub rank: usize,
+    /// Value of the optimization objective.
+    pub objective_value: Decimal,
+    /// Stored optimization.
+    pub record: OptimizationRecord,
+}
+
+/// Change of a recommendation relative to the previous one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecommendationDrift {
+    /// Optimization ID.
+    pub optimization_id: Uuid,
+    /// When the optimization was stored.
+    pub created_at: DateTime<Utc>,
+    /// Volatility the optimization assumed.
+    pub volatility: Decimal,
+    /// Recommended lower price bound.
+    pub recommended_lower: Decimal,
+    /// Recommended upper price bound.
+    pub recommended_upper: Decimal,
+    /// Change in range midpoint vs the previous recommendation, in percent.
+    pub midpoint_change_pct: Option<Decimal>,
+    /// Change in range width vs the previous recommendation, in percent.
+    pub width_change_pct: Option<Decimal>,
+    /// Change in assumed volatility vs the previous recommendation.
+    pub volatility_change: Option<Decimal>,
+}
+
+/// Leaderboard of optimizations for one pool and objective.
+#[derive(Debug, Clone)]
+pub struct OptimizationLeaderboard {
+    /// Objective type the optimizations maximized.
+    pub objective_type: String,
+    /// Best optimizations, highest objective value first.
+    pub entries: Vec<LeaderboardEntry>,
+    /// Recommendation history, oldest first.
+    pub drift: Vec<RecommendationDrift>,
+}
+
+impl OptimizationLeaderboard {
+    /// Builds a leaderboard keeping the `limit` best optimizations.
+    ///
+    /// Records of other objective types, or without an objective value, are
+    /// left out of the ranking; all records of the objective type are kept
+    /// in the drift history.
+    #[must_use]
+    pub fn build(objective_type: &str, records: &[OptimizationRecord], limit: usize) -> Self {
+        let mut history: Vec<&OptimizationRecord> = records
+            .iter()
+            .filter(|r| r.objective_type == objective_type)
+            .collect();
+        history.sort_by_key(|r| r.created_at);
+
+        let mut ranked: Vec<(Decimal, &OptimizationRecord)> = history
+            .iter()
+            .filter_map(|r| Some((r.objective_value()?, *r)))
+            .collect();
+        // Highest value first; ties go to the most recent optimization
+        ranked.sort_by(|a, b| b.0.cmp(&a.0).then(b.1.created_at.cmp(&a.1.created_at)));
+
+        let entries = ranked
+            .into_iter()
+            .take(limit)
+            .enumerate()
+            .map(|(i, (objective_value, record))| LeaderboardEntry {
+                rank: i + 1,
+                objective_value,
+                record: record.clone(),
+            })
+            .collect();
+
+        let drift = history
+            .iter()
+            .enumerate()
+            .map(|(i, record)| {
+                let previous = i.checked_sub(1).map(|p| history[p]);
+                RecommendationDrift {
+                    optimization_id: record.id,
+                    created_at: record.created_at,
+                    volatility: record.volatility,
+                    recommended_lower: record.recommended_lower,
+                    recommended_upper: record.recommended_upper,
+                    midpoint_change_pct: previous
+                        .and_then(|p| change_pct(midpoint(p), midpoint(record))),
+                    width_change_pct: previous.and_then(|p| change_pct(width(p), width(record))),
+                    volatility_change: previous.map(|p| record.volatility - p.volatility),
+                }
+            })
+            .collect();
+
+        Self {
+            objective_type: objective_type.to_string(),
+            entries,
+            drift,
+        }
+    }
+}
+
+/// Midpoint of the recommended range.
+fn midpoint(record: &OptimizationRecord) -> Decimal {
+    (record.recommended_lower + record.recommended_upper) / Decimal::TWO
+}
+
+/// Width of the recommended range.
+fn width(record: &OptimizationRecord) -> Decimal {
+    record.recommended_upper - record.recommended_lower
+}
+
+/// Percentage change from `old` to `new`, if `old` is non-zero.
+fn change_pct(old: Decimal, new: Decimal) -> Option<Decimal> {
+    if old.is_zero() {
+        return None;
+    }
+    Some((new - old) / old * Decimal::ONE_HUNDRED)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use rust_decimal_macros::dec;
+
+    fn record(
+        objective_type: &str,
+        day: u32,
+        lower: Decimal,
+        upper: Decimal,
+        pnl: Decimal,
+    ) -> OptimizationRecord {
+        OptimizationRecord {
+            id: Uuid::new_v4(),
+            pool_id: None,
+            objective_type: objective_type.to_string(),
+            start_timestamp: 0,
+            end_timestamp: 0,
+            initial_capital: dec!(1000),
+            volatility: dec!(0.5),
+            recommended_lower: lower,
+            recommended_upper: upper,
+            expected_pnl: pnl,
+            expected_fees: dec!(10),
+            expected_il: dec!(-1),
+            sharpe_ratio: None,
+            risk_free_rate: None,
+            sharpe_benchmark: None,
+            simulations_run: 100,
+            created_at: Utc.with_ymd_and_hms(2024, 1, day, 0, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_ranks_by_objective_value() {
+        let records = vec![
+            record("pnl", 1, dec!(90), dec!(110), dec!(5)),
+            record("pnl", 2, dec!(95), dec!(115), dec!(20)),
+            record("fees", 3, dec!(90), dec!(110), dec!(99)),
+            record("pnl", 4, dec!(100), dec!(120), dec!(12)),
+        ];
+        let board = OptimizationLeaderboard::build("pnl", &records, 2);
+
+        assert_eq!(board.entries.len(), 2);
+        assert_eq!(board.entries[0].rank, 1);
+        assert_eq!(board.entries[0].objective_value, dec!(20));
+        assert_eq!(board.entries[1].objective_value, dec!(12));
+        // Drift covers every optimization of the objective
+        assert_eq!(board.drift.len(), 3);
+    }
+
+    #[test]
+    fn test_drift_between_recommendations() {
+        let records = vec![
+            record("pnl", 2, dec!(110), dec!(130), dec!(1)),
+            record("pnl", 1, dec!(90), dec!(110), dec!(1)),
+        ];
+        let board = OptimizationLeaderboard::build("pnl", &records, 10);
+
+        let first = &board.drift[0];
+        assert_eq!(first.recommended_lower, dec!(90));
+        assert_eq!(first.midpoint_change_pct, None);
+
+        let second = &board.drift[1];
+        assert_eq!(second.midpoint_change_pct, Some(dec!(20)));
+        assert_eq!(second.width_change_pct, Some(dec!(0)));
+        assert_eq!(second.volatility_change, Some(dec!(0)));
+    }
+
+    #[test]
+    fn test_sharpe_without_ratio_is_unranked() {
+        let records = vec![record("sharpe", 1, dec!(90), dec!(110), dec!(1))];
+        let board = OptimizationLeaderboard::build("sharpe", &records, 10);
+
+        assert!(board.entries.is_empty());
+        assert_eq!(board.drift.len(), 1);
+    }
+}
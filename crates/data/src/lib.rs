@@ -5,6 +5,14 @@ pub mod prelude;
 
 /// Caching layer for market data.
 pub mod cache;
+/// Comparison of saved simulation results.
+pub mod comparison;
+/// Downsampling of price series for charting.
+pub mod downsample;
+/// Perpetual funding and borrow rate series.
+pub mod funding;
+/// Ranking of stored optimizations.
+pub mod leaderboard;
 /// Historical pool state structures.
 pub mod pool_state;
 /// Data providers.
@@ -15,11 +23,16 @@ pub mod repositories;
 pub mod repository;
 /// Time series data structures.
 pub mod timeseries;
+/// Range utilization heatmaps.
+pub mod utilization;
+/// Out-of-sample validation of optimization recommendations.
+pub mod validation;
 
 use anyhow::Result;
 use async_trait::async_trait;
 use clmm_lp_domain::entities::price_candle::PriceCandle;
 use clmm_lp_domain::entities::token::Token;
+use funding::FundingRate;
 
 /// Trait for providing market data.
 #[async_trait]
@@ -34,3 +47,15 @@ pub trait MarketDataProvider {
         resolution: u64, // seconds
     ) -> Result<Vec<PriceCandle>>;
 }
+
+/// Trait for providing perpetual funding rates.
+#[async_trait]
+pub trait FundingRateProvider {
+    /// Fetches the funding rates of a market between two timestamps in seconds.
+    async fn get_funding_history(
+        &self,
+        market: &str,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<Vec<FundingRate>>;
+}
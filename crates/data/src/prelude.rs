@@ -9,23 +9,44 @@
 //! ```
 
 // Traits
-pub use crate::MarketDataProvider;
+pub use crate::{FundingRateProvider, MarketDataProvider};
 
 // Cache
 pub use crate::cache::{
     Cache, CacheEntry, CacheKeyBuilder, CachedProvider, FileCache, MemoryCache,
 };
 
+// Comparison
+pub use crate::comparison::{
+    MetricComparison, NormalizedCurve, SavedSimulation, SimulationComparison,
+};
+
+// Downsampling
+pub use crate::downsample::{DownsampleMethod, downsample_candles, lttb_indices};
+
+// Funding rates
+pub use crate::funding::{FundingCurve, FundingRate, RateKind, RateVenue};
+
+// Leaderboard
+pub use crate::leaderboard::{LeaderboardEntry, OptimizationLeaderboard, RecommendationDrift};
+
 // Pool state
 pub use crate::pool_state::{PoolStateHistory, PoolStateSnapshot};
 
 // Providers
 pub use crate::providers::csv_provider::write_candles_to_csv;
-pub use crate::providers::{BirdeyeProvider, CsvProvider, JupiterProvider, MockMarketDataProvider};
+pub use crate::providers::{
+    BirdeyeConfig, BirdeyeProvider, CsvProvider, DriftFundingProvider, HyperliquidFundingProvider,
+    JupiterProvider, MockMarketDataProvider,
+};
 
 // Database repositories
 pub use crate::repositories::{
-    Database, OptimizationRecord, PoolRecord, PoolRepository, PriceRecord, PriceRepository,
+    AlertRuleRecord, AlertRuleRepository, BackfillJobRecord, BackfillJobRepository, Database,
+    EquityPointRecord, FundingRateRecord, FundingRateRepository, JobQueueRepository, JobRecord,
+    OptimizationPathPointRecord, OptimizationRecord, PoolRecord, PoolRepository,
+    PositionStrategyRecord, PositionStrategyRepository, PositionTagRecord, PositionTagRepository,
+    PriceRecord, PriceRepository, ReadOnlyPositionRecord, ReadOnlyPositionRepository,
     SimulationRecord, SimulationRepository, SimulationResultRecord,
 };
 
@@ -34,3 +55,9 @@ pub use crate::repository::{SimulationDataRepository, SimulationDataRepositoryBu
 
 // Time series
 pub use crate::timeseries::{OhlcvCandle, TimeSeries};
+
+// Utilization
+pub use crate::utilization::{RangeUtilization, UtilizationBucket};
+
+// Validation
+pub use crate::validation::{OptimizationValidation, ValidationRun};
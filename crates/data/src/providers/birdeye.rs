@@ -1,15 +1,56 @@
 //! Birdeye API provider for market data.
+//!
+//! Long windows are split into requests of at most
+//! [`BirdeyeConfig::max_items_per_request`] candles, fetched concurrently up
+//! to [`BirdeyeConfig::max_concurrent_requests`]. Rate-limited (429) and
+//! server errors are retried with exponential backoff, waiting at least as
+//! long as the `Retry-After` or `X-RateLimit-Reset` headers ask.
 
 use crate::MarketDataProvider;
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use clmm_lp_domain::entities::price_candle::PriceCandle;
 use clmm_lp_domain::entities::token::Token;
 use clmm_lp_domain::value_objects::{amount::Amount, price::Price};
-use reqwest::Client;
+use reqwest::header::HeaderMap;
+use reqwest::{Client, StatusCode};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::FromPrimitive;
 use serde::Deserialize;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::Semaphore;
+use tracing::{debug, warn};
+
+/// Base URL for the Birdeye public API.
+const BIRDEYE_API: &str = "https://public-api.birdeye.so";
+
+/// Request limits of the Birdeye client.
+#[derive(Debug, Clone)]
+pub struct BirdeyeConfig {
+    /// Maximum candles requested at once.
+    pub max_items_per_request: u64,
+    /// Maximum requests in flight across all callers of the provider.
+    pub max_concurrent_requests: usize,
+    /// Retries of a rate-limited or failed request.
+    pub max_retries: u32,
+    /// Base delay before the first retry in milliseconds.
+    pub retry_base_delay_ms: u64,
+    /// Maximum backoff delay in milliseconds.
+    pub retry_max_delay_ms: u64,
+}
+
+impl Default for BirdeyeConfig {
+    fn default() -> Self {
+        Self {
+            max_items_per_request: 1000,
+            max_concurrent_requests: 4,
+            max_retries: 5,
+            retry_base_delay_ms: 500,
+            retry_max_delay_ms: 30_000,
+        }
+    }
+}
 
 #[derive(Deserialize, Debug)]
 struct BirdeyeOhlcvResponse {
@@ -39,17 +80,42 @@ pub struct BirdeyeProvider {
     pub client: Client,
     /// The API key.
     pub api_key: String,
+    /// Base URL (can be overridden for testing).
+    base_url: String,
+    /// Request limits.
+    config: BirdeyeConfig,
+    /// Permits for requests in flight.
+    permits: Arc<Semaphore>,
 }
 
 impl BirdeyeProvider {
     /// Creates a new BirdeyeProvider.
     pub fn new(api_key: String) -> Self {
+        let config = BirdeyeConfig::default();
         Self {
             client: Client::new(),
             api_key,
+            base_url: BIRDEYE_API.to_string(),
+            permits: Arc::new(Semaphore::new(config.max_concurrent_requests)),
+            config,
         }
     }
 
+    /// Sets the request limits.
+    #[must_use]
+    pub fn with_config(mut self, config: BirdeyeConfig) -> Self {
+        self.permits = Arc::new(Semaphore::new(config.max_concurrent_requests.max(1)));
+        self.config = config;
+        self
+    }
+
+    /// Sets a custom base URL (useful for testing).
+    #[must_use]
+    pub fn with_base_url(mut self, url: String) -> Self {
+        self.base_url = url;
+        self
+    }
+
     fn map_resolution(&self, seconds: u64) -> &'static str {
         match seconds {
             60 => "1m",
@@ -65,6 +131,72 @@ impl BirdeyeProvider {
             _ => "1h", // Default fallback
         }
     }
+
+    /// Fetches the candles of one window, retrying rate limits and server errors.
+    async fn fetch_window(
+        &self,
+        address: &str,
+        resolution: &str,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<Vec<BirdeyeCandle>> {
+        let url = format!(
+            "{}/defi/ohlcv?address={}&type={}&time_from={}&time_to={}",
+            self.base_url, address, resolution, start_time, end_time
+        );
+        let mut attempt = 0;
+
+        loop {
+            let result = {
+                let _permit = self.permits.acquire().await?;
+                self.client
+                    .get(&url)
+                    .header("X-API-KEY", &self.api_key)
+                    .header("accept", "application/json")
+                    .send()
+                    .await
+            };
+
+            let (error, header_delay) = match result {
+                Ok(resp) if resp.status().is_success() => {
+                    let data: BirdeyeOhlcvResponse = resp.json().await?;
+                    if !data.success {
+                        return Err(anyhow!("Birdeye API returned success=false"));
+                    }
+                    return Ok(data.data.items);
+                }
+                Ok(resp) => {
+                    let status = resp.status();
+                    let delay = rate_limit_delay(resp.headers(), unix_now());
+                    let text = resp.text().await.unwrap_or_default();
+                    let error = anyhow!("Birdeye API error: {} - {}", status, text);
+                    if !is_retryable(status) {
+                        return Err(error);
+                    }
+                    (error, delay)
+                }
+                Err(e) => (e.into(), None),
+            };
+
+            if attempt >= self.config.max_retries {
+                return Err(error);
+            }
+            let backoff = Duration::from_millis(calculate_backoff(
+                attempt,
+                self.config.retry_base_delay_ms,
+                self.config.retry_max_delay_ms,
+            ));
+            let delay = header_delay.map_or(backoff, |delay| delay.max(backoff));
+            warn!(
+                retry = attempt,
+                delay_ms = delay.as_millis() as u64,
+                error = %error,
+                "Birdeye request failed, retrying"
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
 }
 
 #[async_trait]
@@ -87,34 +219,26 @@ impl MarketDataProvider for BirdeyeProvider {
         }
 
         let resolution_str = self.map_resolution(resolution);
-        let url = format!(
-            "https://public-api.birdeye.so/defi/ohlcv?address={}&type={}&time_from={}&time_to={}",
-            token_a.mint_address, resolution_str, start_time, end_time
+        let windows = chunk_windows(
+            start_time,
+            end_time,
+            resolution,
+            self.config.max_items_per_request,
+        );
+        debug!(
+            token = %token_a.symbol,
+            requests = windows.len(),
+            "Fetching Birdeye price history"
         );
 
-        let resp = self
-            .client
-            .get(&url)
-            .header("X-API-KEY", &self.api_key)
-            .header("accept", "application/json")
-            .send()
+        let responses =
+            futures::future::try_join_all(windows.into_iter().map(|(from, to)| {
+                self.fetch_window(&token_a.mint_address, resolution_str, from, to)
+            }))
             .await?;
+        let items = merge_windows(responses, start_time, end_time);
 
-        if !resp.status().is_success() {
-            let status = resp.status();
-            let text = resp.text().await?;
-            return Err(anyhow::anyhow!("Birdeye API error: {} - {}", status, text));
-        }
-
-        let data: BirdeyeOhlcvResponse = resp.json().await?;
-
-        if !data.success {
-            return Err(anyhow::anyhow!("Birdeye API returned success=false"));
-        }
-
-        let candles = data
-            .data
-            .items
+        let candles = items
             .into_iter()
             .map(|item| {
                 let open = Decimal::from_f64(item.o).unwrap_or(Decimal::ZERO);
@@ -148,3 +272,162 @@ impl MarketDataProvider for BirdeyeProvider {
         Ok(candles)
     }
 }
+
+/// Splits `[start_time, end_time]` into windows of at most `max_items` candles.
+fn chunk_windows(
+    start_time: u64,
+    end_time: u64,
+    resolution: u64,
+    max_items: u64,
+) -> Vec<(u64, u64)> {
+    if end_time <= start_time {
+        return vec![(start_time, end_time)];
+    }
+    let span = resolution.max(1) * max_items.max(1);
+    let mut windows = Vec::new();
+    let mut from = start_time;
+    while from < end_time {
+        let to = from.saturating_add(span).min(end_time);
+        windows.push((from, to));
+        from = to;
+    }
+    windows
+}
+
+/// Joins window responses in time order, dropping candles shared by
+/// adjacent windows and any outside the requested range.
+fn merge_windows(
+    responses: Vec<Vec<BirdeyeCandle>>,
+    start_time: u64,
+    end_time: u64,
+) -> Vec<BirdeyeCandle> {
+    let mut items: Vec<BirdeyeCandle> = responses
+        .into_iter()
+        .flatten()
+        .filter(|item| item.unix_time >= start_time && item.unix_time <= end_time)
+        .collect();
+    items.sort_by_key(|item| item.unix_time);
+    items.dedup_by_key(|item| item.unix_time);
+    items
+}
+
+/// Returns true for responses worth retrying: rate limits and server errors.
+fn is_retryable(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Reads how long the server asks to wait before the next request.
+///
+/// `Retry-After` is in seconds; `X-RateLimit-Reset` is either a Unix
+/// timestamp or, if it lies before `now`, a number of seconds.
+fn rate_limit_delay(headers: &HeaderMap, now: u64) -> Option<Duration> {
+    let seconds = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok())
+    };
+    if let Some(secs) = seconds("retry-after") {
+        return Some(Duration::from_secs(secs));
+    }
+    seconds("x-ratelimit-reset").map(|reset| {
+        if reset > now {
+            Duration::from_secs(reset - now)
+        } else {
+            Duration::from_secs(reset)
+        }
+    })
+}
+
+/// Calculates exponential backoff delay.
+fn calculate_backoff(retry: u32, base_ms: u64, max_ms: u64) -> u64 {
+    base_ms
+        .saturating_mul(2u64.saturating_pow(retry))
+        .min(max_ms)
+}
+
+/// Returns the current Unix time in seconds.
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn candle(unix_time: u64) -> BirdeyeCandle {
+        BirdeyeCandle {
+            o: 1.0,
+            h: 1.0,
+            l: 1.0,
+            c: 1.0,
+            v: 0.0,
+            unix_time,
+        }
+    }
+
+    #[test]
+    fn test_chunk_windows() {
+        // 180 days of 5-minute candles need 52 requests of 1000 candles
+        let end = 180 * 86_400;
+        let windows = chunk_windows(0, end, 300, 1000);
+        assert_eq!(windows.len(), 52);
+        assert_eq!(windows[0], (0, 300_000));
+        assert_eq!(windows[1].0, 300_000);
+        assert_eq!(windows.last().unwrap().1, end);
+
+        assert_eq!(chunk_windows(100, 200, 3600, 1000), vec![(100, 200)]);
+    }
+
+    #[test]
+    fn test_merge_windows() {
+        let merged = merge_windows(
+            vec![
+                vec![candle(300), candle(0), candle(600)],
+                vec![candle(600), candle(900), candle(1200)],
+            ],
+            0,
+            900,
+        );
+        let times: Vec<u64> = merged.iter().map(|c| c.unix_time).collect();
+        assert_eq!(times, vec![0, 300, 600, 900]);
+    }
+
+    #[test]
+    fn test_rate_limit_delay() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(rate_limit_delay(&headers, 1_000), None);
+
+        headers.insert("x-ratelimit-reset", HeaderValue::from_static("1010"));
+        assert_eq!(
+            rate_limit_delay(&headers, 1_000),
+            Some(Duration::from_secs(10))
+        );
+        headers.insert("x-ratelimit-reset", HeaderValue::from_static("3"));
+        assert_eq!(
+            rate_limit_delay(&headers, 1_000),
+            Some(Duration::from_secs(3))
+        );
+
+        headers.insert("retry-after", HeaderValue::from_static("7"));
+        assert_eq!(
+            rate_limit_delay(&headers, 1_000),
+            Some(Duration::from_secs(7))
+        );
+    }
+
+    #[test]
+    fn test_retry_policy() {
+        assert!(is_retryable(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable(StatusCode::BAD_GATEWAY));
+        assert!(!is_retryable(StatusCode::UNAUTHORIZED));
+
+        assert_eq!(calculate_backoff(0, 500, 30_000), 500);
+        assert_eq!(calculate_backoff(3, 500, 30_000), 4_000);
+        assert_eq!(calculate_backoff(10, 500, 30_000), 30_000);
+    }
+}
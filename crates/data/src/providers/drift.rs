@@ -0,0 +1,159 @@
+//! Drift Protocol funding rate provider.
+//!
+//! Reads hourly perpetual funding rates from Drift's public data API.
+
+use crate::FundingRateProvider;
+use crate::funding::{FundingRate, RateKind, RateVenue};
+use anyhow::{Context, Result, anyhow};
+use async_trait::async_trait;
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+/// Base URL for the Drift data API.
+const DRIFT_DATA_API: &str = "https://data.api.drift.trade";
+
+/// Precision of Drift funding rates (quote per base, per hour).
+const FUNDING_RATE_PRECISION: i64 = 1_000_000_000;
+
+/// Precision of Drift oracle prices.
+const PRICE_PRECISION: i64 = 1_000_000;
+
+/// Response from the funding rates endpoint.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct DriftFundingResponse {
+    /// Funding rate updates.
+    funding_rates: Vec<DriftFundingRecord>,
+}
+
+/// A single funding rate update.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct DriftFundingRecord {
+    /// Unix timestamp in seconds.
+    ts: String,
+    /// Funding paid per base unit, in quote at `FUNDING_RATE_PRECISION`.
+    funding_rate: String,
+    /// Oracle price TWAP at `PRICE_PRECISION`.
+    oracle_price_twap: String,
+}
+
+/// Provider for Drift perpetual funding rates.
+pub struct DriftFundingProvider {
+    /// The HTTP client.
+    client: Client,
+    /// Base URL (can be overridden for testing).
+    base_url: String,
+}
+
+impl DriftFundingProvider {
+    /// Creates a new DriftFundingProvider.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            client: Client::new(),
+            base_url: DRIFT_DATA_API.to_string(),
+        }
+    }
+
+    /// Sets a custom base URL (useful for testing).
+    #[must_use]
+    pub fn with_base_url(mut self, url: String) -> Self {
+        self.base_url = url;
+        self
+    }
+}
+
+impl Default for DriftFundingProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl FundingRateProvider for DriftFundingProvider {
+    /// Fetches funding rates of a perp market such as "SOL-PERP".
+    ///
+    /// The API returns the most recent updates; older ones are not available.
+    async fn get_funding_history(
+        &self,
+        market: &str,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<Vec<FundingRate>> {
+        let url = format!("{}/fundingRates?marketName={}", self.base_url, market);
+        let response = self.client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!(
+                "Drift API error: {} - {}",
+                response.status(),
+                response.text().await.unwrap_or_default()
+            ));
+        }
+
+        let data: DriftFundingResponse = response.json().await?;
+        let rates = parse_funding(market, data)?;
+        Ok(rates
+            .into_iter()
+            .filter(|r| r.timestamp >= start_time && r.timestamp <= end_time)
+            .collect())
+    }
+}
+
+/// Converts Drift funding updates into hourly fractions of the oracle price.
+fn parse_funding(market: &str, data: DriftFundingResponse) -> Result<Vec<FundingRate>> {
+    data.funding_rates
+        .into_iter()
+        .map(|record| {
+            let timestamp = record.ts.parse().context("Invalid Drift timestamp")?;
+            let funding: Decimal = record
+                .funding_rate
+                .parse()
+                .context("Invalid Drift funding rate")?;
+            let twap: Decimal = record
+                .oracle_price_twap
+                .parse()
+                .context("Invalid Drift oracle TWAP")?;
+            if twap.is_zero() {
+                return Err(anyhow!("Drift oracle TWAP is zero at {}", timestamp));
+            }
+
+            let hourly_rate = (funding / Decimal::from(FUNDING_RATE_PRECISION))
+                / (twap / Decimal::from(PRICE_PRECISION));
+            Ok(FundingRate {
+                venue: RateVenue::Drift,
+                kind: RateKind::Funding,
+                market: market.to_string(),
+                timestamp,
+                hourly_rate,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_parse_funding() {
+        let data: DriftFundingResponse = serde_json::from_str(
+            r#"{"fundingRates": [{
+                "ts": "1700000000",
+                "fundingRate": "1500000",
+                "oraclePriceTwap": "150000000",
+                "markPriceTwap": "150100000"
+            }]}"#,
+        )
+        .unwrap();
+
+        let rates = parse_funding("SOL-PERP", data).unwrap();
+        assert_eq!(rates.len(), 1);
+        assert_eq!(rates[0].timestamp, 1_700_000_000);
+        // 0.0015 USD per SOL at 150 USD
+        assert_eq!(rates[0].hourly_rate, dec!(0.00001));
+    }
+}
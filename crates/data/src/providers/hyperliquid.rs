@@ -0,0 +1,123 @@
+//! Hyperliquid funding rate provider.
+//!
+//! Reads hourly perpetual funding rates from the Hyperliquid info API.
+
+use crate::FundingRateProvider;
+use crate::funding::{FundingRate, RateKind, RateVenue};
+use anyhow::{Context, Result, anyhow};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+
+/// URL of the Hyperliquid info API.
+const HYPERLIQUID_INFO_API: &str = "https://api.hyperliquid.xyz/info";
+
+/// A single funding rate entry.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct HyperliquidFundingRecord {
+    /// Hourly funding rate as a fraction.
+    funding_rate: String,
+    /// Unix timestamp in milliseconds.
+    time: u64,
+}
+
+/// Provider for Hyperliquid perpetual funding rates.
+pub struct HyperliquidFundingProvider {
+    /// The HTTP client.
+    client: Client,
+    /// Info API URL (can be overridden for testing).
+    base_url: String,
+}
+
+impl HyperliquidFundingProvider {
+    /// Creates a new HyperliquidFundingProvider.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            client: Client::new(),
+            base_url: HYPERLIQUID_INFO_API.to_string(),
+        }
+    }
+
+    /// Sets a custom base URL (useful for testing).
+    #[must_use]
+    pub fn with_base_url(mut self, url: String) -> Self {
+        self.base_url = url;
+        self
+    }
+}
+
+impl Default for HyperliquidFundingProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl FundingRateProvider for HyperliquidFundingProvider {
+    /// Fetches funding rates of a coin such as "SOL".
+    async fn get_funding_history(
+        &self,
+        market: &str,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<Vec<FundingRate>> {
+        let body = serde_json::json!({
+            "type": "fundingHistory",
+            "coin": market,
+            "startTime": start_time * 1000,
+            "endTime": end_time * 1000,
+        });
+        let response = self.client.post(&self.base_url).json(&body).send().await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!(
+                "Hyperliquid API error: {} - {}",
+                response.status(),
+                response.text().await.unwrap_or_default()
+            ));
+        }
+
+        let records: Vec<HyperliquidFundingRecord> = response.json().await?;
+        parse_funding(market, records)
+    }
+}
+
+/// Converts Hyperliquid funding entries into rates.
+fn parse_funding(market: &str, records: Vec<HyperliquidFundingRecord>) -> Result<Vec<FundingRate>> {
+    records
+        .into_iter()
+        .map(|record| {
+            Ok(FundingRate {
+                venue: RateVenue::Hyperliquid,
+                kind: RateKind::Funding,
+                market: market.to_string(),
+                timestamp: record.time / 1000,
+                hourly_rate: record
+                    .funding_rate
+                    .parse()
+                    .context("Invalid Hyperliquid funding rate")?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_parse_funding() {
+        let records: Vec<HyperliquidFundingRecord> = serde_json::from_str(
+            r#"[{"coin": "SOL", "fundingRate": "0.0000125", "premium": "-0.0003", "time": 1700000000000}]"#,
+        )
+        .unwrap();
+
+        let rates = parse_funding("SOL", records).unwrap();
+        assert_eq!(rates[0].timestamp, 1_700_000_000);
+        assert_eq!(rates[0].hourly_rate, dec!(0.0000125));
+        assert_eq!(rates[0].venue, RateVenue::Hyperliquid);
+    }
+}
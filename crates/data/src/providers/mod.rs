@@ -1,16 +1,21 @@
 //! Market data providers for fetching price history.
 //!
 //! This module provides different data sources for historical price data,
-//! including API providers and file-based providers.
+//! including API providers and file-based providers, and perp funding rate
+//! providers.
 
 mod birdeye;
 /// CSV provider module for file-based data loading.
 pub mod csv_provider;
+mod drift;
+mod hyperliquid;
 /// Jupiter Price API provider.
 pub mod jupiter;
 mod mock;
 
-pub use birdeye::BirdeyeProvider;
+pub use birdeye::{BirdeyeConfig, BirdeyeProvider};
 pub use csv_provider::CsvProvider;
+pub use drift::DriftFundingProvider;
+pub use hyperliquid::HyperliquidFundingProvider;
 pub use jupiter::JupiterProvider;
 pub use mock::MockMarketDataProvider;
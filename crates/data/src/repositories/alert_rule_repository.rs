@@ -0,0 +1,105 @@
+//! Alert rule repository for rules managed at runtime.
+
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use std::sync::Arc;
+
+/// Database record for an alert rule.
+#[derive(Debug, Clone)]
+pub struct AlertRuleRecord {
+    /// Unique rule name.
+    pub name: String,
+    /// Serialized rule definition as JSON.
+    pub definition: serde_json::Value,
+    /// Record creation timestamp.
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Record update timestamp.
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl AlertRuleRecord {
+    /// Creates an AlertRuleRecord from a database row.
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            name: row.try_get("name")?,
+            definition: row.try_get("definition")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+}
+
+/// Repository for alert rules.
+#[derive(Clone)]
+pub struct AlertRuleRepository {
+    pool: Arc<PgPool>,
+}
+
+impl AlertRuleRepository {
+    /// Creates a new AlertRuleRepository.
+    #[must_use]
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Finds a rule by name.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_by_name(&self, name: &str) -> Result<Option<AlertRuleRecord>, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM alert_rules WHERE name = $1")
+            .bind(name)
+            .fetch_optional(self.pool.as_ref())
+            .await?;
+        row.as_ref().map(AlertRuleRecord::from_row).transpose()
+    }
+
+    /// Finds all rules ordered by name.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_all(&self) -> Result<Vec<AlertRuleRecord>, sqlx::Error> {
+        let rows = sqlx::query("SELECT * FROM alert_rules ORDER BY name")
+            .fetch_all(self.pool.as_ref())
+            .await?;
+        rows.iter().map(AlertRuleRecord::from_row).collect()
+    }
+
+    /// Stores a rule, replacing any existing rule with the same name.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn upsert(
+        &self,
+        name: &str,
+        definition: serde_json::Value,
+    ) -> Result<AlertRuleRecord, sqlx::Error> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO alert_rules (name, definition)
+            VALUES ($1, $2)
+            ON CONFLICT (name) DO UPDATE SET
+                definition = EXCLUDED.definition,
+                updated_at = NOW()
+            RETURNING *
+            "#,
+        )
+        .bind(name)
+        .bind(&definition)
+        .fetch_one(self.pool.as_ref())
+        .await?;
+        AlertRuleRecord::from_row(&row)
+    }
+
+    /// Removes a rule by name.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn delete(&self, name: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM alert_rules WHERE name = $1")
+            .bind(name)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,148 @@
+//! Backfill job repository for scheduled price history fetches.
+
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Database record for a price history backfill.
+#[derive(Debug, Clone)]
+pub struct BackfillJobRecord {
+    /// Unique identifier.
+    pub id: Uuid,
+    /// Pool whose price history is fetched.
+    pub pool_id: Uuid,
+    /// Start of the range in seconds.
+    pub start_timestamp: i64,
+    /// End of the range in seconds.
+    pub end_timestamp: i64,
+    /// Candle resolution in seconds.
+    pub resolution_secs: i64,
+    /// Job status (pending, done, failed).
+    pub status: String,
+    /// Error of the last failed attempt.
+    pub error: Option<String>,
+    /// Record creation timestamp.
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Record update timestamp.
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl BackfillJobRecord {
+    /// Creates a BackfillJobRecord from a database row.
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            pool_id: row.try_get("pool_id")?,
+            start_timestamp: row.try_get("start_timestamp")?,
+            end_timestamp: row.try_get("end_timestamp")?,
+            resolution_secs: row.try_get("resolution_secs")?,
+            status: row.try_get("status")?,
+            error: row.try_get("error")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+}
+
+/// Repository for backfill jobs.
+#[derive(Clone)]
+pub struct BackfillJobRepository {
+    pool: Arc<PgPool>,
+}
+
+impl BackfillJobRepository {
+    /// Creates a new BackfillJobRepository.
+    #[must_use]
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Schedules a backfill of a pool's price history.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn enqueue(
+        &self,
+        pool_id: Uuid,
+        start_timestamp: i64,
+        end_timestamp: i64,
+        resolution_secs: i64,
+    ) -> Result<BackfillJobRecord, sqlx::Error> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO backfill_jobs (id, pool_id, start_timestamp, end_timestamp, resolution_secs)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(pool_id)
+        .bind(start_timestamp)
+        .bind(end_timestamp)
+        .bind(resolution_secs)
+        .fetch_one(self.pool.as_ref())
+        .await?;
+        BackfillJobRecord::from_row(&row)
+    }
+
+    /// Finds pending jobs, oldest first.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_pending(&self, limit: i64) -> Result<Vec<BackfillJobRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT * FROM backfill_jobs WHERE status = 'pending' ORDER BY created_at ASC LIMIT $1",
+        )
+        .bind(limit)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.iter().map(BackfillJobRecord::from_row).collect()
+    }
+
+    /// Finds the pending jobs of a pool.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_pending_by_pool(
+        &self,
+        pool_id: Uuid,
+    ) -> Result<Vec<BackfillJobRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT * FROM backfill_jobs WHERE pool_id = $1 AND status = 'pending' ORDER BY created_at ASC",
+        )
+        .bind(pool_id)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.iter().map(BackfillJobRecord::from_row).collect()
+    }
+
+    /// Marks a job as completed.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn mark_done(&self, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE backfill_jobs SET status = 'done', error = NULL, updated_at = NOW() WHERE id = $1",
+        )
+        .bind(id)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Marks a job as failed with the error that stopped it.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn mark_failed(&self, id: Uuid, error: &str) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE backfill_jobs SET status = 'failed', error = $2, updated_at = NOW() WHERE id = $1",
+        )
+        .bind(id)
+        .bind(error)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(())
+    }
+}
@@ -3,7 +3,11 @@
 //! Provides a unified interface for database operations including
 //! connection management, repository access, and schema migrations.
 
-use super::{PoolRepository, PriceRepository, SimulationRepository};
+use super::{
+    AlertRuleRepository, BackfillJobRepository, FundingRateRepository, JobQueueRepository,
+    PoolRepository, PositionStrategyRepository, PositionTagRepository, PriceRepository,
+    ReadOnlyPositionRepository, SimulationRepository,
+};
 use sqlx::PgPool;
 use std::sync::Arc;
 
@@ -67,25 +71,86 @@ impl Database {
         PriceRepository::new(self.pool.clone())
     }
 
+    /// Creates a PositionStrategyRepository instance.
+    #[must_use]
+    pub fn position_strategies(&self) -> PositionStrategyRepository {
+        PositionStrategyRepository::new(self.pool.clone())
+    }
+
+    /// Creates a PositionTagRepository instance.
+    #[must_use]
+    pub fn position_tags(&self) -> PositionTagRepository {
+        PositionTagRepository::new(self.pool.clone())
+    }
+
+    /// Creates a ReadOnlyPositionRepository instance.
+    #[must_use]
+    pub fn read_only_positions(&self) -> ReadOnlyPositionRepository {
+        ReadOnlyPositionRepository::new(self.pool.clone())
+    }
+
+    /// Creates a FundingRateRepository instance.
+    #[must_use]
+    pub fn funding_rates(&self) -> FundingRateRepository {
+        FundingRateRepository::new(self.pool.clone())
+    }
+
+    /// Creates a BackfillJobRepository instance.
+    #[must_use]
+    pub fn backfill_jobs(&self) -> BackfillJobRepository {
+        BackfillJobRepository::new(self.pool.clone())
+    }
+
+    /// Creates an AlertRuleRepository instance.
+    #[must_use]
+    pub fn alert_rules(&self) -> AlertRuleRepository {
+        AlertRuleRepository::new(self.pool.clone())
+    }
+
+    /// Creates a JobQueueRepository instance.
+    #[must_use]
+    pub fn jobs(&self) -> JobQueueRepository {
+        JobQueueRepository::new(self.pool.clone())
+    }
+
     /// Runs database migrations.
     ///
-    /// Executes the initial schema migration. Splits the migration file
+    /// Executes the schema migrations in order. Splits each migration file
     /// by semicolons and executes each statement separately to support
     /// multiple SQL commands.
     ///
     /// # Errors
     /// Returns an error if any migration statement fails.
     pub async fn migrate(&self) -> Result<(), sqlx::Error> {
-        let migration_sql = include_str!("../../migrations/001_initial_schema.sql");
-
-        // Split by semicolons and execute each statement separately
-        for statement in migration_sql.split(';') {
-            let trimmed = statement.trim();
-            // Skip empty statements and comments-only blocks
-            if trimmed.is_empty() || trimmed.starts_with("--") && !trimmed.contains("CREATE") {
-                continue;
+        let migrations = [
+            include_str!("../../migrations/001_initial_schema.sql"),
+            include_str!("../../migrations/002_add_positions.sql"),
+            include_str!("../../migrations/003_add_position_strategies.sql"),
+            include_str!("../../migrations/004_add_position_tags.sql"),
+            include_str!("../../migrations/005_add_simulation_equity.sql"),
+            include_str!("../../migrations/006_add_funding_rates.sql"),
+            include_str!("../../migrations/007_add_pool_watchlist.sql"),
+            include_str!("../../migrations/008_add_alert_rules.sql"),
+            include_str!("../../migrations/009_add_optimization_paths.sql"),
+            include_str!("../../migrations/010_add_sharpe_benchmark.sql"),
+            include_str!("../../migrations/011_add_simulation_source_optimization.sql"),
+            include_str!("../../migrations/012_add_job_queue.sql"),
+            include_str!("../../migrations/013_add_read_only_positions.sql"),
+        ];
+
+        for migration_sql in migrations {
+            // Split by semicolons and execute each statement separately
+            for statement in migration_sql.split(';') {
+                let trimmed = statement.trim();
+                // Skip empty statements and comments-only blocks
+                if trimmed
+                    .lines()
+                    .all(|line| line.trim().is_empty() || line.trim().starts_with("--"))
+                {
+                    continue;
+                }
+                sqlx::query(trimmed).execute(self.pool.as_ref()).await?;
             }
-            sqlx::query(trimmed).execute(self.pool.as_ref()).await?;
         }
         Ok(())
     }
@@ -0,0 +1,149 @@
+//! Funding and borrow rate repository.
+
+use crate::funding::{FundingRate, RateKind, RateVenue};
+use rust_decimal::Decimal;
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use std::sync::Arc;
+
+/// Database record for a rate observation.
+#[derive(Debug, Clone)]
+pub struct FundingRateRecord {
+    /// Venue identifier, e.g. "drift".
+    pub venue: String,
+    /// Rate kind, "funding" or "borrow".
+    pub kind: String,
+    /// Market name.
+    pub market: String,
+    /// Timestamp in seconds.
+    pub timestamp: i64,
+    /// Rate as a fraction per hour.
+    pub hourly_rate: Decimal,
+    /// Record creation timestamp.
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl FundingRateRecord {
+    /// Creates a FundingRateRecord from a database row.
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            venue: row.try_get("venue")?,
+            kind: row.try_get("kind")?,
+            market: row.try_get("market")?,
+            timestamp: row.try_get("timestamp")?,
+            hourly_rate: row.try_get("hourly_rate")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+
+    /// Converts the record into a rate observation.
+    ///
+    /// # Errors
+    /// Returns an error if the venue or kind is unknown.
+    pub fn to_rate(&self) -> anyhow::Result<FundingRate> {
+        Ok(FundingRate {
+            venue: self.venue.parse()?,
+            kind: self.kind.parse()?,
+            market: self.market.clone(),
+            timestamp: u64::try_from(self.timestamp)?,
+            hourly_rate: self.hourly_rate,
+        })
+    }
+}
+
+/// Repository for funding and borrow rates.
+#[derive(Clone)]
+pub struct FundingRateRepository {
+    pool: Arc<PgPool>,
+}
+
+impl FundingRateRepository {
+    /// Creates a new FundingRateRepository.
+    #[must_use]
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Saves rate observations, overwriting existing ones at the same time.
+    ///
+    /// # Errors
+    /// Returns an error if the transaction fails.
+    pub async fn save_all(&self, rates: &[FundingRate]) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        for rate in rates {
+            sqlx::query(
+                r#"
+                INSERT INTO funding_rates (venue, kind, market, timestamp, hourly_rate)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (venue, kind, market, timestamp) DO UPDATE SET
+                    hourly_rate = EXCLUDED.hourly_rate
+                "#,
+            )
+            .bind(rate.venue.as_str())
+            .bind(rate.kind.as_str())
+            .bind(&rate.market)
+            .bind(rate.timestamp as i64)
+            .bind(rate.hourly_rate)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await
+    }
+
+    /// Finds the rates of a market within a time range, oldest first.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_by_market_and_range(
+        &self,
+        venue: RateVenue,
+        kind: RateKind,
+        market: &str,
+        start_timestamp: i64,
+        end_timestamp: i64,
+    ) -> Result<Vec<FundingRateRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM funding_rates
+            WHERE venue = $1 AND kind = $2 AND market = $3
+              AND timestamp >= $4 AND timestamp <= $5
+            ORDER BY timestamp ASC
+            "#,
+        )
+        .bind(venue.as_str())
+        .bind(kind.as_str())
+        .bind(market)
+        .bind(start_timestamp)
+        .bind(end_timestamp)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.iter().map(FundingRateRecord::from_row).collect()
+    }
+
+    /// Finds the timestamp of the latest stored rate of a market, to resume
+    /// fetching from there.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn latest_timestamp(
+        &self,
+        venue: RateVenue,
+        kind: RateKind,
+        market: &str,
+    ) -> Result<Option<i64>, sqlx::Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT MAX(timestamp) AS latest FROM funding_rates
+            WHERE venue = $1 AND kind = $2 AND market = $3
+            "#,
+        )
+        .bind(venue.as_str())
+        .bind(kind.as_str())
+        .bind(market)
+        .fetch_one(self.pool.as_ref())
+        .await?;
+        row.try_get("latest")
+    }
+}
@@ -0,0 +1,300 @@
+//! Job queue repository for API-submitted jobs run by worker pools.
+//!
+//! Workers claim queued jobs with `FOR UPDATE SKIP LOCKED`, so any number
+//! of workers, in any number of processes, can share the queue. Running
+//! jobs carry a heartbeat; jobs whose worker stops beating are put back in
+//! the queue until they run out of attempts.
+
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Database record for a queued job.
+#[derive(Debug, Clone)]
+pub struct JobRecord {
+    /// Unique identifier.
+    pub id: Uuid,
+    /// Kind of job, selecting the worker that runs it.
+    pub kind: String,
+    /// Job parameters as JSON.
+    pub payload: serde_json::Value,
+    /// Job status (queued, running, completed, failed, cancelled).
+    pub status: String,
+    /// Priority; higher runs first.
+    pub priority: i32,
+    /// Times the job has been claimed.
+    pub attempts: i32,
+    /// Claims allowed before the job fails.
+    pub max_attempts: i32,
+    /// Worker that claimed the job last.
+    pub worker_id: Option<String>,
+    /// Error of the last failed attempt.
+    pub error: Option<String>,
+    /// Last heartbeat of the running worker.
+    pub heartbeat_at: Option<DateTime<Utc>>,
+    /// When the current attempt started.
+    pub started_at: Option<DateTime<Utc>>,
+    /// When the job finished.
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Record creation timestamp.
+    pub created_at: DateTime<Utc>,
+    /// Record update timestamp.
+    pub updated_at: DateTime<Utc>,
+}
+
+impl JobRecord {
+    /// Creates a JobRecord from a database row.
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            kind: row.try_get("kind")?,
+            payload: row.try_get("payload")?,
+            status: row.try_get("status")?,
+            priority: row.try_get("priority")?,
+            attempts: row.try_get("attempts")?,
+            max_attempts: row.try_get("max_attempts")?,
+            worker_id: row.try_get("worker_id")?,
+            error: row.try_get("error")?,
+            heartbeat_at: row.try_get("heartbeat_at")?,
+            started_at: row.try_get("started_at")?,
+            finished_at: row.try_get("finished_at")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+}
+
+/// Repository for the job queue.
+#[derive(Clone)]
+pub struct JobQueueRepository {
+    pool: Arc<PgPool>,
+}
+
+impl JobQueueRepository {
+    /// Creates a new JobQueueRepository.
+    #[must_use]
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Queues a job.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn enqueue(
+        &self,
+        id: Uuid,
+        kind: &str,
+        payload: serde_json::Value,
+        priority: i32,
+        max_attempts: i32,
+    ) -> Result<JobRecord, sqlx::Error> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO job_queue (id, kind, payload, priority, max_attempts)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(kind)
+        .bind(payload)
+        .bind(priority)
+        .bind(max_attempts.max(1))
+        .fetch_one(self.pool.as_ref())
+        .await?;
+        JobRecord::from_row(&row)
+    }
+
+    /// Finds a job by ID.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_by_id(&self, id: Uuid) -> Result<Option<JobRecord>, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM job_queue WHERE id = $1")
+            .bind(id)
+            .fetch_optional(self.pool.as_ref())
+            .await?;
+        row.as_ref().map(JobRecord::from_row).transpose()
+    }
+
+    /// Claims the next queued job of one of `kinds` for `worker_id`,
+    /// highest priority then oldest first.
+    ///
+    /// Returns `None` when nothing is queued.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn claim(
+        &self,
+        worker_id: &str,
+        kinds: &[&str],
+    ) -> Result<Option<JobRecord>, sqlx::Error> {
+        let kinds: Vec<String> = kinds.iter().map(ToString::to_string).collect();
+        let row = sqlx::query(
+            r#"
+            UPDATE job_queue
+            SET status = 'running', worker_id = $1, attempts = attempts + 1,
+                heartbeat_at = NOW(), started_at = NOW(), updated_at = NOW()
+            WHERE id = (
+                SELECT id FROM job_queue
+                WHERE status = 'queued' AND kind = ANY($2)
+                ORDER BY priority DESC, created_at ASC
+                LIMIT 1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING *
+            "#,
+        )
+        .bind(worker_id)
+        .bind(&kinds)
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+        row.as_ref().map(JobRecord::from_row).transpose()
+    }
+
+    /// Records a heartbeat for a job run by `worker_id` and returns its
+    /// status, so the worker notices cancellation.
+    ///
+    /// Returns `None` if the job is no longer held by the worker, e.g.
+    /// after it was requeued as stale.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn heartbeat(
+        &self,
+        id: Uuid,
+        worker_id: &str,
+    ) -> Result<Option<String>, sqlx::Error> {
+        let row = sqlx::query(
+            r#"
+            UPDATE job_queue SET heartbeat_at = NOW()
+            WHERE id = $1 AND worker_id = $2 AND status IN ('running', 'cancelled')
+            RETURNING status
+            "#,
+        )
+        .bind(id)
+        .bind(worker_id)
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+        row.map(|row| row.try_get("status")).transpose()
+    }
+
+    /// Marks a job run by `worker_id` as completed.
+    ///
+    /// Returns false if the job was cancelled or taken over meanwhile.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn mark_completed(&self, id: Uuid, worker_id: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE job_queue
+            SET status = 'completed', error = NULL, finished_at = NOW(), updated_at = NOW()
+            WHERE id = $1 AND worker_id = $2 AND status = 'running'
+            "#,
+        )
+        .bind(id)
+        .bind(worker_id)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Records a failed attempt of a job run by `worker_id`.
+    ///
+    /// With `retry`, the job is queued again until it runs out of attempts.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn mark_failed(
+        &self,
+        id: Uuid,
+        worker_id: &str,
+        error: &str,
+        retry: bool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE job_queue
+            SET status = CASE WHEN $4 AND attempts < max_attempts THEN 'queued' ELSE 'failed' END,
+                finished_at = CASE WHEN $4 AND attempts < max_attempts THEN NULL ELSE NOW() END,
+                error = $3, heartbeat_at = NULL, updated_at = NOW()
+            WHERE id = $1 AND worker_id = $2 AND status = 'running'
+            "#,
+        )
+        .bind(id)
+        .bind(worker_id)
+        .bind(error)
+        .bind(retry)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Puts a job run by `worker_id` back in the queue without counting
+    /// the attempt, e.g. when the worker shuts down.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn release(&self, id: Uuid, worker_id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE job_queue
+            SET status = 'queued', attempts = GREATEST(attempts - 1, 0),
+                heartbeat_at = NULL, updated_at = NOW()
+            WHERE id = $1 AND worker_id = $2 AND status = 'running'
+            "#,
+        )
+        .bind(id)
+        .bind(worker_id)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Cancels a queued or running job; running jobs stop at their
+    /// worker's next heartbeat.
+    ///
+    /// Returns `None` if the job does not exist or has already finished.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn cancel(&self, id: Uuid) -> Result<Option<JobRecord>, sqlx::Error> {
+        let row = sqlx::query(
+            r#"
+            UPDATE job_queue
+            SET status = 'cancelled', finished_at = NOW(), updated_at = NOW()
+            WHERE id = $1 AND status IN ('queued', 'running')
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+        row.as_ref().map(JobRecord::from_row).transpose()
+    }
+
+    /// Requeues running jobs whose last heartbeat is older than `cutoff`,
+    /// failing those out of attempts, and returns how many were affected.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn requeue_stale(&self, cutoff: DateTime<Utc>) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE job_queue
+            SET status = CASE WHEN attempts < max_attempts THEN 'queued' ELSE 'failed' END,
+                finished_at = CASE WHEN attempts < max_attempts THEN NULL ELSE NOW() END,
+                error = 'Worker stopped responding', heartbeat_at = NULL, updated_at = NOW()
+            WHERE status = 'running' AND heartbeat_at < $1
+            "#,
+        )
+        .bind(cutoff)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -1,16 +1,33 @@
 //! Repository implementations for database persistence.
 //!
 //! This module provides repository patterns for storing and retrieving
-//! simulation data, pool configurations, and price history.
+//! simulation data, pool configurations and watchlist, price history,
+//! backfill jobs, funding rates, alert rules, the job queue and read-only
+//! positions.
 
+mod alert_rule_repository;
+mod backfill_job_repository;
 mod database;
+mod funding_rate_repository;
+mod job_queue_repository;
 mod pool_repository;
+mod position_strategy_repository;
+mod position_tag_repository;
 mod price_repository;
+mod read_only_position_repository;
 mod simulation_repository;
 
+pub use alert_rule_repository::{AlertRuleRecord, AlertRuleRepository};
+pub use backfill_job_repository::{BackfillJobRecord, BackfillJobRepository};
 pub use database::Database;
+pub use funding_rate_repository::{FundingRateRecord, FundingRateRepository};
+pub use job_queue_repository::{JobQueueRepository, JobRecord};
 pub use pool_repository::{PoolRecord, PoolRepository};
+pub use position_strategy_repository::{PositionStrategyRecord, PositionStrategyRepository};
+pub use position_tag_repository::{PositionTagRecord, PositionTagRepository};
 pub use price_repository::{PriceRecord, PriceRepository};
+pub use read_only_position_repository::{ReadOnlyPositionRecord, ReadOnlyPositionRepository};
 pub use simulation_repository::{
-    OptimizationRecord, SimulationRecord, SimulationRepository, SimulationResultRecord,
+    EquityPointRecord, OptimizationPathPointRecord, OptimizationRecord, SimulationRecord,
+    SimulationRepository, SimulationResultRecord,
 };
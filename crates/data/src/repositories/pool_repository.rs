@@ -106,6 +106,30 @@ impl PoolRepository {
         rows.iter().map(PoolRecord::from_row).collect()
     }
 
+    /// Finds all pools trading a pair of mints, in either order.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_by_pair(
+        &self,
+        mint_x: &str,
+        mint_y: &str,
+    ) -> Result<Vec<PoolRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM pools
+            WHERE (token_mint_a = $1 AND token_mint_b = $2)
+               OR (token_mint_a = $2 AND token_mint_b = $1)
+            ORDER BY fee_tier ASC
+            "#,
+        )
+        .bind(mint_x)
+        .bind(mint_y)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.iter().map(PoolRecord::from_row).collect()
+    }
+
     /// Finds all pools.
     ///
     /// # Errors
@@ -171,6 +195,49 @@ impl PoolRepository {
         PoolRecord::from_row(&row)
     }
 
+    /// Adds a pool to the watchlist; returns false if it was already watched.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn watch(&self, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "INSERT INTO pool_watchlist (pool_id) VALUES ($1) ON CONFLICT (pool_id) DO NOTHING",
+        )
+        .bind(id)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Removes a pool from the watchlist.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn unwatch(&self, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM pool_watchlist WHERE pool_id = $1")
+            .bind(id)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Finds all watched pools, oldest first.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_watched(&self) -> Result<Vec<PoolRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT pools.* FROM pools
+            JOIN pool_watchlist ON pool_watchlist.pool_id = pools.id
+            ORDER BY pool_watchlist.created_at ASC
+            "#,
+        )
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.iter().map(PoolRecord::from_row).collect()
+    }
+
     /// Deletes a pool by ID.
     ///
     /// # Errors
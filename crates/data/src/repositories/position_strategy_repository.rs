@@ -0,0 +1,116 @@
+//! Position strategy repository for per-position strategy assignments.
+
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use std::sync::Arc;
+
+/// Database record for a position's strategy assignment.
+#[derive(Debug, Clone)]
+pub struct PositionStrategyRecord {
+    /// On-chain position address.
+    pub position_address: String,
+    /// Assigned strategy ID.
+    pub strategy_id: String,
+    /// Decision parameter overrides as JSON.
+    pub overrides: serde_json::Value,
+    /// Record creation timestamp.
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Record update timestamp.
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl PositionStrategyRecord {
+    /// Creates a PositionStrategyRecord from a database row.
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            position_address: row.try_get("position_address")?,
+            strategy_id: row.try_get("strategy_id")?,
+            overrides: row.try_get("overrides")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+}
+
+/// Repository for position strategy assignments.
+#[derive(Clone)]
+pub struct PositionStrategyRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PositionStrategyRepository {
+    /// Creates a new PositionStrategyRepository.
+    #[must_use]
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Finds the assignment for a position.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_by_position(
+        &self,
+        position_address: &str,
+    ) -> Result<Option<PositionStrategyRecord>, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM position_strategies WHERE position_address = $1")
+            .bind(position_address)
+            .fetch_optional(self.pool.as_ref())
+            .await?;
+        row.as_ref()
+            .map(PositionStrategyRecord::from_row)
+            .transpose()
+    }
+
+    /// Finds all assignments.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_all(&self) -> Result<Vec<PositionStrategyRecord>, sqlx::Error> {
+        let rows = sqlx::query("SELECT * FROM position_strategies ORDER BY position_address")
+            .fetch_all(self.pool.as_ref())
+            .await?;
+        rows.iter().map(PositionStrategyRecord::from_row).collect()
+    }
+
+    /// Assigns a strategy to a position, replacing any existing assignment.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn upsert(
+        &self,
+        position_address: &str,
+        strategy_id: &str,
+        overrides: serde_json::Value,
+    ) -> Result<PositionStrategyRecord, sqlx::Error> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO position_strategies (position_address, strategy_id, overrides)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (position_address) DO UPDATE SET
+                strategy_id = EXCLUDED.strategy_id,
+                overrides = EXCLUDED.overrides,
+                updated_at = NOW()
+            RETURNING *
+            "#,
+        )
+        .bind(position_address)
+        .bind(strategy_id)
+        .bind(&overrides)
+        .fetch_one(self.pool.as_ref())
+        .await?;
+        PositionStrategyRecord::from_row(&row)
+    }
+
+    /// Removes the assignment for a position.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn delete(&self, position_address: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM position_strategies WHERE position_address = $1")
+            .bind(position_address)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
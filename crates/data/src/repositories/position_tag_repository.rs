@@ -0,0 +1,127 @@
+//! Position tag repository for grouping positions by label.
+
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Database record for a single position tag.
+#[derive(Debug, Clone)]
+pub struct PositionTagRecord {
+    /// On-chain position address.
+    pub position_address: String,
+    /// Tag label.
+    pub tag: String,
+    /// Record creation timestamp.
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl PositionTagRecord {
+    /// Creates a PositionTagRecord from a database row.
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            position_address: row.try_get("position_address")?,
+            tag: row.try_get("tag")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
+
+/// Repository for position tags.
+#[derive(Clone)]
+pub struct PositionTagRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PositionTagRepository {
+    /// Creates a new PositionTagRepository.
+    #[must_use]
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Finds the tags on a position.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_by_position(
+        &self,
+        position_address: &str,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        let rows =
+            sqlx::query("SELECT * FROM position_tags WHERE position_address = $1 ORDER BY tag")
+                .bind(position_address)
+                .fetch_all(self.pool.as_ref())
+                .await?;
+        rows.iter()
+            .map(|row| PositionTagRecord::from_row(row).map(|r| r.tag))
+            .collect()
+    }
+
+    /// Finds the addresses of positions carrying a tag.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_positions_by_tag(&self, tag: &str) -> Result<Vec<String>, sqlx::Error> {
+        let rows =
+            sqlx::query("SELECT * FROM position_tags WHERE tag = $1 ORDER BY position_address")
+                .bind(tag)
+                .fetch_all(self.pool.as_ref())
+                .await?;
+        rows.iter()
+            .map(|row| PositionTagRecord::from_row(row).map(|r| r.position_address))
+            .collect()
+    }
+
+    /// Finds all tags grouped by position address.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_all(&self) -> Result<HashMap<String, Vec<String>>, sqlx::Error> {
+        let rows = sqlx::query("SELECT * FROM position_tags ORDER BY position_address, tag")
+            .fetch_all(self.pool.as_ref())
+            .await?;
+
+        let mut tags: HashMap<String, Vec<String>> = HashMap::new();
+        for row in &rows {
+            let record = PositionTagRecord::from_row(row)?;
+            tags.entry(record.position_address)
+                .or_default()
+                .push(record.tag);
+        }
+        Ok(tags)
+    }
+
+    /// Replaces the tags on a position.
+    ///
+    /// # Errors
+    /// Returns an error if the transaction fails.
+    pub async fn set_tags(
+        &self,
+        position_address: &str,
+        tags: &[String],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM position_tags WHERE position_address = $1")
+            .bind(position_address)
+            .execute(&mut *tx)
+            .await?;
+
+        for tag in tags {
+            sqlx::query(
+                r#"
+                INSERT INTO position_tags (position_address, tag)
+                VALUES ($1, $2)
+                ON CONFLICT (position_address, tag) DO NOTHING
+                "#,
+            )
+            .bind(position_address)
+            .bind(tag)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await
+    }
+}
@@ -1,5 +1,6 @@
 //! Price history repository for caching market data.
 
+use crate::timeseries::OhlcvCandle;
 use rust_decimal::Decimal;
 use sqlx::postgres::PgRow;
 use sqlx::{PgPool, Row};
@@ -47,6 +48,19 @@ impl PriceRecord {
             created_at: row.try_get("created_at")?,
         })
     }
+
+    /// Converts the record into a candle; missing volume counts as zero.
+    #[must_use]
+    pub fn to_candle(&self) -> OhlcvCandle {
+        OhlcvCandle::new(
+            u64::try_from(self.timestamp).unwrap_or_default(),
+            self.open_price,
+            self.high_price,
+            self.low_price,
+            self.close_price,
+            self.volume.unwrap_or_default(),
+        )
+    }
 }
 
 /// Repository for price history CRUD operations.
@@ -0,0 +1,70 @@
+//! Read-only position repository for positions the bot only monitors.
+
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use std::sync::Arc;
+
+/// Database record for a read-only position.
+#[derive(Debug, Clone)]
+pub struct ReadOnlyPositionRecord {
+    /// On-chain position address.
+    pub position_address: String,
+    /// When the position was marked read-only.
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl ReadOnlyPositionRecord {
+    /// Creates a ReadOnlyPositionRecord from a database row.
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            position_address: row.try_get("position_address")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
+
+/// Repository for read-only positions.
+#[derive(Clone)]
+pub struct ReadOnlyPositionRepository {
+    pool: Arc<PgPool>,
+}
+
+impl ReadOnlyPositionRepository {
+    /// Creates a new ReadOnlyPositionRepository.
+    #[must_use]
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Finds all read-only positions.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_all(&self) -> Result<Vec<ReadOnlyPositionRecord>, sqlx::Error> {
+        let rows = sqlx::query("SELECT * FROM read_only_positions ORDER BY position_address")
+            .fetch_all(self.pool.as_ref())
+            .await?;
+        rows.iter().map(ReadOnlyPositionRecord::from_row).collect()
+    }
+
+    /// Marks a position as read-only, or as managed with `false`.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn set(&self, position_address: &str, read_only: bool) -> Result<(), sqlx::Error> {
+        let query = if read_only {
+            r#"
+            INSERT INTO read_only_positions (position_address)
+            VALUES ($1)
+            ON CONFLICT (position_address) DO NOTHING
+            "#
+        } else {
+            "DELETE FROM read_only_positions WHERE position_address = $1"
+        };
+        sqlx::query(query)
+            .bind(position_address)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 //! Simulation repository for backtest and optimization persistence.
 
+use clmm_lp_domain::metrics::sharpe::SharpeConfig;
 use rust_decimal::Decimal;
 use sqlx::postgres::PgRow;
 use sqlx::{PgPool, Row};
@@ -33,6 +34,8 @@ pub struct SimulationRecord {
     pub fee_rate: Decimal,
     /// Transaction cost per rebalance.
     pub tx_cost: Decimal,
+    /// Optimization whose recommended range this simulation backtested.
+    pub source_optimization_id: Option<Uuid>,
     /// Record creation timestamp.
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
@@ -53,6 +56,7 @@ impl SimulationRecord {
             upper_price: row.try_get("upper_price")?,
             fee_rate: row.try_get("fee_rate")?,
             tx_cost: row.try_get("tx_cost")?,
+            source_optimization_id: row.try_get("source_optimization_id")?,
             created_at: row.try_get("created_at")?,
         })
     }
@@ -89,6 +93,10 @@ pub struct SimulationResultRecord {
     pub vs_hodl: Decimal,
     /// Sharpe ratio if calculated.
     pub sharpe_ratio: Option<Decimal>,
+    /// Annual risk-free rate the Sharpe ratio was computed with.
+    pub risk_free_rate: Option<Decimal>,
+    /// Benchmark the Sharpe ratio was computed against.
+    pub sharpe_benchmark: Option<String>,
     /// Final price at simulation end.
     pub final_price: Decimal,
     /// Record creation timestamp.
@@ -113,6 +121,8 @@ impl SimulationResultRecord {
             hodl_value: row.try_get("hodl_value")?,
             vs_hodl: row.try_get("vs_hodl")?,
             sharpe_ratio: row.try_get("sharpe_ratio")?,
+            risk_free_rate: row.try_get("risk_free_rate")?,
+            sharpe_benchmark: row.try_get("sharpe_benchmark")?,
             final_price: row.try_get("final_price")?,
             created_at: row.try_get("created_at")?,
         })
@@ -148,6 +158,10 @@ pub struct OptimizationRecord {
     pub expected_il: Decimal,
     /// Sharpe ratio if calculated.
     pub sharpe_ratio: Option<Decimal>,
+    /// Annual risk-free rate the Sharpe ratio was computed with.
+    pub risk_free_rate: Option<Decimal>,
+    /// Benchmark the Sharpe ratio was computed against.
+    pub sharpe_benchmark: Option<String>,
     /// Number of simulations run.
     pub simulations_run: i32,
     /// Record creation timestamp.
@@ -171,10 +185,77 @@ impl OptimizationRecord {
             expected_fees: row.try_get("expected_fees")?,
             expected_il: row.try_get("expected_il")?,
             sharpe_ratio: row.try_get("sharpe_ratio")?,
+            risk_free_rate: row.try_get("risk_free_rate")?,
+            sharpe_benchmark: row.try_get("sharpe_benchmark")?,
             simulations_run: row.try_get("simulations_run")?,
             created_at: row.try_get("created_at")?,
         })
     }
+
+    /// Returns the value the optimization maximized, based on its objective
+    /// type ('pnl', 'fees' or 'sharpe').
+    #[must_use]
+    pub fn objective_value(&self) -> Option<Decimal> {
+        match self.objective_type.as_str() {
+            "pnl" => Some(self.expected_pnl),
+            "fees" => Some(self.expected_fees),
+            "sharpe" => self.sharpe_ratio,
+            _ => None,
+        }
+    }
+}
+
+/// Database record for one point of a simulation equity curve.
+#[derive(Debug, Clone)]
+pub struct EquityPointRecord {
+    /// Associated simulation ID.
+    pub simulation_id: Uuid,
+    /// Step index within the simulation.
+    pub step: i32,
+    /// Timestamp in seconds.
+    pub timestamp: i64,
+    /// Portfolio value at this step.
+    pub value: Decimal,
+}
+
+impl EquityPointRecord {
+    /// Creates an EquityPointRecord from a database row.
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            simulation_id: row.try_get("simulation_id")?,
+            step: row.try_get("step")?,
+            timestamp: row.try_get("timestamp")?,
+            value: row.try_get("value")?,
+        })
+    }
+}
+
+/// Database record for one step of a kept optimization Monte Carlo path.
+#[derive(Debug, Clone)]
+pub struct OptimizationPathPointRecord {
+    /// Associated optimization ID.
+    pub optimization_id: Uuid,
+    /// Percentile of the path's final net PnL (0-100).
+    pub percentile: i16,
+    /// Step index within the path.
+    pub step: i32,
+    /// Simulated price at this step.
+    pub price: Decimal,
+    /// Net PnL after this step.
+    pub pnl: Decimal,
+}
+
+impl OptimizationPathPointRecord {
+    /// Creates an OptimizationPathPointRecord from a database row.
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            optimization_id: row.try_get("optimization_id")?,
+            percentile: row.try_get("percentile")?,
+            step: row.try_get("step")?,
+            price: row.try_get("price")?,
+            pnl: row.try_get("pnl")?,
+        })
+    }
 }
 
 /// Repository for simulation CRUD operations.
@@ -209,13 +290,15 @@ impl SimulationRepository {
         upper_price: Decimal,
         fee_rate: Decimal,
         tx_cost: Decimal,
+        source_optimization_id: Option<Uuid>,
     ) -> Result<SimulationRecord, sqlx::Error> {
         let row = sqlx::query(
             r#"
             INSERT INTO simulations (id, pool_id, strategy_type, strategy_config, 
                                     start_timestamp, end_timestamp, initial_capital,
-                                    entry_price, lower_price, upper_price, fee_rate, tx_cost)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                                    entry_price, lower_price, upper_price, fee_rate, tx_cost,
+                                    source_optimization_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
             RETURNING *
             "#,
         )
@@ -231,6 +314,7 @@ impl SimulationRepository {
         .bind(upper_price)
         .bind(fee_rate)
         .bind(tx_cost)
+        .bind(source_optimization_id)
         .fetch_one(self.pool.as_ref())
         .await?;
         SimulationRecord::from_row(&row)
@@ -257,6 +341,7 @@ impl SimulationRepository {
         hodl_value: Decimal,
         vs_hodl: Decimal,
         sharpe_ratio: Option<Decimal>,
+        sharpe_config: Option<SharpeConfig>,
         final_price: Decimal,
     ) -> Result<SimulationResultRecord, sqlx::Error> {
         let row = sqlx::query(
@@ -264,8 +349,9 @@ impl SimulationRepository {
             INSERT INTO simulation_results (id, simulation_id, final_value, final_pnl,
                                            total_fees, total_il, final_il_pct, time_in_range_pct,
                                            max_drawdown, rebalance_count, total_rebalance_cost,
-                                           hodl_value, vs_hodl, sharpe_ratio, final_price)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+                                           hodl_value, vs_hodl, sharpe_ratio, risk_free_rate,
+                                           sharpe_benchmark, final_price)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
             RETURNING *
             "#,
         )
@@ -283,6 +369,8 @@ impl SimulationRepository {
         .bind(hodl_value)
         .bind(vs_hodl)
         .bind(sharpe_ratio)
+        .bind(sharpe_config.map(|c| c.risk_free_rate))
+        .bind(sharpe_config.map(|c| c.benchmark.as_str()))
         .bind(final_price)
         .fetch_one(self.pool.as_ref())
         .await?;
@@ -321,6 +409,58 @@ impl SimulationRepository {
             .transpose()
     }
 
+    /// Saves the equity curve of a simulation as `(timestamp, value)` points.
+    ///
+    /// Replaces any curve previously stored for the simulation.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn save_equity_curve(
+        &self,
+        simulation_id: Uuid,
+        points: &[(i64, Decimal)],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM simulation_equity WHERE simulation_id = $1")
+            .bind(simulation_id)
+            .execute(&mut *tx)
+            .await?;
+
+        for (step, (timestamp, value)) in points.iter().enumerate() {
+            sqlx::query(
+                r#"
+                INSERT INTO simulation_equity (simulation_id, step, timestamp, value)
+                VALUES ($1, $2, $3, $4)
+                "#,
+            )
+            .bind(simulation_id)
+            .bind(step as i32)
+            .bind(timestamp)
+            .bind(value)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await
+    }
+
+    /// Finds the equity curve of a simulation, ordered by step.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_equity_curve(
+        &self,
+        simulation_id: Uuid,
+    ) -> Result<Vec<EquityPointRecord>, sqlx::Error> {
+        let rows =
+            sqlx::query("SELECT * FROM simulation_equity WHERE simulation_id = $1 ORDER BY step")
+                .bind(simulation_id)
+                .fetch_all(self.pool.as_ref())
+                .await?;
+        rows.iter().map(EquityPointRecord::from_row).collect()
+    }
+
     /// Finds recent simulations.
     ///
     /// # Errors
@@ -333,6 +473,28 @@ impl SimulationRepository {
         rows.iter().map(SimulationRecord::from_row).collect()
     }
 
+    /// Finds the simulations that backtested an optimization's
+    /// recommendation, oldest first.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_simulations_by_optimization(
+        &self,
+        optimization_id: Uuid,
+    ) -> Result<Vec<SimulationRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM simulations
+            WHERE source_optimization_id = $1
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(optimization_id)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.iter().map(SimulationRecord::from_row).collect()
+    }
+
     /// Saves an optimization result.
     ///
     /// # Errors
@@ -353,6 +515,7 @@ impl SimulationRepository {
         expected_fees: Decimal,
         expected_il: Decimal,
         sharpe_ratio: Option<Decimal>,
+        sharpe_config: Option<SharpeConfig>,
         simulations_run: i32,
     ) -> Result<OptimizationRecord, sqlx::Error> {
         let row = sqlx::query(
@@ -361,8 +524,9 @@ impl SimulationRepository {
                                              end_timestamp, initial_capital, volatility,
                                              recommended_lower, recommended_upper,
                                              expected_pnl, expected_fees, expected_il,
-                                             sharpe_ratio, simulations_run)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+                                             sharpe_ratio, risk_free_rate, sharpe_benchmark,
+                                             simulations_run)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
             RETURNING *
             "#,
         )
@@ -379,12 +543,93 @@ impl SimulationRepository {
         .bind(expected_fees)
         .bind(expected_il)
         .bind(sharpe_ratio)
+        .bind(sharpe_config.map(|c| c.risk_free_rate))
+        .bind(sharpe_config.map(|c| c.benchmark.as_str()))
         .bind(simulations_run)
         .fetch_one(self.pool.as_ref())
         .await?;
         OptimizationRecord::from_row(&row)
     }
 
+    /// Saves the kept Monte Carlo paths of an optimization, each as its
+    /// percentile and `(price, pnl)` points.
+    ///
+    /// Replaces any paths previously stored for the optimization.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn save_optimization_paths(
+        &self,
+        optimization_id: Uuid,
+        paths: &[(i16, Vec<(Decimal, Decimal)>)],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM optimization_paths WHERE optimization_id = $1")
+            .bind(optimization_id)
+            .execute(&mut *tx)
+            .await?;
+
+        for (percentile, points) in paths {
+            for (step, (price, pnl)) in points.iter().enumerate() {
+                sqlx::query(
+                    r#"
+                    INSERT INTO optimization_paths (optimization_id, percentile, step, price, pnl)
+                    VALUES ($1, $2, $3, $4, $5)
+                    "#,
+                )
+                .bind(optimization_id)
+                .bind(percentile)
+                .bind(step as i32)
+                .bind(price)
+                .bind(pnl)
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+
+        tx.commit().await
+    }
+
+    /// Finds the kept Monte Carlo paths of an optimization, ordered by
+    /// percentile and step.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_optimization_paths(
+        &self,
+        optimization_id: Uuid,
+    ) -> Result<Vec<OptimizationPathPointRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM optimization_paths
+            WHERE optimization_id = $1
+            ORDER BY percentile, step
+            "#,
+        )
+        .bind(optimization_id)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.iter()
+            .map(OptimizationPathPointRecord::from_row)
+            .collect()
+    }
+
+    /// Finds an optimization result by ID.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_optimization_by_id(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<OptimizationRecord>, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM optimization_results WHERE id = $1")
+            .bind(id)
+            .fetch_optional(self.pool.as_ref())
+            .await?;
+        row.as_ref().map(OptimizationRecord::from_row).transpose()
+    }
+
     /// Finds recent optimization results.
     ///
     /// # Errors
@@ -401,6 +646,29 @@ impl SimulationRepository {
         rows.iter().map(OptimizationRecord::from_row).collect()
     }
 
+    /// Finds optimizations for a pool and objective type, oldest first.
+    ///
+    /// # Errors
+    /// Returns an error if the query fails.
+    pub async fn find_optimizations_by_pool(
+        &self,
+        pool_id: Uuid,
+        objective_type: &str,
+    ) -> Result<Vec<OptimizationRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM optimization_results
+            WHERE pool_id = $1 AND objective_type = $2
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(pool_id)
+        .bind(objective_type)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.iter().map(OptimizationRecord::from_row).collect()
+    }
+
     /// Deletes a simulation and its results.
     ///
     /// # Errors
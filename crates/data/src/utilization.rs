@@ -0,0 +1,190 @@
+//! Range utilization heatmaps over a pool's tick grid.
+//!
+//! Historical prices are bucketed into the pool's tick grid and the time the
+//! price spent in each bucket is summed, showing where liquidity would have
+//! been utilized before choosing range bounds.
+
+use clmm_lp_domain::math::price_tick::{price_to_tick, tick_to_price};
+use clmm_lp_domain::value_objects::price::Price;
+use rust_decimal::Decimal;
+
+/// Time the price spent within one bucket of the tick grid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UtilizationBucket {
+    /// Lower tick of the bucket, inclusive.
+    pub tick_lower: i32,
+    /// Upper tick of the bucket, exclusive.
+    pub tick_upper: i32,
+    /// Price at the lower tick.
+    pub price_lower: Decimal,
+    /// Price at the upper tick.
+    pub price_upper: Decimal,
+    /// Seconds the price spent in the bucket.
+    pub seconds: u64,
+    /// Share of the total time, in percent.
+    pub share_pct: Decimal,
+}
+
+/// Time spent per bucket of a pool's tick grid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RangeUtilization {
+    /// Width of each bucket in ticks, a multiple of the tick spacing.
+    pub bucket_ticks: i32,
+    /// Total seconds covered.
+    pub total_seconds: u64,
+    /// Buckets from the lowest price up, including empty buckets between
+    /// visited ones.
+    pub buckets: Vec<UtilizationBucket>,
+}
+
+impl RangeUtilization {
+    /// Builds the heatmap from `(timestamp, price)` points ordered oldest
+    /// first, with prices in whole tokens.
+    ///
+    /// Each point holds its price until the next one; the last point holds
+    /// it for as long as the interval before it. Buckets are the smallest
+    /// multiple of `tick_spacing` keeping the grid within `max_buckets`.
+    /// Points with a non-positive price are skipped.
+    #[must_use]
+    pub fn build(
+        points: &[(u64, Decimal)],
+        tick_spacing: i32,
+        max_buckets: usize,
+        decimals_a: u8,
+        decimals_b: u8,
+    ) -> Self {
+        let spacing = tick_spacing.max(1);
+        let held: Vec<(i32, u64)> = points
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (timestamp, price))| {
+                let raw = Price::new(*price).to_raw(decimals_a, decimals_b);
+                let tick = price_to_tick(raw).ok()?;
+                let seconds = match points.get(i + 1) {
+                    Some((next, _)) => next.saturating_sub(*timestamp),
+                    None if i > 0 => timestamp.saturating_sub(points[i - 1].0),
+                    None => 0,
+                };
+                Some((tick, seconds))
+            })
+            .collect();
+
+        let (Some(min_tick), Some(max_tick)) = (
+            held.iter().map(|(tick, _)| *tick).min(),
+            held.iter().map(|(tick, _)| *tick).max(),
+        ) else {
+            return Self {
+                bucket_ticks: spacing,
+                total_seconds: 0,
+                buckets: Vec::new(),
+            };
+        };
+
+        // Widen buckets by whole tick spacings until the grid fits
+        let spacings =
+            i64::from(max_tick.div_euclid(spacing)) - i64::from(min_tick.div_euclid(spacing)) + 1;
+        let n = max_buckets.max(1) as i64;
+        let per_bucket = ((spacings + n - 1) / n).max(1);
+        let bucket_ticks = i32::try_from(i64::from(spacing) * per_bucket).unwrap_or(i32::MAX);
+
+        let first = min_tick.div_euclid(bucket_ticks);
+        let count = usize::try_from(max_tick.div_euclid(bucket_ticks) - first + 1).unwrap_or(0);
+        let mut seconds = vec![0u64; count];
+        for (tick, held_for) in &held {
+            let index = usize::try_from(tick.div_euclid(bucket_ticks) - first).unwrap_or(0);
+            seconds[index] += held_for;
+        }
+        let total_seconds: u64 = seconds.iter().sum();
+
+        let price_at = |tick: i32| {
+            let raw = tick_to_price(tick).unwrap_or_default();
+            Price::from_raw(raw, decimals_a, decimals_b).value
+        };
+        let buckets = seconds
+            .into_iter()
+            .enumerate()
+            .map(|(i, seconds)| {
+                let tick_lower = (first + i as i32) * bucket_ticks;
+                let tick_upper = tick_lower + bucket_ticks;
+                UtilizationBucket {
+                    tick_lower,
+                    tick_upper,
+                    price_lower: price_at(tick_lower),
+                    price_upper: price_at(tick_upper),
+                    seconds,
+                    share_pct: if total_seconds == 0 {
+                        Decimal::ZERO
+                    } else {
+                        Decimal::from(seconds) / Decimal::from(total_seconds) * Decimal::ONE_HUNDRED
+                    },
+                }
+            })
+            .collect();
+
+        Self {
+            bucket_ticks,
+            total_seconds,
+            buckets,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn at_tick(tick: i32) -> Decimal {
+        tick_to_price(tick).unwrap()
+    }
+
+    #[test]
+    fn test_sums_time_per_tick_bucket() {
+        let points = [(0, at_tick(0)), (60, at_tick(2)), (180, at_tick(0))];
+
+        let utilization = RangeUtilization::build(&points, 1, 100, 6, 6);
+
+        assert_eq!(utilization.bucket_ticks, 1);
+        assert_eq!(utilization.total_seconds, 300);
+        let seconds: Vec<u64> = utilization.buckets.iter().map(|b| b.seconds).collect();
+        // The last point holds its price for the previous interval
+        assert_eq!(seconds, vec![180, 0, 120]);
+        assert_eq!(utilization.buckets[0].tick_lower, 0);
+        assert_eq!(utilization.buckets[2].tick_upper, 3);
+        assert_eq!(utilization.buckets[0].share_pct, dec!(60));
+    }
+
+    #[test]
+    fn test_widens_buckets_to_fit_max() {
+        let points = [(0, at_tick(-5)), (10, at_tick(94))];
+
+        let utilization = RangeUtilization::build(&points, 4, 5, 6, 6);
+
+        // 26 spacings of 4 ticks fit 5 buckets of 6 spacings
+        assert_eq!(utilization.bucket_ticks, 24);
+        assert_eq!(utilization.buckets.len(), 5);
+        assert_eq!(utilization.buckets[0].tick_lower, -24);
+        assert_eq!(utilization.buckets[0].seconds, 10);
+        assert_eq!(utilization.buckets[4].seconds, 10);
+    }
+
+    #[test]
+    fn test_buckets_follow_raw_tick_grid() {
+        // A SOL/USDC-like pair whose raw prices are far below 1
+        let points = [(0, dec!(150)), (3600, dec!(151))];
+
+        let utilization = RangeUtilization::build(&points, 64, 50, 9, 6);
+
+        assert_eq!(utilization.buckets[0].tick_lower % 64, 0);
+        assert!(utilization.buckets[0].price_lower <= dec!(150));
+        let last = utilization.buckets.last().unwrap();
+        assert!(last.price_upper > dec!(151));
+    }
+
+    #[test]
+    fn test_empty_or_invalid_prices() {
+        let utilization = RangeUtilization::build(&[(0, Decimal::ZERO)], 8, 10, 6, 6);
+        assert!(utilization.buckets.is_empty());
+        assert_eq!(utilization.total_seconds, 0);
+    }
+}
@@ -0,0 +1,231 @@
+//! Out-of-sample validation of optimization recommendations.
+//!
+//! Simulations that backtested an optimization's recommended range are
+//! linked to it. Their realized returns are compared with the returns the
+//! optimizer expected, both as a percentage of initial capital so runs with
+//! a different capital than the optimization stay comparable.
+
+use crate::comparison::SavedSimulation;
+use crate::repositories::{OptimizationRecord, SimulationRepository};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+/// A simulation that backtested a recommendation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationRun {
+    /// Simulation ID.
+    pub simulation_id: Uuid,
+    /// When the simulation was stored.
+    pub created_at: DateTime<Utc>,
+    /// Realized net PnL as a percentage of initial capital.
+    pub return_pct: Option<Decimal>,
+    /// Realized fees as a percentage of initial capital.
+    pub fees_pct: Option<Decimal>,
+    /// Realized minus expected return, in percentage points.
+    pub return_error_pct: Option<Decimal>,
+    /// Time in range as percentage.
+    pub time_in_range_pct: Decimal,
+}
+
+/// An optimization with the simulations that validated it.
+#[derive(Debug, Clone)]
+pub struct OptimizationValidation {
+    /// Stored optimization.
+    pub optimization: OptimizationRecord,
+    /// Expected net PnL as a percentage of initial capital.
+    pub expected_return_pct: Option<Decimal>,
+    /// Expected fees as a percentage of initial capital.
+    pub expected_fees_pct: Option<Decimal>,
+    /// Validating simulations, oldest first.
+    pub runs: Vec<ValidationRun>,
+    /// Mean realized minus expected return over the runs, in percentage
+    /// points; `None` without comparable runs.
+    pub mean_return_error_pct: Option<Decimal>,
+}
+
+impl OptimizationValidation {
+    /// Loads an optimization with the simulations linked to it.
+    ///
+    /// Returns `None` if the optimization does not exist. Linked simulations
+    /// without stored results are left out.
+    ///
+    /// # Errors
+    /// Returns an error if a query fails.
+    pub async fn load(
+        repository: &SimulationRepository,
+        optimization_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let Some(optimization) = repository.find_optimization_by_id(optimization_id).await? else {
+            return Ok(None);
+        };
+
+        let mut simulations = Vec::new();
+        for simulation in repository
+            .find_simulations_by_optimization(optimization_id)
+            .await?
+        {
+            if let Some(saved) = SavedSimulation::load(repository, simulation.id).await? {
+                simulations.push(saved);
+            }
+        }
+
+        Ok(Some(Self::build(optimization, &simulations)))
+    }
+
+    /// Compares simulations against the optimization's expectations.
+    #[must_use]
+    pub fn build(optimization: OptimizationRecord, simulations: &[SavedSimulation]) -> Self {
+        let expected_return_pct = pct_of(optimization.expected_pnl, optimization.initial_capital);
+        let expected_fees_pct = pct_of(optimization.expected_fees, optimization.initial_capital);
+
+        let mut runs: Vec<ValidationRun> = simulations
+            .iter()
+            .map(|saved| {
+                let return_pct = saved.return_pct();
+                ValidationRun {
+                    simulation_id: saved.simulation.id,
+                    created_at: saved.simulation.created_at,
+                    return_pct,
+                    fees_pct: pct_of(saved.result.total_fees, saved.simulation.initial_capital),
+                    return_error_pct: return_pct
+                        .zip(expected_return_pct)
+                        .map(|(realized, expected)| realized - expected),
+                    time_in_range_pct: saved.result.time_in_range_pct,
+                }
+            })
+            .collect();
+        runs.sort_by_key(|r| r.created_at);
+
+        let errors: Vec<Decimal> = runs.iter().filter_map(|r| r.return_error_pct).collect();
+        let mean_return_error_pct = (!errors.is_empty())
+            .then(|| errors.iter().sum::<Decimal>() / Decimal::from(errors.len()));
+
+        Self {
+            optimization,
+            expected_return_pct,
+            expected_fees_pct,
+            runs,
+            mean_return_error_pct,
+        }
+    }
+}
+
+/// `value` as a percentage of `capital`, if `capital` is non-zero.
+fn pct_of(value: Decimal, capital: Decimal) -> Option<Decimal> {
+    if capital.is_zero() {
+        return None;
+    }
+    Some(value / capital * Decimal::ONE_HUNDRED)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::{SimulationRecord, SimulationResultRecord};
+    use chrono::TimeZone;
+    use rust_decimal_macros::dec;
+
+    fn optimization(capital: Decimal, expected_pnl: Decimal) -> OptimizationRecord {
+        OptimizationRecord {
+            id: Uuid::new_v4(),
+            pool_id: None,
+            objective_type: "pnl".to_string(),
+            start_timestamp: 0,
+            end_timestamp: 100,
+            initial_capital: capital,
+            volatility: dec!(0.5),
+            recommended_lower: dec!(90),
+            recommended_upper: dec!(110),
+            expected_pnl,
+            expected_fees: dec!(20),
+            expected_il: dec!(-5),
+            sharpe_ratio: None,
+            risk_free_rate: None,
+            sharpe_benchmark: None,
+            simulations_run: 100,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn saved(source: Uuid, day: u32, capital: Decimal, pnl: Decimal) -> SavedSimulation {
+        let id = Uuid::new_v4();
+        SavedSimulation {
+            simulation: SimulationRecord {
+                id,
+                pool_id: None,
+                strategy_type: "static".to_string(),
+                strategy_config: serde_json::Value::Null,
+                start_timestamp: 0,
+                end_timestamp: 100,
+                initial_capital: capital,
+                entry_price: dec!(100),
+                lower_price: dec!(90),
+                upper_price: dec!(110),
+                fee_rate: dec!(0.003),
+                tx_cost: dec!(1),
+                source_optimization_id: Some(source),
+                created_at: Utc.with_ymd_and_hms(2025, 1, day, 0, 0, 0).unwrap(),
+            },
+            result: SimulationResultRecord {
+                id: Uuid::new_v4(),
+                simulation_id: id,
+                final_value: capital + pnl,
+                final_pnl: pnl,
+                total_fees: dec!(10),
+                total_il: dec!(-2),
+                final_il_pct: dec!(-0.5),
+                time_in_range_pct: dec!(80),
+                max_drawdown: dec!(5),
+                rebalance_count: 0,
+                total_rebalance_cost: Decimal::ZERO,
+                hodl_value: capital,
+                vs_hodl: pnl,
+                sharpe_ratio: None,
+                risk_free_rate: None,
+                sharpe_benchmark: None,
+                final_price: dec!(100),
+                created_at: Utc::now(),
+            },
+            equity: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_compares_returns_across_capital() {
+        // Expected 5% return on 1000
+        let optimization = optimization(dec!(1000), dec!(50));
+        let source = optimization.id;
+        let simulations = [
+            saved(source, 2, dec!(2000), dec!(60)),
+            saved(source, 1, dec!(500), dec!(40)),
+        ];
+
+        let validation = OptimizationValidation::build(optimization, &simulations);
+
+        assert_eq!(validation.expected_return_pct, Some(dec!(5)));
+        assert_eq!(validation.expected_fees_pct, Some(dec!(2)));
+        // Oldest first: 8% then 3%
+        assert_eq!(validation.runs[0].return_pct, Some(dec!(8)));
+        assert_eq!(validation.runs[0].return_error_pct, Some(dec!(3)));
+        assert_eq!(validation.runs[0].fees_pct, Some(dec!(2)));
+        assert_eq!(validation.runs[1].return_error_pct, Some(dec!(-2)));
+        assert_eq!(validation.mean_return_error_pct, Some(dec!(0.5)));
+    }
+
+    #[test]
+    fn test_no_runs_or_capital_leaves_errors_empty() {
+        let validation = OptimizationValidation::build(optimization(dec!(1000), dec!(50)), &[]);
+        assert!(validation.runs.is_empty());
+        assert_eq!(validation.mean_return_error_pct, None);
+
+        let optimization = optimization(Decimal::ZERO, dec!(50));
+        let source = optimization.id;
+        let validation =
+            OptimizationValidation::build(optimization, &[saved(source, 1, dec!(1000), dec!(10))]);
+        assert_eq!(validation.expected_return_pct, None);
+        assert_eq!(validation.runs[0].return_pct, Some(dec!(1)));
+        assert_eq!(validation.runs[0].return_error_pct, None);
+        assert_eq!(validation.mean_return_error_pct, None);
+    }
+}
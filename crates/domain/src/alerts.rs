@@ -0,0 +1,150 @@
+//! Alert severities, types and rule conditions.
+//!
+//! These are shared by the alerting engine and the API models, so they live
+//! here rather than with the execution layer that raises alerts.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Alert severity level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AlertLevel {
+    /// Informational alert.
+    Info,
+    /// Warning alert.
+    Warning,
+    /// Critical alert requiring immediate attention.
+    Critical,
+}
+
+impl AlertLevel {
+    /// Returns the emoji for this alert level.
+    #[must_use]
+    pub fn emoji(&self) -> &'static str {
+        match self {
+            Self::Info => "ℹ️",
+            Self::Warning => "⚠️",
+            Self::Critical => "🚨",
+        }
+    }
+
+    /// Returns the color code for this alert level.
+    #[must_use]
+    pub fn color(&self) -> &'static str {
+        match self {
+            Self::Info => "blue",
+            Self::Warning => "yellow",
+            Self::Critical => "red",
+        }
+    }
+}
+
+/// Type of alert.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AlertType {
+    /// Position exited its price range.
+    RangeExit,
+    /// Position re-entered its price range.
+    RangeEntry,
+    /// Impermanent loss exceeded threshold.
+    ILThreshold,
+    /// PnL target reached.
+    PnLTarget,
+    /// Fees earned milestone.
+    FeesMilestone,
+    /// Position needs rebalancing.
+    RebalanceNeeded,
+    /// Position health score degraded.
+    HealthDegraded,
+    /// Price left the band within which closing breaks even.
+    BreakevenBreached,
+    /// Position range is misaligned with the latest optimizer recommendation.
+    RangeAdvice,
+    /// System error occurred.
+    SystemError,
+    /// Connection issue.
+    ConnectionIssue,
+    /// Runtime configuration was changed.
+    ConfigChanged,
+    /// Pool price diverged from an independent oracle.
+    PriceDivergence,
+    /// Scheduled summary of the day's activity.
+    DailyReport,
+    /// Executions halted on a portfolio drawdown.
+    DrawdownHalt,
+    /// Abnormal change in a pool's liquidity, volume or fee tier.
+    PoolAnomaly,
+    /// Custom alert.
+    Custom(String),
+}
+
+impl AlertType {
+    /// Returns a human-readable name for this alert type.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            Self::RangeExit => "Range Exit",
+            Self::RangeEntry => "Range Entry",
+            Self::ILThreshold => "IL Threshold",
+            Self::PnLTarget => "PnL Target",
+            Self::FeesMilestone => "Fees Milestone",
+            Self::RebalanceNeeded => "Rebalance Needed",
+            Self::HealthDegraded => "Health Degraded",
+            Self::BreakevenBreached => "Break-even Breached",
+            Self::RangeAdvice => "Range Advice",
+            Self::SystemError => "System Error",
+            Self::ConnectionIssue => "Connection Issue",
+            Self::ConfigChanged => "Config Changed",
+            Self::PriceDivergence => "Price Divergence",
+            Self::DailyReport => "Daily Report",
+            Self::DrawdownHalt => "Drawdown Halt",
+            Self::PoolAnomaly => "Pool Anomaly",
+            Self::Custom(name) => name,
+        }
+    }
+}
+
+/// Condition for triggering an alert.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RuleCondition {
+    /// Position exits range.
+    RangeExit,
+    /// Position enters range.
+    RangeEntry,
+    /// IL exceeds threshold.
+    ILExceeds(Decimal),
+    /// PnL exceeds threshold (positive).
+    PnLExceeds(Decimal),
+    /// PnL below threshold (negative).
+    PnLBelow(Decimal),
+    /// Fees exceed threshold.
+    FeesExceed(Decimal),
+    /// Time since last rebalance exceeds hours.
+    TimeSinceRebalance(u64),
+    /// Health score below threshold (0-100).
+    HealthBelow(Decimal),
+    /// Price outside the break-even band of the position.
+    OutsideBreakeven,
+    /// Compound condition (AND).
+    And(Box<RuleCondition>, Box<RuleCondition>),
+    /// Compound condition (OR).
+    Or(Box<RuleCondition>, Box<RuleCondition>),
+}
+
+impl RuleCondition {
+    /// Alert type a rule with this condition reports by default.
+    #[must_use]
+    pub fn default_alert_type(&self) -> AlertType {
+        match self {
+            Self::RangeExit => AlertType::RangeExit,
+            Self::RangeEntry => AlertType::RangeEntry,
+            Self::ILExceeds(_) => AlertType::ILThreshold,
+            Self::PnLExceeds(_) | Self::PnLBelow(_) => AlertType::PnLTarget,
+            Self::FeesExceed(_) => AlertType::FeesMilestone,
+            Self::TimeSinceRebalance(_) => AlertType::RebalanceNeeded,
+            Self::HealthBelow(_) => AlertType::HealthDegraded,
+            Self::OutsideBreakeven => AlertType::BreakevenBreached,
+            Self::And(..) | Self::Or(..) => AlertType::Custom("Compound".to_string()),
+        }
+    }
+}
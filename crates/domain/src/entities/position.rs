@@ -1,5 +1,6 @@
 use crate::enums::PositionStatus;
 use crate::value_objects::{amount::Amount, price_range::PriceRange};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -8,7 +9,7 @@ use uuid::Uuid;
 pub struct PositionId(pub Uuid);
 
 /// Represents a liquidity position.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Position {
     /// The unique identifier of the position.
     pub id: PositionId,
@@ -37,3 +38,305 @@ pub struct Position {
     /// The status of the position.
     pub status: PositionStatus,
 }
+
+impl Position {
+    /// Opens a position with an initial deposit.
+    ///
+    /// The deposit sets the token decimals every later amount must match.
+    pub fn new(
+        pool_address: impl Into<String>,
+        owner_address: impl Into<String>,
+        amount_a: Amount,
+        amount_b: Amount,
+        liquidity: u128,
+        opened_at: u64,
+    ) -> Self {
+        Self {
+            id: PositionId(Uuid::new_v4()),
+            pool_address: pool_address.into(),
+            owner_address: owner_address.into(),
+            liquidity_amount: liquidity,
+            deposited_amount_a: amount_a,
+            deposited_amount_b: amount_b,
+            current_amount_a: amount_a,
+            current_amount_b: amount_b,
+            unclaimed_fees_a: Amount::zero(amount_a.decimals),
+            unclaimed_fees_b: Amount::zero(amount_b.decimals),
+            range: None,
+            opened_at,
+            status: PositionStatus::Open,
+        }
+    }
+
+    /// Sets the price range of the position.
+    #[must_use]
+    pub fn with_range(mut self, range: PriceRange) -> Self {
+        self.range = Some(range);
+        self
+    }
+
+    /// Checks the invariants of the position.
+    ///
+    /// All token A amounts must share decimals, as must all token B amounts,
+    /// and the range, if any, must be positive with its lower bound below
+    /// its upper bound.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        let decimals_a = self.deposited_amount_a.decimals;
+        if self.current_amount_a.decimals != decimals_a
+            || self.unclaimed_fees_a.decimals != decimals_a
+        {
+            return Err("Token A amounts have inconsistent decimals");
+        }
+        let decimals_b = self.deposited_amount_b.decimals;
+        if self.current_amount_b.decimals != decimals_b
+            || self.unclaimed_fees_b.decimals != decimals_b
+        {
+            return Err("Token B amounts have inconsistent decimals");
+        }
+        if let Some(range) = &self.range {
+            if range.lower_price.value <= Decimal::ZERO {
+                return Err("Range prices must be positive");
+            }
+            if range.lower_price >= range.upper_price {
+                return Err("Invalid range");
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns true unless the position is closed.
+    #[must_use]
+    pub fn is_open(&self) -> bool {
+        self.status != PositionStatus::Closed
+    }
+
+    /// Adds liquidity and the tokens backing it.
+    pub fn deposit(
+        &mut self,
+        amount_a: Amount,
+        amount_b: Amount,
+        liquidity: u128,
+    ) -> Result<(), &'static str> {
+        self.ensure_open()?;
+        let deposited_a = self
+            .deposited_amount_a
+            .checked_add(amount_a)
+            .ok_or("Invalid token A deposit")?;
+        let deposited_b = self
+            .deposited_amount_b
+            .checked_add(amount_b)
+            .ok_or("Invalid token B deposit")?;
+        let current_a = self
+            .current_amount_a
+            .checked_add(amount_a)
+            .ok_or("Invalid token A deposit")?;
+        let current_b = self
+            .current_amount_b
+            .checked_add(amount_b)
+            .ok_or("Invalid token B deposit")?;
+        let liquidity = self
+            .liquidity_amount
+            .checked_add(liquidity)
+            .ok_or("Liquidity overflow")?;
+
+        self.deposited_amount_a = deposited_a;
+        self.deposited_amount_b = deposited_b;
+        self.current_amount_a = current_a;
+        self.current_amount_b = current_b;
+        self.liquidity_amount = liquidity;
+        Ok(())
+    }
+
+    /// Removes liquidity and the tokens it releases.
+    pub fn withdraw(
+        &mut self,
+        amount_a: Amount,
+        amount_b: Amount,
+        liquidity: u128,
+    ) -> Result<(), &'static str> {
+        self.ensure_open()?;
+        let current_a = self
+            .current_amount_a
+            .checked_sub(amount_a)
+            .ok_or("Invalid token A withdrawal")?;
+        let current_b = self
+            .current_amount_b
+            .checked_sub(amount_b)
+            .ok_or("Invalid token B withdrawal")?;
+        let liquidity = self
+            .liquidity_amount
+            .checked_sub(liquidity)
+            .ok_or("Withdrawal exceeds position liquidity")?;
+
+        self.current_amount_a = current_a;
+        self.current_amount_b = current_b;
+        self.liquidity_amount = liquidity;
+        Ok(())
+    }
+
+    /// Accrues swap fees to the unclaimed balances.
+    pub fn accrue_fees(&mut self, fees_a: Amount, fees_b: Amount) -> Result<(), &'static str> {
+        self.ensure_open()?;
+        let unclaimed_a = self
+            .unclaimed_fees_a
+            .checked_add(fees_a)
+            .ok_or("Invalid token A fees")?;
+        let unclaimed_b = self
+            .unclaimed_fees_b
+            .checked_add(fees_b)
+            .ok_or("Invalid token B fees")?;
+
+        self.unclaimed_fees_a = unclaimed_a;
+        self.unclaimed_fees_b = unclaimed_b;
+        Ok(())
+    }
+
+    /// Collects the unclaimed fees, returning them and resetting the
+    /// balances to zero.
+    pub fn collect_fees(&mut self) -> (Amount, Amount) {
+        let fees = (self.unclaimed_fees_a, self.unclaimed_fees_b);
+        self.unclaimed_fees_a = Amount::zero(fees.0.decimals);
+        self.unclaimed_fees_b = Amount::zero(fees.1.decimals);
+        fees
+    }
+
+    /// Marks the position as in or out of range.
+    pub fn set_in_range(&mut self, in_range: bool) -> Result<(), &'static str> {
+        let status = if in_range {
+            PositionStatus::Open
+        } else {
+            PositionStatus::OutOfRange
+        };
+        if self.status == status {
+            return Ok(());
+        }
+        self.transition_to(status)
+    }
+
+    /// Closes the position. Closed positions accept no further changes.
+    pub fn close(&mut self) -> Result<(), &'static str> {
+        self.transition_to(PositionStatus::Closed)
+    }
+
+    /// Moves the position to `status` if the transition is allowed.
+    fn transition_to(&mut self, status: PositionStatus) -> Result<(), &'static str> {
+        if !self.status.can_transition_to(status) {
+            return Err("Invalid position status transition");
+        }
+        self.status = status;
+        Ok(())
+    }
+
+    /// Fails if the position is closed.
+    fn ensure_open(&self) -> Result<(), &'static str> {
+        if self.is_open() {
+            Ok(())
+        } else {
+            Err("Position is closed")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_objects::price::Price;
+    use rust_decimal_macros::dec;
+
+    fn position() -> Position {
+        Position::new(
+            "pool",
+            "owner",
+            Amount::from_decimal(dec!(10), 9),
+            Amount::from_decimal(dec!(1000), 6),
+            5_000,
+            1_700_000_000,
+        )
+        .with_range(PriceRange::new(Price::new(dec!(90)), Price::new(dec!(110))))
+    }
+
+    #[test]
+    fn test_position_lifecycle() {
+        let mut position = position();
+        assert!(position.validate().is_ok());
+        assert_eq!(position.unclaimed_fees_a, Amount::zero(9));
+
+        position
+            .deposit(
+                Amount::from_decimal(dec!(1), 9),
+                Amount::from_decimal(dec!(100), 6),
+                500,
+            )
+            .unwrap();
+        assert_eq!(position.liquidity_amount, 5_500);
+        assert_eq!(position.deposited_amount_b.to_decimal(), dec!(1100));
+
+        position
+            .withdraw(
+                Amount::from_decimal(dec!(2), 9),
+                Amount::from_decimal(dec!(200), 6),
+                1_000,
+            )
+            .unwrap();
+        assert_eq!(position.current_amount_a.to_decimal(), dec!(9));
+        assert_eq!(position.deposited_amount_a.to_decimal(), dec!(11));
+        assert!(
+            position
+                .withdraw(Amount::zero(9), Amount::zero(6), 10_000)
+                .is_err()
+        );
+
+        position
+            .accrue_fees(Amount::from_decimal(dec!(0.1), 9), Amount::zero(6))
+            .unwrap();
+        let (fees_a, _) = position.collect_fees();
+        assert_eq!(fees_a.to_decimal(), dec!(0.1));
+        assert!(position.unclaimed_fees_a.is_zero());
+
+        position.set_in_range(false).unwrap();
+        assert_eq!(position.status, PositionStatus::OutOfRange);
+        position.close().unwrap();
+        assert!(!position.is_open());
+        assert!(position.close().is_err());
+        assert!(position.set_in_range(true).is_err());
+        assert!(
+            position
+                .accrue_fees(Amount::zero(9), Amount::zero(6))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_position_invariants() {
+        let mut position = position();
+
+        // Amounts in other decimals are rejected and leave the position intact
+        assert!(
+            position
+                .deposit(Amount::from_decimal(dec!(1), 6), Amount::zero(6), 1)
+                .is_err()
+        );
+        assert_eq!(position.liquidity_amount, 5_000);
+
+        position.unclaimed_fees_b = Amount::zero(9);
+        assert!(position.validate().is_err());
+
+        let inverted = Position::new("pool", "owner", Amount::zero(9), Amount::zero(6), 0, 0)
+            .with_range(PriceRange::new(Price::new(dec!(110)), Price::new(dec!(90))));
+        assert!(inverted.validate().is_err());
+    }
+
+    #[test]
+    fn test_position_serde_round_trip() {
+        let mut position = position();
+        position
+            .accrue_fees(Amount::from_decimal(dec!(0.5), 9), Amount::zero(6))
+            .unwrap();
+        position.set_in_range(false).unwrap();
+
+        let json = serde_json::to_string(&position).unwrap();
+        let restored: Position = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, position);
+        assert!(restored.validate().is_ok());
+    }
+}
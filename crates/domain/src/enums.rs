@@ -37,6 +37,17 @@ pub enum PositionStatus {
     OutOfRange,
 }
 
+impl PositionStatus {
+    /// Returns true if a position in this status can move to `next`.
+    ///
+    /// Open and out-of-range positions move freely between each other and
+    /// can be closed; closed positions are final.
+    #[must_use]
+    pub fn can_transition_to(self, next: Self) -> bool {
+        self != Self::Closed && self != next
+    }
+}
+
 /// Objectives for optimization.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum OptimizationObjective {
@@ -3,6 +3,8 @@
 /// Prelude module for convenient imports.
 pub mod prelude;
 
+/// Alert severities, types and rule conditions.
+pub mod alerts;
 pub mod entities;
 /// Enumerations used across the domain.
 pub mod enums;
@@ -0,0 +1,101 @@
+//! Capital efficiency of a concentrated liquidity range.
+//!
+//! Full-range liquidity L is worth 2 * L * sqrt(P) in token B, so capital C
+//! buys C / (2 * sqrt(P)) full-range liquidity. A narrower range buys that
+//! amount times its concentration multiplier, and while the price is inside
+//! the range it earns fees in proportion to its share of active liquidity.
+
+use super::concentrated_liquidity::concentration_multiplier;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::*;
+
+/// Capital efficiency figures of a range at the current price.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CapitalEfficiency {
+    /// Liquidity per unit of capital relative to a full-range position.
+    pub multiplier: Decimal,
+    /// Liquidity the capital provides in the range.
+    pub liquidity: u128,
+    /// Share of swap fees earned at the current active liquidity, 0 to 1;
+    /// zero when the price is outside the range.
+    pub fee_share: Decimal,
+    /// Lower bound relative to the price, in percent (negative below it).
+    pub lower_band_pct: Decimal,
+    /// Upper bound relative to the price, in percent.
+    pub upper_band_pct: Decimal,
+    /// Whether the price is inside the range.
+    pub in_range: bool,
+}
+
+/// Calculates the capital efficiency of `capital` (in token B) placed in
+/// `[lower, upper]` at `price`, joining `active_liquidity` already in range.
+///
+/// Prices, capital and liquidity must share units, e.g. raw token amounts
+/// with prices unadjusted for decimals.
+pub fn capital_efficiency(
+    price: Decimal,
+    lower: Decimal,
+    upper: Decimal,
+    capital: Decimal,
+    active_liquidity: u128,
+) -> Result<CapitalEfficiency, &'static str> {
+    if capital < Decimal::ZERO {
+        return Err("Capital must not be negative");
+    }
+    let multiplier = concentration_multiplier(price, lower, upper)?;
+
+    let p = price.to_f64().ok_or("Overflow converting price")?;
+    let c = capital.to_f64().ok_or("Overflow converting capital")?;
+    let liquidity = c * multiplier.to_f64().ok_or("Overflow")? / (2.0 * p.sqrt());
+    let liquidity = liquidity.to_u128().ok_or("Overflow converting liquidity")?;
+
+    let in_range = price >= lower && price < upper;
+    let fee_share = if in_range && liquidity > 0 {
+        let total = active_liquidity.saturating_add(liquidity);
+        Decimal::from(liquidity) / Decimal::from(total)
+    } else {
+        Decimal::ZERO
+    };
+
+    let hundred = Decimal::ONE_HUNDRED;
+    Ok(CapitalEfficiency {
+        multiplier,
+        liquidity,
+        fee_share,
+        lower_band_pct: (lower / price - Decimal::ONE) * hundred,
+        upper_band_pct: (upper / price - Decimal::ONE) * hundred,
+        in_range,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_capital_efficiency() {
+        // 10_000 at P = 100 buys 10_000 / 20 = 500 full-range liquidity
+        let wide =
+            capital_efficiency(dec!(100), dec!(0.0001), dec!(100000000), dec!(10000), 500).unwrap();
+        assert!((wide.multiplier.to_f64().unwrap() - 1.0).abs() < 0.01);
+        assert!(wide.liquidity.abs_diff(500) <= 5);
+
+        let narrow = capital_efficiency(dec!(100), dec!(90), dec!(110), dec!(10000), 500).unwrap();
+        assert!(narrow.in_range);
+        assert!(narrow.multiplier > dec!(10));
+        assert!(narrow.fee_share > wide.fee_share);
+        assert_eq!(narrow.lower_band_pct, dec!(-10));
+        assert_eq!(narrow.upper_band_pct, dec!(10));
+        let expected = narrow.liquidity as f64 / (narrow.liquidity + 500) as f64;
+        assert!((narrow.fee_share.to_f64().unwrap() - expected).abs() < 1e-9);
+
+        // Out of range ranges earn nothing
+        let above = capital_efficiency(dec!(100), dec!(110), dec!(120), dec!(10000), 500).unwrap();
+        assert!(!above.in_range);
+        assert_eq!(above.fee_share, Decimal::ZERO);
+        assert_eq!(above.lower_band_pct, dec!(10));
+
+        assert!(capital_efficiency(dec!(100), dec!(90), dec!(110), dec!(-1), 500).is_err());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::token::TokenAmount;
+use crate::value_objects::amount::Rounding;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::*;
 
@@ -35,8 +36,7 @@ pub fn get_amount0_delta(
     let factor = num / den;
     let amount = liquidity_dec * factor;
 
-    let amount_u128 = amount.to_u128().ok_or("Overflow converting amount")?;
-    Ok(TokenAmount::from(amount_u128))
+    TokenAmount::try_from_decimal(amount, Rounding::Down)
 }
 
 /// Calculates the amount of token1 (y) given liquidity and price range.
@@ -58,8 +58,7 @@ pub fn get_amount1_delta(
 
     let amount = liquidity_dec * diff;
 
-    let amount_u128 = amount.to_u128().ok_or("Overflow converting amount")?;
-    Ok(TokenAmount::from(amount_u128))
+    TokenAmount::try_from_decimal(amount, Rounding::Down)
 }
 
 /// Calculates liquidity for a given amount of token0 and price range
@@ -75,7 +74,7 @@ pub fn get_liquidity_for_amount0(
         (sqrt_price_b, sqrt_price_a)
     };
 
-    let amount0_dec = Decimal::from_str(&amount0.0.to_string()).map_err(|_| "Conversion error")?;
+    let amount0_dec = amount0.to_decimal()?;
 
     let num = amount0_dec * lower * upper;
     let den = upper - lower;
@@ -101,7 +100,7 @@ pub fn get_liquidity_for_amount1(
         (sqrt_price_b, sqrt_price_a)
     };
 
-    let amount1_dec = Decimal::from_str(&amount1.0.to_string()).map_err(|_| "Conversion error")?;
+    let amount1_dec = amount1.to_decimal()?;
 
     let den = upper - lower;
     if den.is_zero() {
@@ -112,6 +111,42 @@ pub fn get_liquidity_for_amount1(
     liquidity.to_u128().ok_or("Overflow")
 }
 
+/// Calculates the concentration multiplier of a price range.
+///
+/// The multiplier is the ratio between the liquidity a given amount of
+/// capital provides in `[lower, upper]` and the liquidity the same capital
+/// provides over the full price range, both valued at `price`:
+///
+/// multiplier = 2 * sqrt(P) / ((sqrt(P') - sqrt(P_a)) + P * (1/sqrt(P') - 1/sqrt(P_b)))
+///
+/// where P' is the price clamped to the range. For a range centered
+/// geometrically on the price this reduces to 1 / (1 - (P_a/P_b)^(1/4)).
+pub fn concentration_multiplier(
+    price: Decimal,
+    lower: Decimal,
+    upper: Decimal,
+) -> Result<Decimal, &'static str> {
+    if price <= Decimal::ZERO || lower <= Decimal::ZERO {
+        return Err("Prices must be positive");
+    }
+    if lower >= upper {
+        return Err("Invalid range");
+    }
+
+    let p = price.to_f64().ok_or("Overflow converting price")?;
+    let a = lower.to_f64().ok_or("Overflow converting price")?;
+    let b = upper.to_f64().ok_or("Overflow converting price")?;
+    let clamped = p.clamp(a, b);
+
+    let value_per_liquidity =
+        (clamped.sqrt() - a.sqrt()) + p * (1.0 / clamped.sqrt() - 1.0 / b.sqrt());
+    if value_per_liquidity <= 0.0 {
+        return Err("Range too small");
+    }
+
+    Decimal::from_f64(2.0 * p.sqrt() / value_per_liquidity).ok_or("Overflow")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -149,4 +184,118 @@ mod tests {
         let l2 = get_liquidity_for_amount1(dy, sqrt_p_a, sqrt_p_b).unwrap();
         assert_eq!(l2, 1000);
     }
+
+    #[test]
+    fn test_concentration_multiplier() {
+        // Geometrically centered range: 1 / (1 - (a/b)^(1/4))
+        let m = concentration_multiplier(Decimal::from(100), Decimal::from(50), Decimal::from(200))
+            .unwrap();
+        let expected = 1.0 / (1.0 - 0.25_f64.powf(0.25));
+        assert!((m.to_f64().unwrap() - expected).abs() < 1e-9);
+
+        // Narrower ranges concentrate more
+        let narrow =
+            concentration_multiplier(Decimal::from(100), Decimal::from(95), Decimal::from(105))
+                .unwrap();
+        assert!(narrow > m);
+
+        assert!(
+            concentration_multiplier(Decimal::from(100), Decimal::from(105), Decimal::from(95))
+                .is_err()
+        );
+    }
+
+    mod properties {
+        use super::*;
+        use proptest::prelude::*;
+
+        /// Sqrt prices between 0.01 and 100.
+        fn sqrt_price() -> impl Strategy<Value = Decimal> {
+            (100i64..1_000_000).prop_map(|n| Decimal::new(n, 4))
+        }
+
+        fn raw(amount: TokenAmount) -> u128 {
+            amount.as_u256().as_u128()
+        }
+
+        proptest! {
+            #[test]
+            fn deltas_ignore_bound_order(
+                liquidity in 1u128..1_000_000_000_000,
+                a in sqrt_price(),
+                b in sqrt_price(),
+            ) {
+                prop_assert_eq!(
+                    get_amount0_delta(liquidity, a, b).unwrap(),
+                    get_amount0_delta(liquidity, b, a).unwrap()
+                );
+                prop_assert_eq!(
+                    get_amount1_delta(liquidity, a, b).unwrap(),
+                    get_amount1_delta(liquidity, b, a).unwrap()
+                );
+            }
+
+            #[test]
+            fn deltas_grow_with_range_and_liquidity(
+                liquidity in 1u128..1_000_000_000_000,
+                extra in 0u128..1_000_000_000_000,
+                a in sqrt_price(),
+                b in sqrt_price(),
+                c in sqrt_price(),
+            ) {
+                let mut bounds = [a, b, c];
+                bounds.sort();
+                let [lower, mid, upper] = bounds;
+
+                let narrow0 = raw(get_amount0_delta(liquidity, lower, mid).unwrap());
+                let narrow1 = raw(get_amount1_delta(liquidity, lower, mid).unwrap());
+                let wide0 = raw(get_amount0_delta(liquidity, lower, upper).unwrap());
+                let wide1 = raw(get_amount1_delta(liquidity, lower, upper).unwrap());
+                let deep0 = raw(get_amount0_delta(liquidity + extra, lower, mid).unwrap());
+                let deep1 = raw(get_amount1_delta(liquidity + extra, lower, mid).unwrap());
+                prop_assert!(narrow0 <= wide0 && narrow1 <= wide1);
+                prop_assert!(narrow0 <= deep0 && narrow1 <= deep1);
+            }
+
+            #[test]
+            fn split_ranges_conserve_amounts(
+                liquidity in 1u128..1_000_000_000_000,
+                a in sqrt_price(),
+                b in sqrt_price(),
+                c in sqrt_price(),
+            ) {
+                let mut bounds = [a, b, c];
+                bounds.sort();
+                let [lower, mid, upper] = bounds;
+
+                // Each part truncates, so the parts lose at most one unit each
+                for delta in [get_amount0_delta, get_amount1_delta] {
+                    let whole = raw(delta(liquidity, lower, upper).unwrap());
+                    let parts = raw(delta(liquidity, lower, mid).unwrap())
+                        + raw(delta(liquidity, mid, upper).unwrap());
+                    prop_assert!(parts <= whole && whole - parts <= 2, "{parts} vs {whole}");
+                }
+            }
+
+            #[test]
+            fn liquidity_round_trips_through_amounts(
+                liquidity in 1_000_000u128..1_000_000_000_000,
+                a in sqrt_price(),
+                b in sqrt_price(),
+            ) {
+                prop_assume!(a != b);
+                let (lower, upper) = (a.min(b), a.max(b));
+
+                // Truncated amounts never mint more liquidity than they came from
+                let amount0 = get_amount0_delta(liquidity, lower, upper).unwrap();
+                let amount1 = get_amount1_delta(liquidity, lower, upper).unwrap();
+                for restored in [
+                    get_liquidity_for_amount0(amount0, lower, upper).unwrap(),
+                    get_liquidity_for_amount1(amount1, lower, upper).unwrap(),
+                ] {
+                    prop_assert!(restored <= liquidity);
+                }
+            }
+        }
+    }
 }
@@ -46,14 +46,9 @@ pub fn calculate_spot_price(
     reserve_in: TokenAmount,
     reserve_out: TokenAmount,
 ) -> Result<rust_decimal::Decimal, &'static str> {
-    use rust_decimal::prelude::*;
-
-    // We need to be careful with precision here. U256 to Decimal conversion might need handling.
-    // For now, assuming simple conversion works for reasonable reserve sizes.
-    // A better approach is to use big decimal or string parsing.
-
-    let r_in = Decimal::from_str(&reserve_in.0.to_string()).map_err(|_| "Conversion error")?;
-    let r_out = Decimal::from_str(&reserve_out.0.to_string()).map_err(|_| "Conversion error")?;
+    // Reserves beyond the 96 bits of a Decimal are rejected
+    let r_in = reserve_in.to_decimal()?;
+    let r_out = reserve_out.to_decimal()?;
 
     if r_in.is_zero() {
         return Err("Reserve in is zero");
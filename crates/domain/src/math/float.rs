@@ -0,0 +1,73 @@
+//! Fast conversions from floating point.
+//!
+//! `Decimal::from_f64` expands the exact binary value of a float, which is
+//! precise but costs well over 100ns. Simulations convert a float on every
+//! step, so they round to the digits an `f64` actually carries instead.
+
+use rust_decimal::Decimal;
+
+/// Significant decimal digits an `f64` always represents.
+pub const F64_DIGITS: i32 = 15;
+
+/// Largest `Decimal` mantissa (2^96).
+const MAX_MANTISSA: f64 = 79_228_162_514_264_337_593_543_950_336.0;
+
+/// Converts an `f64` to a `Decimal` rounded to [`F64_DIGITS`] significant
+/// digits.
+///
+/// Digits beyond the 28 decimal places of `Decimal` are dropped. Returns
+/// `None` for non-finite values and values beyond the range of `Decimal`.
+#[must_use]
+pub fn decimal_from_f64(value: f64) -> Option<Decimal> {
+    if !value.is_finite() {
+        return None;
+    }
+    if value == 0.0 {
+        return Some(Decimal::ZERO);
+    }
+    let mut scale = (F64_DIGITS - 1 - value.abs().log10().floor() as i32).clamp(0, 28);
+    let rounded = (value * 10f64.powi(scale)).round();
+    if scale == 0 {
+        if rounded.abs() >= MAX_MANTISSA {
+            return None;
+        }
+        return Decimal::try_from_i128_with_scale(rounded as i128, 0).ok();
+    }
+
+    // Below 10^14 the mantissa has at most 15 digits. Trailing zeros are
+    // dropped so round values print as they would from `Decimal::from_f64`.
+    let mut mantissa = rounded as i64;
+    while scale > 0 && mantissa % 10 == 0 {
+        mantissa /= 10;
+        scale -= 1;
+    }
+    Some(Decimal::new(mantissa, scale as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::prelude::ToPrimitive;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_decimal_from_f64_keeps_significant_digits() {
+        assert_eq!(decimal_from_f64(100.0).unwrap().to_string(), "100");
+        assert_eq!(decimal_from_f64(-0.05), Some(dec!(-0.05)));
+        assert_eq!(decimal_from_f64(0.1 + 0.2), Some(dec!(0.3)));
+        assert_eq!(decimal_from_f64(1e-30), Some(Decimal::ZERO));
+        for value in [
+            1.234_567_890_123_4e-9,
+            -0.000_123_456,
+            103.123_456_789,
+            4.2e27,
+        ] {
+            let converted = decimal_from_f64(value).unwrap().to_f64().unwrap();
+            assert!(((converted - value) / value).abs() < 1e-14, "{value}");
+        }
+
+        assert_eq!(decimal_from_f64(f64::NAN), None);
+        assert_eq!(decimal_from_f64(f64::INFINITY), None);
+        assert_eq!(decimal_from_f64(1e29), None);
+    }
+}
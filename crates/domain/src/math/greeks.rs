@@ -0,0 +1,87 @@
+//! Price sensitivities of a concentrated liquidity position.
+//!
+//! Valued in token B at price P (token B per token A), a position holds
+//! x = L * (1/sqrt(P') - 1/sqrt(P_b)) of token A, where P' is P clamped to
+//! `[P_a, P_b]`. Since dV/dP = x, the token A holding is the position's delta.
+
+use super::concentrated_liquidity::get_amount0_delta;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::*;
+
+/// First and second order price sensitivities of a position.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionGreeks {
+    /// Change in value per unit change in price, in token A units.
+    pub delta: Decimal,
+    /// Change in delta per unit change in price; never positive.
+    pub gamma: Decimal,
+}
+
+/// Calculates the delta and gamma of a position with `liquidity` in
+/// `[lower, upper]` at `price`.
+///
+/// Delta equals the position's token A amount: it is largest below the range
+/// and zero above it. Gamma is -L / (2 * P^(3/2)) inside the range and zero
+/// outside it.
+pub fn position_greeks(
+    liquidity: u128,
+    price: Decimal,
+    lower: Decimal,
+    upper: Decimal,
+) -> Result<PositionGreeks, &'static str> {
+    if price <= Decimal::ZERO || lower <= Decimal::ZERO {
+        return Err("Prices must be positive");
+    }
+    if lower >= upper {
+        return Err("Invalid range");
+    }
+
+    let sqrt = |value: Decimal| {
+        value
+            .to_f64()
+            .and_then(|v| Decimal::from_f64(v.sqrt()))
+            .ok_or("Overflow converting price")
+    };
+    let clamped = price.clamp(lower, upper);
+    let amount_a = get_amount0_delta(liquidity, sqrt(clamped)?, sqrt(upper)?)?;
+    let delta = Decimal::from_str(&amount_a.0.to_string()).map_err(|_| "Overflow")?;
+
+    let l = liquidity.to_f64().ok_or("Overflow converting liquidity")?;
+    let p = price.to_f64().ok_or("Overflow converting price")?;
+    let gamma = if price > lower && price < upper {
+        -l / (2.0 * p.powf(1.5))
+    } else {
+        0.0
+    };
+
+    Ok(PositionGreeks {
+        delta,
+        gamma: Decimal::from_f64(gamma).ok_or("Overflow")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_greeks() {
+        let (lower, upper) = (Decimal::ONE, Decimal::from(4));
+
+        // Below the range: all token A, L * (1/1 - 1/2)
+        let below = position_greeks(1000, Decimal::new(5, 1), lower, upper).unwrap();
+        assert_eq!(below.delta, Decimal::from(500));
+        assert_eq!(below.gamma, Decimal::ZERO);
+
+        // In range at P = 2.25: L * (1/1.5 - 1/2), in whole token A units
+        let inside = position_greeks(1000, Decimal::new(225, 2), lower, upper).unwrap();
+        assert_eq!(inside.delta, Decimal::from(166));
+        assert!(inside.gamma < Decimal::ZERO);
+
+        // Above the range: all token B
+        let above = position_greeks(1000, Decimal::from(9), lower, upper).unwrap();
+        assert_eq!(above.delta, Decimal::ZERO);
+
+        assert!(position_greeks(1000, Decimal::ONE, upper, lower).is_err());
+    }
+}
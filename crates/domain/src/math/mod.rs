@@ -2,17 +2,26 @@
 //!
 //! This module provides core mathematical operations for:
 //! - Concentrated liquidity calculations
+//! - Capital efficiency of ranges
 //! - Constant product AMM math
 //! - Price/tick conversions
 //! - Fee calculations
 //! - Price impact estimation
+//! - Position Greeks
+//! - Fast float to decimal conversion
 
+/// Capital efficiency of ranges.
+pub mod capital_efficiency;
 /// Concentrated liquidity math.
 pub mod concentrated_liquidity;
 /// Constant product AMM math.
 pub mod constant_product;
 /// Fee tier and fee calculations.
 pub mod fee_math;
+/// Fast float to decimal conversion.
+pub mod float;
+/// Position delta and gamma.
+pub mod greeks;
 /// Price impact estimation for swaps.
 pub mod price_impact;
 /// Price tick conversions.
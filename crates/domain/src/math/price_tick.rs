@@ -47,4 +47,22 @@ mod tests {
         let t2 = price_to_tick(Decimal::from_f64(1.01004966).unwrap()).unwrap();
         assert_eq!(t2, 100);
     }
+
+    mod properties {
+        use super::*;
+        use proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            fn ticks_round_trip_through_prices(tick in -443_636i32..=443_636) {
+                let price = tick_to_price(tick).unwrap();
+                prop_assert_eq!(price_to_tick(price).unwrap(), tick);
+            }
+
+            #[test]
+            fn prices_increase_with_ticks(tick in -400_000i32..400_000) {
+                prop_assert!(tick_to_price(tick).unwrap() < tick_to_price(tick + 1).unwrap());
+            }
+        }
+    }
 }
@@ -0,0 +1,165 @@
+//! Annualized yields from fee earnings.
+//!
+//! Windows are measured in seconds so partial days count exactly: fees
+//! earned over 36 hours annualize over 1.5 days, not one or two. Simple
+//! rates (APR) scale linearly to a year; compounded rates (APY) assume the
+//! window's return is reinvested every window for a year.
+
+use super::APY;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+
+/// Seconds in a 365-day year.
+pub const SECONDS_PER_YEAR: u64 = 365 * 86_400;
+
+/// Seconds in a day.
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Annualizes a return earned over `window_secs` without compounding.
+///
+/// Returns `None` for an empty window or on overflow.
+#[must_use]
+pub fn annualize_simple(period_return: Decimal, window_secs: u64) -> Option<Decimal> {
+    if window_secs == 0 {
+        return None;
+    }
+    let annual = period_return.checked_mul(Decimal::from(SECONDS_PER_YEAR))?;
+    annual.checked_div(Decimal::from(window_secs))
+}
+
+/// Annualizes a return earned over `window_secs`, compounding once per
+/// window: (1 + r)^(year / window) - 1.
+///
+/// The exponent is fractional for windows that do not divide a year.
+/// Returns `None` for an empty window, a total loss, or when the result
+/// overflows, as it does for large returns over very short windows.
+#[must_use]
+pub fn annualize_compounded(period_return: Decimal, window_secs: u64) -> Option<Decimal> {
+    if window_secs == 0 || period_return <= Decimal::NEGATIVE_ONE {
+        return None;
+    }
+    let growth = 1.0 + period_return.to_f64()?;
+    let periods = SECONDS_PER_YEAR as f64 / window_secs as f64;
+    let annual = growth.powf(periods) - 1.0;
+    if !annual.is_finite() {
+        return None;
+    }
+    Decimal::from_f64(annual)
+}
+
+/// Calculates the simple annual fee yield of `principal` earning `fees`
+/// over `window_secs`.
+///
+/// Returns `None` for a non-positive principal or an empty window.
+#[must_use]
+pub fn fee_apr(fees: Decimal, principal: Decimal, window_secs: u64) -> Option<Decimal> {
+    if principal <= Decimal::ZERO {
+        return None;
+    }
+    annualize_simple(fees / principal, window_secs)
+}
+
+/// Calculates the compounded annual fee yield of `principal` earning `fees`
+/// over `window_secs`, assuming fees are reinvested every window.
+///
+/// Returns `None` for a non-positive principal, an empty window or on
+/// overflow.
+#[must_use]
+pub fn fee_apy(fees: Decimal, principal: Decimal, window_secs: u64) -> Option<Decimal> {
+    if principal <= Decimal::ZERO {
+        return None;
+    }
+    annualize_compounded(fees / principal, window_secs)
+}
+
+/// Sums the fees in `history` earned after `start` and up to `end`.
+///
+/// Each entry is a Unix timestamp in seconds and the fees earned at it.
+#[must_use]
+pub fn fees_in_window(history: &[(u64, Decimal)], start: u64, end: u64) -> Decimal {
+    history
+        .iter()
+        .filter(|(timestamp, _)| *timestamp > start && *timestamp <= end)
+        .map(|(_, fees)| *fees)
+        .sum()
+}
+
+impl APY {
+    /// Derives the APY of `principal` earning `fees` over `window_secs`.
+    ///
+    /// Returns `None` if the yield cannot be annualized; see [`fee_apy`].
+    #[must_use]
+    pub fn from_fees(fees: Decimal, principal: Decimal, window_secs: u64) -> Option<Self> {
+        Some(Self {
+            estimated_annual_return: fee_apy(fees, principal, window_secs)?,
+            apr: fee_apr(fees, principal, window_secs)?,
+            based_on_days: u32::try_from(window_secs / SECONDS_PER_DAY).unwrap_or(u32::MAX),
+        })
+    }
+
+    /// Derives the APY of `principal` from the fees in `history` earned
+    /// between `start` and `end`.
+    ///
+    /// A position opened during the window should pass its opening time
+    /// as `start` so the yield is not diluted by time it did not exist.
+    #[must_use]
+    pub fn from_fee_history(
+        history: &[(u64, Decimal)],
+        principal: Decimal,
+        start: u64,
+        end: u64,
+    ) -> Option<Self> {
+        let fees = fees_in_window(history, start, end);
+        Self::from_fees(fees, principal, end.checked_sub(start)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_partial_periods_annualize_exactly() {
+        // 1% over 36 hours is 1% * 365 / 1.5 simple
+        let apr = fee_apr(dec!(10), dec!(1000), 36 * 3600).unwrap();
+        assert_eq!(
+            apr.round_dp(10),
+            (dec!(0.01) * dec!(365) / dec!(1.5)).round_dp(10)
+        );
+
+        // 0.1% per day compounds daily to (1.001)^365 - 1
+        let apy = fee_apy(dec!(1), dec!(1000), SECONDS_PER_DAY).unwrap();
+        let expected = 1.001_f64.powf(365.0) - 1.0;
+        assert!((apy.to_f64().unwrap() - expected).abs() < 1e-9);
+        assert!(apy > fee_apr(dec!(1), dec!(1000), SECONDS_PER_DAY).unwrap());
+
+        // A full year is the same either way
+        assert_eq!(
+            annualize_compounded(dec!(0.2), SECONDS_PER_YEAR)
+                .unwrap()
+                .round_dp(9),
+            dec!(0.2)
+        );
+
+        assert_eq!(fee_apr(dec!(1), Decimal::ZERO, 3600), None);
+        assert_eq!(fee_apy(dec!(1), dec!(1000), 0), None);
+        assert_eq!(annualize_compounded(dec!(-1), 3600), None);
+        assert_eq!(annualize_compounded(dec!(5), 1), None);
+    }
+
+    #[test]
+    fn test_apy_from_fee_history() {
+        let day = SECONDS_PER_DAY;
+        let history = [(day, dec!(1)), (2 * day, dec!(2)), (3 * day, dec!(4))];
+        assert_eq!(fees_in_window(&history, day, 3 * day), dec!(6));
+
+        // Half a day into the window, only the last entry counts
+        let apy = APY::from_fee_history(&history, dec!(1000), 2 * day + day / 2, 3 * day).unwrap();
+        assert_eq!(apy.based_on_days, 0);
+        assert_eq!(apy.apr, dec!(0.004) * dec!(730));
+        assert!(apy.estimated_annual_return > apy.apr);
+
+        assert!(APY::from_fee_history(&history, dec!(1000), 3 * day, day).is_none());
+    }
+}
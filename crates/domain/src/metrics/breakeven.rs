@@ -0,0 +1,188 @@
+//! Break-even price band of a concentrated liquidity position.
+//!
+//! Against holding the entry tokens, a position loses L * (V(P) - H(P)) to
+//! impermanent loss at price P, where V is the position value and H the
+//! value of the entry holdings, both per unit of liquidity. Closing at P
+//! breaks even while the accrued fees cover that loss. The loss is zero at
+//! the entry price and grows monotonically on either side, so the prices
+//! where fees still cover it form a single band around the entry price.
+
+use rust_decimal::Decimal;
+use rust_decimal::prelude::*;
+use std::fmt;
+
+/// Bisection steps when solving for a band edge.
+const BISECTION_STEPS: usize = 200;
+
+/// Doublings of the entry price searched for the upper edge.
+const MAX_UPPER_DOUBLINGS: usize = 64;
+
+/// Prices within which closing a position yields non-negative PnL against
+/// holding its entry tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BreakevenBand {
+    /// Lowest break-even price; zero if fees cover the loss at any lower
+    /// price.
+    pub lower: Decimal,
+    /// Highest break-even price; `None` if fees cover the loss at any
+    /// higher price.
+    pub upper: Option<Decimal>,
+}
+
+impl BreakevenBand {
+    /// Returns true if closing at `price` breaks even.
+    #[must_use]
+    pub fn contains(&self, price: Decimal) -> bool {
+        price >= self.lower && self.upper.is_none_or(|upper| price <= upper)
+    }
+}
+
+impl fmt::Display for BreakevenBand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.upper {
+            Some(upper) => write!(f, "{:.2}–{:.2}", self.lower, upper),
+            None => write!(f, "above {:.2}", self.lower),
+        }
+    }
+}
+
+/// Calculates the break-even price band of a position.
+///
+/// # Arguments
+/// * `entry_price` - Price when the position was opened
+/// * `price_lower` - Lower bound of the position range
+/// * `price_upper` - Upper bound of the position range
+/// * `entry_value` - Position value at entry, in the quote token
+/// * `fees` - Fees accrued so far, in the quote token
+pub fn breakeven_band(
+    entry_price: Decimal,
+    price_lower: Decimal,
+    price_upper: Decimal,
+    entry_value: Decimal,
+    fees: Decimal,
+) -> Result<BreakevenBand, &'static str> {
+    if entry_price <= Decimal::ZERO || price_lower <= Decimal::ZERO {
+        return Err("Prices must be positive");
+    }
+    if price_lower >= price_upper {
+        return Err("Invalid range");
+    }
+    if entry_value <= Decimal::ZERO {
+        return Err("Entry value must be positive");
+    }
+    if fees < Decimal::ZERO {
+        return Err("Fees must not be negative");
+    }
+
+    let p0 = entry_price.to_f64().ok_or("Overflow converting price")?;
+    let a = price_lower.to_f64().ok_or("Overflow converting price")?;
+    let b = price_upper.to_f64().ok_or("Overflow converting price")?;
+    let fees = fees.to_f64().ok_or("Overflow converting fees")?;
+
+    // Token amounts per unit of liquidity at a price
+    let amounts = |p: f64| {
+        let clamped = p.clamp(a, b);
+        (
+            1.0 / clamped.sqrt() - 1.0 / b.sqrt(),
+            clamped.sqrt() - a.sqrt(),
+        )
+    };
+    let (x0, y0) = amounts(p0);
+    let liquidity = entry_value.to_f64().ok_or("Overflow converting value")? / (x0 * p0 + y0);
+
+    // Closing PnL against holding, including fees
+    let pnl = |p: f64| {
+        let (x, y) = amounts(p);
+        liquidity * ((x * p + y) - (x0 * p + y0)) + fees
+    };
+
+    let lower = if pnl(0.0) >= 0.0 {
+        0.0
+    } else {
+        bisect(&pnl, 0.0, p0)
+    };
+
+    let mut high = p0 * 2.0;
+    let mut upper = None;
+    for _ in 0..MAX_UPPER_DOUBLINGS {
+        if pnl(high) < 0.0 {
+            upper = Some(bisect(&pnl, p0, high));
+            break;
+        }
+        high *= 2.0;
+    }
+
+    Ok(BreakevenBand {
+        lower: Decimal::from_f64(lower).ok_or("Overflow")?,
+        upper: upper
+            .map(|u| Decimal::from_f64(u).ok_or("Overflow"))
+            .transpose()?,
+    })
+}
+
+/// Finds the price between `profitable` and `losing` where `pnl` crosses
+/// zero.
+fn bisect(pnl: &impl Fn(f64) -> f64, profitable: f64, losing: f64) -> f64 {
+    let (mut inside, mut outside) = if pnl(profitable) >= 0.0 {
+        (profitable, losing)
+    } else {
+        (losing, profitable)
+    };
+    for _ in 0..BISECTION_STEPS {
+        let mid = (inside + outside) / 2.0;
+        if pnl(mid) >= 0.0 {
+            inside = mid;
+        } else {
+            outside = mid;
+        }
+    }
+    inside
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::impermanent_loss::calculate_il_concentrated;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_band_edges_are_where_fees_cover_il() {
+        let (a, b) = (dec!(80), dec!(125));
+        let band = breakeven_band(dec!(100), a, b, dec!(1000), dec!(10)).unwrap();
+        let upper = band.upper.unwrap();
+        assert!(band.lower < dec!(100) && upper > dec!(100));
+        assert!(band.contains(dec!(100)));
+        assert!(!band.contains(upper + dec!(1)));
+
+        // IL in quote at each edge equals the fees. The entry holdings are
+        // worth half the entry value in each token for this symmetric range.
+        for edge in [band.lower, upper] {
+            let il = calculate_il_concentrated(dec!(100), edge, a, b)
+                .unwrap()
+                .to_f64()
+                .unwrap();
+            let hodl = 5.0 * edge.to_f64().unwrap() + 500.0;
+            assert!((il * hodl + 10.0).abs() < 1e-3, "edge {edge}: {il}");
+        }
+
+        // More fees widen the band
+        let wider = breakeven_band(dec!(100), a, b, dec!(1000), dec!(20)).unwrap();
+        assert!(wider.lower < band.lower && wider.upper.unwrap() > upper);
+        assert_eq!(band.to_string(), format!("{:.2}–{:.2}", band.lower, upper));
+    }
+
+    #[test]
+    fn test_unbounded_and_invalid_bands() {
+        // Entirely in the quote token above the range: never loses going up
+        let above = breakeven_band(dec!(150), dec!(80), dec!(125), dec!(1000), dec!(1)).unwrap();
+        assert_eq!(above.upper, None);
+        assert!(above.lower > dec!(80));
+
+        // Without fees the band collapses onto the entry price
+        let none = breakeven_band(dec!(100), dec!(80), dec!(125), dec!(1000), dec!(0)).unwrap();
+        assert!((none.upper.unwrap() - none.lower).abs() < dec!(0.000001));
+
+        assert!(breakeven_band(dec!(100), dec!(125), dec!(80), dec!(1000), dec!(1)).is_err());
+        assert!(breakeven_band(dec!(100), dec!(80), dec!(125), dec!(1000), dec!(-1)).is_err());
+    }
+}
@@ -4,6 +4,7 @@
 //! APY projections, and breakeven analysis for LP positions.
 
 use crate::token::TokenAmount;
+use crate::value_objects::amount::Rounding;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::*;
 
@@ -19,14 +20,13 @@ use rust_decimal::prelude::*;
 /// # Errors
 /// Returns error if conversion fails or overflow occurs.
 pub fn calculate_pool_fees(volume: TokenAmount, fee_bps: u32) -> Result<TokenAmount, &'static str> {
-    let vol = Decimal::from_str(&volume.0.to_string()).map_err(|_| "Conversion error")?;
+    let vol = volume.to_decimal()?;
     let bps = Decimal::from(fee_bps);
     let ten_thousand = Decimal::from(10000);
 
     let fees = vol * (bps / ten_thousand);
 
-    let fees_u128 = fees.to_u128().ok_or("Overflow")?;
-    Ok(TokenAmount::from(fees_u128))
+    TokenAmount::try_from_decimal(fees, Rounding::Down)
 }
 
 /// Calculates simple APY based on fees earned over a period.
@@ -1,4 +1,4 @@
-use crate::math::concentrated_liquidity;
+use crate::math::float::decimal_from_f64;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::*;
 
@@ -48,87 +48,95 @@ pub fn calculate_il_concentrated(
     price_lower: Decimal,
     price_upper: Decimal,
 ) -> Result<Decimal, &'static str> {
-    if entry_price.is_zero() || price_lower.is_zero() || price_upper.is_zero() {
-        return Err("Prices must be non-zero");
-    }
-    if price_lower >= price_upper {
-        return Err("Invalid range");
+    ConcentratedIl::new(entry_price, price_lower, price_upper)?.at(current_price)
+}
+
+/// Impermanent loss of a concentrated position opened at a fixed entry price
+/// and range.
+///
+/// The entry holdings and range bounds are computed once, so evaluating the
+/// loss along a price path costs one square root per price. Amounts are per
+/// unit of liquidity, which cancels out of the loss ratio.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConcentratedIl {
+    sqrt_lower: f64,
+    sqrt_upper: f64,
+    /// Token amounts held at entry.
+    entry_amounts: (f64, f64),
+}
+
+impl ConcentratedIl {
+    /// Creates the loss function of a position opened at `entry_price` in
+    /// [`price_lower`, `price_upper`].
+    pub fn new(
+        entry_price: Decimal,
+        price_lower: Decimal,
+        price_upper: Decimal,
+    ) -> Result<Self, &'static str> {
+        if entry_price.is_zero() || price_lower.is_zero() || price_upper.is_zero() {
+            return Err("Prices must be non-zero");
+        }
+        if price_lower >= price_upper {
+            return Err("Invalid range");
+        }
+
+        let sqrt = |p: Decimal| -> Result<f64, &'static str> {
+            let root = p.to_f64().ok_or("Overflow")?.sqrt();
+            if root.is_finite() {
+                Ok(root)
+            } else {
+                Err("Overflow")
+            }
+        };
+
+        let mut il = Self {
+            sqrt_lower: sqrt(price_lower)?,
+            sqrt_upper: sqrt(price_upper)?,
+            entry_amounts: (0.0, 0.0),
+        };
+        il.entry_amounts = il.amounts(sqrt(entry_price)?);
+        Ok(il)
     }
 
-    // Arbitrary liquidity to simulate amounts.
-    // Using a large number to avoid small number precision issues with integer TokenAmount.
-    let liquidity = 1_000_000_000_000_000_000u128; // 1e18
-
-    let sqrt = |p: Decimal| -> Result<Decimal, &'static str> {
-        let f = p.to_f64().ok_or("Overflow")?;
-        Decimal::from_f64(f.sqrt()).ok_or("Overflow")
-    };
-
-    let sqrt_entry = sqrt(entry_price)?;
-    let sqrt_curr = sqrt(current_price)?;
-    let sqrt_lower = sqrt(price_lower)?;
-    let sqrt_upper = sqrt(price_upper)?;
-
-    // 1. Calculate Initial Amounts (Held)
-    // We need to know "active price" at entry to determine amounts.
-    // If entry < lower, all X. If entry > upper, all Y. If in range, mix.
-    // However, for IL calculation, we assume the position was created *at* entry_price.
-    // So we use the standard liquidity formulas with entry_price as the "current" price for initial state.
-
-    // BUT wait: get_amount functions take a range.
-    // For amount0: range is [max(current, lower), upper]? No.
-    // Uniswap logic:
-    // if P < lower: amounts are determined by range [lower, upper] assuming P is below. All X.
-    // actually, standard delta formulas work if we pass the range correctly.
-
-    // Let's use a helper to get amounts at a specific price P for range [Lower, Upper]
-    let get_amounts = |p_sqrt: Decimal| -> Result<(Decimal, Decimal), &'static str> {
-        let mut amt0 = Decimal::ZERO;
-        let mut amt1 = Decimal::ZERO;
-
-        // If P < Lower: Price is below range. Position is all Token0 (X).
-        // Effectively P_current = Lower for the purpose of logic? No.
-        // Standard logic:
-        // Liquidity is active only in [Lower, Upper].
-        // If P < Lower: The curve segment is "above" us. We hold amount0 required to cross [Lower, Upper].
-        // i.e., we are full in X.
-
-        if p_sqrt < sqrt_lower {
-            // Full range crossing for X
-            let a0 = concentrated_liquidity::get_amount0_delta(liquidity, sqrt_lower, sqrt_upper)?;
-            amt0 = Decimal::from_str(&a0.0.to_string()).unwrap();
-        } else if p_sqrt >= sqrt_upper {
-            // Price > Upper. Position is all Token1 (Y).
-            let a1 = concentrated_liquidity::get_amount1_delta(liquidity, sqrt_lower, sqrt_upper)?;
-            amt1 = Decimal::from_str(&a1.0.to_string()).unwrap();
+    /// Token amounts per unit of liquidity at a sqrt price.
+    ///
+    /// Below the range the position is all token0 (X), above it all token1
+    /// (Y), and in range X covers the price up to the upper bound and Y the
+    /// lower bound up to the price.
+    fn amounts(&self, sqrt_price: f64) -> (f64, f64) {
+        let (lower, upper) = (self.sqrt_lower, self.sqrt_upper);
+        if sqrt_price < lower {
+            ((upper - lower) / (lower * upper), 0.0)
+        } else if sqrt_price >= upper {
+            (0.0, upper - lower)
         } else {
-            // In range.
-            // X part: from P to Upper
-            let a0 = concentrated_liquidity::get_amount0_delta(liquidity, p_sqrt, sqrt_upper)?;
-            amt0 = Decimal::from_str(&a0.0.to_string()).unwrap();
-            // Y part: from Lower to P
-            let a1 = concentrated_liquidity::get_amount1_delta(liquidity, sqrt_lower, p_sqrt)?;
-            amt1 = Decimal::from_str(&a1.0.to_string()).unwrap();
+            (
+                (upper - sqrt_price) / (sqrt_price * upper),
+                sqrt_price - lower,
+            )
         }
-        Ok((amt0, amt1))
-    };
+    }
 
-    let (x0, y0) = get_amounts(sqrt_entry)?;
-    let (x1, y1) = get_amounts(sqrt_curr)?;
+    /// Returns the impermanent loss at `current_price` as a negative
+    /// fraction of the value of the entry holdings.
+    pub fn at(&self, current_price: Decimal) -> Result<Decimal, &'static str> {
+        let price = current_price.to_f64().ok_or("Overflow")?;
+        let (x0, y0) = self.entry_amounts;
+        let (x1, y1) = self.amounts(price.sqrt());
 
-    // Value Held: The initial bundle (x0, y0) valued at current_price
-    let value_held = x0 * current_price + y0;
+        // Value Held: The initial bundle (x0, y0) valued at current_price
+        let value_held = x0 * price + y0;
 
-    // Value LP: The current bundle (x1, y1) valued at current_price
-    let value_lp = x1 * current_price + y1;
+        // Value LP: The current bundle (x1, y1) valued at current_price
+        let value_lp = x1 * price + y1;
 
-    if value_held.is_zero() {
-        // If we held nothing, no loss/gain reference. (Should not happen with non-zero liq)
-        return Ok(Decimal::ZERO);
-    }
+        if value_held == 0.0 {
+            // If we held nothing, no loss/gain reference. (Should not happen with non-zero liq)
+            return Ok(Decimal::ZERO);
+        }
 
-    let il = (value_lp - value_held) / value_held;
-    Ok(il)
+        decimal_from_f64((value_lp - value_held) / value_held).ok_or("Overflow")
+    }
 }
 
 #[cfg(test)]
@@ -174,4 +182,109 @@ mod tests {
         let il_up = calculate_il_concentrated(entry, curr_up, lower, upper).unwrap();
         assert!(il_up < Decimal::ZERO);
     }
+
+    #[test]
+    fn test_concentrated_il_reused_along_a_path() {
+        let il =
+            ConcentratedIl::new(Decimal::from(100), Decimal::from(90), Decimal::from(110)).unwrap();
+        for price in [50, 95, 100, 105, 150] {
+            let price = Decimal::from(price);
+            assert_eq!(
+                il.at(price).unwrap(),
+                calculate_il_concentrated(
+                    Decimal::from(100),
+                    price,
+                    Decimal::from(90),
+                    Decimal::from(110)
+                )
+                .unwrap()
+            );
+        }
+        assert_eq!(il.at(Decimal::from(100)).unwrap(), Decimal::ZERO);
+
+        // Entered below the range and still below it: same holdings, no loss
+        let below =
+            ConcentratedIl::new(Decimal::from(80), Decimal::from(90), Decimal::from(110)).unwrap();
+        assert_eq!(below.at(Decimal::from(85)).unwrap(), Decimal::ZERO);
+        assert!(
+            ConcentratedIl::new(Decimal::from(100), Decimal::from(110), Decimal::from(90)).is_err()
+        );
+    }
+
+    mod properties {
+        use super::*;
+        use proptest::prelude::*;
+
+        /// Prices between 0.01 and 10,000.
+        fn price() -> impl Strategy<Value = Decimal> {
+            (1i64..1_000_000).prop_map(|n| Decimal::new(n, 2))
+        }
+
+        fn tolerance() -> Decimal {
+            Decimal::new(1, 9)
+        }
+
+        proptest! {
+            #[test]
+            fn constant_product_il_is_a_symmetric_loss(entry in price(), ratio in 1i64..10_000) {
+                let ratio = Decimal::new(ratio, 2);
+                let up = calculate_il_constant_product(entry, entry * ratio).unwrap();
+                let down = calculate_il_constant_product(entry, entry / ratio).unwrap();
+                prop_assert!(up <= Decimal::ZERO);
+                prop_assert!((up - down).abs() < tolerance());
+            }
+
+            #[test]
+            fn concentrated_il_is_a_loss_and_zero_at_entry(
+                entry in price(),
+                current in price(),
+                lower in price(),
+                upper in price(),
+            ) {
+                prop_assume!(lower < upper);
+                let il = calculate_il_concentrated(entry, current, lower, upper).unwrap();
+                prop_assert!(il <= tolerance(), "IL {il} is a gain");
+
+                let at_entry = calculate_il_concentrated(entry, entry, lower, upper).unwrap();
+                prop_assert!(at_entry.abs() < tolerance());
+            }
+
+            #[test]
+            fn concentrated_il_grows_away_from_entry(
+                entry in price(),
+                near in 1i64..500,
+                far in 1i64..500,
+                width in 11i64..1000,
+            ) {
+                let (near, far) = (near.min(far), near.max(far));
+                let width = Decimal::new(width, 1);
+                let (lower, upper) = (entry / width, entry * width);
+
+                let (near, far) = (
+                    Decimal::ONE + Decimal::new(near, 2),
+                    Decimal::ONE + Decimal::new(far, 2),
+                );
+                for (near, far) in [(entry * near, entry * far), (entry / near, entry / far)] {
+                    let near_il = calculate_il_concentrated(entry, near, lower, upper).unwrap();
+                    let far_il = calculate_il_concentrated(entry, far, lower, upper).unwrap();
+                    prop_assert!(far_il <= near_il + tolerance(), "{far_il} > {near_il}");
+                }
+            }
+
+            #[test]
+            fn concentrated_il_is_symmetric_in_a_symmetric_range(
+                entry in price(),
+                ratio in 100i64..400,
+                width in 101i64..500,
+            ) {
+                // Moves by the same factor either way lose the same within a
+                // range geometrically centred on the entry price
+                let (ratio, width) = (Decimal::new(ratio, 2), Decimal::new(width, 2));
+                let (lower, upper) = (entry / width, entry * width);
+                let up = calculate_il_concentrated(entry, entry * ratio, lower, upper).unwrap();
+                let down = calculate_il_concentrated(entry, entry / ratio, lower, upper).unwrap();
+                prop_assert!((up - down).abs() < Decimal::new(1, 6), "{up} vs {down}");
+            }
+        }
+    }
 }
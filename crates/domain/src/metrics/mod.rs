@@ -1,9 +1,15 @@
 //! Metrics for analysis.
 
+/// Annualized yields from fee earnings.
+pub mod apy;
+/// Break-even price band of a position.
+pub mod breakeven;
 /// Fee related metrics.
 pub mod fees;
 /// Impermanent loss metrics.
 pub mod impermanent_loss;
+/// Sharpe ratios against a benchmark.
+pub mod sharpe;
 /// Metric types.
 mod types;
 
@@ -0,0 +1,256 @@
+//! Sharpe ratios against a configurable benchmark.
+//!
+//! A Sharpe ratio is excess return per unit of volatility, and "excess"
+//! needs a reference. For a liquidity position there are several sensible
+//! ones: cash, the tokens the position was opened with (HODL), or a plain
+//! 50/50 split of the two tokens. [`SharpeConfig`] names the benchmark and
+//! the annual risk-free rate so results computed against different
+//! references are never mixed up.
+
+use crate::value_objects::price_range::PriceRange;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Portfolio a position's returns are measured against.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Benchmark {
+    /// Holding the token amounts the position was opened with.
+    Hodl,
+    /// Holding half the capital in each token from entry.
+    FiftyFifty,
+    /// Cash: returns are measured against the risk-free rate alone.
+    #[default]
+    Zero,
+}
+
+impl Benchmark {
+    /// Name the benchmark is stored and selected by.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Hodl => "hodl",
+            Self::FiftyFifty => "fifty_fifty",
+            Self::Zero => "zero",
+        }
+    }
+
+    /// Returns the value at `price` of `capital` put into the benchmark at
+    /// `entry_price`, with the quote token as the unit of account.
+    ///
+    /// The HODL benchmark holds the base-token share a position in `range`
+    /// starts with; without a range it falls back to 50/50.
+    #[must_use]
+    pub fn value(
+        self,
+        capital: Decimal,
+        entry_price: Decimal,
+        price: Decimal,
+        range: Option<&PriceRange>,
+    ) -> Decimal {
+        if entry_price <= Decimal::ZERO {
+            return capital;
+        }
+        let base_share = match self {
+            Self::Zero => return capital,
+            Self::FiftyFifty => Decimal::new(5, 1),
+            Self::Hodl => range.map_or(Decimal::new(5, 1), |range| {
+                base_share_at(range, entry_price)
+            }),
+        };
+        capital * (base_share * price / entry_price + Decimal::ONE - base_share)
+    }
+}
+
+impl fmt::Display for Benchmark {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Benchmark {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "hodl" => Ok(Self::Hodl),
+            "fifty_fifty" | "50/50" | "50-50" => Ok(Self::FiftyFifty),
+            "zero" | "cash" => Ok(Self::Zero),
+            other => Err(format!(
+                "unknown benchmark '{}', expected hodl, fifty_fifty or zero",
+                other
+            )),
+        }
+    }
+}
+
+/// Share of value a concentrated position in `range` holds in the base
+/// token at `price`.
+fn base_share_at(range: &PriceRange, price: Decimal) -> Decimal {
+    let to_f64 = |d: Decimal| d.to_f64().unwrap_or_default();
+    let (lower, upper, price) = (
+        to_f64(range.lower_price.value),
+        to_f64(range.upper_price.value),
+        to_f64(price),
+    );
+    if price <= lower {
+        return Decimal::ONE;
+    }
+    if price >= upper || lower <= 0.0 {
+        return Decimal::ZERO;
+    }
+    // Per unit of liquidity: base value p(1/√p − 1/√b), quote value √p − √a
+    let base = price.sqrt() - price / upper.sqrt();
+    let quote = price.sqrt() - lower.sqrt();
+    Decimal::from_f64(base / (base + quote)).unwrap_or(Decimal::new(5, 1))
+}
+
+/// Risk-free rate and benchmark used for Sharpe ratios.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SharpeConfig {
+    /// Annual risk-free rate (e.g., 0.05 for 5%).
+    pub risk_free_rate: Decimal,
+    /// Portfolio returns are measured against.
+    pub benchmark: Benchmark,
+}
+
+impl Default for SharpeConfig {
+    /// A 5% risk-free rate against cash.
+    fn default() -> Self {
+        Self {
+            risk_free_rate: Decimal::new(5, 2),
+            benchmark: Benchmark::Zero,
+        }
+    }
+}
+
+impl SharpeConfig {
+    /// Creates a configuration.
+    #[must_use]
+    pub fn new(risk_free_rate: Decimal, benchmark: Benchmark) -> Self {
+        Self {
+            risk_free_rate,
+            benchmark,
+        }
+    }
+
+    /// Annualized Sharpe ratio of a value series sampled `periods_per_year`
+    /// times a year, against `benchmark_values` sampled at the same times.
+    ///
+    /// Each period's excess return is the position's return less the
+    /// benchmark's and the per-period risk-free rate. Returns `None` with
+    /// fewer than two periods, mismatched series, or no volatility.
+    #[must_use]
+    pub fn sharpe_ratio(
+        &self,
+        values: &[Decimal],
+        benchmark_values: &[Decimal],
+        periods_per_year: f64,
+    ) -> Option<Decimal> {
+        if values.len() != benchmark_values.len() || periods_per_year <= 0.0 {
+            return None;
+        }
+        let risk_free = self.risk_free_rate.to_f64()? / periods_per_year;
+        let excess: Vec<f64> = values
+            .windows(2)
+            .zip(benchmark_values.windows(2))
+            .filter_map(|(v, b)| {
+                let ret = period_return(v[0], v[1])?;
+                let benchmark = period_return(b[0], b[1])?;
+                Some(ret - benchmark - risk_free)
+            })
+            .collect();
+        if excess.len() < 2 {
+            return None;
+        }
+
+        let n = excess.len() as f64;
+        let mean = excess.iter().sum::<f64>() / n;
+        let std_dev = (excess.iter().map(|e| (e - mean).powi(2)).sum::<f64>() / n).sqrt();
+        if std_dev < 1e-12 {
+            return None;
+        }
+        Decimal::from_f64(mean / std_dev * periods_per_year.sqrt()).map(|s| s.round_dp(6))
+    }
+}
+
+impl fmt::Display for SharpeConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}% risk-free vs {}",
+            (self.risk_free_rate * Decimal::ONE_HUNDRED).normalize(),
+            self.benchmark
+        )
+    }
+}
+
+fn period_return(from: Decimal, to: Decimal) -> Option<f64> {
+    if from.is_zero() {
+        return None;
+    }
+    ((to - from) / from).to_f64()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_objects::price::Price;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_benchmark_values() {
+        let range = PriceRange::new(Price::new(dec!(80)), Price::new(dec!(125)));
+
+        assert_eq!(
+            Benchmark::Zero.value(dec!(1000), dec!(100), dec!(120), None),
+            dec!(1000)
+        );
+        assert_eq!(
+            Benchmark::FiftyFifty.value(dec!(1000), dec!(100), dec!(120), Some(&range)),
+            dec!(1100)
+        );
+        // A range symmetric in log terms starts half in each token
+        let hodl = Benchmark::Hodl.value(dec!(1000), dec!(100), dec!(120), Some(&range));
+        assert!((hodl - dec!(1100)).abs() < dec!(0.001));
+
+        // A range above the price holds only the base token
+        let above = PriceRange::new(Price::new(dec!(110)), Price::new(dec!(130)));
+        assert_eq!(
+            Benchmark::Hodl.value(dec!(1000), dec!(100), dec!(120), Some(&above)),
+            dec!(1200)
+        );
+
+        assert_eq!("50/50".parse::<Benchmark>().unwrap(), Benchmark::FiftyFifty);
+        assert!("sp500".parse::<Benchmark>().is_err());
+    }
+
+    #[test]
+    fn test_sharpe_ratio_net_of_benchmark_and_risk_free_rate() {
+        let values = [dec!(100), dec!(102), dec!(101), dec!(104), dec!(105)];
+        let flat = [dec!(100); 5];
+
+        let raw = SharpeConfig::new(Decimal::ZERO, Benchmark::Zero)
+            .sharpe_ratio(&values, &flat, 12.0)
+            .unwrap();
+        let with_rate = SharpeConfig::default()
+            .sharpe_ratio(&values, &flat, 12.0)
+            .unwrap();
+        assert!(raw > Decimal::ZERO);
+        assert!(with_rate < raw);
+
+        // Measured against itself, nothing is left over
+        assert_eq!(
+            SharpeConfig::new(Decimal::ZERO, Benchmark::Hodl).sharpe_ratio(&values, &values, 12.0),
+            None
+        );
+        assert_eq!(
+            SharpeConfig::default().sharpe_ratio(&values[..2], &flat[..2], 12.0),
+            None
+        );
+        assert_eq!(SharpeConfig::default().to_string(), "5% risk-free vs zero");
+    }
+}
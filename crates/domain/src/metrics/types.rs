@@ -15,9 +15,12 @@ pub struct ImpermanentLoss {
 /// Represents Annual Percentage Yield (APY).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct APY {
-    /// Estimated annual return.
+    /// Estimated annual return with compounding.
     pub estimated_annual_return: Decimal,
-    /// Number of days the APY is based on.
+    /// Annual return without compounding (APR).
+    pub apr: Decimal,
+    /// Number of whole days the APY is based on; partial days still count
+    /// towards the rates.
     pub based_on_days: u32,
 }
 
@@ -21,14 +21,17 @@ pub use crate::enums::{OptimizationObjective, PoolType, PositionStatus, Protocol
 pub use crate::fees::{FeeAccumulation, FeeTier};
 
 // Math functions
+pub use crate::math::capital_efficiency::{CapitalEfficiency, capital_efficiency};
 pub use crate::math::concentrated_liquidity::{
-    get_amount0_delta, get_amount1_delta, get_liquidity_for_amount0, get_liquidity_for_amount1,
+    concentration_multiplier, get_amount0_delta, get_amount1_delta, get_liquidity_for_amount0,
+    get_liquidity_for_amount1,
 };
 pub use crate::math::constant_product::{calculate_k, calculate_out_amount, calculate_spot_price};
 pub use crate::math::fee_math::{
     FeeTier as MathFeeTier, bps_to_decimal, calculate_effective_fee_rate, calculate_fee_amount,
     calculate_lp_fee_share, decimal_to_bps, estimate_position_fees_24h,
 };
+pub use crate::math::greeks::{PositionGreeks, position_greeks};
 pub use crate::math::price_impact::{
     calculate_execution_price, calculate_slippage, estimate_max_swap_for_impact,
     estimate_price_impact_clmm, estimate_price_impact_constant_product,
@@ -36,22 +39,28 @@ pub use crate::math::price_impact::{
 pub use crate::math::price_tick::{price_to_tick, tick_to_price};
 
 // Metrics
+pub use crate::metrics::apy::{
+    SECONDS_PER_YEAR, annualize_compounded, annualize_simple, fee_apr, fee_apy, fees_in_window,
+};
+pub use crate::metrics::breakeven::{BreakevenBand, breakeven_band};
 pub use crate::metrics::fees::{
     FeeProjectionModel, analyze_fee_sustainability, apr_to_apy, calculate_apy,
     calculate_breakeven_days, calculate_fee_efficiency, calculate_pool_fees,
     calculate_required_fee_rate, project_fees,
 };
 pub use crate::metrics::impermanent_loss::{
-    calculate_il_concentrated, calculate_il_constant_product,
+    ConcentratedIl, calculate_il_concentrated, calculate_il_constant_product,
 };
+pub use crate::metrics::sharpe::{Benchmark, SharpeConfig};
 pub use crate::metrics::{APY, ImpermanentLoss, PnL};
 
 // Value objects
-pub use crate::value_objects::amount::Amount;
-pub use crate::value_objects::optimization_result::OptimizationResult;
+pub use crate::value_objects::amount::{Amount, Rounding};
+pub use crate::value_objects::optimization_result::{Explanation, OptimizationResult};
 pub use crate::value_objects::percentage::Percentage;
 pub use crate::value_objects::price::Price;
 pub use crate::value_objects::price_range::PriceRange;
+pub use crate::value_objects::range_spec::RangeSpec;
 pub use crate::value_objects::simulation_result::SimulationResult;
 pub use crate::value_objects::{
     FeeEarnings, ImpermanentLossResult, PoolMetrics, RiskMetrics, VolatilityEstimate,
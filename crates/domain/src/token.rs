@@ -1,3 +1,4 @@
+use crate::value_objects::amount::{Rounding, decimal_to_u256, u256_to_decimal};
 use primitive_types::U256;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -63,6 +64,25 @@ impl TokenAmount {
     pub fn as_u256(&self) -> U256 {
         self.0
     }
+
+    /// Converts the amount to a decimal of base units.
+    ///
+    /// # Errors
+    /// Returns an error if the amount exceeds the 96 bits a `Decimal` holds.
+    pub fn to_decimal(&self) -> Result<Decimal, &'static str> {
+        u256_to_decimal(self.0).ok_or("Amount overflow")
+    }
+
+    /// Creates an amount from a decimal of base units, resolving fractions
+    /// with `rounding`.
+    ///
+    /// # Errors
+    /// Returns an error for negative values.
+    pub fn try_from_decimal(d: Decimal, rounding: Rounding) -> Result<Self, &'static str> {
+        decimal_to_u256(d, rounding)
+            .map(Self)
+            .ok_or("Amount must not be negative")
+    }
 }
 
 impl From<u64> for TokenAmount {
@@ -1,7 +1,74 @@
+//! Token amounts in base units and their decimal conversions.
+//!
+//! Raw amounts are `U256`, but `Decimal` holds at most 96 bits with up to 28
+//! decimal places. Conversions are therefore explicit about both failure
+//! modes: amounts too large for the other side are rejected (or saturated,
+//! where the method says so), and fractional base units are resolved by a
+//! [`Rounding`] policy instead of silently truncated.
+
 use primitive_types::U256;
-use rust_decimal::Decimal;
-use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::{Decimal, RoundingStrategy};
 use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Largest number of decimals a `Decimal` can represent.
+pub const MAX_DECIMALS: u8 = 28;
+
+/// How fractional base units are resolved when a decimal becomes a raw
+/// amount.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Rounding {
+    /// Toward zero, never crediting more than the exact amount.
+    #[default]
+    Down,
+    /// Away from zero, never crediting less than the exact amount.
+    Up,
+    /// To the nearest unit, ties away from zero.
+    HalfUp,
+    /// To the nearest unit, ties to even (banker's rounding), which avoids
+    /// drift when many rounded amounts are summed.
+    HalfEven,
+}
+
+impl Rounding {
+    fn strategy(self) -> RoundingStrategy {
+        match self {
+            Self::Down => RoundingStrategy::ToZero,
+            Self::Up => RoundingStrategy::AwayFromZero,
+            Self::HalfUp => RoundingStrategy::MidpointAwayFromZero,
+            Self::HalfEven => RoundingStrategy::MidpointNearestEven,
+        }
+    }
+}
+
+/// Converts a raw integer to a decimal.
+///
+/// Returns `None` if it exceeds the 96 bits a `Decimal` can hold.
+#[must_use]
+pub fn u256_to_decimal(raw: U256) -> Option<Decimal> {
+    Decimal::from_u128(u128::try_from(raw).ok()?)
+}
+
+/// Converts a decimal to a raw integer, rounding fractions with `rounding`.
+///
+/// Returns `None` if the rounded value is negative.
+#[must_use]
+pub fn decimal_to_u256(d: Decimal, rounding: Rounding) -> Option<U256> {
+    let rounded = d.round_dp_with_strategy(0, rounding.strategy());
+    if rounded.is_sign_negative() && !rounded.is_zero() {
+        return None;
+    }
+    rounded.to_u128().map(U256::from)
+}
+
+/// Returns 10^decimals as a decimal, or `None` beyond [`MAX_DECIMALS`].
+fn scale_factor(decimals: u8) -> Option<Decimal> {
+    if decimals > MAX_DECIMALS {
+        return None;
+    }
+    Decimal::from_u128(10u128.pow(u32::from(decimals)))
+}
 
 /// Represents an amount with decimals.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
@@ -18,23 +85,241 @@ impl Amount {
         Self { raw, decimals }
     }
 
-    /// Creates an Amount from a decimal.
+    /// Creates a zero amount with the given decimals.
+    pub fn zero(decimals: u8) -> Self {
+        Self::new(U256::zero(), decimals)
+    }
+
+    /// Creates an Amount from a decimal, truncating fractional base units,
+    /// rejecting negative values and amounts that overflow the raw
+    /// representation.
+    pub fn try_from_decimal(d: Decimal, decimals: u8) -> Result<Self, &'static str> {
+        Self::try_from_decimal_rounded(d, decimals, Rounding::Down)
+    }
+
+    /// Creates an Amount from a decimal, resolving fractional base units
+    /// with `rounding`.
+    ///
+    /// # Errors
+    /// Returns an error for negative values, more than [`MAX_DECIMALS`]
+    /// decimals, or amounts that overflow.
+    pub fn try_from_decimal_rounded(
+        d: Decimal,
+        decimals: u8,
+        rounding: Rounding,
+    ) -> Result<Self, &'static str> {
+        if d.is_sign_negative() && !d.is_zero() {
+            return Err("Amount must not be negative");
+        }
+        let factor = scale_factor(decimals).ok_or("Too many decimals")?;
+        let raw = d
+            .checked_mul(factor)
+            .and_then(|raw| decimal_to_u256(raw, rounding))
+            .ok_or("Amount overflow")?;
+        Ok(Self::new(raw, decimals))
+    }
+
+    /// Creates an Amount from a decimal, truncating fractional base units.
+    ///
+    /// Negative or overflowing values become zero; use
+    /// [`Amount::try_from_decimal`] to detect them.
     pub fn from_decimal(d: Decimal, decimals: u8) -> Self {
-        let multiplier = Decimal::from(10u64.pow(decimals as u32));
-        let raw_decimal = d * multiplier;
-        // This conversion is simplistic and might panic on overflow or negative
-        let raw_u128 = raw_decimal.to_u128().unwrap_or(0);
-        Self {
-            raw: U256::from(raw_u128),
-            decimals,
+        Self::try_from_decimal(d, decimals).unwrap_or_else(|_| Self::zero(decimals))
+    }
+
+    /// Converts the Amount to a decimal exactly.
+    ///
+    /// # Errors
+    /// Returns an error if the raw amount exceeds 96 bits or the amount has
+    /// more than [`MAX_DECIMALS`] decimals.
+    pub fn try_to_decimal(&self) -> Result<Decimal, &'static str> {
+        if self.decimals > MAX_DECIMALS {
+            return Err("Too many decimals");
         }
+        let raw = u128::try_from(self.raw)
+            .ok()
+            .and_then(|raw| i128::try_from(raw).ok())
+            .ok_or("Amount overflow")?;
+        Decimal::try_from_i128_with_scale(raw, u32::from(self.decimals))
+            .map_err(|_| "Amount overflow")
     }
 
-    /// Converts the Amount to a decimal.
+    /// Converts the Amount to a decimal, saturating at `Decimal::MAX` for
+    /// amounts that do not fit.
     pub fn to_decimal(&self) -> Decimal {
-        let raw_u128 = self.raw.low_u128(); // Truncates if > u128::MAX, careful
-        let d = Decimal::from(raw_u128);
-        let divisor = Decimal::from(10u64.pow(self.decimals as u32));
-        d / divisor
+        self.try_to_decimal().unwrap_or(Decimal::MAX)
+    }
+
+    /// Returns true if the amount is zero.
+    #[must_use]
+    pub fn is_zero(&self) -> bool {
+        self.raw.is_zero()
+    }
+
+    /// Adds two amounts, returning `None` if their decimals differ or on
+    /// overflow.
+    #[must_use]
+    pub fn checked_add(&self, other: Self) -> Option<Self> {
+        if self.decimals != other.decimals {
+            return None;
+        }
+        let raw = self.raw.checked_add(other.raw)?;
+        Some(Self::new(raw, self.decimals))
+    }
+
+    /// Subtracts an amount, returning `None` if their decimals differ or the
+    /// result would be negative.
+    #[must_use]
+    pub fn checked_sub(&self, other: Self) -> Option<Self> {
+        if self.decimals != other.decimals {
+            return None;
+        }
+        let raw = self.raw.checked_sub(other.raw)?;
+        Some(Self::new(raw, self.decimals))
+    }
+
+    /// Multiplies the amount by a scalar, truncating to whole base units.
+    ///
+    /// Returns `None` for negative scalars, raw amounts beyond 96 bits or on
+    /// overflow.
+    #[must_use]
+    pub fn checked_mul(&self, scalar: Decimal) -> Option<Self> {
+        self.checked_mul_rounded(scalar, Rounding::Down)
+    }
+
+    /// Multiplies the amount by a scalar, resolving fractional base units
+    /// with `rounding`.
+    ///
+    /// Returns `None` for negative scalars, raw amounts beyond 96 bits or on
+    /// overflow.
+    #[must_use]
+    pub fn checked_mul_rounded(&self, scalar: Decimal, rounding: Rounding) -> Option<Self> {
+        if scalar.is_sign_negative() && !scalar.is_zero() {
+            return None;
+        }
+        let product = u256_to_decimal(self.raw)?.checked_mul(scalar)?;
+        Some(Self::new(
+            decimal_to_u256(product, rounding)?,
+            self.decimals,
+        ))
+    }
+
+    /// Formats the amount in whole tokens with all its decimals.
+    #[must_use]
+    pub fn format(&self) -> String {
+        format!("{:.*}", self.decimals as usize, self.to_decimal())
+    }
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.to_decimal().normalize().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_amount_arithmetic_and_validation() {
+        let a = Amount::from_decimal(dec!(1.5), 6);
+        let b = Amount::from_decimal(dec!(0.25), 6);
+
+        assert!(Amount::try_from_decimal(dec!(-1), 6).is_err());
+        assert_eq!(Amount::try_from_decimal(dec!(1.5), 6), Ok(a));
+
+        assert_eq!(a.checked_add(b), Some(Amount::from_decimal(dec!(1.75), 6)));
+        assert_eq!(b.checked_sub(a), None);
+        assert_eq!(a.checked_add(Amount::zero(9)), None);
+        assert_eq!(
+            a.checked_mul(dec!(2)),
+            Some(Amount::from_decimal(dec!(3), 6))
+        );
+        assert_eq!(a.checked_mul(dec!(-2)), None);
+        assert!(a.checked_sub(a).unwrap().is_zero());
+    }
+
+    #[test]
+    fn test_amount_display() {
+        let amount = Amount::from_decimal(dec!(1.5), 6);
+        assert_eq!(amount.to_string(), "1.5");
+        assert_eq!(amount.format(), "1.500000");
+    }
+
+    #[test]
+    fn test_conversion_overflow_and_rounding() {
+        // 2^96 - 1 is the largest raw amount a Decimal holds
+        let max = U256::from(u128::MAX >> 32);
+        assert_eq!(Amount::new(max, 0).try_to_decimal(), Ok(Decimal::MAX));
+        assert!(Amount::new(max + 1, 6).try_to_decimal().is_err());
+        assert!(Amount::new(U256::MAX, 6).try_to_decimal().is_err());
+        assert_eq!(Amount::new(U256::MAX, 6).to_decimal(), Decimal::MAX);
+        assert!(Amount::new(U256::one(), 29).try_to_decimal().is_err());
+        assert!(Amount::try_from_decimal(dec!(1), 29).is_err());
+
+        // 2.5 and 3.5 base units under each policy
+        let units = |d: Decimal, rounding| {
+            Amount::try_from_decimal_rounded(d, 0, rounding)
+                .unwrap()
+                .raw
+                .as_u64()
+        };
+        assert_eq!(units(dec!(2.5), Rounding::Down), 2);
+        assert_eq!(units(dec!(2.5), Rounding::Up), 3);
+        assert_eq!(units(dec!(2.5), Rounding::HalfUp), 3);
+        assert_eq!(units(dec!(2.5), Rounding::HalfEven), 2);
+        assert_eq!(units(dec!(3.5), Rounding::HalfEven), 4);
+        assert_eq!(units(dec!(2.1), Rounding::Up), 3);
+
+        assert_eq!(
+            decimal_to_u256(dec!(-0.4), Rounding::HalfEven),
+            Some(U256::zero())
+        );
+        assert_eq!(decimal_to_u256(dec!(-0.6), Rounding::HalfEven), None);
+        assert_eq!(
+            Amount::from_decimal(dec!(1), 6).checked_mul_rounded(dec!(0.0000015), Rounding::HalfUp),
+            Some(Amount::new(U256::from(2), 6))
+        );
+    }
+
+    #[test]
+    fn test_fuzz_decimal_round_trips() {
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..10_000 {
+            let decimals = rng.random_range(0..=MAX_DECIMALS);
+            let raw = rng.random_range(0..=u128::MAX >> 32) >> rng.random_range(0..96);
+            let amount = Amount::new(U256::from(raw), decimals);
+
+            // Every raw amount within 96 bits converts exactly and back
+            let d = amount.try_to_decimal().unwrap();
+            assert_eq!(d.mantissa() as u128, raw);
+            for rounding in [
+                Rounding::Down,
+                Rounding::Up,
+                Rounding::HalfUp,
+                Rounding::HalfEven,
+            ] {
+                assert_eq!(
+                    Amount::try_from_decimal_rounded(d, decimals, rounding),
+                    Ok(amount)
+                );
+            }
+
+            // Fractions of a base unit round within one unit of the exact value
+            let exact = d.checked_mul(Decimal::new(rng.random_range(1..1000), 3));
+            if let Some(exact) = exact {
+                let down = Amount::try_from_decimal_rounded(exact, decimals, Rounding::Down);
+                let up = Amount::try_from_decimal_rounded(exact, decimals, Rounding::Up);
+                if let (Ok(down), Ok(up)) = (down, up) {
+                    assert!(down.raw <= up.raw && up.raw - down.raw <= U256::one());
+                    assert!(down.to_decimal() <= exact && exact <= up.to_decimal());
+                }
+            }
+        }
     }
 }
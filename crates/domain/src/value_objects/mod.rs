@@ -10,10 +10,12 @@ pub mod percentage;
 pub mod price;
 /// Price range value object.
 pub mod price_range;
+/// Range specification value object.
+pub mod range_spec;
 /// Simulation result value object.
 pub mod simulation_result;
 /// Common value object types.
 mod types;
 
-pub use optimization_result::OptimizationResult;
+pub use optimization_result::{Explanation, OptimizationResult};
 pub use types::{FeeEarnings, ImpermanentLossResult, PoolMetrics, RiskMetrics, VolatilityEstimate};
@@ -1,3 +1,4 @@
+use crate::metrics::sharpe::SharpeConfig;
 use crate::value_objects::price_range::PriceRange;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -11,8 +12,42 @@ pub struct OptimizationResult {
     pub expected_pnl: Decimal,
     /// The expected fees.
     pub expected_fees: Decimal,
+    /// The standard deviation of the expected fees.
+    #[serde(default)]
+    pub fee_std_dev: Decimal,
     /// The expected impermanent loss.
     pub expected_il: Decimal,
     /// The Sharpe ratio.
     pub sharpe_ratio: Option<Decimal>,
+    /// Risk-free rate and benchmark the Sharpe ratio was computed with.
+    #[serde(default)]
+    pub sharpe_config: Option<SharpeConfig>,
+    /// Why this range was recommended.
+    #[serde(default)]
+    pub explanation: Option<Explanation>,
+}
+
+/// Human-readable reasons behind an optimization result.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Explanation {
+    /// Factors that drove the recommended width, most important first.
+    pub drivers: Vec<String>,
+    /// The largest risk of the recommended range.
+    pub main_risk: String,
+    /// How the recommendation compares to the previous one.
+    pub comparison: Option<String>,
+}
+
+impl Explanation {
+    /// Returns the explanation as lines of text: drivers, then the main
+    /// risk, then the comparison.
+    #[must_use]
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = self.drivers.clone();
+        lines.push(format!("Main risk: {}", self.main_risk));
+        if let Some(comparison) = &self.comparison {
+            lines.push(comparison.clone());
+        }
+        lines
+    }
 }
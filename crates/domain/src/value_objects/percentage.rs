@@ -1,6 +1,7 @@
-use rust_decimal::Decimal;
 use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// A wrapper struct representing a percentage value, built on top of a `Decimal` type.
 ///
@@ -18,17 +19,102 @@ use serde::{Deserialize, Serialize};
 ///   for saving or transferring the value as part of an external format (e.g., JSON).
 /// - `Deserialize`: Enables deserialization of a `Percentage` value from an external format,
 ///   allowing it to be reconstructed from serialized data.
+/// - `PartialEq`, `Eq`, `PartialOrd` and `Ord`: Allow comparing and sorting percentages.
 ///
 /// # Fields
-/// - `0` (`Decimal`): A wrapped `Decimal` value representing the percentage value.
+/// - `0` (`Decimal`): A wrapped `Decimal` value representing the percentage as a
+///   fraction, so `0.05` is 5%.
 ///
 /// The `Percentage` struct is designed to be flexible and precise for use cases
 /// involving financial computations, statistics, or any domain requiring accurate
 /// representation of percentages.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Percentage(pub Decimal);
 
 impl Percentage {
+    /// 0%.
+    pub const ZERO: Self = Self(Decimal::ZERO);
+
+    /// 100%.
+    pub const ONE_HUNDRED: Self = Self(Decimal::ONE);
+
+    /// Creates a percentage from a fraction, rejecting values outside
+    /// 0% to 100%.
+    ///
+    /// # Parameters
+    /// - `fraction`: The percentage as a fraction, e.g. `0.05` for 5%.
+    ///
+    /// # Errors
+    /// Returns an error if `fraction` is negative or greater than one.
+    ///
+    pub fn try_new(fraction: Decimal) -> Result<Self, &'static str> {
+        if fraction < Decimal::ZERO || fraction > Decimal::ONE {
+            return Err("Percentage must be between 0% and 100%");
+        }
+        Ok(Self(fraction))
+    }
+
+    /// Creates a percentage from a value in percent, e.g. `5` for 5%.
+    ///
+    /// The value is not bounded, so it can express changes such as -20% or 150%.
+    pub fn from_percent(percent: Decimal) -> Self {
+        Self(percent / Decimal::ONE_HUNDRED)
+    }
+
+    /// Returns the percentage as a fraction, e.g. `0.05` for 5%.
+    #[must_use]
+    pub fn as_fraction(&self) -> Decimal {
+        self.0
+    }
+
+    /// Returns the percentage in percent, e.g. `5` for 5%.
+    #[must_use]
+    pub fn as_percent(&self) -> Decimal {
+        self.0 * Decimal::ONE_HUNDRED
+    }
+
+    /// Returns true if the percentage is between 0% and 100%.
+    #[must_use]
+    pub fn is_bounded(&self) -> bool {
+        self.0 >= Decimal::ZERO && self.0 <= Decimal::ONE
+    }
+
+    /// Applies the percentage to a value, e.g. 5% of 200 is 10.
+    #[must_use]
+    pub fn of(&self, value: Decimal) -> Decimal {
+        value * self.0
+    }
+
+    /// Adds two percentages, returning `None` on overflow.
+    #[must_use]
+    pub fn checked_add(&self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Self)
+    }
+
+    /// Subtracts a percentage, returning `None` on overflow.
+    #[must_use]
+    pub fn checked_sub(&self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(Self)
+    }
+
+    /// Multiplies the percentage by a scalar, returning `None` on overflow.
+    #[must_use]
+    pub fn checked_mul(&self, scalar: Decimal) -> Option<Self> {
+        self.0.checked_mul(scalar).map(Self)
+    }
+
+    /// Formats the percentage in percent, rounded to `decimals` places,
+    /// e.g. `"5.25%"`.
+    #[must_use]
+    pub fn format_with_decimals(&self, decimals: u32) -> String {
+        format!(
+            "{:.*}%",
+            decimals as usize,
+            self.as_percent()
+                .round_dp_with_strategy(decimals, RoundingStrategy::MidpointAwayFromZero)
+        )
+    }
+
     /// Creates a new instance of the type from a given value in basis points (bps).
     ///
     /// # Parameters
@@ -63,3 +149,44 @@ impl Percentage {
         (self.0 * Decimal::from(10000)).to_u32().unwrap_or(0)
     }
 }
+
+impl fmt::Display for Percentage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}%", self.as_percent().normalize())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_percentage_validation_and_arithmetic() {
+        assert!(Percentage::try_new(dec!(-0.01)).is_err());
+        assert!(Percentage::try_new(dec!(1.01)).is_err());
+        assert_eq!(
+            Percentage::try_new(Decimal::ONE),
+            Ok(Percentage::ONE_HUNDRED)
+        );
+
+        let fee = Percentage::from_bps(30);
+        assert_eq!(fee, Percentage::from_percent(dec!(0.3)));
+        assert_eq!(fee.as_percent(), dec!(0.3));
+        assert_eq!(fee.of(dec!(1000)), dec!(3));
+        assert!(fee < Percentage::from_bps(100));
+
+        let loss = Percentage::from_percent(dec!(-20));
+        assert!(!loss.is_bounded());
+        assert_eq!(fee.checked_add(fee), Some(Percentage::from_bps(60)));
+        assert_eq!(fee.checked_sub(fee), Some(Percentage::ZERO));
+        assert_eq!(fee.checked_mul(dec!(2)), Some(Percentage::from_bps(60)));
+    }
+
+    #[test]
+    fn test_percentage_display() {
+        assert_eq!(Percentage::from_bps(525).to_string(), "5.25%");
+        assert_eq!(Percentage::from_percent(dec!(-20)).to_string(), "-20%");
+        assert_eq!(Percentage::from_bps(525).format_with_decimals(1), "5.3%");
+    }
+}
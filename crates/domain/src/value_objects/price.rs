@@ -1,5 +1,6 @@
-use rust_decimal::Decimal;
+use rust_decimal::{Decimal, RoundingStrategy};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// The `Price` struct represents a monetary value using a `Decimal` type for precision.
 ///
@@ -27,11 +28,110 @@ pub struct Price {
 }
 
 impl Price {
+    /// A price of zero.
+    pub const ZERO: Self = Self {
+        value: Decimal::ZERO,
+    };
+
     /// Creates a new Price.
     pub fn new(value: Decimal) -> Self {
         Self { value }
     }
 
+    /// Creates a new Price, rejecting negative values.
+    pub fn try_new(value: Decimal) -> Result<Self, &'static str> {
+        if value.is_sign_negative() && !value.is_zero() {
+            return Err("Price must not be negative");
+        }
+        Ok(Self { value })
+    }
+
+    /// Returns true if the price is zero.
+    #[must_use]
+    pub fn is_zero(&self) -> bool {
+        self.value.is_zero()
+    }
+
+    /// Adds two prices, returning `None` on overflow.
+    #[must_use]
+    pub fn checked_add(&self, other: Self) -> Option<Self> {
+        self.value.checked_add(other.value).map(Self::new)
+    }
+
+    /// Subtracts a price, returning `None` on overflow or a negative result.
+    #[must_use]
+    pub fn checked_sub(&self, other: Self) -> Option<Self> {
+        let value = self.value.checked_sub(other.value)?;
+        Self::try_new(value).ok()
+    }
+
+    /// Multiplies the price by a scalar, returning `None` on overflow or a
+    /// negative result.
+    #[must_use]
+    pub fn checked_mul(&self, scalar: Decimal) -> Option<Self> {
+        let value = self.value.checked_mul(scalar)?;
+        Self::try_new(value).ok()
+    }
+
+    /// Divides the price by a scalar, returning `None` on division by zero,
+    /// overflow or a negative result.
+    #[must_use]
+    pub fn checked_div(&self, scalar: Decimal) -> Option<Self> {
+        let value = self.value.checked_div(scalar)?;
+        Self::try_new(value).ok()
+    }
+
+    /// Returns the relative change from this price to `other`, or `None`
+    /// if this price is zero.
+    ///
+    /// A move from 100 to 105 is a change of 0.05.
+    #[must_use]
+    pub fn change_to(&self, other: Self) -> Option<Decimal> {
+        if self.is_zero() {
+            return None;
+        }
+        Some((other.value - self.value) / self.value)
+    }
+
+    /// Returns true if the price is within `tolerance` (a fraction, e.g.
+    /// 0.01 for 1%) of `other`.
+    #[must_use]
+    pub fn is_close_to(&self, other: Self, tolerance: Decimal) -> bool {
+        self.change_to(other)
+            .is_some_and(|change| change.abs() <= tolerance)
+            || self.value == other.value
+    }
+
+    /// Scales a raw price, quoted in base units, to whole tokens.
+    ///
+    /// A raw price of token B base units per token A base unit is
+    /// multiplied by 10^(`decimals_a` - `decimals_b`).
+    #[must_use]
+    pub fn from_raw(raw: Decimal, decimals_a: u8, decimals_b: u8) -> Self {
+        Self::new(shift_decimals(
+            raw,
+            i32::from(decimals_a) - i32::from(decimals_b),
+        ))
+    }
+
+    /// Scales the price in whole tokens to base units, the inverse of
+    /// [`Price::from_raw`].
+    #[must_use]
+    pub fn to_raw(&self, decimals_a: u8, decimals_b: u8) -> Decimal {
+        shift_decimals(self.value, i32::from(decimals_b) - i32::from(decimals_a))
+    }
+
+    /// Formats the price rounded to `decimals` places.
+    #[must_use]
+    pub fn format_with_decimals(&self, decimals: u32) -> String {
+        format!(
+            "{:.*}",
+            decimals as usize,
+            self.value
+                .round_dp_with_strategy(decimals, RoundingStrategy::MidpointAwayFromZero)
+        )
+    }
+
     /// Inverts the price (1/price).
     pub fn invert(&self) -> Self {
         if self.value.is_zero() {
@@ -46,3 +146,59 @@ impl Price {
         }
     }
 }
+
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.normalize().fmt(f)
+    }
+}
+
+/// Multiplies a value by 10^`shift`.
+fn shift_decimals(value: Decimal, shift: i32) -> Decimal {
+    let scale = Decimal::from(10u64.pow(shift.unsigned_abs()));
+    if shift >= 0 {
+        value * scale
+    } else {
+        value / scale
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_price_arithmetic_and_validation() {
+        let price = Price::new(dec!(100));
+
+        assert!(Price::try_new(dec!(-1)).is_err());
+        assert_eq!(Price::try_new(Decimal::ZERO), Ok(Price::ZERO));
+
+        assert_eq!(
+            price.checked_add(Price::new(dec!(5))),
+            Some(Price::new(dec!(105)))
+        );
+        assert_eq!(price.checked_sub(Price::new(dec!(101))), None);
+        assert_eq!(price.checked_mul(dec!(1.5)), Some(Price::new(dec!(150))));
+        assert_eq!(price.checked_mul(dec!(-1)), None);
+        assert_eq!(price.checked_div(Decimal::ZERO), None);
+
+        assert_eq!(price.change_to(Price::new(dec!(105))), Some(dec!(0.05)));
+        assert!(price.is_close_to(Price::new(dec!(100.5)), dec!(0.01)));
+        assert!(!price.is_close_to(Price::new(dec!(102)), dec!(0.01)));
+        assert_eq!(Price::ZERO.change_to(price), None);
+    }
+
+    #[test]
+    fn test_price_decimals_and_display() {
+        // SOL (9 decimals) priced in USDC (6 decimals)
+        let price = Price::from_raw(dec!(0.15), 9, 6);
+        assert_eq!(price.value, dec!(150));
+        assert_eq!(price.to_raw(9, 6), dec!(0.15));
+
+        assert_eq!(Price::new(dec!(150.500)).to_string(), "150.5");
+        assert_eq!(price.format_with_decimals(2), "150.00");
+        assert_eq!(Price::new(dec!(1.23456)).format_with_decimals(3), "1.235");
+    }
+}
@@ -1,4 +1,5 @@
 use crate::value_objects::price::Price;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
 /// A struct representing a price range with a lower and upper price bound.
@@ -18,7 +19,7 @@ use serde::{Deserialize, Serialize};
 /// The `PriceRange` struct derives several useful traits:
 ///
 /// * `Debug` - Enables formatting the structure using the `{:?}` formatter for debugging purposes.
-/// * `Clone` - Allows creating a duplicate of a `PriceRange` instance.
+/// * `Clone` and `Copy` - Allows creating a duplicate of a `PriceRange` instance.
 /// * `Serialize` - Supports serializing the struct into formats such as JSON, typically for storage or communication.
 /// * `Deserialize` - Supports constructing a `PriceRange` instance from serialized data (e.g., JSON).
 ///
@@ -26,7 +27,7 @@ use serde::{Deserialize, Serialize};
 /// Note: The `Price` type must be predefined with your desired implementation
 /// in order to use this struct effectively.
 /// Represents a price range.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct PriceRange {
     /// The lower bound price.
     pub lower_price: Price,
@@ -62,6 +63,126 @@ impl PriceRange {
     /// - `false` otherwise.
     ///
     pub fn contains(&self, price: Price) -> bool {
-        price.value >= self.lower_price.value && price.value <= self.upper_price.value
+        price >= self.lower_price && price <= self.upper_price
+    }
+
+    /// Creates a range centered on `center` whose width is `width_pct` of
+    /// the center price.
+    ///
+    /// # Parameters
+    /// - `center`: The price at the middle of the range.
+    /// - `width_pct`: The total width as a fraction of `center`, e.g. `0.2`
+    ///   for a range from 90 to 110 around 100.
+    ///
+    pub fn from_center_and_width(center: Price, width_pct: Decimal) -> Self {
+        let half_width = center.value * width_pct / Decimal::TWO;
+        Self::new(
+            Price::new(center.value - half_width),
+            Price::new(center.value + half_width),
+        )
+    }
+
+    /// Returns the arithmetic midpoint of the range.
+    pub fn midpoint(&self) -> Price {
+        Price::new((self.lower_price.value + self.upper_price.value) / Decimal::TWO)
+    }
+
+    /// Returns the absolute width of the range.
+    pub fn width(&self) -> Decimal {
+        self.upper_price.value - self.lower_price.value
+    }
+
+    /// Returns the width of the range as a fraction of its midpoint.
+    ///
+    /// # Returns
+    /// The width relative to the midpoint, e.g. `0.2` for a range from 90
+    /// to 110, or zero if the midpoint is zero.
+    ///
+    pub fn width_pct(&self) -> Decimal {
+        let midpoint = self.midpoint().value;
+        if midpoint.is_zero() {
+            return Decimal::ZERO;
+        }
+        self.width() / midpoint
+    }
+
+    /// Returns the offset of a price from the midpoint as a fraction of the
+    /// midpoint, or zero if the midpoint is zero.
+    pub fn offset_from_midpoint(&self, price: Price) -> Decimal {
+        let midpoint = self.midpoint().value;
+        if midpoint.is_zero() {
+            return Decimal::ZERO;
+        }
+        (price.value - midpoint) / midpoint
+    }
+
+    /// Returns a range of the same absolute width centered on `center`.
+    ///
+    /// # Parameters
+    /// - `center`: The price to center the range on.
+    ///
+    pub fn shift_to_center(&self, center: Price) -> Self {
+        self.shift_by(center.value - self.midpoint().value)
+    }
+
+    /// Returns the range moved by `offset` in price.
+    pub fn shift_by(&self, offset: Decimal) -> Self {
+        Self::new(
+            Price::new(self.lower_price.value + offset),
+            Price::new(self.upper_price.value + offset),
+        )
+    }
+
+    /// Returns the overlap of two ranges.
+    ///
+    /// # Returns
+    /// - `Some(range)` spanning the prices within both ranges.
+    /// - `None` if the ranges do not overlap or only touch at a bound.
+    ///
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        let lower = self.lower_price.max(other.lower_price);
+        let upper = self.upper_price.min(other.upper_price);
+        (lower < upper).then(|| Self::new(lower, upper))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn range(lower: Decimal, upper: Decimal) -> PriceRange {
+        PriceRange::new(Price::new(lower), Price::new(upper))
+    }
+
+    #[test]
+    fn test_range_geometry() {
+        let r = PriceRange::from_center_and_width(Price::new(dec!(100)), dec!(0.2));
+        assert_eq!(r, range(dec!(90), dec!(110)));
+        assert_eq!(r.midpoint(), Price::new(dec!(100)));
+        assert_eq!(r.width(), dec!(20));
+        assert_eq!(r.width_pct(), dec!(0.2));
+        assert_eq!(r.offset_from_midpoint(Price::new(dec!(105))), dec!(0.05));
+
+        assert!(r.contains(Price::new(dec!(110))));
+
+        let shifted = r.shift_to_center(Price::new(dec!(120)));
+        assert_eq!(shifted, range(dec!(110), dec!(130)));
+        assert_eq!(r.shift_by(dec!(-5)), range(dec!(85), dec!(105)));
+    }
+
+    #[test]
+    fn test_range_intersect() {
+        let r = range(dec!(90), dec!(110));
+        assert_eq!(
+            r.intersect(&range(dec!(100), dec!(120))),
+            Some(range(dec!(100), dec!(110)))
+        );
+        assert_eq!(
+            r.intersect(&range(dec!(95), dec!(105))),
+            Some(range(dec!(95), dec!(105)))
+        );
+        assert_eq!(r.intersect(&range(dec!(110), dec!(120))), None);
+        assert_eq!(r.intersect(&range(dec!(120), dec!(130))), None);
     }
 }
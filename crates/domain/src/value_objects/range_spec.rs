@@ -0,0 +1,308 @@
+use crate::math::price_tick::{price_to_tick, tick_to_price};
+use crate::value_objects::price::Price;
+use crate::value_objects::price_range::PriceRange;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// A price range specification that can be resolved against market conditions.
+///
+/// `RangeSpec` lets users express a range in the unit that is most natural to
+/// them and resolves it to concrete prices or ticks once the current price
+/// (and, for standard deviation based ranges, the volatility) is known.
+///
+/// ## Variants
+///
+/// * `Absolute` - Fixed lower and upper prices.
+/// * `Percent` - Symmetric ±fraction around the current price (`0.05` = ±5%).
+/// * `StdDev` - ±N standard deviations around the current price, applied in
+///   log space so the lower bound is always positive.
+/// * `Ticks` - ±N ticks around the current tick.
+///
+/// ## Parsing
+///
+/// `RangeSpec` implements `FromStr` with the following formats:
+///
+/// * `"90:110"` - absolute prices
+/// * `"5%"` or `"±5%"` - percent around price
+/// * `"2sigma"`, `"2σ"` or `"2sd"` - standard deviations
+/// * `"500ticks"` or `"500t"` - ticks around the current tick
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RangeSpec {
+    /// Absolute price bounds.
+    Absolute {
+        /// Lower price.
+        lower: Decimal,
+        /// Upper price.
+        upper: Decimal,
+    },
+    /// ±fraction around the current price.
+    Percent(Decimal),
+    /// ±N standard deviations around the current price.
+    StdDev(Decimal),
+    /// ±N ticks around the current tick.
+    Ticks(u32),
+}
+
+impl RangeSpec {
+    /// Resolves the specification to a price range.
+    ///
+    /// # Parameters
+    /// - `current_price`: The price the range is centered on.
+    /// - `volatility`: Volatility over the holding horizon (as a fraction).
+    ///   Required only for `StdDev`.
+    ///
+    /// # Returns
+    /// The resolved `PriceRange`, or an error if the specification cannot be
+    /// resolved (non-positive price, missing volatility, invalid bounds).
+    pub fn to_price_range(
+        &self,
+        current_price: Decimal,
+        volatility: Option<Decimal>,
+    ) -> Result<PriceRange, &'static str> {
+        let (lower, upper) = match self {
+            Self::Absolute { lower, upper } => (*lower, *upper),
+            Self::Percent(pct) => {
+                if *pct <= Decimal::ZERO || *pct >= Decimal::ONE {
+                    return Err("Percent width must be between 0 and 1");
+                }
+                (
+                    current_price * (Decimal::ONE - pct),
+                    current_price * (Decimal::ONE + pct),
+                )
+            }
+            Self::StdDev(sigmas) => {
+                let volatility = volatility.ok_or("Volatility required for std-dev range")?;
+                let exponent = (*sigmas * volatility)
+                    .to_f64()
+                    .ok_or("Overflow converting std-dev width")?;
+                let factor = Decimal::from_f64(exponent.exp()).ok_or("Overflow in std-dev")?;
+                if factor <= Decimal::ONE {
+                    return Err("Std-dev width must be positive");
+                }
+                (current_price / factor, current_price * factor)
+            }
+            Self::Ticks(ticks) => {
+                if *ticks == 0 {
+                    return Err("Tick width must be positive");
+                }
+                let current_tick = price_to_tick(current_price)?;
+                let delta = i32::try_from(*ticks).map_err(|_| "Tick width too large")?;
+                (
+                    tick_to_price(current_tick.saturating_sub(delta))?,
+                    tick_to_price(current_tick.saturating_add(delta))?,
+                )
+            }
+        };
+
+        if lower <= Decimal::ZERO || current_price <= Decimal::ZERO {
+            return Err("Prices must be positive");
+        }
+        if lower >= upper {
+            return Err("Invalid range");
+        }
+
+        Ok(PriceRange::new(Price::new(lower), Price::new(upper)))
+    }
+
+    /// Resolves the specification to initializable tick bounds.
+    ///
+    /// The lower tick is rounded down and the upper tick rounded up to the
+    /// nearest multiple of `tick_spacing`, so the resolved range never shrinks.
+    pub fn to_ticks(
+        &self,
+        current_price: Decimal,
+        volatility: Option<Decimal>,
+        tick_spacing: u16,
+    ) -> Result<(i32, i32), &'static str> {
+        if tick_spacing == 0 {
+            return Err("Tick spacing must be positive");
+        }
+        let range = self.to_price_range(current_price, volatility)?;
+        let spacing = i32::from(tick_spacing);
+
+        let lower = price_to_tick(range.lower_price.value)?.div_euclid(spacing) * spacing;
+        let upper_raw = price_to_tick(range.upper_price.value)?;
+        let mut upper = upper_raw.div_euclid(spacing) * spacing;
+        if upper < upper_raw {
+            upper += spacing;
+        }
+        if upper <= lower {
+            upper = lower + spacing;
+        }
+
+        Ok((lower, upper))
+    }
+
+    /// Returns the total width of the resolved range relative to the current price.
+    pub fn width_pct(
+        &self,
+        current_price: Decimal,
+        volatility: Option<Decimal>,
+    ) -> Result<Decimal, &'static str> {
+        let range = self.to_price_range(current_price, volatility)?;
+        Ok((range.upper_price.value - range.lower_price.value) / current_price)
+    }
+
+    /// Expresses a price range as the smallest symmetric percent spec that contains it.
+    pub fn percent_from_range(range: &PriceRange, current_price: Decimal) -> Option<Self> {
+        if current_price <= Decimal::ZERO {
+            return None;
+        }
+        let down = (current_price - range.lower_price.value) / current_price;
+        let up = (range.upper_price.value - current_price) / current_price;
+        Some(Self::Percent(down.max(up)))
+    }
+
+    /// Expresses a price range as the smallest symmetric std-dev spec that contains it.
+    pub fn std_dev_from_range(
+        range: &PriceRange,
+        current_price: Decimal,
+        volatility: Decimal,
+    ) -> Option<Self> {
+        if volatility <= Decimal::ZERO || current_price <= Decimal::ZERO {
+            return None;
+        }
+        let price = current_price.to_f64()?;
+        let down = (price / range.lower_price.value.to_f64()?).ln();
+        let up = (range.upper_price.value.to_f64()? / price).ln();
+        let sigmas = down.max(up) / volatility.to_f64()?;
+        Decimal::from_f64(sigmas).map(Self::StdDev)
+    }
+}
+
+impl fmt::Display for RangeSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Absolute { lower, upper } => write!(f, "{}:{}", lower, upper),
+            Self::Percent(pct) => write!(f, "±{}%", (pct * Decimal::ONE_HUNDRED).normalize()),
+            Self::StdDev(sigmas) => write!(f, "±{}σ", sigmas.normalize()),
+            Self::Ticks(ticks) => write!(f, "±{} ticks", ticks),
+        }
+    }
+}
+
+impl FromStr for RangeSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let input = s.trim().trim_start_matches('±').trim_start_matches("+-");
+        let parse = |value: &str| {
+            Decimal::from_str(value.trim()).map_err(|_| format!("Invalid number in range: {}", s))
+        };
+
+        if let Some((lower, upper)) = input.split_once(':') {
+            return Ok(Self::Absolute {
+                lower: parse(lower)?,
+                upper: parse(upper)?,
+            });
+        }
+
+        if let Some(pct) = input.strip_suffix('%') {
+            return Ok(Self::Percent(parse(pct)? / Decimal::ONE_HUNDRED));
+        }
+
+        for suffix in ["sigma", "σ", "sd"] {
+            if let Some(sigmas) = input.strip_suffix(suffix) {
+                return Ok(Self::StdDev(parse(sigmas)?));
+            }
+        }
+
+        for suffix in ["ticks", "t"] {
+            if let Some(ticks) = input.strip_suffix(suffix) {
+                return ticks
+                    .trim()
+                    .parse::<u32>()
+                    .map(Self::Ticks)
+                    .map_err(|_| format!("Invalid tick count in range: {}", s));
+            }
+        }
+
+        Err(format!(
+            "Unrecognized range '{}'; expected LOWER:UPPER, N%, Nsigma or Nticks",
+            s
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_parse_formats() {
+        assert_eq!(
+            "90:110".parse::<RangeSpec>().unwrap(),
+            RangeSpec::Absolute {
+                lower: dec!(90),
+                upper: dec!(110)
+            }
+        );
+        assert_eq!(
+            "±5%".parse::<RangeSpec>().unwrap(),
+            RangeSpec::Percent(dec!(0.05))
+        );
+        assert_eq!(
+            "2sigma".parse::<RangeSpec>().unwrap(),
+            RangeSpec::StdDev(dec!(2))
+        );
+        assert_eq!(
+            "1.5σ".parse::<RangeSpec>().unwrap(),
+            RangeSpec::StdDev(dec!(1.5))
+        );
+        assert_eq!(
+            "500ticks".parse::<RangeSpec>().unwrap(),
+            RangeSpec::Ticks(500)
+        );
+        assert!("wide".parse::<RangeSpec>().is_err());
+    }
+
+    #[test]
+    fn test_percent_range() {
+        let range = RangeSpec::Percent(dec!(0.1))
+            .to_price_range(dec!(100), None)
+            .unwrap();
+        assert_eq!(range.lower_price.value, dec!(90));
+        assert_eq!(range.upper_price.value, dec!(110));
+    }
+
+    #[test]
+    fn test_std_dev_requires_volatility() {
+        let spec = RangeSpec::StdDev(dec!(2));
+        assert!(spec.to_price_range(dec!(100), None).is_err());
+
+        let range = spec.to_price_range(dec!(100), Some(dec!(0.05))).unwrap();
+        assert!(range.lower_price.value > Decimal::ZERO);
+        // Log-symmetric: lower * upper == price^2
+        let product = (range.lower_price.value * range.upper_price.value).round_dp(4);
+        assert_eq!(product, dec!(10000));
+
+        let sigmas = RangeSpec::std_dev_from_range(&range, dec!(100), dec!(0.05)).unwrap();
+        if let RangeSpec::StdDev(n) = sigmas {
+            assert_eq!(n.round_dp(6), dec!(2));
+        } else {
+            panic!("expected std-dev spec");
+        }
+    }
+
+    #[test]
+    fn test_to_ticks_snaps_outward() {
+        let (lower, upper) = RangeSpec::Ticks(100)
+            .to_ticks(Decimal::ONE, None, 64)
+            .unwrap();
+        assert_eq!(lower, -128);
+        assert_eq!(upper, 128);
+    }
+
+    #[test]
+    fn test_percent_from_range() {
+        let range = PriceRange::new(Price::new(dec!(95)), Price::new(dec!(110)));
+        assert_eq!(
+            RangeSpec::percent_from_range(&range, dec!(100)),
+            Some(RangeSpec::Percent(dec!(0.1)))
+        );
+    }
+}
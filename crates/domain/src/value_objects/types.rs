@@ -41,6 +41,17 @@ pub struct PoolMetrics {
     pub volume_24h_usd: Decimal,
     /// Fee APR for 24h.
     pub fee_apr_24h: Decimal,
+    /// APR from reward emissions.
+    #[serde(default)]
+    pub reward_apr: Decimal,
+}
+
+impl PoolMetrics {
+    /// Returns the combined fee and reward APR.
+    #[must_use]
+    pub fn total_apr(&self) -> Decimal {
+        self.fee_apr_24h + self.reward_apr
+    }
 }
 
 /// Represents risk metrics.
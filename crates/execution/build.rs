@@ -0,0 +1,20 @@
+//! Generates the Yellowstone Geyser client from `proto/geyser.proto` when
+//! the `geyser` feature is enabled.
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "geyser")]
+    {
+        // Use the vendored protoc unless one is configured explicitly
+        if std::env::var_os("PROTOC").is_none() {
+            // SAFETY: build scripts are single-threaded
+            unsafe {
+                std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+            }
+        }
+
+        tonic_prost_build::configure()
+            .build_server(false)
+            .compile_protos(&["proto/geyser.proto"], &["proto"])?;
+    }
+    Ok(())
+}
@@ -0,0 +1,111 @@
+//! Plain-text journal rendering.
+
+use super::JournalEntry;
+use std::fmt::Write;
+
+/// Plain-text accounting format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JournalFormat {
+    /// Beancount syntax.
+    Beancount,
+    /// ledger-cli syntax.
+    Ledger,
+}
+
+/// Payee written on every beancount entry.
+const PAYEE: &str = "CLMM";
+
+/// Renders entries as a journal, separated by blank lines.
+#[must_use]
+pub fn journal_to_text(entries: &[JournalEntry], format: JournalFormat) -> String {
+    let mut out = String::new();
+    for entry in entries {
+        match format {
+            JournalFormat::Beancount => write_beancount(&mut out, entry),
+            JournalFormat::Ledger => write_ledger(&mut out, entry),
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Writes a beancount transaction with position and signature metadata.
+fn write_beancount(out: &mut String, entry: &JournalEntry) {
+    let _ = writeln!(
+        out,
+        "{} * \"{}\" \"{}\"",
+        entry.timestamp.format("%Y-%m-%d"),
+        PAYEE,
+        entry.narration.replace('"', "'")
+    );
+    let _ = writeln!(out, "  position: \"{}\"", entry.position);
+    if let Some(signature) = &entry.tx_signature {
+        let _ = writeln!(out, "  tx: \"{}\"", signature);
+    }
+    write_postings(out, entry, "  ");
+}
+
+/// Writes a ledger-cli transaction with position and signature as tags.
+fn write_ledger(out: &mut String, entry: &JournalEntry) {
+    let _ = writeln!(
+        out,
+        "{} * {}",
+        entry.timestamp.format("%Y/%m/%d"),
+        entry.narration
+    );
+    let _ = writeln!(out, "    ; position: {}", entry.position);
+    if let Some(signature) = &entry.tx_signature {
+        let _ = writeln!(out, "    ; tx: {}", signature);
+    }
+    write_postings(out, entry, "    ");
+}
+
+/// Writes postings with amounts aligned after the longest account name.
+fn write_postings(out: &mut String, entry: &JournalEntry, indent: &str) {
+    let width = entry
+        .postings
+        .iter()
+        .map(|p| p.account.len())
+        .max()
+        .unwrap_or(0);
+
+    for posting in &entry.postings {
+        let _ = writeln!(
+            out,
+            "{}{:<width$}  {} {}",
+            indent,
+            posting.account,
+            posting.amount.normalize(),
+            posting.commodity,
+            width = width
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accounting::journal::tests::sample_journal;
+
+    #[test]
+    fn test_beancount_output() {
+        let entries = sample_journal();
+        let text = journal_to_text(&entries, JournalFormat::Beancount);
+
+        assert!(text.starts_with("2024-01-01 * \"CLMM\" \"Open liquidity position\"\n"));
+        assert!(text.contains("  Assets:Crypto:Liquidity  1 SOL\n"));
+        assert!(text.contains("  Assets:Treasury:Hot      -1 SOL\n"));
+        assert!(text.contains("  Expenses:Crypto:NetworkFees  0.000005 SOL\n"));
+        assert_eq!(text.matches("  position: \"").count(), entries.len());
+    }
+
+    #[test]
+    fn test_ledger_output() {
+        let entries = sample_journal();
+        let text = journal_to_text(&entries, JournalFormat::Ledger);
+
+        assert!(text.contains("2024/01/02 * Collect fees\n"));
+        assert!(text.contains("    Income:Liquidity:Fees  -2.5 USDC\n"));
+        assert!(text.contains("    Income:Liquidity:Realized  -60 USDC\n"));
+    }
+}
@@ -0,0 +1,437 @@
+//! Journal entries built from lifecycle events.
+
+use crate::lifecycle::{EventData, LifecycleEvent};
+use crate::tax::{PoolAssets, TaxAsset};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{BTreeMap, HashMap};
+
+/// Account names used in the journal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountNames {
+    /// Wallet holding undeployed tokens.
+    pub wallet: String,
+    /// Tokens deployed in liquidity positions.
+    pub positions: String,
+    /// Trading fees earned.
+    pub fee_income: String,
+    /// Liquidity mining rewards earned.
+    pub reward_income: String,
+    /// Transaction costs paid.
+    pub network_fees: String,
+    /// Difference between tokens deposited and withdrawn when a position closes.
+    pub realized: String,
+}
+
+impl Default for AccountNames {
+    fn default() -> Self {
+        Self {
+            wallet: "Assets:Crypto:Wallet".to_string(),
+            positions: "Assets:Crypto:Liquidity".to_string(),
+            fee_income: "Income:Liquidity:Fees".to_string(),
+            reward_income: "Income:Liquidity:Rewards".to_string(),
+            network_fees: "Expenses:Crypto:NetworkFees".to_string(),
+            realized: "Income:Liquidity:Realized".to_string(),
+        }
+    }
+}
+
+impl AccountNames {
+    /// Sets the wallet account.
+    #[must_use]
+    pub fn with_wallet(mut self, account: impl Into<String>) -> Self {
+        self.wallet = account.into();
+        self
+    }
+
+    /// Sets the deployed liquidity account.
+    #[must_use]
+    pub fn with_positions(mut self, account: impl Into<String>) -> Self {
+        self.positions = account.into();
+        self
+    }
+
+    /// Sets the fee income account.
+    #[must_use]
+    pub fn with_fee_income(mut self, account: impl Into<String>) -> Self {
+        self.fee_income = account.into();
+        self
+    }
+
+    /// Sets the reward income account.
+    #[must_use]
+    pub fn with_reward_income(mut self, account: impl Into<String>) -> Self {
+        self.reward_income = account.into();
+        self
+    }
+
+    /// Sets the network fee expense account.
+    #[must_use]
+    pub fn with_network_fees(mut self, account: impl Into<String>) -> Self {
+        self.network_fees = account.into();
+        self
+    }
+
+    /// Sets the realized result account.
+    #[must_use]
+    pub fn with_realized(mut self, account: impl Into<String>) -> Self {
+        self.realized = account.into();
+        self
+    }
+}
+
+/// A single leg of a journal entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalPosting {
+    /// Account name.
+    pub account: String,
+    /// Signed amount in whole units.
+    pub amount: Decimal,
+    /// Commodity symbol.
+    pub commodity: String,
+}
+
+/// A balanced journal entry for one lifecycle event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// When the event happened.
+    pub timestamp: DateTime<Utc>,
+    /// Description of the event.
+    pub narration: String,
+    /// Position the event belongs to.
+    pub position: Pubkey,
+    /// Transaction signature, if recorded.
+    pub tx_signature: Option<String>,
+    /// Postings, balanced per commodity.
+    pub postings: Vec<JournalPosting>,
+}
+
+impl JournalEntry {
+    /// Returns true if postings sum to zero for every commodity.
+    #[must_use]
+    pub fn is_balanced(&self) -> bool {
+        let mut totals: HashMap<&str, Decimal> = HashMap::new();
+        for posting in &self.postings {
+            *totals.entry(&posting.commodity).or_default() += posting.amount;
+        }
+        totals.values().all(Decimal::is_zero)
+    }
+
+    /// Adds a transfer of `amount` from one account to another.
+    fn transfer(&mut self, from: &str, to: &str, amount: Decimal, commodity: &str) {
+        if amount.is_zero() {
+            return;
+        }
+        self.postings.push(JournalPosting {
+            account: to.to_string(),
+            amount,
+            commodity: commodity.to_string(),
+        });
+        self.postings.push(JournalPosting {
+            account: from.to_string(),
+            amount: -amount,
+            commodity: commodity.to_string(),
+        });
+    }
+}
+
+/// Builds journal entries from lifecycle events.
+pub struct JournalBuilder {
+    /// Account names.
+    accounts: AccountNames,
+    /// Assets by pool address.
+    pools: HashMap<Pubkey, PoolAssets>,
+    /// Asset in which transaction costs are paid.
+    native: TaxAsset,
+}
+
+impl JournalBuilder {
+    /// Creates a builder with the given account names.
+    #[must_use]
+    pub fn new(accounts: AccountNames) -> Self {
+        Self {
+            accounts,
+            pools: HashMap::new(),
+            native: TaxAsset::new("SOL", 9),
+        }
+    }
+
+    /// Registers the assets of a pool.
+    #[must_use]
+    pub fn with_pool(mut self, pool: Pubkey, assets: PoolAssets) -> Self {
+        self.pools.insert(pool, assets);
+        self
+    }
+
+    /// Builds one entry per event that moves tokens, in time order.
+    ///
+    /// Tokens deposited into a position are tracked until it closes; whatever
+    /// the withdrawals did not return (or returned beyond the deposits) is
+    /// then booked against the realized account so the liquidity account
+    /// nets to zero per position.
+    ///
+    /// # Errors
+    /// Returns an error if an event's pool is not registered.
+    pub fn build(&self, events: &[LifecycleEvent]) -> Result<Vec<JournalEntry>> {
+        let mut ordered: Vec<&LifecycleEvent> = events.iter().collect();
+        ordered.sort_by_key(|e| e.timestamp);
+
+        let accounts = &self.accounts;
+        let mut deployed: HashMap<Pubkey, BTreeMap<String, Decimal>> = HashMap::new();
+        let mut entries = Vec::new();
+
+        for event in ordered {
+            let assets = self
+                .pools
+                .get(&event.pool)
+                .with_context(|| format!("No assets registered for pool {}", event.pool))?;
+            let (a, b) = (&assets.token_a, &assets.token_b);
+            let balances = deployed.entry(event.position).or_default();
+
+            let mut entry = JournalEntry {
+                timestamp: event.timestamp,
+                narration: String::new(),
+                position: event.position,
+                tx_signature: event.signature.map(|s| s.to_string()),
+                postings: Vec::new(),
+            };
+
+            let mut deploy = |entry: &mut JournalEntry, asset: &TaxAsset, raw: u64, sign: i8| {
+                let amount = asset.to_units(raw);
+                *balances.entry(asset.symbol.clone()).or_default() += amount * Decimal::from(sign);
+                if sign > 0 {
+                    entry.transfer(&accounts.wallet, &accounts.positions, amount, &asset.symbol);
+                } else {
+                    entry.transfer(&accounts.positions, &accounts.wallet, amount, &asset.symbol);
+                }
+            };
+
+            match &event.data {
+                EventData::PositionOpened(data) => {
+                    entry.narration = "Open liquidity position".to_string();
+                    deploy(&mut entry, a, data.amount_a, 1);
+                    deploy(&mut entry, b, data.amount_b, 1);
+                }
+                EventData::LiquidityChange(data) => {
+                    let sign = if data.is_increase { 1 } else { -1 };
+                    entry.narration = if data.is_increase {
+                        "Increase position liquidity"
+                    } else {
+                        "Decrease position liquidity"
+                    }
+                    .to_string();
+                    deploy(&mut entry, a, data.amount_a, sign);
+                    deploy(&mut entry, b, data.amount_b, sign);
+                }
+                EventData::Rebalance(data) => {
+                    entry.narration = "Rebalance position".to_string();
+                    let cost = self.native.to_units(data.tx_cost_lamports);
+                    entry.transfer(
+                        &accounts.wallet,
+                        &accounts.network_fees,
+                        cost,
+                        &self.native.symbol,
+                    );
+                }
+                EventData::Migration(data) => {
+                    entry.narration = "Migrate position to another pool".to_string();
+                    let cost = self.native.to_units(data.tx_cost_lamports);
+                    entry.transfer(
+                        &accounts.wallet,
+                        &accounts.network_fees,
+                        cost,
+                        &self.native.symbol,
+                    );
+                }
+                EventData::FeesCollected(data) => {
+                    entry.narration = "Collect fees".to_string();
+                    for (asset, raw) in [(a, data.fees_a), (b, data.fees_b)] {
+                        entry.transfer(
+                            &accounts.fee_income,
+                            &accounts.wallet,
+                            asset.to_units(raw),
+                            &asset.symbol,
+                        );
+                    }
+                    for (asset, raw) in assets.rewards.iter().zip(data.rewards) {
+                        entry.transfer(
+                            &accounts.reward_income,
+                            &accounts.wallet,
+                            asset.to_units(raw),
+                            &asset.symbol,
+                        );
+                    }
+                }
+                EventData::PositionClosed(data) => {
+                    entry.narration = "Close liquidity position".to_string();
+                    deploy(&mut entry, a, data.amount_a, -1);
+                    deploy(&mut entry, b, data.amount_b, -1);
+
+                    // Settle what the pool kept or paid out beyond the deposits
+                    for (commodity, residual) in std::mem::take(balances) {
+                        entry.transfer(
+                            &accounts.positions,
+                            &accounts.realized,
+                            residual,
+                            &commodity,
+                        );
+                    }
+                }
+            }
+
+            if !entry.postings.is_empty() {
+                entries.push(entry);
+            }
+        }
+
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+    use crate::lifecycle::{
+        CloseReason, FeesCollectedData, LifecycleEventType, PositionClosedData, PositionOpenedData,
+        RebalanceData, RebalanceReason,
+    };
+    use chrono::TimeZone;
+
+    fn event(day: u32, position: Pubkey, pool: Pubkey, data: EventData) -> LifecycleEvent {
+        let event_type = match &data {
+            EventData::PositionOpened(_) => LifecycleEventType::PositionOpened,
+            EventData::Rebalance(_) => LifecycleEventType::Rebalanced,
+            EventData::Migration(_) => LifecycleEventType::Migrated,
+            EventData::FeesCollected(_) => LifecycleEventType::FeesCollected,
+            EventData::PositionClosed(_) => LifecycleEventType::PositionClosed,
+            EventData::LiquidityChange(_) => LifecycleEventType::LiquidityIncreased,
+        };
+        let mut event = LifecycleEvent::new(event_type, position, pool, data);
+        event.timestamp = Utc.with_ymd_and_hms(2024, 1, day, 12, 0, 0).unwrap();
+        event
+    }
+
+    /// SOL/USDC position that is opened, earns fees, rebalances and closes.
+    pub(crate) fn sample_journal() -> Vec<JournalEntry> {
+        let (position, pool) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let events = vec![
+            event(
+                1,
+                position,
+                pool,
+                EventData::PositionOpened(PositionOpenedData {
+                    tick_lower: -100,
+                    tick_upper: 100,
+                    liquidity: 1000,
+                    amount_a: 1_000_000_000,
+                    amount_b: 100_000_000,
+                    entry_price: Decimal::from(100),
+                    entry_value_usd: Decimal::from(200),
+                }),
+            ),
+            event(
+                2,
+                position,
+                pool,
+                EventData::FeesCollected(FeesCollectedData {
+                    fees_a: 0,
+                    fees_b: 2_500_000,
+                    fees_usd: Decimal::new(25, 1),
+                    rewards: [0, 0, 0],
+                    rewards_usd: Decimal::ZERO,
+                }),
+            ),
+            event(
+                3,
+                position,
+                pool,
+                EventData::Rebalance(RebalanceData {
+                    old_tick_lower: -100,
+                    old_tick_upper: 100,
+                    new_tick_lower: 0,
+                    new_tick_upper: 200,
+                    old_liquidity: 1000,
+                    new_liquidity: 1000,
+                    tx_cost_lamports: 5_000,
+                    il_at_rebalance: Decimal::ZERO,
+                    reason: RebalanceReason::RangeExit,
+                }),
+            ),
+            event(
+                4,
+                position,
+                pool,
+                EventData::PositionClosed(PositionClosedData {
+                    liquidity_removed: 1000,
+                    amount_a: 500_000_000,
+                    amount_b: 160_000_000,
+                    total_fees_a: 0,
+                    total_fees_b: 2_500_000,
+                    final_pnl_usd: Decimal::ZERO,
+                    final_pnl_pct: Decimal::ZERO,
+                    total_il_pct: Decimal::ZERO,
+                    duration_hours: 72,
+                    reason: CloseReason::Manual,
+                }),
+            ),
+        ];
+
+        JournalBuilder::new(AccountNames::default().with_wallet("Assets:Treasury:Hot"))
+            .with_pool(
+                pool,
+                PoolAssets::new(TaxAsset::new("SOL", 9), TaxAsset::new("USDC", 6)),
+            )
+            .build(&events)
+            .unwrap()
+    }
+
+    fn balance(entries: &[JournalEntry], account: &str, commodity: &str) -> Decimal {
+        entries
+            .iter()
+            .flat_map(|e| &e.postings)
+            .filter(|p| p.account == account && p.commodity == commodity)
+            .map(|p| p.amount)
+            .sum()
+    }
+
+    #[test]
+    fn test_journal_entries_balance() {
+        let entries = sample_journal();
+        assert_eq!(entries.len(), 4);
+        assert!(entries.iter().all(JournalEntry::is_balanced));
+
+        let accounts = AccountNames::default();
+        let wallet = "Assets:Treasury:Hot";
+
+        // Liquidity nets out once the position is closed
+        assert!(balance(&entries, &accounts.positions, "SOL").is_zero());
+        assert!(balance(&entries, &accounts.positions, "USDC").is_zero());
+
+        // Withdrew 0.5 SOL less and 60 USDC more than deposited
+        assert_eq!(
+            balance(&entries, &accounts.realized, "SOL"),
+            Decimal::new(5, 1)
+        );
+        assert_eq!(
+            balance(&entries, &accounts.realized, "USDC"),
+            Decimal::from(-60)
+        );
+
+        assert_eq!(
+            balance(&entries, &accounts.fee_income, "USDC"),
+            Decimal::new(-25, 1)
+        );
+        assert_eq!(
+            balance(&entries, &accounts.network_fees, "SOL"),
+            Decimal::new(5, 6)
+        );
+        assert_eq!(
+            balance(&entries, wallet, "SOL"),
+            Decimal::new(-5, 1) - Decimal::new(5, 6)
+        );
+        assert_eq!(balance(&entries, wallet, "USDC"), Decimal::new(625, 1));
+    }
+}
@@ -0,0 +1,11 @@
+//! Double-entry accounting export.
+//!
+//! Maps lifecycle events to journal entries between the wallet, the
+//! liquidity deployed in positions, income and expense accounts, and renders
+//! them as beancount or ledger-cli text for reconciliation against the books.
+
+mod format;
+mod journal;
+
+pub use format::*;
+pub use journal::*;
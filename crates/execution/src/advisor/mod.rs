@@ -0,0 +1,11 @@
+//! Range advisor.
+//!
+//! Re-optimizes every pool with tracked positions on a daily schedule and
+//! sends one digest alert listing the positions whose range has drifted
+//! materially from the new recommendation, with the suggested bounds.
+
+mod review;
+mod runner;
+
+pub use review::{PositionAdvice, PriceBounds, digest_alert, review_positions};
+pub use runner::{AdvisorConfig, OptimizerRecommender, RangeAdvisor, RangeRecommender};
@@ -0,0 +1,232 @@
+//! Comparison of live ranges against recommendations.
+
+use crate::alerts::{Alert, AlertData, AlertLevel, AlertType};
+use crate::monitor::MonitoredPosition;
+use clmm_lp_protocols::prelude::tick_to_price;
+use rust_decimal::Decimal;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// A price range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriceBounds {
+    /// Lower price bound.
+    pub lower: Decimal,
+    /// Upper price bound.
+    pub upper: Decimal,
+}
+
+impl PriceBounds {
+    /// Creates price bounds.
+    #[must_use]
+    pub fn new(lower: Decimal, upper: Decimal) -> Self {
+        Self { lower, upper }
+    }
+
+    /// Returns the larger relative move of either bound from `self` to
+    /// `other`, as a fraction of the bound in `self`.
+    #[must_use]
+    pub fn deviation(&self, other: &Self) -> Decimal {
+        let relative = |from: Decimal, to: Decimal| {
+            if from.is_zero() {
+                Decimal::ZERO
+            } else {
+                ((to - from) / from).abs()
+            }
+        };
+        relative(self.lower, other.lower).max(relative(self.upper, other.upper))
+    }
+}
+
+/// A position whose range is misaligned with the recommendation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionAdvice {
+    /// Position address.
+    pub position: Pubkey,
+    /// Pool address.
+    pub pool: Pubkey,
+    /// Tags of the position.
+    pub tags: Vec<String>,
+    /// Current range of the position.
+    pub current: PriceBounds,
+    /// Range suggested by the latest optimization.
+    pub suggested: PriceBounds,
+    /// Larger relative move of either bound (0.1 = 10%).
+    pub deviation: Decimal,
+}
+
+/// Returns the positions whose range deviates from their pool's
+/// recommendation by at least `threshold`, most misaligned first.
+///
+/// Positions of pools without a recommendation are skipped.
+#[must_use]
+pub fn review_positions(
+    positions: &[MonitoredPosition],
+    recommendations: &HashMap<Pubkey, PriceBounds>,
+    threshold: Decimal,
+) -> Vec<PositionAdvice> {
+    let mut advice: Vec<PositionAdvice> = positions
+        .iter()
+        .filter_map(|position| {
+            let suggested = *recommendations.get(&position.pool)?;
+            let current = PriceBounds::new(
+                tick_to_price(position.on_chain.tick_lower),
+                tick_to_price(position.on_chain.tick_upper),
+            );
+            let deviation = current.deviation(&suggested);
+            (deviation >= threshold).then(|| PositionAdvice {
+                position: position.address,
+                pool: position.pool,
+                tags: position.tags.clone(),
+                current,
+                suggested,
+                deviation,
+            })
+        })
+        .collect();
+
+    advice.sort_by_key(|a| std::cmp::Reverse(a.deviation));
+    advice
+}
+
+/// Builds one alert summarizing all misaligned positions.
+///
+/// Returns `None` when nothing is misaligned. The suggested bounds of each
+/// position are also attached as custom alert data.
+#[must_use]
+pub fn digest_alert(advice: &[PositionAdvice]) -> Option<Alert> {
+    if advice.is_empty() {
+        return None;
+    }
+
+    let mut message = format!(
+        "{} position(s) misaligned with the latest recommendation:",
+        advice.len()
+    );
+    let mut custom = HashMap::new();
+    for item in advice {
+        let _ = write!(
+            message,
+            "\n- {}: {:.4}-{:.4} -> {:.4}-{:.4} ({:.1}% off)",
+            item.position,
+            item.current.lower,
+            item.current.upper,
+            item.suggested.lower,
+            item.suggested.upper,
+            item.deviation * Decimal::ONE_HUNDRED
+        );
+        custom.insert(
+            item.position.to_string(),
+            format!(
+                "{}-{}",
+                item.suggested.lower.round_dp(6).normalize(),
+                item.suggested.upper.round_dp(6).normalize()
+            ),
+        );
+    }
+
+    let mut tags: Vec<String> = advice.iter().flat_map(|a| a.tags.clone()).collect();
+    tags.sort();
+    tags.dedup();
+
+    Some(
+        Alert::new(AlertLevel::Warning, AlertType::RangeAdvice, message)
+            .with_tags(tags)
+            .with_data(AlertData {
+                custom: Some(custom),
+                ..Default::default()
+            }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::PositionPnL;
+    use clmm_lp_protocols::prelude::{OnChainPosition, Protocol};
+    use rust_decimal_macros::dec;
+
+    fn position(pool: Pubkey, tick_lower: i32, tick_upper: i32) -> MonitoredPosition {
+        MonitoredPosition {
+            address: Pubkey::new_unique(),
+            pool,
+            protocol: Protocol::OrcaWhirlpool,
+            on_chain: OnChainPosition {
+                address: Pubkey::new_unique(),
+                pool,
+                owner: Pubkey::new_unique(),
+                tick_lower,
+                tick_upper,
+                liquidity: 1_000_000,
+                fee_growth_inside_a: 0,
+                fee_growth_inside_b: 0,
+                fees_owed_a: 0,
+                fees_owed_b: 0,
+                reward_growth_inside: [0; 3],
+                rewards_owed: [0; 3],
+            },
+            pnl: PositionPnL::default(),
+            in_range: true,
+            tick_current: 0,
+            range_checks: 0,
+            in_range_checks: 0,
+            health: None,
+            strategy: None,
+            tags: vec!["sol".to_string()],
+            read_only: false,
+            stale_since: None,
+            last_updated: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_deviation() {
+        let current = PriceBounds::new(dec!(90), dec!(110));
+        assert_eq!(current.deviation(&current), Decimal::ZERO);
+        let suggested = PriceBounds::new(dec!(99), dec!(121));
+        assert_eq!(current.deviation(&suggested), dec!(0.1));
+    }
+
+    #[test]
+    fn test_review_flags_misaligned_positions() {
+        let pool = Pubkey::new_unique();
+        let other_pool = Pubkey::new_unique();
+        // Ticks of +/-1000 are roughly 0.905-1.105
+        let aligned = position(pool, -1000, 1000);
+        let shifted = position(pool, 2000, 4000);
+        let unadvised = position(other_pool, 2000, 4000);
+        let recommendations = HashMap::from([(pool, PriceBounds::new(dec!(0.9), dec!(1.1)))]);
+
+        let advice = review_positions(
+            &[aligned, shifted.clone(), unadvised],
+            &recommendations,
+            dec!(0.1),
+        );
+        assert_eq!(advice.len(), 1);
+        assert_eq!(advice[0].position, shifted.address);
+        assert!(advice[0].deviation > dec!(0.1));
+    }
+
+    #[test]
+    fn test_digest_alert() {
+        assert!(digest_alert(&[]).is_none());
+
+        let advice = PositionAdvice {
+            position: Pubkey::new_unique(),
+            pool: Pubkey::new_unique(),
+            tags: vec!["sol".to_string()],
+            current: PriceBounds::new(dec!(90), dec!(110)),
+            suggested: PriceBounds::new(dec!(99), dec!(121)),
+            deviation: dec!(0.1),
+        };
+        let alert = digest_alert(std::slice::from_ref(&advice)).unwrap();
+
+        assert_eq!(alert.alert_type, AlertType::RangeAdvice);
+        assert!(alert.message.starts_with("1 position(s) misaligned"));
+        assert!(alert.message.contains("-> 99.0000-121.0000 (10.0% off)"));
+        assert!(alert.has_tag("sol"));
+        let custom = alert.data.unwrap().custom.unwrap();
+        assert_eq!(custom[&advice.position.to_string()], "99-121");
+    }
+}
@@ -0,0 +1,289 @@
+//! Scheduled re-optimization of tracked pools.
+
+use super::{PositionAdvice, PriceBounds, digest_alert, review_positions};
+use crate::alerts::MultiNotifier;
+use crate::monitor::PositionMonitor;
+use crate::scheduler::{ScheduleBuilder, ScheduledTask, Scheduler};
+use crate::shutdown::CancellationToken;
+use crate::supervisor::Heartbeat;
+use async_trait::async_trait;
+use clmm_lp_domain::entities::position::Position;
+use clmm_lp_domain::value_objects::amount::Amount;
+use clmm_lp_optimization::prelude::{
+    MaximizeNetPnL, OptimizationCache, OptimizationKey, PoolConstraints, RangeOptimizer,
+};
+use clmm_lp_protocols::prelude::{RpcProvider, WhirlpoolReader, WhirlpoolState};
+use clmm_lp_simulation::prelude::ConstantVolume;
+use rust_decimal::Decimal;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+/// Name of the advisor's scheduled task.
+const ADVISOR_TASK: &str = "range-advisor";
+
+/// Pool prices kept as the data window results are cached by.
+const ADVISOR_WINDOW: usize = 30;
+
+/// Produces a recommended range for a pool.
+#[async_trait]
+pub trait RangeRecommender: Send + Sync {
+    /// Recommends a price range for the pool at its current state.
+    async fn recommend(&self, pool: &WhirlpoolState) -> anyhow::Result<PriceBounds>;
+}
+
+/// Recommender backed by the Monte Carlo range optimizer, maximizing net PnL.
+///
+/// Results are cached by the pool's recent prices: an unchanged price reuses
+/// the previous result and a moved one warm-starts from it.
+#[derive(Debug, Clone)]
+pub struct OptimizerRecommender {
+    /// Annualized volatility assumed for every pool.
+    volatility: f64,
+    /// Monte Carlo iterations per pool.
+    iterations: usize,
+    /// Worker threads per optimization.
+    threads: usize,
+    /// Results of previous runs.
+    cache: Arc<Mutex<OptimizationCache>>,
+}
+
+impl OptimizerRecommender {
+    /// Creates a recommender assuming the given annualized volatility.
+    #[must_use]
+    pub fn new(volatility: f64) -> Self {
+        Self {
+            volatility,
+            iterations: 100,
+            threads: 1,
+            cache: Arc::new(Mutex::new(OptimizationCache::default())),
+        }
+    }
+
+    /// Sets the number of Monte Carlo iterations.
+    #[must_use]
+    pub fn with_iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Sets the number of worker threads.
+    #[must_use]
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+}
+
+#[async_trait]
+impl RangeRecommender for OptimizerRecommender {
+    async fn recommend(&self, pool: &WhirlpoolState) -> anyhow::Result<PriceBounds> {
+        let optimizer = RangeOptimizer::new(self.iterations, 30, 1.0 / 365.0)
+            .with_pool_constraints(PoolConstraints::new(pool.tick_spacing))
+            .with_threads(self.threads);
+        let price = pool.price;
+        let liquidity = pool.liquidity;
+        let fee_rate = pool.fee_rate();
+        let volatility = self.volatility;
+        let position = Position::new(
+            pool.address.clone(),
+            "advisor",
+            Amount::zero(9),
+            Amount::zero(6),
+            0,
+            0,
+        );
+        let volume = ConstantVolume::from_amount(Amount::from_decimal(Decimal::from(1_000_000), 6));
+        let cache = self.cache.clone();
+        let address = pool.address.clone();
+        let params = (
+            self.iterations,
+            volatility.to_bits(),
+            pool.tick_spacing,
+            fee_rate,
+        );
+
+        let result = tokio::task::spawn_blocking(move || {
+            let mut cache = cache
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            let window = cache.observe(&address, price, ADVISOR_WINDOW);
+            let key = OptimizationKey::new(&address, &window, "MaximizeNetPnL", &params);
+            cache.try_get_or_optimize(key, &window, |previous| {
+                let optimizer = match previous {
+                    Some(previous) => optimizer.with_warm_start(previous.recommended_range),
+                    None => optimizer,
+                };
+                optimizer.try_optimize(
+                    position,
+                    price,
+                    volatility,
+                    0.0,
+                    volume,
+                    liquidity,
+                    fee_rate,
+                    MaximizeNetPnL,
+                )
+            })
+        })
+        .await??;
+
+        Ok(PriceBounds::new(
+            result.recommended_range.lower_price.value,
+            result.recommended_range.upper_price.value,
+        ))
+    }
+}
+
+/// Configuration for the range advisor.
+#[derive(Debug, Clone)]
+pub struct AdvisorConfig {
+    /// Daily run time as (hour, minute).
+    pub run_at: (u8, u8),
+    /// Bound deviation at which a position counts as misaligned (0.1 = 10%).
+    pub misalignment_threshold: Decimal,
+}
+
+impl Default for AdvisorConfig {
+    fn default() -> Self {
+        Self {
+            run_at: (2, 0),
+            misalignment_threshold: Decimal::new(1, 1), // 10%
+        }
+    }
+}
+
+/// Re-optimizes every tracked pool and alerts on misaligned positions.
+pub struct RangeAdvisor {
+    /// Position monitor providing the tracked positions.
+    monitor: Arc<PositionMonitor>,
+    /// Pool state reader.
+    pool_reader: WhirlpoolReader,
+    /// Source of recommended ranges.
+    recommender: Arc<dyn RangeRecommender>,
+    /// Channels the digest alert is sent to.
+    notifier: MultiNotifier,
+    /// Configuration.
+    config: AdvisorConfig,
+    /// Advice from the latest run.
+    last_advice: RwLock<Vec<PositionAdvice>>,
+    /// Beaten by the daily scheduler on every tick, if watched.
+    heartbeat: Option<Heartbeat>,
+}
+
+impl RangeAdvisor {
+    /// Creates a new range advisor.
+    pub fn new(
+        monitor: Arc<PositionMonitor>,
+        provider: Arc<RpcProvider>,
+        recommender: Arc<dyn RangeRecommender>,
+        config: AdvisorConfig,
+    ) -> Self {
+        Self {
+            monitor,
+            pool_reader: WhirlpoolReader::new(provider),
+            recommender,
+            notifier: MultiNotifier::new(),
+            config,
+            last_advice: RwLock::new(Vec::new()),
+            heartbeat: None,
+        }
+    }
+
+    /// Sets the channels the digest alert is sent to.
+    #[must_use]
+    pub fn with_notifier(mut self, notifier: MultiNotifier) -> Self {
+        self.notifier = notifier;
+        self
+    }
+
+    /// Watches the daily scheduler with `heartbeat`.
+    #[must_use]
+    pub fn with_heartbeat(mut self, heartbeat: Heartbeat) -> Self {
+        self.heartbeat = Some(heartbeat);
+        self
+    }
+
+    /// Re-optimizes every pool with tracked positions, sends a digest alert
+    /// if any position is misaligned and returns the advice.
+    ///
+    /// Pools that cannot be read or optimized are skipped.
+    pub async fn run_once(&self) -> Vec<PositionAdvice> {
+        let positions = self.monitor.get_positions().await;
+
+        let mut recommendations: HashMap<Pubkey, PriceBounds> = HashMap::new();
+        for position in &positions {
+            if recommendations.contains_key(&position.pool) {
+                continue;
+            }
+            match self.recommend(&position.pool).await {
+                Ok(bounds) => {
+                    recommendations.insert(position.pool, bounds);
+                }
+                Err(e) => warn!(pool = %position.pool, error = %e, "Failed to re-optimize pool"),
+            }
+        }
+
+        let advice = review_positions(
+            &positions,
+            &recommendations,
+            self.config.misalignment_threshold,
+        );
+        info!(
+            pools = recommendations.len(),
+            misaligned = advice.len(),
+            "Range advisor run complete"
+        );
+
+        if let Some(alert) = digest_alert(&advice) {
+            self.notifier.notify_all(&alert).await;
+        }
+        *self.last_advice.write().await = advice.clone();
+        advice
+    }
+
+    /// Returns the advice from the latest run.
+    pub async fn last_advice(&self) -> Vec<PositionAdvice> {
+        self.last_advice.read().await.clone()
+    }
+
+    /// Runs the advisor daily at the configured time until the scheduler
+    /// stops.
+    pub async fn run_daily(self: Arc<Self>) {
+        self.run_daily_until(CancellationToken::new()).await;
+    }
+
+    /// Runs the advisor once a day until `shutdown` is cancelled.
+    pub async fn run_daily_until(self: Arc<Self>, shutdown: CancellationToken) {
+        let mut scheduler = Scheduler::new();
+        if let Some(heartbeat) = &self.heartbeat {
+            scheduler.set_heartbeat(heartbeat.clone());
+        }
+        scheduler.add_task(ScheduledTask::new(
+            ADVISOR_TASK,
+            ScheduleBuilder::daily_at(vec![self.config.run_at]),
+        ));
+        let Some(mut events) = scheduler.take_receiver() else {
+            return;
+        };
+
+        let advisor = self.clone();
+        tokio::spawn(async move {
+            while let Some(event) = events.recv().await {
+                if event.task_name == ADVISOR_TASK {
+                    advisor.run_once().await;
+                }
+            }
+        });
+
+        scheduler.run_until(shutdown).await;
+    }
+
+    /// Fetches a pool and asks the recommender for a range.
+    async fn recommend(&self, pool: &Pubkey) -> anyhow::Result<PriceBounds> {
+        let state = self.pool_reader.get_pool_state(&pool.to_string()).await?;
+        self.recommender.recommend(&state).await
+    }
+}
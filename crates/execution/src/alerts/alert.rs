@@ -1,83 +1,10 @@
 //! Alert types and structures.
 
+pub use clmm_lp_domain::alerts::{AlertLevel, AlertType};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
 
-/// Alert severity level.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum AlertLevel {
-    /// Informational alert.
-    Info,
-    /// Warning alert.
-    Warning,
-    /// Critical alert requiring immediate attention.
-    Critical,
-}
-
-impl AlertLevel {
-    /// Returns the emoji for this alert level.
-    #[must_use]
-    pub fn emoji(&self) -> &'static str {
-        match self {
-            Self::Info => "ℹ️",
-            Self::Warning => "⚠️",
-            Self::Critical => "🚨",
-        }
-    }
-
-    /// Returns the color code for this alert level.
-    #[must_use]
-    pub fn color(&self) -> &'static str {
-        match self {
-            Self::Info => "blue",
-            Self::Warning => "yellow",
-            Self::Critical => "red",
-        }
-    }
-}
-
-/// Type of alert.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub enum AlertType {
-    /// Position exited its price range.
-    RangeExit,
-    /// Position re-entered its price range.
-    RangeEntry,
-    /// Impermanent loss exceeded threshold.
-    ILThreshold,
-    /// PnL target reached.
-    PnLTarget,
-    /// Fees earned milestone.
-    FeesMilestone,
-    /// Position needs rebalancing.
-    RebalanceNeeded,
-    /// System error occurred.
-    SystemError,
-    /// Connection issue.
-    ConnectionIssue,
-    /// Custom alert.
-    Custom(String),
-}
-
-impl AlertType {
-    /// Returns a human-readable name for this alert type.
-    #[must_use]
-    pub fn name(&self) -> &str {
-        match self {
-            Self::RangeExit => "Range Exit",
-            Self::RangeEntry => "Range Entry",
-            Self::ILThreshold => "IL Threshold",
-            Self::PnLTarget => "PnL Target",
-            Self::FeesMilestone => "Fees Milestone",
-            Self::RebalanceNeeded => "Rebalance Needed",
-            Self::SystemError => "System Error",
-            Self::ConnectionIssue => "Connection Issue",
-            Self::Custom(name) => name,
-        }
-    }
-}
-
 /// An alert instance.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Alert {
@@ -91,6 +18,12 @@ pub struct Alert {
     pub position: Option<String>,
     /// Pool address (if applicable).
     pub pool: Option<String>,
+    /// Tags of the related position.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Notifier channels to deliver to; empty delivers to every notifier.
+    #[serde(default)]
+    pub channels: Vec<String>,
     /// Alert message.
     pub message: String,
     /// Additional data.
@@ -110,6 +43,8 @@ impl Alert {
             alert_type,
             position: None,
             pool: None,
+            tags: Vec::new(),
+            channels: Vec::new(),
             message: message.into(),
             data: None,
             timestamp: chrono::Utc::now(),
@@ -131,6 +66,32 @@ impl Alert {
         self
     }
 
+    /// Sets the position tags for this alert.
+    #[must_use]
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Limits delivery to the notifiers named in `channels`.
+    #[must_use]
+    pub fn with_channels(mut self, channels: Vec<String>) -> Self {
+        self.channels = channels;
+        self
+    }
+
+    /// Returns true if the alert should be delivered to notifier `name`.
+    #[must_use]
+    pub fn routes_to(&self, name: &str) -> bool {
+        self.channels.is_empty() || self.channels.iter().any(|c| c == name)
+    }
+
+    /// Returns true if the alert carries `tag`.
+    #[must_use]
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
     /// Sets additional data for this alert.
     #[must_use]
     pub fn with_data(mut self, data: AlertData) -> Self {
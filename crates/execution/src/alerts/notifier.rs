@@ -1,11 +1,13 @@
 //! Alert notification channels.
 
 use super::Alert;
+#[cfg(feature = "desktop-notifications")]
+use super::AlertLevel;
 use async_trait::async_trait;
 use std::fs::OpenOptions;
 use std::io::Write;
 use std::path::PathBuf;
-use tracing::{error, info};
+use tracing::{debug, error, info};
 
 /// Trait for alert notification channels.
 #[async_trait]
@@ -110,6 +112,165 @@ impl Notifier for WebhookNotifier {
     }
 }
 
+/// Telegram notifier - sends alerts to a chat through a bot.
+pub struct TelegramNotifier {
+    /// Bot API token.
+    token: String,
+    /// Chat ID or `@channel` name.
+    chat_id: String,
+    /// HTTP client.
+    client: reqwest::Client,
+}
+
+impl TelegramNotifier {
+    /// Creates a new Telegram notifier.
+    pub fn new(token: impl Into<String>, chat_id: impl Into<String>) -> Self {
+        Self {
+            token: token.into(),
+            chat_id: chat_id.into(),
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for TelegramNotifier {
+    async fn notify(&self, alert: &Alert) -> anyhow::Result<()> {
+        // Sent as plain text so alert messages need no escaping
+        let payload = serde_json::json!({
+            "chat_id": self.chat_id,
+            "text": alert.format(),
+            "disable_web_page_preview": true,
+        });
+        self.client
+            .post(format!(
+                "https://api.telegram.org/bot{}/sendMessage",
+                self.token
+            ))
+            .json(&payload)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "telegram"
+    }
+}
+
+/// Named pipe notifier - writes alerts as JSON lines to a FIFO.
+///
+/// Lets an operator follow alerts from a terminal, e.g. with
+/// `mkfifo /tmp/clmm-alerts && cat /tmp/clmm-alerts | jq`. Alerts sent
+/// while nothing is reading the pipe are dropped rather than blocking the
+/// sender.
+pub struct PipeNotifier {
+    /// Path to the FIFO.
+    path: PathBuf,
+}
+
+impl PipeNotifier {
+    /// Creates a new pipe notifier. The FIFO must already exist.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait]
+impl Notifier for PipeNotifier {
+    #[cfg(unix)]
+    async fn notify(&self, alert: &Alert) -> anyhow::Result<()> {
+        use tokio::io::AsyncWriteExt;
+
+        // Opening the write end without a reader fails with ENXIO, which
+        // has this value on Linux and macOS
+        const ENXIO: i32 = 6;
+
+        let mut pipe = match tokio::net::unix::pipe::OpenOptions::new().open_sender(&self.path) {
+            Ok(pipe) => pipe,
+            Err(e) if e.raw_os_error() == Some(ENXIO) => {
+                debug!(path = %self.path.display(), "No reader on alert pipe");
+                return Ok(());
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let mut line = serde_json::to_vec(alert)?;
+        line.push(b'\n');
+        pipe.write_all(&line).await?;
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    async fn notify(&self, _alert: &Alert) -> anyhow::Result<()> {
+        anyhow::bail!("named pipe alerts are only supported on Unix")
+    }
+
+    fn name(&self) -> &str {
+        "pipe"
+    }
+}
+
+/// Desktop notifier - shows alerts as desktop notifications.
+///
+/// Critical alerts stay on screen until dismissed.
+#[cfg(feature = "desktop-notifications")]
+pub struct DesktopNotifier {
+    /// Application name shown with the notification.
+    app_name: String,
+}
+
+#[cfg(feature = "desktop-notifications")]
+impl DesktopNotifier {
+    /// Creates a new desktop notifier.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            app_name: "CLMM LP".to_string(),
+        }
+    }
+}
+
+#[cfg(feature = "desktop-notifications")]
+impl Default for DesktopNotifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "desktop-notifications")]
+#[async_trait]
+impl Notifier for DesktopNotifier {
+    async fn notify(&self, alert: &Alert) -> anyhow::Result<()> {
+        let mut notification = notify_rust::Notification::new();
+        notification
+            .appname(&self.app_name)
+            .summary(&format!(
+                "{} {}",
+                alert.level.emoji(),
+                alert.alert_type.name()
+            ))
+            .body(&alert.message);
+        if alert.level == AlertLevel::Critical {
+            notification.timeout(notify_rust::Timeout::Never);
+        }
+        #[cfg(all(unix, not(target_os = "macos")))]
+        notification.urgency(match alert.level {
+            AlertLevel::Info => notify_rust::Urgency::Low,
+            AlertLevel::Warning => notify_rust::Urgency::Normal,
+            AlertLevel::Critical => notify_rust::Urgency::Critical,
+        });
+
+        // Showing talks to the notification daemon synchronously
+        tokio::task::spawn_blocking(move || notification.show().map(|_| ())).await??;
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "desktop"
+    }
+}
+
 /// Multi-channel notifier that sends to multiple channels.
 pub struct MultiNotifier {
     /// List of notifiers.
@@ -133,6 +294,9 @@ impl MultiNotifier {
     /// Sends an alert to all channels.
     pub async fn notify_all(&self, alert: &Alert) {
         for notifier in &self.notifiers {
+            if !alert.routes_to(notifier.name()) {
+                continue;
+            }
             if let Err(e) = notifier.notify(alert).await {
                 error!(
                     notifier = notifier.name(),
@@ -163,4 +327,79 @@ mod tests {
         let result = notifier.notify(&alert).await;
         assert!(result.is_ok());
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_pipe_notifier_writes_json_lines() {
+        use tokio::io::AsyncBufReadExt;
+
+        let path = std::env::temp_dir().join(format!("clmm-alerts-{}", uuid::Uuid::new_v4()));
+        let status = std::process::Command::new("mkfifo")
+            .arg(&path)
+            .status()
+            .unwrap();
+        assert!(status.success());
+        let notifier = PipeNotifier::new(&path);
+        let alert = Alert::new(AlertLevel::Critical, AlertType::RangeExit, "Out of range");
+
+        // Nobody reading: the alert is dropped without blocking
+        notifier.notify(&alert).await.unwrap();
+
+        let reader = tokio::net::unix::pipe::OpenOptions::new()
+            .open_receiver(&path)
+            .unwrap();
+        notifier.notify(&alert).await.unwrap();
+        let mut line = String::new();
+        tokio::io::BufReader::new(reader)
+            .read_line(&mut line)
+            .await
+            .unwrap();
+        let received: Alert = serde_json::from_str(&line).unwrap();
+        assert_eq!(received.id, alert.id);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    struct CountingNotifier {
+        name: &'static str,
+        sent: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Notifier for CountingNotifier {
+        async fn notify(&self, _alert: &Alert) -> anyhow::Result<()> {
+            self.sent.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn name(&self) -> &str {
+            self.name
+        }
+    }
+
+    #[tokio::test]
+    async fn test_alert_channels_limit_delivery() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let console = std::sync::Arc::new(AtomicUsize::new(0));
+        let webhook = std::sync::Arc::new(AtomicUsize::new(0));
+        let mut multi = MultiNotifier::new();
+        multi.add(CountingNotifier {
+            name: "console",
+            sent: console.clone(),
+        });
+        multi.add(CountingNotifier {
+            name: "webhook",
+            sent: webhook.clone(),
+        });
+
+        let alert = Alert::new(AlertLevel::Warning, AlertType::RangeExit, "Out of range");
+        multi.notify_all(&alert).await;
+        multi
+            .notify_all(&alert.with_channels(vec!["webhook".to_string()]))
+            .await;
+
+        assert_eq!(console.load(Ordering::SeqCst), 1);
+        assert_eq!(webhook.load(Ordering::SeqCst), 2);
+    }
 }
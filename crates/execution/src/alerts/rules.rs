@@ -2,6 +2,7 @@
 
 use super::{Alert, AlertLevel, AlertType};
 use crate::monitor::PositionPnL;
+pub use clmm_lp_domain::alerts::RuleCondition;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
@@ -22,6 +23,12 @@ pub struct AlertRule {
     pub enabled: bool,
     /// Cooldown between alerts in seconds.
     pub cooldown_secs: u64,
+    /// Tags the rule is limited to; empty applies to every position.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Notifier channels alerts are sent to; empty sends to every notifier.
+    #[serde(default)]
+    pub channels: Vec<String>,
 }
 
 impl AlertRule {
@@ -40,6 +47,8 @@ impl AlertRule {
             message_template: String::new(),
             enabled: true,
             cooldown_secs: 300, // 5 minutes default
+            tags: Vec::new(),
+            channels: Vec::new(),
         }
     }
 
@@ -57,6 +66,26 @@ impl AlertRule {
         self
     }
 
+    /// Limits the rule to positions carrying any of `tags`.
+    #[must_use]
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Sends the rule's alerts only to the notifiers named in `channels`.
+    #[must_use]
+    pub fn with_channels(mut self, channels: Vec<String>) -> Self {
+        self.channels = channels;
+        self
+    }
+
+    /// Returns true if the rule applies to a position with `tags`.
+    #[must_use]
+    pub fn applies_to(&self, tags: &[String]) -> bool {
+        self.tags.is_empty() || self.tags.iter().any(|t| tags.contains(t))
+    }
+
     /// Disables the rule.
     #[must_use]
     pub fn disabled(mut self) -> Self {
@@ -65,27 +94,22 @@ impl AlertRule {
     }
 }
 
-/// Condition for triggering an alert.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum RuleCondition {
-    /// Position exits range.
-    RangeExit,
-    /// Position enters range.
-    RangeEntry,
-    /// IL exceeds threshold.
-    ILExceeds(Decimal),
-    /// PnL exceeds threshold (positive).
-    PnLExceeds(Decimal),
-    /// PnL below threshold (negative).
-    PnLBelow(Decimal),
-    /// Fees exceed threshold.
-    FeesExceed(Decimal),
-    /// Time since last rebalance exceeds hours.
-    TimeSinceRebalance(u64),
-    /// Compound condition (AND).
-    And(Box<RuleCondition>, Box<RuleCondition>),
-    /// Compound condition (OR).
-    Or(Box<RuleCondition>, Box<RuleCondition>),
+/// Checks that rule names are present and unique, since cooldowns are
+/// tracked by name.
+///
+/// # Errors
+/// Returns an error naming the first offending rule.
+pub fn validate_rules(rules: &[AlertRule]) -> anyhow::Result<()> {
+    let mut names = std::collections::HashSet::new();
+    for rule in rules {
+        if rule.name.trim().is_empty() {
+            anyhow::bail!("Alert rule name must not be empty");
+        }
+        if !names.insert(rule.name.as_str()) {
+            anyhow::bail!("Duplicate alert rule: {}", rule.name);
+        }
+    }
+    Ok(())
 }
 
 /// Context for evaluating rules.
@@ -97,8 +121,14 @@ pub struct RuleContext {
     pub was_in_range: bool,
     /// Current PnL data.
     pub pnl: PositionPnL,
+    /// Current pool price, if known.
+    pub price: Option<Decimal>,
     /// Hours since last rebalance.
     pub hours_since_rebalance: u64,
+    /// Composite health score (0-100), if computed.
+    pub health_score: Option<Decimal>,
+    /// Tags of the position being evaluated.
+    pub tags: Vec<String>,
 }
 
 /// Rules engine for evaluating alert conditions.
@@ -129,13 +159,31 @@ impl RulesEngine {
         self.rules.retain(|r| r.name != name);
     }
 
+    /// Replaces all rules, keeping the cooldowns of rules that remain.
+    ///
+    /// # Errors
+    /// Returns an error, leaving the rules unchanged, if they are invalid.
+    pub fn replace_rules(&mut self, rules: Vec<AlertRule>) -> anyhow::Result<()> {
+        validate_rules(&rules)?;
+        self.last_triggers
+            .retain(|name, _| rules.iter().any(|r| &r.name == name));
+        self.rules = rules;
+        Ok(())
+    }
+
+    /// Gets the configured rules.
+    #[must_use]
+    pub fn rules(&self) -> &[AlertRule] {
+        &self.rules
+    }
+
     /// Evaluates all rules and returns triggered alerts.
     pub fn evaluate(&mut self, context: &RuleContext) -> Vec<Alert> {
         let mut alerts = Vec::new();
         let now = chrono::Utc::now();
 
         for rule in &self.rules {
-            if !rule.enabled {
+            if !rule.enabled || !rule.applies_to(&context.tags) {
                 continue;
             }
 
@@ -150,7 +198,9 @@ impl RulesEngine {
             // Evaluate condition
             if self.evaluate_condition(&rule.condition, context) {
                 let message = self.format_message(&rule.message_template, context);
-                let alert = Alert::new(rule.level, rule.alert_type.clone(), message);
+                let alert = Alert::new(rule.level, rule.alert_type.clone(), message)
+                    .with_tags(context.tags.clone())
+                    .with_channels(rule.channels.clone());
                 alerts.push(alert);
 
                 // Update last trigger time
@@ -172,6 +222,13 @@ impl RulesEngine {
             RuleCondition::PnLBelow(threshold) => context.pnl.net_pnl_pct < *threshold,
             RuleCondition::FeesExceed(threshold) => context.pnl.fees_usd > *threshold,
             RuleCondition::TimeSinceRebalance(hours) => context.hours_since_rebalance > *hours,
+            RuleCondition::HealthBelow(threshold) => {
+                context.health_score.is_some_and(|score| score < *threshold)
+            }
+            RuleCondition::OutsideBreakeven => context
+                .price
+                .zip(context.pnl.breakeven_band)
+                .is_some_and(|(price, band)| !band.contains(price)),
             RuleCondition::And(a, b) => {
                 self.evaluate_condition(a, context) && self.evaluate_condition(b, context)
             }
@@ -189,6 +246,25 @@ impl RulesEngine {
             .replace("{pnl_usd}", &format!("${:.2}", context.pnl.net_pnl_usd))
             .replace("{fees_usd}", &format!("${:.2}", context.pnl.fees_usd))
             .replace("{in_range}", if context.in_range { "yes" } else { "no" })
+            .replace(
+                "{health}",
+                &context
+                    .health_score
+                    .map_or_else(|| "n/a".to_string(), |score| format!("{:.0}", score)),
+            )
+            .replace(
+                "{price}",
+                &context
+                    .price
+                    .map_or_else(|| "n/a".to_string(), |price| format!("{:.2}", price)),
+            )
+            .replace(
+                "{breakeven_band}",
+                &context
+                    .pnl
+                    .breakeven_band
+                    .map_or_else(|| "n/a".to_string(), |band| band.to_string()),
+            )
     }
 
     /// Creates default rules.
@@ -227,6 +303,17 @@ impl RulesEngine {
             .with_message("IL exceeded 10%: {il_pct}"),
         );
 
+        // Price left the break-even band
+        self.add_rule(
+            AlertRule::new(
+                "breakeven_breached",
+                RuleCondition::OutsideBreakeven,
+                AlertLevel::Warning,
+                AlertType::BreakevenBreached,
+            )
+            .with_message("Price {price} left the break-even band; price must stay within {breakeven_band} to remain profitable"),
+        );
+
         self
     }
 }
@@ -240,6 +327,7 @@ impl Default for RulesEngine {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use clmm_lp_domain::metrics::breakeven::BreakevenBand;
 
     #[test]
     fn test_rule_creation() {
@@ -271,11 +359,109 @@ mod tests {
             in_range: false,
             was_in_range: true,
             pnl: PositionPnL::default(),
+            price: None,
             hours_since_rebalance: 0,
+            health_score: None,
+            tags: Vec::new(),
         };
 
         let alerts = engine.evaluate(&context);
         assert_eq!(alerts.len(), 1);
         assert_eq!(alerts[0].level, AlertLevel::Warning);
     }
+
+    #[test]
+    fn test_evaluate_health_below() {
+        let mut engine = RulesEngine::new();
+        engine.add_rule(
+            AlertRule::new(
+                "health",
+                RuleCondition::HealthBelow(Decimal::new(50, 0)),
+                AlertLevel::Warning,
+                AlertType::HealthDegraded,
+            )
+            .with_message("Health score {health}"),
+        );
+
+        let mut context = RuleContext {
+            in_range: true,
+            was_in_range: true,
+            pnl: PositionPnL::default(),
+            price: None,
+            hours_since_rebalance: 0,
+            health_score: None,
+            tags: Vec::new(),
+        };
+        assert!(engine.evaluate(&context).is_empty());
+
+        context.health_score = Some(Decimal::new(35, 0));
+        let alerts = engine.evaluate(&context);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].message, "Health score 35");
+    }
+
+    #[test]
+    fn test_rule_limited_to_tags() {
+        let mut engine = RulesEngine::new();
+        engine.add_rule(
+            AlertRule::new(
+                "core_range_exit",
+                RuleCondition::RangeExit,
+                AlertLevel::Warning,
+                AlertType::RangeExit,
+            )
+            .with_tags(vec!["core".to_string()])
+            .with_channels(vec!["webhook".to_string()])
+            .with_cooldown(0),
+        );
+
+        let mut context = RuleContext {
+            in_range: false,
+            was_in_range: true,
+            pnl: PositionPnL::default(),
+            price: None,
+            hours_since_rebalance: 0,
+            health_score: None,
+            tags: vec!["experiment-a".to_string()],
+        };
+        assert!(engine.evaluate(&context).is_empty());
+
+        context.tags.push("core".to_string());
+        let alerts = engine.evaluate(&context);
+        assert_eq!(alerts.len(), 1);
+        assert!(alerts[0].has_tag("core"));
+        assert!(alerts[0].routes_to("webhook"));
+        assert!(!alerts[0].routes_to("console"));
+    }
+
+    #[test]
+    fn test_evaluate_outside_breakeven() {
+        let mut engine = RulesEngine::new().with_defaults();
+        let mut context = RuleContext {
+            in_range: true,
+            was_in_range: true,
+            pnl: PositionPnL {
+                breakeven_band: Some(BreakevenBand {
+                    lower: Decimal::new(137, 0),
+                    upper: Some(Decimal::new(162, 0)),
+                }),
+                ..Default::default()
+            },
+            price: Some(Decimal::new(150, 0)),
+            hours_since_rebalance: 0,
+            health_score: None,
+            tags: Vec::new(),
+        };
+        assert!(engine.evaluate(&context).is_empty());
+
+        context.price = Some(Decimal::new(165, 0));
+        let alerts = engine.evaluate(&context);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].alert_type, AlertType::BreakevenBreached);
+        assert_eq!(
+            alerts[0].message,
+            "Price 165.00 left the break-even band; price must stay within \
+             137.00–162.00 to remain profitable"
+        );
+    }
 }
@@ -0,0 +1,430 @@
+//! Portfolio-level drawdown guard.
+//!
+//! Samples the total portfolio value periodically and halts new executions
+//! when it falls more than a configured fraction below its peak within a
+//! rolling window.
+//!
+//! Capital deliberately taken out of the portfolio, by closing positions or
+//! withdrawing liquidity, is netted out, so only losses count as drawdown.
+
+use super::CircuitBreaker;
+use crate::alerts::{Alert, AlertLevel, AlertType, MultiNotifier};
+use crate::lifecycle::{EventData, Finality, LifecycleEvent, LifecycleTracker};
+use crate::monitor::PositionMonitor;
+use crate::shutdown::CancellationToken;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tracing::{error, info};
+
+/// Configuration for the drawdown guard.
+#[derive(Debug, Clone)]
+pub struct DrawdownConfig {
+    /// Drawdown from the window's peak that halts executions (0.1 = 10%).
+    pub max_drawdown_pct: Decimal,
+    /// Rolling window the peak is taken over in hours.
+    pub window_hours: u64,
+    /// How often the portfolio value is sampled in seconds.
+    pub snapshot_interval_secs: u64,
+}
+
+impl Default for DrawdownConfig {
+    fn default() -> Self {
+        Self {
+            max_drawdown_pct: Decimal::new(10, 2), // 10%
+            window_hours: 24,
+            snapshot_interval_secs: 300,
+        }
+    }
+}
+
+/// Portfolio value at a point in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValueSnapshot {
+    /// When the value was sampled.
+    pub timestamp: DateTime<Utc>,
+    /// Total portfolio value in USD.
+    pub value_usd: Decimal,
+    /// Capital withdrawn from the portfolio, net of deposits, up to this
+    /// snapshot in USD.
+    pub withdrawn_usd: Decimal,
+}
+
+impl ValueSnapshot {
+    /// Returns the value with withdrawn capital added back, which only
+    /// moves with gains and losses.
+    #[must_use]
+    pub fn adjusted_value_usd(&self) -> Decimal {
+        self.value_usd + self.withdrawn_usd
+    }
+}
+
+/// Halts new executions when the portfolio value drops too far too fast.
+///
+/// Once tripped, the guard stays halted until [`DrawdownGuard::reset`].
+pub struct DrawdownGuard {
+    /// Configuration.
+    config: DrawdownConfig,
+    /// Snapshots within the window, oldest first.
+    snapshots: RwLock<VecDeque<ValueSnapshot>>,
+    /// Capital withdrawn net of deposits since the last reset in USD.
+    withdrawn_usd: RwLock<Decimal>,
+    /// Lifecycle tracker liquidity withdrawals and deposits are read from.
+    lifecycle: Option<Arc<LifecycleTracker>>,
+    /// Whether executions are halted.
+    halted: AtomicBool,
+    /// Circuit breaker tripped along with the guard, if any.
+    circuit_breaker: Option<Arc<CircuitBreaker>>,
+    /// Notifier for the halt alert.
+    notifier: MultiNotifier,
+}
+
+impl DrawdownGuard {
+    /// Creates a new drawdown guard.
+    #[must_use]
+    pub fn new(config: DrawdownConfig) -> Self {
+        Self {
+            config,
+            snapshots: RwLock::new(VecDeque::new()),
+            withdrawn_usd: RwLock::new(Decimal::ZERO),
+            lifecycle: None,
+            halted: AtomicBool::new(false),
+            circuit_breaker: None,
+            notifier: MultiNotifier::new(),
+        }
+    }
+
+    /// Also trips `circuit_breaker` when the guard halts.
+    #[must_use]
+    pub fn with_circuit_breaker(mut self, circuit_breaker: Arc<CircuitBreaker>) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
+
+    /// Nets out liquidity withdrawn from or added to positions, as recorded
+    /// by `lifecycle`, when sampling the monitor.
+    #[must_use]
+    pub fn with_lifecycle(mut self, lifecycle: Arc<LifecycleTracker>) -> Self {
+        self.lifecycle = Some(lifecycle);
+        self
+    }
+
+    /// Sets the notifier for the halt alert.
+    #[must_use]
+    pub fn with_notifier(mut self, notifier: MultiNotifier) -> Self {
+        self.notifier = notifier;
+        self
+    }
+
+    /// Returns whether new executions are halted.
+    #[must_use]
+    pub fn is_halted(&self) -> bool {
+        self.halted.load(Ordering::SeqCst)
+    }
+
+    /// Records capital taken out of the portfolio (positive) or put into
+    /// it (negative), e.g. by closing or opening a position.
+    pub async fn record_flow(&self, withdrawn_usd: Decimal) {
+        *self.withdrawn_usd.write().await += withdrawn_usd;
+    }
+
+    /// Records a portfolio value and returns the drawdown from the window's
+    /// peak, halting if it exceeds the limit.
+    ///
+    /// Values are compared with withdrawn capital added back. An empty
+    /// portfolio (zero value) is not recorded, so closing every position
+    /// does not read as a total loss.
+    pub async fn record(&self, value_usd: Decimal, timestamp: DateTime<Utc>) -> Option<Decimal> {
+        if value_usd <= Decimal::ZERO {
+            return None;
+        }
+
+        let window_start = timestamp - chrono::Duration::hours(self.config.window_hours as i64);
+        let snapshot = ValueSnapshot {
+            timestamp,
+            value_usd,
+            withdrawn_usd: *self.withdrawn_usd.read().await,
+        };
+        let value = snapshot.adjusted_value_usd();
+        let (drawdown, peak) = {
+            let mut snapshots = self.snapshots.write().await;
+            snapshots.push_back(snapshot);
+            while snapshots
+                .front()
+                .is_some_and(|snapshot| snapshot.timestamp < window_start)
+            {
+                snapshots.pop_front();
+            }
+            let peak = snapshots
+                .iter()
+                .map(ValueSnapshot::adjusted_value_usd)
+                .max()
+                .unwrap_or(value);
+            if peak <= Decimal::ZERO {
+                return Some(Decimal::ZERO);
+            }
+            ((peak - value) / peak, peak)
+        };
+
+        if drawdown > self.config.max_drawdown_pct && !self.halted.swap(true, Ordering::SeqCst) {
+            self.trip(drawdown, peak, value).await;
+        }
+        Some(drawdown)
+    }
+
+    /// Returns the snapshots within the window, oldest first.
+    pub async fn snapshots(&self) -> Vec<ValueSnapshot> {
+        self.snapshots.read().await.iter().copied().collect()
+    }
+
+    /// Clears the halt and the snapshot history, so the drop that tripped
+    /// the guard is not counted again.
+    pub async fn reset(&self) {
+        self.snapshots.write().await.clear();
+        *self.withdrawn_usd.write().await = Decimal::ZERO;
+        if self.halted.swap(false, Ordering::SeqCst) {
+            info!("Drawdown guard reset");
+        }
+    }
+
+    /// Samples the monitor's portfolio value periodically until `shutdown`
+    /// is cancelled.
+    ///
+    /// Positions that left the monitor or were closed since the last sample
+    /// count as withdrawn at their last value, new positions as deposited.
+    pub async fn run_until(
+        self: Arc<Self>,
+        monitor: Arc<PositionMonitor>,
+        shutdown: CancellationToken,
+    ) {
+        let mut ticker = tokio::time::interval(Duration::from_secs(
+            self.config.snapshot_interval_secs.max(1),
+        ));
+        let mut last_values: Option<HashMap<Pubkey, Decimal>> = None;
+        let mut last_sample = Utc::now();
+        loop {
+            tokio::select! {
+                () = shutdown.cancelled() => break,
+                _ = ticker.tick() => {}
+            }
+            let now = Utc::now();
+            let values: HashMap<Pubkey, Decimal> = monitor
+                .get_positions()
+                .await
+                .into_iter()
+                .map(|position| (position.address, position.pnl.current_value_usd))
+                .collect();
+            if let Some(last_values) = &last_values {
+                let events = match &self.lifecycle {
+                    Some(lifecycle) => lifecycle
+                        .get_all_events()
+                        .await
+                        .into_iter()
+                        .filter(|event| event.timestamp > last_sample)
+                        .collect(),
+                    None => Vec::new(),
+                };
+                self.record_flow(capital_flow(last_values, &values, &events))
+                    .await;
+            }
+
+            let metrics = monitor.get_portfolio_metrics().await;
+            self.record(metrics.total_value_usd, now).await;
+            last_values = Some(values);
+            last_sample = now;
+        }
+    }
+
+    /// Trips the circuit breaker, if any, and sends the halt alert.
+    async fn trip(&self, drawdown: Decimal, peak: Decimal, value: Decimal) {
+        let reason = format!(
+            "portfolio drawdown {:.1}% within {}h (${:.2} -> ${:.2})",
+            drawdown * Decimal::ONE_HUNDRED,
+            self.config.window_hours,
+            peak,
+            value
+        );
+        error!(reason = %reason, "Drawdown guard halted executions");
+        if let Some(circuit_breaker) = &self.circuit_breaker {
+            circuit_breaker.manual_trip(&reason).await;
+        }
+        let alert = Alert::new(
+            AlertLevel::Critical,
+            AlertType::DrawdownHalt,
+            format!("Executions halted: {}", reason),
+        );
+        self.notifier.notify_all(&alert).await;
+    }
+}
+
+/// Returns the capital withdrawn between two samples of position values,
+/// net of deposits, given the lifecycle events in between.
+///
+/// Closed positions are withdrawn at their last value and new positions
+/// deposited at their current value. Partial liquidity changes move the
+/// matching share of the position's last value.
+fn capital_flow(
+    last: &HashMap<Pubkey, Decimal>,
+    current: &HashMap<Pubkey, Decimal>,
+    events: &[LifecycleEvent],
+) -> Decimal {
+    let events: Vec<&LifecycleEvent> = events
+        .iter()
+        .filter(|event| event.finality != Some(Finality::Dropped))
+        .collect();
+    let closed = |position: &Pubkey| {
+        !current.contains_key(position)
+            || events.iter().any(|event| {
+                event.position == *position && matches!(event.data, EventData::PositionClosed(_))
+            })
+    };
+
+    let mut withdrawn = Decimal::ZERO;
+    for (position, value) in last {
+        if closed(position) {
+            withdrawn += *value - current.get(position).copied().unwrap_or_default();
+        }
+    }
+    for (position, value) in current {
+        if !last.contains_key(position) {
+            withdrawn -= *value;
+        }
+    }
+    for event in &events {
+        let EventData::LiquidityChange(change) = &event.data else {
+            continue;
+        };
+        let Some(value) = last.get(&event.position) else {
+            continue;
+        };
+        if closed(&event.position) {
+            continue;
+        }
+        let before = if change.is_increase {
+            change.new_liquidity.saturating_sub(change.liquidity_delta)
+        } else {
+            change.new_liquidity.saturating_add(change.liquidity_delta)
+        };
+        if before == 0 {
+            continue;
+        }
+        let (Some(delta), Some(before)) = (
+            Decimal::from_u128(change.liquidity_delta),
+            Decimal::from_u128(before),
+        ) else {
+            continue;
+        };
+        let moved = *value * delta / before;
+        if change.is_increase {
+            withdrawn -= moved;
+        } else {
+            withdrawn += moved;
+        }
+    }
+    withdrawn
+}
+
+impl Default for DrawdownGuard {
+    fn default() -> Self {
+        Self::new(DrawdownConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::emergency::CircuitState;
+    use crate::lifecycle::{LifecycleEventType, LiquidityChangeData};
+    use rust_decimal_macros::dec;
+
+    fn hours_ago(hours: i64) -> DateTime<Utc> {
+        Utc::now() - chrono::Duration::hours(hours)
+    }
+
+    #[tokio::test]
+    async fn test_halts_on_drawdown_within_window() {
+        let breaker = Arc::new(CircuitBreaker::default());
+        let guard = DrawdownGuard::default().with_circuit_breaker(breaker.clone());
+
+        assert_eq!(guard.record(dec!(1000), hours_ago(3)).await, Some(dec!(0)));
+        assert_eq!(
+            guard.record(dec!(950), hours_ago(2)).await,
+            Some(dec!(0.05))
+        );
+        assert!(!guard.is_halted());
+
+        assert_eq!(
+            guard.record(dec!(850), hours_ago(1)).await,
+            Some(dec!(0.15))
+        );
+        assert!(guard.is_halted());
+        assert_eq!(breaker.state().await, CircuitState::Open);
+
+        guard.reset().await;
+        assert!(!guard.is_halted());
+        assert!(guard.snapshots().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_peaks_outside_window_are_forgotten() {
+        let guard = DrawdownGuard::default();
+
+        guard.record(dec!(1000), hours_ago(30)).await;
+        assert_eq!(guard.record(dec!(800), hours_ago(1)).await, Some(dec!(0)));
+        assert_eq!(guard.snapshots().await.len(), 1);
+        assert!(!guard.is_halted());
+
+        // An emptied portfolio is not a loss
+        assert_eq!(guard.record(Decimal::ZERO, Utc::now()).await, None);
+        assert!(!guard.is_halted());
+    }
+
+    #[tokio::test]
+    async fn test_closing_positions_is_not_a_drawdown() {
+        let guard = DrawdownGuard::default();
+
+        assert_eq!(guard.record(dec!(1000), hours_ago(2)).await, Some(dec!(0)));
+        // Closing 15% of the portfolio takes its value out, not a loss
+        guard.record_flow(dec!(150)).await;
+        assert_eq!(guard.record(dec!(850), hours_ago(1)).await, Some(dec!(0)));
+        assert!(!guard.is_halted());
+
+        // Losses on what remains still count
+        assert_eq!(guard.record(dec!(680), Utc::now()).await, Some(dec!(0.17)));
+        assert!(guard.is_halted());
+    }
+
+    #[test]
+    fn test_capital_flow_nets_closes_opens_and_withdrawals() {
+        let (closed, opened, reduced, held) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let last = HashMap::from([(closed, dec!(150)), (reduced, dec!(400)), (held, dec!(450))]);
+        let current = HashMap::from([(opened, dec!(200)), (reduced, dec!(300)), (held, dec!(400))]);
+        let withdrawal = LifecycleEvent::new(
+            LifecycleEventType::LiquidityDecreased,
+            reduced,
+            Pubkey::new_unique(),
+            EventData::LiquidityChange(LiquidityChangeData {
+                is_increase: false,
+                liquidity_delta: 250,
+                amount_a: 0,
+                amount_b: 0,
+                new_liquidity: 750,
+            }),
+        );
+
+        // 150 closed + a quarter of 400 withdrawn - 200 opened; the drop
+        // of the held position is a loss, not a flow
+        assert_eq!(capital_flow(&last, &current, &[withdrawal]), dec!(50));
+    }
+}
@@ -122,6 +122,10 @@ impl EmergencyExitManager {
         let mut results = Vec::new();
 
         for position in positions {
+            if position.read_only {
+                info!(position = %position.address, "Skipping read-only position");
+                continue;
+            }
             let result = self.exit_position(&position.address).await;
             results.push(result);
         }
@@ -158,6 +162,18 @@ impl EmergencyExitManager {
             liquidity_removed: None,
         };
 
+        if self
+            .monitor
+            .get_position(position)
+            .await
+            .is_some_and(|p| p.read_only)
+        {
+            warn!(position = %position, "Refusing to exit read-only position");
+            result.status = ExitStatus::Failed;
+            result.error = Some("Position is read-only".to_string());
+            return result;
+        }
+
         // Step 1: Collect fees if configured
         if self.config.collect_fees {
             result.status = ExitStatus::CollectingFees;
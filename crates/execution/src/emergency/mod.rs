@@ -4,9 +4,12 @@
 //! - Circuit breaker for consecutive failures
 //! - Emergency position exit
 //! - Loss threshold protection
+//! - Portfolio drawdown halt from periodic value snapshots
 
 mod circuit_breaker;
+mod drawdown;
 mod emergency_exit;
 
 pub use circuit_breaker::*;
+pub use drawdown::*;
 pub use emergency_exit::*;
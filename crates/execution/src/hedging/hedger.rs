@@ -0,0 +1,397 @@
+//! Keeps a perp short matched to the delta of LP positions.
+
+use super::{PerpFill, PerpVenue};
+use crate::monitor::{MonitoredPosition, PortfolioMetrics, PositionMonitor};
+use crate::shutdown::CancellationToken;
+use crate::sync::PoolStateCache;
+use anyhow::{Context, Result, anyhow};
+use clmm_lp_domain::prelude::position_greeks;
+use clmm_lp_protocols::prelude::{WhirlpoolState, tick_to_price};
+use rust_decimal::Decimal;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::time::interval;
+use tracing::{info, warn};
+
+/// Configuration for the delta hedger.
+#[derive(Debug, Clone)]
+pub struct HedgeConfig {
+    /// Perp market the short is held in.
+    pub market: String,
+    /// Mint of the hedged token; positions in pools with it as token A are
+    /// hedged.
+    pub mint: Pubkey,
+    /// Decimals of the hedged token.
+    pub decimals: u32,
+    /// Fraction of the delta to hedge (1 = fully hedged).
+    pub hedge_ratio: Decimal,
+    /// Drift from target, relative to the target, tolerated before
+    /// adjusting (0.1 = 10%).
+    pub band_pct: Decimal,
+    /// Smallest adjustment worth trading, in token units.
+    pub min_trade_size: Decimal,
+    /// Interval between hedge checks in seconds.
+    pub interval_secs: u64,
+}
+
+impl HedgeConfig {
+    /// Creates a config fully hedging `mint` in `market`.
+    #[must_use]
+    pub fn new(market: impl Into<String>, mint: Pubkey, decimals: u32) -> Self {
+        Self {
+            market: market.into(),
+            mint,
+            decimals,
+            hedge_ratio: Decimal::ONE,
+            band_pct: Decimal::new(1, 1), // 10%
+            min_trade_size: Decimal::ZERO,
+            interval_secs: 60,
+        }
+    }
+}
+
+/// Running PnL of the hedge short.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HedgePnL {
+    /// Open short size.
+    pub size: Decimal,
+    /// Average entry price of the open short.
+    pub entry_price: Decimal,
+    /// PnL realized by buying back.
+    pub realized_pnl: Decimal,
+    /// Fees paid.
+    pub fees: Decimal,
+    /// Latest mark price.
+    pub mark_price: Decimal,
+}
+
+impl HedgePnL {
+    /// Applies a fill to the open short.
+    pub fn apply(&mut self, fill: &PerpFill) {
+        if fill.size > Decimal::ZERO {
+            let size = self.size + fill.size;
+            self.entry_price = (self.entry_price * self.size + fill.price * fill.size) / size;
+            self.size = size;
+        } else {
+            let closed = fill.size.abs().min(self.size);
+            self.realized_pnl += closed * (self.entry_price - fill.price);
+            self.size -= closed;
+            if self.size.is_zero() {
+                self.entry_price = Decimal::ZERO;
+            }
+        }
+        self.fees += fill.fee;
+        self.mark_price = fill.price;
+    }
+
+    /// Returns the PnL of the open short at the latest mark price.
+    #[must_use]
+    pub fn unrealized_pnl(&self) -> Decimal {
+        self.size * (self.entry_price - self.mark_price)
+    }
+
+    /// Returns realized plus unrealized PnL, net of fees.
+    #[must_use]
+    pub fn net_pnl(&self) -> Decimal {
+        self.realized_pnl + self.unrealized_pnl() - self.fees
+    }
+}
+
+/// Returns the delta of a position in token A units.
+///
+/// # Errors
+/// Returns an error if the pool price or range is invalid, or if `decimals`
+/// is too large to scale by.
+pub fn position_delta(
+    position: &MonitoredPosition,
+    pool: &WhirlpoolState,
+    decimals: u32,
+) -> Result<Decimal> {
+    let scale = 10u64
+        .checked_pow(decimals)
+        .with_context(|| format!("Token decimals {} out of range", decimals))?;
+    let greeks = position_greeks(
+        position.on_chain.liquidity,
+        pool.price,
+        tick_to_price(position.on_chain.tick_lower),
+        tick_to_price(position.on_chain.tick_upper),
+    )
+    .map_err(|e| anyhow!(e))?;
+    Ok(greeks.delta / Decimal::from(scale))
+}
+
+/// Returns the short adjustment needed to reach `target`, or `None` if
+/// `current` is within the band.
+#[must_use]
+pub fn hedge_adjustment(
+    target: Decimal,
+    current: Decimal,
+    band_pct: Decimal,
+    min_trade_size: Decimal,
+) -> Option<Decimal> {
+    let drift = target - current;
+    let tolerance = (band_pct * target.abs()).max(min_trade_size);
+    (!drift.is_zero() && drift.abs() > tolerance).then_some(drift)
+}
+
+/// Maintains a perp short sized to the delta of the monitored positions.
+pub struct DeltaHedger {
+    /// Position monitor providing the hedged positions.
+    monitor: Arc<PositionMonitor>,
+    /// Pool state cache.
+    pool_cache: Arc<PoolStateCache>,
+    /// Venue holding the short.
+    venue: Arc<dyn PerpVenue>,
+    /// Configuration.
+    config: HedgeConfig,
+    /// PnL of the short.
+    pnl: RwLock<HedgePnL>,
+}
+
+impl DeltaHedger {
+    /// Creates a new delta hedger.
+    pub fn new(
+        monitor: Arc<PositionMonitor>,
+        pool_cache: Arc<PoolStateCache>,
+        venue: Arc<dyn PerpVenue>,
+        config: HedgeConfig,
+    ) -> Self {
+        Self {
+            monitor,
+            pool_cache,
+            venue,
+            config,
+            pnl: RwLock::new(HedgePnL::default()),
+        }
+    }
+
+    /// Returns the short size matching the current position deltas.
+    ///
+    /// Read-only positions are not hedged.
+    pub async fn target_size(&self) -> Result<Decimal> {
+        let mut delta = Decimal::ZERO;
+        for position in self.monitor.get_positions().await {
+            if position.read_only {
+                continue;
+            }
+            let pool = self.pool_cache.get(&position.pool).await?;
+            if pool.token_mint_a != self.config.mint {
+                continue;
+            }
+            delta += position_delta(&position, &pool, self.config.decimals)?;
+        }
+        Ok(delta * self.config.hedge_ratio)
+    }
+
+    /// Adjusts the short if it drifted outside the band; returns the fill.
+    pub async fn rebalance(&self) -> Result<Option<PerpFill>> {
+        let target = self.target_size().await?;
+        let current = self.venue.short_size(&self.config.market).await?;
+        let mark = self.venue.mark_price(&self.config.market).await?;
+        self.pnl.write().await.mark_price = mark;
+
+        let Some(adjustment) = hedge_adjustment(
+            target,
+            current,
+            self.config.band_pct,
+            self.config.min_trade_size,
+        ) else {
+            return Ok(None);
+        };
+
+        let fill = self
+            .venue
+            .adjust_short(&self.config.market, adjustment)
+            .await?;
+        info!(
+            venue = self.venue.name(),
+            market = %self.config.market,
+            target = %target,
+            size = %fill.size,
+            price = %fill.price,
+            "Adjusted hedge"
+        );
+        self.pnl.write().await.apply(&fill);
+        Ok(Some(fill))
+    }
+
+    /// Returns the PnL of the short.
+    pub async fn pnl(&self) -> HedgePnL {
+        self.pnl.read().await.clone()
+    }
+
+    /// Adds the hedge PnL to portfolio metrics; the monitor does this for a
+    /// hedger registered with [`PositionMonitor::set_hedger`].
+    pub async fn merge_into(&self, metrics: &mut PortfolioMetrics) {
+        let pnl = self.pnl.read().await.net_pnl();
+        metrics.hedge_pnl_usd += pnl;
+        metrics.total_pnl_usd += pnl;
+    }
+
+    /// Runs hedge checks until `shutdown` is cancelled.
+    pub async fn run_until(&self, shutdown: CancellationToken) {
+        let mut ticker = interval(Duration::from_secs(self.config.interval_secs));
+        info!(market = %self.config.market, "Starting delta hedger");
+
+        loop {
+            tokio::select! {
+                () = shutdown.cancelled() => break,
+                _ = ticker.tick() => {}
+            }
+
+            if let Err(e) = self.rebalance().await {
+                warn!(market = %self.config.market, error = %e, "Hedge check failed");
+            }
+        }
+
+        info!("Delta hedger stopped");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hedging::PaperPerpVenue;
+    use crate::monitor::MonitorConfig;
+    use clmm_lp_protocols::prelude::{OnChainPosition, Protocol, RpcConfig, RpcProvider};
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_hedge_adjustment_band() {
+        assert_eq!(
+            hedge_adjustment(dec!(100), dec!(95), dec!(0.1), dec!(0)),
+            None
+        );
+        assert_eq!(
+            hedge_adjustment(dec!(100), dec!(80), dec!(0.1), dec!(0)),
+            Some(dec!(20))
+        );
+        assert_eq!(
+            hedge_adjustment(dec!(0), dec!(5), dec!(0.1), dec!(0)),
+            Some(dec!(-5))
+        );
+        assert_eq!(
+            hedge_adjustment(dec!(0), dec!(5), dec!(0.1), dec!(10)),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hedge_pnl_merged_into_metrics() {
+        let provider = Arc::new(RpcProvider::new(RpcConfig::default()));
+        let monitor = Arc::new(PositionMonitor::new(
+            provider.clone(),
+            MonitorConfig::default(),
+        ));
+        let cache = Arc::new(PoolStateCache::new(provider, Duration::from_secs(10)));
+        let venue = Arc::new(PaperPerpVenue::new(dec!(0.001)));
+        let config = HedgeConfig::new("SOL-PERP", Pubkey::new_unique(), 9);
+        let hedger = Arc::new(DeltaHedger::new(
+            monitor.clone(),
+            cache,
+            venue.clone(),
+            config,
+        ));
+        monitor.set_hedger(&hedger).await;
+
+        // A short left over from positions that have since been closed
+        venue.set_mark_price("SOL-PERP", dec!(100)).await;
+        let fill = venue.adjust_short("SOL-PERP", dec!(10)).await.unwrap();
+        hedger.pnl.write().await.apply(&fill);
+
+        venue.set_mark_price("SOL-PERP", dec!(90)).await;
+        let fill = hedger.rebalance().await.unwrap().unwrap();
+        assert_eq!(fill.size, dec!(-10));
+        assert_eq!(venue.short_size("SOL-PERP").await.unwrap(), Decimal::ZERO);
+
+        let pnl = hedger.pnl().await;
+        assert_eq!(pnl.realized_pnl, dec!(100));
+        assert_eq!(pnl.fees, dec!(1.9));
+
+        let mut metrics = PortfolioMetrics {
+            total_pnl_usd: dec!(-50),
+            ..Default::default()
+        };
+        hedger.merge_into(&mut metrics).await;
+        assert_eq!(metrics.hedge_pnl_usd, dec!(98.1));
+        assert_eq!(metrics.total_pnl_usd, dec!(48.1));
+
+        // The monitor includes the hedge in its own metrics
+        let metrics = monitor.get_portfolio_metrics().await;
+        assert_eq!(metrics.hedge_pnl_usd, dec!(98.1));
+        assert_eq!(metrics.total_pnl_usd, dec!(98.1));
+    }
+
+    #[test]
+    fn test_position_delta_rejects_out_of_range_decimals() {
+        let position = MonitoredPosition {
+            address: Pubkey::new_unique(),
+            pool: Pubkey::new_unique(),
+            protocol: Protocol::OrcaWhirlpool,
+            on_chain: OnChainPosition {
+                address: Pubkey::new_unique(),
+                pool: Pubkey::new_unique(),
+                owner: Pubkey::new_unique(),
+                tick_lower: -1000,
+                tick_upper: 1000,
+                liquidity: 1_000_000,
+                fee_growth_inside_a: 0,
+                fee_growth_inside_b: 0,
+                fees_owed_a: 0,
+                fees_owed_b: 0,
+                reward_growth_inside: [0; 3],
+                rewards_owed: [0; 3],
+            },
+            pnl: Default::default(),
+            in_range: true,
+            tick_current: 0,
+            range_checks: 0,
+            in_range_checks: 0,
+            health: None,
+            strategy: None,
+            tags: Vec::new(),
+            read_only: false,
+            stale_since: None,
+            last_updated: chrono::Utc::now(),
+        };
+        let pool = WhirlpoolState {
+            address: String::new(),
+            token_mint_a: Pubkey::new_unique(),
+            token_mint_b: Pubkey::new_unique(),
+            tick_current: 0,
+            tick_spacing: 64,
+            sqrt_price: 1 << 64,
+            price: Decimal::ONE,
+            liquidity: 1_000_000,
+            fee_rate: 30,
+            protocol_fee_rate_bps: 0,
+            fee_growth_global_a: 0,
+            fee_growth_global_b: 0,
+            rewards: Vec::new(),
+        };
+
+        assert!(position_delta(&position, &pool, 9).unwrap() > Decimal::ZERO);
+        assert!(position_delta(&position, &pool, 20).is_err());
+    }
+
+    #[test]
+    fn test_hedge_pnl_partial_close() {
+        let mut pnl = HedgePnL::default();
+        pnl.apply(&PerpFill {
+            size: dec!(10),
+            price: dec!(100),
+            fee: Decimal::ZERO,
+        });
+        pnl.apply(&PerpFill {
+            size: dec!(-4),
+            price: dec!(90),
+            fee: Decimal::ZERO,
+        });
+
+        assert_eq!(pnl.size, dec!(6));
+        assert_eq!(pnl.realized_pnl, dec!(40));
+        assert_eq!(pnl.unrealized_pnl(), dec!(60));
+    }
+}
@@ -0,0 +1,19 @@
+//! Delta hedging of LP positions with perp shorts.
+//!
+//! An LP position holds more of token A as the price falls, so it carries a
+//! long exposure equal to its delta. The hedger offsets it on a perp venue:
+//! - The target short is the summed delta of the hedged positions
+//! - The short is only adjusted once it drifts outside a band around target
+//! - Fills are tracked so hedge PnL can be added to portfolio metrics
+//!
+//! Hedging is paper-only for now: [`PaperPerpVenue`] is the only
+//! [`PerpVenue`], and neither the API server nor the CLI builds a
+//! [`DeltaHedger`]. Embedders can run one against the paper venue to size
+//! and evaluate a hedge; placing real perp orders needs a venue
+//! implementation of its own.
+
+mod hedger;
+mod venue;
+
+pub use hedger::*;
+pub use venue::*;
@@ -0,0 +1,129 @@
+//! Perp venues the hedge is placed on.
+
+use anyhow::{Context, Result, bail};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+
+/// A filled hedge adjustment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PerpFill {
+    /// Change in short size; positive sells, negative buys back.
+    pub size: Decimal,
+    /// Fill price.
+    pub price: Decimal,
+    /// Fee paid, in quote units.
+    pub fee: Decimal,
+}
+
+/// A venue holding a perp short.
+#[async_trait]
+pub trait PerpVenue: Send + Sync {
+    /// Returns the name of this venue.
+    fn name(&self) -> &str;
+
+    /// Returns the current short size in `market`, in base units.
+    async fn short_size(&self, market: &str) -> Result<Decimal>;
+
+    /// Changes the short in `market` by `size_delta` base units at market.
+    async fn adjust_short(&self, market: &str, size_delta: Decimal) -> Result<PerpFill>;
+
+    /// Returns the mark price of `market`.
+    async fn mark_price(&self, market: &str) -> Result<Decimal>;
+}
+
+/// In-memory venue filling every order at the mark price, for dry runs.
+///
+/// It only holds shorts: orders buying back more than the open short are
+/// rejected rather than flipping it long.
+pub struct PaperPerpVenue {
+    /// Fee charged on the notional of each fill.
+    fee_rate: Decimal,
+    /// Mark price per market.
+    marks: RwLock<HashMap<String, Decimal>>,
+    /// Short size per market.
+    shorts: RwLock<HashMap<String, Decimal>>,
+}
+
+impl PaperPerpVenue {
+    /// Creates a venue charging `fee_rate` of the notional per fill.
+    #[must_use]
+    pub fn new(fee_rate: Decimal) -> Self {
+        Self {
+            fee_rate,
+            marks: RwLock::new(HashMap::new()),
+            shorts: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Sets the mark price of `market`.
+    pub async fn set_mark_price(&self, market: &str, price: Decimal) {
+        self.marks.write().await.insert(market.to_string(), price);
+    }
+}
+
+#[async_trait]
+impl PerpVenue for PaperPerpVenue {
+    fn name(&self) -> &str {
+        "paper"
+    }
+
+    async fn short_size(&self, market: &str) -> Result<Decimal> {
+        Ok(self
+            .shorts
+            .read()
+            .await
+            .get(market)
+            .copied()
+            .unwrap_or_default())
+    }
+
+    async fn adjust_short(&self, market: &str, size_delta: Decimal) -> Result<PerpFill> {
+        let price = self.mark_price(market).await?;
+        let mut shorts = self.shorts.write().await;
+        let short = shorts.entry(market.to_string()).or_default();
+        if *short + size_delta < Decimal::ZERO {
+            bail!(
+                "Cannot buy back {} of {}: the open short is {}",
+                size_delta.abs(),
+                market,
+                short
+            );
+        }
+        *short += size_delta;
+        Ok(PerpFill {
+            size: size_delta,
+            price,
+            fee: size_delta.abs() * price * self.fee_rate,
+        })
+    }
+
+    async fn mark_price(&self, market: &str) -> Result<Decimal> {
+        self.marks
+            .read()
+            .await
+            .get(market)
+            .copied()
+            .with_context(|| format!("No mark price for {}", market))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[tokio::test]
+    async fn test_paper_venue_rejects_flipping_long() {
+        let venue = PaperPerpVenue::new(Decimal::ZERO);
+        venue.set_mark_price("SOL-PERP", dec!(100)).await;
+        venue.adjust_short("SOL-PERP", dec!(5)).await.unwrap();
+
+        assert!(venue.adjust_short("SOL-PERP", dec!(-6)).await.is_err());
+        assert_eq!(venue.short_size("SOL-PERP").await.unwrap(), dec!(5));
+
+        venue.adjust_short("SOL-PERP", dec!(-5)).await.unwrap();
+        assert_eq!(venue.short_size("SOL-PERP").await.unwrap(), Decimal::ZERO);
+    }
+}
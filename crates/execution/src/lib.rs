@@ -7,26 +7,49 @@
 //! - Transaction building and lifecycle management
 //! - Automated strategy execution
 //! - Emergency controls and circuit breaker
+//! - Optional delta hedging with perp shorts, on a paper venue
 //! - Position lifecycle tracking
+//! - Scheduled range advice from optimizer re-runs
+//! - Daily summary reports
+//! - Tax lot reporting and double-entry accounting export
 //! - State synchronization
+//! - Coordinated graceful shutdown
+//! - Supervised restarts of background loops
+//! - Runtime configuration reload
 
 /// Prelude module for convenient imports.
 pub mod prelude;
 
+/// Double-entry accounting export.
+pub mod accounting;
+/// Scheduled range advice.
+pub mod advisor;
 /// Alert system.
 pub mod alerts;
 /// Emergency controls and circuit breaker.
 pub mod emergency;
+/// Delta hedging via perps (paper venue only).
+pub mod hedging;
 /// Position lifecycle tracking.
 pub mod lifecycle;
 /// Position monitoring.
 pub mod monitor;
+/// Runtime configuration reload.
+pub mod reload;
+/// Daily summary reports.
+pub mod report;
 /// Scheduler for strategy timing.
 pub mod scheduler;
+/// Graceful shutdown coordination.
+pub mod shutdown;
 /// Strategy execution.
 pub mod strategy;
+/// Background loop supervision.
+pub mod supervisor;
 /// State synchronization.
 pub mod sync;
+/// Tax reporting from lifecycle events.
+pub mod tax;
 /// Transaction building and sending.
 pub mod transaction;
 /// Wallet management.
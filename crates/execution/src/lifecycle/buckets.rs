@@ -0,0 +1,258 @@
+//! Time-bucketed statistics from lifecycle events.
+//!
+//! Groups events into calendar periods (UTC) so performance can be charted
+//! over time rather than only as lifetime totals.
+
+use super::{EventData, LifecycleEvent};
+use chrono::{DateTime, Datelike, Months, NaiveTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Calendar period used for bucketing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BucketPeriod {
+    /// Calendar day.
+    Daily,
+    /// ISO week, starting Monday.
+    Weekly,
+    /// Calendar month.
+    Monthly,
+}
+
+impl BucketPeriod {
+    /// Returns the start of the bucket containing `timestamp`.
+    #[must_use]
+    pub fn bucket_start(&self, timestamp: DateTime<Utc>) -> DateTime<Utc> {
+        let date = timestamp.date_naive();
+        let start = match self {
+            Self::Daily => date,
+            Self::Weekly => {
+                date - chrono::Days::new(u64::from(date.weekday().num_days_from_monday()))
+            }
+            Self::Monthly => date.with_day(1).unwrap_or(date),
+        };
+        start.and_time(NaiveTime::MIN).and_utc()
+    }
+
+    /// Returns the start of the bucket following the one starting at `start`.
+    #[must_use]
+    pub fn next_start(&self, start: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            Self::Daily => start + chrono::Duration::days(1),
+            Self::Weekly => start + chrono::Duration::weeks(1),
+            Self::Monthly => start
+                .checked_add_months(Months::new(1))
+                .unwrap_or(DateTime::<Utc>::MAX_UTC),
+        }
+    }
+}
+
+/// Statistics for a single time bucket.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatsBucket {
+    /// Start of the bucket (inclusive).
+    pub period_start: DateTime<Utc>,
+    /// Fees collected in USD.
+    pub fees_usd: Decimal,
+    /// Rewards collected in USD.
+    pub rewards_usd: Decimal,
+    /// Realized PnL in USD from positions closed in the bucket.
+    pub realized_pnl_usd: Decimal,
+    /// Rebalances performed.
+    pub rebalances: u32,
+    /// Migrations to another pool performed.
+    #[serde(default)]
+    pub migrations: u32,
+    /// Transaction costs in lamports.
+    pub tx_costs_lamports: u64,
+    /// Positions opened.
+    pub positions_opened: u32,
+    /// Positions closed.
+    pub positions_closed: u32,
+}
+
+impl StatsBucket {
+    /// Creates an empty bucket.
+    #[must_use]
+    pub fn empty(period_start: DateTime<Utc>) -> Self {
+        Self {
+            period_start,
+            fees_usd: Decimal::ZERO,
+            rewards_usd: Decimal::ZERO,
+            realized_pnl_usd: Decimal::ZERO,
+            rebalances: 0,
+            migrations: 0,
+            tx_costs_lamports: 0,
+            positions_opened: 0,
+            positions_closed: 0,
+        }
+    }
+
+    /// Adds an event's contribution to the bucket.
+    fn add(&mut self, event: &LifecycleEvent) {
+        match &event.data {
+            EventData::PositionOpened(_) => self.positions_opened += 1,
+            EventData::LiquidityChange(_) => {}
+            EventData::Rebalance(data) => {
+                self.rebalances += 1;
+                self.tx_costs_lamports += data.tx_cost_lamports;
+            }
+            EventData::Migration(data) => {
+                self.migrations += 1;
+                self.tx_costs_lamports += data.tx_cost_lamports;
+            }
+            EventData::FeesCollected(data) => {
+                self.fees_usd += data.fees_usd;
+                self.rewards_usd += data.rewards_usd;
+            }
+            EventData::PositionClosed(data) => {
+                self.positions_closed += 1;
+                self.realized_pnl_usd += data.final_pnl_usd;
+            }
+        }
+    }
+}
+
+/// Groups events into buckets, oldest first.
+///
+/// Buckets between the first and last event are always present, with zeros
+/// where nothing happened, so the result can be charted directly.
+pub fn bucket_events<'a>(
+    events: impl IntoIterator<Item = &'a LifecycleEvent>,
+    period: BucketPeriod,
+) -> Vec<StatsBucket> {
+    let mut buckets: BTreeMap<DateTime<Utc>, StatsBucket> = BTreeMap::new();
+    for event in events {
+        let start = period.bucket_start(event.timestamp);
+        buckets
+            .entry(start)
+            .or_insert_with(|| StatsBucket::empty(start))
+            .add(event);
+    }
+
+    let (Some(&first), Some(&last)) = (buckets.keys().next(), buckets.keys().next_back()) else {
+        return Vec::new();
+    };
+
+    let mut result = Vec::with_capacity(buckets.len());
+    let mut start = first;
+    while start <= last {
+        result.push(
+            buckets
+                .remove(&start)
+                .unwrap_or_else(|| StatsBucket::empty(start)),
+        );
+        start = period.next_start(start);
+    }
+    result
+}
+
+/// Sums events into a single bucket starting at `period_start`, whatever
+/// their timestamps, for periods that are not calendar aligned.
+pub fn summarize_events<'a>(
+    period_start: DateTime<Utc>,
+    events: impl IntoIterator<Item = &'a LifecycleEvent>,
+) -> StatsBucket {
+    let mut bucket = StatsBucket::empty(period_start);
+    for event in events {
+        bucket.add(event);
+    }
+    bucket
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lifecycle::{FeesCollectedData, LifecycleEventType, RebalanceData, RebalanceReason};
+    use chrono::TimeZone;
+    use solana_sdk::pubkey::Pubkey;
+
+    fn at(y: i32, m: u32, d: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, 15, 30, 0).unwrap()
+    }
+
+    fn fees_event(timestamp: DateTime<Utc>, usd: i64) -> LifecycleEvent {
+        let mut event = LifecycleEvent::new(
+            LifecycleEventType::FeesCollected,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            EventData::FeesCollected(FeesCollectedData {
+                fees_a: 0,
+                fees_b: 0,
+                fees_usd: Decimal::from(usd),
+                rewards: [0, 0, 0],
+                rewards_usd: Decimal::ZERO,
+            }),
+        );
+        event.timestamp = timestamp;
+        event
+    }
+
+    fn rebalance_event(timestamp: DateTime<Utc>, tx_cost_lamports: u64) -> LifecycleEvent {
+        let mut event = LifecycleEvent::new(
+            LifecycleEventType::Rebalanced,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            EventData::Rebalance(RebalanceData {
+                old_tick_lower: -100,
+                old_tick_upper: 100,
+                new_tick_lower: 0,
+                new_tick_upper: 200,
+                old_liquidity: 1000,
+                new_liquidity: 1000,
+                tx_cost_lamports,
+                il_at_rebalance: Decimal::ZERO,
+                reason: RebalanceReason::RangeExit,
+            }),
+        );
+        event.timestamp = timestamp;
+        event
+    }
+
+    #[test]
+    fn test_bucket_start() {
+        // 2024-03-14 is a Thursday
+        let ts = at(2024, 3, 14);
+        assert_eq!(
+            BucketPeriod::Daily.bucket_start(ts),
+            Utc.with_ymd_and_hms(2024, 3, 14, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            BucketPeriod::Weekly.bucket_start(ts),
+            Utc.with_ymd_and_hms(2024, 3, 11, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            BucketPeriod::Monthly.bucket_start(ts),
+            Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            BucketPeriod::Monthly.next_start(BucketPeriod::Monthly.bucket_start(ts)),
+            Utc.with_ymd_and_hms(2024, 4, 1, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_bucket_events_fills_gaps() {
+        let events = vec![
+            fees_event(at(2024, 3, 1), 10),
+            rebalance_event(at(2024, 3, 1), 5000),
+            fees_event(at(2024, 3, 3), 4),
+        ];
+
+        let daily = bucket_events(&events, BucketPeriod::Daily);
+        assert_eq!(daily.len(), 3);
+        assert_eq!(daily[0].fees_usd, Decimal::from(10));
+        assert_eq!(daily[0].rebalances, 1);
+        assert_eq!(daily[0].tx_costs_lamports, 5000);
+        assert_eq!(daily[1], StatsBucket::empty(daily[1].period_start));
+        assert_eq!(daily[2].fees_usd, Decimal::from(4));
+
+        let monthly = bucket_events(&events, BucketPeriod::Monthly);
+        assert_eq!(monthly.len(), 1);
+        assert_eq!(monthly[0].fees_usd, Decimal::from(14));
+
+        assert!(bucket_events(&[], BucketPeriod::Weekly).is_empty());
+    }
+}
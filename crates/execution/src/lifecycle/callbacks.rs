@@ -0,0 +1,337 @@
+//! Signed HTTP callbacks on lifecycle milestones.
+//!
+//! An external treasury or accounting system can mirror the bot's books by
+//! receiving each milestone as it happens: positions opened and closed, and
+//! fees collected. Every callback carries the full [`LifecycleEvent`] and is
+//! signed with HMAC-SHA256 over `"{timestamp}.{body}"`, so the receiver can
+//! check it came from this deployment and reject replays.
+//!
+//! A milestone is posted twice: once when it is recorded, with estimated
+//! amounts, and again once its transaction is attached with the executed
+//! amounts. Both posts carry the same event ID.
+
+use super::{LifecycleEvent, LifecycleEventType};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// Header carrying the hex HMAC-SHA256 signature, prefixed with `sha256=`.
+pub const SIGNATURE_HEADER: &str = "X-Clmm-Signature";
+/// Header carrying the Unix timestamp the signature covers.
+pub const TIMESTAMP_HEADER: &str = "X-Clmm-Timestamp";
+/// Header carrying the lifecycle event type.
+pub const EVENT_HEADER: &str = "X-Clmm-Event";
+
+/// Where and how lifecycle callbacks are delivered.
+#[derive(Debug, Clone)]
+pub struct CallbackConfig {
+    /// Endpoint the callbacks are posted to.
+    pub url: String,
+    /// Shared secret the payloads are signed with.
+    pub secret: String,
+    /// Event types that trigger a callback.
+    pub events: Vec<LifecycleEventType>,
+    /// Timeout for each delivery attempt.
+    pub timeout: Duration,
+    /// Attempts per callback before giving up.
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled for each one after.
+    pub retry_backoff: Duration,
+}
+
+impl CallbackConfig {
+    /// Creates a configuration posting position opens, closes and fee
+    /// collections to `url`.
+    pub fn new(url: impl Into<String>, secret: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            secret: secret.into(),
+            events: vec![
+                LifecycleEventType::PositionOpened,
+                LifecycleEventType::FeesCollected,
+                LifecycleEventType::PositionClosed,
+            ],
+            timeout: Duration::from_secs(10),
+            max_attempts: 3,
+            retry_backoff: Duration::from_secs(1),
+        }
+    }
+
+    /// Sets the event types that trigger a callback.
+    #[must_use]
+    pub fn with_events(mut self, events: Vec<LifecycleEventType>) -> Self {
+        self.events = events;
+        self
+    }
+}
+
+/// Stage of a milestone a callback reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CallbackStage {
+    /// The event was recorded; amounts may be estimates.
+    Recorded,
+    /// The event's transaction was attached; amounts are as executed.
+    Executed,
+}
+
+/// Body of a lifecycle callback.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallbackPayload {
+    /// Stage of the milestone.
+    pub stage: CallbackStage,
+    /// The event, including its data and executed amounts.
+    pub event: LifecycleEvent,
+}
+
+/// Posts signed lifecycle callbacks.
+#[derive(Debug, Clone)]
+pub struct LifecycleCallbacks {
+    config: CallbackConfig,
+    client: reqwest::Client,
+}
+
+impl LifecycleCallbacks {
+    /// Creates a sender for `config`.
+    #[must_use]
+    pub fn new(config: CallbackConfig) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Gets the configuration.
+    #[must_use]
+    pub fn config(&self) -> &CallbackConfig {
+        &self.config
+    }
+
+    /// Returns true if `event_type` triggers a callback.
+    #[must_use]
+    pub fn is_milestone(&self, event_type: &LifecycleEventType) -> bool {
+        self.config.events.contains(event_type)
+    }
+
+    /// Posts `event` in the background if it is a milestone.
+    pub fn dispatch(&self, event: &LifecycleEvent, stage: CallbackStage) {
+        if !self.is_milestone(&event.event_type) {
+            return;
+        }
+        let callbacks = self.clone();
+        let payload = CallbackPayload {
+            stage,
+            event: event.clone(),
+        };
+        tokio::spawn(async move {
+            if let Err(e) = callbacks.send(&payload).await {
+                warn!(
+                    event_id = %payload.event.id,
+                    error = %e,
+                    "Failed to deliver lifecycle callback"
+                );
+            }
+        });
+    }
+
+    /// Posts `payload`, retrying with backoff on failure.
+    pub async fn send(&self, payload: &CallbackPayload) -> anyhow::Result<()> {
+        let body = serde_json::to_vec(payload)?;
+        let event_type = format!("{:?}", payload.event.event_type);
+        let mut backoff = self.config.retry_backoff;
+        let mut attempt = 1;
+        loop {
+            let timestamp = chrono::Utc::now().timestamp();
+            let result = self
+                .client
+                .post(&self.config.url)
+                .timeout(self.config.timeout)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header(
+                    SIGNATURE_HEADER,
+                    sign_callback(&self.config.secret, timestamp, &body),
+                )
+                .header(TIMESTAMP_HEADER, timestamp.to_string())
+                .header(EVENT_HEADER, &event_type)
+                .body(body.clone())
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status);
+
+            match result {
+                Ok(_) => {
+                    debug!(
+                        event_id = %payload.event.id,
+                        stage = ?payload.stage,
+                        attempt,
+                        "Delivered lifecycle callback"
+                    );
+                    return Ok(());
+                }
+                Err(e) if attempt < self.config.max_attempts => {
+                    debug!(attempt, error = %e, "Lifecycle callback failed, retrying");
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+}
+
+/// Signs `body` sent at `timestamp`, returning the value of the
+/// signature header.
+#[must_use]
+pub fn sign_callback(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    format!(
+        "sha256={}",
+        hex::encode(mac(secret, timestamp, body).finalize().into_bytes())
+    )
+}
+
+/// Checks a signature header value against `body` sent at `timestamp`.
+///
+/// Comparison is constant-time.
+#[must_use]
+pub fn verify_callback_signature(
+    secret: &str,
+    timestamp: i64,
+    body: &[u8],
+    signature: &str,
+) -> bool {
+    let Some(digest) = signature
+        .strip_prefix("sha256=")
+        .and_then(|hex| hex::decode(hex).ok())
+    else {
+        return false;
+    };
+    mac(secret, timestamp, body).verify_slice(&digest).is_ok()
+}
+
+fn mac(secret: &str, timestamp: i64, body: &[u8]) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    mac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lifecycle::{EventData, FeesCollectedData};
+    use rust_decimal::Decimal;
+    use solana_sdk::pubkey::Pubkey;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[test]
+    fn test_signature_round_trip() {
+        let signature = sign_callback("secret", 1_700_000_000, b"{}");
+        assert!(signature.starts_with("sha256="));
+        assert!(verify_callback_signature(
+            "secret",
+            1_700_000_000,
+            b"{}",
+            &signature
+        ));
+        assert!(!verify_callback_signature(
+            "other",
+            1_700_000_000,
+            b"{}",
+            &signature
+        ));
+        assert!(!verify_callback_signature(
+            "secret",
+            1_700_000_001,
+            b"{}",
+            &signature
+        ));
+        assert!(!verify_callback_signature(
+            "secret",
+            1_700_000_000,
+            b"{}",
+            "sha256=zz"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_posts_signed_milestone() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            // Read until the whole body announced by Content-Length is in
+            loop {
+                let n = socket.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request).to_lowercase();
+                if let Some(end) = text.find("\r\n\r\n") {
+                    let length: usize = text
+                        .lines()
+                        .find_map(|l| l.strip_prefix("content-length: "))
+                        .and_then(|v| v.trim().parse().ok())
+                        .unwrap_or(0);
+                    if request.len() >= end + 4 + length {
+                        break;
+                    }
+                }
+            }
+            socket
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+                .await
+                .unwrap();
+            String::from_utf8(request).unwrap()
+        });
+
+        let callbacks = LifecycleCallbacks::new(CallbackConfig::new(url, "secret"));
+        let event = LifecycleEvent::new(
+            LifecycleEventType::FeesCollected,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            EventData::FeesCollected(FeesCollectedData {
+                fees_a: 10,
+                fees_b: 20,
+                fees_usd: Decimal::ONE,
+                rewards: [0; 3],
+                rewards_usd: Decimal::ZERO,
+            }),
+        );
+        assert!(callbacks.is_milestone(&event.event_type));
+        assert!(!callbacks.is_milestone(&LifecycleEventType::Rebalanced));
+
+        callbacks
+            .send(&CallbackPayload {
+                stage: CallbackStage::Recorded,
+                event: event.clone(),
+            })
+            .await
+            .unwrap();
+
+        let request = server.await.unwrap();
+        let (head, body) = request.split_once("\r\n\r\n").unwrap();
+        let header = |name: &str| {
+            head.lines()
+                .find_map(|l| l.strip_prefix(&format!("{}: ", name.to_lowercase())))
+                .unwrap()
+                .to_string()
+        };
+        let timestamp: i64 = header(TIMESTAMP_HEADER).parse().unwrap();
+        assert!(verify_callback_signature(
+            "secret",
+            timestamp,
+            body.as_bytes(),
+            &header(SIGNATURE_HEADER)
+        ));
+        assert_eq!(header(EVENT_HEADER), "FeesCollected");
+
+        let payload: CallbackPayload = serde_json::from_str(body).unwrap();
+        assert_eq!(payload.stage, CallbackStage::Recorded);
+        assert_eq!(payload.event.id, event.id);
+    }
+}
@@ -1,5 +1,6 @@
 //! Lifecycle events for position tracking.
 
+use clmm_lp_protocols::prelude::{ExecutedAmounts, ExecutionResult, NUM_REWARDS};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
@@ -16,6 +17,8 @@ pub enum LifecycleEventType {
     LiquidityDecreased,
     /// Position was rebalanced.
     Rebalanced,
+    /// Position was moved to another pool.
+    Migrated,
     /// Fees were collected.
     FeesCollected,
     /// Position was closed.
@@ -39,6 +42,24 @@ pub struct LifecycleEvent {
     pub timestamp: chrono::DateTime<chrono::Utc>,
     /// Event-specific data.
     pub data: EventData,
+    /// Token amounts the event's transaction actually moved.
+    #[serde(default)]
+    pub executed: Option<ExecutedAmounts>,
+    /// Finality of the event's transaction, if it is being tracked.
+    #[serde(default)]
+    pub finality: Option<Finality>,
+}
+
+/// Finality of the transaction behind a lifecycle event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Finality {
+    /// Confirmed but not yet finalized; could still be rolled back.
+    Pending,
+    /// Finalized by the cluster.
+    Finalized,
+    /// Never finalized successfully, e.g. re-orged out or failed; the
+    /// event did not happen on-chain.
+    Dropped,
 }
 
 impl LifecycleEvent {
@@ -57,6 +78,8 @@ impl LifecycleEvent {
             signature: None,
             timestamp: chrono::Utc::now(),
             data,
+            executed: None,
+            finality: None,
         }
     }
 
@@ -66,6 +89,43 @@ impl LifecycleEvent {
         self.signature = Some(signature);
         self
     }
+
+    /// Records the transaction that carried out this event.
+    ///
+    /// When the executed amounts are known, they replace the estimated
+    /// token A/B amounts in the event data. Rewards and rebalance costs
+    /// span other mints or several transactions and are left as recorded.
+    pub fn apply_execution(&mut self, result: &ExecutionResult, mint_a: &Pubkey, mint_b: &Pubkey) {
+        self.signature = Some(result.signature);
+        let Some(amounts) = &result.amounts else {
+            return;
+        };
+
+        match &mut self.data {
+            EventData::PositionOpened(data) => {
+                data.amount_a = amounts.sent(mint_a);
+                data.amount_b = amounts.sent(mint_b);
+            }
+            EventData::LiquidityChange(data) if data.is_increase => {
+                data.amount_a = amounts.sent(mint_a);
+                data.amount_b = amounts.sent(mint_b);
+            }
+            EventData::LiquidityChange(data) => {
+                data.amount_a = amounts.received(mint_a);
+                data.amount_b = amounts.received(mint_b);
+            }
+            EventData::FeesCollected(data) => {
+                data.fees_a = amounts.received(mint_a);
+                data.fees_b = amounts.received(mint_b);
+            }
+            EventData::PositionClosed(data) => {
+                data.amount_a = amounts.received(mint_a);
+                data.amount_b = amounts.received(mint_b);
+            }
+            EventData::Rebalance(_) | EventData::Migration(_) => {}
+        }
+        self.executed = Some(amounts.clone());
+    }
 }
 
 /// Event-specific data.
@@ -77,6 +137,8 @@ pub enum EventData {
     LiquidityChange(LiquidityChangeData),
     /// Rebalance data.
     Rebalance(RebalanceData),
+    /// Migration data.
+    Migration(MigrationData),
     /// Fees collected data.
     FeesCollected(FeesCollectedData),
     /// Position closed data.
@@ -140,6 +202,39 @@ pub struct RebalanceData {
     pub reason: RebalanceReason,
 }
 
+/// Data for migration event.
+///
+/// Recorded against the new position in the target pool.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationData {
+    /// Position that was closed in the source pool.
+    pub old_position: Pubkey,
+    /// Source pool.
+    pub from_pool: Pubkey,
+    /// Old lower tick.
+    pub old_tick_lower: i32,
+    /// Old upper tick.
+    pub old_tick_upper: i32,
+    /// New lower tick.
+    pub new_tick_lower: i32,
+    /// New upper tick.
+    pub new_tick_upper: i32,
+    /// Liquidity removed from the old position.
+    pub old_liquidity: u128,
+    /// Liquidity added to the new position.
+    pub new_liquidity: u128,
+    /// Amount swapped to match the new range's token ratio.
+    pub swap_amount_in: u64,
+    /// Whether the swap sold token A for token B.
+    pub swap_a_to_b: bool,
+    /// Transaction cost in lamports.
+    pub tx_cost_lamports: u64,
+    /// Projected fee APR of the source pool.
+    pub from_fee_apr: Decimal,
+    /// Projected fee APR of the target pool.
+    pub to_fee_apr: Decimal,
+}
+
 /// Reason for rebalancing.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RebalanceReason {
@@ -164,6 +259,12 @@ pub struct FeesCollectedData {
     pub fees_b: u64,
     /// Fees value in USD.
     pub fees_usd: Decimal,
+    /// Rewards collected, one per reward slot.
+    #[serde(default)]
+    pub rewards: [u64; NUM_REWARDS],
+    /// Rewards value in USD.
+    #[serde(default)]
+    pub rewards_usd: Decimal,
 }
 
 /// Data for position closed event.
@@ -3,11 +3,18 @@
 //! Tracks the complete lifecycle of LP positions:
 //! - Position opening
 //! - Rebalancing events
+//! - Migrations between pools
 //! - Fee collections
 //! - Position closing
+//! - Time-bucketed statistics
+//! - Signed HTTP callbacks on milestones
 
+mod buckets;
+mod callbacks;
 mod events;
 mod tracker;
 
+pub use buckets::*;
+pub use callbacks::*;
 pub use events::*;
 pub use tracker::*;
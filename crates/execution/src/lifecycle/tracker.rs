@@ -1,15 +1,19 @@
 //! Lifecycle tracker for position history.
 
 use super::{
-    EventData, FeesCollectedData, LifecycleEvent, LifecycleEventType, LiquidityChangeData,
-    PositionClosedData, PositionOpenedData, RebalanceData,
+    BucketPeriod, CallbackStage, EventData, FeesCollectedData, Finality, LifecycleCallbacks,
+    LifecycleEvent, LifecycleEventType, LiquidityChangeData, MigrationData, PositionClosedData,
+    PositionOpenedData, RebalanceData, StatsBucket, bucket_events,
 };
+use clmm_lp_domain::metrics::APY;
+use clmm_lp_protocols::prelude::ExecutionResult;
 use rust_decimal::Decimal;
 use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 /// Summary of a position's lifecycle.
 #[derive(Debug, Clone)]
@@ -28,6 +32,8 @@ pub struct PositionSummary {
     pub current_value_usd: Decimal,
     /// Total fees collected.
     pub total_fees_usd: Decimal,
+    /// Total rewards collected in USD.
+    pub total_rewards_usd: Decimal,
     /// Number of rebalances.
     pub rebalance_count: u32,
     /// Total transaction costs in lamports.
@@ -40,6 +46,21 @@ pub struct PositionSummary {
     pub net_pnl_pct: Decimal,
     /// Whether position is still open.
     pub is_open: bool,
+    /// Position tags.
+    pub tags: Vec<String>,
+}
+
+impl PositionSummary {
+    /// Annualizes the collected fees on the entry value over the time the
+    /// position has been open, up to its close or `now`.
+    ///
+    /// Returns `None` if the entry value is zero or no time has passed.
+    #[must_use]
+    pub fn fee_yield(&self, now: chrono::DateTime<chrono::Utc>) -> Option<APY> {
+        let end = self.closed_at.unwrap_or(now);
+        let held_secs = u64::try_from((end - self.opened_at).num_seconds()).ok()?;
+        APY::from_fees(self.total_fees_usd, self.entry_value_usd, held_secs)
+    }
 }
 
 /// Tracks lifecycle events for all positions.
@@ -48,6 +69,10 @@ pub struct LifecycleTracker {
     events: Arc<RwLock<HashMap<Pubkey, Vec<LifecycleEvent>>>>,
     /// Position summaries.
     summaries: Arc<RwLock<HashMap<Pubkey, PositionSummary>>>,
+    /// Tags by position, kept even before the position is opened.
+    tags: Arc<RwLock<HashMap<Pubkey, Vec<String>>>>,
+    /// Callbacks posted on lifecycle milestones.
+    callbacks: Option<LifecycleCallbacks>,
 }
 
 impl LifecycleTracker {
@@ -57,9 +82,19 @@ impl LifecycleTracker {
         Self {
             events: Arc::new(RwLock::new(HashMap::new())),
             summaries: Arc::new(RwLock::new(HashMap::new())),
+            tags: Arc::new(RwLock::new(HashMap::new())),
+            callbacks: None,
         }
     }
 
+    /// Posts signed callbacks for milestone events as they are recorded
+    /// and again once their transaction is attached.
+    #[must_use]
+    pub fn with_callbacks(mut self, callbacks: LifecycleCallbacks) -> Self {
+        self.callbacks = Some(callbacks);
+        self
+    }
+
     /// Records a position opened event.
     pub async fn record_position_opened(
         &self,
@@ -85,12 +120,14 @@ impl LifecycleTracker {
             entry_value_usd: data.entry_value_usd,
             current_value_usd: data.entry_value_usd,
             total_fees_usd: Decimal::ZERO,
+            total_rewards_usd: Decimal::ZERO,
             rebalance_count: 0,
             total_tx_costs_lamports: 0,
             total_il_pct: Decimal::ZERO,
             net_pnl_usd: Decimal::ZERO,
             net_pnl_pct: Decimal::ZERO,
             is_open: true,
+            tags: self.get_tags(&position).await,
         };
 
         self.summaries.write().await.insert(position, summary);
@@ -159,6 +196,46 @@ impl LifecycleTracker {
         );
     }
 
+    /// Records a migration to `pool`, where the position continues as
+    /// `position`.
+    ///
+    /// The old position's summary and tags carry over to the new one, so its
+    /// PnL spans both pools.
+    pub async fn record_migration(&self, position: Pubkey, pool: Pubkey, data: MigrationData) {
+        let event = LifecycleEvent::new(
+            LifecycleEventType::Migrated,
+            position,
+            pool,
+            EventData::Migration(data.clone()),
+        );
+
+        self.add_event(position, event).await;
+
+        {
+            let mut summaries = self.summaries.write().await;
+            if let Some(mut summary) = summaries.remove(&data.old_position) {
+                summary.position = position;
+                summary.pool = pool;
+                summary.total_tx_costs_lamports += data.tx_cost_lamports;
+                summaries.insert(position, summary);
+            }
+        }
+        {
+            let mut tags = self.tags.write().await;
+            if let Some(old_tags) = tags.get(&data.old_position).cloned() {
+                tags.insert(position, old_tags);
+            }
+        }
+
+        info!(
+            old_position = %data.old_position,
+            position = %position,
+            from_pool = %data.from_pool,
+            to_pool = %pool,
+            "Position migrated"
+        );
+    }
+
     /// Records a fees collected event.
     pub async fn record_fees_collected(
         &self,
@@ -178,6 +255,7 @@ impl LifecycleTracker {
         // Update summary
         if let Some(summary) = self.summaries.write().await.get_mut(&position) {
             summary.total_fees_usd += data.fees_usd;
+            summary.total_rewards_usd += data.rewards_usd;
         }
 
         info!(
@@ -185,6 +263,7 @@ impl LifecycleTracker {
             fees_a = data.fees_a,
             fees_b = data.fees_b,
             fees_usd = %data.fees_usd,
+            rewards_usd = %data.rewards_usd,
             "Fees collected"
         );
     }
@@ -224,12 +303,89 @@ impl LifecycleTracker {
         );
     }
 
+    /// Replaces the tags on a position.
+    pub async fn set_tags(&self, position: Pubkey, mut tags: Vec<String>) {
+        tags.sort();
+        tags.dedup();
+
+        if let Some(summary) = self.summaries.write().await.get_mut(&position) {
+            summary.tags = tags.clone();
+        }
+        self.tags.write().await.insert(position, tags);
+    }
+
+    /// Gets the tags on a position.
+    pub async fn get_tags(&self, position: &Pubkey) -> Vec<String> {
+        self.tags
+            .read()
+            .await
+            .get(position)
+            .cloned()
+            .unwrap_or_default()
+    }
+
     /// Adds an event to the tracker.
     async fn add_event(&self, position: Pubkey, event: LifecycleEvent) {
+        if let Some(callbacks) = &self.callbacks {
+            callbacks.dispatch(&event, CallbackStage::Recorded);
+        }
         let mut events = self.events.write().await;
         events.entry(position).or_default().push(event);
     }
 
+    /// Attaches a confirmed transaction to the position's latest event,
+    /// replacing its estimated token amounts with the executed ones.
+    ///
+    /// Returns false if the position has no events.
+    pub async fn attach_execution(
+        &self,
+        position: &Pubkey,
+        result: &ExecutionResult,
+        mint_a: &Pubkey,
+        mint_b: &Pubkey,
+    ) -> bool {
+        let mut events = self.events.write().await;
+        let Some(event) = events.get_mut(position).and_then(|e| e.last_mut()) else {
+            return false;
+        };
+        event.apply_execution(result, mint_a, mint_b);
+        if let Some(callbacks) = &self.callbacks {
+            callbacks.dispatch(event, CallbackStage::Executed);
+        }
+
+        debug!(
+            position = %position,
+            signature = %result.signature,
+            executed = event.executed.is_some(),
+            "Attached execution to lifecycle event"
+        );
+        true
+    }
+
+    /// Sets the finality of every event recorded for `signature`.
+    ///
+    /// Returns the number of events updated.
+    pub async fn set_finality(&self, signature: &Signature, finality: Finality) -> usize {
+        let mut updated = 0;
+        for event in self.events.write().await.values_mut().flatten() {
+            if event.signature.as_ref() == Some(signature) {
+                event.finality = Some(finality);
+                updated += 1;
+            }
+        }
+
+        if finality == Finality::Dropped {
+            warn!(
+                signature = %signature,
+                events = updated,
+                "Transaction never finalized; lifecycle events did not happen on-chain"
+            );
+        } else {
+            debug!(signature = %signature, ?finality, events = updated, "Updated event finality");
+        }
+        updated
+    }
+
     /// Gets all events for a position.
     pub async fn get_events(&self, position: &Pubkey) -> Vec<LifecycleEvent> {
         self.events
@@ -240,6 +396,20 @@ impl LifecycleTracker {
             .unwrap_or_default()
     }
 
+    /// Gets the events of all positions, oldest first.
+    pub async fn get_all_events(&self) -> Vec<LifecycleEvent> {
+        let mut events: Vec<LifecycleEvent> = self
+            .events
+            .read()
+            .await
+            .values()
+            .flatten()
+            .cloned()
+            .collect();
+        events.sort_by_key(|e| e.timestamp);
+        events
+    }
+
     /// Gets the summary for a position.
     pub async fn get_summary(&self, position: &Pubkey) -> Option<PositionSummary> {
         self.summaries.read().await.get(position).cloned()
@@ -272,32 +442,63 @@ impl LifecycleTracker {
             .collect()
     }
 
+    /// Gets summaries for positions carrying `tag`.
+    pub async fn get_summaries_by_tag(&self, tag: &str) -> Vec<PositionSummary> {
+        self.summaries
+            .read()
+            .await
+            .values()
+            .filter(|s| s.tags.iter().any(|t| t == tag))
+            .cloned()
+            .collect()
+    }
+
     /// Gets aggregate statistics.
     pub async fn get_aggregate_stats(&self) -> AggregateStats {
-        let summaries = self.summaries.read().await;
+        AggregateStats::from_summaries(self.summaries.read().await.values())
+    }
 
-        let mut stats = AggregateStats::default();
+    /// Gets statistics per time bucket, oldest first.
+    ///
+    /// With `tag`, only events of positions currently carrying it count.
+    pub async fn get_bucketed_stats(
+        &self,
+        period: BucketPeriod,
+        tag: Option<&str>,
+    ) -> Vec<StatsBucket> {
+        let events = self.events.read().await;
+        let tags = self.tags.read().await;
+
+        let selected = events
+            .iter()
+            .filter(|(position, _)| {
+                tag.is_none_or(|tag| {
+                    tags.get(*position)
+                        .is_some_and(|tags| tags.iter().any(|t| t == tag))
+                })
+            })
+            .flat_map(|(_, events)| events);
+        bucket_events(selected, period)
+    }
 
+    /// Gets aggregate statistics per tag.
+    ///
+    /// A position with several tags counts towards each of them; untagged
+    /// positions are left out.
+    pub async fn get_aggregate_stats_by_tag(&self) -> HashMap<String, AggregateStats> {
+        let summaries = self.summaries.read().await;
+
+        let mut by_tag: HashMap<&str, Vec<&PositionSummary>> = HashMap::new();
         for summary in summaries.values() {
-            stats.total_positions += 1;
-            if summary.is_open {
-                stats.open_positions += 1;
-            } else {
-                stats.closed_positions += 1;
+            for tag in &summary.tags {
+                by_tag.entry(tag).or_default().push(summary);
             }
-
-            stats.total_fees_usd += summary.total_fees_usd;
-            stats.total_pnl_usd += summary.net_pnl_usd;
-            stats.total_rebalances += summary.rebalance_count;
-            stats.total_tx_costs_lamports += summary.total_tx_costs_lamports;
         }
 
-        if stats.total_positions > 0 {
-            stats.avg_pnl_pct = summaries.values().map(|s| s.net_pnl_pct).sum::<Decimal>()
-                / Decimal::from(stats.total_positions);
-        }
-
-        stats
+        by_tag
+            .into_iter()
+            .map(|(tag, group)| (tag.to_string(), AggregateStats::from_summaries(group)))
+            .collect()
     }
 }
 
@@ -318,6 +519,8 @@ pub struct AggregateStats {
     pub closed_positions: u32,
     /// Total fees earned in USD.
     pub total_fees_usd: Decimal,
+    /// Total rewards earned in USD.
+    pub total_rewards_usd: Decimal,
     /// Total PnL in USD.
     pub total_pnl_usd: Decimal,
     /// Average PnL percentage.
@@ -328,9 +531,40 @@ pub struct AggregateStats {
     pub total_tx_costs_lamports: u64,
 }
 
+impl AggregateStats {
+    /// Aggregates a set of position summaries.
+    fn from_summaries<'a>(summaries: impl IntoIterator<Item = &'a PositionSummary>) -> Self {
+        let mut stats = Self::default();
+        let mut pnl_pct_sum = Decimal::ZERO;
+
+        for summary in summaries {
+            stats.total_positions += 1;
+            if summary.is_open {
+                stats.open_positions += 1;
+            } else {
+                stats.closed_positions += 1;
+            }
+
+            stats.total_fees_usd += summary.total_fees_usd;
+            stats.total_rewards_usd += summary.total_rewards_usd;
+            stats.total_pnl_usd += summary.net_pnl_usd;
+            stats.total_rebalances += summary.rebalance_count;
+            stats.total_tx_costs_lamports += summary.total_tx_costs_lamports;
+            pnl_pct_sum += summary.net_pnl_pct;
+        }
+
+        if stats.total_positions > 0 {
+            stats.avg_pnl_pct = pnl_pct_sum / Decimal::from(stats.total_positions);
+        }
+
+        stats
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use clmm_lp_protocols::prelude::{ExecutedAmounts, TokenBalanceChange};
 
     #[tokio::test]
     async fn test_lifecycle_tracker() {
@@ -361,5 +595,250 @@ mod tests {
         let summary = tracker.get_summary(&position).await;
         assert!(summary.is_some());
         assert!(summary.unwrap().is_open);
+
+        tracker
+            .record_fees_collected(
+                position,
+                pool,
+                FeesCollectedData {
+                    fees_a: 1000,
+                    fees_b: 100,
+                    fees_usd: Decimal::new(5, 0),
+                    rewards: [2000, 0, 0],
+                    rewards_usd: Decimal::new(3, 0),
+                },
+            )
+            .await;
+
+        let summary = tracker.get_summary(&position).await.unwrap();
+        assert_eq!(summary.total_fees_usd, Decimal::new(5, 0));
+        assert_eq!(summary.total_rewards_usd, Decimal::new(3, 0));
+
+        // $5 on $1000 over 12 hours
+        let fee_yield = summary
+            .fee_yield(summary.opened_at + chrono::Duration::hours(12))
+            .unwrap();
+        assert_eq!(fee_yield.apr.round_dp(6), Decimal::new(365, 2));
+        assert!(fee_yield.estimated_annual_return > fee_yield.apr);
+        assert_eq!(
+            tracker.get_aggregate_stats().await.total_rewards_usd,
+            Decimal::new(3, 0)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_migration_carries_summary_to_new_position() {
+        let tracker = LifecycleTracker::new();
+        let (old_position, new_position) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (from_pool, to_pool) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        tracker
+            .record_position_opened(
+                old_position,
+                from_pool,
+                PositionOpenedData {
+                    tick_lower: -1000,
+                    tick_upper: 1000,
+                    liquidity: 1000000,
+                    amount_a: 1000,
+                    amount_b: 1000,
+                    entry_price: Decimal::ONE,
+                    entry_value_usd: Decimal::new(1000, 0),
+                },
+            )
+            .await;
+        tracker
+            .record_migration(
+                new_position,
+                to_pool,
+                MigrationData {
+                    old_position,
+                    from_pool,
+                    old_tick_lower: -1000,
+                    old_tick_upper: 1000,
+                    new_tick_lower: -1024,
+                    new_tick_upper: 1024,
+                    old_liquidity: 1000000,
+                    new_liquidity: 990000,
+                    swap_amount_in: 10,
+                    swap_a_to_b: true,
+                    tx_cost_lamports: 30000,
+                    from_fee_apr: Decimal::new(10, 2),
+                    to_fee_apr: Decimal::new(30, 2),
+                },
+            )
+            .await;
+
+        assert!(tracker.get_summary(&old_position).await.is_none());
+        let summary = tracker.get_summary(&new_position).await.unwrap();
+        assert_eq!(summary.pool, to_pool);
+        assert_eq!(summary.entry_value_usd, Decimal::new(1000, 0));
+        assert_eq!(summary.total_tx_costs_lamports, 30000);
+
+        let events = tracker.get_events(&new_position).await;
+        assert_eq!(events[0].event_type, LifecycleEventType::Migrated);
+        let buckets = tracker.get_bucketed_stats(BucketPeriod::Daily, None).await;
+        assert_eq!(buckets.iter().map(|b| b.migrations).sum::<u32>(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_aggregate_stats_by_tag() {
+        let tracker = LifecycleTracker::new();
+        let pool = Pubkey::new_unique();
+        let opened = |value: i64| PositionOpenedData {
+            tick_lower: -100,
+            tick_upper: 100,
+            liquidity: 1000,
+            amount_a: 1000,
+            amount_b: 1000,
+            entry_price: Decimal::ONE,
+            entry_value_usd: Decimal::from(value),
+        };
+        let fees = |usd: i64| FeesCollectedData {
+            fees_a: 0,
+            fees_b: 0,
+            fees_usd: Decimal::from(usd),
+            rewards: [0, 0, 0],
+            rewards_usd: Decimal::ZERO,
+        };
+
+        // Tags set before opening carry over into the summary
+        let core = Pubkey::new_unique();
+        tracker
+            .set_tags(core, vec!["core".to_string(), "core".to_string()])
+            .await;
+        tracker
+            .record_position_opened(core, pool, opened(1000))
+            .await;
+        tracker.record_fees_collected(core, pool, fees(10)).await;
+
+        let both = Pubkey::new_unique();
+        tracker
+            .record_position_opened(both, pool, opened(500))
+            .await;
+        tracker
+            .set_tags(both, vec!["experiment-a".to_string(), "core".to_string()])
+            .await;
+        tracker.record_fees_collected(both, pool, fees(4)).await;
+
+        let untagged = Pubkey::new_unique();
+        tracker
+            .record_position_opened(untagged, pool, opened(100))
+            .await;
+
+        assert_eq!(
+            tracker.get_summary(&both).await.unwrap().tags,
+            vec!["core".to_string(), "experiment-a".to_string()]
+        );
+        assert_eq!(tracker.get_summaries_by_tag("core").await.len(), 2);
+
+        let by_tag = tracker.get_aggregate_stats_by_tag().await;
+        assert_eq!(by_tag.len(), 2);
+        assert_eq!(by_tag["core"].total_positions, 2);
+        assert_eq!(by_tag["core"].total_fees_usd, Decimal::from(14));
+        assert_eq!(by_tag["experiment-a"].total_fees_usd, Decimal::from(4));
+        assert_eq!(tracker.get_aggregate_stats().await.total_positions, 3);
+    }
+
+    #[tokio::test]
+    async fn test_attach_execution_replaces_estimates() {
+        let tracker = LifecycleTracker::new();
+        let position = Pubkey::new_unique();
+        let pool = Pubkey::new_unique();
+        let mint_a = Pubkey::new_unique();
+        let mint_b = Pubkey::new_unique();
+
+        tracker
+            .record_position_opened(
+                position,
+                pool,
+                PositionOpenedData {
+                    tick_lower: -1000,
+                    tick_upper: 1000,
+                    liquidity: 1000000,
+                    amount_a: 1000,
+                    amount_b: 500,
+                    entry_price: Decimal::new(100, 0),
+                    entry_value_usd: Decimal::new(1000, 0),
+                },
+            )
+            .await;
+
+        let mut result = ExecutionResult::success(Signature::new_unique(), 42);
+        result.amounts = Some(ExecutedAmounts {
+            slot: 42,
+            fee_lamports: 5000,
+            token_changes: vec![
+                TokenBalanceChange {
+                    mint: mint_a,
+                    pre: 2000,
+                    post: 1010,
+                },
+                TokenBalanceChange {
+                    mint: mint_b,
+                    pre: 500,
+                    post: 20,
+                },
+            ],
+        });
+
+        assert!(
+            tracker
+                .attach_execution(&position, &result, &mint_a, &mint_b)
+                .await
+        );
+        assert!(
+            !tracker
+                .attach_execution(&Pubkey::new_unique(), &result, &mint_a, &mint_b)
+                .await
+        );
+
+        let event = tracker.get_events(&position).await.remove(0);
+        assert_eq!(event.signature, Some(result.signature));
+        assert_eq!(event.executed.as_ref().map(|e| e.fee_lamports), Some(5000));
+        match event.data {
+            EventData::PositionOpened(data) => {
+                assert_eq!(data.amount_a, 990);
+                assert_eq!(data.amount_b, 480);
+            }
+            other => panic!("Expected PositionOpened data, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_set_finality_by_signature() {
+        let tracker = LifecycleTracker::new();
+        let position = Pubkey::new_unique();
+        let pool = Pubkey::new_unique();
+        let signature = Signature::new_unique();
+
+        tracker
+            .record_fees_collected(
+                position,
+                pool,
+                FeesCollectedData {
+                    fees_a: 1,
+                    fees_b: 1,
+                    fees_usd: Decimal::ONE,
+                    rewards: [0, 0, 0],
+                    rewards_usd: Decimal::ZERO,
+                },
+            )
+            .await;
+        let result = ExecutionResult::success(signature, 1);
+        let mint = Pubkey::new_unique();
+        tracker
+            .attach_execution(&position, &result, &mint, &mint)
+            .await;
+
+        assert_eq!(tracker.set_finality(&signature, Finality::Dropped).await, 1);
+        assert_eq!(
+            tracker
+                .set_finality(&Signature::new_unique(), Finality::Finalized)
+                .await,
+            0
+        );
+        let events = tracker.get_events(&position).await;
+        assert_eq!(events[0].finality, Some(Finality::Dropped));
     }
 }
@@ -0,0 +1,355 @@
+//! Composite health scoring for monitored positions.
+
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+
+/// Seconds in a 365-day year.
+const SECONDS_PER_YEAR: f64 = 365.0 * 86_400.0;
+
+/// Configuration for position health scoring.
+///
+/// Weights do not need to sum to one; the final score is normalized by the
+/// total weight.
+#[derive(Debug, Clone)]
+pub struct HealthConfig {
+    /// Weight of the time-in-range component.
+    pub time_in_range_weight: Decimal,
+    /// Weight of the fee efficiency component.
+    pub fee_weight: Decimal,
+    /// Weight of the impermanent loss component.
+    pub il_weight: Decimal,
+    /// Weight of the range position (distance to edge) component.
+    pub range_position_weight: Decimal,
+    /// Weight of the data freshness component.
+    pub freshness_weight: Decimal,
+    /// IL at which the IL component reaches zero (as percentage).
+    pub max_il_pct: Decimal,
+    /// Data age in seconds below which data is considered fresh.
+    pub fresh_within_secs: u64,
+    /// Data age in seconds at which the freshness component reaches zero.
+    pub stale_after_secs: u64,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            time_in_range_weight: Decimal::new(30, 2),  // 30%
+            fee_weight: Decimal::new(20, 2),            // 20%
+            il_weight: Decimal::new(25, 2),             // 25%
+            range_position_weight: Decimal::new(15, 2), // 15%
+            freshness_weight: Decimal::new(10, 2),      // 10%
+            max_il_pct: Decimal::new(15, 2),            // 15%
+            fresh_within_secs: 60,
+            stale_after_secs: 600,
+        }
+    }
+}
+
+/// Raw signals used to compute a health score.
+#[derive(Debug, Clone)]
+pub struct HealthInputs {
+    /// Fraction of observations the position was in range (0-1).
+    pub time_in_range_pct: Decimal,
+    /// Annualized fee yield of the position.
+    pub fee_apr: Decimal,
+    /// Average annualized fee yield of the pool, if known.
+    pub pool_fee_apr: Option<Decimal>,
+    /// Impermanent loss percentage.
+    pub il_pct: Decimal,
+    /// Current pool tick.
+    pub tick_current: i32,
+    /// Lower tick of the position.
+    pub tick_lower: i32,
+    /// Upper tick of the position.
+    pub tick_upper: i32,
+    /// Age of the underlying data in seconds.
+    pub data_age_secs: u64,
+}
+
+/// Per-signal health components, each on a 0-100 scale.
+#[derive(Debug, Clone, Default)]
+pub struct HealthComponents {
+    /// Time-in-range component.
+    pub time_in_range: Decimal,
+    /// Fee APR relative to the pool average.
+    pub fee_efficiency: Decimal,
+    /// Impermanent loss component.
+    pub impermanent_loss: Decimal,
+    /// Distance from the current tick to the nearest range edge.
+    pub range_position: Decimal,
+    /// Data freshness component.
+    pub freshness: Decimal,
+}
+
+/// Composite health score for a position.
+#[derive(Debug, Clone)]
+pub struct HealthScore {
+    /// Overall score (0-100).
+    pub score: Decimal,
+    /// Individual components.
+    pub components: HealthComponents,
+    /// When the score was computed.
+    pub computed_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl HealthScore {
+    /// Computes a health score from raw inputs.
+    #[must_use]
+    pub fn calculate(inputs: &HealthInputs, config: &HealthConfig) -> Self {
+        let hundred = Decimal::ONE_HUNDRED;
+
+        let time_in_range = clamp_score(inputs.time_in_range_pct * hundred);
+
+        // Without a pool benchmark the fee signal is treated as neutral
+        let fee_efficiency = match inputs.pool_fee_apr {
+            Some(pool_apr) if pool_apr > Decimal::ZERO => {
+                clamp_score(inputs.fee_apr / pool_apr * hundred)
+            }
+            _ => Decimal::new(50, 0),
+        };
+
+        let impermanent_loss = if config.max_il_pct > Decimal::ZERO {
+            clamp_score((Decimal::ONE - inputs.il_pct.abs() / config.max_il_pct) * hundred)
+        } else {
+            Decimal::ZERO
+        };
+
+        let range_position =
+            range_position_score(inputs.tick_current, inputs.tick_lower, inputs.tick_upper);
+
+        let freshness = freshness_score(
+            inputs.data_age_secs,
+            config.fresh_within_secs,
+            config.stale_after_secs,
+        );
+
+        let components = HealthComponents {
+            time_in_range,
+            fee_efficiency,
+            impermanent_loss,
+            range_position,
+            freshness,
+        };
+
+        let total_weight = config.time_in_range_weight
+            + config.fee_weight
+            + config.il_weight
+            + config.range_position_weight
+            + config.freshness_weight;
+
+        let score = if total_weight > Decimal::ZERO {
+            let weighted = components.time_in_range * config.time_in_range_weight
+                + components.fee_efficiency * config.fee_weight
+                + components.impermanent_loss * config.il_weight
+                + components.range_position * config.range_position_weight
+                + components.freshness * config.freshness_weight;
+            clamp_score(weighted / total_weight).round_dp(2)
+        } else {
+            Decimal::ZERO
+        };
+
+        Self {
+            score,
+            components,
+            computed_at: chrono::Utc::now(),
+        }
+    }
+
+    /// Returns true if the score is below the given threshold.
+    #[must_use]
+    pub fn is_below(&self, threshold: Decimal) -> bool {
+        self.score < threshold
+    }
+}
+
+/// Fee growth of a pool observed at one point in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeGrowthSample {
+    /// Unix timestamp in seconds.
+    pub timestamp: i64,
+    /// Fees of token A earned per unit of liquidity (Q64.64).
+    pub fee_growth_global_a: u128,
+    /// Fees of token B earned per unit of liquidity (Q64.64).
+    pub fee_growth_global_b: u128,
+    /// Square root of the price (Q64.64).
+    pub sqrt_price: u128,
+}
+
+/// Annualized fee yield between two samples of a pool, for a position
+/// spanning `range_width` on either side of the price (0.1 = ±10%).
+///
+/// Global fee growth is what one unit of in-range liquidity earned, so
+/// dividing it by the value of one unit of liquidity over the reference range
+/// gives the yield of a position of that width that stayed in range.
+/// Returns `None` if the samples are not in order or the width is not
+/// between 0 and 1.
+#[must_use]
+pub fn pool_fee_apr(
+    start: &FeeGrowthSample,
+    end: &FeeGrowthSample,
+    range_width: Decimal,
+) -> Option<Decimal> {
+    let elapsed = end.timestamp - start.timestamp;
+    let width = range_width.to_f64()?;
+    if elapsed <= 0 || width <= 0.0 || width >= 1.0 {
+        return None;
+    }
+
+    // Fee growth wraps around on-chain
+    let q64 = 2f64.powi(64);
+    let earned_a = end
+        .fee_growth_global_a
+        .wrapping_sub(start.fee_growth_global_a) as f64
+        / q64;
+    let earned_b = end
+        .fee_growth_global_b
+        .wrapping_sub(start.fee_growth_global_b) as f64
+        / q64;
+
+    let sqrt_price = end.sqrt_price as f64 / q64;
+    let price = sqrt_price * sqrt_price;
+    if price <= 0.0 {
+        return None;
+    }
+
+    // Token amounts of one unit of liquidity, valued in token B
+    let amount_a = 1.0 / sqrt_price - 1.0 / (price * (1.0 + width)).sqrt();
+    let amount_b = sqrt_price - (price * (1.0 - width)).sqrt();
+    let value = amount_a * price + amount_b;
+
+    let earned = earned_a * price + earned_b;
+    Decimal::from_f64(earned / value * SECONDS_PER_YEAR / elapsed as f64)
+}
+
+/// Scores how centered the current tick is within the range.
+///
+/// Returns 100 at the middle of the range, falling linearly to 0 at either
+/// edge and staying at 0 when out of range.
+fn range_position_score(tick_current: i32, tick_lower: i32, tick_upper: i32) -> Decimal {
+    if tick_upper <= tick_lower || tick_current < tick_lower || tick_current >= tick_upper {
+        return Decimal::ZERO;
+    }
+
+    let half_width = Decimal::from(tick_upper - tick_lower) / Decimal::TWO;
+    let distance = (tick_current - tick_lower).min(tick_upper - tick_current);
+
+    clamp_score(Decimal::from(distance) / half_width * Decimal::ONE_HUNDRED)
+}
+
+/// Scores data freshness, decaying linearly between the fresh and stale ages.
+fn freshness_score(age_secs: u64, fresh_within_secs: u64, stale_after_secs: u64) -> Decimal {
+    if age_secs <= fresh_within_secs {
+        return Decimal::ONE_HUNDRED;
+    }
+    if age_secs >= stale_after_secs {
+        return Decimal::ZERO;
+    }
+
+    let window = Decimal::from(stale_after_secs - fresh_within_secs);
+    let remaining = Decimal::from(stale_after_secs - age_secs);
+
+    clamp_score(remaining / window * Decimal::ONE_HUNDRED)
+}
+
+/// Clamps a value to the 0-100 range.
+fn clamp_score(value: Decimal) -> Decimal {
+    value.max(Decimal::ZERO).min(Decimal::ONE_HUNDRED)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn healthy_inputs() -> HealthInputs {
+        HealthInputs {
+            time_in_range_pct: Decimal::ONE,
+            fee_apr: dec!(0.30),
+            pool_fee_apr: Some(dec!(0.20)),
+            il_pct: Decimal::ZERO,
+            tick_current: 0,
+            tick_lower: -1000,
+            tick_upper: 1000,
+            data_age_secs: 0,
+        }
+    }
+
+    #[test]
+    fn test_perfect_health() {
+        let score = HealthScore::calculate(&healthy_inputs(), &HealthConfig::default());
+        assert_eq!(score.score, dec!(100));
+    }
+
+    #[test]
+    fn test_out_of_range_lowers_score() {
+        let inputs = HealthInputs {
+            tick_current: 2000,
+            time_in_range_pct: dec!(0.5),
+            ..healthy_inputs()
+        };
+        let score = HealthScore::calculate(&inputs, &HealthConfig::default());
+
+        assert_eq!(score.components.range_position, Decimal::ZERO);
+        assert_eq!(score.components.time_in_range, dec!(50));
+        assert!(score.is_below(dec!(80)));
+    }
+
+    #[test]
+    fn test_il_component() {
+        let inputs = HealthInputs {
+            il_pct: dec!(-0.075),
+            ..healthy_inputs()
+        };
+        let score = HealthScore::calculate(&inputs, &HealthConfig::default());
+        assert_eq!(score.components.impermanent_loss, dec!(50));
+    }
+
+    #[test]
+    fn test_stale_data() {
+        let config = HealthConfig::default();
+        let inputs = HealthInputs {
+            data_age_secs: config.stale_after_secs,
+            ..healthy_inputs()
+        };
+        let score = HealthScore::calculate(&inputs, &config);
+        assert_eq!(score.components.freshness, Decimal::ZERO);
+        assert_eq!(score.score, dec!(90));
+    }
+
+    #[test]
+    fn test_pool_fee_apr_from_fee_growth() {
+        let q64 = 1u128 << 64;
+        let start = FeeGrowthSample {
+            timestamp: 0,
+            fee_growth_global_a: u128::MAX - q64 / 2,
+            fee_growth_global_b: 0,
+            sqrt_price: q64,
+        };
+        // A day of fees worth 1% of a ±10% unit of liquidity at price 1,
+        // with token A growth wrapping around
+        let value = (1.0 - 1.1f64.sqrt().recip()) + (1.0 - 0.9f64.sqrt());
+        let earned = (0.01 * value * q64 as f64) as u128;
+        let end = FeeGrowthSample {
+            timestamp: 86_400,
+            fee_growth_global_a: start.fee_growth_global_a.wrapping_add(earned / 2),
+            fee_growth_global_b: earned - earned / 2,
+            sqrt_price: q64,
+        };
+
+        let apr = pool_fee_apr(&start, &end, dec!(0.1)).unwrap();
+        assert!((apr - dec!(3.65)).abs() < dec!(0.0001), "apr = {apr}");
+
+        assert_eq!(pool_fee_apr(&end, &start, dec!(0.1)), None);
+        assert_eq!(pool_fee_apr(&start, &end, Decimal::ZERO), None);
+    }
+
+    #[test]
+    fn test_unknown_pool_apr_is_neutral() {
+        let inputs = HealthInputs {
+            pool_fee_apr: None,
+            ..healthy_inputs()
+        };
+        let score = HealthScore::calculate(&inputs, &HealthConfig::default());
+        assert_eq!(score.components.fee_efficiency, dec!(50));
+    }
+}
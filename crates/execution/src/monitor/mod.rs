@@ -4,11 +4,17 @@
 //! - Position state tracking
 //! - PnL calculation
 //! - Range status monitoring
+//! - Composite health scoring
+//! - Anomaly detection on the pools of tracked positions
 
+mod health;
 mod pnl_tracker;
+mod pool_anomaly;
 mod position_monitor;
 mod state_sync;
 
+pub use health::*;
 pub use pnl_tracker::*;
+pub use pool_anomaly::*;
 pub use position_monitor::*;
 pub use state_sync::*;
@@ -1,9 +1,12 @@
 //! PnL tracking for LP positions.
 
+use clmm_lp_domain::metrics::apy::{annualize_simple, fee_apr};
+use clmm_lp_domain::metrics::breakeven::{BreakevenBand, breakeven_band};
 use clmm_lp_domain::metrics::impermanent_loss::calculate_il_concentrated;
 use rust_decimal::Decimal;
-use rust_decimal::prelude::FromPrimitive;
+use serde::Serialize;
 use std::collections::HashMap;
+use std::ops::Sub;
 use tracing::debug;
 
 /// Entry state for a position.
@@ -44,8 +47,65 @@ pub struct PnLResult {
     pub net_pnl_pct: Decimal,
     /// Performance vs HODL.
     pub vs_hodl_usd: Decimal,
-    /// Annualized return.
+    /// Annualized net return percentage.
     pub apy: Decimal,
+    /// Annualized fee yield on the entry value.
+    pub fee_apr: Decimal,
+    /// Prices within which closing now breaks even against holding.
+    pub breakeven_band: Option<BreakevenBand>,
+}
+
+/// Net PnL split into the price move, impermanent loss and fees.
+///
+/// The components sum to the net PnL: the value change of the position is
+/// the price exposure (what holding the entry tokens would have made) less
+/// impermanent loss.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct PnLAttribution {
+    /// Gain or loss from price moves on the entry tokens, in USD.
+    pub price_exposure_usd: Decimal,
+    /// Impermanent loss in USD; zero or negative.
+    pub il_usd: Decimal,
+    /// Fees earned in USD.
+    pub fees_usd: Decimal,
+}
+
+impl PnLAttribution {
+    /// Splits the PnL of a position entered at `entry_value_usd`, now worth
+    /// `current_value_usd`, with impermanent loss `il_pct` (a fraction of
+    /// the entry value) and `fees_usd` earned.
+    #[must_use]
+    pub fn new(
+        entry_value_usd: Decimal,
+        current_value_usd: Decimal,
+        il_pct: Decimal,
+        fees_usd: Decimal,
+    ) -> Self {
+        let il_usd = -(entry_value_usd * il_pct.abs());
+        Self {
+            price_exposure_usd: current_value_usd - entry_value_usd - il_usd,
+            il_usd,
+            fees_usd,
+        }
+    }
+
+    /// Returns the net PnL in USD.
+    #[must_use]
+    pub fn net_pnl_usd(&self) -> Decimal {
+        self.price_exposure_usd + self.il_usd + self.fees_usd
+    }
+}
+
+impl Sub for PnLAttribution {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            price_exposure_usd: self.price_exposure_usd - rhs.price_exposure_usd,
+            il_usd: self.il_usd - rhs.il_usd,
+            fees_usd: self.fees_usd - rhs.fees_usd,
+        }
+    }
 }
 
 /// Tracks PnL for multiple positions.
@@ -147,14 +207,29 @@ impl PnLTracker {
         // Performance vs HODL
         let vs_hodl_usd = current_value_usd + fees_usd - hodl_value_usd;
 
-        // Calculate APY
-        let duration = chrono::Utc::now() - entry.entry_timestamp;
-        let days = duration.num_days().max(1) as f64;
-        let apy = if days > 0.0 && !entry.entry_value_usd.is_zero() {
-            let daily_return = net_pnl_pct / Decimal::from_f64(days).unwrap_or(Decimal::ONE);
-            daily_return * Decimal::from(365)
-        } else {
+        // Annualize over the exact time held, counting partial days
+        let held_secs = (chrono::Utc::now() - entry.entry_timestamp)
+            .num_seconds()
+            .max(0) as u64;
+        let apy = if entry.entry_value_usd.is_zero() {
             Decimal::ZERO
+        } else {
+            annualize_simple(net_pnl_pct, held_secs).unwrap_or(Decimal::ZERO)
+        };
+        let fee_apr = fee_apr(fees_usd, entry.entry_value_usd, held_secs).unwrap_or(Decimal::ZERO);
+
+        // Break-even band in the quote token, the unit of the pool price
+        let breakeven_band = if price_b_usd.is_zero() {
+            None
+        } else {
+            breakeven_band(
+                entry.entry_price,
+                lower_price,
+                upper_price,
+                entry.entry_value_usd / price_b_usd,
+                fees_usd / price_b_usd,
+            )
+            .ok()
         };
 
         Some(PnLResult {
@@ -167,6 +242,8 @@ impl PnLTracker {
             net_pnl_pct,
             vs_hodl_usd,
             apy,
+            fee_apr,
+            breakeven_band,
         })
     }
 
@@ -218,4 +295,22 @@ mod tests {
         assert_eq!(entry.entry_price, dec!(100));
         assert_eq!(entry.entry_value_usd, dec!(1000));
     }
+
+    #[test]
+    fn test_attribution_sums_to_net_pnl() {
+        // Entered at $1000, now $1030 after 2% IL, with $15 of fees
+        let attribution = PnLAttribution::new(dec!(1000), dec!(1030), dec!(-0.02), dec!(15));
+        assert_eq!(attribution.il_usd, dec!(-20));
+        assert_eq!(attribution.price_exposure_usd, dec!(50));
+        assert_eq!(attribution.net_pnl_usd(), dec!(45));
+
+        let later = PnLAttribution::new(dec!(1000), dec!(1010), dec!(-0.03), dec!(18));
+        let delta = later - attribution;
+        assert_eq!(delta.il_usd, dec!(-10));
+        assert_eq!(delta.fees_usd, dec!(3));
+        assert_eq!(
+            delta.net_pnl_usd(),
+            later.net_pnl_usd() - attribution.net_pnl_usd()
+        );
+    }
 }
@@ -0,0 +1,495 @@
+//! Anomaly detection on the pools of tracked positions.
+//!
+//! Samples each pool's on-chain state and flags abrupt changes that often
+//! precede or accompany a rug: active liquidity collapsing, swap volume
+//! drying up, or the fee tier being changed. Strategies pause on a flagged
+//! pool until it is explicitly resumed.
+
+use super::PositionMonitor;
+use crate::alerts::{Alert, AlertLevel, AlertType, MultiNotifier};
+use crate::shutdown::CancellationToken;
+use crate::sync::PoolStateCache;
+use chrono::{DateTime, Utc};
+use clmm_lp_protocols::prelude::WhirlpoolState;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tracing::{error, info, warn};
+
+/// 2^64, the scale of Q64.64 fee growth values.
+const Q64: f64 = 18_446_744_073_709_551_616.0;
+
+/// Configuration for the pool anomaly detector.
+#[derive(Debug, Clone)]
+pub struct AnomalyConfig {
+    /// Drop of active liquidity from its recent peak that is flagged
+    /// (0.5 = 50%).
+    pub liquidity_drop_pct: Decimal,
+    /// Window the liquidity peak is taken over in seconds.
+    pub liquidity_window_secs: u64,
+    /// Drop of swap volume from the previous window that is flagged
+    /// (0.9 = 90%).
+    pub volume_drop_pct: Decimal,
+    /// Length of each volume comparison window in seconds.
+    pub volume_window_secs: u64,
+    /// How often pools are sampled in seconds.
+    pub check_interval_secs: u64,
+}
+
+impl Default for AnomalyConfig {
+    fn default() -> Self {
+        Self {
+            liquidity_drop_pct: Decimal::new(5, 1), // 50%
+            liquidity_window_secs: 3600,
+            volume_drop_pct: Decimal::new(9, 1), // 90%
+            volume_window_secs: 3600,
+            check_interval_secs: 60,
+        }
+    }
+}
+
+/// On-chain metrics of a pool at a point in time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoolSample {
+    /// When the pool was read.
+    pub timestamp: DateTime<Utc>,
+    /// Active liquidity.
+    pub liquidity: u128,
+    /// Fee growth global of token A (Q64.64).
+    pub fee_growth_global_a: u128,
+    /// Fee growth global of token B (Q64.64).
+    pub fee_growth_global_b: u128,
+    /// Raw price of token A in token B.
+    pub price: f64,
+    /// Fee rate in basis points.
+    pub fee_rate_bps: u16,
+}
+
+impl PoolSample {
+    /// Samples a pool state read at `timestamp`.
+    #[must_use]
+    pub fn from_state(state: &WhirlpoolState, timestamp: DateTime<Utc>) -> Self {
+        Self {
+            timestamp,
+            liquidity: state.liquidity,
+            fee_growth_global_a: state.fee_growth_global_a,
+            fee_growth_global_b: state.fee_growth_global_b,
+            price: state.price.to_f64().unwrap_or(0.0),
+            fee_rate_bps: state.fee_bps().round().to_u16().unwrap_or(u16::MAX),
+        }
+    }
+
+    /// Estimates the swap volume between `self` and a later sample, in raw
+    /// token A units, from the fees earned by the active liquidity.
+    fn volume_until(&self, later: &Self) -> f64 {
+        let liquidity = self.liquidity as f64;
+        let fees_a = later
+            .fee_growth_global_a
+            .wrapping_sub(self.fee_growth_global_a) as f64
+            * liquidity
+            / Q64;
+        let fees_b = later
+            .fee_growth_global_b
+            .wrapping_sub(self.fee_growth_global_b) as f64
+            * liquidity
+            / Q64;
+        let fees = if later.price > 0.0 {
+            fees_a + fees_b / later.price
+        } else {
+            fees_a
+        };
+        fees * 10_000.0 / f64::from(later.fee_rate_bps.max(1))
+    }
+}
+
+/// An abnormal change in a pool's metrics.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PoolAnomaly {
+    /// Active liquidity fell sharply from its recent peak.
+    LiquidityCollapse {
+        /// Peak liquidity within the window.
+        peak: u128,
+        /// Current liquidity.
+        current: u128,
+        /// Drop from the peak (0.5 = 50%).
+        drop_pct: Decimal,
+    },
+    /// Swap volume fell sharply from the previous window.
+    VolumeCollapse {
+        /// Estimated volume of the previous window.
+        previous: Decimal,
+        /// Estimated volume of the latest window.
+        current: Decimal,
+        /// Drop from the previous window (0.9 = 90%).
+        drop_pct: Decimal,
+    },
+    /// The pool's fee tier changed.
+    FeeTierChange {
+        /// Previous fee rate in basis points.
+        from_bps: u16,
+        /// New fee rate in basis points.
+        to_bps: u16,
+    },
+}
+
+impl fmt::Display for PoolAnomaly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LiquidityCollapse { drop_pct, .. } => write!(
+                f,
+                "active liquidity down {:.1}% from its recent peak",
+                drop_pct * Decimal::ONE_HUNDRED
+            ),
+            Self::VolumeCollapse { drop_pct, .. } => write!(
+                f,
+                "volume down {:.1}% from the previous window",
+                drop_pct * Decimal::ONE_HUNDRED
+            ),
+            Self::FeeTierChange { from_bps, to_bps } => {
+                write!(f, "fee tier changed from {} to {} bps", from_bps, to_bps)
+            }
+        }
+    }
+}
+
+/// A pool strategies are paused on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PausedPool {
+    /// Pool address.
+    pub pool: Pubkey,
+    /// When the pool was paused.
+    pub paused_at: DateTime<Utc>,
+    /// Anomalies that paused it.
+    pub anomalies: Vec<PoolAnomaly>,
+}
+
+/// Flags abnormal pool changes and pauses strategies on the pool.
+pub struct PoolAnomalyDetector {
+    /// Configuration.
+    config: AnomalyConfig,
+    /// Recent samples per pool, oldest first.
+    samples: RwLock<HashMap<Pubkey, VecDeque<PoolSample>>>,
+    /// Pools strategies are paused on.
+    paused: RwLock<HashMap<Pubkey, PausedPool>>,
+    /// Notifier for anomaly alerts.
+    notifier: MultiNotifier,
+}
+
+impl PoolAnomalyDetector {
+    /// Creates a new pool anomaly detector.
+    #[must_use]
+    pub fn new(config: AnomalyConfig) -> Self {
+        Self {
+            config,
+            samples: RwLock::new(HashMap::new()),
+            paused: RwLock::new(HashMap::new()),
+            notifier: MultiNotifier::new(),
+        }
+    }
+
+    /// Sets the notifier for anomaly alerts.
+    #[must_use]
+    pub fn with_notifier(mut self, notifier: MultiNotifier) -> Self {
+        self.notifier = notifier;
+        self
+    }
+
+    /// Records a sample of `pool` and returns the anomalies it shows.
+    ///
+    /// A pool with anomalies is paused and alerted on; while paused it keeps
+    /// being sampled but is not alerted on again.
+    pub async fn observe(&self, pool: Pubkey, sample: PoolSample) -> Vec<PoolAnomaly> {
+        let anomalies = {
+            let mut samples = self.samples.write().await;
+            let history = samples.entry(pool).or_default();
+            let anomalies = self.detect(history, &sample);
+            history.push_back(sample);
+            let keep_secs = self
+                .config
+                .liquidity_window_secs
+                .max(self.config.volume_window_secs * 2);
+            let cutoff = sample.timestamp - chrono::Duration::seconds(keep_secs as i64);
+            // Keep one sample at or before the cutoff so a full window can
+            // be measured
+            while history.len() > 1 && history[1].timestamp <= cutoff {
+                history.pop_front();
+            }
+            anomalies
+        };
+
+        if !anomalies.is_empty() {
+            self.pause(pool, &anomalies, sample.timestamp).await;
+        }
+        anomalies
+    }
+
+    /// Returns whether strategies are paused on `pool`.
+    pub async fn is_paused(&self, pool: &Pubkey) -> bool {
+        self.paused.read().await.contains_key(pool)
+    }
+
+    /// Returns the pools strategies are paused on.
+    pub async fn paused_pools(&self) -> Vec<PausedPool> {
+        let mut paused: Vec<PausedPool> = self.paused.read().await.values().cloned().collect();
+        paused.sort_by_key(|p| p.paused_at);
+        paused
+    }
+
+    /// Resumes strategies on `pool`, returning false if it was not paused.
+    ///
+    /// The pool's samples are cleared so the change that paused it is not
+    /// flagged again.
+    pub async fn resume(&self, pool: &Pubkey) -> bool {
+        let resumed = self.paused.write().await.remove(pool).is_some();
+        if resumed {
+            self.samples.write().await.remove(pool);
+            info!(pool = %pool, "Strategies resumed on pool");
+        }
+        resumed
+    }
+
+    /// Samples the pools of the monitor's positions periodically until
+    /// `shutdown` is cancelled.
+    ///
+    /// Pools that cannot be read are skipped until the next check.
+    pub async fn run_until(
+        self: Arc<Self>,
+        monitor: Arc<PositionMonitor>,
+        pool_cache: Arc<PoolStateCache>,
+        shutdown: CancellationToken,
+    ) {
+        let mut ticker =
+            tokio::time::interval(Duration::from_secs(self.config.check_interval_secs.max(1)));
+        loop {
+            tokio::select! {
+                () = shutdown.cancelled() => break,
+                _ = ticker.tick() => {}
+            }
+            let pools: HashSet<Pubkey> = monitor
+                .get_positions()
+                .await
+                .iter()
+                .map(|position| position.pool)
+                .collect();
+            for pool in pools {
+                match pool_cache.get(&pool).await {
+                    Ok(state) => {
+                        self.observe(pool, PoolSample::from_state(&state, Utc::now()))
+                            .await;
+                    }
+                    Err(e) => warn!(pool = %pool, error = %e, "Failed to sample pool"),
+                }
+            }
+        }
+    }
+
+    /// Compares a new sample against the pool's history.
+    fn detect(&self, history: &VecDeque<PoolSample>, sample: &PoolSample) -> Vec<PoolAnomaly> {
+        let mut anomalies = Vec::new();
+        let Some(last) = history.back() else {
+            return anomalies;
+        };
+
+        if last.fee_rate_bps != sample.fee_rate_bps {
+            anomalies.push(PoolAnomaly::FeeTierChange {
+                from_bps: last.fee_rate_bps,
+                to_bps: sample.fee_rate_bps,
+            });
+        }
+
+        let liquidity_start =
+            sample.timestamp - chrono::Duration::seconds(self.config.liquidity_window_secs as i64);
+        let peak = history
+            .iter()
+            .filter(|s| s.timestamp >= liquidity_start)
+            .map(|s| s.liquidity)
+            .max()
+            .unwrap_or(0);
+        if peak > 0 {
+            let drop_pct =
+                Decimal::from(peak.saturating_sub(sample.liquidity)) / Decimal::from(peak);
+            if drop_pct > self.config.liquidity_drop_pct {
+                anomalies.push(PoolAnomaly::LiquidityCollapse {
+                    peak,
+                    current: sample.liquidity,
+                    drop_pct,
+                });
+            }
+        }
+
+        if let Some((previous, current)) = self.window_volumes(history, sample)
+            && previous > 0.0
+        {
+            let drop = (1.0 - current / previous).max(0.0);
+            let drop_pct = Decimal::from_f64(drop).unwrap_or(Decimal::ZERO).round_dp(4);
+            if drop_pct > self.config.volume_drop_pct {
+                anomalies.push(PoolAnomaly::VolumeCollapse {
+                    previous: Decimal::from_f64(previous)
+                        .unwrap_or(Decimal::ZERO)
+                        .round_dp(2),
+                    current: Decimal::from_f64(current)
+                        .unwrap_or(Decimal::ZERO)
+                        .round_dp(2),
+                    drop_pct,
+                });
+            }
+        }
+        anomalies
+    }
+
+    /// Estimates the volume of the previous and the latest window ending at
+    /// `sample`, or `None` until the history covers both windows.
+    fn window_volumes(
+        &self,
+        history: &VecDeque<PoolSample>,
+        sample: &PoolSample,
+    ) -> Option<(f64, f64)> {
+        let window = chrono::Duration::seconds(self.config.volume_window_secs as i64);
+        let current_start = sample.timestamp - window;
+        let previous_start = current_start - window;
+        if history.front()?.timestamp > previous_start {
+            return None;
+        }
+
+        let mut previous = 0.0;
+        let mut current = 0.0;
+        let later_samples = history.iter().skip(1).chain(std::iter::once(sample));
+        for (earlier, later) in history.iter().zip(later_samples) {
+            let volume = earlier.volume_until(later);
+            if later.timestamp > current_start {
+                current += volume;
+            } else if later.timestamp > previous_start {
+                previous += volume;
+            }
+        }
+        Some((previous, current))
+    }
+
+    /// Pauses strategies on `pool` and alerts, unless already paused.
+    async fn pause(&self, pool: Pubkey, anomalies: &[PoolAnomaly], at: DateTime<Utc>) {
+        {
+            let mut paused = self.paused.write().await;
+            if paused.contains_key(&pool) {
+                return;
+            }
+            paused.insert(
+                pool,
+                PausedPool {
+                    pool,
+                    paused_at: at,
+                    anomalies: anomalies.to_vec(),
+                },
+            );
+        }
+
+        let reasons: Vec<String> = anomalies.iter().map(ToString::to_string).collect();
+        error!(pool = %pool, anomalies = ?reasons, "Pool anomaly detected, pausing strategies");
+        let alert = Alert::new(
+            AlertLevel::Critical,
+            AlertType::PoolAnomaly,
+            format!("Strategies paused on pool {}: {}", pool, reasons.join("; ")),
+        );
+        self.notifier.notify_all(&alert).await;
+    }
+}
+
+impl Default for PoolAnomalyDetector {
+    fn default() -> Self {
+        Self::new(AnomalyConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(minutes: i64, liquidity: u128, fee_growth: u128) -> PoolSample {
+        PoolSample {
+            timestamp: DateTime::from_timestamp(1_700_000_000, 0).unwrap()
+                + chrono::Duration::minutes(minutes),
+            liquidity,
+            fee_growth_global_a: fee_growth,
+            fee_growth_global_b: 0,
+            price: 1.0,
+            fee_rate_bps: 30,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_liquidity_collapse_pauses_pool_until_resumed() {
+        let detector = PoolAnomalyDetector::default();
+        let pool = Pubkey::new_unique();
+
+        assert!(detector.observe(pool, sample(0, 1_000, 0)).await.is_empty());
+        assert!(detector.observe(pool, sample(10, 600, 0)).await.is_empty());
+        let anomalies = detector.observe(pool, sample(20, 400, 0)).await;
+        assert!(matches!(
+            anomalies[..],
+            [PoolAnomaly::LiquidityCollapse {
+                peak: 1_000,
+                current: 400,
+                ..
+            }]
+        ));
+        assert!(detector.is_paused(&pool).await);
+        assert_eq!(detector.paused_pools().await[0].anomalies, anomalies);
+
+        assert!(detector.resume(&pool).await);
+        assert!(!detector.is_paused(&pool).await);
+        assert!(!detector.resume(&pool).await);
+        // History was cleared with the pause
+        assert!(detector.observe(pool, sample(30, 400, 0)).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fee_tier_change_is_flagged() {
+        let detector = PoolAnomalyDetector::default();
+        let pool = Pubkey::new_unique();
+        detector.observe(pool, sample(0, 1_000, 0)).await;
+
+        let mut changed = sample(1, 1_000, 0);
+        changed.fee_rate_bps = 100;
+        let anomalies = detector.observe(pool, changed).await;
+        assert_eq!(
+            anomalies,
+            vec![PoolAnomaly::FeeTierChange {
+                from_bps: 30,
+                to_bps: 100
+            }]
+        );
+        assert!(anomalies[0].to_string().contains("30 to 100 bps"));
+    }
+
+    #[tokio::test]
+    async fn test_volume_collapse_needs_two_windows() {
+        let detector = PoolAnomalyDetector::default();
+        let pool = Pubkey::new_unique();
+        let step = 1u128 << 64;
+
+        // Steady fee growth for an hour, then almost none
+        let mut growth = 0;
+        for minutes in (0..=60).step_by(10) {
+            assert!(
+                detector
+                    .observe(pool, sample(minutes, 1_000, growth))
+                    .await
+                    .is_empty()
+            );
+            growth += step;
+        }
+        let growth = growth - step;
+        let mut anomalies = Vec::new();
+        for minutes in (70..=120).step_by(10) {
+            anomalies = detector.observe(pool, sample(minutes, 1_000, growth)).await;
+        }
+        assert!(matches!(
+            anomalies[..],
+            [PoolAnomaly::VolumeCollapse { drop_pct, .. }] if drop_pct == Decimal::ONE
+        ));
+    }
+}
@@ -1,16 +1,28 @@
 //! Position monitor for real-time tracking.
 
-use crate::alerts::{Alert, AlertRule};
+use super::{
+    FeeGrowthSample, HealthConfig, HealthInputs, HealthScore, PnLAttribution, PnLResult,
+    pool_fee_apr,
+};
+use crate::alerts::{Alert, AlertRule, validate_rules};
+use crate::hedging::DeltaHedger;
+use crate::shutdown::CancellationToken;
+use crate::strategy::PositionStrategy;
+use crate::supervisor::Heartbeat;
+use clmm_lp_domain::metrics::breakeven::BreakevenBand;
 use clmm_lp_protocols::prelude::*;
 use rust_decimal::Decimal;
 use solana_sdk::pubkey::Pubkey;
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Weak};
 use std::time::Duration;
 use tokio::sync::RwLock;
 use tokio::time::interval;
 use tracing::{debug, error, info, warn};
 
+/// Shortest span of fee growth samples a pool fee APR is estimated from.
+const MIN_POOL_FEE_SPAN_SECS: i64 = 3600;
+
 /// Configuration for position monitoring.
 #[derive(Debug, Clone)]
 pub struct MonitorConfig {
@@ -24,6 +36,15 @@ pub struct MonitorConfig {
     pub il_critical_threshold: Decimal,
     /// Range exit alert enabled.
     pub range_exit_alert: bool,
+    /// Health score below which a warning is logged.
+    pub health_warning_threshold: Decimal,
+    /// Health score configuration.
+    pub health: HealthConfig,
+    /// Seconds of pool fee growth the fee APR benchmark is measured over.
+    pub pool_fee_window_secs: u64,
+    /// Range width on either side of the price the fee APR benchmark is
+    /// measured for (0.1 = ±10%).
+    pub pool_fee_range_width: Decimal,
 }
 
 impl Default for MonitorConfig {
@@ -34,6 +55,10 @@ impl Default for MonitorConfig {
             il_warning_threshold: Decimal::new(5, 2),   // 5%
             il_critical_threshold: Decimal::new(10, 2), // 10%
             range_exit_alert: true,
+            health_warning_threshold: Decimal::new(40, 0),
+            health: HealthConfig::default(),
+            pool_fee_window_secs: 86_400,
+            pool_fee_range_width: Decimal::new(1, 1), // ±10%
         }
     }
 }
@@ -45,16 +70,62 @@ pub struct MonitoredPosition {
     pub address: Pubkey,
     /// Pool address.
     pub pool: Pubkey,
+    /// Protocol the position belongs to.
+    pub protocol: Protocol,
     /// Current on-chain state.
     pub on_chain: OnChainPosition,
     /// PnL tracker for this position.
     pub pnl: PositionPnL,
     /// Whether position is currently in range.
     pub in_range: bool,
+    /// Pool tick at the last update.
+    pub tick_current: i32,
+    /// Number of updates observed.
+    pub range_checks: u64,
+    /// Number of updates where the position was in range.
+    pub in_range_checks: u64,
+    /// Latest health score, if computed.
+    pub health: Option<HealthScore>,
+    /// Strategy assigned to this position; `None` uses the executor default.
+    pub strategy: Option<PositionStrategy>,
+    /// Free-form labels for grouping positions, sorted and unique.
+    pub tags: Vec<String>,
+    /// Externally managed: tracked and alerted on, but never acted on.
+    pub read_only: bool,
+    /// Since when the strategy executor has skipped the position because
+    /// its pool state is unavailable, if it currently does.
+    pub stale_since: Option<chrono::DateTime<chrono::Utc>>,
     /// Last update timestamp.
     pub last_updated: chrono::DateTime<chrono::Utc>,
 }
 
+impl MonitoredPosition {
+    /// Returns true if the position carries `tag`.
+    #[must_use]
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// Returns the fraction of updates where the position was in range.
+    #[must_use]
+    pub fn time_in_range_pct(&self) -> Decimal {
+        if self.range_checks == 0 {
+            return if self.in_range {
+                Decimal::ONE
+            } else {
+                Decimal::ZERO
+            };
+        }
+        Decimal::from(self.in_range_checks) / Decimal::from(self.range_checks)
+    }
+
+    /// Returns the health score value, if computed.
+    #[must_use]
+    pub fn health_score(&self) -> Option<Decimal> {
+        self.health.as_ref().map(|h| h.score)
+    }
+}
+
 /// PnL data for a position.
 #[derive(Debug, Clone, Default)]
 pub struct PositionPnL {
@@ -76,6 +147,39 @@ pub struct PositionPnL {
     pub net_pnl_pct: Decimal,
     /// Annualized return.
     pub apy: Decimal,
+    /// Annualized fee yield.
+    pub fee_apr: Decimal,
+    /// Uncollected rewards, one per reward slot.
+    pub rewards_earned: [u64; NUM_REWARDS],
+    /// Uncollected rewards in USD.
+    pub rewards_usd: Decimal,
+    /// Prices within which closing now breaks even, if known.
+    pub breakeven_band: Option<BreakevenBand>,
+}
+
+impl PositionPnL {
+    /// Updates the USD figures from a PnL calculation.
+    pub fn apply(&mut self, result: &PnLResult) {
+        self.current_value_usd = result.current_value_usd;
+        self.fees_usd = result.fees_usd;
+        self.il_pct = result.il_pct;
+        self.net_pnl_usd = result.net_pnl_usd;
+        self.net_pnl_pct = result.net_pnl_pct;
+        self.apy = result.apy;
+        self.fee_apr = result.fee_apr;
+        self.breakeven_band = result.breakeven_band;
+    }
+
+    /// Splits the net PnL into price exposure, impermanent loss and fees.
+    #[must_use]
+    pub fn attribution(&self) -> PnLAttribution {
+        PnLAttribution::new(
+            self.entry_value_usd,
+            self.current_value_usd,
+            self.il_pct,
+            self.fees_usd,
+        )
+    }
 }
 
 /// Position monitor for tracking multiple positions.
@@ -83,57 +187,132 @@ pub struct PositionMonitor {
     /// RPC provider.
     #[allow(dead_code)]
     provider: Arc<RpcProvider>,
-    /// Whirlpool reader.
-    pool_reader: WhirlpoolReader,
-    /// Position reader.
+    /// Liquidity math for token amounts, valid for every CLMM protocol.
     position_reader: PositionReader,
+    /// State readers, one per supported protocol.
+    adapters: Vec<Arc<dyn ProtocolAdapter>>,
     /// Monitored positions.
     positions: Arc<RwLock<HashMap<Pubkey, MonitoredPosition>>>,
     /// Configuration.
     config: MonitorConfig,
-    /// Alert rules.
-    alert_rules: Vec<AlertRule>,
+    /// Alert rules, replaceable at runtime.
+    alert_rules: RwLock<Vec<AlertRule>>,
     /// Alert callback.
     #[allow(dead_code)]
     alert_callback: Option<Box<dyn Fn(Alert) + Send + Sync>>,
+    /// Average fee APR per pool, used as the health benchmark.
+    pool_fee_aprs: Arc<RwLock<HashMap<Pubkey, Decimal>>>,
+    /// Recent fee growth samples per pool the benchmark is estimated from.
+    fee_growth: Arc<RwLock<HashMap<Pubkey, VecDeque<FeeGrowthSample>>>>,
+    /// USD value of one raw unit of each reward mint.
+    reward_prices: Arc<RwLock<HashMap<Pubkey, Decimal>>>,
+    /// Beaten after every update cycle, if watched.
+    heartbeat: Option<Heartbeat>,
+    /// Hedger whose PnL is included in portfolio metrics; weak because the
+    /// hedger holds the monitor.
+    hedger: RwLock<Option<Weak<DeltaHedger>>>,
+    /// Positions marked read-only, applied to them when they are added.
+    read_only: RwLock<HashSet<Pubkey>>,
 }
 
 impl PositionMonitor {
     /// Creates a new position monitor.
     pub fn new(provider: Arc<RpcProvider>, config: MonitorConfig) -> Self {
-        let pool_reader = WhirlpoolReader::new(provider.clone());
         let position_reader = PositionReader::new(provider.clone());
+        let adapters: Vec<Arc<dyn ProtocolAdapter>> = vec![
+            Arc::new(OrcaAdapter::new(provider.clone())),
+            Arc::new(RaydiumAdapter::new(provider.clone())),
+        ];
 
         Self {
             provider,
-            pool_reader,
             position_reader,
+            adapters,
             positions: Arc::new(RwLock::new(HashMap::new())),
             config,
-            alert_rules: Vec::new(),
+            alert_rules: RwLock::new(Vec::new()),
             alert_callback: None,
+            pool_fee_aprs: Arc::new(RwLock::new(HashMap::new())),
+            fee_growth: Arc::new(RwLock::new(HashMap::new())),
+            reward_prices: Arc::new(RwLock::new(HashMap::new())),
+            heartbeat: None,
+            hedger: RwLock::new(None),
+            read_only: RwLock::new(HashSet::new()),
         }
     }
 
-    /// Adds a position to monitor.
+    /// Adds a protocol adapter, replacing any for the same protocol.
+    #[must_use]
+    pub fn with_adapter(mut self, adapter: Arc<dyn ProtocolAdapter>) -> Self {
+        self.adapters.retain(|a| a.protocol() != adapter.protocol());
+        self.adapters.push(adapter);
+        self
+    }
+
+    /// Adds an Orca Whirlpool position to monitor.
     pub async fn add_position(&self, position_address: &str) -> anyhow::Result<()> {
-        let position = self.position_reader.get_position(position_address).await?;
+        self.add_protocol_position(position_address, Protocol::OrcaWhirlpool)
+            .await
+    }
+
+    /// Adds a Raydium CLMM position to monitor.
+    pub async fn add_raydium_position(&self, position_address: &str) -> anyhow::Result<()> {
+        self.add_protocol_position(position_address, Protocol::RaydiumClmm)
+            .await
+    }
 
+    /// Adds a position of any supported protocol to monitor.
+    pub async fn add_protocol_position(
+        &self,
+        position_address: &str,
+        protocol: Protocol,
+    ) -> anyhow::Result<()> {
+        let position = self
+            .adapter(protocol)?
+            .get_position(position_address)
+            .await?;
+        self.insert_position(position, protocol).await;
+
+        info!(
+            position = position_address,
+            protocol = ?protocol,
+            "Added position to monitor"
+        );
+
+        Ok(())
+    }
+
+    /// Gets the adapter reading `protocol`.
+    fn adapter(&self, protocol: Protocol) -> anyhow::Result<&Arc<dyn ProtocolAdapter>> {
+        self.adapters
+            .iter()
+            .find(|a| a.protocol() == protocol)
+            .ok_or_else(|| anyhow::anyhow!("No adapter for protocol {:?}", protocol))
+    }
+
+    /// Inserts a freshly read position into the monitored set.
+    async fn insert_position(&self, position: OnChainPosition, protocol: Protocol) {
+        let read_only = self.read_only.read().await.contains(&position.address);
         let monitored = MonitoredPosition {
             address: position.address,
             pool: position.pool,
+            protocol,
             on_chain: position.clone(),
             pnl: PositionPnL::default(),
             in_range: true,
+            tick_current: 0,
+            range_checks: 0,
+            in_range_checks: 0,
+            health: None,
+            strategy: None,
+            tags: Vec::new(),
+            read_only,
+            stale_since: None,
             last_updated: chrono::Utc::now(),
         };
 
         let mut positions = self.positions.write().await;
         positions.insert(position.address, monitored);
-
-        info!(position = position_address, "Added position to monitor");
-
-        Ok(())
     }
 
     /// Removes a position from monitoring.
@@ -159,6 +338,115 @@ impl PositionMonitor {
         positions.get(address).cloned()
     }
 
+    /// Assigns a strategy to a position, or clears it with `None`.
+    ///
+    /// Returns false if the position is not monitored.
+    pub async fn set_position_strategy(
+        &self,
+        address: &Pubkey,
+        strategy: Option<PositionStrategy>,
+    ) -> bool {
+        let mut positions = self.positions.write().await;
+        let Some(monitored) = positions.get_mut(address) else {
+            return false;
+        };
+
+        info!(
+            position = %address,
+            strategy = ?strategy.as_ref().map(|s| &s.name),
+            "Updated position strategy"
+        );
+        monitored.strategy = strategy;
+        true
+    }
+
+    /// Replaces the tags on a position.
+    ///
+    /// Returns false if the position is not monitored.
+    pub async fn set_position_tags(&self, address: &Pubkey, mut tags: Vec<String>) -> bool {
+        let mut positions = self.positions.write().await;
+        let Some(monitored) = positions.get_mut(address) else {
+            return false;
+        };
+
+        tags.sort();
+        tags.dedup();
+        info!(position = %address, tags = ?tags, "Updated position tags");
+        monitored.tags = tags;
+        true
+    }
+
+    /// Marks a position as read-only or managed.
+    ///
+    /// Read-only positions keep being updated and alerted on, but get no
+    /// strategy decisions and are skipped by emergency exits.
+    ///
+    /// Returns false if the position is not monitored.
+    pub async fn set_position_read_only(&self, address: &Pubkey, read_only: bool) -> bool {
+        let mut positions = self.positions.write().await;
+        let Some(monitored) = positions.get_mut(address) else {
+            return false;
+        };
+
+        info!(position = %address, read_only, "Updated position management mode");
+        monitored.read_only = read_only;
+        let mut marked = self.read_only.write().await;
+        if read_only {
+            marked.insert(*address);
+        } else {
+            marked.remove(address);
+        }
+        true
+    }
+
+    /// Restores read-only marks saved before a restart; they apply to the
+    /// positions already monitored and to ones added later.
+    pub async fn load_read_only(&self, addresses: impl IntoIterator<Item = Pubkey>) {
+        let mut positions = self.positions.write().await;
+        let mut marked = self.read_only.write().await;
+        marked.extend(addresses);
+        for (address, monitored) in positions.iter_mut() {
+            monitored.read_only |= marked.contains(address);
+        }
+        info!(positions = marked.len(), "Loaded read-only positions");
+    }
+
+    /// Marks a position stale because its pool state is unavailable,
+    /// keeping the time of an earlier mark.
+    ///
+    /// Returns true if the position was not stale before.
+    pub async fn mark_position_stale(&self, address: &Pubkey) -> bool {
+        let mut positions = self.positions.write().await;
+        let Some(monitored) = positions.get_mut(address) else {
+            return false;
+        };
+
+        let newly_stale = monitored.stale_since.is_none();
+        monitored.stale_since.get_or_insert_with(chrono::Utc::now);
+        newly_stale
+    }
+
+    /// Clears the stale mark of a position.
+    ///
+    /// Returns when the position became stale, if it was.
+    pub async fn clear_position_stale(
+        &self,
+        address: &Pubkey,
+    ) -> Option<chrono::DateTime<chrono::Utc>> {
+        let mut positions = self.positions.write().await;
+        positions.get_mut(address)?.stale_since.take()
+    }
+
+    /// Gets the monitored positions carrying `tag`.
+    pub async fn get_positions_by_tag(&self, tag: &str) -> Vec<MonitoredPosition> {
+        let positions = self.positions.read().await;
+        positions
+            .values()
+            .filter(|p| p.has_tag(tag))
+            .cloned()
+            .collect()
+    }
+
     /// Updates all monitored positions.
     pub async fn update_all(&self) -> anyhow::Result<()> {
         let position_addresses: Vec<Pubkey> = {
@@ -176,28 +464,89 @@ impl PositionMonitor {
             }
         }
 
+        // Recompute health for every position so stale data is reflected
+        self.refresh_health().await;
+
         Ok(())
     }
 
+    /// Updates the positions stored at, or trading in a pool at, any of the
+    /// given addresses. Returns the number of positions updated.
+    pub async fn refresh_positions(&self, affected: &[Pubkey]) -> usize {
+        let addresses: Vec<Pubkey> = self
+            .positions
+            .read()
+            .await
+            .iter()
+            .filter(|(address, p)| {
+                affected.contains(address) || affected.contains(&p.on_chain.pool)
+            })
+            .map(|(address, _)| *address)
+            .collect();
+        if addresses.is_empty() {
+            return 0;
+        }
+
+        for address in &addresses {
+            if let Err(e) = self.update_position(address).await {
+                error!(
+                    position = %address,
+                    error = %e,
+                    "Failed to refresh position"
+                );
+            }
+        }
+        self.refresh_health().await;
+
+        addresses.len()
+    }
+
+    /// Reads the latest position and pool snapshot for the position's protocol.
+    async fn read_position(
+        &self,
+        address: &Pubkey,
+        protocol: Protocol,
+    ) -> anyhow::Result<(OnChainPosition, PoolSnapshot)> {
+        let adapter = self.adapter(protocol)?;
+        let position = adapter.get_position(&address.to_string()).await?;
+        let pool = adapter.get_pool(&position.pool.to_string()).await?;
+        Ok((position, pool))
+    }
+
     /// Updates a single position.
     async fn update_position(&self, address: &Pubkey) -> anyhow::Result<()> {
-        let position = self
-            .position_reader
-            .get_position(&address.to_string())
-            .await?;
-        let pool_state = self
-            .pool_reader
-            .get_pool_state(&position.pool.to_string())
-            .await?;
+        let protocol = {
+            let positions = self.positions.read().await;
+            positions
+                .get(address)
+                .map_or(Protocol::OrcaWhirlpool, |p| p.protocol)
+        };
+        let (position, pool_state) = self.read_position(address, protocol).await?;
+
+        self.record_fee_growth(
+            position.pool,
+            FeeGrowthSample {
+                timestamp: chrono::Utc::now().timestamp(),
+                fee_growth_global_a: pool_state.fee_growth_global_a,
+                fee_growth_global_b: pool_state.fee_growth_global_b,
+                sqrt_price: pool_state.sqrt_price,
+            },
+        )
+        .await;
 
         // Check if in range
-        let in_range = pool_state.is_tick_in_range(position.tick_lower, position.tick_upper);
+        let in_range = position.tick_lower <= pool_state.tick_current
+            && pool_state.tick_current < position.tick_upper;
 
         // Calculate token amounts
-        let (amount_a, amount_b) = self.position_reader.calculate_token_amounts(
-            &position,
-            pool_state.tick_current,
-            pool_state.sqrt_price,
+        let (amount_a, amount_b) = self
+            .position_reader
+            .calculate_token_amounts(&position, pool_state.sqrt_price)?;
+
+        let rewards_usd = rewards_value(
+            &position.rewards_owed,
+            &pool_state.rewards,
+            &*self.reward_prices.read().await,
         );
 
         // Update position state
@@ -207,17 +556,25 @@ impl PositionMonitor {
 
             monitored.on_chain = position.clone();
             monitored.in_range = in_range;
+            monitored.tick_current = pool_state.tick_current;
+            monitored.range_checks += 1;
+            if in_range {
+                monitored.in_range_checks += 1;
+            }
             monitored.last_updated = chrono::Utc::now();
 
             // Update PnL
             monitored.pnl.fees_earned_a = position.fees_owed_a;
             monitored.pnl.fees_earned_b = position.fees_owed_b;
+            monitored.pnl.rewards_earned = position.rewards_owed;
+            monitored.pnl.rewards_usd = rewards_usd;
 
             debug!(
                 position = %address,
                 in_range = in_range,
                 amount_a = amount_a,
                 amount_b = amount_b,
+                breakeven_band = ?monitored.pnl.breakeven_band.map(|b| b.to_string()),
                 "Updated position state"
             );
 
@@ -234,8 +591,109 @@ impl PositionMonitor {
         Ok(())
     }
 
+    /// Adds a fee growth sample of a pool and re-estimates its fee APR
+    /// benchmark once the samples span long enough.
+    async fn record_fee_growth(&self, pool: Pubkey, sample: FeeGrowthSample) {
+        let fee_apr = {
+            let mut fee_growth = self.fee_growth.write().await;
+            let samples = fee_growth.entry(pool).or_default();
+            // Positions sharing a pool read it in the same cycle
+            if samples
+                .back()
+                .is_some_and(|last| last.timestamp == sample.timestamp)
+            {
+                return;
+            }
+            samples.push_back(sample);
+
+            let window = i64::try_from(self.config.pool_fee_window_secs).unwrap_or(i64::MAX);
+            while samples.len() > 2
+                && samples
+                    .get(1)
+                    .is_some_and(|next| sample.timestamp - next.timestamp >= window)
+            {
+                samples.pop_front();
+            }
+
+            match samples.front() {
+                Some(first) if sample.timestamp - first.timestamp >= MIN_POOL_FEE_SPAN_SECS => {
+                    pool_fee_apr(first, &sample, self.config.pool_fee_range_width)
+                }
+                _ => None,
+            }
+        };
+
+        if let Some(fee_apr) = fee_apr {
+            debug!(pool = %pool, fee_apr = %fee_apr, "Estimated pool fee APR");
+            self.pool_fee_aprs.write().await.insert(pool, fee_apr);
+        }
+    }
+
+    /// Recomputes the health score of all monitored positions.
+    pub async fn refresh_health(&self) {
+        let pool_fee_aprs = self.pool_fee_aprs.read().await.clone();
+        let now = chrono::Utc::now();
+
+        let mut positions = self.positions.write().await;
+        for monitored in positions.values_mut() {
+            let inputs = HealthInputs {
+                time_in_range_pct: monitored.time_in_range_pct(),
+                fee_apr: monitored.pnl.fee_apr,
+                pool_fee_apr: pool_fee_aprs.get(&monitored.pool).copied(),
+                il_pct: monitored.pnl.il_pct,
+                tick_current: monitored.tick_current,
+                tick_lower: monitored.on_chain.tick_lower,
+                tick_upper: monitored.on_chain.tick_upper,
+                data_age_secs: (now - monitored.last_updated).num_seconds().max(0) as u64,
+            };
+            let health = HealthScore::calculate(&inputs, &self.config.health);
+
+            if health.is_below(self.config.health_warning_threshold) {
+                warn!(
+                    position = %monitored.address,
+                    score = %health.score,
+                    "Position health degraded"
+                );
+            }
+
+            monitored.health = Some(health);
+        }
+    }
+
+    /// Gets the health score of a position.
+    pub async fn get_position_health(&self, address: &Pubkey) -> Option<HealthScore> {
+        let positions = self.positions.read().await;
+        positions.get(address).and_then(|p| p.health.clone())
+    }
+
+    /// Sets the average fee APR of a pool used as the health benchmark.
+    ///
+    /// The monitor estimates the benchmark from the pool's fee growth; a set
+    /// value holds until the next estimate replaces it.
+    pub async fn set_pool_fee_apr(&self, pool: Pubkey, fee_apr: Decimal) {
+        self.pool_fee_aprs.write().await.insert(pool, fee_apr);
+    }
+
+    /// Sets the USD price of a reward token used to value uncollected rewards.
+    pub async fn set_reward_price(&self, mint: Pubkey, price_usd: Decimal, decimals: u32) {
+        let unit_price = price_usd / Decimal::from(10u64.pow(decimals.min(18)));
+        self.reward_prices.write().await.insert(mint, unit_price);
+    }
+
+    /// Includes the PnL of a delta hedger in portfolio metrics.
+    pub async fn set_hedger(&self, hedger: &Arc<DeltaHedger>) {
+        *self.hedger.write().await = Some(Arc::downgrade(hedger));
+    }
+
     /// Starts the monitoring loop.
     pub async fn start(&self) {
+        self.run_until(CancellationToken::new()).await;
+    }
+
+    /// Runs the monitoring loop until `shutdown` is cancelled.
+    ///
+    /// An update in progress completes before the loop exits.
+    pub async fn run_until(&self, shutdown: CancellationToken) {
         let poll_interval = Duration::from_secs(self.config.poll_interval_secs);
         let mut ticker = interval(poll_interval);
 
@@ -245,17 +703,49 @@ impl PositionMonitor {
         );
 
         loop {
-            ticker.tick().await;
+            tokio::select! {
+                () = shutdown.cancelled() => break,
+                _ = ticker.tick() => {}
+            }
 
             if let Err(e) = self.update_all().await {
                 error!(error = %e, "Monitor update failed");
             }
+            if let Some(heartbeat) = &self.heartbeat {
+                heartbeat.beat();
+            }
         }
+
+        if let Some(heartbeat) = &self.heartbeat {
+            heartbeat.pause();
+        }
+        info!("Position monitor stopped");
+    }
+
+    /// Beats `heartbeat` after every update cycle, so a hung loop is
+    /// reported.
+    pub fn set_heartbeat(&mut self, heartbeat: Heartbeat) {
+        self.heartbeat = Some(heartbeat);
     }
 
     /// Adds an alert rule.
     pub fn add_alert_rule(&mut self, rule: AlertRule) {
-        self.alert_rules.push(rule);
+        self.alert_rules.get_mut().push(rule);
+    }
+
+    /// Replaces the alert rules while the monitor is running.
+    ///
+    /// # Errors
+    /// Returns an error, leaving the rules unchanged, if they are invalid.
+    pub async fn set_alert_rules(&self, rules: Vec<AlertRule>) -> anyhow::Result<()> {
+        validate_rules(&rules)?;
+        *self.alert_rules.write().await = rules;
+        Ok(())
+    }
+
+    /// Gets the alert rules.
+    pub async fn alert_rules(&self) -> Vec<AlertRule> {
+        self.alert_rules.read().await.clone()
     }
 
     /// Sets the alert callback.
@@ -276,6 +766,7 @@ impl PositionMonitor {
             metrics.total_positions += 1;
             metrics.total_value_usd += pos.pnl.current_value_usd;
             metrics.total_fees_usd += pos.pnl.fees_usd;
+            metrics.total_rewards_usd += pos.pnl.rewards_usd;
             metrics.total_pnl_usd += pos.pnl.net_pnl_usd;
 
             if pos.in_range {
@@ -287,6 +778,12 @@ impl PositionMonitor {
             metrics.avg_il_pct = positions.values().map(|p| p.pnl.il_pct).sum::<Decimal>()
                 / Decimal::from(metrics.total_positions);
         }
+        drop(positions);
+
+        let hedger = self.hedger.read().await.as_ref().and_then(Weak::upgrade);
+        if let Some(hedger) = hedger {
+            hedger.merge_into(&mut metrics).await;
+        }
 
         metrics
     }
@@ -303,8 +800,204 @@ pub struct PortfolioMetrics {
     pub total_value_usd: Decimal,
     /// Total fees earned in USD.
     pub total_fees_usd: Decimal,
-    /// Total PnL in USD.
+    /// Total uncollected rewards in USD.
+    pub total_rewards_usd: Decimal,
+    /// Total PnL in USD, including hedge PnL once merged.
     pub total_pnl_usd: Decimal,
+    /// PnL of perp hedges in USD.
+    pub hedge_pnl_usd: Decimal,
     /// Average IL percentage.
     pub avg_il_pct: Decimal,
 }
+
+/// Values uncollected rewards using per-unit reward token prices.
+///
+/// Rewards whose mint has no known price are ignored.
+fn rewards_value(
+    rewards_owed: &[u64; NUM_REWARDS],
+    rewards: &[RewardEmission],
+    unit_prices: &HashMap<Pubkey, Decimal>,
+) -> Decimal {
+    rewards
+        .iter()
+        .filter_map(|reward| {
+            let owed = rewards_owed.get(reward.index)?;
+            let price = unit_prices.get(&reward.mint)?;
+            Some(Decimal::from(*owed) * price)
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use rust_decimal_macros::dec;
+
+    fn on_chain_position(address: Pubkey, pool: Pubkey) -> OnChainPosition {
+        OnChainPosition {
+            address,
+            pool,
+            owner: Pubkey::new_unique(),
+            tick_lower: -100,
+            tick_upper: 100,
+            liquidity: 1_000,
+            fee_growth_inside_a: 0,
+            fee_growth_inside_b: 0,
+            fees_owed_a: 0,
+            fees_owed_b: 0,
+            reward_growth_inside: [0; NUM_REWARDS],
+            rewards_owed: [0; NUM_REWARDS],
+        }
+    }
+
+    /// Serves one position in a pool whose tick is fixed.
+    struct FixedAdapter {
+        position: Pubkey,
+        pool: Pubkey,
+        tick_current: i32,
+    }
+
+    #[async_trait]
+    impl ProtocolAdapter for FixedAdapter {
+        fn protocol(&self) -> Protocol {
+            Protocol::MeteoraDlmm
+        }
+
+        async fn get_position(&self, _position_address: &str) -> anyhow::Result<OnChainPosition> {
+            Ok(on_chain_position(self.position, self.pool))
+        }
+
+        async fn get_pool(&self, pool_address: &str) -> anyhow::Result<PoolSnapshot> {
+            Ok(PoolSnapshot {
+                address: pool_address.to_string(),
+                token_mint_a: Pubkey::new_unique(),
+                token_mint_b: Pubkey::new_unique(),
+                tick_current: self.tick_current,
+                tick_spacing: 1,
+                sqrt_price: 1 << 64,
+                price: Decimal::ONE,
+                liquidity: 1_000,
+                fee_growth_global_a: 0,
+                fee_growth_global_b: 0,
+                rewards: Vec::new(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_rewards_value_skips_unpriced_mints() {
+        let priced = RewardEmission {
+            index: 0,
+            mint: Pubkey::new_unique(),
+            vault: Pubkey::new_unique(),
+            emissions_per_second: Decimal::ONE,
+            growth_global_x64: 0,
+        };
+        let unpriced = RewardEmission {
+            index: 2,
+            mint: Pubkey::new_unique(),
+            ..priced.clone()
+        };
+        let prices = HashMap::from([(priced.mint, dec!(0.000002))]);
+
+        let value = rewards_value(&[5_000_000, 0, 7_000_000], &[priced, unpriced], &prices);
+        assert_eq!(value, dec!(10));
+    }
+
+    #[tokio::test]
+    async fn test_set_position_read_only() {
+        let provider = Arc::new(RpcProvider::new(RpcConfig::default()));
+        let monitor = PositionMonitor::new(provider, MonitorConfig::default());
+        let address = Pubkey::new_unique();
+        monitor
+            .insert_position(
+                on_chain_position(address, Pubkey::new_unique()),
+                Protocol::OrcaWhirlpool,
+            )
+            .await;
+        assert!(!monitor.get_position(&address).await.unwrap().read_only);
+
+        assert!(monitor.set_position_read_only(&address, true).await);
+        assert!(monitor.get_position(&address).await.unwrap().read_only);
+        assert!(
+            !monitor
+                .set_position_read_only(&Pubkey::new_unique(), true)
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_loaded_read_only_marks_apply_to_positions() {
+        let provider = Arc::new(RpcProvider::new(RpcConfig::default()));
+        let monitor = PositionMonitor::new(provider, MonitorConfig::default());
+        let (monitored, added, managed) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        for address in [monitored, managed] {
+            monitor
+                .insert_position(
+                    on_chain_position(address, Pubkey::new_unique()),
+                    Protocol::OrcaWhirlpool,
+                )
+                .await;
+        }
+
+        monitor.load_read_only([monitored, added]).await;
+        assert!(monitor.get_position(&monitored).await.unwrap().read_only);
+        assert!(!monitor.get_position(&managed).await.unwrap().read_only);
+
+        // Positions added after the marks were loaded get them too
+        monitor
+            .insert_position(
+                on_chain_position(added, Pubkey::new_unique()),
+                Protocol::OrcaWhirlpool,
+            )
+            .await;
+        assert!(monitor.get_position(&added).await.unwrap().read_only);
+
+        // Clearing the mark survives the position being re-added
+        assert!(monitor.set_position_read_only(&added, false).await);
+        monitor.remove_position(&added).await;
+        monitor
+            .insert_position(
+                on_chain_position(added, Pubkey::new_unique()),
+                Protocol::OrcaWhirlpool,
+            )
+            .await;
+        assert!(!monitor.get_position(&added).await.unwrap().read_only);
+    }
+
+    #[tokio::test]
+    async fn test_routes_through_protocol_adapter() {
+        let provider = Arc::new(RpcProvider::new(RpcConfig::default()));
+        let address = Pubkey::new_unique();
+        let monitor = PositionMonitor::new(provider.clone(), MonitorConfig::default());
+        assert!(
+            monitor
+                .add_protocol_position(&address.to_string(), Protocol::MeteoraDlmm)
+                .await
+                .is_err()
+        );
+
+        let monitor = PositionMonitor::new(provider, MonitorConfig::default()).with_adapter(
+            Arc::new(FixedAdapter {
+                position: address,
+                pool: Pubkey::new_unique(),
+                tick_current: 500,
+            }),
+        );
+        monitor
+            .add_protocol_position(&address.to_string(), Protocol::MeteoraDlmm)
+            .await
+            .unwrap();
+        monitor.update_position(&address).await.unwrap();
+
+        let position = monitor.get_position(&address).await.unwrap();
+        assert_eq!(position.protocol, Protocol::MeteoraDlmm);
+        assert_eq!(position.tick_current, 500);
+        assert!(!position.in_range);
+    }
+}
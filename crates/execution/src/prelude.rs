@@ -8,50 +8,112 @@
 //! use clmm_lp_execution::prelude::*;
 //! ```
 
+// Accounting
+pub use crate::accounting::{
+    AccountNames, JournalBuilder, JournalEntry, JournalFormat, JournalPosting, journal_to_text,
+};
+
+// Advisor
+pub use crate::advisor::{
+    AdvisorConfig, OptimizerRecommender, PositionAdvice, PriceBounds, RangeAdvisor,
+    RangeRecommender, digest_alert, review_positions,
+};
+
 // Alerts
+#[cfg(feature = "desktop-notifications")]
+pub use crate::alerts::DesktopNotifier;
 pub use crate::alerts::{
     Alert, AlertData, AlertLevel, AlertRule, AlertType, ConsoleNotifier, FileNotifier,
-    MultiNotifier, Notifier, RuleCondition, RuleContext, RulesEngine, WebhookNotifier,
+    MultiNotifier, Notifier, PipeNotifier, RuleCondition, RuleContext, RulesEngine,
+    TelegramNotifier, WebhookNotifier,
 };
 
 // Emergency
 pub use crate::emergency::{
-    CircuitBreaker, CircuitBreakerConfig, CircuitBreakerStats, CircuitState, EmergencyExitConfig,
-    EmergencyExitManager, ExitResult, ExitStatus,
+    CircuitBreaker, CircuitBreakerConfig, CircuitBreakerStats, CircuitState, DrawdownConfig,
+    DrawdownGuard, EmergencyExitConfig, EmergencyExitManager, ExitResult, ExitStatus,
+    ValueSnapshot,
+};
+
+// Hedging
+pub use crate::hedging::{
+    DeltaHedger, HedgeConfig, HedgePnL, PaperPerpVenue, PerpFill, PerpVenue, hedge_adjustment,
+    position_delta,
 };
 
 // Lifecycle
 pub use crate::lifecycle::{
-    AggregateStats, CloseReason, EventData, FeesCollectedData, LifecycleEvent, LifecycleEventType,
-    LifecycleTracker, LiquidityChangeData, PositionClosedData, PositionOpenedData, PositionSummary,
-    RebalanceData, RebalanceReason,
+    AggregateStats, BucketPeriod, CallbackConfig, CallbackPayload, CallbackStage, CloseReason,
+    EventData, FeesCollectedData, Finality, LifecycleCallbacks, LifecycleEvent, LifecycleEventType,
+    LifecycleTracker, LiquidityChangeData, MigrationData, PositionClosedData, PositionOpenedData,
+    PositionSummary, RebalanceData, RebalanceReason, StatsBucket,
 };
 
 // Monitor
 pub use crate::monitor::{
-    MonitorConfig, MonitoredPosition, PnLResult, PnLTracker, PortfolioMetrics, PositionEntry,
-    PositionMonitor, PositionPnL, ReconcileResult, StateSynchronizer, SyncState,
+    AnomalyConfig, FeeGrowthSample, HealthComponents, HealthConfig, HealthInputs, HealthScore,
+    MonitorConfig, MonitoredPosition, PausedPool, PnLAttribution, PnLResult, PnLTracker,
+    PoolAnomaly, PoolAnomalyDetector, PoolSample, PortfolioMetrics, PositionEntry, PositionMonitor,
+    PositionPnL, ReconcileResult, StateSynchronizer, SyncState, pool_fee_apr,
+};
+
+// Reload
+pub use crate::reload::{
+    ConfigAudit, ConfigChange, ConfigReloader, ConfigSection, ConfigSource, RuntimeConfig,
 };
 
+// Report
+pub use crate::report::{AlertCounts, DailyReporter, DailySummary, PositionRange, ReportConfig};
+
 // Scheduler
-pub use crate::scheduler::{Schedule, ScheduleBuilder, ScheduledTask, Scheduler, TaskEvent};
+pub use crate::scheduler::{
+    Schedule, ScheduleBuilder, ScheduleDefinition, ScheduledTask, Scheduler, TaskEvent,
+};
+
+// Shutdown
+pub use crate::shutdown::{
+    InFlightGuard, InFlightTracker, ShutdownConfig, ShutdownController, ShutdownReport,
+};
 
 // Strategy
 pub use crate::strategy::{
-    Decision, DecisionConfig, DecisionContext, DecisionEngine, ExecutorConfig, ProfitabilityCheck,
-    RebalanceConfig, RebalanceExecutor, RebalanceParams, RebalanceResult, StrategyExecutor,
+    ActionPriority, ActionQueue, Decision, DecisionConfig, DecisionContext, DecisionEngine,
+    DecisionKind, DecisionOutcome, DecisionOverrides, DecisionRecord, DeferredAction,
+    ExecutorConfig, InFlightOperation, InFlightRegistry, LiquidityScalingConfig, MarketRegime,
+    MigrationCheck, MigrationConfig, MigrationExecutor, MigrationParams, MigrationResult,
+    PositionStrategy, ProfitabilityCheck, QueuedAction, RatioSwap, RebalanceConfig,
+    RebalanceExecutor, RebalanceParams, RebalanceResult, ReferencePrice, RegimeSignal,
+    StrategyExecutor, check_bundle_simulation, price_divergence, ratio_swap,
+};
+
+// Supervisor
+pub use crate::supervisor::{
+    Heartbeat, HeartbeatConfig, HeartbeatMonitor, HeartbeatStatus, RestartPolicy, Supervisor,
+    SupervisorConfig, TaskHealth,
 };
 
 // Sync
 pub use crate::sync::{
-    AccountListener, AccountListenerConfig, AccountState, AccountUpdate, ReconcileStatus,
-    Reconciler, ReconcilerConfig, Subscription, SubscriptionType,
+    AccountListener, AccountListenerConfig, AccountState, AccountUpdate, CandleBuilderConfig,
+    LiveCandle, LiveCandleBuilder, PoolStateCache, ReconcileStatus, Reconciler, ReconcilerConfig,
+    SlotOrderStats, SlotOrderer, StateSource, Subscription, SubscriptionType, UpdateOrder,
+};
+#[cfg(feature = "geyser")]
+pub use crate::sync::{GeyserConfig, GeyserSource};
+
+// Tax
+pub use crate::tax::{
+    HistoricalPrices, PoolAssets, PriceOracle, TaxAsset, TaxCsvFormat, TaxRecord, TaxRecordKind,
+    TaxReportBuilder, tax_records_to_csv,
 };
 
 // Transaction
 pub use crate::transaction::{
+    ConfirmationHook, DurableNonce, NonceManager, PriorityFeeConfig, PriorityFeeEstimator,
     PriorityLevel, SimulationResult, TransactionBuilder, TransactionConfig, TransactionManager,
-    TransactionResult, TransactionStatus,
+    TransactionResult, TransactionStatus, advance_nonce_instruction, assess_finality,
+    create_nonce_account_instructions, set_compute_unit_limit_instruction,
+    set_compute_unit_price_instruction, writable_accounts,
 };
 
 // Wallet
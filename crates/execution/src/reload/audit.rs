@@ -0,0 +1,150 @@
+//! Audit log of configuration changes.
+
+use crate::alerts::{Alert, AlertLevel, AlertType, MultiNotifier};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt;
+use tokio::sync::RwLock;
+use tracing::info;
+
+/// Number of changes kept in the audit log.
+const MAX_CHANGES: usize = 500;
+
+/// Part of the runtime configuration that changed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigSection {
+    /// Decision configuration, of a named strategy if set.
+    Decision {
+        /// Strategy name; `None` for the executor default.
+        strategy: Option<String>,
+    },
+    /// Alert rules.
+    AlertRules,
+    /// Scheduled task definitions.
+    Schedules,
+}
+
+impl fmt::Display for ConfigSection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Decision { strategy: None } => write!(f, "decision config"),
+            Self::Decision {
+                strategy: Some(name),
+            } => write!(f, "decision config of strategy {}", name),
+            Self::AlertRules => write!(f, "alert rules"),
+            Self::Schedules => write!(f, "schedules"),
+        }
+    }
+}
+
+/// Where a configuration change came from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigSource {
+    /// A watched configuration file.
+    File(String),
+    /// An API request.
+    Api,
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::File(path) => write!(f, "file {}", path),
+            Self::Api => write!(f, "API"),
+        }
+    }
+}
+
+/// An applied configuration change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigChange {
+    /// When the change was applied.
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Section that changed.
+    pub section: ConfigSection,
+    /// Where the change came from.
+    pub source: ConfigSource,
+    /// Short description of the new values.
+    pub summary: String,
+}
+
+impl ConfigChange {
+    /// Creates a change applied now.
+    #[must_use]
+    pub fn new(section: ConfigSection, source: ConfigSource, summary: impl Into<String>) -> Self {
+        Self {
+            timestamp: chrono::Utc::now(),
+            section,
+            source,
+            summary: summary.into(),
+        }
+    }
+}
+
+/// Records configuration changes and announces them.
+pub struct ConfigAudit {
+    /// Changes, oldest first.
+    changes: RwLock<VecDeque<ConfigChange>>,
+    /// Notifier for "config changed" alerts.
+    notifier: MultiNotifier,
+}
+
+impl ConfigAudit {
+    /// Creates an empty audit log.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            changes: RwLock::new(VecDeque::new()),
+            notifier: MultiNotifier::new(),
+        }
+    }
+
+    /// Sets the notifier for "config changed" alerts.
+    #[must_use]
+    pub fn with_notifier(mut self, notifier: MultiNotifier) -> Self {
+        self.notifier = notifier;
+        self
+    }
+
+    /// Records an applied change.
+    pub async fn record(&self, change: ConfigChange) {
+        info!(
+            section = %change.section,
+            source = %change.source,
+            summary = %change.summary,
+            "Configuration changed"
+        );
+
+        let alert = Alert::new(
+            AlertLevel::Info,
+            AlertType::ConfigChanged,
+            format!(
+                "Updated {} from {}: {}",
+                change.section, change.source, change.summary
+            ),
+        );
+
+        {
+            let mut changes = self.changes.write().await;
+            if changes.len() == MAX_CHANGES {
+                changes.pop_front();
+            }
+            changes.push_back(change);
+        }
+
+        self.notifier.notify_all(&alert).await;
+    }
+
+    /// Gets the recorded changes, oldest first.
+    pub async fn changes(&self) -> Vec<ConfigChange> {
+        self.changes.read().await.iter().cloned().collect()
+    }
+}
+
+impl Default for ConfigAudit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,14 @@
+//! Runtime configuration reload.
+//!
+//! Decision configs, alert rules and schedules can be replaced without a
+//! restart, keeping circuit breaker, monitor and price history state:
+//! - Changes arrive from a watched file or through the API
+//! - Every section is validated before any is applied
+//! - Each applied change is recorded in an audit log and announced as a
+//!   "config changed" alert
+
+mod audit;
+mod reloader;
+
+pub use audit::*;
+pub use reloader::*;
@@ -0,0 +1,342 @@
+//! Validated application of runtime configuration.
+
+use super::{ConfigAudit, ConfigChange, ConfigSection, ConfigSource};
+use crate::alerts::{AlertRule, validate_rules};
+use crate::monitor::PositionMonitor;
+use crate::scheduler::{ScheduleDefinition, ScheduledTask, validate_tasks};
+use crate::shutdown::CancellationToken;
+use crate::strategy::{DecisionConfig, StrategyExecutor};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::sync::{Mutex, mpsc};
+use tracing::{debug, info, warn};
+
+/// Reloadable configuration; absent sections are left as they are.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RuntimeConfig {
+    /// Default decision configuration of the executors.
+    #[serde(default)]
+    pub decision: Option<DecisionConfig>,
+    /// Named strategies positions can be assigned to.
+    #[serde(default)]
+    pub strategies: HashMap<String, DecisionConfig>,
+    /// Alert rules of the position monitor.
+    #[serde(default)]
+    pub alert_rules: Option<Vec<AlertRule>>,
+    /// Scheduled task definitions.
+    #[serde(default)]
+    pub schedules: Option<Vec<ScheduleDefinition>>,
+}
+
+impl RuntimeConfig {
+    /// Reads a JSON configuration file.
+    pub async fn load(path: &std::path::Path) -> Result<Self> {
+        let contents = tokio::fs::read_to_string(path)
+            .await
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Checks every section and converts the schedules into tasks.
+    ///
+    /// # Errors
+    /// Returns the first validation error, prefixed with its section.
+    pub fn validate(&self) -> Result<Option<Vec<ScheduledTask>>> {
+        if let Some(decision) = &self.decision {
+            decision.validate().context("Invalid decision config")?;
+        }
+        for (name, config) in &self.strategies {
+            config
+                .validate()
+                .with_context(|| format!("Invalid strategy {}", name))?;
+        }
+        if let Some(rules) = &self.alert_rules {
+            validate_rules(rules).context("Invalid alert rules")?;
+        }
+        self.schedules
+            .as_ref()
+            .map(|definitions| -> Result<Vec<ScheduledTask>> {
+                let tasks = definitions
+                    .iter()
+                    .map(ScheduleDefinition::to_task)
+                    .collect::<Result<Vec<_>>>()?;
+                validate_tasks(&tasks)?;
+                Ok(tasks)
+            })
+            .transpose()
+            .context("Invalid schedules")
+    }
+}
+
+/// Applies runtime configuration to running components.
+pub struct ConfigReloader {
+    /// Audit log of applied changes.
+    audit: Arc<ConfigAudit>,
+    /// Executors receiving decision configs.
+    executors: Vec<Arc<StrategyExecutor>>,
+    /// Monitor receiving alert rules.
+    monitor: Option<Arc<PositionMonitor>>,
+    /// Scheduler receiving task definitions.
+    scheduler: Option<mpsc::Sender<Vec<ScheduledTask>>>,
+    /// Last applied value of each section, to skip unchanged ones.
+    applied: Mutex<HashMap<String, serde_json::Value>>,
+}
+
+impl ConfigReloader {
+    /// Creates a reloader recording changes in `audit`.
+    pub fn new(audit: Arc<ConfigAudit>) -> Self {
+        Self {
+            audit,
+            executors: Vec::new(),
+            monitor: None,
+            scheduler: None,
+            applied: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Adds an executor to receive decision configs.
+    #[must_use]
+    pub fn with_executor(mut self, executor: Arc<StrategyExecutor>) -> Self {
+        self.executors.push(executor);
+        self
+    }
+
+    /// Sets the monitor to receive alert rules.
+    #[must_use]
+    pub fn with_monitor(mut self, monitor: Arc<PositionMonitor>) -> Self {
+        self.monitor = Some(monitor);
+        self
+    }
+
+    /// Sets the scheduler to receive task definitions, from
+    /// [`crate::scheduler::Scheduler::reload_handle`].
+    #[must_use]
+    pub fn with_scheduler(mut self, handle: mpsc::Sender<Vec<ScheduledTask>>) -> Self {
+        self.scheduler = Some(handle);
+        self
+    }
+
+    /// Validates and applies a configuration.
+    ///
+    /// Nothing is applied unless every section is valid. Sections identical
+    /// to the last applied ones are skipped.
+    pub async fn apply(
+        &self,
+        config: RuntimeConfig,
+        source: ConfigSource,
+    ) -> Result<Vec<ConfigChange>> {
+        let tasks = config.validate()?;
+        let mut changes = Vec::new();
+
+        if let Some(decision) = config.decision
+            && self.mark_changed("decision", &decision).await?
+        {
+            for executor in &self.executors {
+                executor.reload_decision_config(decision.clone()).await?;
+            }
+            changes.push(ConfigChange::new(
+                ConfigSection::Decision { strategy: None },
+                source.clone(),
+                format!(
+                    "rebalance at {} IL, close at {} IL",
+                    decision.il_rebalance_threshold, decision.il_close_threshold
+                ),
+            ));
+        }
+
+        for (name, decision) in config.strategies {
+            if !self
+                .mark_changed(&format!("strategy:{}", name), &decision)
+                .await?
+            {
+                continue;
+            }
+            for executor in &self.executors {
+                executor.reload_strategy(&name, decision.clone()).await?;
+            }
+            changes.push(ConfigChange::new(
+                ConfigSection::Decision {
+                    strategy: Some(name),
+                },
+                source.clone(),
+                format!(
+                    "rebalance at {} IL, close at {} IL",
+                    decision.il_rebalance_threshold, decision.il_close_threshold
+                ),
+            ));
+        }
+
+        if let Some(rules) = config.alert_rules
+            && self.mark_changed("alert_rules", &rules).await?
+        {
+            let summary = format!("{} rules", rules.len());
+            if let Some(monitor) = &self.monitor {
+                monitor.set_alert_rules(rules).await?;
+            }
+            changes.push(ConfigChange::new(
+                ConfigSection::AlertRules,
+                source.clone(),
+                summary,
+            ));
+        }
+
+        if let (Some(tasks), Some(definitions)) = (tasks, config.schedules)
+            && self.mark_changed("schedules", &definitions).await?
+        {
+            let summary = format!("{} tasks", tasks.len());
+            if let Some(scheduler) = &self.scheduler
+                && scheduler.send(tasks).await.is_err()
+            {
+                warn!("Scheduler is not running, schedules not applied");
+            }
+            changes.push(ConfigChange::new(
+                ConfigSection::Schedules,
+                source.clone(),
+                summary,
+            ));
+        }
+
+        for change in &changes {
+            self.audit.record(change.clone()).await;
+        }
+        Ok(changes)
+    }
+
+    /// Polls a configuration file and applies it whenever it changes, until
+    /// `shutdown` is cancelled.
+    ///
+    /// Invalid files are logged and ignored; the previous configuration stays
+    /// in effect.
+    pub async fn watch_file(
+        self: Arc<Self>,
+        path: PathBuf,
+        poll_interval: Duration,
+        shutdown: CancellationToken,
+    ) {
+        info!(path = %path.display(), "Watching configuration file");
+        let mut last_modified: Option<SystemTime> = None;
+        let mut ticker = tokio::time::interval(poll_interval);
+
+        loop {
+            tokio::select! {
+                () = shutdown.cancelled() => break,
+                _ = ticker.tick() => {}
+            }
+
+            let modified = match tokio::fs::metadata(&path).await.and_then(|m| m.modified()) {
+                Ok(modified) => modified,
+                Err(e) => {
+                    debug!(path = %path.display(), error = %e, "Configuration file unavailable");
+                    continue;
+                }
+            };
+            if last_modified == Some(modified) {
+                continue;
+            }
+            last_modified = Some(modified);
+
+            let source = ConfigSource::File(path.display().to_string());
+            match RuntimeConfig::load(&path).await {
+                Ok(config) => {
+                    if let Err(e) = self.apply(config, source).await {
+                        warn!(path = %path.display(), error = %e, "Rejected configuration");
+                    }
+                }
+                Err(e) => warn!(path = %path.display(), error = %e, "Rejected configuration"),
+            }
+        }
+    }
+
+    /// Remembers a section's value; returns true if it differs from the last one.
+    async fn mark_changed<T: Serialize>(&self, key: &str, value: &T) -> Result<bool> {
+        let value = serde_json::to_value(value)?;
+        let mut applied = self.applied.lock().await;
+        if applied.get(key) == Some(&value) {
+            return Ok(false);
+        }
+        applied.insert(key.to_string(), value);
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::MonitorConfig;
+    use clmm_lp_protocols::prelude::{RpcConfig, RpcProvider};
+
+    fn monitor() -> Arc<PositionMonitor> {
+        let provider = Arc::new(RpcProvider::new(RpcConfig::default()));
+        Arc::new(PositionMonitor::new(provider, MonitorConfig::default()))
+    }
+
+    const CONFIG: &str = r#"{
+        "alert_rules": [{
+            "name": "out_of_range",
+            "condition": "RangeExit",
+            "level": "Warning",
+            "alert_type": "RangeExit",
+            "message_template": "",
+            "enabled": true,
+            "cooldown_secs": 60
+        }],
+        "schedules": [{"name": "sync", "every_secs": 30}]
+    }"#;
+
+    #[tokio::test]
+    async fn test_apply_and_audit() {
+        let audit = Arc::new(ConfigAudit::new());
+        let monitor = monitor();
+        let mut scheduler = crate::scheduler::Scheduler::new();
+        let (handle, mut reloads) = mpsc::channel(1);
+        let reloader = ConfigReloader::new(audit.clone())
+            .with_monitor(monitor.clone())
+            .with_scheduler(handle);
+
+        let config: RuntimeConfig = serde_json::from_str(CONFIG).unwrap();
+        let changes = reloader
+            .apply(config.clone(), ConfigSource::Api)
+            .await
+            .unwrap();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(monitor.alert_rules().await.len(), 1);
+        assert_eq!(audit.changes().await.len(), 2);
+
+        // The scheduler picks the tasks up on its next tick
+        let tasks = reloads.try_recv().unwrap();
+        scheduler
+            .replace_tasks(tasks, tokio::time::Instant::now())
+            .unwrap();
+        assert_eq!(scheduler.tasks()[0].name, "sync");
+
+        // Reapplying the same file is not a change
+        let changes = reloader.apply(config, ConfigSource::Api).await.unwrap();
+        assert!(changes.is_empty());
+        assert_eq!(audit.changes().await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_config_applies_nothing() {
+        let audit = Arc::new(ConfigAudit::new());
+        let monitor = monitor();
+        let reloader = ConfigReloader::new(audit.clone()).with_monitor(monitor.clone());
+
+        let mut config: RuntimeConfig = serde_json::from_str(CONFIG).unwrap();
+        config.schedules = Some(vec![ScheduleDefinition {
+            name: "sync".to_string(),
+            every_secs: Some(0),
+            daily_at: Vec::new(),
+            enabled: true,
+        }]);
+
+        let err = reloader.apply(config, ConfigSource::Api).await.unwrap_err();
+        assert!(err.to_string().contains("Invalid schedules"));
+        assert!(monitor.alert_rules().await.is_empty());
+        assert!(audit.changes().await.is_empty());
+    }
+}
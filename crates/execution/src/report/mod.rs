@@ -0,0 +1,12 @@
+//! Daily summary reports.
+//!
+//! Compiles each day's fees, PnL change, rebalances, transaction costs and
+//! alerts from the lifecycle tracker, together with the monitor's current
+//! ranges, into a report sent through the alert notifiers and optionally
+//! written to a Markdown file.
+
+mod runner;
+mod summary;
+
+pub use runner::{DailyReporter, ReportConfig};
+pub use summary::{AlertCounts, DailySummary, PositionRange};
@@ -0,0 +1,251 @@
+//! Scheduled compilation and delivery of daily summaries.
+
+use super::{AlertCounts, DailySummary};
+use crate::alerts::{AlertLevel, MultiNotifier};
+use crate::lifecycle::LifecycleTracker;
+use crate::monitor::PositionMonitor;
+use crate::scheduler::{ScheduleBuilder, ScheduledTask, Scheduler};
+use crate::shutdown::CancellationToken;
+use crate::supervisor::Heartbeat;
+use chrono::{DateTime, Utc};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+/// Name of the reporter's scheduled task.
+const REPORT_TASK: &str = "daily-report";
+
+/// Configuration for the daily reporter.
+#[derive(Debug, Clone)]
+pub struct ReportConfig {
+    /// Daily run time as (hour, minute).
+    pub run_at: (u8, u8),
+    /// Directory each report is also written to as a Markdown file.
+    pub output_dir: Option<PathBuf>,
+}
+
+impl Default for ReportConfig {
+    fn default() -> Self {
+        Self {
+            run_at: (0, 5),
+            output_dir: None,
+        }
+    }
+}
+
+/// Compiles and sends a summary of the last day's activity.
+///
+/// Each report covers the time since the previous one, or the last 24 hours
+/// for the first report.
+pub struct DailyReporter {
+    /// Lifecycle tracker providing the day's events.
+    lifecycle: Arc<LifecycleTracker>,
+    /// Position monitor providing current ranges and PnL.
+    monitor: Arc<PositionMonitor>,
+    /// Channels the report is sent to.
+    notifier: MultiNotifier,
+    /// Configuration.
+    config: ReportConfig,
+    /// Alerts raised since the previous report.
+    alerts: Mutex<Vec<(DateTime<Utc>, AlertLevel)>>,
+    /// Latest report.
+    last_report: RwLock<Option<DailySummary>>,
+    /// Beaten by the daily scheduler on every tick, if watched.
+    heartbeat: Option<Heartbeat>,
+}
+
+impl DailyReporter {
+    /// Creates a new daily reporter.
+    pub fn new(
+        lifecycle: Arc<LifecycleTracker>,
+        monitor: Arc<PositionMonitor>,
+        config: ReportConfig,
+    ) -> Self {
+        Self {
+            lifecycle,
+            monitor,
+            notifier: MultiNotifier::new(),
+            config,
+            alerts: Mutex::new(Vec::new()),
+            last_report: RwLock::new(None),
+            heartbeat: None,
+        }
+    }
+
+    /// Sets the channels the report is sent to.
+    #[must_use]
+    pub fn with_notifier(mut self, notifier: MultiNotifier) -> Self {
+        self.notifier = notifier;
+        self
+    }
+
+    /// Watches the daily scheduler with `heartbeat`.
+    #[must_use]
+    pub fn with_heartbeat(mut self, heartbeat: Heartbeat) -> Self {
+        self.heartbeat = Some(heartbeat);
+        self
+    }
+
+    /// Counts an alert towards the report covering `timestamp`.
+    pub fn record_alert(&self, level: AlertLevel, timestamp: DateTime<Utc>) {
+        self.alerts
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push((timestamp, level));
+    }
+
+    /// Compiles a report of the period since the previous one, writes and
+    /// sends it, and returns it.
+    ///
+    /// A report that cannot be written to the output directory is still sent.
+    pub async fn run_once(&self) -> DailySummary {
+        let now = Utc::now();
+        let previous = self.last_report.read().await.clone();
+        let period_start = previous
+            .as_ref()
+            .map_or(now - chrono::Duration::days(1), |report| report.period_end);
+
+        let events = self.lifecycle.get_all_events().await;
+        let positions = self.monitor.get_positions().await;
+        let summary = DailySummary::compile(
+            period_start,
+            now,
+            &events,
+            &positions,
+            self.take_alerts(period_start, now),
+            previous.map(|report| report.net_pnl_usd),
+        );
+        info!(
+            fees_usd = %summary.stats.fees_usd,
+            rebalances = summary.stats.rebalances,
+            alerts = summary.alerts.total(),
+            "Daily report compiled"
+        );
+
+        if let Some(dir) = &self.config.output_dir {
+            match write_markdown(dir, &summary).await {
+                Ok(path) => info!(path = %path.display(), "Daily report written"),
+                Err(e) => warn!(error = %e, "Failed to write daily report"),
+            }
+        }
+        self.notifier.notify_all(&summary.to_alert()).await;
+        *self.last_report.write().await = Some(summary.clone());
+        summary
+    }
+
+    /// Returns the latest report.
+    pub async fn last_report(&self) -> Option<DailySummary> {
+        self.last_report.read().await.clone()
+    }
+
+    /// Runs the reporter once a day until `shutdown` is cancelled.
+    pub async fn run_daily_until(self: Arc<Self>, shutdown: CancellationToken) {
+        let mut scheduler = Scheduler::new();
+        if let Some(heartbeat) = &self.heartbeat {
+            scheduler.set_heartbeat(heartbeat.clone());
+        }
+        scheduler.add_task(ScheduledTask::new(
+            REPORT_TASK,
+            ScheduleBuilder::daily_at(vec![self.config.run_at]),
+        ));
+        let Some(mut events) = scheduler.take_receiver() else {
+            return;
+        };
+
+        let reporter = self.clone();
+        tokio::spawn(async move {
+            while let Some(event) = events.recv().await {
+                if event.task_name == REPORT_TASK {
+                    reporter.run_once().await;
+                }
+            }
+        });
+
+        scheduler.run_until(shutdown).await;
+    }
+
+    /// Counts the alerts raised in `[start, end)`, dropping them and any
+    /// older ones.
+    fn take_alerts(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> AlertCounts {
+        let mut alerts = self.alerts.lock().unwrap_or_else(|e| e.into_inner());
+        let mut counts = AlertCounts::default();
+        for (_, level) in alerts
+            .iter()
+            .filter(|(timestamp, _)| *timestamp >= start && *timestamp < end)
+        {
+            counts.record(*level);
+        }
+        alerts.retain(|(timestamp, _)| *timestamp >= end);
+        counts
+    }
+}
+
+/// Writes a report to `dir` as `daily-report-<date>.md`, returning the path.
+async fn write_markdown(dir: &Path, summary: &DailySummary) -> anyhow::Result<PathBuf> {
+    tokio::fs::create_dir_all(dir).await?;
+    let path = dir.join(format!(
+        "daily-report-{}.md",
+        summary.period_end.format("%Y-%m-%d")
+    ));
+    tokio::fs::write(&path, summary.to_markdown()).await?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::MonitorConfig;
+    use clmm_lp_protocols::prelude::{RpcConfig, RpcProvider};
+
+    fn reporter(output_dir: Option<PathBuf>) -> DailyReporter {
+        let provider = Arc::new(RpcProvider::new(RpcConfig::default()));
+        DailyReporter::new(
+            Arc::new(LifecycleTracker::new()),
+            Arc::new(PositionMonitor::new(provider, MonitorConfig::default())),
+            ReportConfig {
+                output_dir,
+                ..Default::default()
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_reports_cover_time_since_previous_report() {
+        let reporter = reporter(None);
+        let now = Utc::now();
+        reporter.record_alert(AlertLevel::Warning, now - chrono::Duration::hours(2));
+        reporter.record_alert(AlertLevel::Critical, now - chrono::Duration::days(3));
+
+        let first = reporter.run_once().await;
+        assert_eq!(
+            first.period_end - first.period_start,
+            chrono::Duration::days(1)
+        );
+        assert_eq!(first.alerts.warning, 1);
+        assert_eq!(first.alerts.total(), 1);
+        assert_eq!(first.pnl_change_usd, None);
+
+        reporter.record_alert(AlertLevel::Info, Utc::now());
+        let second = reporter.run_once().await;
+        assert_eq!(second.period_start, first.period_end);
+        assert_eq!(second.alerts.info, 1);
+        assert_eq!(second.alerts.total(), 1);
+        assert_eq!(second.pnl_change_usd, Some(rust_decimal::Decimal::ZERO));
+        assert_eq!(reporter.last_report().await, Some(second));
+    }
+
+    #[tokio::test]
+    async fn test_writes_markdown_file() {
+        let dir = std::env::temp_dir().join(format!("clmm-report-{}", uuid::Uuid::new_v4()));
+        let summary = reporter(Some(dir.clone())).run_once().await;
+
+        let path = dir.join(format!(
+            "daily-report-{}.md",
+            summary.period_end.format("%Y-%m-%d")
+        ));
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(written, summary.to_markdown());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
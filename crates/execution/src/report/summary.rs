@@ -0,0 +1,424 @@
+//! Compilation and formatting of a daily summary.
+
+use crate::alerts::{Alert, AlertLevel, AlertType};
+use crate::lifecycle::{LifecycleEvent, StatsBucket, summarize_events};
+use crate::monitor::MonitoredPosition;
+use chrono::{DateTime, Utc};
+use clmm_lp_protocols::prelude::tick_to_price;
+use rust_decimal::Decimal;
+use solana_sdk::pubkey::Pubkey;
+use std::fmt::Write;
+
+/// Lamports per SOL.
+const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+/// Number of alerts raised, by level.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AlertCounts {
+    /// Informational alerts.
+    pub info: u32,
+    /// Warnings.
+    pub warning: u32,
+    /// Critical alerts.
+    pub critical: u32,
+}
+
+impl AlertCounts {
+    /// Counts one alert of `level`.
+    pub fn record(&mut self, level: AlertLevel) {
+        match level {
+            AlertLevel::Info => self.info += 1,
+            AlertLevel::Warning => self.warning += 1,
+            AlertLevel::Critical => self.critical += 1,
+        }
+    }
+
+    /// Returns the number of alerts of any level.
+    #[must_use]
+    pub fn total(&self) -> u32 {
+        self.info + self.warning + self.critical
+    }
+}
+
+/// Current range of a tracked position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionRange {
+    /// Position address.
+    pub position: Pubkey,
+    /// Pool address.
+    pub pool: Pubkey,
+    /// Lower price bound.
+    pub lower: Decimal,
+    /// Upper price bound.
+    pub upper: Decimal,
+    /// Whether the position is in range.
+    pub in_range: bool,
+    /// Net PnL in USD.
+    pub net_pnl_usd: Decimal,
+}
+
+impl PositionRange {
+    /// Reads the range of a monitored position.
+    #[must_use]
+    pub fn from_position(position: &MonitoredPosition) -> Self {
+        Self {
+            position: position.address,
+            pool: position.pool,
+            lower: tick_to_price(position.on_chain.tick_lower),
+            upper: tick_to_price(position.on_chain.tick_upper),
+            in_range: position.in_range,
+            net_pnl_usd: position.pnl.net_pnl_usd,
+        }
+    }
+}
+
+/// Summary of one reporting period.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailySummary {
+    /// Start of the period (inclusive).
+    pub period_start: DateTime<Utc>,
+    /// End of the period (exclusive).
+    pub period_end: DateTime<Utc>,
+    /// Lifecycle statistics of the period.
+    pub stats: StatsBucket,
+    /// Net PnL in USD of the tracked positions at the end of the period.
+    pub net_pnl_usd: Decimal,
+    /// Change in net PnL since the previous report, if there was one.
+    pub pnl_change_usd: Option<Decimal>,
+    /// Alerts raised during the period.
+    pub alerts: AlertCounts,
+    /// Current ranges of the tracked positions.
+    pub ranges: Vec<PositionRange>,
+}
+
+impl DailySummary {
+    /// Compiles the summary of `[period_start, period_end)`.
+    ///
+    /// Events outside the period are ignored. `previous_net_pnl_usd` is the
+    /// net PnL of the previous report, from which the change is computed.
+    #[must_use]
+    pub fn compile(
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+        events: &[LifecycleEvent],
+        positions: &[MonitoredPosition],
+        alerts: AlertCounts,
+        previous_net_pnl_usd: Option<Decimal>,
+    ) -> Self {
+        let stats = summarize_events(
+            period_start,
+            events
+                .iter()
+                .filter(|e| e.timestamp >= period_start && e.timestamp < period_end),
+        );
+        let net_pnl_usd = positions.iter().map(|p| p.pnl.net_pnl_usd).sum();
+        let mut ranges: Vec<PositionRange> =
+            positions.iter().map(PositionRange::from_position).collect();
+        // Out-of-range positions first, as they are the ones needing attention
+        ranges.sort_by_key(|r| r.in_range);
+
+        Self {
+            period_start,
+            period_end,
+            stats,
+            net_pnl_usd,
+            pnl_change_usd: previous_net_pnl_usd.map(|previous| net_pnl_usd - previous),
+            alerts,
+            ranges,
+        }
+    }
+
+    /// Returns the transaction costs of the period in SOL.
+    #[must_use]
+    pub fn tx_costs_sol(&self) -> Decimal {
+        Decimal::from(self.stats.tx_costs_lamports) / Decimal::from(LAMPORTS_PER_SOL)
+    }
+
+    /// Formats the summary as plain text, suitable for chat messages and
+    /// email bodies.
+    #[must_use]
+    pub fn to_text(&self) -> String {
+        let mut text = format!("Daily report for {}", self.period_label());
+        let _ = write!(
+            text,
+            "\nFees earned: {}",
+            usd(self.stats.fees_usd + self.stats.rewards_usd)
+        );
+        let _ = write!(text, "\nPnL change: {}", self.pnl_change_label());
+        let _ = write!(text, "\nNet PnL: {}", usd(self.net_pnl_usd));
+        let _ = write!(
+            text,
+            "\nRebalances: {} ({:.6} SOL in tx costs)",
+            self.stats.rebalances,
+            self.tx_costs_sol()
+        );
+        let _ = write!(text, "\nAlerts: {}", self.alerts_label());
+        if self.ranges.is_empty() {
+            text.push_str("\nNo tracked positions");
+        } else {
+            let _ = write!(text, "\nRanges ({}):", self.ranges.len());
+            for range in &self.ranges {
+                let _ = write!(
+                    text,
+                    "\n- {}: {:.4}-{:.4} {} ({})",
+                    short(&range.position),
+                    range.lower,
+                    range.upper,
+                    if range.in_range {
+                        "in range"
+                    } else {
+                        "OUT OF RANGE"
+                    },
+                    usd(range.net_pnl_usd)
+                );
+            }
+        }
+        text
+    }
+
+    /// Formats the summary as a Markdown document.
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        let stats = &self.stats;
+        let mut md = format!("# Daily Report: {}\n\n", self.period_label());
+        md.push_str("| Metric | Value |\n|---|---|\n");
+        let rows = [
+            ("Fees earned", usd(stats.fees_usd)),
+            ("Rewards earned", usd(stats.rewards_usd)),
+            ("PnL change", self.pnl_change_label()),
+            ("Net PnL", usd(self.net_pnl_usd)),
+            ("Realized PnL", usd(stats.realized_pnl_usd)),
+            ("Rebalances", stats.rebalances.to_string()),
+            (
+                "Transaction costs",
+                format!("{:.6} SOL", self.tx_costs_sol()),
+            ),
+            ("Positions opened", stats.positions_opened.to_string()),
+            ("Positions closed", stats.positions_closed.to_string()),
+            ("Alerts", self.alerts_label()),
+        ];
+        for (metric, value) in rows {
+            let _ = writeln!(md, "| {} | {} |", metric, value);
+        }
+
+        md.push_str("\n## Current Ranges\n\n");
+        if self.ranges.is_empty() {
+            md.push_str("No tracked positions.\n");
+            return md;
+        }
+        md.push_str("| Position | Pool | Range | Status | Net PnL |\n|---|---|---|---|---|\n");
+        for range in &self.ranges {
+            let _ = writeln!(
+                md,
+                "| `{}` | `{}` | {:.4} - {:.4} | {} | {} |",
+                range.position,
+                range.pool,
+                range.lower,
+                range.upper,
+                if range.in_range {
+                    "✅ In range"
+                } else {
+                    "⚠️ Out of range"
+                },
+                usd(range.net_pnl_usd)
+            );
+        }
+        md
+    }
+
+    /// Builds the alert carrying the plain-text summary.
+    #[must_use]
+    pub fn to_alert(&self) -> Alert {
+        Alert::new(AlertLevel::Info, AlertType::DailyReport, self.to_text())
+    }
+
+    /// Describes the period, e.g. "2024-03-01 (24h to 00:05 UTC)".
+    fn period_label(&self) -> String {
+        let hours = (self.period_end - self.period_start).num_hours();
+        format!(
+            "{} ({}h to {} UTC)",
+            self.period_end.format("%Y-%m-%d"),
+            hours,
+            self.period_end.format("%H:%M")
+        )
+    }
+
+    /// Describes the PnL change, noting when there is no previous report.
+    fn pnl_change_label(&self) -> String {
+        match self.pnl_change_usd {
+            Some(change) if change.is_sign_negative() => usd(change),
+            Some(change) => format!("+{}", usd(change)),
+            None => "n/a (first report)".to_string(),
+        }
+    }
+
+    /// Describes the alert counts.
+    fn alerts_label(&self) -> String {
+        let alerts = &self.alerts;
+        format!(
+            "{} ({} critical, {} warning, {} info)",
+            alerts.total(),
+            alerts.critical,
+            alerts.warning,
+            alerts.info
+        )
+    }
+}
+
+/// Formats a USD amount, e.g. "-$5.00".
+fn usd(value: Decimal) -> String {
+    let sign = if value.is_sign_negative() && !value.is_zero() {
+        "-"
+    } else {
+        ""
+    };
+    format!("{}${:.2}", sign, value.abs())
+}
+
+/// Shortens an address to its first and last four characters.
+fn short(address: &Pubkey) -> String {
+    let address = address.to_string();
+    format!("{}…{}", &address[..4], &address[address.len() - 4..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lifecycle::{
+        EventData, FeesCollectedData, LifecycleEventType, RebalanceData, RebalanceReason,
+    };
+    use crate::monitor::PositionPnL;
+    use chrono::TimeZone;
+    use clmm_lp_protocols::prelude::{OnChainPosition, Protocol};
+    use rust_decimal_macros::dec;
+
+    fn position(in_range: bool, net_pnl_usd: Decimal) -> MonitoredPosition {
+        let pool = Pubkey::new_unique();
+        MonitoredPosition {
+            address: Pubkey::new_unique(),
+            pool,
+            protocol: Protocol::OrcaWhirlpool,
+            on_chain: OnChainPosition {
+                address: Pubkey::new_unique(),
+                pool,
+                owner: Pubkey::new_unique(),
+                tick_lower: -1000,
+                tick_upper: 1000,
+                liquidity: 1_000_000,
+                fee_growth_inside_a: 0,
+                fee_growth_inside_b: 0,
+                fees_owed_a: 0,
+                fees_owed_b: 0,
+                reward_growth_inside: [0; 3],
+                rewards_owed: [0; 3],
+            },
+            pnl: PositionPnL {
+                net_pnl_usd,
+                ..Default::default()
+            },
+            in_range,
+            tick_current: 0,
+            range_checks: 0,
+            in_range_checks: 0,
+            health: None,
+            strategy: None,
+            tags: Vec::new(),
+            read_only: false,
+            stale_since: None,
+            last_updated: Utc::now(),
+        }
+    }
+
+    fn event(timestamp: DateTime<Utc>, data: EventData) -> LifecycleEvent {
+        let event_type = match data {
+            EventData::Rebalance(_) => LifecycleEventType::Rebalanced,
+            _ => LifecycleEventType::FeesCollected,
+        };
+        let mut event =
+            LifecycleEvent::new(event_type, Pubkey::new_unique(), Pubkey::new_unique(), data);
+        event.timestamp = timestamp;
+        event
+    }
+
+    fn fees(usd: Decimal) -> EventData {
+        EventData::FeesCollected(FeesCollectedData {
+            fees_a: 0,
+            fees_b: 0,
+            fees_usd: usd,
+            rewards: [0, 0, 0],
+            rewards_usd: Decimal::ZERO,
+        })
+    }
+
+    fn rebalance(tx_cost_lamports: u64) -> EventData {
+        EventData::Rebalance(RebalanceData {
+            old_tick_lower: -1000,
+            old_tick_upper: 1000,
+            new_tick_lower: -500,
+            new_tick_upper: 1500,
+            old_liquidity: 1_000_000,
+            new_liquidity: 1_000_000,
+            tx_cost_lamports,
+            il_at_rebalance: Decimal::ZERO,
+            reason: RebalanceReason::RangeExit,
+        })
+    }
+
+    fn summary() -> DailySummary {
+        let end = Utc.with_ymd_and_hms(2024, 3, 2, 0, 5, 0).unwrap();
+        let start = end - chrono::Duration::days(1);
+        let events = vec![
+            event(start - chrono::Duration::hours(1), fees(dec!(100))),
+            event(start + chrono::Duration::hours(2), fees(dec!(12.5))),
+            event(start + chrono::Duration::hours(3), rebalance(15_000)),
+            event(end, fees(dec!(100))),
+        ];
+        let mut alerts = AlertCounts::default();
+        alerts.record(AlertLevel::Warning);
+        alerts.record(AlertLevel::Critical);
+
+        DailySummary::compile(
+            start,
+            end,
+            &events,
+            &[position(true, dec!(30)), position(false, dec!(-10))],
+            alerts,
+            Some(dec!(25)),
+        )
+    }
+
+    #[test]
+    fn test_compile_counts_only_the_period() {
+        let summary = summary();
+        assert_eq!(summary.stats.fees_usd, dec!(12.5));
+        assert_eq!(summary.stats.rebalances, 1);
+        assert_eq!(summary.tx_costs_sol(), dec!(0.000015));
+        assert_eq!(summary.net_pnl_usd, dec!(20));
+        assert_eq!(summary.pnl_change_usd, Some(dec!(-5)));
+        assert_eq!(summary.alerts.total(), 2);
+        assert!(!summary.ranges[0].in_range);
+    }
+
+    #[test]
+    fn test_formats() {
+        let summary = summary();
+
+        let text = summary.to_text();
+        assert!(text.starts_with("Daily report for 2024-03-02 (24h to 00:05 UTC)"));
+        assert!(text.contains("Fees earned: $12.50"));
+        assert!(text.contains("PnL change: -$5.00"));
+        assert!(text.contains("Rebalances: 1 (0.000015 SOL in tx costs)"));
+        assert!(text.contains("Alerts: 2 (1 critical, 1 warning, 0 info)"));
+        assert!(text.contains("0.9048-1.1051 OUT OF RANGE (-$10.00)"));
+
+        let md = summary.to_markdown();
+        assert!(md.starts_with("# Daily Report: 2024-03-02"));
+        assert!(md.contains("| Rebalances | 1 |"));
+        assert!(md.contains(&format!("| `{}` |", summary.ranges[1].position)));
+        assert!(md.contains("✅ In range"));
+
+        let alert = summary.to_alert();
+        assert_eq!(alert.alert_type, AlertType::DailyReport);
+        assert_eq!(alert.message, text);
+    }
+}
@@ -9,4 +9,6 @@ mod runner;
 mod types;
 
 pub use runner::Scheduler;
-pub use types::{Schedule, ScheduleBuilder, ScheduledTask, TaskEvent};
+pub use types::{
+    Schedule, ScheduleBuilder, ScheduleDefinition, ScheduledTask, TaskEvent, validate_tasks,
+};
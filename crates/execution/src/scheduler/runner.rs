@@ -1,6 +1,8 @@
 //! Scheduler implementation for task execution timing.
 
-use super::{Schedule, ScheduledTask, TaskEvent};
+use super::{Schedule, ScheduledTask, TaskEvent, validate_tasks};
+use crate::shutdown::CancellationToken;
+use crate::supervisor::Heartbeat;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
@@ -18,20 +20,35 @@ pub struct Scheduler {
     event_rx: Option<mpsc::Receiver<TaskEvent>>,
     /// Running flag.
     running: Arc<AtomicBool>,
+    /// Sender for replacement task lists.
+    reload_tx: mpsc::Sender<Vec<ScheduledTask>>,
+    /// Replacement task lists, applied on the next tick.
+    reload_rx: mpsc::Receiver<Vec<ScheduledTask>>,
+    /// Beaten after every tick, if watched.
+    heartbeat: Option<Heartbeat>,
 }
 
 impl Scheduler {
     /// Creates a new scheduler.
     pub fn new() -> Self {
         let (tx, rx) = mpsc::channel(100);
+        let (reload_tx, reload_rx) = mpsc::channel(8);
         Self {
             tasks: Vec::new(),
             event_tx: tx,
             event_rx: Some(rx),
             running: Arc::new(AtomicBool::new(false)),
+            reload_tx,
+            reload_rx,
+            heartbeat: None,
         }
     }
 
+    /// Beats `heartbeat` after every tick, so a hung scheduler is reported.
+    pub fn set_heartbeat(&mut self, heartbeat: Heartbeat) {
+        self.heartbeat = Some(heartbeat);
+    }
+
     /// Adds a task to the scheduler.
     pub fn add_task(&mut self, task: ScheduledTask) {
         info!(task = %task.name, "Adding task to scheduler");
@@ -57,6 +74,44 @@ impl Scheduler {
         }
     }
 
+    /// Replaces all tasks.
+    ///
+    /// Tasks whose name and schedule are unchanged keep their timing; the
+    /// rest are scheduled from `now`.
+    ///
+    /// # Errors
+    /// Returns an error, leaving the tasks unchanged, if they are invalid.
+    pub fn replace_tasks(
+        &mut self,
+        mut tasks: Vec<ScheduledTask>,
+        now: Instant,
+    ) -> anyhow::Result<()> {
+        validate_tasks(&tasks)?;
+        for task in &mut tasks {
+            match self
+                .tasks
+                .iter()
+                .find(|t| t.name == task.name && t.schedule == task.schedule)
+            {
+                Some(existing) => {
+                    task.last_run = existing.last_run;
+                    task.next_run = existing.next_run;
+                }
+                None => {
+                    task.next_run = Some(Self::calculate_next_run_static(&task.schedule, now));
+                }
+            }
+        }
+        info!(tasks = tasks.len(), "Replaced scheduled tasks");
+        self.tasks = tasks;
+        Ok(())
+    }
+
+    /// Returns a sender that replaces the tasks of the running scheduler.
+    pub fn reload_handle(&self) -> mpsc::Sender<Vec<ScheduledTask>> {
+        self.reload_tx.clone()
+    }
+
     /// Takes the event receiver for processing events.
     pub fn take_receiver(&mut self) -> Option<mpsc::Receiver<TaskEvent>> {
         self.event_rx.take()
@@ -64,6 +119,11 @@ impl Scheduler {
 
     /// Starts the scheduler.
     pub async fn start(&mut self) {
+        self.run_until(CancellationToken::new()).await;
+    }
+
+    /// Runs the scheduler until stopped or `shutdown` is cancelled.
+    pub async fn run_until(&mut self, shutdown: CancellationToken) {
         self.running.store(true, Ordering::SeqCst);
 
         info!(tasks = self.tasks.len(), "Starting scheduler");
@@ -79,10 +139,24 @@ impl Scheduler {
         let mut check_interval = interval(Duration::from_secs(1));
 
         while self.running.load(Ordering::SeqCst) {
-            check_interval.tick().await;
+            tokio::select! {
+                () = shutdown.cancelled() => break,
+                _ = check_interval.tick() => {}
+            }
 
             let now = Instant::now();
 
+            // Apply the latest reload, if any
+            let mut reload = None;
+            while let Ok(tasks) = self.reload_rx.try_recv() {
+                reload = Some(tasks);
+            }
+            if let Some(tasks) = reload
+                && let Err(e) = self.replace_tasks(tasks, now)
+            {
+                warn!(error = %e, "Rejected scheduled task reload");
+            }
+
             // Collect events to send
             let mut events_to_send = Vec::new();
 
@@ -121,8 +195,16 @@ impl Scheduler {
                     warn!(task = %task_name, error = %e, "Failed to send task event");
                 }
             }
+
+            if let Some(heartbeat) = &self.heartbeat {
+                heartbeat.beat();
+            }
         }
 
+        if let Some(heartbeat) = &self.heartbeat {
+            heartbeat.pause();
+        }
+        self.running.store(false, Ordering::SeqCst);
         info!("Scheduler stopped");
     }
 
@@ -178,4 +260,31 @@ mod tests {
 
         assert_eq!(scheduler.tasks().len(), 1);
     }
+
+    #[test]
+    fn test_replace_tasks_keeps_unchanged_timing() {
+        let mut scheduler = Scheduler::new();
+        let now = Instant::now();
+        let mut kept = ScheduledTask::new("kept", ScheduleBuilder::every_secs(60));
+        kept.next_run = Some(now + Duration::from_secs(5));
+        scheduler.add_task(kept.clone());
+        scheduler.add_task(ScheduledTask::new(
+            "dropped",
+            ScheduleBuilder::every_secs(1),
+        ));
+
+        let added = ScheduledTask::new("added", ScheduleBuilder::every_secs(10));
+        scheduler
+            .replace_tasks(vec![kept.clone(), added], now)
+            .unwrap();
+
+        let tasks = scheduler.tasks();
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].next_run, kept.next_run);
+        assert_eq!(tasks[1].next_run, Some(now + Duration::from_secs(10)));
+
+        let duplicate = vec![kept.clone(), kept];
+        assert!(scheduler.replace_tasks(duplicate, now).is_err());
+        assert_eq!(scheduler.tasks().len(), 2);
+    }
 }
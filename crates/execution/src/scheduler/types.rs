@@ -1,10 +1,12 @@
 //! Types for the scheduler module.
 
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::time::Duration;
 use tokio::time::Instant;
 
 /// Schedule type for task execution.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Schedule {
     /// Run at fixed intervals.
     Interval(Duration),
@@ -51,6 +53,70 @@ impl ScheduledTask {
     }
 }
 
+/// Serializable task definition, as found in configuration files.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduleDefinition {
+    /// Task name.
+    pub name: String,
+    /// Run every N seconds.
+    #[serde(default)]
+    pub every_secs: Option<u64>,
+    /// Run daily at these (hour, minute) times.
+    #[serde(default)]
+    pub daily_at: Vec<(u8, u8)>,
+    /// Whether the task is enabled.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl ScheduleDefinition {
+    /// Converts the definition into a task.
+    ///
+    /// # Errors
+    /// Returns an error unless exactly one of a positive interval or valid
+    /// daily times is given.
+    pub fn to_task(&self) -> anyhow::Result<ScheduledTask> {
+        let schedule = match (self.every_secs, self.daily_at.is_empty()) {
+            (Some(0), _) => anyhow::bail!("Task {}: interval must be positive", self.name),
+            (Some(secs), true) => ScheduleBuilder::every_secs(secs),
+            (None, false) => {
+                if let Some((h, m)) = self.daily_at.iter().find(|(h, m)| *h > 23 || *m > 59) {
+                    anyhow::bail!("Task {}: invalid time {:02}:{:02}", self.name, h, m);
+                }
+                ScheduleBuilder::daily_at(self.daily_at.clone())
+            }
+            _ => anyhow::bail!(
+                "Task {}: set exactly one of every_secs or daily_at",
+                self.name
+            ),
+        };
+
+        let task = ScheduledTask::new(self.name.clone(), schedule);
+        Ok(if self.enabled { task } else { task.disabled() })
+    }
+}
+
+/// Checks that task names are present and unique.
+///
+/// # Errors
+/// Returns an error naming the first offending task.
+pub fn validate_tasks(tasks: &[ScheduledTask]) -> anyhow::Result<()> {
+    let mut names = HashSet::new();
+    for task in tasks {
+        if task.name.trim().is_empty() {
+            anyhow::bail!("Task name must not be empty");
+        }
+        if !names.insert(task.name.as_str()) {
+            anyhow::bail!("Duplicate task: {}", task.name);
+        }
+    }
+    Ok(())
+}
+
 /// Event sent when a task should run.
 #[derive(Debug, Clone)]
 pub struct TaskEvent {
@@ -117,4 +183,32 @@ mod tests {
         assert!(task.enabled);
         assert_eq!(task.name, "test");
     }
+
+    #[test]
+    fn test_schedule_definition() {
+        let every: ScheduleDefinition =
+            serde_json::from_str(r#"{"name": "sync", "every_secs": 30}"#).unwrap();
+        let task = every.to_task().unwrap();
+        assert_eq!(task.schedule, ScheduleBuilder::every_secs(30));
+        assert!(task.enabled);
+
+        let both = ScheduleDefinition {
+            daily_at: vec![(9, 0)],
+            ..every.clone()
+        };
+        assert!(both.to_task().is_err());
+
+        let late = ScheduleDefinition {
+            every_secs: None,
+            daily_at: vec![(24, 0)],
+            ..every
+        };
+        assert!(late.to_task().is_err());
+
+        let tasks = vec![
+            ScheduledTask::new("a", ScheduleBuilder::every_secs(1)),
+            ScheduledTask::new("a", ScheduleBuilder::every_secs(2)),
+        ];
+        assert!(validate_tasks(&tasks).is_err());
+    }
 }
@@ -0,0 +1,316 @@
+//! Shutdown controller.
+
+use anyhow::Result;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::sync::{Mutex, Notify};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+/// Boxed future returned by a flush hook.
+type FlushFuture = Pin<Box<dyn Future<Output = Result<()>> + Send>>;
+
+/// Hook run once background work has stopped.
+type FlushHook = Box<dyn FnOnce() -> FlushFuture + Send>;
+
+/// Configuration for shutdown.
+#[derive(Debug, Clone)]
+pub struct ShutdownConfig {
+    /// How long background tasks may take to finish their current cycle, in seconds.
+    pub task_timeout_secs: u64,
+    /// How long in-flight transactions may take to confirm, in seconds.
+    pub drain_timeout_secs: u64,
+}
+
+impl Default for ShutdownConfig {
+    fn default() -> Self {
+        Self {
+            task_timeout_secs: 30,
+            drain_timeout_secs: 90,
+        }
+    }
+}
+
+/// Counts transactions that were sent but are not yet confirmed.
+#[derive(Debug, Clone, Default)]
+pub struct InFlightTracker {
+    inner: Arc<InFlightInner>,
+}
+
+#[derive(Debug, Default)]
+struct InFlightInner {
+    count: AtomicUsize,
+    idle: Notify,
+}
+
+impl InFlightTracker {
+    /// Creates an empty tracker.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Marks a transaction as in flight until the guard is dropped.
+    #[must_use]
+    pub fn begin(&self) -> InFlightGuard {
+        self.inner.count.fetch_add(1, Ordering::SeqCst);
+        InFlightGuard {
+            inner: self.inner.clone(),
+        }
+    }
+
+    /// Returns the number of transactions in flight.
+    #[must_use]
+    pub fn count(&self) -> usize {
+        self.inner.count.load(Ordering::SeqCst)
+    }
+
+    /// Waits until no transaction is in flight.
+    pub async fn wait_idle(&self) {
+        loop {
+            let idle = self.inner.idle.notified();
+            if self.count() == 0 {
+                return;
+            }
+            idle.await;
+        }
+    }
+}
+
+/// Keeps a transaction counted as in flight while alive.
+#[derive(Debug)]
+pub struct InFlightGuard {
+    inner: Arc<InFlightInner>,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if self.inner.count.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.inner.idle.notify_waiters();
+        }
+    }
+}
+
+/// Outcome of a shutdown.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShutdownReport {
+    /// Tasks that stopped within the timeout.
+    pub stopped_tasks: Vec<String>,
+    /// Tasks aborted after overrunning the timeout.
+    pub aborted_tasks: Vec<String>,
+    /// Transactions still unconfirmed when the drain timed out.
+    pub abandoned_transactions: usize,
+    /// Flush hooks that failed.
+    pub failed_flushes: Vec<String>,
+}
+
+impl ShutdownReport {
+    /// Returns true if everything stopped, confirmed and flushed in time.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.aborted_tasks.is_empty()
+            && self.abandoned_transactions == 0
+            && self.failed_flushes.is_empty()
+    }
+}
+
+/// Coordinates shutdown across monitor, scheduler, sync and executor loops.
+pub struct ShutdownController {
+    /// Cancelled when shutdown begins.
+    token: CancellationToken,
+    /// Transactions awaiting confirmation.
+    in_flight: InFlightTracker,
+    /// Background tasks by name.
+    tasks: Mutex<Vec<(String, JoinHandle<()>)>>,
+    /// Flush hooks by name, run in registration order.
+    flush_hooks: Mutex<Vec<(String, FlushHook)>>,
+    /// Configuration.
+    config: ShutdownConfig,
+}
+
+impl ShutdownController {
+    /// Creates a new shutdown controller.
+    pub fn new(config: ShutdownConfig) -> Self {
+        Self {
+            token: CancellationToken::new(),
+            in_flight: InFlightTracker::new(),
+            tasks: Mutex::new(Vec::new()),
+            flush_hooks: Mutex::new(Vec::new()),
+            config,
+        }
+    }
+
+    /// Returns the token loops watch to stop taking new work.
+    #[must_use]
+    pub fn token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+
+    /// Returns the tracker transaction managers report in-flight work to.
+    #[must_use]
+    pub fn in_flight(&self) -> InFlightTracker {
+        self.in_flight.clone()
+    }
+
+    /// Returns true once shutdown has begun.
+    #[must_use]
+    pub fn is_shutting_down(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// Spawns a background task that is joined on shutdown.
+    ///
+    /// The task should exit when [`Self::token`] is cancelled.
+    pub async fn spawn<F>(&self, name: impl Into<String>, task: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let name = name.into();
+        info!(task = %name, "Spawning background task");
+        let mut tasks = self.tasks.lock().await;
+        tasks.retain(|(_, handle)| !handle.is_finished());
+        tasks.push((name, tokio::spawn(task)));
+    }
+
+    /// Registers a hook that persists state after background work stops.
+    pub async fn on_flush<F, Fut>(&self, name: impl Into<String>, hook: F)
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.flush_hooks
+            .lock()
+            .await
+            .push((name.into(), Box::new(move || Box::pin(hook()))));
+    }
+
+    /// Shuts down all registered components.
+    ///
+    /// Only the first call does any work; later calls return an empty report.
+    pub async fn shutdown(&self) -> ShutdownReport {
+        let mut report = ShutdownReport::default();
+        if self.token.is_cancelled() {
+            return report;
+        }
+
+        info!("Shutting down: stopping intake");
+        self.token.cancel();
+
+        let task_timeout = Duration::from_secs(self.config.task_timeout_secs);
+        let tasks = std::mem::take(&mut *self.tasks.lock().await);
+        for (name, mut handle) in tasks {
+            match tokio::time::timeout(task_timeout, &mut handle).await {
+                Ok(Ok(())) => report.stopped_tasks.push(name),
+                Ok(Err(e)) => {
+                    warn!(task = %name, error = %e, "Background task ended abnormally");
+                    report.stopped_tasks.push(name);
+                }
+                Err(_) => {
+                    warn!(task = %name, "Background task did not stop in time, aborting");
+                    handle.abort();
+                    report.aborted_tasks.push(name);
+                }
+            }
+        }
+
+        let in_flight = self.in_flight.count();
+        if in_flight > 0 {
+            info!(in_flight, "Waiting for in-flight transactions to confirm");
+            let drain_timeout = Duration::from_secs(self.config.drain_timeout_secs);
+            if tokio::time::timeout(drain_timeout, self.in_flight.wait_idle())
+                .await
+                .is_err()
+            {
+                report.abandoned_transactions = self.in_flight.count();
+                warn!(
+                    abandoned = report.abandoned_transactions,
+                    "Transactions still unconfirmed at shutdown"
+                );
+            }
+        }
+
+        let hooks = std::mem::take(&mut *self.flush_hooks.lock().await);
+        for (name, hook) in hooks {
+            if let Err(e) = hook().await {
+                error!(hook = %name, error = %e, "Flush failed");
+                report.failed_flushes.push(name);
+            }
+        }
+
+        info!(clean = report.is_clean(), "Shutdown complete");
+        report
+    }
+}
+
+impl Default for ShutdownController {
+    fn default() -> Self {
+        Self::new(ShutdownConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicBool;
+
+    #[tokio::test]
+    async fn test_shutdown_phases() {
+        let controller = ShutdownController::default();
+        let tracker = controller.in_flight();
+
+        // A transaction confirming shortly after shutdown begins
+        let guard = tracker.begin();
+        let token = controller.token();
+        controller
+            .spawn("loop", async move {
+                token.cancelled().await;
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                drop(guard);
+            })
+            .await;
+
+        let flushed = Arc::new(AtomicBool::new(false));
+        let flag = flushed.clone();
+        controller
+            .on_flush("state", move || async move {
+                flag.store(true, Ordering::SeqCst);
+                Ok(())
+            })
+            .await;
+        controller
+            .on_flush("broken", || async { Err(anyhow::anyhow!("disk full")) })
+            .await;
+
+        let report = controller.shutdown().await;
+        assert!(controller.is_shutting_down());
+        assert_eq!(report.stopped_tasks, vec!["loop".to_string()]);
+        assert_eq!(report.abandoned_transactions, 0);
+        assert_eq!(report.failed_flushes, vec!["broken".to_string()]);
+        assert!(flushed.load(Ordering::SeqCst));
+        assert_eq!(tracker.count(), 0);
+
+        // Second call is a no-op
+        assert_eq!(controller.shutdown().await, ShutdownReport::default());
+    }
+
+    #[tokio::test]
+    async fn test_overrunning_work_is_reported() {
+        let controller = ShutdownController::new(ShutdownConfig {
+            task_timeout_secs: 0,
+            drain_timeout_secs: 0,
+        });
+        let _stuck = controller.in_flight().begin();
+        controller
+            .spawn("stuck", std::future::pending::<()>())
+            .await;
+
+        let report = controller.shutdown().await;
+        assert_eq!(report.aborted_tasks, vec!["stuck".to_string()]);
+        assert_eq!(report.abandoned_transactions, 1);
+        assert!(!report.is_clean());
+    }
+}
@@ -0,0 +1,13 @@
+//! Coordinated shutdown of background components.
+//!
+//! Shutdown runs in phases:
+//! - Intake stops: the shared token is cancelled and every loop exits after
+//!   finishing its current cycle
+//! - Background tasks are joined, aborting those that overrun the timeout
+//! - In-flight transactions are given time to confirm
+//! - Registered flush hooks persist state before the process exits
+
+mod controller;
+
+pub use controller::*;
+pub use tokio_util::sync::CancellationToken;
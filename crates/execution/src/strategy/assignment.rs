@@ -0,0 +1,222 @@
+//! Per-position strategy assignment.
+//!
+//! A position can be assigned a named strategy plus parameter overrides, so
+//! a stable pair and a volatile pair can run different rules in the same
+//! executor. Unassigned positions use the executor's default configuration.
+
+use super::DecisionConfig;
+use clmm_lp_domain::value_objects::range_spec::RangeSpec;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Decision parameters that override a base configuration.
+///
+/// Percentages are fractions (0.05 = 5%), as in [`DecisionConfig`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DecisionOverrides {
+    /// IL threshold for rebalancing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub il_rebalance_threshold: Option<Decimal>,
+    /// IL threshold for closing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub il_close_threshold: Option<Decimal>,
+    /// Minimum time between rebalances in hours.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_rebalance_interval_hours: Option<u64>,
+    /// Range width for new positions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range_width_pct: Option<Decimal>,
+    /// Range specification for new positions, e.g. "5%" or "2sigma".
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "range_spec_str::serialize",
+        deserialize_with = "range_spec_str::deserialize"
+    )]
+    pub range_spec: Option<RangeSpec>,
+    /// Volatility over the holding horizon.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volatility: Option<Decimal>,
+    /// Whether to auto-collect fees.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_collect_fees: Option<bool>,
+    /// Minimum fees to collect in USD.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_fees_to_collect: Option<Decimal>,
+    /// Health score (0-100) below which to rebalance.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_health_score: Option<Decimal>,
+    /// Whether to pause rebalancing while the market is trending.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pause_on_trend: Option<bool>,
+    /// Trend strength at or above which the market counts as trending.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trend_threshold: Option<Decimal>,
+    /// Range width used while paused.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paused_range_width_pct: Option<Decimal>,
+}
+
+impl DecisionOverrides {
+    /// Returns true if no parameter is overridden.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Returns `base` with the overridden parameters replaced.
+    #[must_use]
+    pub fn apply(&self, base: &DecisionConfig) -> DecisionConfig {
+        let mut config = base.clone();
+        if let Some(v) = self.il_rebalance_threshold {
+            config.il_rebalance_threshold = v;
+        }
+        if let Some(v) = self.il_close_threshold {
+            config.il_close_threshold = v;
+        }
+        if let Some(v) = self.min_rebalance_interval_hours {
+            config.min_rebalance_interval_hours = v;
+        }
+        if let Some(v) = self.range_width_pct {
+            config.range_width_pct = v;
+        }
+        if let Some(v) = &self.range_spec {
+            config.range_spec = Some(v.clone());
+        }
+        if let Some(v) = self.volatility {
+            config.volatility = Some(v);
+        }
+        if let Some(v) = self.auto_collect_fees {
+            config.auto_collect_fees = v;
+        }
+        if let Some(v) = self.min_fees_to_collect {
+            config.min_fees_to_collect = v;
+        }
+        if let Some(v) = self.min_health_score {
+            config.min_health_score = Some(v);
+        }
+        if let Some(v) = self.pause_on_trend {
+            config.pause_on_trend = v;
+        }
+        if let Some(v) = self.trend_threshold {
+            config.trend_threshold = v;
+        }
+        if let Some(v) = self.paused_range_width_pct {
+            config.paused_range_width_pct = v;
+        }
+        config
+    }
+}
+
+/// Strategy assigned to a single position.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PositionStrategy {
+    /// Name of the strategy the position runs.
+    pub name: String,
+    /// Parameter overrides on top of the named strategy.
+    #[serde(default)]
+    pub overrides: DecisionOverrides,
+}
+
+impl PositionStrategy {
+    /// Creates an assignment to a named strategy without overrides.
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            overrides: DecisionOverrides::default(),
+        }
+    }
+
+    /// Sets the parameter overrides.
+    #[must_use]
+    pub fn with_overrides(mut self, overrides: DecisionOverrides) -> Self {
+        self.overrides = overrides;
+        self
+    }
+
+    /// Resolves the decision configuration for this assignment.
+    ///
+    /// Uses the named strategy from `strategies` as the base, falling back to
+    /// `default` for unknown names.
+    #[must_use]
+    pub fn resolve(
+        &self,
+        strategies: &HashMap<String, DecisionConfig>,
+        default: &DecisionConfig,
+    ) -> DecisionConfig {
+        self.overrides
+            .apply(strategies.get(&self.name).unwrap_or(default))
+    }
+}
+
+/// Serializes range specs in their string form ("5%", "2sigma", ...).
+mod range_spec_str {
+    use super::RangeSpec;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        spec: &Option<RangeSpec>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match spec {
+            Some(spec) => serializer.collect_str(spec),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<RangeSpec>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|s| s.parse().map_err(serde::de::Error::custom))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_uses_named_base_and_overrides() {
+        let stable = DecisionConfig {
+            range_width_pct: Decimal::new(2, 2),
+            ..Default::default()
+        };
+        let strategies = HashMap::from([("stable".to_string(), stable)]);
+        let default = DecisionConfig::default();
+
+        let assignment = PositionStrategy::new("stable").with_overrides(DecisionOverrides {
+            min_rebalance_interval_hours: Some(4),
+            ..Default::default()
+        });
+        let config = assignment.resolve(&strategies, &default);
+        assert_eq!(config.range_width_pct, Decimal::new(2, 2));
+        assert_eq!(config.min_rebalance_interval_hours, 4);
+
+        // Unknown names fall back to the default configuration
+        let config = PositionStrategy::new("missing").resolve(&strategies, &default);
+        assert_eq!(config.range_width_pct, default.range_width_pct);
+    }
+
+    #[test]
+    fn test_overrides_serde_round_trip() {
+        let json = serde_json::json!({
+            "il_rebalance_threshold": "0.08",
+            "range_spec": "2sigma",
+            "pause_on_trend": true
+        });
+        let overrides: DecisionOverrides = serde_json::from_value(json).unwrap();
+        assert_eq!(overrides.range_spec, Some("2sigma".parse().unwrap()));
+        assert!(!overrides.is_empty());
+
+        let value = serde_json::to_value(&overrides).unwrap();
+        assert_eq!(value["range_spec"], "±2σ");
+        assert!(value.get("volatility").is_none());
+
+        let unknown = serde_json::json!({ "not_a_field": 1 });
+        assert!(serde_json::from_value::<DecisionOverrides>(unknown).is_err());
+    }
+}
@@ -1,13 +1,64 @@
 //! Decision engine for strategy execution.
 
 use super::Decision;
+use super::oracle::price_divergence;
+use super::regime::RegimeSignal;
 use crate::monitor::MonitoredPosition;
+use clmm_lp_domain::value_objects::range_spec::RangeSpec;
 use clmm_lp_protocols::prelude::WhirlpoolState;
 use rust_decimal::Decimal;
-use tracing::debug;
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+/// Configuration for scaling position size with market risk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LiquidityScalingConfig {
+    /// Per-sample return volatility at which the risk score reaches 1.
+    pub max_volatility: Decimal,
+    /// Fee APR that justifies full size at a risk score of 1.
+    pub min_fee_apr: Decimal,
+    /// Fraction of full size deployed when risk-off.
+    pub risk_off_fraction: Decimal,
+    /// Relative size difference ignored to avoid churn.
+    pub tolerance: Decimal,
+}
+
+impl Default for LiquidityScalingConfig {
+    fn default() -> Self {
+        Self {
+            max_volatility: Decimal::new(1, 2),    // 1% per sample
+            min_fee_apr: Decimal::new(20, 2),      // 20%
+            risk_off_fraction: Decimal::new(5, 1), // 50%
+            tolerance: Decimal::new(10, 2),        // 10%
+        }
+    }
+}
+
+impl LiquidityScalingConfig {
+    /// Returns the risk score: volatility relative to `max_volatility`.
+    #[must_use]
+    pub fn risk_score(&self, signal: &RegimeSignal) -> Decimal {
+        if self.max_volatility <= Decimal::ZERO {
+            return Decimal::ZERO;
+        }
+        signal.volatility / self.max_volatility
+    }
+
+    /// Returns true if fees do not compensate for the current risk.
+    ///
+    /// Risk-off once the risk score reaches 1, unless fee APR keeps pace with
+    /// `min_fee_apr` scaled by the risk score.
+    #[must_use]
+    pub fn is_risk_off(&self, risk_score: Decimal, fee_apr: Decimal) -> bool {
+        risk_score >= Decimal::ONE && fee_apr < self.min_fee_apr * risk_score
+    }
+}
 
 /// Configuration for the decision engine.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct DecisionConfig {
     /// IL threshold for rebalancing (as percentage).
     pub il_rebalance_threshold: Decimal,
@@ -17,10 +68,29 @@ pub struct DecisionConfig {
     pub min_rebalance_interval_hours: u64,
     /// Range width for new positions (as percentage).
     pub range_width_pct: Decimal,
+    /// Range specification for new positions; overrides `range_width_pct` when set.
+    pub range_spec: Option<RangeSpec>,
+    /// Volatility over the holding horizon, used by std-dev range specs.
+    pub volatility: Option<Decimal>,
     /// Whether to auto-collect fees.
     pub auto_collect_fees: bool,
     /// Minimum fees to collect in USD.
     pub min_fees_to_collect: Decimal,
+    /// Health score (0-100) below which to rebalance, if enabled.
+    pub min_health_score: Option<Decimal>,
+    /// Number of recent prices used to detect the market regime.
+    pub trend_lookback: usize,
+    /// Trend strength (0-1) at or above which the market counts as trending.
+    pub trend_threshold: Decimal,
+    /// Whether to pause rebalancing while the market is trending.
+    pub pause_on_trend: bool,
+    /// Range width used while paused (as percentage).
+    pub paused_range_width_pct: Decimal,
+    /// Scale position size up or down with risk, if enabled.
+    pub liquidity_scaling: Option<LiquidityScalingConfig>,
+    /// Maximum divergence of the pool price from the oracle price (as
+    /// percentage) before actions are held, if enabled.
+    pub max_oracle_divergence_pct: Option<Decimal>,
 }
 
 impl Default for DecisionConfig {
@@ -30,12 +100,70 @@ impl Default for DecisionConfig {
             il_close_threshold: Decimal::new(15, 2),    // 15%
             min_rebalance_interval_hours: 24,
             range_width_pct: Decimal::new(10, 2), // 10%
+            range_spec: None,
+            volatility: None,
             auto_collect_fees: true,
             min_fees_to_collect: Decimal::new(10, 0), // $10
+            min_health_score: None,
+            trend_lookback: 24,
+            trend_threshold: Decimal::new(6, 1),
+            pause_on_trend: false,
+            paused_range_width_pct: Decimal::new(30, 2), // 30%
+            liquidity_scaling: None,
+            max_oracle_divergence_pct: Some(Decimal::new(5, 2)), // 5%
         }
     }
 }
 
+impl DecisionConfig {
+    /// Checks that thresholds are consistent.
+    ///
+    /// # Errors
+    /// Returns an error describing the first invalid setting.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.il_rebalance_threshold <= Decimal::ZERO {
+            anyhow::bail!("IL rebalance threshold must be positive");
+        }
+        if self.il_close_threshold < self.il_rebalance_threshold {
+            anyhow::bail!("IL close threshold must not be below the rebalance threshold");
+        }
+        for (name, width) in [
+            ("Range width", self.range_width_pct),
+            ("Paused range width", self.paused_range_width_pct),
+        ] {
+            if width <= Decimal::ZERO || width >= Decimal::ONE {
+                anyhow::bail!("{} must be between 0 and 1", name);
+            }
+        }
+        if self.min_fees_to_collect < Decimal::ZERO {
+            anyhow::bail!("Minimum fees to collect must not be negative");
+        }
+        if let Some(score) = self.min_health_score
+            && !(Decimal::ZERO..=Decimal::ONE_HUNDRED).contains(&score)
+        {
+            anyhow::bail!("Minimum health score must be between 0 and 100");
+        }
+        if self.trend_lookback < 2 {
+            anyhow::bail!("Trend lookback needs at least 2 prices");
+        }
+        if self.trend_threshold < Decimal::ZERO || self.trend_threshold > Decimal::ONE {
+            anyhow::bail!("Trend threshold must be between 0 and 1");
+        }
+        if let Some(scaling) = &self.liquidity_scaling
+            && (scaling.risk_off_fraction < Decimal::ZERO
+                || scaling.risk_off_fraction > Decimal::ONE)
+        {
+            anyhow::bail!("Risk-off fraction must be between 0 and 1");
+        }
+        if let Some(divergence) = self.max_oracle_divergence_pct
+            && divergence <= Decimal::ZERO
+        {
+            anyhow::bail!("Maximum oracle divergence must be positive");
+        }
+        Ok(())
+    }
+}
+
 /// Context for making decisions.
 #[derive(Debug, Clone)]
 pub struct DecisionContext {
@@ -45,6 +173,28 @@ pub struct DecisionContext {
     pub pool: WhirlpoolState,
     /// Hours since last rebalance.
     pub hours_since_rebalance: u64,
+    /// Recent pool prices, oldest first.
+    pub recent_prices: Vec<Decimal>,
+    /// Liquidity of the position at full size; defaults to its current liquidity.
+    pub full_liquidity: Option<u128>,
+    /// Pool price from an independent oracle, in the units of the pool price.
+    pub oracle_price: Option<Decimal>,
+    /// Daily volatility estimated from live prices, used by std-dev range
+    /// specs when the config sets none.
+    pub volatility: Option<Decimal>,
+}
+
+/// A decision together with why it was made, for the audit log.
+#[derive(Debug, Clone)]
+pub struct DecisionOutcome {
+    /// The decision.
+    pub decision: Decision,
+    /// Rule that produced the decision.
+    pub reason: String,
+    /// Market regime at decision time, if enough prices were available.
+    pub regime: Option<RegimeSignal>,
+    /// Divergence from the oracle price that held back an action, if any.
+    pub oracle_divergence: Option<Decimal>,
 }
 
 /// Decision engine for automated strategy execution.
@@ -62,67 +212,254 @@ impl DecisionEngine {
 
     /// Makes a decision for a position.
     pub fn decide(&self, context: &DecisionContext) -> Decision {
+        self.evaluate(context).decision
+    }
+
+    /// Makes a decision for a position and records the rule and regime behind it.
+    ///
+    /// Actions are held while the pool price diverges from the oracle price
+    /// by more than the configured bound, which points at manipulation or a
+    /// broken feed.
+    pub fn evaluate(&self, context: &DecisionContext) -> DecisionOutcome {
+        let outcome = self.evaluate_rules(context);
+        if !outcome.decision.requires_transaction() {
+            return outcome;
+        }
+
+        let divergence = context
+            .oracle_price
+            .and_then(|oracle| price_divergence(context.pool.price, oracle));
+        match (divergence, self.config.max_oracle_divergence_pct) {
+            (Some(divergence), Some(max)) if divergence > max => {
+                warn!(
+                    position = %context.position.address,
+                    pool_price = %context.pool.price,
+                    oracle_price = ?context.oracle_price,
+                    divergence = %divergence,
+                    held = %outcome.decision.description(),
+                    "Pool price diverges from oracle, holding"
+                );
+                DecisionOutcome {
+                    decision: Decision::Hold,
+                    reason: "Pool price diverges from oracle".to_string(),
+                    regime: outcome.regime,
+                    oracle_divergence: Some(divergence),
+                }
+            }
+            _ => outcome,
+        }
+    }
+
+    /// Applies the decision rules in priority order.
+    fn evaluate_rules(&self, context: &DecisionContext) -> DecisionOutcome {
         let position = &context.position;
         let pool = &context.pool;
 
+        let window_start = context
+            .recent_prices
+            .len()
+            .saturating_sub(self.config.trend_lookback);
+        let regime = RegimeSignal::from_prices(
+            &context.recent_prices[window_start..],
+            self.config.trend_threshold,
+        );
+        let outcome = |decision: Decision, reason: &str| DecisionOutcome {
+            decision,
+            reason: reason.to_string(),
+            regime: regime.clone(),
+            oracle_divergence: None,
+        };
+
         debug!(
             position = %position.address,
             in_range = position.in_range,
             il_pct = %position.pnl.il_pct,
+            regime = ?regime.as_ref().map(|r| r.regime),
             "Evaluating position"
         );
 
         // Check for critical IL - close position
         if position.pnl.il_pct.abs() > self.config.il_close_threshold {
             debug!("IL exceeds close threshold, recommending close");
-            return Decision::Close;
+            return outcome(Decision::Close, "IL exceeds close threshold");
         }
 
         // Check for fee collection
         if self.config.auto_collect_fees && position.pnl.fees_usd > self.config.min_fees_to_collect
         {
             debug!("Fees exceed threshold, recommending collection");
-            return Decision::CollectFees;
+            return outcome(Decision::CollectFees, "Fees exceed collection threshold");
         }
 
-        // Check if out of range
-        if !position.in_range {
-            // Check if enough time has passed since last rebalance
-            if context.hours_since_rebalance >= self.config.min_rebalance_interval_hours {
-                let (new_lower, new_upper) = self.calculate_new_range(pool);
-                debug!(
-                    new_lower = new_lower,
-                    new_upper = new_upper,
-                    "Position out of range, recommending rebalance"
+        // Scale size with risk while in range; out-of-range positions are rebalanced first
+        if position.in_range
+            && let Some(decision) = self.scaling_decision(context, regime.as_ref())
+        {
+            return outcome(decision, "Scaling liquidity with risk");
+        }
+
+        let rebalance_allowed =
+            context.hours_since_rebalance >= self.config.min_rebalance_interval_hours;
+
+        // Rebalancing into a strong trend keeps realizing IL; sit in a wide range instead
+        if self.config.pause_on_trend
+            && let Some(signal) = regime.as_ref().filter(|r| r.regime.is_trending())
+        {
+            let (wide_lower, wide_upper) = clmm_lp_protocols::prelude::calculate_tick_range(
+                pool.tick_current,
+                self.config.paused_range_width_pct,
+                pool.tick_spacing,
+            );
+            let current_width = position.on_chain.tick_upper - position.on_chain.tick_lower;
+
+            if !position.in_range && rebalance_allowed && current_width < wide_upper - wide_lower {
+                info!(
+                    position = %position.address,
+                    trend_strength = %signal.trend_strength,
+                    regime = %signal.regime,
+                    "Strong trend, moving to wide range"
+                );
+                return outcome(
+                    Decision::Rebalance {
+                        new_tick_lower: wide_lower,
+                        new_tick_upper: wide_upper,
+                    },
+                    "Strong trend, moving to wide range",
                 );
-                return Decision::Rebalance {
+            }
+
+            debug!(
+                trend_strength = %signal.trend_strength,
+                regime = %signal.regime,
+                "Rebalancing paused during strong trend"
+            );
+            return outcome(Decision::Hold, "Rebalancing paused during strong trend");
+        }
+
+        // Check if out of range
+        if !position.in_range && rebalance_allowed {
+            let (new_lower, new_upper) = self.calculate_new_range(pool, context.volatility);
+            debug!(
+                new_lower = new_lower,
+                new_upper = new_upper,
+                "Position out of range, recommending rebalance"
+            );
+            return outcome(
+                Decision::Rebalance {
                     new_tick_lower: new_lower,
                     new_tick_upper: new_upper,
-                };
-            }
+                },
+                "Position out of range",
+            );
         }
 
-        // Check for IL-based rebalancing
-        if position.pnl.il_pct.abs() > self.config.il_rebalance_threshold
-            && context.hours_since_rebalance >= self.config.min_rebalance_interval_hours
+        // Check for health-based rebalancing
+        if let (Some(threshold), Some(score)) =
+            (self.config.min_health_score, position.health_score())
+            && score < threshold
+            && rebalance_allowed
         {
-            let (new_lower, new_upper) = self.calculate_new_range(pool);
+            let (new_lower, new_upper) = self.calculate_new_range(pool, context.volatility);
+            debug!(
+                health_score = %score,
+                "Health score below threshold, recommending rebalance"
+            );
+            return outcome(
+                Decision::Rebalance {
+                    new_tick_lower: new_lower,
+                    new_tick_upper: new_upper,
+                },
+                "Health score below threshold",
+            );
+        }
+
+        // Check for IL-based rebalancing
+        if position.pnl.il_pct.abs() > self.config.il_rebalance_threshold && rebalance_allowed {
+            let (new_lower, new_upper) = self.calculate_new_range(pool, context.volatility);
             debug!(
                 il_pct = %position.pnl.il_pct,
                 "IL exceeds threshold, recommending rebalance"
             );
-            return Decision::Rebalance {
-                new_tick_lower: new_lower,
-                new_tick_upper: new_upper,
-            };
+            return outcome(
+                Decision::Rebalance {
+                    new_tick_lower: new_lower,
+                    new_tick_upper: new_upper,
+                },
+                "IL exceeds rebalance threshold",
+            );
         }
 
         // Default: hold
-        Decision::Hold
+        outcome(Decision::Hold, "No rule triggered")
+    }
+
+    /// Returns a liquidity change if the position is away from its risk-adjusted size.
+    fn scaling_decision(
+        &self,
+        context: &DecisionContext,
+        regime: Option<&RegimeSignal>,
+    ) -> Option<Decision> {
+        let scaling = self.config.liquidity_scaling.as_ref()?;
+        let signal = regime?;
+        let position = &context.position;
+
+        let current = Decimal::from(position.on_chain.liquidity);
+        let full = Decimal::from(
+            context
+                .full_liquidity
+                .unwrap_or(position.on_chain.liquidity)
+                .max(position.on_chain.liquidity),
+        );
+        let risk_score = scaling.risk_score(signal);
+        let risk_off = scaling.is_risk_off(risk_score, position.pnl.fee_apr);
+        let target = if risk_off {
+            (full * scaling.risk_off_fraction).floor()
+        } else {
+            full
+        };
+
+        let band = target * scaling.tolerance;
+        let decision = if current > target + band {
+            Decision::DecreaseLiquidity {
+                amount: current - target,
+            }
+        } else if current + band < target {
+            Decision::IncreaseLiquidity {
+                amount: target - current,
+            }
+        } else {
+            return None;
+        };
+
+        info!(
+            position = %position.address,
+            risk_score = %risk_score,
+            fee_apr = %position.pnl.fee_apr,
+            risk_off = risk_off,
+            target_liquidity = target.to_u128().unwrap_or_default(),
+            "Scaling liquidity with risk"
+        );
+        Some(decision)
     }
 
     /// Calculates a new range centered on current price.
-    fn calculate_new_range(&self, pool: &WhirlpoolState) -> (i32, i32) {
+    fn calculate_new_range(
+        &self,
+        pool: &WhirlpoolState,
+        live_volatility: Option<Decimal>,
+    ) -> (i32, i32) {
+        if let Some(spec) = &self.config.range_spec {
+            let volatility = self.config.volatility.or(live_volatility);
+            match spec.to_ticks(pool.price, volatility, pool.tick_spacing) {
+                Ok(ticks) => return ticks,
+                Err(e) => warn!(
+                    spec = %spec,
+                    error = e,
+                    "Failed to resolve range spec, falling back to width"
+                ),
+            }
+        }
+
         clmm_lp_protocols::prelude::calculate_tick_range(
             pool.tick_current,
             self.config.range_width_pct,
@@ -158,6 +495,7 @@ mod tests {
         let position = MonitoredPosition {
             address: Pubkey::new_unique(),
             pool: Pubkey::new_unique(),
+            protocol: clmm_lp_protocols::prelude::Protocol::OrcaWhirlpool,
             on_chain: clmm_lp_protocols::prelude::OnChainPosition {
                 address: Pubkey::new_unique(),
                 pool: Pubkey::new_unique(),
@@ -169,12 +507,22 @@ mod tests {
                 fee_growth_inside_b: 0,
                 fees_owed_a: 0,
                 fees_owed_b: 0,
+                reward_growth_inside: [0; 3],
+                rewards_owed: [0; 3],
             },
             pnl: PositionPnL {
                 il_pct,
                 ..Default::default()
             },
             in_range,
+            tick_current: 0,
+            range_checks: 0,
+            in_range_checks: 0,
+            health: None,
+            strategy: None,
+            tags: Vec::new(),
+            read_only: false,
+            stale_since: None,
             last_updated: chrono::Utc::now(),
         };
 
@@ -187,16 +535,21 @@ mod tests {
             sqrt_price: 1 << 64,
             price: Decimal::ONE,
             liquidity: 1000000,
-            fee_rate_bps: 30,
+            fee_rate: 30,
             protocol_fee_rate_bps: 0,
             fee_growth_global_a: 0,
             fee_growth_global_b: 0,
+            rewards: Vec::new(),
         };
 
         DecisionContext {
             position,
             pool,
             hours_since_rebalance: 48,
+            recent_prices: Vec::new(),
+            full_liquidity: None,
+            oracle_price: None,
+            volatility: None,
         }
     }
 
@@ -218,6 +571,41 @@ mod tests {
         assert!(matches!(decision, Decision::Rebalance { .. }));
     }
 
+    #[test]
+    fn test_rebalance_on_low_health() {
+        let engine = DecisionEngine::new(DecisionConfig {
+            min_health_score: Some(Decimal::new(50, 0)),
+            ..Default::default()
+        });
+        let mut context = create_test_context(true, Decimal::ZERO);
+        context.position.health = Some(crate::monitor::HealthScore {
+            score: Decimal::new(30, 0),
+            components: Default::default(),
+            computed_at: chrono::Utc::now(),
+        });
+
+        let decision = engine.decide(&context);
+        assert!(matches!(decision, Decision::Rebalance { .. }));
+    }
+
+    #[test]
+    fn test_rebalance_with_range_spec() {
+        let engine = DecisionEngine::new(DecisionConfig {
+            range_spec: Some(RangeSpec::Ticks(100)),
+            ..Default::default()
+        });
+        let context = create_test_context(false, Decimal::ZERO);
+
+        let decision = engine.decide(&context);
+        assert!(matches!(
+            decision,
+            Decision::Rebalance {
+                new_tick_lower: -128,
+                new_tick_upper: 128
+            }
+        ));
+    }
+
     #[test]
     fn test_close_on_high_il() {
         let engine = DecisionEngine::default();
@@ -226,4 +614,121 @@ mod tests {
         let decision = engine.decide(&context);
         assert!(matches!(decision, Decision::Close));
     }
+
+    #[test]
+    fn test_trend_pauses_rebalancing() {
+        let engine = DecisionEngine::new(DecisionConfig {
+            pause_on_trend: true,
+            ..Default::default()
+        });
+        let trending: Vec<Decimal> = (0..10).map(|i| Decimal::from(100 + i)).collect();
+
+        // Narrow range out of range: move once to the wide range
+        let mut context = create_test_context(false, Decimal::ZERO);
+        context.recent_prices = trending.clone();
+        let outcome = engine.evaluate(&context);
+        let Decision::Rebalance {
+            new_tick_lower,
+            new_tick_upper,
+        } = outcome.decision
+        else {
+            panic!("expected rebalance, got {:?}", outcome.decision);
+        };
+        assert!(new_tick_upper - new_tick_lower > 2000);
+        assert!(outcome.regime.unwrap().regime.is_trending());
+
+        // Already wide: hold even though out of range
+        context.position.on_chain.tick_lower = new_tick_lower;
+        context.position.on_chain.tick_upper = new_tick_upper;
+        let outcome = engine.evaluate(&context);
+        assert!(matches!(outcome.decision, Decision::Hold));
+        assert_eq!(outcome.reason, "Rebalancing paused during strong trend");
+
+        // Choppy prices: normal rebalancing resumes
+        context.recent_prices = [100, 103, 99, 102, 98, 101]
+            .iter()
+            .map(|p| Decimal::from(*p))
+            .collect();
+        let outcome = engine.evaluate(&context);
+        assert!(matches!(outcome.decision, Decision::Rebalance { .. }));
+        assert_eq!(outcome.reason, "Position out of range");
+    }
+
+    #[test]
+    fn test_liquidity_scaling_risk_off_and_on() {
+        let engine = DecisionEngine::new(DecisionConfig {
+            liquidity_scaling: Some(LiquidityScalingConfig::default()),
+            ..Default::default()
+        });
+
+        // ~4% swings per sample with low fees: halve the position
+        let mut context = create_test_context(true, Decimal::ZERO);
+        context.recent_prices = [100, 104, 99, 103, 98, 102]
+            .iter()
+            .map(|p| Decimal::from(*p))
+            .collect();
+        let outcome = engine.evaluate(&context);
+        assert!(matches!(
+            outcome.decision,
+            Decision::DecreaseLiquidity { amount } if amount == Decimal::from(500_000)
+        ));
+
+        // Fees high enough to pay for the risk: stay at full size
+        context.position.pnl.fee_apr = Decimal::from(10);
+        assert!(matches!(engine.decide(&context), Decision::Hold));
+
+        // Calm market after scaling down: top back up to full size
+        context.position.pnl.fee_apr = Decimal::ZERO;
+        context.position.on_chain.liquidity = 500_000;
+        context.full_liquidity = Some(1_000_000);
+        context.recent_prices = [100, 100, 101, 101, 101]
+            .iter()
+            .map(|p| Decimal::from(*p))
+            .collect();
+        assert!(matches!(
+            engine.decide(&context),
+            Decision::IncreaseLiquidity { amount } if amount == Decimal::from(500_000)
+        ));
+    }
+
+    #[test]
+    fn test_oracle_divergence_holds_action() {
+        let engine = DecisionEngine::default();
+        let mut context = create_test_context(false, Decimal::ZERO);
+
+        // Within 5% of the oracle: rebalance goes ahead
+        context.oracle_price = Some(Decimal::new(102, 2));
+        let outcome = engine.evaluate(&context);
+        assert!(matches!(outcome.decision, Decision::Rebalance { .. }));
+        assert!(outcome.oracle_divergence.is_none());
+
+        // 20% away: hold and report the divergence
+        context.oracle_price = Some(Decimal::new(125, 2));
+        let outcome = engine.evaluate(&context);
+        assert!(matches!(outcome.decision, Decision::Hold));
+        assert_eq!(outcome.oracle_divergence, Some(Decimal::new(2, 1)));
+    }
+
+    #[test]
+    fn test_validate_config() {
+        assert!(DecisionConfig::default().validate().is_ok());
+
+        let inverted = DecisionConfig {
+            il_close_threshold: Decimal::new(1, 2),
+            ..Default::default()
+        };
+        assert!(inverted.validate().is_err());
+
+        let too_wide = DecisionConfig {
+            range_width_pct: Decimal::ONE,
+            ..Default::default()
+        };
+        assert!(too_wide.validate().is_err());
+
+        // Partial documents fill in defaults
+        let parsed: DecisionConfig =
+            serde_json::from_str(r#"{"il_rebalance_threshold": "0.08"}"#).unwrap();
+        assert_eq!(parsed.il_rebalance_threshold, Decimal::new(8, 2));
+        assert_eq!(parsed.trend_lookback, 24);
+    }
 }
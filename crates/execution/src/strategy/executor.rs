@@ -1,18 +1,28 @@
 //! Strategy executor for automated position management.
 
 use super::{
-    Decision, DecisionConfig, DecisionContext, DecisionEngine, RebalanceConfig, RebalanceExecutor,
-    RebalanceParams,
+    ActionQueue, Decision, DecisionConfig, DecisionContext, DecisionEngine, DecisionKind,
+    InFlightOperation, InFlightRegistry, QueuedAction, RebalanceConfig, RebalanceExecutor,
+    RebalanceParams, ReferencePrice, RegimeSignal,
 };
-use crate::emergency::CircuitBreaker;
+use crate::alerts::{Alert, AlertLevel, AlertType, MultiNotifier};
+use crate::emergency::{CircuitBreaker, DrawdownGuard};
 use crate::lifecycle::{LifecycleTracker, RebalanceReason};
-use crate::monitor::PositionMonitor;
+use crate::monitor::{PoolAnomalyDetector, PositionMonitor};
+use crate::shutdown::CancellationToken;
+use crate::supervisor::Heartbeat;
+use crate::sync::{LiveCandleBuilder, PoolStateCache};
 use crate::transaction::TransactionManager;
 use crate::wallet::Wallet;
 use clmm_lp_protocols::prelude::*;
+use futures::StreamExt;
 use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::{RwLock, broadcast};
 use tokio::time::interval;
 use tracing::{debug, error, info, warn};
 
@@ -29,6 +39,16 @@ pub struct ExecutorConfig {
     pub max_slippage_pct: Decimal,
     /// Dry run mode - simulate but don't execute.
     pub dry_run: bool,
+    /// Maximum age of a position's synced state for its decisions to be
+    /// executed, in seconds.
+    pub max_state_age_secs: u64,
+    /// How long fetched pool states are reused across positions, in seconds.
+    pub pool_cache_ttl_secs: u64,
+    /// Maximum number of decisions executed at the same time.
+    pub max_concurrent_executions: usize,
+    /// Whether low-priority actions, such as collecting fees, wait while
+    /// higher-priority actions are queued.
+    pub defer_low_priority: bool,
 }
 
 impl Default for ExecutorConfig {
@@ -39,16 +59,70 @@ impl Default for ExecutorConfig {
             require_confirmation: true,
             max_slippage_pct: Decimal::new(5, 3), // 0.5%
             dry_run: false,
+            max_state_age_secs: 120,
+            pool_cache_ttl_secs: 10,
+            max_concurrent_executions: 4,
+            defer_low_priority: true,
         }
     }
 }
 
+impl ExecutorConfig {
+    /// Checks whether state synced at `last_updated` is recent enough to act on.
+    #[must_use]
+    pub fn is_fresh(
+        &self,
+        last_updated: chrono::DateTime<chrono::Utc>,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> bool {
+        (now - last_updated).num_seconds() <= self.max_state_age_secs as i64
+    }
+}
+
+/// Maximum number of entries kept in the decision audit log.
+const MAX_DECISION_LOG: usize = 1000;
+
+/// Audit log entry for a decision.
+#[derive(Debug, Clone)]
+pub struct DecisionRecord {
+    /// Position address.
+    pub position: Pubkey,
+    /// The decision made.
+    pub decision: Decision,
+    /// Rule that produced the decision.
+    pub reason: String,
+    /// Strategy assigned to the position, if any.
+    pub strategy: Option<String>,
+    /// Market regime at decision time.
+    pub regime: Option<RegimeSignal>,
+    /// Pool price at decision time.
+    pub price: Decimal,
+    /// Whether execution was skipped because the same kind of decision was
+    /// already in flight for the position.
+    pub deduplicated: bool,
+    /// Timestamp.
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+/// A low-priority action held back while more urgent ones ran.
+#[derive(Debug, Clone)]
+pub struct DeferredAction {
+    /// Position address.
+    pub position: Pubkey,
+    /// The deferred decision.
+    pub decision: Decision,
+    /// When the position's action was first deferred.
+    pub since: chrono::DateTime<chrono::Utc>,
+}
+
 /// Strategy executor for automated position management.
 pub struct StrategyExecutor {
     /// Position monitor.
     monitor: Arc<PositionMonitor>,
-    /// Decision engine.
-    decision_engine: DecisionEngine,
+    /// Decision engine, reconfigurable while running.
+    decision_engine: RwLock<DecisionEngine>,
+    /// Named decision configurations for per-position assignments.
+    strategies: RwLock<HashMap<String, DecisionConfig>>,
     /// Transaction manager.
     #[allow(dead_code)]
     tx_manager: Arc<TransactionManager>,
@@ -64,8 +138,33 @@ pub struct StrategyExecutor {
     config: ExecutorConfig,
     /// Running flag.
     running: std::sync::atomic::AtomicBool,
-    /// Pool reader for fetching state.
-    pool_reader: WhirlpoolReader,
+    /// Pool states shared by positions in the same pool.
+    pool_cache: Arc<PoolStateCache>,
+    /// Recent prices per pool, oldest first.
+    price_history: RwLock<HashMap<Pubkey, VecDeque<Decimal>>>,
+    /// Live candles and the interval read from them, preferred over
+    /// `price_history` once they cover enough of the lookback.
+    candles: Option<(Arc<LiveCandleBuilder>, u64)>,
+    /// Decision audit log, oldest first.
+    decision_log: RwLock<VecDeque<DecisionRecord>>,
+    /// Channel every recorded decision is published on, if any.
+    decision_sender: Option<broadcast::Sender<DecisionRecord>>,
+    /// Largest liquidity seen per position, treated as its full size.
+    full_liquidity: RwLock<HashMap<Pubkey, u128>>,
+    /// Independent price source pool prices are checked against.
+    price_oracle: Option<Arc<dyn ReferencePrice>>,
+    /// Notifier for actions held back by the executor.
+    notifier: MultiNotifier,
+    /// Beaten after every evaluation cycle, if watched.
+    heartbeat: Option<Heartbeat>,
+    /// Portfolio drawdown guard that halts evaluation, if any.
+    drawdown_guard: Option<Arc<DrawdownGuard>>,
+    /// Detector whose paused pools are skipped, if any.
+    anomaly_detector: Option<Arc<PoolAnomalyDetector>>,
+    /// Low-priority actions deferred in the last cycle, by position.
+    deferred: RwLock<HashMap<Pubkey, DeferredAction>>,
+    /// Decisions being executed, possibly shared with other executors.
+    in_flight: Arc<InFlightRegistry>,
 }
 
 impl StrategyExecutor {
@@ -78,7 +177,10 @@ impl StrategyExecutor {
     ) -> Self {
         let lifecycle = Arc::new(LifecycleTracker::new());
         let circuit_breaker = Arc::new(CircuitBreaker::default());
-        let pool_reader = WhirlpoolReader::new(provider.clone());
+        let pool_cache = Arc::new(PoolStateCache::new(
+            provider.clone(),
+            Duration::from_secs(config.pool_cache_ttl_secs),
+        ));
 
         let mut rebalance_executor = RebalanceExecutor::new(
             provider,
@@ -90,7 +192,8 @@ impl StrategyExecutor {
 
         Self {
             monitor,
-            decision_engine: DecisionEngine::default(),
+            decision_engine: RwLock::new(DecisionEngine::default()),
+            strategies: RwLock::new(HashMap::new()),
             tx_manager,
             rebalance_executor,
             circuit_breaker,
@@ -98,7 +201,19 @@ impl StrategyExecutor {
             wallet: None,
             config,
             running: std::sync::atomic::AtomicBool::new(false),
-            pool_reader,
+            pool_cache,
+            price_history: RwLock::new(HashMap::new()),
+            candles: None,
+            decision_log: RwLock::new(VecDeque::new()),
+            decision_sender: None,
+            full_liquidity: RwLock::new(HashMap::new()),
+            price_oracle: None,
+            notifier: MultiNotifier::new(),
+            heartbeat: None,
+            drawdown_guard: None,
+            anomaly_detector: None,
+            deferred: RwLock::new(HashMap::new()),
+            in_flight: Arc::new(InFlightRegistry::new()),
         }
     }
 
@@ -108,9 +223,103 @@ impl StrategyExecutor {
         self.rebalance_executor.set_wallet(wallet);
     }
 
+    /// Requires pool tokens to pass risk screening before positions are opened.
+    pub fn set_risk_checker(&mut self, checker: Arc<TokenRiskChecker>) {
+        self.rebalance_executor.set_risk_checker(checker);
+    }
+
+    /// Checks pool prices against an independent oracle before acting.
+    pub fn set_price_oracle(&mut self, oracle: Arc<dyn ReferencePrice>) {
+        self.price_oracle = Some(oracle);
+    }
+
+    /// Reads market regime and volatility from live candles of the given
+    /// interval instead of prices sampled once per evaluation.
+    pub fn set_candle_builder(&mut self, builder: Arc<LiveCandleBuilder>, interval_secs: u64) {
+        self.candles = Some((builder, interval_secs));
+    }
+
+    /// Sets the notifier for actions held back by the executor.
+    pub fn set_notifier(&mut self, notifier: MultiNotifier) {
+        self.notifier = notifier;
+    }
+
+    /// Publishes every recorded decision on `sender`, e.g. for streaming to
+    /// clients.
+    pub fn set_decision_sender(&mut self, sender: broadcast::Sender<DecisionRecord>) {
+        self.decision_sender = Some(sender);
+    }
+
+    /// Beats `heartbeat` after every evaluation cycle, so a hung loop is
+    /// reported.
+    pub fn set_heartbeat(&mut self, heartbeat: Heartbeat) {
+        self.heartbeat = Some(heartbeat);
+    }
+
+    /// Skips evaluation while `guard` is halted on a portfolio drawdown.
+    pub fn set_drawdown_guard(&mut self, guard: Arc<DrawdownGuard>) {
+        self.drawdown_guard = Some(guard);
+    }
+
+    /// Skips positions in pools `detector` has paused on an anomaly.
+    pub fn set_anomaly_detector(&mut self, detector: Arc<PoolAnomalyDetector>) {
+        self.anomaly_detector = Some(detector);
+    }
+
+    /// Shares an in-flight registry with other executors acting on the same
+    /// positions, so none executes a decision another is still executing.
+    pub fn set_in_flight_registry(&mut self, registry: Arc<InFlightRegistry>) {
+        self.in_flight = registry;
+    }
+
+    /// Gets the registry of decisions being executed.
+    pub fn in_flight(&self) -> &Arc<InFlightRegistry> {
+        &self.in_flight
+    }
+
     /// Sets the decision engine configuration.
     pub fn set_decision_config(&mut self, config: DecisionConfig) {
-        self.decision_engine.set_config(config);
+        self.decision_engine.get_mut().set_config(config);
+    }
+
+    /// Registers a named strategy that positions can be assigned to.
+    pub fn register_strategy(&mut self, name: impl Into<String>, config: DecisionConfig) {
+        self.strategies.get_mut().insert(name.into(), config);
+    }
+
+    /// Replaces the decision configuration while the executor is running.
+    ///
+    /// Takes effect from the next evaluation; breaker, price history and
+    /// decision log are kept.
+    ///
+    /// # Errors
+    /// Returns an error, leaving the configuration unchanged, if it is invalid.
+    pub async fn reload_decision_config(&self, config: DecisionConfig) -> anyhow::Result<()> {
+        config.validate()?;
+        self.decision_engine.write().await.set_config(config);
+        info!("Decision configuration reloaded");
+        Ok(())
+    }
+
+    /// Replaces a named strategy while the executor is running.
+    ///
+    /// # Errors
+    /// Returns an error, leaving the strategy unchanged, if it is invalid.
+    pub async fn reload_strategy(
+        &self,
+        name: impl Into<String>,
+        config: DecisionConfig,
+    ) -> anyhow::Result<()> {
+        config.validate()?;
+        let name = name.into();
+        info!(strategy = %name, "Strategy configuration reloaded");
+        self.strategies.write().await.insert(name, config);
+        Ok(())
+    }
+
+    /// Gets the current decision configuration.
+    pub async fn decision_config(&self) -> DecisionConfig {
+        self.decision_engine.read().await.config().clone()
     }
 
     /// Enables or disables dry run mode.
@@ -119,6 +328,16 @@ impl StrategyExecutor {
         self.rebalance_executor.set_dry_run(dry_run);
     }
 
+    /// Shares a pool state cache, e.g. one invalidated by account updates.
+    pub fn set_pool_cache(&mut self, cache: Arc<PoolStateCache>) {
+        self.pool_cache = cache;
+    }
+
+    /// Gets the pool state cache.
+    pub fn pool_cache(&self) -> &Arc<PoolStateCache> {
+        &self.pool_cache
+    }
+
     /// Gets the circuit breaker.
     pub fn circuit_breaker(&self) -> &Arc<CircuitBreaker> {
         &self.circuit_breaker
@@ -129,8 +348,28 @@ impl StrategyExecutor {
         &self.lifecycle
     }
 
+    /// Gets the low-priority actions deferred in the last cycle because
+    /// more urgent actions were queued.
+    pub async fn deferred_actions(&self) -> Vec<DeferredAction> {
+        self.deferred.read().await.values().cloned().collect()
+    }
+
+    /// Gets the decision audit log, oldest first.
+    pub async fn decision_log(&self) -> Vec<DecisionRecord> {
+        self.decision_log.read().await.iter().cloned().collect()
+    }
+
     /// Starts the strategy execution loop.
     pub async fn start(&self) {
+        self.run_until(CancellationToken::new()).await;
+    }
+
+    /// Runs the strategy execution loop until stopped or `shutdown` is
+    /// cancelled.
+    ///
+    /// An evaluation in progress, including any rebalance it started,
+    /// completes before the loop exits.
+    pub async fn run_until(&self, shutdown: CancellationToken) {
         self.running
             .store(true, std::sync::atomic::Ordering::SeqCst);
 
@@ -145,22 +384,24 @@ impl StrategyExecutor {
         );
 
         while self.running.load(std::sync::atomic::Ordering::SeqCst) {
-            ticker.tick().await;
-
-            // Check circuit breaker
-            if !self.circuit_breaker.is_allowed().await {
-                warn!("Circuit breaker open, skipping evaluation");
-                continue;
+            tokio::select! {
+                () = shutdown.cancelled() => break,
+                _ = ticker.tick() => {}
             }
 
-            if let Err(e) = self.evaluate_all().await {
-                error!(error = %e, "Strategy evaluation failed");
-                self.circuit_breaker.record_failure().await;
-            } else {
-                self.circuit_breaker.record_success().await;
+            self.run_cycle().await;
+
+            if let Some(heartbeat) = &self.heartbeat {
+                heartbeat.beat();
             }
         }
 
+        // Stopping on purpose is not a stall
+        if let Some(heartbeat) = &self.heartbeat {
+            heartbeat.pause();
+        }
+        self.running
+            .store(false, std::sync::atomic::Ordering::SeqCst);
         info!("Strategy executor stopped");
     }
 
@@ -170,62 +411,180 @@ impl StrategyExecutor {
             .store(false, std::sync::atomic::Ordering::SeqCst);
     }
 
-    /// Evaluates all monitored positions.
+    /// Runs one evaluation cycle unless the circuit breaker or drawdown
+    /// guard holds it, recording its outcome with the breaker.
+    async fn run_cycle(&self) {
+        if !self.circuit_breaker.is_allowed().await {
+            warn!("Circuit breaker open, skipping evaluation");
+        } else if self
+            .drawdown_guard
+            .as_ref()
+            .is_some_and(|guard| guard.is_halted())
+        {
+            warn!("Drawdown guard halted, skipping evaluation");
+        } else if let Err(e) = self.evaluate_all().await {
+            error!(error = %e, "Strategy evaluation failed");
+            self.circuit_breaker.record_failure().await;
+        } else {
+            self.circuit_breaker.record_success().await;
+        }
+    }
+
+    /// Evaluates all monitored positions, then executes the actions they
+    /// call for in priority order.
+    ///
+    /// Positions whose pool state is unavailable are marked stale and
+    /// skipped; the rest of the portfolio is still evaluated, and the cycle
+    /// does not count toward the circuit breaker.
     async fn evaluate_all(&self) -> anyhow::Result<()> {
         let positions = self.monitor.get_positions().await;
 
         debug!(count = positions.len(), "Evaluating positions");
 
+        let mut queue = ActionQueue::new();
         for position in positions {
-            if let Err(e) = self.evaluate_position(&position).await {
-                warn!(
+            if position.read_only {
+                debug!(position = %position.address, "Skipping read-only position");
+                continue;
+            }
+            if let Some(detector) = &self.anomaly_detector
+                && detector.is_paused(&position.pool).await
+            {
+                debug!(position = %position.address, "Skipping position in paused pool");
+                continue;
+            }
+            match self.evaluate_position(&position).await {
+                Ok(Some(action)) => queue.push(action),
+                Ok(None) => {}
+                Err(e) => warn!(
                     position = %position.address,
                     error = %e,
                     "Failed to evaluate position"
-                );
+                ),
             }
         }
-
+        self.execute_queue(queue).await;
         Ok(())
     }
 
-    /// Evaluates a single position.
+    /// Evaluates a single position, returning the action to queue if its
+    /// decision is to be executed.
     async fn evaluate_position(
         &self,
         position: &crate::monitor::MonitoredPosition,
-    ) -> anyhow::Result<()> {
-        // Fetch current pool state
-        let pool = self
-            .pool_reader
-            .get_pool_state(&position.pool.to_string())
-            .await
-            .unwrap_or_else(|_| WhirlpoolState {
-                address: position.pool.to_string(),
-                token_mint_a: solana_sdk::pubkey::Pubkey::default(),
-                token_mint_b: solana_sdk::pubkey::Pubkey::default(),
-                tick_current: 0,
-                tick_spacing: 64,
-                sqrt_price: 1 << 64,
-                price: Decimal::ONE,
-                liquidity: 0,
-                fee_rate_bps: 30,
-                protocol_fee_rate_bps: 0,
-                fee_growth_global_a: 0,
-                fee_growth_global_b: 0,
-            });
+    ) -> anyhow::Result<Option<QueuedAction>> {
+        // Without the real pool state there is nothing sound to decide on
+        let pool = match self.pool_cache.get(&position.pool).await {
+            Ok(pool) => {
+                self.mark_recovered(&position.address).await;
+                pool
+            }
+            Err(e) => {
+                self.mark_degraded(position, &e).await;
+                return Ok(None);
+            }
+        };
 
         // Calculate hours since last rebalance from lifecycle
         let hours_since_rebalance = self
             .calculate_hours_since_rebalance(&position.address)
             .await;
 
+        let sampled_prices = self.record_price(&position.pool, pool.price).await;
+        let (recent_prices, volatility) = match self.live_prices(&position.pool).await {
+            Some(live) => live,
+            None => (sampled_prices, None),
+        };
+        let full_liquidity = *self
+            .full_liquidity
+            .write()
+            .await
+            .entry(position.address)
+            .and_modify(|l| *l = (*l).max(position.on_chain.liquidity))
+            .or_insert(position.on_chain.liquidity);
+
+        let oracle_price = match &self.price_oracle {
+            Some(oracle) => match oracle.price(&pool).await {
+                Ok(price) => Some(price),
+                Err(e) => {
+                    warn!(
+                        pool = %position.pool,
+                        oracle = oracle.name(),
+                        error = %e,
+                        "Oracle price unavailable"
+                    );
+                    None
+                }
+            },
+            None => None,
+        };
+
         let context = DecisionContext {
             position: position.clone(),
             pool: pool.clone(),
             hours_since_rebalance,
+            recent_prices,
+            full_liquidity: Some(full_liquidity),
+            oracle_price,
+            volatility,
         };
 
-        let decision = self.decision_engine.decide(&context);
+        let engine = self.decision_engine.read().await;
+        let outcome = match &position.strategy {
+            Some(assignment) => {
+                let strategies = self.strategies.read().await;
+                if !strategies.contains_key(&assignment.name) {
+                    debug!(
+                        position = %position.address,
+                        strategy = %assignment.name,
+                        "Unknown strategy, applying overrides to default config"
+                    );
+                }
+                let config = assignment.resolve(&strategies, engine.config());
+                DecisionEngine::new(config).evaluate(&context)
+            }
+            None => engine.evaluate(&context),
+        };
+        drop(engine);
+        let strategy = position.strategy.as_ref().map(|s| s.name.clone());
+        let decision = outcome.decision.clone();
+
+        if let (Some(divergence), Some(oracle_price)) = (outcome.oracle_divergence, oracle_price) {
+            let alert = Alert::new(
+                AlertLevel::Warning,
+                AlertType::PriceDivergence,
+                format!(
+                    "Pool price {} diverges {:.2}% from oracle price {}; action skipped",
+                    pool.price,
+                    divergence * Decimal::ONE_HUNDRED,
+                    oracle_price
+                ),
+            )
+            .with_position(&position.address)
+            .with_pool(&position.pool);
+            self.notifier.notify_all(&alert).await;
+        }
+
+        info!(
+            position = %position.address,
+            decision = %decision.description(),
+            reason = %outcome.reason,
+            strategy = ?strategy,
+            regime = ?outcome.regime.as_ref().map(|r| r.regime),
+            trend_strength = ?outcome.regime.as_ref().map(|r| r.trend_strength),
+            "Decision made"
+        );
+        self.record_decision(DecisionRecord {
+            position: position.address,
+            decision: outcome.decision,
+            reason: outcome.reason,
+            strategy,
+            regime: outcome.regime,
+            price: pool.price,
+            deduplicated: false,
+            timestamp: chrono::Utc::now(),
+        })
+        .await;
 
         if decision.requires_transaction() {
             info!(
@@ -236,11 +595,197 @@ impl StrategyExecutor {
             );
 
             if self.config.auto_execute {
-                self.execute_decision(position, &decision, &pool).await?;
+                return Ok(Some(QueuedAction::new(position.clone(), decision, pool)));
             }
         }
 
-        Ok(())
+        Ok(None)
+    }
+
+    /// Executes queued actions, highest priority and largest loss at risk
+    /// first, with at most `max_concurrent_executions` in flight.
+    ///
+    /// Low-priority actions are deferred while anything more urgent is
+    /// queued; they are decided again next cycle.
+    async fn execute_queue(&self, queue: ActionQueue) {
+        let (ready, deferred) = queue.into_batches(self.config.defer_low_priority);
+        self.record_deferred(&deferred).await;
+        if ready.is_empty() {
+            return;
+        }
+
+        let max_concurrent = self.config.max_concurrent_executions.max(1);
+        info!(
+            queued = ready.len(),
+            deferred = deferred.len(),
+            max_concurrent,
+            "Executing queued actions"
+        );
+        futures::stream::iter(ready)
+            .map(|action| async move {
+                let kind = DecisionKind::of(&action.decision);
+                let _in_flight = match self.in_flight.try_begin(action.position.address, kind) {
+                    Ok(guard) => guard,
+                    Err(running) => {
+                        self.record_duplicate(&action, &running).await;
+                        return;
+                    }
+                };
+                if let Err(e) = self
+                    .execute_decision(&action.position, &action.decision, &action.pool)
+                    .await
+                {
+                    warn!(
+                        position = %action.position.address,
+                        decision = %action.decision.description(),
+                        priority = ?action.priority,
+                        error = %e,
+                        "Failed to execute decision"
+                    );
+                }
+            })
+            .buffer_unordered(max_concurrent)
+            .for_each(|()| async {})
+            .await;
+    }
+
+    /// Records in the audit log that `action` was skipped because the same
+    /// kind of decision is already executing for its position.
+    async fn record_duplicate(&self, action: &QueuedAction, running: &InFlightOperation) {
+        warn!(
+            position = %action.position.address,
+            kind = %running.kind,
+            in_flight_since = %running.started_at,
+            "Decision already in flight, skipping duplicate execution"
+        );
+        self.record_decision(DecisionRecord {
+            position: action.position.address,
+            decision: action.decision.clone(),
+            reason: format!(
+                "Duplicate of {} in flight since {}; not executed",
+                running.kind, running.started_at
+            ),
+            strategy: action.position.strategy.as_ref().map(|s| s.name.clone()),
+            regime: None,
+            price: action.pool.price,
+            deduplicated: true,
+            timestamp: chrono::Utc::now(),
+        })
+        .await;
+    }
+
+    /// Replaces the deferred actions, keeping when each position was first
+    /// deferred.
+    async fn record_deferred(&self, deferred: &[QueuedAction]) {
+        let now = chrono::Utc::now();
+        let mut current = self.deferred.write().await;
+        let previous = std::mem::take(&mut *current);
+        for action in deferred {
+            let address = action.position.address;
+            let since = previous.get(&address).map_or(now, |d| d.since);
+            info!(
+                position = %address,
+                decision = %action.decision.description(),
+                deferred_since = %since,
+                "Low-priority action deferred while urgent actions run"
+            );
+            current.insert(
+                address,
+                DeferredAction {
+                    position: address,
+                    decision: action.decision.clone(),
+                    since,
+                },
+            );
+        }
+    }
+
+    /// Appends an observed pool price and returns the pool's recent prices.
+    async fn record_price(&self, pool: &Pubkey, price: Decimal) -> Vec<Decimal> {
+        let lookback = self
+            .decision_engine
+            .read()
+            .await
+            .config()
+            .trend_lookback
+            .max(1);
+        let mut history = self.price_history.write().await;
+        let prices = history.entry(*pool).or_default();
+
+        prices.push_back(price);
+        while prices.len() > lookback {
+            prices.pop_front();
+        }
+        prices.iter().copied().collect()
+    }
+
+    /// Returns the latest candle closes of a pool and its daily volatility,
+    /// if live candles are configured and at least three are available.
+    async fn live_prices(&self, pool: &Pubkey) -> Option<(Vec<Decimal>, Option<Decimal>)> {
+        let (builder, interval_secs) = self.candles.as_ref()?;
+        let lookback = self
+            .decision_engine
+            .read()
+            .await
+            .config()
+            .trend_lookback
+            .max(1);
+        let closes = builder.close_prices(pool, *interval_secs, lookback).await;
+        if closes.len() < 3 {
+            return None;
+        }
+        let volatility = builder
+            .daily_volatility(pool, *interval_secs, lookback)
+            .await;
+        Some((closes, volatility))
+    }
+
+    /// Marks a position stale after its pool state could not be fetched,
+    /// alerting once when it first degrades.
+    async fn mark_degraded(
+        &self,
+        position: &crate::monitor::MonitoredPosition,
+        error: &anyhow::Error,
+    ) {
+        warn!(
+            position = %position.address,
+            pool = %position.pool,
+            error = %error,
+            "Pool state unavailable, skipping position"
+        );
+        if self.monitor.mark_position_stale(&position.address).await {
+            let alert = Alert::new(
+                AlertLevel::Warning,
+                AlertType::ConnectionIssue,
+                format!(
+                    "Pool state of {} unavailable ({}); position is not evaluated until it recovers",
+                    position.pool, error
+                ),
+            )
+            .with_position(&position.address)
+            .with_pool(&position.pool);
+            self.notifier.notify_all(&alert).await;
+        }
+    }
+
+    /// Clears the stale mark of a position whose pool state was fetched.
+    async fn mark_recovered(&self, position: &Pubkey) {
+        if let Some(since) = self.monitor.clear_position_stale(position).await {
+            info!(position = %position, degraded_since = %since, "Pool state available again");
+        }
+    }
+
+    /// Appends a decision to the audit log, dropping the oldest when full.
+    async fn record_decision(&self, record: DecisionRecord) {
+        if let Some(sender) = &self.decision_sender {
+            // No subscribers is not an error
+            let _ = sender.send(record.clone());
+        }
+        let mut log = self.decision_log.write().await;
+        if log.len() >= MAX_DECISION_LOG {
+            log.pop_front();
+        }
+        log.push_back(record);
     }
 
     /// Calculates hours since last rebalance.
@@ -272,6 +817,26 @@ impl StrategyExecutor {
         decision: &Decision,
         _pool: &WhirlpoolState,
     ) -> anyhow::Result<()> {
+        if position.read_only {
+            anyhow::bail!("Position {} is read-only", position.address);
+        }
+
+        // During WebSocket outages the monitor may lag; never act on old state
+        if !self
+            .config
+            .is_fresh(position.last_updated, chrono::Utc::now())
+        {
+            warn!(
+                position = %position.address,
+                last_updated = %position.last_updated,
+                max_age_secs = self.config.max_state_age_secs,
+                decision = %decision.description(),
+                "Position state is stale, skipping execution and refreshing"
+            );
+            self.monitor.refresh_positions(&[position.address]).await;
+            return Ok(());
+        }
+
         info!(
             position = %position.address,
             decision = %decision.description(),
@@ -314,11 +879,27 @@ impl StrategyExecutor {
                 info!("Would execute close");
                 // TODO: Implement close via emergency exit manager
             }
-            Decision::IncreaseLiquidity { amount } => {
-                info!(amount = %amount, "Would execute increase liquidity");
-            }
-            Decision::DecreaseLiquidity { amount } => {
-                info!(amount = %amount, "Would execute decrease liquidity");
+            Decision::IncreaseLiquidity { amount } | Decision::DecreaseLiquidity { amount } => {
+                let is_increase = matches!(decision, Decision::IncreaseLiquidity { .. });
+                let delta = amount
+                    .to_u128()
+                    .ok_or_else(|| anyhow::anyhow!("Invalid liquidity amount: {}", amount))?;
+
+                let change = self
+                    .rebalance_executor
+                    .adjust_liquidity(
+                        position.address,
+                        position.pool,
+                        position.on_chain.liquidity,
+                        is_increase,
+                        delta,
+                    )
+                    .await?;
+                info!(
+                    position = %position.address,
+                    new_liquidity = change.new_liquidity,
+                    "Liquidity adjusted"
+                );
             }
             Decision::CollectFees => {
                 info!("Would execute collect fees");
@@ -328,3 +909,357 @@ impl StrategyExecutor {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alerts::Notifier;
+    use crate::emergency::CircuitState;
+    use crate::monitor::MonitorConfig;
+    use crate::transaction::TransactionConfig;
+    use async_trait::async_trait;
+    use base64::Engine;
+    use std::str::FromStr;
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    /// Serves fixed positions in range at tick 0 and counts how often they
+    /// are read.
+    struct FixedAdapter {
+        pools: HashMap<Pubkey, Pubkey>,
+        reads: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl ProtocolAdapter for FixedAdapter {
+        fn protocol(&self) -> Protocol {
+            Protocol::MeteoraDlmm
+        }
+
+        async fn get_position(&self, position_address: &str) -> anyhow::Result<OnChainPosition> {
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            let address = Pubkey::from_str(position_address)?;
+            Ok(OnChainPosition {
+                address,
+                pool: self.pools[&address],
+                owner: Pubkey::new_unique(),
+                tick_lower: -100,
+                tick_upper: 100,
+                liquidity: 1_000,
+                fee_growth_inside_a: 0,
+                fee_growth_inside_b: 0,
+                fees_owed_a: 0,
+                fees_owed_b: 0,
+                reward_growth_inside: [0; NUM_REWARDS],
+                rewards_owed: [0; NUM_REWARDS],
+            })
+        }
+
+        async fn get_pool(&self, pool_address: &str) -> anyhow::Result<PoolSnapshot> {
+            Ok(PoolSnapshot {
+                address: pool_address.to_string(),
+                token_mint_a: Pubkey::new_unique(),
+                token_mint_b: Pubkey::new_unique(),
+                tick_current: 0,
+                tick_spacing: 1,
+                sqrt_price: 1 << 64,
+                price: Decimal::ONE,
+                liquidity: 1_000,
+                fee_growth_global_a: 0,
+                fee_growth_global_b: 0,
+                rewards: Vec::new(),
+            })
+        }
+    }
+
+    /// Records the alerts it is sent.
+    #[derive(Clone, Default)]
+    struct RecordingNotifier {
+        alerts: Arc<Mutex<Vec<Alert>>>,
+    }
+
+    #[async_trait]
+    impl Notifier for RecordingNotifier {
+        async fn notify(&self, alert: &Alert) -> anyhow::Result<()> {
+            self.alerts.lock().unwrap().push(alert.clone());
+            Ok(())
+        }
+
+        fn name(&self) -> &str {
+            "recording"
+        }
+    }
+
+    /// Encodes a Whirlpool account at tick 0.
+    fn whirlpool_data() -> Vec<u8> {
+        borsh::to_vec(&Whirlpool {
+            discriminator: [0; 8],
+            whirlpools_config: Pubkey::new_unique(),
+            whirlpool_bump: [255],
+            tick_spacing: 1,
+            tick_spacing_seed: [1, 0],
+            fee_rate: 100,
+            protocol_fee_rate: 0,
+            liquidity: 1_000,
+            sqrt_price: 1 << 64,
+            tick_current_index: 0,
+            protocol_fee_owed_a: 0,
+            protocol_fee_owed_b: 0,
+            token_mint_a: Pubkey::new_unique(),
+            token_vault_a: Pubkey::new_unique(),
+            fee_growth_global_a: 0,
+            token_mint_b: Pubkey::new_unique(),
+            token_vault_b: Pubkey::new_unique(),
+            fee_growth_global_b: 0,
+            reward_last_updated_timestamp: 0,
+            reward_infos: Default::default(),
+        })
+        .unwrap()
+    }
+
+    type Accounts = Arc<Mutex<HashMap<Pubkey, Vec<u8>>>>;
+
+    /// Serves `getAccountInfo` for the given accounts, answering missing
+    /// ones as not found; returns the endpoint URL.
+    async fn serve_rpc(accounts: Accounts) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                tokio::spawn(answer_rpc(socket, accounts.clone()));
+            }
+        });
+        url
+    }
+
+    async fn answer_rpc(mut socket: TcpStream, accounts: Accounts) {
+        let mut request = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            let Ok(n) = socket.read(&mut buf).await else {
+                return;
+            };
+            if n == 0 {
+                return;
+            }
+            request.extend_from_slice(&buf[..n]);
+
+            // Answer each complete request; the client keeps the connection
+            let text = String::from_utf8_lossy(&request).to_lowercase();
+            let Some(end) = text.find("\r\n\r\n") else {
+                continue;
+            };
+            let length: usize = text
+                .lines()
+                .find_map(|l| l.strip_prefix("content-length: "))
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(0);
+            if request.len() < end + 4 + length {
+                continue;
+            }
+            let body: serde_json::Value =
+                serde_json::from_slice(&request[end + 4..end + 4 + length]).unwrap();
+            request.drain(..end + 4 + length);
+
+            let result = match body["method"].as_str() {
+                Some("getAccountInfo") => {
+                    let address = Pubkey::from_str(body["params"][0].as_str().unwrap()).unwrap();
+                    let value = accounts.lock().unwrap().get(&address).map(|data| {
+                        serde_json::json!({
+                            "data": [base64::engine::general_purpose::STANDARD.encode(data), "base64"],
+                            "executable": false,
+                            "lamports": 1_000_000,
+                            "owner": Pubkey::new_unique().to_string(),
+                            "rentEpoch": 0,
+                            "space": data.len(),
+                        })
+                    });
+                    serde_json::json!({ "context": { "slot": 7 }, "value": value })
+                }
+                other => panic!("Unexpected RPC method {:?}", other),
+            };
+            let response =
+                serde_json::json!({ "jsonrpc": "2.0", "result": result, "id": body["id"] })
+                    .to_string();
+            let head = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n",
+                response.len()
+            );
+            if socket.write_all(head.as_bytes()).await.is_err()
+                || socket.write_all(response.as_bytes()).await.is_err()
+            {
+                return;
+            }
+        }
+    }
+
+    /// An executor monitoring one position in each of `pools`, whose
+    /// accounts are served from `accounts`; returns it with the positions
+    /// and how often they were read.
+    async fn executor_for(
+        pools: &[Pubkey],
+        accounts: Accounts,
+        config: ExecutorConfig,
+    ) -> (StrategyExecutor, Vec<Pubkey>, Arc<AtomicUsize>) {
+        let url = serve_rpc(accounts).await;
+        let provider = Arc::new(RpcProvider::new(RpcConfig {
+            fallback_urls: Vec::new(),
+            max_retries: 0,
+            ..RpcConfig::new(url)
+        }));
+        let positions: Vec<Pubkey> = pools.iter().map(|_| Pubkey::new_unique()).collect();
+        let reads = Arc::new(AtomicUsize::new(0));
+        let adapter = FixedAdapter {
+            pools: positions
+                .iter()
+                .copied()
+                .zip(pools.iter().copied())
+                .collect(),
+            reads: reads.clone(),
+        };
+        let monitor = Arc::new(
+            PositionMonitor::new(provider.clone(), MonitorConfig::default())
+                .with_adapter(Arc::new(adapter)),
+        );
+        for position in &positions {
+            monitor
+                .add_protocol_position(&position.to_string(), Protocol::MeteoraDlmm)
+                .await
+                .unwrap();
+        }
+        let tx_manager = Arc::new(TransactionManager::new(
+            provider.clone(),
+            TransactionConfig::default(),
+        ));
+        let executor = StrategyExecutor::new(provider, monitor, tx_manager, config);
+        (executor, positions, reads)
+    }
+
+    #[test]
+    fn test_state_freshness() {
+        let config = ExecutorConfig::default();
+        let now = chrono::Utc::now();
+        assert!(config.is_fresh(now - chrono::Duration::seconds(60), now));
+        assert!(!config.is_fresh(now - chrono::Duration::seconds(600), now));
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_pool_state_skips_position() {
+        let (available, missing) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let accounts = Arc::new(Mutex::new(HashMap::from([(available, whirlpool_data())])));
+        let (mut executor, positions, _) =
+            executor_for(&[available, missing], accounts, ExecutorConfig::default()).await;
+        let notifier = RecordingNotifier::default();
+        let mut notifiers = MultiNotifier::new();
+        notifiers.add(notifier.clone());
+        executor.set_notifier(notifiers);
+
+        // More failing cycles than the breaker tolerates
+        for _ in 0..5 {
+            executor.run_cycle().await;
+        }
+
+        let decisions = executor.decision_log().await;
+        assert_eq!(decisions.len(), 5);
+        assert!(decisions.iter().all(|d| d.position == positions[0]));
+        let healthy = executor.monitor.get_position(&positions[0]).await.unwrap();
+        assert!(healthy.stale_since.is_none());
+        let stale = executor.monitor.get_position(&positions[1]).await.unwrap();
+        assert!(stale.stale_since.is_some());
+
+        // One alert for the whole episode, and the portfolio keeps running
+        let alerts = notifier.alerts.lock().unwrap().clone();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(
+            alerts[0].position.as_deref(),
+            Some(positions[1].to_string().as_str())
+        );
+        assert_eq!(
+            executor.circuit_breaker().state().await,
+            CircuitState::Closed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recovered_pool_state_clears_stale_position() {
+        let pool = Pubkey::new_unique();
+        let accounts: Accounts = Arc::default();
+        let config = ExecutorConfig {
+            pool_cache_ttl_secs: 0,
+            ..ExecutorConfig::default()
+        };
+        let (mut executor, positions, _) = executor_for(&[pool], accounts.clone(), config).await;
+        let notifier = RecordingNotifier::default();
+        let mut notifiers = MultiNotifier::new();
+        notifiers.add(notifier.clone());
+        executor.set_notifier(notifiers);
+
+        executor.run_cycle().await;
+        let since = executor
+            .monitor
+            .get_position(&positions[0])
+            .await
+            .unwrap()
+            .stale_since;
+        assert!(since.is_some());
+        executor.run_cycle().await;
+        assert_eq!(
+            executor
+                .monitor
+                .get_position(&positions[0])
+                .await
+                .unwrap()
+                .stale_since,
+            since
+        );
+
+        accounts.lock().unwrap().insert(pool, whirlpool_data());
+        executor.run_cycle().await;
+        let position = executor.monitor.get_position(&positions[0]).await.unwrap();
+        assert!(position.stale_since.is_none());
+        assert_eq!(executor.decision_log().await.len(), 1);
+        assert_eq!(notifier.alerts.lock().unwrap().len(), 1);
+
+        // A new outage is a new episode
+        accounts.lock().unwrap().clear();
+        executor.run_cycle().await;
+        assert_eq!(notifier.alerts.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_decision_on_stale_state_refreshes_instead() {
+        let pool = Pubkey::new_unique();
+        let accounts = Arc::new(Mutex::new(HashMap::from([(pool, whirlpool_data())])));
+        let (executor, positions, reads) =
+            executor_for(&[pool], accounts, ExecutorConfig::default()).await;
+        let pool_state = executor.pool_cache.get(&pool).await.unwrap();
+        let decision = Decision::DecreaseLiquidity {
+            amount: Decimal::from(500),
+        };
+        let mut position = executor.monitor.get_position(&positions[0]).await.unwrap();
+        let read_before = reads.load(Ordering::SeqCst);
+
+        // Fresh state is acted on; without a wallet the execution fails
+        let error = executor
+            .execute_decision(&position, &decision, &pool_state)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("No wallet"));
+        assert_eq!(reads.load(Ordering::SeqCst), read_before);
+
+        position.last_updated = chrono::Utc::now() - chrono::Duration::seconds(600);
+        executor
+            .execute_decision(&position, &decision, &pool_state)
+            .await
+            .unwrap();
+        assert_eq!(reads.load(Ordering::SeqCst), read_before + 1);
+        let refreshed = executor.monitor.get_position(&positions[0]).await.unwrap();
+        assert!(
+            executor
+                .config
+                .is_fresh(refreshed.last_updated, chrono::Utc::now())
+        );
+    }
+}
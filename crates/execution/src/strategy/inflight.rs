@@ -0,0 +1,193 @@
+//! Registry of decisions being executed.
+//!
+//! Executors sharing a monitor, or evaluation cycles that overlap, can
+//! reach the same decision for a position at nearly the same time. The
+//! registry lets only the first execution of a given kind of decision per
+//! position proceed until it finishes.
+
+use super::Decision;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+
+/// Kind of decision, without its parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DecisionKind {
+    /// Hold current position.
+    Hold,
+    /// Rebalance to a new range.
+    Rebalance,
+    /// Close the position.
+    Close,
+    /// Increase liquidity.
+    IncreaseLiquidity,
+    /// Decrease liquidity.
+    DecreaseLiquidity,
+    /// Collect fees.
+    CollectFees,
+}
+
+impl DecisionKind {
+    /// Returns the kind of `decision`.
+    #[must_use]
+    pub fn of(decision: &Decision) -> Self {
+        match decision {
+            Decision::Hold => Self::Hold,
+            Decision::Rebalance { .. } => Self::Rebalance,
+            Decision::Close => Self::Close,
+            Decision::IncreaseLiquidity { .. } => Self::IncreaseLiquidity,
+            Decision::DecreaseLiquidity { .. } => Self::DecreaseLiquidity,
+            Decision::CollectFees => Self::CollectFees,
+        }
+    }
+
+    /// Returns the snake_case name of the kind.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Hold => "hold",
+            Self::Rebalance => "rebalance",
+            Self::Close => "close",
+            Self::IncreaseLiquidity => "increase_liquidity",
+            Self::DecreaseLiquidity => "decrease_liquidity",
+            Self::CollectFees => "collect_fees",
+        }
+    }
+}
+
+impl fmt::Display for DecisionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An operation registered as in flight.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InFlightOperation {
+    /// Position the operation is for.
+    pub position: Pubkey,
+    /// Kind of decision being executed.
+    pub kind: DecisionKind,
+    /// When execution started.
+    pub started_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Operations currently executing, keyed by position and decision kind.
+#[derive(Debug, Default)]
+pub struct InFlightRegistry {
+    operations: Mutex<HashMap<(Pubkey, DecisionKind), chrono::DateTime<chrono::Utc>>>,
+}
+
+impl InFlightRegistry {
+    /// Creates an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `kind` as in flight for `position`.
+    ///
+    /// Returns a guard that releases the entry when dropped, or the
+    /// operation already in flight if there is one.
+    pub fn try_begin(
+        &self,
+        position: Pubkey,
+        kind: DecisionKind,
+    ) -> Result<InFlightGuard<'_>, InFlightOperation> {
+        let mut operations = self.lock();
+        if let Some(started_at) = operations.get(&(position, kind)) {
+            return Err(InFlightOperation {
+                position,
+                kind,
+                started_at: *started_at,
+            });
+        }
+        operations.insert((position, kind), chrono::Utc::now());
+        Ok(InFlightGuard {
+            registry: self,
+            key: (position, kind),
+        })
+    }
+
+    /// Returns true if `kind` is in flight for `position`.
+    #[must_use]
+    pub fn is_in_flight(&self, position: &Pubkey, kind: DecisionKind) -> bool {
+        self.lock().contains_key(&(*position, kind))
+    }
+
+    /// Gets the operations in flight.
+    #[must_use]
+    pub fn operations(&self) -> Vec<InFlightOperation> {
+        self.lock()
+            .iter()
+            .map(|(&(position, kind), &started_at)| InFlightOperation {
+                position,
+                kind,
+                started_at,
+            })
+            .collect()
+    }
+
+    fn lock(
+        &self,
+    ) -> std::sync::MutexGuard<'_, HashMap<(Pubkey, DecisionKind), chrono::DateTime<chrono::Utc>>>
+    {
+        // Entries are plain data, so a panic while holding the lock leaves
+        // them consistent
+        self.operations
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// Marks an operation in flight until dropped, including when execution
+/// fails or panics.
+#[derive(Debug)]
+pub struct InFlightGuard<'a> {
+    registry: &'a InFlightRegistry,
+    key: (Pubkey, DecisionKind),
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.registry.lock().remove(&self.key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dedupes_by_position_and_kind() {
+        let registry = InFlightRegistry::new();
+        let position = Pubkey::new_unique();
+
+        let guard = registry
+            .try_begin(position, DecisionKind::Rebalance)
+            .unwrap();
+        let duplicate = registry
+            .try_begin(position, DecisionKind::Rebalance)
+            .unwrap_err();
+        assert_eq!(duplicate.kind, DecisionKind::Rebalance);
+        assert!(registry.is_in_flight(&position, DecisionKind::Rebalance));
+
+        // Other kinds and positions are independent
+        let _fees = registry
+            .try_begin(position, DecisionKind::CollectFees)
+            .unwrap();
+        let _other = registry
+            .try_begin(Pubkey::new_unique(), DecisionKind::Rebalance)
+            .unwrap();
+        assert_eq!(registry.operations().len(), 3);
+
+        drop(guard);
+        assert!(!registry.is_in_flight(&position, DecisionKind::Rebalance));
+        assert!(
+            registry
+                .try_begin(position, DecisionKind::Rebalance)
+                .is_ok()
+        );
+    }
+}
@@ -0,0 +1,657 @@
+//! Migration of positions between pools of the same pair.
+
+use crate::lifecycle::{LifecycleTracker, MigrationData};
+use crate::transaction::TransactionManager;
+use crate::wallet::Wallet;
+use clmm_lp_protocols::prelude::*;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+/// Lamports per SOL.
+const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+/// Configuration for migrations.
+#[derive(Debug, Clone)]
+pub struct MigrationConfig {
+    /// Maximum slippage tolerance in basis points.
+    pub max_slippage_bps: u16,
+    /// Days the APR difference is projected over.
+    pub horizon_days: u32,
+    /// Minimum multiple of the migration cost the projected gain must reach.
+    pub min_profit_multiplier: Decimal,
+    /// Whether to collect fees before closing the old position.
+    pub collect_fees_first: bool,
+}
+
+impl Default for MigrationConfig {
+    fn default() -> Self {
+        Self {
+            max_slippage_bps: 50, // 0.5%
+            horizon_days: 30,
+            min_profit_multiplier: Decimal::new(2, 0), // 2x cost
+            collect_fees_first: true,
+        }
+    }
+}
+
+/// Parameters for a migration.
+#[derive(Debug, Clone)]
+pub struct MigrationParams {
+    /// Position to migrate.
+    pub position: Pubkey,
+    /// Pool the position is in.
+    pub from_pool: Pubkey,
+    /// Pool to move the position to.
+    pub to_pool: Pubkey,
+    /// Current tick lower.
+    pub current_tick_lower: i32,
+    /// Current tick upper.
+    pub current_tick_upper: i32,
+    /// Tick lower in the target pool.
+    pub new_tick_lower: i32,
+    /// Tick upper in the target pool.
+    pub new_tick_upper: i32,
+    /// Current liquidity.
+    pub current_liquidity: u128,
+    /// Current position value in USD.
+    pub position_value_usd: Decimal,
+    /// Projected fee APR of the source pool.
+    pub from_fee_apr: Decimal,
+    /// Projected fee APR of the target pool.
+    pub to_fee_apr: Decimal,
+    /// SOL price in USD, to value transaction costs.
+    pub sol_price_usd: Decimal,
+}
+
+/// Outcome of a migration profitability check.
+#[derive(Debug, Clone)]
+pub struct MigrationCheck {
+    /// Whether the migration is worth its cost.
+    pub is_profitable: bool,
+    /// Extra fees projected over the horizon in USD.
+    pub projected_gain_usd: Decimal,
+    /// Estimated transaction cost in lamports.
+    pub estimated_tx_cost: u64,
+    /// Estimated cost in USD, including slippage on the ratio swap.
+    pub estimated_cost_usd: Decimal,
+    /// Minimum projected gain required in USD.
+    pub min_required_gain_usd: Decimal,
+}
+
+/// Swap that brings token amounts to the ratio a range needs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RatioSwap {
+    /// Whether token A is sold for token B.
+    pub a_to_b: bool,
+    /// Amount of the sold token.
+    pub amount_in: u64,
+}
+
+/// Result of a migration.
+#[derive(Debug, Clone)]
+pub struct MigrationResult {
+    /// Whether the migration was successful.
+    pub success: bool,
+    /// Old position address.
+    pub old_position: Pubkey,
+    /// New position address in the target pool (if opened).
+    pub new_position: Option<Pubkey>,
+    /// Profitability check the migration was gated on.
+    pub check: MigrationCheck,
+    /// Ratio swap performed, if any.
+    pub swap: Option<RatioSwap>,
+    /// Liquidity removed from the old position.
+    pub liquidity_removed: u128,
+    /// Liquidity added to the new position.
+    pub liquidity_added: u128,
+    /// Transaction cost in lamports.
+    pub tx_cost_lamports: u64,
+    /// Error message if failed.
+    pub error: Option<String>,
+}
+
+/// Executor moving positions to another pool of the same pair: close in
+/// the source pool, swap to the target range's ratio, open in the target.
+pub struct MigrationExecutor {
+    /// RPC provider.
+    provider: Arc<RpcProvider>,
+    /// Transaction manager.
+    tx_manager: Arc<TransactionManager>,
+    /// Wallet for signing.
+    wallet: Option<Arc<Wallet>>,
+    /// Lifecycle tracker.
+    lifecycle: Arc<LifecycleTracker>,
+    /// Screens target pool tokens before a position is opened there.
+    risk_checker: Option<Arc<TokenRiskChecker>>,
+    /// Configuration.
+    config: MigrationConfig,
+    /// Dry run mode.
+    dry_run: bool,
+}
+
+impl MigrationExecutor {
+    /// Creates a new migration executor.
+    pub fn new(
+        provider: Arc<RpcProvider>,
+        tx_manager: Arc<TransactionManager>,
+        lifecycle: Arc<LifecycleTracker>,
+        config: MigrationConfig,
+    ) -> Self {
+        Self {
+            provider,
+            tx_manager,
+            wallet: None,
+            lifecycle,
+            risk_checker: None,
+            config,
+            dry_run: false,
+        }
+    }
+
+    /// Sets the wallet for signing.
+    pub fn set_wallet(&mut self, wallet: Arc<Wallet>) {
+        self.wallet = Some(wallet);
+    }
+
+    /// Enables or disables dry run mode.
+    pub fn set_dry_run(&mut self, dry_run: bool) {
+        self.dry_run = dry_run;
+    }
+
+    /// Requires target pool tokens to pass risk screening.
+    pub fn set_risk_checker(&mut self, checker: Arc<TokenRiskChecker>) {
+        self.risk_checker = Some(checker);
+    }
+
+    /// Checks whether the fee APR gained over the horizon pays for the
+    /// migration.
+    #[must_use]
+    pub fn check(&self, params: &MigrationParams) -> MigrationCheck {
+        let estimated_tx_cost = self.estimate_tx_cost();
+        let tx_cost_usd = Decimal::from(estimated_tx_cost) / Decimal::from(LAMPORTS_PER_SOL)
+            * params.sol_price_usd;
+        // At worst half the position is swapped, paying up to the slippage allowance
+        let swap_cost_usd = params.position_value_usd / Decimal::TWO
+            * Decimal::from(self.config.max_slippage_bps)
+            / Decimal::from(10_000);
+        let estimated_cost_usd = tx_cost_usd + swap_cost_usd;
+
+        let projected_gain_usd = params.position_value_usd
+            * (params.to_fee_apr - params.from_fee_apr)
+            * Decimal::from(self.config.horizon_days)
+            / Decimal::from(365);
+        let min_required_gain_usd = estimated_cost_usd * self.config.min_profit_multiplier;
+
+        MigrationCheck {
+            is_profitable: projected_gain_usd > min_required_gain_usd,
+            projected_gain_usd,
+            estimated_tx_cost,
+            estimated_cost_usd,
+            min_required_gain_usd,
+        }
+    }
+
+    /// Estimates transaction cost for a migration.
+    fn estimate_tx_cost(&self) -> u64 {
+        // A rebalance plus a swap; estimate ~0.012 SOL total
+        12_000_000
+    }
+
+    /// Executes a migration.
+    pub async fn execute(&self, params: MigrationParams) -> MigrationResult {
+        info!(
+            position = %params.position,
+            from_pool = %params.from_pool,
+            to_pool = %params.to_pool,
+            new_range = format!("[{}, {}]", params.new_tick_lower, params.new_tick_upper),
+            dry_run = self.dry_run,
+            "Executing migration"
+        );
+
+        let check = self.check(&params);
+        let mut result = MigrationResult {
+            success: false,
+            old_position: params.position,
+            new_position: None,
+            check: check.clone(),
+            swap: None,
+            liquidity_removed: 0,
+            liquidity_added: 0,
+            tx_cost_lamports: 0,
+            error: None,
+        };
+
+        if !check.is_profitable {
+            warn!(
+                projected_gain = %check.projected_gain_usd,
+                min_required = %check.min_required_gain_usd,
+                "Migration not profitable, skipping"
+            );
+            result.error = Some("Migration not profitable".to_string());
+            return result;
+        }
+
+        // Screen the target pool's tokens before closing anything; fail closed
+        if let Some(checker) = &self.risk_checker {
+            match checker.check_pool(&params.to_pool.to_string()).await {
+                Ok(report) if report.is_blocked() => {
+                    let reasons = report.blocking_reasons().join("; ");
+                    warn!(pool = %params.to_pool, reasons = %reasons, "Token risk check failed, skipping");
+                    result.error = Some(format!("Token risk check failed: {}", reasons));
+                    return result;
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    error!(error = %e, "Token risk check unavailable");
+                    result.error = Some(format!("Token risk check unavailable: {}", e));
+                    return result;
+                }
+            }
+        }
+
+        if self.dry_run {
+            info!("Dry run mode - simulating migration");
+            result.success = true;
+            result.liquidity_removed = params.current_liquidity;
+            return result;
+        }
+
+        if let Err(e) = self.migrate(&params, &mut result).await {
+            error!(error = %e, "Migration failed");
+            result.error = Some(e.to_string());
+            return result;
+        }
+
+        result.success = true;
+        info!(
+            old_position = %params.position,
+            new_position = ?result.new_position,
+            tx_cost = result.tx_cost_lamports,
+            "Migration completed successfully"
+        );
+        result
+    }
+
+    /// Runs the migration steps, filling in `result` as they complete.
+    ///
+    /// Lifecycle events are only recorded once every step confirmed.
+    async fn migrate(
+        &self,
+        params: &MigrationParams,
+        result: &mut MigrationResult,
+    ) -> anyhow::Result<()> {
+        let wallet = self
+            .wallet
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("No wallet to sign the migration with"))?;
+        let payer = wallet.keypair();
+
+        let reader = WhirlpoolReader::new(self.provider.clone());
+        let from = reader.get_pool_state(&params.from_pool.to_string()).await?;
+        let to = reader.get_pool_state(&params.to_pool.to_string()).await?;
+        if (from.token_mint_a, from.token_mint_b) != (to.token_mint_a, to.token_mint_b) {
+            anyhow::bail!(
+                "Pools {} and {} hold different pairs",
+                from.address,
+                to.address
+            );
+        }
+        let executor =
+            WhirlpoolExecutor::new(self.provider.clone()).with_sender(self.tx_manager.clone());
+
+        // Step 1: Collect fees if configured
+        if self.config.collect_fees_first {
+            match executor
+                .collect_fees(&params.position, &params.from_pool, payer)
+                .await
+                .and_then(confirmed)
+            {
+                Ok(collected) => result.tx_cost_lamports += tx_cost(&collected),
+                Err(e) => warn!(error = %e, "Failed to collect fees, continuing"),
+            }
+        }
+
+        // Step 2: Withdraw everything from the old position and close it
+        let closed = confirmed(
+            executor
+                .close_position(&params.position, &params.from_pool, payer)
+                .await?,
+        )?;
+        result.liquidity_removed = params.current_liquidity;
+        result.tx_cost_lamports += tx_cost(&closed);
+
+        let (amount_a, amount_b) = match &closed.amounts {
+            Some(amounts) => (
+                amounts.received(&from.token_mint_a),
+                amounts.received(&from.token_mint_b),
+            ),
+            None => token_amounts_for_liquidity(
+                params.current_liquidity,
+                from.sqrt_price,
+                params.current_tick_lower,
+                params.current_tick_upper,
+                false,
+            )
+            .ok_or_else(|| anyhow::anyhow!("Withdrawn amounts overflow"))?,
+        };
+
+        // Step 3: Swap to the target range's token ratio
+        let (amount_a, amount_b) = match ratio_swap(
+            &to,
+            params.new_tick_lower,
+            params.new_tick_upper,
+            amount_a,
+            amount_b,
+        ) {
+            Some(swap) => {
+                let min_amount_out = min_swap_out(&to, swap, self.config.max_slippage_bps);
+                let swapped = confirmed(
+                    executor
+                        .swap(
+                            &SwapParams {
+                                pool: params.to_pool,
+                                a_to_b: swap.a_to_b,
+                                amount_in: swap.amount_in,
+                                min_amount_out,
+                            },
+                            payer,
+                        )
+                        .await?,
+                )?;
+                result.swap = Some(swap);
+                result.tx_cost_lamports += tx_cost(&swapped);
+
+                // The program guarantees at least the minimum output
+                let mint_out = if swap.a_to_b {
+                    &to.token_mint_b
+                } else {
+                    &to.token_mint_a
+                };
+                let amount_out = swapped
+                    .amounts
+                    .as_ref()
+                    .map_or(min_amount_out, |amounts| amounts.received(mint_out));
+                if swap.a_to_b {
+                    (
+                        amount_a.saturating_sub(swap.amount_in),
+                        amount_b + amount_out,
+                    )
+                } else {
+                    (
+                        amount_a + amount_out,
+                        amount_b.saturating_sub(swap.amount_in),
+                    )
+                }
+            }
+            None => (amount_a, amount_b),
+        };
+
+        // Step 4: Open the new position and deposit
+        let quote = quote_deposit(
+            &to,
+            params.new_tick_lower,
+            params.new_tick_upper,
+            amount_a,
+            amount_b,
+            self.config.max_slippage_bps,
+        )?;
+        let (new_position, opened) = executor
+            .open_position(
+                &OpenPositionParams {
+                    pool: params.to_pool,
+                    quote: quote.clone(),
+                },
+                payer,
+            )
+            .await?;
+        let opened = confirmed(opened)?;
+        result.new_position = Some(new_position);
+        result.liquidity_added = quote.liquidity;
+        result.tx_cost_lamports += tx_cost(&opened);
+
+        self.lifecycle
+            .record_migration(
+                new_position,
+                params.to_pool,
+                MigrationData {
+                    old_position: params.position,
+                    from_pool: params.from_pool,
+                    old_tick_lower: params.current_tick_lower,
+                    old_tick_upper: params.current_tick_upper,
+                    new_tick_lower: params.new_tick_lower,
+                    new_tick_upper: params.new_tick_upper,
+                    old_liquidity: result.liquidity_removed,
+                    new_liquidity: result.liquidity_added,
+                    swap_amount_in: result.swap.map_or(0, |swap| swap.amount_in),
+                    swap_a_to_b: result.swap.is_some_and(|swap| swap.a_to_b),
+                    tx_cost_lamports: result.tx_cost_lamports,
+                    from_fee_apr: params.from_fee_apr,
+                    to_fee_apr: params.to_fee_apr,
+                },
+            )
+            .await;
+        if self
+            .lifecycle
+            .attach_execution(&new_position, &opened, &to.token_mint_a, &to.token_mint_b)
+            .await
+        {
+            self.tx_manager
+                .watch_finality(opened.signature, self.lifecycle.clone());
+        }
+        Ok(())
+    }
+}
+
+/// Turns a transaction that did not confirm into an error.
+pub(super) fn confirmed(result: ExecutionResult) -> anyhow::Result<ExecutionResult> {
+    if result.success {
+        Ok(result)
+    } else {
+        anyhow::bail!(
+            "Transaction {} failed: {}",
+            result.signature,
+            result.error.as_deref().unwrap_or("unknown error")
+        )
+    }
+}
+
+/// Returns the fee a confirmed transaction paid, or a single signature's
+/// fee if it could not be read.
+pub(super) fn tx_cost(result: &ExecutionResult) -> u64 {
+    result
+        .amounts
+        .as_ref()
+        .map_or(5000, |amounts| amounts.fee_lamports)
+}
+
+/// Returns the least a ratio swap in `pool` may return: the output at the
+/// current price after the pool fee, less the slippage allowance.
+#[must_use]
+pub fn min_swap_out(pool: &WhirlpoolState, swap: RatioSwap, max_slippage_bps: u16) -> u64 {
+    let price = pool.price.to_f64().unwrap_or_default();
+    let fee = 1.0 - pool.fee_rate().to_f64().unwrap_or_default();
+    let slippage = 1.0 - f64::from(max_slippage_bps) / 10_000.0;
+    let amount_out = if swap.a_to_b {
+        swap.amount_in as f64 * price
+    } else if price > 0.0 {
+        swap.amount_in as f64 / price
+    } else {
+        0.0
+    };
+    (amount_out * fee * slippage).floor() as u64
+}
+
+/// Returns the swap that brings `amount_a` and `amount_b` to the token
+/// ratio a range needs at the pool's current price, ignoring swap fees.
+///
+/// Returns `None` if no swap is needed or there is nothing to swap.
+#[must_use]
+pub fn ratio_swap(
+    pool: &WhirlpoolState,
+    tick_lower: i32,
+    tick_upper: i32,
+    amount_a: u64,
+    amount_b: u64,
+) -> Option<RatioSwap> {
+    let price = pool.price.to_f64().filter(|price| *price > 0.0)?;
+    let sqrt_lower = tick_to_price(tick_lower).to_f64()?.sqrt();
+    let sqrt_upper = tick_to_price(tick_upper).to_f64()?.sqrt();
+    let sqrt_current = price.sqrt().clamp(sqrt_lower, sqrt_upper);
+
+    // Token amounts per unit of liquidity, and the share of value held in A
+    let unit_a = (sqrt_upper - sqrt_current) / (sqrt_current * sqrt_upper);
+    let unit_b = sqrt_current - sqrt_lower;
+    let value_a = unit_a * price;
+    if value_a + unit_b <= 0.0 {
+        return None;
+    }
+    let share_a = value_a / (value_a + unit_b);
+
+    let total_in_b = amount_a as f64 * price + amount_b as f64;
+    let target_a = total_in_b * share_a / price;
+    let swap = if (amount_a as f64) > target_a {
+        RatioSwap {
+            a_to_b: true,
+            amount_in: (amount_a as f64 - target_a).floor() as u64,
+        }
+    } else {
+        RatioSwap {
+            a_to_b: false,
+            amount_in: ((target_a - amount_a as f64) * price).floor() as u64,
+        }
+    };
+    (swap.amount_in > 0).then_some(swap)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn executor() -> MigrationExecutor {
+        let provider = Arc::new(RpcProvider::new(RpcConfig::default()));
+        MigrationExecutor::new(
+            provider.clone(),
+            Arc::new(TransactionManager::new(
+                provider,
+                crate::transaction::TransactionConfig::default(),
+            )),
+            Arc::new(LifecycleTracker::new()),
+            MigrationConfig::default(),
+        )
+    }
+
+    fn params(from_fee_apr: Decimal) -> MigrationParams {
+        MigrationParams {
+            position: Pubkey::new_unique(),
+            from_pool: Pubkey::new_unique(),
+            to_pool: Pubkey::new_unique(),
+            current_tick_lower: -1000,
+            current_tick_upper: 1000,
+            new_tick_lower: -1024,
+            new_tick_upper: 1024,
+            current_liquidity: 1_000_000,
+            position_value_usd: dec!(10000),
+            from_fee_apr,
+            to_fee_apr: dec!(0.30),
+            sol_price_usd: dec!(150),
+        }
+    }
+
+    fn pool_at_price_one() -> WhirlpoolState {
+        WhirlpoolState {
+            address: String::new(),
+            token_mint_a: Pubkey::new_unique(),
+            token_mint_b: Pubkey::new_unique(),
+            tick_current: 0,
+            tick_spacing: 8,
+            sqrt_price: 1 << 64,
+            price: Decimal::ONE,
+            liquidity: 0,
+            fee_rate: 5,
+            protocol_fee_rate_bps: 0,
+            fee_growth_global_a: 0,
+            fee_growth_global_b: 0,
+            rewards: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_check_compares_fee_apr_gain_with_cost() {
+        let executor = executor();
+
+        // 20% APR over 30 days on $10k is ~$164, against ~$27 of cost
+        let check = executor.check(&params(dec!(0.10)));
+        assert!(check.is_profitable);
+        assert_eq!(check.estimated_cost_usd, dec!(26.8));
+        assert_eq!(check.min_required_gain_usd, dec!(53.6));
+
+        // 5% APR is ~$41, short of twice the cost
+        assert!(!executor.check(&params(dec!(0.25))).is_profitable);
+    }
+
+    #[test]
+    fn test_ratio_swap_matches_range() {
+        let pool = pool_at_price_one();
+
+        // A symmetric range around the price holds half the value in each token
+        let swap = ratio_swap(&pool, -1000, 1000, 1_000_000, 0).unwrap();
+        assert!(swap.a_to_b);
+        assert!(swap.amount_in.abs_diff(500_000) <= 1);
+        assert!(
+            ratio_swap(&pool, -1000, 1000, 500_000, 500_000).is_none_or(|swap| swap.amount_in <= 1)
+        );
+
+        // A range above the price holds only token A
+        let swap = ratio_swap(&pool, 1000, 2000, 0, 1_000_000).unwrap();
+        assert_eq!(
+            swap,
+            RatioSwap {
+                a_to_b: false,
+                amount_in: 1_000_000
+            }
+        );
+    }
+
+    #[test]
+    fn test_min_swap_out_applies_fee_and_slippage() {
+        // 0.3% fee tier is 3000 on-chain
+        let mut pool = pool_at_price_one();
+        pool.fee_rate = 3000;
+        let swap = RatioSwap {
+            a_to_b: true,
+            amount_in: 1_000_000,
+        };
+
+        // 1M less 0.3% fee, less 0.5% slippage
+        assert_eq!(min_swap_out(&pool, swap, 50), 992_015);
+    }
+
+    #[tokio::test]
+    async fn test_migration_without_wallet_records_nothing() {
+        let executor = executor();
+
+        let result = executor.execute(params(dec!(0.05))).await;
+        assert!(!result.success);
+        assert!(result.new_position.is_none());
+        assert!(executor.lifecycle.get_all_events().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unprofitable_migration_is_skipped() {
+        let mut executor = executor();
+        executor.set_dry_run(true);
+
+        let skipped = executor.execute(params(dec!(0.29))).await;
+        assert!(!skipped.success);
+        assert_eq!(skipped.error.as_deref(), Some("Migration not profitable"));
+
+        let simulated = executor.execute(params(dec!(0.05))).await;
+        assert!(simulated.success);
+        assert_eq!(simulated.liquidity_removed, 1_000_000);
+        assert!(executor.lifecycle.get_all_events().await.is_empty());
+    }
+}
@@ -2,15 +2,33 @@
 //!
 //! Provides automated strategy execution including:
 //! - Decision engine
+//! - Per-position strategy assignment
 //! - Rebalancing logic
+//! - Migration between pools of the same pair
+//! - Market regime detection
+//! - Pool price sanity checks against an oracle
+//! - Prioritized, bounded execution of queued actions
+//! - Deduplication of decisions already being executed
 //! - Position lifecycle management
 
+mod assignment;
 mod decision;
 mod executor;
+mod inflight;
+mod migration;
+mod oracle;
+mod queue;
 mod rebalance;
+mod regime;
 mod types;
 
+pub use assignment::{DecisionOverrides, PositionStrategy};
 pub use decision::*;
 pub use executor::*;
+pub use inflight::{DecisionKind, InFlightGuard, InFlightOperation, InFlightRegistry};
+pub use migration::*;
+pub use oracle::*;
+pub use queue::{ActionPriority, ActionQueue, QueuedAction};
 pub use rebalance::*;
+pub use regime::{MarketRegime, RegimeSignal};
 pub use types::Decision;
@@ -0,0 +1,39 @@
+//! Independent reference prices for sanity-checking pool prices.
+
+use async_trait::async_trait;
+use clmm_lp_protocols::prelude::WhirlpoolState;
+use rust_decimal::Decimal;
+
+/// Source of a pool price independent of the pool itself, e.g. Pyth or Jupiter.
+#[async_trait]
+pub trait ReferencePrice: Send + Sync {
+    /// Returns the price of the pool's token A in token B, in the same raw
+    /// units as [`WhirlpoolState::price`].
+    async fn price(&self, pool: &WhirlpoolState) -> anyhow::Result<Decimal>;
+
+    /// Returns the name of this source.
+    fn name(&self) -> &str;
+}
+
+/// Returns the relative divergence of `pool_price` from `reference`, or
+/// `None` if the reference is not positive.
+#[must_use]
+pub fn price_divergence(pool_price: Decimal, reference: Decimal) -> Option<Decimal> {
+    if reference <= Decimal::ZERO {
+        return None;
+    }
+    Some(((pool_price - reference) / reference).abs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_price_divergence() {
+        assert_eq!(price_divergence(dec!(110), dec!(100)), Some(dec!(0.1)));
+        assert_eq!(price_divergence(dec!(90), dec!(100)), Some(dec!(0.1)));
+        assert_eq!(price_divergence(dec!(90), Decimal::ZERO), None);
+    }
+}
@@ -0,0 +1,243 @@
+//! Prioritization of actions competing for execution.
+//!
+//! In a sharp market move many positions need action in the same cycle.
+//! Instead of executing them in monitor order, the executor queues them,
+//! runs the ones protecting the most value first, and defers routine
+//! actions such as fee collection until the urgent ones have drained.
+
+use super::Decision;
+use crate::monitor::MonitoredPosition;
+use clmm_lp_protocols::prelude::WhirlpoolState;
+use rust_decimal::Decimal;
+use std::cmp::Ordering;
+
+/// How urgently a decision should be executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ActionPriority {
+    /// Optional upkeep: collecting fees or adding liquidity.
+    Low,
+    /// Repositioning an active position.
+    Normal,
+    /// Reducing exposure: closing or removing liquidity.
+    High,
+}
+
+impl ActionPriority {
+    /// Returns the priority of executing `decision`.
+    #[must_use]
+    pub fn of(decision: &Decision) -> Self {
+        match decision {
+            Decision::Close | Decision::DecreaseLiquidity { .. } => Self::High,
+            Decision::Rebalance { .. } => Self::Normal,
+            Decision::Hold | Decision::IncreaseLiquidity { .. } | Decision::CollectFees => {
+                Self::Low
+            }
+        }
+    }
+}
+
+/// A decision waiting to be executed.
+#[derive(Debug, Clone)]
+pub struct QueuedAction {
+    /// Position the decision is for.
+    pub position: MonitoredPosition,
+    /// The decision.
+    pub decision: Decision,
+    /// Pool state the decision was made on.
+    pub pool: WhirlpoolState,
+    /// Priority of the decision.
+    pub priority: ActionPriority,
+    /// Loss in USD the action guards against: the position's value times
+    /// its impermanent loss.
+    pub loss_at_risk: Decimal,
+}
+
+impl QueuedAction {
+    /// Queues `decision` for `position`, deriving its priority and the loss
+    /// at risk.
+    #[must_use]
+    pub fn new(position: MonitoredPosition, decision: Decision, pool: WhirlpoolState) -> Self {
+        let value = if position.pnl.current_value_usd > Decimal::ZERO {
+            position.pnl.current_value_usd
+        } else {
+            position.pnl.entry_value_usd
+        };
+        let loss_at_risk = value * position.pnl.il_pct.abs();
+        Self {
+            priority: ActionPriority::of(&decision),
+            position,
+            decision,
+            pool,
+            loss_at_risk,
+        }
+    }
+
+    /// Orders actions to run first before those to run later.
+    fn execution_order(&self, other: &Self) -> Ordering {
+        other
+            .priority
+            .cmp(&self.priority)
+            .then_with(|| other.loss_at_risk.cmp(&self.loss_at_risk))
+    }
+}
+
+/// Actions collected over one evaluation cycle.
+#[derive(Debug, Clone, Default)]
+pub struct ActionQueue {
+    actions: Vec<QueuedAction>,
+}
+
+impl ActionQueue {
+    /// Creates an empty queue.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an action.
+    pub fn push(&mut self, action: QueuedAction) {
+        self.actions.push(action);
+    }
+
+    /// Returns the number of queued actions.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.actions.len()
+    }
+
+    /// Returns true if nothing is queued.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// Splits the queue into actions to execute now, highest priority and
+    /// largest loss at risk first, and low-priority actions deferred.
+    ///
+    /// With `defer_low_priority`, low-priority actions wait while any
+    /// higher-priority action is queued.
+    #[must_use]
+    pub fn into_batches(
+        mut self,
+        defer_low_priority: bool,
+    ) -> (Vec<QueuedAction>, Vec<QueuedAction>) {
+        self.actions.sort_by(QueuedAction::execution_order);
+        let urgent = self
+            .actions
+            .iter()
+            .any(|action| action.priority > ActionPriority::Low);
+        if !(defer_low_priority && urgent) {
+            return (self.actions, Vec::new());
+        }
+        self.actions
+            .into_iter()
+            .partition(|action| action.priority > ActionPriority::Low)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::PositionPnL;
+    use clmm_lp_protocols::prelude::{OnChainPosition, Protocol};
+    use rust_decimal_macros::dec;
+    use solana_sdk::pubkey::Pubkey;
+
+    fn action(decision: Decision, value: Decimal, il_pct: Decimal) -> QueuedAction {
+        let position = MonitoredPosition {
+            address: Pubkey::new_unique(),
+            pool: Pubkey::new_unique(),
+            protocol: Protocol::OrcaWhirlpool,
+            on_chain: OnChainPosition {
+                address: Pubkey::new_unique(),
+                pool: Pubkey::new_unique(),
+                owner: Pubkey::new_unique(),
+                tick_lower: -1000,
+                tick_upper: 1000,
+                liquidity: 1_000_000,
+                fee_growth_inside_a: 0,
+                fee_growth_inside_b: 0,
+                fees_owed_a: 0,
+                fees_owed_b: 0,
+                reward_growth_inside: [0; 3],
+                rewards_owed: [0; 3],
+            },
+            pnl: PositionPnL {
+                current_value_usd: value,
+                il_pct,
+                ..Default::default()
+            },
+            in_range: false,
+            tick_current: 0,
+            range_checks: 0,
+            in_range_checks: 0,
+            health: None,
+            strategy: None,
+            tags: Vec::new(),
+            read_only: false,
+            stale_since: None,
+            last_updated: chrono::Utc::now(),
+        };
+        let pool = WhirlpoolState {
+            address: String::new(),
+            token_mint_a: Pubkey::new_unique(),
+            token_mint_b: Pubkey::new_unique(),
+            tick_current: 0,
+            tick_spacing: 64,
+            sqrt_price: 1 << 64,
+            price: Decimal::ONE,
+            liquidity: 1_000_000,
+            fee_rate: 30,
+            protocol_fee_rate_bps: 0,
+            fee_growth_global_a: 0,
+            fee_growth_global_b: 0,
+            rewards: Vec::new(),
+        };
+        QueuedAction::new(position, decision, pool)
+    }
+
+    fn rebalance() -> Decision {
+        Decision::Rebalance {
+            new_tick_lower: -100,
+            new_tick_upper: 100,
+        }
+    }
+
+    #[test]
+    fn test_orders_by_priority_then_loss_at_risk() {
+        let mut queue = ActionQueue::new();
+        queue.push(action(rebalance(), dec!(1000), dec!(-0.02)));
+        queue.push(action(rebalance(), dec!(5000), dec!(-0.03)));
+        queue.push(action(Decision::Close, dec!(100), dec!(-0.2)));
+        assert_eq!(queue.len(), 3);
+
+        let (ready, deferred) = queue.into_batches(true);
+        assert!(deferred.is_empty());
+        assert!(matches!(ready[0].decision, Decision::Close));
+        assert_eq!(ready[1].loss_at_risk, dec!(150));
+        assert_eq!(ready[2].loss_at_risk, dec!(20));
+    }
+
+    #[test]
+    fn test_defers_low_priority_until_urgent_actions_drain() {
+        let mut queue = ActionQueue::new();
+        queue.push(action(Decision::CollectFees, dec!(1000), dec!(0)));
+        queue.push(action(rebalance(), dec!(1000), dec!(-0.05)));
+
+        let (ready, deferred) = queue.clone().into_batches(true);
+        assert_eq!(ready.len(), 1);
+        assert_eq!(deferred.len(), 1);
+        assert_eq!(deferred[0].priority, ActionPriority::Low);
+
+        let (ready, deferred) = queue.into_batches(false);
+        assert_eq!(ready.len(), 2);
+        assert!(deferred.is_empty());
+
+        // Once nothing urgent is queued, low-priority actions run
+        let mut drained = ActionQueue::new();
+        drained.push(action(Decision::CollectFees, dec!(1000), dec!(0)));
+        let (ready, deferred) = drained.into_batches(true);
+        assert_eq!(ready.len(), 1);
+        assert!(deferred.is_empty());
+    }
+}
@@ -1,6 +1,9 @@
 //! Rebalancing execution logic.
 
-use crate::lifecycle::{FeesCollectedData, LifecycleTracker, RebalanceData, RebalanceReason};
+use super::migration::{confirmed, tx_cost};
+use crate::lifecycle::{
+    FeesCollectedData, LifecycleTracker, LiquidityChangeData, RebalanceData, RebalanceReason,
+};
 use crate::transaction::TransactionManager;
 use crate::wallet::Wallet;
 use clmm_lp_protocols::prelude::*;
@@ -20,6 +23,9 @@ pub struct RebalanceConfig {
     pub collect_fees_first: bool,
     /// Priority fee level.
     pub priority_level: crate::transaction::PriorityLevel,
+    /// Whether a rebalance bundle whose simulation fails or misses
+    /// expectations is aborted; otherwise it is only logged.
+    pub enforce_simulation: bool,
 }
 
 impl Default for RebalanceConfig {
@@ -29,6 +35,7 @@ impl Default for RebalanceConfig {
             min_profit_multiplier: Decimal::new(2, 0), // 2x tx cost
             collect_fees_first: true,
             priority_level: crate::transaction::PriorityLevel::Medium,
+            enforce_simulation: true,
         }
     }
 }
@@ -80,15 +87,15 @@ pub struct RebalanceResult {
 /// Executor for rebalancing operations.
 pub struct RebalanceExecutor {
     /// RPC provider.
-    #[allow(dead_code)]
     provider: Arc<RpcProvider>,
     /// Transaction manager.
-    #[allow(dead_code)]
     tx_manager: Arc<TransactionManager>,
     /// Wallet for signing.
     wallet: Option<Arc<Wallet>>,
     /// Lifecycle tracker.
     lifecycle: Arc<LifecycleTracker>,
+    /// Screens pool tokens before a new position is opened.
+    risk_checker: Option<Arc<TokenRiskChecker>>,
     /// Configuration.
     config: RebalanceConfig,
     /// Dry run mode.
@@ -108,6 +115,7 @@ impl RebalanceExecutor {
             tx_manager,
             wallet: None,
             lifecycle,
+            risk_checker: None,
             config,
             dry_run: false,
         }
@@ -123,6 +131,11 @@ impl RebalanceExecutor {
         self.dry_run = dry_run;
     }
 
+    /// Requires pool tokens to pass risk screening before opening positions.
+    pub fn set_risk_checker(&mut self, checker: Arc<TokenRiskChecker>) {
+        self.risk_checker = Some(checker);
+    }
+
     /// Checks if a rebalance is profitable.
     pub async fn is_profitable(&self, params: &RebalanceParams) -> ProfitabilityCheck {
         // Estimate transaction costs
@@ -193,6 +206,34 @@ impl RebalanceExecutor {
             return result;
         }
 
+        // Screen the pool's tokens before closing anything; fail closed
+        if let Some(checker) = &self.risk_checker {
+            match checker.check_pool(&params.pool.to_string()).await {
+                Ok(report) if report.is_blocked() => {
+                    let reasons = report.blocking_reasons().join("; ");
+                    warn!(pool = %params.pool, reasons = %reasons, "Token risk check failed, skipping");
+                    result.error = Some(format!("Token risk check failed: {}", reasons));
+                    return result;
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    error!(error = %e, "Token risk check unavailable");
+                    result.error = Some(format!("Token risk check unavailable: {}", e));
+                    return result;
+                }
+            }
+        }
+
+        // Simulate the whole bundle before touching anything
+        if let Err(e) = self.simulate_bundle(&params).await {
+            if self.config.enforce_simulation {
+                error!(error = %e, "Rebalance simulation failed, aborting");
+                result.error = Some(format!("Simulation failed: {}", e));
+                return result;
+            }
+            warn!(error = %e, "Rebalance simulation failed, continuing");
+        }
+
         if self.dry_run {
             info!("Dry run mode - simulating rebalance");
             result.success = true;
@@ -201,12 +242,57 @@ impl RebalanceExecutor {
             return result;
         }
 
+        if let Err(e) = self.rebalance(&params, &mut result).await {
+            error!(error = %e, "Rebalance failed");
+            result.error = Some(e.to_string());
+            return result;
+        }
+
+        result.success = true;
+        info!(
+            old_position = %params.position,
+            new_position = ?result.new_position,
+            tx_cost = result.tx_cost_lamports,
+            "Rebalance completed successfully"
+        );
+
+        result
+    }
+
+    /// Runs the rebalance steps, filling in `result` as they complete.
+    ///
+    /// Each confirmed transaction is attached to its lifecycle event and
+    /// watched until it finalizes.
+    async fn rebalance(
+        &self,
+        params: &RebalanceParams,
+        result: &mut RebalanceResult,
+    ) -> anyhow::Result<()> {
+        let wallet = self
+            .wallet
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("No wallet to sign the rebalance with"))?;
+        let payer = wallet.keypair();
+
+        let pool = WhirlpoolReader::new(self.provider.clone())
+            .get_pool_state(&params.pool.to_string())
+            .await?;
+        let (mint_a, mint_b) = (&pool.token_mint_a, &pool.token_mint_b);
+        let executor = self.whirlpool_executor();
+
         // Step 1: Collect fees if configured
         if self.config.collect_fees_first {
-            match self.collect_fees(&params.position).await {
-                Ok(fees) => {
+            match executor
+                .collect_fees(&params.position, &params.pool, payer)
+                .await
+                .and_then(confirmed)
+            {
+                Ok(collected) => {
+                    let fees = collected.amounts.as_ref().map_or((0, 0), |amounts| {
+                        (amounts.received(mint_a), amounts.received(mint_b))
+                    });
                     result.fees_collected = Some(fees);
-                    result.tx_cost_lamports += 5000; // Approximate
+                    result.tx_cost_lamports += tx_cost(&collected);
 
                     // Record in lifecycle
                     self.lifecycle
@@ -217,9 +303,13 @@ impl RebalanceExecutor {
                                 fees_a: fees.0,
                                 fees_b: fees.1,
                                 fees_usd: Decimal::ZERO, // Would need price oracle
+                                rewards: [0; NUM_REWARDS],
+                                rewards_usd: Decimal::ZERO,
                             },
                         )
                         .await;
+                    self.watch(&params.position, &collected, mint_a, mint_b)
+                        .await;
                 }
                 Err(e) => {
                     warn!(error = %e, "Failed to collect fees, continuing");
@@ -227,60 +317,49 @@ impl RebalanceExecutor {
             }
         }
 
-        // Step 2: Decrease liquidity from current position
-        match self
-            .decrease_liquidity(&params.position, params.current_liquidity)
-            .await
-        {
-            Ok(liquidity) => {
-                result.liquidity_removed = liquidity;
-                result.tx_cost_lamports += 5000;
-            }
-            Err(e) => {
-                error!(error = %e, "Failed to decrease liquidity");
-                result.error = Some(e.to_string());
-                return result;
-            }
-        }
-
-        // Step 3: Close old position
-        if let Err(e) = self.close_position(&params.position).await {
-            error!(error = %e, "Failed to close position");
-            result.error = Some(e.to_string());
-            return result;
-        }
-        result.tx_cost_lamports += 5000;
-
-        // Step 4: Open new position
-        let new_position = match self
-            .open_position(&params.pool, params.new_tick_lower, params.new_tick_upper)
-            .await
-        {
-            Ok(pos) => pos,
-            Err(e) => {
-                error!(error = %e, "Failed to open new position");
-                result.error = Some(e.to_string());
-                return result;
-            }
+        // Step 2: Withdraw all liquidity and close the old position
+        let closed = confirmed(
+            executor
+                .close_position(&params.position, &params.pool, payer)
+                .await?,
+        )?;
+        result.liquidity_removed = params.current_liquidity;
+        result.tx_cost_lamports += tx_cost(&closed);
+
+        let (amount_a, amount_b) = match &closed.amounts {
+            Some(amounts) => (amounts.received(mint_a), amounts.received(mint_b)),
+            None => token_amounts_for_liquidity(
+                params.current_liquidity,
+                pool.sqrt_price,
+                params.current_tick_lower,
+                params.current_tick_upper,
+                false,
+            )
+            .ok_or_else(|| anyhow::anyhow!("Withdrawn amounts overflow"))?,
         };
-        result.new_position = Some(new_position);
-        result.tx_cost_lamports += 5000;
 
-        // Step 5: Increase liquidity in new position
-        match self
-            .increase_liquidity(&new_position, params.current_liquidity)
-            .await
-        {
-            Ok(liquidity) => {
-                result.liquidity_added = liquidity;
-                result.tx_cost_lamports += 5000;
-            }
-            Err(e) => {
-                error!(error = %e, "Failed to increase liquidity");
-                result.error = Some(e.to_string());
-                return result;
-            }
-        }
+        // Step 3: Open the new position and deposit into it
+        let quote = quote_deposit(
+            &pool,
+            params.new_tick_lower,
+            params.new_tick_upper,
+            amount_a,
+            amount_b,
+            self.config.max_slippage_bps,
+        )?;
+        let (new_position, opened) = executor
+            .open_position(
+                &OpenPositionParams {
+                    pool: params.pool,
+                    quote: quote.clone(),
+                },
+                payer,
+            )
+            .await?;
+        let opened = confirmed(opened)?;
+        result.new_position = Some(new_position);
+        result.liquidity_added = quote.liquidity;
+        result.tx_cost_lamports += tx_cost(&opened);
 
         // Record rebalance in lifecycle
         self.lifecycle
@@ -296,73 +375,266 @@ impl RebalanceExecutor {
                     new_liquidity: result.liquidity_added,
                     tx_cost_lamports: result.tx_cost_lamports,
                     il_at_rebalance: params.current_il_pct,
-                    reason: params.reason,
+                    reason: params.reason.clone(),
                 },
             )
             .await;
+        self.watch(&new_position, &opened, mint_a, mint_b).await;
+        Ok(())
+    }
 
-        result.success = true;
+    /// Simulates the complete rebalance instruction sequence and checks the
+    /// outcome against expectations.
+    ///
+    /// Skipped in dry run mode without a wallet, since there is no owner.
+    async fn simulate_bundle(&self, params: &RebalanceParams) -> anyhow::Result<()> {
+        let owner = match &self.wallet {
+            Some(wallet) => wallet.pubkey(),
+            None if self.dry_run => {
+                debug!("No wallet, skipping rebalance simulation");
+                return Ok(());
+            }
+            None => anyhow::bail!("No wallet to simulate the rebalance with"),
+        };
+
+        let position_reader = PositionReader::new(self.provider.clone());
+        let position = position_reader
+            .get_position(&params.position.to_string())
+            .await?;
+        let pool = WhirlpoolReader::new(self.provider.clone())
+            .get_pool_state(&params.pool.to_string())
+            .await?;
+        let (amount_a, amount_b) =
+            position_reader.calculate_token_amounts(&position, pool.sqrt_price)?;
+
+        let executor = WhirlpoolExecutor::new(self.provider.clone());
+        let bundle = executor
+            .build_rebalance_bundle(
+                &RebalanceBundleParams {
+                    position: params.position,
+                    pool: params.pool,
+                    new_tick_lower: params.new_tick_lower,
+                    new_tick_upper: params.new_tick_upper,
+                    amount_a,
+                    amount_b,
+                    slippage_bps: self.config.max_slippage_bps,
+                },
+                &owner,
+            )
+            .await?;
+        let simulation = executor.simulate_rebalance_bundle(&bundle, &owner).await?;
+
+        check_bundle_simulation(&bundle, &simulation, self.config.max_slippage_bps)?;
         info!(
-            old_position = %params.position,
-            new_position = %new_position,
-            tx_cost = result.tx_cost_lamports,
-            "Rebalance completed successfully"
+            position = %params.position,
+            units_consumed = ?simulation.units_consumed,
+            delta_a = simulation.amounts.delta(&bundle.token_mint_a),
+            delta_b = simulation.amounts.delta(&bundle.token_mint_b),
+            "Rebalance simulation passed"
         );
-
-        result
+        Ok(())
     }
 
-    /// Collects fees from a position.
-    async fn collect_fees(&self, _position: &Pubkey) -> anyhow::Result<(u64, u64)> {
-        // TODO: Implement actual fee collection via Whirlpool instruction
-        debug!("Would collect fees");
-        Ok((0, 0))
+    /// Adds or removes part of a position's liquidity without changing its range.
+    ///
+    /// Decreases are capped at the position's current liquidity. Outside dry
+    /// run the change is sent and recorded once confirmed. Returns the
+    /// applied change.
+    pub async fn adjust_liquidity(
+        &self,
+        position: Pubkey,
+        pool: Pubkey,
+        current_liquidity: u128,
+        is_increase: bool,
+        liquidity_delta: u128,
+    ) -> anyhow::Result<LiquidityChangeData> {
+        let delta = if is_increase {
+            liquidity_delta
+        } else {
+            liquidity_delta.min(current_liquidity)
+        };
+        if delta == 0 {
+            anyhow::bail!("Liquidity change is zero");
+        }
+
+        info!(
+            position = %position,
+            is_increase = is_increase,
+            delta = delta,
+            dry_run = self.dry_run,
+            "Adjusting liquidity"
+        );
+
+        let new_liquidity = if is_increase {
+            current_liquidity.saturating_add(delta)
+        } else {
+            current_liquidity.saturating_sub(delta)
+        };
+        let mut data = LiquidityChangeData {
+            is_increase,
+            liquidity_delta: delta,
+            amount_a: 0,
+            amount_b: 0,
+            new_liquidity,
+        };
+
+        if self.dry_run {
+            info!("Dry run mode - simulating liquidity change");
+            return Ok(data);
+        }
+
+        let wallet = self
+            .wallet
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("No wallet to sign the liquidity change with"))?;
+        let on_chain = PositionReader::new(self.provider.clone())
+            .get_position(&position.to_string())
+            .await?;
+        let pool_state = WhirlpoolReader::new(self.provider.clone())
+            .get_pool_state(&pool.to_string())
+            .await?;
+
+        // The program rounds deposits up and withdrawals down
+        let (amount_a, amount_b) = token_amounts_for_liquidity(
+            delta,
+            pool_state.sqrt_price,
+            on_chain.tick_lower,
+            on_chain.tick_upper,
+            is_increase,
+        )
+        .ok_or_else(|| anyhow::anyhow!("Token amounts for liquidity overflow"))?;
+        let tolerance = f64::from(self.config.max_slippage_bps) / 10_000.0;
+
+        let executor = self.whirlpool_executor();
+        let result = if is_increase {
+            let max = |amount: u64| (amount as f64 * (1.0 + tolerance)).ceil() as u64;
+            executor
+                .increase_liquidity(
+                    &IncreaseLiquidityParams {
+                        position,
+                        pool,
+                        liquidity_amount: delta,
+                        token_max_a: max(amount_a),
+                        token_max_b: max(amount_b),
+                    },
+                    wallet.keypair(),
+                )
+                .await?
+        } else {
+            let min = |amount: u64| (amount as f64 * (1.0 - tolerance).max(0.0)).floor() as u64;
+            executor
+                .decrease_liquidity(
+                    &DecreaseLiquidityParams {
+                        position,
+                        pool,
+                        liquidity_amount: delta,
+                        token_min_a: min(amount_a),
+                        token_min_b: min(amount_b),
+                    },
+                    wallet.keypair(),
+                )
+                .await?
+        };
+        if !result.success {
+            anyhow::bail!(
+                "Liquidity change failed: {}",
+                result.error.as_deref().unwrap_or("unknown error")
+            );
+        }
+
+        // Executed amounts replace the estimates once read back
+        data.amount_a = amount_a;
+        data.amount_b = amount_b;
+        if let Some(amounts) = &result.amounts {
+            let (mint_a, mint_b) = (&pool_state.token_mint_a, &pool_state.token_mint_b);
+            (data.amount_a, data.amount_b) = if is_increase {
+                (amounts.sent(mint_a), amounts.sent(mint_b))
+            } else {
+                (amounts.received(mint_a), amounts.received(mint_b))
+            };
+        }
+        self.lifecycle
+            .record_liquidity_change(position, pool, data.clone())
+            .await;
+        self.watch(
+            &position,
+            &result,
+            &pool_state.token_mint_a,
+            &pool_state.token_mint_b,
+        )
+        .await;
+
+        Ok(data)
     }
 
-    /// Decreases liquidity from a position.
-    async fn decrease_liquidity(
+    /// Attaches a confirmed execution to the position's latest lifecycle
+    /// event and tracks its finality.
+    async fn watch(
         &self,
-        _position: &Pubkey,
-        liquidity: u128,
-    ) -> anyhow::Result<u128> {
-        // TODO: Implement actual liquidity decrease via Whirlpool instruction
-        debug!(liquidity = liquidity, "Would decrease liquidity");
-        Ok(liquidity)
+        position: &Pubkey,
+        result: &ExecutionResult,
+        mint_a: &Pubkey,
+        mint_b: &Pubkey,
+    ) {
+        if self
+            .lifecycle
+            .attach_execution(position, result, mint_a, mint_b)
+            .await
+        {
+            self.tx_manager
+                .watch_finality(result.signature, self.lifecycle.clone());
+        }
     }
 
-    /// Closes a position.
-    async fn close_position(&self, _position: &Pubkey) -> anyhow::Result<()> {
-        // TODO: Implement actual position close via Whirlpool instruction
-        debug!("Would close position");
-        Ok(())
+    /// Returns a Whirlpool executor sending through the transaction manager.
+    fn whirlpool_executor(&self) -> WhirlpoolExecutor {
+        WhirlpoolExecutor::new(self.provider.clone()).with_sender(self.tx_manager.clone())
     }
+}
 
-    /// Opens a new position.
-    async fn open_position(
-        &self,
-        _pool: &Pubkey,
-        tick_lower: i32,
-        tick_upper: i32,
-    ) -> anyhow::Result<Pubkey> {
-        // TODO: Implement actual position open via Whirlpool instruction
-        debug!(
-            tick_lower = tick_lower,
-            tick_upper = tick_upper,
-            "Would open position"
-        );
-        Ok(Pubkey::new_unique())
+/// Checks a simulated rebalance bundle: it must succeed, close the old
+/// position, fund the new one with the quoted liquidity, and cost the owner
+/// no more of either token than the slippage allowance of the deposit.
+///
+/// # Errors
+/// Returns an error describing the first unmet expectation.
+pub fn check_bundle_simulation(
+    bundle: &RebalanceBundle,
+    simulation: &BundleSimulation,
+    max_slippage_bps: u16,
+) -> anyhow::Result<()> {
+    if let Some(err) = &simulation.error {
+        anyhow::bail!("Bundle would fail: {}", err);
+    }
+    if !simulation.old_position_closed {
+        anyhow::bail!("Old position {} would stay open", bundle.old_position);
+    }
+    match simulation.new_position_liquidity {
+        Some(liquidity) if liquidity >= bundle.quote.liquidity => {}
+        Some(liquidity) => anyhow::bail!(
+            "New position would hold {} liquidity, expected {}",
+            liquidity,
+            bundle.quote.liquidity
+        ),
+        None => anyhow::bail!("New position {} would not exist", bundle.new_position),
     }
 
-    /// Increases liquidity in a position.
-    async fn increase_liquidity(
-        &self,
-        _position: &Pubkey,
-        liquidity: u128,
-    ) -> anyhow::Result<u128> {
-        // TODO: Implement actual liquidity increase via Whirlpool instruction
-        debug!(liquidity = liquidity, "Would increase liquidity");
-        Ok(liquidity)
+    let allowance = |amount: u64| i128::from(amount) * i128::from(max_slippage_bps) / 10_000;
+    for (mint, deposit) in [
+        (&bundle.token_mint_a, bundle.quote.amount_a),
+        (&bundle.token_mint_b, bundle.quote.amount_b),
+    ] {
+        let delta = simulation.amounts.delta(mint);
+        if delta < -allowance(deposit) {
+            anyhow::bail!(
+                "Bundle would cost {} of token {}, allowance is {}",
+                -delta,
+                mint,
+                allowance(deposit)
+            );
+        }
     }
+    Ok(())
 }
 
 /// Result of profitability check.
@@ -381,6 +653,7 @@ pub struct ProfitabilityCheck {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use solana_sdk::signature::Keypair;
 
     #[tokio::test]
     async fn test_rebalance_config_default() {
@@ -388,4 +661,107 @@ mod tests {
         assert_eq!(config.max_slippage_bps, 50);
         assert!(config.collect_fees_first);
     }
+
+    #[test]
+    fn test_check_bundle_simulation() {
+        let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let bundle = RebalanceBundle {
+            instructions: Vec::new(),
+            old_position: Pubkey::new_unique(),
+            new_position: Pubkey::new_unique(),
+            position_mint: Arc::new(Keypair::new()),
+            token_mint_a: mint_a,
+            token_mint_b: mint_b,
+            quote: Quote {
+                side: DepositSide::TwoSided,
+                tick_lower: -640,
+                tick_upper: 640,
+                liquidity: 1_000,
+                amount_a: 10_000,
+                amount_b: 20_000,
+                token_max_a: 10_050,
+                token_max_b: 20_100,
+                token_min_a: 9_950,
+                token_min_b: 19_900,
+                slippage_bps: 50,
+            },
+        };
+        let simulation = |delta_a: i64| BundleSimulation {
+            error: None,
+            logs: Vec::new(),
+            units_consumed: Some(200_000),
+            amounts: ExecutedAmounts {
+                slot: 1,
+                fee_lamports: 5_000,
+                token_changes: vec![TokenBalanceChange {
+                    mint: mint_a,
+                    pre: 100_000,
+                    post: (100_000 + delta_a) as u64,
+                }],
+            },
+            old_position_closed: true,
+            new_position_liquidity: Some(1_000),
+        };
+
+        // Collected fees and dust within the 0.5% allowance pass
+        assert!(check_bundle_simulation(&bundle, &simulation(120), 50).is_ok());
+        assert!(check_bundle_simulation(&bundle, &simulation(-50), 50).is_ok());
+        // Losing more than the allowance of token A fails
+        assert!(check_bundle_simulation(&bundle, &simulation(-51), 50).is_err());
+
+        let mut unfunded = simulation(0);
+        unfunded.new_position_liquidity = Some(10);
+        assert!(check_bundle_simulation(&bundle, &unfunded, 50).is_err());
+
+        let mut failed = simulation(0);
+        failed.error = Some("custom program error: 0x1771".to_string());
+        assert!(check_bundle_simulation(&bundle, &failed, 50).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_adjust_liquidity_caps_and_records_only_executed_changes() {
+        let provider = Arc::new(RpcProvider::new(RpcConfig::default()));
+        let tx_manager = Arc::new(TransactionManager::new(
+            provider.clone(),
+            crate::transaction::TransactionConfig::default(),
+        ));
+        let lifecycle = Arc::new(LifecycleTracker::new());
+        let mut executor = RebalanceExecutor::new(
+            provider,
+            tx_manager,
+            lifecycle.clone(),
+            RebalanceConfig::default(),
+        );
+        let (position, pool) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        // Without a wallet nothing can be sent, so nothing is recorded
+        assert!(
+            executor
+                .adjust_liquidity(position, pool, 1_000, true, 400)
+                .await
+                .is_err()
+        );
+
+        executor.set_dry_run(true);
+        let decreased = executor
+            .adjust_liquidity(position, pool, 1_000, false, 5_000)
+            .await
+            .unwrap();
+        assert_eq!(decreased.liquidity_delta, 1_000);
+        assert_eq!(decreased.new_liquidity, 0);
+
+        let increased = executor
+            .adjust_liquidity(position, pool, 0, true, 400)
+            .await
+            .unwrap();
+        assert_eq!(increased.new_liquidity, 400);
+        assert!(
+            executor
+                .adjust_liquidity(position, pool, 0, false, 10)
+                .await
+                .is_err()
+        );
+
+        assert!(lifecycle.get_events(&position).await.is_empty());
+    }
 }
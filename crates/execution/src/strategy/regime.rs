@@ -0,0 +1,147 @@
+//! Market regime detection for strategy decisions.
+//!
+//! Classifies recent price action as range-bound or trending using the
+//! efficiency ratio: net price change divided by the total path travelled.
+//! A ratio near 0 means price is chopping around; near 1 means it is moving
+//! steadily in one direction.
+
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Market regime derived from recent prices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MarketRegime {
+    /// Price oscillates without a clear direction.
+    RangeBound,
+    /// Price is moving steadily up.
+    TrendingUp,
+    /// Price is moving steadily down.
+    TrendingDown,
+}
+
+impl MarketRegime {
+    /// Returns true for either trending regime.
+    #[must_use]
+    pub fn is_trending(&self) -> bool {
+        !matches!(self, Self::RangeBound)
+    }
+}
+
+impl fmt::Display for MarketRegime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RangeBound => write!(f, "range-bound"),
+            Self::TrendingUp => write!(f, "trending up"),
+            Self::TrendingDown => write!(f, "trending down"),
+        }
+    }
+}
+
+/// Regime signal computed from a price window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegimeSignal {
+    /// Efficiency ratio between 0 (choppy) and 1 (straight line).
+    pub trend_strength: Decimal,
+    /// Net price change over the window as a fraction of the first price.
+    pub net_change_pct: Decimal,
+    /// Standard deviation of per-sample returns.
+    pub volatility: Decimal,
+    /// Number of prices in the window.
+    pub samples: usize,
+    /// Classified regime.
+    pub regime: MarketRegime,
+}
+
+impl RegimeSignal {
+    /// Computes the signal from prices ordered oldest first.
+    ///
+    /// Prices count as trending once the trend strength reaches `threshold`.
+    /// Returns `None` with fewer than three prices or a non-positive start.
+    #[must_use]
+    pub fn from_prices(prices: &[Decimal], threshold: Decimal) -> Option<Self> {
+        let (first, last) = (*prices.first()?, *prices.last()?);
+        if prices.len() < 3 || first <= Decimal::ZERO {
+            return None;
+        }
+
+        let net = last - first;
+        let path: Decimal = prices.windows(2).map(|w| (w[1] - w[0]).abs()).sum();
+        let trend_strength = if path.is_zero() {
+            Decimal::ZERO
+        } else {
+            net.abs() / path
+        };
+
+        let regime = if trend_strength < threshold {
+            MarketRegime::RangeBound
+        } else if net > Decimal::ZERO {
+            MarketRegime::TrendingUp
+        } else {
+            MarketRegime::TrendingDown
+        };
+
+        Some(Self {
+            trend_strength,
+            net_change_pct: net / first,
+            volatility: return_std_dev(prices),
+            samples: prices.len(),
+            regime,
+        })
+    }
+}
+
+/// Standard deviation of simple returns between consecutive prices.
+fn return_std_dev(prices: &[Decimal]) -> Decimal {
+    let returns: Vec<f64> = prices
+        .windows(2)
+        .filter(|w| !w[0].is_zero())
+        .filter_map(|w| ((w[1] - w[0]) / w[0]).to_f64())
+        .collect();
+    if returns.len() < 2 {
+        return Decimal::ZERO;
+    }
+
+    let n = returns.len() as f64;
+    let mean = returns.iter().sum::<f64>() / n;
+    let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    Decimal::from_f64(variance.sqrt()).unwrap_or(Decimal::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prices(values: &[i64]) -> Vec<Decimal> {
+        values.iter().map(|v| Decimal::from(*v)).collect()
+    }
+
+    #[test]
+    fn test_steady_move_is_trending() {
+        let threshold = Decimal::new(6, 1);
+
+        let up = RegimeSignal::from_prices(&prices(&[100, 102, 104, 106, 108]), threshold).unwrap();
+        assert_eq!(up.trend_strength, Decimal::ONE);
+        assert_eq!(up.regime, MarketRegime::TrendingUp);
+        assert_eq!(up.net_change_pct, Decimal::new(8, 2));
+
+        let down = RegimeSignal::from_prices(&prices(&[100, 98, 99, 95, 92]), threshold).unwrap();
+        assert_eq!(down.regime, MarketRegime::TrendingDown);
+    }
+
+    #[test]
+    fn test_chop_is_range_bound() {
+        let signal =
+            RegimeSignal::from_prices(&prices(&[100, 104, 98, 103, 99, 101]), Decimal::new(6, 1))
+                .unwrap();
+
+        // Net 1 over a path of 4 + 6 + 5 + 4 + 2 = 21
+        assert_eq!(signal.trend_strength, Decimal::ONE / Decimal::from(21));
+        assert_eq!(signal.regime, MarketRegime::RangeBound);
+        assert!(!signal.regime.is_trending());
+        assert!(signal.volatility > Decimal::new(4, 2));
+
+        assert!(RegimeSignal::from_prices(&prices(&[100, 101]), Decimal::ONE).is_none());
+    }
+}
@@ -0,0 +1,273 @@
+//! Dead-man's switch for background loops.
+//!
+//! A loop that hangs (a stuck RPC call, a deadlock) never panics, so the
+//! supervisor cannot see it. Loops instead beat a [`Heartbeat`] after every
+//! cycle, and the [`HeartbeatMonitor`] raises a critical alert once a beat is
+//! overdue.
+
+use crate::alerts::{Alert, AlertLevel, AlertType, MultiNotifier};
+use crate::shutdown::CancellationToken;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+use tracing::{error, info};
+
+/// Configuration for the heartbeat monitor.
+#[derive(Debug, Clone)]
+pub struct HeartbeatConfig {
+    /// How often heartbeats are checked in seconds.
+    pub check_interval_secs: u64,
+}
+
+impl Default for HeartbeatConfig {
+    fn default() -> Self {
+        Self {
+            check_interval_secs: 15,
+        }
+    }
+}
+
+/// Shared state of one heartbeat.
+#[derive(Debug)]
+struct Beat {
+    /// Name of the loop.
+    name: String,
+    /// Longest allowed time between beats.
+    window: Duration,
+    /// Time of the last beat, or of registration.
+    last: Mutex<Instant>,
+    /// Whether the loop is expected to be running.
+    active: AtomicBool,
+    /// Whether a stall has been reported and not yet cleared.
+    stalled: AtomicBool,
+}
+
+impl Beat {
+    fn since_last(&self, now: Instant) -> Duration {
+        let last = *self.last.lock().unwrap_or_else(|e| e.into_inner());
+        now.saturating_duration_since(last)
+    }
+}
+
+/// Handle a loop uses to report that it completed a cycle.
+///
+/// The loop stops being watched once every clone of its handle is dropped.
+#[derive(Debug, Clone)]
+pub struct Heartbeat {
+    beat: Arc<Beat>,
+}
+
+impl Heartbeat {
+    /// Records a completed cycle.
+    pub fn beat(&self) {
+        *self.beat.last.lock().unwrap_or_else(|e| e.into_inner()) = Instant::now();
+        self.beat.active.store(true, Ordering::SeqCst);
+    }
+
+    /// Stops watching the loop until its next beat, for a deliberate stop.
+    pub fn pause(&self) {
+        self.beat.active.store(false, Ordering::SeqCst);
+    }
+
+    /// Returns the name of the loop.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.beat.name
+    }
+
+    /// Returns the longest allowed time between beats.
+    #[must_use]
+    pub fn window(&self) -> Duration {
+        self.beat.window
+    }
+}
+
+/// Heartbeat state of a watched loop.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeartbeatStatus {
+    /// Name of the loop.
+    pub name: String,
+    /// Longest allowed time between beats.
+    pub window: Duration,
+    /// Time since the last beat.
+    pub since_last: Duration,
+    /// Whether the loop is considered stalled.
+    pub stalled: bool,
+}
+
+/// Raises a critical alert when a watched loop stops completing cycles.
+pub struct HeartbeatMonitor {
+    /// Configuration.
+    config: HeartbeatConfig,
+    /// Notifier for stalls and recoveries.
+    notifier: MultiNotifier,
+    /// Registered heartbeats; dropped handles are pruned on check.
+    beats: Mutex<Vec<Weak<Beat>>>,
+}
+
+impl HeartbeatMonitor {
+    /// Creates a heartbeat monitor.
+    #[must_use]
+    pub fn new(config: HeartbeatConfig) -> Self {
+        Self {
+            config,
+            notifier: MultiNotifier::new(),
+            beats: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Sets the notifier for stalls and recoveries.
+    #[must_use]
+    pub fn with_notifier(mut self, notifier: MultiNotifier) -> Self {
+        self.notifier = notifier;
+        self
+    }
+
+    /// Registers a loop that must beat at least once per `window`.
+    ///
+    /// The window starts now, so a loop that never completes its first
+    /// cycle is reported too.
+    pub fn register(&self, name: impl Into<String>, window: Duration) -> Heartbeat {
+        let beat = Arc::new(Beat {
+            name: name.into(),
+            window,
+            last: Mutex::new(Instant::now()),
+            active: AtomicBool::new(true),
+            stalled: AtomicBool::new(false),
+        });
+        self.beats
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(Arc::downgrade(&beat));
+        Heartbeat { beat }
+    }
+
+    /// Returns the state of every watched loop.
+    #[must_use]
+    pub fn status(&self) -> Vec<HeartbeatStatus> {
+        let now = Instant::now();
+        self.live_beats()
+            .iter()
+            .filter(|beat| beat.active.load(Ordering::SeqCst))
+            .map(|beat| HeartbeatStatus {
+                name: beat.name.clone(),
+                window: beat.window,
+                since_last: beat.since_last(now),
+                stalled: beat.stalled.load(Ordering::SeqCst),
+            })
+            .collect()
+    }
+
+    /// Checks every heartbeat once, returning an alert for each loop that
+    /// stalled or recovered since the last check.
+    pub fn check(&self) -> Vec<Alert> {
+        let now = Instant::now();
+        let mut alerts = Vec::new();
+        for beat in self.live_beats() {
+            let elapsed = beat.since_last(now);
+            let overdue = beat.active.load(Ordering::SeqCst) && elapsed > beat.window;
+            let was_stalled = beat.stalled.swap(overdue, Ordering::SeqCst);
+
+            if overdue && !was_stalled {
+                error!(
+                    task = %beat.name,
+                    elapsed_secs = elapsed.as_secs(),
+                    "Loop missed its heartbeat"
+                );
+                alerts.push(Alert::new(
+                    AlertLevel::Critical,
+                    AlertType::SystemError,
+                    format!(
+                        "Bot appears stalled: '{}' has not completed a cycle in {}s (window {}s)",
+                        beat.name,
+                        elapsed.as_secs(),
+                        beat.window.as_secs()
+                    ),
+                ));
+            } else if !overdue && was_stalled {
+                info!(task = %beat.name, "Loop heartbeat recovered");
+                alerts.push(Alert::new(
+                    AlertLevel::Info,
+                    AlertType::SystemError,
+                    format!("'{}' is completing cycles again", beat.name),
+                ));
+            }
+        }
+        alerts
+    }
+
+    /// Checks heartbeats periodically until `shutdown` is cancelled.
+    pub async fn run_until(self: Arc<Self>, shutdown: CancellationToken) {
+        let mut ticker =
+            tokio::time::interval(Duration::from_secs(self.config.check_interval_secs.max(1)));
+        loop {
+            tokio::select! {
+                () = shutdown.cancelled() => break,
+                _ = ticker.tick() => {}
+            }
+            for alert in self.check() {
+                self.notifier.notify_all(&alert).await;
+            }
+        }
+    }
+
+    /// Returns the registered heartbeats that still have a handle, pruning
+    /// the rest.
+    fn live_beats(&self) -> Vec<Arc<Beat>> {
+        let mut beats = self.beats.lock().unwrap_or_else(|e| e.into_inner());
+        beats.retain(|beat| beat.strong_count() > 0);
+        beats.iter().filter_map(Weak::upgrade).collect()
+    }
+}
+
+impl Default for HeartbeatMonitor {
+    fn default() -> Self {
+        Self::new(HeartbeatConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: Duration = Duration::from_millis(50);
+
+    #[tokio::test]
+    async fn test_overdue_loop_alerts_once_and_recovers() {
+        let monitor = HeartbeatMonitor::default();
+        let heartbeat = monitor.register("executor", WINDOW);
+        assert!(monitor.check().is_empty());
+
+        tokio::time::sleep(WINDOW * 2).await;
+        let alerts = monitor.check();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].level, AlertLevel::Critical);
+        assert!(alerts[0].message.contains("'executor'"));
+        assert!(monitor.status()[0].stalled);
+
+        // Reported once per stall
+        assert!(monitor.check().is_empty());
+
+        heartbeat.beat();
+        let alerts = monitor.check();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].level, AlertLevel::Info);
+        assert!(!monitor.status()[0].stalled);
+    }
+
+    #[tokio::test]
+    async fn test_paused_and_dropped_loops_are_not_watched() {
+        let monitor = HeartbeatMonitor::default();
+        let stopped = monitor.register("stopped", WINDOW);
+        let dropped = monitor.register("dropped", WINDOW);
+        stopped.pause();
+        drop(dropped);
+
+        tokio::time::sleep(WINDOW * 2).await;
+        assert!(monitor.check().is_empty());
+        assert!(monitor.status().is_empty());
+
+        stopped.beat();
+        assert_eq!(monitor.status().len(), 1);
+    }
+}
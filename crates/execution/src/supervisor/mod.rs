@@ -0,0 +1,12 @@
+//! Supervision of background loops.
+//!
+//! A loop that panics or returns unexpectedly is restarted with exponential
+//! backoff, so a single bad RPC response does not leave monitor or sync
+//! state silently stale. Repeated failures raise an alert, and loops that
+//! hang without failing are caught by their missed heartbeats.
+
+mod heartbeat;
+mod task;
+
+pub use heartbeat::*;
+pub use task::*;
@@ -0,0 +1,279 @@
+//! Supervised task runner.
+
+use crate::alerts::{Alert, AlertLevel, AlertType, MultiNotifier};
+use crate::shutdown::CancellationToken;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tracing::{error, info, warn};
+
+/// When a supervised task is restarted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RestartPolicy {
+    /// Restart only after a panic; a normal return ends supervision.
+    #[default]
+    OnPanic,
+    /// Restart whenever the task ends before shutdown, e.g. a listener
+    /// that gave up reconnecting.
+    Always,
+}
+
+/// Configuration for the supervisor.
+#[derive(Debug, Clone)]
+pub struct SupervisorConfig {
+    /// Delay before the first restart in milliseconds.
+    pub initial_backoff_ms: u64,
+    /// Maximum delay between restarts in milliseconds.
+    pub max_backoff_ms: u64,
+    /// Consecutive failures after which an alert is raised.
+    pub alert_after_failures: u32,
+    /// A run lasting this long resets the failure count, in seconds.
+    pub healthy_after_secs: u64,
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        Self {
+            initial_backoff_ms: 1_000,
+            max_backoff_ms: 60_000,
+            alert_after_failures: 3,
+            healthy_after_secs: 300,
+        }
+    }
+}
+
+impl SupervisorConfig {
+    /// Returns the delay before restarting after `failures` consecutive failures.
+    #[must_use]
+    pub fn backoff(&self, failures: u32) -> Duration {
+        let factor = 2u64.saturating_pow(failures.saturating_sub(1));
+        Duration::from_millis(
+            self.initial_backoff_ms
+                .saturating_mul(factor)
+                .min(self.max_backoff_ms),
+        )
+    }
+}
+
+/// Health of a supervised task.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaskHealth {
+    /// Whether the task is currently running.
+    pub running: bool,
+    /// Number of restarts since supervision began.
+    pub restarts: u32,
+    /// Failures since the task last ran healthily.
+    pub consecutive_failures: u32,
+    /// Last failure description.
+    pub last_error: Option<String>,
+}
+
+/// Restarts background loops that panic or exit unexpectedly.
+pub struct Supervisor {
+    /// Configuration.
+    config: SupervisorConfig,
+    /// Cancelled on shutdown; supervised tasks stop and are not restarted.
+    shutdown: CancellationToken,
+    /// Notifier for repeated failures.
+    notifier: MultiNotifier,
+    /// Health by task name.
+    health: RwLock<HashMap<String, TaskHealth>>,
+}
+
+impl Supervisor {
+    /// Creates a supervisor that stops restarting once `shutdown` is cancelled.
+    pub fn new(config: SupervisorConfig, shutdown: CancellationToken) -> Self {
+        Self {
+            config,
+            shutdown,
+            notifier: MultiNotifier::new(),
+            health: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Sets the notifier for repeated failures.
+    #[must_use]
+    pub fn with_notifier(mut self, notifier: MultiNotifier) -> Self {
+        self.notifier = notifier;
+        self
+    }
+
+    /// Returns the health of all supervised tasks.
+    pub async fn health(&self) -> HashMap<String, TaskHealth> {
+        self.health.read().await.clone()
+    }
+
+    /// Runs a task under supervision until shutdown.
+    ///
+    /// `factory` builds a fresh run of the task from the shutdown token each
+    /// time it is (re)started. The returned future is meant to be handed to
+    /// [`crate::shutdown::ShutdownController::spawn`].
+    pub fn supervise<F, Fut>(
+        self: &Arc<Self>,
+        name: impl Into<String>,
+        policy: RestartPolicy,
+        factory: F,
+    ) -> impl Future<Output = ()> + Send + 'static
+    where
+        F: Fn(CancellationToken) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let supervisor = self.clone();
+        let name = name.into();
+        async move {
+            loop {
+                supervisor.set_running(&name, true).await;
+                let started = Instant::now();
+                let outcome = tokio::spawn(factory(supervisor.shutdown.clone())).await;
+                supervisor.set_running(&name, false).await;
+
+                if supervisor.shutdown.is_cancelled() {
+                    break;
+                }
+
+                let reason = match outcome {
+                    Err(e) if e.is_panic() => "panicked".to_string(),
+                    Err(e) => e.to_string(),
+                    Ok(()) if policy == RestartPolicy::Always => "exited".to_string(),
+                    Ok(()) => {
+                        info!(task = %name, "Supervised task finished");
+                        break;
+                    }
+                };
+
+                let healthy =
+                    started.elapsed() >= Duration::from_secs(supervisor.config.healthy_after_secs);
+                let failures = supervisor.record_failure(&name, &reason, healthy).await;
+                let delay = supervisor.config.backoff(failures);
+                warn!(
+                    task = %name,
+                    reason = %reason,
+                    failures,
+                    delay_ms = delay.as_millis(),
+                    "Supervised task stopped, restarting"
+                );
+
+                if failures == supervisor.config.alert_after_failures {
+                    supervisor.alert(&name, &reason, failures).await;
+                }
+
+                tokio::select! {
+                    () = supervisor.shutdown.cancelled() => break,
+                    () = tokio::time::sleep(delay) => {}
+                }
+            }
+        }
+    }
+
+    async fn set_running(&self, name: &str, running: bool) {
+        self.health
+            .write()
+            .await
+            .entry(name.to_string())
+            .or_default()
+            .running = running;
+    }
+
+    /// Records a failure and returns the consecutive failure count.
+    async fn record_failure(&self, name: &str, reason: &str, healthy: bool) -> u32 {
+        let mut health = self.health.write().await;
+        let entry = health.entry(name.to_string()).or_default();
+        if healthy {
+            entry.consecutive_failures = 0;
+        }
+        entry.consecutive_failures += 1;
+        entry.restarts += 1;
+        entry.last_error = Some(reason.to_string());
+        entry.consecutive_failures
+    }
+
+    async fn alert(&self, name: &str, reason: &str, failures: u32) {
+        error!(task = %name, failures, "Supervised task keeps failing");
+        let alert = Alert::new(
+            AlertLevel::Critical,
+            AlertType::SystemError,
+            format!(
+                "Background task '{}' failed {} times in a row (last: {})",
+                name, failures, reason
+            ),
+        );
+        self.notifier.notify_all(&alert).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn fast_config() -> SupervisorConfig {
+        SupervisorConfig {
+            initial_backoff_ms: 1,
+            max_backoff_ms: 4,
+            alert_after_failures: 2,
+            healthy_after_secs: 60,
+        }
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_cap() {
+        let config = SupervisorConfig::default();
+        assert_eq!(config.backoff(1), Duration::from_secs(1));
+        assert_eq!(config.backoff(3), Duration::from_secs(4));
+        assert_eq!(config.backoff(40), Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn test_restarts_after_panic() {
+        let supervisor = Arc::new(Supervisor::new(fast_config(), CancellationToken::new()));
+        let runs = Arc::new(AtomicU32::new(0));
+
+        let counter = runs.clone();
+        supervisor
+            .supervise("flaky", RestartPolicy::OnPanic, move |_| {
+                let counter = counter.clone();
+                async move {
+                    // Panics twice, then finishes normally
+                    if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+                        panic!("boom");
+                    }
+                }
+            })
+            .await;
+
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+        let health = supervisor.health().await;
+        let flaky = &health["flaky"];
+        assert_eq!(flaky.restarts, 2);
+        assert_eq!(flaky.consecutive_failures, 2);
+        assert_eq!(flaky.last_error.as_deref(), Some("panicked"));
+        assert!(!flaky.running);
+    }
+
+    #[tokio::test]
+    async fn test_always_policy_stops_on_shutdown() {
+        let shutdown = CancellationToken::new();
+        let supervisor = Arc::new(Supervisor::new(fast_config(), shutdown.clone()));
+        let runs = Arc::new(AtomicU32::new(0));
+
+        let counter = runs.clone();
+        let stopper = shutdown.clone();
+        supervisor
+            .supervise("listener", RestartPolicy::Always, move |_| {
+                let counter = counter.clone();
+                let stopper = stopper.clone();
+                async move {
+                    // Exits immediately; shutdown arrives on the third run
+                    if counter.fetch_add(1, Ordering::SeqCst) == 2 {
+                        stopper.cancel();
+                    }
+                }
+            })
+            .await;
+
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+        assert_eq!(supervisor.health().await["listener"].restarts, 2);
+    }
+}
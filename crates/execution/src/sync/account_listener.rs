@@ -1,11 +1,31 @@
 //! WebSocket account listener for real-time updates.
-
+//!
+//! Accounts are watched with the RPC `accountSubscribe` method, each at its
+//! own commitment level.
+
+use super::{SlotOrderer, StateSource, UpdateOrder, fetch_snapshot};
+use crate::shutdown::CancellationToken;
+use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use clmm_lp_protocols::prelude::{Cluster, CommitmentLevel, RpcProvider};
+use futures::stream::{SplitSink, SplitStream};
+use futures::{SinkExt, StreamExt};
+use serde_json::{Value, json};
 use solana_sdk::pubkey::Pubkey;
 use std::collections::HashMap;
+use std::str::FromStr;
 use std::sync::Arc;
-use tokio::sync::{RwLock, mpsc};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::net::TcpStream;
+use tokio::sync::{Mutex, RwLock, mpsc};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
 use tracing::{debug, error, info, warn};
 
+/// WebSocket connection to the RPC node.
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
 /// Account update event.
 #[derive(Debug, Clone)]
 pub struct AccountUpdate {
@@ -39,6 +59,8 @@ pub struct Subscription {
     pub address: Pubkey,
     /// Subscription type.
     pub sub_type: SubscriptionType,
+    /// Commitment level notifications are sent at.
+    pub commitment: CommitmentLevel,
     /// WebSocket subscription ID (if connected).
     pub ws_subscription_id: Option<u64>,
     /// Whether subscription is active.
@@ -54,17 +76,28 @@ pub struct AccountListenerConfig {
     pub reconnect_delay_secs: u64,
     /// Maximum reconnect attempts.
     pub max_reconnect_attempts: u32,
-    /// Commitment level for subscriptions.
-    pub commitment: String,
+    /// Default commitment level for subscriptions.
+    pub commitment: CommitmentLevel,
+}
+
+impl AccountListenerConfig {
+    /// Creates a configuration for the cluster's public WebSocket endpoint.
+    #[must_use]
+    pub fn for_cluster(cluster: Cluster) -> Self {
+        Self {
+            ws_url: cluster.ws_url().to_string(),
+            ..Default::default()
+        }
+    }
 }
 
 impl Default for AccountListenerConfig {
     fn default() -> Self {
         Self {
-            ws_url: "wss://api.mainnet-beta.solana.com".to_string(),
+            ws_url: Cluster::MainnetBeta.ws_url().to_string(),
             reconnect_delay_secs: 5,
             max_reconnect_attempts: 10,
-            commitment: "confirmed".to_string(),
+            commitment: CommitmentLevel::Confirmed,
         }
     }
 }
@@ -83,6 +116,18 @@ pub struct AccountListener {
     connected: Arc<RwLock<bool>>,
     /// Reconnect attempts.
     reconnect_attempts: Arc<RwLock<u32>>,
+    /// Drops updates older than, or repeating, what was already delivered.
+    slot_orderer: Arc<SlotOrderer>,
+    /// Provider initial snapshots of subscribed accounts are fetched with.
+    provider: Option<Arc<RpcProvider>>,
+    /// Write half of the connection, while connected.
+    sink: Mutex<Option<SplitSink<Socket, Message>>>,
+    /// Read half of the connection, until the event loop takes it.
+    stream: Mutex<Option<SplitStream<Socket>>>,
+    /// Id of the next JSON-RPC request.
+    next_request_id: AtomicU64,
+    /// Accounts whose subscription requests await a reply, by request id.
+    pending: RwLock<HashMap<u64, Pubkey>>,
 }
 
 impl AccountListener {
@@ -96,19 +141,62 @@ impl AccountListener {
             update_rx: Some(rx),
             connected: Arc::new(RwLock::new(false)),
             reconnect_attempts: Arc::new(RwLock::new(0)),
+            slot_orderer: Arc::new(SlotOrderer::new()),
+            provider: None,
+            sink: Mutex::new(None),
+            stream: Mutex::new(None),
+            next_request_id: AtomicU64::new(1),
+            pending: RwLock::new(HashMap::new()),
         }
     }
 
+    /// Fetches a snapshot of each account when it is subscribed and after
+    /// every reconnect, so its state is known before the first change.
+    #[must_use]
+    pub fn with_provider(mut self, provider: Arc<RpcProvider>) -> Self {
+        self.provider = Some(provider);
+        self
+    }
+
+    /// Shares a slot orderer, so ordering survives the listener being
+    /// recreated and drop counts can be reported elsewhere.
+    #[must_use]
+    pub fn with_slot_orderer(mut self, orderer: Arc<SlotOrderer>) -> Self {
+        self.slot_orderer = orderer;
+        self
+    }
+
+    /// Gets the slot orderer updates pass through.
+    pub fn slot_orderer(&self) -> &Arc<SlotOrderer> {
+        &self.slot_orderer
+    }
+
     /// Takes the update receiver.
     pub fn take_receiver(&mut self) -> Option<mpsc::Receiver<AccountUpdate>> {
         self.update_rx.take()
     }
 
-    /// Subscribes to an account.
+    /// Subscribes to an account at the configured commitment level.
     pub async fn subscribe(&self, address: Pubkey, sub_type: SubscriptionType) {
+        self.subscribe_with_commitment(address, sub_type, self.config.commitment)
+            .await;
+    }
+
+    /// Subscribes to an account at `commitment`, e.g. `Processed` for pools
+    /// driving prices and `Finalized` for positions driving accounting.
+    ///
+    /// With a provider set, the account's current state is delivered right
+    /// away as the first update.
+    pub async fn subscribe_with_commitment(
+        &self,
+        address: Pubkey,
+        sub_type: SubscriptionType,
+        commitment: CommitmentLevel,
+    ) {
         let subscription = Subscription {
             address,
             sub_type,
+            commitment,
             ws_subscription_id: None,
             active: false,
         };
@@ -121,6 +209,7 @@ impl AccountListener {
         info!(
             address = %address,
             sub_type = ?sub_type,
+            commitment = commitment.as_str(),
             "Added subscription"
         );
 
@@ -128,6 +217,18 @@ impl AccountListener {
         if *self.connected.read().await {
             self.activate_subscription(&address).await;
         }
+        self.snapshot(&address, commitment).await;
+    }
+
+    /// Delivers the current state of an account as an update, if a
+    /// provider is set.
+    async fn snapshot(&self, address: &Pubkey, commitment: CommitmentLevel) {
+        let Some(provider) = &self.provider else {
+            return;
+        };
+        if let Some(update) = fetch_snapshot(provider, address, commitment).await {
+            self.dispatch(update).await;
+        }
     }
 
     /// Unsubscribes from an account.
@@ -136,19 +237,32 @@ impl AccountListener {
             if sub.active {
                 self.deactivate_subscription(&sub).await;
             }
+            self.slot_orderer.forget(address);
             info!(address = %address, "Removed subscription");
         }
     }
 
     /// Starts the listener.
     pub async fn start(&self) {
+        self.run_until(CancellationToken::new()).await;
+    }
+
+    /// Runs the listener until `shutdown` is cancelled, closing the
+    /// connection on the way out.
+    pub async fn run_until(&self, shutdown: CancellationToken) {
         info!(ws_url = %self.config.ws_url, "Starting account listener");
 
         loop {
             match self.connect().await {
                 Ok(()) => {
                     *self.reconnect_attempts.write().await = 0;
-                    self.run_event_loop().await;
+                    tokio::select! {
+                        () = shutdown.cancelled() => {
+                            self.disconnect().await;
+                            break;
+                        }
+                        () = self.run_event_loop() => {}
+                    }
                 }
                 Err(e) => {
                     error!(error = %e, "WebSocket connection failed");
@@ -173,54 +287,208 @@ impl AccountListener {
                 "Reconnecting..."
             );
 
-            tokio::time::sleep(std::time::Duration::from_secs(
-                self.config.reconnect_delay_secs,
-            ))
-            .await;
+            tokio::select! {
+                () = shutdown.cancelled() => break,
+                () = tokio::time::sleep(std::time::Duration::from_secs(
+                    self.config.reconnect_delay_secs,
+                )) => {}
+            }
+        }
+
+        info!("Account listener stopped");
+    }
+
+    /// Deactivates all subscriptions and closes the connection.
+    async fn disconnect(&self) {
+        let subscriptions: Vec<Subscription> = self
+            .subscriptions
+            .read()
+            .await
+            .values()
+            .filter(|sub| sub.active)
+            .cloned()
+            .collect();
+        for sub in &subscriptions {
+            self.deactivate_subscription(sub).await;
         }
+        if let Some(mut sink) = self.sink.lock().await.take()
+            && let Err(e) = sink.close().await
+        {
+            debug!(error = %e, "Failed to close WebSocket");
+        }
+        self.stream.lock().await.take();
+        self.mark_disconnected().await;
+    }
+
+    /// Marks the listener and all subscriptions disconnected.
+    async fn mark_disconnected(&self) {
+        for sub in self.subscriptions.write().await.values_mut() {
+            sub.active = false;
+            sub.ws_subscription_id = None;
+        }
+        self.pending.write().await.clear();
+        *self.connected.write().await = false;
     }
 
     /// Connects to the WebSocket.
     async fn connect(&self) -> anyhow::Result<()> {
-        // Note: In a real implementation, this would establish a WebSocket connection
-        // using tokio-tungstenite or similar
-        info!("Would connect to WebSocket: {}", self.config.ws_url);
-
+        let (socket, _) = tokio_tungstenite::connect_async(self.config.ws_url.as_str()).await?;
+        let (sink, stream) = socket.split();
+        *self.sink.lock().await = Some(sink);
+        *self.stream.lock().await = Some(stream);
         *self.connected.write().await = true;
+        info!(ws_url = %self.config.ws_url, "Connected to WebSocket");
 
-        // Activate all subscriptions
-        let addresses: Vec<Pubkey> = self.subscriptions.read().await.keys().copied().collect();
-        for address in addresses {
+        // Activate all subscriptions, catching up on changes missed while
+        // disconnected
+        let subscriptions: Vec<(Pubkey, CommitmentLevel)> = self
+            .subscriptions
+            .read()
+            .await
+            .values()
+            .map(|sub| (sub.address, sub.commitment))
+            .collect();
+        for (address, commitment) in subscriptions {
             self.activate_subscription(&address).await;
+            self.snapshot(&address, commitment).await;
         }
 
         Ok(())
     }
 
-    /// Runs the event loop.
+    /// Runs the event loop until the connection closes.
     async fn run_event_loop(&self) {
-        // Note: In a real implementation, this would process WebSocket messages
+        let Some(mut stream) = self.stream.lock().await.take() else {
+            return;
+        };
         debug!("Running event loop");
 
-        // Simulate running for a while
-        tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+        while let Some(message) = stream.next().await {
+            match message {
+                Ok(Message::Text(text)) => self.handle_message(text.as_str()).await,
+                Ok(Message::Close(_)) => break,
+                Ok(_) => {}
+                Err(e) => {
+                    warn!(error = %e, "WebSocket read failed");
+                    break;
+                }
+            }
+        }
 
-        *self.connected.write().await = false;
+        warn!("WebSocket connection closed");
+        self.sink.lock().await.take();
+        self.mark_disconnected().await;
     }
 
-    /// Activates a subscription.
+    /// Handles a JSON-RPC message: a reply to a subscription request or an
+    /// account notification.
+    async fn handle_message(&self, text: &str) {
+        let message: Value = match serde_json::from_str(text) {
+            Ok(message) => message,
+            Err(e) => {
+                warn!(error = %e, "Invalid WebSocket message");
+                return;
+            }
+        };
+
+        if message["method"] == "accountNotification" {
+            let params = &message["params"];
+            let Some(subscription_id) = params["subscription"].as_u64() else {
+                return;
+            };
+            let address = self
+                .subscriptions
+                .read()
+                .await
+                .values()
+                .find(|sub| sub.ws_subscription_id == Some(subscription_id))
+                .map(|sub| sub.address);
+            match address.and_then(|address| account_update(address, &params["result"])) {
+                Some(update) => self.dispatch(update).await,
+                None => debug!(subscription_id, "Ignoring account notification"),
+            }
+            return;
+        }
+
+        let Some(request_id) = message["id"].as_u64() else {
+            return;
+        };
+        let Some(address) = self.pending.write().await.remove(&request_id) else {
+            return;
+        };
+        let Some(subscription_id) = message["result"].as_u64() else {
+            warn!(address = %address, error = %message["error"], "Subscription rejected");
+            return;
+        };
+        let activated = match self.subscriptions.write().await.get_mut(&address) {
+            Some(sub) => {
+                sub.active = true;
+                sub.ws_subscription_id = Some(subscription_id);
+                debug!(
+                    address = %address,
+                    commitment = sub.commitment.as_str(),
+                    subscription_id,
+                    "Activated subscription"
+                );
+                true
+            }
+            None => false,
+        };
+        // Unsubscribed while the request was in flight
+        if !activated {
+            self.send("accountUnsubscribe", json!([subscription_id]))
+                .await;
+        }
+    }
+
+    /// Sends a JSON-RPC request, returning its id.
+    async fn send(&self, method: &str, params: Value) -> Option<u64> {
+        let id = self.next_request_id.fetch_add(1, Ordering::Relaxed);
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        });
+        let mut sink = self.sink.lock().await;
+        let sink = sink.as_mut()?;
+        match sink.send(Message::text(request.to_string())).await {
+            Ok(()) => Some(id),
+            Err(e) => {
+                warn!(method = method, error = %e, "Failed to send WebSocket request");
+                None
+            }
+        }
+    }
+
+    /// Sends a subscription request at the subscription's commitment.
+    ///
+    /// The subscription becomes active once the node replies.
     async fn activate_subscription(&self, address: &Pubkey) {
-        if let Some(sub) = self.subscriptions.write().await.get_mut(address) {
-            // Note: In a real implementation, this would send a subscription request
-            sub.active = true;
-            sub.ws_subscription_id = Some(1); // Placeholder
-            debug!(address = %address, "Activated subscription");
+        let Some(commitment) = self
+            .subscriptions
+            .read()
+            .await
+            .get(address)
+            .map(|sub| sub.commitment)
+        else {
+            return;
+        };
+        let params = json!([
+            address.to_string(),
+            { "encoding": "base64", "commitment": commitment.as_str() },
+        ]);
+        if let Some(id) = self.send("accountSubscribe", params).await {
+            self.pending.write().await.insert(id, *address);
         }
     }
 
     /// Deactivates a subscription.
     async fn deactivate_subscription(&self, sub: &Subscription) {
-        // Note: In a real implementation, this would send an unsubscribe request
+        if let Some(subscription_id) = sub.ws_subscription_id {
+            self.send("accountUnsubscribe", json!([subscription_id]))
+                .await;
+        }
         debug!(address = %sub.address, "Deactivated subscription");
     }
 
@@ -241,12 +509,69 @@ impl AccountListener {
 
     /// Simulates an account update (for testing).
     pub async fn simulate_update(&self, update: AccountUpdate) {
+        self.dispatch(update).await;
+    }
+
+    /// Delivers an update to the receiver unless it is stale or a repeat.
+    async fn dispatch(&self, update: AccountUpdate) {
+        if self.slot_orderer.check(&update) != UpdateOrder::Fresh {
+            return;
+        }
         if let Err(e) = self.update_tx.send(update).await {
-            error!(error = %e, "Failed to send simulated update");
+            error!(error = %e, "Failed to send account update");
         }
     }
 }
 
+#[async_trait]
+impl StateSource for AccountListener {
+    fn name(&self) -> &'static str {
+        "websocket"
+    }
+
+    fn take_receiver(&mut self) -> Option<mpsc::Receiver<AccountUpdate>> {
+        AccountListener::take_receiver(self)
+    }
+
+    async fn subscribe(&self, address: Pubkey, sub_type: SubscriptionType) {
+        AccountListener::subscribe(self, address, sub_type).await;
+    }
+
+    async fn subscribe_with_commitment(
+        &self,
+        address: Pubkey,
+        sub_type: SubscriptionType,
+        commitment: CommitmentLevel,
+    ) {
+        AccountListener::subscribe_with_commitment(self, address, sub_type, commitment).await;
+    }
+
+    async fn unsubscribe(&self, address: &Pubkey) {
+        AccountListener::unsubscribe(self, address).await;
+    }
+
+    async fn subscription_count(&self) -> usize {
+        AccountListener::subscription_count(self).await
+    }
+
+    async fn run_until(&self, shutdown: CancellationToken) {
+        AccountListener::run_until(self, shutdown).await;
+    }
+}
+
+/// Builds an update from the `result` of an account notification.
+fn account_update(address: Pubkey, result: &Value) -> Option<AccountUpdate> {
+    let value = &result["value"];
+    let data = BASE64.decode(value["data"][0].as_str()?).ok()?;
+    Some(AccountUpdate {
+        address,
+        slot: result["context"]["slot"].as_u64()?,
+        data,
+        lamports: value["lamports"].as_u64()?,
+        owner: Pubkey::from_str(value["owner"].as_str()?).ok()?,
+    })
+}
+
 impl Default for AccountListener {
     fn default() -> Self {
         Self::new(AccountListenerConfig::default())
@@ -267,6 +592,19 @@ mod tests {
         let subs = listener.get_subscriptions().await;
         assert_eq!(subs.len(), 1);
         assert_eq!(subs[0].address, address);
+        assert_eq!(subs[0].commitment, CommitmentLevel::Confirmed);
+
+        let position = Pubkey::new_unique();
+        listener
+            .subscribe_with_commitment(
+                position,
+                SubscriptionType::Position,
+                CommitmentLevel::Finalized,
+            )
+            .await;
+        let subs = listener.get_subscriptions().await;
+        let sub = subs.iter().find(|s| s.address == position).unwrap();
+        assert_eq!(sub.commitment, CommitmentLevel::Finalized);
     }
 
     #[tokio::test]
@@ -279,4 +617,115 @@ mod tests {
 
         assert_eq!(listener.subscription_count().await, 0);
     }
+
+    #[tokio::test]
+    async fn test_account_listener_delivers_updates_in_slot_order() {
+        let mut listener = AccountListener::default();
+        let mut updates = listener.take_receiver().unwrap();
+        let address = Pubkey::new_unique();
+        let update = |slot: u64| AccountUpdate {
+            address,
+            slot,
+            data: vec![slot as u8],
+            lamports: 0,
+            owner: Pubkey::default(),
+        };
+
+        // A replay after reconnect repeats slot 11 and sends slot 10 late
+        for slot in [10, 12, 11, 12, 13] {
+            listener.simulate_update(update(slot)).await;
+        }
+        drop(listener);
+
+        let mut delivered = Vec::new();
+        while let Some(update) = updates.recv().await {
+            delivered.push(update.slot);
+        }
+        assert_eq!(delivered, vec![10, 12, 13]);
+    }
+
+    #[tokio::test]
+    async fn test_subscribes_at_commitment_and_delivers_notifications() {
+        let server = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let ws_url = format!("ws://{}", server.local_addr().unwrap());
+        let owner = Pubkey::new_unique();
+        let node = tokio::spawn(async move {
+            let (tcp, _) = server.accept().await.unwrap();
+            let mut socket = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            let Some(Ok(Message::Text(text))) = socket.next().await else {
+                panic!("expected a subscription request");
+            };
+            let request: Value = serde_json::from_str(text.as_str()).unwrap();
+            let reply = json!({ "jsonrpc": "2.0", "id": request["id"], "result": 42 });
+            let notification = json!({
+                "jsonrpc": "2.0",
+                "method": "accountNotification",
+                "params": {
+                    "subscription": 42,
+                    "result": {
+                        "context": { "slot": 7 },
+                        "value": {
+                            "lamports": 100,
+                            "owner": owner.to_string(),
+                            "data": ["AQID", "base64"],
+                            "executable": false,
+                            "rentEpoch": 0,
+                        },
+                    },
+                },
+            });
+            for message in [reply, notification] {
+                socket
+                    .send(Message::text(message.to_string()))
+                    .await
+                    .unwrap();
+            }
+            // Keep the connection open until the listener closes it
+            while socket.next().await.is_some() {}
+            request
+        });
+
+        let mut listener = AccountListener::new(AccountListenerConfig {
+            ws_url,
+            ..Default::default()
+        });
+        let mut updates = listener.take_receiver().unwrap();
+        let address = Pubkey::new_unique();
+        listener
+            .subscribe_with_commitment(
+                address,
+                SubscriptionType::Position,
+                CommitmentLevel::Finalized,
+            )
+            .await;
+
+        let listener = Arc::new(listener);
+        let shutdown = CancellationToken::new();
+        let running = tokio::spawn({
+            let listener = listener.clone();
+            let shutdown = shutdown.clone();
+            async move { listener.run_until(shutdown).await }
+        });
+
+        let update = tokio::time::timeout(std::time::Duration::from_secs(5), updates.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(update.address, address);
+        assert_eq!(update.slot, 7);
+        assert_eq!(update.data, vec![1, 2, 3]);
+        assert_eq!(update.lamports, 100);
+        assert_eq!(update.owner, owner);
+        let subs = listener.get_subscriptions().await;
+        assert!(subs[0].active);
+        assert_eq!(subs[0].ws_subscription_id, Some(42));
+
+        shutdown.cancel();
+        running.await.unwrap();
+        let request = node.await.unwrap();
+        assert_eq!(request["method"], "accountSubscribe");
+        assert_eq!(request["params"][0], address.to_string());
+        assert_eq!(request["params"][1]["commitment"], "finalized");
+        assert_eq!(request["params"][1]["encoding"], "base64");
+    }
 }
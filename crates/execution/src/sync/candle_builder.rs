@@ -0,0 +1,302 @@
+//! Live candles built from real-time price updates.
+//!
+//! Aggregates pool prices, from account updates or any other price feed,
+//! into rolling in-memory candles per interval so strategies can read
+//! recent volatility and regime without waiting for historical data.
+
+use super::AccountUpdate;
+use crate::strategy::RegimeSignal;
+use clmm_lp_protocols::prelude::WhirlpoolState;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, VecDeque};
+use tokio::sync::RwLock;
+use tracing::debug;
+
+/// Seconds in a day, the horizon volatility estimates are scaled to.
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// An OHLC candle of pool prices.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiveCandle {
+    /// Start of the candle in Unix seconds, aligned to the interval.
+    pub start: u64,
+    /// Candle length in seconds.
+    pub interval_secs: u64,
+    /// First price.
+    pub open: Decimal,
+    /// Highest price.
+    pub high: Decimal,
+    /// Lowest price.
+    pub low: Decimal,
+    /// Last price.
+    pub close: Decimal,
+    /// Number of price updates aggregated.
+    pub updates: u32,
+}
+
+impl LiveCandle {
+    /// Opens a candle at the first price of its interval.
+    fn open_at(start: u64, interval_secs: u64, price: Decimal) -> Self {
+        Self {
+            start,
+            interval_secs,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            updates: 1,
+        }
+    }
+
+    /// Adds a price to the candle.
+    fn update(&mut self, price: Decimal) {
+        self.high = self.high.max(price);
+        self.low = self.low.min(price);
+        self.close = price;
+        self.updates += 1;
+    }
+}
+
+/// Configuration of the candle builder.
+#[derive(Debug, Clone)]
+pub struct CandleBuilderConfig {
+    /// Candle intervals in seconds.
+    pub intervals: Vec<u64>,
+    /// Closed candles kept per pool and interval.
+    pub max_candles: usize,
+}
+
+impl Default for CandleBuilderConfig {
+    fn default() -> Self {
+        Self {
+            intervals: vec![60, 300],
+            max_candles: 500,
+        }
+    }
+}
+
+/// Closed candles and the candle in progress of one pool and interval.
+#[derive(Debug, Default)]
+struct CandleSeries {
+    closed: VecDeque<LiveCandle>,
+    current: Option<LiveCandle>,
+}
+
+/// Aggregates price updates into rolling candles per pool.
+#[derive(Debug, Default)]
+pub struct LiveCandleBuilder {
+    /// Configuration.
+    config: CandleBuilderConfig,
+    /// Candles per pool and interval.
+    series: RwLock<HashMap<(Pubkey, u64), CandleSeries>>,
+}
+
+impl LiveCandleBuilder {
+    /// Creates a builder with the given configuration.
+    #[must_use]
+    pub fn new(config: CandleBuilderConfig) -> Self {
+        Self {
+            config,
+            series: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the candle intervals in seconds.
+    #[must_use]
+    pub fn intervals(&self) -> &[u64] {
+        &self.config.intervals
+    }
+
+    /// Records a pool price observed at `timestamp` (Unix seconds).
+    ///
+    /// Returns the candles this update closed. Updates older than the
+    /// candle in progress are ignored.
+    pub async fn record(&self, pool: Pubkey, price: Decimal, timestamp: u64) -> Vec<LiveCandle> {
+        let mut series = self.series.write().await;
+        let mut closed = Vec::new();
+
+        for &interval in &self.config.intervals {
+            if interval == 0 {
+                continue;
+            }
+            let start = timestamp - timestamp % interval;
+            let entry = series.entry((pool, interval)).or_default();
+
+            match entry.current.as_mut() {
+                Some(current) if current.start == start => current.update(price),
+                Some(current) if current.start > start => {}
+                _ => {
+                    let previous = entry
+                        .current
+                        .replace(LiveCandle::open_at(start, interval, price));
+                    if let Some(previous) = previous {
+                        entry.closed.push_back(previous.clone());
+                        while entry.closed.len() > self.config.max_candles {
+                            entry.closed.pop_front();
+                        }
+                        closed.push(previous);
+                    }
+                }
+            }
+        }
+        closed
+    }
+
+    /// Records the price of a Whirlpool account update.
+    ///
+    /// Updates that are not Whirlpool accounts are ignored.
+    pub async fn process_update(&self, update: &AccountUpdate, timestamp: u64) -> Vec<LiveCandle> {
+        let address = update.address.to_string();
+        match WhirlpoolState::from_account_data(&update.data, &address) {
+            Ok(pool) => self.record(update.address, pool.price, timestamp).await,
+            Err(e) => {
+                debug!(address = %address, error = %e, "Not a Whirlpool update");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Returns the closed candles of a pool followed by the one in progress,
+    /// oldest first.
+    pub async fn candles(&self, pool: &Pubkey, interval_secs: u64) -> Vec<LiveCandle> {
+        self.series
+            .read()
+            .await
+            .get(&(*pool, interval_secs))
+            .map(|series| {
+                series
+                    .closed
+                    .iter()
+                    .chain(series.current.iter())
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns up to `count` of the latest close prices, oldest first,
+    /// including the candle in progress.
+    pub async fn close_prices(
+        &self,
+        pool: &Pubkey,
+        interval_secs: u64,
+        count: usize,
+    ) -> Vec<Decimal> {
+        let candles = self.candles(pool, interval_secs).await;
+        let skip = candles.len().saturating_sub(count);
+        candles[skip..].iter().map(|c| c.close).collect()
+    }
+
+    /// Computes the regime signal of the latest `lookback` candles.
+    pub async fn regime(
+        &self,
+        pool: &Pubkey,
+        interval_secs: u64,
+        lookback: usize,
+        threshold: Decimal,
+    ) -> Option<RegimeSignal> {
+        let closes = self.close_prices(pool, interval_secs, lookback).await;
+        RegimeSignal::from_prices(&closes, threshold)
+    }
+
+    /// Estimates daily volatility from the latest `lookback` candles.
+    ///
+    /// Scales the per-candle return volatility by the square root of the
+    /// candles in a day. Returns `None` with fewer than three candles.
+    pub async fn daily_volatility(
+        &self,
+        pool: &Pubkey,
+        interval_secs: u64,
+        lookback: usize,
+    ) -> Option<Decimal> {
+        let signal = self
+            .regime(pool, interval_secs, lookback, Decimal::ONE)
+            .await?;
+        scale_to_daily(signal.volatility, interval_secs)
+    }
+}
+
+/// Scales a per-interval volatility to a daily one.
+fn scale_to_daily(volatility: Decimal, interval_secs: u64) -> Option<Decimal> {
+    if interval_secs == 0 {
+        return None;
+    }
+    let periods = (SECONDS_PER_DAY as f64 / interval_secs as f64).sqrt();
+    Decimal::from_f64(volatility.to_f64()? * periods)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[tokio::test]
+    async fn test_aggregates_updates_into_candles() {
+        let builder = LiveCandleBuilder::new(CandleBuilderConfig {
+            intervals: vec![60, 300],
+            max_candles: 2,
+        });
+        let pool = Pubkey::new_unique();
+
+        assert!(builder.record(pool, dec!(100), 1_000_020).await.is_empty());
+        builder.record(pool, dec!(103), 1_000_030).await;
+        builder.record(pool, dec!(99), 1_000_050).await;
+        let closed = builder.record(pool, dec!(101), 1_000_085).await;
+
+        // The first minute closed; the 5-minute candle is still open
+        assert_eq!(closed.len(), 1);
+        assert_eq!(
+            closed[0],
+            LiveCandle {
+                start: 1_000_020,
+                interval_secs: 60,
+                open: dec!(100),
+                high: dec!(103),
+                low: dec!(99),
+                close: dec!(99),
+                updates: 3,
+            }
+        );
+        let five_minute = builder.candles(&pool, 300).await;
+        assert_eq!(five_minute.len(), 1);
+        assert_eq!(five_minute[0].updates, 4);
+
+        // Late updates are dropped and only `max_candles` closed candles kept
+        builder.record(pool, dec!(50), 1_000_000).await;
+        builder.record(pool, dec!(102), 1_000_140).await;
+        builder.record(pool, dec!(104), 1_000_200).await;
+        let minutes = builder.candles(&pool, 60).await;
+        assert_eq!(minutes.len(), 3);
+        assert_eq!(minutes[0].close, dec!(101));
+        assert_eq!(
+            builder.close_prices(&pool, 60, 2).await,
+            vec![dec!(102), dec!(104)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_regime_and_volatility_from_candles() {
+        let builder = LiveCandleBuilder::new(CandleBuilderConfig {
+            intervals: vec![60],
+            max_candles: 100,
+        });
+        let pool = Pubkey::new_unique();
+        assert!(builder.daily_volatility(&pool, 60, 10).await.is_none());
+
+        for (i, price) in [100, 101, 102, 103, 104, 105].iter().enumerate() {
+            builder
+                .record(pool, Decimal::from(*price), i as u64 * 60)
+                .await;
+        }
+
+        let signal = builder.regime(&pool, 60, 10, dec!(0.6)).await.unwrap();
+        assert!(signal.regime.is_trending());
+        assert_eq!(signal.samples, 6);
+
+        let daily = builder.daily_volatility(&pool, 60, 10).await.unwrap();
+        let per_candle = signal.volatility.to_f64().unwrap();
+        assert!((daily.to_f64().unwrap() - per_candle * 1440f64.sqrt()).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,522 @@
+//! Yellowstone Geyser gRPC stream as a source of account updates.
+//!
+//! Geyser plugins push account writes straight from a validator, so they
+//! keep up under load where WebSocket subscriptions drop notifications.
+//! One stream is opened per commitment level; each carries a filter on the
+//! accounts subscribed at its level, replaced in place as subscriptions
+//! change.
+
+use super::{
+    AccountUpdate, SlotOrderer, StateSource, Subscription, SubscriptionType, UpdateOrder,
+    fetch_snapshot,
+};
+use crate::shutdown::CancellationToken;
+use async_trait::async_trait;
+use clmm_lp_protocols::prelude::{CommitmentLevel, RpcProvider};
+use futures::StreamExt;
+use futures::stream::BoxStream;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{RwLock, mpsc};
+use tonic::metadata::AsciiMetadataValue;
+use tonic::transport::{ClientTlsConfig, Endpoint};
+use tonic::{Request, Status};
+use tracing::{debug, error, info, warn};
+
+/// Generated protobuf messages and client.
+#[allow(missing_docs, clippy::all)]
+pub mod proto {
+    tonic::include_proto!("geyser");
+}
+
+use proto::geyser_client::GeyserClient;
+use proto::subscribe_update::UpdateOneof;
+
+/// Metadata key carrying the Geyser access token.
+pub const X_TOKEN_METADATA: &str = "x-token";
+
+/// Name of the accounts filter sent on each stream.
+const ACCOUNTS_FILTER: &str = "accounts";
+
+/// Commitment levels a stream is opened for.
+const COMMITMENT_LEVELS: [CommitmentLevel; 3] = [
+    CommitmentLevel::Processed,
+    CommitmentLevel::Confirmed,
+    CommitmentLevel::Finalized,
+];
+
+/// Configuration for the Geyser source.
+#[derive(Debug, Clone)]
+pub struct GeyserConfig {
+    /// gRPC endpoint, e.g. `https://example.rpcpool.com:443`.
+    pub endpoint: String,
+    /// Access token sent in the `x-token` header, if the endpoint needs one.
+    pub x_token: Option<String>,
+    /// Default commitment level for subscriptions.
+    pub commitment: CommitmentLevel,
+    /// Connection timeout in seconds.
+    pub connect_timeout_secs: u64,
+    /// Reconnect delay in seconds.
+    pub reconnect_delay_secs: u64,
+    /// Maximum reconnect attempts.
+    pub max_reconnect_attempts: u32,
+}
+
+impl GeyserConfig {
+    /// Creates a configuration for `endpoint` with default settings.
+    #[must_use]
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            x_token: None,
+            commitment: CommitmentLevel::Confirmed,
+            connect_timeout_secs: 10,
+            reconnect_delay_secs: 5,
+            max_reconnect_attempts: 10,
+        }
+    }
+
+    /// Sets the access token.
+    #[must_use]
+    pub fn with_x_token(mut self, token: impl Into<String>) -> Self {
+        self.x_token = Some(token.into());
+        self
+    }
+}
+
+/// Source of account updates streamed from a Yellowstone Geyser endpoint.
+pub struct GeyserSource {
+    /// Configuration.
+    config: GeyserConfig,
+    /// Active subscriptions.
+    subscriptions: Arc<RwLock<HashMap<Pubkey, Subscription>>>,
+    /// Request senders of the open streams, by commitment level.
+    streams: Arc<RwLock<BTreeMap<CommitmentLevel, mpsc::Sender<proto::SubscribeRequest>>>>,
+    /// Update sender.
+    update_tx: mpsc::Sender<AccountUpdate>,
+    /// Update receiver.
+    update_rx: Option<mpsc::Receiver<AccountUpdate>>,
+    /// Drops updates older than, or repeating, what was already delivered.
+    slot_orderer: Arc<SlotOrderer>,
+    /// Provider initial snapshots of subscribed accounts are fetched with.
+    provider: Option<Arc<RpcProvider>>,
+}
+
+impl GeyserSource {
+    /// Creates a new Geyser source.
+    pub fn new(config: GeyserConfig) -> Self {
+        let (tx, rx) = mpsc::channel(1000);
+        Self {
+            config,
+            subscriptions: Arc::new(RwLock::new(HashMap::new())),
+            streams: Arc::new(RwLock::new(BTreeMap::new())),
+            update_tx: tx,
+            update_rx: Some(rx),
+            slot_orderer: Arc::new(SlotOrderer::new()),
+            provider: None,
+        }
+    }
+
+    /// Fetches a snapshot of each account when it is subscribed and after
+    /// every reconnect, so its state is known before the first change.
+    #[must_use]
+    pub fn with_provider(mut self, provider: Arc<RpcProvider>) -> Self {
+        self.provider = Some(provider);
+        self
+    }
+
+    /// Shares a slot orderer, so ordering survives the source being
+    /// recreated and drop counts can be reported elsewhere.
+    #[must_use]
+    pub fn with_slot_orderer(mut self, orderer: Arc<SlotOrderer>) -> Self {
+        self.slot_orderer = orderer;
+        self
+    }
+
+    /// Gets the slot orderer updates pass through.
+    pub fn slot_orderer(&self) -> &Arc<SlotOrderer> {
+        &self.slot_orderer
+    }
+
+    /// Checks if the streams are open.
+    pub async fn is_connected(&self) -> bool {
+        !self.streams.read().await.is_empty()
+    }
+
+    /// Gets all subscriptions.
+    pub async fn get_subscriptions(&self) -> Vec<Subscription> {
+        self.subscriptions.read().await.values().cloned().collect()
+    }
+
+    /// Builds the request filtering a stream to the accounts subscribed at
+    /// `commitment`.
+    async fn request_for(&self, commitment: CommitmentLevel) -> proto::SubscribeRequest {
+        let accounts: Vec<String> = self
+            .subscriptions
+            .read()
+            .await
+            .values()
+            .filter(|sub| sub.commitment == commitment)
+            .map(|sub| sub.address.to_string())
+            .collect();
+
+        // A filter without accounts or owners matches every account, so
+        // streams without subscriptions send no filter at all
+        let mut filters = HashMap::new();
+        if !accounts.is_empty() {
+            filters.insert(
+                ACCOUNTS_FILTER.to_string(),
+                proto::SubscribeRequestFilterAccounts {
+                    account: accounts,
+                    owner: Vec::new(),
+                },
+            );
+        }
+
+        proto::SubscribeRequest {
+            accounts: filters,
+            commitment: Some(proto_commitment(commitment) as i32),
+            ping: None,
+        }
+    }
+
+    /// Replaces the filter of the stream at `commitment`, if open.
+    async fn refresh_filter(&self, commitment: CommitmentLevel) {
+        let Some(stream) = self.streams.read().await.get(&commitment).cloned() else {
+            return;
+        };
+        let request = self.request_for(commitment).await;
+        if stream.send(request).await.is_err() {
+            warn!(
+                commitment = commitment.as_str(),
+                "Geyser stream closed before its filter was updated"
+            );
+        }
+    }
+
+    /// Delivers the current state of an account as an update, if a
+    /// provider is set.
+    async fn snapshot(&self, address: &Pubkey, commitment: CommitmentLevel) {
+        let Some(provider) = &self.provider else {
+            return;
+        };
+        if let Some(update) = fetch_snapshot(provider, address, commitment).await {
+            self.dispatch(update).await;
+        }
+    }
+
+    /// Connects, opens a stream per commitment level and delivers updates
+    /// until a stream fails.
+    async fn run_session(&self) -> anyhow::Result<()> {
+        let mut endpoint = Endpoint::from_shared(self.config.endpoint.clone())?
+            .connect_timeout(Duration::from_secs(self.config.connect_timeout_secs))
+            .tcp_keepalive(Some(Duration::from_secs(30)));
+        if self.config.endpoint.starts_with("https") {
+            endpoint = endpoint.tls_config(ClientTlsConfig::new().with_enabled_roots())?;
+        }
+        let channel = endpoint.connect().await?;
+
+        let token: Option<AsciiMetadataValue> =
+            self.config.x_token.as_deref().map(str::parse).transpose()?;
+        let mut client = GeyserClient::with_interceptor(channel, move |mut request: Request<()>| {
+            if let Some(token) = &token {
+                request
+                    .metadata_mut()
+                    .insert(X_TOKEN_METADATA, token.clone());
+            }
+            Ok::<_, Status>(request)
+        });
+
+        let mut updates: Vec<BoxStream<'static, (CommitmentLevel, _)>> = Vec::new();
+        for commitment in COMMITMENT_LEVELS {
+            let (tx, rx) = mpsc::channel(16);
+            tx.send(self.request_for(commitment).await).await?;
+            let requests = futures::stream::unfold(rx, |mut rx| async move {
+                rx.recv().await.map(|request| (request, rx))
+            });
+            let responses = client.subscribe(requests).await?.into_inner();
+            updates.push(responses.map(move |update| (commitment, update)).boxed());
+            self.streams.write().await.insert(commitment, tx);
+        }
+        info!(endpoint = %self.config.endpoint, "Opened Geyser streams");
+
+        // Catch up on changes missed while disconnected
+        let subscriptions: Vec<(Pubkey, CommitmentLevel)> = self
+            .subscriptions
+            .read()
+            .await
+            .values()
+            .map(|sub| (sub.address, sub.commitment))
+            .collect();
+        for (address, commitment) in subscriptions {
+            self.snapshot(&address, commitment).await;
+        }
+
+        let mut updates = futures::stream::select_all(updates);
+        while let Some((commitment, update)) = updates.next().await {
+            self.handle(commitment, update?).await;
+        }
+        anyhow::bail!("Geyser stream closed")
+    }
+
+    /// Handles a message from the stream at `commitment`.
+    async fn handle(&self, commitment: CommitmentLevel, update: proto::SubscribeUpdate) {
+        match update.update_oneof {
+            Some(UpdateOneof::Account(account)) => {
+                let Some(update) = account_update(account) else {
+                    warn!("Ignored malformed Geyser account update");
+                    return;
+                };
+                // Filters are replaced asynchronously, so updates can
+                // still arrive for an account just unsubscribed
+                if !self
+                    .subscriptions
+                    .read()
+                    .await
+                    .contains_key(&update.address)
+                {
+                    return;
+                }
+                self.dispatch(update).await;
+            }
+            Some(UpdateOneof::Ping(_)) => {
+                // Answer server pings so load balancers keep the stream open
+                let stream = self.streams.read().await.get(&commitment).cloned();
+                if let Some(stream) = stream {
+                    let ping = proto::SubscribeRequest {
+                        ping: Some(proto::SubscribeRequestPing { id: 1 }),
+                        ..Default::default()
+                    };
+                    let _ = stream.send(ping).await;
+                }
+            }
+            Some(UpdateOneof::Pong(_)) | None => {}
+        }
+    }
+
+    /// Delivers an update to the receiver unless it is stale or a repeat.
+    async fn dispatch(&self, update: AccountUpdate) {
+        if self.slot_orderer.check(&update) != UpdateOrder::Fresh {
+            return;
+        }
+        if let Err(e) = self.update_tx.send(update).await {
+            error!(error = %e, "Failed to send account update");
+        }
+    }
+}
+
+#[async_trait]
+impl StateSource for GeyserSource {
+    fn name(&self) -> &'static str {
+        "geyser"
+    }
+
+    fn take_receiver(&mut self) -> Option<mpsc::Receiver<AccountUpdate>> {
+        self.update_rx.take()
+    }
+
+    async fn subscribe(&self, address: Pubkey, sub_type: SubscriptionType) {
+        self.subscribe_with_commitment(address, sub_type, self.config.commitment)
+            .await;
+    }
+
+    async fn subscribe_with_commitment(
+        &self,
+        address: Pubkey,
+        sub_type: SubscriptionType,
+        commitment: CommitmentLevel,
+    ) {
+        let previous = self.subscriptions.write().await.insert(
+            address,
+            Subscription {
+                address,
+                sub_type,
+                commitment,
+                ws_subscription_id: None,
+                active: true,
+            },
+        );
+
+        info!(
+            address = %address,
+            sub_type = ?sub_type,
+            commitment = commitment.as_str(),
+            "Added Geyser subscription"
+        );
+
+        // Moving an account to another level updates both streams
+        if let Some(previous) = previous.filter(|p| p.commitment != commitment) {
+            self.refresh_filter(previous.commitment).await;
+        }
+        self.refresh_filter(commitment).await;
+        self.snapshot(&address, commitment).await;
+    }
+
+    async fn unsubscribe(&self, address: &Pubkey) {
+        if let Some(sub) = self.subscriptions.write().await.remove(address) {
+            self.refresh_filter(sub.commitment).await;
+            self.slot_orderer.forget(address);
+            info!(address = %address, "Removed Geyser subscription");
+        }
+    }
+
+    async fn subscription_count(&self) -> usize {
+        self.subscriptions.read().await.len()
+    }
+
+    async fn run_until(&self, shutdown: CancellationToken) {
+        info!(endpoint = %self.config.endpoint, "Starting Geyser source");
+
+        let mut attempts = 0;
+        loop {
+            let result = tokio::select! {
+                () = shutdown.cancelled() => break,
+                result = self.run_session() => result,
+            };
+            // Streams that opened and later failed start a fresh count
+            if self.is_connected().await {
+                attempts = 0;
+            }
+            self.streams.write().await.clear();
+            if let Err(e) = result {
+                error!(error = %e, "Geyser stream failed");
+            }
+
+            attempts += 1;
+            if attempts >= self.config.max_reconnect_attempts {
+                error!("Max reconnect attempts reached, stopping Geyser source");
+                break;
+            }
+
+            warn!(
+                attempts = attempts,
+                delay_secs = self.config.reconnect_delay_secs,
+                "Reconnecting to Geyser..."
+            );
+
+            tokio::select! {
+                () = shutdown.cancelled() => break,
+                () = tokio::time::sleep(Duration::from_secs(
+                    self.config.reconnect_delay_secs,
+                )) => {}
+            }
+        }
+
+        self.streams.write().await.clear();
+        info!("Geyser source stopped");
+    }
+}
+
+/// Maps a commitment level to its Geyser counterpart.
+fn proto_commitment(commitment: CommitmentLevel) -> proto::CommitmentLevel {
+    match commitment {
+        CommitmentLevel::Processed => proto::CommitmentLevel::Processed,
+        CommitmentLevel::Confirmed => proto::CommitmentLevel::Confirmed,
+        CommitmentLevel::Finalized => proto::CommitmentLevel::Finalized,
+    }
+}
+
+/// Converts a streamed account write, if its keys are well formed.
+fn account_update(account: proto::SubscribeUpdateAccount) -> Option<AccountUpdate> {
+    let info = account.account?;
+    let update = AccountUpdate {
+        address: Pubkey::try_from(info.pubkey.as_slice()).ok()?,
+        slot: account.slot,
+        data: info.data,
+        lamports: info.lamports,
+        owner: Pubkey::try_from(info.owner.as_slice()).ok()?,
+    };
+    debug!(address = %update.address, slot = update.slot, "Received Geyser account update");
+    Some(update)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(address: Pubkey, slot: u64) -> proto::SubscribeUpdate {
+        proto::SubscribeUpdate {
+            filters: vec![ACCOUNTS_FILTER.to_string()],
+            update_oneof: Some(UpdateOneof::Account(proto::SubscribeUpdateAccount {
+                account: Some(proto::SubscribeUpdateAccountInfo {
+                    pubkey: address.to_bytes().to_vec(),
+                    lamports: 42,
+                    owner: Pubkey::default().to_bytes().to_vec(),
+                    data: vec![slot as u8],
+                    ..Default::default()
+                }),
+                slot,
+                is_startup: false,
+            })),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_filters_follow_subscriptions_per_commitment() {
+        let source = GeyserSource::new(GeyserConfig::new("http://localhost:10000"));
+        let pool = Pubkey::new_unique();
+        let position = Pubkey::new_unique();
+
+        source.subscribe(pool, SubscriptionType::Pool).await;
+        source
+            .subscribe_with_commitment(
+                position,
+                SubscriptionType::Position,
+                CommitmentLevel::Finalized,
+            )
+            .await;
+
+        let confirmed = source.request_for(CommitmentLevel::Confirmed).await;
+        assert_eq!(
+            confirmed.accounts[ACCOUNTS_FILTER].account,
+            vec![pool.to_string()]
+        );
+        assert_eq!(
+            confirmed.commitment,
+            Some(proto::CommitmentLevel::Confirmed as i32)
+        );
+        let finalized = source.request_for(CommitmentLevel::Finalized).await;
+        assert_eq!(
+            finalized.accounts[ACCOUNTS_FILTER].account,
+            vec![position.to_string()]
+        );
+
+        // An empty filter would match every account
+        source.unsubscribe(&pool).await;
+        let confirmed = source.request_for(CommitmentLevel::Confirmed).await;
+        assert!(confirmed.accounts.is_empty());
+        assert_eq!(source.subscription_count().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_delivers_subscribed_account_updates_in_slot_order() {
+        let mut source = GeyserSource::new(GeyserConfig::new("http://localhost:10000"));
+        let mut updates = StateSource::take_receiver(&mut source).unwrap();
+        let address = Pubkey::new_unique();
+        source.subscribe(address, SubscriptionType::Pool).await;
+
+        for slot in [10, 12, 11, 12] {
+            source
+                .handle(CommitmentLevel::Confirmed, account(address, slot))
+                .await;
+        }
+        // Updates for accounts no longer subscribed are dropped
+        source
+            .handle(
+                CommitmentLevel::Confirmed,
+                account(Pubkey::new_unique(), 13),
+            )
+            .await;
+        drop(source);
+
+        let mut delivered = Vec::new();
+        while let Some(update) = updates.recv().await {
+            assert_eq!(update.address, address);
+            assert_eq!(update.lamports, 42);
+            delivered.push(update.slot);
+        }
+        assert_eq!(delivered, vec![10, 12]);
+    }
+}
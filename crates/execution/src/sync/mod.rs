@@ -1,12 +1,27 @@
 //! State synchronization with on-chain data.
 //!
 //! Provides real-time synchronization via:
-//! - WebSocket account subscriptions
-//! - Slot tracking
+//! - WebSocket account subscriptions, or a Yellowstone Geyser gRPC stream
+//!   with the `geyser` feature, behind the [`StateSource`] trait
+//! - Slot tracking, dropping stale and duplicate updates
 //! - State reconciliation
+//! - Pool state caching with invalidation on account updates
+//! - Live candles aggregated from price updates
 
 mod account_listener;
+mod candle_builder;
+#[cfg(feature = "geyser")]
+mod geyser_source;
+mod pool_cache;
 mod reconciler;
+mod slot_order;
+mod state_source;
 
 pub use account_listener::*;
+pub use candle_builder::*;
+#[cfg(feature = "geyser")]
+pub use geyser_source::*;
+pub use pool_cache::*;
 pub use reconciler::*;
+pub use slot_order::*;
+pub use state_source::*;
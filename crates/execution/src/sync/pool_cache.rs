@@ -0,0 +1,155 @@
+//! Short-lived cache of Whirlpool states shared by positions in one pool.
+
+use super::AccountUpdate;
+use crate::transaction::{ConfirmationHook, TransactionResult};
+use async_trait::async_trait;
+use clmm_lp_protocols::prelude::{RpcProvider, WhirlpoolReader, WhirlpoolState};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tracing::debug;
+
+/// A cached pool state.
+#[derive(Debug, Clone)]
+struct CachedPool {
+    /// Pool state.
+    state: WhirlpoolState,
+    /// When the state was fetched.
+    fetched_at: Instant,
+}
+
+/// Pool state cache keyed by pool address.
+///
+/// Entries expire after a TTL and are dropped early when the pool account
+/// changes, either from a WebSocket update or a confirmed transaction.
+pub struct PoolStateCache {
+    /// Reader used on cache misses.
+    reader: WhirlpoolReader,
+    /// Time an entry stays valid.
+    ttl: Duration,
+    /// Cached states.
+    entries: RwLock<HashMap<Pubkey, CachedPool>>,
+}
+
+impl PoolStateCache {
+    /// Creates an empty cache whose entries live for `ttl`.
+    pub fn new(provider: Arc<RpcProvider>, ttl: Duration) -> Self {
+        Self {
+            reader: WhirlpoolReader::new(provider),
+            ttl,
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Gets a pool state, fetching it if absent or expired.
+    pub async fn get(&self, pool: &Pubkey) -> anyhow::Result<WhirlpoolState> {
+        if let Some(state) = self.cached(pool, Instant::now()).await {
+            return Ok(state);
+        }
+
+        let state = self.reader.get_pool_state(&pool.to_string()).await?;
+        self.insert(*pool, state.clone(), Instant::now()).await;
+        Ok(state)
+    }
+
+    /// Gets a cached pool state if it has not expired at `now`.
+    async fn cached(&self, pool: &Pubkey, now: Instant) -> Option<WhirlpoolState> {
+        let entries = self.entries.read().await;
+        entries
+            .get(pool)
+            .filter(|entry| now.duration_since(entry.fetched_at) < self.ttl)
+            .map(|entry| entry.state.clone())
+    }
+
+    /// Stores a pool state fetched at `fetched_at`.
+    async fn insert(&self, pool: Pubkey, state: WhirlpoolState, fetched_at: Instant) {
+        self.entries
+            .write()
+            .await
+            .insert(pool, CachedPool { state, fetched_at });
+    }
+
+    /// Drops the cached state of a pool; returns true if one was cached.
+    pub async fn invalidate(&self, pool: &Pubkey) -> bool {
+        let removed = self.entries.write().await.remove(pool).is_some();
+        if removed {
+            debug!(pool = %pool, "Invalidated cached pool state");
+        }
+        removed
+    }
+
+    /// Drops the cached state of a pool whose account changed.
+    pub async fn process_update(&self, update: &AccountUpdate) {
+        self.invalidate(&update.address).await;
+    }
+
+    /// Drops every cached state.
+    pub async fn clear(&self) {
+        self.entries.write().await.clear();
+    }
+}
+
+#[async_trait]
+impl ConfirmationHook for PoolStateCache {
+    async fn on_confirmed(&self, _result: &TransactionResult, accounts: &[Pubkey]) {
+        for account in accounts {
+            self.invalidate(account).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clmm_lp_protocols::prelude::RpcConfig;
+    use rust_decimal::Decimal;
+
+    fn pool_state(tick_current: i32) -> WhirlpoolState {
+        WhirlpoolState {
+            address: String::new(),
+            token_mint_a: Pubkey::default(),
+            token_mint_b: Pubkey::default(),
+            tick_current,
+            tick_spacing: 64,
+            sqrt_price: 0,
+            price: Decimal::ONE,
+            liquidity: 0,
+            fee_rate: 30,
+            protocol_fee_rate_bps: 0,
+            fee_growth_global_a: 0,
+            fee_growth_global_b: 0,
+            rewards: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_expiry_and_invalidation() {
+        let provider = Arc::new(RpcProvider::new(RpcConfig::default()));
+        let cache = PoolStateCache::new(provider, Duration::from_secs(10));
+        let pool = Pubkey::new_unique();
+        let now = Instant::now();
+
+        cache.insert(pool, pool_state(42), now).await;
+        assert_eq!(cache.cached(&pool, now).await.unwrap().tick_current, 42);
+        assert!(
+            cache
+                .cached(&pool, now + Duration::from_secs(10))
+                .await
+                .is_none()
+        );
+
+        cache
+            .process_update(&AccountUpdate {
+                address: pool,
+                slot: 1,
+                data: Vec::new(),
+                lamports: 0,
+                owner: Pubkey::default(),
+            })
+            .await;
+        assert!(cache.cached(&pool, now).await.is_none());
+        assert!(!cache.invalidate(&pool).await);
+    }
+}
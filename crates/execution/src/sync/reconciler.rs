@@ -1,6 +1,11 @@
 //! State reconciler for ensuring consistency.
 
-use super::AccountUpdate;
+use super::{AccountUpdate, StateSource, SubscriptionType};
+use crate::monitor::PositionMonitor;
+use crate::shutdown::CancellationToken;
+use crate::supervisor::Heartbeat;
+use crate::transaction::{ConfirmationHook, TransactionResult};
+use async_trait::async_trait;
 use clmm_lp_protocols::prelude::RpcProvider;
 use solana_sdk::pubkey::Pubkey;
 use std::collections::HashMap;
@@ -68,6 +73,10 @@ pub struct Reconciler {
     accounts: Arc<RwLock<HashMap<Pubkey, AccountState>>>,
     /// Current slot.
     current_slot: Arc<RwLock<u64>>,
+    /// Monitor whose positions are refreshed after transactions.
+    monitor: Option<Arc<PositionMonitor>>,
+    /// Beaten after every reconciliation cycle, if watched.
+    heartbeat: Option<Heartbeat>,
 }
 
 impl Reconciler {
@@ -78,9 +87,26 @@ impl Reconciler {
             config,
             accounts: Arc::new(RwLock::new(HashMap::new())),
             current_slot: Arc::new(RwLock::new(0)),
+            monitor: None,
+            heartbeat: None,
         }
     }
 
+    /// Sets the monitor whose positions are refreshed after transactions.
+    #[must_use]
+    pub fn with_monitor(mut self, monitor: Arc<PositionMonitor>) -> Self {
+        self.monitor = Some(monitor);
+        self
+    }
+
+    /// Beats `heartbeat` after every reconciliation cycle, so a hung loop
+    /// is reported.
+    #[must_use]
+    pub fn with_heartbeat(mut self, heartbeat: Heartbeat) -> Self {
+        self.heartbeat = Some(heartbeat);
+        self
+    }
+
     /// Tracks an account for reconciliation.
     pub async fn track_account(&self, address: Pubkey) {
         let state = AccountState {
@@ -95,17 +121,41 @@ impl Reconciler {
         debug!(address = %address, "Tracking account for reconciliation");
     }
 
+    /// Tracks an account and subscribes to it on `source`, so its updates
+    /// reach the reconciler whichever source delivers them.
+    pub async fn track_via(
+        &self,
+        source: &dyn StateSource,
+        address: Pubkey,
+        sub_type: SubscriptionType,
+    ) {
+        self.track_account(address).await;
+        source.subscribe(address, sub_type).await;
+    }
+
     /// Stops tracking an account.
     pub async fn untrack_account(&self, address: &Pubkey) {
         self.accounts.write().await.remove(address);
         debug!(address = %address, "Stopped tracking account");
     }
 
-    /// Processes an account update from WebSocket.
+    /// Processes an account update from a [`StateSource`].
+    ///
+    /// Updates older than the last known slot are ignored, so an account
+    /// never moves back to older state.
     pub async fn process_update(&self, update: AccountUpdate) {
         let mut accounts = self.accounts.write().await;
 
         if let Some(state) = accounts.get_mut(&update.address) {
+            if update.slot < state.last_slot {
+                debug!(
+                    address = %update.address,
+                    slot = update.slot,
+                    last_slot = state.last_slot,
+                    "Ignored stale account update"
+                );
+                return;
+            }
             state.last_slot = update.slot;
             state.last_update = Instant::now();
             state.status = ReconcileStatus::InSync;
@@ -149,15 +199,7 @@ impl Reconciler {
                     Err(e) => {
                         warn!(address = %address, error = %e, "Reconciliation failed");
                         result.failed += 1;
-
-                        // Update failure count
-                        let mut accounts = self.accounts.write().await;
-                        if let Some(state) = accounts.get_mut(&address) {
-                            state.failure_count += 1;
-                            if state.failure_count >= self.config.max_failures {
-                                state.status = ReconcileStatus::Failed;
-                            }
-                        }
+                        self.record_failure(&address).await;
                     }
                 }
             } else {
@@ -169,6 +211,61 @@ impl Reconciler {
         result
     }
 
+    /// Reconciles the given accounts right away, regardless of their age,
+    /// e.g. after a transaction touched them at `slot`.
+    ///
+    /// Untracked accounts are skipped; monitored positions stored at or
+    /// trading in any of the accounts are refreshed as well.
+    pub async fn refresh_accounts(&self, addresses: &[Pubkey], slot: u64) -> ReconcileResult {
+        {
+            let mut current_slot = self.current_slot.write().await;
+            *current_slot = (*current_slot).max(slot);
+        }
+
+        let mut result = ReconcileResult {
+            current_slot: *self.current_slot.read().await,
+            ..Default::default()
+        };
+        let tracked: Vec<Pubkey> = {
+            let accounts = self.accounts.read().await;
+            addresses
+                .iter()
+                .filter(|address| accounts.contains_key(address))
+                .copied()
+                .collect()
+        };
+
+        for address in tracked {
+            match self.reconcile_account(&address).await {
+                Ok(()) => result.reconciled += 1,
+                Err(e) => {
+                    warn!(address = %address, error = %e, "Targeted refresh failed");
+                    result.failed += 1;
+                    self.record_failure(&address).await;
+                }
+            }
+        }
+
+        if let Some(monitor) = &self.monitor {
+            let positions = monitor.refresh_positions(addresses).await;
+            debug!(positions, "Refreshed monitored positions");
+        }
+
+        result
+    }
+
+    /// Counts a failed reconciliation, marking the account failed after
+    /// too many.
+    async fn record_failure(&self, address: &Pubkey) {
+        let mut accounts = self.accounts.write().await;
+        if let Some(state) = accounts.get_mut(address) {
+            state.failure_count += 1;
+            if state.failure_count >= self.config.max_failures {
+                state.status = ReconcileStatus::Failed;
+            }
+        }
+    }
+
     /// Reconciles a single account.
     async fn reconcile_account(&self, address: &Pubkey) -> anyhow::Result<()> {
         // Mark as updating
@@ -186,7 +283,8 @@ impl Reconciler {
         {
             let mut accounts = self.accounts.write().await;
             if let Some(state) = accounts.get_mut(address) {
-                state.last_slot = *self.current_slot.read().await;
+                let current_slot = *self.current_slot.read().await;
+                state.last_slot = state.last_slot.max(current_slot);
                 state.last_update = Instant::now();
                 state.status = ReconcileStatus::InSync;
                 state.failure_count = 0;
@@ -242,6 +340,11 @@ impl Reconciler {
 
     /// Starts the reconciliation loop.
     pub async fn start(&self) {
+        self.run_until(CancellationToken::new()).await;
+    }
+
+    /// Runs the reconciliation loop until `shutdown` is cancelled.
+    pub async fn run_until(&self, shutdown: CancellationToken) {
         info!(
             interval_secs = self.config.reconcile_interval_secs,
             "Starting reconciler"
@@ -251,7 +354,10 @@ impl Reconciler {
             tokio::time::interval(Duration::from_secs(self.config.reconcile_interval_secs));
 
         loop {
-            interval.tick().await;
+            tokio::select! {
+                () = shutdown.cancelled() => break,
+                _ = interval.tick() => {}
+            }
 
             let result = self.reconcile().await;
             debug!(
@@ -260,7 +366,28 @@ impl Reconciler {
                 failed = result.failed,
                 "Reconciliation cycle complete"
             );
+            if let Some(heartbeat) = &self.heartbeat {
+                heartbeat.beat();
+            }
         }
+
+        if let Some(heartbeat) = &self.heartbeat {
+            heartbeat.pause();
+        }
+        info!("Reconciler stopped");
+    }
+}
+
+#[async_trait]
+impl ConfirmationHook for Reconciler {
+    async fn on_confirmed(&self, result: &TransactionResult, accounts: &[Pubkey]) {
+        let refreshed = self.refresh_accounts(accounts, result.slot).await;
+        debug!(
+            signature = %result.signature,
+            reconciled = refreshed.reconciled,
+            failed = refreshed.failed,
+            "Refreshed accounts after transaction"
+        );
     }
 }
 
@@ -294,4 +421,34 @@ mod tests {
         let status = reconciler.get_status().await;
         assert!(status.contains_key(&address));
     }
+
+    #[tokio::test]
+    async fn test_refresh_accounts_skips_untracked() {
+        let provider = Arc::new(RpcProvider::new(RpcConfig::default()));
+        let reconciler = Reconciler::new(provider, ReconcilerConfig::default());
+
+        let result = reconciler
+            .refresh_accounts(&[Pubkey::new_unique()], 42)
+            .await;
+        assert_eq!(result.reconciled + result.failed, 0);
+        assert_eq!(result.current_slot, 42);
+    }
+
+    #[tokio::test]
+    async fn test_track_via_subscribes_on_source() {
+        use crate::sync::{AccountListener, AccountListenerConfig};
+        use clmm_lp_protocols::prelude::Cluster;
+
+        let provider = Arc::new(RpcProvider::new(RpcConfig::default()));
+        let reconciler = Reconciler::new(provider, ReconcilerConfig::default());
+        let listener = AccountListener::new(AccountListenerConfig::for_cluster(Cluster::Devnet));
+
+        let address = Pubkey::new_unique();
+        reconciler
+            .track_via(&listener, address, SubscriptionType::Pool)
+            .await;
+
+        assert!(reconciler.get_status().await.contains_key(&address));
+        assert_eq!(StateSource::subscription_count(&listener).await, 1);
+    }
 }
@@ -0,0 +1,188 @@
+//! Slot-based ordering of account updates.
+//!
+//! After a reconnect a subscription may replay notifications already seen,
+//! or deliver an older slot after a newer one from another connection.
+//! Applying those would move cached state backwards. [`SlotOrderer`]
+//! remembers the newest slot seen per account and lets only newer updates
+//! through, counting what it drops.
+
+use super::AccountUpdate;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing::debug;
+
+/// How an update relates to what was already seen for its account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateOrder {
+    /// Newer than anything seen; should be applied.
+    Fresh,
+    /// Same slot and data as the last update applied.
+    Duplicate,
+    /// Older than the last update applied.
+    Stale {
+        /// Slot of the last update applied.
+        latest_slot: u64,
+    },
+}
+
+/// Counts of updates let through and dropped.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SlotOrderStats {
+    /// Updates let through.
+    pub accepted: u64,
+    /// Updates dropped for an older slot.
+    pub stale_dropped: u64,
+    /// Updates dropped as repeats of the last one.
+    pub duplicates_dropped: u64,
+}
+
+/// Drops account updates older than, or identical to, the last one seen.
+#[derive(Debug, Default)]
+pub struct SlotOrderer {
+    /// Slot and data hash of the last update let through, per account.
+    latest: Mutex<HashMap<Pubkey, (u64, u64)>>,
+    accepted: AtomicU64,
+    stale_dropped: AtomicU64,
+    duplicates_dropped: AtomicU64,
+}
+
+impl SlotOrderer {
+    /// Creates an orderer that has seen nothing.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Classifies `update`, remembering it if it is fresh.
+    ///
+    /// Updates in the slot already seen count as fresh when their data
+    /// differs, since an account can change more than once in a slot.
+    pub fn check(&self, update: &AccountUpdate) -> UpdateOrder {
+        let digest = data_digest(&update.data);
+        let order = {
+            let mut latest = self
+                .latest
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            match latest.get(&update.address) {
+                Some(&(slot, _)) if update.slot < slot => UpdateOrder::Stale { latest_slot: slot },
+                Some(&(slot, seen)) if update.slot == slot && seen == digest => {
+                    UpdateOrder::Duplicate
+                }
+                _ => {
+                    latest.insert(update.address, (update.slot, digest));
+                    UpdateOrder::Fresh
+                }
+            }
+        };
+
+        match order {
+            UpdateOrder::Fresh => {
+                self.accepted.fetch_add(1, Ordering::Relaxed);
+            }
+            UpdateOrder::Duplicate => {
+                self.duplicates_dropped.fetch_add(1, Ordering::Relaxed);
+                debug!(address = %update.address, slot = update.slot, "Dropped duplicate update");
+            }
+            UpdateOrder::Stale { latest_slot } => {
+                self.stale_dropped.fetch_add(1, Ordering::Relaxed);
+                debug!(
+                    address = %update.address,
+                    slot = update.slot,
+                    latest_slot,
+                    "Dropped stale update"
+                );
+            }
+        }
+        order
+    }
+
+    /// Returns the slot of the last update let through for `address`.
+    #[must_use]
+    pub fn latest_slot(&self, address: &Pubkey) -> Option<u64> {
+        self.latest
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .get(address)
+            .map(|&(slot, _)| slot)
+    }
+
+    /// Forgets an account, e.g. after unsubscribing from it.
+    pub fn forget(&self, address: &Pubkey) {
+        self.latest
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .remove(address);
+    }
+
+    /// Gets the counts of updates let through and dropped.
+    #[must_use]
+    pub fn stats(&self) -> SlotOrderStats {
+        SlotOrderStats {
+            accepted: self.accepted.load(Ordering::Relaxed),
+            stale_dropped: self.stale_dropped.load(Ordering::Relaxed),
+            duplicates_dropped: self.duplicates_dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+fn data_digest(data: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    data.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(address: Pubkey, slot: u64, data: &[u8]) -> AccountUpdate {
+        AccountUpdate {
+            address,
+            slot,
+            data: data.to_vec(),
+            lamports: 0,
+            owner: Pubkey::default(),
+        }
+    }
+
+    #[test]
+    fn test_drops_stale_and_duplicate_updates() {
+        let orderer = SlotOrderer::new();
+        let pool = Pubkey::new_unique();
+
+        assert_eq!(orderer.check(&update(pool, 100, b"a")), UpdateOrder::Fresh);
+        assert_eq!(
+            orderer.check(&update(pool, 100, b"a")),
+            UpdateOrder::Duplicate
+        );
+        // A second change within the slot still applies
+        assert_eq!(orderer.check(&update(pool, 100, b"b")), UpdateOrder::Fresh);
+        assert_eq!(
+            orderer.check(&update(pool, 99, b"c")),
+            UpdateOrder::Stale { latest_slot: 100 }
+        );
+        assert_eq!(orderer.check(&update(pool, 101, b"a")), UpdateOrder::Fresh);
+        assert_eq!(orderer.latest_slot(&pool), Some(101));
+
+        // Accounts are ordered independently
+        let other = Pubkey::new_unique();
+        assert_eq!(orderer.check(&update(other, 50, b"a")), UpdateOrder::Fresh);
+
+        assert_eq!(
+            orderer.stats(),
+            SlotOrderStats {
+                accepted: 4,
+                stale_dropped: 1,
+                duplicates_dropped: 1,
+            }
+        );
+
+        orderer.forget(&pool);
+        assert_eq!(orderer.check(&update(pool, 1, b"a")), UpdateOrder::Fresh);
+    }
+}
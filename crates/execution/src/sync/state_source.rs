@@ -0,0 +1,73 @@
+//! Sources of account updates the sync layer consumes.
+
+use super::{AccountUpdate, SubscriptionType};
+use crate::shutdown::CancellationToken;
+use async_trait::async_trait;
+use clmm_lp_protocols::prelude::{CommitmentLevel, RpcProvider};
+use solana_sdk::pubkey::Pubkey;
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+/// A source of account updates, such as WebSocket subscriptions or a
+/// Geyser gRPC stream.
+///
+/// Updates are delivered in slot order through the receiver taken with
+/// [`StateSource::take_receiver`]; stale and duplicate updates are dropped.
+#[async_trait]
+pub trait StateSource: Send + Sync {
+    /// Name of the source for logs.
+    fn name(&self) -> &'static str;
+
+    /// Takes the update receiver.
+    fn take_receiver(&mut self) -> Option<mpsc::Receiver<AccountUpdate>>;
+
+    /// Subscribes to an account at the source's default commitment level.
+    async fn subscribe(&self, address: Pubkey, sub_type: SubscriptionType);
+
+    /// Subscribes to an account at `commitment`.
+    async fn subscribe_with_commitment(
+        &self,
+        address: Pubkey,
+        sub_type: SubscriptionType,
+        commitment: CommitmentLevel,
+    );
+
+    /// Unsubscribes from an account.
+    async fn unsubscribe(&self, address: &Pubkey);
+
+    /// Gets subscription count.
+    async fn subscription_count(&self) -> usize;
+
+    /// Runs the source until `shutdown` is cancelled.
+    async fn run_until(&self, shutdown: CancellationToken);
+}
+
+/// Fetches the current state of an account as an update.
+///
+/// Failures are logged; the account is then known from its first change.
+pub(crate) async fn fetch_snapshot(
+    provider: &RpcProvider,
+    address: &Pubkey,
+    commitment: CommitmentLevel,
+) -> Option<AccountUpdate> {
+    match provider.get_account_with_slot(address, commitment).await {
+        Ok((slot, Some(account))) => {
+            debug!(address = %address, slot, "Fetched account snapshot");
+            Some(AccountUpdate {
+                address: *address,
+                slot,
+                data: account.data,
+                lamports: account.lamports,
+                owner: account.owner,
+            })
+        }
+        Ok((slot, None)) => {
+            warn!(address = %address, slot, "Subscribed account does not exist");
+            None
+        }
+        Err(e) => {
+            warn!(address = %address, error = %e, "Failed to fetch account snapshot");
+            None
+        }
+    }
+}
@@ -0,0 +1,172 @@
+//! CSV export of tax records.
+
+use super::{TaxRecord, TaxRecordKind};
+use rust_decimal::Decimal;
+
+/// CSV layout for tax exports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaxCsvFormat {
+    /// One row per record with cost basis and gain columns.
+    Generic,
+    /// Koinly universal import template.
+    Koinly,
+}
+
+/// Header of the generic format.
+const GENERIC_HEADER: &str =
+    "date,type,asset,amount,value_usd,cost_basis_usd,gain_usd,position,event,tx_hash";
+
+/// Header of the Koinly universal template.
+const KOINLY_HEADER: &str = "Date,Sent Amount,Sent Currency,Received Amount,Received Currency,\
+Fee Amount,Fee Currency,Net Worth Amount,Net Worth Currency,Label,Description,TxHash";
+
+/// Renders records as CSV, one line per record after the header.
+#[must_use]
+pub fn tax_records_to_csv(records: &[TaxRecord], format: TaxCsvFormat) -> String {
+    let mut csv = String::new();
+    csv.push_str(match format {
+        TaxCsvFormat::Generic => GENERIC_HEADER,
+        TaxCsvFormat::Koinly => KOINLY_HEADER,
+    });
+    csv.push('\n');
+
+    for record in records {
+        let row = match format {
+            TaxCsvFormat::Generic => generic_row(record),
+            TaxCsvFormat::Koinly => koinly_row(record),
+        };
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+/// Builds a generic-format row.
+fn generic_row(record: &TaxRecord) -> Vec<String> {
+    vec![
+        record.timestamp.to_rfc3339(),
+        record.kind.as_str().to_string(),
+        field(&record.asset),
+        record.amount.normalize().to_string(),
+        usd(record.value_usd),
+        record.cost_basis_usd.map(usd).unwrap_or_default(),
+        record.gain_usd().map(usd).unwrap_or_default(),
+        record.position.to_string(),
+        field(&record.event),
+        record.tx_signature.clone().unwrap_or_default(),
+    ]
+}
+
+/// Builds a Koinly-format row.
+///
+/// Deposits and withdrawals use Koinly's liquidity labels, so the tokens are
+/// tracked as moving in and out of the pool.
+fn koinly_row(record: &TaxRecord) -> Vec<String> {
+    let amount = record.amount.normalize().to_string();
+    let asset = field(&record.asset);
+    let empty = String::new;
+
+    let (sent, received, fee, label) = match record.kind {
+        TaxRecordKind::Disposal => (
+            (amount, asset),
+            (empty(), empty()),
+            (empty(), empty()),
+            "liquidity in",
+        ),
+        TaxRecordKind::Acquisition => (
+            (empty(), empty()),
+            (amount, asset),
+            (empty(), empty()),
+            "liquidity out",
+        ),
+        TaxRecordKind::Income => (
+            (empty(), empty()),
+            (amount, asset),
+            (empty(), empty()),
+            "reward",
+        ),
+        TaxRecordKind::Fee => (
+            (empty(), empty()),
+            (empty(), empty()),
+            (amount, asset),
+            "cost",
+        ),
+    };
+
+    vec![
+        record.timestamp.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+        sent.0,
+        sent.1,
+        received.0,
+        received.1,
+        fee.0,
+        fee.1,
+        usd(record.value_usd),
+        "USD".to_string(),
+        label.to_string(),
+        field(&format!("{} {}", record.event, record.position)),
+        record.tx_signature.clone().unwrap_or_default(),
+    ]
+}
+
+/// Formats a USD amount with cents.
+fn usd(value: Decimal) -> String {
+    value.round_dp(2).to_string()
+}
+
+/// Quotes a text field if it contains CSV metacharacters.
+fn field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tax::report::tests::{sample_builder, sample_events, sample_oracle};
+    use solana_sdk::pubkey::Pubkey;
+
+    #[test]
+    fn test_generic_csv() {
+        let pool = Pubkey::new_unique();
+        let oracle = sample_oracle();
+        let records = sample_builder(&oracle, pool)
+            .build(&sample_events(pool))
+            .unwrap();
+
+        let csv = tax_records_to_csv(&records, TaxCsvFormat::Generic);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), records.len() + 1);
+        assert_eq!(lines[0], GENERIC_HEADER);
+        assert!(lines[1].starts_with("2024-01-01T12:00:00+00:00,disposal,SOL,1,100.00,,,"));
+        assert!(lines[4].contains(",fee,SOL,0.000005,0.00,0.00,0.00,"));
+    }
+
+    #[test]
+    fn test_koinly_csv() {
+        let pool = Pubkey::new_unique();
+        let oracle = sample_oracle();
+        let records = sample_builder(&oracle, pool)
+            .build(&sample_events(pool))
+            .unwrap();
+
+        let csv = tax_records_to_csv(&records, TaxCsvFormat::Koinly);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0].split(',').count(), 12);
+        assert!(lines[1].starts_with("2024-01-01 12:00:00 UTC,1,SOL,,,,,100.00,USD,liquidity in,"));
+        assert!(lines[3].starts_with("2024-01-02 12:00:00 UTC,,,0.01,SOL,,,1.00,USD,reward,"));
+        assert!(lines[4].contains(",,,,,0.000005,SOL,0.00,USD,cost,"));
+        assert!(lines.iter().skip(1).all(|l| l.split(',').count() == 12));
+    }
+
+    #[test]
+    fn test_field_quoting() {
+        assert_eq!(field("SOL"), "SOL");
+        assert_eq!(field("a,b"), "\"a,b\"");
+        assert_eq!(field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
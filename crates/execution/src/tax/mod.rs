@@ -0,0 +1,18 @@
+//! Tax reporting from position lifecycle events.
+//!
+//! Converts lifecycle events into dated acquisition and disposal records:
+//! - Deposits into a position dispose of the deposited tokens
+//! - Withdrawals and closes acquire the returned tokens
+//! - Collected fees and rewards are income
+//! - Rebalance transaction costs are network fees paid in SOL
+//!
+//! Cost basis is tracked per asset with FIFO lots and valued in USD through
+//! a [`PriceOracle`]. Records export to CSV in generic or Koinly format.
+
+mod csv;
+mod oracle;
+mod report;
+
+pub use csv::*;
+pub use oracle::*;
+pub use report::*;
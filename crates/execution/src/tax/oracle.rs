@@ -0,0 +1,80 @@
+//! USD price sources for tax reporting.
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashMap};
+
+/// Source of historical USD prices.
+pub trait PriceOracle {
+    /// Returns the USD price of one unit of `symbol` at `at`, if known.
+    fn price_usd(&self, symbol: &str, at: DateTime<Utc>) -> Option<Decimal>;
+}
+
+/// Price oracle backed by preloaded price points.
+///
+/// Looks up the latest price at or before the requested time.
+#[derive(Debug, Clone, Default)]
+pub struct HistoricalPrices {
+    /// Price points by symbol, ordered by time.
+    prices: HashMap<String, BTreeMap<DateTime<Utc>, Decimal>>,
+}
+
+impl HistoricalPrices {
+    /// Creates an empty oracle.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a price point.
+    pub fn insert(&mut self, symbol: impl Into<String>, at: DateTime<Utc>, price_usd: Decimal) {
+        self.prices
+            .entry(symbol.into())
+            .or_default()
+            .insert(at, price_usd);
+    }
+
+    /// Adds a price point.
+    #[must_use]
+    pub fn with_price(
+        mut self,
+        symbol: impl Into<String>,
+        at: DateTime<Utc>,
+        price_usd: Decimal,
+    ) -> Self {
+        self.insert(symbol, at, price_usd);
+        self
+    }
+}
+
+impl PriceOracle for HistoricalPrices {
+    fn price_usd(&self, symbol: &str, at: DateTime<Utc>) -> Option<Decimal> {
+        self.prices
+            .get(symbol)?
+            .range(..=at)
+            .next_back()
+            .map(|(_, price)| *price)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_historical_prices_uses_latest_point() {
+        let day = |d| Utc.with_ymd_and_hms(2024, 1, d, 0, 0, 0).unwrap();
+        let oracle = HistoricalPrices::new()
+            .with_price("SOL", day(1), Decimal::from(100))
+            .with_price("SOL", day(3), Decimal::from(120));
+
+        assert_eq!(oracle.price_usd("SOL", day(2)), Some(Decimal::from(100)));
+        assert_eq!(oracle.price_usd("SOL", day(5)), Some(Decimal::from(120)));
+        assert_eq!(
+            oracle.price_usd("SOL", day(1) - chrono::Duration::hours(1)),
+            None
+        );
+        assert_eq!(oracle.price_usd("USDC", day(2)), None);
+    }
+}
@@ -0,0 +1,475 @@
+//! Acquisition and disposal records with FIFO cost basis.
+
+use super::PriceOracle;
+use crate::lifecycle::{EventData, LifecycleEvent};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, VecDeque};
+
+/// Decimals of native SOL, in which transaction costs are paid.
+const SOL_DECIMALS: u8 = 9;
+
+/// Token as it appears in a tax report.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaxAsset {
+    /// Ticker symbol used for pricing and export.
+    pub symbol: String,
+    /// Token decimals used to convert raw amounts.
+    pub decimals: u8,
+}
+
+impl TaxAsset {
+    /// Creates a tax asset.
+    #[must_use]
+    pub fn new(symbol: impl Into<String>, decimals: u8) -> Self {
+        Self {
+            symbol: symbol.into(),
+            decimals,
+        }
+    }
+
+    /// Converts a raw token amount into whole units.
+    #[must_use]
+    pub fn to_units(&self, raw: u64) -> Decimal {
+        Decimal::from_i128_with_scale(i128::from(raw), u32::from(self.decimals))
+    }
+}
+
+/// Tokens of a pool, needed to interpret raw amounts in its events.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PoolAssets {
+    /// Token A.
+    pub token_a: TaxAsset,
+    /// Token B.
+    pub token_b: TaxAsset,
+    /// Reward tokens by reward slot; missing slots are left out of the report.
+    pub rewards: Vec<TaxAsset>,
+}
+
+impl PoolAssets {
+    /// Creates pool assets without reward tokens.
+    #[must_use]
+    pub fn new(token_a: TaxAsset, token_b: TaxAsset) -> Self {
+        Self {
+            token_a,
+            token_b,
+            rewards: Vec::new(),
+        }
+    }
+
+    /// Sets the reward tokens by slot.
+    #[must_use]
+    pub fn with_rewards(mut self, rewards: Vec<TaxAsset>) -> Self {
+        self.rewards = rewards;
+        self
+    }
+}
+
+/// Kind of taxable record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TaxRecordKind {
+    /// Tokens received back from a position.
+    Acquisition,
+    /// Tokens deposited into a position.
+    Disposal,
+    /// Fees or rewards earned.
+    Income,
+    /// Network fee paid.
+    Fee,
+}
+
+impl TaxRecordKind {
+    /// Returns true for kinds that add a cost-basis lot.
+    #[must_use]
+    pub fn is_inflow(&self) -> bool {
+        matches!(self, Self::Acquisition | Self::Income)
+    }
+
+    /// Returns the lowercase name used in exports.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Acquisition => "acquisition",
+            Self::Disposal => "disposal",
+            Self::Income => "income",
+            Self::Fee => "fee",
+        }
+    }
+}
+
+/// A dated acquisition or disposal of a single asset.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaxRecord {
+    /// When the event happened.
+    pub timestamp: DateTime<Utc>,
+    /// Record kind.
+    pub kind: TaxRecordKind,
+    /// Asset symbol.
+    pub asset: String,
+    /// Amount in whole units.
+    pub amount: Decimal,
+    /// Market value in USD at the time: cost for inflows, proceeds for outflows.
+    pub value_usd: Decimal,
+    /// FIFO cost basis of an outflow; `None` if earlier acquisitions do not cover it.
+    pub cost_basis_usd: Option<Decimal>,
+    /// Position the event belongs to.
+    pub position: Pubkey,
+    /// Lifecycle event that produced the record.
+    pub event: String,
+    /// Transaction signature, if recorded.
+    pub tx_signature: Option<String>,
+}
+
+impl TaxRecord {
+    /// Returns the realized gain of an outflow with a known cost basis.
+    #[must_use]
+    pub fn gain_usd(&self) -> Option<Decimal> {
+        self.cost_basis_usd.map(|basis| self.value_usd - basis)
+    }
+}
+
+/// Open cost-basis lot.
+#[derive(Debug, Clone)]
+struct Lot {
+    amount: Decimal,
+    unit_cost: Decimal,
+}
+
+/// FIFO lots per asset.
+#[derive(Debug, Default)]
+struct LotBook {
+    lots: HashMap<String, VecDeque<Lot>>,
+}
+
+impl LotBook {
+    /// Adds a lot.
+    fn acquire(&mut self, asset: &str, amount: Decimal, unit_cost: Decimal) {
+        self.lots
+            .entry(asset.to_string())
+            .or_default()
+            .push_back(Lot { amount, unit_cost });
+    }
+
+    /// Consumes lots oldest first and returns their cost.
+    ///
+    /// Returns `None` if the lots do not cover `amount`; what is available is
+    /// still consumed.
+    fn dispose(&mut self, asset: &str, amount: Decimal) -> Option<Decimal> {
+        let lots = self.lots.entry(asset.to_string()).or_default();
+        let mut remaining = amount;
+        let mut cost = Decimal::ZERO;
+
+        while remaining > Decimal::ZERO {
+            let lot = lots.front_mut()?;
+            let used = remaining.min(lot.amount);
+            cost += used * lot.unit_cost;
+            remaining -= used;
+            lot.amount -= used;
+            if lot.amount.is_zero() {
+                lots.pop_front();
+            }
+        }
+
+        Some(cost)
+    }
+}
+
+/// Builds tax records from lifecycle events.
+pub struct TaxReportBuilder<'a, O: PriceOracle> {
+    /// USD price source.
+    oracle: &'a O,
+    /// Assets by pool address.
+    pools: HashMap<Pubkey, PoolAssets>,
+    /// Asset in which transaction costs are paid.
+    native: TaxAsset,
+}
+
+impl<'a, O: PriceOracle> TaxReportBuilder<'a, O> {
+    /// Creates a builder pricing assets with `oracle`.
+    #[must_use]
+    pub fn new(oracle: &'a O) -> Self {
+        Self {
+            oracle,
+            pools: HashMap::new(),
+            native: TaxAsset::new("SOL", SOL_DECIMALS),
+        }
+    }
+
+    /// Registers the assets of a pool.
+    #[must_use]
+    pub fn with_pool(mut self, pool: Pubkey, assets: PoolAssets) -> Self {
+        self.pools.insert(pool, assets);
+        self
+    }
+
+    /// Builds records for `events`, processed in time order.
+    ///
+    /// # Errors
+    /// Returns an error if an event's pool is not registered or the oracle
+    /// has no price for an asset at the event time.
+    pub fn build(&self, events: &[LifecycleEvent]) -> Result<Vec<TaxRecord>> {
+        let mut ordered: Vec<&LifecycleEvent> = events.iter().collect();
+        ordered.sort_by_key(|e| e.timestamp);
+
+        let mut lots = LotBook::default();
+        let mut records = Vec::new();
+
+        for event in ordered {
+            let assets = self
+                .pools
+                .get(&event.pool)
+                .with_context(|| format!("No assets registered for pool {}", event.pool))?;
+
+            for (kind, asset, raw) in Self::flows(event, assets, &self.native) {
+                if raw == 0 {
+                    continue;
+                }
+                let amount = asset.to_units(raw);
+                let price = self
+                    .oracle
+                    .price_usd(&asset.symbol, event.timestamp)
+                    .with_context(|| {
+                        format!("No USD price for {} at {}", asset.symbol, event.timestamp)
+                    })?;
+
+                let cost_basis_usd = if kind.is_inflow() {
+                    lots.acquire(&asset.symbol, amount, price);
+                    None
+                } else {
+                    lots.dispose(&asset.symbol, amount)
+                };
+
+                records.push(TaxRecord {
+                    timestamp: event.timestamp,
+                    kind,
+                    asset: asset.symbol.clone(),
+                    amount,
+                    value_usd: amount * price,
+                    cost_basis_usd,
+                    position: event.position,
+                    event: format!("{:?}", event.event_type),
+                    tx_signature: event.signature.map(|s| s.to_string()),
+                });
+            }
+        }
+
+        Ok(records)
+    }
+
+    /// Returns the token flows of an event as (kind, asset, raw amount).
+    fn flows<'b>(
+        event: &LifecycleEvent,
+        assets: &'b PoolAssets,
+        native: &'b TaxAsset,
+    ) -> Vec<(TaxRecordKind, &'b TaxAsset, u64)> {
+        let (a, b) = (&assets.token_a, &assets.token_b);
+        match &event.data {
+            EventData::PositionOpened(data) => vec![
+                (TaxRecordKind::Disposal, a, data.amount_a),
+                (TaxRecordKind::Disposal, b, data.amount_b),
+            ],
+            EventData::LiquidityChange(data) => {
+                let kind = if data.is_increase {
+                    TaxRecordKind::Disposal
+                } else {
+                    TaxRecordKind::Acquisition
+                };
+                vec![(kind, a, data.amount_a), (kind, b, data.amount_b)]
+            }
+            EventData::Rebalance(data) => vec![(TaxRecordKind::Fee, native, data.tx_cost_lamports)],
+            EventData::Migration(data) => vec![(TaxRecordKind::Fee, native, data.tx_cost_lamports)],
+            EventData::FeesCollected(data) => {
+                let mut flows = vec![
+                    (TaxRecordKind::Income, a, data.fees_a),
+                    (TaxRecordKind::Income, b, data.fees_b),
+                ];
+                flows.extend(
+                    assets
+                        .rewards
+                        .iter()
+                        .zip(data.rewards)
+                        .map(|(asset, raw)| (TaxRecordKind::Income, asset, raw)),
+                );
+                flows
+            }
+            EventData::PositionClosed(data) => vec![
+                (TaxRecordKind::Acquisition, a, data.amount_a),
+                (TaxRecordKind::Acquisition, b, data.amount_b),
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+    use crate::lifecycle::{
+        CloseReason, FeesCollectedData, LifecycleEventType, PositionClosedData, PositionOpenedData,
+        RebalanceData, RebalanceReason,
+    };
+    use crate::tax::HistoricalPrices;
+    use chrono::TimeZone;
+
+    pub(crate) fn day(d: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, d, 12, 0, 0).unwrap()
+    }
+
+    fn event(
+        at: DateTime<Utc>,
+        event_type: LifecycleEventType,
+        pool: Pubkey,
+        data: EventData,
+    ) -> LifecycleEvent {
+        let mut event = LifecycleEvent::new(event_type, Pubkey::new_unique(), pool, data);
+        event.timestamp = at;
+        event
+    }
+
+    fn opened(at: DateTime<Utc>, pool: Pubkey, amount_a: u64, amount_b: u64) -> LifecycleEvent {
+        event(
+            at,
+            LifecycleEventType::PositionOpened,
+            pool,
+            EventData::PositionOpened(PositionOpenedData {
+                tick_lower: -100,
+                tick_upper: 100,
+                liquidity: 1000,
+                amount_a,
+                amount_b,
+                entry_price: Decimal::from(100),
+                entry_value_usd: Decimal::from(200),
+            }),
+        )
+    }
+
+    /// SOL/USDC events: open, collect fees, rebalance, close, reopen.
+    pub(crate) fn sample_events(pool: Pubkey) -> Vec<LifecycleEvent> {
+        vec![
+            opened(day(1), pool, 1_000_000_000, 100_000_000),
+            event(
+                day(2),
+                LifecycleEventType::FeesCollected,
+                pool,
+                EventData::FeesCollected(FeesCollectedData {
+                    fees_a: 10_000_000,
+                    fees_b: 0,
+                    fees_usd: Decimal::ONE,
+                    rewards: [0, 0, 0],
+                    rewards_usd: Decimal::ZERO,
+                }),
+            ),
+            event(
+                day(3),
+                LifecycleEventType::Rebalanced,
+                pool,
+                EventData::Rebalance(RebalanceData {
+                    old_tick_lower: -100,
+                    old_tick_upper: 100,
+                    new_tick_lower: 0,
+                    new_tick_upper: 200,
+                    old_liquidity: 1000,
+                    new_liquidity: 1000,
+                    tx_cost_lamports: 5_000,
+                    il_at_rebalance: Decimal::ZERO,
+                    reason: RebalanceReason::RangeExit,
+                }),
+            ),
+            event(
+                day(4),
+                LifecycleEventType::PositionClosed,
+                pool,
+                EventData::PositionClosed(PositionClosedData {
+                    liquidity_removed: 1000,
+                    amount_a: 2_000_000_000,
+                    amount_b: 0,
+                    total_fees_a: 10_000_000,
+                    total_fees_b: 0,
+                    final_pnl_usd: Decimal::ZERO,
+                    final_pnl_pct: Decimal::ZERO,
+                    total_il_pct: Decimal::ZERO,
+                    duration_hours: 72,
+                    reason: CloseReason::Manual,
+                }),
+            ),
+            opened(day(5), pool, 1_500_000_000, 0),
+        ]
+    }
+
+    pub(crate) fn sample_builder(
+        oracle: &HistoricalPrices,
+        pool: Pubkey,
+    ) -> TaxReportBuilder<'_, HistoricalPrices> {
+        TaxReportBuilder::new(oracle).with_pool(
+            pool,
+            PoolAssets::new(TaxAsset::new("SOL", 9), TaxAsset::new("USDC", 6)),
+        )
+    }
+
+    pub(crate) fn sample_oracle() -> HistoricalPrices {
+        HistoricalPrices::new()
+            .with_price("SOL", day(1), Decimal::from(100))
+            .with_price("SOL", day(4), Decimal::from(110))
+            .with_price("SOL", day(5), Decimal::from(120))
+            .with_price("USDC", day(1), Decimal::ONE)
+    }
+
+    #[test]
+    fn test_build_records_and_fifo_cost_basis() {
+        let pool = Pubkey::new_unique();
+        let oracle = sample_oracle();
+        let records = sample_builder(&oracle, pool)
+            .build(&sample_events(pool))
+            .unwrap();
+
+        let kinds: Vec<_> = records.iter().map(|r| (r.kind, r.asset.as_str())).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (TaxRecordKind::Disposal, "SOL"),
+                (TaxRecordKind::Disposal, "USDC"),
+                (TaxRecordKind::Income, "SOL"),
+                (TaxRecordKind::Fee, "SOL"),
+                (TaxRecordKind::Acquisition, "SOL"),
+                (TaxRecordKind::Disposal, "SOL"),
+            ]
+        );
+
+        // Tokens deposited before any tracked acquisition have no basis
+        assert_eq!(records[0].value_usd, Decimal::from(100));
+        assert_eq!(records[0].cost_basis_usd, None);
+
+        // Fee income of 0.01 SOL at $100
+        assert_eq!(records[2].amount, Decimal::new(1, 2));
+        assert_eq!(records[2].value_usd, Decimal::ONE);
+
+        // The 5000 lamport rebalance cost is covered by the income lot
+        assert_eq!(records[3].amount, Decimal::new(5, 6));
+        assert_eq!(records[3].cost_basis_usd, Some(Decimal::new(5, 4)));
+
+        // Reopening with 1.5 SOL at $120: FIFO takes the rest of the income
+        // lot at $100, then 1.490005 SOL from the close at $110
+        let reopen = &records[5];
+        assert_eq!(reopen.value_usd, Decimal::from(180));
+        let expected_basis = Decimal::new(9_995, 6) * Decimal::from(100)
+            + Decimal::new(1_490_005, 6) * Decimal::from(110);
+        assert_eq!(reopen.cost_basis_usd, Some(expected_basis));
+        assert_eq!(reopen.gain_usd(), Some(Decimal::from(180) - expected_basis));
+    }
+
+    #[test]
+    fn test_build_requires_prices_and_pools() {
+        let pool = Pubkey::new_unique();
+        let events = vec![opened(day(1), pool, 1, 1)];
+
+        let no_prices = HistoricalPrices::new();
+        let err = sample_builder(&no_prices, pool).build(&events).unwrap_err();
+        assert!(err.to_string().contains("No USD price for SOL"));
+
+        let oracle = sample_oracle();
+        let err = TaxReportBuilder::new(&oracle).build(&events).unwrap_err();
+        assert!(err.to_string().contains("No assets registered"));
+    }
+}
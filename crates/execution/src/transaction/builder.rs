@@ -1,13 +1,22 @@
 //! Transaction builder.
 
-use super::PriorityLevel;
+use super::{DurableNonce, PriorityLevel, advance_nonce_instruction};
 use anyhow::{Context, Result};
 use solana_sdk::hash::Hash;
 use solana_sdk::instruction::Instruction;
 use solana_sdk::message::Message;
+use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::Keypair;
 use solana_sdk::signer::Signer;
 use solana_sdk::transaction::Transaction;
+use std::str::FromStr;
+
+/// Compute budget program ID.
+pub const COMPUTE_BUDGET_PROGRAM_ID: &str = "ComputeBudget111111111111111111111111111111";
+
+/// Compute budget instruction tags.
+const SET_COMPUTE_UNIT_LIMIT: u8 = 2;
+const SET_COMPUTE_UNIT_PRICE: u8 = 3;
 
 /// Builder for constructing transactions.
 pub struct TransactionBuilder {
@@ -17,10 +26,14 @@ pub struct TransactionBuilder {
     compute_units: Option<u32>,
     /// Priority level.
     priority: PriorityLevel,
+    /// Compute unit price in micro-lamports, overriding the priority level.
+    compute_unit_price: Option<u64>,
     /// Recent blockhash.
     blockhash: Option<Hash>,
     /// Fee payer.
     fee_payer: Option<solana_sdk::pubkey::Pubkey>,
+    /// Durable nonce used in place of a recent blockhash.
+    nonce: Option<DurableNonce>,
 }
 
 impl TransactionBuilder {
@@ -31,8 +44,10 @@ impl TransactionBuilder {
             instructions: Vec::new(),
             compute_units: None,
             priority: PriorityLevel::default(),
+            compute_unit_price: None,
             blockhash: None,
             fee_payer: None,
+            nonce: None,
         }
     }
 
@@ -58,12 +73,23 @@ impl TransactionBuilder {
     }
 
     /// Sets the priority level.
+    ///
+    /// Without an explicit compute unit price, the level's static price is
+    /// paid.
     #[must_use]
     pub fn with_priority(mut self, priority: PriorityLevel) -> Self {
         self.priority = priority;
         self
     }
 
+    /// Sets the compute unit price in micro-lamports, such as one estimated
+    /// from recent network fees.
+    #[must_use]
+    pub fn with_compute_unit_price(mut self, micro_lamports: u64) -> Self {
+        self.compute_unit_price = Some(micro_lamports);
+        self
+    }
+
     /// Sets the recent blockhash.
     #[must_use]
     pub fn with_blockhash(mut self, blockhash: Hash) -> Self {
@@ -78,6 +104,17 @@ impl TransactionBuilder {
         self
     }
 
+    /// Signs against a durable nonce instead of a recent blockhash.
+    ///
+    /// The transaction stays valid until the nonce is advanced, so it can
+    /// wait for slow approvals. The nonce authority must be among the signers.
+    #[must_use]
+    pub fn with_durable_nonce(mut self, nonce: &DurableNonce) -> Self {
+        self.blockhash = Some(nonce.nonce);
+        self.nonce = Some(*nonce);
+        self
+    }
+
     /// Builds the transaction.
     pub fn build(self, signers: &[&Keypair]) -> Result<Transaction> {
         let blockhash = self.blockhash.context("Blockhash not set")?;
@@ -90,11 +127,18 @@ impl TransactionBuilder {
         // Build instructions with compute budget
         let mut all_instructions = Vec::new();
 
-        // Note: Compute budget instructions would be added here
-        // In solana-sdk 3.x, these are in a separate crate
-        // For now, we skip compute budget instructions
-        let _ = self.compute_units;
-        let _ = self.priority;
+        // The nonce advance must be the first instruction
+        if let Some(nonce) = &self.nonce {
+            all_instructions.push(advance_nonce_instruction(&nonce.account, &nonce.authority));
+        }
+
+        if let Some(units) = self.compute_units {
+            all_instructions.push(set_compute_unit_limit_instruction(units));
+        }
+        let price = self
+            .compute_unit_price
+            .unwrap_or_else(|| self.priority.default_micro_lamports());
+        all_instructions.push(set_compute_unit_price_instruction(price));
 
         // Add user instructions
         all_instructions.extend(self.instructions);
@@ -116,6 +160,27 @@ impl TransactionBuilder {
     }
 }
 
+/// Builds the instruction capping the compute units a transaction may use.
+#[must_use]
+pub fn set_compute_unit_limit_instruction(units: u32) -> Instruction {
+    let mut data = vec![SET_COMPUTE_UNIT_LIMIT];
+    data.extend_from_slice(&units.to_le_bytes());
+    Instruction::new_with_bytes(compute_budget_program_id(), &data, vec![])
+}
+
+/// Builds the instruction setting the priority fee paid per compute unit,
+/// in micro-lamports.
+#[must_use]
+pub fn set_compute_unit_price_instruction(micro_lamports: u64) -> Instruction {
+    let mut data = vec![SET_COMPUTE_UNIT_PRICE];
+    data.extend_from_slice(&micro_lamports.to_le_bytes());
+    Instruction::new_with_bytes(compute_budget_program_id(), &data, vec![])
+}
+
+fn compute_budget_program_id() -> Pubkey {
+    Pubkey::from_str(COMPUTE_BUDGET_PROGRAM_ID).expect("Invalid compute budget program ID")
+}
+
 impl Default for TransactionBuilder {
     fn default() -> Self {
         Self::new()
@@ -138,11 +203,72 @@ mod tests {
     #[test]
     fn test_add_instruction() {
         // Create a simple instruction
-        let instruction =
-            Instruction::new_with_bytes(solana_sdk::pubkey::Pubkey::new_unique(), &[], vec![]);
+        let instruction = Instruction::new_with_bytes(Pubkey::new_unique(), &[], vec![]);
 
         let builder = TransactionBuilder::new().add_instruction(instruction);
 
         assert_eq!(builder.instructions.len(), 1);
     }
+
+    #[test]
+    fn test_durable_nonce_advances_first() {
+        let authority = Keypair::new();
+        let nonce = DurableNonce {
+            account: Pubkey::new_unique(),
+            authority: authority.pubkey(),
+            nonce: Hash::new_from_array([3; 32]),
+            lamports_per_signature: 5000,
+        };
+        let instruction = Instruction::new_with_bytes(Pubkey::new_unique(), &[], vec![]);
+
+        let transaction = TransactionBuilder::new()
+            .add_instruction(instruction)
+            .with_durable_nonce(&nonce)
+            .build(&[&authority])
+            .unwrap();
+
+        assert_eq!(transaction.message.recent_blockhash, nonce.nonce);
+        let first = &transaction.message.instructions[0];
+        let program = transaction.message.account_keys[first.program_id_index as usize];
+        assert_eq!(program, Pubkey::default());
+        assert_eq!(first.data, 4u32.to_le_bytes());
+        assert!(transaction.is_signed());
+    }
+
+    #[test]
+    fn test_compute_budget_instructions() {
+        let payer = Keypair::new();
+        let instruction = Instruction::new_with_bytes(Pubkey::new_unique(), &[], vec![]);
+        let build = |builder: TransactionBuilder| {
+            builder
+                .add_instruction(instruction.clone())
+                .with_blockhash(Hash::new_from_array([1; 32]))
+                .build(&[&payer])
+                .unwrap()
+        };
+
+        let transaction = build(
+            TransactionBuilder::new()
+                .with_compute_units(150_000)
+                .with_priority(PriorityLevel::High)
+                .with_compute_unit_price(25_000),
+        );
+        let instructions = &transaction.message.instructions;
+        assert_eq!(instructions.len(), 3);
+        let program = transaction.message.account_keys[instructions[0].program_id_index as usize];
+        assert_eq!(program, compute_budget_program_id());
+        assert_eq!(instructions[0].data[0], SET_COMPUTE_UNIT_LIMIT);
+        assert_eq!(instructions[0].data[1..], 150_000u32.to_le_bytes());
+        assert_eq!(instructions[1].data[0], SET_COMPUTE_UNIT_PRICE);
+        assert_eq!(instructions[1].data[1..], 25_000u64.to_le_bytes());
+
+        // Without an explicit price the level's static price is paid
+        let transaction = build(TransactionBuilder::new().with_priority(PriorityLevel::Low));
+        let instructions = &transaction.message.instructions;
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(
+            instructions[0].data[1..],
+            PriorityLevel::Low.default_micro_lamports().to_le_bytes()
+        );
+    }
 }
@@ -0,0 +1,313 @@
+//! Priority fee estimation from recent network fees.
+//!
+//! A static priority fee is outbid as soon as the network congests. The
+//! estimator samples the fees recently paid by transactions writing to the
+//! same accounts and maps each [`PriorityLevel`] to a percentile of them,
+//! optionally taking the higher of that and a third-party fee API.
+
+use super::PriorityLevel;
+use anyhow::{Context, Result};
+use clmm_lp_protocols::prelude::RpcProvider;
+use serde_json::json;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+/// Configuration for priority fee estimation.
+#[derive(Debug, Clone)]
+pub struct PriorityFeeConfig {
+    /// Percentile of recent fees paid at [`PriorityLevel::Low`].
+    pub low_percentile: u8,
+    /// Percentile of recent fees paid at [`PriorityLevel::Medium`].
+    pub medium_percentile: u8,
+    /// Percentile of recent fees paid at [`PriorityLevel::High`].
+    pub high_percentile: u8,
+    /// Percentile of recent fees paid at [`PriorityLevel::Urgent`].
+    pub urgent_percentile: u8,
+    /// Lowest compute unit price paid, in micro-lamports.
+    pub min_micro_lamports: u64,
+    /// Highest compute unit price paid, in micro-lamports.
+    pub max_micro_lamports: u64,
+    /// How long sampled fees are reused, in seconds.
+    pub cache_ttl_secs: u64,
+    /// Endpoint answering Helius-style `getPriorityFeeEstimate` requests,
+    /// consulted alongside the RPC samples.
+    pub fee_api_url: Option<String>,
+}
+
+impl Default for PriorityFeeConfig {
+    fn default() -> Self {
+        Self {
+            low_percentile: 25,
+            medium_percentile: 50,
+            high_percentile: 75,
+            urgent_percentile: 95,
+            min_micro_lamports: 1_000,
+            max_micro_lamports: 5_000_000,
+            cache_ttl_secs: 10,
+            fee_api_url: None,
+        }
+    }
+}
+
+impl PriorityFeeConfig {
+    /// Returns the percentile of recent fees paid at `level`.
+    #[must_use]
+    pub fn percentile(&self, level: PriorityLevel) -> u8 {
+        match level {
+            PriorityLevel::Low => self.low_percentile,
+            PriorityLevel::Medium => self.medium_percentile,
+            PriorityLevel::High => self.high_percentile,
+            PriorityLevel::Urgent => self.urgent_percentile,
+        }
+    }
+}
+
+/// Fees sampled for a set of accounts.
+#[derive(Debug, Clone)]
+struct FeeSample {
+    /// When the fees were sampled.
+    fetched_at: Instant,
+    /// Non-zero fees paid in recent slots, sorted ascending.
+    fees: Vec<u64>,
+}
+
+/// Estimates compute unit prices from recently paid priority fees.
+pub struct PriorityFeeEstimator {
+    /// RPC provider fees are sampled with.
+    provider: Arc<RpcProvider>,
+    /// Configuration.
+    config: PriorityFeeConfig,
+    /// HTTP client for the fee API.
+    client: reqwest::Client,
+    /// Recent samples, by sorted writable accounts.
+    cache: RwLock<HashMap<Vec<Pubkey>, FeeSample>>,
+}
+
+impl PriorityFeeEstimator {
+    /// Creates a new estimator.
+    pub fn new(provider: Arc<RpcProvider>, config: PriorityFeeConfig) -> Self {
+        Self {
+            provider,
+            config,
+            client: reqwest::Client::new(),
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Consults a Helius-style fee API alongside the RPC samples.
+    #[must_use]
+    pub fn with_fee_api(mut self, url: impl Into<String>) -> Self {
+        self.config.fee_api_url = Some(url.into());
+        self
+    }
+
+    /// Gets the configuration.
+    pub fn config(&self) -> &PriorityFeeConfig {
+        &self.config
+    }
+
+    /// Estimates the compute unit price, in micro-lamports, for a
+    /// transaction writing to `accounts` at `level`.
+    ///
+    /// Falls back to the level's static price when no recent fees are
+    /// known. The result is clamped to the configured bounds.
+    pub async fn estimate(&self, level: PriorityLevel, accounts: &[Pubkey]) -> u64 {
+        let sampled = match self.recent_fees(accounts).await {
+            Ok(fees) => fee_percentile(&fees, self.config.percentile(level)),
+            Err(e) => {
+                warn!(error = %e, "Failed to sample recent priority fees");
+                None
+            }
+        };
+
+        let quoted = match &self.config.fee_api_url {
+            Some(url) => match self.fee_api_estimate(url, level, accounts).await {
+                Ok(fee) => fee,
+                Err(e) => {
+                    warn!(error = %e, "Failed to query priority fee API");
+                    None
+                }
+            },
+            None => None,
+        };
+
+        let price = match (sampled, quoted) {
+            (None, None) => level.default_micro_lamports(),
+            (sampled, quoted) => sampled.max(quoted).unwrap_or_default(),
+        };
+        let price = price.clamp(
+            self.config.min_micro_lamports,
+            self.config
+                .max_micro_lamports
+                .max(self.config.min_micro_lamports),
+        );
+
+        debug!(
+            level = ?level,
+            sampled = ?sampled,
+            quoted = ?quoted,
+            micro_lamports = price,
+            "Estimated priority fee"
+        );
+        price
+    }
+
+    /// Gets the non-zero fees recently paid writing to `accounts`, sorted
+    /// ascending, from the cache while fresh.
+    async fn recent_fees(&self, accounts: &[Pubkey]) -> Result<Vec<u64>> {
+        let mut key = accounts.to_vec();
+        key.sort_unstable();
+        key.dedup();
+
+        let ttl = Duration::from_secs(self.config.cache_ttl_secs);
+        if let Some(sample) = self.cache.read().await.get(&key)
+            && sample.fetched_at.elapsed() < ttl
+        {
+            return Ok(sample.fees.clone());
+        }
+
+        let mut fees: Vec<u64> = self
+            .provider
+            .get_recent_prioritization_fees(&key)
+            .await?
+            .into_iter()
+            .map(|(_, fee)| fee)
+            .filter(|fee| *fee > 0)
+            .collect();
+        fees.sort_unstable();
+
+        let mut cache = self.cache.write().await;
+        cache.retain(|_, sample| sample.fetched_at.elapsed() < ttl);
+        cache.insert(
+            key,
+            FeeSample {
+                fetched_at: Instant::now(),
+                fees: fees.clone(),
+            },
+        );
+        Ok(fees)
+    }
+
+    /// Queries the fee API for the price at `level`.
+    async fn fee_api_estimate(
+        &self,
+        url: &str,
+        level: PriorityLevel,
+        accounts: &[Pubkey],
+    ) -> Result<Option<u64>> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getPriorityFeeEstimate",
+            "params": [{
+                "accountKeys": accounts.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                "options": { "includeAllPriorityFeeLevels": true },
+            }],
+        });
+
+        let response: serde_json::Value = self
+            .client
+            .post(url)
+            .json(&request)
+            .timeout(Duration::from_secs(5))
+            .send()
+            .await
+            .context("Fee API request failed")?
+            .error_for_status()
+            .context("Fee API returned an error")?
+            .json()
+            .await
+            .context("Invalid fee API response")?;
+
+        Ok(api_fee_level(&response, level))
+    }
+}
+
+/// Returns the `percentile` of fees sorted ascending, or `None` if empty.
+fn fee_percentile(fees: &[u64], percentile: u8) -> Option<u64> {
+    if fees.is_empty() {
+        return None;
+    }
+    let rank = (fees.len() - 1) * usize::from(percentile.min(100)) / 100;
+    Some(fees[rank])
+}
+
+/// Reads the fee for `level` from a `getPriorityFeeEstimate` response.
+fn api_fee_level(response: &serde_json::Value, level: PriorityLevel) -> Option<u64> {
+    let key = match level {
+        PriorityLevel::Low => "low",
+        PriorityLevel::Medium => "medium",
+        PriorityLevel::High => "high",
+        PriorityLevel::Urgent => "veryHigh",
+    };
+    let fee = response
+        .get("result")?
+        .get("priorityFeeLevels")?
+        .get(key)?
+        .as_f64()?;
+    (fee.is_finite() && fee >= 0.0).then(|| fee.ceil() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_percentiles() {
+        let fees = [100, 200, 300, 400, 500];
+        assert_eq!(fee_percentile(&fees, 0), Some(100));
+        assert_eq!(fee_percentile(&fees, 50), Some(300));
+        assert_eq!(fee_percentile(&fees, 75), Some(400));
+        assert_eq!(fee_percentile(&fees, 100), Some(500));
+        assert_eq!(fee_percentile(&[], 50), None);
+    }
+
+    #[test]
+    fn test_reads_fee_api_levels() {
+        let response = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {
+                "priorityFeeLevels": {
+                    "low": 1200.0,
+                    "medium": 5000.5,
+                    "high": 25000.0,
+                    "veryHigh": 120000.0
+                }
+            }
+        });
+
+        assert_eq!(api_fee_level(&response, PriorityLevel::Low), Some(1200));
+        assert_eq!(api_fee_level(&response, PriorityLevel::Medium), Some(5001));
+        assert_eq!(
+            api_fee_level(&response, PriorityLevel::Urgent),
+            Some(120_000)
+        );
+        assert_eq!(
+            api_fee_level(&json!({ "error": {} }), PriorityLevel::High),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_static_price() {
+        // Nothing listens on the endpoint, so no fees can be sampled
+        let provider = Arc::new(RpcProvider::new(clmm_lp_protocols::prelude::RpcConfig {
+            primary_url: "http://127.0.0.1:1".to_string(),
+            fallback_urls: Vec::new(),
+            max_retries: 0,
+            ..Default::default()
+        }));
+        let estimator = PriorityFeeEstimator::new(provider, PriorityFeeConfig::default());
+
+        let price = estimator
+            .estimate(PriorityLevel::High, &[Pubkey::new_unique()])
+            .await;
+
+        assert_eq!(price, PriorityLevel::High.default_micro_lamports());
+    }
+}
@@ -0,0 +1,51 @@
+//! Finality assessment for confirmed transactions.
+
+use crate::lifecycle::Finality;
+use clmm_lp_protocols::prelude::{CommitmentLevel, SignatureConfirmation};
+
+/// Derives a transaction's final outcome from its latest status.
+///
+/// Returns `None` while the outcome is still open: the transaction is not
+/// finalized yet, or the cluster does not currently know it, which happens
+/// briefly while a fork is being resolved. Callers decide when to give up.
+#[must_use]
+pub fn assess_finality(status: Option<&SignatureConfirmation>) -> Option<Finality> {
+    let status = status?;
+    if status.error.is_some() {
+        return Some(Finality::Dropped);
+    }
+    (status.commitment == CommitmentLevel::Finalized).then_some(Finality::Finalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(commitment: CommitmentLevel, error: Option<&str>) -> SignatureConfirmation {
+        SignatureConfirmation {
+            slot: 100,
+            commitment,
+            error: error.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_assess_finality() {
+        assert_eq!(assess_finality(None), None);
+        assert_eq!(
+            assess_finality(Some(&status(CommitmentLevel::Confirmed, None))),
+            None
+        );
+        assert_eq!(
+            assess_finality(Some(&status(CommitmentLevel::Finalized, None))),
+            Some(Finality::Finalized)
+        );
+        assert_eq!(
+            assess_finality(Some(&status(
+                CommitmentLevel::Finalized,
+                Some("InstructionError")
+            ))),
+            Some(Finality::Dropped)
+        );
+    }
+}
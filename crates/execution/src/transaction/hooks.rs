@@ -0,0 +1,55 @@
+//! Hooks run after a transaction is confirmed.
+
+use super::TransactionResult;
+use async_trait::async_trait;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::Transaction;
+
+/// Receives confirmed transactions, e.g. to refresh the accounts they touched.
+#[async_trait]
+pub trait ConfirmationHook: Send + Sync {
+    /// Called once a transaction reached the configured commitment.
+    ///
+    /// `accounts` are the accounts the transaction could write to.
+    async fn on_confirmed(&self, result: &TransactionResult, accounts: &[Pubkey]);
+}
+
+/// Returns the accounts a transaction may write to, excluding programs.
+#[must_use]
+pub fn writable_accounts(transaction: &Transaction) -> Vec<Pubkey> {
+    let message = &transaction.message;
+    message
+        .account_keys
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| message.is_maybe_writable(*i, None))
+        .map(|(_, key)| *key)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+    use solana_sdk::message::Message;
+
+    #[test]
+    fn test_writable_accounts() {
+        let payer = Pubkey::new_unique();
+        let position = Pubkey::new_unique();
+        let oracle = Pubkey::new_unique();
+        let program = Pubkey::new_unique();
+        let instruction = Instruction::new_with_bytes(
+            program,
+            &[],
+            vec![
+                AccountMeta::new(position, false),
+                AccountMeta::new_readonly(oracle, false),
+            ],
+        );
+        let transaction = Transaction::new_unsigned(Message::new(&[instruction], Some(&payer)));
+
+        let accounts = writable_accounts(&transaction);
+        assert_eq!(accounts, vec![payer, position]);
+    }
+}
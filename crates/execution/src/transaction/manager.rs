@@ -1,12 +1,20 @@
 //! Transaction manager for lifecycle handling.
 
-use super::TransactionResult;
+use super::{
+    ConfirmationHook, PriorityFeeEstimator, PriorityLevel, TransactionBuilder, TransactionResult,
+    assess_finality, writable_accounts,
+};
+use crate::lifecycle::{Finality, LifecycleTracker};
+use crate::shutdown::InFlightTracker;
 use anyhow::Result;
-use clmm_lp_protocols::prelude::RpcProvider;
-use solana_sdk::signature::Signature;
+use async_trait::async_trait;
+use clmm_lp_protocols::prelude::{CommitmentLevel, RpcProvider, TransactionSender};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::{Keypair, Signature};
 use solana_sdk::transaction::Transaction;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
 use tokio::time::sleep;
 use tracing::{debug, error, info, warn};
 
@@ -21,6 +29,11 @@ pub struct TransactionConfig {
     pub confirmation_timeout_secs: u64,
     /// Whether to simulate before sending.
     pub simulate_before_send: bool,
+    /// Commitment level a transaction must reach to count as confirmed.
+    pub commitment: CommitmentLevel,
+    /// How long a confirmed transaction may take to finalize before it is
+    /// treated as dropped, in seconds.
+    pub finality_timeout_secs: u64,
 }
 
 impl Default for TransactionConfig {
@@ -30,6 +43,8 @@ impl Default for TransactionConfig {
             retry_base_delay_ms: 500,
             confirmation_timeout_secs: 60,
             simulate_before_send: true,
+            commitment: CommitmentLevel::Confirmed,
+            finality_timeout_secs: 120,
         }
     }
 }
@@ -40,12 +55,88 @@ pub struct TransactionManager {
     provider: Arc<RpcProvider>,
     /// Configuration.
     config: TransactionConfig,
+    /// Tracks unconfirmed transactions so shutdown can wait for them.
+    in_flight: Option<InFlightTracker>,
+    /// Hooks run after each confirmed transaction.
+    hooks: Vec<Arc<dyn ConfirmationHook>>,
+    /// Prices priority fees from recent network fees.
+    fee_estimator: Option<Arc<PriorityFeeEstimator>>,
 }
 
 impl TransactionManager {
     /// Creates a new transaction manager.
     pub fn new(provider: Arc<RpcProvider>, config: TransactionConfig) -> Self {
-        Self { provider, config }
+        Self {
+            provider,
+            config,
+            in_flight: None,
+            hooks: Vec::new(),
+            fee_estimator: None,
+        }
+    }
+
+    /// Reports unconfirmed transactions to a shutdown tracker.
+    #[must_use]
+    pub fn with_in_flight(mut self, tracker: InFlightTracker) -> Self {
+        self.in_flight = Some(tracker);
+        self
+    }
+
+    /// Adds a hook run after each transaction confirmed by
+    /// [`Self::send_and_confirm`].
+    #[must_use]
+    pub fn with_confirmation_hook(mut self, hook: Arc<dyn ConfirmationHook>) -> Self {
+        self.hooks.push(hook);
+        self
+    }
+
+    /// Prices priority fees of built transactions from recent network fees
+    /// instead of the static per-level prices.
+    #[must_use]
+    pub fn with_fee_estimator(mut self, estimator: Arc<PriorityFeeEstimator>) -> Self {
+        self.fee_estimator = Some(estimator);
+        self
+    }
+
+    /// Returns the compute unit price, in micro-lamports, paid at `priority`
+    /// by a transaction running `instructions`.
+    pub async fn compute_unit_price(
+        &self,
+        priority: PriorityLevel,
+        instructions: &[Instruction],
+    ) -> u64 {
+        let Some(estimator) = &self.fee_estimator else {
+            return priority.default_micro_lamports();
+        };
+        let accounts: Vec<_> = instructions
+            .iter()
+            .flat_map(|ix| ix.accounts.iter())
+            .filter(|meta| meta.is_writable)
+            .map(|meta| meta.pubkey)
+            .collect();
+        estimator.estimate(priority, &accounts).await
+    }
+
+    /// Builds and signs a transaction running `instructions` at `priority`,
+    /// against the latest blockhash.
+    ///
+    /// The first signer pays the fees.
+    pub async fn build_transaction(
+        &self,
+        instructions: Vec<Instruction>,
+        priority: PriorityLevel,
+        signers: &[&Keypair],
+    ) -> Result<Transaction> {
+        let price = self.compute_unit_price(priority, &instructions).await;
+        let blockhash = self.provider.get_latest_blockhash().await?;
+        debug!(priority = ?priority, micro_lamports = price, "Building transaction");
+
+        TransactionBuilder::new()
+            .add_instructions(instructions)
+            .with_priority(priority)
+            .with_compute_unit_price(price)
+            .with_blockhash(blockhash)
+            .build(signers)
     }
 
     /// Sends a transaction with retry logic.
@@ -79,10 +170,8 @@ impl TransactionManager {
     }
 
     /// Tries to send a transaction once.
-    async fn try_send_transaction(&self, _transaction: &Transaction) -> Result<Signature> {
-        // TODO: Implement actual transaction sending
-        // For now, return a placeholder
-        Err(anyhow::anyhow!("Transaction sending not implemented"))
+    async fn try_send_transaction(&self, transaction: &Transaction) -> Result<Signature> {
+        self.provider.send_transaction(transaction).await
     }
 
     /// Waits for transaction confirmation.
@@ -90,14 +179,18 @@ impl TransactionManager {
         let start = Instant::now();
         let timeout = Duration::from_secs(self.config.confirmation_timeout_secs);
 
-        info!(signature = %signature, "Waiting for confirmation");
+        info!(
+            signature = %signature,
+            commitment = self.config.commitment.as_str(),
+            "Waiting for confirmation"
+        );
 
         loop {
             if start.elapsed() > timeout {
                 return Err(anyhow::anyhow!("Confirmation timeout"));
             }
 
-            match self.check_confirmation(signature).await {
+            match self.check_confirmation(signature, start).await {
                 Ok(Some(result)) => {
                     info!(
                         signature = %signature,
@@ -119,27 +212,103 @@ impl TransactionManager {
         }
     }
 
-    /// Checks if a transaction is confirmed.
-    async fn check_confirmation(&self, signature: &Signature) -> Result<Option<TransactionResult>> {
-        let status = self.provider.get_signature_status(signature).await?;
+    /// Checks if a transaction reached the configured commitment level.
+    async fn check_confirmation(
+        &self,
+        signature: &Signature,
+        started: Instant,
+    ) -> Result<Option<TransactionResult>> {
+        let Some(status) = self.provider.get_signature_confirmation(signature).await? else {
+            return Ok(None);
+        };
 
-        match status {
-            Some(err) => {
-                // Transaction failed
-                Err(anyhow::anyhow!("Transaction failed: {:?}", err))
-            }
-            None => {
-                // Check if confirmed by getting slot
-                // For now, assume not confirmed
-                Ok(None)
+        if let Some(err) = status.error {
+            return Err(anyhow::anyhow!("Transaction failed: {}", err));
+        }
+        if status.commitment < self.config.commitment {
+            return Ok(None);
+        }
+
+        Ok(Some(TransactionResult {
+            signature: *signature,
+            slot: status.slot,
+            confirmation_time: started.elapsed(),
+            compute_units: None,
+            fee: 0,
+            commitment: status.commitment,
+        }))
+    }
+
+    /// Waits until a transaction is finalized.
+    ///
+    /// Returns [`Finality::Dropped`] if it fails or does not finalize
+    /// within the finality timeout, which is what happens to transactions
+    /// on an abandoned fork.
+    pub async fn await_finality(&self, signature: &Signature) -> Finality {
+        let start = Instant::now();
+        let timeout = Duration::from_secs(self.config.finality_timeout_secs);
+
+        while start.elapsed() <= timeout {
+            match self.provider.get_signature_confirmation(signature).await {
+                Ok(status) => {
+                    if let Some(finality) = assess_finality(status.as_ref()) {
+                        return finality;
+                    }
+                }
+                Err(e) => warn!(signature = %signature, error = %e, "Finality check failed"),
             }
+            sleep(Duration::from_secs(2)).await;
         }
+
+        warn!(
+            signature = %signature,
+            timeout_secs = self.config.finality_timeout_secs,
+            "Transaction did not finalize in time"
+        );
+        Finality::Dropped
     }
 
-    /// Sends and confirms a transaction.
+    /// Watches a confirmed transaction in the background and records its
+    /// finality on the lifecycle events carrying its signature.
+    ///
+    /// Events are marked pending right away. Nothing is watched when the
+    /// configured commitment is already finalized.
+    pub fn watch_finality(
+        self: &Arc<Self>,
+        signature: Signature,
+        lifecycle: Arc<LifecycleTracker>,
+    ) -> JoinHandle<Finality> {
+        let manager = self.clone();
+        tokio::spawn(async move {
+            if manager.config.commitment == CommitmentLevel::Finalized {
+                lifecycle
+                    .set_finality(&signature, Finality::Finalized)
+                    .await;
+                return Finality::Finalized;
+            }
+
+            lifecycle.set_finality(&signature, Finality::Pending).await;
+            let _in_flight = manager.in_flight.as_ref().map(InFlightTracker::begin);
+            let finality = manager.await_finality(&signature).await;
+            lifecycle.set_finality(&signature, finality).await;
+            finality
+        })
+    }
+
+    /// Sends and confirms a transaction, then runs the confirmation hooks
+    /// with the accounts it wrote to.
     pub async fn send_and_confirm(&self, transaction: &Transaction) -> Result<TransactionResult> {
+        let _in_flight = self.in_flight.as_ref().map(InFlightTracker::begin);
         let signature = self.send_transaction(transaction).await?;
-        self.wait_for_confirmation(&signature).await
+        let result = self.wait_for_confirmation(&signature).await?;
+
+        if !self.hooks.is_empty() {
+            let accounts = writable_accounts(transaction);
+            for hook in &self.hooks {
+                hook.on_confirmed(&result, &accounts).await;
+            }
+        }
+        Ok(result)
     }
 
     /// Simulates a transaction.
@@ -154,6 +323,17 @@ impl TransactionManager {
     }
 }
 
+/// Executors sending through the manager confirm at its commitment level
+/// and run its confirmation hooks.
+#[async_trait]
+impl TransactionSender for TransactionManager {
+    async fn send_and_confirm(&self, transaction: &Transaction) -> Result<Signature> {
+        TransactionManager::send_and_confirm(self, transaction)
+            .await
+            .map(|result| result.signature)
+    }
+}
+
 /// Result of transaction simulation.
 #[derive(Debug, Clone)]
 pub struct SimulationResult {
@@ -166,3 +346,140 @@ pub struct SimulationResult {
     /// Error message if failed.
     pub error: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clmm_lp_protocols::prelude::RpcConfig;
+    use solana_sdk::hash::Hash;
+    use solana_sdk::instruction::AccountMeta;
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::signer::Signer;
+    use std::sync::Mutex;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    /// Records the transactions it is called with.
+    #[derive(Default)]
+    struct RecordingHook {
+        confirmed: Mutex<Vec<(Signature, Vec<Pubkey>)>>,
+    }
+
+    #[async_trait]
+    impl ConfirmationHook for RecordingHook {
+        async fn on_confirmed(&self, result: &TransactionResult, accounts: &[Pubkey]) {
+            self.confirmed
+                .lock()
+                .unwrap()
+                .push((result.signature, accounts.to_vec()));
+        }
+    }
+
+    /// Serves the JSON-RPC calls of a send that lands as `signature` and is
+    /// confirmed right away; returns the endpoint URL.
+    async fn serve_rpc(signature: Signature) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                tokio::spawn(answer_rpc(socket, signature));
+            }
+        });
+        url
+    }
+
+    async fn answer_rpc(mut socket: TcpStream, signature: Signature) {
+        let mut request = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            let Ok(n) = socket.read(&mut buf).await else {
+                return;
+            };
+            if n == 0 {
+                return;
+            }
+            request.extend_from_slice(&buf[..n]);
+
+            // Answer each complete request; the client keeps the connection
+            let text = String::from_utf8_lossy(&request).to_lowercase();
+            let Some(end) = text.find("\r\n\r\n") else {
+                continue;
+            };
+            let length: usize = text
+                .lines()
+                .find_map(|l| l.strip_prefix("content-length: "))
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(0);
+            if request.len() < end + 4 + length {
+                continue;
+            }
+            let body: serde_json::Value =
+                serde_json::from_slice(&request[end + 4..end + 4 + length]).unwrap();
+            request.drain(..end + 4 + length);
+
+            let result = match body["method"].as_str() {
+                Some("sendTransaction") => serde_json::json!(signature.to_string()),
+                Some("getSignatureStatuses") => serde_json::json!({
+                    "context": { "slot": 7 },
+                    "value": [{
+                        "slot": 7,
+                        "confirmations": 1,
+                        "status": { "Ok": null },
+                        "err": null,
+                        "confirmationStatus": "confirmed",
+                    }],
+                }),
+                other => panic!("Unexpected RPC method {:?}", other),
+            };
+            let response =
+                serde_json::json!({ "jsonrpc": "2.0", "result": result, "id": body["id"] })
+                    .to_string();
+            let head = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n",
+                response.len()
+            );
+            if socket.write_all(head.as_bytes()).await.is_err()
+                || socket.write_all(response.as_bytes()).await.is_err()
+            {
+                return;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sent_transactions_run_confirmation_hooks() {
+        let payer = Keypair::new();
+        let position = Pubkey::new_unique();
+        let instruction = Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[],
+            vec![AccountMeta::new(position, false)],
+        );
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[&payer],
+            Hash::default(),
+        );
+
+        let url = serve_rpc(transaction.signatures[0]).await;
+        let provider = Arc::new(RpcProvider::new(RpcConfig {
+            fallback_urls: Vec::new(),
+            max_retries: 0,
+            ..RpcConfig::new(url)
+        }));
+        let hook = Arc::new(RecordingHook::default());
+        let manager = TransactionManager::new(provider, TransactionConfig::default())
+            .with_confirmation_hook(hook.clone());
+
+        // Executors send through the manager as their transaction sender
+        let sender: Arc<dyn TransactionSender> = Arc::new(manager);
+        let signature = sender.send_and_confirm(&transaction).await.unwrap();
+
+        assert_eq!(signature, transaction.signatures[0]);
+        assert_eq!(
+            *hook.confirmed.lock().unwrap(),
+            vec![(signature, vec![payer.pubkey(), position])]
+        );
+    }
+}
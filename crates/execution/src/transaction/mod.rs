@@ -4,12 +4,22 @@
 //! - Transaction building
 //! - Priority fee estimation
 //! - Simulation
-//! - Confirmation tracking
+//! - Confirmation tracking and post-confirmation hooks
+//! - Finality watching
+//! - Durable nonce accounts
 
 mod builder;
+mod fee_estimator;
+mod finality;
+mod hooks;
 mod manager;
+mod nonce;
 mod types;
 
 pub use builder::*;
+pub use fee_estimator::*;
+pub use finality::*;
+pub use hooks::*;
 pub use manager::*;
+pub use nonce::*;
 pub use types::{PriorityLevel, TransactionResult, TransactionStatus};
@@ -0,0 +1,276 @@
+//! Durable nonce accounts.
+//!
+//! A transaction signed against a recent blockhash expires after about a
+//! minute, which is too short when a human or a multisig has to approve it.
+//! Signing against the value stored in a nonce account instead keeps the
+//! transaction valid until the nonce is advanced, which happens when the
+//! transaction lands or when it is advanced explicitly to discard it.
+
+use anyhow::{Context, Result};
+use clmm_lp_protocols::orca::executor::SYSTEM_PROGRAM_ID;
+use clmm_lp_protocols::prelude::RpcProvider;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature};
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::Transaction;
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::info;
+
+/// Size of a nonce account in bytes.
+pub const NONCE_ACCOUNT_LENGTH: usize = 80;
+
+/// Recent blockhashes sysvar, required by the nonce instructions.
+const RECENT_BLOCKHASHES_SYSVAR_ID: &str = "SysvarRecentB1ockHashes11111111111111111111";
+
+/// System instruction tags.
+const CREATE_ACCOUNT: u32 = 0;
+const ADVANCE_NONCE_ACCOUNT: u32 = 4;
+const INITIALIZE_NONCE_ACCOUNT: u32 = 6;
+
+/// Nonce account state tag for an initialized account.
+const NONCE_STATE_INITIALIZED: u32 = 1;
+
+/// Current value of a nonce account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DurableNonce {
+    /// Nonce account address.
+    pub account: Pubkey,
+    /// Authority allowed to advance the nonce.
+    pub authority: Pubkey,
+    /// Stored nonce, used in place of a recent blockhash.
+    pub nonce: Hash,
+    /// Fee per signature recorded with the nonce.
+    pub lamports_per_signature: u64,
+}
+
+impl DurableNonce {
+    /// Parses the data of a nonce account.
+    ///
+    /// # Errors
+    /// Returns an error if the data is not an initialized nonce account.
+    pub fn parse(account: Pubkey, data: &[u8]) -> Result<Self> {
+        if data.len() < NONCE_ACCOUNT_LENGTH {
+            anyhow::bail!("Account data too short for a nonce account");
+        }
+
+        // Versions tag (legacy or current) followed by the state tag
+        let state = u32::from_le_bytes(data[4..8].try_into()?);
+        if state != NONCE_STATE_INITIALIZED {
+            anyhow::bail!("Nonce account {} is not initialized", account);
+        }
+
+        Ok(Self {
+            account,
+            authority: Pubkey::try_from(&data[8..40])?,
+            nonce: Hash::new_from_array(data[40..72].try_into()?),
+            lamports_per_signature: u64::from_le_bytes(data[72..80].try_into()?),
+        })
+    }
+}
+
+/// Builds the instruction that advances a nonce; it must come first in any
+/// transaction signed against the nonce.
+#[must_use]
+pub fn advance_nonce_instruction(nonce_account: &Pubkey, authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: system_program_id(),
+        accounts: vec![
+            AccountMeta::new(*nonce_account, false),
+            AccountMeta::new_readonly(recent_blockhashes_sysvar_id(), false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: ADVANCE_NONCE_ACCOUNT.to_le_bytes().to_vec(),
+    }
+}
+
+/// Builds the instructions that create and initialize a nonce account.
+///
+/// The nonce account must sign the transaction alongside the payer.
+#[must_use]
+pub fn create_nonce_account_instructions(
+    payer: &Pubkey,
+    nonce_account: &Pubkey,
+    authority: &Pubkey,
+    lamports: u64,
+) -> Vec<Instruction> {
+    let system_program = system_program_id();
+
+    let mut create_data = CREATE_ACCOUNT.to_le_bytes().to_vec();
+    create_data.extend_from_slice(&lamports.to_le_bytes());
+    create_data.extend_from_slice(&(NONCE_ACCOUNT_LENGTH as u64).to_le_bytes());
+    create_data.extend_from_slice(system_program.as_ref());
+
+    let mut initialize_data = INITIALIZE_NONCE_ACCOUNT.to_le_bytes().to_vec();
+    initialize_data.extend_from_slice(authority.as_ref());
+
+    vec![
+        Instruction {
+            program_id: system_program,
+            accounts: vec![
+                AccountMeta::new(*payer, true),
+                AccountMeta::new(*nonce_account, true),
+            ],
+            data: create_data,
+        },
+        Instruction {
+            program_id: system_program,
+            accounts: vec![
+                AccountMeta::new(*nonce_account, false),
+                AccountMeta::new_readonly(recent_blockhashes_sysvar_id(), false),
+                AccountMeta::new_readonly(solana_sdk::sysvar::rent::ID, false),
+            ],
+            data: initialize_data,
+        },
+    ]
+}
+
+/// Creates, reads and advances nonce accounts.
+pub struct NonceManager {
+    /// RPC provider.
+    provider: Arc<RpcProvider>,
+}
+
+impl NonceManager {
+    /// Creates a new nonce manager.
+    pub fn new(provider: Arc<RpcProvider>) -> Self {
+        Self { provider }
+    }
+
+    /// Creates a rent-exempt nonce account controlled by `authority`.
+    pub async fn create(
+        &self,
+        payer: &Keypair,
+        nonce_account: &Keypair,
+        authority: &Pubkey,
+    ) -> Result<Signature> {
+        let lamports = self
+            .provider
+            .get_minimum_balance_for_rent_exemption(NONCE_ACCOUNT_LENGTH)
+            .await?;
+
+        info!(
+            nonce_account = %nonce_account.pubkey(),
+            authority = %authority,
+            lamports,
+            "Creating nonce account"
+        );
+
+        let instructions = create_nonce_account_instructions(
+            &payer.pubkey(),
+            &nonce_account.pubkey(),
+            authority,
+            lamports,
+        );
+        let blockhash = self.provider.get_latest_blockhash().await?;
+        let transaction = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&payer.pubkey()),
+            &[payer, nonce_account],
+            blockhash,
+        );
+
+        self.provider
+            .send_and_confirm_transaction(&transaction)
+            .await
+    }
+
+    /// Reads the current nonce.
+    pub async fn fetch(&self, nonce_account: &Pubkey) -> Result<DurableNonce> {
+        let account = self
+            .provider
+            .get_account(nonce_account)
+            .await
+            .context("Failed to fetch nonce account")?;
+        if account.owner != system_program_id() {
+            anyhow::bail!(
+                "Account {} is not owned by the system program",
+                nonce_account
+            );
+        }
+        DurableNonce::parse(*nonce_account, &account.data)
+    }
+
+    /// Advances the nonce, invalidating every transaction signed against
+    /// its current value, e.g. after an approval is rejected.
+    pub async fn advance(
+        &self,
+        nonce_account: &Pubkey,
+        authority: &Keypair,
+        payer: &Keypair,
+    ) -> Result<Signature> {
+        info!(nonce_account = %nonce_account, "Advancing nonce");
+
+        let instruction = advance_nonce_instruction(nonce_account, &authority.pubkey());
+        let blockhash = self.provider.get_latest_blockhash().await?;
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[payer, authority],
+            blockhash,
+        );
+
+        self.provider
+            .send_and_confirm_transaction(&transaction)
+            .await
+    }
+}
+
+fn system_program_id() -> Pubkey {
+    Pubkey::from_str(SYSTEM_PROGRAM_ID).expect("Invalid system program ID")
+}
+
+fn recent_blockhashes_sysvar_id() -> Pubkey {
+    Pubkey::from_str(RECENT_BLOCKHASHES_SYSVAR_ID).expect("Invalid sysvar ID")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_nonce_account() {
+        let account = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let nonce = Hash::new_from_array([7; 32]);
+
+        let mut data = Vec::with_capacity(NONCE_ACCOUNT_LENGTH);
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(&NONCE_STATE_INITIALIZED.to_le_bytes());
+        data.extend_from_slice(authority.as_ref());
+        data.extend_from_slice(nonce.as_ref());
+        data.extend_from_slice(&5000u64.to_le_bytes());
+
+        let parsed = DurableNonce::parse(account, &data).unwrap();
+        assert_eq!(parsed.authority, authority);
+        assert_eq!(parsed.nonce, nonce);
+        assert_eq!(parsed.lamports_per_signature, 5000);
+
+        // Uninitialized accounts carry no nonce
+        data[4..8].copy_from_slice(&0u32.to_le_bytes());
+        assert!(DurableNonce::parse(account, &data).is_err());
+        assert!(DurableNonce::parse(account, &data[..40]).is_err());
+    }
+
+    #[test]
+    fn test_create_nonce_account_instructions() {
+        let payer = Pubkey::new_unique();
+        let nonce_account = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+
+        let instructions =
+            create_nonce_account_instructions(&payer, &nonce_account, &authority, 1_447_680);
+        assert_eq!(instructions.len(), 2);
+
+        let create = &instructions[0];
+        assert_eq!(create.data.len(), 4 + 8 + 8 + 32);
+        assert_eq!(&create.data[4..12], &1_447_680u64.to_le_bytes());
+        assert!(create.accounts.iter().all(|a| a.is_signer));
+
+        let initialize = &instructions[1];
+        assert_eq!(&initialize.data[..4], &6u32.to_le_bytes());
+        assert_eq!(&initialize.data[4..], authority.as_ref());
+    }
+}
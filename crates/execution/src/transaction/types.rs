@@ -1,5 +1,6 @@
 //! Transaction types and enums.
 
+use clmm_lp_protocols::prelude::CommitmentLevel;
 use solana_sdk::signature::Signature;
 use std::time::Duration;
 
@@ -33,8 +34,13 @@ pub struct TransactionResult {
     pub compute_units: Option<u64>,
     /// Fee paid in lamports.
     pub fee: u64,
+    /// Commitment level reached when the result was produced.
+    pub commitment: CommitmentLevel,
 }
 
+/// Compute unit price of the lowest priority level, in micro-lamports.
+const BASE_MICRO_LAMPORTS: u64 = 1_000;
+
 /// Priority fee level.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum PriorityLevel {
@@ -60,4 +66,11 @@ impl PriorityLevel {
             Self::Urgent => 1000,
         }
     }
+
+    /// Returns the static compute unit price in micro-lamports, used when
+    /// recent network fees are unknown.
+    #[must_use]
+    pub fn default_micro_lamports(&self) -> u64 {
+        BASE_MICRO_LAMPORTS * self.price_multiplier()
+    }
 }
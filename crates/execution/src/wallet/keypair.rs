@@ -1,6 +1,7 @@
 //! Wallet implementation for transaction signing.
 
 use anyhow::{Context, Result};
+use clmm_lp_protocols::prelude::RpcProvider;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::{Keypair, Signer};
 use std::fs;
@@ -103,4 +104,28 @@ impl Wallet {
     pub fn keypair(&self) -> &Keypair {
         &self.keypair
     }
+
+    /// Tops the wallet up to `min_lamports` through an airdrop.
+    ///
+    /// Meant for rehearsing on devnet or a local validator; fails on
+    /// clusters without airdrops when the balance is short.
+    ///
+    /// # Returns
+    /// The balance after any airdrop.
+    pub async fn airdrop_if_below(&self, provider: &RpcProvider, min_lamports: u64) -> Result<u64> {
+        let balance = provider.get_balance(&self.pubkey()).await?;
+        if balance >= min_lamports {
+            return Ok(balance);
+        }
+
+        let shortfall = min_lamports - balance;
+        info!(
+            wallet = %self.label,
+            lamports = shortfall,
+            cluster = %provider.cluster(),
+            "Airdropping to wallet"
+        );
+        provider.request_airdrop(&self.pubkey(), shortfall).await?;
+        provider.get_balance(&self.pubkey()).await
+    }
 }
@@ -0,0 +1,63 @@
+//! Range optimizer benchmarks.
+//!
+//! Each run evaluates every candidate width with a full Monte Carlo
+//! simulation, so this measures the optimizer loop end to end.
+//!
+//! Run with `make bench` or `cargo bench -p clmm-lp-optimization`.
+
+use clmm_lp_domain::entities::position::{Position, PositionId};
+use clmm_lp_domain::enums::PositionStatus;
+use clmm_lp_domain::value_objects::amount::Amount;
+use clmm_lp_optimization::objective::MaximizeNetPnL;
+use clmm_lp_optimization::range_optimizer::RangeOptimizer;
+use clmm_lp_simulation::volume::ConstantVolume;
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use primitive_types::U256;
+use rust_decimal_macros::dec;
+use std::hint::black_box;
+use uuid::Uuid;
+
+fn position() -> Position {
+    let zero = Amount::new(U256::zero(), 6);
+    Position {
+        id: PositionId(Uuid::new_v4()),
+        pool_address: "pool".to_string(),
+        owner_address: "owner".to_string(),
+        liquidity_amount: 0,
+        deposited_amount_a: zero,
+        deposited_amount_b: zero,
+        current_amount_a: zero,
+        current_amount_b: zero,
+        unclaimed_fees_a: zero,
+        unclaimed_fees_b: zero,
+        range: None,
+        opened_at: 0,
+        status: PositionStatus::Open,
+    }
+}
+
+fn bench_range_optimizer(c: &mut Criterion) {
+    let mut group = c.benchmark_group("range_optimizer");
+    group.sample_size(10);
+    for iterations in [100, 1_000] {
+        let optimizer = RangeOptimizer::new(iterations, 30, 1.0 / 365.0);
+        group.bench_function(BenchmarkId::new("paths_per_candidate", iterations), |b| {
+            b.iter(|| {
+                optimizer.optimize(
+                    position(),
+                    black_box(dec!(100)),
+                    0.5,
+                    0.0,
+                    ConstantVolume::new(dec!(1000000)),
+                    100_000_000,
+                    dec!(0.003),
+                    MaximizeNetPnL,
+                )
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_range_optimizer);
+criterion_main!(benches);
@@ -0,0 +1,399 @@
+//! Caching for optimization results.
+//!
+//! Scheduled re-optimization usually sees the same data window, or one that
+//! differs only in its newest candles. This module caches results keyed by
+//! pool, data window, objective and parameters, and finds a previous result
+//! to warm-start from when only the tail of the window moved.
+
+use clmm_lp_domain::value_objects::OptimizationResult;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Identifies an optimization run.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OptimizationKey {
+    /// Pool address.
+    pub pool: String,
+    /// Hash of the price window the run was based on.
+    pub window_hash: u64,
+    /// Objective function name.
+    pub objective: String,
+    /// Hash of the optimizer parameters.
+    pub params_hash: u64,
+}
+
+impl OptimizationKey {
+    /// Creates a key from a pool, price window, objective name and parameters.
+    #[must_use]
+    pub fn new<P: Hash>(pool: &str, window: &[Decimal], objective: &str, params: &P) -> Self {
+        Self {
+            pool: pool.to_string(),
+            window_hash: hash_of(&window),
+            objective: objective.to_string(),
+            params_hash: hash_of(params),
+        }
+    }
+
+    /// Returns true if both keys describe the same run apart from the data window.
+    #[must_use]
+    pub fn same_run(&self, other: &Self) -> bool {
+        self.pool == other.pool
+            && self.objective == other.objective
+            && self.params_hash == other.params_hash
+    }
+}
+
+/// Outcome of a cache lookup.
+#[derive(Debug, Clone)]
+pub enum CacheLookup {
+    /// A result for the exact key.
+    Hit(OptimizationResult),
+    /// A result for the same run whose window differs only in the newest candles.
+    WarmStart(OptimizationResult),
+    /// Nothing usable.
+    Miss,
+}
+
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    window: Vec<Decimal>,
+    result: OptimizationResult,
+    sequence: u64,
+}
+
+/// In-memory cache of optimization results.
+#[derive(Debug, Clone)]
+pub struct OptimizationCache {
+    entries: HashMap<OptimizationKey, CacheEntry>,
+    /// Maximum number of cached results.
+    pub max_entries: usize,
+    /// Maximum number of new candles for a window to count as an incremental update.
+    pub max_new_candles: usize,
+    /// Rolling price windows by run, for callers that see one price at a time.
+    windows: HashMap<String, Vec<Decimal>>,
+    sequence: u64,
+}
+
+impl Default for OptimizationCache {
+    fn default() -> Self {
+        Self::new(64)
+    }
+}
+
+impl OptimizationCache {
+    /// Creates a cache holding at most `max_entries` results.
+    #[must_use]
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            max_entries: max_entries.max(1),
+            max_new_candles: 1,
+            windows: HashMap::new(),
+            sequence: 0,
+        }
+    }
+
+    /// Sets how many new candles still allow a warm start.
+    #[must_use]
+    pub fn with_max_new_candles(mut self, max_new_candles: usize) -> Self {
+        self.max_new_candles = max_new_candles;
+        self
+    }
+
+    /// Returns the number of cached results.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the cache is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Looks up a result for the key, falling back to a warm-start candidate.
+    #[must_use]
+    pub fn lookup(&self, key: &OptimizationKey, window: &[Decimal]) -> CacheLookup {
+        if let Some(entry) = self.entries.get(key) {
+            return CacheLookup::Hit(entry.result.clone());
+        }
+
+        self.entries
+            .iter()
+            .filter(|(k, entry)| {
+                k.same_run(key) && is_incremental(&entry.window, window, self.max_new_candles)
+            })
+            .max_by_key(|(_, entry)| entry.sequence)
+            .map_or(CacheLookup::Miss, |(_, entry)| {
+                CacheLookup::WarmStart(entry.result.clone())
+            })
+    }
+
+    /// Stores a result, evicting the oldest entry when full.
+    pub fn insert(&mut self, key: OptimizationKey, window: &[Decimal], result: OptimizationResult) {
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.max_entries {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.sequence)
+                .map(|(k, _)| k.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+
+        self.sequence += 1;
+        self.entries.insert(
+            key,
+            CacheEntry {
+                window: window.to_vec(),
+                result,
+                sequence: self.sequence,
+            },
+        );
+    }
+
+    /// Returns the cached result or runs the optimization.
+    ///
+    /// On a miss `optimize` receives the previous result to warm-start from,
+    /// if the window is an incremental update of a cached one.
+    pub fn get_or_optimize<F>(
+        &mut self,
+        key: OptimizationKey,
+        window: &[Decimal],
+        optimize: F,
+    ) -> OptimizationResult
+    where
+        F: FnOnce(Option<&OptimizationResult>) -> OptimizationResult,
+    {
+        match self.try_get_or_optimize(key, window, |previous| {
+            Ok::<_, std::convert::Infallible>(optimize(previous))
+        }) {
+            Ok(result) => result,
+            Err(never) => match never {},
+        }
+    }
+
+    /// Like [`Self::get_or_optimize`] for an optimization that can fail;
+    /// failures are not cached.
+    ///
+    /// # Errors
+    /// Returns the error of `optimize`.
+    pub fn try_get_or_optimize<F, E>(
+        &mut self,
+        key: OptimizationKey,
+        window: &[Decimal],
+        optimize: F,
+    ) -> Result<OptimizationResult, E>
+    where
+        F: FnOnce(Option<&OptimizationResult>) -> Result<OptimizationResult, E>,
+    {
+        let result = match self.lookup(&key, window) {
+            CacheLookup::Hit(result) => return Ok(result),
+            CacheLookup::WarmStart(previous) => optimize(Some(&previous))?,
+            CacheLookup::Miss => optimize(None)?,
+        };
+
+        self.insert(key, window, result.clone());
+        Ok(result)
+    }
+
+    /// Appends a price to the rolling window of `run` and returns the window.
+    ///
+    /// Callers that only see the current price, such as a scheduled advisor,
+    /// use this as their data window: an unchanged price keeps the window,
+    /// and so the cached result, while a new one rolls it forward by one
+    /// candle for a warm start.
+    pub fn observe(&mut self, run: &str, price: Decimal, max_len: usize) -> Vec<Decimal> {
+        let window = self.windows.entry(run.to_string()).or_default();
+        if window.last() != Some(&price) {
+            window.push(price);
+            let excess = window.len().saturating_sub(max_len.max(1));
+            window.drain(..excess);
+        }
+        window.clone()
+    }
+
+    /// Removes all cached results for a pool.
+    pub fn invalidate_pool(&mut self, pool: &str) {
+        self.entries.retain(|key, _| key.pool != pool);
+    }
+
+    /// Removes all cached results and price windows.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.windows.clear();
+    }
+}
+
+/// Hashes a value with the standard hasher.
+fn hash_of<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Returns true if `new` differs from `old` only in its newest `max_new` candles.
+///
+/// Covers both a rolling window (old candles dropped, new ones appended) and
+/// an in-progress last candle being updated.
+fn is_incremental(old: &[Decimal], new: &[Decimal], max_new: usize) -> bool {
+    let settled = new.len().saturating_sub(max_new);
+    if settled == 0 {
+        return false;
+    }
+
+    (0..=max_new).any(|shift| {
+        old.get(shift..)
+            .and_then(|tail| tail.get(..settled))
+            .is_some_and(|tail| tail == &new[..settled])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clmm_lp_domain::value_objects::price::Price;
+    use clmm_lp_domain::value_objects::price_range::PriceRange;
+
+    fn result(pnl: i64) -> OptimizationResult {
+        OptimizationResult {
+            recommended_range: PriceRange::new(
+                Price::new(Decimal::from(90)),
+                Price::new(Decimal::from(110)),
+            ),
+            expected_pnl: Decimal::from(pnl),
+            expected_fees: Decimal::ZERO,
+            fee_std_dev: Decimal::ZERO,
+            expected_il: Decimal::ZERO,
+            sharpe_ratio: None,
+            sharpe_config: None,
+            explanation: None,
+        }
+    }
+
+    fn window(prices: &[i64]) -> Vec<Decimal> {
+        prices.iter().map(|p| Decimal::from(*p)).collect()
+    }
+
+    #[test]
+    fn test_cache_hit_and_warm_start() {
+        let mut cache = OptimizationCache::new(4);
+        let old = window(&[100, 101, 102, 103]);
+        let key = OptimizationKey::new("pool", &old, "MaximizeNetPnL", &(100usize, 30usize));
+        cache.insert(key.clone(), &old, result(1));
+
+        assert!(
+            matches!(cache.lookup(&key, &old), CacheLookup::Hit(r) if r.expected_pnl == Decimal::ONE)
+        );
+
+        // Rolled forward by one candle
+        let rolled = window(&[101, 102, 103, 104]);
+        let rolled_key =
+            OptimizationKey::new("pool", &rolled, "MaximizeNetPnL", &(100usize, 30usize));
+        assert!(matches!(
+            cache.lookup(&rolled_key, &rolled),
+            CacheLookup::WarmStart(_)
+        ));
+
+        // Different parameters never warm-start
+        let other_params =
+            OptimizationKey::new("pool", &rolled, "MaximizeNetPnL", &(200usize, 30usize));
+        assert!(matches!(
+            cache.lookup(&other_params, &rolled),
+            CacheLookup::Miss
+        ));
+
+        // Older candles changed
+        let rewritten = window(&[100, 99, 102, 104]);
+        let rewritten_key =
+            OptimizationKey::new("pool", &rewritten, "MaximizeNetPnL", &(100usize, 30usize));
+        assert!(matches!(
+            cache.lookup(&rewritten_key, &rewritten),
+            CacheLookup::Miss
+        ));
+    }
+
+    #[test]
+    fn test_get_or_optimize_passes_warm_start() {
+        let mut cache = OptimizationCache::new(4);
+        let first = window(&[100, 101, 102]);
+        let key = OptimizationKey::new("pool", &first, "MaximizeFees", &1u8);
+
+        let r = cache.get_or_optimize(key.clone(), &first, |prev| {
+            assert!(prev.is_none());
+            result(1)
+        });
+        assert_eq!(r.expected_pnl, Decimal::ONE);
+
+        // Cached: the closure is not called again
+        let r = cache.get_or_optimize(key, &first, |_| unreachable!());
+        assert_eq!(r.expected_pnl, Decimal::ONE);
+
+        let updated = window(&[100, 101, 105]);
+        let key = OptimizationKey::new("pool", &updated, "MaximizeFees", &1u8);
+        let r = cache.get_or_optimize(key, &updated, |prev| {
+            assert_eq!(prev.map(|p| p.expected_pnl), Some(Decimal::ONE));
+            result(2)
+        });
+        assert_eq!(r.expected_pnl, Decimal::TWO);
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_observed_windows_roll_forward() {
+        let mut cache = OptimizationCache::new(4);
+        let first = cache.observe("pool", Decimal::from(100), 3);
+        let key = OptimizationKey::new("pool", &first, "o", &0u8);
+        cache
+            .try_get_or_optimize(key, &first, |_| Ok::<_, ()>(result(1)))
+            .unwrap();
+
+        // Same price: same window, cached result
+        let same = cache.observe("pool", Decimal::from(100), 3);
+        assert_eq!(same, first);
+
+        let rolled = cache.observe("pool", Decimal::from(101), 3);
+        let key = OptimizationKey::new("pool", &rolled, "o", &0u8);
+        assert!(matches!(
+            cache.lookup(&key, &rolled),
+            CacheLookup::WarmStart(_)
+        ));
+
+        // Failures are not cached
+        assert!(
+            cache
+                .try_get_or_optimize(key.clone(), &rolled, |_| Err(()))
+                .is_err()
+        );
+        assert!(!matches!(cache.lookup(&key, &rolled), CacheLookup::Hit(_)));
+
+        for price in [102, 103, 104] {
+            cache.observe("pool", Decimal::from(price), 3);
+        }
+        assert_eq!(
+            cache.observe("pool", Decimal::from(104), 3),
+            window(&[102, 103, 104])
+        );
+    }
+
+    #[test]
+    fn test_eviction_and_invalidation() {
+        let mut cache = OptimizationCache::new(2);
+        for (i, pool) in ["a", "b", "c"].iter().enumerate() {
+            let w = window(&[i as i64]);
+            cache.insert(OptimizationKey::new(pool, &w, "o", &0u8), &w, result(0));
+        }
+        assert_eq!(cache.len(), 2);
+
+        let w = window(&[0]);
+        let evicted = OptimizationKey::new("a", &w, "o", &0u8);
+        assert!(matches!(cache.lookup(&evicted, &w), CacheLookup::Miss));
+
+        cache.invalidate_pool("b");
+        assert_eq!(cache.len(), 1);
+    }
+}
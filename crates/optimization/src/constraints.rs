@@ -3,9 +3,17 @@
 //! This module defines constraints that limit the search space
 //! during optimization, ensuring valid and practical solutions.
 
+use clmm_lp_domain::math::price_tick::{price_to_tick, tick_to_price};
+use clmm_lp_domain::value_objects::price::Price;
+use clmm_lp_domain::value_objects::price_range::PriceRange;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::FromPrimitive;
 
+/// Lowest initializable tick.
+const MIN_TICK: i32 = -443_636;
+/// Highest initializable tick.
+const MAX_TICK: i32 = 443_636;
+
 /// Constraints for position optimization.
 #[derive(Debug, Clone)]
 pub struct PositionConstraints {
@@ -197,6 +205,144 @@ impl RebalanceConstraints {
     }
 }
 
+/// Pool-level constraints imposed by the fee tier and tick spacing.
+///
+/// Ranges can only start and end on initializable ticks (multiples of the
+/// tick spacing), so candidate ranges are snapped outward to the nearest
+/// valid ticks and widened to a minimum number of tick spacings.
+///
+/// Ticks index the raw on-chain price (token B base units per token A base
+/// unit), so decimal-adjusted prices are scaled by the token decimals before
+/// snapping.
+#[derive(Debug, Clone)]
+pub struct PoolConstraints {
+    /// Tick spacing of the pool.
+    pub tick_spacing: u16,
+    /// Minimum range width in tick spacings.
+    pub min_width_spacings: u32,
+    /// Token B decimals minus token A decimals.
+    pub decimals_offset: i32,
+}
+
+impl PoolConstraints {
+    /// Creates pool constraints for a known tick spacing.
+    #[must_use]
+    pub fn new(tick_spacing: u16) -> Self {
+        Self {
+            tick_spacing: tick_spacing.max(1),
+            min_width_spacings: 1,
+            decimals_offset: 0,
+        }
+    }
+
+    /// Creates pool constraints using the standard tick spacing for a fee tier.
+    ///
+    /// Tiers follow the Orca Whirlpool defaults: 0.01% → 1, 0.05% → 8,
+    /// 0.30% → 64 and 1% → 128.
+    #[must_use]
+    pub fn for_fee_tier(fee_rate: Decimal) -> Self {
+        let tick_spacing = if fee_rate <= Decimal::new(1, 4) {
+            1
+        } else if fee_rate <= Decimal::new(5, 4) {
+            8
+        } else if fee_rate <= Decimal::new(3, 3) {
+            64
+        } else {
+            128
+        };
+        Self::new(tick_spacing)
+    }
+
+    /// Sets the minimum range width in tick spacings.
+    #[must_use]
+    pub fn with_min_width_spacings(mut self, spacings: u32) -> Self {
+        self.min_width_spacings = spacings.max(1);
+        self
+    }
+
+    /// Sets the token decimals, so ranges given in decimal-adjusted prices
+    /// snap to the pool's raw-price ticks.
+    #[must_use]
+    pub fn with_token_decimals(mut self, decimals_a: u8, decimals_b: u8) -> Self {
+        self.decimals_offset = i32::from(decimals_b) - i32::from(decimals_a);
+        self
+    }
+
+    /// Returns the factor converting a decimal-adjusted price to a raw price.
+    fn raw_price_scale(&self) -> Decimal {
+        let scale = Decimal::from(10u64.pow(self.decimals_offset.unsigned_abs()));
+        if self.decimals_offset >= 0 {
+            scale
+        } else {
+            Decimal::ONE / scale
+        }
+    }
+
+    /// Returns the minimum viable range width as a fraction of price.
+    #[must_use]
+    pub fn min_width_pct(&self) -> Decimal {
+        let ticks = i64::from(self.tick_spacing) * i64::from(self.min_width_spacings);
+        let width = 1.0001_f64.powf(ticks as f64) - 1.0;
+        Decimal::from_f64(width).unwrap_or(Decimal::ZERO)
+    }
+
+    /// Checks if a range width is at least the minimum viable width.
+    #[must_use]
+    pub fn is_viable_width(&self, width: Decimal) -> bool {
+        width >= self.min_width_pct()
+    }
+
+    /// Snaps a decimal-adjusted price range outward to initializable ticks.
+    ///
+    /// # Returns
+    /// The lower and upper tick, aligned to the tick spacing and at least
+    /// `min_width_spacings` apart.
+    pub fn snap_ticks(&self, range: &PriceRange) -> Result<(i32, i32), &'static str> {
+        let spacing = i32::from(self.tick_spacing);
+        let min_aligned = MIN_TICK.div_euclid(spacing) * spacing + spacing;
+        let max_aligned = MAX_TICK.div_euclid(spacing) * spacing;
+
+        let scale = self.raw_price_scale();
+        let mut lower =
+            price_to_tick(range.lower_price.value * scale)?.div_euclid(spacing) * spacing;
+        let upper_raw = price_to_tick(range.upper_price.value * scale)?;
+        let mut upper = upper_raw.div_euclid(spacing) * spacing;
+        if upper < upper_raw {
+            upper += spacing;
+        }
+
+        let min_width = i32::try_from(self.min_width_spacings)
+            .map_err(|_| "Minimum width too large")?
+            .saturating_mul(spacing);
+        let deficit = min_width - (upper - lower);
+        if deficit > 0 {
+            // Widen evenly, giving the odd spacing to the upper side
+            let spacings = deficit / spacing + i32::from(deficit % spacing != 0);
+            lower -= (spacings / 2) * spacing;
+            upper += (spacings - spacings / 2) * spacing;
+        }
+
+        lower = lower.max(min_aligned);
+        upper = upper.min(max_aligned);
+        if lower >= upper {
+            return Err("Range collapses after snapping to tick spacing");
+        }
+
+        Ok((lower, upper))
+    }
+
+    /// Snaps a price range outward to initializable ticks and returns the
+    /// decimal-adjusted prices.
+    pub fn snap(&self, range: &PriceRange) -> Result<PriceRange, &'static str> {
+        let (lower, upper) = self.snap_ticks(range)?;
+        let scale = self.raw_price_scale();
+        Ok(PriceRange::new(
+            Price::new(tick_to_price(lower)? / scale),
+            Price::new(tick_to_price(upper)? / scale),
+        ))
+    }
+}
+
 /// Combined constraints for full optimization.
 #[derive(Debug, Clone, Default)]
 pub struct OptimizationConstraints {
@@ -204,6 +350,8 @@ pub struct OptimizationConstraints {
     pub position: PositionConstraints,
     /// Rebalance constraints.
     pub rebalance: RebalanceConstraints,
+    /// Pool constraints, if the target pool is known.
+    pub pool: Option<PoolConstraints>,
 }
 
 impl OptimizationConstraints {
@@ -226,6 +374,23 @@ impl OptimizationConstraints {
         self.rebalance = constraints;
         self
     }
+
+    /// Sets pool constraints.
+    #[must_use]
+    pub fn with_pool(mut self, constraints: PoolConstraints) -> Self {
+        self.pool = Some(constraints);
+        self
+    }
+
+    /// Checks if a range width satisfies both position and pool constraints.
+    #[must_use]
+    pub fn is_valid_range_width(&self, width: Decimal) -> bool {
+        self.position.is_valid_range_width(width)
+            && self
+                .pool
+                .as_ref()
+                .is_none_or(|pool| pool.is_viable_width(width))
+    }
 }
 
 #[cfg(test)]
@@ -272,6 +437,83 @@ mod tests {
         assert!(!constraints.is_valid_price_threshold(Decimal::from_f64(0.005).unwrap()));
     }
 
+    #[test]
+    fn test_pool_constraints_for_fee_tier() {
+        assert_eq!(
+            PoolConstraints::for_fee_tier(Decimal::new(1, 4)).tick_spacing,
+            1
+        );
+        assert_eq!(
+            PoolConstraints::for_fee_tier(Decimal::new(5, 4)).tick_spacing,
+            8
+        );
+        assert_eq!(
+            PoolConstraints::for_fee_tier(Decimal::new(3, 3)).tick_spacing,
+            64
+        );
+        assert_eq!(
+            PoolConstraints::for_fee_tier(Decimal::new(1, 2)).tick_spacing,
+            128
+        );
+    }
+
+    #[test]
+    fn test_pool_constraints_snap_outward() {
+        let pool = PoolConstraints::new(64);
+        let range = PriceRange::new(
+            Price::new(tick_to_price(-100).unwrap()),
+            Price::new(tick_to_price(100).unwrap()),
+        );
+
+        let (lower, upper) = pool.snap_ticks(&range).unwrap();
+        assert_eq!((lower, upper), (-128, 128));
+
+        let snapped = pool.snap(&range).unwrap();
+        assert!(snapped.lower_price.value <= range.lower_price.value);
+        assert!(snapped.upper_price.value >= range.upper_price.value);
+    }
+
+    #[test]
+    fn test_pool_constraints_snap_in_raw_price_space() {
+        // SOL (9 decimals) / USDC (6 decimals): a UI price of 150 is 0.15 raw
+        let pool = PoolConstraints::new(64).with_token_decimals(9, 6);
+        let range = PriceRange::new(
+            Price::new(Decimal::from(140)),
+            Price::new(Decimal::from(160)),
+        );
+
+        let (lower, upper) = pool.snap_ticks(&range).unwrap();
+        assert_eq!(
+            lower,
+            price_to_tick(Decimal::new(14, 2)).unwrap().div_euclid(64) * 64
+        );
+        assert!(upper >= price_to_tick(Decimal::new(16, 2)).unwrap());
+        assert_eq!(upper % 64, 0);
+
+        let snapped = pool.snap(&range).unwrap();
+        assert!(snapped.lower_price.value <= range.lower_price.value);
+        assert!(snapped.lower_price.value > Decimal::from(139));
+        assert!(snapped.upper_price.value >= range.upper_price.value);
+        assert!(snapped.upper_price.value < Decimal::from(161));
+    }
+
+    #[test]
+    fn test_pool_constraints_min_width() {
+        let pool = PoolConstraints::new(64).with_min_width_spacings(4);
+        let range = PriceRange::new(
+            Price::new(tick_to_price(-10).unwrap()),
+            Price::new(tick_to_price(10).unwrap()),
+        );
+
+        // Snaps to [-64, 64] then widens to four spacings
+        let (lower, upper) = pool.snap_ticks(&range).unwrap();
+        assert_eq!((lower, upper), (-128, 128));
+
+        let constraints = OptimizationConstraints::new().with_pool(pool);
+        assert!(!constraints.is_valid_range_width(Decimal::from_f64(0.02).unwrap()));
+        assert!(constraints.is_valid_range_width(Decimal::from_f64(0.05).unwrap()));
+    }
+
     #[test]
     fn test_constraints_builder() {
         let constraints = PositionConstraints::new()
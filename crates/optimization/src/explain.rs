@@ -0,0 +1,297 @@
+//! Plain-language explanations of optimization results.
+//!
+//! A recommended range is the outcome of a trade-off: wider ranges stay
+//! active through larger moves but dilute fees, narrower ones earn more per
+//! dollar while in range but leave it sooner and rebalance more. The
+//! explanation names the inputs that set that trade-off, the risk that
+//! dominates the result, and how the range moved since the last run.
+
+use clmm_lp_domain::value_objects::optimization_result::{Explanation, OptimizationResult};
+use clmm_lp_domain::value_objects::price_range::PriceRange;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+/// Fee standard deviation, as a fraction of expected fees, above which fee
+/// income is called out as uncertain.
+const UNCERTAIN_FEES_RATIO: f64 = 0.5;
+
+/// Market inputs an optimization result is explained against.
+#[derive(Debug, Clone)]
+pub struct ExplanationInputs {
+    /// Price the range was centred on.
+    pub current_price: Decimal,
+    /// Annualized volatility used by the simulation.
+    pub volatility: f64,
+    /// Simulated holding period in years.
+    pub horizon_years: f64,
+    /// Pool fee rate (e.g., 0.003 for 0.3%).
+    pub fee_rate: Decimal,
+    /// Capital the expected PnL, fees and IL are measured on.
+    pub capital: Decimal,
+    /// Cost of one rebalance transaction in USD.
+    pub tx_cost: Option<Decimal>,
+    /// Range recommended by the previous run.
+    pub previous: Option<PriceRange>,
+}
+
+impl ExplanationInputs {
+    /// Creates inputs without a transaction cost or previous recommendation.
+    #[must_use]
+    pub fn new(
+        current_price: Decimal,
+        volatility: f64,
+        horizon_years: f64,
+        fee_rate: Decimal,
+        capital: Decimal,
+    ) -> Self {
+        Self {
+            current_price,
+            volatility,
+            horizon_years,
+            fee_rate,
+            capital,
+            tx_cost: None,
+            previous: None,
+        }
+    }
+
+    /// Sets the cost of one rebalance transaction.
+    #[must_use]
+    pub fn with_tx_cost(mut self, tx_cost: Decimal) -> Self {
+        self.tx_cost = Some(tx_cost);
+        self
+    }
+
+    /// Sets the previous recommendation to compare against.
+    #[must_use]
+    pub fn with_previous(mut self, previous: PriceRange) -> Self {
+        self.previous = Some(previous);
+        self
+    }
+}
+
+/// Explains why `result` recommends its range under `inputs`.
+#[must_use]
+pub fn explain(result: &OptimizationResult, inputs: &ExplanationInputs) -> Explanation {
+    let range = &result.recommended_range;
+    let price = inputs.current_price.to_f64().unwrap_or_default();
+    let half_width = log_half_width(range, price);
+    let expected_move = inputs.volatility * inputs.horizon_years.max(0.0).sqrt();
+    let moves_covered = if expected_move > 0.0 {
+        half_width / expected_move
+    } else {
+        f64::INFINITY
+    };
+    let horizon_days = inputs.horizon_years * 365.0;
+
+    let capital = inputs.capital.to_f64().unwrap_or_default();
+    let fees = result.expected_fees.to_f64().unwrap_or_default();
+    let il = result.expected_il.to_f64().unwrap_or_default().abs();
+    let fee_std_dev = result.fee_std_dev.to_f64().unwrap_or_default();
+    let pct_of_capital = |value: f64| {
+        if capital > 0.0 {
+            value / capital * 100.0
+        } else {
+            0.0
+        }
+    };
+    let fee_apr = if inputs.horizon_years > 0.0 {
+        pct_of_capital(fees) / inputs.horizon_years
+    } else {
+        0.0
+    };
+
+    let mut drivers = vec![
+        format!(
+            "Volatility of {:.0}% a year implies a typical move of ±{:.1}% over {:.0} days; \
+             the ±{:.1}% range covers {:.1} of those moves",
+            inputs.volatility * 100.0,
+            expected_move * 100.0,
+            horizon_days,
+            half_width * 100.0,
+            moves_covered
+        ),
+        format!(
+            "Fees project to {:.1}% APR at a {}% fee tier, against {:.2}% expected \
+             impermanent loss over the period",
+            fee_apr,
+            (inputs.fee_rate * Decimal::ONE_HUNDRED).normalize(),
+            pct_of_capital(il)
+        ),
+    ];
+    if let Some(tx_cost) = inputs.tx_cost {
+        let tx_cost = tx_cost.to_f64().unwrap_or_default();
+        let share_of_fees = if fees > 0.0 {
+            format!("{:.1}% of the expected fees", tx_cost / fees * 100.0)
+        } else {
+            "more than the expected fees".to_string()
+        };
+        drivers.push(format!(
+            "Each rebalance costs ${:.2}, {}; narrower ranges pay it more often",
+            tx_cost, share_of_fees
+        ));
+    }
+
+    let main_risk = if il > fees {
+        format!(
+            "impermanent loss (${:.2}) is expected to exceed fees (${:.2})",
+            il, fees
+        )
+    } else if moves_covered < 1.0 {
+        format!(
+            "the price is likely to leave the range: it covers {:.1} of a typical move",
+            moves_covered
+        )
+    } else if fees > 0.0 && fee_std_dev / fees > UNCERTAIN_FEES_RATIO {
+        format!(
+            "fee income is uncertain, varying by ±{:.0}% across simulated paths",
+            fee_std_dev / fees * 100.0
+        )
+    } else {
+        format!(
+            "a move beyond ±{:.1}% stops fees and locks in impermanent loss",
+            half_width * 100.0
+        )
+    };
+
+    Explanation {
+        drivers,
+        main_risk,
+        comparison: inputs
+            .previous
+            .as_ref()
+            .map(|previous| compare(previous, range, price)),
+    }
+}
+
+/// Half-width of `range` around `price` in log terms, the mean of the
+/// distances to either bound.
+fn log_half_width(range: &PriceRange, price: f64) -> f64 {
+    let lower = range.lower_price.value.to_f64().unwrap_or_default();
+    let upper = range.upper_price.value.to_f64().unwrap_or_default();
+    if price <= 0.0 || lower <= 0.0 || upper <= 0.0 {
+        return 0.0;
+    }
+    ((upper / price).ln() + (price / lower).ln()) / 2.0
+}
+
+/// Describes how `current` differs from `previous` in width and centre.
+fn compare(previous: &PriceRange, current: &PriceRange, price: f64) -> String {
+    if previous == current {
+        return "Unchanged from the previous recommendation".to_string();
+    }
+    let width = |range: &PriceRange| {
+        let mid = range.midpoint().value.to_f64().unwrap_or_default();
+        log_half_width(range, if mid > 0.0 { mid } else { price }) * 100.0
+    };
+    let (old_width, new_width) = (width(previous), width(current));
+    let old_mid = previous.midpoint().value.to_f64().unwrap_or_default();
+    let new_mid = current.midpoint().value.to_f64().unwrap_or_default();
+    let shift = if old_mid > 0.0 {
+        (new_mid / old_mid - 1.0) * 100.0
+    } else {
+        0.0
+    };
+
+    let shape = if new_width < old_width {
+        "Narrower than"
+    } else if new_width > old_width {
+        "Wider than"
+    } else {
+        "As wide as"
+    };
+    format!(
+        "{} the previous recommendation (±{:.1}% → ±{:.1}%), centred {:.1}% {}",
+        shape,
+        old_width,
+        new_width,
+        shift.abs(),
+        if shift >= 0.0 { "higher" } else { "lower" }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clmm_lp_domain::value_objects::price::Price;
+    use rust_decimal_macros::dec;
+
+    fn range(lower: Decimal, upper: Decimal) -> PriceRange {
+        PriceRange::new(Price::new(lower), Price::new(upper))
+    }
+
+    fn result(fees: Decimal, il: Decimal, fee_std_dev: Decimal) -> OptimizationResult {
+        OptimizationResult {
+            recommended_range: range(dec!(90), dec!(110)),
+            expected_pnl: fees + il,
+            expected_fees: fees,
+            fee_std_dev,
+            expected_il: il,
+            sharpe_ratio: None,
+            sharpe_config: None,
+            explanation: None,
+        }
+    }
+
+    fn inputs() -> ExplanationInputs {
+        ExplanationInputs::new(dec!(100), 0.5, 30.0 / 365.0, dec!(0.003), dec!(1000))
+    }
+
+    #[test]
+    fn test_drivers_cover_volatility_fees_and_tx_cost() {
+        let explanation = explain(
+            &result(dec!(20), dec!(-5), dec!(2)),
+            &inputs().with_tx_cost(dec!(1)),
+        );
+
+        assert_eq!(explanation.drivers.len(), 3);
+        assert!(explanation.drivers[0].contains("Volatility of 50%"));
+        // 20 on 1000 over 30 days
+        assert!(explanation.drivers[1].contains("24.3% APR at a 0.3% fee tier"));
+        assert!(explanation.drivers[2].contains("5.0% of the expected fees"));
+        // ±10% covers about 0.7 of a 14% typical move
+        assert!(
+            explanation
+                .main_risk
+                .starts_with("the price is likely to leave")
+        );
+        assert_eq!(explanation.comparison, None);
+        assert_eq!(explanation.lines().len(), 4);
+    }
+
+    #[test]
+    fn test_main_risk_ranks_losses_first() {
+        let calm = ExplanationInputs {
+            volatility: 0.1,
+            ..inputs()
+        };
+        let losing = explain(&result(dec!(5), dec!(-8), dec!(0)), &calm);
+        assert!(losing.main_risk.starts_with("impermanent loss"));
+
+        let noisy = explain(&result(dec!(10), dec!(-1), dec!(8)), &calm);
+        assert!(noisy.main_risk.starts_with("fee income is uncertain"));
+
+        let steady = explain(&result(dec!(10), dec!(-1), dec!(1)), &calm);
+        assert!(steady.main_risk.starts_with("a move beyond"));
+    }
+
+    #[test]
+    fn test_compares_with_previous_recommendation() {
+        let explanation = explain(
+            &result(dec!(20), dec!(-5), dec!(2)),
+            &inputs().with_previous(range(dec!(85), dec!(115))),
+        );
+        let comparison = explanation.comparison.unwrap();
+        assert!(comparison.starts_with("Narrower than the previous recommendation"));
+        assert!(comparison.ends_with("centred 0.0% higher"));
+
+        let same = explain(
+            &result(dec!(20), dec!(-5), dec!(2)),
+            &inputs().with_previous(range(dec!(90), dec!(110))),
+        );
+        assert_eq!(
+            same.comparison.as_deref(),
+            Some("Unchanged from the previous recommendation")
+        );
+    }
+}
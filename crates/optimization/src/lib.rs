@@ -3,13 +3,32 @@
 /// Prelude module for convenient imports.
 pub mod prelude;
 
+/// Optimization result caching.
+pub mod cache;
 /// Optimization constraints.
 pub mod constraints;
+/// Plain-language explanations of results.
+pub mod explain;
 /// Optimization objectives.
 pub mod objective;
 /// General optimizer logic.
 pub mod optimizer;
 /// Parameter optimization logic.
 pub mod parameter_optimizer;
+/// Pool scoring for discovery and allocation.
+pub mod pool_score;
+/// Progress reporting and cancellation.
+pub mod progress;
 /// Range optimization logic.
 pub mod range_optimizer;
+/// Registry of built-in and config-defined objectives.
+pub mod registry;
+/// Sensitivity analysis of recommendations.
+pub mod sensitivity;
+/// Kelly-criterion capital sizing.
+pub mod sizing;
+/// Venue comparison across pools of a pair.
+pub mod venue;
+/// WebAssembly bindings for the range optimizer.
+#[cfg(feature = "wasm")]
+pub mod wasm;
@@ -2,6 +2,7 @@ use clmm_lp_domain::value_objects::simulation_result::SimulationResult;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::FromPrimitive;
 use std::cmp::Ordering;
+use std::sync::Arc;
 
 /// Trait for objective functions.
 pub trait ObjectiveFunction {
@@ -19,6 +20,20 @@ pub trait ObjectiveFunction {
     fn name(&self) -> &'static str;
 }
 
+impl<T: ObjectiveFunction + ?Sized> ObjectiveFunction for Arc<T> {
+    fn evaluate(&self, result: &SimulationResult) -> Decimal {
+        (**self).evaluate(result)
+    }
+
+    fn compare(&self, a: &SimulationResult, b: &SimulationResult) -> Ordering {
+        (**self).compare(a, b)
+    }
+
+    fn name(&self) -> &'static str {
+        (**self).name()
+    }
+}
+
 /// Objective function to maximize Net PnL.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct MaximizeNetPnL;
@@ -5,6 +5,9 @@
 
 use crate::constraints::OptimizationConstraints;
 use crate::objective::ObjectiveFunction;
+use clmm_lp_domain::value_objects::price_range::PriceRange;
+use clmm_lp_simulation::history::HistoryMode;
+use clmm_lp_simulation::state::SimulationConfig;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::FromPrimitive;
 use std::cmp::Ordering;
@@ -110,7 +113,7 @@ impl GridSearchOptimizer {
         self.range_widths
             .iter()
             .copied()
-            .filter(|w| self.constraints.position.is_valid_range_width(*w))
+            .filter(|w| self.constraints.is_valid_range_width(*w))
             .collect()
     }
 
@@ -149,6 +152,9 @@ pub struct OptimizationConfig {
     pub tx_cost: Decimal,
 }
 
+/// Memory the histories of one optimization run may retain, in bytes.
+pub const HISTORY_BUDGET_BYTES: usize = 256 * 1024 * 1024;
+
 impl Default for OptimizationConfig {
     fn default() -> Self {
         Self {
@@ -206,6 +212,28 @@ impl OptimizationConfig {
         self.fee_rate = fee_rate;
         self
     }
+
+    /// Returns the most detailed history mode whose histories for all
+    /// iterations fit in [`HISTORY_BUDGET_BYTES`].
+    #[must_use]
+    pub fn history_mode(&self) -> HistoryMode {
+        HistoryMode::for_budget(
+            self.mc_iterations,
+            self.simulation_steps,
+            HISTORY_BUDGET_BYTES,
+        )
+    }
+
+    /// Builds the simulation config for one candidate range.
+    #[must_use]
+    pub fn simulation_config(&self, capital: Decimal, range: PriceRange) -> SimulationConfig {
+        SimulationConfig::new(capital, range)
+            .with_fee_rate(self.fee_rate)
+            .with_pool_liquidity(self.pool_liquidity)
+            .with_rebalance_cost(self.tx_cost)
+            .with_steps(self.simulation_steps)
+            .with_history(self.history_mode())
+    }
 }
 
 /// Trait for optimization algorithms.
@@ -317,7 +345,7 @@ impl Optimizer for AnalyticalOptimizer {
         let mut candidates: Vec<CandidateResult> = self
             .range_widths
             .iter()
-            .filter(|w| self.constraints.position.is_valid_range_width(**w))
+            .filter(|w| self.constraints.is_valid_range_width(**w))
             .map(|&width| {
                 let time_in_range = self.estimate_time_in_range(width, config.volatility);
                 let fees = self.estimate_fees(width, config, time_in_range);
@@ -351,6 +379,7 @@ impl Optimizer for AnalyticalOptimizer {
 mod tests {
     use super::*;
     use crate::objective::MaximizeNetPnL;
+    use clmm_lp_domain::value_objects::price::Price;
 
     #[test]
     fn test_grid_search_optimizer_creation() {
@@ -457,4 +486,27 @@ mod tests {
         assert_eq!(config.volatility, 0.4);
         assert_eq!(config.current_price, Decimal::from(150));
     }
+
+    #[test]
+    fn test_history_mode_shrinks_with_run_size() {
+        let small = OptimizationConfig::new();
+        assert_eq!(small.history_mode(), HistoryMode::Full);
+
+        let large = OptimizationConfig::new()
+            .with_iterations(50_000)
+            .with_steps(100);
+        assert_eq!(large.history_mode(), HistoryMode::Compact);
+
+        let huge = large.with_steps(10_000);
+        assert_eq!(huge.history_mode(), HistoryMode::SummaryOnly);
+
+        let range = PriceRange::new(
+            Price::new(Decimal::from(90)),
+            Price::new(Decimal::from(110)),
+        );
+        let sim = huge.simulation_config(Decimal::from(1000), range);
+        assert_eq!(sim.history, HistoryMode::SummaryOnly);
+        assert_eq!(sim.steps, 10_000);
+        assert_eq!(sim.rebalance_cost, huge.tx_cost);
+    }
 }
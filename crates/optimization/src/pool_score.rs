@@ -0,0 +1,323 @@
+//! Pool scoring for discovery and allocation.
+//!
+//! Each pool is rated on five components, each normalized to `[0, 1]` where
+//! higher is better: fee APR, day-to-day volume consistency, TVL depth, price
+//! volatility (calmer scores higher) and token risk flags. The overall score
+//! is the weighted mean of the components.
+
+use clmm_lp_domain::metrics::apy::{fee_apr, fee_apy};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use std::cmp::Ordering;
+
+/// Seconds in a day, the window of one day's volume.
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Fee APR scoring half of the fee component (0.5 = 50%).
+const FEE_APR_MIDPOINT: f64 = 0.5;
+
+/// Annualized volatility scoring half of the volatility component.
+const VOLATILITY_MIDPOINT: f64 = 0.8;
+
+/// TVL at which the depth component starts rising from zero (log10 USD).
+const DEPTH_LOG_FLOOR: f64 = 4.0;
+
+/// TVL at which the depth component saturates (log10 USD).
+const DEPTH_LOG_CEILING: f64 = 8.0;
+
+/// A risk attribute of one of the pool's tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenRiskFlag {
+    /// The mint authority can still issue tokens.
+    MintAuthority,
+    /// The freeze authority can freeze token accounts.
+    FreezeAuthority,
+    /// The token charges a fee on transfers.
+    TransferFee,
+    /// The token is not on a verified token list.
+    Unverified,
+}
+
+impl TokenRiskFlag {
+    /// Fraction of the token risk component removed by this flag.
+    #[must_use]
+    pub fn penalty(&self) -> f64 {
+        match self {
+            Self::MintAuthority => 0.3,
+            Self::FreezeAuthority => 0.4,
+            Self::TransferFee => 0.2,
+            Self::Unverified => 0.25,
+        }
+    }
+}
+
+/// Observed pool metrics used for scoring.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolMetrics {
+    /// Fee rate as decimal (e.g., 0.003 for 0.3%).
+    pub fee_rate: Decimal,
+    /// Daily volumes in USD over the observation window.
+    pub daily_volumes: Vec<Decimal>,
+    /// Total value locked in USD.
+    pub tvl_usd: Decimal,
+    /// Annualized price volatility (0.6 = 60%).
+    pub volatility: f64,
+    /// Risk flags of both tokens.
+    pub risk_flags: Vec<TokenRiskFlag>,
+}
+
+impl PoolMetrics {
+    /// Creates pool metrics without risk flags.
+    #[must_use]
+    pub fn new(
+        fee_rate: Decimal,
+        daily_volumes: Vec<Decimal>,
+        tvl_usd: Decimal,
+        volatility: f64,
+    ) -> Self {
+        Self {
+            fee_rate,
+            daily_volumes,
+            tvl_usd,
+            volatility,
+            risk_flags: Vec::new(),
+        }
+    }
+
+    /// Sets the token risk flags.
+    #[must_use]
+    pub fn with_risk_flags(mut self, flags: Vec<TokenRiskFlag>) -> Self {
+        self.risk_flags = flags;
+        self
+    }
+
+    /// Returns the mean daily volume in USD.
+    #[must_use]
+    pub fn mean_daily_volume(&self) -> Decimal {
+        if self.daily_volumes.is_empty() {
+            return Decimal::ZERO;
+        }
+        self.daily_volumes.iter().sum::<Decimal>() / Decimal::from(self.daily_volumes.len())
+    }
+
+    /// Returns the annual fee yield on TVL at the mean daily volume.
+    #[must_use]
+    pub fn fee_apr(&self) -> Decimal {
+        fee_apr(self.daily_fees(), self.tvl_usd, SECONDS_PER_DAY).unwrap_or(Decimal::ZERO)
+    }
+
+    /// Returns the fee yield on TVL at the mean daily volume, compounded
+    /// daily as if fees were reinvested.
+    #[must_use]
+    pub fn fee_apy(&self) -> Decimal {
+        fee_apy(self.daily_fees(), self.tvl_usd, SECONDS_PER_DAY).unwrap_or(Decimal::ZERO)
+    }
+
+    /// Returns the fees paid to LPs at the mean daily volume.
+    fn daily_fees(&self) -> Decimal {
+        self.mean_daily_volume() * self.fee_rate
+    }
+
+    /// Returns the coefficient of variation of daily volume, if defined.
+    #[must_use]
+    pub fn volume_variation(&self) -> Option<f64> {
+        let mean = self.mean_daily_volume().to_f64()?;
+        if mean <= 0.0 {
+            return None;
+        }
+        let variance = self
+            .daily_volumes
+            .iter()
+            .filter_map(|v| v.to_f64())
+            .map(|v| (v - mean).powi(2))
+            .sum::<f64>()
+            / self.daily_volumes.len() as f64;
+        Some(variance.sqrt() / mean)
+    }
+}
+
+/// Relative weights of the score components.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoolScoreWeights {
+    /// Weight of fee APR.
+    pub fee_apr: f64,
+    /// Weight of volume consistency.
+    pub volume_consistency: f64,
+    /// Weight of TVL depth.
+    pub depth: f64,
+    /// Weight of low volatility.
+    pub volatility: f64,
+    /// Weight of token risk.
+    pub token_risk: f64,
+}
+
+impl Default for PoolScoreWeights {
+    fn default() -> Self {
+        Self {
+            fee_apr: 0.35,
+            volume_consistency: 0.2,
+            depth: 0.2,
+            volatility: 0.15,
+            token_risk: 0.1,
+        }
+    }
+}
+
+impl PoolScoreWeights {
+    /// Returns the sum of all weights.
+    #[must_use]
+    pub fn total(&self) -> f64 {
+        self.fee_apr + self.volume_consistency + self.depth + self.volatility + self.token_risk
+    }
+}
+
+/// Score of a pool and its components, each in `[0, 1]`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoolScore {
+    /// Weighted mean of the components.
+    pub total: f64,
+    /// Fee APR component.
+    pub fee_apr: f64,
+    /// Volume consistency component.
+    pub volume_consistency: f64,
+    /// TVL depth component.
+    pub depth: f64,
+    /// Volatility component; calmer pools score higher.
+    pub volatility: f64,
+    /// Token risk component; 1 without risk flags.
+    pub token_risk: f64,
+}
+
+impl PoolScore {
+    /// Scores a pool with the given weights.
+    ///
+    /// Weights are normalized by their sum; the total is zero if they sum to
+    /// zero or less.
+    #[must_use]
+    pub fn compute(metrics: &PoolMetrics, weights: &PoolScoreWeights) -> Self {
+        let apr = metrics.fee_apr().to_f64().unwrap_or(0.0).max(0.0);
+        let fee_apr = apr / (apr + FEE_APR_MIDPOINT);
+
+        let volume_consistency = metrics
+            .volume_variation()
+            .map_or(0.0, |cv| 1.0 / (1.0 + cv));
+
+        let tvl = metrics.tvl_usd.to_f64().unwrap_or(0.0);
+        let depth = if tvl > 0.0 {
+            ((tvl.log10() - DEPTH_LOG_FLOOR) / (DEPTH_LOG_CEILING - DEPTH_LOG_FLOOR))
+                .clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+
+        let volatility = VOLATILITY_MIDPOINT / (VOLATILITY_MIDPOINT + metrics.volatility.max(0.0));
+
+        let token_risk = metrics
+            .risk_flags
+            .iter()
+            .map(|flag| 1.0 - flag.penalty())
+            .product::<f64>();
+
+        let weight_sum = weights.total();
+        let total = if weight_sum > 0.0 {
+            (fee_apr * weights.fee_apr
+                + volume_consistency * weights.volume_consistency
+                + depth * weights.depth
+                + volatility * weights.volatility
+                + token_risk * weights.token_risk)
+                / weight_sum
+        } else {
+            0.0
+        };
+
+        Self {
+            total,
+            fee_apr,
+            volume_consistency,
+            depth,
+            volatility,
+            token_risk,
+        }
+    }
+}
+
+/// Scores pools and sorts them best first.
+#[must_use]
+pub fn rank_pools<K>(
+    pools: Vec<(K, PoolMetrics)>,
+    weights: &PoolScoreWeights,
+) -> Vec<(K, PoolScore)> {
+    let mut ranked: Vec<(K, PoolScore)> = pools
+        .into_iter()
+        .map(|(key, metrics)| {
+            let score = PoolScore::compute(&metrics, weights);
+            (key, score)
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.1.total.partial_cmp(&a.1.total).unwrap_or(Ordering::Equal));
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn metrics(volumes: &[Decimal], tvl: Decimal, volatility: f64) -> PoolMetrics {
+        PoolMetrics::new(dec!(0.003), volumes.to_vec(), tvl, volatility)
+    }
+
+    #[test]
+    fn test_components() {
+        // 1M/day at 0.3% on 3M TVL is 36.5% APR
+        let pool = metrics(&[dec!(1000000), dec!(1000000)], dec!(3000000), 0.8);
+        assert_eq!(pool.fee_apr(), dec!(0.365));
+        let daily = 1.0_f64 + 0.365 / 365.0;
+        assert!((pool.fee_apy().to_f64().unwrap() - (daily.powf(365.0) - 1.0)).abs() < 1e-9);
+
+        let score = PoolScore::compute(&pool, &PoolScoreWeights::default());
+        assert!((score.volume_consistency - 1.0).abs() < 1e-12);
+        assert!((score.volatility - 0.5).abs() < 1e-12);
+        assert!((score.token_risk - 1.0).abs() < 1e-12);
+        // log10(3M) = 6.48 is 62% of the way from 10k to 100M
+        assert!((score.depth - 0.6193).abs() < 1e-3);
+        assert!(score.total > 0.0 && score.total < 1.0);
+    }
+
+    #[test]
+    fn test_risk_flags_and_weights() {
+        let pool = metrics(&[dec!(1000000)], dec!(3000000), 0.5).with_risk_flags(vec![
+            TokenRiskFlag::FreezeAuthority,
+            TokenRiskFlag::Unverified,
+        ]);
+
+        let only_risk = PoolScoreWeights {
+            fee_apr: 0.0,
+            volume_consistency: 0.0,
+            depth: 0.0,
+            volatility: 0.0,
+            token_risk: 1.0,
+        };
+        let score = PoolScore::compute(&pool, &only_risk);
+        assert!((score.total - 0.45).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_rank_prefers_steady_volume() {
+        let steady = metrics(
+            &[dec!(500000), dec!(500000), dec!(500000)],
+            dec!(2000000),
+            0.6,
+        );
+        let spiky = metrics(&[dec!(0), dec!(1500000), dec!(0)], dec!(2000000), 0.6);
+
+        let ranked = rank_pools(
+            vec![("spiky", spiky), ("steady", steady)],
+            &PoolScoreWeights::default(),
+        );
+        assert_eq!(ranked[0].0, "steady");
+        assert!(ranked[0].1.volume_consistency > ranked[1].1.volume_consistency);
+        // Same mean volume, same APR
+        assert_eq!(ranked[0].1.fee_apr, ranked[1].1.fee_apr);
+    }
+}
@@ -8,8 +8,13 @@
 //! use clmm_lp_optimization::prelude::*;
 //! ```
 
+// Cache
+pub use crate::cache::{CacheLookup, OptimizationCache, OptimizationKey};
+
 // Constraints
-pub use crate::constraints::{OptimizationConstraints, PositionConstraints, RebalanceConstraints};
+pub use crate::constraints::{
+    OptimizationConstraints, PoolConstraints, PositionConstraints, RebalanceConstraints,
+};
 
 // Objective functions
 pub use crate::objective::{
@@ -17,9 +22,13 @@ pub use crate::objective::{
     MaximizeTimeInRange, MinimizeIL, ObjectiveFunction, RiskAdjustedReturn,
 };
 
+// Explanations
+pub use crate::explain::{ExplanationInputs, explain};
+
 // Optimizer
 pub use crate::optimizer::{
-    AnalyticalOptimizer, CandidateResult, GridSearchOptimizer, OptimizationConfig, Optimizer,
+    AnalyticalOptimizer, CandidateResult, GridSearchOptimizer, HISTORY_BUDGET_BYTES,
+    OptimizationConfig, Optimizer,
 };
 
 // Parameter optimizer
@@ -28,5 +37,33 @@ pub use crate::parameter_optimizer::{
     PeriodicCandidate, PeriodicParams, ThresholdCandidate, ThresholdParams,
 };
 
+// Pool scoring
+pub use crate::pool_score::{PoolMetrics, PoolScore, PoolScoreWeights, TokenRiskFlag, rank_pools};
+
+// Progress
+pub use crate::progress::{
+    CancellationToken, OptimizationError, OptimizationProgress, ProgressCallback, channel_callback,
+};
+
 // Range optimizer
 pub use crate::range_optimizer::RangeOptimizer;
+
+// Objective registry
+pub use crate::registry::{
+    Metric, ObjectiveConfig, ObjectiveDefinition, ObjectiveRegistry, ObjectiveTerm, RegistryError,
+    SharedObjective, WeightedObjective,
+};
+
+// Sensitivity analysis
+pub use crate::sensitivity::{
+    BOUND_PERTURBATIONS, Perturbation, SensitivityPoint, SensitivityReport,
+    VOLATILITY_PERTURBATIONS, perturb_bounds,
+};
+
+// Venue comparison
+pub use crate::venue::{
+    VenueEstimate, range_depth, rank_venues, realized_volatility, time_in_range,
+};
+
+// Capital sizing
+pub use crate::sizing::{CapitalSizing, KellySizing, expected_log_growth, full_kelly_fraction};
@@ -0,0 +1,198 @@
+//! Progress reporting and cancellation for long-running optimizations.
+//!
+//! A [`ProgressCallback`] receives snapshots as Monte Carlo paths complete,
+//! and a [`CancellationToken`] lets another thread abort the run between paths.
+
+use clmm_lp_domain::value_objects::price_range::PriceRange;
+use rust_decimal::Decimal;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+/// Errors returned by an optimization run.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum OptimizationError {
+    /// The run was cancelled through its cancellation token.
+    #[error("Optimization cancelled")]
+    Cancelled,
+    /// No candidate range could be evaluated.
+    #[error("No candidates evaluated")]
+    NoCandidates,
+}
+
+/// Snapshot of an optimization run's progress.
+#[derive(Debug, Clone, Default)]
+pub struct OptimizationProgress {
+    /// Monte Carlo paths completed across all candidates.
+    pub iterations_done: usize,
+    /// Total Monte Carlo paths to run.
+    pub total_iterations: usize,
+    /// Candidate ranges fully evaluated.
+    pub candidates_done: usize,
+    /// Total candidate ranges.
+    pub total_candidates: usize,
+    /// Best objective score so far.
+    pub best_score: Option<Decimal>,
+    /// Range with the best score so far.
+    pub best_range: Option<PriceRange>,
+}
+
+impl OptimizationProgress {
+    /// Returns the completed fraction between 0 and 1.
+    #[must_use]
+    pub fn fraction(&self) -> f64 {
+        if self.total_iterations == 0 {
+            return 0.0;
+        }
+        (self.iterations_done as f64 / self.total_iterations as f64).min(1.0)
+    }
+
+    /// Returns true once every candidate has been evaluated.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.total_candidates > 0 && self.candidates_done >= self.total_candidates
+    }
+}
+
+/// Callback invoked with progress snapshots; may be called from worker threads.
+pub type ProgressCallback = Arc<dyn Fn(&OptimizationProgress) + Send + Sync>;
+
+/// Creates a progress callback that forwards snapshots to a channel.
+///
+/// Snapshots are dropped once the receiver goes away.
+#[must_use]
+pub fn channel_callback(sender: Sender<OptimizationProgress>) -> ProgressCallback {
+    Arc::new(move |progress| {
+        let _ = sender.send(progress.clone());
+    })
+}
+
+/// Cooperative cancellation flag shared between an optimizer and its caller.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Creates a new, uncancelled token.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests cancellation.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns true if cancellation was requested.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// Collects progress from parallel workers and forwards it to the callback.
+pub(crate) struct ProgressTracker {
+    callback: Option<ProgressCallback>,
+    cancellation: Option<CancellationToken>,
+    report_every: usize,
+    state: Mutex<OptimizationProgress>,
+}
+
+impl ProgressTracker {
+    /// Creates a tracker for `candidates` ranges of `iterations` paths each.
+    pub(crate) fn new(
+        callback: Option<ProgressCallback>,
+        cancellation: Option<CancellationToken>,
+        candidates: usize,
+        iterations: usize,
+    ) -> Self {
+        let total_iterations = candidates * iterations;
+        Self {
+            callback,
+            cancellation,
+            // Roughly one report per percent of work
+            report_every: (total_iterations / 100).max(1),
+            state: Mutex::new(OptimizationProgress {
+                total_iterations,
+                total_candidates: candidates,
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Returns true if the run should stop.
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    }
+
+    /// Records a finished path; returns false if the run should stop.
+    pub(crate) fn path_done(&self) -> bool {
+        if let Some(callback) = &self.callback
+            && let Ok(mut state) = self.state.lock()
+        {
+            state.iterations_done += 1;
+            if state.iterations_done % self.report_every == 0 {
+                callback(&state);
+            }
+        }
+        !self.is_cancelled()
+    }
+
+    /// Records a fully evaluated candidate.
+    pub(crate) fn candidate_done(&self, score: Decimal, range: &PriceRange) {
+        if let Some(callback) = &self.callback
+            && let Ok(mut state) = self.state.lock()
+        {
+            state.candidates_done += 1;
+            if state.best_score.is_none_or(|best| score > best) {
+                state.best_score = Some(score);
+                state.best_range = Some(*range);
+            }
+            callback(&state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clmm_lp_domain::value_objects::price::Price;
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_tracker_reports_best_so_far() {
+        let (tx, rx) = mpsc::channel();
+        let tracker = ProgressTracker::new(Some(channel_callback(tx)), None, 2, 1);
+        let range = PriceRange::new(
+            Price::new(Decimal::from(90)),
+            Price::new(Decimal::from(110)),
+        );
+
+        assert!(tracker.path_done());
+        tracker.candidate_done(Decimal::from(5), &range);
+        assert!(tracker.path_done());
+        tracker.candidate_done(Decimal::from(3), &range);
+
+        let last = rx.try_iter().last().unwrap();
+        assert_eq!(last.iterations_done, 2);
+        assert_eq!(last.best_score, Some(Decimal::from(5)));
+        assert!(last.is_complete());
+        assert!((last.fraction() - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_cancellation_stops_tracker() {
+        let token = CancellationToken::new();
+        let tracker = ProgressTracker::new(None, Some(token.clone()), 1, 10);
+
+        assert!(tracker.path_done());
+        token.cancel();
+        assert!(!tracker.path_done());
+        assert!(tracker.is_cancelled());
+    }
+}
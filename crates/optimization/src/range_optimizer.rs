@@ -1,14 +1,43 @@
+use crate::constraints::PoolConstraints;
+use crate::explain::{ExplanationInputs, explain};
 use crate::objective::ObjectiveFunction;
+use crate::progress::{CancellationToken, OptimizationError, ProgressCallback, ProgressTracker};
+use crate::sensitivity::{
+    BOUND_PERTURBATIONS, Perturbation, SensitivityPoint, SensitivityReport,
+    VOLATILITY_PERTURBATIONS, perturb_bounds,
+};
+use crate::sizing::{CapitalSizing, KellySizing};
 use clmm_lp_domain::entities::position::Position;
+use clmm_lp_domain::math::concentrated_liquidity::concentration_multiplier;
+use clmm_lp_domain::metrics::sharpe::SharpeConfig;
 use clmm_lp_domain::value_objects::OptimizationResult;
-use clmm_lp_domain::value_objects::price::Price;
 use clmm_lp_domain::value_objects::price_range::PriceRange;
+use clmm_lp_domain::value_objects::range_spec::RangeSpec;
 use clmm_lp_domain::value_objects::simulation_result::SimulationResult;
-use clmm_lp_simulation::liquidity::ConstantLiquidity;
-use clmm_lp_simulation::monte_carlo::MonteCarloRunner;
-use clmm_lp_simulation::volume::ConstantVolume;
+use clmm_lp_simulation::liquidity::LiquidityDistribution;
+use clmm_lp_simulation::monte_carlo::{AggregateResult, MonteCarloRunner, PercentilePath};
+use clmm_lp_simulation::volume::VolumeModel;
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::*;
+use tracing::warn;
+
+/// Position value the simulation engine measures PnL, fees and IL against.
+const SIMULATED_CAPITAL: i64 = 1000;
+
+/// Candidate half-widths: 1%, 2%, 5%, 10%, 20%, 50%.
+const CANDIDATE_WIDTHS: [f64; 6] = [0.01, 0.02, 0.05, 0.10, 0.20, 0.50];
+
+/// Market assumptions shared by every simulated range.
+#[derive(Clone)]
+struct MarketInputs<V> {
+    current_price: Decimal,
+    volatility: f64,
+    drift: f64,
+    volume: V,
+    fee_rate: Decimal,
+}
 
 /// Optimizer for finding the best price range.
 pub struct RangeOptimizer {
@@ -18,6 +47,22 @@ pub struct RangeOptimizer {
     pub steps: usize,
     /// Time step in years.
     pub time_step: f64,
+    /// Tick spacing constraints of the target pool.
+    pub pool_constraints: Option<PoolConstraints>,
+    /// Active liquidity around the current price; defaults to the flat pool liquidity.
+    pub liquidity_distribution: Option<LiquidityDistribution>,
+    /// Previous recommendation to search around instead of the full grid.
+    pub warm_start: Option<PriceRange>,
+    /// Cost of one rebalance transaction in USD, weighed in explanations.
+    pub tx_cost: Option<Decimal>,
+    /// Risk-free rate and benchmark for Sharpe ratios; `None` skips them.
+    pub sharpe: Option<SharpeConfig>,
+    /// Maximum worker threads; `None` uses the global rayon pool.
+    pub threads: Option<usize>,
+    /// Receives progress snapshots while optimizing.
+    pub progress: Option<ProgressCallback>,
+    /// Aborts the run when cancelled.
+    pub cancellation: Option<CancellationToken>,
 }
 
 impl RangeOptimizer {
@@ -27,94 +72,526 @@ impl RangeOptimizer {
             iterations,
             steps,
             time_step,
+            pool_constraints: None,
+            liquidity_distribution: None,
+            warm_start: None,
+            tx_cost: None,
+            sharpe: None,
+            threads: None,
+            progress: None,
+            cancellation: None,
+        }
+    }
+
+    /// Snaps candidate ranges to the pool's initializable ticks.
+    #[must_use]
+    pub fn with_pool_constraints(mut self, constraints: PoolConstraints) -> Self {
+        self.pool_constraints = Some(constraints);
+        self
+    }
+
+    /// Uses the pool's in-range liquidity distribution for fee share estimates.
+    #[must_use]
+    pub fn with_liquidity_distribution(mut self, distribution: LiquidityDistribution) -> Self {
+        self.liquidity_distribution = Some(distribution);
+        self
+    }
+
+    /// Limits the number of worker threads used for simulations.
+    ///
+    /// Useful when embedded in a server that must keep threads for other work.
+    #[must_use]
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads.max(1));
+        self
+    }
+
+    /// Reports progress (paths done, best range so far) to a callback.
+    #[must_use]
+    pub fn with_progress(mut self, callback: ProgressCallback) -> Self {
+        self.progress = Some(callback);
+        self
+    }
+
+    /// Allows the run to be aborted through a cancellation token.
+    #[must_use]
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Restricts the search to the previous recommendation and its neighbouring widths.
+    #[must_use]
+    pub fn with_warm_start(mut self, previous: PriceRange) -> Self {
+        self.warm_start = Some(previous);
+        self
+    }
+
+    /// Sets the cost of one rebalance transaction, so explanations can weigh
+    /// it against the expected fees.
+    #[must_use]
+    pub fn with_tx_cost(mut self, tx_cost: Decimal) -> Self {
+        self.tx_cost = Some(tx_cost);
+        self
+    }
+
+    /// Computes Sharpe ratios for every candidate under `config`.
+    ///
+    /// Needed by Sharpe objectives; without it they fall back to a
+    /// drawdown-based approximation.
+    #[must_use]
+    pub fn with_sharpe(mut self, config: SharpeConfig) -> Self {
+        self.sharpe = Some(config);
+        self
+    }
+
+    /// Returns the half-widths to evaluate at the given price.
+    ///
+    /// Without a warm start this is the full candidate grid. With one, it is the
+    /// previous width re-centred on the price plus the nearest grid widths on
+    /// either side.
+    #[must_use]
+    pub fn candidate_widths(&self, current_price: Decimal) -> Vec<f64> {
+        let Some(previous) = &self.warm_start else {
+            return CANDIDATE_WIDTHS.to_vec();
+        };
+
+        let previous_width = ((previous.upper_price.value - previous.lower_price.value)
+            / (Decimal::TWO * current_price))
+            .to_f64()
+            .unwrap_or(0.0);
+        if previous_width <= 0.0 {
+            return CANDIDATE_WIDTHS.to_vec();
         }
+
+        let below = CANDIDATE_WIDTHS
+            .iter()
+            .copied()
+            .rfind(|w| *w < previous_width);
+        let above = CANDIDATE_WIDTHS
+            .iter()
+            .copied()
+            .find(|w| *w > previous_width);
+
+        below
+            .into_iter()
+            .chain(std::iter::once(previous_width))
+            .chain(above)
+            .collect()
     }
 
     /// Optimizes the price range for a given position.
+    ///
+    /// # Panics
+    /// Panics if the run is cancelled or no candidate can be evaluated; use
+    /// [`RangeOptimizer::try_optimize`] when a cancellation token is set.
     #[allow(clippy::too_many_arguments)]
-    pub fn optimize<O: ObjectiveFunction>(
+    pub fn optimize<O, V>(
         &self,
         base_position: Position,
         current_price: Decimal,
         volatility: f64,
         drift: f64,
-        volume: ConstantVolume,
+        volume: V,
         pool_liquidity: u128,
         fee_rate: Decimal,
         objective: O,
-    ) -> OptimizationResult {
-        // Candidate widths: 1%, 2%, 5%, 10%, 20%, 50%
-        let widths = vec![0.01, 0.02, 0.05, 0.10, 0.20, 0.50];
-
-        let mut best_result: Option<(SimulationResult, PriceRange)> = None;
-        let mut best_score = Decimal::MIN;
-
-        // Assume 1000 USD capital for estimation
-        let _capital = Decimal::from(1000);
-        let liquidity_model = ConstantLiquidity::new(pool_liquidity);
-
-        for width in widths {
-            let lower_mult = Decimal::from_f64(1.0 - width).unwrap();
-            let upper_mult = Decimal::from_f64(1.0 + width).unwrap();
-
-            let lower_price = current_price * lower_mult;
-            let upper_price = current_price * upper_mult;
-
-            let range = PriceRange::new(Price::new(lower_price), Price::new(upper_price));
-
-            // Estimate Liquidity L for this range given Capital
-            // Narrower range -> Higher L
-            // Approximation: L = Capital / (Width_factor)
-            // For simplicity, let's use L = 1 / width (relative to 1000 base)
-            // Real calc is complex, this proxy ensures narrower ranges get higher fees.
-            let width_dec = Decimal::from_f64(width).unwrap();
-            let liquidity_proxy = (Decimal::from(1000) / width_dec).to_u128().unwrap_or(1000);
+    ) -> OptimizationResult
+    where
+        O: ObjectiveFunction + Sync,
+        V: VolumeModel + Clone + Send + Sync,
+    {
+        self.try_optimize(
+            base_position,
+            current_price,
+            volatility,
+            drift,
+            volume,
+            pool_liquidity,
+            fee_rate,
+            objective,
+        )
+        .expect("No candidates evaluated")
+    }
 
-            let mut candidate_position = base_position.clone();
-            candidate_position.range = Some(range.clone());
-            candidate_position.liquidity_amount = liquidity_proxy;
+    /// Optimizes the price range, stopping early if cancelled.
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_optimize<O, V>(
+        &self,
+        base_position: Position,
+        current_price: Decimal,
+        volatility: f64,
+        drift: f64,
+        volume: V,
+        pool_liquidity: u128,
+        fee_rate: Decimal,
+        objective: O,
+    ) -> Result<OptimizationResult, OptimizationError>
+    where
+        O: ObjectiveFunction + Sync,
+        V: VolumeModel + Clone + Send + Sync,
+    {
+        let market = MarketInputs {
+            current_price,
+            volatility,
+            drift,
+            volume,
+            fee_rate,
+        };
+        let liquidity_model = self
+            .liquidity_distribution
+            .clone()
+            .unwrap_or_else(|| LiquidityDistribution::new(pool_liquidity));
+        let ranges = self.candidate_ranges(current_price);
+        let tracker = ProgressTracker::new(
+            self.progress.clone(),
+            self.cancellation.clone(),
+            ranges.len(),
+            self.iterations,
+        );
 
-            let mut runner = MonteCarloRunner {
-                position: candidate_position,
-                volume_model: volume.clone(),
-                liquidity_model: liquidity_model.clone(),
-                fee_rate,
-                initial_price: current_price,
-                drift,
-                volatility,
-                time_step: self.time_step,
-                steps: self.steps,
-                iterations: self.iterations,
-            };
+        // Candidates and their Monte Carlo paths both run on the rayon pool
+        let evaluate = || {
+            ranges
+                .into_par_iter()
+                .filter_map(|range| {
+                    if tracker.is_cancelled() {
+                        return None;
+                    }
 
-            let agg_result = runner.run();
+                    let agg_result = self.simulate_range(
+                        &base_position,
+                        &range,
+                        &market,
+                        &liquidity_model,
+                        || tracker.path_done(),
+                    )?;
 
-            let sim_result = SimulationResult {
-                final_position_value: Decimal::ZERO,
-                total_fees_earned: agg_result.mean_fees,
-                total_il: agg_result.mean_il,
-                net_pnl: agg_result.mean_net_pnl,
-                max_drawdown: Decimal::ZERO,
-                time_in_range_percentage: Decimal::ZERO,
-                sharpe_ratio: None,
-            };
+                    let sim_result = SimulationResult {
+                        final_position_value: Decimal::ZERO,
+                        total_fees_earned: agg_result.mean_fees,
+                        total_il: agg_result.mean_il,
+                        net_pnl: agg_result.mean_net_pnl,
+                        max_drawdown: Decimal::ZERO,
+                        time_in_range_percentage: Decimal::ZERO,
+                        sharpe_ratio: agg_result.mean_sharpe,
+                    };
+                    let score = objective.evaluate(&sim_result);
+                    tracker.candidate_done(score, &range);
 
-            let score = objective.evaluate(&sim_result);
+                    Some((score, sim_result, agg_result.fee_std_dev, range))
+                })
+                .collect::<Vec<_>>()
+        };
+        let evaluated = match self.thread_pool() {
+            Some(pool) => pool.install(evaluate),
+            None => evaluate(),
+        };
+        if tracker.is_cancelled() {
+            return Err(OptimizationError::Cancelled);
+        }
 
+        // Pick the first best candidate so ties resolve the same as a sequential run
+        let mut best_result: Option<(SimulationResult, Decimal, PriceRange)> = None;
+        let mut best_score = Decimal::MIN;
+        for (score, sim_result, fee_std_dev, range) in evaluated {
             if score > best_score {
                 best_score = score;
-                best_result = Some((sim_result, range));
+                best_result = Some((sim_result, fee_std_dev, range));
             }
         }
 
-        let (best_sim, best_range) = best_result.expect("No candidates evaluated");
+        let (best_sim, fee_std_dev, best_range) =
+            best_result.ok_or(OptimizationError::NoCandidates)?;
 
-        OptimizationResult {
+        let mut result = OptimizationResult {
             recommended_range: best_range,
             expected_pnl: best_sim.net_pnl,
             expected_fees: best_sim.total_fees_earned,
+            fee_std_dev,
             expected_il: best_sim.total_il,
             sharpe_ratio: best_sim.sharpe_ratio,
+            sharpe_config: self.sharpe,
+            explanation: None,
+        };
+        result.explanation = Some(explain(&result, &self.explanation_inputs(&market)));
+        Ok(result)
+    }
+
+    /// Re-evaluates a recommended range under perturbed bounds and volatility.
+    ///
+    /// Bounds are moved away from (positive) or towards (negative) the current
+    /// price by each of [`BOUND_PERTURBATIONS`], and volatility is scaled by each
+    /// of [`VOLATILITY_PERTURBATIONS`]. Perturbed ranges are not snapped to the
+    /// pool's ticks so that small perturbations are not rounded away.
+    #[allow(clippy::too_many_arguments)]
+    pub fn sensitivity<V: VolumeModel + Clone + Send + Sync>(
+        &self,
+        base_position: Position,
+        recommended_range: &PriceRange,
+        current_price: Decimal,
+        volatility: f64,
+        drift: f64,
+        volume: V,
+        pool_liquidity: u128,
+        fee_rate: Decimal,
+    ) -> Option<SensitivityReport> {
+        let market = MarketInputs {
+            current_price,
+            volatility,
+            drift,
+            volume,
+            fee_rate,
+        };
+        let liquidity_model = self
+            .liquidity_distribution
+            .clone()
+            .unwrap_or_else(|| LiquidityDistribution::new(pool_liquidity));
+
+        let mut scenarios = vec![(Perturbation::Baseline, *recommended_range, volatility)];
+        for pct in BOUND_PERTURBATIONS {
+            let pct = Decimal::from_f64(pct).unwrap_or_default();
+            if let Some(range) = perturb_bounds(recommended_range, current_price, pct) {
+                scenarios.push((Perturbation::Bounds(pct), range, volatility));
+            }
+        }
+        for pct in VOLATILITY_PERTURBATIONS {
+            scenarios.push((
+                Perturbation::Volatility(Decimal::from_f64(pct).unwrap_or_default()),
+                *recommended_range,
+                volatility * (1.0 + pct),
+            ));
+        }
+
+        let evaluate = || {
+            scenarios
+                .into_par_iter()
+                .filter_map(|(perturbation, range, volatility)| {
+                    let market = MarketInputs {
+                        volatility,
+                        ..market.clone()
+                    };
+                    let agg = self.simulate_range(
+                        &base_position,
+                        &range,
+                        &market,
+                        &liquidity_model,
+                        || true,
+                    )?;
+                    Some(SensitivityPoint {
+                        perturbation,
+                        range,
+                        volatility,
+                        expected_pnl: agg.mean_net_pnl,
+                        expected_fees: agg.mean_fees,
+                        expected_il: agg.mean_il,
+                    })
+                })
+                .collect::<Vec<_>>()
+        };
+        let mut points = match self.thread_pool() {
+            Some(pool) => pool.install(evaluate),
+            None => evaluate(),
+        };
+
+        if points.first()?.perturbation != Perturbation::Baseline {
+            return None;
+        }
+        let baseline = points.remove(0);
+        Some(SensitivityReport { baseline, points })
+    }
+
+    /// Sizes the capital to deploy into a range from its simulated returns.
+    ///
+    /// Instead of assuming full deployment, the range's Monte Carlo return
+    /// distribution is used to find the growth-optimal fraction, scaled and
+    /// capped by `sizing`. Returns `None` if the range cannot be simulated.
+    #[allow(clippy::too_many_arguments)]
+    pub fn size_capital<V: VolumeModel + Clone + Send + Sync>(
+        &self,
+        base_position: Position,
+        range: &PriceRange,
+        current_price: Decimal,
+        volatility: f64,
+        drift: f64,
+        volume: V,
+        pool_liquidity: u128,
+        fee_rate: Decimal,
+        sizing: KellySizing,
+    ) -> Option<CapitalSizing> {
+        let market = MarketInputs {
+            current_price,
+            volatility,
+            drift,
+            volume,
+            fee_rate,
+        };
+        let liquidity_model = self
+            .liquidity_distribution
+            .clone()
+            .unwrap_or_else(|| LiquidityDistribution::new(pool_liquidity));
+
+        let simulate =
+            || self.simulate_range(&base_position, range, &market, &liquidity_model, || true);
+        let agg = match self.thread_pool() {
+            Some(pool) => pool.install(simulate),
+            None => simulate(),
+        }?;
+
+        let returns: Vec<f64> = agg.path_returns.iter().filter_map(|r| r.to_f64()).collect();
+        sizing.size(&returns, self.steps as f64 * self.time_step)
+    }
+
+    /// Simulates a range and keeps the paths at the given percentiles of
+    /// final net PnL, so the spread behind a recommendation can be charted.
+    ///
+    /// Returns an empty list if the range cannot be simulated.
+    #[allow(clippy::too_many_arguments)]
+    pub fn percentile_paths<V: VolumeModel + Clone + Send + Sync>(
+        &self,
+        base_position: Position,
+        range: &PriceRange,
+        current_price: Decimal,
+        volatility: f64,
+        drift: f64,
+        volume: V,
+        pool_liquidity: u128,
+        fee_rate: Decimal,
+        percentiles: &[u8],
+    ) -> Vec<PercentilePath> {
+        let market = MarketInputs {
+            current_price,
+            volatility,
+            drift,
+            volume,
+            fee_rate,
+        };
+        let liquidity_model = self
+            .liquidity_distribution
+            .clone()
+            .unwrap_or_else(|| LiquidityDistribution::new(pool_liquidity));
+        let Some(runner) = self.runner(&base_position, range, &market, &liquidity_model) else {
+            return Vec::new();
+        };
+
+        let simulate = || runner.percentile_paths(percentiles);
+        match self.thread_pool() {
+            Some(pool) => pool.install(simulate),
+            None => simulate(),
+        }
+    }
+
+    /// Runs the Monte Carlo simulation for one range.
+    ///
+    /// Returns `None` if the range is invalid or `on_path` stops the run.
+    fn simulate_range<V, F>(
+        &self,
+        base_position: &Position,
+        range: &PriceRange,
+        market: &MarketInputs<V>,
+        liquidity_model: &LiquidityDistribution,
+        on_path: F,
+    ) -> Option<AggregateResult>
+    where
+        V: VolumeModel + Clone + Send + Sync,
+        F: Fn() -> bool + Sync,
+    {
+        self.runner(base_position, range, market, liquidity_model)?
+            .run_parallel_with(on_path)
+    }
+
+    /// Builds the Monte Carlo runner for one range, or `None` if the range
+    /// is invalid.
+    fn runner<V: VolumeModel + Clone>(
+        &self,
+        base_position: &Position,
+        range: &PriceRange,
+        market: &MarketInputs<V>,
+        liquidity_model: &LiquidityDistribution,
+    ) -> Option<MonteCarloRunner<V, LiquidityDistribution>> {
+        // The same capital buys more liquidity in a narrower range, so fee
+        // share scales with the concentration multiplier of the range.
+        // Capital is expressed as 1000 units of full-range liquidity.
+        let multiplier = concentration_multiplier(
+            market.current_price,
+            range.lower_price.value,
+            range.upper_price.value,
+        )
+        .ok()?;
+        let liquidity_proxy = (Decimal::from(1000) * multiplier).to_u128().unwrap_or(1000);
+
+        let mut candidate_position = base_position.clone();
+        candidate_position.range = Some(*range);
+        candidate_position.liquidity_amount = liquidity_proxy;
+
+        Some(MonteCarloRunner {
+            position: candidate_position,
+            volume_model: market.volume.clone(),
+            liquidity_model: liquidity_model.clone(),
+            fee_rate: market.fee_rate,
+            initial_price: market.current_price,
+            drift: market.drift,
+            volatility: market.volatility,
+            time_step: self.time_step,
+            steps: self.steps,
+            iterations: self.iterations,
+            sharpe: self.sharpe,
+        })
+    }
+
+    /// Builds the inputs a result from this optimizer is explained against.
+    fn explanation_inputs<V>(&self, market: &MarketInputs<V>) -> ExplanationInputs {
+        let mut inputs = ExplanationInputs::new(
+            market.current_price,
+            market.volatility,
+            self.steps as f64 * self.time_step,
+            market.fee_rate,
+            Decimal::from(SIMULATED_CAPITAL),
+        );
+        if let Some(tx_cost) = self.tx_cost {
+            inputs = inputs.with_tx_cost(tx_cost);
+        }
+        if let Some(previous) = self.warm_start {
+            inputs = inputs.with_previous(previous);
+        }
+        inputs
+    }
+
+    /// Builds the candidate ranges, snapped to the pool and deduplicated.
+    fn candidate_ranges(&self, current_price: Decimal) -> Vec<PriceRange> {
+        let mut ranges: Vec<PriceRange> = Vec::new();
+
+        for width in self.candidate_widths(current_price) {
+            let width_dec = Decimal::from_f64(width).unwrap();
+            let Ok(mut range) = RangeSpec::Percent(width_dec).to_price_range(current_price, None)
+            else {
+                continue;
+            };
+
+            // Align to initializable ticks; narrow widths may snap to the same range
+            if let Some(pool) = &self.pool_constraints {
+                let Ok(snapped) = pool.snap(&range) else {
+                    continue;
+                };
+                range = snapped;
+            }
+            if !ranges.contains(&range) {
+                ranges.push(range);
+            }
+        }
+
+        ranges
+    }
+
+    /// Builds a dedicated pool when a thread budget is set.
+    fn thread_pool(&self) -> Option<ThreadPool> {
+        let threads = self.threads?;
+        match ThreadPoolBuilder::new().num_threads(threads).build() {
+            Ok(pool) => Some(pool),
+            Err(e) => {
+                warn!(error = %e, threads, "Failed to build optimizer thread pool, using global pool");
+                None
+            }
         }
     }
 }
@@ -126,6 +603,7 @@ mod tests {
     use clmm_lp_domain::entities::position::{Position, PositionId};
     use clmm_lp_domain::enums::PositionStatus;
     use clmm_lp_domain::value_objects::amount::Amount;
+    use clmm_lp_simulation::volume::{ConstantVolume, SeasonalVolumeModel};
     use primitive_types::U256;
     use uuid::Uuid;
 
@@ -174,5 +652,346 @@ mod tests {
         // Check recommended range is valid
         assert!(result.recommended_range.lower_price.value < current_price);
         assert!(result.recommended_range.upper_price.value > current_price);
+        // Every result explains itself; no warm start means nothing to compare
+        let explanation = result.explanation.unwrap();
+        assert_eq!(explanation.drivers.len(), 2);
+        assert!(explanation.comparison.is_none());
+    }
+
+    #[test]
+    fn test_optimization_snaps_to_tick_spacing() {
+        use clmm_lp_domain::math::price_tick::price_to_tick;
+
+        let optimizer = RangeOptimizer::new(5, 5, 1.0 / 365.0)
+            .with_pool_constraints(PoolConstraints::new(128).with_min_width_spacings(2));
+        let volume = ConstantVolume::from_amount(Amount::new(U256::from(1000000), 6));
+        let current_price = Decimal::from(100);
+
+        let result = optimizer.optimize(
+            create_dummy_position(),
+            current_price,
+            0.1,
+            0.0,
+            volume,
+            100_000_000,
+            Decimal::from_f64(0.01).unwrap(),
+            MaximizeNetPnL,
+        );
+
+        let lower = price_to_tick(result.recommended_range.lower_price.value).unwrap();
+        let upper = price_to_tick(result.recommended_range.upper_price.value).unwrap();
+        assert_eq!(lower % 128, 0);
+        assert_eq!(upper % 128, 0);
+        assert!(upper - lower >= 256);
+    }
+
+    #[test]
+    fn test_fee_projection_favors_concentrated_ranges() {
+        use crate::objective::MaximizeFees;
+
+        let optimizer = RangeOptimizer::new(10, 5, 1.0 / 365.0).with_liquidity_distribution(
+            LiquidityDistribution::new(100_000_000).with_bucket(
+                Decimal::from(99),
+                Decimal::from(101),
+                200_000_000,
+            ),
+        );
+        let volume = ConstantVolume::from_amount(Amount::new(U256::from(1000000), 6));
+        let current_price = Decimal::from(100);
+
+        // Near-zero volatility keeps every candidate in range, so the
+        // narrowest range wins on concentration alone
+        let result = optimizer.optimize(
+            create_dummy_position(),
+            current_price,
+            0.0001,
+            0.0,
+            volume,
+            100_000_000,
+            Decimal::from_f64(0.003).unwrap(),
+            MaximizeFees,
+        );
+
+        let width =
+            result.recommended_range.upper_price.value - result.recommended_range.lower_price.value;
+        assert_eq!(width, Decimal::TWO);
+        assert!(result.expected_fees > Decimal::ZERO);
+        assert!(result.fee_std_dev >= Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_warm_start_narrows_candidates() {
+        let optimizer = RangeOptimizer::new(5, 5, 1.0 / 365.0);
+        assert_eq!(optimizer.candidate_widths(Decimal::from(100)).len(), 6);
+
+        // Previous range was +/-7% around 100
+        let previous = PriceRange::new(
+            clmm_lp_domain::value_objects::price::Price::new(Decimal::from(93)),
+            clmm_lp_domain::value_objects::price::Price::new(Decimal::from(107)),
+        );
+        let warm = optimizer.with_warm_start(previous);
+        let widths = warm.candidate_widths(Decimal::from(100));
+        assert_eq!(widths.len(), 3);
+        assert!((widths[0] - 0.05).abs() < 1e-12);
+        assert!((widths[1] - 0.07).abs() < 1e-12);
+        assert!((widths[2] - 0.10).abs() < 1e-12);
+
+        let volume = ConstantVolume::from_amount(Amount::new(U256::from(1000000), 6));
+        let result = warm.optimize(
+            create_dummy_position(),
+            Decimal::from(100),
+            0.1,
+            0.0,
+            volume,
+            100_000_000,
+            Decimal::from_f64(0.003).unwrap(),
+            MaximizeNetPnL,
+        );
+        let width =
+            result.recommended_range.upper_price.value - result.recommended_range.lower_price.value;
+        assert!(width >= Decimal::from(10) && width <= Decimal::from(20));
+    }
+
+    #[test]
+    fn test_optimization_with_thread_budget() {
+        let optimizer = RangeOptimizer::new(20, 5, 1.0 / 365.0).with_threads(2);
+        assert_eq!(optimizer.threads, Some(2));
+
+        let volume = ConstantVolume::from_amount(Amount::new(U256::from(1000000), 6));
+        let result = optimizer.optimize(
+            create_dummy_position(),
+            Decimal::from(100),
+            0.1,
+            0.0,
+            volume,
+            100_000_000,
+            Decimal::from_f64(0.003).unwrap(),
+            MaximizeNetPnL,
+        );
+
+        assert!(result.recommended_range.lower_price.value < Decimal::from(100));
+        assert!(result.recommended_range.upper_price.value > Decimal::from(100));
+        assert_eq!(
+            RangeOptimizer::new(1, 1, 1.0).with_threads(0).threads,
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_progress_and_cancellation() {
+        use crate::progress::{CancellationToken, OptimizationProgress};
+        use std::sync::{Arc, Mutex};
+
+        let snapshots: Arc<Mutex<Vec<OptimizationProgress>>> = Arc::default();
+        let sink = snapshots.clone();
+        let optimizer = RangeOptimizer::new(10, 5, 1.0 / 365.0).with_progress(Arc::new(
+            move |p: &OptimizationProgress| sink.lock().unwrap().push(p.clone()),
+        ));
+        let volume = ConstantVolume::from_amount(Amount::new(U256::from(1000000), 6));
+        let result = optimizer
+            .try_optimize(
+                create_dummy_position(),
+                Decimal::from(100),
+                0.1,
+                0.0,
+                volume.clone(),
+                100_000_000,
+                Decimal::from_f64(0.003).unwrap(),
+                MaximizeNetPnL,
+            )
+            .unwrap();
+
+        let snapshots = snapshots.lock().unwrap();
+        let last = snapshots.last().unwrap();
+        assert!(last.is_complete());
+        assert_eq!(last.iterations_done, last.total_iterations);
+        assert_eq!(last.best_range.as_ref(), Some(&result.recommended_range));
+
+        let token = CancellationToken::new();
+        token.cancel();
+        let cancelled = RangeOptimizer::new(10, 5, 1.0 / 365.0)
+            .with_cancellation(token)
+            .try_optimize(
+                create_dummy_position(),
+                Decimal::from(100),
+                0.1,
+                0.0,
+                volume,
+                100_000_000,
+                Decimal::from_f64(0.003).unwrap(),
+                MaximizeNetPnL,
+            );
+        assert_eq!(cancelled.unwrap_err(), OptimizationError::Cancelled);
+    }
+
+    #[test]
+    fn test_sensitivity_covers_all_perturbations() {
+        let optimizer = RangeOptimizer::new(5, 5, 1.0 / 365.0);
+        let volume = ConstantVolume::from_amount(Amount::new(U256::from(1000000), 6));
+        let range = PriceRange::new(
+            clmm_lp_domain::value_objects::price::Price::new(Decimal::from(90)),
+            clmm_lp_domain::value_objects::price::Price::new(Decimal::from(110)),
+        );
+
+        let report = optimizer
+            .sensitivity(
+                create_dummy_position(),
+                &range,
+                Decimal::from(100),
+                0.3,
+                0.0,
+                volume,
+                100_000_000,
+                Decimal::from_f64(0.003).unwrap(),
+            )
+            .unwrap();
+
+        assert_eq!(report.baseline.range, range);
+        assert_eq!(
+            report.points.len(),
+            BOUND_PERTURBATIONS.len() + VOLATILITY_PERTURBATIONS.len()
+        );
+        // Scenarios keep their order: bounds first, then volatility
+        assert!(matches!(
+            report.points[0].perturbation,
+            Perturbation::Bounds(_)
+        ));
+        let last = report.points.last().unwrap();
+        assert!(matches!(last.perturbation, Perturbation::Volatility(_)));
+        assert!((last.volatility - 0.375).abs() < 1e-12);
+        // -20% pulls the bounds towards the price
+        assert!(report.points[0].range.lower_price.value > Decimal::from(90));
+    }
+
+    #[test]
+    fn test_size_capital_within_cap() {
+        let optimizer = RangeOptimizer::new(20, 5, 1.0 / 365.0);
+        let volume = ConstantVolume::from_amount(Amount::new(U256::from(1000000), 6));
+        let range = PriceRange::new(
+            clmm_lp_domain::value_objects::price::Price::new(Decimal::from(90)),
+            clmm_lp_domain::value_objects::price::Price::new(Decimal::from(110)),
+        );
+
+        let sizing = optimizer
+            .size_capital(
+                create_dummy_position(),
+                &range,
+                Decimal::from(100),
+                0.3,
+                0.0,
+                volume,
+                100_000_000,
+                Decimal::from_f64(0.003).unwrap(),
+                KellySizing::new(0.5, 0.8),
+            )
+            .unwrap();
+
+        assert!((0.0..=1.0).contains(&sizing.full_kelly));
+        assert!(sizing.fraction <= 0.8);
+        assert!(sizing.fraction <= sizing.full_kelly);
+        assert!(sizing.growth_rate >= 0.0);
+    }
+
+    #[test]
+    fn test_percentile_paths_are_ordered_by_pnl() {
+        let optimizer = RangeOptimizer::new(50, 10, 1.0 / 365.0);
+        let volume = ConstantVolume::from_amount(Amount::new(U256::from(1000000), 6));
+        let range = PriceRange::new(
+            clmm_lp_domain::value_objects::price::Price::new(Decimal::from(90)),
+            clmm_lp_domain::value_objects::price::Price::new(Decimal::from(110)),
+        );
+
+        let paths = optimizer.percentile_paths(
+            create_dummy_position(),
+            &range,
+            Decimal::from(100),
+            0.5,
+            0.0,
+            volume,
+            100_000_000,
+            Decimal::from_f64(0.003).unwrap(),
+            &[5, 50, 95],
+        );
+
+        assert_eq!(
+            paths.iter().map(|p| p.percentile).collect::<Vec<_>>(),
+            vec![5, 50, 95]
+        );
+        for path in &paths {
+            assert_eq!(path.prices.len(), path.pnl.len());
+            assert!(!path.pnl.is_empty());
+        }
+        let finals: Vec<Decimal> = paths.iter().map(|p| *p.pnl.last().unwrap()).collect();
+        assert!(finals[0] <= finals[1] && finals[1] <= finals[2]);
+    }
+
+    #[test]
+    fn test_sharpe_objective_uses_configured_benchmark() {
+        use crate::objective::MaximizeSharpeRatio;
+        use clmm_lp_domain::metrics::sharpe::Benchmark;
+
+        let config = SharpeConfig::new(Decimal::new(3, 2), Benchmark::FiftyFifty);
+        let optimizer = RangeOptimizer::new(10, 10, 1.0 / 365.0).with_sharpe(config);
+        let volume = ConstantVolume::from_amount(Amount::new(U256::from(1000000), 6));
+
+        let result = optimizer.optimize(
+            create_dummy_position(),
+            Decimal::from(100),
+            0.5,
+            0.0,
+            volume,
+            100_000_000,
+            Decimal::from_f64(0.003).unwrap(),
+            MaximizeSharpeRatio::new(config.risk_free_rate),
+        );
+
+        assert!(result.sharpe_ratio.is_some());
+        assert_eq!(result.sharpe_config, Some(config));
+    }
+
+    #[test]
+    fn test_optimization_with_seasonal_volume() {
+        let optimizer = RangeOptimizer::new(5, 7, 1.0 / 365.0);
+        // Flat seasonality at 1 unit/hour matches a constant 24 units/day
+        let seasonal = SeasonalVolumeModel::flat(Decimal::ONE, 24);
+        let constant = ConstantVolume::new(Decimal::from(24));
+        let range = PriceRange::new(
+            clmm_lp_domain::value_objects::price::Price::new(Decimal::from(90)),
+            clmm_lp_domain::value_objects::price::Price::new(Decimal::from(110)),
+        );
+        let fee_rate = Decimal::from_f64(0.003).unwrap();
+
+        // Zero volatility keeps every path in range, so fees are deterministic
+        let seasonal_fees = optimizer
+            .sensitivity(
+                create_dummy_position(),
+                &range,
+                Decimal::from(100),
+                0.0,
+                0.0,
+                seasonal,
+                100_000_000,
+                fee_rate,
+            )
+            .unwrap()
+            .baseline
+            .expected_fees;
+        let constant_fees = optimizer
+            .sensitivity(
+                create_dummy_position(),
+                &range,
+                Decimal::from(100),
+                0.0,
+                0.0,
+                constant,
+                100_000_000,
+                fee_rate,
+            )
+            .unwrap()
+            .baseline
+            .expected_fees;
+
+        assert!(seasonal_fees > Decimal::ZERO);
+        assert_eq!(seasonal_fees, constant_fees);
     }
 }
@@ -0,0 +1,516 @@
+//! Objective registry.
+//!
+//! Besides the built-in objectives, users can define their own as weighted
+//! sums of simulation metrics written as formulas in configuration, such as
+//! `0.7*fees - 0.3*|il| - drawdown`. Formulas are parsed once when they are
+//! registered and compiled into a [`WeightedObjective`], so evaluating a
+//! candidate costs no more than a built-in objective.
+
+use crate::objective::{
+    CompositeObjective, MaximizeFees, MaximizeNetPnL, MaximizeSharpeRatio, MaximizeTimeInRange,
+    MinimizeIL, ObjectiveFunction,
+};
+use clmm_lp_domain::value_objects::simulation_result::SimulationResult;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use thiserror::Error;
+
+/// An objective shared across optimization threads.
+pub type SharedObjective = Arc<dyn ObjectiveFunction + Send + Sync>;
+
+/// Errors raised while compiling or registering an objective.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum RegistryError {
+    /// The objective has no name.
+    #[error("Objective name is empty")]
+    EmptyName,
+    /// An objective with the same name is already registered.
+    #[error("Objective '{0}' is already registered")]
+    Duplicate(String),
+    /// The formula could not be parsed.
+    #[error("Invalid formula for objective '{name}': {reason}")]
+    InvalidFormula {
+        /// Objective the formula belongs to.
+        name: String,
+        /// What was wrong with it.
+        reason: String,
+    },
+}
+
+/// A simulation metric a custom objective can weigh.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    /// Net profit and loss.
+    NetPnl,
+    /// Fees earned.
+    Fees,
+    /// Impermanent loss, negative when the position lost value.
+    Il,
+    /// Maximum drawdown.
+    Drawdown,
+    /// Percentage of time the price stayed in range.
+    TimeInRange,
+    /// Sharpe ratio, zero when the simulation did not report one.
+    Sharpe,
+    /// Position value at the end of the simulation.
+    FinalValue,
+}
+
+impl Metric {
+    /// Names metrics are written as in formulas.
+    pub const NAMES: [&'static str; 7] = [
+        "pnl",
+        "fees",
+        "il",
+        "drawdown",
+        "time_in_range",
+        "sharpe",
+        "final_value",
+    ];
+
+    /// Reads the metric from a simulation result.
+    #[must_use]
+    pub fn value(self, result: &SimulationResult) -> Decimal {
+        match self {
+            Self::NetPnl => result.net_pnl,
+            Self::Fees => result.total_fees_earned,
+            Self::Il => result.total_il,
+            Self::Drawdown => result.max_drawdown,
+            Self::TimeInRange => result.time_in_range_percentage,
+            Self::Sharpe => result.sharpe_ratio.unwrap_or_default(),
+            Self::FinalValue => result.final_position_value,
+        }
+    }
+}
+
+impl FromStr for Metric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pnl" | "net_pnl" => Ok(Self::NetPnl),
+            "fees" => Ok(Self::Fees),
+            "il" => Ok(Self::Il),
+            "drawdown" => Ok(Self::Drawdown),
+            "time_in_range" => Ok(Self::TimeInRange),
+            "sharpe" => Ok(Self::Sharpe),
+            "final_value" => Ok(Self::FinalValue),
+            other => Err(format!(
+                "unknown metric '{}', expected one of {}",
+                other,
+                Self::NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+/// One weighted metric of a custom objective.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObjectiveTerm {
+    /// Metric the term reads.
+    pub metric: Metric,
+    /// Weight applied to the metric; negative weights penalize.
+    pub weight: Decimal,
+    /// Whether the metric's absolute value is used.
+    pub absolute: bool,
+}
+
+impl ObjectiveTerm {
+    fn evaluate(&self, result: &SimulationResult) -> Decimal {
+        let value = self.metric.value(result);
+        self.weight * if self.absolute { value.abs() } else { value }
+    }
+}
+
+/// A custom objective as written in configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ObjectiveDefinition {
+    /// Name the objective is selected by.
+    pub name: String,
+    /// Weighted sum of metrics, e.g. `0.7*fees - 0.3*|il| - drawdown`.
+    pub formula: String,
+    /// Optional description shown when listing objectives.
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+impl ObjectiveDefinition {
+    /// Parses the formula into a [`WeightedObjective`].
+    ///
+    /// # Errors
+    /// Returns an error if the name is empty or the formula is invalid.
+    pub fn compile(&self) -> Result<WeightedObjective, RegistryError> {
+        if self.name.trim().is_empty() {
+            return Err(RegistryError::EmptyName);
+        }
+        let terms =
+            parse_formula(&self.formula).map_err(|reason| RegistryError::InvalidFormula {
+                name: self.name.clone(),
+                reason,
+            })?;
+        Ok(WeightedObjective {
+            label: self.name.clone(),
+            terms,
+        })
+    }
+}
+
+/// Custom objectives loaded from a configuration file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct ObjectiveConfig {
+    /// Objective definitions, registered in order.
+    #[serde(default)]
+    pub objectives: Vec<ObjectiveDefinition>,
+}
+
+/// A compiled weighted sum of simulation metrics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeightedObjective {
+    label: String,
+    terms: Vec<ObjectiveTerm>,
+}
+
+impl WeightedObjective {
+    /// Creates an objective from already parsed terms.
+    #[must_use]
+    pub fn new(label: impl Into<String>, terms: Vec<ObjectiveTerm>) -> Self {
+        Self {
+            label: label.into(),
+            terms,
+        }
+    }
+
+    /// Returns the name the objective was defined with.
+    #[must_use]
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Returns the compiled terms.
+    #[must_use]
+    pub fn terms(&self) -> &[ObjectiveTerm] {
+        &self.terms
+    }
+}
+
+impl ObjectiveFunction for WeightedObjective {
+    fn evaluate(&self, result: &SimulationResult) -> Decimal {
+        self.terms.iter().map(|term| term.evaluate(result)).sum()
+    }
+
+    fn name(&self) -> &'static str {
+        "WeightedObjective"
+    }
+}
+
+/// Named objectives, built-in and user defined.
+#[derive(Clone)]
+pub struct ObjectiveRegistry {
+    objectives: BTreeMap<String, SharedObjective>,
+}
+
+impl std::fmt::Debug for ObjectiveRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ObjectiveRegistry")
+            .field("objectives", &self.objectives.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Default for ObjectiveRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ObjectiveRegistry {
+    /// Creates a registry holding the built-in objectives: `pnl`, `fees`,
+    /// `sharpe`, `min_il`, `time_in_range` and `composite`.
+    #[must_use]
+    pub fn new() -> Self {
+        let builtins: [(&str, SharedObjective); 6] = [
+            ("pnl", Arc::new(MaximizeNetPnL)),
+            ("fees", Arc::new(MaximizeFees)),
+            ("sharpe", Arc::new(MaximizeSharpeRatio::default())),
+            ("min_il", Arc::new(MinimizeIL::default())),
+            ("time_in_range", Arc::new(MaximizeTimeInRange)),
+            ("composite", Arc::new(CompositeObjective::new())),
+        ];
+        Self {
+            objectives: builtins
+                .into_iter()
+                .map(|(name, objective)| (name.to_string(), objective))
+                .collect(),
+        }
+    }
+
+    /// Creates a registry with the built-ins and every objective in `config`.
+    ///
+    /// # Errors
+    /// Returns the first definition that fails to compile or register.
+    pub fn from_config(config: &ObjectiveConfig) -> Result<Self, RegistryError> {
+        let mut registry = Self::new();
+        for definition in &config.objectives {
+            registry.define(definition)?;
+        }
+        Ok(registry)
+    }
+
+    /// Compiles and registers a custom objective.
+    ///
+    /// # Errors
+    /// Returns an error if the formula is invalid or the name is taken.
+    pub fn define(&mut self, definition: &ObjectiveDefinition) -> Result<(), RegistryError> {
+        let objective = definition.compile()?;
+        self.register(definition.name.trim(), Arc::new(objective))
+    }
+
+    /// Registers an objective under `name`.
+    ///
+    /// # Errors
+    /// Returns an error if the name is empty or already registered.
+    pub fn register(
+        &mut self,
+        name: &str,
+        objective: SharedObjective,
+    ) -> Result<(), RegistryError> {
+        if name.is_empty() {
+            return Err(RegistryError::EmptyName);
+        }
+        if self.objectives.contains_key(name) {
+            return Err(RegistryError::Duplicate(name.to_string()));
+        }
+        self.objectives.insert(name.to_string(), objective);
+        Ok(())
+    }
+
+    /// Returns the objective registered under `name`.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<SharedObjective> {
+        self.objectives.get(name).cloned()
+    }
+
+    /// Returns the registered names in alphabetical order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.objectives.keys().map(String::as_str)
+    }
+}
+
+/// Parses `formula` into terms.
+///
+/// A formula is a sum of terms separated by `+` or `-`. Each term is an
+/// optional weight followed by `*` and a metric name, with `|metric|` taking
+/// the absolute value: `0.7*fees - 0.3*|il| - drawdown`.
+fn parse_formula(formula: &str) -> Result<Vec<ObjectiveTerm>, String> {
+    let tokens = tokenize(formula)?;
+    if tokens.is_empty() {
+        return Err("formula is empty".to_string());
+    }
+
+    let mut terms = Vec::new();
+    let mut tokens = tokens.into_iter().peekable();
+    let mut first = true;
+    while tokens.peek().is_some() {
+        let sign = match tokens.peek() {
+            Some(Token::Plus) => {
+                tokens.next();
+                Decimal::ONE
+            }
+            Some(Token::Minus) => {
+                tokens.next();
+                Decimal::NEGATIVE_ONE
+            }
+            _ if first => Decimal::ONE,
+            Some(token) => return Err(format!("expected '+' or '-' before {}", token)),
+            None => unreachable!(),
+        };
+        first = false;
+
+        let weight = match tokens.peek() {
+            Some(Token::Number(weight)) => {
+                let weight = *weight;
+                tokens.next();
+                match tokens.next() {
+                    Some(Token::Star) => weight,
+                    Some(token) => {
+                        return Err(format!("expected '*' after {}, found {}", weight, token));
+                    }
+                    None => return Err(format!("expected a metric after {}", weight)),
+                }
+            }
+            _ => Decimal::ONE,
+        };
+
+        let (metric, absolute) = match tokens.next() {
+            Some(Token::Ident(name)) => (name, false),
+            Some(Token::Bar) => match (tokens.next(), tokens.next()) {
+                (Some(Token::Ident(name)), Some(Token::Bar)) => (name, true),
+                _ => return Err("expected a metric between '|' bars".to_string()),
+            },
+            Some(token) => return Err(format!("expected a metric, found {}", token)),
+            None => return Err("formula ends with an operator".to_string()),
+        };
+        terms.push(ObjectiveTerm {
+            metric: metric.parse()?,
+            weight: sign * weight,
+            absolute,
+        });
+    }
+    Ok(terms)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Number(Decimal),
+    Ident(String),
+    Plus,
+    Minus,
+    Star,
+    Bar,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(n) => write!(f, "'{}'", n),
+            Self::Ident(name) => write!(f, "'{}'", name),
+            Self::Plus => write!(f, "'+'"),
+            Self::Minus => write!(f, "'-'"),
+            Self::Star => write!(f, "'*'"),
+            Self::Bar => write!(f, "'|'"),
+        }
+    }
+}
+
+fn tokenize(formula: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = formula.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '+' | '-' | '*' | '|' => {
+                chars.next();
+                tokens.push(match c {
+                    '+' => Token::Plus,
+                    '-' => Token::Minus,
+                    '*' => Token::Star,
+                    _ => Token::Bar,
+                });
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                let mut number = String::new();
+                while let Some(&d) = chars.peek().filter(|d| d.is_ascii_digit() || **d == '.') {
+                    number.push(d);
+                    chars.next();
+                }
+                let value = Decimal::from_str(&number)
+                    .map_err(|_| format!("invalid number '{}'", number))?;
+                tokens.push(Token::Number(value));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut ident = String::new();
+                while let Some(&d) = chars
+                    .peek()
+                    .filter(|d| d.is_ascii_alphanumeric() || **d == '_')
+                {
+                    ident.push(d);
+                    chars.next();
+                }
+                tokens.push(Token::Ident(ident.to_ascii_lowercase()));
+            }
+            other => return Err(format!("unexpected character '{}'", other)),
+        }
+    }
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn result() -> SimulationResult {
+        SimulationResult {
+            final_position_value: dec!(1100),
+            total_fees_earned: dec!(50),
+            total_il: dec!(-20),
+            net_pnl: dec!(30),
+            max_drawdown: dec!(10),
+            time_in_range_percentage: dec!(75),
+            sharpe_ratio: None,
+        }
+    }
+
+    fn definition(name: &str, formula: &str) -> ObjectiveDefinition {
+        ObjectiveDefinition {
+            name: name.to_string(),
+            formula: formula.to_string(),
+            description: None,
+        }
+    }
+
+    #[test]
+    fn test_formula_compiles_to_weighted_sum() {
+        let objective = definition("fee_heavy", "0.7*fees - 0.3*|il| - drawdown")
+            .compile()
+            .unwrap();
+
+        assert_eq!(objective.label(), "fee_heavy");
+        assert_eq!(objective.terms().len(), 3);
+        assert!(objective.terms()[1].absolute);
+        // 0.7·50 − 0.3·20 − 10
+        assert_eq!(objective.evaluate(&result()), dec!(19));
+
+        let plain = definition("plain", "-il + 2 * sharpe + PnL")
+            .compile()
+            .unwrap();
+        assert_eq!(plain.evaluate(&result()), dec!(50));
+    }
+
+    #[test]
+    fn test_invalid_formulas_are_rejected() {
+        for formula in [
+            "", "0.7*", "fees +", "0.5 fees", "|il", "fees il", "apr", "fees / 2",
+        ] {
+            let err = definition("bad", formula).compile().unwrap_err();
+            assert!(
+                matches!(err, RegistryError::InvalidFormula { .. }),
+                "{} accepted",
+                formula
+            );
+        }
+        assert_eq!(
+            definition(" ", "fees").compile().unwrap_err(),
+            RegistryError::EmptyName
+        );
+    }
+
+    #[test]
+    fn test_registry_loads_config_alongside_builtins() {
+        let config = ObjectiveConfig {
+            objectives: vec![definition("fee_heavy", "0.7*fees - 0.3*|il| - drawdown")],
+        };
+        let registry = ObjectiveRegistry::from_config(&config).unwrap();
+
+        assert_eq!(registry.get("pnl").unwrap().evaluate(&result()), dec!(30));
+        assert_eq!(
+            registry.get("fee_heavy").unwrap().evaluate(&result()),
+            dec!(19)
+        );
+        assert!(registry.get("missing").is_none());
+        assert!(registry.names().any(|name| name == "fee_heavy"));
+
+        let clash = ObjectiveConfig {
+            objectives: vec![definition("fees", "fees")],
+        };
+        assert_eq!(
+            ObjectiveRegistry::from_config(&clash).unwrap_err(),
+            RegistryError::Duplicate("fees".to_string())
+        );
+    }
+}
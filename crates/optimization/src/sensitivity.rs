@@ -0,0 +1,170 @@
+//! Sensitivity analysis for recommended ranges.
+//!
+//! Shows how expected PnL, fees and IL move when the recommended bounds are
+//! nudged or the volatility estimate is off, so a recommendation that only
+//! works at exactly the optimized inputs can be spotted before deploying.
+
+use clmm_lp_domain::value_objects::price::Price;
+use clmm_lp_domain::value_objects::price_range::PriceRange;
+use rust_decimal::Decimal;
+use std::fmt;
+
+/// Relative changes applied to the distance of each bound from the price.
+pub const BOUND_PERTURBATIONS: [f64; 6] = [-0.20, -0.10, -0.05, 0.05, 0.10, 0.20];
+
+/// Relative changes applied to the volatility estimate.
+pub const VOLATILITY_PERTURBATIONS: [f64; 2] = [-0.25, 0.25];
+
+/// A change applied to the optimizer inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Perturbation {
+    /// The unmodified recommendation.
+    Baseline,
+    /// Bounds moved away from (positive) or towards (negative) the price.
+    Bounds(Decimal),
+    /// Volatility scaled by the given fraction.
+    Volatility(Decimal),
+}
+
+impl fmt::Display for Perturbation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pct = |v: &Decimal| (*v * Decimal::ONE_HUNDRED).normalize();
+        match self {
+            Self::Baseline => write!(f, "Baseline"),
+            Self::Bounds(v) => write!(f, "Bounds {:+}%", pct(v)),
+            Self::Volatility(v) => write!(f, "Volatility {:+}%", pct(v)),
+        }
+    }
+}
+
+/// Expected outcome under one perturbation.
+#[derive(Debug, Clone)]
+pub struct SensitivityPoint {
+    /// The applied perturbation.
+    pub perturbation: Perturbation,
+    /// The simulated range.
+    pub range: PriceRange,
+    /// The simulated annualized volatility.
+    pub volatility: f64,
+    /// Expected PnL.
+    pub expected_pnl: Decimal,
+    /// Expected fees.
+    pub expected_fees: Decimal,
+    /// Expected impermanent loss.
+    pub expected_il: Decimal,
+}
+
+/// Sensitivity of a recommendation to its inputs.
+#[derive(Debug, Clone)]
+pub struct SensitivityReport {
+    /// The recommendation re-simulated without perturbation.
+    pub baseline: SensitivityPoint,
+    /// Perturbed scenarios.
+    pub points: Vec<SensitivityPoint>,
+}
+
+impl SensitivityReport {
+    /// Returns the change in expected PnL of a point relative to the baseline.
+    #[must_use]
+    pub fn pnl_change(&self, point: &SensitivityPoint) -> Decimal {
+        point.expected_pnl - self.baseline.expected_pnl
+    }
+
+    /// Returns the largest PnL drop across all scenarios (zero if none is worse).
+    #[must_use]
+    pub fn worst_pnl_drop(&self) -> Decimal {
+        self.points
+            .iter()
+            .map(|p| self.pnl_change(p))
+            .min()
+            .unwrap_or(Decimal::ZERO)
+            .min(Decimal::ZERO)
+            .abs()
+    }
+
+    /// Returns true if any scenario loses more than `tolerance` of the baseline PnL.
+    ///
+    /// `tolerance` is a fraction, e.g. 0.5 = 50%.
+    #[must_use]
+    pub fn is_fragile(&self, tolerance: Decimal) -> bool {
+        let baseline = self.baseline.expected_pnl.abs();
+        if baseline.is_zero() {
+            return self.worst_pnl_drop() > Decimal::ZERO;
+        }
+        self.worst_pnl_drop() / baseline > tolerance
+    }
+}
+
+/// Moves both bounds away from (positive `pct`) or towards the price.
+///
+/// Returns `None` if the price is outside the range or the lower bound would
+/// not stay positive.
+#[must_use]
+pub fn perturb_bounds(range: &PriceRange, price: Decimal, pct: Decimal) -> Option<PriceRange> {
+    let lower = range.lower_price.value;
+    let upper = range.upper_price.value;
+    if price <= lower || price >= upper {
+        return None;
+    }
+
+    let scale = Decimal::ONE + pct;
+    let new_lower = price - (price - lower) * scale;
+    let new_upper = price + (upper - price) * scale;
+    if new_lower <= Decimal::ZERO || new_lower >= new_upper {
+        return None;
+    }
+
+    Some(PriceRange::new(
+        Price::new(new_lower),
+        Price::new(new_upper),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn point(perturbation: Perturbation, pnl: Decimal) -> SensitivityPoint {
+        SensitivityPoint {
+            perturbation,
+            range: PriceRange::new(Price::new(dec!(90)), Price::new(dec!(110))),
+            volatility: 0.5,
+            expected_pnl: pnl,
+            expected_fees: Decimal::ZERO,
+            expected_il: Decimal::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_perturb_bounds() {
+        let range = PriceRange::new(Price::new(dec!(90)), Price::new(dec!(120)));
+
+        let wider = perturb_bounds(&range, dec!(100), dec!(0.10)).unwrap();
+        assert_eq!(wider.lower_price.value, dec!(89));
+        assert_eq!(wider.upper_price.value, dec!(122));
+
+        let narrower = perturb_bounds(&range, dec!(100), dec!(-0.20)).unwrap();
+        assert_eq!(narrower.lower_price.value, dec!(92));
+        assert_eq!(narrower.upper_price.value, dec!(116));
+
+        assert!(perturb_bounds(&range, dec!(130), dec!(0.10)).is_none());
+        assert!(perturb_bounds(&range, dec!(100), dec!(-1)).is_none());
+    }
+
+    #[test]
+    fn test_report_fragility() {
+        let report = SensitivityReport {
+            baseline: point(Perturbation::Baseline, dec!(10)),
+            points: vec![
+                point(Perturbation::Bounds(dec!(0.05)), dec!(9)),
+                point(Perturbation::Volatility(dec!(0.25)), dec!(2)),
+            ],
+        };
+
+        assert_eq!(report.worst_pnl_drop(), dec!(8));
+        assert!(report.is_fragile(dec!(0.5)));
+        assert!(!report.is_fragile(dec!(0.9)));
+        assert_eq!(Perturbation::Bounds(dec!(-0.05)).to_string(), "Bounds -5%");
+    }
+}
@@ -0,0 +1,182 @@
+//! Kelly-criterion capital sizing.
+//!
+//! Optimizers assume all capital goes into the recommended range. Given the
+//! Monte Carlo return distribution of that range, the Kelly fraction is the
+//! share of capital that maximizes expected log growth, with the rest held
+//! back as cash. Fractional Kelly scales it down to trade growth for lower
+//! variance, and a cap bounds the deployed share.
+
+/// Bisection steps used to locate the Kelly fraction.
+const BISECTION_STEPS: usize = 64;
+
+/// Margin kept from the fraction at which the worst path loses everything.
+const RUIN_MARGIN: f64 = 0.999;
+
+/// Fractional Kelly sizing with a deployment cap.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KellySizing {
+    /// Multiple of the full Kelly fraction to deploy (0.5 = half Kelly).
+    pub kelly_multiplier: f64,
+    /// Maximum fraction of capital to deploy.
+    pub max_fraction: f64,
+}
+
+impl Default for KellySizing {
+    fn default() -> Self {
+        Self {
+            kelly_multiplier: 0.5,
+            max_fraction: 1.0,
+        }
+    }
+}
+
+impl KellySizing {
+    /// Creates a sizing policy; both inputs are clamped to `[0, 1]`.
+    #[must_use]
+    pub fn new(kelly_multiplier: f64, max_fraction: f64) -> Self {
+        Self {
+            kelly_multiplier: kelly_multiplier.clamp(0.0, 1.0),
+            max_fraction: max_fraction.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Sizes a position from the per-path returns of its range.
+    ///
+    /// Returns are fractions of deployed capital over `horizon_years`.
+    /// Returns `None` if there are no returns.
+    #[must_use]
+    pub fn size(&self, returns: &[f64], horizon_years: f64) -> Option<CapitalSizing> {
+        if returns.is_empty() {
+            return None;
+        }
+
+        let full_kelly = full_kelly_fraction(returns);
+        let fraction = (full_kelly * self.kelly_multiplier).min(self.max_fraction);
+        let growth_rate = expected_log_growth(returns, fraction).unwrap_or(0.0);
+        let annualize = |g: f64| {
+            if horizon_years > 0.0 {
+                g / horizon_years
+            } else {
+                g
+            }
+        };
+
+        Some(CapitalSizing {
+            full_kelly,
+            fraction,
+            growth_rate,
+            annualized_growth_rate: annualize(growth_rate),
+            full_deployment_growth_rate: expected_log_growth(returns, 1.0),
+        })
+    }
+}
+
+/// Recommended share of capital to deploy into a range.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CapitalSizing {
+    /// Growth-optimal fraction of capital, without leverage.
+    pub full_kelly: f64,
+    /// Recommended fraction after the Kelly multiplier and cap.
+    pub fraction: f64,
+    /// Expected log growth over the horizon at the recommended fraction.
+    pub growth_rate: f64,
+    /// Expected log growth per year at the recommended fraction.
+    pub annualized_growth_rate: f64,
+    /// Expected log growth over the horizon with all capital deployed;
+    /// `None` if some path loses all of it.
+    pub full_deployment_growth_rate: Option<f64>,
+}
+
+/// Returns the mean of `ln(1 + fraction * r)` over `returns`.
+///
+/// Returns `None` if any path would lose everything at that fraction.
+#[must_use]
+pub fn expected_log_growth(returns: &[f64], fraction: f64) -> Option<f64> {
+    if returns.is_empty() {
+        return None;
+    }
+    let mut total = 0.0;
+    for r in returns {
+        let wealth = 1.0 + fraction * r;
+        if wealth <= 0.0 {
+            return None;
+        }
+        total += wealth.ln();
+    }
+    Some(total / returns.len() as f64)
+}
+
+/// Returns the fraction in `[0, 1]` maximizing expected log growth.
+///
+/// Zero when the mean return is not positive; capped below the fraction at
+/// which the worst path would be wiped out.
+#[must_use]
+pub fn full_kelly_fraction(returns: &[f64]) -> f64 {
+    // Derivative of the expected log growth; decreasing in the fraction
+    let slope = |f: f64| returns.iter().map(|r| r / (1.0 + f * r)).sum::<f64>();
+
+    if returns.is_empty() || slope(0.0) <= 0.0 {
+        return 0.0;
+    }
+
+    let worst = returns.iter().copied().fold(0.0_f64, f64::min);
+    let mut upper = if worst < 0.0 {
+        (-RUIN_MARGIN / worst).min(1.0)
+    } else {
+        1.0
+    };
+    if slope(upper) >= 0.0 {
+        return upper;
+    }
+
+    let mut lower = 0.0;
+    for _ in 0..BISECTION_STEPS {
+        let mid = (lower + upper) / 2.0;
+        if slope(mid) > 0.0 {
+            lower = mid;
+        } else {
+            upper = mid;
+        }
+    }
+    (lower + upper) / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kelly_of_binary_bet() {
+        // Win 100% or lose 50% with equal odds: f* = p/a - q/b = 0.5/0.5 - 0.5/1 = 0.5
+        let returns = [1.0, -0.5];
+        assert!((full_kelly_fraction(&returns) - 0.5).abs() < 1e-9);
+
+        // Losing bets get nothing
+        assert_eq!(full_kelly_fraction(&[0.1, -0.2]), 0.0);
+        // Riskless gains deploy everything
+        assert_eq!(full_kelly_fraction(&[0.01, 0.02]), 1.0);
+    }
+
+    #[test]
+    fn test_fractional_kelly_with_cap() {
+        let returns = [1.0, -0.5];
+
+        let half = KellySizing::new(0.5, 1.0).size(&returns, 0.5).unwrap();
+        assert!((half.fraction - 0.25).abs() < 1e-9);
+        assert!(half.growth_rate > 0.0);
+        assert!((half.annualized_growth_rate - half.growth_rate * 2.0).abs() < 1e-12);
+        // Full deployment is past the Kelly fraction and grows slower
+        assert!(half.full_deployment_growth_rate.unwrap() < half.growth_rate);
+
+        let capped = KellySizing::new(1.0, 0.1).size(&returns, 1.0).unwrap();
+        assert!((capped.fraction - 0.1).abs() < 1e-12);
+
+        assert!(KellySizing::default().size(&[], 1.0).is_none());
+    }
+
+    #[test]
+    fn test_growth_is_undefined_at_ruin() {
+        assert_eq!(expected_log_growth(&[-1.0, 0.5], 1.0), None);
+        assert!(expected_log_growth(&[-1.0, 0.5], 0.5).is_some());
+    }
+}
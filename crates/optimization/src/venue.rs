@@ -0,0 +1,178 @@
+//! Venue comparison for a token pair.
+//!
+//! The same pair often trades in several pools: different protocols and
+//! fee tiers. A new position earns the fees of the volume that trades
+//! through its range, shared with the liquidity already there, and only
+//! while the price stays inside. Venues are therefore compared on the fee
+//! APR against the liquidity competing within the range, scaled by the
+//! expected share of time in range at the pool's volatility.
+
+use crate::pool_score::PoolMetrics;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+
+/// Seconds in a year, used to annualize volatility.
+const SECONDS_PER_YEAR: f64 = 365.0 * 86_400.0;
+
+/// Estimated yield of a range of a given width in one venue.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VenueEstimate {
+    /// Fee APR while the price stays in range.
+    pub fee_apr: Decimal,
+    /// Expected fraction of the horizon the price stays in range.
+    pub time_in_range: f64,
+    /// Fee APR weighted by the expected time in range.
+    pub expected_fee_apr: Decimal,
+}
+
+impl VenueEstimate {
+    /// Estimates the yield of a range `range_width` wide (0.1 = ±10%) held
+    /// for `horizon_days`, for a pool whose `tvl_usd` is the value of the
+    /// liquidity within that range.
+    #[must_use]
+    pub fn compute(metrics: &PoolMetrics, range_width: f64, horizon_days: f64) -> Self {
+        let fee_apr = metrics.fee_apr();
+        let time_in_range = time_in_range(range_width, metrics.volatility, horizon_days);
+        Self {
+            fee_apr,
+            time_in_range,
+            expected_fee_apr: fee_apr * Decimal::from_f64(time_in_range).unwrap_or_default(),
+        }
+    }
+}
+
+/// Expected fraction of `horizon_days` a price with annualized `volatility`
+/// spends within ±`range_width` of its start.
+///
+/// Uses the mean exit time of a driftless random walk from the middle of a
+/// band of log half-width `a`, `a² / σ²`, capped at the horizon.
+#[must_use]
+pub fn time_in_range(range_width: f64, volatility: f64, horizon_days: f64) -> f64 {
+    if range_width <= 0.0 || horizon_days <= 0.0 {
+        return 0.0;
+    }
+    if volatility <= 0.0 {
+        return 1.0;
+    }
+    let half_width = (1.0 + range_width).ln();
+    let exit_years = half_width.powi(2) / volatility.powi(2);
+    (exit_years * 365.0 / horizon_days).min(1.0)
+}
+
+/// Value, in raw token B units, of `liquidity` spread over ±`range_width`
+/// around the raw `price` (token B per token A).
+#[must_use]
+pub fn range_depth(liquidity: u128, price: f64, range_width: f64) -> f64 {
+    if price <= 0.0 || range_width <= 0.0 {
+        return 0.0;
+    }
+    let liquidity = liquidity as f64;
+    let sqrt_price = price.sqrt();
+    let sqrt_upper = (price * (1.0 + range_width)).sqrt();
+    let sqrt_lower = (price / (1.0 + range_width)).sqrt();
+    let amount_a = liquidity * (1.0 / sqrt_price - 1.0 / sqrt_upper);
+    let amount_b = liquidity * (sqrt_price - sqrt_lower);
+    amount_a * price + amount_b
+}
+
+/// Annualized volatility of log returns between consecutive
+/// `(timestamp, price)` points, oldest first.
+///
+/// Returns `None` with fewer than three points or no elapsed time.
+#[must_use]
+pub fn realized_volatility(points: &[(u64, Decimal)]) -> Option<f64> {
+    let returns: Vec<f64> = points
+        .windows(2)
+        .filter_map(|w| {
+            let (from, to) = (w[0].1.to_f64()?, w[1].1.to_f64()?);
+            (from > 0.0 && to > 0.0).then(|| (to / from).ln())
+        })
+        .collect();
+    if returns.len() < 2 {
+        return None;
+    }
+    let elapsed = points.last()?.0.checked_sub(points.first()?.0)? as f64;
+    if elapsed <= 0.0 {
+        return None;
+    }
+
+    let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+    let variance =
+        returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (returns.len() - 1) as f64;
+    let interval = elapsed / (points.len() - 1) as f64;
+    Some((variance * SECONDS_PER_YEAR / interval).sqrt())
+}
+
+/// Estimates every venue and sorts them best first by expected fee APR.
+#[must_use]
+pub fn rank_venues<K>(
+    venues: Vec<(K, PoolMetrics)>,
+    range_width: f64,
+    horizon_days: f64,
+) -> Vec<(K, VenueEstimate)> {
+    let mut ranked: Vec<(K, VenueEstimate)> = venues
+        .into_iter()
+        .map(|(key, metrics)| {
+            let estimate = VenueEstimate::compute(&metrics, range_width, horizon_days);
+            (key, estimate)
+        })
+        .collect();
+    ranked.sort_by_key(|(_, estimate)| std::cmp::Reverse(estimate.expected_fee_apr));
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_time_in_range_shrinks_with_volatility() {
+        // ±10% at 50% volatility is left after ~13 days on average
+        let narrow = time_in_range(0.1, 0.5, 30.0);
+        assert!((narrow - 0.442).abs() < 1e-3);
+        assert!(time_in_range(0.1, 0.25, 30.0) > narrow);
+        assert_eq!(time_in_range(1.0, 0.5, 30.0), 1.0);
+        assert_eq!(time_in_range(0.1, 0.0, 30.0), 1.0);
+    }
+
+    #[test]
+    fn test_range_depth_and_volatility() {
+        // At price 1, ±10% holds ~0.0465 of token A and ~0.0465 of token B per unit
+        let depth = range_depth(1_000_000, 1.0, 0.1);
+        assert!((depth - 93_074.0).abs() < 1.0);
+        assert_eq!(range_depth(1_000_000, 0.0, 0.1), 0.0);
+
+        let flat: Vec<(u64, Decimal)> = (0..10).map(|i| (i * 3600, dec!(100))).collect();
+        assert_eq!(realized_volatility(&flat), Some(0.0));
+        let choppy: Vec<(u64, Decimal)> = (0..10)
+            .map(|i| (i * 3600, if i % 2 == 0 { dec!(100) } else { dec!(101) }))
+            .collect();
+        assert!(realized_volatility(&choppy).unwrap() > 0.9);
+        assert_eq!(realized_volatility(&flat[..2]), None);
+    }
+
+    #[test]
+    fn test_rank_venues_prefers_fees_per_unit_of_depth() {
+        let volumes = vec![dec!(1_000_000); 7];
+        let ranked = rank_venues(
+            vec![
+                // 0.30% tier with deep liquidity
+                (
+                    "30bps",
+                    PoolMetrics::new(dec!(0.003), volumes.clone(), dec!(5_000_000), 0.5),
+                ),
+                // 0.05% tier with the same volume and thin liquidity
+                (
+                    "5bps",
+                    PoolMetrics::new(dec!(0.0005), volumes, dec!(200_000), 0.5),
+                ),
+            ],
+            0.1,
+            30.0,
+        );
+        assert_eq!(ranked[0].0, "5bps");
+        assert!(ranked[0].1.fee_apr > ranked[1].1.fee_apr);
+        assert_eq!(ranked[0].1.time_in_range, ranked[1].1.time_in_range);
+    }
+}
@@ -0,0 +1,130 @@
+//! WebAssembly bindings for running range optimization in a browser.
+//!
+//! Build with `--target wasm32-unknown-unknown --features wasm`. Rayon falls
+//! back to the calling thread on wasm, so runs are single-threaded; keep the
+//! iteration count modest for interactive use.
+
+use crate::constraints::PoolConstraints;
+use crate::objective::{MaximizeFees, MaximizeNetPnL, MaximizeSharpeRatio};
+use crate::range_optimizer::RangeOptimizer;
+use clmm_lp_domain::entities::position::Position;
+use clmm_lp_domain::metrics::sharpe::{Benchmark, SharpeConfig};
+use clmm_lp_domain::value_objects::amount::Amount;
+use clmm_lp_simulation::volume::ConstantVolume;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use wasm_bindgen::prelude::*;
+
+/// Range optimizer exposed to JavaScript.
+#[wasm_bindgen]
+pub struct WasmRangeOptimizer {
+    iterations: usize,
+    steps: usize,
+    time_step: f64,
+    sharpe: SharpeConfig,
+}
+
+#[wasm_bindgen]
+impl WasmRangeOptimizer {
+    /// Creates an optimizer running `iterations` paths of `steps` steps of
+    /// `time_step` years each.
+    #[wasm_bindgen(constructor)]
+    #[must_use]
+    pub fn new(iterations: usize, steps: usize, time_step: f64) -> Self {
+        Self {
+            iterations,
+            steps,
+            time_step,
+            sharpe: SharpeConfig::default(),
+        }
+    }
+
+    /// Sets the annual risk-free rate and the benchmark (`hodl`,
+    /// `fifty_fifty` or `zero`) Sharpe ratios are computed with; defaults
+    /// to 5% against `zero`.
+    #[wasm_bindgen(js_name = withSharpe)]
+    pub fn with_sharpe(mut self, risk_free_rate: f64, benchmark: &str) -> Result<Self, JsError> {
+        let benchmark: Benchmark = benchmark.parse().map_err(|e: String| JsError::new(&e))?;
+        self.sharpe = SharpeConfig::new(decimal(risk_free_rate, "risk_free_rate")?, benchmark);
+        Ok(self)
+    }
+
+    /// Finds the best range and returns the `OptimizationResult` as a JS
+    /// object.
+    ///
+    /// # Arguments
+    /// * `current_price` - Current pool price
+    /// * `volatility` - Annualized volatility
+    /// * `drift` - Annualized drift
+    /// * `volume` - Swap volume per step, in the quote token
+    /// * `pool_liquidity` - Active pool liquidity
+    /// * `fee_rate` - Pool fee rate as a fraction
+    /// * `objective` - One of `pnl`, `fees` or `sharpe`
+    #[allow(clippy::too_many_arguments)]
+    pub fn optimize(
+        &self,
+        current_price: f64,
+        volatility: f64,
+        drift: f64,
+        volume: f64,
+        pool_liquidity: f64,
+        fee_rate: f64,
+        objective: &str,
+    ) -> Result<JsValue, JsError> {
+        let current_price = decimal(current_price, "current_price")?;
+        let volume = ConstantVolume::new(decimal(volume, "volume")?);
+        let fee_rate = decimal(fee_rate, "fee_rate")?;
+        if !pool_liquidity.is_finite() || pool_liquidity < 0.0 {
+            return Err(JsError::new("pool_liquidity must be a non-negative number"));
+        }
+        // Saturating float-to-integer conversion; fractions are dropped
+        let pool_liquidity = pool_liquidity as u128;
+
+        let optimizer = RangeOptimizer::new(self.iterations, self.steps, self.time_step)
+            .with_pool_constraints(PoolConstraints::for_fee_tier(fee_rate))
+            .with_sharpe(self.sharpe);
+        let position = Position::new("wasm", "wasm", Amount::zero(9), Amount::zero(6), 0, 0);
+
+        let result = match objective {
+            "pnl" => optimizer.try_optimize(
+                position,
+                current_price,
+                volatility,
+                drift,
+                volume,
+                pool_liquidity,
+                fee_rate,
+                MaximizeNetPnL,
+            ),
+            "fees" => optimizer.try_optimize(
+                position,
+                current_price,
+                volatility,
+                drift,
+                volume,
+                pool_liquidity,
+                fee_rate,
+                MaximizeFees,
+            ),
+            "sharpe" => optimizer.try_optimize(
+                position,
+                current_price,
+                volatility,
+                drift,
+                volume,
+                pool_liquidity,
+                fee_rate,
+                MaximizeSharpeRatio::new(self.sharpe.risk_free_rate),
+            ),
+            other => return Err(JsError::new(&format!("Unknown objective: {other}"))),
+        }
+        .map_err(|e| JsError::new(&e.to_string()))?;
+
+        serde_wasm_bindgen::to_value(&result).map_err(|e| JsError::new(&e.to_string()))
+    }
+}
+
+/// Converts a JS number to a `Decimal`, naming the argument on failure.
+fn decimal(value: f64, name: &str) -> Result<Decimal, JsError> {
+    Decimal::from_f64(value).ok_or_else(|| JsError::new(&format!("{name} is not a valid number")))
+}
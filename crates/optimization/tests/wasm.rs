@@ -0,0 +1,50 @@
+//! Tests of the WebAssembly bindings, run in Node with `make wasm-test`.
+
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+
+use clmm_lp_domain::value_objects::optimization_result::OptimizationResult;
+use clmm_lp_optimization::wasm::WasmRangeOptimizer;
+use rust_decimal::Decimal;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn optimizer() -> WasmRangeOptimizer {
+    WasmRangeOptimizer::new(5, 10, 1.0 / 365.0)
+}
+
+#[wasm_bindgen_test]
+fn test_optimize_returns_a_range_around_the_price() {
+    let value = optimizer()
+        .optimize(100.0, 0.5, 0.0, 1_000_000.0, 1e9, 0.003, "pnl")
+        .unwrap();
+    let result: OptimizationResult = serde_wasm_bindgen::from_value(value).unwrap();
+
+    // Candidate ranges are centred on the current price
+    let range = result.recommended_range;
+    assert!(range.lower_price.value < Decimal::ONE_HUNDRED);
+    assert!(range.upper_price.value > Decimal::ONE_HUNDRED);
+}
+
+#[wasm_bindgen_test]
+fn test_invalid_arguments_are_errors() {
+    let optimizer = optimizer();
+    assert!(
+        optimizer
+            .optimize(100.0, 0.5, 0.0, 1_000_000.0, 1e9, 0.003, "volume")
+            .is_err()
+    );
+    assert!(
+        optimizer
+            .optimize(f64::NAN, 0.5, 0.0, 1_000_000.0, 1e9, 0.003, "pnl")
+            .is_err()
+    );
+    assert!(
+        optimizer
+            .optimize(100.0, 0.5, 0.0, 1_000_000.0, -1.0, 0.003, "pnl")
+            .is_err()
+    );
+    assert!(
+        WasmRangeOptimizer::new(5, 10, 0.01)
+            .with_sharpe(0.05, "moon")
+            .is_err()
+    );
+}
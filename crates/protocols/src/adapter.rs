@@ -0,0 +1,85 @@
+//! Protocol-independent access to CLMM positions and pools.
+
+use crate::events::{OnChainPosition, Protocol};
+use crate::orca::pool_reader::{RewardEmission, WhirlpoolState};
+use crate::raydium::pool_reader::RaydiumPoolState;
+use anyhow::Result;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use solana_sdk::pubkey::Pubkey;
+
+/// Pool state shared by all supported CLMM protocols.
+#[derive(Debug, Clone)]
+pub struct PoolSnapshot {
+    /// Pool address.
+    pub address: String,
+    /// Token A mint.
+    pub token_mint_a: Pubkey,
+    /// Token B mint.
+    pub token_mint_b: Pubkey,
+    /// Current tick index.
+    pub tick_current: i32,
+    /// Tick spacing.
+    pub tick_spacing: u16,
+    /// Current sqrt price (Q64.64).
+    pub sqrt_price: u128,
+    /// Current price (derived from sqrt_price).
+    pub price: Decimal,
+    /// Current liquidity.
+    pub liquidity: u128,
+    /// Fees of token A earned per unit of liquidity (Q64.64).
+    pub fee_growth_global_a: u128,
+    /// Fees of token B earned per unit of liquidity (Q64.64).
+    pub fee_growth_global_b: u128,
+    /// Active reward emissions.
+    pub rewards: Vec<RewardEmission>,
+}
+
+impl From<WhirlpoolState> for PoolSnapshot {
+    fn from(pool: WhirlpoolState) -> Self {
+        Self {
+            address: pool.address,
+            token_mint_a: pool.token_mint_a,
+            token_mint_b: pool.token_mint_b,
+            tick_current: pool.tick_current,
+            tick_spacing: pool.tick_spacing,
+            sqrt_price: pool.sqrt_price,
+            price: pool.price,
+            liquidity: pool.liquidity,
+            fee_growth_global_a: pool.fee_growth_global_a,
+            fee_growth_global_b: pool.fee_growth_global_b,
+            rewards: pool.rewards,
+        }
+    }
+}
+
+impl From<RaydiumPoolState> for PoolSnapshot {
+    fn from(pool: RaydiumPoolState) -> Self {
+        Self {
+            address: pool.address,
+            token_mint_a: pool.token_mint_a,
+            token_mint_b: pool.token_mint_b,
+            tick_current: pool.tick_current,
+            tick_spacing: pool.tick_spacing,
+            sqrt_price: pool.sqrt_price,
+            price: pool.price,
+            liquidity: pool.liquidity,
+            fee_growth_global_a: pool.fee_growth_global_a,
+            fee_growth_global_b: pool.fee_growth_global_b,
+            rewards: pool.rewards,
+        }
+    }
+}
+
+/// Reads positions and pools of one CLMM protocol.
+#[async_trait]
+pub trait ProtocolAdapter: Send + Sync {
+    /// Returns the protocol this adapter reads.
+    fn protocol(&self) -> Protocol;
+
+    /// Gets a position by its address.
+    async fn get_position(&self, position_address: &str) -> Result<OnChainPosition>;
+
+    /// Gets the current state of a pool.
+    async fn get_pool(&self, pool_address: &str) -> Result<PoolSnapshot>;
+}
@@ -0,0 +1,232 @@
+//! Executed amounts from confirmed transactions.
+//!
+//! Instruction builders only know the amounts we asked for: quotes, maximums
+//! and minimums. The token balances recorded in a confirmed transaction's
+//! metadata show what actually moved, so results are built from those.
+//! Simulations report the same balances before anything is sent.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use solana_client::rpc_response::RpcSimulateTransactionResult;
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status_client_types::option_serializer::OptionSerializer;
+use solana_transaction_status_client_types::{UiTransactionStatusMeta, UiTransactionTokenBalance};
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
+
+/// Change of one token balance held by the owner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenBalanceChange {
+    /// Token mint.
+    pub mint: Pubkey,
+    /// Raw balance before the transaction.
+    pub pre: u64,
+    /// Raw balance after the transaction.
+    pub post: u64,
+}
+
+impl TokenBalanceChange {
+    /// Returns the signed change; negative when tokens left the owner.
+    #[must_use]
+    pub fn delta(&self) -> i128 {
+        i128::from(self.post) - i128::from(self.pre)
+    }
+}
+
+/// Token amounts actually moved by a confirmed transaction.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutedAmounts {
+    /// Slot the transaction was confirmed in.
+    pub slot: u64,
+    /// Transaction fee paid in lamports.
+    pub fee_lamports: u64,
+    /// Changed token balances of the owner, one per mint.
+    pub token_changes: Vec<TokenBalanceChange>,
+}
+
+impl ExecutedAmounts {
+    /// Extracts the owner's token balance changes from transaction metadata.
+    ///
+    /// Accounts created or closed within the transaction count as a zero
+    /// balance on the side where they are missing.
+    ///
+    /// # Errors
+    /// Returns an error if a recorded mint or amount cannot be parsed.
+    pub fn from_meta(slot: u64, meta: &UiTransactionStatusMeta, owner: &Pubkey) -> Result<Self> {
+        let entries = |balances: &OptionSerializer<Vec<UiTransactionTokenBalance>>| match balances {
+            OptionSerializer::Some(balances) => balances
+                .iter()
+                .map(|b| {
+                    (
+                        b.mint.clone(),
+                        Option::from(b.owner.clone()),
+                        b.ui_token_amount.amount.clone(),
+                    )
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        let pre = entries(&meta.pre_token_balances);
+        let post = entries(&meta.post_token_balances);
+
+        Ok(Self {
+            slot,
+            fee_lamports: meta.fee,
+            token_changes: balance_changes(&pre, &post, &owner.to_string())?,
+        })
+    }
+
+    /// Extracts the owner's token balance changes a simulated transaction
+    /// would make.
+    ///
+    /// # Errors
+    /// Returns an error if a recorded mint or amount cannot be parsed.
+    pub fn from_simulation(
+        slot: u64,
+        result: &RpcSimulateTransactionResult,
+        owner: &Pubkey,
+    ) -> Result<Self> {
+        let entries = |balances: &Option<Vec<UiTransactionTokenBalance>>| {
+            balances
+                .iter()
+                .flatten()
+                .map(|b| {
+                    (
+                        b.mint.clone(),
+                        Option::from(b.owner.clone()),
+                        b.ui_token_amount.amount.clone(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        let pre = entries(&result.pre_token_balances);
+        let post = entries(&result.post_token_balances);
+
+        Ok(Self {
+            slot,
+            fee_lamports: result.fee.unwrap_or(0),
+            token_changes: balance_changes(&pre, &post, &owner.to_string())?,
+        })
+    }
+
+    /// Returns the signed change of a mint; zero if it did not change.
+    #[must_use]
+    pub fn delta(&self, mint: &Pubkey) -> i128 {
+        self.token_changes
+            .iter()
+            .find(|c| c.mint == *mint)
+            .map_or(0, TokenBalanceChange::delta)
+    }
+
+    /// Returns the amount of a mint that left the owner (deposits).
+    #[must_use]
+    pub fn sent(&self, mint: &Pubkey) -> u64 {
+        u64::try_from(-self.delta(mint)).unwrap_or(0)
+    }
+
+    /// Returns the amount of a mint the owner received (withdrawals,
+    /// collected fees and rewards).
+    #[must_use]
+    pub fn received(&self, mint: &Pubkey) -> u64 {
+        u64::try_from(self.delta(mint)).unwrap_or(0)
+    }
+}
+
+/// Diffs pre and post `(mint, owner, raw amount)` balances held by `owner`.
+///
+/// Balances of the same mint across several accounts are summed; unchanged
+/// mints are omitted.
+fn balance_changes(
+    pre: &[(String, Option<String>, String)],
+    post: &[(String, Option<String>, String)],
+    owner: &str,
+) -> Result<Vec<TokenBalanceChange>> {
+    let totals = |balances: &[(String, Option<String>, String)]| -> Result<BTreeMap<Pubkey, u64>> {
+        let mut totals = BTreeMap::new();
+        for (mint, holder, amount) in balances {
+            if holder.as_deref() != Some(owner) {
+                continue;
+            }
+            let mint = Pubkey::from_str(mint).context("Invalid mint in token balance")?;
+            let amount: u64 = amount.parse().context("Invalid token balance amount")?;
+            *totals.entry(mint).or_insert(0u64) += amount;
+        }
+        Ok(totals)
+    };
+    let pre = totals(pre)?;
+    let post = totals(post)?;
+
+    let mints: BTreeSet<&Pubkey> = pre.keys().chain(post.keys()).collect();
+    Ok(mints
+        .into_iter()
+        .map(|mint| TokenBalanceChange {
+            mint: *mint,
+            pre: pre.get(mint).copied().unwrap_or(0),
+            post: post.get(mint).copied().unwrap_or(0),
+        })
+        .filter(|change| change.pre != change.post)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn balance(mint: &Pubkey, owner: &Pubkey, amount: u64) -> (String, Option<String>, String) {
+        (
+            mint.to_string(),
+            Some(owner.to_string()),
+            amount.to_string(),
+        )
+    }
+
+    #[test]
+    fn test_balance_changes_of_owner() {
+        let owner = Pubkey::new_unique();
+        let vault = Pubkey::new_unique();
+        let mint_a = Pubkey::new_unique();
+        let mint_b = Pubkey::new_unique();
+        let mint_c = Pubkey::new_unique();
+
+        let pre = vec![
+            balance(&mint_a, &owner, 1_000),
+            balance(&mint_b, &owner, 500),
+            balance(&mint_c, &owner, 7),
+            balance(&mint_a, &vault, 1_000_000),
+        ];
+        let post = vec![
+            balance(&mint_a, &owner, 400),
+            // Temporary account of the owner, e.g. wrapped SOL
+            balance(&mint_b, &owner, 500),
+            balance(&mint_b, &owner, 25),
+            balance(&mint_c, &owner, 7),
+            balance(&mint_a, &vault, 1_000_600),
+        ];
+
+        let amounts = ExecutedAmounts {
+            slot: 1,
+            fee_lamports: 5_000,
+            token_changes: balance_changes(&pre, &post, &owner.to_string()).unwrap(),
+        };
+
+        // Unchanged mints and other owners are left out
+        assert_eq!(amounts.token_changes.len(), 2);
+        assert_eq!(amounts.sent(&mint_a), 600);
+        assert_eq!(amounts.received(&mint_a), 0);
+        assert_eq!(amounts.received(&mint_b), 25);
+        assert_eq!(amounts.delta(&mint_c), 0);
+    }
+
+    #[test]
+    fn test_closed_account_counts_as_zero() {
+        let owner = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+
+        let changes =
+            balance_changes(&[balance(&mint, &owner, 300)], &[], &owner.to_string()).unwrap();
+        assert_eq!(changes[0].delta(), -300);
+
+        let bad = vec![(mint.to_string(), Some(owner.to_string()), "abc".to_string())];
+        assert!(balance_changes(&bad, &[], &owner.to_string()).is_err());
+    }
+}
@@ -3,10 +3,12 @@
 //! This module provides functionality to fetch and parse on-chain events
 //! from CLMM protocol transactions.
 
+mod balances;
 mod fetcher;
 mod parser;
 mod types;
 
+pub use balances::*;
 pub use fetcher::*;
 pub use parser::*;
 pub use types::*;
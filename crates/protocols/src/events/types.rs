@@ -1,5 +1,6 @@
 //! Event types for CLMM protocols.
 
+use crate::orca::whirlpool::NUM_REWARDS;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
@@ -166,4 +167,16 @@ pub struct OnChainPosition {
     pub fees_owed_a: u64,
     /// Uncollected fees for token B.
     pub fees_owed_b: u64,
+    /// Reward growth checkpoints, one per reward slot.
+    pub reward_growth_inside: [u128; NUM_REWARDS],
+    /// Uncollected rewards, one per reward slot.
+    pub rewards_owed: [u64; NUM_REWARDS],
+}
+
+impl OnChainPosition {
+    /// Returns true if any reward slot has uncollected rewards.
+    #[must_use]
+    pub fn has_rewards_owed(&self) -> bool {
+        self.rewards_owed.iter().any(|owed| *owed > 0)
+    }
 }
@@ -8,14 +8,20 @@
 /// Prelude module for convenient imports.
 pub mod prelude;
 
+/// Protocol-independent position and pool access.
+pub mod adapter;
 /// Event fetching and parsing.
 pub mod events;
 /// Orca protocol adapter.
 pub mod orca;
 /// Data parsers.
 pub mod parsers;
+/// Deposit quotes.
+pub mod quote;
 /// Raydium protocol adapter.
 pub mod raydium;
+/// Token risk screening.
+pub mod risk;
 /// RPC provider with health checks and fallback.
 pub mod rpc;
 /// Solana client wrapper.
@@ -0,0 +1,505 @@
+//! Account resolution for Whirlpool instructions.
+//!
+//! Liquidity instructions need more than the pool and position addresses:
+//! the pool's token vaults, the owner's token accounts, the account holding
+//! the position NFT and the tick arrays bounding the range. This module
+//! reads the pool and position accounts, derives the rest, and finds which
+//! of the owner's token accounts still have to be created.
+
+use crate::orca::executor::{ASSOCIATED_TOKEN_PROGRAM_ID, SYSTEM_PROGRAM_ID, TOKEN_PROGRAM_ID};
+use crate::orca::position_reader::WhirlpoolPosition;
+use crate::orca::whirlpool::Whirlpool;
+use crate::rpc::RpcProvider;
+use anyhow::{Context, Result};
+use borsh::BorshDeserialize;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::debug;
+
+/// Ticks per Whirlpool tick array.
+pub const TICK_ARRAY_SIZE: i32 = 88;
+
+/// Returns the start index of the tick array containing `tick`.
+#[must_use]
+pub fn tick_array_start_index(tick: i32, tick_spacing: u16) -> i32 {
+    let ticks_per_array = i32::from(tick_spacing) * TICK_ARRAY_SIZE;
+    tick.div_euclid(ticks_per_array) * ticks_per_array
+}
+
+/// Derives the tick array starting at `start_index` in a Whirlpool.
+///
+/// Whirlpool seeds the start index as its decimal string, not its bytes.
+#[must_use]
+pub fn derive_tick_array(program_id: &Pubkey, whirlpool: &Pubkey, start_index: i32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"tick_array",
+            whirlpool.as_ref(),
+            start_index.to_string().as_bytes(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+/// Derives the three tick arrays a swap from `tick_current` walks through,
+/// in the direction of the swap.
+///
+/// Swaps from B to A start from the next tick, as the program does, so a
+/// price sitting on an array boundary starts in the array above it.
+#[must_use]
+pub fn derive_swap_tick_arrays(
+    program_id: &Pubkey,
+    whirlpool: &Pubkey,
+    tick_current: i32,
+    tick_spacing: u16,
+    a_to_b: bool,
+) -> [Pubkey; 3] {
+    let ticks_per_array = i32::from(tick_spacing) * TICK_ARRAY_SIZE;
+    let (shift, step) = if a_to_b {
+        (0, -ticks_per_array)
+    } else {
+        (i32::from(tick_spacing), ticks_per_array)
+    };
+    let start = tick_array_start_index(tick_current + shift, tick_spacing);
+    [0, 1, 2].map(|i| derive_tick_array(program_id, whirlpool, start + i * step))
+}
+
+/// Derives the price oracle of a Whirlpool.
+#[must_use]
+pub fn derive_oracle(program_id: &Pubkey, whirlpool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"oracle", whirlpool.as_ref()], program_id).0
+}
+
+/// Builds an instruction initializing the tick array starting at
+/// `start_index` in a Whirlpool, paid by `funder`.
+#[must_use]
+pub fn initialize_tick_array_instruction(
+    program_id: &Pubkey,
+    whirlpool: &Pubkey,
+    funder: &Pubkey,
+    start_index: i32,
+) -> Instruction {
+    // Whirlpool InitializeTickArray instruction discriminator
+    let discriminator: [u8; 8] = [11, 188, 193, 214, 141, 91, 149, 184];
+    let system_program = Pubkey::from_str(SYSTEM_PROGRAM_ID).expect("Invalid system program ID");
+
+    let mut data = Vec::with_capacity(12);
+    data.extend_from_slice(&discriminator);
+    data.extend_from_slice(&start_index.to_le_bytes());
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*whirlpool, false), // whirlpool
+            AccountMeta::new(*funder, true),              // funder
+            AccountMeta::new(derive_tick_array(program_id, whirlpool, start_index), false), // tick_array
+            AccountMeta::new_readonly(system_program, false), // system_program
+        ],
+        data,
+    }
+}
+
+/// Derives the associated token account of `owner` for an SPL Token mint.
+#[must_use]
+pub fn derive_ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    let token_program = Pubkey::from_str(TOKEN_PROGRAM_ID).expect("Invalid token program ID");
+    let ata_program =
+        Pubkey::from_str(ASSOCIATED_TOKEN_PROGRAM_ID).expect("Invalid ATA program ID");
+    Pubkey::find_program_address(
+        &[owner.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &ata_program,
+    )
+    .0
+}
+
+/// Builds an instruction creating `owner`'s associated token account for
+/// `mint`, which succeeds without effect if the account already exists.
+#[must_use]
+pub fn create_ata_idempotent_instruction(
+    payer: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    let token_program = Pubkey::from_str(TOKEN_PROGRAM_ID).expect("Invalid token program ID");
+    let system_program = Pubkey::from_str(SYSTEM_PROGRAM_ID).expect("Invalid system program ID");
+    Instruction {
+        program_id: Pubkey::from_str(ASSOCIATED_TOKEN_PROGRAM_ID).expect("Invalid ATA program ID"),
+        accounts: vec![
+            AccountMeta::new(*payer, true),                   // funding_account
+            AccountMeta::new(derive_ata(owner, mint), false), // associated_token_account
+            AccountMeta::new_readonly(*owner, false),         // wallet
+            AccountMeta::new_readonly(*mint, false),          // mint
+            AccountMeta::new_readonly(system_program, false), // system_program
+            AccountMeta::new_readonly(token_program, false),  // token_program
+        ],
+        // CreateIdempotent
+        data: vec![1],
+    }
+}
+
+/// Accounts of an initialized pool reward.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RewardAccounts {
+    /// Reward slot index.
+    pub index: u8,
+    /// Reward token mint.
+    pub mint: Pubkey,
+    /// Vault the reward is paid from.
+    pub vault: Pubkey,
+}
+
+/// Pool-level accounts of a Whirlpool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolAccounts {
+    /// Whirlpool address.
+    pub whirlpool: Pubkey,
+    /// Token A mint.
+    pub token_mint_a: Pubkey,
+    /// Token B mint.
+    pub token_mint_b: Pubkey,
+    /// Vault holding the pool's token A.
+    pub token_vault_a: Pubkey,
+    /// Vault holding the pool's token B.
+    pub token_vault_b: Pubkey,
+    /// Tick spacing.
+    pub tick_spacing: u16,
+    /// Initialized rewards.
+    pub rewards: Vec<RewardAccounts>,
+}
+
+impl PoolAccounts {
+    /// Collects the accounts of the Whirlpool at `address`.
+    #[must_use]
+    pub fn from_whirlpool(address: Pubkey, whirlpool: &Whirlpool) -> Self {
+        let rewards = whirlpool
+            .reward_infos
+            .iter()
+            .enumerate()
+            .filter(|(_, reward)| reward.is_initialized())
+            .map(|(index, reward)| RewardAccounts {
+                index: index as u8,
+                mint: reward.mint,
+                vault: reward.vault,
+            })
+            .collect();
+        Self {
+            whirlpool: address,
+            token_mint_a: whirlpool.token_mint_a,
+            token_mint_b: whirlpool.token_mint_b,
+            token_vault_a: whirlpool.token_vault_a,
+            token_vault_b: whirlpool.token_vault_b,
+            tick_spacing: whirlpool.tick_spacing,
+            rewards,
+        }
+    }
+}
+
+/// Every account a position's liquidity, fee, reward and close
+/// instructions refer to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiquidityAccounts {
+    /// Pool accounts.
+    pub pool: PoolAccounts,
+    /// Position address.
+    pub position: Pubkey,
+    /// Mint of the position NFT.
+    pub position_mint: Pubkey,
+    /// Owner's account holding the position NFT.
+    pub position_token_account: Pubkey,
+    /// Liquidity currently in the position; zero for a new one.
+    pub liquidity: u128,
+    /// Tick array holding the lower tick.
+    pub tick_array_lower: Pubkey,
+    /// Tick array holding the upper tick.
+    pub tick_array_upper: Pubkey,
+    /// Owner's token A account.
+    pub token_owner_account_a: Pubkey,
+    /// Owner's token B account.
+    pub token_owner_account_b: Pubkey,
+}
+
+impl LiquidityAccounts {
+    /// Derives the accounts of a position over `[tick_lower, tick_upper]`
+    /// owned by `owner`.
+    #[must_use]
+    pub fn derive(
+        program_id: &Pubkey,
+        pool: PoolAccounts,
+        position: Pubkey,
+        position_mint: Pubkey,
+        (tick_lower, tick_upper): (i32, i32),
+        owner: &Pubkey,
+    ) -> Self {
+        let tick_array = |tick: i32| {
+            derive_tick_array(
+                program_id,
+                &pool.whirlpool,
+                tick_array_start_index(tick, pool.tick_spacing),
+            )
+        };
+        Self {
+            position,
+            position_mint,
+            position_token_account: derive_ata(owner, &position_mint),
+            liquidity: 0,
+            tick_array_lower: tick_array(tick_lower),
+            tick_array_upper: tick_array(tick_upper),
+            token_owner_account_a: derive_ata(owner, &pool.token_mint_a),
+            token_owner_account_b: derive_ata(owner, &pool.token_mint_b),
+            pool,
+        }
+    }
+
+    /// Mints the owner needs token accounts for to deposit into or
+    /// withdraw everything from the position.
+    #[must_use]
+    pub fn owner_mints(&self) -> Vec<Pubkey> {
+        let mut mints = vec![self.pool.token_mint_a, self.pool.token_mint_b];
+        for reward in &self.pool.rewards {
+            if !mints.contains(&reward.mint) {
+                mints.push(reward.mint);
+            }
+        }
+        mints
+    }
+}
+
+/// Reads Whirlpool and position accounts to resolve instruction accounts.
+pub struct AccountResolver {
+    /// RPC provider for blockchain interaction.
+    provider: Arc<RpcProvider>,
+    /// Whirlpool program ID.
+    program_id: Pubkey,
+}
+
+impl AccountResolver {
+    /// Creates a resolver for the provider's cluster.
+    pub fn new(provider: Arc<RpcProvider>) -> Self {
+        Self {
+            program_id: provider.cluster().whirlpool_program_id(),
+            provider,
+        }
+    }
+
+    /// Reads the pool-level accounts of a Whirlpool.
+    pub async fn pool_accounts(&self, whirlpool: &Pubkey) -> Result<PoolAccounts> {
+        let account = self.provider.get_account(whirlpool).await?;
+        let state = Whirlpool::deserialize(&mut account.data.as_slice())
+            .context("Failed to deserialize Whirlpool account")?;
+        Ok(PoolAccounts::from_whirlpool(*whirlpool, &state))
+    }
+
+    /// Reads a position and its pool, and derives the accounts its
+    /// instructions need when signed by `owner`.
+    pub async fn liquidity_accounts(
+        &self,
+        position: &Pubkey,
+        owner: &Pubkey,
+    ) -> Result<LiquidityAccounts> {
+        let account = self.provider.get_account(position).await?;
+        let state = WhirlpoolPosition::try_from_slice(&account.data)
+            .context("Failed to deserialize position account")?;
+        let pool = self.pool_accounts(&state.whirlpool).await?;
+
+        let mut accounts = LiquidityAccounts::derive(
+            &self.program_id,
+            pool,
+            *position,
+            state.position_mint,
+            (state.tick_lower_index, state.tick_upper_index),
+            owner,
+        );
+        accounts.liquidity = state.liquidity;
+
+        debug!(
+            position = %position,
+            whirlpool = %accounts.pool.whirlpool,
+            tick_array_lower = %accounts.tick_array_lower,
+            tick_array_upper = %accounts.tick_array_upper,
+            "Resolved position accounts"
+        );
+        Ok(accounts)
+    }
+
+    /// Builds instructions initializing the tick arrays a range over
+    /// `[tick_lower, tick_upper]` needs that do not exist yet, paid by
+    /// `payer`.
+    ///
+    /// A range crossing tick array boundaries needs both the array holding
+    /// its lower tick and the one holding its upper tick; a range within
+    /// one array needs just that one.
+    pub async fn initialize_missing_tick_arrays(
+        &self,
+        payer: &Pubkey,
+        pool: &PoolAccounts,
+        (tick_lower, tick_upper): (i32, i32),
+    ) -> Result<Vec<Instruction>> {
+        let mut starts = vec![tick_array_start_index(tick_lower, pool.tick_spacing)];
+        let upper_start = tick_array_start_index(tick_upper, pool.tick_spacing);
+        if upper_start != starts[0] {
+            starts.push(upper_start);
+        }
+        let addresses: Vec<Pubkey> = starts
+            .iter()
+            .map(|&start| derive_tick_array(&self.program_id, &pool.whirlpool, start))
+            .collect();
+        let existing = self.provider.get_multiple_accounts(&addresses).await?;
+
+        Ok(starts
+            .into_iter()
+            .zip(existing)
+            .filter(|(_, account)| account.is_none())
+            .map(|(start, _)| {
+                debug!(
+                    whirlpool = %pool.whirlpool,
+                    start_index = start,
+                    "Initializing missing tick array"
+                );
+                initialize_tick_array_instruction(&self.program_id, &pool.whirlpool, payer, start)
+            })
+            .collect())
+    }
+
+    /// Builds instructions creating the token accounts `owner` lacks for
+    /// `mints`, paid by `payer`.
+    pub async fn create_missing_atas(
+        &self,
+        payer: &Pubkey,
+        owner: &Pubkey,
+        mints: &[Pubkey],
+    ) -> Result<Vec<Instruction>> {
+        let atas: Vec<Pubkey> = mints.iter().map(|mint| derive_ata(owner, mint)).collect();
+        let existing = self.provider.get_multiple_accounts(&atas).await?;
+
+        Ok(mints
+            .iter()
+            .zip(existing)
+            .filter(|(_, account)| account.is_none())
+            .map(|(mint, _)| {
+                debug!(owner = %owner, mint = %mint, "Creating missing token account");
+                create_ata_idempotent_instruction(payer, owner, mint)
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orca::executor::WHIRLPOOL_PROGRAM_ID;
+
+    fn pool() -> PoolAccounts {
+        PoolAccounts {
+            whirlpool: Pubkey::new_unique(),
+            token_mint_a: Pubkey::new_unique(),
+            token_mint_b: Pubkey::new_unique(),
+            token_vault_a: Pubkey::new_unique(),
+            token_vault_b: Pubkey::new_unique(),
+            tick_spacing: 64,
+            rewards: vec![RewardAccounts {
+                index: 0,
+                mint: Pubkey::new_unique(),
+                vault: Pubkey::new_unique(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_tick_array_start_index() {
+        // 64 * 88 = 5632 ticks per array
+        assert_eq!(tick_array_start_index(0, 64), 0);
+        assert_eq!(tick_array_start_index(5631, 64), 0);
+        assert_eq!(tick_array_start_index(5632, 64), 5632);
+        assert_eq!(tick_array_start_index(-1, 64), -5632);
+    }
+
+    #[test]
+    fn test_swap_tick_arrays_follow_direction() {
+        let program_id = Pubkey::from_str(WHIRLPOOL_PROGRAM_ID).unwrap();
+        let whirlpool = Pubkey::new_unique();
+        let array = |start| derive_tick_array(&program_id, &whirlpool, start);
+
+        let down = derive_swap_tick_arrays(&program_id, &whirlpool, 100, 64, true);
+        assert_eq!(down, [array(0), array(-5632), array(-11264)]);
+
+        let up = derive_swap_tick_arrays(&program_id, &whirlpool, 100, 64, false);
+        assert_eq!(up, [array(0), array(5632), array(11264)]);
+
+        // Just below a boundary, a swap up starts in the next array
+        let up = derive_swap_tick_arrays(&program_id, &whirlpool, 5600, 64, false);
+        assert_eq!(up[0], array(5632));
+    }
+
+    #[test]
+    fn test_derives_position_accounts() {
+        let program_id = Pubkey::from_str(WHIRLPOOL_PROGRAM_ID).unwrap();
+        let pool = pool();
+        let owner = Pubkey::new_unique();
+        let position_mint = Pubkey::new_unique();
+
+        let accounts = LiquidityAccounts::derive(
+            &program_id,
+            pool.clone(),
+            Pubkey::new_unique(),
+            position_mint,
+            (-100, 6000),
+            &owner,
+        );
+
+        assert_eq!(
+            accounts.tick_array_lower,
+            derive_tick_array(&program_id, &pool.whirlpool, -5632)
+        );
+        assert_eq!(
+            accounts.tick_array_upper,
+            derive_tick_array(&program_id, &pool.whirlpool, 5632)
+        );
+        assert_ne!(accounts.tick_array_lower, accounts.tick_array_upper);
+        assert_eq!(
+            accounts.position_token_account,
+            derive_ata(&owner, &position_mint)
+        );
+        assert_eq!(
+            accounts.token_owner_account_a,
+            derive_ata(&owner, &pool.token_mint_a)
+        );
+        assert_eq!(accounts.owner_mints().len(), 3);
+    }
+
+    #[test]
+    fn test_initialize_tick_array_instruction() {
+        let program_id = Pubkey::from_str(WHIRLPOOL_PROGRAM_ID).unwrap();
+        let (whirlpool, funder) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let ix = initialize_tick_array_instruction(&program_id, &whirlpool, &funder, -5632);
+
+        // Anchor discriminator: first 8 bytes of sha256("global:<name>")
+        let discriminator = solana_sdk::hash::hashv(&[b"global:initialize_tick_array"]);
+        assert_eq!(ix.data[..8], discriminator.to_bytes()[..8]);
+        assert_eq!(ix.data[8..], (-5632i32).to_le_bytes());
+        assert_eq!(ix.accounts.len(), 4);
+        assert!(ix.accounts[1].is_signer);
+        assert_eq!(
+            ix.accounts[2].pubkey,
+            derive_tick_array(&program_id, &whirlpool, -5632)
+        );
+        assert!(ix.accounts[2].is_writable);
+    }
+
+    #[test]
+    fn test_create_ata_idempotent_instruction() {
+        let (payer, owner, mint) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let ix = create_ata_idempotent_instruction(&payer, &owner, &mint);
+
+        assert_eq!(ix.data, vec![1]);
+        assert_eq!(ix.accounts.len(), 6);
+        assert!(ix.accounts[0].is_signer);
+        assert_eq!(ix.accounts[1].pubkey, derive_ata(&owner, &mint));
+    }
+}
@@ -0,0 +1,43 @@
+//! Orca Whirlpool implementation of [`ProtocolAdapter`].
+
+use super::pool_reader::WhirlpoolReader;
+use super::position_reader::PositionReader;
+use crate::adapter::{PoolSnapshot, ProtocolAdapter};
+use crate::events::{OnChainPosition, Protocol};
+use crate::rpc::RpcProvider;
+use anyhow::Result;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Reads Orca Whirlpool positions and pools.
+pub struct OrcaAdapter {
+    /// Whirlpool reader.
+    pool_reader: WhirlpoolReader,
+    /// Position reader.
+    position_reader: PositionReader,
+}
+
+impl OrcaAdapter {
+    /// Creates a new Orca adapter.
+    pub fn new(provider: Arc<RpcProvider>) -> Self {
+        Self {
+            pool_reader: WhirlpoolReader::new(provider.clone()),
+            position_reader: PositionReader::new(provider),
+        }
+    }
+}
+
+#[async_trait]
+impl ProtocolAdapter for OrcaAdapter {
+    fn protocol(&self) -> Protocol {
+        Protocol::OrcaWhirlpool
+    }
+
+    async fn get_position(&self, position_address: &str) -> Result<OnChainPosition> {
+        self.position_reader.get_position(position_address).await
+    }
+
+    async fn get_pool(&self, pool_address: &str) -> Result<PoolSnapshot> {
+        Ok(self.pool_reader.get_pool_state(pool_address).await?.into())
+    }
+}
@@ -1,23 +1,39 @@
 //! Whirlpool executor for on-chain operations.
 //!
 //! Provides functionality to execute LP operations on Orca Whirlpools:
-//! - Open positions
+//! - Open positions (two-sided or single-sided)
 //! - Increase/decrease liquidity
-//! - Collect fees
+//! - Collect fees and rewards
 //! - Close positions
-
-use crate::rpc::RpcProvider;
+//! - Swap in a pool
+//! - Build and simulate complete rebalance bundles
+//!
+//! Instruction accounts are resolved by [`AccountResolver`]. Token
+//! accounts the owner lacks, and tick arrays a new range needs, are created
+//! ahead of the instructions using them.
+
+use crate::events::ExecutedAmounts;
+use crate::orca::accounts::{
+    AccountResolver, LiquidityAccounts, PoolAccounts, RewardAccounts, derive_ata, derive_oracle,
+    derive_swap_tick_arrays,
+};
+use crate::orca::math::{MAX_TICK_INDEX, MIN_TICK_INDEX, tick_to_sqrt_price_x64};
+use crate::orca::pool_reader::WhirlpoolReader;
+use crate::orca::position_reader::WhirlpoolPosition;
+use crate::quote::{Quote, quote_deposit};
+use crate::rpc::{RpcProvider, TransactionSender};
 use anyhow::{Context, Result};
+use borsh::BorshDeserialize;
 use solana_sdk::{
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
-    signature::Signature,
+    signature::{Keypair, Signature},
     signer::Signer,
     transaction::Transaction,
 };
 use std::str::FromStr;
 use std::sync::Arc;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 /// Orca Whirlpool program ID (mainnet).
 pub const WHIRLPOOL_PROGRAM_ID: &str = "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc";
@@ -32,20 +48,15 @@ pub const ASSOCIATED_TOKEN_PROGRAM_ID: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25e
 pub const SYSTEM_PROGRAM_ID: &str = "11111111111111111111111111111111";
 
 /// Parameters for opening a new position.
+///
+/// The deposit is fixed by a [`Quote`], built with [`quote_deposit`] from
+/// token amounts or with [`crate::quote::quote_capital`] from a USD budget.
 #[derive(Debug, Clone)]
 pub struct OpenPositionParams {
     /// Pool address.
     pub pool: Pubkey,
-    /// Lower tick bound.
-    pub tick_lower: i32,
-    /// Upper tick bound.
-    pub tick_upper: i32,
-    /// Amount of token A to deposit.
-    pub amount_a: u64,
-    /// Amount of token B to deposit.
-    pub amount_b: u64,
-    /// Slippage tolerance in basis points.
-    pub slippage_bps: u16,
+    /// Range, liquidity and token bounds of the deposit.
+    pub quote: Quote,
 }
 
 /// Parameters for increasing liquidity.
@@ -78,6 +89,74 @@ pub struct DecreaseLiquidityParams {
     pub token_min_b: u64,
 }
 
+/// Parameters for swapping an exact input amount.
+#[derive(Debug, Clone)]
+pub struct SwapParams {
+    /// Pool address.
+    pub pool: Pubkey,
+    /// Whether token A is sold for token B.
+    pub a_to_b: bool,
+    /// Amount of the sold token.
+    pub amount_in: u64,
+    /// Minimum amount of the bought token.
+    pub min_amount_out: u64,
+}
+
+/// Parameters for moving a position's liquidity to a new range.
+#[derive(Debug, Clone)]
+pub struct RebalanceBundleParams {
+    /// Position to close.
+    pub position: Pubkey,
+    /// Pool address.
+    pub pool: Pubkey,
+    /// Lower tick bound of the new position.
+    pub new_tick_lower: i32,
+    /// Upper tick bound of the new position.
+    pub new_tick_upper: i32,
+    /// Token A expected back from the old position, redeposited.
+    pub amount_a: u64,
+    /// Token B expected back from the old position, redeposited.
+    pub amount_b: u64,
+    /// Slippage tolerance in basis points.
+    pub slippage_bps: u16,
+}
+
+/// Complete instruction sequence of a rebalance, for one transaction.
+#[derive(Debug, Clone)]
+pub struct RebalanceBundle {
+    /// Collect, withdraw, close, open and deposit instructions, in order.
+    pub instructions: Vec<Instruction>,
+    /// Position being closed.
+    pub old_position: Pubkey,
+    /// Position being opened.
+    pub new_position: Pubkey,
+    /// Mint of the new position; co-signs the bundle's transaction.
+    pub position_mint: Arc<Keypair>,
+    /// Token A mint of the pool.
+    pub token_mint_a: Pubkey,
+    /// Token B mint of the pool.
+    pub token_mint_b: Pubkey,
+    /// Deposit into the new position.
+    pub quote: Quote,
+}
+
+/// What a simulated rebalance bundle would do.
+#[derive(Debug, Clone)]
+pub struct BundleSimulation {
+    /// Simulation error, if the bundle would fail.
+    pub error: Option<String>,
+    /// Program logs.
+    pub logs: Vec<String>,
+    /// Compute units consumed.
+    pub units_consumed: Option<u64>,
+    /// Token balance changes of the owner.
+    pub amounts: ExecutedAmounts,
+    /// Whether the old position account would be closed.
+    pub old_position_closed: bool,
+    /// Liquidity of the new position afterwards, if it would exist.
+    pub new_position_liquidity: Option<u128>,
+}
+
 /// Result of an execution operation.
 #[derive(Debug, Clone)]
 pub struct ExecutionResult {
@@ -89,6 +168,8 @@ pub struct ExecutionResult {
     pub slot: Option<u64>,
     /// Error message if failed.
     pub error: Option<String>,
+    /// Token amounts the transaction actually moved, if they could be read.
+    pub amounts: Option<ExecutedAmounts>,
 }
 
 impl ExecutionResult {
@@ -100,6 +181,26 @@ impl ExecutionResult {
             success: true,
             slot: Some(slot),
             error: None,
+            amounts: None,
+        }
+    }
+
+    /// Creates a successful result for a confirmed transaction, with the
+    /// amounts it moved for `owner` read back from the chain.
+    ///
+    /// Falls back to the current slot and no amounts if the transaction
+    /// cannot be fetched.
+    pub async fn confirmed(provider: &RpcProvider, signature: Signature, owner: &Pubkey) -> Self {
+        match provider.get_executed_amounts(&signature, owner).await {
+            Ok(amounts) => Self {
+                amounts: Some(amounts.clone()),
+                ..Self::success(signature, amounts.slot)
+            },
+            Err(e) => {
+                warn!(signature = %signature, error = %e, "Failed to read executed amounts");
+                let slot = provider.get_slot().await.unwrap_or(0);
+                Self::success(signature, slot)
+            }
         }
     }
 
@@ -111,6 +212,7 @@ impl ExecutionResult {
             success: false,
             slot: None,
             error: Some(error),
+            amounts: None,
         }
     }
 }
@@ -119,6 +221,10 @@ impl ExecutionResult {
 pub struct WhirlpoolExecutor {
     /// RPC provider for blockchain interaction.
     provider: Arc<RpcProvider>,
+    /// Sends signed transactions and waits for their confirmation.
+    sender: Arc<dyn TransactionSender>,
+    /// Resolves the accounts instructions refer to.
+    resolver: AccountResolver,
     /// Whirlpool program ID.
     program_id: Pubkey,
     /// Token program ID.
@@ -130,11 +236,13 @@ pub struct WhirlpoolExecutor {
 }
 
 impl WhirlpoolExecutor {
-    /// Creates a new WhirlpoolExecutor.
+    /// Creates a new WhirlpoolExecutor for the provider's cluster.
     pub fn new(provider: Arc<RpcProvider>) -> Self {
         Self {
+            program_id: provider.cluster().whirlpool_program_id(),
+            resolver: AccountResolver::new(provider.clone()),
+            sender: provider.clone(),
             provider,
-            program_id: Pubkey::from_str(WHIRLPOOL_PROGRAM_ID).expect("Invalid program ID"),
             token_program: Pubkey::from_str(TOKEN_PROGRAM_ID).expect("Invalid token program ID"),
             ata_program: Pubkey::from_str(ASSOCIATED_TOKEN_PROGRAM_ID)
                 .expect("Invalid ATA program ID"),
@@ -142,54 +250,91 @@ impl WhirlpoolExecutor {
         }
     }
 
+    /// Sends transactions through `sender` instead of the RPC provider.
+    #[must_use]
+    pub fn with_sender(mut self, sender: Arc<dyn TransactionSender>) -> Self {
+        self.sender = sender;
+        self
+    }
+
     /// Opens a new position in a Whirlpool.
     ///
+    /// Mints the quoted liquidity, spending no more than the quote's
+    /// maximum token amounts.
+    ///
+    /// Tick arrays the range needs that do not exist yet are initialized,
+    /// and token accounts the payer lacks for the pool's tokens are
+    /// created, in the same transaction.
+    ///
+    /// Whirlpool positions are owned through an NFT; a new mint is
+    /// generated and co-signs the transaction.
+    ///
     /// # Arguments
     /// * `params` - Position parameters
     /// * `payer` - Transaction payer and position owner
     ///
     /// # Returns
-    /// Execution result with transaction signature.
+    /// The position address along with the execution result.
     pub async fn open_position<S: Signer>(
         &self,
         params: &OpenPositionParams,
         payer: &S,
-    ) -> Result<ExecutionResult> {
+    ) -> Result<(Pubkey, ExecutionResult)> {
+        let quote = &params.quote;
         info!(
             pool = %params.pool,
-            tick_lower = params.tick_lower,
-            tick_upper = params.tick_upper,
+            tick_lower = quote.tick_lower,
+            tick_upper = quote.tick_upper,
+            side = ?quote.side,
+            liquidity = quote.liquidity,
             "Opening new position"
         );
 
-        // Derive position mint PDA
-        let position_mint =
-            self.derive_position_mint(&params.pool, params.tick_lower, params.tick_upper)?;
+        let owner = payer.pubkey();
+        let pool = self.resolver.pool_accounts(&params.pool).await?;
 
-        // Derive position PDA
-        let (position_pda, _bump) =
-            Pubkey::find_program_address(&[b"position", position_mint.as_ref()], &self.program_id);
+        let position_mint = Keypair::new();
+        let position = self.position_address(&position_mint.pubkey());
 
-        // Build open position instruction
-        let open_ix = self.build_open_position_instruction(
-            params,
-            &payer.pubkey(),
-            &position_mint,
-            &position_pda,
-        )?;
+        let accounts = LiquidityAccounts::derive(
+            &self.program_id,
+            pool,
+            position,
+            position_mint.pubkey(),
+            (quote.tick_lower, quote.tick_upper),
+            &owner,
+        );
 
-        // Build increase liquidity instruction
-        let increase_ix = self.build_increase_liquidity_instruction(
-            &position_pda,
-            &params.pool,
-            &payer.pubkey(),
-            params.amount_a,
-            params.amount_b,
-        )?;
+        let mut instructions = self
+            .resolver
+            .initialize_missing_tick_arrays(
+                &owner,
+                &accounts.pool,
+                (quote.tick_lower, quote.tick_upper),
+            )
+            .await?;
+        instructions.extend(
+            self.resolver
+                .create_missing_atas(
+                    &owner,
+                    &owner,
+                    &[accounts.pool.token_mint_a, accounts.pool.token_mint_b],
+                )
+                .await?,
+        );
+        instructions.push(self.build_open_position_instruction(params, &owner, &accounts)?);
+        instructions.push(self.build_increase_liquidity_instruction(
+            &accounts,
+            &owner,
+            quote.liquidity,
+            quote.token_max_a,
+            quote.token_max_b,
+        ));
 
-        // Create and send transaction
-        let instructions = vec![open_ix, increase_ix];
-        self.send_transaction(&instructions, payer).await
+        let result = self
+            .send_transaction(&instructions, payer, &[&position_mint])
+            .await?;
+        Ok((position, result))
     }
 
     /// Increases liquidity in an existing position.
@@ -204,15 +349,27 @@ impl WhirlpoolExecutor {
             "Increasing liquidity"
         );
 
-        let ix = self.build_increase_liquidity_instruction(
-            &params.position,
-            &params.pool,
-            &payer.pubkey(),
+        let owner = payer.pubkey();
+        let accounts = self
+            .resolve_position(&params.position, &params.pool, &owner)
+            .await?;
+        let mut instructions = self
+            .resolver
+            .create_missing_atas(
+                &owner,
+                &owner,
+                &[accounts.pool.token_mint_a, accounts.pool.token_mint_b],
+            )
+            .await?;
+        instructions.push(self.build_increase_liquidity_instruction(
+            &accounts,
+            &owner,
+            params.liquidity_amount,
             params.token_max_a,
             params.token_max_b,
-        )?;
+        ));
 
-        self.send_transaction(&[ix], payer).await
+        self.send_transaction(&instructions, payer, &[]).await
     }
 
     /// Decreases liquidity from an existing position.
@@ -227,19 +384,32 @@ impl WhirlpoolExecutor {
             "Decreasing liquidity"
         );
 
-        let ix = self.build_decrease_liquidity_instruction(
-            &params.position,
-            &params.pool,
-            &payer.pubkey(),
+        let owner = payer.pubkey();
+        let accounts = self
+            .resolve_position(&params.position, &params.pool, &owner)
+            .await?;
+        let mut instructions = self
+            .resolver
+            .create_missing_atas(
+                &owner,
+                &owner,
+                &[accounts.pool.token_mint_a, accounts.pool.token_mint_b],
+            )
+            .await?;
+        instructions.push(self.build_decrease_liquidity_instruction(
+            &accounts,
+            &owner,
             params.liquidity_amount,
             params.token_min_a,
             params.token_min_b,
-        )?;
+        ));
 
-        self.send_transaction(&[ix], payer).await
+        self.send_transaction(&instructions, payer, &[]).await
     }
 
     /// Collects fees from a position.
+    ///
+    /// Rewards emitted by the pool are claimed in the same transaction.
     pub async fn collect_fees<S: Signer>(
         &self,
         position: &Pubkey,
@@ -248,12 +418,46 @@ impl WhirlpoolExecutor {
     ) -> Result<ExecutionResult> {
         info!(position = %position, "Collecting fees");
 
-        let ix = self.build_collect_fees_instruction(position, pool, &payer.pubkey())?;
+        let owner = payer.pubkey();
+        let accounts = self.resolve_position(position, pool, &owner).await?;
+        let mut instructions = self
+            .resolver
+            .create_missing_atas(&owner, &owner, &accounts.owner_mints())
+            .await?;
+        instructions.push(self.build_collect_fees_instruction(&accounts, &owner));
+        instructions.extend(self.build_collect_reward_instructions(&accounts, &owner));
+
+        self.send_transaction(&instructions, payer, &[]).await
+    }
+
+    /// Collects all pool rewards owed to a position.
+    pub async fn collect_rewards<S: Signer>(
+        &self,
+        position: &Pubkey,
+        pool: &Pubkey,
+        payer: &S,
+    ) -> Result<ExecutionResult> {
+        info!(position = %position, "Collecting rewards");
 
-        self.send_transaction(&[ix], payer).await
+        let owner = payer.pubkey();
+        let accounts = self.resolve_position(position, pool, &owner).await?;
+        if accounts.pool.rewards.is_empty() {
+            anyhow::bail!("Pool {} has no active rewards", pool);
+        }
+        let reward_mints: Vec<Pubkey> = accounts.pool.rewards.iter().map(|r| r.mint).collect();
+        let mut instructions = self
+            .resolver
+            .create_missing_atas(&owner, &owner, &reward_mints)
+            .await?;
+        instructions.extend(self.build_collect_reward_instructions(&accounts, &owner));
+
+        self.send_transaction(&instructions, payer, &[]).await
     }
 
     /// Closes a position.
+    ///
+    /// Withdraws the remaining liquidity, fees and rewards first, since a
+    /// Whirlpool position can only be closed once empty.
     pub async fn close_position<S: Signer>(
         &self,
         position: &Pubkey,
@@ -262,24 +466,165 @@ impl WhirlpoolExecutor {
     ) -> Result<ExecutionResult> {
         info!(position = %position, "Closing position");
 
-        // First decrease all liquidity
-        let decrease_ix = self.build_decrease_liquidity_instruction(
-            position,
-            pool,
-            &payer.pubkey(),
-            u128::MAX, // All liquidity
-            0,         // Min token A
-            0,         // Min token B
+        let owner = payer.pubkey();
+        let accounts = self.resolve_position(position, pool, &owner).await?;
+        let mut instructions = self
+            .resolver
+            .create_missing_atas(&owner, &owner, &accounts.owner_mints())
+            .await?;
+        instructions.extend(self.build_withdraw_and_close_instructions(&accounts, &owner));
+
+        self.send_transaction(&instructions, payer, &[]).await
+    }
+
+    /// Sells exactly `amount_in` of one of the pool's tokens for at least
+    /// `min_amount_out` of the other.
+    ///
+    /// The price may move as far as the pool's tick arrays allow; the
+    /// minimum output is the slippage bound.
+    pub async fn swap<S: Signer>(&self, params: &SwapParams, payer: &S) -> Result<ExecutionResult> {
+        info!(
+            pool = %params.pool,
+            a_to_b = params.a_to_b,
+            amount_in = params.amount_in,
+            min_amount_out = params.min_amount_out,
+            "Swapping"
+        );
+
+        let owner = payer.pubkey();
+        let pool = self.resolver.pool_accounts(&params.pool).await?;
+        let tick_current = WhirlpoolReader::new(self.provider.clone())
+            .get_current_tick(&params.pool.to_string())
+            .await?;
+        let mut instructions = self
+            .resolver
+            .create_missing_atas(&owner, &owner, &[pool.token_mint_a, pool.token_mint_b])
+            .await?;
+        instructions.push(self.build_swap_instruction(params, &pool, tick_current, &owner));
+
+        self.send_transaction(&instructions, payer, &[]).await
+    }
+
+    /// Builds the complete instruction sequence of a rebalance: withdraw
+    /// everything, collect fees and rewards, close the position, then open
+    /// the new range and deposit the quoted liquidity.
+    ///
+    /// Token accounts the owner lacks, and tick arrays the new range needs
+    /// that do not exist yet, are created first.
+    pub async fn build_rebalance_bundle(
+        &self,
+        params: &RebalanceBundleParams,
+        owner: &Pubkey,
+    ) -> Result<RebalanceBundle> {
+        let pool_state = WhirlpoolReader::new(self.provider.clone())
+            .get_pool_state(&params.pool.to_string())
+            .await
+            .context("Failed to fetch pool state")?;
+
+        let quote = quote_deposit(
+            &pool_state,
+            params.new_tick_lower,
+            params.new_tick_upper,
+            params.amount_a,
+            params.amount_b,
+            params.slippage_bps,
         )?;
 
-        // Collect any remaining fees
-        let collect_ix = self.build_collect_fees_instruction(position, pool, &payer.pubkey())?;
+        let open_params = OpenPositionParams {
+            pool: params.pool,
+            quote: quote.clone(),
+        };
+        let position_mint = Keypair::new();
+        let new_position = self.position_address(&position_mint.pubkey());
+
+        let old = self
+            .resolve_position(&params.position, &params.pool, owner)
+            .await?;
+        let new = LiquidityAccounts::derive(
+            &self.program_id,
+            old.pool.clone(),
+            new_position,
+            position_mint.pubkey(),
+            (params.new_tick_lower, params.new_tick_upper),
+            owner,
+        );
+
+        let mut instructions = self
+            .resolver
+            .create_missing_atas(owner, owner, &old.owner_mints())
+            .await?;
+        instructions.extend(
+            self.resolver
+                .initialize_missing_tick_arrays(
+                    owner,
+                    &new.pool,
+                    (params.new_tick_lower, params.new_tick_upper),
+                )
+                .await?,
+        );
+        instructions.extend(self.build_withdraw_and_close_instructions(&old, owner));
+        instructions.push(self.build_open_position_instruction(&open_params, owner, &new)?);
+        instructions.push(self.build_increase_liquidity_instruction(
+            &new,
+            owner,
+            quote.liquidity,
+            quote.token_max_a,
+            quote.token_max_b,
+        ));
+
+        Ok(RebalanceBundle {
+            instructions,
+            old_position: params.position,
+            new_position,
+            position_mint: Arc::new(position_mint),
+            token_mint_a: pool_state.token_mint_a,
+            token_mint_b: pool_state.token_mint_b,
+            quote,
+        })
+    }
 
-        // Close the position
-        let close_ix = self.build_close_position_instruction(position, &payer.pubkey())?;
+    /// Simulates a rebalance bundle as one transaction paid by `owner`,
+    /// reading back the token changes and both positions.
+    pub async fn simulate_rebalance_bundle(
+        &self,
+        bundle: &RebalanceBundle,
+        owner: &Pubkey,
+    ) -> Result<BundleSimulation> {
+        debug!(
+            instructions = bundle.instructions.len(),
+            "Simulating rebalance bundle"
+        );
 
-        let instructions = vec![decrease_ix, collect_ix, close_ix];
-        self.send_transaction(&instructions, payer).await
+        let transaction = Transaction::new_with_payer(&bundle.instructions, Some(owner));
+        let result = self
+            .provider
+            .simulate_transaction_with_accounts(
+                &transaction,
+                &[bundle.old_position, bundle.new_position],
+            )
+            .await?;
+
+        let account = |index: usize| {
+            result
+                .accounts
+                .as_ref()
+                .and_then(|accounts| accounts.get(index).cloned().flatten())
+        };
+        let old_position_closed = account(0).is_none_or(|a| a.lamports == 0);
+        let new_position_liquidity = account(1)
+            .and_then(|a| a.data.decode())
+            .and_then(|data| WhirlpoolPosition::try_from_slice(&data).ok())
+            .map(|position| position.liquidity);
+        let slot = self.provider.get_slot().await.unwrap_or(0);
+
+        Ok(BundleSimulation {
+            error: result.err.as_ref().map(ToString::to_string),
+            logs: result.logs.clone().unwrap_or_default(),
+            units_consumed: result.units_consumed,
+            amounts: ExecutedAmounts::from_simulation(slot, &result, owner)?,
+            old_position_closed,
+            new_position_liquidity,
+        })
     }
 
     /// Simulates a transaction without broadcasting.
@@ -323,53 +668,68 @@ impl WhirlpoolExecutor {
 
     // Private helper methods
 
-    fn derive_position_mint(
+    /// Resolves a position's accounts, checking it belongs to `pool`.
+    async fn resolve_position(
         &self,
+        position: &Pubkey,
         pool: &Pubkey,
-        tick_lower: i32,
-        tick_upper: i32,
-    ) -> Result<Pubkey> {
-        let (mint, _bump) = Pubkey::find_program_address(
-            &[
-                b"position_mint",
-                pool.as_ref(),
-                &tick_lower.to_le_bytes(),
-                &tick_upper.to_le_bytes(),
-            ],
-            &self.program_id,
-        );
-        Ok(mint)
+        owner: &Pubkey,
+    ) -> Result<LiquidityAccounts> {
+        let accounts = self
+            .resolver
+            .liquidity_accounts(position, owner)
+            .await
+            .context("Failed to resolve position accounts")?;
+        if accounts.pool.whirlpool != *pool {
+            anyhow::bail!(
+                "Position {} belongs to pool {}, not {}",
+                position,
+                accounts.pool.whirlpool,
+                pool
+            );
+        }
+        Ok(accounts)
+    }
+
+    /// Derives the position owned through `position_mint`.
+    fn position_address(&self, position_mint: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"position", position_mint.as_ref()], &self.program_id).0
     }
 
     fn build_open_position_instruction(
         &self,
         params: &OpenPositionParams,
         owner: &Pubkey,
-        position_mint: &Pubkey,
-        position: &Pubkey,
+        accounts: &LiquidityAccounts,
     ) -> Result<Instruction> {
         // Whirlpool OpenPosition instruction discriminator
         let discriminator: [u8; 8] = [0x87, 0x80, 0x2f, 0x4d, 0x0f, 0x98, 0xf0, 0x31];
 
-        let mut data = Vec::with_capacity(24);
-        data.extend_from_slice(&discriminator);
-        data.extend_from_slice(&params.tick_lower.to_le_bytes());
-        data.extend_from_slice(&params.tick_upper.to_le_bytes());
+        let (position, position_bump) = Pubkey::find_program_address(
+            &[b"position", accounts.position_mint.as_ref()],
+            &self.program_id,
+        );
+        if position != accounts.position {
+            anyhow::bail!("Position {} does not match its mint", accounts.position);
+        }
 
-        // Derive position token account
-        let position_token_account = self.derive_ata(owner, position_mint)?;
+        let mut data = Vec::with_capacity(17);
+        data.extend_from_slice(&discriminator);
+        data.push(position_bump);
+        data.extend_from_slice(&params.quote.tick_lower.to_le_bytes());
+        data.extend_from_slice(&params.quote.tick_upper.to_le_bytes());
 
         let accounts = vec![
-            AccountMeta::new(*owner, true),                        // funder
-            AccountMeta::new_readonly(*owner, false),              // owner
-            AccountMeta::new(*position, false),                    // position
-            AccountMeta::new(*position_mint, true),                // position_mint
-            AccountMeta::new(position_token_account, false),       // position_token_account
-            AccountMeta::new_readonly(params.pool, false),         // whirlpool
-            AccountMeta::new_readonly(self.token_program, false),  // token_program
-            AccountMeta::new_readonly(self.system_program, false), // system_program
+            AccountMeta::new(*owner, true),                           // funder
+            AccountMeta::new_readonly(*owner, false),                 // owner
+            AccountMeta::new(accounts.position, false),               // position
+            AccountMeta::new(accounts.position_mint, true),           // position_mint
+            AccountMeta::new(accounts.position_token_account, false), // position_token_account
+            AccountMeta::new_readonly(params.pool, false),            // whirlpool
+            AccountMeta::new_readonly(self.token_program, false),     // token_program
+            AccountMeta::new_readonly(self.system_program, false),    // system_program
             AccountMeta::new_readonly(solana_sdk::sysvar::rent::ID, false), // rent
-            AccountMeta::new_readonly(self.ata_program, false),    // associated_token_program
+            AccountMeta::new_readonly(self.ata_program, false),       // associated_token_program
         ];
 
         Ok(Instruction {
@@ -379,48 +739,59 @@ impl WhirlpoolExecutor {
         })
     }
 
+    /// Accounts shared by the increase and decrease liquidity instructions.
+    fn liquidity_account_metas(
+        &self,
+        accounts: &LiquidityAccounts,
+        owner: &Pubkey,
+    ) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(accounts.pool.whirlpool, false), // whirlpool
+            AccountMeta::new_readonly(self.token_program, false), // token_program
+            AccountMeta::new_readonly(*owner, true),          // position_authority
+            AccountMeta::new(accounts.position, false),       // position
+            AccountMeta::new_readonly(accounts.position_token_account, false), // position_token_account
+            AccountMeta::new(accounts.token_owner_account_a, false), // token_owner_account_a
+            AccountMeta::new(accounts.token_owner_account_b, false), // token_owner_account_b
+            AccountMeta::new(accounts.pool.token_vault_a, false),    // token_vault_a
+            AccountMeta::new(accounts.pool.token_vault_b, false),    // token_vault_b
+            AccountMeta::new(accounts.tick_array_lower, false),      // tick_array_lower
+            AccountMeta::new(accounts.tick_array_upper, false),      // tick_array_upper
+        ]
+    }
+
     fn build_increase_liquidity_instruction(
         &self,
-        position: &Pubkey,
-        pool: &Pubkey,
+        accounts: &LiquidityAccounts,
         owner: &Pubkey,
+        liquidity_amount: u128,
         token_max_a: u64,
         token_max_b: u64,
-    ) -> Result<Instruction> {
+    ) -> Instruction {
         // Whirlpool IncreaseLiquidity instruction discriminator
-        let discriminator: [u8; 8] = [0x2e, 0x9c, 0xf3, 0x76, 0x0d, 0xc6, 0x1e, 0x84];
+        let discriminator: [u8; 8] = [0x2e, 0x9c, 0xf3, 0x76, 0x0d, 0xcd, 0xfb, 0xb2];
 
         let mut data = Vec::with_capacity(40);
         data.extend_from_slice(&discriminator);
-        data.extend_from_slice(&0u128.to_le_bytes()); // liquidity_amount (calculated by program)
+        data.extend_from_slice(&liquidity_amount.to_le_bytes());
         data.extend_from_slice(&token_max_a.to_le_bytes());
         data.extend_from_slice(&token_max_b.to_le_bytes());
 
-        let accounts = vec![
-            AccountMeta::new(*pool, false),                       // whirlpool
-            AccountMeta::new_readonly(self.token_program, false), // token_program
-            AccountMeta::new_readonly(*owner, true),              // position_authority
-            AccountMeta::new(*position, false),                   // position
-                                                                  // Additional accounts would be derived from pool state
-                                                                  // token_owner_account_a, token_owner_account_b, token_vault_a, token_vault_b, tick_array_lower, tick_array_upper
-        ];
-
-        Ok(Instruction {
+        Instruction {
             program_id: self.program_id,
-            accounts,
+            accounts: self.liquidity_account_metas(accounts, owner),
             data,
-        })
+        }
     }
 
     fn build_decrease_liquidity_instruction(
         &self,
-        position: &Pubkey,
-        pool: &Pubkey,
+        accounts: &LiquidityAccounts,
         owner: &Pubkey,
         liquidity_amount: u128,
         token_min_a: u64,
         token_min_b: u64,
-    ) -> Result<Instruction> {
+    ) -> Instruction {
         // Whirlpool DecreaseLiquidity instruction discriminator
         let discriminator: [u8; 8] = [0xa0, 0x26, 0xd0, 0x6f, 0x68, 0x5b, 0x2c, 0x01];
 
@@ -430,83 +801,184 @@ impl WhirlpoolExecutor {
         data.extend_from_slice(&token_min_a.to_le_bytes());
         data.extend_from_slice(&token_min_b.to_le_bytes());
 
-        let accounts = vec![
-            AccountMeta::new(*pool, false),                       // whirlpool
-            AccountMeta::new_readonly(self.token_program, false), // token_program
-            AccountMeta::new_readonly(*owner, true),              // position_authority
-            AccountMeta::new(*position, false),                   // position
-                                                                  // Additional accounts derived from pool state
-        ];
-
-        Ok(Instruction {
+        Instruction {
             program_id: self.program_id,
-            accounts,
+            accounts: self.liquidity_account_metas(accounts, owner),
             data,
-        })
+        }
     }
 
     fn build_collect_fees_instruction(
         &self,
-        position: &Pubkey,
-        pool: &Pubkey,
+        accounts: &LiquidityAccounts,
         owner: &Pubkey,
-    ) -> Result<Instruction> {
+    ) -> Instruction {
         // Whirlpool CollectFees instruction discriminator
-        let discriminator: [u8; 8] = [0xa4, 0x98, 0xcf, 0x63, 0x1e, 0xba, 0x13, 0x7a];
+        let discriminator: [u8; 8] = [0xa4, 0x98, 0xcf, 0x63, 0x1e, 0xba, 0x13, 0xb6];
+
+        Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(accounts.pool.whirlpool, false), // whirlpool
+                AccountMeta::new_readonly(*owner, true),                   // position_authority
+                AccountMeta::new(accounts.position, false),                // position
+                AccountMeta::new_readonly(accounts.position_token_account, false), // position_token_account
+                AccountMeta::new(accounts.token_owner_account_a, false), // token_owner_account_a
+                AccountMeta::new(accounts.pool.token_vault_a, false),    // token_vault_a
+                AccountMeta::new(accounts.token_owner_account_b, false), // token_owner_account_b
+                AccountMeta::new(accounts.pool.token_vault_b, false),    // token_vault_b
+                AccountMeta::new_readonly(self.token_program, false),    // token_program
+            ],
+            data: discriminator.to_vec(),
+        }
+    }
 
-        let data = discriminator.to_vec();
+    /// Builds one collect reward instruction per active pool reward.
+    fn build_collect_reward_instructions(
+        &self,
+        accounts: &LiquidityAccounts,
+        owner: &Pubkey,
+    ) -> Vec<Instruction> {
+        accounts
+            .pool
+            .rewards
+            .iter()
+            .map(|reward| self.build_collect_reward_instruction(accounts, owner, reward))
+            .collect()
+    }
 
-        let accounts = vec![
-            AccountMeta::new(*pool, false),          // whirlpool
-            AccountMeta::new_readonly(*owner, true), // position_authority
-            AccountMeta::new(*position, false),      // position
-            AccountMeta::new_readonly(self.token_program, false), // token_program
-                                                     // Additional accounts: token_owner_account_a, token_owner_account_b, token_vault_a, token_vault_b
-        ];
+    fn build_collect_reward_instruction(
+        &self,
+        accounts: &LiquidityAccounts,
+        owner: &Pubkey,
+        reward: &RewardAccounts,
+    ) -> Instruction {
+        // Whirlpool CollectReward instruction discriminator
+        let discriminator: [u8; 8] = [0x46, 0x05, 0x84, 0x57, 0x56, 0xeb, 0xb1, 0x22];
 
-        Ok(Instruction {
+        let mut data = Vec::with_capacity(9);
+        data.extend_from_slice(&discriminator);
+        data.push(reward.index);
+
+        Instruction {
             program_id: self.program_id,
-            accounts,
+            accounts: vec![
+                AccountMeta::new_readonly(accounts.pool.whirlpool, false), // whirlpool
+                AccountMeta::new_readonly(*owner, true),                   // position_authority
+                AccountMeta::new(accounts.position, false),                // position
+                AccountMeta::new_readonly(accounts.position_token_account, false), // position_token_account
+                AccountMeta::new(derive_ata(owner, &reward.mint), false), // reward_owner_account
+                AccountMeta::new(reward.vault, false),                    // reward_vault
+                AccountMeta::new_readonly(self.token_program, false),     // token_program
+            ],
             data,
-        })
+        }
     }
 
     fn build_close_position_instruction(
         &self,
-        position: &Pubkey,
+        accounts: &LiquidityAccounts,
         owner: &Pubkey,
-    ) -> Result<Instruction> {
+    ) -> Instruction {
         // Whirlpool ClosePosition instruction discriminator
-        let discriminator: [u8; 8] = [0x7b, 0x86, 0x51, 0x0c, 0x31, 0x5b, 0xfc, 0x00];
+        let discriminator: [u8; 8] = [0x7b, 0x86, 0x51, 0x00, 0x31, 0x44, 0x62, 0x62];
 
-        let data = discriminator.to_vec();
+        Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(*owner, true), // position_authority
+                AccountMeta::new(*owner, false),         // receiver
+                AccountMeta::new(accounts.position, false), // position
+                AccountMeta::new(accounts.position_mint, false), // position_mint
+                AccountMeta::new(accounts.position_token_account, false), // position_token_account
+                AccountMeta::new_readonly(self.token_program, false), // token_program
+            ],
+            data: discriminator.to_vec(),
+        }
+    }
 
-        let accounts = vec![
-            AccountMeta::new_readonly(*owner, true), // position_authority
-            AccountMeta::new(*owner, false),         // receiver
-            AccountMeta::new(*position, false),      // position
-                                                     // position_mint, position_token_account, token_program
-        ];
+    fn build_swap_instruction(
+        &self,
+        params: &SwapParams,
+        pool: &PoolAccounts,
+        tick_current: i32,
+        owner: &Pubkey,
+    ) -> Instruction {
+        // Whirlpool Swap instruction discriminator
+        let discriminator: [u8; 8] = [0xf8, 0xc6, 0x9e, 0x91, 0xe1, 0x75, 0x87, 0xc8];
+
+        // No price limit beyond the slippage bound on the output
+        let sqrt_price_limit = if params.a_to_b {
+            tick_to_sqrt_price_x64(MIN_TICK_INDEX)
+        } else {
+            tick_to_sqrt_price_x64(MAX_TICK_INDEX)
+        };
+
+        let mut data = Vec::with_capacity(42);
+        data.extend_from_slice(&discriminator);
+        data.extend_from_slice(&params.amount_in.to_le_bytes());
+        data.extend_from_slice(&params.min_amount_out.to_le_bytes());
+        data.extend_from_slice(&sqrt_price_limit.to_le_bytes());
+        data.push(1); // amount_specified_is_input
+        data.push(u8::from(params.a_to_b));
 
-        Ok(Instruction {
+        let tick_arrays = derive_swap_tick_arrays(
+            &self.program_id,
+            &pool.whirlpool,
+            tick_current,
+            pool.tick_spacing,
+            params.a_to_b,
+        );
+
+        Instruction {
             program_id: self.program_id,
-            accounts,
+            accounts: vec![
+                AccountMeta::new_readonly(self.token_program, false), // token_program
+                AccountMeta::new_readonly(*owner, true),              // token_authority
+                AccountMeta::new(pool.whirlpool, false),              // whirlpool
+                AccountMeta::new(derive_ata(owner, &pool.token_mint_a), false), // token_owner_account_a
+                AccountMeta::new(pool.token_vault_a, false),                    // token_vault_a
+                AccountMeta::new(derive_ata(owner, &pool.token_mint_b), false), // token_owner_account_b
+                AccountMeta::new(pool.token_vault_b, false),                    // token_vault_b
+                AccountMeta::new(tick_arrays[0], false),                        // tick_array_0
+                AccountMeta::new(tick_arrays[1], false),                        // tick_array_1
+                AccountMeta::new(tick_arrays[2], false),                        // tick_array_2
+                AccountMeta::new(derive_oracle(&self.program_id, &pool.whirlpool), false), // oracle
+            ],
             data,
-        })
+        }
     }
 
-    fn derive_ata(&self, owner: &Pubkey, mint: &Pubkey) -> Result<Pubkey> {
-        let (ata, _bump) = Pubkey::find_program_address(
-            &[owner.as_ref(), self.token_program.as_ref(), mint.as_ref()],
-            &self.ata_program,
-        );
-        Ok(ata)
+    /// Withdraws all liquidity, collects fees and rewards, and closes.
+    ///
+    /// Whirlpool rejects a zero-liquidity decrease, so an already empty
+    /// position skips it.
+    fn build_withdraw_and_close_instructions(
+        &self,
+        accounts: &LiquidityAccounts,
+        owner: &Pubkey,
+    ) -> Vec<Instruction> {
+        let mut instructions = Vec::new();
+        if accounts.liquidity > 0 {
+            instructions.push(self.build_decrease_liquidity_instruction(
+                accounts,
+                owner,
+                accounts.liquidity,
+                0,
+                0,
+            ));
+        }
+        instructions.push(self.build_collect_fees_instruction(accounts, owner));
+        instructions.extend(self.build_collect_reward_instructions(accounts, owner));
+        instructions.push(self.build_close_position_instruction(accounts, owner));
+        instructions
     }
 
     async fn send_transaction<S: Signer>(
         &self,
         instructions: &[Instruction],
         payer: &S,
+        co_signers: &[&Keypair],
     ) -> Result<ExecutionResult> {
         let recent_blockhash = self
             .provider
@@ -514,25 +986,24 @@ impl WhirlpoolExecutor {
             .await
             .context("Failed to get recent blockhash")?;
 
-        let transaction = Transaction::new_signed_with_payer(
-            instructions,
-            Some(&payer.pubkey()),
-            &[payer],
-            recent_blockhash,
-        );
+        // Signers must not be held across the send, which needs `Send`
+        let transaction = {
+            let mut signers: Vec<&dyn Signer> = vec![payer];
+            signers.extend(co_signers.iter().map(|signer| *signer as &dyn Signer));
+            Transaction::new_signed_with_payer(
+                instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_blockhash,
+            )
+        };
 
         debug!("Sending transaction...");
 
-        match self
-            .provider
-            .send_and_confirm_transaction(&transaction)
-            .await
-        {
+        match self.sender.send_and_confirm(&transaction).await {
             Ok(signature) => {
                 info!(signature = %signature, "Transaction confirmed");
-                // Get slot from transaction status
-                let slot = self.provider.get_slot().await.unwrap_or(0);
-                Ok(ExecutionResult::success(signature, slot))
+                Ok(ExecutionResult::confirmed(&self.provider, signature, &payer.pubkey()).await)
             }
             Err(e) => {
                 let signature = transaction.signatures.first().copied().unwrap_or_default();
@@ -545,6 +1016,13 @@ impl WhirlpoolExecutor {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::orca::accounts::{initialize_tick_array_instruction, tick_array_start_index};
+    use crate::orca::whirlpool::Whirlpool;
+    use crate::quote::quote_deposit_at;
+    use base64::Engine;
+    use std::collections::HashMap;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
 
     #[test]
     fn test_program_ids() {
@@ -553,6 +1031,394 @@ mod tests {
         assert!(Pubkey::from_str(ASSOCIATED_TOKEN_PROGRAM_ID).is_ok());
     }
 
+    fn executor() -> WhirlpoolExecutor {
+        WhirlpoolExecutor::new(Arc::new(RpcProvider::new(crate::rpc::RpcConfig::default())))
+    }
+
+    fn liquidity_accounts(
+        executor: &WhirlpoolExecutor,
+        rewards: Vec<RewardAccounts>,
+        owner: &Pubkey,
+    ) -> LiquidityAccounts {
+        let pool = crate::orca::accounts::PoolAccounts {
+            whirlpool: Pubkey::new_unique(),
+            token_mint_a: Pubkey::new_unique(),
+            token_mint_b: Pubkey::new_unique(),
+            token_vault_a: Pubkey::new_unique(),
+            token_vault_b: Pubkey::new_unique(),
+            tick_spacing: 64,
+            rewards,
+        };
+        let position_mint = Pubkey::new_unique();
+        let (position, _) = Pubkey::find_program_address(
+            &[b"position", position_mint.as_ref()],
+            &executor.program_id,
+        );
+        LiquidityAccounts::derive(
+            &executor.program_id,
+            pool,
+            position,
+            position_mint,
+            (-128, 128),
+            owner,
+        )
+    }
+
+    #[test]
+    fn test_collect_reward_instruction() {
+        let executor = executor();
+        let reward = RewardAccounts {
+            index: 2,
+            mint: Pubkey::new_unique(),
+            vault: Pubkey::new_unique(),
+        };
+        let owner = Pubkey::new_unique();
+        let accounts = liquidity_accounts(&executor, vec![reward], &owner);
+
+        let ix = executor.build_collect_reward_instruction(&accounts, &owner, &reward);
+
+        assert_eq!(ix.data.len(), 9);
+        assert_eq!(ix.data[8], 2);
+        assert_eq!(ix.accounts.len(), 7);
+        assert_eq!(ix.accounts[3].pubkey, accounts.position_token_account);
+        assert_eq!(ix.accounts[5].pubkey, reward.vault);
+        assert_eq!(ix.accounts[4].pubkey, derive_ata(&owner, &reward.mint));
+    }
+
+    #[test]
+    fn test_liquidity_instructions_carry_all_accounts() {
+        let executor = executor();
+        let owner = Pubkey::new_unique();
+        let mut accounts = liquidity_accounts(&executor, Vec::new(), &owner);
+
+        let increase = executor.build_increase_liquidity_instruction(&accounts, &owner, 1, 2, 3);
+        assert_eq!(increase.accounts.len(), 11);
+        assert_eq!(increase.accounts[4].pubkey, accounts.position_token_account);
+        assert_eq!(increase.accounts[7].pubkey, accounts.pool.token_vault_a);
+        assert_eq!(increase.accounts[9].pubkey, accounts.tick_array_lower);
+        assert_eq!(increase.accounts[10].pubkey, accounts.tick_array_upper);
+
+        let collect = executor.build_collect_fees_instruction(&accounts, &owner);
+        assert_eq!(collect.accounts.len(), 9);
+        assert_eq!(collect.accounts[4].pubkey, accounts.token_owner_account_a);
+        assert_eq!(collect.accounts[5].pubkey, accounts.pool.token_vault_a);
+
+        let open = executor
+            .build_open_position_instruction(
+                &OpenPositionParams {
+                    pool: accounts.pool.whirlpool,
+                    quote: quote_deposit_at(0, 1 << 64, -128, 128, 1, 1, 0).unwrap(),
+                },
+                &owner,
+                &accounts,
+            )
+            .unwrap();
+        // Discriminator, position bump and both ticks
+        assert_eq!(open.data.len(), 17);
+
+        // An empty position is collected and closed without a decrease
+        let close = executor.build_withdraw_and_close_instructions(&accounts, &owner);
+        assert_eq!(close.len(), 2);
+        assert_eq!(close[1].accounts.len(), 6);
+        assert_eq!(close[1].accounts[3].pubkey, accounts.position_mint);
+
+        accounts.liquidity = 500;
+        let close = executor.build_withdraw_and_close_instructions(&accounts, &owner);
+        assert_eq!(close.len(), 3);
+        assert_eq!(
+            u128::from_le_bytes(close[0].data[8..24].try_into().unwrap()),
+            500
+        );
+    }
+
+    #[test]
+    fn test_open_position_is_signed_by_its_mint() {
+        let executor = executor();
+        let payer = Keypair::new();
+        let position_mint = Keypair::new();
+        let pool = liquidity_accounts(&executor, Vec::new(), &payer.pubkey()).pool;
+        let accounts = LiquidityAccounts::derive(
+            &executor.program_id,
+            pool,
+            executor.position_address(&position_mint.pubkey()),
+            position_mint.pubkey(),
+            (-128, 128),
+            &payer.pubkey(),
+        );
+        let open = executor
+            .build_open_position_instruction(
+                &OpenPositionParams {
+                    pool: accounts.pool.whirlpool,
+                    quote: quote_deposit_at(0, 1 << 64, -128, 128, 1, 1, 0).unwrap(),
+                },
+                &payer.pubkey(),
+                &accounts,
+            )
+            .unwrap();
+
+        // The payer alone cannot sign for the new mint
+        let message = solana_sdk::message::Message::new(&[open], Some(&payer.pubkey()));
+        let mut transaction = Transaction::new_unsigned(message);
+        let blockhash = solana_sdk::hash::Hash::default();
+        assert!(transaction.try_sign(&[&payer], blockhash).is_err());
+        transaction
+            .try_sign(&[&payer as &dyn Signer, &position_mint], blockhash)
+            .unwrap();
+        assert!(transaction.is_signed());
+    }
+
+    #[test]
+    fn test_discriminators_match_anchor_names() {
+        let executor = executor();
+        let owner = Pubkey::new_unique();
+        let mut accounts = liquidity_accounts(&executor, Vec::new(), &owner);
+        accounts.liquidity = 1;
+        let open = executor
+            .build_open_position_instruction(
+                &OpenPositionParams {
+                    pool: accounts.pool.whirlpool,
+                    quote: quote_deposit_at(0, 1 << 64, -128, 128, 1, 1, 0).unwrap(),
+                },
+                &owner,
+                &accounts,
+            )
+            .unwrap();
+        let withdraw = executor.build_withdraw_and_close_instructions(&accounts, &owner);
+
+        // Anchor discriminator: first 8 bytes of sha256("global:<name>")
+        for (name, ix) in [
+            ("open_position", &open),
+            (
+                "increase_liquidity",
+                &executor.build_increase_liquidity_instruction(&accounts, &owner, 1, 1, 1),
+            ),
+            ("decrease_liquidity", &withdraw[0]),
+            ("collect_fees", &withdraw[1]),
+            ("close_position", &withdraw[2]),
+        ] {
+            let hash = solana_sdk::hash::hashv(&[format!("global:{}", name).as_bytes()]);
+            assert_eq!(ix.data[..8], hash.to_bytes()[..8], "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_swap_instruction_layout() {
+        let executor = executor();
+        let owner = Pubkey::new_unique();
+        let accounts = liquidity_accounts(&executor, Vec::new(), &owner);
+        let params = SwapParams {
+            pool: accounts.pool.whirlpool,
+            a_to_b: true,
+            amount_in: 1_000,
+            min_amount_out: 990,
+        };
+
+        let ix = executor.build_swap_instruction(&params, &accounts.pool, 100, &owner);
+
+        let discriminator = solana_sdk::hash::hashv(&[b"global:swap"]);
+        assert_eq!(ix.data[..8], discriminator.to_bytes()[..8]);
+        assert_eq!(ix.data.len(), 42);
+        assert_eq!(
+            u64::from_le_bytes(ix.data[8..16].try_into().unwrap()),
+            1_000
+        );
+        assert_eq!(u64::from_le_bytes(ix.data[16..24].try_into().unwrap()), 990);
+        assert_eq!(
+            u128::from_le_bytes(ix.data[24..40].try_into().unwrap()),
+            tick_to_sqrt_price_x64(MIN_TICK_INDEX)
+        );
+        assert_eq!(ix.data[40..], [1, 1]);
+        assert_eq!(ix.accounts.len(), 11);
+        assert!(ix.accounts[1].is_signer);
+        assert_eq!(ix.accounts[3].pubkey, accounts.token_owner_account_a);
+        assert_eq!(ix.accounts[6].pubkey, accounts.pool.token_vault_b);
+        assert_eq!(
+            ix.accounts[10].pubkey,
+            derive_oracle(&executor.program_id, &accounts.pool.whirlpool)
+        );
+    }
+
+    /// Serves `getAccountInfo` from `accounts` and answers
+    /// `getMultipleAccounts` as if none of the requested accounts existed;
+    /// returns the endpoint URL.
+    async fn serve_rpc(accounts: HashMap<Pubkey, Vec<u8>>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let accounts = Arc::new(accounts);
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                tokio::spawn(answer_rpc(socket, accounts.clone()));
+            }
+        });
+        url
+    }
+
+    async fn answer_rpc(mut socket: TcpStream, accounts: Arc<HashMap<Pubkey, Vec<u8>>>) {
+        let mut request = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            let Ok(n) = socket.read(&mut buf).await else {
+                return;
+            };
+            if n == 0 {
+                return;
+            }
+            request.extend_from_slice(&buf[..n]);
+
+            // Answer each complete request; the client keeps the connection
+            let text = String::from_utf8_lossy(&request).to_lowercase();
+            let Some(end) = text.find("\r\n\r\n") else {
+                continue;
+            };
+            let length: usize = text
+                .lines()
+                .find_map(|l| l.strip_prefix("content-length: "))
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(0);
+            if request.len() < end + 4 + length {
+                continue;
+            }
+            let body: serde_json::Value =
+                serde_json::from_slice(&request[end + 4..end + 4 + length]).unwrap();
+            request.drain(..end + 4 + length);
+
+            let result = match body["method"].as_str() {
+                Some("getAccountInfo") => {
+                    let address = Pubkey::from_str(body["params"][0].as_str().unwrap()).unwrap();
+                    let data = &accounts[&address];
+                    serde_json::json!({
+                        "context": { "slot": 7 },
+                        "value": {
+                            "data": [base64::engine::general_purpose::STANDARD.encode(data), "base64"],
+                            "executable": false,
+                            "lamports": 1_000_000,
+                            "owner": WHIRLPOOL_PROGRAM_ID,
+                            "rentEpoch": 0,
+                            "space": data.len(),
+                        },
+                    })
+                }
+                Some("getMultipleAccounts") => {
+                    let count = body["params"][0].as_array().unwrap().len();
+                    serde_json::json!({
+                        "context": { "slot": 7 },
+                        "value": vec![serde_json::Value::Null; count],
+                    })
+                }
+                other => panic!("Unexpected RPC method {:?}", other),
+            };
+            let response =
+                serde_json::json!({ "jsonrpc": "2.0", "result": result, "id": body["id"] })
+                    .to_string();
+            let head = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n",
+                response.len()
+            );
+            if socket.write_all(head.as_bytes()).await.is_err()
+                || socket.write_all(response.as_bytes()).await.is_err()
+            {
+                return;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rebalance_bundle_initializes_missing_tick_arrays() {
+        let pool = Pubkey::new_unique();
+        let position = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let whirlpool = Whirlpool {
+            discriminator: [0; 8],
+            whirlpools_config: Pubkey::new_unique(),
+            whirlpool_bump: [255],
+            tick_spacing: 64,
+            tick_spacing_seed: 64u16.to_le_bytes(),
+            fee_rate: 3000,
+            protocol_fee_rate: 300,
+            liquidity: 1 << 40,
+            sqrt_price: 1 << 64,
+            tick_current_index: 0,
+            protocol_fee_owed_a: 0,
+            protocol_fee_owed_b: 0,
+            token_mint_a: Pubkey::new_unique(),
+            token_vault_a: Pubkey::new_unique(),
+            fee_growth_global_a: 0,
+            token_mint_b: Pubkey::new_unique(),
+            token_vault_b: Pubkey::new_unique(),
+            fee_growth_global_b: 0,
+            reward_last_updated_timestamp: 0,
+            reward_infos: Default::default(),
+        };
+        let old_position = WhirlpoolPosition {
+            discriminator: [0; 8],
+            whirlpool: pool,
+            position_mint: Pubkey::new_unique(),
+            liquidity: 1_000,
+            tick_lower_index: -128,
+            tick_upper_index: 128,
+            fee_growth_checkpoint_a: 0,
+            fee_owed_a: 0,
+            fee_growth_checkpoint_b: 0,
+            fee_owed_b: 0,
+            reward_infos: Default::default(),
+        };
+        let accounts = HashMap::from([
+            (pool, borsh::to_vec(&whirlpool).unwrap()),
+            (position, borsh::to_vec(&old_position).unwrap()),
+        ]);
+
+        let url = serve_rpc(accounts).await;
+        let provider = Arc::new(RpcProvider::new(crate::rpc::RpcConfig {
+            fallback_urls: Vec::new(),
+            max_retries: 0,
+            ..crate::rpc::RpcConfig::new(url)
+        }));
+        let executor = WhirlpoolExecutor::new(provider);
+
+        // The new range spans two tick arrays, neither of which exists
+        let (new_tick_lower, new_tick_upper) = (5632, 11328);
+        let bundle = executor
+            .build_rebalance_bundle(
+                &RebalanceBundleParams {
+                    position,
+                    pool,
+                    new_tick_lower,
+                    new_tick_upper,
+                    amount_a: 1_000_000,
+                    amount_b: 0,
+                    slippage_bps: 50,
+                },
+                &owner,
+            )
+            .await
+            .unwrap();
+
+        let index_of = |ix: &Instruction| bundle.instructions.iter().position(|i| i == ix);
+        let open_index = bundle
+            .instructions
+            .iter()
+            .position(|ix| {
+                ix.program_id == executor.program_id
+                    && ix.data[..8] == [0x87, 0x80, 0x2f, 0x4d, 0x0f, 0x98, 0xf0, 0x31]
+            })
+            .unwrap();
+        let last_ata_index = bundle
+            .instructions
+            .iter()
+            .rposition(|ix| ix.program_id == executor.ata_program)
+            .unwrap();
+        for tick in [new_tick_lower, new_tick_upper] {
+            let init = initialize_tick_array_instruction(
+                &executor.program_id,
+                &pool,
+                &owner,
+                tick_array_start_index(tick, 64),
+            );
+            let index = index_of(&init).expect("missing tick array is initialized");
+            assert!(last_ata_index < index && index < open_index);
+        }
+    }
+
     #[test]
     fn test_execution_result() {
         let sig = Signature::default();
@@ -0,0 +1,298 @@
+//! Exact Whirlpool fixed-point math.
+//!
+//! Mirrors the integer arithmetic of the Whirlpool program, so token amounts
+//! and fees computed here match what the program and the Orca SDK produce to
+//! the last unit. Sqrt prices are Q64.64 and fee growths are Q64.64 wrapping
+//! counters.
+
+use crate::events::OnChainPosition;
+use primitive_types::U256;
+
+/// Lowest tick index of a Whirlpool.
+pub const MIN_TICK_INDEX: i32 = -443_636;
+
+/// Highest tick index of a Whirlpool.
+pub const MAX_TICK_INDEX: i32 = 443_636;
+
+/// Returns the Q64.64 sqrt price of a tick.
+///
+/// Ticks outside [`MIN_TICK_INDEX`, `MAX_TICK_INDEX`] are clamped.
+#[must_use]
+pub fn tick_to_sqrt_price_x64(tick: i32) -> u128 {
+    let tick = tick.clamp(MIN_TICK_INDEX, MAX_TICK_INDEX);
+    if tick >= 0 {
+        sqrt_price_positive_tick(tick)
+    } else {
+        sqrt_price_negative_tick(tick)
+    }
+}
+
+/// sqrt(1.0001^tick) as a product of precomputed powers, in Q32.96 then
+/// shifted down to Q64.64.
+fn sqrt_price_positive_tick(tick: i32) -> u128 {
+    const FACTORS: [(i32, u128); 18] = [
+        (2, 79236085330515764027303304731),
+        (4, 79244008939048815603706035061),
+        (8, 79259858533276714757314932305),
+        (16, 79291567232598584799939703904),
+        (32, 79355022692464371645785046466),
+        (64, 79482085999252804386437311141),
+        (128, 79736823300114093921829183326),
+        (256, 80248749790819932309965073892),
+        (512, 81282483887344747381513967011),
+        (1024, 83390072131320151908154831281),
+        (2048, 87770609709833776024991924138),
+        (4096, 97234110755111693312479820773),
+        (8192, 119332217159966728226237229890),
+        (16384, 179736315981702064433883588727),
+        (32768, 407748233172238350107850275304),
+        (65536, 2098478828474011932436660412517),
+        (131072, 55581415166113811149459800483533),
+        (262144, 38992368544603139932233054999993551),
+    ];
+
+    let mut ratio: u128 = if tick & 1 != 0 {
+        79232123823359799118286999567
+    } else {
+        79228162514264337593543950336
+    };
+    for (bit, factor) in FACTORS {
+        if tick & bit != 0 {
+            ratio = ((U256::from(ratio) * U256::from(factor)) >> 96).as_u128();
+        }
+    }
+    ratio >> 32
+}
+
+/// sqrt(1.0001^tick) for negative ticks as a product of precomputed inverse
+/// powers in Q64.64.
+fn sqrt_price_negative_tick(tick: i32) -> u128 {
+    const FACTORS: [(i32, u128); 18] = [
+        (2, 18444899583751176498),
+        (4, 18443055278223354162),
+        (8, 18439367220385604838),
+        (16, 18431993317065449817),
+        (32, 18417254355718160513),
+        (64, 18387811781193591352),
+        (128, 18329067761203520168),
+        (256, 18212142134806087854),
+        (512, 17980523815641551639),
+        (1024, 17526086738831147013),
+        (2048, 16651378430235024244),
+        (4096, 15030750278693429944),
+        (8192, 12247334978882834399),
+        (16384, 8131365268884726200),
+        (32768, 3584323654723342297),
+        (65536, 696457651847595233),
+        (131072, 26294789957452057),
+        (262144, 37481735321082),
+    ];
+
+    let abs_tick = tick.abs();
+    let mut ratio: u128 = if abs_tick & 1 != 0 {
+        18445821805675392311
+    } else {
+        18446744073709551616
+    };
+    for (bit, factor) in FACTORS {
+        if abs_tick & bit != 0 {
+            ratio = (ratio * factor) >> 64;
+        }
+    }
+    ratio
+}
+
+/// Token A between two sqrt prices: L * (sqrt_b - sqrt_a) / (sqrt_a * sqrt_b).
+///
+/// Returns `None` if the amount exceeds `u64` or a sqrt price is zero.
+#[must_use]
+pub fn amount_a_delta(sqrt_a: u128, sqrt_b: u128, liquidity: u128, round_up: bool) -> Option<u64> {
+    let (lower, upper) = (sqrt_a.min(sqrt_b), sqrt_a.max(sqrt_b));
+    let numerator = U256::from(liquidity)
+        .checked_mul(U256::from(upper - lower))?
+        .checked_mul(U256::one() << 64)?;
+    let denominator = U256::from(lower).checked_mul(U256::from(upper))?;
+    if denominator.is_zero() {
+        return None;
+    }
+    let (quotient, remainder) = numerator.div_mod(denominator);
+    let amount = if round_up && !remainder.is_zero() {
+        quotient + 1
+    } else {
+        quotient
+    };
+    u64::try_from(amount).ok()
+}
+
+/// Token B between two sqrt prices: L * (sqrt_b - sqrt_a).
+///
+/// Returns `None` if the amount exceeds `u64`.
+#[must_use]
+pub fn amount_b_delta(sqrt_a: u128, sqrt_b: u128, liquidity: u128, round_up: bool) -> Option<u64> {
+    let (lower, upper) = (sqrt_a.min(sqrt_b), sqrt_a.max(sqrt_b));
+    let product = U256::from(liquidity).checked_mul(U256::from(upper - lower))?;
+    let quotient = product >> 64;
+    let amount = if round_up && !(product & U256::from(u64::MAX)).is_zero() {
+        quotient + 1
+    } else {
+        quotient
+    };
+    u64::try_from(amount).ok()
+}
+
+/// Token amounts held by `liquidity` in a tick range at a sqrt price.
+///
+/// The range is below the price when the price is at or above its upper
+/// bound, and above the price when the price is at or below its lower bound.
+/// Returns `None` on overflow.
+#[must_use]
+pub fn token_amounts_for_liquidity(
+    liquidity: u128,
+    sqrt_price: u128,
+    tick_lower: i32,
+    tick_upper: i32,
+    round_up: bool,
+) -> Option<(u64, u64)> {
+    let sqrt_lower = tick_to_sqrt_price_x64(tick_lower);
+    let sqrt_upper = tick_to_sqrt_price_x64(tick_upper);
+
+    if liquidity == 0 || sqrt_lower >= sqrt_upper {
+        Some((0, 0))
+    } else if sqrt_price <= sqrt_lower {
+        Some((
+            amount_a_delta(sqrt_lower, sqrt_upper, liquidity, round_up)?,
+            0,
+        ))
+    } else if sqrt_price >= sqrt_upper {
+        Some((
+            0,
+            amount_b_delta(sqrt_lower, sqrt_upper, liquidity, round_up)?,
+        ))
+    } else {
+        Some((
+            amount_a_delta(sqrt_price, sqrt_upper, liquidity, round_up)?,
+            amount_b_delta(sqrt_lower, sqrt_price, liquidity, round_up)?,
+        ))
+    }
+}
+
+/// Fee growth recorded outside an initialized tick.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TickFeeGrowth {
+    /// Fee growth outside the tick for token A (Q64.64).
+    pub fee_growth_outside_a: u128,
+    /// Fee growth outside the tick for token B (Q64.64).
+    pub fee_growth_outside_b: u128,
+}
+
+/// Fee growth inside a range, from the global growth and the growth outside
+/// its bounds.
+fn fee_growth_inside(
+    tick_current: i32,
+    position: &OnChainPosition,
+    global: u128,
+    outside_lower: u128,
+    outside_upper: u128,
+) -> u128 {
+    let below = if tick_current < position.tick_lower {
+        global.wrapping_sub(outside_lower)
+    } else {
+        outside_lower
+    };
+    let above = if tick_current >= position.tick_upper {
+        global.wrapping_sub(outside_upper)
+    } else {
+        outside_upper
+    };
+    global.wrapping_sub(below).wrapping_sub(above)
+}
+
+/// Calculates the fees a position could collect now, including fees not yet
+/// checkpointed on the position account.
+///
+/// # Arguments
+/// * `tick_current` - Current pool tick
+/// * `fee_growth_global` - Global fee growth of tokens A and B
+/// * `position` - The position
+/// * `lower` - Fee growth outside the position's lower tick
+/// * `upper` - Fee growth outside the position's upper tick
+///
+/// Returns `None` if the fees exceed `u64`.
+#[must_use]
+pub fn fees_owed(
+    tick_current: i32,
+    fee_growth_global: (u128, u128),
+    position: &OnChainPosition,
+    lower: &TickFeeGrowth,
+    upper: &TickFeeGrowth,
+) -> Option<(u64, u64)> {
+    let owed = |global, outside_lower, outside_upper, checkpoint: u128, owed: u64| {
+        let inside =
+            fee_growth_inside(tick_current, position, global, outside_lower, outside_upper);
+        let delta = U256::from(inside.wrapping_sub(checkpoint))
+            .checked_mul(U256::from(position.liquidity))?
+            >> 64;
+        owed.checked_add(u64::try_from(delta).ok()?)
+    };
+
+    Some((
+        owed(
+            fee_growth_global.0,
+            lower.fee_growth_outside_a,
+            upper.fee_growth_outside_a,
+            position.fee_growth_inside_a,
+            position.fees_owed_a,
+        )?,
+        owed(
+            fee_growth_global.1,
+            lower.fee_growth_outside_b,
+            upper.fee_growth_outside_b,
+            position.fee_growth_inside_b,
+            position.fees_owed_b,
+        )?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sqrt_price_bounds_and_symmetry() {
+        assert_eq!(tick_to_sqrt_price_x64(0), 1u128 << 64);
+        assert_eq!(tick_to_sqrt_price_x64(MIN_TICK_INDEX), 4_295_048_016);
+        assert_eq!(
+            tick_to_sqrt_price_x64(MAX_TICK_INDEX),
+            79_226_673_515_401_279_992_447_579_055
+        );
+        assert_eq!(
+            tick_to_sqrt_price_x64(MAX_TICK_INDEX + 1),
+            tick_to_sqrt_price_x64(MAX_TICK_INDEX)
+        );
+
+        // sqrt(1.0001^t) * sqrt(1.0001^-t) = 1 within rounding
+        for tick in [1, 64, 18_972, 100_000] {
+            let product = (U256::from(tick_to_sqrt_price_x64(tick))
+                * U256::from(tick_to_sqrt_price_x64(-tick)))
+                >> 64;
+            let one = U256::one() << 64;
+            assert!(one - product < U256::from(1_000_000u64), "tick {tick}");
+        }
+    }
+
+    #[test]
+    fn test_amount_deltas_round_as_requested() {
+        let (lower, upper) = (1u128 << 64, 2u128 << 64);
+        assert_eq!(amount_a_delta(lower, upper, 1000, false), Some(500));
+        assert_eq!(amount_b_delta(upper, lower, 1000, false), Some(1000));
+
+        // 3 * (1/1 - 1/2) = 1.5 and 3 * 0.5 = 1.5
+        let half = 3u128 << 63;
+        assert_eq!(amount_a_delta(lower, upper, 3, false), Some(1));
+        assert_eq!(amount_a_delta(lower, upper, 3, true), Some(2));
+        assert_eq!(amount_b_delta(lower, half, 3, false), Some(1));
+        assert_eq!(amount_b_delta(lower, half, 3, true), Some(2));
+
+        assert_eq!(amount_b_delta(lower, upper, u128::MAX, false), None);
+    }
+}
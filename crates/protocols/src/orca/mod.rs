@@ -3,11 +3,20 @@
 //! This module provides functionality to interact with Orca Whirlpool pools:
 //! - Read pool state
 //! - Read position state
-//! - Execute LP operations
+//! - Execute LP operations with fully resolved accounts
 //! - Calculate token amounts
 
+/// Account resolution for instructions.
+pub mod accounts;
+/// Unified protocol adapter implementation.
+pub mod adapter;
 /// Executor for on-chain operations.
 pub mod executor;
+/// Golden vectors computed with the Orca SDK.
+#[cfg(test)]
+mod golden_tests;
+/// Exact Whirlpool fixed-point math.
+pub mod math;
 /// Pool reader for on-chain state.
 pub mod pool_reader;
 /// Position reader for on-chain state.
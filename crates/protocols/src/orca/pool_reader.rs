@@ -2,13 +2,14 @@
 //!
 //! Reads pool state from on-chain accounts.
 
-use super::whirlpool::Whirlpool;
+use super::whirlpool::{Whirlpool, WhirlpoolRewardInfo};
 use crate::rpc::RpcProvider;
 use anyhow::{Context, Result};
 use borsh::BorshDeserialize;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::FromPrimitive;
 use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
 use tracing::{debug, info};
@@ -41,16 +42,15 @@ impl WhirlpoolReader {
         info!(pool = pool_address, "Fetching Whirlpool state");
 
         let account = self.provider.get_account(&pubkey).await?;
-        let whirlpool = Whirlpool::try_from_slice(&account.data)
-            .context("Failed to deserialize Whirlpool account")?;
+        let state = WhirlpoolState::from_account_data(&account.data, pool_address)?;
 
         debug!(
-            tick = whirlpool.tick_current_index,
-            liquidity = %whirlpool.liquidity,
+            tick = state.tick_current,
+            liquidity = %state.liquidity,
             "Parsed Whirlpool state"
         );
 
-        Ok(WhirlpoolState::from_whirlpool(&whirlpool, pool_address))
+        Ok(state)
     }
 
     /// Gets the current price from a pool.
@@ -112,17 +112,28 @@ pub struct WhirlpoolState {
     pub price: Decimal,
     /// Current liquidity.
     pub liquidity: u128,
-    /// Fee rate in basis points.
-    pub fee_rate_bps: u16,
+    /// Fee rate in hundredths of a basis point, as stored on-chain
+    /// (3000 = 0.3%). Use [`Self::fee_rate`] or [`Self::fee_bps`] to convert.
+    pub fee_rate: u16,
     /// Protocol fee rate in basis points.
     pub protocol_fee_rate_bps: u16,
     /// Fee growth global for token A.
     pub fee_growth_global_a: u128,
     /// Fee growth global for token B.
     pub fee_growth_global_b: u128,
+    /// Active reward emissions.
+    pub rewards: Vec<RewardEmission>,
 }
 
 impl WhirlpoolState {
+    /// Parses the state from raw Whirlpool account data, e.g. from an
+    /// account subscription.
+    pub fn from_account_data(data: &[u8], address: &str) -> Result<Self> {
+        let whirlpool =
+            Whirlpool::try_from_slice(data).context("Failed to deserialize Whirlpool account")?;
+        Ok(Self::from_whirlpool(&whirlpool, address))
+    }
+
     /// Creates a WhirlpoolState from a deserialized Whirlpool.
     fn from_whirlpool(wp: &Whirlpool, address: &str) -> Self {
         Self {
@@ -134,17 +145,30 @@ impl WhirlpoolState {
             sqrt_price: wp.sqrt_price,
             price: sqrt_price_to_price(wp.sqrt_price),
             liquidity: wp.liquidity,
-            fee_rate_bps: wp.fee_rate,
+            fee_rate: wp.fee_rate,
             protocol_fee_rate_bps: wp.protocol_fee_rate,
             fee_growth_global_a: wp.fee_growth_global_a,
             fee_growth_global_b: wp.fee_growth_global_b,
+            rewards: wp
+                .reward_infos
+                .iter()
+                .enumerate()
+                .filter(|(_, info)| info.is_initialized())
+                .map(|(index, info)| RewardEmission::from_reward_info(index, info))
+                .collect(),
         }
     }
 
     /// Returns the fee rate as a decimal.
     #[must_use]
     pub fn fee_rate(&self) -> Decimal {
-        Decimal::from(self.fee_rate_bps) / Decimal::from(10_000)
+        Decimal::from(self.fee_rate) / Decimal::from(1_000_000)
+    }
+
+    /// Returns the fee rate in basis points.
+    #[must_use]
+    pub fn fee_bps(&self) -> Decimal {
+        Decimal::from(self.fee_rate) / Decimal::from(100)
     }
 
     /// Checks if a tick is within the current range.
@@ -152,13 +176,81 @@ impl WhirlpoolState {
     pub fn is_tick_in_range(&self, tick_lower: i32, tick_upper: i32) -> bool {
         self.tick_current >= tick_lower && self.tick_current < tick_upper
     }
+
+    /// Calculates the combined reward APR for the pool.
+    ///
+    /// # Arguments
+    /// * `unit_prices` - Value of one raw unit of each reward mint
+    /// * `tvl` - Pool TVL in the same unit as the prices
+    #[must_use]
+    pub fn reward_apr(&self, unit_prices: &HashMap<Pubkey, Decimal>, tvl: Decimal) -> Decimal {
+        self.rewards
+            .iter()
+            .filter_map(|reward| {
+                unit_prices
+                    .get(&reward.mint)
+                    .map(|price| reward.apr(*price, tvl))
+            })
+            .sum()
+    }
+}
+
+/// Seconds in a year, used to annualize reward emissions.
+const SECONDS_PER_YEAR: u64 = 31_536_000;
+
+/// A reward token emitted by a Whirlpool.
+#[derive(Debug, Clone)]
+pub struct RewardEmission {
+    /// Reward slot index (0-2).
+    pub index: usize,
+    /// Reward token mint.
+    pub mint: Pubkey,
+    /// Reward vault.
+    pub vault: Pubkey,
+    /// Raw reward tokens emitted per second.
+    pub emissions_per_second: Decimal,
+    /// Global reward growth per unit of liquidity (Q64.64).
+    pub growth_global_x64: u128,
+}
+
+impl RewardEmission {
+    fn from_reward_info(index: usize, info: &WhirlpoolRewardInfo) -> Self {
+        let emissions = info.emissions_per_second_x64 as f64 / (1u128 << 64) as f64;
+
+        Self {
+            index,
+            mint: info.mint,
+            vault: info.vault,
+            emissions_per_second: Decimal::from_f64(emissions).unwrap_or(Decimal::ZERO),
+            growth_global_x64: info.growth_global_x64,
+        }
+    }
+
+    /// Returns the raw reward tokens emitted per day.
+    #[must_use]
+    pub fn emissions_per_day(&self) -> Decimal {
+        self.emissions_per_second * Decimal::from(86_400)
+    }
+
+    /// Calculates the reward APR for the whole pool.
+    ///
+    /// # Arguments
+    /// * `reward_price` - Value of one raw reward token unit
+    /// * `tvl` - Pool TVL in the same unit as `reward_price`
+    #[must_use]
+    pub fn apr(&self, reward_price: Decimal, tvl: Decimal) -> Decimal {
+        if tvl <= Decimal::ZERO {
+            return Decimal::ZERO;
+        }
+        self.emissions_per_second * Decimal::from(SECONDS_PER_YEAR) * reward_price / tvl
+    }
 }
 
 /// Converts sqrt_price (Q64.64) to a human-readable price.
 ///
 /// sqrt_price is stored as a Q64.64 fixed-point number.
 /// price = (sqrt_price / 2^64)^2
-fn sqrt_price_to_price(sqrt_price: u128) -> Decimal {
+pub(crate) fn sqrt_price_to_price(sqrt_price: u128) -> Decimal {
     // sqrt_price is Q64.64, so we need to divide by 2^64
     let sqrt_price_f64 = sqrt_price as f64 / (1u128 << 64) as f64;
     let price = sqrt_price_f64 * sqrt_price_f64;
@@ -239,6 +331,47 @@ mod tests {
         assert!(tick > 0);
     }
 
+    #[test]
+    fn test_fee_rate_units() {
+        let state = WhirlpoolState {
+            address: String::new(),
+            token_mint_a: Pubkey::new_unique(),
+            token_mint_b: Pubkey::new_unique(),
+            tick_current: 0,
+            tick_spacing: 4,
+            sqrt_price: 1 << 64,
+            price: Decimal::ONE,
+            liquidity: 0,
+            fee_rate: 400,
+            protocol_fee_rate_bps: 0,
+            fee_growth_global_a: 0,
+            fee_growth_global_b: 0,
+            rewards: Vec::new(),
+        };
+
+        // On-chain 400 is 0.04%
+        assert_eq!(state.fee_rate(), Decimal::new(4, 4));
+        assert_eq!(state.fee_bps(), Decimal::from(4));
+    }
+
+    #[test]
+    fn test_reward_emission_apr() {
+        let info = WhirlpoolRewardInfo {
+            mint: Pubkey::new_unique(),
+            emissions_per_second_x64: 10u128 << 64,
+            ..Default::default()
+        };
+        let reward = RewardEmission::from_reward_info(1, &info);
+
+        assert_eq!(reward.emissions_per_second, Decimal::from(10));
+        assert_eq!(reward.emissions_per_day(), Decimal::from(864_000));
+
+        // 315.36M tokens/year at 0.001 each over a 315,360 TVL = 100%
+        let apr = reward.apr(Decimal::new(1, 3), Decimal::from(315_360));
+        assert_eq!(apr, Decimal::ONE);
+        assert_eq!(reward.apr(Decimal::ONE, Decimal::ZERO), Decimal::ZERO);
+    }
+
     #[test]
     fn test_calculate_tick_range() {
         let (lower, upper) = calculate_tick_range(0, Decimal::from_f64(0.1).unwrap(), 64);
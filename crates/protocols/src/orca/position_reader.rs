@@ -2,17 +2,19 @@
 //!
 //! Reads position state from on-chain accounts.
 
+use super::math::token_amounts_for_liquidity;
+use super::whirlpool::NUM_REWARDS;
 use crate::events::OnChainPosition;
 use crate::rpc::RpcProvider;
 use anyhow::{Context, Result};
-use borsh::BorshDeserialize;
+use borsh::{BorshDeserialize, BorshSerialize};
 use solana_sdk::pubkey::Pubkey;
 use std::str::FromStr;
 use std::sync::Arc;
 use tracing::{debug, info};
 
 /// Whirlpool position account structure.
-#[derive(BorshDeserialize, Debug, Clone)]
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone)]
 pub struct WhirlpoolPosition {
     /// Account discriminator.
     pub discriminator: [u8; 8],
@@ -34,7 +36,44 @@ pub struct WhirlpoolPosition {
     pub fee_growth_checkpoint_b: u128,
     /// Fee owed for token B.
     pub fee_owed_b: u64,
-    // Reward fields omitted for simplicity
+    /// Reward checkpoints and amounts owed.
+    pub reward_infos: [PositionRewardInfo; NUM_REWARDS],
+}
+
+impl WhirlpoolPosition {
+    /// Converts the account into a position at `address`.
+    ///
+    /// The owner is left unset; it is held by the position NFT's token
+    /// account, not the position account.
+    #[must_use]
+    pub fn into_on_chain(self, address: Pubkey) -> OnChainPosition {
+        OnChainPosition {
+            address,
+            pool: self.whirlpool,
+            owner: Pubkey::default(),
+            tick_lower: self.tick_lower_index,
+            tick_upper: self.tick_upper_index,
+            liquidity: self.liquidity,
+            fee_growth_inside_a: self.fee_growth_checkpoint_a,
+            fee_growth_inside_b: self.fee_growth_checkpoint_b,
+            fees_owed_a: self.fee_owed_a,
+            fees_owed_b: self.fee_owed_b,
+            reward_growth_inside: self
+                .reward_infos
+                .each_ref()
+                .map(|r| r.growth_inside_checkpoint),
+            rewards_owed: self.reward_infos.each_ref().map(|r| r.amount_owed),
+        }
+    }
+}
+
+/// Per-reward state stored on a Whirlpool position.
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, Default)]
+pub struct PositionRewardInfo {
+    /// Reward growth inside the range at the last update (Q64.64).
+    pub growth_inside_checkpoint: u128,
+    /// Reward tokens owed to the position.
+    pub amount_owed: u64,
 }
 
 /// Reads Orca Whirlpool positions from on-chain.
@@ -63,21 +102,11 @@ impl PositionReader {
             liquidity = %position.liquidity,
             tick_lower = position.tick_lower_index,
             tick_upper = position.tick_upper_index,
+            rewards_owed = ?position.reward_infos.iter().map(|r| r.amount_owed).collect::<Vec<_>>(),
             "Parsed position state"
         );
 
-        Ok(OnChainPosition {
-            address: pubkey,
-            pool: position.whirlpool,
-            owner: Pubkey::default(), // Owner needs to be fetched from token account
-            tick_lower: position.tick_lower_index,
-            tick_upper: position.tick_upper_index,
-            liquidity: position.liquidity,
-            fee_growth_inside_a: position.fee_growth_checkpoint_a,
-            fee_growth_inside_b: position.fee_growth_checkpoint_b,
-            fees_owed_a: position.fee_owed_a,
-            fees_owed_b: position.fee_owed_b,
-        })
+        Ok(position.into_on_chain(pubkey))
     }
 
     /// Gets all positions for a given owner.
@@ -107,118 +136,66 @@ impl PositionReader {
         Ok(vec![])
     }
 
-    /// Calculates the token amounts for a position.
+    /// Calculates the token amounts of a position at a sqrt price,
+    /// rounding down as a withdrawal would.
+    ///
+    /// Fails if either amount does not fit in a `u64`.
     pub fn calculate_token_amounts(
         &self,
         position: &OnChainPosition,
-        current_tick: i32,
         sqrt_price: u128,
-    ) -> (u64, u64) {
-        // If position is out of range, all liquidity is in one token
-        if current_tick < position.tick_lower {
-            // All in token A
-            let amount_a =
-                self.get_amount_a(position.liquidity, position.tick_lower, position.tick_upper);
-            return (amount_a, 0);
-        }
-
-        if current_tick >= position.tick_upper {
-            // All in token B
-            let amount_b =
-                self.get_amount_b(position.liquidity, position.tick_lower, position.tick_upper);
-            return (0, amount_b);
-        }
-
-        // Position is in range - split between tokens
-        let amount_a = self.get_amount_a_in_range(
+    ) -> Result<(u64, u64)> {
+        token_amounts_for_liquidity(
             position.liquidity,
-            current_tick,
-            position.tick_upper,
             sqrt_price,
-        );
-        let amount_b = self.get_amount_b_in_range(
-            position.liquidity,
             position.tick_lower,
-            current_tick,
-            sqrt_price,
-        );
-
-        (amount_a, amount_b)
-    }
-
-    /// Gets amount of token A when position is below current tick.
-    fn get_amount_a(&self, liquidity: u128, tick_lower: i32, tick_upper: i32) -> u64 {
-        let sqrt_price_lower = tick_to_sqrt_price(tick_lower);
-        let sqrt_price_upper = tick_to_sqrt_price(tick_upper);
-
-        // amount_a = L * (1/sqrt_price_lower - 1/sqrt_price_upper)
-        let inv_lower = (1u128 << 64) / sqrt_price_lower;
-        let inv_upper = (1u128 << 64) / sqrt_price_upper;
-
-        let delta = inv_lower.saturating_sub(inv_upper);
-        ((liquidity * delta) >> 64) as u64
-    }
-
-    /// Gets amount of token B when position is above current tick.
-    fn get_amount_b(&self, liquidity: u128, tick_lower: i32, tick_upper: i32) -> u64 {
-        let sqrt_price_lower = tick_to_sqrt_price(tick_lower);
-        let sqrt_price_upper = tick_to_sqrt_price(tick_upper);
-
-        // amount_b = L * (sqrt_price_upper - sqrt_price_lower)
-        let delta = sqrt_price_upper.saturating_sub(sqrt_price_lower);
-        ((liquidity * delta) >> 64) as u64
-    }
-
-    /// Gets amount of token A when in range.
-    fn get_amount_a_in_range(
-        &self,
-        liquidity: u128,
-        _current_tick: i32,
-        tick_upper: i32,
-        sqrt_price: u128,
-    ) -> u64 {
-        let sqrt_price_upper = tick_to_sqrt_price(tick_upper);
-
-        let inv_current = (1u128 << 64) / sqrt_price;
-        let inv_upper = (1u128 << 64) / sqrt_price_upper;
-
-        let delta = inv_current.saturating_sub(inv_upper);
-        ((liquidity * delta) >> 64) as u64
-    }
-
-    /// Gets amount of token B when in range.
-    fn get_amount_b_in_range(
-        &self,
-        liquidity: u128,
-        tick_lower: i32,
-        _current_tick: i32,
-        sqrt_price: u128,
-    ) -> u64 {
-        let sqrt_price_lower = tick_to_sqrt_price(tick_lower);
-
-        let delta = sqrt_price.saturating_sub(sqrt_price_lower);
-        ((liquidity * delta) >> 64) as u64
+            position.tick_upper,
+            false,
+        )
+        .with_context(|| {
+            format!(
+                "Token amounts of position {} overflow at sqrt price {}",
+                position.address, sqrt_price
+            )
+        })
     }
 }
 
-/// Converts a tick to sqrt_price (Q64.64).
-fn tick_to_sqrt_price(tick: i32) -> u128 {
-    // sqrt_price = sqrt(1.0001^tick) * 2^64
-    let base: f64 = 1.0001;
-    let sqrt_price = base.powi(tick).sqrt() * (1u128 << 64) as f64;
-    sqrt_price as u128
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rpc::RpcConfig;
+
+    fn position(liquidity: u128) -> OnChainPosition {
+        WhirlpoolPosition {
+            discriminator: [0; 8],
+            whirlpool: Pubkey::new_unique(),
+            position_mint: Pubkey::new_unique(),
+            liquidity,
+            tick_lower_index: -443584,
+            tick_upper_index: 443584,
+            fee_growth_checkpoint_a: 0,
+            fee_owed_a: 0,
+            fee_growth_checkpoint_b: 0,
+            fee_owed_b: 0,
+            reward_infos: Default::default(),
+        }
+        .into_on_chain(Pubkey::new_unique())
+    }
 
     #[test]
-    fn test_tick_to_sqrt_price() {
-        // tick 0 should give sqrt_price = 2^64
-        let sqrt_price = tick_to_sqrt_price(0);
-        let expected = 1u128 << 64;
-        // Allow some floating point error
-        assert!((sqrt_price as i128 - expected as i128).abs() < 1000);
+    fn test_token_amounts_overflow_is_an_error() {
+        let reader = PositionReader::new(Arc::new(RpcProvider::new(RpcConfig::default())));
+
+        let (amount_a, amount_b) = reader
+            .calculate_token_amounts(&position(1 << 40), 1 << 64)
+            .unwrap();
+        assert!(amount_a > 0 && amount_b > 0);
+
+        assert!(
+            reader
+                .calculate_token_amounts(&position(u128::MAX), 1 << 64)
+                .is_err()
+        );
     }
 }
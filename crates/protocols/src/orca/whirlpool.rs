@@ -46,11 +46,34 @@ pub struct Whirlpool {
     pub fee_growth_global_b: u128,
     /// The last updated timestamp for rewards.
     pub reward_last_updated_timestamp: u64,
-    // ... there are more fields (rewards, etc.)
-    // Borsh deserialization fails if struct doesn't match exact bytes.
-    // So we usually need the FULL struct or use a manual parser (unsafe pointer cast or byte slicing).
-    // For safety in Rust, using the Anchor deserializer is best if we have the IDL.
-    // Or we can skip bytes if we know offsets.
+    /// Reward emission slots.
+    pub reward_infos: [WhirlpoolRewardInfo; NUM_REWARDS],
+}
+
+/// Number of reward slots on a Whirlpool.
+pub const NUM_REWARDS: usize = 3;
+
+/// Reward emission configuration stored on a Whirlpool.
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, Default)]
+pub struct WhirlpoolRewardInfo {
+    /// The reward token mint (default pubkey if the slot is unused).
+    pub mint: Pubkey,
+    /// The vault holding reward tokens.
+    pub vault: Pubkey,
+    /// The authority allowed to configure emissions.
+    pub authority: Pubkey,
+    /// Reward tokens emitted per second (Q64.64).
+    pub emissions_per_second_x64: u128,
+    /// Global reward growth per unit of liquidity (Q64.64).
+    pub growth_global_x64: u128,
+}
+
+impl WhirlpoolRewardInfo {
+    /// Returns true if this reward slot has been initialized.
+    #[must_use]
+    pub fn is_initialized(&self) -> bool {
+        self.mint != Pubkey::default()
+    }
 }
 
 /// Helper for parsing Whirlpool data.
@@ -10,28 +10,67 @@
 
 // Traits
 pub use crate::PoolFetcher;
+pub use crate::adapter::{PoolSnapshot, ProtocolAdapter};
 
 // RPC provider
-pub use crate::rpc::{CommitmentLevel, EndpointHealth, HealthChecker, RpcConfig, RpcProvider};
+pub use crate::rpc::{
+    Cluster, CommitmentLevel, EndpointHealth, HealthChecker, RAYDIUM_CLMM_DEVNET_PROGRAM_ID,
+    RpcConfig, RpcProvider, SignatureConfirmation, TransactionSender,
+};
 
 // Events
 pub use crate::events::{
-    ClosePositionEvent, CollectFeesEvent, EventFetcher, EventParser, FetchConfig, LiquidityEvent,
-    OnChainPosition, OpenPositionEvent, Protocol, ProtocolEvent, SwapEvent, VolumeData,
-    WhirlpoolInstruction,
+    ClosePositionEvent, CollectFeesEvent, EventFetcher, EventParser, ExecutedAmounts, FetchConfig,
+    LiquidityEvent, OnChainPosition, OpenPositionEvent, Protocol, ProtocolEvent, SwapEvent,
+    TokenBalanceChange, VolumeData, WhirlpoolInstruction,
 };
 
 // Orca
+pub use crate::orca::accounts::{
+    AccountResolver, LiquidityAccounts, PoolAccounts, RewardAccounts,
+    create_ata_idempotent_instruction, derive_ata, derive_tick_array,
+    initialize_tick_array_instruction, tick_array_start_index,
+};
+pub use crate::orca::adapter::OrcaAdapter;
 pub use crate::orca::executor::{
-    DecreaseLiquidityParams, ExecutionResult, IncreaseLiquidityParams, OpenPositionParams,
-    WhirlpoolExecutor,
+    BundleSimulation, DecreaseLiquidityParams, ExecutionResult, IncreaseLiquidityParams,
+    OpenPositionParams, RebalanceBundle, RebalanceBundleParams, SwapParams, WhirlpoolExecutor,
+};
+pub use crate::orca::math::{
+    TickFeeGrowth, amount_a_delta, amount_b_delta, fees_owed, tick_to_sqrt_price_x64,
+    token_amounts_for_liquidity,
 };
 pub use crate::orca::pool_reader::{
-    WhirlpoolReader, WhirlpoolState, calculate_tick_range, price_to_tick, tick_to_price,
+    RewardEmission, WhirlpoolReader, WhirlpoolState, calculate_tick_range, price_to_tick,
+    tick_to_price,
 };
-pub use crate::orca::position_reader::{PositionReader, WhirlpoolPosition};
+pub use crate::orca::position_reader::{PositionReader, PositionRewardInfo, WhirlpoolPosition};
 pub use crate::orca::provider::OrcaPoolProvider;
-pub use crate::orca::whirlpool::{Whirlpool, WhirlpoolParser};
+pub use crate::orca::whirlpool::{NUM_REWARDS, Whirlpool, WhirlpoolParser, WhirlpoolRewardInfo};
+
+// Quotes
+pub use crate::quote::{
+    CapitalQuoteParams, DepositSide, Quote, quote_capital, quote_capital_at, quote_deposit,
+    quote_deposit_at,
+};
+
+// Raydium
+pub use crate::raydium::adapter::RaydiumAdapter;
+pub use crate::raydium::clmm::{
+    ClmmPool, ClmmRewardInfo, PersonalPosition, PersonalPositionRewardInfo,
+    RAYDIUM_CLMM_PROGRAM_ID, RAYDIUM_NUM_REWARDS,
+};
+pub use crate::raydium::executor::{RaydiumClmmExecutor, RaydiumExecutor};
+pub use crate::raydium::pool_reader::{RaydiumPoolReader, RaydiumPoolState};
+pub use crate::raydium::position_reader::{
+    RaydiumPositionReader, derive_personal_position, derive_personal_position_with_program,
+};
+
+// Token risk
+pub use crate::risk::{
+    DEFAULT_TRUSTED_MINTS, PoolRiskReport, TOKEN_METADATA_PROGRAM_ID, TokenRisk, TokenRiskChecker,
+    TokenRiskReport, metadata_address,
+};
 
 // Solana client
 pub use crate::solana_client::SolanaRpcAdapter;
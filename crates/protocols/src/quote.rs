@@ -0,0 +1,489 @@
+//! Deposit quotes for concentrated liquidity ranges.
+//!
+//! A [`Quote`] fixes everything a deposit needs before it is sent: the
+//! range, the liquidity to mint, the token amounts it consumes at the
+//! current price and the bounds those amounts may move within under a
+//! slippage tolerance. Quotes are built either from token amounts the owner
+//! holds ([`quote_deposit`]) or from a USD budget ([`quote_capital`]), which
+//! works out the token split itself.
+//!
+//! The math only depends on the current tick and Q64.64 sqrt price, so the
+//! `_at` variants quote pools of any protocol.
+
+use crate::orca::math::token_amounts_for_liquidity;
+use crate::orca::pool_reader::WhirlpoolState;
+use anyhow::Result;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+/// Q64 fixed point scale.
+const Q64: f64 = 18_446_744_073_709_551_616.0;
+
+/// Token composition required by a deposit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepositSide {
+    /// Range contains the current price; both tokens are required.
+    TwoSided,
+    /// Range is entirely above the current price; only token A is required.
+    TokenAOnly,
+    /// Range is entirely below the current price; only token B is required.
+    TokenBOnly,
+}
+
+impl DepositSide {
+    /// Determines the deposit side for a range given the current tick.
+    ///
+    /// Follows the Whirlpool convention where the lower tick is inclusive and
+    /// the upper tick is exclusive.
+    #[must_use]
+    pub fn for_range(tick_current: i32, tick_lower: i32, tick_upper: i32) -> Self {
+        if tick_current < tick_lower {
+            Self::TokenAOnly
+        } else if tick_current >= tick_upper {
+            Self::TokenBOnly
+        } else {
+            Self::TwoSided
+        }
+    }
+
+    /// Returns true if only one token is deposited.
+    #[must_use]
+    pub fn is_single_sided(&self) -> bool {
+        !matches!(self, Self::TwoSided)
+    }
+}
+
+/// Quote for depositing liquidity into a range.
+#[derive(Debug, Clone)]
+pub struct Quote {
+    /// Deposit side.
+    pub side: DepositSide,
+    /// Lower tick bound.
+    pub tick_lower: i32,
+    /// Upper tick bound.
+    pub tick_upper: i32,
+    /// Liquidity that the deposit provides.
+    pub liquidity: u128,
+    /// Token A amount consumed at the current price.
+    pub amount_a: u64,
+    /// Token B amount consumed at the current price.
+    pub amount_b: u64,
+    /// Maximum token A amount including slippage.
+    pub token_max_a: u64,
+    /// Maximum token B amount including slippage.
+    pub token_max_b: u64,
+    /// Minimum token A amount within the slippage tolerance.
+    pub token_min_a: u64,
+    /// Minimum token B amount within the slippage tolerance.
+    pub token_min_b: u64,
+    /// Slippage tolerance in basis points.
+    pub slippage_bps: u16,
+}
+
+impl Quote {
+    /// Builds a quote around the amounts consumed at the current price.
+    fn new(
+        side: DepositSide,
+        (tick_lower, tick_upper): (i32, i32),
+        liquidity: u128,
+        (amount_a, amount_b): (u64, u64),
+        slippage_bps: u16,
+    ) -> Self {
+        let tolerance = f64::from(slippage_bps) / 10_000.0;
+        let max = |amount: u64| (amount as f64 * (1.0 + tolerance)).ceil() as u64;
+        let min = |amount: u64| (amount as f64 * (1.0 - tolerance).max(0.0)).floor() as u64;
+
+        Self {
+            side,
+            tick_lower,
+            tick_upper,
+            liquidity,
+            amount_a,
+            amount_b,
+            token_max_a: max(amount_a),
+            token_max_b: max(amount_b),
+            token_min_a: min(amount_a),
+            token_min_b: min(amount_b),
+            slippage_bps,
+        }
+    }
+}
+
+/// Capital to deploy into a range, valued in USD.
+#[derive(Debug, Clone, Copy)]
+pub struct CapitalQuoteParams {
+    /// Lower tick bound.
+    pub tick_lower: i32,
+    /// Upper tick bound.
+    pub tick_upper: i32,
+    /// Total capital to deposit, in USD.
+    pub capital_usd: Decimal,
+    /// USD price of one whole token B.
+    ///
+    /// Token A is valued through the pool price, so only one USD price is
+    /// needed.
+    pub token_b_price_usd: Decimal,
+    /// Decimals of token B.
+    pub decimals_b: u8,
+    /// Slippage tolerance in basis points.
+    pub slippage_bps: u16,
+}
+
+/// Sqrt prices of a range and of the current price clamped into it.
+struct RangeSqrtPrices {
+    lower: f64,
+    upper: f64,
+    current: f64,
+}
+
+impl RangeSqrtPrices {
+    fn new(sqrt_price: u128, tick_lower: i32, tick_upper: i32) -> Result<Self> {
+        if tick_lower >= tick_upper {
+            anyhow::bail!("Invalid tick range: {} >= {}", tick_lower, tick_upper);
+        }
+
+        let lower = tick_to_sqrt_price_f64(tick_lower);
+        let upper = tick_to_sqrt_price_f64(tick_upper);
+        Ok(Self {
+            lower,
+            upper,
+            current: (sqrt_price as f64 / Q64).clamp(lower, upper),
+        })
+    }
+
+    /// Token amounts backing one unit of liquidity on the given side.
+    fn amounts_per_liquidity(&self, side: DepositSide) -> (f64, f64) {
+        match side {
+            DepositSide::TokenAOnly => ((self.upper - self.lower) / (self.lower * self.upper), 0.0),
+            DepositSide::TokenBOnly => (0.0, self.upper - self.lower),
+            DepositSide::TwoSided => (
+                (self.upper - self.current) / (self.current * self.upper),
+                self.current - self.lower,
+            ),
+        }
+    }
+}
+
+/// Quotes a deposit of up to `amount_a` and `amount_b` into a tick range.
+///
+/// Single-sided ranges ignore the token that is not needed, so a position
+/// above the current price only consumes token A and one below it only
+/// consumes token B. Two-sided ranges use the liquidity of the limiting token.
+/// Consumed amounts are rounded up as the program charges them, and never
+/// exceed the amounts given.
+///
+/// # Errors
+/// Returns an error if the range is invalid, the required token amount is
+/// zero, or the amounts are too small to mint any liquidity.
+pub fn quote_deposit(
+    pool: &WhirlpoolState,
+    tick_lower: i32,
+    tick_upper: i32,
+    amount_a: u64,
+    amount_b: u64,
+    slippage_bps: u16,
+) -> Result<Quote> {
+    quote_deposit_at(
+        pool.tick_current,
+        pool.sqrt_price,
+        tick_lower,
+        tick_upper,
+        amount_a,
+        amount_b,
+        slippage_bps,
+    )
+}
+
+/// Quotes a deposit into a tick range of a pool at `tick_current` and the
+/// Q64.64 `sqrt_price`.
+///
+/// # Errors
+/// Same as [`quote_deposit`].
+pub fn quote_deposit_at(
+    tick_current: i32,
+    sqrt_price: u128,
+    tick_lower: i32,
+    tick_upper: i32,
+    amount_a: u64,
+    amount_b: u64,
+    slippage_bps: u16,
+) -> Result<Quote> {
+    let range = RangeSqrtPrices::new(sqrt_price, tick_lower, tick_upper)?;
+    let side = DepositSide::for_range(tick_current, tick_lower, tick_upper);
+    let (per_a, per_b) = range.amounts_per_liquidity(side);
+
+    let liquidity = match side {
+        DepositSide::TokenAOnly => {
+            if amount_a == 0 {
+                anyhow::bail!("Range is above the current price; token A is required");
+            }
+            amount_a as f64 / per_a
+        }
+        DepositSide::TokenBOnly => {
+            if amount_b == 0 {
+                anyhow::bail!("Range is below the current price; token B is required");
+            }
+            amount_b as f64 / per_b
+        }
+        DepositSide::TwoSided => {
+            if amount_a == 0 || amount_b == 0 {
+                anyhow::bail!("Range contains the current price; both tokens are required");
+            }
+            (amount_a as f64 / per_a).min(amount_b as f64 / per_b)
+        }
+    };
+
+    // The program rounds the cost of liquidity up; shrink the liquidity
+    // until that cost fits the amounts held
+    let mut liquidity = liquidity.floor() as u128;
+    let (used_a, used_b) = loop {
+        let (used_a, used_b) =
+            token_amounts_for_liquidity(liquidity, sqrt_price, tick_lower, tick_upper, true)
+                .ok_or_else(|| anyhow::anyhow!("Deposit amounts overflow"))?;
+        if used_a <= amount_a && used_b <= amount_b {
+            break (used_a, used_b);
+        }
+        let excess = |used: u64, held: u64| {
+            if used > held {
+                used as f64 / held.max(1) as f64
+            } else {
+                1.0
+            }
+        };
+        let scale = excess(used_a, amount_a).max(excess(used_b, amount_b));
+        liquidity = ((liquidity as f64 / scale).floor() as u128).min(liquidity - 1);
+    };
+    if liquidity == 0 {
+        anyhow::bail!("Amounts are too small to provide liquidity");
+    }
+
+    Ok(Quote::new(
+        side,
+        (tick_lower, tick_upper),
+        liquidity,
+        (used_a, used_b),
+        slippage_bps,
+    ))
+}
+
+/// Quotes depositing a USD budget into a Whirlpool range.
+///
+/// # Errors
+/// Same as [`quote_capital_at`].
+pub fn quote_capital(pool: &WhirlpoolState, params: &CapitalQuoteParams) -> Result<Quote> {
+    quote_capital_at(pool.tick_current, pool.sqrt_price, params)
+}
+
+/// Quotes depositing a USD budget into a tick range of a pool at
+/// `tick_current` and the Q64.64 `sqrt_price`.
+///
+/// The budget is split between the tokens in the ratio the range requires at
+/// the current price, so the whole amount becomes liquidity. Single-sided
+/// ranges put all of it into the one token they hold. Token amounts are
+/// rounded up, as the program rounds up what a liquidity amount costs.
+///
+/// # Errors
+/// Returns an error if the range is invalid, the capital or token price is
+/// not positive, or the capital is too small to mint any liquidity.
+pub fn quote_capital_at(
+    tick_current: i32,
+    sqrt_price: u128,
+    params: &CapitalQuoteParams,
+) -> Result<Quote> {
+    let range = RangeSqrtPrices::new(sqrt_price, params.tick_lower, params.tick_upper)?;
+    if params.capital_usd <= Decimal::ZERO {
+        anyhow::bail!("Capital must be positive, got {}", params.capital_usd);
+    }
+    if params.token_b_price_usd <= Decimal::ZERO {
+        anyhow::bail!(
+            "Token B price must be positive, got {}",
+            params.token_b_price_usd
+        );
+    }
+
+    let side = DepositSide::for_range(tick_current, params.tick_lower, params.tick_upper);
+    let (per_a, per_b) = range.amounts_per_liquidity(side);
+
+    // Value everything in raw token B units: the pool price is raw B per raw A
+    let price = (sqrt_price as f64 / Q64).powi(2);
+    let value_per_liquidity = per_a * price + per_b;
+    let capital_b = (params.capital_usd / params.token_b_price_usd)
+        .to_f64()
+        .unwrap_or_default()
+        * 10f64.powi(i32::from(params.decimals_b));
+
+    let liquidity = capital_b / value_per_liquidity;
+    if !liquidity.is_finite() || liquidity < 1.0 {
+        anyhow::bail!(
+            "Capital of {} USD is too small to provide liquidity",
+            params.capital_usd
+        );
+    }
+
+    let amount_a = (liquidity.floor() * per_a).ceil() as u64;
+    let amount_b = (liquidity.floor() * per_b).ceil() as u64;
+
+    Ok(Quote::new(
+        side,
+        (params.tick_lower, params.tick_upper),
+        liquidity.floor() as u128,
+        (amount_a, amount_b),
+        params.slippage_bps,
+    ))
+}
+
+/// Converts a tick to a (non-fixed-point) sqrt price.
+fn tick_to_sqrt_price_f64(tick: i32) -> f64 {
+    1.0001_f64.powi(tick).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+
+    fn pool_at_tick(tick_current: i32) -> WhirlpoolState {
+        let sqrt_price = (tick_to_sqrt_price_f64(tick_current) * Q64) as u128;
+        WhirlpoolState {
+            address: String::new(),
+            token_mint_a: Pubkey::new_unique(),
+            token_mint_b: Pubkey::new_unique(),
+            tick_current,
+            tick_spacing: 64,
+            sqrt_price,
+            price: Decimal::ONE,
+            liquidity: 0,
+            fee_rate: 30,
+            protocol_fee_rate_bps: 0,
+            fee_growth_global_a: 0,
+            fee_growth_global_b: 0,
+            rewards: Vec::new(),
+        }
+    }
+
+    fn capital(tick_lower: i32, tick_upper: i32, capital_usd: Decimal) -> CapitalQuoteParams {
+        CapitalQuoteParams {
+            tick_lower,
+            tick_upper,
+            capital_usd,
+            token_b_price_usd: Decimal::ONE,
+            decimals_b: 6,
+            slippage_bps: 100,
+        }
+    }
+
+    #[test]
+    fn test_deposit_side() {
+        assert_eq!(DepositSide::for_range(0, 64, 128), DepositSide::TokenAOnly);
+        assert_eq!(
+            DepositSide::for_range(128, 64, 128),
+            DepositSide::TokenBOnly
+        );
+        assert_eq!(DepositSide::for_range(64, 64, 128), DepositSide::TwoSided);
+        assert!(DepositSide::TokenAOnly.is_single_sided());
+    }
+
+    #[test]
+    fn test_quote_single_sided_above_price() {
+        let pool = pool_at_tick(0);
+        let quote = quote_deposit(&pool, 640, 1280, 1_000_000, 0, 100).unwrap();
+
+        assert_eq!(quote.side, DepositSide::TokenAOnly);
+        assert!(quote.liquidity > 0);
+        assert_eq!(quote.amount_b, 0);
+        assert_eq!(quote.token_max_b, 0);
+        assert!(quote.amount_a <= 1_000_000);
+        assert!(quote.token_max_a >= quote.amount_a);
+
+        // Token B alone cannot fund a range above the price
+        assert!(quote_deposit(&pool, 640, 1280, 0, 1_000_000, 100).is_err());
+    }
+
+    #[test]
+    fn test_quote_single_sided_below_price() {
+        let pool = pool_at_tick(0);
+        let quote = quote_deposit(&pool, -1280, -640, 5, 1_000_000, 0).unwrap();
+
+        assert_eq!(quote.side, DepositSide::TokenBOnly);
+        assert_eq!(quote.amount_a, 0);
+        assert!(quote.amount_b > 0);
+    }
+
+    #[test]
+    fn test_quote_two_sided_requires_both() {
+        let pool = pool_at_tick(0);
+        assert!(quote_deposit(&pool, -640, 640, 1_000_000, 0, 50).is_err());
+
+        let quote = quote_deposit(&pool, -640, 640, 1_000_000, 1_000_000, 50).unwrap();
+        assert_eq!(quote.side, DepositSide::TwoSided);
+        assert!(quote.amount_a > 0 && quote.amount_b > 0);
+        assert_eq!((quote.tick_lower, quote.tick_upper), (-640, 640));
+        assert!(quote.token_min_a < quote.amount_a && quote.amount_a < quote.token_max_a);
+    }
+
+    #[test]
+    fn test_zero_slippage_quote_covers_program_cost() {
+        let pool = pool_at_tick(10);
+        for (amount_a, amount_b) in [(1_000_000, 1_000_000), (37, 5_000), (3, 3)] {
+            let quote = quote_deposit(&pool, -640, 640, amount_a, amount_b, 0).unwrap();
+
+            // What the program charges for the quoted liquidity, rounded up
+            let (cost_a, cost_b) =
+                token_amounts_for_liquidity(quote.liquidity, pool.sqrt_price, -640, 640, true)
+                    .unwrap();
+            assert!(quote.token_max_a >= cost_a && quote.token_max_b >= cost_b);
+            assert!(quote.token_max_a <= amount_a && quote.token_max_b <= amount_b);
+        }
+
+        assert!(quote_deposit(&pool, -640, 640, 1, 0, 0).is_err());
+    }
+
+    #[test]
+    fn test_quote_capital_splits_symmetric_range_evenly() {
+        // At a price of one, a symmetric range holds close to equal amounts
+        let pool = pool_at_tick(0);
+        let quote = quote_capital(&pool, &capital(-640, 640, Decimal::from(1000))).unwrap();
+
+        assert_eq!(quote.side, DepositSide::TwoSided);
+        let total = quote.amount_a + quote.amount_b;
+        assert!(total.abs_diff(1_000_000_000) <= 2, "total {total}");
+        assert!(quote.amount_a.abs_diff(quote.amount_b) < 100_000_000 / 100);
+
+        // The same tokens quoted as amounts yield the same liquidity
+        let from_amounts =
+            quote_deposit(&pool, -640, 640, quote.amount_a, quote.amount_b, 100).unwrap();
+        let diff = from_amounts.liquidity.abs_diff(quote.liquidity);
+        assert!(diff * 1_000_000 < quote.liquidity, "diff {diff}");
+    }
+
+    #[test]
+    fn test_quote_capital_single_sided_and_slippage() {
+        let pool = pool_at_tick(0);
+
+        let above = quote_capital(&pool, &capital(640, 1280, Decimal::from(500))).unwrap();
+        assert_eq!(above.side, DepositSide::TokenAOnly);
+        assert_eq!(above.amount_b, 0);
+        // Token A is worth one token B at tick zero
+        assert!(above.amount_a.abs_diff(500_000_000) <= 1);
+        assert_eq!(above.token_max_a, 505_000_000);
+        assert_eq!(above.token_min_a, 495_000_000);
+
+        // A token B worth two dollars halves the amounts
+        let params = CapitalQuoteParams {
+            token_b_price_usd: Decimal::from(2),
+            ..capital(-1280, -640, Decimal::from(500))
+        };
+        let below = quote_capital(&pool, &params).unwrap();
+        assert_eq!(below.side, DepositSide::TokenBOnly);
+        assert_eq!(below.amount_a, 0);
+        assert!(below.amount_b.abs_diff(250_000_000) <= 1);
+    }
+
+    #[test]
+    fn test_quote_capital_rejects_bad_input() {
+        let pool = pool_at_tick(0);
+        assert!(quote_capital(&pool, &capital(640, 640, Decimal::from(100))).is_err());
+        assert!(quote_capital(&pool, &capital(-640, 640, Decimal::ZERO)).is_err());
+        assert!(quote_capital(&pool, &capital(-640, 640, Decimal::new(1, 10))).is_err());
+    }
+}
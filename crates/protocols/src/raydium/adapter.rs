@@ -0,0 +1,43 @@
+//! Raydium CLMM implementation of [`ProtocolAdapter`].
+
+use super::pool_reader::RaydiumPoolReader;
+use super::position_reader::RaydiumPositionReader;
+use crate::adapter::{PoolSnapshot, ProtocolAdapter};
+use crate::events::{OnChainPosition, Protocol};
+use crate::rpc::RpcProvider;
+use anyhow::Result;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Reads Raydium CLMM positions and pools.
+pub struct RaydiumAdapter {
+    /// Pool reader.
+    pool_reader: RaydiumPoolReader,
+    /// Position reader.
+    position_reader: RaydiumPositionReader,
+}
+
+impl RaydiumAdapter {
+    /// Creates a new Raydium adapter.
+    pub fn new(provider: Arc<RpcProvider>) -> Self {
+        Self {
+            pool_reader: RaydiumPoolReader::new(provider.clone()),
+            position_reader: RaydiumPositionReader::new(provider),
+        }
+    }
+}
+
+#[async_trait]
+impl ProtocolAdapter for RaydiumAdapter {
+    fn protocol(&self) -> Protocol {
+        Protocol::RaydiumClmm
+    }
+
+    async fn get_position(&self, position_address: &str) -> Result<OnChainPosition> {
+        self.position_reader.get_position(position_address).await
+    }
+
+    async fn get_pool(&self, pool_address: &str) -> Result<PoolSnapshot> {
+        Ok(self.pool_reader.get_pool_state(pool_address).await?.into())
+    }
+}
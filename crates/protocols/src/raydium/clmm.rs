@@ -0,0 +1,191 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_sdk::pubkey::Pubkey;
+
+/// Raydium CLMM program ID (mainnet).
+pub const RAYDIUM_CLMM_PROGRAM_ID: &str = "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK";
+
+/// Number of farm reward slots on a Raydium CLMM pool.
+pub const RAYDIUM_NUM_REWARDS: usize = 3;
+
+/// Number of ticks covered by one tick array, in units of tick spacing.
+pub const TICK_ARRAY_SIZE: i32 = 60;
+
+// Raydium accounts are zero-copy and packed, so the leading fields can be read
+// with Borsh. Only the fields up to the reward infos are declared; the trailing
+// bitmap, fee counters and padding are not needed and are left unread.
+
+/// Represents a Raydium CLMM pool account.
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone)]
+pub struct ClmmPool {
+    /// Discriminator to identify the account type.
+    pub discriminator: [u8; 8],
+    /// The bump seed for the pool.
+    pub bump: [u8; 1],
+    /// The AMM config account.
+    pub amm_config: Pubkey,
+    /// The pool creator.
+    pub owner: Pubkey,
+    /// The mint of token 0.
+    pub token_mint_0: Pubkey,
+    /// The mint of token 1.
+    pub token_mint_1: Pubkey,
+    /// The vault for token 0.
+    pub token_vault_0: Pubkey,
+    /// The vault for token 1.
+    pub token_vault_1: Pubkey,
+    /// The observation account.
+    pub observation_key: Pubkey,
+    /// Decimals of token 0.
+    pub mint_decimals_0: u8,
+    /// Decimals of token 1.
+    pub mint_decimals_1: u8,
+    /// The tick spacing.
+    pub tick_spacing: u16,
+    /// The liquidity amount.
+    pub liquidity: u128,
+    /// The square root price (Q64.64).
+    pub sqrt_price_x64: u128,
+    /// The current tick index.
+    pub tick_current: i32,
+    /// Padding.
+    pub padding3: u16,
+    /// Padding.
+    pub padding4: u16,
+    /// The fee growth global for token 0.
+    pub fee_growth_global_0_x64: u128,
+    /// The fee growth global for token 1.
+    pub fee_growth_global_1_x64: u128,
+    /// Protocol fees owed in token 0.
+    pub protocol_fees_token_0: u64,
+    /// Protocol fees owed in token 1.
+    pub protocol_fees_token_1: u64,
+    /// Cumulative swap input of token 0.
+    pub swap_in_amount_token_0: u128,
+    /// Cumulative swap output of token 1.
+    pub swap_out_amount_token_1: u128,
+    /// Cumulative swap input of token 1.
+    pub swap_in_amount_token_1: u128,
+    /// Cumulative swap output of token 0.
+    pub swap_out_amount_token_0: u128,
+    /// Pool status bitflags.
+    pub status: u8,
+    /// Padding.
+    pub padding: [u8; 7],
+    /// Farm reward slots.
+    pub reward_infos: [ClmmRewardInfo; RAYDIUM_NUM_REWARDS],
+}
+
+/// Farm reward configuration stored on a Raydium CLMM pool.
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, Default)]
+pub struct ClmmRewardInfo {
+    /// Reward state (0 = uninitialized).
+    pub reward_state: u8,
+    /// Emission start time (unix seconds).
+    pub open_time: u64,
+    /// Emission end time (unix seconds).
+    pub end_time: u64,
+    /// Last time the reward growth was updated.
+    pub last_update_time: u64,
+    /// Reward tokens emitted per second (Q64.64).
+    pub emissions_per_second_x64: u128,
+    /// Total rewards emitted so far.
+    pub reward_total_emissioned: u64,
+    /// Total rewards claimed so far.
+    pub reward_claimed: u64,
+    /// The reward token mint.
+    pub token_mint: Pubkey,
+    /// The vault holding reward tokens.
+    pub token_vault: Pubkey,
+    /// The authority allowed to configure emissions.
+    pub authority: Pubkey,
+    /// Global reward growth per unit of liquidity (Q64.64).
+    pub reward_growth_global_x64: u128,
+}
+
+impl ClmmRewardInfo {
+    /// Returns true if this reward slot has been initialized.
+    #[must_use]
+    pub fn is_initialized(&self) -> bool {
+        self.reward_state != 0 && self.token_mint != Pubkey::default()
+    }
+
+    /// Returns true if rewards are being emitted at the given unix time.
+    #[must_use]
+    pub fn is_emitting(&self, now: u64) -> bool {
+        self.is_initialized() && now >= self.open_time && now < self.end_time
+    }
+}
+
+/// Represents a Raydium CLMM personal position account.
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone)]
+pub struct PersonalPosition {
+    /// Discriminator to identify the account type.
+    pub discriminator: [u8; 8],
+    /// The bump seed for the position.
+    pub bump: [u8; 1],
+    /// The position NFT mint.
+    pub nft_mint: Pubkey,
+    /// The pool this position belongs to.
+    pub pool_id: Pubkey,
+    /// Lower tick index.
+    pub tick_lower_index: i32,
+    /// Upper tick index.
+    pub tick_upper_index: i32,
+    /// The liquidity in this position.
+    pub liquidity: u128,
+    /// Fee growth checkpoint for token 0.
+    pub fee_growth_inside_0_last_x64: u128,
+    /// Fee growth checkpoint for token 1.
+    pub fee_growth_inside_1_last_x64: u128,
+    /// Fee owed for token 0.
+    pub token_fees_owed_0: u64,
+    /// Fee owed for token 1.
+    pub token_fees_owed_1: u64,
+    /// Reward checkpoints and amounts owed.
+    pub reward_infos: [PersonalPositionRewardInfo; RAYDIUM_NUM_REWARDS],
+}
+
+/// Per-reward state stored on a Raydium personal position.
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, Default)]
+pub struct PersonalPositionRewardInfo {
+    /// Reward growth inside the range at the last update (Q64.64).
+    pub growth_inside_last_x64: u128,
+    /// Reward tokens owed to the position.
+    pub reward_amount_owed: u64,
+}
+
+/// Returns the start tick of the tick array containing `tick`.
+#[must_use]
+pub fn tick_array_start_index(tick: i32, tick_spacing: u16) -> i32 {
+    let ticks_per_array = i32::from(tick_spacing) * TICK_ARRAY_SIZE;
+    tick.div_euclid(ticks_per_array) * ticks_per_array
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tick_array_start_index() {
+        assert_eq!(tick_array_start_index(0, 10), 0);
+        assert_eq!(tick_array_start_index(599, 10), 0);
+        assert_eq!(tick_array_start_index(600, 10), 600);
+        assert_eq!(tick_array_start_index(-1, 10), -600);
+    }
+
+    #[test]
+    fn test_reward_emitting_window() {
+        let info = ClmmRewardInfo {
+            reward_state: 1,
+            open_time: 100,
+            end_time: 200,
+            token_mint: Pubkey::new_unique(),
+            ..Default::default()
+        };
+
+        assert!(!info.is_emitting(99));
+        assert!(info.is_emitting(100));
+        assert!(!info.is_emitting(200));
+        assert!(!ClmmRewardInfo::default().is_initialized());
+    }
+}
@@ -0,0 +1,1122 @@
+//! Raydium CLMM executor for on-chain operations.
+//!
+//! Provides functionality to execute LP operations on Raydium CLMM pools:
+//! - Open positions (each backed by a freshly minted position NFT)
+//! - Increase and decrease liquidity
+//! - Collect fees and harvest farm rewards
+//! - Close positions
+//!
+//! Instructions follow the CLMM program's Anchor interface: each starts with
+//! the first 8 bytes of `sha256("global:<instruction_name>")`, followed by
+//! the Borsh-encoded arguments.
+
+use super::clmm::{PersonalPosition, TICK_ARRAY_SIZE, tick_array_start_index};
+use super::pool_reader::{RaydiumPoolReader, RaydiumPoolState};
+use super::position_reader::{RaydiumPositionReader, derive_personal_position_with_program};
+use crate::orca::executor::{
+    ASSOCIATED_TOKEN_PROGRAM_ID, DecreaseLiquidityParams, ExecutionResult, IncreaseLiquidityParams,
+    OpenPositionParams, SYSTEM_PROGRAM_ID, TOKEN_PROGRAM_ID,
+};
+use crate::risk::TOKEN_METADATA_PROGRAM_ID;
+use crate::rpc::RpcProvider;
+use anyhow::{Context, Result};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::Transaction,
+};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::{debug, info};
+
+/// Token-2022 program ID.
+pub const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+
+/// Memo program ID.
+pub const MEMO_PROGRAM_ID: &str = "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr";
+
+/// Rent sysvar ID.
+pub const RENT_SYSVAR_ID: &str = "SysvarRent111111111111111111111111111111111";
+
+/// `open_position_v2` instruction discriminator.
+const OPEN_POSITION_V2_DISCRIMINATOR: [u8; 8] = [0x4d, 0xb8, 0x4a, 0xd6, 0x70, 0x56, 0xf1, 0xc7];
+/// `increase_liquidity_v2` instruction discriminator.
+const INCREASE_LIQUIDITY_V2_DISCRIMINATOR: [u8; 8] =
+    [0x85, 0x1d, 0x59, 0xdf, 0x45, 0xee, 0xb0, 0x0a];
+/// `decrease_liquidity_v2` instruction discriminator.
+const DECREASE_LIQUIDITY_V2_DISCRIMINATOR: [u8; 8] =
+    [0x3a, 0x7f, 0xbc, 0x3e, 0x4f, 0x52, 0xc4, 0x60];
+/// `close_position` instruction discriminator.
+const CLOSE_POSITION_DISCRIMINATOR: [u8; 8] = [0x7b, 0x86, 0x51, 0x00, 0x31, 0x44, 0x62, 0x62];
+
+/// Tick arrays tracked by the pool's own bitmap on each side of tick 0.
+/// Arrays beyond it are tracked by the bitmap extension account.
+const TICK_ARRAY_BITMAP_SIZE: i32 = 512;
+
+/// Executor for Raydium CLMM operations.
+///
+/// Also available as [`RaydiumClmmExecutor`].
+pub struct RaydiumExecutor {
+    /// RPC provider for blockchain interaction.
+    provider: Arc<RpcProvider>,
+    /// Raydium CLMM program ID.
+    program_id: Pubkey,
+    /// Token program ID.
+    token_program: Pubkey,
+    /// Token-2022 program ID.
+    token_program_2022: Pubkey,
+    /// Memo program ID.
+    memo_program: Pubkey,
+    /// Associated token program ID.
+    ata_program: Pubkey,
+    /// System program ID.
+    system_program: Pubkey,
+    /// Token metadata program ID.
+    metadata_program: Pubkey,
+    /// Rent sysvar ID.
+    rent_sysvar: Pubkey,
+}
+
+/// Name the executor goes by alongside the protocol's other CLMM types.
+pub type RaydiumClmmExecutor = RaydiumExecutor;
+
+/// Token program (SPL Token or Token-2022) owning each pool and reward mint.
+type MintPrograms = HashMap<Pubkey, Pubkey>;
+
+/// Accounts shared by the instructions that touch a position's liquidity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RangeAccounts {
+    /// Pool address.
+    pool: Pubkey,
+    /// Pool-wide position aggregating all liquidity in the range.
+    protocol_position: Pubkey,
+    /// Tick array holding the lower tick.
+    tick_array_lower: Pubkey,
+    /// Tick array holding the upper tick.
+    tick_array_upper: Pubkey,
+    /// Start index of the lower tick array.
+    tick_array_lower_start: i32,
+    /// Start index of the upper tick array.
+    tick_array_upper_start: i32,
+    /// Tick array bitmap extension, when either tick array lies outside
+    /// the range the pool's bitmap covers.
+    bitmap_extension: Option<Pubkey>,
+}
+
+impl RaydiumExecutor {
+    /// Creates a new RaydiumExecutor for the provider's cluster.
+    pub fn new(provider: Arc<RpcProvider>) -> Self {
+        Self {
+            program_id: provider.cluster().raydium_clmm_program_id(),
+            provider,
+            token_program: Pubkey::from_str(TOKEN_PROGRAM_ID).expect("Invalid token program ID"),
+            token_program_2022: Pubkey::from_str(TOKEN_2022_PROGRAM_ID)
+                .expect("Invalid token-2022 program ID"),
+            memo_program: Pubkey::from_str(MEMO_PROGRAM_ID).expect("Invalid memo program ID"),
+            ata_program: Pubkey::from_str(ASSOCIATED_TOKEN_PROGRAM_ID)
+                .expect("Invalid ATA program ID"),
+            system_program: Pubkey::from_str(SYSTEM_PROGRAM_ID).expect("Invalid system program ID"),
+            metadata_program: Pubkey::from_str(TOKEN_METADATA_PROGRAM_ID)
+                .expect("Invalid metadata program ID"),
+            rent_sysvar: Pubkey::from_str(RENT_SYSVAR_ID).expect("Invalid rent sysvar ID"),
+        }
+    }
+
+    /// Opens a position and deposits into it in one transaction.
+    ///
+    /// Raydium positions are owned through an NFT; a new mint is generated
+    /// and co-signs the transaction. Returns the personal position address
+    /// along with the execution result.
+    pub async fn open_position<S: Signer>(
+        &self,
+        params: &OpenPositionParams,
+        payer: &S,
+    ) -> Result<(Pubkey, ExecutionResult)> {
+        let pool_state = RaydiumPoolReader::new(self.provider.clone())
+            .get_pool_state(&params.pool.to_string())
+            .await
+            .context("Failed to fetch pool state")?;
+        let mint_programs = self.fetch_mint_programs(&pool_state).await?;
+
+        let nft_mint = Keypair::new();
+        let ix = self.build_open_position_instruction(
+            params,
+            &pool_state,
+            &mint_programs,
+            &payer.pubkey(),
+            &nft_mint.pubkey(),
+        )?;
+        let position = derive_personal_position_with_program(&nft_mint.pubkey(), &self.program_id);
+
+        info!(
+            pool = %params.pool,
+            position = %position,
+            tick_lower = params.quote.tick_lower,
+            tick_upper = params.quote.tick_upper,
+            liquidity = params.quote.liquidity,
+            "Opening new Raydium position"
+        );
+
+        let result = self.send_transaction(&[ix], payer, &[&nft_mint]).await?;
+        Ok((position, result))
+    }
+
+    /// Increases liquidity in an existing position.
+    pub async fn increase_liquidity<S: Signer>(
+        &self,
+        params: &IncreaseLiquidityParams,
+        payer: &S,
+    ) -> Result<ExecutionResult> {
+        info!(
+            position = %params.position,
+            liquidity = params.liquidity_amount,
+            "Increasing Raydium liquidity"
+        );
+
+        let (personal_position, pool_state, mint_programs) =
+            self.fetch_position(&params.position).await?;
+        let ix = self.build_increase_liquidity_instruction(
+            &params.position,
+            &personal_position,
+            &pool_state,
+            &mint_programs,
+            &payer.pubkey(),
+            params.liquidity_amount,
+            params.token_max_a,
+            params.token_max_b,
+        )?;
+
+        self.send_transaction(&[ix], payer, &[]).await
+    }
+
+    /// Decreases liquidity in an existing position.
+    ///
+    /// Accrued fees and farm rewards are paid out along with the tokens.
+    pub async fn decrease_liquidity<S: Signer>(
+        &self,
+        params: &DecreaseLiquidityParams,
+        payer: &S,
+    ) -> Result<ExecutionResult> {
+        info!(
+            position = %params.position,
+            liquidity = params.liquidity_amount,
+            "Decreasing Raydium liquidity"
+        );
+
+        let (personal_position, pool_state, mint_programs) =
+            self.fetch_position(&params.position).await?;
+        let ix = self.build_decrease_liquidity_instruction(
+            &params.position,
+            &personal_position,
+            &pool_state,
+            &mint_programs,
+            &payer.pubkey(),
+            params.liquidity_amount,
+            params.token_min_a,
+            params.token_min_b,
+        )?;
+
+        self.send_transaction(&[ix], payer, &[]).await
+    }
+
+    /// Collects accrued fees from a position.
+    ///
+    /// Fees are paid out by the same zero-liquidity decrease that harvests
+    /// rewards, so this collects both.
+    pub async fn collect_fees<S: Signer>(
+        &self,
+        position: &Pubkey,
+        payer: &S,
+    ) -> Result<ExecutionResult> {
+        self.harvest_rewards(position, payer).await
+    }
+
+    /// Closes a position, withdrawing its remaining liquidity, fees and
+    /// rewards first, and burns the position NFT.
+    pub async fn close_position<S: Signer>(
+        &self,
+        position: &Pubkey,
+        payer: &S,
+    ) -> Result<ExecutionResult> {
+        info!(position = %position, "Closing Raydium position");
+
+        let (personal_position, pool_state, mint_programs) = self.fetch_position(position).await?;
+        let owner = payer.pubkey();
+        let decrease_ix = self.build_decrease_liquidity_instruction(
+            position,
+            &personal_position,
+            &pool_state,
+            &mint_programs,
+            &owner,
+            personal_position.liquidity,
+            0,
+            0,
+        )?;
+        let close_ix = self.build_close_position_instruction(position, &personal_position, &owner);
+
+        self.send_transaction(&[decrease_ix, close_ix], payer, &[])
+            .await
+    }
+
+    /// Harvests farm rewards and accrued fees from a position.
+    ///
+    /// Raydium has no dedicated harvest instruction; rewards are paid out by a
+    /// zero-liquidity `decrease_liquidity_v2` with the reward accounts appended.
+    pub async fn harvest_rewards<S: Signer>(
+        &self,
+        position: &Pubkey,
+        payer: &S,
+    ) -> Result<ExecutionResult> {
+        info!(position = %position, "Harvesting Raydium rewards");
+
+        let (personal_position, pool_state, mint_programs) = self.fetch_position(position).await?;
+        let ix = self.build_harvest_instruction(
+            position,
+            &personal_position,
+            &pool_state,
+            &mint_programs,
+            &payer.pubkey(),
+        )?;
+
+        self.send_transaction(&[ix], payer, &[]).await
+    }
+
+    async fn fetch_position(
+        &self,
+        position: &Pubkey,
+    ) -> Result<(PersonalPosition, RaydiumPoolState, MintPrograms)> {
+        let personal_position = RaydiumPositionReader::new(self.provider.clone())
+            .get_personal_position(&position.to_string())
+            .await
+            .context("Failed to fetch position state")?;
+        let pool_state = RaydiumPoolReader::new(self.provider.clone())
+            .get_pool_state(&personal_position.pool_id.to_string())
+            .await
+            .context("Failed to fetch pool state")?;
+        let mint_programs = self.fetch_mint_programs(&pool_state).await?;
+        Ok((personal_position, pool_state, mint_programs))
+    }
+
+    /// Looks up which token program owns each of the pool's token and reward
+    /// mints; token accounts for Token-2022 mints live at different addresses.
+    async fn fetch_mint_programs(&self, pool: &RaydiumPoolState) -> Result<MintPrograms> {
+        let mints: Vec<Pubkey> = [pool.token_mint_a, pool.token_mint_b]
+            .into_iter()
+            .chain(pool.rewards.iter().map(|reward| reward.mint))
+            .collect();
+        let accounts = self
+            .provider
+            .get_multiple_accounts(&mints)
+            .await
+            .context("Failed to fetch token mints")?;
+
+        mints
+            .into_iter()
+            .zip(accounts)
+            .map(|(mint, account)| {
+                let account = account.with_context(|| format!("Mint {} not found", mint))?;
+                if account.owner != self.token_program && account.owner != self.token_program_2022 {
+                    anyhow::bail!("Mint {} is not owned by a token program", mint);
+                }
+                Ok((mint, account.owner))
+            })
+            .collect()
+    }
+
+    fn build_open_position_instruction(
+        &self,
+        params: &OpenPositionParams,
+        pool: &RaydiumPoolState,
+        mint_programs: &MintPrograms,
+        owner: &Pubkey,
+        nft_mint: &Pubkey,
+    ) -> Result<Instruction> {
+        let quote = &params.quote;
+        let range = self.range_accounts(pool, quote.tick_lower, quote.tick_upper)?;
+
+        let mut data = Vec::with_capacity(60);
+        data.extend_from_slice(&OPEN_POSITION_V2_DISCRIMINATOR);
+        data.extend_from_slice(&quote.tick_lower.to_le_bytes());
+        data.extend_from_slice(&quote.tick_upper.to_le_bytes());
+        data.extend_from_slice(&range.tick_array_lower_start.to_le_bytes());
+        data.extend_from_slice(&range.tick_array_upper_start.to_le_bytes());
+        data.extend_from_slice(&quote.liquidity.to_le_bytes());
+        data.extend_from_slice(&quote.token_max_a.to_le_bytes()); // amount_0_max
+        data.extend_from_slice(&quote.token_max_b.to_le_bytes()); // amount_1_max
+        data.push(1); // with_metadata
+        data.push(0); // base_flag: None, deposit by liquidity
+
+        let (metadata, _) = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                self.metadata_program.as_ref(),
+                nft_mint.as_ref(),
+            ],
+            &self.metadata_program,
+        );
+        let personal_position = derive_personal_position_with_program(nft_mint, &self.program_id);
+        // `open_position_v2` mints the position NFT under the legacy token program
+        let nft_account = self.derive_ata(owner, nft_mint, &self.token_program);
+        let token_account_a = self.derive_mint_ata(owner, &pool.token_mint_a, mint_programs)?;
+        let token_account_b = self.derive_mint_ata(owner, &pool.token_mint_b, mint_programs)?;
+
+        let mut accounts = vec![
+            AccountMeta::new(*owner, true),                            // payer
+            AccountMeta::new_readonly(*owner, false),                  // position_nft_owner
+            AccountMeta::new(*nft_mint, true),                         // position_nft_mint
+            AccountMeta::new(nft_account, false),                      // position_nft_account
+            AccountMeta::new(metadata, false),                         // metadata_account
+            AccountMeta::new(range.pool, false),                       // pool_state
+            AccountMeta::new(range.protocol_position, false),          // protocol_position
+            AccountMeta::new(range.tick_array_lower, false),           // tick_array_lower
+            AccountMeta::new(range.tick_array_upper, false),           // tick_array_upper
+            AccountMeta::new(personal_position, false),                // personal_position
+            AccountMeta::new(token_account_a, false),                  // token_account_0
+            AccountMeta::new(token_account_b, false),                  // token_account_1
+            AccountMeta::new(pool.token_vault_a, false),               // token_vault_0
+            AccountMeta::new(pool.token_vault_b, false),               // token_vault_1
+            AccountMeta::new_readonly(self.rent_sysvar, false),        // rent
+            AccountMeta::new_readonly(self.system_program, false),     // system_program
+            AccountMeta::new_readonly(self.token_program, false),      // token_program
+            AccountMeta::new_readonly(self.ata_program, false),        // associated_token_program
+            AccountMeta::new_readonly(self.metadata_program, false),   // metadata_program
+            AccountMeta::new_readonly(self.token_program_2022, false), // token_program_2022
+            AccountMeta::new_readonly(pool.token_mint_a, false),       // vault_0_mint
+            AccountMeta::new_readonly(pool.token_mint_b, false),       // vault_1_mint
+        ];
+        if let Some(extension) = range.bitmap_extension {
+            accounts.push(AccountMeta::new(extension, false));
+        }
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn build_increase_liquidity_instruction(
+        &self,
+        position: &Pubkey,
+        personal_position: &PersonalPosition,
+        pool: &RaydiumPoolState,
+        mint_programs: &MintPrograms,
+        owner: &Pubkey,
+        liquidity: u128,
+        amount_0_max: u64,
+        amount_1_max: u64,
+    ) -> Result<Instruction> {
+        let range = self.range_accounts(
+            pool,
+            personal_position.tick_lower_index,
+            personal_position.tick_upper_index,
+        )?;
+
+        let mut data = Vec::with_capacity(41);
+        data.extend_from_slice(&INCREASE_LIQUIDITY_V2_DISCRIMINATOR);
+        data.extend_from_slice(&liquidity.to_le_bytes());
+        data.extend_from_slice(&amount_0_max.to_le_bytes());
+        data.extend_from_slice(&amount_1_max.to_le_bytes());
+        data.push(0); // base_flag: None, deposit by liquidity
+
+        let nft_account = self.derive_ata(owner, &personal_position.nft_mint, &self.token_program);
+        let token_account_a = self.derive_mint_ata(owner, &pool.token_mint_a, mint_programs)?;
+        let token_account_b = self.derive_mint_ata(owner, &pool.token_mint_b, mint_programs)?;
+
+        let mut accounts = vec![
+            AccountMeta::new_readonly(*owner, true),          // nft_owner
+            AccountMeta::new_readonly(nft_account, false),    // nft_account
+            AccountMeta::new(range.pool, false),              // pool_state
+            AccountMeta::new(range.protocol_position, false), // protocol_position
+            AccountMeta::new(*position, false),               // personal_position
+            AccountMeta::new(range.tick_array_lower, false),  // tick_array_lower
+            AccountMeta::new(range.tick_array_upper, false),  // tick_array_upper
+            AccountMeta::new(token_account_a, false),         // token_account_0
+            AccountMeta::new(token_account_b, false),         // token_account_1
+            AccountMeta::new(pool.token_vault_a, false),      // token_vault_0
+            AccountMeta::new(pool.token_vault_b, false),      // token_vault_1
+            AccountMeta::new_readonly(self.token_program, false), // token_program
+            AccountMeta::new_readonly(self.token_program_2022, false), // token_program_2022
+            AccountMeta::new_readonly(pool.token_mint_a, false), // vault_0_mint
+            AccountMeta::new_readonly(pool.token_mint_b, false), // vault_1_mint
+        ];
+        if let Some(extension) = range.bitmap_extension {
+            accounts.push(AccountMeta::new(extension, false));
+        }
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        })
+    }
+
+    fn build_harvest_instruction(
+        &self,
+        position: &Pubkey,
+        personal_position: &PersonalPosition,
+        pool: &RaydiumPoolState,
+        mint_programs: &MintPrograms,
+        owner: &Pubkey,
+    ) -> Result<Instruction> {
+        self.build_decrease_liquidity_instruction(
+            position,
+            personal_position,
+            pool,
+            mint_programs,
+            owner,
+            0,
+            0,
+            0,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn build_decrease_liquidity_instruction(
+        &self,
+        position: &Pubkey,
+        personal_position: &PersonalPosition,
+        pool: &RaydiumPoolState,
+        mint_programs: &MintPrograms,
+        owner: &Pubkey,
+        liquidity: u128,
+        amount_0_min: u64,
+        amount_1_min: u64,
+    ) -> Result<Instruction> {
+        let range = self.range_accounts(
+            pool,
+            personal_position.tick_lower_index,
+            personal_position.tick_upper_index,
+        )?;
+
+        let mut data = Vec::with_capacity(40);
+        data.extend_from_slice(&DECREASE_LIQUIDITY_V2_DISCRIMINATOR);
+        data.extend_from_slice(&liquidity.to_le_bytes());
+        data.extend_from_slice(&amount_0_min.to_le_bytes());
+        data.extend_from_slice(&amount_1_min.to_le_bytes());
+
+        let nft_account = self.derive_ata(owner, &personal_position.nft_mint, &self.token_program);
+        let recipient_a = self.derive_mint_ata(owner, &pool.token_mint_a, mint_programs)?;
+        let recipient_b = self.derive_mint_ata(owner, &pool.token_mint_b, mint_programs)?;
+
+        let mut accounts = vec![
+            AccountMeta::new_readonly(*owner, true),          // nft_owner
+            AccountMeta::new_readonly(nft_account, false),    // nft_account
+            AccountMeta::new(*position, false),               // personal_position
+            AccountMeta::new(range.pool, false),              // pool_state
+            AccountMeta::new(range.protocol_position, false), // protocol_position
+            AccountMeta::new(pool.token_vault_a, false),      // token_vault_0
+            AccountMeta::new(pool.token_vault_b, false),      // token_vault_1
+            AccountMeta::new(range.tick_array_lower, false),  // tick_array_lower
+            AccountMeta::new(range.tick_array_upper, false),  // tick_array_upper
+            AccountMeta::new(recipient_a, false),             // recipient_token_account_0
+            AccountMeta::new(recipient_b, false),             // recipient_token_account_1
+            AccountMeta::new_readonly(self.token_program, false), // token_program
+            AccountMeta::new_readonly(self.token_program_2022, false), // token_program_2022
+            AccountMeta::new_readonly(self.memo_program, false), // memo_program
+            AccountMeta::new_readonly(pool.token_mint_a, false), // vault_0_mint
+            AccountMeta::new_readonly(pool.token_mint_b, false), // vault_1_mint
+        ];
+
+        // Remaining accounts: the bitmap extension if needed, then
+        // (reward_vault, recipient, reward_mint) per reward
+        if let Some(extension) = range.bitmap_extension {
+            accounts.push(AccountMeta::new(extension, false));
+        }
+        for reward in &pool.rewards {
+            accounts.push(AccountMeta::new(reward.vault, false));
+            let recipient = self.derive_mint_ata(owner, &reward.mint, mint_programs)?;
+            accounts.push(AccountMeta::new(recipient, false));
+            accounts.push(AccountMeta::new_readonly(reward.mint, false));
+        }
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        })
+    }
+
+    fn build_close_position_instruction(
+        &self,
+        position: &Pubkey,
+        personal_position: &PersonalPosition,
+        owner: &Pubkey,
+    ) -> Instruction {
+        let nft_mint = personal_position.nft_mint;
+        let nft_account = self.derive_ata(owner, &nft_mint, &self.token_program);
+        Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(*owner, true),                        // nft_owner
+                AccountMeta::new(nft_mint, false),                     // position_nft_mint
+                AccountMeta::new(nft_account, false),                  // position_nft_account
+                AccountMeta::new(*position, false),                    // personal_position
+                AccountMeta::new_readonly(self.system_program, false), // system_program
+                AccountMeta::new_readonly(self.token_program, false),  // token_program
+            ],
+            data: CLOSE_POSITION_DISCRIMINATOR.to_vec(),
+        }
+    }
+
+    /// Derives the pool-level accounts for a tick range.
+    fn range_accounts(
+        &self,
+        pool: &RaydiumPoolState,
+        tick_lower: i32,
+        tick_upper: i32,
+    ) -> Result<RangeAccounts> {
+        if tick_lower >= tick_upper {
+            anyhow::bail!("Invalid tick range: {} >= {}", tick_lower, tick_upper);
+        }
+        let pool_id = Pubkey::from_str(&pool.address).context("Invalid pool address")?;
+
+        let (protocol_position, _) = Pubkey::find_program_address(
+            &[
+                b"position",
+                pool_id.as_ref(),
+                &tick_lower.to_be_bytes(),
+                &tick_upper.to_be_bytes(),
+            ],
+            &self.program_id,
+        );
+        let lower_start = tick_array_start_index(tick_lower, pool.tick_spacing);
+        let upper_start = tick_array_start_index(tick_upper, pool.tick_spacing);
+
+        let bitmap_limit = i32::from(pool.tick_spacing) * TICK_ARRAY_SIZE * TICK_ARRAY_BITMAP_SIZE;
+        let outside_bitmap = |start: i32| start >= bitmap_limit || start < -bitmap_limit;
+        let bitmap_extension =
+            (outside_bitmap(lower_start) || outside_bitmap(upper_start)).then(|| {
+                Pubkey::find_program_address(
+                    &[b"pool_tick_array_bitmap_extension", pool_id.as_ref()],
+                    &self.program_id,
+                )
+                .0
+            });
+
+        Ok(RangeAccounts {
+            pool: pool_id,
+            protocol_position,
+            tick_array_lower: self.derive_tick_array(&pool_id, lower_start),
+            tick_array_upper: self.derive_tick_array(&pool_id, upper_start),
+            tick_array_lower_start: lower_start,
+            tick_array_upper_start: upper_start,
+            bitmap_extension,
+        })
+    }
+
+    fn derive_tick_array(&self, pool: &Pubkey, start_index: i32) -> Pubkey {
+        let (tick_array, _bump) = Pubkey::find_program_address(
+            &[b"tick_array", pool.as_ref(), &start_index.to_be_bytes()],
+            &self.program_id,
+        );
+        tick_array
+    }
+
+    fn derive_ata(&self, owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+        let (ata, _bump) = Pubkey::find_program_address(
+            &[owner.as_ref(), token_program.as_ref(), mint.as_ref()],
+            &self.ata_program,
+        );
+        ata
+    }
+
+    /// Derives `owner`'s token account for a pool or reward mint under the
+    /// token program that owns the mint.
+    fn derive_mint_ata(
+        &self,
+        owner: &Pubkey,
+        mint: &Pubkey,
+        mint_programs: &MintPrograms,
+    ) -> Result<Pubkey> {
+        let token_program = mint_programs
+            .get(mint)
+            .with_context(|| format!("Token program of mint {} is unknown", mint))?;
+        Ok(self.derive_ata(owner, mint, token_program))
+    }
+
+    async fn send_transaction<S: Signer>(
+        &self,
+        instructions: &[Instruction],
+        payer: &S,
+        co_signers: &[&Keypair],
+    ) -> Result<ExecutionResult> {
+        let recent_blockhash = self
+            .provider
+            .get_latest_blockhash()
+            .await
+            .context("Failed to get recent blockhash")?;
+
+        let mut signers: Vec<&dyn Signer> = vec![payer];
+        signers.extend(co_signers.iter().map(|signer| *signer as &dyn Signer));
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &signers,
+            recent_blockhash,
+        );
+
+        debug!("Sending transaction...");
+
+        match self
+            .provider
+            .send_and_confirm_transaction(&transaction)
+            .await
+        {
+            Ok(signature) => {
+                info!(signature = %signature, "Transaction confirmed");
+                Ok(ExecutionResult::confirmed(&self.provider, signature, &payer.pubkey()).await)
+            }
+            Err(e) => {
+                let signature = transaction.signatures.first().copied().unwrap_or_default();
+                Ok(ExecutionResult::failure(signature, e.to_string()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orca::pool_reader::RewardEmission;
+    use crate::quote::quote_deposit_at;
+    use crate::raydium::clmm::RAYDIUM_CLMM_PROGRAM_ID;
+    use crate::rpc::RAYDIUM_CLMM_DEVNET_PROGRAM_ID;
+    use rust_decimal::Decimal;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    #[test]
+    fn test_program_ids() {
+        assert!(Pubkey::from_str(RAYDIUM_CLMM_PROGRAM_ID).is_ok());
+        assert!(Pubkey::from_str(TOKEN_2022_PROGRAM_ID).is_ok());
+        assert!(Pubkey::from_str(MEMO_PROGRAM_ID).is_ok());
+    }
+
+    #[test]
+    fn test_program_id_follows_cluster() {
+        let devnet = RaydiumExecutor::new(Arc::new(RpcProvider::devnet()));
+        assert_eq!(
+            devnet.program_id,
+            Pubkey::from_str(RAYDIUM_CLMM_DEVNET_PROGRAM_ID).unwrap()
+        );
+        let mainnet = RaydiumExecutor::new(Arc::new(RpcProvider::mainnet()));
+        assert_eq!(
+            mainnet.program_id,
+            Pubkey::from_str(RAYDIUM_CLMM_PROGRAM_ID).unwrap()
+        );
+    }
+
+    fn executor() -> RaydiumExecutor {
+        RaydiumExecutor::new(Arc::new(RpcProvider::new(crate::rpc::RpcConfig::default())))
+    }
+
+    fn pool(rewards: Vec<RewardEmission>) -> RaydiumPoolState {
+        RaydiumPoolState {
+            address: Pubkey::new_unique().to_string(),
+            token_mint_a: Pubkey::new_unique(),
+            token_mint_b: Pubkey::new_unique(),
+            token_vault_a: Pubkey::new_unique(),
+            token_vault_b: Pubkey::new_unique(),
+            tick_current: 0,
+            tick_spacing: 10,
+            sqrt_price: 1u128 << 64,
+            price: Decimal::ONE,
+            liquidity: 0,
+            fee_growth_global_a: 0,
+            fee_growth_global_b: 0,
+            rewards,
+        }
+    }
+
+    /// Every pool and reward mint owned by `token_program`.
+    fn mint_programs(pool: &RaydiumPoolState, token_program: &str) -> MintPrograms {
+        let token_program = Pubkey::from_str(token_program).unwrap();
+        [pool.token_mint_a, pool.token_mint_b]
+            .into_iter()
+            .chain(pool.rewards.iter().map(|reward| reward.mint))
+            .map(|mint| (mint, token_program))
+            .collect()
+    }
+
+    fn personal_position(
+        pool: &RaydiumPoolState,
+        tick_lower: i32,
+        tick_upper: i32,
+    ) -> PersonalPosition {
+        PersonalPosition {
+            discriminator: [0; 8],
+            bump: [0],
+            nft_mint: Pubkey::new_unique(),
+            pool_id: Pubkey::from_str(&pool.address).unwrap(),
+            tick_lower_index: tick_lower,
+            tick_upper_index: tick_upper,
+            liquidity: 1_000,
+            fee_growth_inside_0_last_x64: 0,
+            fee_growth_inside_1_last_x64: 0,
+            token_fees_owed_0: 0,
+            token_fees_owed_1: 0,
+            reward_infos: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_discriminators_match_anchor_names() {
+        let discriminator = |name: &str| {
+            let hash = solana_sdk::hash::hashv(&[format!("global:{}", name).as_bytes()]);
+            hash.to_bytes()[..8].to_vec()
+        };
+        assert_eq!(
+            discriminator("open_position_v2"),
+            OPEN_POSITION_V2_DISCRIMINATOR
+        );
+        assert_eq!(
+            discriminator("increase_liquidity_v2"),
+            INCREASE_LIQUIDITY_V2_DISCRIMINATOR
+        );
+        assert_eq!(
+            discriminator("decrease_liquidity_v2"),
+            DECREASE_LIQUIDITY_V2_DISCRIMINATOR
+        );
+        assert_eq!(
+            discriminator("close_position"),
+            CLOSE_POSITION_DISCRIMINATOR
+        );
+    }
+
+    #[test]
+    fn test_harvest_instruction_appends_reward_accounts() {
+        let executor = executor();
+        let reward = RewardEmission {
+            index: 0,
+            mint: Pubkey::new_unique(),
+            vault: Pubkey::new_unique(),
+            emissions_per_second: Decimal::ONE,
+            growth_global_x64: 0,
+        };
+        let pool = pool(vec![reward.clone()]);
+        let personal_position = personal_position(&pool, -600, 600);
+
+        let ix = executor
+            .build_harvest_instruction(
+                &Pubkey::new_unique(),
+                &personal_position,
+                &pool,
+                &mint_programs(&pool, TOKEN_PROGRAM_ID),
+                &Pubkey::new_unique(),
+            )
+            .unwrap();
+
+        // Zero liquidity and zero minimums
+        assert_eq!(ix.data.len(), 40);
+        assert!(ix.data[8..].iter().all(|b| *b == 0));
+        assert_eq!(ix.accounts.len(), 16 + 3);
+        assert_eq!(ix.accounts[16].pubkey, reward.vault);
+        assert_eq!(ix.accounts[18].pubkey, reward.mint);
+    }
+
+    #[test]
+    fn test_open_position_instruction_layout() {
+        let executor = executor();
+        let pool = pool(Vec::new());
+        let owner = Pubkey::new_unique();
+        let nft_mint = Pubkey::new_unique();
+        let params = OpenPositionParams {
+            pool: Pubkey::from_str(&pool.address).unwrap(),
+            quote: quote_deposit_at(
+                pool.tick_current,
+                pool.sqrt_price,
+                -1100,
+                1200,
+                1_000_000,
+                1_000_000,
+                50,
+            )
+            .unwrap(),
+        };
+
+        let ix = executor
+            .build_open_position_instruction(
+                &params,
+                &pool,
+                &mint_programs(&pool, TOKEN_PROGRAM_ID),
+                &owner,
+                &nft_mint,
+            )
+            .unwrap();
+
+        // Discriminator, 4 x i32, u128, 2 x u64, bool and Option<bool>
+        assert_eq!(ix.data.len(), 8 + 16 + 16 + 16 + 2);
+        assert_eq!(ix.data[..8], OPEN_POSITION_V2_DISCRIMINATOR);
+        assert_eq!(
+            i32::from_le_bytes(ix.data[16..20].try_into().unwrap()),
+            -1200
+        );
+        assert_eq!(
+            i32::from_le_bytes(ix.data[20..24].try_into().unwrap()),
+            1200
+        );
+        assert!(u128::from_le_bytes(ix.data[24..40].try_into().unwrap()) > 0);
+
+        assert_eq!(ix.accounts.len(), 22);
+        assert!(ix.accounts[0].is_signer);
+        assert_eq!(ix.accounts[2].pubkey, nft_mint);
+        assert!(ix.accounts[2].is_signer);
+        assert_eq!(
+            ix.accounts[9].pubkey,
+            derive_personal_position_with_program(&nft_mint, &executor.program_id)
+        );
+        assert_eq!(
+            ix.accounts[7].pubkey,
+            executor.derive_tick_array(&params.pool, -1200)
+        );
+    }
+
+    #[test]
+    fn test_liquidity_and_close_instructions() {
+        let executor = executor();
+        let pool = pool(Vec::new());
+        let personal_position = personal_position(&pool, -600, 600);
+        let (position, owner) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mint_programs = mint_programs(&pool, TOKEN_PROGRAM_ID);
+
+        let increase = executor
+            .build_increase_liquidity_instruction(
+                &position,
+                &personal_position,
+                &pool,
+                &mint_programs,
+                &owner,
+                500,
+                10,
+                20,
+            )
+            .unwrap();
+        assert_eq!(increase.data[..8], INCREASE_LIQUIDITY_V2_DISCRIMINATOR);
+        assert_eq!(increase.data.len(), 8 + 16 + 16 + 1);
+        assert_eq!(increase.accounts.len(), 15);
+        assert_eq!(increase.accounts[4].pubkey, position);
+
+        let decrease = executor
+            .build_decrease_liquidity_instruction(
+                &position,
+                &personal_position,
+                &pool,
+                &mint_programs,
+                &owner,
+                500,
+                1,
+                2,
+            )
+            .unwrap();
+        assert_eq!(
+            u128::from_le_bytes(decrease.data[8..24].try_into().unwrap()),
+            500
+        );
+        assert_eq!(decrease.accounts[2].pubkey, position);
+
+        let close =
+            executor.build_close_position_instruction(&position, &personal_position, &owner);
+        assert_eq!(close.data, CLOSE_POSITION_DISCRIMINATOR);
+        assert_eq!(close.accounts.len(), 6);
+        assert_eq!(close.accounts[1].pubkey, personal_position.nft_mint);
+    }
+
+    #[test]
+    fn test_token_accounts_follow_the_mint_token_program() {
+        let executor = executor();
+        let reward = RewardEmission {
+            index: 0,
+            mint: Pubkey::new_unique(),
+            vault: Pubkey::new_unique(),
+            emissions_per_second: Decimal::ONE,
+            growth_global_x64: 0,
+        };
+        let pool = pool(vec![reward.clone()]);
+        let personal_position = personal_position(&pool, -600, 600);
+        let owner = Pubkey::new_unique();
+        // Token B and the reward are Token-2022 mints
+        let mut mint_programs = mint_programs(&pool, TOKEN_2022_PROGRAM_ID);
+        mint_programs.insert(pool.token_mint_a, executor.token_program);
+
+        let ix = executor
+            .build_harvest_instruction(
+                &Pubkey::new_unique(),
+                &personal_position,
+                &pool,
+                &mint_programs,
+                &owner,
+            )
+            .unwrap();
+
+        let ata = |mint: &Pubkey, token_program: &Pubkey| {
+            Pubkey::find_program_address(
+                &[owner.as_ref(), token_program.as_ref(), mint.as_ref()],
+                &executor.ata_program,
+            )
+            .0
+        };
+        let (legacy, token_2022) = (&executor.token_program, &executor.token_program_2022);
+        assert_eq!(
+            ix.accounts[1].pubkey,
+            ata(&personal_position.nft_mint, legacy)
+        );
+        assert_eq!(ix.accounts[9].pubkey, ata(&pool.token_mint_a, legacy));
+        assert_eq!(ix.accounts[10].pubkey, ata(&pool.token_mint_b, token_2022));
+        assert_eq!(ix.accounts[17].pubkey, ata(&reward.mint, token_2022));
+        assert_ne!(ix.accounts[17].pubkey, ata(&reward.mint, legacy));
+
+        // A mint whose token program was not looked up is an error
+        mint_programs.remove(&reward.mint);
+        assert!(
+            executor
+                .build_harvest_instruction(
+                    &Pubkey::new_unique(),
+                    &personal_position,
+                    &pool,
+                    &mint_programs,
+                    &owner,
+                )
+                .is_err()
+        );
+    }
+
+    /// Answers `getMultipleAccounts` with empty accounts owned by the program
+    /// `owners` maps each address to; other addresses do not exist. Returns
+    /// the endpoint URL.
+    async fn serve_rpc(owners: HashMap<Pubkey, Pubkey>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let owners = Arc::new(owners);
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                tokio::spawn(answer_rpc(socket, owners.clone()));
+            }
+        });
+        url
+    }
+
+    async fn answer_rpc(mut socket: TcpStream, owners: Arc<HashMap<Pubkey, Pubkey>>) {
+        let mut request = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            let Ok(n) = socket.read(&mut buf).await else {
+                return;
+            };
+            if n == 0 {
+                return;
+            }
+            request.extend_from_slice(&buf[..n]);
+
+            // Answer each complete request; the client keeps the connection
+            let text = String::from_utf8_lossy(&request).to_lowercase();
+            let Some(end) = text.find("\r\n\r\n") else {
+                continue;
+            };
+            let length: usize = text
+                .lines()
+                .find_map(|l| l.strip_prefix("content-length: "))
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(0);
+            if request.len() < end + 4 + length {
+                continue;
+            }
+            let body: serde_json::Value =
+                serde_json::from_slice(&request[end + 4..end + 4 + length]).unwrap();
+            request.drain(..end + 4 + length);
+
+            assert_eq!(body["method"], "getMultipleAccounts");
+            let value: Vec<serde_json::Value> = body["params"][0]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|address| {
+                    let address = Pubkey::from_str(address.as_str().unwrap()).unwrap();
+                    owners
+                        .get(&address)
+                        .map_or(serde_json::Value::Null, |owner| {
+                            serde_json::json!({
+                                "data": ["", "base64"],
+                                "executable": false,
+                                "lamports": 1_000_000,
+                                "owner": owner.to_string(),
+                                "rentEpoch": 0,
+                                "space": 0,
+                            })
+                        })
+                })
+                .collect();
+            let result = serde_json::json!({ "context": { "slot": 7 }, "value": value });
+            let response =
+                serde_json::json!({ "jsonrpc": "2.0", "result": result, "id": body["id"] })
+                    .to_string();
+            let head = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n",
+                response.len()
+            );
+            if socket.write_all(head.as_bytes()).await.is_err()
+                || socket.write_all(response.as_bytes()).await.is_err()
+            {
+                return;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mint_programs_are_read_from_mint_owners() {
+        let legacy = Pubkey::from_str(TOKEN_PROGRAM_ID).unwrap();
+        let token_2022 = Pubkey::from_str(TOKEN_2022_PROGRAM_ID).unwrap();
+        let reward_mint = Pubkey::new_unique();
+        let pool = pool(vec![RewardEmission {
+            index: 0,
+            mint: reward_mint,
+            vault: Pubkey::new_unique(),
+            emissions_per_second: Decimal::ONE,
+            growth_global_x64: 0,
+        }]);
+        let unknown_mint = Pubkey::new_unique();
+        let owners = HashMap::from([
+            (pool.token_mint_a, legacy),
+            (pool.token_mint_b, legacy),
+            (reward_mint, token_2022),
+            (unknown_mint, Pubkey::new_unique()),
+        ]);
+
+        let url = serve_rpc(owners).await;
+        let executor = RaydiumExecutor::new(Arc::new(RpcProvider::new(crate::rpc::RpcConfig {
+            fallback_urls: Vec::new(),
+            max_retries: 0,
+            ..crate::rpc::RpcConfig::new(url)
+        })));
+
+        let programs = executor.fetch_mint_programs(&pool).await.unwrap();
+        assert_eq!(programs.len(), 3);
+        assert_eq!(programs[&pool.token_mint_a], legacy);
+        assert_eq!(programs[&reward_mint], token_2022);
+
+        // Mints that are missing or not owned by a token program are errors
+        let mut missing = pool.clone();
+        missing.token_mint_b = Pubkey::new_unique();
+        assert!(executor.fetch_mint_programs(&missing).await.is_err());
+        let mut foreign = pool.clone();
+        foreign.rewards[0].mint = unknown_mint;
+        assert!(executor.fetch_mint_programs(&foreign).await.is_err());
+    }
+
+    #[test]
+    fn test_bitmap_extension_only_for_far_tick_arrays() {
+        let executor = executor();
+        let pool = pool(Vec::new());
+        // Spacing 10 covers ±10 * 60 * 512 ticks with the pool's bitmap
+        let near = executor.range_accounts(&pool, -307_200, 306_000).unwrap();
+        assert_eq!(near.bitmap_extension, None);
+
+        let far = executor.range_accounts(&pool, 0, 307_200).unwrap();
+        assert!(far.bitmap_extension.is_some());
+        assert!(executor.range_accounts(&pool, 10, 10).is_err());
+    }
+}
@@ -1,2 +1,18 @@
-// Raydium adapter module
-// TODO: Implement Raydium V4 (CLMM) and CLMM parsing logic
+//! Raydium CLMM protocol adapter.
+//!
+//! This module provides functionality to interact with Raydium CLMM pools:
+//! - Read pool state, including farm reward emissions
+//! - Read personal position state
+//! - Open, fund, drain and close positions
+//! - Collect fees and harvest farm rewards
+
+/// Unified protocol adapter implementation.
+pub mod adapter;
+/// Raydium CLMM account structures.
+pub mod clmm;
+/// Executor for on-chain operations.
+pub mod executor;
+/// Pool reader for on-chain state.
+pub mod pool_reader;
+/// Position reader for on-chain state.
+pub mod position_reader;
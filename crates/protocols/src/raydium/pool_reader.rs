@@ -0,0 +1,207 @@
+//! Raydium CLMM pool reader.
+//!
+//! Reads pool state, including farm reward emissions, from on-chain accounts.
+
+use super::clmm::{ClmmPool, ClmmRewardInfo};
+use crate::orca::pool_reader::{RewardEmission, sqrt_price_to_price};
+use crate::rpc::RpcProvider;
+use anyhow::{Context, Result};
+use borsh::BorshDeserialize;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{debug, info};
+
+/// Reads Raydium CLMM pool state from on-chain.
+pub struct RaydiumPoolReader {
+    /// RPC provider.
+    provider: Arc<RpcProvider>,
+}
+
+impl RaydiumPoolReader {
+    /// Creates a new Raydium pool reader.
+    pub fn new(provider: Arc<RpcProvider>) -> Self {
+        Self { provider }
+    }
+
+    /// Gets the pool state for a given pool address.
+    pub async fn get_pool_state(&self, pool_address: &str) -> Result<RaydiumPoolState> {
+        let pubkey = Pubkey::from_str(pool_address).context("Invalid pool address")?;
+
+        info!(pool = pool_address, "Fetching Raydium CLMM pool state");
+
+        let account = self.provider.get_account(&pubkey).await?;
+        let pool = ClmmPool::deserialize(&mut account.data.as_slice())
+            .context("Failed to deserialize Raydium CLMM pool account")?;
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let state = RaydiumPoolState::from_pool(&pool, pool_address, now);
+
+        debug!(
+            tick = state.tick_current,
+            liquidity = %state.liquidity,
+            rewards = state.rewards.len(),
+            "Parsed Raydium CLMM pool state"
+        );
+
+        Ok(state)
+    }
+}
+
+/// Parsed Raydium CLMM pool state.
+#[derive(Debug, Clone)]
+pub struct RaydiumPoolState {
+    /// Pool address.
+    pub address: String,
+    /// Token A (token 0) mint.
+    pub token_mint_a: Pubkey,
+    /// Token B (token 1) mint.
+    pub token_mint_b: Pubkey,
+    /// Token A vault.
+    pub token_vault_a: Pubkey,
+    /// Token B vault.
+    pub token_vault_b: Pubkey,
+    /// Current tick index.
+    pub tick_current: i32,
+    /// Tick spacing.
+    pub tick_spacing: u16,
+    /// Current sqrt price (Q64.64).
+    pub sqrt_price: u128,
+    /// Current price (derived from sqrt_price).
+    pub price: Decimal,
+    /// Current liquidity.
+    pub liquidity: u128,
+    /// Fee growth global for token A.
+    pub fee_growth_global_a: u128,
+    /// Fee growth global for token B.
+    pub fee_growth_global_b: u128,
+    /// Initialized farm rewards; emissions are zero outside the reward window.
+    pub rewards: Vec<RewardEmission>,
+}
+
+impl RaydiumPoolState {
+    /// Creates a RaydiumPoolState from a deserialized pool at the given unix time.
+    #[must_use]
+    pub fn from_pool(pool: &ClmmPool, address: &str, now: u64) -> Self {
+        Self {
+            address: address.to_string(),
+            token_mint_a: pool.token_mint_0,
+            token_mint_b: pool.token_mint_1,
+            token_vault_a: pool.token_vault_0,
+            token_vault_b: pool.token_vault_1,
+            tick_current: pool.tick_current,
+            tick_spacing: pool.tick_spacing,
+            sqrt_price: pool.sqrt_price_x64,
+            price: sqrt_price_to_price(pool.sqrt_price_x64),
+            liquidity: pool.liquidity,
+            fee_growth_global_a: pool.fee_growth_global_0_x64,
+            fee_growth_global_b: pool.fee_growth_global_1_x64,
+            rewards: pool
+                .reward_infos
+                .iter()
+                .enumerate()
+                .filter(|(_, info)| info.is_initialized())
+                .map(|(index, info)| reward_emission(index, info, now))
+                .collect(),
+        }
+    }
+
+    /// Checks if a tick is within the current range.
+    #[must_use]
+    pub fn is_tick_in_range(&self, tick_lower: i32, tick_upper: i32) -> bool {
+        self.tick_current >= tick_lower && self.tick_current < tick_upper
+    }
+
+    /// Calculates the combined farm reward APR for the pool.
+    ///
+    /// # Arguments
+    /// * `unit_prices` - Value of one raw unit of each reward mint
+    /// * `tvl` - Pool TVL in the same unit as the prices
+    #[must_use]
+    pub fn reward_apr(&self, unit_prices: &HashMap<Pubkey, Decimal>, tvl: Decimal) -> Decimal {
+        self.rewards
+            .iter()
+            .filter_map(|reward| {
+                unit_prices
+                    .get(&reward.mint)
+                    .map(|price| reward.apr(*price, tvl))
+            })
+            .sum()
+    }
+}
+
+/// Converts a Raydium reward slot into a reward emission.
+fn reward_emission(index: usize, info: &ClmmRewardInfo, now: u64) -> RewardEmission {
+    let emissions = if info.is_emitting(now) {
+        info.emissions_per_second_x64 as f64 / (1u128 << 64) as f64
+    } else {
+        0.0
+    };
+
+    RewardEmission {
+        index,
+        mint: info.token_mint,
+        vault: info.token_vault,
+        emissions_per_second: Decimal::from_f64(emissions).unwrap_or(Decimal::ZERO),
+        growth_global_x64: info.reward_growth_global_x64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reward_info(end_time: u64) -> ClmmRewardInfo {
+        ClmmRewardInfo {
+            reward_state: 1,
+            open_time: 0,
+            end_time,
+            emissions_per_second_x64: 2u128 << 64,
+            token_mint: Pubkey::new_unique(),
+            token_vault: Pubkey::new_unique(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_expired_reward_has_no_emissions() {
+        let active = reward_emission(0, &reward_info(1_000), 500);
+        let expired = reward_emission(1, &reward_info(1_000), 1_000);
+
+        assert_eq!(active.emissions_per_second, Decimal::from(2));
+        assert_eq!(expired.emissions_per_second, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_reward_apr_uses_priced_rewards() {
+        let priced = reward_emission(0, &reward_info(1_000), 500);
+        let unpriced = reward_emission(1, &reward_info(1_000), 500);
+        let state = RaydiumPoolState {
+            address: String::new(),
+            token_mint_a: Pubkey::new_unique(),
+            token_mint_b: Pubkey::new_unique(),
+            token_vault_a: Pubkey::new_unique(),
+            token_vault_b: Pubkey::new_unique(),
+            tick_current: 0,
+            tick_spacing: 10,
+            sqrt_price: 1u128 << 64,
+            price: Decimal::ONE,
+            liquidity: 0,
+            fee_growth_global_a: 0,
+            fee_growth_global_b: 0,
+            rewards: vec![priced.clone(), unpriced],
+        };
+        let prices = HashMap::from([(priced.mint, Decimal::ONE)]);
+
+        // 2 tokens/s for a year over a TVL of 63,072,000 = 100%
+        let apr = state.reward_apr(&prices, Decimal::from(63_072_000));
+        assert_eq!(apr, Decimal::ONE);
+    }
+}
@@ -0,0 +1,136 @@
+//! Raydium CLMM position reader.
+//!
+//! Reads personal position state from on-chain accounts.
+
+use super::clmm::{PersonalPosition, RAYDIUM_CLMM_PROGRAM_ID};
+use crate::events::OnChainPosition;
+use crate::rpc::RpcProvider;
+use anyhow::{Context, Result};
+use borsh::BorshDeserialize;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::{debug, info};
+
+/// Reads Raydium CLMM positions from on-chain.
+pub struct RaydiumPositionReader {
+    /// RPC provider.
+    provider: Arc<RpcProvider>,
+}
+
+impl RaydiumPositionReader {
+    /// Creates a new position reader.
+    pub fn new(provider: Arc<RpcProvider>) -> Self {
+        Self { provider }
+    }
+
+    /// Gets the raw personal position account by its address.
+    pub async fn get_personal_position(&self, position_address: &str) -> Result<PersonalPosition> {
+        let pubkey = Pubkey::from_str(position_address).context("Invalid position address")?;
+
+        info!(
+            position = position_address,
+            "Fetching Raydium position state"
+        );
+
+        let account = self.provider.get_account(&pubkey).await?;
+        let position = PersonalPosition::deserialize(&mut account.data.as_slice())
+            .context("Failed to deserialize Raydium position account")?;
+
+        debug!(
+            liquidity = %position.liquidity,
+            tick_lower = position.tick_lower_index,
+            tick_upper = position.tick_upper_index,
+            rewards_owed = ?position.reward_infos.iter().map(|r| r.reward_amount_owed).collect::<Vec<_>>(),
+            "Parsed Raydium position state"
+        );
+
+        Ok(position)
+    }
+
+    /// Gets a position by its address.
+    pub async fn get_position(&self, position_address: &str) -> Result<OnChainPosition> {
+        let position = self.get_personal_position(position_address).await?;
+        let address = Pubkey::from_str(position_address).context("Invalid position address")?;
+
+        Ok(to_on_chain_position(address, &position))
+    }
+}
+
+/// Derives the mainnet personal position address for a position NFT mint.
+#[must_use]
+pub fn derive_personal_position(nft_mint: &Pubkey) -> Pubkey {
+    let program_id = Pubkey::from_str(RAYDIUM_CLMM_PROGRAM_ID).expect("Invalid program ID");
+    derive_personal_position_with_program(nft_mint, &program_id)
+}
+
+/// Derives the personal position address under a given CLMM program.
+#[must_use]
+pub fn derive_personal_position_with_program(nft_mint: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    let (address, _bump) =
+        Pubkey::find_program_address(&[b"position", nft_mint.as_ref()], program_id);
+    address
+}
+
+/// Converts a Raydium personal position into the protocol-agnostic representation.
+fn to_on_chain_position(address: Pubkey, position: &PersonalPosition) -> OnChainPosition {
+    OnChainPosition {
+        address,
+        pool: position.pool_id,
+        owner: Pubkey::default(), // Owner holds the position NFT
+        tick_lower: position.tick_lower_index,
+        tick_upper: position.tick_upper_index,
+        liquidity: position.liquidity,
+        fee_growth_inside_a: position.fee_growth_inside_0_last_x64,
+        fee_growth_inside_b: position.fee_growth_inside_1_last_x64,
+        fees_owed_a: position.token_fees_owed_0,
+        fees_owed_b: position.token_fees_owed_1,
+        reward_growth_inside: position
+            .reward_infos
+            .each_ref()
+            .map(|r| r.growth_inside_last_x64),
+        rewards_owed: position
+            .reward_infos
+            .each_ref()
+            .map(|r| r.reward_amount_owed),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::raydium::clmm::PersonalPositionRewardInfo;
+
+    #[test]
+    fn test_to_on_chain_position_carries_rewards() {
+        let position = PersonalPosition {
+            discriminator: [0; 8],
+            bump: [0],
+            nft_mint: Pubkey::new_unique(),
+            pool_id: Pubkey::new_unique(),
+            tick_lower_index: -100,
+            tick_upper_index: 100,
+            liquidity: 1_000,
+            fee_growth_inside_0_last_x64: 0,
+            fee_growth_inside_1_last_x64: 0,
+            token_fees_owed_0: 1,
+            token_fees_owed_1: 2,
+            reward_infos: [
+                PersonalPositionRewardInfo::default(),
+                PersonalPositionRewardInfo {
+                    growth_inside_last_x64: 7,
+                    reward_amount_owed: 42,
+                },
+                PersonalPositionRewardInfo::default(),
+            ],
+        };
+
+        let on_chain =
+            to_on_chain_position(derive_personal_position(&position.nft_mint), &position);
+
+        assert_eq!(on_chain.pool, position.pool_id);
+        assert_eq!(on_chain.rewards_owed, [0, 42, 0]);
+        assert_eq!(on_chain.reward_growth_inside[1], 7);
+        assert!(on_chain.has_rewards_owed());
+    }
+}
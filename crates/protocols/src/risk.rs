@@ -0,0 +1,350 @@
+//! Token risk screening.
+//!
+//! Inspects the mints of a pool for red flags before capital is deployed:
+//! an active mint or freeze authority, supply concentrated in a few holders
+//! and missing token metadata.
+
+use crate::orca::pool_reader::WhirlpoolReader;
+use crate::rpc::RpcProvider;
+use anyhow::{Context, Result};
+use rust_decimal::Decimal;
+use solana_sdk::pubkey::Pubkey;
+use spl_token::solana_program::program_option::COption;
+use spl_token::solana_program::program_pack::Pack;
+use spl_token::state::Mint;
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::{debug, info};
+
+/// Metaplex token metadata program ID.
+pub const TOKEN_METADATA_PROGRAM_ID: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
+
+/// Mints that skip screening: wrapped SOL, USDC and USDT.
+///
+/// USDC and USDT keep mint and freeze authorities by design.
+pub const DEFAULT_TRUSTED_MINTS: [&str; 3] = [
+    "So11111111111111111111111111111111111111112",
+    "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+    "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB",
+];
+
+/// Number of largest holders whose combined share is checked.
+const TOP_HOLDERS: usize = 10;
+
+/// A red flag found on a token mint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenRisk {
+    /// The mint authority can still issue tokens.
+    MintAuthority(Pubkey),
+    /// The freeze authority can freeze token accounts, including the pool vault.
+    FreezeAuthority(Pubkey),
+    /// The largest holders own too much of the supply.
+    ConcentratedHolders {
+        /// Share of supply held by the largest holders (0.9 = 90%).
+        top_holders_share: Decimal,
+    },
+    /// No token metadata account exists for the mint.
+    MissingMetadata,
+}
+
+impl TokenRisk {
+    /// Returns true if the risk should prevent opening positions.
+    ///
+    /// Missing metadata alone only warrants a warning.
+    #[must_use]
+    pub fn is_blocking(&self) -> bool {
+        !matches!(self, Self::MissingMetadata)
+    }
+}
+
+impl fmt::Display for TokenRisk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MintAuthority(authority) => write!(f, "active mint authority {}", authority),
+            Self::FreezeAuthority(authority) => write!(f, "active freeze authority {}", authority),
+            Self::ConcentratedHolders { top_holders_share } => write!(
+                f,
+                "top {} holders own {}% of supply",
+                TOP_HOLDERS,
+                (top_holders_share * Decimal::ONE_HUNDRED).round_dp(1)
+            ),
+            Self::MissingMetadata => write!(f, "no token metadata"),
+        }
+    }
+}
+
+/// Screening result for one mint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenRiskReport {
+    /// Token mint.
+    pub mint: Pubkey,
+    /// Whether the mint is trusted and was not screened.
+    pub trusted: bool,
+    /// Red flags found.
+    pub risks: Vec<TokenRisk>,
+    /// Share of supply held by the largest holders, if known.
+    pub top_holders_share: Option<Decimal>,
+}
+
+impl TokenRiskReport {
+    /// Creates a report for a trusted mint.
+    #[must_use]
+    pub fn trusted(mint: Pubkey) -> Self {
+        Self {
+            mint,
+            trusted: true,
+            risks: Vec::new(),
+            top_holders_share: None,
+        }
+    }
+
+    /// Returns true if any risk should prevent opening positions.
+    #[must_use]
+    pub fn is_blocked(&self) -> bool {
+        self.risks.iter().any(TokenRisk::is_blocking)
+    }
+}
+
+/// Screening result for both tokens of a pool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolRiskReport {
+    /// Pool address.
+    pub pool: String,
+    /// Token A report.
+    pub token_a: TokenRiskReport,
+    /// Token B report.
+    pub token_b: TokenRiskReport,
+}
+
+impl PoolRiskReport {
+    /// Returns true if either token should prevent opening positions.
+    #[must_use]
+    pub fn is_blocked(&self) -> bool {
+        self.token_a.is_blocked() || self.token_b.is_blocked()
+    }
+
+    /// Describes the blocking risks of both tokens.
+    #[must_use]
+    pub fn blocking_reasons(&self) -> Vec<String> {
+        [&self.token_a, &self.token_b]
+            .into_iter()
+            .flat_map(|report| {
+                report
+                    .risks
+                    .iter()
+                    .filter(|risk| risk.is_blocking())
+                    .map(move |risk| format!("{}: {}", report.mint, risk))
+            })
+            .collect()
+    }
+}
+
+/// Screens token mints for red flags.
+pub struct TokenRiskChecker {
+    /// RPC provider.
+    provider: Arc<RpcProvider>,
+    /// Mints that skip screening.
+    trusted_mints: HashSet<Pubkey>,
+    /// Largest acceptable share of supply held by the top holders.
+    max_top_holders_share: Decimal,
+}
+
+impl TokenRiskChecker {
+    /// Creates a checker trusting [`DEFAULT_TRUSTED_MINTS`] and flagging
+    /// mints whose top holders own more than 80% of supply.
+    pub fn new(provider: Arc<RpcProvider>) -> Self {
+        Self {
+            provider,
+            trusted_mints: DEFAULT_TRUSTED_MINTS
+                .iter()
+                .filter_map(|m| Pubkey::from_str(m).ok())
+                .collect(),
+            max_top_holders_share: Decimal::new(8, 1),
+        }
+    }
+
+    /// Adds mints that skip screening.
+    #[must_use]
+    pub fn with_trusted_mints(mut self, mints: impl IntoIterator<Item = Pubkey>) -> Self {
+        self.trusted_mints.extend(mints);
+        self
+    }
+
+    /// Sets the largest acceptable share of supply held by the top holders.
+    #[must_use]
+    pub fn with_max_top_holders_share(mut self, share: Decimal) -> Self {
+        self.max_top_holders_share = share;
+        self
+    }
+
+    /// Screens both tokens of a Whirlpool.
+    pub async fn check_pool(&self, pool_address: &str) -> Result<PoolRiskReport> {
+        let state = WhirlpoolReader::new(self.provider.clone())
+            .get_pool_state(pool_address)
+            .await?;
+
+        Ok(PoolRiskReport {
+            pool: pool_address.to_string(),
+            token_a: self.check_mint(&state.token_mint_a).await?,
+            token_b: self.check_mint(&state.token_mint_b).await?,
+        })
+    }
+
+    /// Screens a token mint.
+    pub async fn check_mint(&self, mint: &Pubkey) -> Result<TokenRiskReport> {
+        if self.trusted_mints.contains(mint) {
+            return Ok(TokenRiskReport::trusted(*mint));
+        }
+
+        info!(mint = %mint, "Screening token mint");
+
+        let account = self.provider.get_account(mint).await?;
+        // Token-2022 mints share the base layout, with extensions appended
+        let mint_state = account
+            .data
+            .get(..Mint::LEN)
+            .context("Account is not a token mint")
+            .and_then(|data| Mint::unpack(data).context("Failed to parse token mint"))?;
+
+        let metadata = metadata_address(mint);
+        let has_metadata = self
+            .provider
+            .get_multiple_accounts(&[metadata])
+            .await?
+            .first()
+            .is_some_and(Option::is_some);
+
+        let largest = self.provider.get_token_largest_balances(mint).await?;
+        debug!(
+            mint = %mint,
+            holders = largest.len(),
+            has_metadata,
+            "Fetched mint details"
+        );
+
+        Ok(self.assess(*mint, &mint_state, has_metadata, &largest))
+    }
+
+    /// Derives the risks of a mint from its on-chain state.
+    fn assess(
+        &self,
+        mint: Pubkey,
+        state: &Mint,
+        has_metadata: bool,
+        largest_balances: &[u64],
+    ) -> TokenRiskReport {
+        let mut risks = Vec::new();
+        if let COption::Some(authority) = state.mint_authority {
+            risks.push(TokenRisk::MintAuthority(authority));
+        }
+        if let COption::Some(authority) = state.freeze_authority {
+            risks.push(TokenRisk::FreezeAuthority(authority));
+        }
+
+        let top_holders_share = (state.supply > 0).then(|| {
+            let mut balances = largest_balances.to_vec();
+            balances.sort_unstable_by(|a, b| b.cmp(a));
+            let top: u128 = balances
+                .iter()
+                .take(TOP_HOLDERS)
+                .map(|b| u128::from(*b))
+                .sum();
+            Decimal::from(top) / Decimal::from(state.supply)
+        });
+        if let Some(share) = top_holders_share
+            && share > self.max_top_holders_share
+        {
+            risks.push(TokenRisk::ConcentratedHolders {
+                top_holders_share: share,
+            });
+        }
+
+        if !has_metadata {
+            risks.push(TokenRisk::MissingMetadata);
+        }
+
+        TokenRiskReport {
+            mint,
+            trusted: false,
+            risks,
+            top_holders_share,
+        }
+    }
+}
+
+/// Derives the Metaplex metadata account of a mint.
+#[must_use]
+pub fn metadata_address(mint: &Pubkey) -> Pubkey {
+    let program = Pubkey::from_str(TOKEN_METADATA_PROGRAM_ID).unwrap_or_default();
+    Pubkey::find_program_address(&[b"metadata", program.as_ref(), mint.as_ref()], &program).0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::RpcConfig;
+
+    fn checker() -> TokenRiskChecker {
+        TokenRiskChecker::new(Arc::new(RpcProvider::new(RpcConfig::default())))
+    }
+
+    fn mint(mint_authority: Option<Pubkey>, freeze_authority: Option<Pubkey>) -> Mint {
+        Mint {
+            mint_authority: mint_authority.into(),
+            supply: 1_000,
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority: freeze_authority.into(),
+        }
+    }
+
+    #[test]
+    fn test_clean_mint() {
+        let report = checker().assess(Pubkey::new_unique(), &mint(None, None), true, &[100, 50]);
+
+        assert!(report.risks.is_empty());
+        assert!(!report.is_blocked());
+        assert_eq!(report.top_holders_share, Some(Decimal::new(15, 2)));
+    }
+
+    #[test]
+    fn test_flags_authorities_and_concentration() {
+        let authority = Pubkey::new_unique();
+        let report = checker().assess(
+            Pubkey::new_unique(),
+            &mint(Some(authority), Some(authority)),
+            false,
+            &[900, 50],
+        );
+
+        assert_eq!(
+            report.risks,
+            vec![
+                TokenRisk::MintAuthority(authority),
+                TokenRisk::FreezeAuthority(authority),
+                TokenRisk::ConcentratedHolders {
+                    top_holders_share: Decimal::new(95, 2)
+                },
+                TokenRisk::MissingMetadata,
+            ]
+        );
+        assert!(report.is_blocked());
+    }
+
+    #[test]
+    fn test_missing_metadata_does_not_block() {
+        let token_a = checker().assess(Pubkey::new_unique(), &mint(None, None), false, &[10]);
+        let usdc = Pubkey::from_str(DEFAULT_TRUSTED_MINTS[1]).unwrap();
+        let pool = PoolRiskReport {
+            pool: "pool".to_string(),
+            token_a,
+            token_b: TokenRiskReport::trusted(usdc),
+        };
+
+        assert!(!pool.is_blocked());
+        assert!(pool.blocking_reasons().is_empty());
+        assert_eq!(pool.token_a.risks, vec![TokenRisk::MissingMetadata]);
+    }
+}
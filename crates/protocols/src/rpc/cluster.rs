@@ -0,0 +1,134 @@
+//! Solana cluster profiles.
+
+use anyhow::{Result, bail};
+use solana_sdk::pubkey::Pubkey;
+use std::fmt;
+use std::str::FromStr;
+
+/// Raydium CLMM program ID on devnet.
+pub const RAYDIUM_CLMM_DEVNET_PROGRAM_ID: &str = "devi51mZmdwUJGU9hjN27vEz64Gps7uUefqxg27EAtH";
+
+/// Solana cluster the execution path runs against.
+///
+/// Determines default endpoints, program IDs and whether airdrops are
+/// available. Localnet assumes programs cloned from mainnet at their
+/// mainnet addresses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Cluster {
+    /// Mainnet beta.
+    #[default]
+    MainnetBeta,
+    /// Devnet.
+    Devnet,
+    /// Testnet.
+    Testnet,
+    /// Local test validator.
+    Localnet,
+}
+
+impl Cluster {
+    /// Returns the cluster name as used by the Solana CLI.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MainnetBeta => "mainnet-beta",
+            Self::Devnet => "devnet",
+            Self::Testnet => "testnet",
+            Self::Localnet => "localnet",
+        }
+    }
+
+    /// Returns the default JSON RPC endpoint.
+    #[must_use]
+    pub fn rpc_url(&self) -> &'static str {
+        match self {
+            Self::MainnetBeta => "https://api.mainnet-beta.solana.com",
+            Self::Devnet => "https://api.devnet.solana.com",
+            Self::Testnet => "https://api.testnet.solana.com",
+            Self::Localnet => "http://127.0.0.1:8899",
+        }
+    }
+
+    /// Returns the default WebSocket endpoint.
+    #[must_use]
+    pub fn ws_url(&self) -> &'static str {
+        match self {
+            Self::MainnetBeta => "wss://api.mainnet-beta.solana.com",
+            Self::Devnet => "wss://api.devnet.solana.com",
+            Self::Testnet => "wss://api.testnet.solana.com",
+            Self::Localnet => "ws://127.0.0.1:8900",
+        }
+    }
+
+    /// Returns true if the cluster hands out SOL through airdrops.
+    #[must_use]
+    pub fn supports_airdrop(&self) -> bool {
+        !matches!(self, Self::MainnetBeta)
+    }
+
+    /// Returns the Orca Whirlpool program ID.
+    ///
+    /// Orca deploys to the same address on every cluster.
+    #[must_use]
+    pub fn whirlpool_program_id(&self) -> Pubkey {
+        Pubkey::from_str(crate::orca::executor::WHIRLPOOL_PROGRAM_ID).expect("Invalid program ID")
+    }
+
+    /// Returns the Raydium CLMM program ID.
+    #[must_use]
+    pub fn raydium_clmm_program_id(&self) -> Pubkey {
+        let id = match self {
+            Self::Devnet => RAYDIUM_CLMM_DEVNET_PROGRAM_ID,
+            _ => crate::raydium::clmm::RAYDIUM_CLMM_PROGRAM_ID,
+        };
+        Pubkey::from_str(id).expect("Invalid program ID")
+    }
+}
+
+impl fmt::Display for Cluster {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Cluster {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "mainnet-beta" | "mainnet" => Ok(Self::MainnetBeta),
+            "devnet" => Ok(Self::Devnet),
+            "testnet" => Ok(Self::Testnet),
+            "localnet" | "localhost" => Ok(Self::Localnet),
+            other => bail!("Unknown cluster: {}", other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cluster() {
+        assert_eq!("mainnet".parse::<Cluster>().unwrap(), Cluster::MainnetBeta);
+        assert_eq!("Devnet".parse::<Cluster>().unwrap(), Cluster::Devnet);
+        assert_eq!("localhost".parse::<Cluster>().unwrap(), Cluster::Localnet);
+        assert!("moonnet".parse::<Cluster>().is_err());
+        assert_eq!(Cluster::MainnetBeta.to_string(), "mainnet-beta");
+    }
+
+    #[test]
+    fn test_program_ids_per_cluster() {
+        assert_eq!(
+            Cluster::Devnet.whirlpool_program_id(),
+            Cluster::MainnetBeta.whirlpool_program_id()
+        );
+        assert_ne!(
+            Cluster::Devnet.raydium_clmm_program_id(),
+            Cluster::MainnetBeta.raydium_clmm_program_id()
+        );
+        assert!(!Cluster::MainnetBeta.supports_airdrop());
+        assert!(Cluster::Localnet.supports_airdrop());
+    }
+}
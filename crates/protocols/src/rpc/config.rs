@@ -1,10 +1,15 @@
 //! RPC configuration for Solana endpoints.
 
+use super::Cluster;
+use solana_commitment_config::CommitmentConfig;
+use std::str::FromStr;
 use std::time::Duration;
 
 /// Configuration for RPC endpoints.
 #[derive(Debug, Clone)]
 pub struct RpcConfig {
+    /// Cluster the endpoints belong to.
+    pub cluster: Cluster,
     /// Primary RPC endpoint URL.
     pub primary_url: String,
     /// Fallback RPC endpoint URLs.
@@ -26,7 +31,8 @@ pub struct RpcConfig {
 impl Default for RpcConfig {
     fn default() -> Self {
         Self {
-            primary_url: "https://api.mainnet-beta.solana.com".to_string(),
+            cluster: Cluster::MainnetBeta,
+            primary_url: Cluster::MainnetBeta.rpc_url().to_string(),
             fallback_urls: vec![
                 "https://solana-api.projectserum.com".to_string(),
                 "https://rpc.ankr.com/solana".to_string(),
@@ -87,39 +93,52 @@ impl RpcConfig {
         endpoints
     }
 
+    /// Sets the cluster, keeping the configured endpoints.
+    #[must_use]
+    pub fn with_cluster(mut self, cluster: Cluster) -> Self {
+        self.cluster = cluster;
+        self
+    }
+
+    /// Creates a configuration for the cluster's public endpoint.
+    ///
+    /// Mainnet keeps the default fallbacks; other clusters have none.
+    #[must_use]
+    pub fn for_cluster(cluster: Cluster) -> Self {
+        match cluster {
+            Cluster::MainnetBeta => Self::default(),
+            _ => Self {
+                cluster,
+                primary_url: cluster.rpc_url().to_string(),
+                fallback_urls: vec![],
+                ..Default::default()
+            },
+        }
+    }
+
     /// Creates a devnet configuration.
     #[must_use]
     pub fn devnet() -> Self {
-        Self {
-            primary_url: "https://api.devnet.solana.com".to_string(),
-            fallback_urls: vec![],
-            ..Default::default()
-        }
+        Self::for_cluster(Cluster::Devnet)
     }
 
     /// Creates a testnet configuration.
     #[must_use]
     pub fn testnet() -> Self {
-        Self {
-            primary_url: "https://api.testnet.solana.com".to_string(),
-            fallback_urls: vec![],
-            ..Default::default()
-        }
+        Self::for_cluster(Cluster::Testnet)
     }
 
     /// Creates a localhost configuration.
     #[must_use]
     pub fn localhost() -> Self {
-        Self {
-            primary_url: "http://127.0.0.1:8899".to_string(),
-            fallback_urls: vec![],
-            ..Default::default()
-        }
+        Self::for_cluster(Cluster::Localnet)
     }
 }
 
 /// Commitment level for RPC requests.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+///
+/// Levels are ordered from least to most final.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub enum CommitmentLevel {
     /// Processed commitment (fastest, least reliable).
     Processed,
@@ -140,6 +159,29 @@ impl CommitmentLevel {
             Self::Finalized => "finalized",
         }
     }
+
+    /// Returns the commitment configuration for RPC requests.
+    #[must_use]
+    pub fn to_config(self) -> CommitmentConfig {
+        match self {
+            Self::Processed => CommitmentConfig::processed(),
+            Self::Confirmed => CommitmentConfig::confirmed(),
+            Self::Finalized => CommitmentConfig::finalized(),
+        }
+    }
+}
+
+impl FromStr for CommitmentLevel {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.to_lowercase().as_str() {
+            "processed" => Ok(Self::Processed),
+            "confirmed" => Ok(Self::Confirmed),
+            "finalized" => Ok(Self::Finalized),
+            other => anyhow::bail!("Unknown commitment level: {}", other),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -169,5 +211,24 @@ mod tests {
         let config = RpcConfig::devnet();
         assert!(config.primary_url.contains("devnet"));
         assert!(config.fallback_urls.is_empty());
+        assert_eq!(config.cluster, Cluster::Devnet);
+    }
+
+    #[test]
+    fn test_commitment_levels() {
+        assert_eq!(
+            "Finalized".parse::<CommitmentLevel>().unwrap(),
+            CommitmentLevel::Finalized
+        );
+        assert!("rooted".parse::<CommitmentLevel>().is_err());
+        assert!(CommitmentLevel::Processed < CommitmentLevel::Confirmed);
+        assert!(CommitmentLevel::Confirmed < CommitmentLevel::Finalized);
+    }
+
+    #[test]
+    fn test_custom_endpoint_keeps_cluster() {
+        let config = RpcConfig::new("https://my-devnet-rpc.example").with_cluster(Cluster::Devnet);
+        assert_eq!(config.cluster, Cluster::Devnet);
+        assert_eq!(config.primary_url, "https://my-devnet-rpc.example");
     }
 }
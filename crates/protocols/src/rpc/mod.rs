@@ -5,11 +5,17 @@
 //! - Health checking and endpoint rotation
 //! - Rate limiting
 //! - Retry logic with exponential backoff
+//! - Pluggable transaction sending
+//! - Cluster profiles (mainnet-beta, devnet, testnet, localnet)
 
+mod cluster;
 mod config;
 mod health;
 mod provider;
+mod sender;
 
+pub use cluster::*;
 pub use config::*;
 pub use health::*;
 pub use provider::*;
+pub use sender::*;
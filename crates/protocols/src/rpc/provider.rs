@@ -1,11 +1,21 @@
 //! RPC provider with automatic failover and retry logic.
 
-use super::{HealthChecker, RpcConfig};
+use super::{Cluster, CommitmentLevel, HealthChecker, RpcConfig};
+use crate::events::ExecutedAmounts;
 use anyhow::{Context, Result};
 use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{
+    RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig, RpcTransactionConfig,
+    UiAccountEncoding,
+};
 use solana_sdk::account::Account;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::Signature;
+use solana_transaction_status_client_types::{
+    TransactionConfirmationStatus, UiTransactionEncoding,
+};
+use spl_token::solana_program::program_pack::Pack;
+use spl_token::state::Mint;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -13,6 +23,17 @@ use tokio::sync::RwLock;
 use tokio::time::sleep;
 use tracing::{debug, info, warn};
 
+/// How far a transaction has progressed on the cluster.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureConfirmation {
+    /// Slot the transaction landed in.
+    pub slot: u64,
+    /// Highest commitment level reached.
+    pub commitment: CommitmentLevel,
+    /// Error if the transaction landed but failed.
+    pub error: Option<String>,
+}
+
 /// RPC provider with automatic failover and health checking.
 pub struct RpcProvider {
     /// Configuration.
@@ -52,6 +73,12 @@ impl RpcProvider {
         Self::new(RpcConfig::localhost())
     }
 
+    /// Returns the cluster the provider is connected to.
+    #[must_use]
+    pub fn cluster(&self) -> Cluster {
+        self.config.cluster
+    }
+
     /// Returns the current active endpoint.
     pub async fn current_endpoint(&self) -> String {
         let idx = *self.current_endpoint_idx.read().await;
@@ -173,12 +200,43 @@ impl RpcProvider {
         .await
     }
 
+    /// Gets an account as of `commitment`, with the slot it was read at.
+    ///
+    /// The account is `None` if it does not exist.
+    pub async fn get_account_with_slot(
+        &self,
+        address: &Pubkey,
+        commitment: CommitmentLevel,
+    ) -> Result<(u64, Option<Account>)> {
+        let addr = *address;
+        self.execute_with_retry(|client| async move {
+            let response = client
+                .get_account_with_commitment(&addr, commitment.to_config())
+                .await
+                .context("Failed to get account")?;
+            Ok((response.context.slot, response.value))
+        })
+        .await
+    }
+
     /// Gets account data by address string.
     pub async fn get_account_by_address(&self, address: &str) -> Result<Account> {
         let pubkey = Pubkey::from_str(address).context("Invalid pubkey")?;
         self.get_account(&pubkey).await
     }
 
+    /// Gets the decimals of a token mint.
+    pub async fn get_mint_decimals(&self, mint: &Pubkey) -> Result<u8> {
+        let account = self.get_account(mint).await?;
+        // Token-2022 mints share the base layout, with extensions appended
+        let mint_state = account
+            .data
+            .get(..Mint::LEN)
+            .context("Account is not a token mint")
+            .and_then(|data| Mint::unpack(data).context("Failed to parse token mint"))?;
+        Ok(mint_state.decimals)
+    }
+
     /// Gets multiple accounts.
     pub async fn get_multiple_accounts(
         &self,
@@ -197,6 +255,30 @@ impl RpcProvider {
         .await
     }
 
+    /// Gets the raw balances of a mint's largest token accounts (at most 20).
+    pub async fn get_token_largest_balances(&self, mint: &Pubkey) -> Result<Vec<u64>> {
+        let mint = *mint;
+        let accounts = self
+            .execute_with_retry(|client| async move {
+                client
+                    .get_token_largest_accounts(&mint)
+                    .await
+                    .context("Failed to get largest token accounts")
+            })
+            .await?;
+
+        accounts
+            .iter()
+            .map(|account| {
+                account
+                    .amount
+                    .amount
+                    .parse::<u64>()
+                    .context("Invalid token amount")
+            })
+            .collect()
+    }
+
     /// Gets the balance of an account in lamports.
     pub async fn get_balance(&self, address: &Pubkey) -> Result<u64> {
         let addr = *address;
@@ -209,6 +291,88 @@ impl RpcProvider {
         .await
     }
 
+    /// Requests an airdrop of `lamports` and waits for it to confirm.
+    ///
+    /// # Errors
+    /// Returns an error on clusters without airdrops, or if the airdrop
+    /// is not confirmed.
+    pub async fn request_airdrop(&self, address: &Pubkey, lamports: u64) -> Result<Signature> {
+        let cluster = self.cluster();
+        if !cluster.supports_airdrop() {
+            anyhow::bail!("Airdrops are not available on {}", cluster);
+        }
+
+        info!(address = %address, lamports, cluster = %cluster, "Requesting airdrop");
+
+        let addr = *address;
+        let signature = self
+            .execute_with_retry(|client| async move {
+                client
+                    .request_airdrop(&addr, lamports)
+                    .await
+                    .context("Failed to request airdrop")
+            })
+            .await?;
+
+        self.execute_with_retry(|client| async move {
+            client
+                .poll_for_signature(&signature)
+                .await
+                .context("Airdrop was not confirmed")
+        })
+        .await?;
+
+        Ok(signature)
+    }
+
+    /// Gets the token amounts a confirmed transaction moved for `owner`.
+    ///
+    /// # Errors
+    /// Returns an error if the transaction cannot be fetched or has no
+    /// status metadata.
+    pub async fn get_executed_amounts(
+        &self,
+        signature: &Signature,
+        owner: &Pubkey,
+    ) -> Result<ExecutedAmounts> {
+        let sig = *signature;
+        let transaction = self
+            .execute_with_retry(|client| async move {
+                // Same commitment the transaction was confirmed at
+                let commitment = client.commitment();
+                client
+                    .get_transaction_with_config(
+                        &sig,
+                        RpcTransactionConfig {
+                            encoding: Some(UiTransactionEncoding::Json),
+                            commitment: Some(commitment),
+                            max_supported_transaction_version: Some(0),
+                        },
+                    )
+                    .await
+                    .context("Failed to get transaction")
+            })
+            .await?;
+
+        let meta = transaction
+            .transaction
+            .meta
+            .context("Transaction has no status metadata")?;
+        ExecutedAmounts::from_meta(transaction.slot, &meta, owner)
+    }
+
+    /// Gets the minimum balance for an account of `data_len` bytes to be
+    /// rent exempt.
+    pub async fn get_minimum_balance_for_rent_exemption(&self, data_len: usize) -> Result<u64> {
+        self.execute_with_retry(|client| async move {
+            client
+                .get_minimum_balance_for_rent_exemption(data_len)
+                .await
+                .context("Failed to get rent exemption minimum")
+        })
+        .await
+    }
+
     /// Gets the latest blockhash.
     pub async fn get_latest_blockhash(&self) -> Result<solana_sdk::hash::Hash> {
         self.execute_with_retry(|client| async move {
@@ -220,6 +384,33 @@ impl RpcProvider {
         .await
     }
 
+    /// Gets the prioritization fees paid in recent slots by transactions
+    /// writing to all of `addresses`, as `(slot, micro-lamports per compute
+    /// unit)` pairs.
+    ///
+    /// An empty `addresses` returns the cluster-wide minimum per slot.
+    pub async fn get_recent_prioritization_fees(
+        &self,
+        addresses: &[Pubkey],
+    ) -> Result<Vec<(u64, u64)>> {
+        let addrs = addresses.to_vec();
+        let fees = self
+            .execute_with_retry(|client| {
+                let addrs = addrs.clone();
+                async move {
+                    client
+                        .get_recent_prioritization_fees(&addrs)
+                        .await
+                        .context("Failed to get recent prioritization fees")
+                }
+            })
+            .await?;
+        Ok(fees
+            .into_iter()
+            .map(|fee| (fee.slot, fee.prioritization_fee))
+            .collect())
+    }
+
     /// Gets transaction status.
     pub async fn get_signature_status(
         &self,
@@ -240,6 +431,40 @@ impl RpcProvider {
         .await
     }
 
+    /// Gets the commitment level a transaction has reached.
+    ///
+    /// Returns `None` if the cluster does not know the signature, e.g.
+    /// because it was dropped or its fork was abandoned.
+    pub async fn get_signature_confirmation(
+        &self,
+        signature: &Signature,
+    ) -> Result<Option<SignatureConfirmation>> {
+        let sig = *signature;
+        self.execute_with_retry(|client| async move {
+            let statuses = client
+                .get_signature_statuses(&[sig])
+                .await
+                .context("Failed to get signature status")?;
+
+            Ok(statuses.value.into_iter().next().flatten().map(|status| {
+                let commitment = match status.confirmation_status {
+                    Some(TransactionConfirmationStatus::Processed) => CommitmentLevel::Processed,
+                    Some(TransactionConfirmationStatus::Confirmed) => CommitmentLevel::Confirmed,
+                    Some(TransactionConfirmationStatus::Finalized) => CommitmentLevel::Finalized,
+                    // Older nodes only report confirmations; none means rooted
+                    None if status.confirmations.is_none() => CommitmentLevel::Finalized,
+                    None => CommitmentLevel::Confirmed,
+                };
+                SignatureConfirmation {
+                    slot: status.slot,
+                    commitment,
+                    error: status.err.map(|e| e.to_string()),
+                }
+            }))
+        })
+        .await
+    }
+
     /// Gets the health status of all endpoints.
     pub async fn get_health_status(
         &self,
@@ -274,6 +499,39 @@ impl RpcProvider {
         .await
     }
 
+    /// Simulates a transaction and returns the state of `accounts` after it.
+    ///
+    /// Signatures are not verified and the blockhash is replaced, so the
+    /// transaction may be unsigned.
+    pub async fn simulate_transaction_with_accounts(
+        &self,
+        transaction: &solana_sdk::transaction::Transaction,
+        accounts: &[Pubkey],
+    ) -> Result<solana_client::rpc_response::RpcSimulateTransactionResult> {
+        let tx = transaction.clone();
+        let addresses: Vec<String> = accounts.iter().map(ToString::to_string).collect();
+        self.execute_with_retry(|client| {
+            let tx = tx.clone();
+            let config = RpcSimulateTransactionConfig {
+                sig_verify: false,
+                replace_recent_blockhash: true,
+                accounts: Some(RpcSimulateTransactionAccountsConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    addresses: addresses.clone(),
+                }),
+                ..Default::default()
+            };
+            async move {
+                let response = client
+                    .simulate_transaction_with_config(&tx, config)
+                    .await
+                    .context("Failed to simulate transaction")?;
+                Ok(response.value)
+            }
+        })
+        .await
+    }
+
     /// Sends and confirms a transaction.
     pub async fn send_and_confirm_transaction(
         &self,
@@ -342,5 +600,13 @@ mod tests {
         let provider = RpcProvider::devnet();
         let endpoint = provider.current_endpoint().await;
         assert!(endpoint.contains("devnet"));
+        assert_eq!(provider.cluster(), Cluster::Devnet);
+    }
+
+    #[tokio::test]
+    async fn test_mainnet_refuses_airdrop() {
+        let provider = RpcProvider::mainnet();
+        let result = provider.request_airdrop(&Pubkey::new_unique(), 1).await;
+        assert!(result.is_err());
     }
 }
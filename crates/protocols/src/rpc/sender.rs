@@ -0,0 +1,25 @@
+//! Sending signed transactions to the cluster.
+
+use super::RpcProvider;
+use anyhow::Result;
+use async_trait::async_trait;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::Transaction;
+
+/// Sends signed transactions and waits for them to be confirmed.
+///
+/// Executors send through the RPC provider by default; a sender with its
+/// own confirmation policy, e.g. one running post-confirmation hooks, can
+/// be plugged in instead.
+#[async_trait]
+pub trait TransactionSender: Send + Sync {
+    /// Sends a signed transaction and waits until it is confirmed.
+    async fn send_and_confirm(&self, transaction: &Transaction) -> Result<Signature>;
+}
+
+#[async_trait]
+impl TransactionSender for RpcProvider {
+    async fn send_and_confirm(&self, transaction: &Transaction) -> Result<Signature> {
+        self.send_and_confirm_transaction(transaction).await
+    }
+}
@@ -0,0 +1,37 @@
+//! Python bindings for the simulation and optimization crates.
+//!
+//! Built with maturin into the `clmm_lp` module:
+//!
+//! ```text
+//! cd crates/py && maturin develop --release
+//! ```
+//!
+//! Inputs are plain floats and float sequences (lists or numpy arrays); the
+//! per-step series of a simulation come back as numpy arrays, so results
+//! drop straight into pandas or matplotlib. All math runs in the same Rust
+//! code as the CLI and API.
+
+/// Range optimizer bindings.
+pub mod optimization;
+/// Strategy simulation bindings.
+pub mod simulation;
+
+use clmm_lp_domain::math::float::decimal_from_f64;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use rust_decimal::Decimal;
+
+/// The `clmm_lp` Python module.
+#[pymodule]
+fn clmm_lp(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<simulation::PySimulationConfig>()?;
+    m.add_function(wrap_pyfunction!(simulation::simulate_with_strategy, m)?)?;
+    m.add_class::<optimization::PyRangeOptimizer>()?;
+    Ok(())
+}
+
+/// Converts a Python float to a `Decimal`, naming the argument on failure.
+pub(crate) fn decimal(value: f64, name: &str) -> PyResult<Decimal> {
+    decimal_from_f64(value)
+        .ok_or_else(|| PyValueError::new_err(format!("{name} must be a finite number")))
+}
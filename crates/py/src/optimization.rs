@@ -0,0 +1,189 @@
+//! `RangeOptimizer` for Python.
+
+use crate::decimal;
+use clmm_lp_domain::entities::position::Position;
+use clmm_lp_domain::metrics::sharpe::{Benchmark, SharpeConfig};
+use clmm_lp_domain::value_objects::OptimizationResult;
+use clmm_lp_domain::value_objects::amount::Amount;
+use clmm_lp_optimization::constraints::PoolConstraints;
+use clmm_lp_optimization::objective::{MaximizeFees, MaximizeNetPnL, MaximizeSharpeRatio};
+use clmm_lp_optimization::progress::OptimizationError;
+use clmm_lp_optimization::range_optimizer::RangeOptimizer;
+use clmm_lp_simulation::volume::ConstantVolume;
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+/// Monte Carlo range optimizer.
+#[pyclass(name = "RangeOptimizer", module = "clmm_lp", frozen)]
+#[derive(Debug, Clone)]
+pub struct PyRangeOptimizer {
+    iterations: usize,
+    steps: usize,
+    time_step: f64,
+    threads: Option<usize>,
+}
+
+#[pymethods]
+impl PyRangeOptimizer {
+    /// Creates an optimizer running `iterations` paths of `steps` steps of
+    /// `time_step` years each, on at most `threads` threads.
+    #[new]
+    #[pyo3(signature = (iterations = 100, steps = 30, time_step = 1.0 / 365.0, threads = None))]
+    fn new(iterations: usize, steps: usize, time_step: f64, threads: Option<usize>) -> Self {
+        Self {
+            iterations,
+            steps,
+            time_step,
+            threads,
+        }
+    }
+
+    /// Finds the best range around `current_price`.
+    ///
+    /// `objective` is one of `pnl`, `fees` or `sharpe`; Sharpe ratios use
+    /// `risk_free_rate` and a `benchmark` of `hodl`, `fifty_fifty` or `zero`.
+    /// Returns a dict with the recommended `lower` and `upper` prices and the
+    /// expected metrics. The GIL is released while the simulations run.
+    #[pyo3(signature = (
+        current_price,
+        volatility,
+        volume,
+        pool_liquidity,
+        fee_rate = 0.003,
+        drift = 0.0,
+        objective = "pnl",
+        risk_free_rate = 0.05,
+        benchmark = "zero",
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn optimize<'py>(
+        &self,
+        py: Python<'py>,
+        current_price: f64,
+        volatility: f64,
+        volume: f64,
+        pool_liquidity: u128,
+        fee_rate: f64,
+        drift: f64,
+        objective: &str,
+        risk_free_rate: f64,
+        benchmark: &str,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let current_price = decimal(current_price, "current_price")?;
+        let volume = ConstantVolume::new(decimal(volume, "volume")?);
+        let fee_rate = decimal(fee_rate, "fee_rate")?;
+        if !matches!(objective, "pnl" | "fees" | "sharpe") {
+            return Err(PyValueError::new_err(format!(
+                "Unknown objective: {objective} (expected pnl, fees or sharpe)"
+            )));
+        }
+
+        let benchmark: Benchmark = benchmark.parse().map_err(PyValueError::new_err)?;
+        let sharpe = SharpeConfig::new(decimal(risk_free_rate, "risk_free_rate")?, benchmark);
+
+        let mut optimizer = RangeOptimizer::new(self.iterations, self.steps, self.time_step)
+            .with_pool_constraints(PoolConstraints::for_fee_tier(fee_rate))
+            .with_sharpe(sharpe);
+        if let Some(threads) = self.threads {
+            optimizer = optimizer.with_threads(threads);
+        }
+        let position = Position::new("python", "python", Amount::zero(9), Amount::zero(6), 0, 0);
+
+        let result = py
+            .detach(|| -> Result<OptimizationResult, OptimizationError> {
+                match objective {
+                    "fees" => optimizer.try_optimize(
+                        position,
+                        current_price,
+                        volatility,
+                        drift,
+                        volume,
+                        pool_liquidity,
+                        fee_rate,
+                        MaximizeFees,
+                    ),
+                    "sharpe" => optimizer.try_optimize(
+                        position,
+                        current_price,
+                        volatility,
+                        drift,
+                        volume,
+                        pool_liquidity,
+                        fee_rate,
+                        MaximizeSharpeRatio::new(sharpe.risk_free_rate),
+                    ),
+                    _ => optimizer.try_optimize(
+                        position,
+                        current_price,
+                        volatility,
+                        drift,
+                        volume,
+                        pool_liquidity,
+                        fee_rate,
+                        MaximizeNetPnL,
+                    ),
+                }
+            })
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+
+        let out = PyDict::new(py);
+        for (name, value) in result_fields(&result) {
+            out.set_item(name, value)?;
+        }
+        out.set_item("risk_free_rate", risk_free_rate)?;
+        out.set_item("benchmark", benchmark.as_str())?;
+        Ok(out)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "RangeOptimizer(iterations={}, steps={}, time_step={})",
+            self.iterations, self.steps, self.time_step
+        )
+    }
+}
+
+/// Optimization result as `(name, value)` pairs; a missing Sharpe ratio is
+/// `None`.
+fn result_fields(result: &OptimizationResult) -> [(&'static str, Option<f64>); 7] {
+    let to_f64 = |value: Decimal| value.to_f64();
+    [
+        ("lower", to_f64(result.recommended_range.lower_price.value)),
+        ("upper", to_f64(result.recommended_range.upper_price.value)),
+        ("expected_pnl", to_f64(result.expected_pnl)),
+        ("expected_fees", to_f64(result.expected_fees)),
+        ("fee_std_dev", to_f64(result.fee_std_dev)),
+        ("expected_il", to_f64(result.expected_il)),
+        ("sharpe_ratio", result.sharpe_ratio.and_then(to_f64)),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clmm_lp_domain::value_objects::price::Price;
+    use clmm_lp_domain::value_objects::price_range::PriceRange;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_result_fields() {
+        let result = OptimizationResult {
+            recommended_range: PriceRange::new(Price::new(dec!(90)), Price::new(dec!(110))),
+            expected_pnl: dec!(12.5),
+            expected_fees: dec!(20),
+            fee_std_dev: dec!(2),
+            expected_il: dec!(-0.01),
+            sharpe_ratio: None,
+            sharpe_config: None,
+            explanation: None,
+        };
+        let fields = result_fields(&result);
+        assert_eq!(fields[0], ("lower", Some(90.0)));
+        assert_eq!(fields[1], ("upper", Some(110.0)));
+        assert_eq!(fields[2], ("expected_pnl", Some(12.5)));
+        assert_eq!(fields[6], ("sharpe_ratio", None));
+    }
+}
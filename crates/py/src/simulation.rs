@@ -0,0 +1,277 @@
+//! `SimulationConfig` and `simulate_with_strategy` for Python.
+
+use crate::decimal;
+use clmm_lp_domain::value_objects::price::Price;
+use clmm_lp_domain::value_objects::price_range::PriceRange;
+use clmm_lp_simulation::history::HistoryMode;
+use clmm_lp_simulation::liquidity::ConstantLiquidity;
+use clmm_lp_simulation::price_path::DeterministicPricePath;
+use clmm_lp_simulation::state::{SimulationConfig, SimulationSummary};
+use clmm_lp_simulation::strategies::{
+    ILLimitStrategy, PeriodicRebalance, RebalanceStrategy, StaticRange, ThresholdRebalance,
+};
+use clmm_lp_simulation::strategy_simulator;
+use clmm_lp_simulation::volume::ConstantVolume;
+use numpy::IntoPyArray;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+/// Configuration of a strategy simulation.
+#[pyclass(name = "SimulationConfig", module = "clmm_lp", frozen)]
+#[derive(Debug, Clone)]
+pub struct PySimulationConfig {
+    inner: SimulationConfig,
+}
+
+#[pymethods]
+impl PySimulationConfig {
+    /// Creates a config for `capital` in the quote token, starting in the
+    /// range `[lower, upper]`.
+    #[new]
+    #[pyo3(signature = (
+        capital,
+        lower,
+        upper,
+        fee_rate = 0.003,
+        pool_liquidity = 1_000_000,
+        rebalance_cost = 1.0,
+        step_duration_seconds = 3600,
+    ))]
+    fn new(
+        capital: f64,
+        lower: f64,
+        upper: f64,
+        fee_rate: f64,
+        pool_liquidity: u128,
+        rebalance_cost: f64,
+        step_duration_seconds: u64,
+    ) -> PyResult<Self> {
+        let lower = decimal(lower, "lower")?;
+        let upper = decimal(upper, "upper")?;
+        if lower <= Decimal::ZERO || lower >= upper {
+            return Err(PyValueError::new_err(
+                "lower must be positive and below upper",
+            ));
+        }
+        let range = PriceRange::new(Price::new(lower), Price::new(upper));
+
+        let inner = SimulationConfig::new(decimal(capital, "capital")?, range)
+            .with_fee_rate(decimal(fee_rate, "fee_rate")?)
+            .with_pool_liquidity(pool_liquidity)
+            .with_rebalance_cost(decimal(rebalance_cost, "rebalance_cost")?)
+            .with_step_duration(step_duration_seconds)
+            .with_history(HistoryMode::Compact);
+        Ok(Self { inner })
+    }
+
+    /// Initial capital.
+    #[getter]
+    fn capital(&self) -> f64 {
+        to_f64(self.inner.initial_capital)
+    }
+
+    /// Lower bound of the initial range.
+    #[getter]
+    fn lower(&self) -> f64 {
+        to_f64(self.inner.initial_range.lower_price.value)
+    }
+
+    /// Upper bound of the initial range.
+    #[getter]
+    fn upper(&self) -> f64 {
+        to_f64(self.inner.initial_range.upper_price.value)
+    }
+
+    /// Pool fee rate.
+    #[getter]
+    fn fee_rate(&self) -> f64 {
+        to_f64(self.inner.fee_rate)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "SimulationConfig(capital={}, lower={}, upper={}, fee_rate={})",
+            self.inner.initial_capital,
+            self.inner.initial_range.lower_price.value,
+            self.inner.initial_range.upper_price.value,
+            self.inner.fee_rate,
+        )
+    }
+}
+
+/// Simulates a position over a price series with a rebalancing strategy.
+///
+/// `strategy` is one of `static`, `periodic`, `threshold` or `il_limit`.
+/// Returns a dict with numpy arrays `prices`, `pnl`, `il`, `fees` (one entry
+/// per price) and `rebalance_steps`, and a `summary` dict of floats.
+#[pyfunction]
+#[pyo3(signature = (
+    config,
+    prices,
+    volume,
+    strategy = "static",
+    range_width = 0.1,
+    threshold = 0.05,
+    interval = 24,
+    max_il = 0.05,
+))]
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_with_strategy<'py>(
+    py: Python<'py>,
+    config: &PySimulationConfig,
+    prices: Vec<f64>,
+    volume: f64,
+    strategy: &str,
+    range_width: f64,
+    threshold: f64,
+    interval: u64,
+    max_il: f64,
+) -> PyResult<Bound<'py, PyDict>> {
+    // Raises ImportError here rather than panicking when building the arrays
+    py.import("numpy")?;
+    let prices = prices
+        .into_iter()
+        .map(|p| decimal(p, "prices"))
+        .collect::<PyResult<Vec<_>>>()?;
+    let strategy = build_strategy(
+        strategy,
+        decimal(range_width, "range_width")?,
+        decimal(threshold, "threshold")?,
+        interval,
+        decimal(max_il, "max_il")?,
+    )?;
+    let config = config.inner.clone().with_steps(prices.len());
+    let mut price_path = DeterministicPricePath::new(prices);
+    let mut volume = ConstantVolume::new(decimal(volume, "volume")?);
+    let liquidity = ConstantLiquidity::new(config.pool_liquidity);
+
+    let result = py.detach(|| {
+        strategy_simulator::simulate_with_strategy(
+            &config,
+            &mut price_path,
+            &mut volume,
+            &liquidity,
+            strategy.as_ref(),
+        )
+    });
+
+    let history = result.compact_history.unwrap_or_default();
+    let rebalance_steps: Vec<u64> = result
+        .range_history
+        .iter()
+        .skip(1)
+        .map(|(step, _)| *step)
+        .collect();
+
+    let summary = PyDict::new(py);
+    for (name, value) in summary_fields(&result.summary) {
+        summary.set_item(name, value)?;
+    }
+
+    let out = PyDict::new(py);
+    out.set_item("prices", history.prices.into_pyarray(py))?;
+    out.set_item("pnl", history.pnl.into_pyarray(py))?;
+    out.set_item("il", history.il.into_pyarray(py))?;
+    out.set_item("fees", history.fees.into_pyarray(py))?;
+    out.set_item("rebalance_steps", rebalance_steps.into_pyarray(py))?;
+    out.set_item("summary", summary)?;
+    Ok(out)
+}
+
+/// Builds a strategy from its Python name and parameters.
+fn build_strategy(
+    name: &str,
+    range_width: Decimal,
+    threshold: Decimal,
+    interval: u64,
+    max_il: Decimal,
+) -> PyResult<Box<dyn RebalanceStrategy>> {
+    Ok(match name {
+        "static" => Box::new(StaticRange::new()),
+        "periodic" => Box::new(PeriodicRebalance::new(interval, range_width)),
+        "threshold" => Box::new(ThresholdRebalance::new(threshold, range_width)),
+        "il_limit" => Box::new(ILLimitStrategy::new(max_il, range_width)),
+        other => {
+            return Err(PyValueError::new_err(format!(
+                "Unknown strategy: {other} (expected static, periodic, threshold or il_limit)"
+            )));
+        }
+    })
+}
+
+/// Summary metrics as `(name, value)` pairs.
+fn summary_fields(summary: &SimulationSummary) -> [(&'static str, f64); 13] {
+    [
+        ("entry_price", to_f64(summary.entry_price.value)),
+        ("final_price", to_f64(summary.final_price.value)),
+        ("final_value", to_f64(summary.final_value)),
+        ("total_fees", to_f64(summary.total_fees)),
+        ("final_il_pct", to_f64(summary.final_il_pct)),
+        ("max_il_pct", to_f64(summary.max_il_pct)),
+        ("net_pnl", to_f64(summary.net_pnl)),
+        ("net_pnl_pct", to_f64(summary.net_pnl_pct)),
+        ("rebalance_count", f64::from(summary.rebalance_count)),
+        ("total_rebalance_cost", to_f64(summary.total_rebalance_cost)),
+        ("max_drawdown_pct", to_f64(summary.max_drawdown_pct)),
+        ("time_in_range_pct", to_f64(summary.time_in_range_pct())),
+        ("vs_hodl", to_f64(summary.vs_hodl)),
+    ]
+}
+
+fn to_f64(value: Decimal) -> f64 {
+    value.to_f64().unwrap_or(f64::NAN)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clmm_lp_simulation::strategy_simulator::simulate_with_strategy;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_strategies_by_name_and_summary_fields() {
+        let names = ["static", "periodic", "threshold", "il_limit"];
+        for name in names {
+            let strategy = build_strategy(name, dec!(0.1), dec!(0.05), 24, dec!(0.05)).unwrap();
+            assert!(!strategy.name().is_empty(), "{name}");
+        }
+        assert!(build_strategy("grid", dec!(0.1), dec!(0.05), 24, dec!(0.05)).is_err());
+
+        let config = PySimulationConfig::new(1000.0, 95.0, 105.0, 0.003, 1_000_000, 1.0, 3600)
+            .unwrap()
+            .inner
+            .with_steps(4);
+        assert_eq!(config.history, HistoryMode::Compact);
+        let strategy = build_strategy("threshold", dec!(0.1), dec!(0.05), 24, dec!(0.05)).unwrap();
+        let result = simulate_with_strategy(
+            &config,
+            &mut DeterministicPricePath::new(vec![dec!(100), dec!(104), dec!(112), dec!(111)]),
+            &mut ConstantVolume::new(dec!(10000)),
+            &ConstantLiquidity::new(config.pool_liquidity),
+            strategy.as_ref(),
+        );
+
+        assert_eq!(result.compact_history.unwrap().len(), 4);
+        let fields = summary_fields(&result.summary);
+        let net_pnl = fields
+            .iter()
+            .find(|(name, _)| *name == "net_pnl")
+            .unwrap()
+            .1;
+        assert_eq!(net_pnl, to_f64(result.summary.net_pnl));
+        let rebalances = fields.iter().find(|(name, _)| *name == "rebalance_count");
+        assert_eq!(
+            rebalances.unwrap().1,
+            f64::from(result.summary.rebalance_count)
+        );
+    }
+
+    #[test]
+    fn test_invalid_config_is_rejected() {
+        assert!(PySimulationConfig::new(1000.0, 105.0, 95.0, 0.003, 1, 1.0, 3600).is_err());
+        assert!(PySimulationConfig::new(f64::NAN, 95.0, 105.0, 0.003, 1, 1.0, 3600).is_err());
+    }
+}
@@ -0,0 +1,144 @@
+//! Simulator benchmarks.
+//!
+//! Performance budget on a single core in release mode:
+//! * `simulate_position` and `simulate_with_strategy`: under 1 µs per step
+//! * Monte Carlo: 1M paths of 30 steps in under 10 s
+//!
+//! Run with `make bench` or `cargo bench -p clmm-lp-simulation`.
+
+use clmm_lp_domain::entities::position::{Position, PositionId};
+use clmm_lp_domain::enums::PositionStatus;
+use clmm_lp_domain::value_objects::amount::Amount;
+use clmm_lp_domain::value_objects::price::Price;
+use clmm_lp_domain::value_objects::price_range::PriceRange;
+use clmm_lp_simulation::liquidity::ConstantLiquidity;
+use clmm_lp_simulation::monte_carlo::MonteCarloRunner;
+use clmm_lp_simulation::position_simulator::simulate_position;
+use clmm_lp_simulation::price_path::{
+    DeterministicPricePath, GeometricBrownianMotion, PricePathGenerator,
+};
+use clmm_lp_simulation::state::SimulationConfig;
+use clmm_lp_simulation::strategies::ThresholdRebalance;
+use clmm_lp_simulation::strategy_simulator::simulate_with_strategy;
+use clmm_lp_simulation::volume::ConstantVolume;
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use primitive_types::U256;
+use rust_decimal_macros::dec;
+use std::hint::black_box;
+use uuid::Uuid;
+
+const STEPS: [usize; 2] = [1_000, 10_000];
+
+fn range() -> PriceRange {
+    PriceRange::new(Price::new(dec!(90)), Price::new(dec!(110)))
+}
+
+fn config(steps: usize) -> SimulationConfig {
+    SimulationConfig::new(dec!(1000), range())
+        .with_steps(steps)
+        .with_fee_rate(dec!(0.003))
+        .with_pool_liquidity(1_000_000)
+        .with_rebalance_cost(dec!(1))
+}
+
+fn price_path(steps: usize) -> Vec<Price> {
+    GeometricBrownianMotion::new(dec!(100), 0.0, 0.8, 1.0 / 365.0).generate(steps)
+}
+
+fn position() -> Position {
+    let zero = Amount::new(U256::zero(), 6);
+    Position {
+        id: PositionId(Uuid::new_v4()),
+        pool_address: "pool".to_string(),
+        owner_address: "owner".to_string(),
+        liquidity_amount: 1_000,
+        deposited_amount_a: zero,
+        deposited_amount_b: zero,
+        current_amount_a: zero,
+        current_amount_b: zero,
+        unclaimed_fees_a: zero,
+        unclaimed_fees_b: zero,
+        range: Some(range()),
+        opened_at: 0,
+        status: PositionStatus::Open,
+    }
+}
+
+fn bench_simulate_position(c: &mut Criterion) {
+    let mut group = c.benchmark_group("simulate_position");
+    for steps in STEPS {
+        let config = config(steps);
+        let prices = price_path(steps);
+        group.throughput(Throughput::Elements(steps as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(steps), &prices, |b, prices| {
+            b.iter(|| {
+                let mut path = DeterministicPricePath::from_prices(prices.clone());
+                let mut volume = ConstantVolume::new(dec!(10000));
+                simulate_position(
+                    black_box(&config),
+                    &mut path,
+                    &mut volume,
+                    &ConstantLiquidity::new(1_000_000),
+                )
+            });
+        });
+    }
+    group.finish();
+}
+
+fn bench_simulate_with_strategy(c: &mut Criterion) {
+    let mut group = c.benchmark_group("simulate_with_strategy");
+    let strategy = ThresholdRebalance::new(dec!(0.05), dec!(0.1));
+    for steps in STEPS {
+        let config = config(steps);
+        let prices = price_path(steps);
+        group.throughput(Throughput::Elements(steps as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(steps), &prices, |b, prices| {
+            b.iter(|| {
+                let mut path = DeterministicPricePath::from_prices(prices.clone());
+                let mut volume = ConstantVolume::new(dec!(10000));
+                simulate_with_strategy(
+                    black_box(&config),
+                    &mut path,
+                    &mut volume,
+                    &ConstantLiquidity::new(1_000_000),
+                    &strategy,
+                )
+            });
+        });
+    }
+    group.finish();
+}
+
+fn bench_monte_carlo(c: &mut Criterion) {
+    let mut group = c.benchmark_group("monte_carlo");
+    group.sample_size(10);
+    for iterations in [1_000, 10_000] {
+        let runner = MonteCarloRunner {
+            position: position(),
+            volume_model: ConstantVolume::new(dec!(10000)),
+            liquidity_model: ConstantLiquidity::new(1_000_000),
+            fee_rate: dec!(0.003),
+            initial_price: dec!(100),
+            drift: 0.0,
+            volatility: 0.8,
+            time_step: 1.0 / 365.0,
+            steps: 30,
+            iterations,
+            sharpe: None,
+        };
+        group.throughput(Throughput::Elements(iterations as u64));
+        group.bench_function(BenchmarkId::new("paths", iterations), |b| {
+            b.iter(|| black_box(runner.run_parallel()).mean_net_pnl);
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_simulate_position,
+    bench_simulate_with_strategy,
+    bench_monte_carlo
+);
+criterion_main!(benches);
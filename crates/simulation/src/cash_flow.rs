@@ -0,0 +1,199 @@
+//! Scheduled deposits and withdrawals.
+//!
+//! Real LP capital is rarely static: positions are topped up on a schedule
+//! and fees or principal are taken out along the way. A
+//! [`CashFlowSchedule`] describes those flows in simulation steps, and
+//! [`money_weighted_return`] measures performance net of their timing.
+
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+
+/// Seconds in a year, used to annualize returns.
+const SECONDS_PER_YEAR: f64 = 365.0 * 86_400.0;
+
+/// A single kind of scheduled cash flow.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CashFlowKind {
+    /// Adds this much capital in USD to the position.
+    Deposit(Decimal),
+    /// Removes this much capital in USD from the position, capped at the
+    /// capital deployed.
+    Withdraw(Decimal),
+    /// Takes out all fees collected as cash so far.
+    WithdrawFees,
+}
+
+/// A cash flow repeated every `interval_steps` steps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledCashFlow {
+    /// What the flow does.
+    pub kind: CashFlowKind,
+    /// Steps between flows; the first happens after one interval.
+    pub interval_steps: u64,
+}
+
+/// Deposits and withdrawals applied during a simulation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CashFlowSchedule {
+    /// Scheduled flows, applied in order when several fall on one step.
+    pub flows: Vec<ScheduledCashFlow>,
+    /// Step duration in seconds, used to annualize returns.
+    pub step_seconds: u64,
+}
+
+impl CashFlowSchedule {
+    /// Creates an empty schedule for steps of `step_seconds`.
+    #[must_use]
+    pub fn new(step_seconds: u64) -> Self {
+        Self {
+            flows: Vec::new(),
+            step_seconds: step_seconds.max(1),
+        }
+    }
+
+    /// Deposits `amount` every `interval_steps` steps.
+    #[must_use]
+    pub fn deposit_every(self, interval_steps: u64, amount: Decimal) -> Self {
+        self.with_flow(CashFlowKind::Deposit(amount), interval_steps)
+    }
+
+    /// Withdraws `amount` of capital every `interval_steps` steps.
+    #[must_use]
+    pub fn withdraw_every(self, interval_steps: u64, amount: Decimal) -> Self {
+        self.with_flow(CashFlowKind::Withdraw(amount), interval_steps)
+    }
+
+    /// Withdraws the collected fees every `interval_steps` steps.
+    #[must_use]
+    pub fn withdraw_fees_every(self, interval_steps: u64) -> Self {
+        self.with_flow(CashFlowKind::WithdrawFees, interval_steps)
+    }
+
+    fn with_flow(mut self, kind: CashFlowKind, interval_steps: u64) -> Self {
+        self.flows.push(ScheduledCashFlow {
+            kind,
+            interval_steps: interval_steps.max(1),
+        });
+        self
+    }
+
+    /// Returns true if the schedule has no flows.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.flows.is_empty()
+    }
+
+    /// Returns the flows due at `step` (counted from 1).
+    pub fn due_at(&self, step: u64) -> impl Iterator<Item = &CashFlowKind> {
+        self.flows
+            .iter()
+            .filter(move |flow| step > 0 && step.is_multiple_of(flow.interval_steps))
+            .map(|flow| &flow.kind)
+    }
+
+    /// Splits fixed amounts evenly across `parts` positions sharing the
+    /// schedule, such as the sub-ranges of a grid.
+    #[must_use]
+    pub fn split(&self, parts: usize) -> Self {
+        let parts = Decimal::from(parts.max(1));
+        let flows = self
+            .flows
+            .iter()
+            .map(|flow| ScheduledCashFlow {
+                kind: match &flow.kind {
+                    CashFlowKind::Deposit(amount) => CashFlowKind::Deposit(*amount / parts),
+                    CashFlowKind::Withdraw(amount) => CashFlowKind::Withdraw(*amount / parts),
+                    CashFlowKind::WithdrawFees => CashFlowKind::WithdrawFees,
+                },
+                interval_steps: flow.interval_steps,
+            })
+            .collect();
+        Self {
+            flows,
+            step_seconds: self.step_seconds,
+        }
+    }
+}
+
+/// Annualized money-weighted return (internal rate of return) of a series
+/// of `(step, amount)` cash flows from the investor's side: deposits
+/// negative, withdrawals and the final value positive.
+///
+/// Returns `None` unless the flows change sign and a rate balancing them
+/// is found.
+#[must_use]
+pub fn money_weighted_return(flows: &[(u64, Decimal)], step_seconds: u64) -> Option<Decimal> {
+    let flows: Vec<(f64, f64)> = flows
+        .iter()
+        .filter_map(|(step, amount)| {
+            let years = *step as f64 * step_seconds as f64 / SECONDS_PER_YEAR;
+            Some((years, amount.to_f64()?))
+        })
+        .collect();
+    let npv = |rate: f64| -> f64 {
+        flows
+            .iter()
+            .map(|(years, amount)| amount / (1.0 + rate).powf(*years))
+            .sum()
+    };
+
+    // Net present value falls as the rate rises for a deposit-first series
+    let (mut low, mut high) = (-0.9999_f64, 1.0e6_f64);
+    let (npv_low, npv_high) = (npv(low), npv(high));
+    if !npv_low.is_finite() || !npv_high.is_finite() || npv_low.signum() == npv_high.signum() {
+        return None;
+    }
+    for _ in 0..200 {
+        let mid = (low + high) / 2.0;
+        if npv(mid).signum() == npv_low.signum() {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    Decimal::from_f64((low + high) / 2.0).map(|rate| rate.round_dp(6))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_schedule_due_steps_and_split() {
+        let schedule = CashFlowSchedule::new(3600)
+            .deposit_every(720, dec!(100))
+            .withdraw_fees_every(2160);
+
+        assert_eq!(schedule.due_at(0).count(), 0);
+        assert_eq!(schedule.due_at(719).count(), 0);
+        assert_eq!(
+            schedule.due_at(720).collect::<Vec<_>>(),
+            vec![&CashFlowKind::Deposit(dec!(100))]
+        );
+        assert_eq!(schedule.due_at(2160).count(), 2);
+
+        let half = schedule.split(4);
+        assert_eq!(half.flows[0].kind, CashFlowKind::Deposit(dec!(25)));
+        assert_eq!(half.flows[1].kind, CashFlowKind::WithdrawFees);
+    }
+
+    #[test]
+    fn test_money_weighted_return() {
+        // 1000 in, 1100 out a year later: 10%
+        let year = 365 * 24;
+        let rate = money_weighted_return(&[(0, dec!(-1000)), (year, dec!(1100))], 3600).unwrap();
+        assert!((rate - dec!(0.1)).abs() < dec!(0.0001));
+
+        // A late deposit earns for less time, so the same gain is a higher rate
+        let late = money_weighted_return(
+            &[(0, dec!(-500)), (year / 2, dec!(-500)), (year, dec!(1100))],
+            3600,
+        )
+        .unwrap();
+        assert!(late > rate);
+
+        // No sign change, no rate
+        assert_eq!(money_weighted_return(&[(0, dec!(-1000))], 3600), None);
+    }
+}
@@ -1,8 +1,10 @@
 use crate::liquidity::LiquidityModel;
+use crate::position_simulator::RangeActivity;
 use crate::price_path::PricePathGenerator;
 use crate::volume::VolumeModel;
 use clmm_lp_domain::entities::position::Position;
 use clmm_lp_domain::metrics::impermanent_loss::calculate_il_concentrated;
+use clmm_lp_domain::metrics::sharpe::{Benchmark, SharpeConfig};
 use clmm_lp_domain::value_objects::price::Price;
 use clmm_lp_domain::value_objects::simulation_result::SimulationResult;
 use rust_decimal::Decimal;
@@ -21,6 +23,9 @@ pub struct SimulationEngine<P: PricePathGenerator, V: VolumeModel, L: LiquidityM
     pub fee_rate: Decimal,
     /// The number of simulation steps.
     pub steps: usize,
+    /// Sharpe configuration and steps per year; `None` skips the Sharpe
+    /// ratio, which needs the position value at every step.
+    pub sharpe: Option<(SharpeConfig, f64)>,
 }
 
 impl<P: PricePathGenerator, V: VolumeModel, L: LiquidityModel> SimulationEngine<P, V, L> {
@@ -40,12 +45,47 @@ impl<P: PricePathGenerator, V: VolumeModel, L: LiquidityModel> SimulationEngine<
             liquidity_model,
             fee_rate,
             steps,
+            sharpe: None,
         }
     }
 
+    /// Computes the Sharpe ratio of each run under `config`, with
+    /// `steps_per_year` steps to annualize it.
+    #[must_use]
+    pub fn with_sharpe(mut self, config: SharpeConfig, steps_per_year: f64) -> Self {
+        self.sharpe = Some((config, steps_per_year));
+        self
+    }
+
     /// Runs the simulation.
     pub fn run(&mut self) -> SimulationResult {
-        let prices = self.price_path_generator.generate(self.steps);
+        self.run_with_buffer(&mut Vec::new())
+    }
+
+    /// Runs the simulation, generating the price path into `prices`.
+    ///
+    /// Reusing `prices` across runs avoids allocating a path per run.
+    pub fn run_with_buffer(&mut self, prices: &mut Vec<Price>) -> SimulationResult {
+        self.simulate(prices, None)
+    }
+
+    /// Runs the simulation like [`Self::run_with_buffer`], also recording
+    /// the net PnL after each step into `pnl_trace`.
+    pub fn run_with_trace(
+        &mut self,
+        prices: &mut Vec<Price>,
+        pnl_trace: &mut Vec<Decimal>,
+    ) -> SimulationResult {
+        pnl_trace.clear();
+        self.simulate(prices, Some(pnl_trace))
+    }
+
+    fn simulate(
+        &mut self,
+        prices: &mut Vec<Price>,
+        mut pnl_trace: Option<&mut Vec<Decimal>>,
+    ) -> SimulationResult {
+        self.price_path_generator.generate_into(self.steps, prices);
 
         let mut total_fees_usd = Decimal::ZERO;
         let initial_price = prices
@@ -60,21 +100,28 @@ impl<P: PricePathGenerator, V: VolumeModel, L: LiquidityModel> SimulationEngine<
         let initial_value_usd = Decimal::from(1000); // Placeholder, should compute from position.liquidity
 
         // We assume position range is fixed for this basic simulation
-        let range = self
-            .position
-            .range
-            .clone()
-            .expect("CLMM position needs range");
+        let range = self.position.range.expect("CLMM position needs range");
         let lower = range.lower_price.value;
         let upper = range.upper_price.value;
+        let mut activity = RangeActivity::open(range, Price::new(initial_price));
 
         let mut time_in_range_count = 0;
+        let pos_liq = Decimal::from(self.position.liquidity_amount);
+        let mut last_share: Option<(u128, Decimal)> = None;
+
+        // Position and benchmark values per step, for the Sharpe ratio
+        let mut sharpe_values = self.sharpe.map(|_| {
+            (
+                Vec::with_capacity(prices.len()),
+                Vec::with_capacity(prices.len()),
+            )
+        });
 
-        for price in &prices {
+        for price in prices.iter() {
             current_price = price.value;
 
             // 1. Check range
-            let in_range = current_price >= lower && current_price <= upper;
+            let in_range = activity.is_active_at(*price);
             if in_range {
                 time_in_range_count += 1;
 
@@ -86,24 +133,54 @@ impl<P: PricePathGenerator, V: VolumeModel, L: LiquidityModel> SimulationEngine<
                 // Get global liquidity at current price
                 let global_liquidity = self.liquidity_model.get_liquidity_at_price(current_price);
 
-                // Calculate fee share
-                let fee_share = if global_liquidity > 0 {
-                    let pos_liq = Decimal::from(self.position.liquidity_amount);
-                    let global_liq = Decimal::from(global_liquidity);
-                    // Cap share at 1.0 (100%)
-                    if pos_liq > global_liq {
-                        Decimal::ONE
-                    } else {
-                        pos_liq / global_liq
+                // Calculate fee share, reusing the last one while pool
+                // liquidity is unchanged
+                let fee_share = match last_share {
+                    Some((liquidity, share)) if liquidity == global_liquidity => share,
+                    _ => {
+                        let share = fee_share(pos_liq, global_liquidity);
+                        last_share = Some((global_liquidity, share));
+                        share
                     }
-                } else {
-                    Decimal::ZERO
                 };
 
                 let step_fees = vol * fee_share * self.fee_rate;
                 total_fees_usd += step_fees;
             }
+
+            if pnl_trace.is_some() || sharpe_values.is_some() {
+                let il_pct = calculate_il_concentrated(initial_price, current_price, lower, upper)
+                    .unwrap_or(Decimal::ZERO);
+                let pnl = initial_value_usd * il_pct + total_fees_usd;
+                if let Some(trace) = pnl_trace.as_deref_mut() {
+                    trace.push(pnl);
+                }
+                if let (Some((values, benchmark)), Some((config, _))) =
+                    (sharpe_values.as_mut(), self.sharpe.as_ref())
+                {
+                    // IL is measured against holding the entry tokens
+                    let hodl = Benchmark::Hodl.value(
+                        initial_value_usd,
+                        initial_price,
+                        current_price,
+                        Some(&range),
+                    );
+                    values.push(hodl + pnl);
+                    benchmark.push(config.benchmark.value(
+                        initial_value_usd,
+                        initial_price,
+                        current_price,
+                        Some(&range),
+                    ));
+                }
+            }
         }
+        let sharpe_ratio = match (sharpe_values, self.sharpe) {
+            (Some((values, benchmark)), Some((config, steps_per_year))) => {
+                config.sharpe_ratio(&values, &benchmark, steps_per_year)
+            }
+            _ => None,
+        };
 
         // 3. Calculate Final IL
         let il_pct = calculate_il_concentrated(initial_price, current_price, lower, upper)
@@ -122,11 +199,25 @@ impl<P: PricePathGenerator, V: VolumeModel, L: LiquidityModel> SimulationEngine<
             max_drawdown: Decimal::ZERO, // Need track path for this
             time_in_range_percentage: Decimal::from(time_in_range_count)
                 / Decimal::from(self.steps),
-            sharpe_ratio: None,
+            sharpe_ratio,
         }
     }
 }
 
+/// Share of fees earned by `position_liquidity` out of `global_liquidity`,
+/// capped at 1.0 (100%).
+fn fee_share(position_liquidity: Decimal, global_liquidity: u128) -> Decimal {
+    if global_liquidity == 0 {
+        return Decimal::ZERO;
+    }
+    let global_liq = Decimal::from(global_liquidity);
+    if position_liquidity > global_liq {
+        Decimal::ONE
+    } else {
+        position_liquidity / global_liq
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -224,4 +315,37 @@ mod tests {
         // IL should be negative (price moved)
         assert!(result.total_il < Decimal::ZERO);
     }
+
+    #[test]
+    fn test_simulation_sharpe_depends_on_benchmark() {
+        let prices: Vec<Price> = [100, 104, 98, 106, 101, 103]
+            .into_iter()
+            .map(|p| Price::new(Decimal::from(p)))
+            .collect();
+        let run = |sharpe: Option<SharpeConfig>| {
+            let volume = ConstantVolume::from_amount(Amount::new(U256::zero(), 6));
+            let path_gen = DeterministicPricePath {
+                prices: prices.clone(),
+            };
+            let mut engine = SimulationEngine::new(
+                create_dummy_position(),
+                path_gen,
+                volume,
+                ConstantLiquidity::new(10000),
+                Decimal::ZERO,
+                prices.len(),
+            );
+            if let Some(config) = sharpe {
+                engine = engine.with_sharpe(config, 365.0);
+            }
+            engine.run().sharpe_ratio
+        };
+
+        assert_eq!(run(None), None);
+        // Without fees the position only gives up value to holding
+        let vs_hodl = run(Some(SharpeConfig::new(Decimal::ZERO, Benchmark::Hodl))).unwrap();
+        let vs_cash = run(Some(SharpeConfig::new(Decimal::ZERO, Benchmark::Zero))).unwrap();
+        assert!(vs_hodl < Decimal::ZERO);
+        assert_ne!(vs_hodl, vs_cash);
+    }
 }
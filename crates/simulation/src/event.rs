@@ -3,6 +3,8 @@
 //! This module defines event types that can occur during a simulation,
 //! such as swaps, rebalances, fee collections, and position changes.
 
+use crate::history::HistoryMode;
+use crate::state::SimulationClock;
 use clmm_lp_domain::value_objects::price::Price;
 use clmm_lp_domain::value_objects::price_range::PriceRange;
 use rust_decimal::Decimal;
@@ -35,7 +37,8 @@ pub enum SimulationEventType {
 pub struct SimulationEvent {
     /// Step number when event occurred.
     pub step: u64,
-    /// Timestamp in seconds (if available).
+    /// Unix timestamp of the step in seconds, if the simulation's clock
+    /// has calendar time.
     pub timestamp: Option<u64>,
     /// Type of event.
     pub event_type: SimulationEventType,
@@ -220,17 +223,45 @@ impl SimulationEvent {
 pub struct EventLog {
     /// All recorded events.
     events: Vec<SimulationEvent>,
+    /// Which events are kept.
+    mode: HistoryMode,
+    /// Calendar time events are stamped with.
+    clock: SimulationClock,
 }
 
 impl EventLog {
     /// Creates a new empty event log.
     #[must_use]
     pub fn new() -> Self {
-        Self { events: Vec::new() }
+        Self::default()
     }
 
-    /// Records an event.
-    pub fn record(&mut self, event: SimulationEvent) {
+    /// Creates an empty event log that keeps only the events `mode` retains.
+    #[must_use]
+    pub fn retaining(mode: HistoryMode) -> Self {
+        Self {
+            events: Vec::new(),
+            mode,
+            clock: SimulationClock::Steps,
+        }
+    }
+
+    /// Stamps recorded events without a timestamp with the time of their
+    /// step on `clock`.
+    #[must_use]
+    pub fn with_clock(mut self, clock: SimulationClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Records an event, unless the log's history mode drops its type.
+    pub fn record(&mut self, mut event: SimulationEvent) {
+        if !self.mode.keeps_event(&event.event_type) {
+            return;
+        }
+        if event.timestamp.is_none() {
+            event.timestamp = self.clock.timestamp_at(event.step);
+        }
         self.events.push(event);
     }
 
@@ -240,6 +271,12 @@ impl EventLog {
         &self.events
     }
 
+    /// Consumes the log, returning its events.
+    #[must_use]
+    pub fn into_events(self) -> Vec<SimulationEvent> {
+        self.events
+    }
+
     /// Returns events of a specific type.
     #[must_use]
     pub fn events_of_type(&self, event_type: SimulationEventType) -> Vec<&SimulationEvent> {
@@ -292,7 +329,7 @@ mod tests {
             0,
             price,
             dec!(1000),
-            range.clone(),
+            range,
         ));
         log.record(SimulationEvent::fee_collection(
             1,
@@ -309,7 +346,7 @@ mod tests {
         log.record(SimulationEvent::out_of_range(
             3,
             Price::new(dec!(120)),
-            range.clone(),
+            range,
         ));
 
         assert_eq!(log.events().len(), 4);
@@ -335,4 +372,21 @@ mod tests {
         assert_eq!(event.event_type, SimulationEventType::Rebalance);
         assert_eq!(event.step, 5);
     }
+
+    #[test]
+    fn test_event_log_stamps_steps_with_clock() {
+        let price = Price::new(dec!(100));
+        let range = PriceRange::new(Price::new(dec!(90)), Price::new(dec!(110)));
+        let mut log = EventLog::new().with_clock(SimulationClock::from_timestamps(vec![
+            1_700_000_000,
+            1_700_003_600,
+        ]));
+
+        log.record(SimulationEvent::out_of_range(1, price, range));
+        log.record(SimulationEvent::back_in_range(1, price, range).with_timestamp(42));
+
+        assert_eq!(log.events()[0].timestamp, Some(1_700_003_600));
+        // Explicit timestamps are kept
+        assert_eq!(log.events()[1].timestamp, Some(42));
+    }
 }
@@ -0,0 +1,169 @@
+//! Fee modeling.
+//!
+//! Most pools charge a fixed fee tier, but some protocols adjust the fee
+//! with market conditions, typically raising it when the price moves fast
+//! so LPs are paid more for the adverse selection they absorb. A
+//! [`FeeModel`] gives the fee rate charged at each simulation step.
+
+use clmm_lp_domain::value_objects::price::Price;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+
+/// Fee rate charged by a pool over a simulation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FeeModel {
+    /// A flat fee tier (e.g., 0.003 for 0.3%).
+    Constant(Decimal),
+    /// A fee that rises with recent volatility.
+    Dynamic(DynamicFee),
+}
+
+impl FeeModel {
+    /// Returns the fee rate with no market activity: the flat rate, or the
+    /// dynamic fee's base rate.
+    #[must_use]
+    pub fn base_rate(&self) -> Decimal {
+        match self {
+            Self::Constant(rate) => *rate,
+            Self::Dynamic(dynamic) => dynamic.base_rate,
+        }
+    }
+
+    /// Returns the fee rate charged at `step` of the price path `prices`.
+    #[must_use]
+    pub fn rate_at(&self, prices: &[Price], step: usize) -> Decimal {
+        match self {
+            Self::Constant(rate) => *rate,
+            Self::Dynamic(dynamic) => dynamic.rate_at(prices, step),
+        }
+    }
+}
+
+impl Default for FeeModel {
+    fn default() -> Self {
+        Self::Constant(Decimal::new(3, 3))
+    }
+}
+
+/// Volatility-linked dynamic fee.
+///
+/// The rate is `base_rate + sensitivity × σ`, where `σ` is the standard
+/// deviation of the per-step log returns over the last `window` steps,
+/// capped at `max_rate`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DynamicFee {
+    /// Rate charged when the price is still.
+    pub base_rate: Decimal,
+    /// Upper bound on the rate.
+    pub max_rate: Decimal,
+    /// Rate added per unit of per-step return standard deviation.
+    pub sensitivity: Decimal,
+    /// Number of trailing steps the volatility is measured over.
+    pub window: usize,
+}
+
+impl DynamicFee {
+    /// Creates a dynamic fee with a 24-step volatility window.
+    #[must_use]
+    pub fn new(base_rate: Decimal, max_rate: Decimal, sensitivity: Decimal) -> Self {
+        Self {
+            base_rate,
+            max_rate,
+            sensitivity,
+            window: 24,
+        }
+    }
+
+    /// Sets the volatility window in steps.
+    #[must_use]
+    pub fn with_window(mut self, window: usize) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Returns the rate charged at `step`, from the returns leading up to it.
+    #[must_use]
+    pub fn rate_at(&self, prices: &[Price], step: usize) -> Decimal {
+        if prices.is_empty() {
+            return self.base_rate.min(self.max_rate);
+        }
+        let end = step.min(prices.len() - 1);
+        let start = end.saturating_sub(self.window);
+        let volatility = return_std_dev(&prices[start..=end])
+            .and_then(Decimal::from_f64)
+            .unwrap_or(Decimal::ZERO);
+
+        (self.base_rate + self.sensitivity * volatility).min(self.max_rate)
+    }
+}
+
+/// Population standard deviation of the log returns between consecutive
+/// prices, or `None` with fewer than two returns.
+fn return_std_dev(prices: &[Price]) -> Option<f64> {
+    let returns: Vec<f64> = prices
+        .windows(2)
+        .filter_map(|w| {
+            let (from, to) = (w[0].value.to_f64()?, w[1].value.to_f64()?);
+            (from > 0.0 && to > 0.0).then(|| (to / from).ln())
+        })
+        .collect();
+    if returns.len() < 2 {
+        return None;
+    }
+    let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+    let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / returns.len() as f64;
+    Some(variance.sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn path(values: &[Decimal]) -> Vec<Price> {
+        values.iter().map(|v| Price::new(*v)).collect()
+    }
+
+    #[test]
+    fn test_constant_fee_ignores_prices() {
+        let prices = path(&[dec!(100), dec!(150), dec!(80)]);
+        let model = FeeModel::Constant(dec!(0.0005));
+        assert_eq!(model.rate_at(&prices, 2), dec!(0.0005));
+        assert_eq!(model.base_rate(), dec!(0.0005));
+        assert_eq!(FeeModel::default().rate_at(&[], 0), dec!(0.003));
+    }
+
+    #[test]
+    fn test_dynamic_fee_rises_with_volatility_and_caps() {
+        let prices = path(&[
+            dec!(100),
+            dec!(100),
+            dec!(100),
+            dec!(100),
+            dec!(101),
+            dec!(99),
+            dec!(102),
+            dec!(98),
+        ]);
+        let fee = DynamicFee::new(dec!(0.001), dec!(0.01), dec!(0.2)).with_window(3);
+        let model = FeeModel::Dynamic(fee.clone());
+
+        // A still price charges the base rate
+        assert_eq!(model.rate_at(&prices, 3), dec!(0.001));
+        assert_eq!(model.rate_at(&prices, 0), dec!(0.001));
+
+        // Choppy prices raise it
+        let choppy = model.rate_at(&prices, 7);
+        assert!(choppy > dec!(0.005) && choppy < dec!(0.01));
+
+        // Steps past the path reuse the last window
+        assert_eq!(model.rate_at(&prices, 50), choppy);
+
+        // Capped at the maximum
+        let steep = FeeModel::Dynamic(DynamicFee {
+            sensitivity: dec!(10),
+            ..fee
+        });
+        assert_eq!(steep.rate_at(&prices, 7), dec!(0.01));
+    }
+}
@@ -0,0 +1,61 @@
+//! Fee collection cadence.
+//!
+//! Without a collection policy, fees count as cash the moment they are
+//! earned. A policy instead leaves fees uncollected in the position until a
+//! scheduled collection, which costs one transaction, and can compound the
+//! collected fees back into liquidity so they earn fees themselves.
+
+use rust_decimal::Decimal;
+
+/// Schedule and cost of fee collections.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeCollection {
+    /// Steps between collections.
+    pub interval_steps: u64,
+    /// Transaction cost per collection in USD.
+    pub tx_cost: Decimal,
+    /// Whether collected fees are added back to the position's liquidity.
+    pub compound: bool,
+}
+
+impl FeeCollection {
+    /// Collects fees every `interval_steps` steps at `tx_cost` per collection.
+    #[must_use]
+    pub fn every(interval_steps: u64, tx_cost: Decimal) -> Self {
+        Self {
+            interval_steps: interval_steps.max(1),
+            tx_cost,
+            compound: false,
+        }
+    }
+
+    /// Compounds collected fees back into liquidity.
+    #[must_use]
+    pub fn with_compounding(mut self) -> Self {
+        self.compound = true;
+        self
+    }
+
+    /// Returns true if a collection is due after `steps_since_collection`.
+    #[must_use]
+    pub fn is_due(&self, steps_since_collection: u64) -> bool {
+        steps_since_collection >= self.interval_steps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_is_due() {
+        let policy = FeeCollection::every(24, dec!(0.5));
+        assert!(!policy.compound);
+        assert!(!policy.is_due(23));
+        assert!(policy.is_due(24));
+
+        // A zero interval collects every step
+        assert!(FeeCollection::every(0, dec!(0.5)).is_due(1));
+    }
+}
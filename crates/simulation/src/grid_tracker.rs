@@ -0,0 +1,334 @@
+//! Position tracking for grid strategies.
+//!
+//! A grid position is a set of sub-range positions with equal shares of the
+//! capital. Each sub-range is tracked by its own [`PositionTracker`] and
+//! rebalanced independently; results are aggregated across sub-ranges.
+
+use crate::cash_flow::{CashFlowSchedule, money_weighted_return};
+use crate::fee_collection::FeeCollection;
+use crate::position_tracker::{PositionTracker, TrackerSummary};
+use crate::state::SimulationClock;
+use crate::strategies::{GridStrategy, RebalanceAction};
+use clmm_lp_domain::metrics::apy::SECONDS_PER_YEAR;
+use clmm_lp_domain::metrics::sharpe::SharpeConfig;
+use clmm_lp_domain::value_objects::price::Price;
+use clmm_lp_domain::value_objects::price_range::PriceRange;
+use rust_decimal::Decimal;
+
+/// Tracks the sub-range positions of a grid throughout a simulation.
+#[derive(Debug)]
+pub struct GridTracker {
+    /// Initial capital in USD, split evenly across sub-ranges.
+    pub initial_capital: Decimal,
+    /// Entry price.
+    pub entry_price: Price,
+    /// One tracker per sub-range, lowest range first at entry.
+    pub levels: Vec<PositionTracker>,
+    /// Scheduled deposits and withdrawals for the whole grid.
+    cash_flows: Option<CashFlowSchedule>,
+}
+
+impl GridTracker {
+    /// Creates a grid tracker with the strategy's sub-ranges around `center`.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial_capital` - Starting capital in USD
+    /// * `entry_price` - Price at position entry
+    /// * `center` - Center price of the initial grid
+    /// * `strategy` - Grid strategy defining the sub-ranges
+    /// * `rebalance_cost` - Cost per sub-range rebalance in USD
+    #[must_use]
+    pub fn new(
+        initial_capital: Decimal,
+        entry_price: Price,
+        center: Price,
+        strategy: &GridStrategy,
+        rebalance_cost: Decimal,
+    ) -> Self {
+        let ranges = strategy.initial_ranges(center);
+        let level_capital = initial_capital / Decimal::from(ranges.len());
+        let levels = ranges
+            .into_iter()
+            .map(|range| PositionTracker::new(level_capital, entry_price, range, rebalance_cost))
+            .collect();
+
+        Self {
+            initial_capital,
+            entry_price,
+            levels,
+            cash_flows: None,
+        }
+    }
+
+    /// Collects each sub-range's fees on a schedule, one transaction per
+    /// sub-range and collection.
+    #[must_use]
+    pub fn with_fee_collection(mut self, policy: FeeCollection) -> Self {
+        self.levels = self
+            .levels
+            .into_iter()
+            .map(|level| level.with_fee_collection(policy.clone()))
+            .collect();
+        self
+    }
+
+    /// Reports the grid's Sharpe ratio under `config`, with steps of
+    /// `step_seconds` to annualize it.
+    #[must_use]
+    pub fn with_sharpe(mut self, config: SharpeConfig, step_seconds: u64) -> Self {
+        self.levels = self
+            .levels
+            .into_iter()
+            .map(|level| level.with_sharpe(config, step_seconds))
+            .collect();
+        self
+    }
+
+    /// Stamps every sub-range's snapshots with the calendar time of their
+    /// step on `clock`.
+    #[must_use]
+    pub fn with_clock(mut self, clock: SimulationClock) -> Self {
+        self.levels = self
+            .levels
+            .into_iter()
+            .map(|level| level.with_clock(clock.clone()))
+            .collect();
+        self
+    }
+
+    /// Applies scheduled deposits and withdrawals to the grid, split evenly
+    /// across sub-ranges.
+    #[must_use]
+    pub fn with_cash_flows(mut self, schedule: CashFlowSchedule) -> Self {
+        let level_schedule = schedule.split(self.levels.len());
+        self.levels = self
+            .levels
+            .into_iter()
+            .map(|level| level.with_cash_flows(level_schedule.clone()))
+            .collect();
+        self.cash_flows = Some(schedule);
+        self
+    }
+
+    /// Returns the current sub-ranges.
+    #[must_use]
+    pub fn ranges(&self) -> Vec<PriceRange> {
+        self.levels.iter().map(|l| l.current_range).collect()
+    }
+
+    /// Returns true if any sub-range contains `price`.
+    #[must_use]
+    pub fn is_in_range(&self, price: Price) -> bool {
+        self.levels.iter().any(|l| l.current_range.contains(price))
+    }
+
+    /// Records a step for every sub-range.
+    ///
+    /// # Arguments
+    ///
+    /// * `price` - Current price
+    /// * `step_fees` - Fees the whole capital earns this step while price is
+    ///   inside the grid; sub-ranges containing the price share them evenly,
+    ///   since each holds the grid's liquidity density over its own width
+    /// * `strategy` - Grid strategy evaluated for each sub-range
+    ///
+    /// # Returns
+    ///
+    /// The actions taken this step
+    pub fn record_step(
+        &mut self,
+        price: Price,
+        step_fees: Decimal,
+        strategy: &GridStrategy,
+    ) -> Vec<RebalanceAction> {
+        let active = self
+            .levels
+            .iter()
+            .filter(|l| l.current_range.contains(price))
+            .count();
+        let fees_per_active = if active > 0 {
+            step_fees / Decimal::from(active)
+        } else {
+            Decimal::ZERO
+        };
+
+        self.levels
+            .iter_mut()
+            .filter_map(|level| {
+                let fees = if level.current_range.contains(price) {
+                    fees_per_active
+                } else {
+                    Decimal::ZERO
+                };
+                level.record_step(price, fees, Some(strategy))
+            })
+            .collect()
+    }
+
+    /// Returns summary statistics for each sub-range.
+    #[must_use]
+    pub fn level_summaries(&self) -> Vec<TrackerSummary> {
+        self.levels.iter().map(PositionTracker::summary).collect()
+    }
+
+    /// Returns summary statistics aggregated across sub-ranges.
+    ///
+    /// The grid counts as in range on steps where any sub-range is, and
+    /// drawdown is measured on the combined value.
+    #[must_use]
+    pub fn summary(&self) -> TrackerSummary {
+        let summaries = self.level_summaries();
+        let total_steps = summaries.first().map_or(0, |s| s.total_steps);
+
+        let sharpe = self.levels.first().and_then(PositionTracker::sharpe_config);
+        let mut values = vec![self.initial_capital];
+        let mut benchmark = vec![self.initial_capital];
+
+        let mut in_range_steps = 0u64;
+        let mut peak = self.initial_capital;
+        let mut max_drawdown = Decimal::ZERO;
+        for step in 0..total_steps as usize {
+            if self.levels.iter().any(|l| l.snapshots[step].in_range) {
+                in_range_steps += 1;
+            }
+            let value: Decimal = self.initial_capital
+                + self
+                    .levels
+                    .iter()
+                    .map(|l| l.snapshots[step].net_pnl)
+                    .sum::<Decimal>();
+            peak = peak.max(value);
+            if !peak.is_zero() {
+                max_drawdown = max_drawdown.max((peak - value) / peak);
+            }
+            if let Some((config, _)) = &sharpe {
+                values.push(value);
+                benchmark.push(
+                    self.levels
+                        .iter()
+                        .map(|l| l.benchmark_value(config, l.snapshots[step].price))
+                        .sum(),
+                );
+            }
+        }
+        let sharpe_ratio = sharpe.and_then(|(config, step_seconds)| {
+            config.sharpe_ratio(
+                &values,
+                &benchmark,
+                SECONDS_PER_YEAR as f64 / step_seconds as f64,
+            )
+        });
+        let time_in_range_pct = if total_steps > 0 {
+            Decimal::from(in_range_steps) / Decimal::from(total_steps)
+        } else {
+            Decimal::ZERO
+        };
+
+        let sum = |f: fn(&TrackerSummary) -> Decimal| summaries.iter().map(f).sum::<Decimal>();
+        let final_value = sum(|s| s.final_value);
+        let hodl_value = sum(|s| s.hodl_value);
+        let total_deposited = sum(|s| s.total_deposited);
+        let total_withdrawn = sum(|s| s.total_withdrawn);
+        let money_weighted_return = self.cash_flows.as_ref().and_then(|schedule| {
+            let mut flows = vec![(0, -self.initial_capital)];
+            flows.extend(
+                self.levels
+                    .iter()
+                    .flat_map(|l| l.cash_flow_ledger().iter().copied()),
+            );
+            flows.push((total_steps, final_value));
+            money_weighted_return(&flows, schedule.step_seconds)
+        });
+        let final_il_pct = if summaries.is_empty() {
+            Decimal::ZERO
+        } else {
+            sum(|s| s.final_il_pct) / Decimal::from(summaries.len())
+        };
+
+        TrackerSummary {
+            total_steps,
+            final_value,
+            final_pnl: final_value + total_withdrawn - total_deposited - self.initial_capital,
+            final_il_pct,
+            total_fees: sum(|s| s.total_fees),
+            time_in_range_pct,
+            rebalance_count: summaries.iter().map(|s| s.rebalance_count).sum(),
+            total_rebalance_cost: sum(|s| s.total_rebalance_cost),
+            fee_collections: summaries.iter().map(|s| s.fee_collections).sum(),
+            total_collection_cost: sum(|s| s.total_collection_cost),
+            compounded_fees: sum(|s| s.compounded_fees),
+            total_deposited,
+            total_withdrawn,
+            money_weighted_return,
+            sharpe_ratio,
+            sharpe_config: sharpe.map(|(config, _)| config),
+            max_drawdown,
+            hodl_value,
+            vs_hodl: final_value + total_withdrawn - hodl_value,
+            start_timestamp: summaries.first().and_then(|s| s.start_timestamp),
+            end_timestamp: summaries.first().and_then(|s| s.end_timestamp),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn grid() -> (GridStrategy, GridTracker) {
+        let strategy = GridStrategy::new(4, dec!(0.05));
+        let tracker = GridTracker::new(
+            dec!(1000),
+            Price::new(dec!(100)),
+            Price::new(dec!(100)),
+            &strategy,
+            dec!(1),
+        );
+        (strategy, tracker)
+    }
+
+    #[test]
+    fn test_fees_go_to_active_levels() {
+        let (strategy, mut tracker) = grid();
+
+        // Inside the second level only
+        tracker.record_step(Price::new(dec!(98)), dec!(8), &strategy);
+        assert_eq!(tracker.levels[1].summary().total_fees, dec!(8));
+        assert_eq!(tracker.levels[0].summary().total_fees, dec!(0));
+
+        // On the boundary of the middle levels, fees are shared
+        tracker.record_step(Price::new(dec!(100)), dec!(8), &strategy);
+        assert_eq!(tracker.levels[1].summary().total_fees, dec!(12));
+        assert_eq!(tracker.levels[2].summary().total_fees, dec!(4));
+
+        let summary = tracker.summary();
+        assert_eq!(summary.total_fees, dec!(16));
+        assert_eq!(summary.time_in_range_pct, dec!(1));
+        assert_eq!(summary.rebalance_count, 0);
+    }
+
+    #[test]
+    fn test_levels_rebalance_independently() {
+        let (strategy, mut tracker) = grid();
+
+        let actions = tracker.record_step(Price::new(dec!(112)), dec!(0), &strategy);
+        assert_eq!(actions.len(), 2);
+
+        let mut lowers: Vec<Decimal> = tracker
+            .ranges()
+            .iter()
+            .map(|r| r.lower_price.value)
+            .collect();
+        lowers.sort();
+        assert_eq!(lowers, vec![dec!(100), dec!(105), dec!(110), dec!(115)]);
+        assert!(tracker.is_in_range(Price::new(dec!(112))));
+
+        let summary = tracker.summary();
+        assert_eq!(summary.rebalance_count, 2);
+        assert_eq!(summary.total_rebalance_cost, dec!(2));
+        // Price left the initial grid before the levels moved
+        assert_eq!(summary.time_in_range_pct, dec!(0));
+    }
+}
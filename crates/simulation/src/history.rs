@@ -0,0 +1,238 @@
+//! Retention of per-step simulation histories.
+//!
+//! A full history keeps every price, PnL, IL and fee value as `Decimal`
+//! plus an event per fee collection, which for thousands of Monte Carlo
+//! paths runs to gigabytes. Compact histories keep the per-step series as
+//! `f64` and drop fee collection events; summary-only runs keep nothing but
+//! the [`SimulationSummary`](crate::state::SimulationSummary). Both full and
+//! compact histories keep the calendar time of each step when the
+//! simulation's clock has one.
+
+use crate::event::{SimulationEvent, SimulationEventType};
+use crate::state::SimulationClock;
+use clmm_lp_domain::value_objects::price::Price;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use std::mem::size_of;
+
+/// How much of a simulation's per-step history to keep.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HistoryMode {
+    /// Every step as `Decimal`, plus all events.
+    #[default]
+    Full,
+    /// Every step as `f64`, plus all events except fee collections.
+    Compact,
+    /// No per-step history or events; only the summary.
+    SummaryOnly,
+}
+
+impl HistoryMode {
+    /// Approximate bytes retained per simulated step.
+    #[must_use]
+    pub fn bytes_per_step(self) -> usize {
+        match self {
+            // Price, PnL, IL and fees, plus a fee collection event and the
+            // step's timestamp
+            Self::Full => {
+                size_of::<Price>()
+                    + 3 * size_of::<Decimal>()
+                    + size_of::<SimulationEvent>()
+                    + size_of::<u64>()
+            }
+            Self::Compact => 4 * size_of::<f64>() + size_of::<u64>(),
+            Self::SummaryOnly => 0,
+        }
+    }
+
+    /// Picks the most detailed mode whose histories for `paths` runs of
+    /// `steps` steps fit in `budget_bytes`.
+    #[must_use]
+    pub fn for_budget(paths: usize, steps: usize, budget_bytes: usize) -> Self {
+        let steps = paths.saturating_mul(steps);
+        [Self::Full, Self::Compact]
+            .into_iter()
+            .find(|mode| steps.saturating_mul(mode.bytes_per_step()) <= budget_bytes)
+            .unwrap_or(Self::SummaryOnly)
+    }
+
+    /// Returns true if events of `event_type` are kept.
+    #[must_use]
+    pub fn keeps_event(self, event_type: &SimulationEventType) -> bool {
+        match self {
+            Self::Full => true,
+            Self::Compact => *event_type != SimulationEventType::FeeCollection,
+            Self::SummaryOnly => false,
+        }
+    }
+}
+
+/// Per-step history stored as `f64`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CompactHistory {
+    /// Price at each step.
+    pub prices: Vec<f64>,
+    /// Net PnL at each step.
+    pub pnl: Vec<f64>,
+    /// IL at each step.
+    pub il: Vec<f64>,
+    /// Cumulative fees at each step.
+    pub fees: Vec<f64>,
+}
+
+impl CompactHistory {
+    /// Creates an empty history with room for `steps` steps.
+    #[must_use]
+    pub fn with_capacity(steps: usize) -> Self {
+        Self {
+            prices: Vec::with_capacity(steps),
+            pnl: Vec::with_capacity(steps),
+            il: Vec::with_capacity(steps),
+            fees: Vec::with_capacity(steps),
+        }
+    }
+
+    /// Returns the number of steps recorded.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.prices.len()
+    }
+
+    /// Returns true if no steps were recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.prices.is_empty()
+    }
+}
+
+/// Per-step histories as stored in simulation results.
+///
+/// Only the fields of the recorder's mode are filled.
+#[derive(Debug, Default)]
+pub(crate) struct Histories {
+    pub(crate) prices: Vec<Price>,
+    pub(crate) pnl: Vec<Decimal>,
+    pub(crate) il: Vec<Decimal>,
+    pub(crate) fees: Vec<Decimal>,
+    pub(crate) compact: Option<CompactHistory>,
+    pub(crate) timestamps: Vec<u64>,
+}
+
+/// Records per-step values in the form a [`HistoryMode`] asks for.
+pub(crate) struct HistoryRecorder {
+    mode: HistoryMode,
+    histories: Histories,
+}
+
+impl HistoryRecorder {
+    /// Creates a recorder with room for `steps` steps.
+    pub(crate) fn new(mode: HistoryMode, steps: usize) -> Self {
+        let histories = match mode {
+            HistoryMode::Full => Histories {
+                pnl: Vec::with_capacity(steps),
+                il: Vec::with_capacity(steps),
+                fees: Vec::with_capacity(steps),
+                ..Histories::default()
+            },
+            HistoryMode::Compact => Histories {
+                compact: Some(CompactHistory::with_capacity(steps)),
+                ..Histories::default()
+            },
+            HistoryMode::SummaryOnly => Histories::default(),
+        };
+        Self { mode, histories }
+    }
+
+    /// Records one step.
+    pub(crate) fn record(&mut self, price: Price, pnl: Decimal, il: Decimal, fees: Decimal) {
+        match self.mode {
+            HistoryMode::Full => {
+                self.histories.pnl.push(pnl);
+                self.histories.il.push(il);
+                self.histories.fees.push(fees);
+            }
+            HistoryMode::Compact => {
+                if let Some(compact) = &mut self.histories.compact {
+                    compact
+                        .prices
+                        .push(price.value.to_f64().unwrap_or(f64::NAN));
+                    compact.pnl.push(pnl.to_f64().unwrap_or(f64::NAN));
+                    compact.il.push(il.to_f64().unwrap_or(f64::NAN));
+                    compact.fees.push(fees.to_f64().unwrap_or(f64::NAN));
+                }
+            }
+            HistoryMode::SummaryOnly => {}
+        }
+    }
+
+    /// Returns the histories, keeping the simulated `prices` only in full
+    /// mode and the steps' timestamps on `clock` unless summary-only.
+    pub(crate) fn finish(self, prices: Vec<Price>, clock: &SimulationClock) -> Histories {
+        let mut histories = self.histories;
+        if self.mode != HistoryMode::SummaryOnly {
+            histories.timestamps = clock.timestamps(prices.len());
+        }
+        if self.mode == HistoryMode::Full {
+            histories.prices = prices;
+        }
+        histories
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_modes_retain_less_and_fit_budgets() {
+        let price = Price::new(dec!(100));
+        let mut full = HistoryRecorder::new(HistoryMode::Full, 2);
+        let mut compact = HistoryRecorder::new(HistoryMode::Compact, 2);
+        let mut summary = HistoryRecorder::new(HistoryMode::SummaryOnly, 2);
+        for recorder in [&mut full, &mut compact, &mut summary] {
+            recorder.record(price, dec!(1.5), dec!(-0.25), dec!(2));
+            recorder.record(price, dec!(2.5), dec!(-0.5), dec!(3));
+        }
+
+        let clock = SimulationClock::Regular {
+            start: 1_700_000_000,
+            step_seconds: 60,
+        };
+        let full = full.finish(vec![price; 2], &clock);
+        assert_eq!(full.pnl, vec![dec!(1.5), dec!(2.5)]);
+        assert_eq!(full.prices.len(), 2);
+        assert_eq!(full.timestamps, vec![1_700_000_000, 1_700_000_060]);
+        assert!(full.compact.is_none());
+
+        let compact = compact.finish(vec![price; 2], &clock);
+        assert_eq!(compact.timestamps.len(), 2);
+        assert!(compact.pnl.is_empty() && compact.prices.is_empty());
+        let series = compact.compact.unwrap();
+        assert_eq!(series.len(), 2);
+        assert_eq!(series.il, vec![-0.25, -0.5]);
+        assert_eq!(series.prices, vec![100.0, 100.0]);
+
+        let summary = summary.finish(vec![price; 2], &clock);
+        assert!(summary.pnl.is_empty() && summary.prices.is_empty());
+        assert!(summary.timestamps.is_empty());
+        assert!(summary.compact.is_none());
+
+        assert!(HistoryMode::Compact.bytes_per_step() < HistoryMode::Full.bytes_per_step());
+        let budget = 1000 * HistoryMode::Compact.bytes_per_step();
+        assert_eq!(HistoryMode::for_budget(1, 100, budget), HistoryMode::Full);
+        assert_eq!(
+            HistoryMode::for_budget(10, 100, budget),
+            HistoryMode::Compact
+        );
+        assert_eq!(
+            HistoryMode::for_budget(11, 100, budget),
+            HistoryMode::SummaryOnly
+        );
+
+        assert!(HistoryMode::Full.keeps_event(&SimulationEventType::FeeCollection));
+        assert!(!HistoryMode::Compact.keeps_event(&SimulationEventType::FeeCollection));
+        assert!(HistoryMode::Compact.keeps_event(&SimulationEventType::Rebalance));
+        assert!(!HistoryMode::SummaryOnly.keeps_event(&SimulationEventType::PositionClosed));
+    }
+}
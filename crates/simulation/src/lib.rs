@@ -3,10 +3,20 @@
 /// Prelude module for convenient imports.
 pub mod prelude;
 
+/// Scheduled deposits and withdrawals.
+pub mod cash_flow;
 /// Simulation engine implementation.
 pub mod engine;
 /// Event definitions.
 pub mod event;
+/// Fee modeling.
+pub mod fee;
+/// Fee collection cadence and compounding.
+pub mod fee_collection;
+/// Grid position tracking.
+pub mod grid_tracker;
+/// Retention of per-step histories.
+pub mod history;
 /// Liquidity modeling.
 pub mod liquidity;
 /// Monte Carlo simulation logic.
@@ -17,11 +27,15 @@ pub mod position_simulator;
 pub mod position_tracker;
 /// Price path generation.
 pub mod price_path;
+/// Counterfactual replay of position histories.
+pub mod replay;
 /// Simulation state management.
 pub mod state;
 /// Rebalancing strategies.
 pub mod strategies;
 /// Strategy simulation logic.
 pub mod strategy_simulator;
+/// Backtests of strategy parameter grids.
+pub mod sweep;
 /// Volume modeling.
 pub mod volume;
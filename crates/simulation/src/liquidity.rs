@@ -1,3 +1,4 @@
+use rand_distr::{Distribution, Normal};
 use rust_decimal::Decimal;
 
 /// Trait to model the global liquidity of a pool.
@@ -31,3 +32,183 @@ impl LiquidityModel for ConstantLiquidity {
         self.liquidity
     }
 }
+
+/// A liquidity range with constant active liquidity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiquidityBucket {
+    /// Lower price bound (inclusive).
+    pub lower: Decimal,
+    /// Upper price bound (exclusive).
+    pub upper: Decimal,
+    /// Active liquidity within the bucket.
+    pub liquidity: u128,
+}
+
+/// A model of the pool's active liquidity across price levels.
+///
+/// Built from the current in-range liquidity distribution (e.g. initialized
+/// ticks); prices outside every bucket fall back to the base liquidity.
+#[derive(Debug, Clone)]
+pub struct LiquidityDistribution {
+    /// Liquidity used outside the known buckets.
+    pub base_liquidity: u128,
+    /// Known liquidity buckets, sorted by lower bound.
+    pub buckets: Vec<LiquidityBucket>,
+}
+
+impl LiquidityDistribution {
+    /// Creates a distribution with only a base liquidity.
+    pub fn new(base_liquidity: u128) -> Self {
+        Self {
+            base_liquidity,
+            buckets: Vec::new(),
+        }
+    }
+
+    /// Adds a liquidity bucket.
+    #[must_use]
+    pub fn with_bucket(mut self, lower: Decimal, upper: Decimal, liquidity: u128) -> Self {
+        if lower < upper {
+            self.buckets.push(LiquidityBucket {
+                lower,
+                upper,
+                liquidity,
+            });
+            self.buckets.sort_by_key(|b| b.lower);
+        }
+        self
+    }
+}
+
+impl LiquidityModel for LiquidityDistribution {
+    fn get_liquidity_at_price(&self, price: Decimal) -> u128 {
+        self.buckets
+            .iter()
+            .find(|b| price >= b.lower && price < b.upper)
+            .map_or(self.base_liquidity, |b| b.liquidity)
+    }
+}
+
+/// A model where total pool liquidity evolves over time.
+///
+/// Liquidity migrates in and out of a pool as incentives and volatility
+/// change, so the LP's fee share moves with it. The path is fixed at
+/// construction, either generated from a mean-reverting process with random
+/// shocks or replayed from observed history; steps past the end hold the last
+/// value.
+#[derive(Debug, Clone)]
+pub struct DynamicLiquidityModel {
+    /// Total pool liquidity per step.
+    pub path: Vec<u128>,
+}
+
+impl DynamicLiquidityModel {
+    /// Replays an observed liquidity history.
+    pub fn from_history(path: Vec<u128>) -> Self {
+        Self { path }
+    }
+
+    /// Generates a path where log-liquidity reverts towards `long_run_liquidity`.
+    ///
+    /// # Arguments
+    /// * `initial_liquidity` - Liquidity at step 0
+    /// * `long_run_liquidity` - Level liquidity reverts to
+    /// * `reversion_speed` - Fraction of the gap closed per step (0 to 1)
+    /// * `shock_volatility` - Standard deviation of the per-step log shock
+    /// * `steps` - Number of steps to generate
+    pub fn mean_reverting(
+        initial_liquidity: u128,
+        long_run_liquidity: u128,
+        reversion_speed: f64,
+        shock_volatility: f64,
+        steps: usize,
+    ) -> Self {
+        let mut rng = rand::rng();
+        let normal = Normal::new(0.0, shock_volatility.max(0.0)).unwrap();
+        let speed = reversion_speed.clamp(0.0, 1.0);
+
+        let target = (long_run_liquidity.max(1) as f64).ln();
+        let mut current = (initial_liquidity.max(1) as f64).ln();
+
+        let mut path = Vec::with_capacity(steps.max(1));
+        path.push(initial_liquidity);
+        for _ in 1..steps {
+            current += speed * (target - current) + normal.sample(&mut rng);
+            path.push(current.exp().round() as u128);
+        }
+
+        Self { path }
+    }
+
+    /// Returns the liquidity at a step.
+    pub fn liquidity_at(&self, step: usize) -> u128 {
+        self.path
+            .get(step)
+            .or_else(|| self.path.last())
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+impl LiquidityModel for DynamicLiquidityModel {
+    /// Returns the starting liquidity; the path is indexed by step, not price.
+    fn get_liquidity_at_price(&self, _price: Decimal) -> u128 {
+        self.liquidity_at(0)
+    }
+
+    fn get_liquidity(&self, step: usize) -> u128 {
+        self.liquidity_at(step)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_liquidity_distribution_lookup() {
+        let distribution = LiquidityDistribution::new(1_000)
+            .with_bucket(Decimal::from(95), Decimal::from(105), 50_000)
+            .with_bucket(Decimal::from(90), Decimal::from(95), 10_000);
+
+        assert_eq!(
+            distribution.get_liquidity_at_price(Decimal::from(100)),
+            50_000
+        );
+        assert_eq!(
+            distribution.get_liquidity_at_price(Decimal::from(92)),
+            10_000
+        );
+        assert_eq!(
+            distribution.get_liquidity_at_price(Decimal::from(105)),
+            1_000
+        );
+        assert_eq!(distribution.buckets[0].lower, Decimal::from(90));
+    }
+
+    #[test]
+    fn test_dynamic_liquidity_reverts_to_mean() {
+        let model = DynamicLiquidityModel::mean_reverting(1_000, 4_000, 0.5, 0.0, 20);
+
+        assert_eq!(model.path.len(), 20);
+        assert_eq!(model.get_liquidity(0), 1_000);
+        // Half the log gap closes each step: 1000 -> 2000 -> ~2828
+        assert_eq!(model.get_liquidity(1), 2_000);
+        assert!(model.get_liquidity(2) > 2_800 && model.get_liquidity(2) < 2_850);
+        assert!(model.path.windows(2).all(|w| w[0] <= w[1]));
+        assert!(model.get_liquidity(19).abs_diff(4_000) <= 1);
+    }
+
+    #[test]
+    fn test_dynamic_liquidity_replays_history() {
+        let model = DynamicLiquidityModel::from_history(vec![10, 20, 30]);
+
+        assert_eq!(model.get_liquidity(1), 20);
+        assert_eq!(model.get_liquidity(10), 30);
+        assert_eq!(model.get_liquidity_at_price(Decimal::from(100)), 10);
+        assert_eq!(
+            DynamicLiquidityModel::from_history(vec![]).get_liquidity(0),
+            0
+        );
+    }
+}
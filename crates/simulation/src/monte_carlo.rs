@@ -3,8 +3,12 @@ use crate::liquidity::LiquidityModel;
 use crate::price_path::GeometricBrownianMotion;
 use crate::volume::VolumeModel;
 use clmm_lp_domain::entities::position::Position;
+use clmm_lp_domain::metrics::sharpe::SharpeConfig;
+use clmm_lp_domain::value_objects::price::Price;
 use clmm_lp_domain::value_objects::simulation_result::SimulationResult;
+use rayon::prelude::*;
 use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
 
 /// Runner for Monte Carlo simulations.
 pub struct MonteCarloRunner<V: VolumeModel + Clone, L: LiquidityModel + Clone> {
@@ -28,6 +32,20 @@ pub struct MonteCarloRunner<V: VolumeModel + Clone, L: LiquidityModel + Clone> {
     pub steps: usize,
     /// The number of iterations.
     pub iterations: usize,
+    /// Sharpe configuration; `None` leaves the Sharpe ratio uncomputed.
+    pub sharpe: Option<SharpeConfig>,
+}
+
+/// A simulated path at a percentile of final net PnL, kept to chart the
+/// spread of outcomes behind a result.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PercentilePath {
+    /// Percentile of the path's final net PnL among all paths (0-100).
+    pub percentile: u8,
+    /// Price at each step.
+    pub prices: Vec<Decimal>,
+    /// Net PnL after each step.
+    pub pnl: Vec<Decimal>,
 }
 
 /// Result of a Monte Carlo simulation run.
@@ -40,8 +58,14 @@ pub struct AggregateResult {
     pub var_95_net_pnl: Decimal, // Value at Risk (5th percentile)
     /// Mean fees earned.
     pub mean_fees: Decimal,
+    /// Standard deviation of fees earned across iterations.
+    pub fee_std_dev: Decimal,
     /// Mean impermanent loss.
     pub mean_il: Decimal,
+    /// Mean of the paths' Sharpe ratios, when the runner computes them.
+    pub mean_sharpe: Option<Decimal>,
+    /// Net PnL of each path as a fraction of its initial value, ascending.
+    pub path_returns: Vec<Decimal>,
     /// Number of iterations run.
     pub iterations: usize,
 }
@@ -49,35 +73,48 @@ pub struct AggregateResult {
 impl<V: VolumeModel + Clone, L: LiquidityModel + Clone> MonteCarloRunner<V, L> {
     /// Runs the Monte Carlo simulation.
     pub fn run(&mut self) -> AggregateResult {
-        let mut results: Vec<SimulationResult> = Vec::with_capacity(self.iterations);
-
-        for _ in 0..self.iterations {
-            let gbm = GeometricBrownianMotion::new(
-                self.initial_price,
-                self.drift,
-                self.volatility,
-                self.time_step,
-            );
-
-            // Create a fresh volume model for each run if it has state
-            let vol = self.volume_model.clone();
-            let liq = self.liquidity_model.clone();
-
-            let mut engine = SimulationEngine::new(
-                self.position.clone(),
-                gbm,
-                vol,
-                liq,
-                self.fee_rate,
-                self.steps,
-            );
-
-            results.push(engine.run());
-        }
+        let mut prices = Vec::with_capacity(self.steps + 1);
+        let results: Vec<SimulationResult> = (0..self.iterations)
+            .map(|_| self.run_path(&mut prices))
+            .collect();
 
         self.aggregate(results)
     }
 
+    /// Simulates a single price path, generated into `prices`.
+    fn run_path(&self, prices: &mut Vec<Price>) -> SimulationResult {
+        self.engine().run_with_buffer(prices)
+    }
+
+    /// Builds the engine for one path.
+    fn engine(&self) -> SimulationEngine<GeometricBrownianMotion, V, L> {
+        let gbm = GeometricBrownianMotion::new(
+            self.initial_price,
+            self.drift,
+            self.volatility,
+            self.time_step,
+        );
+
+        // Create a fresh volume model for each run if it has state
+        let vol = self.volume_model.clone();
+        let liq = self.liquidity_model.clone();
+
+        let engine = SimulationEngine::new(
+            self.position.clone(),
+            gbm,
+            vol,
+            liq,
+            self.fee_rate,
+            self.steps,
+        );
+        match self.sharpe {
+            Some(config) if self.time_step > 0.0 => {
+                engine.with_sharpe(config, 1.0 / self.time_step)
+            }
+            _ => engine,
+        }
+    }
+
     fn aggregate(&self, results: Vec<SimulationResult>) -> AggregateResult {
         let count = Decimal::from(results.len());
 
@@ -89,6 +126,16 @@ impl<V: VolumeModel + Clone, L: LiquidityModel + Clone> MonteCarloRunner<V, L> {
         let mean_fees = total_fees / count;
         let mean_il = total_il / count;
 
+        let fee_variance = results
+            .iter()
+            .map(|r| (r.total_fees_earned - mean_fees) * (r.total_fees_earned - mean_fees))
+            .sum::<Decimal>()
+            / count;
+        let fee_std_dev = fee_variance
+            .to_f64()
+            .and_then(|v| Decimal::from_f64(v.sqrt()))
+            .unwrap_or(Decimal::ZERO);
+
         // Sort for percentiles
         let mut pnls: Vec<Decimal> = results.iter().map(|r| r.net_pnl).collect();
         pnls.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
@@ -100,13 +147,103 @@ impl<V: VolumeModel + Clone, L: LiquidityModel + Clone> MonteCarloRunner<V, L> {
         let var_idx = (results.len() as f64 * 0.05).floor() as usize;
         let var_95 = pnls[var_idx.min(results.len() - 1)];
 
+        let sharpes: Vec<Decimal> = results.iter().filter_map(|r| r.sharpe_ratio).collect();
+        let mean_sharpe = (!sharpes.is_empty())
+            .then(|| sharpes.iter().copied().sum::<Decimal>() / Decimal::from(sharpes.len()));
+
+        let mut path_returns: Vec<Decimal> = results
+            .iter()
+            .filter_map(|r| {
+                let initial_value = r.final_position_value - r.net_pnl;
+                (!initial_value.is_zero()).then(|| r.net_pnl / initial_value)
+            })
+            .collect();
+        path_returns.sort();
+
         AggregateResult {
             mean_net_pnl: mean_pnl,
             median_net_pnl: median_pnl,
             var_95_net_pnl: var_95,
             mean_fees,
+            fee_std_dev,
             mean_il,
+            mean_sharpe,
+            path_returns,
             iterations: results.len(),
         }
     }
 }
+
+impl<V, L> MonteCarloRunner<V, L>
+where
+    V: VolumeModel + Clone + Send + Sync,
+    L: LiquidityModel + Clone + Send + Sync,
+{
+    /// Runs the Monte Carlo simulation with paths spread across the rayon pool.
+    ///
+    /// Paths run on the current rayon pool, so callers can bound the thread
+    /// count by calling this inside `ThreadPool::install`.
+    pub fn run_parallel(&self) -> AggregateResult {
+        self.run_parallel_with(|| true)
+            .expect("Paths only stop when the callback asks to")
+    }
+
+    /// Runs the simulation in parallel, calling `on_path` after each path.
+    ///
+    /// Returns `None` if `on_path` returns false, which stops the remaining
+    /// paths as soon as possible.
+    pub fn run_parallel_with<F>(&self, on_path: F) -> Option<AggregateResult>
+    where
+        F: Fn() -> bool + Sync,
+    {
+        // Each worker reuses one price buffer for all of its paths
+        let results: Option<Vec<SimulationResult>> = (0..self.iterations)
+            .into_par_iter()
+            .map_init(
+                || Vec::with_capacity(self.steps + 1),
+                |prices, _| {
+                    let result = self.run_path(prices);
+                    on_path().then_some(result)
+                },
+            )
+            .collect();
+
+        results.map(|results| self.aggregate(results))
+    }
+
+    /// Runs the simulation in parallel and returns the path at each of
+    /// `percentiles` of final net PnL, with its prices and PnL trace.
+    ///
+    /// Percentiles above 100 are clamped; an empty list skips the run.
+    pub fn percentile_paths(&self, percentiles: &[u8]) -> Vec<PercentilePath> {
+        if percentiles.is_empty() || self.iterations == 0 {
+            return Vec::new();
+        }
+
+        let mut paths: Vec<(Decimal, Vec<Price>, Vec<Decimal>)> = (0..self.iterations)
+            .into_par_iter()
+            .map(|_| {
+                let mut prices = Vec::with_capacity(self.steps + 1);
+                let mut pnl = Vec::with_capacity(self.steps + 1);
+                let result = self.engine().run_with_trace(&mut prices, &mut pnl);
+                (result.net_pnl, prices, pnl)
+            })
+            .collect();
+        paths.sort_by_key(|(net_pnl, _, _)| *net_pnl);
+
+        let last = paths.len() - 1;
+        percentiles
+            .iter()
+            .map(|&percentile| {
+                let percentile = percentile.min(100);
+                let index = (f64::from(percentile) / 100.0 * last as f64).round() as usize;
+                let (_, prices, pnl) = &paths[index];
+                PercentilePath {
+                    percentile,
+                    prices: prices.iter().map(|p| p.value).collect(),
+                    pnl: pnl.clone(),
+                }
+            })
+            .collect()
+    }
+}
@@ -4,11 +4,12 @@
 //! over a price path, returning comprehensive results.
 
 use crate::event::{EventLog, SimulationEvent};
+use crate::history::{CompactHistory, HistoryRecorder};
 use crate::liquidity::LiquidityModel;
 use crate::price_path::PricePathGenerator;
 use crate::state::{SimulationConfig, SimulationSummary};
 use crate::volume::VolumeModel;
-use clmm_lp_domain::metrics::impermanent_loss::calculate_il_concentrated;
+use clmm_lp_domain::metrics::impermanent_loss::ConcentratedIl;
 use clmm_lp_domain::value_objects::price::Price;
 use clmm_lp_domain::value_objects::price_range::PriceRange;
 use rust_decimal::Decimal;
@@ -21,14 +22,19 @@ pub struct PositionSimulationResult {
     pub summary: SimulationSummary,
     /// Event log from the simulation.
     pub events: Vec<SimulationEvent>,
-    /// Price path used.
+    /// Price path used; empty unless the history mode is full.
     pub prices: Vec<Price>,
-    /// Step-by-step PnL values.
+    /// Step-by-step PnL values; empty unless the history mode is full.
     pub pnl_history: Vec<Decimal>,
-    /// Step-by-step IL values.
+    /// Step-by-step IL values; empty unless the history mode is full.
     pub il_history: Vec<Decimal>,
-    /// Step-by-step fee values.
+    /// Step-by-step fee values; empty unless the history mode is full.
     pub fee_history: Vec<Decimal>,
+    /// Step-by-step values as `f64`, kept in compact history mode.
+    pub compact_history: Option<CompactHistory>,
+    /// Unix timestamp of each step; empty without calendar time or in
+    /// summary-only history mode.
+    pub timestamps: Vec<u64>,
 }
 
 /// Simulates a static LP position (no rebalancing).
@@ -60,44 +66,45 @@ where
 
     let entry_price = prices[0];
     let range = &config.initial_range;
+    let il = ConcentratedIl::new(
+        entry_price.value,
+        range.lower_price.value,
+        range.upper_price.value,
+    )
+    .ok();
+    let il_at = |price: Price| {
+        il.and_then(|il| il.at(price.value).ok())
+            .unwrap_or(Decimal::ZERO)
+    };
 
-    let mut event_log = EventLog::new();
+    let mut event_log = EventLog::retaining(config.history).with_clock(config.clock.clone());
     let mut cumulative_fees = Decimal::ZERO;
     let mut steps_in_range: u64 = 0;
     let mut max_il = Decimal::ZERO;
     let mut max_value = config.initial_capital;
     let mut max_drawdown = Decimal::ZERO;
 
-    let mut pnl_history = Vec::with_capacity(prices.len());
-    let mut il_history = Vec::with_capacity(prices.len());
-    let mut fee_history = Vec::with_capacity(prices.len());
+    let mut history = HistoryRecorder::new(config.history, prices.len());
 
-    let mut was_in_range = is_in_range(&entry_price, range);
+    let mut activity = RangeActivity::open(*range, entry_price);
+    let mut was_in_range = activity.is_active_at(entry_price);
 
     // Record position opened
     event_log.record(SimulationEvent::position_opened(
         0,
         entry_price,
         config.initial_capital,
-        range.clone(),
+        *range,
     ));
 
     for (step, price) in prices.iter().enumerate() {
-        let in_range = is_in_range(price, range);
+        let in_range = activity.is_active_at(*price);
 
         // Track range transitions
         if in_range && !was_in_range {
-            event_log.record(SimulationEvent::back_in_range(
-                step as u64,
-                *price,
-                range.clone(),
-            ));
+            event_log.record(SimulationEvent::back_in_range(step as u64, *price, *range));
         } else if !in_range && was_in_range {
-            event_log.record(SimulationEvent::out_of_range(
-                step as u64,
-                *price,
-                range.clone(),
-            ));
+            event_log.record(SimulationEvent::out_of_range(step as u64, *price, *range));
         }
         was_in_range = in_range;
 
@@ -110,7 +117,7 @@ where
 
             let step_fees = if pool_liquidity > 0 {
                 let lp_share = Decimal::from(config.pool_liquidity) / Decimal::from(pool_liquidity);
-                volume * config.fee_rate * lp_share
+                volume * config.fee_model.rate_at(&prices, step) * lp_share
             } else {
                 Decimal::ZERO
             };
@@ -128,13 +135,7 @@ where
         }
 
         // Calculate IL
-        let il_decimal = calculate_il_concentrated(
-            entry_price.value,
-            price.value,
-            range.lower_price.value,
-            range.upper_price.value,
-        )
-        .unwrap_or(Decimal::ZERO);
+        let il_decimal = il_at(*price);
 
         if il_decimal < max_il {
             max_il = il_decimal;
@@ -158,20 +159,12 @@ where
             max_drawdown = drawdown;
         }
 
-        pnl_history.push(net_pnl);
-        il_history.push(il_decimal);
-        fee_history.push(cumulative_fees);
+        history.record(*price, net_pnl, il_decimal, cumulative_fees);
     }
 
     let final_price = *prices.last().unwrap_or(&entry_price);
 
-    let final_il_decimal = calculate_il_concentrated(
-        entry_price.value,
-        final_price.value,
-        range.lower_price.value,
-        range.upper_price.value,
-    )
-    .unwrap_or(Decimal::ZERO);
+    let final_il_decimal = il_at(final_price);
 
     let final_price_ratio = if entry_price.value.is_zero() {
         1.0
@@ -222,21 +215,48 @@ where
         max_drawdown_pct: max_drawdown,
         hodl_value,
         vs_hodl,
+        start_timestamp: config.clock.timestamp_at(0),
+        end_timestamp: config.clock.timestamp_at(prices.len() as u64 - 1),
     };
 
+    let histories = history.finish(prices, &config.clock);
     PositionSimulationResult {
         summary,
-        events: event_log.events().to_vec(),
-        prices,
-        pnl_history,
-        il_history,
-        fee_history,
+        events: event_log.into_events(),
+        prices: histories.prices,
+        pnl_history: histories.pnl,
+        il_history: histories.il,
+        fee_history: histories.fees,
+        compact_history: histories.compact,
+        timestamps: histories.timestamps,
     }
 }
 
-/// Checks if a price is within a range.
-fn is_in_range(price: &Price, range: &PriceRange) -> bool {
-    price.value >= range.lower_price.value && price.value <= range.upper_price.value
+/// Tracks whether a position's liquidity is active as price moves.
+///
+/// Ranges are closed, except for single-sided token B deposits: opened at
+/// or above the upper bound, they hold no token A there and only start
+/// earning once price moves below the upper bound into the range.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RangeActivity {
+    range: PriceRange,
+    entered: bool,
+}
+
+impl RangeActivity {
+    /// Starts tracking a position opened at `entry_price`.
+    pub(crate) fn open(range: PriceRange, entry_price: Price) -> Self {
+        Self {
+            range,
+            entered: entry_price < range.upper_price,
+        }
+    }
+
+    /// Returns true if the position earns fees at `price`.
+    pub(crate) fn is_active_at(&mut self, price: Price) -> bool {
+        self.entered |= price < self.range.upper_price;
+        self.entered && self.range.contains(price)
+    }
 }
 
 /// Creates an empty result for edge cases.
@@ -259,6 +279,8 @@ fn empty_result(config: &SimulationConfig) -> PositionSimulationResult {
         max_drawdown_pct: Decimal::ZERO,
         hodl_value: config.initial_capital,
         vs_hodl: Decimal::ZERO,
+        start_timestamp: None,
+        end_timestamp: None,
     };
 
     PositionSimulationResult {
@@ -268,6 +290,8 @@ fn empty_result(config: &SimulationConfig) -> PositionSimulationResult {
         pnl_history: Vec::new(),
         il_history: Vec::new(),
         fee_history: Vec::new(),
+        compact_history: None,
+        timestamps: Vec::new(),
     }
 }
 
@@ -277,6 +301,7 @@ mod tests {
     use crate::liquidity::ConstantLiquidity;
     use crate::price_path::DeterministicPricePath;
     use crate::volume::ConstantVolume;
+    use clmm_lp_domain::value_objects::price_range::PriceRange;
     use rust_decimal_macros::dec;
 
     #[test]
@@ -331,6 +356,80 @@ mod tests {
         assert!(result.summary.final_il_pct < Decimal::ZERO);
     }
 
+    #[test]
+    fn test_single_sided_position_earns_only_in_range() {
+        // Range entirely above the entry price (token A only deposit)
+        let range = PriceRange::new(Price::new(dec!(100)), Price::new(dec!(110)));
+        let config = SimulationConfig::new(dec!(1000), range)
+            .with_steps(5)
+            .with_fee_rate(dec!(0.003));
+
+        let prices = vec![dec!(95), dec!(98), dec!(99), dec!(101), dec!(105)];
+        let mut price_path = DeterministicPricePath::new(prices);
+        let mut volume_model = ConstantVolume::new(dec!(10000));
+        let liquidity_model = ConstantLiquidity::new(1_000_000);
+
+        let result = simulate_position(
+            &config,
+            &mut price_path,
+            &mut volume_model,
+            &liquidity_model,
+        );
+
+        assert_eq!(result.summary.steps_in_range, 2);
+        assert_eq!(result.fee_history[2], Decimal::ZERO);
+        assert!(result.fee_history[3] > Decimal::ZERO);
+        // Price stayed below the range until entering it, so no IL before entry
+        assert_eq!(result.il_history[2], Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_position_at_upper_bound_is_out_of_range() {
+        // Range entirely below the price (token B only deposit)
+        let range = PriceRange::new(Price::new(dec!(90)), Price::new(dec!(100)));
+        let config = SimulationConfig::new(dec!(1000), range)
+            .with_steps(3)
+            .with_fee_rate(dec!(0.003));
+
+        let prices = vec![dec!(100); 3];
+        let mut price_path = DeterministicPricePath::new(prices);
+        let mut volume_model = ConstantVolume::new(dec!(10000));
+        let liquidity_model = ConstantLiquidity::new(1_000_000);
+
+        let result = simulate_position(
+            &config,
+            &mut price_path,
+            &mut volume_model,
+            &liquidity_model,
+        );
+
+        assert_eq!(result.summary.steps_in_range, 0);
+        assert_eq!(result.summary.total_fees, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_range_upper_bound_is_inclusive_once_entered() {
+        // Opened inside the range, so the upper bound still earns fees
+        let range = PriceRange::new(Price::new(dec!(90)), Price::new(dec!(100)));
+        let config = SimulationConfig::new(dec!(1000), range)
+            .with_steps(3)
+            .with_fee_rate(dec!(0.003));
+
+        let prices = vec![dec!(95), dec!(100), dec!(100)];
+        let mut price_path = DeterministicPricePath::new(prices);
+        let mut volume_model = ConstantVolume::new(dec!(10000));
+        let liquidity_model = ConstantLiquidity::new(1_000_000);
+
+        let result = simulate_position(
+            &config,
+            &mut price_path,
+            &mut volume_model,
+            &liquidity_model,
+        );
+
+        assert_eq!(result.summary.steps_in_range, 3);
+    }
+
     #[test]
     fn test_simulate_position_events() {
         let range = PriceRange::new(Price::new(dec!(95)), Price::new(dec!(105)));
@@ -365,4 +464,34 @@ mod tests {
             crate::event::SimulationEventType::PositionClosed
         ));
     }
+
+    #[test]
+    fn test_simulate_position_dynamic_fee() {
+        let range = PriceRange::new(Price::new(dec!(90)), Price::new(dec!(110)));
+        let prices = vec![
+            dec!(100),
+            dec!(103),
+            dec!(98),
+            dec!(104),
+            dec!(97),
+            dec!(101),
+        ];
+        let run = |config: &SimulationConfig| {
+            let mut price_path = DeterministicPricePath::new(prices.clone());
+            let mut volume_model = ConstantVolume::new(dec!(10000));
+            let liquidity_model = ConstantLiquidity::new(1_000_000);
+            simulate_position(config, &mut price_path, &mut volume_model, &liquidity_model)
+        };
+
+        let flat = SimulationConfig::new(dec!(1000), range)
+            .with_steps(6)
+            .with_fee_rate(dec!(0.003));
+        let dynamic = flat.clone().with_fee_model(crate::fee::FeeModel::Dynamic(
+            crate::fee::DynamicFee::new(dec!(0.003), dec!(0.02), dec!(0.1)),
+        ));
+        assert_eq!(dynamic.fee_rate, dec!(0.003));
+
+        // The choppy path charges more than the flat base rate
+        assert!(run(&dynamic).summary.total_fees > run(&flat).summary.total_fees);
+    }
 }
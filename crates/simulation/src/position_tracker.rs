@@ -3,8 +3,13 @@
 //! This module provides functionality to track position state over time,
 //! recording snapshots and computing metrics at each step.
 
+use crate::cash_flow::{CashFlowKind, CashFlowSchedule, money_weighted_return};
+use crate::fee_collection::FeeCollection;
+use crate::state::SimulationClock;
 use crate::strategies::{RebalanceAction, RebalanceStrategy, StrategyContext};
+use clmm_lp_domain::metrics::apy::SECONDS_PER_YEAR;
 use clmm_lp_domain::metrics::impermanent_loss::calculate_il_concentrated;
+use clmm_lp_domain::metrics::sharpe::SharpeConfig;
 use clmm_lp_domain::value_objects::price::Price;
 use clmm_lp_domain::value_objects::price_range::PriceRange;
 use rust_decimal::Decimal;
@@ -14,6 +19,8 @@ use rust_decimal::Decimal;
 pub struct PositionSnapshot {
     /// Step number in the simulation.
     pub step: u64,
+    /// Unix timestamp of the step, if the tracker's clock has calendar time.
+    pub timestamp: Option<u64>,
     /// Current price at this step.
     pub price: Price,
     /// Current position range.
@@ -22,6 +29,8 @@ pub struct PositionSnapshot {
     pub in_range: bool,
     /// Cumulative fees earned up to this step.
     pub cumulative_fees: Decimal,
+    /// Fees earned but not yet collected.
+    pub uncollected_fees: Decimal,
     /// Current impermanent loss percentage.
     pub il_pct: Decimal,
     /// Current position value in USD.
@@ -51,8 +60,38 @@ pub struct PositionTracker {
     pub total_rebalance_cost: Decimal,
     /// Cost per rebalance in USD.
     pub rebalance_cost: Decimal,
+    /// Capital providing liquidity, including compounded fees.
+    pub deployed_capital: Decimal,
+    /// Number of scheduled fee collections.
+    pub collection_count: u32,
+    /// Total transaction costs from fee collections.
+    pub total_collection_cost: Decimal,
+    /// Fees compounded back into liquidity.
+    pub compounded_fees: Decimal,
+    /// Capital deposited after the initial capital.
+    pub total_deposited: Decimal,
+    /// Capital and fees withdrawn.
+    pub total_withdrawn: Decimal,
+    /// Scheduled deposits and withdrawals.
+    cash_flows: Option<CashFlowSchedule>,
+    /// Applied flows as `(step, amount)`, deposits negative.
+    cash_flow_ledger: Vec<(u64, Decimal)>,
+    /// Deposits as `(amount, price)`, for the HODL comparison.
+    deposits: Vec<(Decimal, Price)>,
+    /// Sharpe configuration and step duration in seconds.
+    sharpe: Option<(SharpeConfig, u64)>,
+    /// Calendar time of the recorded steps, starting with the first.
+    clock: SimulationClock,
+    /// Fee collection policy; `None` credits fees as they are earned.
+    fee_collection: Option<FeeCollection>,
     /// Cumulative fees earned.
     cumulative_fees: Decimal,
+    /// Collected fees held as cash.
+    collected_fees: Decimal,
+    /// Fees earned but not yet collected.
+    uncollected_fees: Decimal,
+    /// Steps since fees were last collected.
+    steps_since_collection: u64,
     /// Current step.
     current_step: u64,
 }
@@ -82,17 +121,76 @@ impl PositionTracker {
             rebalance_count: 0,
             total_rebalance_cost: Decimal::ZERO,
             rebalance_cost,
+            deployed_capital: initial_capital,
+            collection_count: 0,
+            total_collection_cost: Decimal::ZERO,
+            compounded_fees: Decimal::ZERO,
+            total_deposited: Decimal::ZERO,
+            total_withdrawn: Decimal::ZERO,
+            cash_flows: None,
+            cash_flow_ledger: Vec::new(),
+            deposits: Vec::new(),
+            sharpe: None,
+            clock: SimulationClock::Steps,
+            fee_collection: None,
             cumulative_fees: Decimal::ZERO,
+            collected_fees: Decimal::ZERO,
+            uncollected_fees: Decimal::ZERO,
+            steps_since_collection: 0,
             current_step: 0,
         }
     }
 
+    /// Collects fees on a schedule instead of crediting them as earned.
+    #[must_use]
+    pub fn with_fee_collection(mut self, policy: FeeCollection) -> Self {
+        self.fee_collection = Some(policy);
+        self
+    }
+
+    /// Applies scheduled deposits and withdrawals as the simulation runs.
+    #[must_use]
+    pub fn with_cash_flows(mut self, schedule: CashFlowSchedule) -> Self {
+        self.cash_flows = Some(schedule);
+        self
+    }
+
+    /// Reports the Sharpe ratio under `config`, with steps of
+    /// `step_seconds` to annualize it.
+    #[must_use]
+    pub fn with_sharpe(mut self, config: SharpeConfig, step_seconds: u64) -> Self {
+        self.sharpe = Some((config, step_seconds.max(1)));
+        self
+    }
+
+    /// Stamps snapshots with the calendar time of their step on `clock`,
+    /// the first recorded step being step 0.
+    #[must_use]
+    pub fn with_clock(mut self, clock: SimulationClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Returns the Sharpe configuration and step duration, if set.
+    #[must_use]
+    pub fn sharpe_config(&self) -> Option<(SharpeConfig, u64)> {
+        self.sharpe
+    }
+
+    /// Returns the applied cash flows as `(step, amount)` from the
+    /// investor's side: deposits negative, withdrawals positive.
+    #[must_use]
+    pub fn cash_flow_ledger(&self) -> &[(u64, Decimal)] {
+        &self.cash_flow_ledger
+    }
+
     /// Records a step in the simulation.
     ///
     /// # Arguments
     ///
     /// * `price` - Current price
-    /// * `step_fees` - Fees earned this step
+    /// * `step_fees` - Fees earned this step by the initial capital; scaled
+    ///   up as fees are compounded into liquidity
     /// * `strategy` - Optional strategy to evaluate for rebalancing
     ///
     /// # Returns
@@ -106,7 +204,7 @@ impl PositionTracker {
     ) -> Option<RebalanceAction> {
         self.current_step += 1;
         self.steps_since_rebalance += 1;
-        self.cumulative_fees += step_fees;
+        self.accrue_fees(step_fees);
 
         // Calculate current IL
         let il_pct = calculate_il_concentrated(
@@ -116,22 +214,25 @@ impl PositionTracker {
             self.current_range.upper_price.value,
         )
         .unwrap_or(Decimal::ZERO);
+        self.apply_cash_flows(price, il_pct);
 
-        // Calculate position value
-        let il_amount = self.initial_capital * il_pct;
+        // Calculate position value; uncollected fees are claimable and count
+        let il_amount = self.deployed_capital * il_pct;
         let position_value =
-            self.initial_capital + il_amount + self.cumulative_fees - self.total_rebalance_cost;
-        let net_pnl = position_value - self.initial_capital;
+            self.deployed_capital + il_amount + self.collected_fees + self.uncollected_fees
+                - self.total_rebalance_cost
+                - self.total_collection_cost;
+        let net_pnl =
+            position_value + self.total_withdrawn - self.total_deposited - self.initial_capital;
 
         // Check if in range
-        let in_range = price.value >= self.current_range.lower_price.value
-            && price.value <= self.current_range.upper_price.value;
+        let in_range = self.current_range.contains(price);
 
         // Evaluate strategy if provided
         let action = strategy.map(|s| {
             let context = StrategyContext {
                 current_price: price,
-                current_range: self.current_range.clone(),
+                current_range: self.current_range,
                 entry_price: self.entry_price,
                 steps_since_open: self.current_step,
                 steps_since_rebalance: self.steps_since_rebalance,
@@ -145,7 +246,7 @@ impl PositionTracker {
         let final_action = if let Some(ref act) = action {
             match act {
                 RebalanceAction::Rebalance { new_range, .. } => {
-                    self.execute_rebalance(new_range.clone());
+                    self.execute_rebalance(*new_range);
                     action.clone()
                 }
                 RebalanceAction::Close { .. } => action.clone(),
@@ -158,10 +259,12 @@ impl PositionTracker {
         // Record snapshot
         let snapshot = PositionSnapshot {
             step: self.current_step,
+            timestamp: self.clock.timestamp_at(self.current_step - 1),
             price,
-            range: self.current_range.clone(),
+            range: self.current_range,
             in_range,
             cumulative_fees: self.cumulative_fees,
+            uncollected_fees: self.uncollected_fees,
             il_pct,
             position_value_usd: position_value,
             net_pnl,
@@ -172,14 +275,137 @@ impl PositionTracker {
         final_action
     }
 
+    /// Adds a step's fees, collecting them if a collection is due.
+    fn accrue_fees(&mut self, step_fees: Decimal) {
+        let step_fees = if self.initial_capital.is_zero() {
+            step_fees
+        } else {
+            step_fees * self.deployed_capital / self.initial_capital
+        };
+        self.cumulative_fees += step_fees;
+
+        let Some(policy) = &self.fee_collection else {
+            self.collected_fees += step_fees;
+            return;
+        };
+        self.uncollected_fees += step_fees;
+        self.steps_since_collection += 1;
+        if policy.is_due(self.steps_since_collection) && !self.uncollected_fees.is_zero() {
+            self.collection_count += 1;
+            self.total_collection_cost += policy.tx_cost;
+            self.settle_fees();
+        }
+    }
+
+    /// Applies the cash flows due this step.
+    ///
+    /// Deposits and withdrawals change the deployed capital, so later fees
+    /// scale with it; fee withdrawals take the fees collected as cash.
+    /// Capital is moved at its current value after `il_pct`, so the
+    /// position's value changes by exactly the amount.
+    fn apply_cash_flows(&mut self, price: Price, il_pct: Decimal) {
+        let Some(schedule) = &self.cash_flows else {
+            return;
+        };
+        let due: Vec<CashFlowKind> = schedule.due_at(self.current_step).cloned().collect();
+        let value_per_capital = Decimal::ONE + il_pct;
+        for kind in due {
+            let amount = match kind {
+                CashFlowKind::Deposit(amount) if value_per_capital > Decimal::ZERO => {
+                    self.deployed_capital += amount / value_per_capital;
+                    self.total_deposited += amount;
+                    self.deposits.push((amount, price));
+                    -amount
+                }
+                CashFlowKind::Withdraw(amount) if value_per_capital > Decimal::ZERO => {
+                    let deployed_value = self.deployed_capital * value_per_capital;
+                    let amount = amount.min(deployed_value).max(Decimal::ZERO);
+                    self.deployed_capital -= amount / value_per_capital;
+                    amount
+                }
+                CashFlowKind::Deposit(_) | CashFlowKind::Withdraw(_) => Decimal::ZERO,
+                CashFlowKind::WithdrawFees => std::mem::take(&mut self.collected_fees),
+            };
+            if amount.is_zero() {
+                continue;
+            }
+            if amount > Decimal::ZERO {
+                self.total_withdrawn += amount;
+            }
+            self.cash_flow_ledger.push((self.current_step, amount));
+        }
+    }
+
+    /// Moves uncollected fees to cash, or into liquidity when compounding.
+    fn settle_fees(&mut self) {
+        let fees = std::mem::take(&mut self.uncollected_fees);
+        self.steps_since_collection = 0;
+        if self.fee_collection.as_ref().is_some_and(|p| p.compound) {
+            self.deployed_capital += fees;
+            self.compounded_fees += fees;
+        } else {
+            self.collected_fees += fees;
+        }
+    }
+
     /// Executes a rebalance to a new range.
+    ///
+    /// Withdrawing the old position collects its fees at no extra cost.
     fn execute_rebalance(&mut self, new_range: PriceRange) {
+        self.settle_fees();
         self.current_range = new_range;
         self.steps_since_rebalance = 0;
         self.rebalance_count += 1;
         self.total_rebalance_cost += self.rebalance_cost;
     }
 
+    /// Annualized money-weighted return of the initial capital, the applied
+    /// cash flows and `final_value`; `None` without a cash-flow schedule.
+    fn money_weighted_return(&self, final_value: Decimal) -> Option<Decimal> {
+        let schedule = self.cash_flows.as_ref()?;
+        let mut flows = Vec::with_capacity(self.cash_flow_ledger.len() + 2);
+        flows.push((0, -self.initial_capital));
+        flows.extend_from_slice(&self.cash_flow_ledger);
+        flows.push((self.current_step, final_value));
+        money_weighted_return(&flows, schedule.step_seconds)
+    }
+
+    /// Value of the initial capital in the configured benchmark at `price`,
+    /// starting from the entry range.
+    pub(crate) fn benchmark_value(&self, config: &SharpeConfig, price: Price) -> Decimal {
+        let entry_range = self.snapshots.first().map(|s| &s.range);
+        config.benchmark.value(
+            self.initial_capital,
+            self.entry_price.value,
+            price.value,
+            entry_range,
+        )
+    }
+
+    fn sharpe_ratio(&self) -> Option<Decimal> {
+        let (config, step_seconds) = self.sharpe.as_ref()?;
+        // Cash flows are left out: value is the initial capital plus PnL
+        let values: Vec<Decimal> = std::iter::once(self.initial_capital)
+            .chain(
+                self.snapshots
+                    .iter()
+                    .map(|s| self.initial_capital + s.net_pnl),
+            )
+            .collect();
+        let benchmark: Vec<Decimal> = std::iter::once(self.initial_capital)
+            .chain(
+                self.snapshots
+                    .iter()
+                    .map(|s| self.benchmark_value(config, s.price)),
+            )
+            .collect();
+        config.sharpe_ratio(
+            &values,
+            &benchmark,
+            SECONDS_PER_YEAR as f64 / *step_seconds as f64,
+        )
+    }
+
     /// Returns summary statistics for the tracked position.
     #[must_use]
     pub fn summary(&self) -> TrackerSummary {
@@ -199,14 +425,20 @@ impl PositionTracker {
         let final_pnl = final_snapshot.map(|s| s.net_pnl).unwrap_or(Decimal::ZERO);
         let final_il = final_snapshot.map(|s| s.il_pct).unwrap_or(Decimal::ZERO);
 
-        // Calculate max drawdown
+        // Calculate max drawdown on the initial capital plus PnL, so deposits
+        // and withdrawals don't read as gains or losses
         let mut peak = self.initial_capital;
         let mut max_drawdown = Decimal::ZERO;
         for snapshot in &self.snapshots {
-            if snapshot.position_value_usd > peak {
-                peak = snapshot.position_value_usd;
+            let value = self.initial_capital + snapshot.net_pnl;
+            if value > peak {
+                peak = value;
             }
-            let drawdown = (peak - snapshot.position_value_usd) / peak;
+            let drawdown = if peak.is_zero() {
+                Decimal::ZERO
+            } else {
+                (peak - value) / peak
+            };
             if drawdown > max_drawdown {
                 max_drawdown = drawdown;
             }
@@ -215,14 +447,24 @@ impl PositionTracker {
         // Calculate HODL comparison
         let hodl_value = if let Some(final_snap) = final_snapshot {
             // Simple HODL: assume 50/50 split at entry, track price change
-            let price_ratio = final_snap.price.value / self.entry_price.value;
             // HODL value = initial * (1 + price_change) / 2 + initial / 2
-            // Simplified: assume quote token is stable
-            self.initial_capital * (Decimal::ONE + price_ratio) / Decimal::from(2)
+            // Simplified: assume quote token is stable. Deposits are held
+            // from the price they were made at.
+            let hodl = |amount: Decimal, price: Price| {
+                let price_ratio = final_snap.price.value / price.value;
+                amount * (Decimal::ONE + price_ratio) / Decimal::from(2)
+            };
+            hodl(self.initial_capital, self.entry_price)
+                + self
+                    .deposits
+                    .iter()
+                    .map(|(amount, price)| hodl(*amount, *price))
+                    .sum::<Decimal>()
         } else {
-            self.initial_capital
+            self.initial_capital + self.total_deposited
         };
-        let vs_hodl = final_value - hodl_value;
+        // Withdrawn cash is part of the LP outcome
+        let vs_hodl = final_value + self.total_withdrawn - hodl_value;
 
         TrackerSummary {
             total_steps,
@@ -233,9 +475,19 @@ impl PositionTracker {
             time_in_range_pct,
             rebalance_count: self.rebalance_count,
             total_rebalance_cost: self.total_rebalance_cost,
+            fee_collections: self.collection_count,
+            total_collection_cost: self.total_collection_cost,
+            compounded_fees: self.compounded_fees,
+            total_deposited: self.total_deposited,
+            total_withdrawn: self.total_withdrawn,
+            money_weighted_return: self.money_weighted_return(final_value),
+            sharpe_ratio: self.sharpe_ratio(),
+            sharpe_config: self.sharpe.map(|(config, _)| config),
             max_drawdown,
             hodl_value,
             vs_hodl,
+            start_timestamp: self.snapshots.first().and_then(|s| s.timestamp),
+            end_timestamp: final_snapshot.and_then(|s| s.timestamp),
         }
     }
 }
@@ -259,12 +511,33 @@ pub struct TrackerSummary {
     pub rebalance_count: u32,
     /// Total cost of rebalancing.
     pub total_rebalance_cost: Decimal,
+    /// Number of scheduled fee collections.
+    pub fee_collections: u32,
+    /// Total cost of fee collections.
+    pub total_collection_cost: Decimal,
+    /// Fees compounded back into liquidity.
+    pub compounded_fees: Decimal,
+    /// Capital deposited after the initial capital.
+    pub total_deposited: Decimal,
+    /// Capital and fees withdrawn.
+    pub total_withdrawn: Decimal,
+    /// Annualized money-weighted return; set when a cash-flow schedule is
+    /// applied.
+    pub money_weighted_return: Option<Decimal>,
+    /// Annualized Sharpe ratio; set when a Sharpe configuration is applied.
+    pub sharpe_ratio: Option<Decimal>,
+    /// Risk-free rate and benchmark the Sharpe ratio was computed with.
+    pub sharpe_config: Option<SharpeConfig>,
     /// Maximum drawdown percentage.
     pub max_drawdown: Decimal,
-    /// HODL strategy value for comparison.
+    /// HODL strategy value for comparison, holding every deposit.
     pub hodl_value: Decimal,
-    /// Performance vs HODL (positive = outperformed).
+    /// Performance vs HODL, counting withdrawn cash (positive = outperformed).
     pub vs_hodl: Decimal,
+    /// Timestamp of the first step, if the clock has calendar time.
+    pub start_timestamp: Option<u64>,
+    /// Timestamp of the last step, if the clock has calendar time.
+    pub end_timestamp: Option<u64>,
 }
 
 #[cfg(test)]
@@ -294,6 +567,28 @@ mod tests {
         assert_eq!(summary.total_steps, 3);
         assert_eq!(summary.total_fees, dec!(30));
         assert_eq!(summary.rebalance_count, 0);
+        assert_eq!(summary.start_timestamp, None);
+    }
+
+    #[test]
+    fn test_tracker_stamps_snapshots_with_clock() {
+        let mut tracker = PositionTracker::new(
+            dec!(1000),
+            Price::new(dec!(100)),
+            PriceRange::new(Price::new(dec!(90)), Price::new(dec!(110))),
+            dec!(5),
+        )
+        .with_clock(SimulationClock::from_timestamps(vec![3600, 7200, 14400]));
+
+        for price in [dec!(100), dec!(102), dec!(98)] {
+            tracker.record_step::<StaticRange>(Price::new(price), dec!(1), None);
+        }
+
+        assert_eq!(tracker.snapshots[0].timestamp, Some(3600));
+        assert_eq!(tracker.snapshots[2].timestamp, Some(14400));
+        let summary = tracker.summary();
+        assert_eq!(summary.start_timestamp, Some(3600));
+        assert_eq!(summary.end_timestamp, Some(14400));
     }
 
     #[test]
@@ -323,6 +618,90 @@ mod tests {
         assert_eq!(tracker.current_range.upper_price.value, dec!(132)); // 120 + 12
     }
 
+    #[test]
+    fn test_scheduled_fee_collection() {
+        let mut tracker = PositionTracker::new(
+            dec!(1000),
+            Price::new(dec!(100)),
+            PriceRange::new(Price::new(dec!(90)), Price::new(dec!(110))),
+            dec!(5),
+        )
+        .with_fee_collection(FeeCollection::every(2, dec!(1)));
+
+        for _ in 0..3 {
+            tracker.record_step::<StaticRange>(Price::new(dec!(100)), dec!(10), None);
+        }
+
+        assert_eq!(tracker.snapshots[0].uncollected_fees, dec!(10));
+        assert_eq!(tracker.snapshots[1].uncollected_fees, dec!(0));
+        let summary = tracker.summary();
+        assert_eq!(summary.fee_collections, 1);
+        assert_eq!(summary.total_collection_cost, dec!(1));
+        assert_eq!(summary.total_fees, dec!(30));
+        // Uncollected fees still count towards value
+        assert_eq!(summary.final_value, dec!(1029));
+    }
+
+    #[test]
+    fn test_compounding_scales_fees() {
+        let mut tracker = PositionTracker::new(
+            dec!(1000),
+            Price::new(dec!(100)),
+            PriceRange::new(Price::new(dec!(90)), Price::new(dec!(110))),
+            dec!(5),
+        )
+        .with_fee_collection(FeeCollection::every(2, dec!(1)).with_compounding());
+
+        for _ in 0..3 {
+            tracker.record_step::<StaticRange>(Price::new(dec!(100)), dec!(10), None);
+        }
+
+        assert_eq!(tracker.deployed_capital, dec!(1020));
+        let summary = tracker.summary();
+        assert_eq!(summary.compounded_fees, dec!(20));
+        // The third step earns on 1020 of liquidity
+        assert_eq!(summary.total_fees, dec!(30.2));
+        assert_eq!(summary.final_value, dec!(1029.2));
+    }
+
+    #[test]
+    fn test_cash_flows_adjust_pnl_and_fees() {
+        let mut tracker = PositionTracker::new(
+            dec!(1000),
+            Price::new(dec!(100)),
+            PriceRange::new(Price::new(dec!(90)), Price::new(dec!(110))),
+            dec!(5),
+        )
+        .with_cash_flows(
+            // Monthly steps
+            CashFlowSchedule::new(30 * 86_400)
+                .deposit_every(2, dec!(1000))
+                .withdraw_fees_every(3),
+        );
+
+        for _ in 0..4 {
+            tracker.record_step::<StaticRange>(Price::new(dec!(100)), dec!(10), None);
+        }
+
+        assert_eq!(tracker.deployed_capital, dec!(3000));
+        assert_eq!(
+            tracker.cash_flow_ledger(),
+            &[(2, dec!(-1000)), (3, dec!(40)), (4, dec!(-1000))]
+        );
+
+        let summary = tracker.summary();
+        // Fees double after the first deposit
+        assert_eq!(summary.total_fees, dec!(60));
+        assert_eq!(summary.total_deposited, dec!(2000));
+        assert_eq!(summary.total_withdrawn, dec!(40));
+        assert_eq!(summary.final_value, dec!(3020));
+        // Flows are neither gains nor losses
+        assert_eq!(summary.final_pnl, dec!(60));
+        assert_eq!(summary.max_drawdown, Decimal::ZERO);
+        assert_eq!(summary.vs_hodl, dec!(60));
+        assert!(summary.money_weighted_return.unwrap() > Decimal::ZERO);
+    }
+
     #[test]
     fn test_tracker_time_in_range() {
         let mut tracker = PositionTracker::new(
@@ -342,4 +721,40 @@ mod tests {
         assert!(summary.time_in_range_pct > dec!(0.66));
         assert!(summary.time_in_range_pct < dec!(0.67));
     }
+
+    #[test]
+    fn test_sharpe_ratio_against_benchmarks() {
+        use clmm_lp_domain::metrics::sharpe::Benchmark;
+
+        let run = |config: Option<SharpeConfig>| {
+            let mut tracker = PositionTracker::new(
+                dec!(1000),
+                Price::new(dec!(100)),
+                PriceRange::new(Price::new(dec!(90)), Price::new(dec!(110))),
+                dec!(5),
+            );
+            if let Some(config) = config {
+                tracker = tracker.with_sharpe(config, 86_400);
+            }
+            for (price, fees) in [(101, 3), (99, 2), (103, 4), (100, 1), (104, 3)] {
+                tracker.record_step::<StaticRange>(
+                    Price::new(Decimal::from(price)),
+                    Decimal::from(fees),
+                    None,
+                );
+            }
+            tracker.summary()
+        };
+
+        let plain = run(None);
+        assert_eq!(plain.sharpe_ratio, None);
+        assert_eq!(plain.sharpe_config, None);
+
+        let cash = SharpeConfig::new(dec!(0.05), Benchmark::Zero);
+        let vs_cash = run(Some(cash));
+        assert_eq!(vs_cash.sharpe_config, Some(cash));
+        let vs_fifty = run(Some(SharpeConfig::new(dec!(0.05), Benchmark::FiftyFifty)));
+        assert!(vs_cash.sharpe_ratio.is_some());
+        assert_ne!(vs_cash.sharpe_ratio, vs_fifty.sharpe_ratio);
+    }
 }
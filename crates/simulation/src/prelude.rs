@@ -8,17 +8,37 @@
 //! use clmm_lp_simulation::prelude::*;
 //! ```
 
+// Cash flows
+pub use crate::cash_flow::{
+    CashFlowKind, CashFlowSchedule, ScheduledCashFlow, money_weighted_return,
+};
+
 // Engine
 pub use crate::engine::SimulationEngine;
 
 // Events
 pub use crate::event::{EventData, EventLog, SimulationEvent, SimulationEventType};
 
+// Fee models
+pub use crate::fee::{DynamicFee, FeeModel};
+
+// Fee collection
+pub use crate::fee_collection::FeeCollection;
+
+// Grid tracking
+pub use crate::grid_tracker::GridTracker;
+
+// Histories
+pub use crate::history::{CompactHistory, HistoryMode};
+
 // Liquidity models
-pub use crate::liquidity::{ConstantLiquidity, LiquidityModel};
+pub use crate::liquidity::{
+    ConstantLiquidity, DynamicLiquidityModel, LiquidityBucket, LiquidityDistribution,
+    LiquidityModel,
+};
 
 // Monte Carlo
-pub use crate::monte_carlo::{AggregateResult, MonteCarloRunner};
+pub use crate::monte_carlo::{AggregateResult, MonteCarloRunner, PercentilePath};
 
 // Position simulator
 pub use crate::position_simulator::{PositionSimulationResult, simulate_position};
@@ -31,19 +51,30 @@ pub use crate::price_path::{
     DeterministicPricePath, GeometricBrownianMotion, HistoricalPricePath, PricePathGenerator,
 };
 
+// Replay
+pub use crate::replay::{
+    ReplayHistory, ScenarioOutcome, WhatIfReport, WhatIfScenario, WhatIfStrategy, replay_what_if,
+    scale_range,
+};
+
 // State management
 pub use crate::state::{
-    PoolState, PositionState, SimulationConfig, SimulationState, SimulationSummary,
+    PoolState, PositionState, SimulationClock, SimulationConfig, SimulationState, SimulationSummary,
 };
 
 // Strategies
 pub use crate::strategies::{
-    ILLimitStrategy, PeriodicRebalance, RebalanceAction, RebalanceReason, RebalanceStrategy,
-    StaticRange, StrategyContext, ThresholdRebalance,
+    GridStrategy, ILLimitStrategy, PeriodicRebalance, RebalanceAction, RebalanceReason,
+    RebalanceStrategy, ScheduledRanges, StaticRange, StrategyContext, ThresholdRebalance,
 };
 
 // Strategy simulator
 pub use crate::strategy_simulator::{StrategySimulationResult, simulate_with_strategy};
 
+// Parameter sweeps
+pub use crate::sweep::{
+    SweepCandidate, SweepEngine, SweepMode, SweepParams, SweepReport, SweepSeries,
+};
+
 // Volume models
-pub use crate::volume::{ConstantVolume, VolumeModel};
+pub use crate::volume::{ConstantVolume, HistoricalVolume, SeasonalVolumeModel, VolumeModel};
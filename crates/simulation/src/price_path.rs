@@ -1,3 +1,4 @@
+use clmm_lp_domain::math::float::decimal_from_f64;
 use clmm_lp_domain::value_objects::price::Price;
 use rand_distr::{Distribution, Normal};
 use rust_decimal::Decimal;
@@ -7,6 +8,15 @@ use rust_decimal::prelude::*;
 pub trait PricePathGenerator {
     /// Generates a price path with the specified number of steps.
     fn generate(&mut self, steps: usize) -> Vec<Price>;
+
+    /// Generates a price path into `prices`, replacing its contents.
+    ///
+    /// Callers simulating many paths can reuse one buffer instead of
+    /// allocating a path each time.
+    fn generate_into(&mut self, steps: usize, prices: &mut Vec<Price>) {
+        prices.clear();
+        prices.extend(self.generate(steps));
+    }
 }
 
 /// Geometric Brownian Motion price path generator.
@@ -36,6 +46,13 @@ impl GeometricBrownianMotion {
 impl PricePathGenerator for GeometricBrownianMotion {
     fn generate(&mut self, steps: usize) -> Vec<Price> {
         let mut prices = Vec::with_capacity(steps + 1);
+        self.generate_into(steps, &mut prices);
+        prices
+    }
+
+    fn generate_into(&mut self, steps: usize, prices: &mut Vec<Price>) {
+        prices.clear();
+        prices.reserve(steps + 1);
         prices.push(Price::new(self.initial_price));
 
         let mut rng = rand::rng();
@@ -56,11 +73,9 @@ impl PricePathGenerator for GeometricBrownianMotion {
             // Note: Standard f64 precision might drift from Decimal over simulated time,
             // but for Monte Carlo high performance, f64 is standard.
             // We cast back to Decimal for the domain object.
-            let p = Decimal::from_f64(current_price).unwrap_or(Decimal::ZERO);
+            let p = decimal_from_f64(current_price).unwrap_or(Decimal::ZERO);
             prices.push(Price::new(p));
         }
-
-        prices
     }
 }
 
@@ -90,6 +105,11 @@ impl PricePathGenerator for DeterministicPricePath {
     fn generate(&mut self, _steps: usize) -> Vec<Price> {
         self.prices.clone()
     }
+
+    fn generate_into(&mut self, _steps: usize, prices: &mut Vec<Price>) {
+        prices.clear();
+        prices.extend_from_slice(&self.prices);
+    }
 }
 
 /// Historical price path generator that uses a provided list of historical prices.
@@ -115,6 +135,11 @@ impl PricePathGenerator for HistoricalPricePath {
         // For now, return full path.
         self.prices.clone()
     }
+
+    fn generate_into(&mut self, _steps: usize, prices: &mut Vec<Price>) {
+        prices.clear();
+        prices.extend_from_slice(&self.prices);
+    }
 }
 
 #[cfg(test)]
@@ -137,5 +162,11 @@ mod tests {
         // Check that prices are not all same (unless vol is 0)
         let all_same = path.iter().all(|p| p.value == initial);
         assert!(!all_same);
+
+        // Refilling a buffer replaces its previous path
+        let mut buffer = path;
+        gbm.generate_into(5, &mut buffer);
+        assert_eq!(buffer.len(), 6);
+        assert_eq!(buffer[0].value, initial);
     }
 }
@@ -0,0 +1,298 @@
+//! Counterfactual replay of a position's history.
+//!
+//! Replays the market a live position went through under its actual range
+//! changes and under alternative scenarios, e.g. "what if the range had been
+//! twice as wide?", so the outcomes can be compared on the same prices.
+
+use crate::liquidity::LiquidityModel;
+use crate::price_path::DeterministicPricePath;
+use crate::state::{SimulationConfig, SimulationSummary};
+use crate::strategies::{PeriodicRebalance, ScheduledRanges, StaticRange, ThresholdRebalance};
+use crate::strategy_simulator::simulate_with_strategy;
+use crate::volume::VolumeModel;
+use clmm_lp_domain::value_objects::price::Price;
+use clmm_lp_domain::value_objects::price_range::PriceRange;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+
+/// Ranges a position actually held.
+#[derive(Debug, Clone)]
+pub struct ReplayHistory {
+    /// Range the position was opened with.
+    pub initial_range: PriceRange,
+    /// Rebalances as (step, new range), in step order.
+    pub range_changes: Vec<(u64, PriceRange)>,
+}
+
+/// How an alternative scenario manages its range.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WhatIfStrategy {
+    /// Rebalance at the same steps as the position did, to scaled ranges.
+    Actual,
+    /// Never rebalance.
+    Static,
+    /// Rebalance every `interval` steps.
+    Periodic {
+        /// Steps between rebalances.
+        interval: u64,
+    },
+    /// Rebalance when price moves `threshold_pct` from the range center.
+    Threshold {
+        /// Price move that triggers a rebalance (0.05 = 5%).
+        threshold_pct: Decimal,
+    },
+}
+
+/// An alternative way the position could have been managed.
+#[derive(Debug, Clone)]
+pub struct WhatIfScenario {
+    /// Name shown in the report.
+    pub label: String,
+    /// Factor applied to the width of every range (2 = twice as wide).
+    pub width_multiplier: Decimal,
+    /// Range management strategy.
+    pub strategy: WhatIfStrategy,
+}
+
+impl WhatIfScenario {
+    /// Creates a scenario replaying the actual rebalances with scaled ranges.
+    #[must_use]
+    pub fn new(label: impl Into<String>, width_multiplier: Decimal) -> Self {
+        Self {
+            label: label.into(),
+            width_multiplier,
+            strategy: WhatIfStrategy::Actual,
+        }
+    }
+
+    /// Sets the range management strategy.
+    #[must_use]
+    pub fn with_strategy(mut self, strategy: WhatIfStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+}
+
+/// Outcome of one scenario compared to the actual history.
+#[derive(Debug, Clone)]
+pub struct ScenarioOutcome {
+    /// Scenario name.
+    pub label: String,
+    /// Simulation summary.
+    pub summary: SimulationSummary,
+    /// Net PnL minus the actual net PnL.
+    pub pnl_vs_actual: Decimal,
+    /// Fees minus the actual fees.
+    pub fees_vs_actual: Decimal,
+}
+
+/// Actual and counterfactual outcomes over the same market data.
+#[derive(Debug, Clone)]
+pub struct WhatIfReport {
+    /// Replay of the ranges the position actually held.
+    pub actual: SimulationSummary,
+    /// Alternative scenarios, in request order.
+    pub scenarios: Vec<ScenarioOutcome>,
+}
+
+/// Scales the width of a range around its geometric center.
+///
+/// Scaling in log space keeps the lower bound positive for any multiplier:
+/// doubling a range of ±10% gives roughly ±21%. Non-positive multipliers
+/// leave the range unchanged.
+#[must_use]
+pub fn scale_range(range: &PriceRange, multiplier: Decimal) -> PriceRange {
+    if multiplier == Decimal::ONE {
+        return *range;
+    }
+    let (Some(lower), Some(upper), Some(m)) = (
+        range.lower_price.value.to_f64(),
+        range.upper_price.value.to_f64(),
+        multiplier.to_f64(),
+    ) else {
+        return *range;
+    };
+    if m <= 0.0 || lower <= 0.0 || upper <= 0.0 {
+        return *range;
+    }
+
+    let center = (lower * upper).sqrt();
+    let scaled_lower = Decimal::from_f64(center * (lower / center).powf(m));
+    let scaled_upper = Decimal::from_f64(center * (upper / center).powf(m));
+    match (scaled_lower, scaled_upper) {
+        (Some(lower), Some(upper)) => PriceRange::new(Price::new(lower), Price::new(upper)),
+        _ => *range,
+    }
+}
+
+/// Replays the actual history and each scenario over `prices`.
+///
+/// `config` supplies capital, fee rate, position liquidity and rebalance
+/// cost; its range and step count are taken from the history and prices.
+/// Each run starts from a fresh copy of `volume`.
+pub fn replay_what_if<V, L>(
+    config: &SimulationConfig,
+    prices: &[Price],
+    history: &ReplayHistory,
+    volume: &V,
+    liquidity: &L,
+    scenarios: &[WhatIfScenario],
+) -> WhatIfReport
+where
+    V: VolumeModel + Clone,
+    L: LiquidityModel,
+{
+    let run = |range: PriceRange, strategy: &WhatIfStrategy, multiplier: Decimal| {
+        let config = config
+            .clone()
+            .with_steps(prices.len())
+            .with_initial_range(range);
+        let mut price_path = DeterministicPricePath::from_prices(prices.to_vec());
+        let mut volume = volume.clone();
+        let width_pct = range.width_pct();
+
+        let result = match strategy {
+            WhatIfStrategy::Actual => {
+                let changes = history
+                    .range_changes
+                    .iter()
+                    .map(|(step, range)| (*step, scale_range(range, multiplier)));
+                simulate_with_strategy(
+                    &config,
+                    &mut price_path,
+                    &mut volume,
+                    liquidity,
+                    &ScheduledRanges::new(changes),
+                )
+            }
+            WhatIfStrategy::Static => simulate_with_strategy(
+                &config,
+                &mut price_path,
+                &mut volume,
+                liquidity,
+                &StaticRange::new(),
+            ),
+            WhatIfStrategy::Periodic { interval } => simulate_with_strategy(
+                &config,
+                &mut price_path,
+                &mut volume,
+                liquidity,
+                &PeriodicRebalance::new(*interval, width_pct),
+            ),
+            WhatIfStrategy::Threshold { threshold_pct } => simulate_with_strategy(
+                &config,
+                &mut price_path,
+                &mut volume,
+                liquidity,
+                &ThresholdRebalance::new(*threshold_pct, width_pct),
+            ),
+        };
+        result.summary
+    };
+
+    let actual = run(history.initial_range, &WhatIfStrategy::Actual, Decimal::ONE);
+    let scenarios = scenarios
+        .iter()
+        .map(|scenario| {
+            let range = scale_range(&history.initial_range, scenario.width_multiplier);
+            let summary = run(range, &scenario.strategy, scenario.width_multiplier);
+            ScenarioOutcome {
+                label: scenario.label.clone(),
+                pnl_vs_actual: summary.net_pnl - actual.net_pnl,
+                fees_vs_actual: summary.total_fees - actual.total_fees,
+                summary,
+            }
+        })
+        .collect();
+
+    WhatIfReport { actual, scenarios }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::liquidity::ConstantLiquidity;
+    use crate::volume::ConstantVolume;
+    use rust_decimal_macros::dec;
+
+    fn range(lower: Decimal, upper: Decimal) -> PriceRange {
+        PriceRange::new(Price::new(lower), Price::new(upper))
+    }
+
+    #[test]
+    fn test_scale_range() {
+        let scaled = scale_range(&range(dec!(90), dec!(110)), dec!(2));
+        let center = (dec!(90) * dec!(110)).to_f64().unwrap().sqrt();
+        let lower = scaled.lower_price.value.to_f64().unwrap();
+        let upper = scaled.upper_price.value.to_f64().unwrap();
+        assert!(((lower * upper).sqrt() - center).abs() < 1e-9);
+        assert!(lower < 90.0 && lower > 0.0);
+        assert!(upper > 110.0);
+
+        let unchanged = scale_range(&range(dec!(90), dec!(110)), dec!(-1));
+        assert_eq!(unchanged, range(dec!(90), dec!(110)));
+    }
+
+    #[test]
+    fn test_wider_range_stays_in_range_longer() {
+        // Price trends up and out of the actual range
+        let prices: Vec<Price> = (0..20)
+            .map(|i| Price::new(dec!(100) + Decimal::from(i)))
+            .collect();
+        let history = ReplayHistory {
+            initial_range: range(dec!(95), dec!(105)),
+            range_changes: Vec::new(),
+        };
+        let config = SimulationConfig::new(dec!(1000), history.initial_range)
+            .with_pool_liquidity(1_000)
+            .with_rebalance_cost(dec!(1));
+        let scenarios = vec![
+            WhatIfScenario::new("2x wider", dec!(2)),
+            WhatIfScenario::new("periodic", dec!(1))
+                .with_strategy(WhatIfStrategy::Periodic { interval: 5 }),
+        ];
+
+        let report = replay_what_if(
+            &config,
+            &prices,
+            &history,
+            &ConstantVolume::new(dec!(10000)),
+            &ConstantLiquidity::new(100_000),
+            &scenarios,
+        );
+
+        assert_eq!(report.actual.rebalance_count, 0);
+        let wider = &report.scenarios[0];
+        assert_eq!(wider.label, "2x wider");
+        assert!(wider.summary.steps_in_range > report.actual.steps_in_range);
+        assert!(wider.fees_vs_actual > Decimal::ZERO);
+        assert!(report.scenarios[1].summary.rebalance_count > 0);
+    }
+
+    #[test]
+    fn test_actual_replays_recorded_rebalances() {
+        let prices = vec![Price::new(dec!(100)); 10];
+        let history = ReplayHistory {
+            initial_range: range(dec!(95), dec!(105)),
+            range_changes: vec![
+                (3, range(dec!(96), dec!(104))),
+                (7, range(dec!(97), dec!(103))),
+            ],
+        };
+        let config = SimulationConfig::new(dec!(1000), history.initial_range);
+        let scenarios = vec![WhatIfScenario::new("same", dec!(1))];
+
+        let report = replay_what_if(
+            &config,
+            &prices,
+            &history,
+            &ConstantVolume::new(dec!(10000)),
+            &ConstantLiquidity::new(1_000_000),
+            &scenarios,
+        );
+
+        assert_eq!(report.actual.rebalance_count, 2);
+        assert_eq!(report.actual.total_steps, 10);
+        assert_eq!(report.scenarios[0].pnl_vs_actual, Decimal::ZERO);
+    }
+}
@@ -3,9 +3,12 @@
 //! This module provides structures for capturing and managing the state
 //! of a simulation at any point in time.
 
+use crate::fee::FeeModel;
+use crate::history::HistoryMode;
 use clmm_lp_domain::value_objects::price::Price;
 use clmm_lp_domain::value_objects::price_range::PriceRange;
 use rust_decimal::Decimal;
+use std::sync::Arc;
 
 /// Current state of a simulated pool.
 #[derive(Debug, Clone)]
@@ -97,7 +100,7 @@ impl PositionState {
     /// Checks if price is within the position range.
     #[must_use]
     pub fn is_price_in_range(&self, price: &Price) -> bool {
-        price.value >= self.range.lower_price.value && price.value <= self.range.upper_price.value
+        self.range.contains(*price)
     }
 }
 
@@ -134,6 +137,68 @@ impl SimulationState {
     }
 }
 
+/// Calendar time of simulation steps.
+///
+/// Steps are indices into the price path; a clock maps them to Unix
+/// timestamps so results can be plotted on calendar time.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum SimulationClock {
+    /// Steps have no calendar time, e.g. on synthetic price paths.
+    #[default]
+    Steps,
+    /// Steps start at `start` and are `step_seconds` apart.
+    Regular {
+        /// Timestamp of step 0 in seconds.
+        start: u64,
+        /// Seconds between steps.
+        step_seconds: u64,
+    },
+    /// Timestamp of each step, e.g. the start of each candle.
+    Timestamps(Arc<[u64]>),
+}
+
+impl SimulationClock {
+    /// Creates a clock from the timestamp of each step.
+    #[must_use]
+    pub fn from_timestamps(timestamps: impl Into<Arc<[u64]>>) -> Self {
+        Self::Timestamps(timestamps.into())
+    }
+
+    /// Returns the timestamp of `step`, if the clock has calendar time.
+    ///
+    /// Steps past the last timestamp continue at the last interval, so the
+    /// close after the final step falls after it.
+    #[must_use]
+    pub fn timestamp_at(&self, step: u64) -> Option<u64> {
+        match self {
+            Self::Steps => None,
+            Self::Regular {
+                start,
+                step_seconds,
+            } => Some(start.saturating_add(step.saturating_mul(*step_seconds))),
+            Self::Timestamps(timestamps) => {
+                let index = usize::try_from(step).unwrap_or(usize::MAX);
+                if let Some(timestamp) = timestamps.get(index) {
+                    return Some(*timestamp);
+                }
+                let (&last, rest) = timestamps.split_last()?;
+                let interval = rest.last().map_or(0, |prev| last.saturating_sub(*prev));
+                let beyond = step - (timestamps.len() as u64 - 1);
+                Some(last.saturating_add(beyond.saturating_mul(interval)))
+            }
+        }
+    }
+
+    /// Returns the timestamps of the first `steps` steps, or an empty list
+    /// without calendar time.
+    #[must_use]
+    pub fn timestamps(&self, steps: usize) -> Vec<u64> {
+        (0..steps as u64)
+            .map_while(|step| self.timestamp_at(step))
+            .collect()
+    }
+}
+
 /// Configuration for a simulation run.
 #[derive(Debug, Clone)]
 pub struct SimulationConfig {
@@ -141,8 +206,10 @@ pub struct SimulationConfig {
     pub initial_capital: Decimal,
     /// Initial price range.
     pub initial_range: PriceRange,
-    /// Fee rate as decimal.
+    /// Fee rate as decimal; the base rate of `fee_model`.
     pub fee_rate: Decimal,
+    /// Fee rate charged at each step.
+    pub fee_model: FeeModel,
     /// Pool liquidity.
     pub pool_liquidity: u128,
     /// Cost per rebalance transaction.
@@ -151,6 +218,10 @@ pub struct SimulationConfig {
     pub steps: usize,
     /// Step duration in seconds (for time-based calculations).
     pub step_duration_seconds: u64,
+    /// How much per-step history the simulation keeps.
+    pub history: HistoryMode,
+    /// Calendar time of the steps.
+    pub clock: SimulationClock,
 }
 
 impl SimulationConfig {
@@ -161,17 +232,36 @@ impl SimulationConfig {
             initial_capital,
             initial_range,
             fee_rate: Decimal::new(3, 3), // 0.3%
+            fee_model: FeeModel::default(),
             pool_liquidity: 1_000_000,
             rebalance_cost: Decimal::ONE,
             steps: 100,
             step_duration_seconds: 3600, // 1 hour
+            history: HistoryMode::Full,
+            clock: SimulationClock::Steps,
         }
     }
 
-    /// Sets the fee rate.
+    /// Sets the initial price range.
+    #[must_use]
+    pub fn with_initial_range(mut self, range: PriceRange) -> Self {
+        self.initial_range = range;
+        self
+    }
+
+    /// Sets a flat fee rate.
     #[must_use]
     pub fn with_fee_rate(mut self, fee_rate: Decimal) -> Self {
         self.fee_rate = fee_rate;
+        self.fee_model = FeeModel::Constant(fee_rate);
+        self
+    }
+
+    /// Sets the fee model, e.g. a volatility-linked dynamic fee.
+    #[must_use]
+    pub fn with_fee_model(mut self, fee_model: FeeModel) -> Self {
+        self.fee_rate = fee_model.base_rate();
+        self.fee_model = fee_model;
         self
     }
 
@@ -203,6 +293,20 @@ impl SimulationConfig {
         self
     }
 
+    /// Sets how much per-step history to keep.
+    #[must_use]
+    pub fn with_history(mut self, mode: HistoryMode) -> Self {
+        self.history = mode;
+        self
+    }
+
+    /// Sets the calendar time of the steps, e.g. from candle timestamps.
+    #[must_use]
+    pub fn with_clock(mut self, clock: SimulationClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Returns total simulation duration in seconds.
     #[must_use]
     pub fn total_duration_seconds(&self) -> u64 {
@@ -251,6 +355,10 @@ pub struct SimulationSummary {
     pub hodl_value: Decimal,
     /// Performance vs HODL.
     pub vs_hodl: Decimal,
+    /// Timestamp of the first step, if the clock has calendar time.
+    pub start_timestamp: Option<u64>,
+    /// Timestamp of the last step, if the clock has calendar time.
+    pub end_timestamp: Option<u64>,
 }
 
 impl SimulationSummary {
@@ -263,10 +371,20 @@ impl SimulationSummary {
         Decimal::from(self.steps_in_range) / Decimal::from(self.total_steps)
     }
 
+    /// Returns the duration covered in days, from the calendar time of the
+    /// steps if known and the configured step duration otherwise.
+    #[must_use]
+    pub fn duration_days(&self) -> f64 {
+        match (self.start_timestamp, self.end_timestamp) {
+            (Some(start), Some(end)) if end > start => (end - start) as f64 / 86400.0,
+            _ => self.config.total_duration_days(),
+        }
+    }
+
     /// Returns the annualized return.
     #[must_use]
     pub fn annualized_return(&self) -> Decimal {
-        let days = self.config.total_duration_days();
+        let days = self.duration_days();
         if days <= 0.0 || self.config.initial_capital.is_zero() {
             return Decimal::ZERO;
         }
@@ -337,8 +455,32 @@ mod tests {
             max_drawdown_pct: dec!(-0.03),
             hodl_value: dec!(1025),
             vs_hodl: dec!(25),
+            start_timestamp: None,
+            end_timestamp: None,
         };
 
         assert_eq!(summary.time_in_range_pct(), dec!(0.8));
     }
+
+    #[test]
+    fn test_clock_maps_steps_to_calendar_time() {
+        assert_eq!(SimulationClock::Steps.timestamp_at(3), None);
+        assert!(SimulationClock::Steps.timestamps(3).is_empty());
+
+        let regular = SimulationClock::Regular {
+            start: 1_700_000_000,
+            step_seconds: 3600,
+        };
+        assert_eq!(regular.timestamp_at(2), Some(1_700_007_200));
+
+        // Candles with a gap keep their own timestamps
+        let candles = SimulationClock::from_timestamps(vec![100, 160, 400]);
+        assert_eq!(candles.timestamps(3), vec![100, 160, 400]);
+        // The close after the last step continues at the last interval
+        assert_eq!(candles.timestamp_at(3), Some(640));
+        assert_eq!(
+            SimulationClock::from_timestamps(Vec::new()).timestamp_at(0),
+            None
+        );
+    }
 }
@@ -0,0 +1,145 @@
+//! Grid (ladder) strategy.
+//!
+//! This strategy splits the position into adjacent sub-ranges of equal
+//! width. Each sub-range is evaluated on its own: once price drifts more than
+//! half a grid away from it, it is moved across the grid to the side price
+//! moved to, so the ladder follows price one level at a time.
+
+use super::{RebalanceAction, RebalanceReason, RebalanceStrategy, StrategyContext};
+use clmm_lp_domain::value_objects::price::Price;
+use clmm_lp_domain::value_objects::price_range::PriceRange;
+use rust_decimal::Decimal;
+
+/// Grid strategy with independently rebalanced sub-ranges.
+///
+/// Each sub-range is tracked as its own position; the strategy is evaluated
+/// once per sub-range with that sub-range as the current range.
+#[derive(Debug, Clone)]
+pub struct GridStrategy {
+    /// Number of sub-ranges.
+    pub levels: usize,
+    /// Width of each sub-range as a percentage of the center price (e.g., 0.04 for 4%).
+    pub level_width_pct: Decimal,
+}
+
+impl GridStrategy {
+    /// Creates a new grid strategy.
+    ///
+    /// # Arguments
+    ///
+    /// * `levels` - Number of sub-ranges (at least one)
+    /// * `level_width_pct` - Width of each sub-range as percentage of the center price
+    #[must_use]
+    pub fn new(levels: usize, level_width_pct: Decimal) -> Self {
+        Self {
+            levels: levels.max(1),
+            level_width_pct,
+        }
+    }
+
+    /// Returns the sub-ranges of a grid centered on `center`, lowest first.
+    ///
+    /// The grid spans `levels * level_width_pct` of the center price in
+    /// total.
+    #[must_use]
+    pub fn initial_ranges(&self, center: Price) -> Vec<PriceRange> {
+        let width = center.value * self.level_width_pct;
+        let lower = center.value - width * Decimal::from(self.levels) / Decimal::from(2);
+        (0..self.levels)
+            .map(|i| {
+                let start = lower + width * Decimal::from(i);
+                PriceRange::new(Price::new(start), Price::new(start + width))
+            })
+            .collect()
+    }
+}
+
+impl RebalanceStrategy for GridStrategy {
+    fn evaluate(&self, context: &StrategyContext) -> RebalanceAction {
+        let range = &context.current_range;
+        let span = range.width() * Decimal::from(self.levels);
+        if span.is_zero() {
+            return RebalanceAction::Hold;
+        }
+
+        let offset = context.current_price.value - range.midpoint().value;
+        if offset.abs() <= span / Decimal::from(2) {
+            return RebalanceAction::Hold;
+        }
+
+        // Move by whole grid spans so the sub-range stays on the ladder
+        let new_range = range.shift_by(span * (offset / span).round());
+        if new_range.lower_price.value <= Decimal::ZERO {
+            return RebalanceAction::Hold;
+        }
+
+        RebalanceAction::Rebalance {
+            new_range,
+            reason: RebalanceReason::OutOfRange {
+                current_price: context.current_price.value,
+            },
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "Grid"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn create_context(range: &PriceRange, current_price: Decimal) -> StrategyContext {
+        StrategyContext {
+            current_price: Price::new(current_price),
+            current_range: *range,
+            entry_price: Price::new(dec!(100)),
+            steps_since_open: 10,
+            steps_since_rebalance: 10,
+            current_il_pct: dec!(0),
+            total_fees_earned: dec!(0),
+        }
+    }
+
+    #[test]
+    fn test_initial_ranges_are_adjacent() {
+        let strategy = GridStrategy::new(4, dec!(0.05));
+        let ranges = strategy.initial_ranges(Price::new(dec!(100)));
+
+        assert_eq!(ranges.len(), 4);
+        assert_eq!(ranges[0].lower_price.value, dec!(90));
+        assert_eq!(ranges[1].lower_price.value, dec!(95));
+        assert_eq!(ranges[3].upper_price.value, dec!(110));
+    }
+
+    #[test]
+    fn test_levels_roll_independently() {
+        let strategy = GridStrategy::new(4, dec!(0.05));
+        let ranges = strategy.initial_ranges(Price::new(dec!(100)));
+
+        // Price at 112: the two lowest levels are more than half a grid away
+        match strategy.evaluate(&create_context(&ranges[0], dec!(112))) {
+            RebalanceAction::Rebalance { new_range, .. } => {
+                assert_eq!(new_range.lower_price.value, dec!(110));
+                assert_eq!(new_range.upper_price.value, dec!(115));
+            }
+            other => panic!("Expected Rebalance action, got {other:?}"),
+        }
+        match strategy.evaluate(&create_context(&ranges[1], dec!(112))) {
+            RebalanceAction::Rebalance { new_range, .. } => {
+                assert_eq!(new_range.lower_price.value, dec!(115));
+            }
+            other => panic!("Expected Rebalance action, got {other:?}"),
+        }
+        assert_eq!(
+            strategy.evaluate(&create_context(&ranges[2], dec!(112))),
+            RebalanceAction::Hold
+        );
+        assert_eq!(
+            strategy.evaluate(&create_context(&ranges[3], dec!(112))),
+            RebalanceAction::Hold
+        );
+    }
+}
@@ -4,8 +4,6 @@
 //! exceeds a specified threshold, protecting against excessive losses.
 
 use super::{RebalanceAction, RebalanceReason, RebalanceStrategy, StrategyContext};
-use clmm_lp_domain::value_objects::price::Price;
-use clmm_lp_domain::value_objects::price_range::PriceRange;
 use rust_decimal::Decimal;
 
 /// Strategy that triggers action when IL exceeds a threshold.
@@ -118,19 +116,13 @@ impl RebalanceStrategy for ILLimitStrategy {
     fn name(&self) -> &'static str {
         "IL Limit"
     }
-
-    fn calculate_new_range(&self, current_price: Price, range_width_pct: Decimal) -> PriceRange {
-        let half_width = current_price.value * range_width_pct / Decimal::from(2);
-        PriceRange::new(
-            Price::new(current_price.value - half_width),
-            Price::new(current_price.value + half_width),
-        )
-    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use clmm_lp_domain::value_objects::price::Price;
+    use clmm_lp_domain::value_objects::price_range::PriceRange;
     use rust_decimal_macros::dec;
 
     fn create_test_context() -> StrategyContext {
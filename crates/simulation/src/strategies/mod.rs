@@ -3,14 +3,18 @@
 //! This module provides different strategies for managing LP positions,
 //! including when and how to rebalance based on market conditions.
 
+mod grid;
 mod il_limit;
 mod periodic;
+mod scheduled;
 mod static_range;
 mod threshold;
 mod types;
 
+pub use grid::GridStrategy;
 pub use il_limit::ILLimitStrategy;
 pub use periodic::PeriodicRebalance;
+pub use scheduled::ScheduledRanges;
 pub use static_range::StaticRange;
 pub use threshold::ThresholdRebalance;
 pub use types::{RebalanceAction, RebalanceReason, RebalanceStrategy, StrategyContext};
@@ -0,0 +1,79 @@
+//! Scheduled range strategy - replays recorded range changes.
+//!
+//! This strategy moves the position to predetermined ranges at predetermined
+//! steps, reproducing the rebalances a live position actually made.
+
+use super::{RebalanceAction, RebalanceReason, RebalanceStrategy, StrategyContext};
+use clmm_lp_domain::value_objects::price_range::PriceRange;
+use std::collections::BTreeMap;
+
+/// Strategy that rebalances to recorded ranges at recorded steps.
+#[derive(Debug, Clone, Default)]
+pub struct ScheduledRanges {
+    /// New range keyed by the step it takes effect at.
+    changes: BTreeMap<u64, PriceRange>,
+}
+
+impl ScheduledRanges {
+    /// Creates a strategy from `(step, new range)` pairs.
+    ///
+    /// When several changes share a step, the last one wins.
+    #[must_use]
+    pub fn new(changes: impl IntoIterator<Item = (u64, PriceRange)>) -> Self {
+        Self {
+            changes: changes.into_iter().collect(),
+        }
+    }
+}
+
+impl RebalanceStrategy for ScheduledRanges {
+    fn evaluate(&self, context: &StrategyContext) -> RebalanceAction {
+        match self.changes.get(&context.steps_since_open) {
+            Some(range) => RebalanceAction::Rebalance {
+                new_range: *range,
+                reason: RebalanceReason::Manual,
+            },
+            None => RebalanceAction::Hold,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "Scheduled Ranges"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clmm_lp_domain::value_objects::price::Price;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_rebalances_at_recorded_steps() {
+        let range = PriceRange::new(Price::new(dec!(95)), Price::new(dec!(105)));
+        let strategy = ScheduledRanges::new(vec![(3, range)]);
+
+        let ctx = StrategyContext {
+            current_price: Price::new(dec!(100)),
+            current_range: PriceRange::new(Price::new(dec!(90)), Price::new(dec!(110))),
+            entry_price: Price::new(dec!(100)),
+            steps_since_open: 2,
+            steps_since_rebalance: 2,
+            current_il_pct: dec!(0),
+            total_fees_earned: dec!(0),
+        };
+        assert_eq!(strategy.evaluate(&ctx), RebalanceAction::Hold);
+
+        let ctx = StrategyContext {
+            steps_since_open: 3,
+            ..ctx
+        };
+        assert_eq!(
+            strategy.evaluate(&ctx),
+            RebalanceAction::Rebalance {
+                new_range: range,
+                reason: RebalanceReason::Manual,
+            }
+        );
+    }
+}
@@ -73,8 +73,7 @@ impl StrategyContext {
     /// Checks if the current price is within the position range.
     #[must_use]
     pub fn is_in_range(&self) -> bool {
-        self.current_price.value >= self.current_range.lower_price.value
-            && self.current_price.value <= self.current_range.upper_price.value
+        self.current_range.contains(self.current_price)
     }
 
     /// Calculates the price change percentage from entry.
@@ -89,13 +88,7 @@ impl StrategyContext {
     /// Calculates the price change percentage from range midpoint.
     #[must_use]
     pub fn price_change_from_midpoint(&self) -> Decimal {
-        let midpoint = (self.current_range.lower_price.value
-            + self.current_range.upper_price.value)
-            / Decimal::from(2);
-        if midpoint == Decimal::ZERO {
-            return Decimal::ZERO;
-        }
-        (self.current_price.value - midpoint) / midpoint
+        self.current_range.offset_from_midpoint(self.current_price)
     }
 }
 
@@ -109,11 +102,7 @@ pub trait RebalanceStrategy: Send + Sync {
 
     /// Calculates a new range centered around the current price.
     fn calculate_new_range(&self, current_price: Price, range_width_pct: Decimal) -> PriceRange {
-        let half_width = current_price.value * range_width_pct / Decimal::from(2);
-        PriceRange::new(
-            Price::new(current_price.value - half_width),
-            Price::new(current_price.value + half_width),
-        )
+        PriceRange::from_center_and_width(current_price, range_width_pct)
     }
 }
 
@@ -4,12 +4,14 @@
 //! allowing for dynamic position management during backtests.
 
 use crate::event::{EventLog, SimulationEvent};
+use crate::history::{CompactHistory, HistoryMode, HistoryRecorder};
 use crate::liquidity::LiquidityModel;
+use crate::position_simulator::RangeActivity;
 use crate::price_path::PricePathGenerator;
 use crate::state::{SimulationConfig, SimulationSummary};
 use crate::strategies::{RebalanceAction, RebalanceReason, RebalanceStrategy, StrategyContext};
 use crate::volume::VolumeModel;
-use clmm_lp_domain::metrics::impermanent_loss::calculate_il_concentrated;
+use clmm_lp_domain::metrics::impermanent_loss::ConcentratedIl;
 use clmm_lp_domain::value_objects::price::Price;
 use clmm_lp_domain::value_objects::price_range::PriceRange;
 use rust_decimal::Decimal;
@@ -22,15 +24,20 @@ pub struct StrategySimulationResult {
     pub summary: SimulationSummary,
     /// Event log from the simulation.
     pub events: Vec<SimulationEvent>,
-    /// Price path used.
+    /// Price path used; empty unless the history mode is full.
     pub prices: Vec<Price>,
-    /// Step-by-step PnL values.
+    /// Step-by-step PnL values; empty unless the history mode is full.
     pub pnl_history: Vec<Decimal>,
-    /// Step-by-step IL values.
+    /// Step-by-step IL values; empty unless the history mode is full.
     pub il_history: Vec<Decimal>,
-    /// Step-by-step fee values.
+    /// Step-by-step fee values; empty unless the history mode is full.
     pub fee_history: Vec<Decimal>,
-    /// Range history (step, range).
+    /// Step-by-step values as `f64`, kept in compact history mode.
+    pub compact_history: Option<CompactHistory>,
+    /// Unix timestamp of each step; empty without calendar time or in
+    /// summary-only history mode.
+    pub timestamps: Vec<u64>,
+    /// Range history (step, range); empty in summary-only history mode.
     pub range_history: Vec<(u64, PriceRange)>,
 }
 
@@ -56,7 +63,7 @@ where
     P: PricePathGenerator,
     V: VolumeModel,
     L: LiquidityModel,
-    S: RebalanceStrategy,
+    S: RebalanceStrategy + ?Sized,
 {
     let prices = price_path.generate(config.steps);
 
@@ -65,9 +72,10 @@ where
     }
 
     let entry_price = prices[0];
-    let mut current_range = config.initial_range.clone();
+    let mut current_range = config.initial_range;
+    let mut il = range_il(entry_price, &current_range);
 
-    let mut event_log = EventLog::new();
+    let mut event_log = EventLog::retaining(config.history).with_clock(config.clock.clone());
     let mut cumulative_fees = Decimal::ZERO;
     let mut steps_in_range: u64 = 0;
     let mut max_il = Decimal::ZERO;
@@ -76,52 +84,49 @@ where
     let mut rebalance_count: u32 = 0;
     let mut total_rebalance_cost = Decimal::ZERO;
     let mut steps_since_rebalance: u64 = 0;
+    let mut closed = false;
 
-    let mut pnl_history = Vec::with_capacity(prices.len());
-    let mut il_history = Vec::with_capacity(prices.len());
-    let mut fee_history = Vec::with_capacity(prices.len());
+    let mut history = HistoryRecorder::new(config.history, prices.len());
+    let keep_ranges = config.history != HistoryMode::SummaryOnly;
     let mut range_history = Vec::new();
 
-    let mut was_in_range = is_in_range(&entry_price, &current_range);
+    let mut activity = RangeActivity::open(current_range, entry_price);
+    let mut was_in_range = activity.is_active_at(entry_price);
 
     // Record initial range
-    range_history.push((0, current_range.clone()));
+    if keep_ranges {
+        range_history.push((0, current_range));
+    }
 
     // Record position opened
     event_log.record(SimulationEvent::position_opened(
         0,
         entry_price,
         config.initial_capital,
-        current_range.clone(),
+        current_range,
     ));
 
     for (step, price) in prices.iter().enumerate() {
-        let in_range = is_in_range(price, &current_range);
+        let in_range = activity.is_active_at(*price);
 
         // Track range transitions
         if in_range && !was_in_range {
             event_log.record(SimulationEvent::back_in_range(
                 step as u64,
                 *price,
-                current_range.clone(),
+                current_range,
             ));
         } else if !in_range && was_in_range {
             event_log.record(SimulationEvent::out_of_range(
                 step as u64,
                 *price,
-                current_range.clone(),
+                current_range,
             ));
         }
         was_in_range = in_range;
 
         // Calculate current IL for strategy context
-        let il_decimal = calculate_il_concentrated(
-            entry_price.value,
-            price.value,
-            current_range.lower_price.value,
-            current_range.upper_price.value,
-        )
-        .unwrap_or(Decimal::ZERO);
+        let il_decimal = il_at(il, *price);
 
         if il_decimal < max_il {
             max_il = il_decimal;
@@ -130,7 +135,7 @@ where
         // Build strategy context
         let context = StrategyContext {
             current_price: *price,
-            current_range: current_range.clone(),
+            current_range,
             entry_price,
             steps_since_open: step as u64,
             steps_since_rebalance,
@@ -143,25 +148,29 @@ where
 
         match &action {
             RebalanceAction::Rebalance { new_range, reason } => {
-                let old_range = current_range.clone();
-                current_range = new_range.clone();
+                let old_range = current_range;
+                current_range = *new_range;
+                il = range_il(entry_price, &current_range);
                 rebalance_count += 1;
                 total_rebalance_cost += config.rebalance_cost;
                 steps_since_rebalance = 0;
 
-                range_history.push((step as u64, current_range.clone()));
+                if keep_ranges {
+                    range_history.push((step as u64, current_range));
+                }
 
                 event_log.record(SimulationEvent::rebalance(
                     step as u64,
                     *price,
                     old_range,
-                    new_range.clone(),
+                    *new_range,
                     format_reason(reason),
                     config.rebalance_cost,
                 ));
 
                 // Update in_range status after rebalance
-                was_in_range = is_in_range(price, &current_range);
+                activity = RangeActivity::open(current_range, *price);
+                was_in_range = activity.is_active_at(*price);
             }
             RebalanceAction::Close { reason: _ } => {
                 // For close action, we stop earning fees but continue tracking
@@ -178,6 +187,7 @@ where
                         - total_rebalance_cost,
                 ));
                 // Position is closed, skip remaining steps
+                closed = true;
                 break;
             }
             RebalanceAction::Hold => {
@@ -186,7 +196,7 @@ where
         }
 
         // Calculate fees if in range
-        let in_range_now = is_in_range(price, &current_range);
+        let in_range_now = activity.is_active_at(*price);
         if in_range_now {
             steps_in_range += 1;
 
@@ -195,7 +205,7 @@ where
 
             let step_fees = if pool_liquidity > 0 {
                 let lp_share = Decimal::from(config.pool_liquidity) / Decimal::from(pool_liquidity);
-                volume * config.fee_rate * lp_share
+                volume * config.fee_model.rate_at(&prices, step) * lp_share
             } else {
                 Decimal::ZERO
             };
@@ -231,20 +241,12 @@ where
             max_drawdown = drawdown;
         }
 
-        pnl_history.push(net_pnl);
-        il_history.push(il_decimal);
-        fee_history.push(cumulative_fees);
+        history.record(*price, net_pnl, il_decimal, cumulative_fees);
     }
 
     let final_price = *prices.last().unwrap_or(&entry_price);
 
-    let final_il_decimal = calculate_il_concentrated(
-        entry_price.value,
-        final_price.value,
-        current_range.lower_price.value,
-        current_range.upper_price.value,
-    )
-    .unwrap_or(Decimal::ZERO);
+    let final_il_decimal = il_at(il, final_price);
 
     let final_price_ratio = if entry_price.value.is_zero() {
         1.0
@@ -269,11 +271,7 @@ where
     let vs_hodl = final_value - hodl_value;
 
     // Record position closed if not already closed
-    if !event_log
-        .events()
-        .iter()
-        .any(|e| e.event_type == crate::event::SimulationEventType::PositionClosed)
-    {
+    if !closed {
         event_log.record(SimulationEvent::position_closed(
             prices.len() as u64,
             final_price,
@@ -301,19 +299,42 @@ where
         max_drawdown_pct: max_drawdown,
         hodl_value,
         vs_hodl,
+        start_timestamp: config.clock.timestamp_at(0),
+        end_timestamp: config.clock.timestamp_at(prices.len() as u64 - 1),
     };
 
+    let histories = history.finish(prices, &config.clock);
     StrategySimulationResult {
         summary,
-        events: event_log.events().to_vec(),
-        prices,
-        pnl_history,
-        il_history,
-        fee_history,
+        events: event_log.into_events(),
+        prices: histories.prices,
+        pnl_history: histories.pnl,
+        il_history: histories.il,
+        fee_history: histories.fees,
+        compact_history: histories.compact,
+        timestamps: histories.timestamps,
         range_history,
     }
 }
 
+/// Builds the IL function of a range entered at `entry_price`.
+///
+/// Returns `None` for an invalid range, whose IL is taken as zero.
+fn range_il(entry_price: Price, range: &PriceRange) -> Option<ConcentratedIl> {
+    ConcentratedIl::new(
+        entry_price.value,
+        range.lower_price.value,
+        range.upper_price.value,
+    )
+    .ok()
+}
+
+/// Evaluates the IL at `price`, or zero if it cannot be computed.
+fn il_at(il: Option<ConcentratedIl>, price: Price) -> Decimal {
+    il.and_then(|il| il.at(price.value).ok())
+        .unwrap_or(Decimal::ZERO)
+}
+
 /// Formats a rebalance reason as a string.
 fn format_reason(reason: &RebalanceReason) -> String {
     match reason {
@@ -333,11 +354,6 @@ fn format_reason(reason: &RebalanceReason) -> String {
     }
 }
 
-/// Checks if a price is within a range.
-fn is_in_range(price: &Price, range: &PriceRange) -> bool {
-    price.value >= range.lower_price.value && price.value <= range.upper_price.value
-}
-
 /// Creates an empty result for edge cases.
 fn empty_result(config: &SimulationConfig) -> StrategySimulationResult {
     let entry_price = Price::new(Decimal::ZERO);
@@ -358,6 +374,8 @@ fn empty_result(config: &SimulationConfig) -> StrategySimulationResult {
         max_drawdown_pct: Decimal::ZERO,
         hodl_value: config.initial_capital,
         vs_hodl: Decimal::ZERO,
+        start_timestamp: None,
+        end_timestamp: None,
     };
 
     StrategySimulationResult {
@@ -367,6 +385,8 @@ fn empty_result(config: &SimulationConfig) -> StrategySimulationResult {
         pnl_history: Vec::new(),
         il_history: Vec::new(),
         fee_history: Vec::new(),
+        compact_history: None,
+        timestamps: Vec::new(),
         range_history: Vec::new(),
     }
 }
@@ -376,6 +396,7 @@ mod tests {
     use super::*;
     use crate::liquidity::ConstantLiquidity;
     use crate::price_path::DeterministicPricePath;
+    use crate::state::SimulationClock;
     use crate::strategies::{PeriodicRebalance, StaticRange, ThresholdRebalance};
     use crate::volume::ConstantVolume;
     use rust_decimal_macros::dec;
@@ -407,6 +428,78 @@ mod tests {
         assert!(result.summary.total_fees > Decimal::ZERO);
     }
 
+    #[test]
+    fn test_candle_timestamps_reach_events_histories_and_summary() {
+        let range = PriceRange::new(Price::new(dec!(95)), Price::new(dec!(105)));
+        // Hourly candles with a missing hour before the last
+        let timestamps = vec![1_700_000_000, 1_700_003_600, 1_700_007_200, 1_700_014_400];
+        let config = SimulationConfig::new(dec!(1000), range)
+            .with_steps(4)
+            .with_clock(SimulationClock::from_timestamps(timestamps.clone()));
+
+        let result = simulate_with_strategy(
+            &config,
+            &mut DeterministicPricePath::new(vec![dec!(100), dec!(101), dec!(110), dec!(104)]),
+            &mut ConstantVolume::new(dec!(10000)),
+            &ConstantLiquidity::new(1_000_000),
+            &StaticRange,
+        );
+
+        assert_eq!(result.timestamps, timestamps);
+        assert!(result.events.iter().all(|e| e.timestamp.is_some()));
+        let out_of_range = result
+            .events
+            .iter()
+            .find(|e| e.event_type == crate::event::SimulationEventType::OutOfRange)
+            .unwrap();
+        assert_eq!(out_of_range.timestamp, Some(1_700_007_200));
+        assert_eq!(result.summary.start_timestamp, Some(1_700_000_000));
+        assert_eq!(result.summary.end_timestamp, Some(1_700_014_400));
+        assert!((result.summary.duration_days() - 1.0 / 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_history_modes_keep_the_same_summary() {
+        let range = PriceRange::new(Price::new(dec!(95)), Price::new(dec!(105)));
+        let run = |mode| {
+            let config = SimulationConfig::new(dec!(1000), range)
+                .with_steps(5)
+                .with_history(mode);
+            let prices = vec![dec!(100), dec!(101), dec!(110), dec!(110), dec!(104)];
+            simulate_with_strategy(
+                &config,
+                &mut DeterministicPricePath::new(prices),
+                &mut ConstantVolume::new(dec!(10000)),
+                &ConstantLiquidity::new(1_000_000),
+                &ThresholdRebalance::new(dec!(0.05), dec!(0.10)),
+            )
+        };
+        let full = run(HistoryMode::Full);
+        let compact = run(HistoryMode::Compact);
+        let summary = run(HistoryMode::SummaryOnly);
+
+        for other in [&compact, &summary] {
+            assert_eq!(other.summary.net_pnl, full.summary.net_pnl);
+            assert_eq!(other.summary.rebalance_count, full.summary.rebalance_count);
+            assert!(other.pnl_history.is_empty() && other.prices.is_empty());
+        }
+
+        let series = compact.compact_history.unwrap();
+        assert_eq!(series.len(), full.pnl_history.len());
+        assert_eq!(series.pnl[2], full.pnl_history[2].to_f64().unwrap());
+        assert_eq!(compact.range_history, full.range_history);
+        assert!(compact.events.len() < full.events.len());
+        assert!(
+            compact
+                .events
+                .iter()
+                .all(|e| e.event_type != crate::event::SimulationEventType::FeeCollection)
+        );
+
+        assert!(summary.compact_history.is_none());
+        assert!(summary.events.is_empty() && summary.range_history.is_empty());
+    }
+
     #[test]
     fn test_simulate_with_periodic_strategy() {
         let range = PriceRange::new(Price::new(dec!(90)), Price::new(dec!(110)));
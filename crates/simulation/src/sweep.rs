@@ -0,0 +1,481 @@
+//! Backtests of strategy parameter grids over historical prices.
+//!
+//! Every point of a grid is backtested on the same price series and ranked
+//! by score. Minute-level series make a full sweep slow, so the two-pass
+//! mode scans the grid on the series resampled to a coarser resolution and
+//! re-verifies only the best candidates on the original one.
+
+use crate::history::HistoryMode;
+use crate::liquidity::ConstantLiquidity;
+use crate::price_path::DeterministicPricePath;
+use crate::state::{SimulationClock, SimulationConfig, SimulationSummary};
+use crate::strategies::{PeriodicRebalance, StaticRange, ThresholdRebalance};
+use crate::strategy_simulator::simulate_with_strategy;
+use crate::volume::HistoricalVolume;
+use clmm_lp_domain::value_objects::price::Price;
+use clmm_lp_domain::value_objects::price_range::PriceRange;
+use rayon::prelude::*;
+use rust_decimal::Decimal;
+
+/// A point of a parameter grid.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SweepParams {
+    /// Never rebalance.
+    Static {
+        /// Total range width (0.2 = 20%).
+        width_pct: Decimal,
+    },
+    /// Rebalance at a fixed interval.
+    Periodic {
+        /// Total range width (0.2 = 20%).
+        width_pct: Decimal,
+        /// Hours between rebalances, so the interval means the same at any
+        /// resolution.
+        interval_hours: u64,
+    },
+    /// Rebalance when price moves away from the range center.
+    Threshold {
+        /// Total range width (0.2 = 20%).
+        width_pct: Decimal,
+        /// Price move that triggers a rebalance (0.05 = 5%).
+        threshold_pct: Decimal,
+    },
+}
+
+impl SweepParams {
+    /// Builds the grid of every static range in `widths`, and every
+    /// combination of a width with an interval or a threshold.
+    #[must_use]
+    pub fn grid(widths: &[Decimal], intervals_hours: &[u64], thresholds: &[Decimal]) -> Vec<Self> {
+        widths
+            .iter()
+            .flat_map(|&width_pct| {
+                std::iter::once(Self::Static { width_pct })
+                    .chain(
+                        intervals_hours
+                            .iter()
+                            .map(move |&interval_hours| Self::Periodic {
+                                width_pct,
+                                interval_hours,
+                            }),
+                    )
+                    .chain(
+                        thresholds
+                            .iter()
+                            .map(move |&threshold_pct| Self::Threshold {
+                                width_pct,
+                                threshold_pct,
+                            }),
+                    )
+            })
+            .collect()
+    }
+
+    /// Returns the total range width.
+    #[must_use]
+    pub fn width_pct(&self) -> Decimal {
+        match self {
+            Self::Static { width_pct }
+            | Self::Periodic { width_pct, .. }
+            | Self::Threshold { width_pct, .. } => *width_pct,
+        }
+    }
+}
+
+/// A price series at a fixed resolution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepSeries {
+    /// Seconds between steps.
+    pub step_seconds: u64,
+    /// Unix timestamp of each step.
+    pub timestamps: Vec<u64>,
+    /// Closing price of each step.
+    pub prices: Vec<Price>,
+    /// Traded volume in each step.
+    pub volumes: Vec<Decimal>,
+}
+
+impl SweepSeries {
+    /// Creates a series from `(timestamp, close, volume)` points ordered
+    /// oldest first, `step_seconds` apart.
+    #[must_use]
+    pub fn new(step_seconds: u64, points: &[(u64, Price, Decimal)]) -> Self {
+        Self {
+            step_seconds: step_seconds.max(1),
+            timestamps: points.iter().map(|(t, _, _)| *t).collect(),
+            prices: points.iter().map(|(_, p, _)| *p).collect(),
+            volumes: points.iter().map(|(_, _, v)| *v).collect(),
+        }
+    }
+
+    /// Returns the number of steps.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.prices.len()
+    }
+
+    /// Returns true if the series has no steps.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.prices.is_empty()
+    }
+
+    /// Aggregates the series into steps of `step_seconds`, aligned to
+    /// multiples of it: each step keeps the last close and the summed
+    /// volume of the points it covers.
+    ///
+    /// Resampling to the same or a finer resolution returns a copy.
+    #[must_use]
+    pub fn resample(&self, step_seconds: u64) -> Self {
+        if step_seconds <= self.step_seconds {
+            return self.clone();
+        }
+
+        let mut resampled = Self {
+            step_seconds,
+            timestamps: Vec::new(),
+            prices: Vec::new(),
+            volumes: Vec::new(),
+        };
+        for ((timestamp, price), volume) in
+            self.timestamps.iter().zip(&self.prices).zip(&self.volumes)
+        {
+            let bucket = timestamp - timestamp % step_seconds;
+            if resampled.timestamps.last() == Some(&bucket) {
+                if let Some(close) = resampled.prices.last_mut() {
+                    *close = *price;
+                }
+                if let Some(total) = resampled.volumes.last_mut() {
+                    *total += *volume;
+                }
+            } else {
+                resampled.timestamps.push(bucket);
+                resampled.prices.push(*price);
+                resampled.volumes.push(*volume);
+            }
+        }
+        resampled
+    }
+}
+
+/// How a sweep backtests its grid.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SweepMode {
+    /// Backtest every point on the series as given.
+    #[default]
+    SinglePass,
+    /// Scan the grid on the series resampled to `coarse_step_seconds`, then
+    /// re-verify the `top_n` best points on the series as given.
+    TwoPass {
+        /// Resolution of the scan, e.g. 3600 for hourly.
+        coarse_step_seconds: u64,
+        /// Number of candidates re-verified at full resolution.
+        top_n: usize,
+    },
+}
+
+/// Result of backtesting one point of the grid.
+#[derive(Debug, Clone)]
+pub struct SweepCandidate {
+    /// Parameters backtested.
+    pub params: SweepParams,
+    /// Summary at the resolution the candidate was last backtested at.
+    pub summary: SimulationSummary,
+    /// Score of `summary`.
+    pub score: Decimal,
+    /// Score in the coarse scan, for candidates re-verified in two-pass
+    /// mode.
+    pub coarse_score: Option<Decimal>,
+    /// Whether the candidate was backtested at full resolution.
+    pub verified: bool,
+}
+
+/// Candidates of a sweep, best first.
+#[derive(Debug, Clone)]
+pub struct SweepReport {
+    /// Candidates verified at full resolution, best first, followed by the
+    /// remaining coarse scan results.
+    pub candidates: Vec<SweepCandidate>,
+    /// Backtests run on the coarse series.
+    pub coarse_runs: usize,
+    /// Backtests run on the full resolution series.
+    pub fine_runs: usize,
+    /// Simulated steps across all backtests.
+    pub simulated_steps: u64,
+}
+
+impl SweepReport {
+    /// Returns the best verified candidate.
+    #[must_use]
+    pub fn best(&self) -> Option<&SweepCandidate> {
+        self.candidates.first().filter(|c| c.verified)
+    }
+}
+
+/// Backtests a grid of strategy parameters on a price series.
+#[derive(Debug, Clone)]
+pub struct SweepEngine {
+    /// Capital, fee rate, position liquidity and rebalance cost of each
+    /// backtest; range, steps and clock come from the grid and series.
+    config: SimulationConfig,
+    /// Points of the grid.
+    grid: Vec<SweepParams>,
+    /// Pool liquidity the position's share of volume is taken against.
+    market_liquidity: u128,
+    /// How the grid is backtested.
+    mode: SweepMode,
+    /// Scores a backtest; higher is better.
+    score: fn(&SimulationSummary) -> Decimal,
+}
+
+impl SweepEngine {
+    /// Creates an engine ranking `grid` by net PnL in a single pass.
+    ///
+    /// The position is credited all traded volume until a market
+    /// liquidity is set.
+    #[must_use]
+    pub fn new(config: SimulationConfig, grid: Vec<SweepParams>) -> Self {
+        Self {
+            market_liquidity: config.pool_liquidity,
+            config,
+            grid,
+            mode: SweepMode::SinglePass,
+            score: |summary| summary.net_pnl,
+        }
+    }
+
+    /// Sets how the grid is backtested.
+    #[must_use]
+    pub fn with_mode(mut self, mode: SweepMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the pool liquidity the position's share of volume is taken
+    /// against.
+    #[must_use]
+    pub fn with_market_liquidity(mut self, liquidity: u128) -> Self {
+        self.market_liquidity = liquidity;
+        self
+    }
+
+    /// Ranks backtests by `score` instead of net PnL.
+    #[must_use]
+    pub fn with_score(mut self, score: fn(&SimulationSummary) -> Decimal) -> Self {
+        self.score = score;
+        self
+    }
+
+    /// Backtests the grid on `series` and ranks the candidates.
+    ///
+    /// In two-pass mode a coarse resolution no coarser than the series
+    /// falls back to a single pass.
+    #[must_use]
+    pub fn run(&self, series: &SweepSeries) -> SweepReport {
+        let (coarse_step_seconds, top_n) = match self.mode {
+            SweepMode::TwoPass {
+                coarse_step_seconds,
+                top_n,
+            } if coarse_step_seconds > series.step_seconds => (coarse_step_seconds, top_n),
+            _ => {
+                let candidates = self.backtest_all(&self.grid, series);
+                return SweepReport {
+                    fine_runs: candidates.len(),
+                    coarse_runs: 0,
+                    simulated_steps: (candidates.len() * series.len()) as u64,
+                    candidates,
+                };
+            }
+        };
+
+        let coarse = series.resample(coarse_step_seconds);
+        let mut scanned = self.backtest_all(&self.grid, &coarse);
+        let mut rest = scanned.split_off(top_n.min(scanned.len()));
+        for candidate in &mut rest {
+            candidate.verified = false;
+        }
+
+        let finalists: Vec<SweepParams> = scanned.iter().map(|c| c.params.clone()).collect();
+        let mut verified = self.backtest_all(&finalists, series);
+        for candidate in &mut verified {
+            candidate.coarse_score = scanned
+                .iter()
+                .find(|c| c.params == candidate.params)
+                .map(|c| c.score);
+        }
+
+        SweepReport {
+            coarse_runs: self.grid.len(),
+            fine_runs: verified.len(),
+            simulated_steps: (self.grid.len() * coarse.len() + verified.len() * series.len())
+                as u64,
+            candidates: verified.into_iter().chain(rest).collect(),
+        }
+    }
+
+    /// Backtests each of `params` on `series`, best first.
+    fn backtest_all(&self, params: &[SweepParams], series: &SweepSeries) -> Vec<SweepCandidate> {
+        let mut candidates: Vec<SweepCandidate> = params
+            .par_iter()
+            .map(|params| {
+                let summary = self.backtest(params, series);
+                SweepCandidate {
+                    params: params.clone(),
+                    score: (self.score)(&summary),
+                    summary,
+                    coarse_score: None,
+                    verified: true,
+                }
+            })
+            .collect();
+        candidates.sort_by_key(|c| std::cmp::Reverse(c.score));
+        candidates
+    }
+
+    /// Backtests one point of the grid on `series`.
+    fn backtest(&self, params: &SweepParams, series: &SweepSeries) -> SimulationSummary {
+        let entry = series
+            .prices
+            .first()
+            .copied()
+            .unwrap_or(Price::new(Decimal::ONE));
+        let width_pct = params.width_pct();
+        let config = self
+            .config
+            .clone()
+            .with_initial_range(PriceRange::from_center_and_width(entry, width_pct))
+            .with_steps(series.len())
+            .with_step_duration(series.step_seconds)
+            .with_clock(SimulationClock::from_timestamps(series.timestamps.clone()))
+            .with_history(HistoryMode::SummaryOnly);
+
+        let mut price_path = DeterministicPricePath::from_prices(series.prices.clone());
+        let mut volume = HistoricalVolume::new(series.volumes.clone());
+        let liquidity = ConstantLiquidity::new(self.market_liquidity);
+
+        let result = match params {
+            SweepParams::Static { .. } => simulate_with_strategy(
+                &config,
+                &mut price_path,
+                &mut volume,
+                &liquidity,
+                &StaticRange,
+            ),
+            SweepParams::Periodic { interval_hours, .. } => {
+                let interval = (interval_hours * 3600 / series.step_seconds).max(1);
+                simulate_with_strategy(
+                    &config,
+                    &mut price_path,
+                    &mut volume,
+                    &liquidity,
+                    &PeriodicRebalance::new(interval, width_pct),
+                )
+            }
+            SweepParams::Threshold { threshold_pct, .. } => simulate_with_strategy(
+                &config,
+                &mut price_path,
+                &mut volume,
+                &liquidity,
+                &ThresholdRebalance::new(*threshold_pct, width_pct),
+            ),
+        };
+        result.summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    /// Minute series oscillating around 100, with a constant volume.
+    fn minute_series(hours: u64) -> SweepSeries {
+        let points: Vec<(u64, Price, Decimal)> = (0..hours * 60)
+            .map(|minute| {
+                let swing = Decimal::from((minute % 240) as i64 - 120) / dec!(20);
+                (minute * 60, Price::new(dec!(100) + swing), dec!(100))
+            })
+            .collect();
+        SweepSeries::new(60, &points)
+    }
+
+    #[test]
+    fn test_resample_keeps_last_close_and_sums_volume() {
+        let points = [
+            (0, Price::new(dec!(1)), dec!(1)),
+            (60, Price::new(dec!(2)), dec!(2)),
+            (3600, Price::new(dec!(3)), dec!(4)),
+            (3660, Price::new(dec!(4)), dec!(8)),
+        ];
+        let hourly = SweepSeries::new(60, &points).resample(3600);
+
+        assert_eq!(hourly.timestamps, vec![0, 3600]);
+        assert_eq!(
+            hourly.prices,
+            vec![Price::new(dec!(2)), Price::new(dec!(4))]
+        );
+        assert_eq!(hourly.volumes, vec![dec!(3), dec!(12)]);
+    }
+
+    #[test]
+    fn test_two_pass_verifies_top_candidates_at_full_resolution() {
+        let config = SimulationConfig::new(
+            dec!(1000),
+            PriceRange::from_center_and_width(Price::new(dec!(100)), dec!(0.1)),
+        );
+        let grid = SweepParams::grid(
+            &[dec!(0.02), dec!(0.05), dec!(0.2)],
+            &[6, 24],
+            &[dec!(0.01), dec!(0.05)],
+        );
+        assert_eq!(grid.len(), 15);
+        let series = minute_series(48);
+
+        let full = SweepEngine::new(config.clone(), grid.clone()).run(&series);
+        let two_pass = SweepEngine::new(config, grid)
+            .with_mode(SweepMode::TwoPass {
+                coarse_step_seconds: 3600,
+                top_n: 3,
+            })
+            .run(&series);
+
+        assert_eq!(full.fine_runs, 15);
+        assert_eq!(two_pass.coarse_runs, 15);
+        assert_eq!(two_pass.fine_runs, 3);
+        assert_eq!(two_pass.candidates.len(), 15);
+        assert!(two_pass.simulated_steps * 4 < full.simulated_steps);
+
+        let verified: Vec<_> = two_pass.candidates.iter().filter(|c| c.verified).collect();
+        assert_eq!(verified.len(), 3);
+        assert!(verified.iter().all(|c| c.coarse_score.is_some()));
+        // Verified scores match a full-resolution backtest of the same point
+        let best = two_pass.best().unwrap();
+        let same = full
+            .candidates
+            .iter()
+            .find(|c| c.params == best.params)
+            .unwrap();
+        assert_eq!(best.score, same.score);
+        assert_eq!(best.summary.total_steps, series.len() as u64);
+    }
+
+    #[test]
+    fn test_two_pass_without_coarser_resolution_is_single_pass() {
+        let config = SimulationConfig::new(
+            dec!(1000),
+            PriceRange::from_center_and_width(Price::new(dec!(100)), dec!(0.1)),
+        );
+        let series = minute_series(2).resample(3600);
+
+        let report = SweepEngine::new(config, SweepParams::grid(&[dec!(0.1)], &[1], &[]))
+            .with_mode(SweepMode::TwoPass {
+                coarse_step_seconds: 3600,
+                top_n: 1,
+            })
+            .run(&series);
+
+        assert_eq!(report.coarse_runs, 0);
+        assert_eq!(report.fine_runs, 2);
+        assert!(report.candidates.iter().all(|c| c.verified));
+    }
+}
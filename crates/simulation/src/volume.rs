@@ -49,4 +49,223 @@ impl VolumeModel for ConstantVolume {
     }
 }
 
-// Could add StochasticVolume later
+/// Volume model replaying observed per-step volumes.
+///
+/// Steps past the end of the history have no volume.
+#[derive(Debug, Clone)]
+pub struct HistoricalVolume {
+    /// Volume of each step.
+    volumes: Vec<Decimal>,
+    /// Next step returned by `next_volume`.
+    current_step: usize,
+}
+
+impl HistoricalVolume {
+    /// Creates a model from per-step volumes.
+    #[must_use]
+    pub fn new(volumes: Vec<Decimal>) -> Self {
+        Self {
+            volumes,
+            current_step: 0,
+        }
+    }
+}
+
+impl VolumeModel for HistoricalVolume {
+    fn next_volume(&mut self) -> Amount {
+        let volume = self.get_volume(self.current_step);
+        self.current_step += 1;
+        Amount::from_decimal(volume, 6)
+    }
+
+    fn get_volume(&mut self, step: usize) -> Decimal {
+        self.volumes.get(step).copied().unwrap_or_default()
+    }
+}
+
+/// Hours in a day.
+const HOURS_PER_DAY: usize = 24;
+/// Days in a week.
+const DAYS_PER_WEEK: usize = 7;
+/// Hours in a week.
+const HOURS_PER_WEEK: usize = HOURS_PER_DAY * DAYS_PER_WEEK;
+
+/// Volume model with hour-of-day and day-of-week seasonality.
+///
+/// Each hour of the week has a multiplicative factor relative to the mean
+/// hourly volume. A step spanning several hours sums the factors of the hours
+/// it covers, so daily steps only see the day-of-week pattern.
+#[derive(Debug, Clone)]
+pub struct SeasonalVolumeModel {
+    /// Mean volume per hour.
+    pub base_hourly_volume: Decimal,
+    /// Factors indexed by `day_of_week * 24 + hour` (Monday = 0, UTC).
+    pub factors: [Decimal; HOURS_PER_WEEK],
+    /// Hour of the week at step 0.
+    pub start_hour: usize,
+    /// Hours covered by each step.
+    pub step_hours: usize,
+    /// Next step returned by `next_volume`.
+    current_step: usize,
+}
+
+impl SeasonalVolumeModel {
+    /// Creates a model with no seasonality.
+    #[must_use]
+    pub fn flat(base_hourly_volume: Decimal, step_hours: usize) -> Self {
+        Self {
+            base_hourly_volume,
+            factors: [Decimal::ONE; HOURS_PER_WEEK],
+            start_hour: 0,
+            step_hours: step_hours.max(1),
+            current_step: 0,
+        }
+    }
+
+    /// Fits factors from historical hourly volume.
+    ///
+    /// # Arguments
+    /// * `samples` - `(unix timestamp, volume)` pairs, one per hour
+    /// * `step_hours` - Hours covered by each simulation step
+    ///
+    /// Hours of the week without samples get a factor of one.
+    #[must_use]
+    pub fn fit(samples: &[(u64, Decimal)], step_hours: usize) -> Self {
+        let mut sums = [Decimal::ZERO; HOURS_PER_WEEK];
+        let mut counts = [0u32; HOURS_PER_WEEK];
+        for (timestamp, volume) in samples {
+            let hour = hour_of_week(*timestamp);
+            sums[hour] += *volume;
+            counts[hour] += 1;
+        }
+
+        let total: Decimal = samples.iter().map(|(_, v)| *v).sum();
+        let mut model = Self::flat(Decimal::ZERO, step_hours);
+        if samples.is_empty() || total <= Decimal::ZERO {
+            return model;
+        }
+
+        let mean = total / Decimal::from(samples.len());
+        model.base_hourly_volume = mean;
+        for hour in 0..HOURS_PER_WEEK {
+            if counts[hour] > 0 {
+                model.factors[hour] = sums[hour] / Decimal::from(counts[hour]) / mean;
+            }
+        }
+        model
+    }
+
+    /// Sets the time of step 0.
+    #[must_use]
+    pub fn starting_at(mut self, timestamp: u64) -> Self {
+        self.start_hour = hour_of_week(timestamp);
+        self
+    }
+
+    /// Rescales the model so an average step has the given volume.
+    ///
+    /// Keeps the fitted seasonal shape while taking the level from elsewhere.
+    #[must_use]
+    pub fn with_mean_step_volume(mut self, volume: Decimal) -> Self {
+        self.base_hourly_volume = volume / Decimal::from(self.step_hours);
+        self
+    }
+
+    /// Returns the factor for an hour of the week (Monday 00:00 UTC = 0).
+    #[must_use]
+    pub fn factor(&self, hour_of_week: usize) -> Decimal {
+        self.factors[hour_of_week % HOURS_PER_WEEK]
+    }
+
+    /// Returns the (peak, trough) hourly factors.
+    #[must_use]
+    pub fn factor_range(&self) -> (Decimal, Decimal) {
+        let peak = self.factors.iter().copied().max().unwrap_or(Decimal::ONE);
+        let trough = self.factors.iter().copied().min().unwrap_or(Decimal::ONE);
+        (peak, trough)
+    }
+
+    /// Returns the expected volume of a step.
+    #[must_use]
+    pub fn volume_at(&self, step: usize) -> Decimal {
+        let first_hour = self.start_hour + step * self.step_hours;
+        let factor_sum: Decimal = (first_hour..first_hour + self.step_hours)
+            .map(|hour| self.factor(hour))
+            .sum();
+        self.base_hourly_volume * factor_sum
+    }
+}
+
+impl VolumeModel for SeasonalVolumeModel {
+    fn next_volume(&mut self) -> Amount {
+        let volume = self.volume_at(self.current_step);
+        self.current_step += 1;
+        Amount::from_decimal(volume, 6)
+    }
+
+    fn get_volume(&mut self, step: usize) -> Decimal {
+        self.volume_at(step)
+    }
+}
+
+/// Returns the hour of the week (Monday 00:00 UTC = 0) of a unix timestamp.
+fn hour_of_week(timestamp: u64) -> usize {
+    let hours = (timestamp / 3600) as usize;
+    // 1970-01-01 was a Thursday, three days after Monday
+    (hours + 3 * HOURS_PER_DAY) % HOURS_PER_WEEK
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Monday 2024-01-01 00:00 UTC
+    const MONDAY: u64 = 1_704_067_200;
+
+    #[test]
+    fn test_hour_of_week() {
+        assert_eq!(hour_of_week(MONDAY), 0);
+        assert_eq!(hour_of_week(MONDAY + 3600 * 13), 13);
+        assert_eq!(
+            hour_of_week(MONDAY + 86_400 * 6 + 3600 * 23),
+            HOURS_PER_WEEK - 1
+        );
+        assert_eq!(hour_of_week(MONDAY + 86_400 * 7), 0);
+    }
+
+    #[test]
+    fn test_fit_seasonal_factors() {
+        // Two weeks where 14:00-15:00 UTC trades 25x the rest of the day
+        let samples: Vec<(u64, Decimal)> = (0..HOURS_PER_WEEK as u64 * 2)
+            .map(|h| {
+                let volume = if h % 24 == 14 { 1250 } else { 50 };
+                (MONDAY + h * 3600, Decimal::from(volume))
+            })
+            .collect();
+
+        let mut model = SeasonalVolumeModel::fit(&samples, 1).starting_at(MONDAY + 3600 * 13);
+        let (peak, trough) = model.factor_range();
+        assert!(peak > Decimal::from(3));
+        assert!(trough < Decimal::ONE);
+
+        // Step 0 is 13:00, step 1 is the 14:00 peak
+        let off_peak = model.next_volume().to_decimal();
+        let peak_volume = model.next_volume().to_decimal();
+        assert_eq!(off_peak, Decimal::from(50));
+        assert_eq!(peak_volume, Decimal::from(1250));
+
+        // Daily steps average out the intraday pattern
+        let daily = SeasonalVolumeModel::fit(&samples, 24).starting_at(MONDAY);
+        assert_eq!(daily.volume_at(0), Decimal::from(2400));
+        assert_eq!(
+            daily.with_mean_step_volume(Decimal::from(24)).volume_at(3),
+            Decimal::from(24)
+        );
+    }
+
+    #[test]
+    fn test_fit_without_volume_is_flat() {
+        let model = SeasonalVolumeModel::fit(&[(MONDAY, Decimal::ZERO)], 1);
+        assert_eq!(model.factor_range(), (Decimal::ONE, Decimal::ONE));
+    }
+}
@@ -12,6 +12,26 @@ export interface PositionUpdate {
   }
 }
 
+export interface PnLAttribution {
+  price_exposure_usd: string
+  il_usd: string
+  fees_usd: string
+}
+
+// Sent on /ws/positions whenever a position's PnL attribution changes;
+// deltas are null on the first update for a position
+export interface PnLUpdate {
+  update_type: 'pnl'
+  position_address: string
+  timestamp: string
+  data: {
+    net_pnl_usd: string
+    attribution: PnLAttribution
+    net_pnl_delta_usd: string | null
+    delta: PnLAttribution | null
+  }
+}
+
 export interface AlertUpdate {
   type: 'alert'
   level: 'info' | 'warning' | 'critical'
@@ -20,7 +40,7 @@ export interface AlertUpdate {
   timestamp: string
 }
 
-export type WebSocketMessage = PositionUpdate | AlertUpdate
+export type WebSocketMessage = PositionUpdate | PnLUpdate | AlertUpdate
 
 type MessageHandler = (message: WebSocketMessage) => void
 